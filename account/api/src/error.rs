@@ -9,6 +9,8 @@ pub enum AccountError {
     AccountNotExist(AccountAddress),
     #[error("account with address {0} already exists")]
     AccountAlreadyExist(AccountAddress),
+    #[error("account {0} has a pending key of an unconfirmed rotation, replace it explicitly")]
+    PendingKeyExists(AccountAddress),
     #[error("account {0} is locked")]
     AccountLocked(AccountAddress),
     #[error("cannot remove default account {0}")]
@@ -18,6 +20,16 @@ pub enum AccountError {
     InvalidPassword(AccountAddress),
     #[error("invalid private key")]
     InvalidPrivateKey,
+    #[error("invalid public key")]
+    InvalidPublicKey,
+    #[error("account {0} is read-only, it has no private key in wallet")]
+    AccountReadOnly(AccountAddress),
+    #[error("invalid keystore, {0}")]
+    InvalidKeyStore(String),
+    #[error("invalid signer endpoint {0}, it should be http(s)://... or unix://<socket path>")]
+    InvalidSignerEndpoint(String),
+    #[error("invalid mnemonic, {0}")]
+    InvalidMnemonic(String),
 
     // logic error
     #[error("transaction sign error, {0:?}")]
@@ -26,6 +38,10 @@ pub enum AccountError {
     // DecryptPrivateKeyError(anyhow::Error),
     #[error("no private key data associate with address {0}")]
     AccountPrivateKeyMissing(AccountAddress),
+    #[error("the external signer of account {0} does not respond in time")]
+    ExternalSignerTimeout(AccountAddress),
+    #[error("the external signer of account {0} fails, {1}")]
+    ExternalSignerError(AccountAddress, String),
     #[error("account vault store error, {0:?}")]
     StoreError(#[from] anyhow::Error),
 }
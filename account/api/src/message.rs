@@ -1,13 +1,15 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::AccountInfo;
+use crate::{AccountInfo, DerivedAccount, SignerEndpoint, TxnHistoryEntry};
 use anyhow::Result;
 use starcoin_service_registry::ServiceRequest;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::token_code::TokenCode;
 use starcoin_types::sign_message::SigningMessage;
-use starcoin_types::transaction::authenticator::AccountSignature;
+use starcoin_types::transaction::authenticator::{
+    AccountPublicKey, AccountSignature, AuthenticationKey,
+};
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
 use std::time::Duration;
 
@@ -31,6 +33,8 @@ pub enum AccountRequest {
     },
     UnlockAccount(AccountAddress, String, Duration),
     LockAccount(AccountAddress),
+    /// Lock all the unlocked accounts.
+    LockAllAccounts,
     ImportAccount {
         address: AccountAddress,
         private_key: Vec<u8>,
@@ -40,10 +44,64 @@ pub enum AccountRequest {
         address: AccountAddress,
         password: String,
     },
+    ImportReadonlyAccount {
+        address: AccountAddress,
+        public_key: Vec<u8>,
+    },
+    ImportKeyStore {
+        keystore: String,
+        password: String,
+    },
+    ExportKeyStore {
+        address: AccountAddress,
+        password: String,
+    },
     ChangePassword {
         address: AccountAddress,
+        /// None to use the password of the unlocked account.
+        old_password: Option<String>,
         new_password: String,
     },
+    RemoveAccount {
+        address: AccountAddress,
+        password: String,
+        /// Remove the account even if it is the default account.
+        force: bool,
+    },
+    PrepareRotateKey {
+        address: AccountAddress,
+        private_key: Option<Vec<u8>>,
+        dry_run: bool,
+        /// Replace the pending key of an unconfirmed rotation.
+        replace: bool,
+    },
+    CommitRotateKey {
+        address: AccountAddress,
+        new_auth_key: AuthenticationKey,
+    },
+    AbortRotateKey(AccountAddress),
+    /// Sign the txns of `address` by the external signer at `endpoint` instead of the wallet.
+    BindExternalSigner {
+        address: AccountAddress,
+        endpoint: SignerEndpoint,
+    },
+    GetSignerEndpoint(AccountAddress),
+    GenerateMnemonic(u32),
+    /// Import the account derived from the mnemonic at the derivation index.
+    ImportFromMnemonic {
+        mnemonic: String,
+        index: u32,
+        password: String,
+    },
+    DeriveAccount {
+        mnemonic: String,
+        index: u32,
+    },
+    TxnHistory {
+        address: AccountAddress,
+        start: u64,
+        limit: u64,
+    },
 }
 
 impl ServiceRequest for AccountRequest {
@@ -57,8 +115,15 @@ pub enum AccountResponse {
     AccountList(Vec<AccountInfo>),
     SignedTxn(Box<SignedUserTransaction>),
     UnlockAccountResponse,
+    AccountAddressList(Vec<AccountAddress>),
     ExportAccountResponse(Vec<u8>),
+    ExportKeyStoreResponse(String),
     AcceptedTokens(Vec<TokenCode>),
     MessageSignature(Box<AccountSignature>),
+    PublicKey(Box<AccountPublicKey>),
+    SignerEndpoint(Option<SignerEndpoint>),
+    Mnemonic(String),
+    DerivedAccount(Box<DerivedAccount>),
+    TxnHistory(Vec<TxnHistoryEntry>),
     None,
 }
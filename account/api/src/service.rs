@@ -2,14 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::message::{AccountRequest, AccountResponse};
-use crate::AccountInfo;
+use crate::{AccountInfo, DerivedAccount, SignerEndpoint, TxnHistoryEntry};
 use anyhow::Result;
 use starcoin_crypto::multi_ed25519::MultiEd25519Signature;
 use starcoin_service_registry::{ActorService, ServiceHandler, ServiceRef};
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::token_code::TokenCode;
 use starcoin_types::sign_message::SigningMessage;
-use starcoin_types::transaction::authenticator::AccountSignature;
+use starcoin_types::transaction::authenticator::{
+    AccountPublicKey, AccountSignature, AuthenticationKey,
+};
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
 
 #[async_trait::async_trait]
@@ -43,6 +45,8 @@ pub trait AccountAsyncService:
         duration: std::time::Duration,
     ) -> Result<()>;
     async fn lock_account(&self, address: AccountAddress) -> Result<()>;
+    /// Lock all the unlocked accounts, return the locked accounts.
+    async fn lock_all_accounts(&self) -> Result<Vec<AccountAddress>>;
     async fn import_account(
         &self,
         address: AccountAddress,
@@ -50,17 +54,92 @@ pub trait AccountAsyncService:
         password: String,
     ) -> Result<AccountInfo>;
 
+    /// Import a watch-only account with public key, the account can not sign anything.
+    async fn import_readonly_account(
+        &self,
+        address: AccountAddress,
+        public_key: Vec<u8>,
+    ) -> Result<AccountInfo>;
+
     /// Return the private key as bytes for `address`
     async fn export_account(&self, address: AccountAddress, password: String) -> Result<Vec<u8>>;
 
+    /// Import account from the keystore json encrypted by `password`.
+    async fn import_keystore(&self, keystore: String, password: String) -> Result<AccountInfo>;
+
+    /// Return the keystore json of `address`, encrypted by the account password.
+    async fn export_keystore(&self, address: AccountAddress, password: String) -> Result<String>;
+
     async fn accepted_tokens(&self, address: AccountAddress) -> Result<Vec<TokenCode>>;
 
-    // change account password, user need to unlock account first.
+    /// Change the account password, the `old_password` is required if the account is locked.
     async fn change_account_password(
         &self,
         address: AccountAddress,
+        old_password: Option<String>,
         new_password: String,
     ) -> Result<()>;
+
+    /// Remove the account from the wallet, the default account is only removed if `force`.
+    /// Return the info of the removed account.
+    async fn remove_account(
+        &self,
+        address: AccountAddress,
+        password: String,
+        force: bool,
+    ) -> Result<AccountInfo>;
+
+    /// Prepare the new key of `address` for the authentication key rotation, return the new public key.
+    /// The key is not saved if `dry_run`, and the existing pending key is replaced only if `replace`.
+    async fn prepare_rotate_key(
+        &self,
+        address: AccountAddress,
+        private_key: Option<Vec<u8>>,
+        dry_run: bool,
+        replace: bool,
+    ) -> Result<AccountPublicKey>;
+
+    /// Switch `address` to the prepared key after the rotation to `new_auth_key` is confirmed on chain.
+    async fn commit_rotate_key(
+        &self,
+        address: AccountAddress,
+        new_auth_key: AuthenticationKey,
+    ) -> Result<AccountInfo>;
+
+    /// Drop the prepared key, `address` keeps the old key.
+    async fn abort_rotate_key(&self, address: AccountAddress) -> Result<()>;
+
+    /// Bind `address` to the external signer at `endpoint`, the txns of `address` are signed by it.
+    async fn bind_external_signer(
+        &self,
+        address: AccountAddress,
+        endpoint: SignerEndpoint,
+    ) -> Result<()>;
+
+    /// Get the external signer endpoint bound to `address`, None if the txns are signed by the wallet.
+    async fn get_signer_endpoint(&self, address: AccountAddress) -> Result<Option<SignerEndpoint>>;
+
+    /// Generate a BIP-39 mnemonic with `word_count` words.
+    async fn generate_mnemonic(&self, word_count: u32) -> Result<String>;
+
+    /// Import the account derived from `mnemonic` at the derivation `index`.
+    async fn import_from_mnemonic(
+        &self,
+        mnemonic: String,
+        index: u32,
+        password: String,
+    ) -> Result<AccountInfo>;
+
+    /// Derive the account from `mnemonic` at the derivation `index`, it is not imported.
+    async fn derive_account(&self, mnemonic: String, index: u32) -> Result<DerivedAccount>;
+
+    /// Get the txn history of `address` from the newest to the oldest, skip `start` entries and return at most `limit` entries.
+    async fn txn_history(
+        &self,
+        address: AccountAddress,
+        start: u64,
+        limit: u64,
+    ) -> Result<Vec<TxnHistoryEntry>>;
 }
 
 #[async_trait::async_trait]
@@ -187,6 +266,15 @@ where
         }
     }
 
+    async fn lock_all_accounts(&self) -> Result<Vec<AccountAddress>> {
+        let response = self.send(AccountRequest::LockAllAccounts).await??;
+        if let AccountResponse::AccountAddressList(addresses) = response {
+            Ok(addresses)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
     async fn import_account(
         &self,
         address: AccountAddress,
@@ -207,6 +295,24 @@ where
         }
     }
 
+    async fn import_readonly_account(
+        &self,
+        address: AccountAddress,
+        public_key: Vec<u8>,
+    ) -> Result<AccountInfo> {
+        let response = self
+            .send(AccountRequest::ImportReadonlyAccount {
+                address,
+                public_key,
+            })
+            .await??;
+        if let AccountResponse::AccountInfo(account) = response {
+            Ok(*account)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
     async fn export_account(&self, address: AccountAddress, password: String) -> Result<Vec<u8>> {
         let response = self
             .send(AccountRequest::ExportAccount { address, password })
@@ -218,6 +324,28 @@ where
         }
     }
 
+    async fn import_keystore(&self, keystore: String, password: String) -> Result<AccountInfo> {
+        let response = self
+            .send(AccountRequest::ImportKeyStore { keystore, password })
+            .await??;
+        if let AccountResponse::AccountInfo(account) = response {
+            Ok(*account)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
+    async fn export_keystore(&self, address: AccountAddress, password: String) -> Result<String> {
+        let response = self
+            .send(AccountRequest::ExportKeyStore { address, password })
+            .await??;
+        if let AccountResponse::ExportKeyStoreResponse(keystore) = response {
+            Ok(keystore)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
     async fn accepted_tokens(&self, address: AccountAddress) -> Result<Vec<TokenCode>> {
         let response = self
             .send(AccountRequest::AccountAcceptedTokens { address })
@@ -232,11 +360,13 @@ where
     async fn change_account_password(
         &self,
         address: AccountAddress,
+        old_password: Option<String>,
         new_password: String,
     ) -> Result<()> {
         let response = self
             .send(AccountRequest::ChangePassword {
                 address,
+                old_password,
                 new_password,
             })
             .await??;
@@ -246,4 +376,161 @@ where
             panic!("Unexpected response type.")
         }
     }
+
+    async fn remove_account(
+        &self,
+        address: AccountAddress,
+        password: String,
+        force: bool,
+    ) -> Result<AccountInfo> {
+        let response = self
+            .send(AccountRequest::RemoveAccount {
+                address,
+                password,
+                force,
+            })
+            .await??;
+        if let AccountResponse::AccountInfo(account) = response {
+            Ok(*account)
+        } else {
+            panic!("Unexpected response type.")
+        }
+    }
+
+    async fn prepare_rotate_key(
+        &self,
+        address: AccountAddress,
+        private_key: Option<Vec<u8>>,
+        dry_run: bool,
+        replace: bool,
+    ) -> Result<AccountPublicKey> {
+        let response = self
+            .send(AccountRequest::PrepareRotateKey {
+                address,
+                private_key,
+                dry_run,
+                replace,
+            })
+            .await??;
+        if let AccountResponse::PublicKey(public_key) = response {
+            Ok(*public_key)
+        } else {
+            panic!("Unexpected response type.")
+        }
+    }
+
+    async fn commit_rotate_key(
+        &self,
+        address: AccountAddress,
+        new_auth_key: AuthenticationKey,
+    ) -> Result<AccountInfo> {
+        let response = self
+            .send(AccountRequest::CommitRotateKey {
+                address,
+                new_auth_key,
+            })
+            .await??;
+        if let AccountResponse::AccountInfo(account) = response {
+            Ok(*account)
+        } else {
+            panic!("Unexpected response type.")
+        }
+    }
+
+    async fn abort_rotate_key(&self, address: AccountAddress) -> Result<()> {
+        let response = self.send(AccountRequest::AbortRotateKey(address)).await??;
+        if let AccountResponse::None = response {
+            Ok(())
+        } else {
+            panic!("Unexpected response type.")
+        }
+    }
+
+    async fn bind_external_signer(
+        &self,
+        address: AccountAddress,
+        endpoint: SignerEndpoint,
+    ) -> Result<()> {
+        let response = self
+            .send(AccountRequest::BindExternalSigner { address, endpoint })
+            .await??;
+        if let AccountResponse::None = response {
+            Ok(())
+        } else {
+            panic!("Unexpected response type.")
+        }
+    }
+
+    async fn get_signer_endpoint(&self, address: AccountAddress) -> Result<Option<SignerEndpoint>> {
+        let response = self
+            .send(AccountRequest::GetSignerEndpoint(address))
+            .await??;
+        if let AccountResponse::SignerEndpoint(endpoint) = response {
+            Ok(endpoint)
+        } else {
+            panic!("Unexpected response type.")
+        }
+    }
+
+    async fn generate_mnemonic(&self, word_count: u32) -> Result<String> {
+        let response = self
+            .send(AccountRequest::GenerateMnemonic(word_count))
+            .await??;
+        if let AccountResponse::Mnemonic(mnemonic) = response {
+            Ok(mnemonic)
+        } else {
+            panic!("Unexpected response type.")
+        }
+    }
+
+    async fn import_from_mnemonic(
+        &self,
+        mnemonic: String,
+        index: u32,
+        password: String,
+    ) -> Result<AccountInfo> {
+        let response = self
+            .send(AccountRequest::ImportFromMnemonic {
+                mnemonic,
+                index,
+                password,
+            })
+            .await??;
+        if let AccountResponse::AccountInfo(account) = response {
+            Ok(*account)
+        } else {
+            panic!("Unexpected response type.")
+        }
+    }
+
+    async fn derive_account(&self, mnemonic: String, index: u32) -> Result<DerivedAccount> {
+        let response = self
+            .send(AccountRequest::DeriveAccount { mnemonic, index })
+            .await??;
+        if let AccountResponse::DerivedAccount(account) = response {
+            Ok(*account)
+        } else {
+            panic!("Unexpected response type.")
+        }
+    }
+
+    async fn txn_history(
+        &self,
+        address: AccountAddress,
+        start: u64,
+        limit: u64,
+    ) -> Result<Vec<TxnHistoryEntry>> {
+        let response = self
+            .send(AccountRequest::TxnHistory {
+                address,
+                start,
+                limit,
+            })
+            .await??;
+        if let AccountResponse::TxnHistory(history) = response {
+            Ok(history)
+        } else {
+            panic!("Unexpected response type.")
+        }
+    }
 }
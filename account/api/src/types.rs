@@ -1,12 +1,19 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::error::AccountError;
 use serde::{Deserialize, Serialize};
+use serde_helpers::{deserialize_from_string_opt, serialize_to_string_opt};
 use starcoin_crypto::keygen::KeyGen;
+use starcoin_crypto::HashValue;
 use starcoin_types::{
     account_address::{self, AccountAddress},
+    block::BlockNumber,
     transaction::authenticator::AuthenticationKey,
+    transaction::SignedUserTransaction,
 };
+use std::fmt;
+use std::str::FromStr;
 
 pub use starcoin_types::transaction::authenticator::{
     AccountPrivateKey, AccountPublicKey, AccountSignature,
@@ -20,7 +27,23 @@ pub struct AccountInfo {
     /// This account is default at current wallet.
     /// Every wallet must has one default account.
     pub is_default: bool,
+    /// The account is a watch-only account, the wallet has no private key of it.
+    #[serde(default)]
+    pub is_readonly: bool,
     pub public_key: AccountPublicKey,
+    /// How the account is derived from a mnemonic, None if it is not imported from a mnemonic.
+    #[serde(default)]
+    pub derivation: Option<DerivationInfo>,
+    /// The account is locked, signing with it requires unlocking it by the password first.
+    #[serde(default)]
+    pub is_locked: bool,
+    /// The unix timestamp in seconds when the unlocked account is re-locked, None if it is locked.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_from_string_opt",
+        serialize_with = "serialize_to_string_opt"
+    )]
+    pub unlocked_until: Option<u64>,
 }
 
 impl AccountInfo {
@@ -29,6 +52,10 @@ impl AccountInfo {
             address,
             public_key,
             is_default,
+            is_readonly: false,
+            derivation: None,
+            is_locked: true,
+            unlocked_until: None,
         }
     }
 
@@ -47,7 +74,120 @@ impl AccountInfo {
         AccountInfo {
             address,
             is_default: false,
+            is_readonly: false,
             public_key: AccountPublicKey::Single(public_key),
+            derivation: None,
+            is_locked: true,
+            unlocked_until: None,
         }
     }
 }
+
+/// The word count of the generated mnemonic if not specified.
+pub const DEFAULT_MNEMONIC_WORD_COUNT: u32 = 12;
+
+/// The derivation of an account key from a mnemonic, the mnemonic itself is never saved.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DerivationInfo {
+    /// Identify the mnemonic seed, it is the hex of the first 4 bytes of
+    /// the sha3-256 hash of the seed's master public key.
+    pub seed_fingerprint: String,
+    pub index: u32,
+    /// The SLIP-0010 derivation path, such as `m/44'/101010'/0'/0'/0'`.
+    pub path: String,
+}
+
+/// The account derived from a mnemonic, for previewing the address without importing it.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DerivedAccount {
+    pub address: AccountAddress,
+    pub public_key: AccountPublicKey,
+    pub derivation: DerivationInfo,
+}
+
+/// The last observed status of a txn in the wallet history.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TxnHistoryStatus {
+    /// The txn is signed or submitted, but not mined yet.
+    Pending,
+    /// The txn is mined in the main chain.
+    Mined,
+    /// Another txn with the same sender and sequence number is mined.
+    Replaced,
+}
+
+/// A txn signed by the wallet or sent from a wallet account.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TxnHistoryEntry {
+    pub txn: SignedUserTransaction,
+    /// When the txn is recorded, in unix seconds.
+    pub recorded_at: u64,
+    pub status: TxnHistoryStatus,
+    /// The block which the txn is mined in, only set when the status is `Mined`.
+    pub block_hash: Option<HashValue>,
+    pub block_number: Option<BlockNumber>,
+}
+
+impl TxnHistoryEntry {
+    pub fn new(txn: SignedUserTransaction, recorded_at: u64) -> Self {
+        Self {
+            txn,
+            recorded_at,
+            status: TxnHistoryStatus::Pending,
+            block_hash: None,
+            block_number: None,
+        }
+    }
+}
+
+/// The endpoint of an external signer which holds the private key of an account, such as a HSM.
+/// It is `http://...`, `https://...` or `unix://<socket path>`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SignerEndpoint {
+    Http(String),
+    Unix(String),
+}
+
+impl FromStr for SignerEndpoint {
+    type Err = AccountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with("http://") || s.starts_with("https://") {
+            Ok(SignerEndpoint::Http(s.to_string()))
+        } else if let Some(path) = s.strip_prefix("unix://").filter(|path| !path.is_empty()) {
+            Ok(SignerEndpoint::Unix(path.to_string()))
+        } else {
+            Err(AccountError::InvalidSignerEndpoint(s.to_string()))
+        }
+    }
+}
+
+impl fmt::Display for SignerEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignerEndpoint::Http(url) => write!(f, "{}", url),
+            SignerEndpoint::Unix(path) => write!(f, "unix://{}", path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signer_endpoint() {
+        for endpoint in vec![
+            "http://127.0.0.1:8080",
+            "https://signer.example.com/sign",
+            "unix:///var/run/signer.sock",
+        ] {
+            assert_eq!(
+                SignerEndpoint::from_str(endpoint).unwrap().to_string(),
+                endpoint
+            );
+        }
+        assert!(SignerEndpoint::from_str("unix://").is_err());
+        assert!(SignerEndpoint::from_str("ws://127.0.0.1:8080").is_err());
+    }
+}
@@ -1,35 +1,49 @@
 use anyhow::{Error, Result};
 use starcoin_account::account_storage::AccountStorage;
-use starcoin_chain_notify::message::ContractEventNotification;
+use starcoin_account_api::TxnHistoryEntry;
+use starcoin_chain_notify::message::{
+    BlockNotificationKind, ContractEventNotification, NewHeadEventNotification,
+};
+use starcoin_config::NodeConfig;
 use starcoin_logger::prelude::*;
 use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
+use starcoin_txpool_api::PropagateTransactions;
 use starcoin_types::account_config::accept_token_payment::AcceptTokenEvent;
 use starcoin_types::contract_event::ContractEvent;
 use starcoin_types::event::EventKey;
 use std::collections::HashSet;
 use std::convert::TryFrom;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[derive(Clone)]
 pub struct AccountEventService {
     storage: AccountStorage,
+    max_txn_history: usize,
 }
 
 impl ActorService for AccountEventService {
     fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.subscribe::<ContractEventNotification>();
+        ctx.subscribe::<NewHeadEventNotification>();
+        ctx.subscribe::<PropagateTransactions>();
         Ok(())
     }
 
     fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.unsubscribe::<ContractEventNotification>();
+        ctx.unsubscribe::<NewHeadEventNotification>();
+        ctx.unsubscribe::<PropagateTransactions>();
         Ok(())
     }
 }
 
 impl ServiceFactory<AccountEventService> for AccountEventService {
     fn create(ctx: &mut ServiceContext<AccountEventService>) -> Result<AccountEventService> {
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
         Ok(Self {
             storage: ctx.get_shared::<AccountStorage>()?,
+            max_txn_history: config.vault.max_txn_history() as usize,
         })
     }
 }
@@ -70,6 +84,71 @@ impl EventHandler<Self, ContractEventNotification> for AccountEventService {
     }
 }
 
+/// Record the txns sent from the wallet accounts when they enter the txpool,
+/// such as the txns submitted by `txpool.submit_transaction` or signed by the external signers.
+impl EventHandler<Self, PropagateTransactions> for AccountEventService {
+    fn handle_event(
+        &mut self,
+        item: PropagateTransactions,
+        _ctx: &mut ServiceContext<AccountEventService>,
+    ) {
+        let addrs: HashSet<_> = match self.storage.list_addresses() {
+            Ok(addresses) => addresses.into_iter().collect(),
+            Err(e) => {
+                error!("Fail to get account list from storage, err: {}", e);
+                return;
+            }
+        };
+        for txn in item.transaction_to_propagate() {
+            if !addrs.contains(&txn.sender()) {
+                continue;
+            }
+            let txn_hash = txn.id();
+            let entry = TxnHistoryEntry::new(txn, now_secs());
+            if let Err(e) = self.storage.add_txn_history(entry, self.max_txn_history) {
+                error!("Fail to record txn {} to history, err: {}", txn_hash, e);
+            }
+        }
+    }
+}
+
+/// Update the status of the recorded txns when the blocks are connected or retracted.
+impl EventHandler<Self, NewHeadEventNotification> for AccountEventService {
+    fn handle_event(
+        &mut self,
+        item: NewHeadEventNotification,
+        _ctx: &mut ServiceContext<AccountEventService>,
+    ) {
+        let block = &item.0.block;
+        let block_hash = block.header().id();
+        let block_number = block.header().number();
+        for txn_hash in block.body() {
+            let result = match item.0.kind {
+                BlockNotificationKind::NewHead => {
+                    self.storage
+                        .mark_txn_mined(*txn_hash, block_hash, block_number)
+                }
+                BlockNotificationKind::Retracted => {
+                    self.storage.mark_txn_retracted(*txn_hash, block_hash)
+                }
+            };
+            if let Err(e) = result {
+                error!(
+                    "Fail to update the history of txn {} in block {}, err: {}",
+                    txn_hash, block_hash, e
+                );
+            }
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
 impl AccountEventService {
     fn handle_contract_event(&self, event: &ContractEvent) -> Result<(), Error> {
         let evt = AcceptTokenEvent::try_from(event)?;
@@ -3,7 +3,12 @@
 
 mod account_events;
 mod service;
+mod signer;
 
 pub use account_events::AccountEventService;
 pub use service::AccountService;
+pub use signer::{
+    signer_of, ExternalSigner, Signer, WalletSigner, DEFAULT_EXTERNAL_SIGNER_TIMEOUT,
+    SIGN_RAW_TXN_METHOD,
+};
 pub use starcoin_account::account_storage::AccountStorage;
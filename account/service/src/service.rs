@@ -8,15 +8,27 @@ use starcoin_config::NodeConfig;
 use starcoin_crypto::ValidCryptoMaterial;
 use starcoin_logger::prelude::*;
 use starcoin_service_registry::mocker::MockHandler;
-use starcoin_service_registry::{ActorService, ServiceContext, ServiceFactory, ServiceHandler};
+use starcoin_service_registry::{
+    ActorService, EventHandler, ServiceContext, ServiceFactory, ServiceHandler,
+};
 use starcoin_types::account_config::{association_address, STC_TOKEN_CODE};
 use std::any::Any;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub const DEFAULT_ACCOUNT_PASSWORD: &str = "";
 
+/// The interval to re-lock the accounts whose unlock duration is expired.
+const LOCK_EXPIRED_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Re-lock the accounts whose unlock duration is expired.
+#[derive(Clone, Debug)]
+struct LockExpiredTick;
+
 pub struct AccountService {
     manager: AccountManager,
+    max_txn_history: usize,
+    max_unlock_duration: Duration,
 }
 
 impl AccountService {
@@ -24,7 +36,11 @@ impl AccountService {
         let manager = AccountManager::new(AccountStorage::mock())?;
         //auto create default account.
         manager.create_account("")?;
-        Ok(Self { manager })
+        Ok(Self {
+            manager,
+            max_txn_history: NodeConfig::default().vault.max_txn_history() as usize,
+            max_unlock_duration: NodeConfig::default().vault.max_unlock_duration(),
+        })
     }
 }
 
@@ -71,15 +87,32 @@ impl ActorService for AccountService {
                 }
             }
         }
+        ctx.run_interval(LOCK_EXPIRED_INTERVAL, |ctx| ctx.notify(LockExpiredTick));
         Ok(())
     }
 }
 
+impl EventHandler<Self, LockExpiredTick> for AccountService {
+    fn handle_event(&mut self, _msg: LockExpiredTick, _ctx: &mut ServiceContext<Self>) {
+        for address in self.manager.lock_expired() {
+            info!(
+                "Account {} is re-locked for the unlock duration is expired.",
+                address
+            );
+        }
+    }
+}
+
 impl ServiceFactory<AccountService> for AccountService {
     fn create(ctx: &mut ServiceContext<AccountService>) -> Result<AccountService> {
         let account_storage = ctx.get_shared::<AccountStorage>()?;
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
         let manager = AccountManager::new(account_storage)?;
-        Ok(Self { manager })
+        Ok(Self {
+            manager,
+            max_txn_history: config.vault.max_txn_history() as usize,
+            max_unlock_duration: config.vault.max_unlock_duration(),
+        })
     }
 }
 
@@ -114,11 +147,21 @@ impl ServiceHandler<AccountService, AccountRequest> for AccountService {
             AccountRequest::SignTxn {
                 txn: raw_txn,
                 signer,
-            } => AccountResponse::SignedTxn(Box::new(self.manager.sign_txn(signer, *raw_txn)?)),
+            } => {
+                let signed_txn = self.manager.sign_txn(signer, *raw_txn)?;
+                if let Err(e) = self
+                    .manager
+                    .record_txn(signed_txn.clone(), self.max_txn_history)
+                {
+                    error!("Fail to record txn {} to history: {:?}", signed_txn.id(), e);
+                }
+                AccountResponse::SignedTxn(Box::new(signed_txn))
+            }
             AccountRequest::SignMessage { message, signer } => AccountResponse::MessageSignature(
                 Box::new(self.manager.sign_message(signer, message)?),
             ),
             AccountRequest::UnlockAccount(address, password, duration) => {
+                let duration = std::cmp::min(duration, self.max_unlock_duration);
                 self.manager
                     .unlock_account(address, password.as_str(), duration)?;
                 AccountResponse::UnlockAccountResponse
@@ -127,6 +170,11 @@ impl ServiceHandler<AccountService, AccountRequest> for AccountService {
                 self.manager.lock_account(address)?;
                 AccountResponse::None
             }
+            AccountRequest::LockAllAccounts => {
+                let addresses = self.manager.lock_all();
+                info!("Lock all the {} unlocked accounts.", addresses.len());
+                AccountResponse::AccountAddressList(addresses)
+            }
             AccountRequest::ExportAccount { address, password } => {
                 let data = self.manager.export_account(address, password.as_str())?;
                 AccountResponse::ExportAccountResponse(data)
@@ -141,6 +189,23 @@ impl ServiceHandler<AccountService, AccountRequest> for AccountService {
                         .import_account(address, private_key, password.as_str())?;
                 AccountResponse::AccountInfo(Box::new(wallet.info()))
             }
+            AccountRequest::ImportReadonlyAccount {
+                address,
+                public_key,
+            } => {
+                let account_info = self.manager.import_readonly_account(address, public_key)?;
+                AccountResponse::AccountInfo(Box::new(account_info))
+            }
+            AccountRequest::ImportKeyStore { keystore, password } => {
+                let account = self
+                    .manager
+                    .import_keystore(keystore.as_str(), password.as_str())?;
+                AccountResponse::AccountInfo(Box::new(account.info()))
+            }
+            AccountRequest::ExportKeyStore { address, password } => {
+                let keystore = self.manager.export_keystore(address, password.as_str())?;
+                AccountResponse::ExportKeyStoreResponse(keystore)
+            }
             AccountRequest::AccountAcceptedTokens { address } => {
                 let mut tokens = self.manager.accepted_tokens(address)?;
                 //auto add STC to accepted tokens.
@@ -151,11 +216,81 @@ impl ServiceHandler<AccountService, AccountRequest> for AccountService {
             }
             AccountRequest::ChangePassword {
                 address,
+                old_password,
                 new_password,
             } => {
-                self.manager.change_password(address, new_password)?;
+                self.manager
+                    .change_password(address, old_password, new_password)?;
                 AccountResponse::None
             }
+            AccountRequest::RemoveAccount {
+                address,
+                password,
+                force,
+            } => {
+                let account_info =
+                    self.manager
+                        .remove_account(address, password.as_str(), force)?;
+                info!("Account {} is removed from the wallet.", address);
+                AccountResponse::AccountInfo(Box::new(account_info))
+            }
+            AccountRequest::PrepareRotateKey {
+                address,
+                private_key,
+                dry_run,
+                replace,
+            } => {
+                let public_key =
+                    self.manager
+                        .prepare_rotate_key(address, private_key, dry_run, replace)?;
+                AccountResponse::PublicKey(Box::new(public_key))
+            }
+            AccountRequest::CommitRotateKey {
+                address,
+                new_auth_key,
+            } => {
+                let account_info = self.manager.commit_rotate_key(address, new_auth_key)?;
+                AccountResponse::AccountInfo(Box::new(account_info))
+            }
+            AccountRequest::AbortRotateKey(address) => {
+                self.manager.abort_rotate_key(address)?;
+                AccountResponse::None
+            }
+            AccountRequest::BindExternalSigner { address, endpoint } => {
+                self.manager.bind_external_signer(address, endpoint)?;
+                AccountResponse::None
+            }
+            AccountRequest::GetSignerEndpoint(address) => {
+                AccountResponse::SignerEndpoint(self.manager.signer_endpoint(address)?)
+            }
+            AccountRequest::GenerateMnemonic(word_count) => {
+                AccountResponse::Mnemonic(self.manager.generate_mnemonic(word_count)?)
+            }
+            AccountRequest::ImportFromMnemonic {
+                mnemonic,
+                index,
+                password,
+            } => {
+                let account_info = self.manager.import_from_mnemonic(
+                    mnemonic.as_str(),
+                    index,
+                    password.as_str(),
+                )?;
+                AccountResponse::AccountInfo(Box::new(account_info))
+            }
+            AccountRequest::DeriveAccount { mnemonic, index } => {
+                let account = self.manager.derive_account(mnemonic.as_str(), index)?;
+                AccountResponse::DerivedAccount(Box::new(account))
+            }
+            AccountRequest::TxnHistory {
+                address,
+                start,
+                limit,
+            } => AccountResponse::TxnHistory(self.manager.txn_history(
+                address,
+                start as usize,
+                limit as usize,
+            )?),
         };
         Ok(response)
     }
@@ -0,0 +1,183 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, format_err, Result};
+use futures::future::{self, Either};
+use futures_timer::Delay;
+use reqwest::header::CONTENT_TYPE;
+use starcoin_account_api::error::AccountError;
+use starcoin_account_api::{AccountAsyncService, SignerEndpoint};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::transaction::authenticator::TransactionAuthenticator;
+use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+
+/// The json rpc method called on the external signer.
+pub const SIGN_RAW_TXN_METHOD: &str = "sign_raw_txn";
+pub const DEFAULT_EXTERNAL_SIGNER_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[async_trait::async_trait]
+pub trait Signer: Send + Sync {
+    async fn sign_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        signer_address: AccountAddress,
+    ) -> Result<SignedUserTransaction>;
+}
+
+/// Sign txns with the keys in the wallet.
+pub struct WalletSigner<S>
+where
+    S: AccountAsyncService,
+{
+    service: S,
+}
+
+impl<S> WalletSigner<S>
+where
+    S: AccountAsyncService,
+{
+    pub fn new(service: S) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> Signer for WalletSigner<S>
+where
+    S: AccountAsyncService,
+{
+    async fn sign_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        signer_address: AccountAddress,
+    ) -> Result<SignedUserTransaction> {
+        self.service.sign_txn(raw_txn, signer_address).await
+    }
+}
+
+/// Forward the signing to an external signer, such as a HSM.
+/// The request is a json rpc call of `sign_raw_txn(raw_txn_bcs_hex, address)`,
+/// the result is the hex encoded bcs bytes of the `TransactionAuthenticator`.
+/// The unix socket signer reads a request line and writes a response line.
+pub struct ExternalSigner {
+    endpoint: SignerEndpoint,
+    timeout: Duration,
+}
+
+impl ExternalSigner {
+    pub fn new(endpoint: SignerEndpoint, timeout: Duration) -> Self {
+        Self { endpoint, timeout }
+    }
+
+    async fn call(&self, request: Vec<u8>) -> Result<Vec<u8>> {
+        match &self.endpoint {
+            SignerEndpoint::Http(url) => {
+                let response = reqwest::Client::new()
+                    .post(url.as_str())
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(request)
+                    .send()
+                    .await?
+                    .error_for_status()?;
+                Ok(response.bytes().await?.to_vec())
+            }
+            SignerEndpoint::Unix(path) => {
+                let mut stream = UnixStream::connect(path).await?;
+                stream.write_all(request.as_slice()).await?;
+                stream.write_all(b"\n").await?;
+                let mut line = String::new();
+                BufReader::new(stream).read_line(&mut line).await?;
+                Ok(line.into_bytes())
+            }
+        }
+    }
+}
+
+fn decode_authenticator(response: &[u8]) -> Result<TransactionAuthenticator> {
+    let response: serde_json::Value = serde_json::from_slice(response)?;
+    if let Some(error) = response.get("error") {
+        bail!("{}", error);
+    }
+    let result = response
+        .get("result")
+        .and_then(|result| result.as_str())
+        .ok_or_else(|| format_err!("the response has no result"))?;
+    let bytes = hex::decode(result.strip_prefix("0x").unwrap_or(result))?;
+    bcs_ext::from_bytes(bytes.as_slice())
+}
+
+#[async_trait::async_trait]
+impl Signer for ExternalSigner {
+    async fn sign_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        signer_address: AccountAddress,
+    ) -> Result<SignedUserTransaction> {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": SIGN_RAW_TXN_METHOD,
+            "params": [
+                format!("0x{}", hex::encode(bcs_ext::to_bytes(&raw_txn)?)),
+                signer_address.to_string(),
+            ],
+        });
+        let call = Box::pin(self.call(serde_json::to_vec(&request)?));
+        let response = match future::select(call, Delay::new(self.timeout)).await {
+            Either::Left((response, _)) => response
+                .map_err(|e| AccountError::ExternalSignerError(signer_address, e.to_string()))?,
+            Either::Right(_) => {
+                return Err(AccountError::ExternalSignerTimeout(signer_address).into())
+            }
+        };
+        let authenticator = decode_authenticator(response.as_slice())
+            .map_err(|e| AccountError::ExternalSignerError(signer_address, e.to_string()))?;
+        let signed_txn = SignedUserTransaction::new(raw_txn, authenticator);
+        signed_txn.clone().check_signature().map_err(|e| {
+            AccountError::ExternalSignerError(signer_address, format!("invalid signature, {}", e))
+        })?;
+        Ok(signed_txn)
+    }
+}
+
+/// Get the signer of `address`, it is the bound external signer, or the wallet if no signer is bound.
+pub async fn signer_of<S>(service: S, address: AccountAddress) -> Result<Box<dyn Signer>>
+where
+    S: AccountAsyncService + 'static,
+{
+    Ok(match service.get_signer_endpoint(address).await? {
+        Some(endpoint) => Box::new(ExternalSigner::new(
+            endpoint,
+            DEFAULT_EXTERNAL_SIGNER_TIMEOUT,
+        )),
+        None => Box::new(WalletSigner::new(service)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_authenticator() {
+        let authenticator = SignedUserTransaction::mock().authenticator();
+        let response = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": format!("0x{}", hex::encode(bcs_ext::to_bytes(&authenticator).unwrap())),
+        });
+        assert_eq!(
+            decode_authenticator(serde_json::to_vec(&response).unwrap().as_slice()).unwrap(),
+            authenticator
+        );
+        let response = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": {"code": -1, "message": "key not found"},
+        });
+        assert!(decode_authenticator(serde_json::to_vec(&response).unwrap().as_slice()).is_err());
+    }
+}
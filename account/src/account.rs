@@ -43,7 +43,12 @@ impl Account {
         password: &str,
         store: AccountStorage,
     ) -> AccountResult<Option<Self>> {
-        let decrypted_key = store.decrypt_private_key(addr, password)?;
+        let decrypted_key = store.decrypt_private_key(addr, password).map_err(|e| {
+            match e.downcast::<AccountError>() {
+                Ok(e) => e,
+                Err(e) => AccountError::StoreError(e),
+            }
+        })?;
         let private_key = match decrypted_key {
             None => return Ok(None),
             Some(p) => p,
@@ -3,15 +3,21 @@
 
 use crate::account::Account;
 use crate::account_storage::AccountStorage;
+use crate::keystore::KeyStore;
+use crate::mnemonic;
 
+use anyhow::format_err;
 use parking_lot::RwLock;
 use rand::prelude::*;
 use starcoin_account_api::error::AccountError;
-use starcoin_account_api::{AccountInfo, AccountPrivateKey, AccountResult};
+use starcoin_account_api::{
+    AccountInfo, AccountPrivateKey, AccountPublicKey, AccountResult, DerivedAccount,
+    SignerEndpoint, TxnHistoryEntry,
+};
 use starcoin_crypto::ed25519::Ed25519PrivateKey;
 use starcoin_crypto::{Uniform, ValidCryptoMaterial};
 use starcoin_types::sign_message::SigningMessage;
-use starcoin_types::transaction::authenticator::AccountSignature;
+use starcoin_types::transaction::authenticator::{AccountSignature, AuthenticationKey};
 use starcoin_types::{
     account_address::AccountAddress,
     account_config::token_code::TokenCode,
@@ -20,8 +26,8 @@ use starcoin_types::{
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::ops::Add;
-use std::time::Duration;
 use std::time::Instant;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Account manager
 pub struct AccountManager {
@@ -29,35 +35,80 @@ pub struct AccountManager {
     key_cache: RwLock<PasswordCache>,
 }
 
+#[derive(Debug, PartialEq, Eq)]
+struct CachedPass {
+    ttl: Instant,
+    /// The unix timestamp in seconds of the ttl.
+    unlocked_until: u64,
+    pass: String,
+}
+
 #[derive(Default, Debug, PartialEq, Eq)]
 struct PasswordCache {
-    cache: HashMap<AccountAddress, (Instant, String)>,
+    cache: HashMap<AccountAddress, CachedPass>,
 }
 impl PasswordCache {
-    pub fn cache_pass(&mut self, account: AccountAddress, pass: String, ttl: Instant) {
-        self.cache.insert(account, (ttl, pass));
+    pub fn cache_pass(&mut self, account: AccountAddress, pass: String, duration: Duration) {
+        let unlocked_until = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("System time is before the UNIX_EPOCH")
+            .add(duration)
+            .as_secs();
+        self.cache.insert(
+            account,
+            CachedPass {
+                ttl: Instant::now().add(duration),
+                unlocked_until,
+                pass,
+            },
+        );
     }
     pub fn remove_pass(&mut self, account: &AccountAddress) {
         self.cache.remove(account);
     }
     pub fn get_pass(&mut self, account: &AccountAddress) -> Option<String> {
-        match self.cache.remove(account) {
-            None => None,
-            Some((ttl, kp)) => {
-                if Instant::now() < ttl {
-                    self.cache.insert(*account, (ttl, kp));
-                    self.cache.get(account).map(|t| t.1.to_string())
-                } else {
-                    None
-                }
-            }
+        self.get(account).map(|cached| cached.pass.clone())
+    }
+
+    /// Return the unlock deadline in unix seconds if the account is unlocked.
+    pub fn unlocked_until(&mut self, account: &AccountAddress) -> Option<u64> {
+        self.get(account).map(|cached| cached.unlocked_until)
+    }
+
+    fn get(&mut self, account: &AccountAddress) -> Option<&CachedPass> {
+        let expired = match self.cache.get(account) {
+            None => return None,
+            Some(cached) => Instant::now() >= cached.ttl,
+        };
+        if expired {
+            self.cache.remove(account);
+            None
+        } else {
+            self.cache.get(account)
         }
     }
 
-    #[allow(dead_code)]
-    pub fn clean_expired(&mut self) {
+    /// Remove the expired passwords, return the re-locked accounts.
+    pub fn clean_expired(&mut self) -> Vec<AccountAddress> {
         let cur_instant = Instant::now();
-        self.cache.retain(|_account, (ttl, _)| &cur_instant < ttl);
+        let expired: Vec<AccountAddress> = self
+            .cache
+            .iter()
+            .filter(|(_account, cached)| cur_instant >= cached.ttl)
+            .map(|(account, _cached)| *account)
+            .collect();
+        for account in &expired {
+            self.cache.remove(account);
+        }
+        expired
+    }
+
+    /// Remove all the passwords, return the locked accounts.
+    pub fn clean_all(&mut self) -> Vec<AccountAddress> {
+        self.cache
+            .drain()
+            .map(|(account, _cached)| account)
+            .collect()
     }
 }
 
@@ -83,12 +134,12 @@ impl AccountManager {
         password: &str,
         duration: Duration,
     ) -> AccountResult<()> {
+        self.ensure_not_readonly(address)?;
         let _ = Account::load(address, password, self.store.clone())?
             .ok_or(AccountError::AccountNotExist(address))?;
-        let ttl = std::time::Instant::now().add(duration);
         self.key_cache
             .write()
-            .cache_pass(address, password.to_string(), ttl);
+            .cache_pass(address, password.to_string(), duration);
         Ok(())
     }
 
@@ -97,6 +148,16 @@ impl AccountManager {
         Ok(())
     }
 
+    /// Lock all the unlocked accounts, return the locked accounts.
+    pub fn lock_all(&self) -> Vec<AccountAddress> {
+        self.key_cache.write().clean_all()
+    }
+
+    /// Re-lock the accounts whose unlock duration is expired, return the re-locked accounts.
+    pub fn lock_expired(&self) -> Vec<AccountAddress> {
+        self.key_cache.write().clean_expired()
+    }
+
     pub fn import_account(
         &self,
         address: AccountAddress,
@@ -108,6 +169,59 @@ impl AccountManager {
         self.save_account(address, private_key, password.to_string())
     }
 
+    /// Import a watch-only account, only the public key is saved in the wallet.
+    pub fn import_readonly_account(
+        &self,
+        address: AccountAddress,
+        public_key: Vec<u8>,
+    ) -> AccountResult<AccountInfo> {
+        let public_key = AccountPublicKey::try_from(public_key.as_slice())
+            .map_err(|_| AccountError::InvalidPublicKey)?;
+        if self.contains(&address)? {
+            return Err(AccountError::AccountAlreadyExist(address));
+        }
+        self.store.update_public_key(address, public_key)?;
+        self.store.add_address(address)?;
+        // if it's the first address, set it default.
+        if self.store.list_addresses()?.len() == 1 {
+            self.set_default_account(address)?;
+        }
+        self.account_info(address)?
+            .ok_or(AccountError::AccountNotExist(address))
+    }
+
+    /// Import the account derived from `mnemonic` at `index` of the Starcoin derivation path,
+    /// the derivation is saved so it can be shown in the account info.
+    pub fn import_from_mnemonic(
+        &self,
+        mnemonic: &str,
+        index: u32,
+        password: &str,
+    ) -> AccountResult<AccountInfo> {
+        let (private_key, derivation) = mnemonic::derive_private_key(mnemonic, index)?;
+        let address = private_key.public_key().derived_address();
+        self.save_account(address, private_key, password.to_string())?;
+        self.store.set_derivation(address, derivation)?;
+        self.account_info(address)?
+            .ok_or(AccountError::AccountNotExist(address))
+    }
+
+    /// Derive the account from `mnemonic` at `index` without importing it.
+    pub fn derive_account(&self, mnemonic: &str, index: u32) -> AccountResult<DerivedAccount> {
+        mnemonic::derive_account(mnemonic, index)
+    }
+
+    pub fn generate_mnemonic(&self, word_count: u32) -> AccountResult<String> {
+        mnemonic::generate_mnemonic(word_count)
+    }
+
+    fn ensure_not_readonly(&self, address: AccountAddress) -> AccountResult<()> {
+        if self.store.is_readonly(address)? {
+            return Err(AccountError::AccountReadOnly(address));
+        }
+        Ok(())
+    }
+
     fn save_account(
         &self,
         address: AccountAddress,
@@ -132,11 +246,31 @@ impl AccountManager {
         address: AccountAddress,
         password: &str,
     ) -> AccountResult<Vec<u8>> {
+        self.ensure_not_readonly(address)?;
         let account = Account::load(address, password, self.store.clone())?
             .ok_or(AccountError::AccountNotExist(address))?;
         Ok(account.private_key().to_bytes().to_vec())
     }
 
+    /// Import the account from a keystore json, the account is saved with the keystore password.
+    pub fn import_keystore(&self, keystore: &str, password: &str) -> AccountResult<Account> {
+        let keystore = KeyStore::from_json(keystore)?;
+        let private_key = keystore.decrypt(password)?;
+        self.save_account(keystore.address, private_key, password.to_string())
+    }
+
+    /// Export the account as a keystore json encrypted by the account password.
+    pub fn export_keystore(
+        &self,
+        address: AccountAddress,
+        password: &str,
+    ) -> AccountResult<String> {
+        self.ensure_not_readonly(address)?;
+        let account = Account::load(address, password, self.store.clone())?
+            .ok_or(AccountError::AccountNotExist(address))?;
+        Ok(KeyStore::encrypt(address, account.private_key(), password)?.to_json())
+    }
+
     pub fn contains(&self, address: &AccountAddress) -> AccountResult<bool> {
         self.store
             .contain_address(*address)
@@ -158,10 +292,15 @@ impl AccountManager {
             let pubkey = self.store.public_key(account)?;
             match pubkey {
                 Some(p) => {
+                    let unlocked_until = self.key_cache.write().unlocked_until(&account);
                     res.push(AccountInfo {
                         address: account,
                         is_default: default_account.filter(|a| a == &account).is_some(),
+                        is_readonly: self.store.is_readonly(account)?,
                         public_key: p,
+                        derivation: self.store.get_derivation(account)?,
+                        is_locked: unlocked_until.is_none(),
+                        unlocked_until,
                     });
                 }
                 None => {
@@ -176,10 +315,15 @@ impl AccountManager {
         match self.store.public_key(address)? {
             Some(p) => {
                 let default_account = self.store.default_address()?;
+                let unlocked_until = self.key_cache.write().unlocked_until(&address);
                 Ok(Some(AccountInfo {
                     address,
                     is_default: default_account.filter(|a| a == &address).is_some(),
+                    is_readonly: self.store.is_readonly(address)?,
                     public_key: p,
+                    derivation: self.store.get_derivation(address)?,
+                    is_locked: unlocked_until.is_none(),
+                    unlocked_until,
                 }))
             }
             None => Ok(None),
@@ -190,6 +334,7 @@ impl AccountManager {
         signer_address: AccountAddress,
         message: SigningMessage,
     ) -> AccountResult<AccountSignature> {
+        self.ensure_not_readonly(signer_address)?;
         let pass = self.key_cache.write().get_pass(&signer_address);
         match pass {
             None => Err(AccountError::AccountLocked(signer_address)),
@@ -206,6 +351,7 @@ impl AccountManager {
         signer_address: AccountAddress,
         raw_txn: RawUserTransaction,
     ) -> AccountResult<SignedUserTransaction> {
+        self.ensure_not_readonly(signer_address)?;
         let pass = self.key_cache.write().get_pass(&signer_address);
         match pass {
             None => Err(AccountError::AccountLocked(signer_address)),
@@ -225,47 +371,124 @@ impl AccountManager {
             .map_err(AccountError::StoreError)
     }
 
+    /// Change the password of the account, the `old_password` is required if the account is locked.
     pub fn change_password(
         &self,
         address: AccountAddress,
+        old_password: Option<String>,
         new_pass: impl AsRef<str>,
     ) -> AccountResult<()> {
-        let pass = self.key_cache.write().get_pass(&address);
+        self.ensure_not_readonly(address)?;
+        let old_pass = match old_password {
+            Some(old_pass) => old_pass,
+            None => self
+                .key_cache
+                .write()
+                .get_pass(&address)
+                .ok_or(AccountError::AccountLocked(address))?,
+        };
+        let account = Account::load(address, old_pass.as_str(), self.store.clone())?
+            .ok_or(AccountError::AccountNotExist(address))?;
+        self.store
+            .update_password(address, account.private_key(), old_pass, new_pass)
+            .map_err(AccountError::StoreError)?;
 
-        match pass {
-            None => Err(AccountError::AccountLocked(address)),
-            Some(old_pass) => {
-                // use old pass to export the private key
-                let private_key = self.export_account(address, old_pass.as_str())?;
-                // and use new pass to update the encrypted private key.
-                self.store
-                    .update_key(
-                        address,
-                        &AccountPrivateKey::try_from(private_key.as_slice())
-                            .expect("AccountPrivateKey from bytes should be ok"),
-                        new_pass,
-                    )
-                    .map_err(AccountError::StoreError)?;
-
-                // After changing password success, we should remove the old pass cache.
-                // And user need to login in again, like we always did in websites.
-                self.key_cache.write().remove_pass(&address);
-                Ok(())
-            }
+        // After changing password success, we should remove the old pass cache.
+        // And user need to login in again, like we always did in websites.
+        self.key_cache.write().remove_pass(&address);
+        Ok(())
+    }
+
+    /// Prepare a new key for rotating the authentication key of the unlocked account.
+    /// The key is generated if `private_key` is None, and is saved as pending key unless `dry_run`,
+    /// the account still signs with the old key until `commit_rotate_key`.
+    /// The pending key of an unconfirmed rotation is replaced only if `replace`.
+    pub fn prepare_rotate_key(
+        &self,
+        address: AccountAddress,
+        private_key: Option<Vec<u8>>,
+        dry_run: bool,
+        replace: bool,
+    ) -> AccountResult<AccountPublicKey> {
+        self.ensure_not_readonly(address)?;
+        let pass = self
+            .key_cache
+            .write()
+            .get_pass(&address)
+            .ok_or(AccountError::AccountLocked(address))?;
+        let account = Account::load(address, pass.as_str(), self.store.clone())?
+            .ok_or(AccountError::AccountNotExist(address))?;
+        let new_key = match private_key {
+            Some(private_key) => AccountPrivateKey::try_from(private_key.as_slice())
+                .map_err(|_| AccountError::InvalidPrivateKey)?,
+            None => gen_private_key().into(),
+        };
+        let new_public_key = new_key.public_key();
+        if new_public_key == account.public_key() {
+            return Err(AccountError::InvalidPrivateKey);
+        }
+        if !replace && self.store.pending_public_key(address)?.is_some() {
+            return Err(AccountError::PendingKeyExists(address));
+        }
+        if !dry_run {
+            self.store
+                .update_pending_key(address, &new_key, pass, replace)?;
         }
+        Ok(new_public_key)
     }
 
-    /// remove wallet need user password.
-    #[allow(unused)]
-    pub fn delete_account(&self, address: AccountAddress, password: &str) -> AccountResult<()> {
-        let account = Account::load(address, password, self.store.clone())?;
-        match account {
-            Some(account) => {
-                self.key_cache.write().remove_pass(&address);
-                account.destroy().map_err(AccountError::StoreError)
-            }
-            None => Ok(()),
+    /// Switch the account to the pending key once the rotation to `new_auth_key` is confirmed on chain.
+    pub fn commit_rotate_key(
+        &self,
+        address: AccountAddress,
+        new_auth_key: AuthenticationKey,
+    ) -> AccountResult<AccountInfo> {
+        let pending_public_key = self.store.pending_public_key(address)?.ok_or_else(|| {
+            AccountError::StoreError(format_err!("no pending key for address {}", address))
+        })?;
+        if pending_public_key.authentication_key() != new_auth_key {
+            return Err(AccountError::StoreError(format_err!(
+                "pending key of address {} does not match auth key {}",
+                address,
+                new_auth_key
+            )));
+        }
+        self.store.commit_pending_key(address)?;
+        self.account_info(address)?
+            .ok_or(AccountError::AccountNotExist(address))
+    }
+
+    /// Drop the pending key, the account keeps the old key.
+    pub fn abort_rotate_key(&self, address: AccountAddress) -> AccountResult<()> {
+        self.store
+            .remove_pending_key(address)
+            .map_err(AccountError::StoreError)
+    }
+
+    /// Remove the account from the wallet, the password is required unless the account is read-only.
+    /// The default account is only removed if `force`, then the wallet has no default account.
+    /// Return the info of the removed account.
+    pub fn remove_account(
+        &self,
+        address: AccountAddress,
+        password: &str,
+        force: bool,
+    ) -> AccountResult<AccountInfo> {
+        let account_info = self
+            .account_info(address)?
+            .ok_or(AccountError::AccountNotExist(address))?;
+        if account_info.is_default && !force {
+            return Err(AccountError::RemoveDefaultAccountError(address));
+        }
+        if !account_info.is_readonly {
+            let _ = Account::load(address, password, self.store.clone())?
+                .ok_or(AccountError::AccountNotExist(address))?;
         }
+        self.key_cache.write().remove_pass(&address);
+        self.store
+            .destroy_account(address)
+            .map_err(AccountError::StoreError)?;
+        Ok(account_info)
     }
 
     pub fn accepted_tokens(&self, address: AccountAddress) -> AccountResult<Vec<TokenCode>> {
@@ -273,6 +496,51 @@ impl AccountManager {
             .get_accepted_tokens(address)
             .map_err(AccountError::StoreError)
     }
+
+    /// The account is not required in the wallet, the external signer may hold the only key of it.
+    pub fn bind_external_signer(
+        &self,
+        address: AccountAddress,
+        endpoint: SignerEndpoint,
+    ) -> AccountResult<()> {
+        self.store
+            .set_signer_endpoint(address, endpoint)
+            .map_err(AccountError::StoreError)
+    }
+
+    pub fn signer_endpoint(
+        &self,
+        address: AccountAddress,
+    ) -> AccountResult<Option<SignerEndpoint>> {
+        self.store
+            .get_signer_endpoint(address)
+            .map_err(AccountError::StoreError)
+    }
+
+    /// Record the txn to the history of its sender as pending, keep at most `max_history` entries.
+    pub fn record_txn(&self, txn: SignedUserTransaction, max_history: usize) -> AccountResult<()> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        self.store
+            .add_txn_history(TxnHistoryEntry::new(txn, now), max_history)
+            .map_err(AccountError::StoreError)
+    }
+
+    /// The txn history of the address, from the newest to the oldest.
+    pub fn txn_history(
+        &self,
+        address: AccountAddress,
+        start: usize,
+        limit: usize,
+    ) -> AccountResult<Vec<TxnHistoryEntry>> {
+        let history = self
+            .store
+            .get_txn_history(address)
+            .map_err(AccountError::StoreError)?;
+        Ok(history.into_iter().rev().skip(start).take(limit).collect())
+    }
 }
 
 pub(crate) fn gen_private_key() -> Ed25519PrivateKey {
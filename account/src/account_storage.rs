@@ -1,13 +1,18 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::{Error, Result};
+use anyhow::{ensure, Error, Result};
 use bcs_ext::BCSCodec;
+use parking_lot::Mutex;
 use serde::Deserialize;
 use serde::Serialize;
-use starcoin_account_api::{AccountPrivateKey, AccountPublicKey, Setting};
+use starcoin_account_api::error::AccountError;
+use starcoin_account_api::{
+    AccountPrivateKey, AccountPublicKey, DerivationInfo, Setting, SignerEndpoint, TxnHistoryEntry,
+    TxnHistoryStatus,
+};
 use starcoin_config::RocksdbConfig;
-use starcoin_crypto::ValidCryptoMaterial;
+use starcoin_crypto::{HashValue, ValidCryptoMaterial};
 use starcoin_decrypt::{decrypt, encrypt};
 use starcoin_storage::cache_storage::CacheStorage;
 use starcoin_storage::db_storage::DBStorage;
@@ -18,14 +23,22 @@ use starcoin_storage::{
 };
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::token_code::TokenCode;
+use starcoin_types::block::BlockNumber;
 use std::convert::TryFrom;
 use std::path::Path;
+use std::sync::Arc;
 
 pub const SETTING_PREFIX_NAME: ColumnFamilyName = "account_settings";
 pub const ENCRYPTED_PRIVATE_KEY_PREFIX_NAME: ColumnFamilyName = "encrypted_private_key";
 pub const PUBLIC_KEY_PREFIX_NAME: ColumnFamilyName = "public_key";
 pub const ACCEPTED_TOKEN_PREFIX_NAME: ColumnFamilyName = "accepted_token";
 pub const GLOBAL_PREFIX_NAME: ColumnFamilyName = "global";
+pub const PENDING_KEY_PREFIX_NAME: ColumnFamilyName = "pending_key";
+pub const SIGNER_ENDPOINT_PREFIX_NAME: ColumnFamilyName = "signer_endpoint";
+pub const DERIVATION_PREFIX_NAME: ColumnFamilyName = "account_derivation";
+pub const TXN_HISTORY_PREFIX_NAME: ColumnFamilyName = "account_txn_history";
+pub const TXN_HISTORY_INDEX_PREFIX_NAME: ColumnFamilyName = "account_txn_history_index";
+pub const PASSWORD_UPDATE_PREFIX_NAME: ColumnFamilyName = "account_password_update";
 
 define_storage!(
     AccountSettingStore,
@@ -61,6 +74,49 @@ define_storage!(
     ACCEPTED_TOKEN_PREFIX_NAME
 );
 
+define_storage!(
+    PendingKeyStore,
+    AccountAddressWrapper,
+    PendingKey,
+    PENDING_KEY_PREFIX_NAME
+);
+
+define_storage!(
+    SignerEndpointStore,
+    AccountAddressWrapper,
+    SignerEndpointWrapper,
+    SIGNER_ENDPOINT_PREFIX_NAME
+);
+
+define_storage!(
+    DerivationStore,
+    AccountAddressWrapper,
+    DerivationInfoWrapper,
+    DERIVATION_PREFIX_NAME
+);
+
+define_storage!(
+    TxnHistoryStore,
+    AccountAddressWrapper,
+    TxnHistory,
+    TXN_HISTORY_PREFIX_NAME
+);
+
+// find the sender of a recorded txn by the txn hash.
+define_storage!(
+    TxnHistoryIndexStore,
+    HashValue,
+    AccountAddressWrapper,
+    TXN_HISTORY_INDEX_PREFIX_NAME
+);
+
+define_storage!(
+    PasswordUpdateStore,
+    AccountAddressWrapper,
+    PasswordUpdate,
+    PASSWORD_UPDATE_PREFIX_NAME
+);
+
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct AcceptedTokens(pub Vec<TokenCode>);
 
@@ -133,6 +189,18 @@ impl KeyCodec for AccountAddressWrapper {
     }
 }
 
+impl ValueCodec for AccountAddressWrapper {
+    fn encode_value(&self) -> Result<Vec<u8>, Error> {
+        Ok(self.0.to_vec())
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self, Error> {
+        AccountAddress::try_from(data)
+            .map(AccountAddressWrapper)
+            .map_err(anyhow::Error::new)
+    }
+}
+
 impl ValueCodec for SettingWrapper {
     fn encode_value(&self) -> Result<Vec<u8>, Error> {
         self.0.encode()
@@ -179,6 +247,89 @@ impl ValueCodec for PublicKeyWrapper {
     }
 }
 
+/// The new key of an account waiting for the on-chain authentication key rotation.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PendingKey {
+    public_key: AccountPublicKey,
+    encrypted_private_key: Vec<u8>,
+}
+
+impl ValueCodec for PendingKey {
+    fn encode_value(&self) -> Result<Vec<u8>, Error> {
+        self.encode()
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self, Error> {
+        Self::decode(data)
+    }
+}
+
+pub struct SignerEndpointWrapper(SignerEndpoint);
+impl From<SignerEndpoint> for SignerEndpointWrapper {
+    fn from(endpoint: SignerEndpoint) -> Self {
+        Self(endpoint)
+    }
+}
+
+impl ValueCodec for SignerEndpointWrapper {
+    fn encode_value(&self) -> Result<Vec<u8>, Error> {
+        bcs_ext::to_bytes(&self.0)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self, Error> {
+        Ok(Self(bcs_ext::from_bytes::<SignerEndpoint>(data)?))
+    }
+}
+
+pub struct DerivationInfoWrapper(DerivationInfo);
+impl From<DerivationInfo> for DerivationInfoWrapper {
+    fn from(derivation: DerivationInfo) -> Self {
+        Self(derivation)
+    }
+}
+
+impl ValueCodec for DerivationInfoWrapper {
+    fn encode_value(&self) -> Result<Vec<u8>, Error> {
+        bcs_ext::to_bytes(&self.0)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self, Error> {
+        Ok(Self(bcs_ext::from_bytes::<DerivationInfo>(data)?))
+    }
+}
+
+/// The txn history of an account, from the oldest to the newest.
+/// The keys of an account re-encrypted by the new password,
+/// it is saved before the keys are replaced, and removed after, so an interrupted update can be redone.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PasswordUpdate {
+    encrypted_private_key: Vec<u8>,
+    pending_key: Option<PendingKey>,
+}
+
+impl ValueCodec for PasswordUpdate {
+    fn encode_value(&self) -> Result<Vec<u8>, Error> {
+        self.encode()
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self, Error> {
+        Self::decode(data)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct TxnHistory(pub Vec<TxnHistoryEntry>);
+
+impl ValueCodec for TxnHistory {
+    fn encode_value(&self) -> Result<Vec<u8>, Error> {
+        self.0.encode()
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self, Error> {
+        <Vec<TxnHistoryEntry>>::decode(data).map(TxnHistory)
+    }
+}
+
 #[derive(Clone)]
 pub struct AccountStorage {
     setting_store: AccountSettingStore,
@@ -186,6 +337,15 @@ pub struct AccountStorage {
     public_key_store: PublicKeyStore,
     global_value_store: GlobalSettingStore,
     accepted_token_store: AcceptedTokenStore,
+    pending_key_store: PendingKeyStore,
+    signer_endpoint_store: SignerEndpointStore,
+    derivation_store: DerivationStore,
+    txn_history_store: TxnHistoryStore,
+    txn_history_index_store: TxnHistoryIndexStore,
+    password_update_store: PasswordUpdateStore,
+    /// The txn history of an address is one value, updated by read-modify-write from the account
+    /// service and the account event service, so the updates are serialized by the lock.
+    txn_history_lock: Arc<Mutex<()>>,
 }
 
 impl AccountStorage {
@@ -198,13 +358,21 @@ impl AccountStorage {
                 PUBLIC_KEY_PREFIX_NAME,
                 ACCEPTED_TOKEN_PREFIX_NAME,
                 GLOBAL_PREFIX_NAME,
+                PENDING_KEY_PREFIX_NAME,
+                SIGNER_ENDPOINT_PREFIX_NAME,
+                DERIVATION_PREFIX_NAME,
+                TXN_HISTORY_PREFIX_NAME,
+                TXN_HISTORY_INDEX_PREFIX_NAME,
+                PASSWORD_UPDATE_PREFIX_NAME,
             ],
             false,
             rocksdb_config,
         )?;
         let storage_instance =
             StorageInstance::new_cache_and_db_instance(CacheStorage::default(), db);
-        Ok(Self::new(storage_instance))
+        let storage = Self::new(storage_instance);
+        storage.recover_password_updates()?;
+        Ok(storage)
     }
 
     pub fn new(store: StorageInstance) -> Self {
@@ -213,7 +381,14 @@ impl AccountStorage {
             private_key_store: PrivateKeyStore::new(store.clone()),
             public_key_store: PublicKeyStore::new(store.clone()),
             accepted_token_store: AcceptedTokenStore::new(store.clone()),
+            pending_key_store: PendingKeyStore::new(store.clone()),
+            signer_endpoint_store: SignerEndpointStore::new(store.clone()),
+            derivation_store: DerivationStore::new(store.clone()),
+            txn_history_store: TxnHistoryStore::new(store.clone()),
+            txn_history_index_store: TxnHistoryIndexStore::new(store.clone()),
+            password_update_store: PasswordUpdateStore::new(store.clone()),
             global_value_store: GlobalSettingStore::new(store),
+            txn_history_lock: Arc::new(Mutex::new(())),
         }
     }
 
@@ -291,6 +466,8 @@ impl AccountStorage {
             .map(|w| w.map(|p| p.0))
     }
 
+    /// Return None if the address has no private key,
+    /// and the `AccountError::InvalidPassword` error if the password is wrong.
     pub fn decrypt_private_key(
         &self,
         address: AccountAddress,
@@ -299,7 +476,8 @@ impl AccountStorage {
         match self.private_key_store.get(address.into())? {
             None => Ok(None),
             Some(encrypted_key) => {
-                let plain_key_data = decrypt(password.as_ref().as_bytes(), &encrypted_key.0)?;
+                let plain_key_data = decrypt(password.as_ref().as_bytes(), &encrypted_key.0)
+                    .map_err(|_| AccountError::InvalidPassword(address))?;
                 let private_key = AccountPrivateKey::try_from(plain_key_data.as_slice())?;
                 Ok(Some(private_key))
             }
@@ -321,6 +499,146 @@ impl AccountStorage {
         Ok(())
     }
 
+    /// Re-encrypt the private key of the address, and the pending key if any, by the new password.
+    /// The re-encrypted keys are saved by a single put before they replace the old ones,
+    /// so a crash in the middle is redone by `recover_password_updates` at the next start,
+    /// and the keys are never left encrypted by different passwords.
+    pub fn update_password(
+        &self,
+        address: AccountAddress,
+        private_key: &AccountPrivateKey,
+        old_password: impl AsRef<str>,
+        new_password: impl AsRef<str>,
+    ) -> Result<()> {
+        let update = self.save_password_update(address, private_key, old_password, new_password)?;
+        self.apply_password_update(address, update)
+    }
+
+    /// Save the keys re-encrypted by the new password, without replacing the old ones.
+    pub(crate) fn save_password_update(
+        &self,
+        address: AccountAddress,
+        private_key: &AccountPrivateKey,
+        old_password: impl AsRef<str>,
+        new_password: impl AsRef<str>,
+    ) -> Result<PasswordUpdate> {
+        let key_data = private_key.to_bytes();
+        let encrypted_private_key = encrypt(new_password.as_ref().as_bytes(), &key_data);
+        ensure!(
+            decrypt(new_password.as_ref().as_bytes(), &encrypted_private_key)? == key_data,
+            "The re-encrypted private key of address {} mismatches",
+            address
+        );
+        let pending_key = match self.pending_key_store.get(address.into())? {
+            Some(pending_key) => {
+                let pending_key_data = decrypt(
+                    old_password.as_ref().as_bytes(),
+                    &pending_key.encrypted_private_key,
+                )?;
+                Some(PendingKey {
+                    public_key: pending_key.public_key,
+                    encrypted_private_key: encrypt(
+                        new_password.as_ref().as_bytes(),
+                        &pending_key_data,
+                    ),
+                })
+            }
+            None => None,
+        };
+        let update = PasswordUpdate {
+            encrypted_private_key,
+            pending_key,
+        };
+        self.password_update_store
+            .put(address.into(), update.clone())?;
+        Ok(update)
+    }
+
+    fn apply_password_update(&self, address: AccountAddress, update: PasswordUpdate) -> Result<()> {
+        if let Some(pending_key) = update.pending_key {
+            self.pending_key_store.put(address.into(), pending_key)?;
+        }
+        self.private_key_store
+            .put(address.into(), update.encrypted_private_key.into())?;
+        self.password_update_store.remove(address.into())
+    }
+
+    /// Redo the password updates interrupted by a crash, return the addresses of them.
+    pub fn recover_password_updates(&self) -> Result<Vec<AccountAddress>> {
+        let mut recovered = vec![];
+        for address in self.list_addresses()? {
+            if let Some(update) = self.password_update_store.get(address.into())? {
+                self.apply_password_update(address, update)?;
+                recovered.push(address);
+            }
+        }
+        Ok(recovered)
+    }
+
+    /// Save the new key of the address, it takes effect only after `commit_pending_key`.
+    /// The existing pending key is replaced only if `replace`,
+    /// since its rotation txn may still be executed on chain.
+    pub fn update_pending_key(
+        &self,
+        address: AccountAddress,
+        private_key: &AccountPrivateKey,
+        password: impl AsRef<str>,
+        replace: bool,
+    ) -> Result<()> {
+        ensure!(
+            replace || !self.pending_key_store.contains_key(address.into())?,
+            "The pending key of address {} exists",
+            address
+        );
+        let encrypted_private_key = encrypt(password.as_ref().as_bytes(), &private_key.to_bytes());
+        self.pending_key_store.put(
+            address.into(),
+            PendingKey {
+                public_key: private_key.public_key(),
+                encrypted_private_key,
+            },
+        )
+    }
+
+    pub fn pending_public_key(&self, address: AccountAddress) -> Result<Option<AccountPublicKey>> {
+        self.pending_key_store
+            .get(address.into())
+            .map(|k| k.map(|k| k.public_key))
+    }
+
+    /// Replace the current key of the address with the pending key, return the new public key.
+    /// The pending key is removed at last, so a interrupted commit can be retried.
+    pub fn commit_pending_key(&self, address: AccountAddress) -> Result<Option<AccountPublicKey>> {
+        let pending_key = match self.pending_key_store.get(address.into())? {
+            Some(pending_key) => pending_key,
+            None => return Ok(None),
+        };
+        self.private_key_store
+            .put(address.into(), pending_key.encrypted_private_key.into())?;
+        self.public_key_store
+            .put(address.into(), pending_key.public_key.clone().into())?;
+        self.pending_key_store.remove(address.into())?;
+        Ok(Some(pending_key.public_key))
+    }
+
+    pub fn remove_pending_key(&self, address: AccountAddress) -> Result<()> {
+        self.pending_key_store.remove(address.into())
+    }
+
+    /// Save the public key of a read-only account, which has no private key.
+    pub fn update_public_key(
+        &self,
+        address: AccountAddress,
+        public_key: AccountPublicKey,
+    ) -> Result<()> {
+        self.public_key_store.put(address.into(), public_key.into())
+    }
+
+    /// The account is read-only if it has public key but no private key.
+    pub fn is_readonly(&self, address: AccountAddress) -> Result<bool> {
+        Ok(self.contain_address(address)? && self.private_key_store.get(address.into())?.is_none())
+    }
+
     #[allow(unused)]
     pub fn update_default_settings(
         &self,
@@ -339,10 +657,111 @@ impl AccountStorage {
         self.public_key_store.remove(address.into())?;
         self.setting_store.remove(address.into())?;
         self.accepted_token_store.remove(address.into())?;
+        self.pending_key_store.remove(address.into())?;
+        self.signer_endpoint_store.remove(address.into())?;
+        self.derivation_store.remove(address.into())?;
+        self.password_update_store.remove(address.into())?;
+        let _guard = self.txn_history_lock.lock();
+        for entry in self.get_txn_history(address)? {
+            self.txn_history_index_store.remove(entry.txn.id())?;
+        }
+        self.txn_history_store.remove(address.into())?;
 
         Ok(())
     }
 
+    /// The txn history of the address, from the oldest to the newest.
+    pub fn get_txn_history(&self, address: AccountAddress) -> Result<Vec<TxnHistoryEntry>> {
+        Ok(self
+            .txn_history_store
+            .get(address.into())?
+            .map(|history| history.0)
+            .unwrap_or_default())
+    }
+
+    /// Add the txn to the history of its sender if it is not recorded yet,
+    /// the oldest entries are pruned to keep at most `max_len` entries.
+    pub fn add_txn_history(&self, entry: TxnHistoryEntry, max_len: usize) -> Result<()> {
+        let _guard = self.txn_history_lock.lock();
+        let txn_hash = entry.txn.id();
+        if max_len == 0 || self.txn_history_index_store.get(txn_hash)?.is_some() {
+            return Ok(());
+        }
+        let address = entry.txn.sender();
+        let mut history = self.get_txn_history(address)?;
+        history.push(entry);
+        let pruned = history.len().saturating_sub(max_len);
+        for entry in history.drain(..pruned) {
+            self.txn_history_index_store.remove(entry.txn.id())?;
+        }
+        self.txn_history_store
+            .put(address.into(), TxnHistory(history))?;
+        self.txn_history_index_store.put(txn_hash, address.into())
+    }
+
+    /// Mark the recorded txn as mined in the block, and the pending txns with the same sequence number as replaced.
+    /// Return false if the txn is not recorded.
+    pub fn mark_txn_mined(
+        &self,
+        txn_hash: HashValue,
+        block_hash: HashValue,
+        block_number: BlockNumber,
+    ) -> Result<bool> {
+        self.update_txn_history(txn_hash, |entry, is_target| {
+            if is_target {
+                entry.status = TxnHistoryStatus::Mined;
+                entry.block_hash = Some(block_hash);
+                entry.block_number = Some(block_number);
+            } else if entry.status == TxnHistoryStatus::Pending {
+                entry.status = TxnHistoryStatus::Replaced;
+            }
+        })
+    }
+
+    /// Mark the txn mined in the retracted block as pending again, with the txns replaced by it.
+    /// Return false if the txn is not recorded.
+    pub fn mark_txn_retracted(&self, txn_hash: HashValue, block_hash: HashValue) -> Result<bool> {
+        self.update_txn_history(txn_hash, |entry, is_target| {
+            let retracted = if is_target {
+                entry.block_hash == Some(block_hash)
+            } else {
+                entry.status == TxnHistoryStatus::Replaced
+            };
+            if retracted {
+                entry.status = TxnHistoryStatus::Pending;
+                entry.block_hash = None;
+                entry.block_number = None;
+            }
+        })
+    }
+
+    /// Call `f` on the entry of the txn and the other entries with the same sequence number,
+    /// the second argument of `f` is true for the entry of the txn.
+    fn update_txn_history<F>(&self, txn_hash: HashValue, mut f: F) -> Result<bool>
+    where
+        F: FnMut(&mut TxnHistoryEntry, bool),
+    {
+        let _guard = self.txn_history_lock.lock();
+        let address = match self.txn_history_index_store.get(txn_hash)? {
+            Some(address) => address.0,
+            None => return Ok(false),
+        };
+        let mut history = self.get_txn_history(address)?;
+        let hashes: Vec<HashValue> = history.iter().map(|entry| entry.txn.id()).collect();
+        let sequence_number = match hashes.iter().position(|hash| *hash == txn_hash) {
+            Some(i) => history[i].txn.sequence_number(),
+            None => return Ok(false),
+        };
+        for (entry, hash) in history.iter_mut().zip(hashes) {
+            if entry.txn.sequence_number() == sequence_number {
+                f(entry, hash == txn_hash);
+            }
+        }
+        self.txn_history_store
+            .put(address.into(), TxnHistory(history))?;
+        Ok(true)
+    }
+
     pub fn get_accepted_tokens(&self, address: AccountAddress) -> Result<Vec<TokenCode>> {
         let ts = self.accepted_token_store.get(address.into())?;
         Ok(ts.map(|t| t.0).unwrap_or_default())
@@ -365,4 +784,36 @@ impl AccountStorage {
         }
         Ok(())
     }
+
+    pub fn get_signer_endpoint(&self, address: AccountAddress) -> Result<Option<SignerEndpoint>> {
+        Ok(self
+            .signer_endpoint_store
+            .get(address.into())?
+            .map(|endpoint| endpoint.0))
+    }
+
+    pub fn set_signer_endpoint(
+        &self,
+        address: AccountAddress,
+        endpoint: SignerEndpoint,
+    ) -> Result<()> {
+        self.signer_endpoint_store
+            .put(address.into(), endpoint.into())
+    }
+
+    /// The derivation of the account, it is none if the account is not imported from a mnemonic.
+    pub fn get_derivation(&self, address: AccountAddress) -> Result<Option<DerivationInfo>> {
+        Ok(self
+            .derivation_store
+            .get(address.into())?
+            .map(|derivation| derivation.0))
+    }
+
+    pub fn set_derivation(
+        &self,
+        address: AccountAddress,
+        derivation: DerivationInfo,
+    ) -> Result<()> {
+        self.derivation_store.put(address.into(), derivation.into())
+    }
 }
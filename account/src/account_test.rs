@@ -2,12 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::account_storage::AccountStorage;
+use crate::keystore::{
+    KeyStore, KeyType, DEFAULT_SCRYPT_LOG_N, DEFAULT_SCRYPT_P, DEFAULT_SCRYPT_R, MAX_SCRYPT_LOG_N,
+    MAX_SCRYPT_P, MAX_SCRYPT_R,
+};
 use crate::Account;
 use crate::AccountManager;
 use anyhow::Result;
 use starcoin_account_api::error::AccountError;
+use starcoin_account_api::{AccountPrivateKey, SignerEndpoint, TxnHistoryEntry, TxnHistoryStatus};
 use starcoin_config::RocksdbConfig;
-use starcoin_crypto::{SigningKey, ValidCryptoMaterial};
+use starcoin_crypto::multi_ed25519::multi_shard::MultiEd25519KeyShard;
+use starcoin_crypto::{HashValue, SigningKey, ValidCryptoMaterial};
 use starcoin_types::access_path::AccessPath;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::genesis_config::ChainId;
@@ -16,6 +22,7 @@ use starcoin_types::language_storage::{StructTag, CORE_CODE_ADDRESS};
 use starcoin_types::transaction::{
     RawUserTransaction, Script, SignedUserTransaction, TransactionPayload,
 };
+use std::convert::TryFrom;
 use std::time::Duration;
 
 #[test]
@@ -98,7 +105,7 @@ pub fn test_wallet_unlock() -> Result<()> {
 
     // test on wallet change password
     {
-        manager.change_password(*wallet.address(), "hell0")?;
+        manager.change_password(*wallet.address(), None, "hell0")?;
         let unlock_result =
             manager.unlock_account(*wallet.address(), "hello", Duration::from_secs(10));
         assert!(unlock_result.is_err());
@@ -111,6 +118,147 @@ pub fn test_wallet_unlock() -> Result<()> {
     Ok(())
 }
 
+#[test]
+pub fn test_change_password_and_remove() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage)?;
+    let default_account = *manager.create_account("hello")?.address();
+    manager.set_default_account(default_account)?;
+    let account = *manager.create_account("hello")?.address();
+    let public_key = manager.account_info(account)?.unwrap().public_key;
+
+    // the locked account needs the old password.
+    assert!(matches!(
+        manager.change_password(account, None, "world").unwrap_err(),
+        AccountError::AccountLocked(addr) if addr == account
+    ));
+    assert!(matches!(
+        manager.change_password(account, Some("hell0".to_string()), "world").unwrap_err(),
+        AccountError::InvalidPassword(addr) if addr == account
+    ));
+    manager.change_password(account, Some("hello".to_string()), "world")?;
+    assert!(matches!(
+        manager.unlock_account(account, "hello", Duration::from_secs(10)).unwrap_err(),
+        AccountError::InvalidPassword(addr) if addr == account
+    ));
+    // the key still signs after the password change.
+    manager.unlock_account(account, "world", Duration::from_secs(10))?;
+    let raw_txn = RawUserTransaction::new_with_default_gas_token(
+        account,
+        1,
+        TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+        1000,
+        1,
+        100000,
+        ChainId::new(1),
+    );
+    let signed_txn = manager.sign_txn(account, raw_txn)?;
+    assert_eq!(
+        signed_txn.authenticator().public_key().to_bytes(),
+        public_key.to_bytes()
+    );
+    assert!(signed_txn.check_signature().is_ok());
+
+    // remove needs the right password, and the default account needs force.
+    let private_key = manager.export_account(account, "world")?;
+    assert!(matches!(
+        manager.remove_account(account, "hello", false).unwrap_err(),
+        AccountError::InvalidPassword(addr) if addr == account
+    ));
+    assert!(matches!(
+        manager
+            .remove_account(AccountAddress::random(), "world", false)
+            .unwrap_err(),
+        AccountError::AccountNotExist(_)
+    ));
+    manager.remove_account(account, "world", false)?;
+    assert!(manager.account_info(account)?.is_none());
+    assert!(matches!(
+        manager.remove_account(default_account, "hello", false).unwrap_err(),
+        AccountError::RemoveDefaultAccountError(addr) if addr == default_account
+    ));
+    manager.remove_account(default_account, "hello", true)?;
+    assert!(manager.default_account_info()?.is_none());
+
+    // the removed account can be imported again from the exported key.
+    let imported = manager.import_account(account, private_key, "again")?;
+    assert_eq!(imported.public_key().to_bytes(), public_key.to_bytes());
+    Ok(())
+}
+
+#[test]
+pub fn test_recover_interrupted_password_update() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage.clone())?;
+    let account = *manager.create_account("hello")?.address();
+    let private_key = manager.export_account(account, "hello")?;
+    let private_key = AccountPrivateKey::try_from(private_key.as_slice())?;
+
+    // the update is saved, but the node crashes before the key is replaced.
+    storage.save_password_update(account, &private_key, "hello", "world")?;
+    assert!(storage.decrypt_private_key(account, "hello")?.is_some());
+    drop(manager);
+    drop(storage);
+
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    assert!(storage.recover_password_updates()?.is_empty());
+    assert!(storage.decrypt_private_key(account, "hello").is_err());
+    assert_eq!(
+        storage
+            .decrypt_private_key(account, "world")?
+            .unwrap()
+            .to_bytes(),
+        private_key.to_bytes()
+    );
+    Ok(())
+}
+
+#[test]
+pub fn test_unlock_expired_and_lock_all() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage)?;
+
+    let account1 = *manager.create_account("hello")?.address();
+    let account2 = *manager.create_account("hello")?.address();
+    let info = manager.account_info(account1)?.unwrap();
+    assert!(info.is_locked);
+    assert!(info.unlocked_until.is_none());
+
+    manager.unlock_account(account1, "hello", Duration::from_millis(200))?;
+    manager.unlock_account(account2, "hello", Duration::from_secs(100))?;
+    let info = manager.account_info(account1)?.unwrap();
+    assert!(!info.is_locked);
+    assert!(info.unlocked_until.is_some());
+
+    std::thread::sleep(Duration::from_millis(300));
+    assert_eq!(manager.lock_expired(), vec![account1]);
+    let fake_txn = RawUserTransaction::new_with_default_gas_token(
+        account1,
+        1,
+        TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+        1000,
+        1,
+        100000,
+        ChainId::new(1),
+    );
+    match manager.sign_txn(account1, fake_txn) {
+        Err(AccountError::AccountLocked(address)) => assert_eq!(address, account1),
+        other => panic!("expect account locked error, but got {:?}", other),
+    }
+    let infos = manager.list_account_infos()?;
+    for info in infos {
+        assert_eq!(info.is_locked, info.address == account1);
+    }
+
+    assert_eq!(manager.lock_all(), vec![account2]);
+    assert!(manager.account_info(account2)?.unwrap().is_locked);
+    assert!(manager.lock_all().is_empty());
+    Ok(())
+}
+
 // ignore for now.
 #[ignore]
 #[test]
@@ -213,3 +361,388 @@ pub fn test_wallet_account() -> Result<()> {
     println!("txn hash is {:?}", stxn.id());
     Ok(())
 }
+
+#[test]
+pub fn test_keystore() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage)?;
+    let account = manager.create_account("hello")?;
+    let keystore = manager.export_keystore(*account.address(), "hello")?;
+
+    let tempdir2 = tempfile::tempdir()?;
+    let storage2 = AccountStorage::create_from_path(tempdir2.path(), RocksdbConfig::default())?;
+    let manager2 = AccountManager::new(storage2)?;
+
+    let result = manager2.import_keystore(keystore.as_str(), "world");
+    assert!(
+        matches!(result.err().unwrap(), AccountError::InvalidPassword(addr) if addr == *account.address())
+    );
+    let result = manager2.import_keystore(&keystore[1..], "hello");
+    assert!(matches!(
+        result.err().unwrap(),
+        AccountError::InvalidKeyStore(_)
+    ));
+
+    let imported = manager2.import_keystore(keystore.as_str(), "hello")?;
+    assert_eq!(imported.address(), account.address());
+    assert_eq!(
+        imported.private_key().to_bytes(),
+        account.private_key().to_bytes()
+    );
+    Ok(())
+}
+
+#[test]
+pub fn test_multi_ed25519_keystore() -> Result<()> {
+    let mut rng = rand::rngs::OsRng;
+    let shard = MultiEd25519KeyShard::generate(&mut rng, 3, 2)?
+        .pop()
+        .unwrap();
+    let private_key = AccountPrivateKey::Multi(shard);
+    let address = private_key.public_key().derived_address();
+    let keystore = KeyStore::encrypt(address, &private_key, "hello")?;
+    assert_eq!(keystore.key_type, KeyType::MultiEd25519);
+
+    let keystore = KeyStore::from_json(keystore.to_json().as_str())?;
+    let decrypted = keystore.decrypt("hello")?;
+    assert!(matches!(decrypted, AccountPrivateKey::Multi(_)));
+    assert_eq!(decrypted.public_key(), private_key.public_key());
+
+    let mut invalid = keystore;
+    invalid.key_type = KeyType::Ed25519;
+    assert!(matches!(
+        invalid.decrypt("hello").err().unwrap(),
+        AccountError::InvalidKeyStore(_)
+    ));
+    Ok(())
+}
+
+#[test]
+pub fn test_keystore_scrypt_params_limit() -> Result<()> {
+    let (private_key, _) = starcoin_crypto::ed25519::genesis_key_pair();
+    let private_key = AccountPrivateKey::Single(private_key);
+    let address = private_key.public_key().derived_address();
+    let keystore = KeyStore::encrypt(address, &private_key, "hello")?;
+
+    let invalid_params = vec![
+        (
+            1u64 << (MAX_SCRYPT_LOG_N + 1),
+            DEFAULT_SCRYPT_R,
+            DEFAULT_SCRYPT_P,
+        ),
+        (1u64 << 63, DEFAULT_SCRYPT_R, DEFAULT_SCRYPT_P),
+        (1u64 << DEFAULT_SCRYPT_LOG_N, 0, DEFAULT_SCRYPT_P),
+        (
+            1u64 << DEFAULT_SCRYPT_LOG_N,
+            MAX_SCRYPT_R + 1,
+            DEFAULT_SCRYPT_P,
+        ),
+        (1u64 << DEFAULT_SCRYPT_LOG_N, DEFAULT_SCRYPT_R, 0),
+        (
+            1u64 << DEFAULT_SCRYPT_LOG_N,
+            DEFAULT_SCRYPT_R,
+            MAX_SCRYPT_P + 1,
+        ),
+        // both n and r are in range, but the memory is too large.
+        (1u64 << MAX_SCRYPT_LOG_N, MAX_SCRYPT_R, DEFAULT_SCRYPT_P),
+    ];
+    for (n, r, p) in invalid_params {
+        let mut invalid = keystore.clone();
+        invalid.crypto.kdfparams.n = n;
+        invalid.crypto.kdfparams.r = r;
+        invalid.crypto.kdfparams.p = p;
+        // rejected before the key derivation, the password is not checked.
+        assert!(matches!(
+            invalid.decrypt("wrong").err().unwrap(),
+            AccountError::InvalidKeyStore(_)
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+pub fn test_readonly_account() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage)?;
+    let wallet = manager.create_account("hello")?;
+
+    let public_key =
+        AccountPrivateKey::Single(super::account_manager::gen_private_key()).public_key();
+    let address = public_key.derived_address();
+    let account_info = manager.import_readonly_account(address, public_key.to_bytes())?;
+    assert!(account_info.is_readonly);
+    assert_eq!(account_info.public_key, public_key);
+
+    let result = manager.import_readonly_account(address, public_key.to_bytes());
+    assert!(
+        matches!(result.err().unwrap(), AccountError::AccountAlreadyExist(addr) if addr == address)
+    );
+
+    let account_infos = manager.list_account_infos()?;
+    assert_eq!(account_infos.len(), 2);
+    for account_info in account_infos {
+        assert_eq!(account_info.is_readonly, account_info.address == address);
+    }
+
+    let result = manager.unlock_account(address, "hello", Duration::from_secs(100));
+    assert!(
+        matches!(result.err().unwrap(), AccountError::AccountReadOnly(addr) if addr == address)
+    );
+    let fake_txn = RawUserTransaction::new_with_default_gas_token(
+        address,
+        1,
+        TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+        1000,
+        1,
+        100000,
+        ChainId::new(1),
+    );
+    let result = manager.sign_txn(address, fake_txn);
+    assert!(
+        matches!(result.err().unwrap(), AccountError::AccountReadOnly(addr) if addr == address)
+    );
+    assert!(
+        !manager
+            .account_info(*wallet.address())?
+            .unwrap()
+            .is_readonly
+    );
+    Ok(())
+}
+
+#[test]
+pub fn test_rotate_key() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage)?;
+    let wallet = manager.create_account("hello")?;
+    let address = *wallet.address();
+    let old_public_key = wallet.public_key();
+
+    let result = manager.prepare_rotate_key(address, None, false, false);
+    assert!(matches!(result.err().unwrap(), AccountError::AccountLocked(addr) if addr == address));
+    manager.unlock_account(address, "hello", Duration::from_secs(100))?;
+
+    // dry run does not save the new key.
+    let _ = manager.prepare_rotate_key(address, None, true, false)?;
+    let new_public_key = manager.prepare_rotate_key(address, None, true, false)?;
+    assert!(manager
+        .commit_rotate_key(address, new_public_key.authentication_key())
+        .is_err());
+
+    // the old key is kept before commit, and after abort.
+    let new_public_key = manager.prepare_rotate_key(address, None, false, false)?;
+    // the pending key is not overwritten unless it is replaced explicitly.
+    assert!(matches!(
+        manager.prepare_rotate_key(address, None, false, false).unwrap_err(),
+        AccountError::PendingKeyExists(addr) if addr == address
+    ));
+    let replaced_public_key = manager.prepare_rotate_key(address, None, false, true)?;
+    assert!(manager
+        .commit_rotate_key(address, new_public_key.authentication_key())
+        .is_err());
+    let new_public_key = replaced_public_key;
+    assert_eq!(
+        manager.account_info(address)?.unwrap().public_key,
+        old_public_key
+    );
+    manager.abort_rotate_key(address)?;
+    assert!(manager
+        .commit_rotate_key(address, new_public_key.authentication_key())
+        .is_err());
+    assert_eq!(
+        manager.account_info(address)?.unwrap().public_key,
+        old_public_key
+    );
+
+    let new_private_key = AccountPrivateKey::from(crate::account_manager::gen_private_key());
+    let new_public_key =
+        manager.prepare_rotate_key(address, Some(new_private_key.to_bytes()), false, false)?;
+    assert_eq!(new_public_key, new_private_key.public_key());
+    // the pending key should match the on-chain auth key.
+    assert!(manager
+        .commit_rotate_key(address, old_public_key.authentication_key())
+        .is_err());
+    let account_info = manager.commit_rotate_key(address, new_public_key.authentication_key())?;
+    assert_eq!(account_info.public_key, new_public_key);
+
+    let fake_txn = RawUserTransaction::new_with_default_gas_token(
+        address,
+        1,
+        TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+        1000,
+        1,
+        100000,
+        ChainId::new(1),
+    );
+    let signed_txn = manager.sign_txn(address, fake_txn)?;
+    assert_eq!(signed_txn.authenticator().public_key(), new_public_key);
+    // the new key is encrypted by the same password.
+    assert_eq!(
+        manager.export_account(address, "hello")?,
+        new_private_key.to_bytes()
+    );
+    Ok(())
+}
+
+#[test]
+pub fn test_bind_external_signer() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage)?;
+    let account = manager.create_account("hello")?;
+    assert!(manager.signer_endpoint(*account.address())?.is_none());
+
+    let endpoint = SignerEndpoint::Unix("/var/run/signer.sock".to_string());
+    manager.bind_external_signer(*account.address(), endpoint.clone())?;
+    assert_eq!(manager.signer_endpoint(*account.address())?, Some(endpoint));
+
+    // the account which is not in the wallet can be bound too.
+    let address = AccountAddress::random();
+    let endpoint = SignerEndpoint::Http("http://127.0.0.1:8080".to_string());
+    manager.bind_external_signer(address, endpoint.clone())?;
+    assert_eq!(manager.signer_endpoint(address)?, Some(endpoint));
+    Ok(())
+}
+
+#[test]
+pub fn test_import_from_mnemonic() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage)?;
+
+    let mnemonic = manager.generate_mnemonic(12)?;
+    let derived = manager.derive_account(mnemonic.as_str(), 1)?;
+    let account_info = manager.import_from_mnemonic(mnemonic.as_str(), 1, "hello")?;
+    assert_eq!(account_info.address, derived.address);
+    assert_eq!(account_info.public_key, derived.public_key);
+    assert_eq!(account_info.derivation, Some(derived.derivation));
+    assert!(account_info.is_default);
+    assert!(matches!(
+        manager.import_from_mnemonic(mnemonic.as_str(), 1, "hello"),
+        Err(AccountError::AccountAlreadyExist(addr)) if addr == derived.address
+    ));
+    assert!(matches!(
+        manager.import_from_mnemonic("hello world", 0, "hello"),
+        Err(AccountError::InvalidMnemonic(_))
+    ));
+
+    let wallet = manager.create_account("hello")?;
+    let account_infos = manager.list_account_infos()?;
+    assert_eq!(account_infos.len(), 2);
+    assert!(account_infos
+        .iter()
+        .find(|info| &info.address == wallet.address())
+        .map(|info| info.derivation.is_none())
+        .unwrap_or(false));
+    Ok(())
+}
+
+#[test]
+pub fn test_txn_history() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage.clone())?;
+    let wallet = manager.create_account("hello")?;
+    let address = *wallet.address();
+    manager.unlock_account(address, "hello", Duration::from_secs(60))?;
+    let sign = |sequence_number: u64, gas_unit_price: u64| {
+        let raw_txn = RawUserTransaction::new_with_default_gas_token(
+            address,
+            sequence_number,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            1000,
+            gas_unit_price,
+            100000,
+            ChainId::new(1),
+        );
+        manager.sign_txn(address, raw_txn)
+    };
+    let txn0 = sign(0, 1)?;
+    let txn0_replacement = sign(0, 2)?;
+    let txn1 = sign(1, 1)?;
+    for txn in vec![txn0.clone(), txn0_replacement.clone(), txn1.clone()] {
+        manager.record_txn(txn, 2)?;
+    }
+    // the oldest txn is pruned, and recording again is ignored.
+    manager.record_txn(txn1.clone(), 2)?;
+    let history = manager.txn_history(address, 0, 10)?;
+    assert_eq!(
+        history.iter().map(|e| e.txn.id()).collect::<Vec<_>>(),
+        vec![txn1.id(), txn0_replacement.id()]
+    );
+    assert!(!storage.mark_txn_mined(txn0.id(), HashValue::random(), 1)?);
+
+    let block_hash = HashValue::random();
+    manager.record_txn(txn0.clone(), 3)?;
+    assert!(storage.mark_txn_mined(txn0.id(), block_hash, 1)?);
+    let history = manager.txn_history(address, 1, 10)?;
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].txn.id(), txn1.id());
+    assert_eq!(history[0].status, TxnHistoryStatus::Pending);
+    assert_eq!(history[1].txn.id(), txn0_replacement.id());
+    assert_eq!(history[1].status, TxnHistoryStatus::Replaced);
+    let mined = &manager.txn_history(address, 0, 1)?[0];
+    assert_eq!(mined.status, TxnHistoryStatus::Mined);
+    assert_eq!(mined.block_hash, Some(block_hash));
+    assert_eq!(mined.block_number, Some(1));
+
+    assert!(storage.mark_txn_retracted(txn0.id(), block_hash)?);
+    assert!(manager
+        .txn_history(address, 0, 10)?
+        .iter()
+        .all(|e| e.status == TxnHistoryStatus::Pending && e.block_hash.is_none()));
+    Ok(())
+}
+
+#[test]
+pub fn test_txn_history_concurrent_update() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage.clone())?;
+    let wallet = manager.create_account("hello")?;
+    let address = *wallet.address();
+    manager.unlock_account(address, "hello", Duration::from_secs(60))?;
+    let txns = (0..40u64)
+        .map(|sequence_number| {
+            let raw_txn = RawUserTransaction::new_with_default_gas_token(
+                address,
+                sequence_number,
+                TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+                1000,
+                1,
+                100000,
+                ChainId::new(1),
+            );
+            manager.sign_txn(address, raw_txn)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    // record the txns and mark the recorded txns mined from several threads at the same time,
+    // no update is lost.
+    let handles: Vec<_> = txns
+        .chunks(10)
+        .map(|txns| {
+            let storage = storage.clone();
+            let txns = txns.to_vec();
+            std::thread::spawn(move || -> Result<()> {
+                for txn in txns {
+                    let txn_hash = txn.id();
+                    storage.add_txn_history(TxnHistoryEntry::new(txn, 0), 100)?;
+                    assert!(storage.mark_txn_mined(txn_hash, HashValue::random(), 1)?);
+                }
+                Ok(())
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().expect("thread should not panic")?;
+    }
+    let history = storage.get_txn_history(address)?;
+    assert_eq!(history.len(), txns.len());
+    assert!(history
+        .iter()
+        .all(|e| e.status == TxnHistoryStatus::Mined && e.block_number == Some(1)));
+    Ok(())
+}
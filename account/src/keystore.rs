@@ -0,0 +1,257 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Encrypted keystore file of an account, it follows the web3 secret storage format,
+//! use scrypt to derive the key and AES-128-CTR to encrypt the private key,
+//! and record the key type for restoring the right `AccountPrivateKey` variant.
+
+use aes::cipher::{NewCipher, StreamCipher};
+use aes::Aes128Ctr;
+use rand::RngCore;
+use serde::Deserialize;
+use serde::Serialize;
+use sha3::{Digest, Keccak256};
+use starcoin_account_api::error::AccountError;
+use starcoin_account_api::{AccountPrivateKey, AccountResult};
+use starcoin_crypto::ed25519::Ed25519PrivateKey;
+use starcoin_crypto::multi_ed25519::multi_shard::MultiEd25519KeyShard;
+use starcoin_crypto::ValidCryptoMaterial;
+use starcoin_types::account_address::AccountAddress;
+use std::convert::TryFrom;
+
+pub const KEYSTORE_VERSION: u32 = 1;
+pub const CIPHER_AES_128_CTR: &str = "aes-128-ctr";
+pub const KDF_SCRYPT: &str = "scrypt";
+pub const DEFAULT_SCRYPT_LOG_N: u8 = 14;
+pub const DEFAULT_SCRYPT_R: u32 = 8;
+pub const DEFAULT_SCRYPT_P: u32 = 1;
+/// The max scrypt params accepted when decrypting an imported keystore, the key derivation of
+/// larger params costs too much memory or cpu.
+pub const MAX_SCRYPT_LOG_N: u8 = 20;
+pub const MAX_SCRYPT_R: u32 = 32;
+pub const MAX_SCRYPT_P: u32 = 16;
+/// The max memory of the key derivation, scrypt uses `128 * n * r` bytes.
+const MAX_SCRYPT_MEMORY: u64 = 1024 * 1024 * 1024;
+const DERIVED_KEY_LEN: usize = 32;
+const SALT_LEN: usize = 32;
+const IV_LEN: usize = 16;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyType {
+    Ed25519,
+    MultiEd25519,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CipherParams {
+    pub iv: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ScryptParams {
+    pub dklen: u32,
+    pub n: u64,
+    pub r: u32,
+    pub p: u32,
+    pub salt: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KeyStoreCrypto {
+    pub cipher: String,
+    pub cipherparams: CipherParams,
+    pub ciphertext: String,
+    pub kdf: String,
+    pub kdfparams: ScryptParams,
+    pub mac: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KeyStore {
+    pub version: u32,
+    pub address: AccountAddress,
+    pub key_type: KeyType,
+    pub crypto: KeyStoreCrypto,
+}
+
+impl KeyStore {
+    /// Encrypt the `private_key` of `address` with `password`.
+    pub fn encrypt(
+        address: AccountAddress,
+        private_key: &AccountPrivateKey,
+        password: &str,
+    ) -> AccountResult<Self> {
+        let key_type = match private_key {
+            AccountPrivateKey::Single(_) => KeyType::Ed25519,
+            AccountPrivateKey::Multi(_) => KeyType::MultiEd25519,
+        };
+        let mut salt = [0u8; SALT_LEN];
+        rand::thread_rng().fill_bytes(&mut salt);
+        let mut iv = [0u8; IV_LEN];
+        rand::thread_rng().fill_bytes(&mut iv);
+        let kdfparams = ScryptParams {
+            dklen: DERIVED_KEY_LEN as u32,
+            n: 1u64 << DEFAULT_SCRYPT_LOG_N,
+            r: DEFAULT_SCRYPT_R,
+            p: DEFAULT_SCRYPT_P,
+            salt: hex::encode(salt),
+        };
+        let derived_key = derive_key(
+            password,
+            &salt,
+            DEFAULT_SCRYPT_LOG_N,
+            DEFAULT_SCRYPT_R,
+            DEFAULT_SCRYPT_P,
+        )?;
+        let mut ciphertext = private_key.to_bytes();
+        aes_128_ctr(&derived_key[..16], &iv, &mut ciphertext)?;
+        let mac = mac(&derived_key, &ciphertext);
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            address,
+            key_type,
+            crypto: KeyStoreCrypto {
+                cipher: CIPHER_AES_128_CTR.to_string(),
+                cipherparams: CipherParams {
+                    iv: hex::encode(iv),
+                },
+                ciphertext: hex::encode(ciphertext),
+                kdf: KDF_SCRYPT.to_string(),
+                kdfparams,
+                mac: hex::encode(mac),
+            },
+        })
+    }
+
+    /// Decrypt the private key with `password`,
+    /// return `AccountError::InvalidPassword` if the password is wrong,
+    /// and `AccountError::InvalidKeyStore` if the keystore is malformed.
+    pub fn decrypt(&self, password: &str) -> AccountResult<AccountPrivateKey> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(invalid_keystore(format!(
+                "unsupported version {}",
+                self.version
+            )));
+        }
+        let crypto = &self.crypto;
+        if crypto.cipher != CIPHER_AES_128_CTR {
+            return Err(invalid_keystore(format!(
+                "unsupported cipher {}",
+                crypto.cipher
+            )));
+        }
+        if crypto.kdf != KDF_SCRYPT {
+            return Err(invalid_keystore(format!("unsupported kdf {}", crypto.kdf)));
+        }
+        let params = &crypto.kdfparams;
+        if params.dklen as usize != DERIVED_KEY_LEN {
+            return Err(invalid_keystore(format!(
+                "unsupported dklen {}",
+                params.dklen
+            )));
+        }
+        if !params.n.is_power_of_two() || params.n < 2 {
+            return Err(invalid_keystore(format!(
+                "scrypt n {} is not a power of two",
+                params.n
+            )));
+        }
+        let log_n = params.n.trailing_zeros() as u8;
+        if log_n > MAX_SCRYPT_LOG_N {
+            return Err(invalid_keystore(format!(
+                "scrypt n {} exceeds the max 2^{}",
+                params.n, MAX_SCRYPT_LOG_N
+            )));
+        }
+        if params.r == 0 || params.r > MAX_SCRYPT_R {
+            return Err(invalid_keystore(format!(
+                "scrypt r {} is out of range [1, {}]",
+                params.r, MAX_SCRYPT_R
+            )));
+        }
+        if params.p == 0 || params.p > MAX_SCRYPT_P {
+            return Err(invalid_keystore(format!(
+                "scrypt p {} is out of range [1, {}]",
+                params.p, MAX_SCRYPT_P
+            )));
+        }
+        if 128 * params.n * params.r as u64 > MAX_SCRYPT_MEMORY {
+            return Err(invalid_keystore(format!(
+                "scrypt n {} and r {} need more than {} bytes memory",
+                params.n, params.r, MAX_SCRYPT_MEMORY
+            )));
+        }
+        let salt = decode_hex("salt", &params.salt)?;
+        let iv = decode_hex("iv", &crypto.cipherparams.iv)?;
+        if iv.len() != IV_LEN {
+            return Err(invalid_keystore(format!("invalid iv length {}", iv.len())));
+        }
+        let ciphertext = decode_hex("ciphertext", &crypto.ciphertext)?;
+        let expect_mac = decode_hex("mac", &crypto.mac)?;
+
+        let derived_key = derive_key(password, &salt, log_n, params.r, params.p)?;
+        if mac(&derived_key, &ciphertext).as_slice() != expect_mac.as_slice() {
+            return Err(AccountError::InvalidPassword(self.address));
+        }
+        let mut plaintext = ciphertext;
+        aes_128_ctr(&derived_key[..16], &iv, &mut plaintext)?;
+        let private_key = match self.key_type {
+            KeyType::Ed25519 => {
+                Ed25519PrivateKey::try_from(plaintext.as_slice()).map(AccountPrivateKey::Single)
+            }
+            KeyType::MultiEd25519 => {
+                MultiEd25519KeyShard::try_from(plaintext.as_slice()).map(AccountPrivateKey::Multi)
+            }
+        };
+        private_key.map_err(|e| {
+            invalid_keystore(format!("invalid {:?} private key, {}", self.key_type, e))
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("serialize keystore should never fail")
+    }
+
+    pub fn from_json(json: &str) -> AccountResult<Self> {
+        serde_json::from_str(json).map_err(|e| invalid_keystore(e.to_string()))
+    }
+}
+
+fn invalid_keystore(msg: String) -> AccountError {
+    AccountError::InvalidKeyStore(msg)
+}
+
+fn decode_hex(name: &str, value: &str) -> AccountResult<Vec<u8>> {
+    hex::decode(value.strip_prefix("0x").unwrap_or(value))
+        .map_err(|e| invalid_keystore(format!("invalid {}, {}", name, e)))
+}
+
+fn derive_key(
+    password: &str,
+    salt: &[u8],
+    log_n: u8,
+    r: u32,
+    p: u32,
+) -> AccountResult<[u8; DERIVED_KEY_LEN]> {
+    let params = scrypt::Params::new(log_n, r, p)
+        .map_err(|e| invalid_keystore(format!("invalid scrypt params, {}", e)))?;
+    let mut derived_key = [0u8; DERIVED_KEY_LEN];
+    scrypt::scrypt(password.as_bytes(), salt, &params, &mut derived_key)
+        .map_err(|e| invalid_keystore(format!("scrypt error, {}", e)))?;
+    Ok(derived_key)
+}
+
+fn aes_128_ctr(key: &[u8], iv: &[u8], data: &mut [u8]) -> AccountResult<()> {
+    let mut cipher = Aes128Ctr::new_from_slices(key, iv)
+        .map_err(|e| invalid_keystore(format!("invalid cipher params, {}", e)))?;
+    cipher.apply_keystream(data);
+    Ok(())
+}
+
+fn mac(derived_key: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+    let mut hasher = Keccak256::new();
+    hasher.update(&derived_key[16..32]);
+    hasher.update(ciphertext);
+    hasher.finalize().to_vec()
+}
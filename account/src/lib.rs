@@ -7,6 +7,8 @@ mod account_manager;
 pub use account::Account;
 pub use account_manager::AccountManager;
 pub mod account_storage;
+pub mod keystore;
+pub mod mnemonic;
 
 #[cfg(test)]
 mod account_test;
@@ -0,0 +1,247 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! BIP-39 mnemonic and SLIP-0010 ed25519 key derivation,
+//! so the accounts derived from a mnemonic can be recreated by other wallets following the same path.
+
+use bip39::{Language, Mnemonic, MnemonicType, Seed};
+use hmac::{Hmac, Mac, NewMac};
+use sha2::Sha512;
+use starcoin_account_api::error::AccountError;
+use starcoin_account_api::{AccountPrivateKey, AccountResult, DerivationInfo, DerivedAccount};
+use starcoin_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
+use starcoin_crypto::{HashValue, ValidCryptoMaterial};
+use std::convert::TryFrom;
+
+/// The SLIP-0044 coin type of Starcoin.
+pub const STARCOIN_COIN_TYPE: u32 = 101010;
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+const ED25519_SEED_KEY: &[u8] = b"ed25519 seed";
+
+/// The derivation path of the account at `index`, it is `m/44'/101010'/0'/0'/<index>'`.
+/// Every level is hardened, SLIP-0010 does not support non-hardened derivation for ed25519.
+pub fn derivation_path(index: u32) -> Vec<u32> {
+    vec![44, STARCOIN_COIN_TYPE, 0, 0, index]
+}
+
+pub fn format_path(path: &[u32]) -> String {
+    let mut result = "m".to_string();
+    for index in path {
+        result.push_str(format!("/{}'", index).as_str());
+    }
+    result
+}
+
+/// A SLIP-0010 extended private key of ed25519.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExtendedKey {
+    key: [u8; 32],
+    chain_code: [u8; 32],
+}
+
+impl ExtendedKey {
+    pub fn master(seed: &[u8]) -> Self {
+        Self::from_hmac(ED25519_SEED_KEY, &[seed])
+    }
+
+    /// Derive the hardened child at `index`, `index` should be less than 2^31.
+    pub fn derive_child(&self, index: u32) -> AccountResult<Self> {
+        if index >= HARDENED_OFFSET {
+            return Err(AccountError::InvalidMnemonic(format!(
+                "derivation index {} should be less than {}",
+                index, HARDENED_OFFSET
+            )));
+        }
+        let index = (index | HARDENED_OFFSET).to_be_bytes();
+        Ok(Self::from_hmac(
+            &self.chain_code,
+            &[&[0u8], &self.key, &index],
+        ))
+    }
+
+    pub fn derive_path(&self, path: &[u32]) -> AccountResult<Self> {
+        let mut key = self.clone();
+        for index in path {
+            key = key.derive_child(*index)?;
+        }
+        Ok(key)
+    }
+
+    pub fn key(&self) -> &[u8; 32] {
+        &self.key
+    }
+
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    pub fn private_key(&self) -> Ed25519PrivateKey {
+        Ed25519PrivateKey::try_from(&self.key[..])
+            .expect("any 32 bytes should be a valid ed25519 private key")
+    }
+
+    pub fn public_key(&self) -> Ed25519PublicKey {
+        Ed25519PublicKey::from(&self.private_key())
+    }
+
+    fn from_hmac(key: &[u8], data: &[&[u8]]) -> Self {
+        let mut mac = Hmac::<Sha512>::new_varkey(key).expect("HMAC can take key of any size");
+        for d in data {
+            mac.update(d);
+        }
+        let output = mac.finalize().into_bytes();
+        let mut key = [0u8; 32];
+        let mut chain_code = [0u8; 32];
+        key.copy_from_slice(&output[..32]);
+        chain_code.copy_from_slice(&output[32..]);
+        Self { key, chain_code }
+    }
+}
+
+/// Generate an english mnemonic with `word_count` words, it should be one of 12, 15, 18, 21 and 24.
+pub fn generate_mnemonic(word_count: u32) -> AccountResult<String> {
+    let mnemonic_type = MnemonicType::for_word_count(word_count as usize)
+        .map_err(|e| AccountError::InvalidMnemonic(e.to_string()))?;
+    Ok(Mnemonic::new(mnemonic_type, Language::English)
+        .phrase()
+        .to_string())
+}
+
+/// The BIP-39 seed of the english `mnemonic`, the passphrase is empty.
+pub fn mnemonic_seed(mnemonic: &str) -> AccountResult<Vec<u8>> {
+    let mnemonic = Mnemonic::from_phrase(mnemonic.trim(), Language::English)
+        .map_err(|e| AccountError::InvalidMnemonic(e.to_string()))?;
+    Ok(Seed::new(&mnemonic, "").as_bytes().to_vec())
+}
+
+/// Identify the seed by the first 4 bytes of the sha3-256 hash of its master public key.
+pub fn seed_fingerprint(master: &ExtendedKey) -> String {
+    let hash = HashValue::sha3_256_of(master.public_key().to_bytes().as_slice());
+    hex::encode(&hash.as_ref()[..4])
+}
+
+/// Derive the private key of the account at `index` from `mnemonic`.
+pub fn derive_private_key(
+    mnemonic: &str,
+    index: u32,
+) -> AccountResult<(AccountPrivateKey, DerivationInfo)> {
+    let master = ExtendedKey::master(mnemonic_seed(mnemonic)?.as_slice());
+    let path = derivation_path(index);
+    let key = master.derive_path(path.as_slice())?;
+    let derivation = DerivationInfo {
+        seed_fingerprint: seed_fingerprint(&master),
+        index,
+        path: format_path(path.as_slice()),
+    };
+    Ok((AccountPrivateKey::Single(key.private_key()), derivation))
+}
+
+/// Derive the account at `index` from `mnemonic`, without the private key.
+pub fn derive_account(mnemonic: &str, index: u32) -> AccountResult<DerivedAccount> {
+    let (private_key, derivation) = derive_private_key(mnemonic, index)?;
+    let public_key = private_key.public_key();
+    Ok(DerivedAccount {
+        address: public_key.derived_address(),
+        public_key,
+        derivation,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_account_api::DEFAULT_MNEMONIC_WORD_COUNT;
+    use starcoin_types::account_address::AccountAddress;
+    use std::str::FromStr;
+
+    const TEST_MNEMONIC: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_slip10_vectors() {
+        // the test vector 1 for ed25519 of SLIP-0010.
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let master = ExtendedKey::master(seed.as_slice());
+        for (path, chain_code, private_key, public_key) in vec![
+            (
+                vec![],
+                "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb",
+                "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+                "a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed",
+            ),
+            (
+                vec![0],
+                "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+                "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+                "8c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c",
+            ),
+            (
+                vec![0, 1],
+                "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14",
+                "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+                "1932a5270f335bed617d5b935c80aedb1a35bd9fc1e31acafd5372c30f5c1187",
+            ),
+            (
+                vec![0, 1, 2],
+                "2e69929e00b5ab250f49c3fb1c12f252de4fed2c1db88387094a0f8c4c9ccd6c",
+                "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9",
+                "ae98736566d30ed0e9d2f4486a64bc95740d89c7db33f52121f8ea8f76ff0fc1",
+            ),
+        ] {
+            let key = master.derive_path(path.as_slice()).unwrap();
+            assert_eq!(hex::encode(key.chain_code()), chain_code);
+            assert_eq!(hex::encode(key.key()), private_key);
+            assert_eq!(hex::encode(key.public_key().to_bytes()), public_key);
+        }
+        assert!(master.derive_child(HARDENED_OFFSET).is_err());
+    }
+
+    #[test]
+    fn test_mnemonic_seed() {
+        // the first test vector of BIP-39 without passphrase.
+        assert_eq!(
+            hex::encode(mnemonic_seed(TEST_MNEMONIC).unwrap()),
+            "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4"
+        );
+        // the checksum is wrong.
+        assert!(mnemonic_seed(TEST_MNEMONIC.replace("about", "abandon").as_str()).is_err());
+    }
+
+    #[test]
+    fn test_derive_account() {
+        for (index, address, public_key) in vec![
+            (
+                0,
+                "0x1ff4fc74d30f8bdfdddbcbf27c5a59b3",
+                "1c56fa2dfdf5c058d050951274f759f1e6c4a752df41e141001097ed7bf78184",
+            ),
+            (
+                1,
+                "0xcfac0c4bf5f759bd024db910343d48c0",
+                "6d809b2e10e2ea168bdb43a78c2da5a3d589b3a16aee11967d8bdae3270f8313",
+            ),
+        ] {
+            let account = derive_account(TEST_MNEMONIC, index).unwrap();
+            assert_eq!(account.address, AccountAddress::from_str(address).unwrap());
+            assert_eq!(hex::encode(account.public_key.to_bytes()), public_key);
+            assert_eq!(account.derivation.index, index);
+            assert_eq!(
+                account.derivation.path,
+                format!("m/44'/101010'/0'/0'/{}'", index)
+            );
+            assert_eq!(account.derivation.seed_fingerprint, "3f995ceb");
+        }
+    }
+
+    #[test]
+    fn test_generate_mnemonic() {
+        let mnemonic = generate_mnemonic(DEFAULT_MNEMONIC_WORD_COUNT).unwrap();
+        assert_eq!(mnemonic.split_whitespace().count(), 12);
+        assert!(mnemonic_seed(mnemonic.as_str()).is_ok());
+        assert_eq!(
+            generate_mnemonic(24).unwrap().split_whitespace().count(),
+            24
+        );
+        assert!(generate_mnemonic(13).is_err());
+    }
+}
@@ -0,0 +1,42 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use starcoin_vm_types::transaction::signature_verifier::SignatureVerifier;
+use starcoin_vm_types::transaction::SignedUserTransaction;
+
+/// Compare the sequential and the parallel signature verification of a block's user txns,
+/// a new verifier is used in every iteration, so the cache does not take effect.
+pub fn signature_verification(c: &mut Criterion) {
+    let mut group = c.benchmark_group("signature");
+    group.sample_size(10);
+    for count in vec![100usize, 1000].into_iter() {
+        let txns: Vec<SignedUserTransaction> =
+            (0..count).map(|_| SignedUserTransaction::mock()).collect();
+        group.bench_function(BenchmarkId::new("sequential_verify", count), |b| {
+            b.iter_batched(
+                || SignatureVerifier::new(count),
+                |verifier| {
+                    for txn in txns.iter() {
+                        assert!(verifier.verify(txn));
+                    }
+                },
+                BatchSize::SmallInput,
+            )
+        });
+        group.bench_function(BenchmarkId::new("batch_verify", count), |b| {
+            b.iter_batched(
+                || SignatureVerifier::new(count),
+                |verifier| {
+                    assert!(verifier
+                        .batch_verify(txns.as_slice())
+                        .into_iter()
+                        .all(|v| v))
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+}
+
+criterion_group!(starcoin_signature_benches, signature_verification);
+criterion_main!(starcoin_signature_benches);
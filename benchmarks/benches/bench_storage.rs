@@ -8,8 +8,10 @@ use starcoin_config::RocksdbConfig;
 use starcoin_storage::cache_storage::CacheStorage;
 use starcoin_storage::db_storage::DBStorage;
 use starcoin_storage::storage::StorageInstance;
-use starcoin_storage::Storage;
+use starcoin_storage::{BlockStore, Storage};
 use std::sync::Arc;
+use types::block::{Block, BlockBody, BlockHeaderBuilder};
+use types::transaction::SignedUserTransaction;
 
 //
 // Storage benchmarks
@@ -63,6 +65,55 @@ fn accumulator_append(c: &mut Criterion) {
     });
 }
 
+/// The blocks read by one iteration of the header benchmarks, a light client syncs 10k headers.
+const HEADERS_BENCH_BLOCKS: u64 = 10_000;
+/// The txns of every block in the header benchmarks.
+const HEADERS_BENCH_TXNS_PER_BLOCK: usize = 10;
+
+/// Read the headers only, like `chain.get_headers_by_number`, compared with reading the whole blocks.
+fn storage_get_headers(c: &mut Criterion) {
+    ::logger::init_for_test();
+    let storage = Storage::new(StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new(),
+        DBStorage::new(
+            starcoin_config::temp_path().as_ref(),
+            RocksdbConfig::default(),
+        )
+        .unwrap(),
+    ))
+    .unwrap();
+    let txns: Vec<SignedUserTransaction> = (0..HEADERS_BENCH_TXNS_PER_BLOCK)
+        .map(|_| SignedUserTransaction::mock())
+        .collect();
+    let ids: Vec<HashValue> = (0..HEADERS_BENCH_BLOCKS)
+        .map(|number| {
+            let header = BlockHeaderBuilder::random().with_number(number).build();
+            let block = Block::new(header, BlockBody::new(txns.clone(), None));
+            let id = block.id();
+            storage.commit_block(block).unwrap();
+            id
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("storage_get_headers");
+    group.sample_size(10);
+    group.bench_function("get_block_header_by_hash", |b| {
+        b.iter(|| {
+            for id in &ids {
+                storage.get_block_header_by_hash(*id).unwrap().unwrap();
+            }
+        })
+    });
+    group.bench_function("get_block_by_hash", |b| {
+        b.iter(|| {
+            for id in &ids {
+                storage.get_block_by_hash(*id).unwrap().unwrap();
+            }
+        })
+    });
+    group.finish();
+}
+
 fn create_leaves(nums: std::ops::Range<usize>) -> Vec<HashValue> {
     nums.map(|x| HashValue::sha3_256_of(x.to_be_bytes().as_ref()))
         .collect()
@@ -70,6 +121,7 @@ fn create_leaves(nums: std::ops::Range<usize>) -> Vec<HashValue> {
 criterion_group!(
     starcoin_storage_benches,
     storage_transaction,
-    accumulator_append
+    accumulator_append,
+    storage_get_headers
 );
 criterion_main!(starcoin_storage_benches);
@@ -5,8 +5,9 @@ use anyhow::Result;
 use starcoin_crypto::HashValue;
 use starcoin_state_api::{ChainState, ChainStateReader};
 use starcoin_types::block::BlockIdAndNumber;
+use starcoin_types::proof::TransactionInfoWithProof;
 use starcoin_types::startup_info::{ChainInfo, ChainStatus};
-use starcoin_types::transaction::BlockTransactionInfo;
+use starcoin_types::transaction::{BlockTransactionInfo, TransactionPosition};
 use starcoin_types::{
     block::{Block, BlockHeader, BlockInfo, BlockNumber},
     transaction::Transaction,
@@ -37,11 +38,40 @@ pub trait ChainReader {
     fn get_transaction(&self, hash: HashValue) -> Result<Option<Transaction>>;
     /// Get transaction info by transaction's hash
     fn get_transaction_info(&self, txn_hash: HashValue) -> Result<Option<BlockTransactionInfo>>;
+    /// Get the main chain transaction infos of the txns, the storage is read in batch,
+    /// None if the txn is not included in the main chain, keep the order of `txn_hashes`.
+    fn get_transaction_infos_by_hashes(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> Result<Vec<Option<BlockTransactionInfo>>>;
+    /// Get the positions of the txns in the main chain by the txn position index,
+    /// None if the txn is not included in the main chain, keep the order of `txn_hashes`.
+    fn get_transaction_positions(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> Result<Vec<Option<TransactionPosition>>>;
 
     /// get txn info at version in main chain.
     fn get_transaction_info_by_version(&self, version: u64)
         -> Result<Option<BlockTransactionInfo>>;
 
+    /// Get txn infos by the leaf index of txn accumulator, `start_index`'s txn info is include.
+    fn get_transaction_infos(
+        &self,
+        start_index: u64,
+        reverse: bool,
+        max_size: u64,
+    ) -> Result<Vec<BlockTransactionInfo>>;
+
+    /// Get the txn info at `transaction_index` of block `block_id` with its accumulator proof,
+    /// and the proof of the event at `event_index` if present.
+    fn get_transaction_proof(
+        &self,
+        block_id: HashValue,
+        transaction_index: u64,
+        event_index: Option<u64>,
+    ) -> Result<Option<TransactionInfoWithProof>>;
+
     fn chain_state_reader(&self) -> &dyn ChainStateReader;
     fn get_block_info(&self, block_id: Option<HashValue>) -> Result<Option<BlockInfo>>;
     fn get_total_difficulty(&self) -> Result<U256>;
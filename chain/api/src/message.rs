@@ -4,10 +4,11 @@
 use anyhow::Result;
 use starcoin_crypto::HashValue;
 use starcoin_service_registry::ServiceRequest;
-use starcoin_types::block::BlockSummary;
 use starcoin_types::block::EpochUncleSummary;
+use starcoin_types::block::{BlockReward, BlockSummary, BranchInfo};
+use starcoin_types::proof::TransactionInfoWithProof;
 use starcoin_types::stress_test::TPS;
-use starcoin_types::transaction::BlockTransactionInfo;
+use starcoin_types::transaction::{BlockTransactionInfo, TransactionPosition};
 use starcoin_types::{
     block::{Block, BlockHeader, BlockInfo, BlockNumber, BlockTemplate},
     contract_event::ContractEventInfo,
@@ -35,6 +36,8 @@ pub enum ChainRequest {
     GetGlobalTimeByNumber(u64),
     GetTransactionBlock(HashValue),
     GetTransaction(HashValue),
+    GetMainTransactions(Vec<HashValue>),
+    GetTransactionPositions(Vec<HashValue>),
     GetTransactionInfo(HashValue),
     GetBlockTransactionInfos(HashValue),
     GetTransactionInfoByBlockAndIndex {
@@ -51,11 +54,29 @@ pub enum ChainRequest {
         reverse: bool,
         max_size: u64,
     },
+    GetTransactionInfos {
+        start_index: u64,
+        reverse: bool,
+        max_size: u64,
+    },
     GetBlocks(Vec<HashValue>),
     GetHeaders(Vec<HashValue>),
     GetEpochUnclesByNumber(Option<BlockNumber>),
     UnclePath(HashValue, HashValue),
     EpochUncleSummaryByNumber(Option<BlockNumber>),
+    GetBlockByUncleHash(HashValue),
+    GetUnclesByNumber(BlockNumber),
+    GetBlockRewards {
+        start_number: BlockNumber,
+        count: u64,
+    },
+    GetTransactionProof {
+        block_id: HashValue,
+        transaction_index: u64,
+        event_index: Option<u64>,
+    },
+    GetBranches(),
+    GetCommonAncestor(HashValue, HashValue),
 }
 
 impl ServiceRequest for ChainRequest {
@@ -75,9 +96,12 @@ pub enum ChainResponse {
     ChainStatus(Box<ChainStatus>),
     Transaction(Box<Transaction>),
     TransactionOption(Option<Box<Transaction>>),
+    TransactionWithInfoOptionVec(Vec<Option<(Transaction, BlockTransactionInfo)>>),
+    TransactionPositionOptionVec(Vec<Option<TransactionPosition>>),
     BlockVec(Vec<Block>),
     BlockOptionVec(Vec<Option<Block>>),
     BlockHeaderVec(Vec<BlockHeader>),
+    BlockHeaderOptionVec(Vec<Option<BlockHeader>>),
     TransactionInfos(Vec<BlockTransactionInfo>),
     TransactionInfo(Option<BlockTransactionInfo>),
     Events(Vec<ContractEventInfo>),
@@ -90,4 +114,8 @@ pub enum ChainResponse {
     TPS(TPS),
     BlockSummaries(Vec<BlockSummary>),
     UncleSummary(EpochUncleSummary),
+    TransactionProof(Box<Option<TransactionInfoWithProof>>),
+    BlockRewards(Vec<BlockReward>),
+    Branches(Vec<BranchInfo>),
+    HashValueOption(Option<HashValue>),
 }
@@ -5,11 +5,12 @@ use crate::message::{ChainRequest, ChainResponse};
 use anyhow::{bail, Result};
 use starcoin_crypto::HashValue;
 use starcoin_service_registry::{ActorService, ServiceHandler, ServiceRef};
-use starcoin_types::block::{BlockSummary, EpochUncleSummary};
+use starcoin_types::block::{BlockReward, BlockSummary, BranchInfo, EpochUncleSummary};
 use starcoin_types::contract_event::{ContractEvent, ContractEventInfo};
 use starcoin_types::filter::Filter;
+use starcoin_types::proof::TransactionInfoWithProof;
 use starcoin_types::startup_info::ChainStatus;
-use starcoin_types::transaction::{BlockTransactionInfo, Transaction};
+use starcoin_types::transaction::{BlockTransactionInfo, Transaction, TransactionPosition};
 use starcoin_types::{
     block::{Block, BlockHeader, BlockInfo, BlockNumber},
     startup_info::StartupInfo,
@@ -21,10 +22,22 @@ pub trait ReadableChainService {
     fn get_header_by_hash(&self, hash: HashValue) -> Result<Option<BlockHeader>>;
     fn get_block_by_hash(&self, hash: HashValue) -> Result<Option<Block>>;
     fn get_blocks(&self, ids: Vec<HashValue>) -> Result<Vec<Option<Block>>>;
-    fn get_headers(&self, ids: Vec<HashValue>) -> Result<Vec<BlockHeader>>;
+    /// Get the headers without reading the block bodies, None if the block does not exist.
+    fn get_headers(&self, ids: Vec<HashValue>) -> Result<Vec<Option<BlockHeader>>>;
     fn get_block_info_by_hash(&self, hash: HashValue) -> Result<Option<BlockInfo>>;
     fn get_transaction(&self, hash: HashValue) -> Result<Option<Transaction>>;
     fn get_transaction_info(&self, txn_hash: HashValue) -> Result<Option<BlockTransactionInfo>>;
+    /// Get the main chain txns with their txn infos, None if the txn is not included in the main chain,
+    /// keep the order of `txn_hashes`.
+    fn get_main_transactions(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> Result<Vec<Option<(Transaction, BlockTransactionInfo)>>>;
+    /// Get the main chain positions of the transactions, `None` if the transaction is not on the main chain.
+    fn get_transaction_positions(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> Result<Vec<Option<TransactionPosition>>>;
     fn get_block_txn_infos(&self, block_id: HashValue) -> Result<Vec<BlockTransactionInfo>>;
     fn get_txn_info_by_block_and_index(
         &self,
@@ -53,12 +66,36 @@ pub trait ReadableChainService {
         reverse: bool,
         max_size: u64,
     ) -> Result<Vec<HashValue>>;
+    fn get_transaction_infos(
+        &self,
+        start_index: u64,
+        reverse: bool,
+        max_size: u64,
+    ) -> Result<Vec<BlockTransactionInfo>>;
     fn get_epoch_uncles_by_number(&self, number: Option<BlockNumber>) -> Result<Vec<BlockSummary>>;
     fn uncle_path(&self, block_id: HashValue, uncle_id: HashValue) -> Result<Vec<BlockHeader>>;
     fn epoch_uncle_summary_by_number(
         &self,
         number: Option<BlockNumber>,
     ) -> Result<EpochUncleSummary>;
+    /// Get the main chain block which includes the uncle `uncle_id`.
+    fn get_block_by_uncle_hash(&self, uncle_id: HashValue) -> Result<Option<Block>>;
+    /// Get the uncles included in the main chain block of `number`.
+    fn main_uncles_by_number(&self, number: BlockNumber) -> Result<Vec<BlockHeader>>;
+    /// Get the rewards of the main chain blocks from `start_number`, at most `count` blocks,
+    /// the blocks whose reward is not distributed yet are excluded.
+    fn main_block_rewards(&self, start_number: BlockNumber, count: u64)
+        -> Result<Vec<BlockReward>>;
+    fn get_transaction_proof(
+        &self,
+        block_id: HashValue,
+        transaction_index: u64,
+        event_index: Option<u64>,
+    ) -> Result<Option<TransactionInfoWithProof>>;
+    /// Get the main chain head and the branch heads tracked by the service.
+    fn get_branches(&self) -> Result<Vec<BranchInfo>>;
+    /// Get the latest common ancestor of the two blocks, None if any block does not exist.
+    fn get_common_ancestor(&self, hash1: HashValue, hash2: HashValue) -> Result<Option<HashValue>>;
 }
 
 /// Writeable block chain service trait
@@ -73,7 +110,7 @@ pub trait ChainAsyncService:
     async fn get_header_by_hash(&self, hash: &HashValue) -> Result<Option<BlockHeader>>;
     async fn get_block_by_hash(&self, hash: HashValue) -> Result<Option<Block>>;
     async fn get_blocks(&self, hashes: Vec<HashValue>) -> Result<Vec<Option<Block>>>;
-    async fn get_headers(&self, hashes: Vec<HashValue>) -> Result<Vec<BlockHeader>>;
+    async fn get_headers(&self, hashes: Vec<HashValue>) -> Result<Vec<Option<BlockHeader>>>;
     async fn uncle_path(
         &self,
         block_id: HashValue,
@@ -87,6 +124,16 @@ pub trait ChainAsyncService:
         txn_hash: HashValue,
     ) -> Result<Option<BlockTransactionInfo>>;
     async fn get_transaction_block(&self, txn_hash: HashValue) -> Result<Option<Block>>;
+    /// Get the main chain txns with their txn infos in batch, keep the order of `txn_hashes`.
+    async fn get_main_transactions(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> Result<Vec<Option<(Transaction, BlockTransactionInfo)>>>;
+    /// Get the main chain positions of the transactions, `None` if the transaction is not on the main chain.
+    async fn get_transaction_positions(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> Result<Vec<Option<TransactionPosition>>>;
     async fn get_block_txn_infos(&self, block_hash: HashValue)
         -> Result<Vec<BlockTransactionInfo>>;
     async fn get_txn_info_by_block_and_index(
@@ -118,6 +165,13 @@ pub trait ChainAsyncService:
         reverse: bool,
         max_size: u64,
     ) -> Result<Vec<HashValue>>;
+    /// Get txn infos of main chain by the leaf index of txn accumulator.
+    async fn get_transaction_infos(
+        &self,
+        start_index: u64,
+        reverse: bool,
+        max_size: u64,
+    ) -> Result<Vec<BlockTransactionInfo>>;
     async fn get_epoch_uncles_by_number(
         &self,
         number: Option<BlockNumber>,
@@ -126,6 +180,26 @@ pub trait ChainAsyncService:
         &self,
         number: Option<BlockNumber>,
     ) -> Result<EpochUncleSummary>;
+    async fn get_block_by_uncle_hash(&self, uncle_id: HashValue) -> Result<Option<Block>>;
+    async fn main_uncles_by_number(&self, number: BlockNumber) -> Result<Vec<BlockHeader>>;
+    async fn main_block_rewards(
+        &self,
+        start_number: BlockNumber,
+        count: u64,
+    ) -> Result<Vec<BlockReward>>;
+    async fn get_transaction_proof(
+        &self,
+        block_id: HashValue,
+        transaction_index: u64,
+        event_index: Option<u64>,
+    ) -> Result<Option<TransactionInfoWithProof>>;
+    /// Get the main chain head and the branch heads tracked by the chain service.
+    async fn get_branches(&self) -> Result<Vec<BranchInfo>>;
+    async fn get_common_ancestor(
+        &self,
+        hash1: HashValue,
+        hash2: HashValue,
+    ) -> Result<Option<HashValue>>;
 }
 
 #[async_trait::async_trait]
@@ -167,8 +241,8 @@ where
         }
     }
 
-    async fn get_headers(&self, ids: Vec<HashValue>) -> Result<Vec<BlockHeader>> {
-        if let ChainResponse::BlockHeaderVec(headers) =
+    async fn get_headers(&self, ids: Vec<HashValue>) -> Result<Vec<Option<BlockHeader>>> {
+        if let ChainResponse::BlockHeaderOptionVec(headers) =
             self.send(ChainRequest::GetHeaders(ids)).await??
         {
             Ok(headers)
@@ -205,6 +279,34 @@ where
         }
     }
 
+    async fn get_main_transactions(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> Result<Vec<Option<(Transaction, BlockTransactionInfo)>>> {
+        let response = self
+            .send(ChainRequest::GetMainTransactions(txn_hashes))
+            .await??;
+        if let ChainResponse::TransactionWithInfoOptionVec(txns) = response {
+            Ok(txns)
+        } else {
+            bail!("get main transactions response type error.")
+        }
+    }
+
+    async fn get_transaction_positions(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> Result<Vec<Option<TransactionPosition>>> {
+        let response = self
+            .send(ChainRequest::GetTransactionPositions(txn_hashes))
+            .await??;
+        if let ChainResponse::TransactionPositionOptionVec(positions) = response {
+            Ok(positions)
+        } else {
+            bail!("get transaction positions response type error.")
+        }
+    }
+
     async fn get_transaction_info(
         &self,
         txn_hash: HashValue,
@@ -405,6 +507,26 @@ where
         }
     }
 
+    async fn get_transaction_infos(
+        &self,
+        start_index: u64,
+        reverse: bool,
+        max_size: u64,
+    ) -> Result<Vec<BlockTransactionInfo>> {
+        let response = self
+            .send(ChainRequest::GetTransactionInfos {
+                start_index,
+                reverse,
+                max_size,
+            })
+            .await??;
+        if let ChainResponse::TransactionInfos(txn_infos) = response {
+            Ok(txn_infos)
+        } else {
+            bail!("get_transaction_infos invalid response")
+        }
+    }
+
     async fn get_epoch_uncles_by_number(
         &self,
         number: Option<BlockNumber>,
@@ -433,6 +555,44 @@ where
         }
     }
 
+    async fn get_block_by_uncle_hash(&self, uncle_id: HashValue) -> Result<Option<Block>> {
+        let response = self
+            .send(ChainRequest::GetBlockByUncleHash(uncle_id))
+            .await??;
+        if let ChainResponse::BlockOption(block) = response {
+            Ok(block.map(|b| *b))
+        } else {
+            bail!("get block by uncle hash error.")
+        }
+    }
+
+    async fn main_uncles_by_number(&self, number: BlockNumber) -> Result<Vec<BlockHeader>> {
+        let response = self.send(ChainRequest::GetUnclesByNumber(number)).await??;
+        if let ChainResponse::BlockHeaderVec(headers) = response {
+            Ok(headers)
+        } else {
+            bail!("get uncles by number error.")
+        }
+    }
+
+    async fn main_block_rewards(
+        &self,
+        start_number: BlockNumber,
+        count: u64,
+    ) -> Result<Vec<BlockReward>> {
+        let response = self
+            .send(ChainRequest::GetBlockRewards {
+                start_number,
+                count,
+            })
+            .await??;
+        if let ChainResponse::BlockRewards(rewards) = response {
+            Ok(rewards)
+        } else {
+            bail!("get block rewards error.")
+        }
+    }
+
     async fn uncle_path(
         &self,
         block_id: HashValue,
@@ -447,4 +607,48 @@ where
             bail!("get uncle path error.")
         }
     }
+
+    async fn get_transaction_proof(
+        &self,
+        block_id: HashValue,
+        transaction_index: u64,
+        event_index: Option<u64>,
+    ) -> Result<Option<TransactionInfoWithProof>> {
+        let response = self
+            .send(ChainRequest::GetTransactionProof {
+                block_id,
+                transaction_index,
+                event_index,
+            })
+            .await??;
+        if let ChainResponse::TransactionProof(proof) = response {
+            Ok(*proof)
+        } else {
+            bail!("get transaction proof error.")
+        }
+    }
+
+    async fn get_branches(&self) -> Result<Vec<BranchInfo>> {
+        let response = self.send(ChainRequest::GetBranches()).await??;
+        if let ChainResponse::Branches(branches) = response {
+            Ok(branches)
+        } else {
+            bail!("get branches error.")
+        }
+    }
+
+    async fn get_common_ancestor(
+        &self,
+        hash1: HashValue,
+        hash2: HashValue,
+    ) -> Result<Option<HashValue>> {
+        let response = self
+            .send(ChainRequest::GetCommonAncestor(hash1, hash2))
+            .await??;
+        if let ChainResponse::HashValueOption(ancestor) = response {
+            Ok(ancestor)
+        } else {
+            bail!("get common ancestor error.")
+        }
+    }
 }
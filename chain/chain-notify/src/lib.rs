@@ -3,25 +3,34 @@
 
 pub mod message;
 
-use crate::message::{ContractEventNotification, Event, Notification, ThinBlock};
-use anyhow::{format_err, Result};
+use crate::message::{
+    BlockNotification, ContractEventNotification, Event, Notification, ThinBlock,
+};
+use anyhow::{ensure, format_err, Result};
+use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
-use starcoin_storage::{Storage, Store};
-use starcoin_types::block::Block;
+use starcoin_storage::{BlockStore, Storage, Store};
+use starcoin_types::block::{Block, BlockHeader};
 use starcoin_types::system_events::NewHeadBlock;
 use std::sync::Arc;
 
+/// Max blocks to walk back for finding the common ancestor of the old and new head.
+const MAX_BRANCH_DEPTH: usize = 64;
+
 /// ChainNotify watch `NewHeadBlock` message from bus,
-/// and then reproduce `Notification<ThinBlock>` and `Notification<Arc<[Event]>>` message to bus.
+/// and then reproduce `Notification<BlockNotification>` and `Notification<Arc<[Event]>>` message to bus.
 /// User can subscribe the two notification to watch onchain events.
+/// If the main chain switches branches, the blocks removed from main chain are notified as retracted,
+/// before the new heads.
 pub struct ChainNotifyHandlerService {
     store: Arc<dyn Store>,
+    head: Option<BlockHeader>,
 }
 
 impl ChainNotifyHandlerService {
-    pub fn new(store: Arc<dyn Store>) -> Self {
-        Self { store }
+    pub fn new(store: Arc<dyn Store>, head: Option<BlockHeader>) -> Self {
+        Self { store, head }
     }
 }
 
@@ -30,7 +39,11 @@ impl ServiceFactory<Self> for ChainNotifyHandlerService {
         ctx: &mut ServiceContext<ChainNotifyHandlerService>,
     ) -> Result<ChainNotifyHandlerService> {
         let storage = ctx.get_shared::<Arc<Storage>>()?;
-        Ok(Self::new(storage))
+        let head = match storage.get_startup_info()? {
+            Some(startup_info) => storage.get_block_header_by_hash(startup_info.main)?,
+            None => None,
+        };
+        Ok(Self::new(storage, head))
     }
 }
 
@@ -65,12 +78,79 @@ impl EventHandler<Self, NewHeadBlock> for ChainNotifyHandlerService {
 }
 
 impl ChainNotifyHandlerService {
-    pub fn notify_new_block(&self, block: &Block, ctx: &mut ServiceContext<Self>) {
-        let thin_block = ThinBlock::new(
+    pub fn notify_new_block(&mut self, block: &Block, ctx: &mut ServiceContext<Self>) {
+        let notifications = match self.block_notifications(block) {
+            Ok(notifications) => notifications,
+            Err(e) => {
+                warn!(target: "pubsub", "fail to find the branch of new head {}, err: {}", block.id(), &e);
+                vec![BlockNotification::new_head(Self::thin_block(block), None)]
+            }
+        };
+        self.head = Some(block.header().clone());
+        for notification in notifications {
+            ctx.broadcast(Notification(notification));
+        }
+    }
+
+    /// Notifications of the new head: retracted blocks from the old head down to the common ancestor,
+    /// then the enacted blocks from the common ancestor up to the new head.
+    fn block_notifications(&self, block: &Block) -> Result<Vec<BlockNotification>> {
+        let head_id = match &self.head {
+            Some(head) if head.id() != block.header().parent_hash() => head.id(),
+            _ => {
+                return Ok(vec![BlockNotification::new_head(
+                    Self::thin_block(block),
+                    None,
+                )])
+            }
+        };
+        let mut retracted = vec![];
+        let mut enacted = vec![block.clone()];
+        let mut old = self.get_block(head_id)?;
+        let mut new = self.get_block(block.header().parent_hash())?;
+        while old.id() != new.id() {
+            ensure!(
+                retracted.len() + enacted.len() <= MAX_BRANCH_DEPTH,
+                "the branch is deeper than {}",
+                MAX_BRANCH_DEPTH
+            );
+            if new.header().number() >= old.header().number() {
+                let parent = self.get_block(new.header().parent_hash())?;
+                enacted.push(std::mem::replace(&mut new, parent));
+            } else {
+                let parent = self.get_block(old.header().parent_hash())?;
+                retracted.push(std::mem::replace(&mut old, parent));
+            }
+        }
+        let common_ancestor = if retracted.is_empty() {
+            None
+        } else {
+            Some(old.id())
+        };
+        let mut notifications: Vec<BlockNotification> = retracted
+            .iter()
+            .map(|block| BlockNotification::retracted(Self::thin_block(block)))
+            .collect();
+        notifications.extend(enacted.iter().rev().enumerate().map(|(i, block)| {
+            BlockNotification::new_head(
+                Self::thin_block(block),
+                if i == 0 { common_ancestor } else { None },
+            )
+        }));
+        Ok(notifications)
+    }
+
+    fn get_block(&self, block_id: HashValue) -> Result<Block> {
+        self.store
+            .get_block_by_hash(block_id)?
+            .ok_or_else(|| format_err!("cannot find block by id {}", block_id))
+    }
+
+    fn thin_block(block: &Block) -> ThinBlock {
+        ThinBlock::new(
             block.header().clone(),
             block.transactions().iter().map(|t| t.id()).collect(),
-        );
-        ctx.broadcast(Notification(thin_block));
+        )
     }
 
     pub fn notify_events(
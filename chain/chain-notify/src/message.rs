@@ -10,7 +10,7 @@ use std::sync::Arc;
 pub struct Notification<T>(pub T);
 
 pub type ContractEventNotification = Notification<Arc<[Event]>>;
-pub type NewHeadEventNotification = Notification<ThinBlock>;
+pub type NewHeadEventNotification = Notification<BlockNotification>;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Event {
@@ -60,3 +60,36 @@ impl ThinBlock {
         &self.body
     }
 }
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum BlockNotificationKind {
+    NewHead,
+    Retracted,
+}
+
+/// Block notification, `Retracted` means the block is removed from main chain by a reorg.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct BlockNotification {
+    pub kind: BlockNotificationKind,
+    pub block: ThinBlock,
+    /// the common ancestor of the old and new branch, only set on the first new head after a reorg.
+    pub common_ancestor: Option<HashValue>,
+}
+
+impl BlockNotification {
+    pub fn new_head(block: ThinBlock, common_ancestor: Option<HashValue>) -> Self {
+        Self {
+            kind: BlockNotificationKind::NewHead,
+            block,
+            common_ancestor,
+        }
+    }
+
+    pub fn retracted(block: ThinBlock) -> Self {
+        Self {
+            kind: BlockNotificationKind::Retracted,
+            block,
+            common_ancestor: None,
+        }
+    }
+}
@@ -0,0 +1,186 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Prune the block bodies, txn infos and events of the old main chain blocks,
+//! the headers, the accumulators and the state are kept, so the head queries,
+//! the proofs of the recent txns and the sync of the new blocks still work.
+
+use anyhow::{format_err, Result};
+use starcoin_chain::BlockChain;
+use starcoin_chain_api::ChainReader;
+use starcoin_config::PruneHorizon;
+use starcoin_logger::prelude::*;
+use starcoin_storage::chain_info::PruneInfo;
+use starcoin_storage::errors::BlockPrunedError;
+use starcoin_storage::Store;
+use starcoin_types::block::BlockNumber;
+use std::sync::Arc;
+
+/// The recent blocks are never pruned, so the main chain can be switched to a not too deep fork.
+pub const MIN_KEEP_RECENT_BLOCKS: u64 = 100;
+/// Prune at most so many blocks at a time, so the chain reads are not blocked for long.
+pub const MAX_PRUNE_BLOCKS_PER_ROUND: u64 = 1000;
+
+pub struct BlockPruner {
+    storage: Arc<dyn Store>,
+    prune_info: PruneInfo,
+}
+
+impl BlockPruner {
+    pub fn new(storage: Arc<dyn Store>) -> Result<Self> {
+        let prune_info = storage.get_prune_info()?.unwrap_or_default();
+        Ok(Self {
+            storage,
+            prune_info,
+        })
+    }
+
+    pub fn prune_info(&self) -> PruneInfo {
+        self.prune_info
+    }
+
+    pub fn is_pruned(&self, number: BlockNumber) -> bool {
+        self.prune_info.is_pruned(number)
+    }
+
+    /// Return the `BlockPrunedError` if the data of the main chain block at `number` is pruned.
+    pub fn ensure_not_pruned(&self, number: BlockNumber) -> Result<()> {
+        if self.is_pruned(number) {
+            return Err(BlockPrunedError {
+                number,
+                pruned_before: self.prune_info.pruned_before,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// The blocks before the returned number can be pruned, the blocks of the current epoch are
+    /// kept because the chain reads their uncles, and the recent blocks are kept for the forks.
+    pub fn safe_prune_before(main: &BlockChain, horizon: PruneHorizon) -> BlockNumber {
+        let head_number = main.current_header().number();
+        horizon
+            .prune_before(head_number)
+            .min(main.epoch().start_block_number())
+            .min(PruneHorizon::KeepRecent(MIN_KEEP_RECENT_BLOCKS).prune_before(head_number))
+    }
+
+    /// Prune at most `max_blocks` main chain blocks before the horizon, return the count of the pruned blocks.
+    pub fn prune(
+        &mut self,
+        main: &BlockChain,
+        horizon: PruneHorizon,
+        max_blocks: u64,
+    ) -> Result<u64> {
+        let prune_before = Self::safe_prune_before(main, horizon);
+        // the genesis is always kept.
+        let start = self.prune_info.pruned_before.max(1);
+        if start >= prune_before {
+            return Ok(0);
+        }
+        let end = prune_before.min(start.saturating_add(max_blocks));
+        let mut pruned_bytes = 0;
+        for number in start..end {
+            let block_id = main
+                .get_hash_by_number(number)?
+                .ok_or_else(|| format_err!("Can not find block hash by number {}", number))?;
+            pruned_bytes += self.storage.prune_block_data(block_id)?;
+        }
+        let prune_info = PruneInfo {
+            pruned_before: end,
+            pruned_bytes: self.prune_info.pruned_bytes.saturating_add(pruned_bytes),
+        };
+        self.storage.save_prune_info(prune_info)?;
+        self.prune_info = prune_info;
+        info!(
+            "Pruned the data of blocks [{}, {}), {} bytes.",
+            start, end, pruned_bytes
+        );
+        Ok(end - start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain_service::ChainReaderServiceInner;
+    use starcoin_chain_api::{ChainWriter, ReadableChainService};
+    use starcoin_chain_mock::MockChain;
+    use starcoin_config::NodeConfig;
+    use starcoin_storage::{BlockPruneStore, BlockStore};
+
+    #[stest::test(timeout = 300)]
+    fn test_prune_blocks() -> Result<()> {
+        let config = Arc::new(NodeConfig::random_for_test());
+        // the peer keeps the full history.
+        let mut peer = MockChain::new(config.net().clone())?;
+        peer.produce_and_apply_times(1000)?;
+
+        // the local node syncs the blocks from the peer.
+        let (storage, chain_info, _) = test_helper::Genesis::init_storage_for_test(config.net())?;
+        let mut local = BlockChain::new(
+            config.net().time_service(),
+            chain_info.head().id(),
+            storage.clone(),
+        )?;
+        let blocks = peer.head().get_blocks_by_number(None, 1000)?;
+        for block in blocks.into_iter().rev() {
+            local.apply(block)?;
+        }
+        let head = local.current_header();
+        assert_eq!(head.number(), 1000);
+        let startup_info = storage
+            .get_startup_info()?
+            .expect("startup info should exist");
+        let mut inner = ChainReaderServiceInner::new(config, startup_info, storage.clone())?;
+        inner.switch_main(head.id())?;
+
+        let horizon = PruneHorizon::KeepRecent(100);
+        let prune_before = BlockPruner::safe_prune_before(inner.get_main(), horizon);
+        assert_eq!(prune_before, 901);
+        let old_block = inner
+            .main_block_header_by_number(500)?
+            .expect("header should be kept");
+        while inner.prune_blocks(horizon, 300)? > 0 {}
+        let prune_info = storage.get_prune_info()?.expect("prune info should exist");
+        assert_eq!(prune_info.pruned_before, prune_before);
+        assert!(prune_info.pruned_bytes > 0);
+
+        // the headers and the genesis are kept, the pruned data returns the pruned error.
+        assert!(storage.get_block_by_hash(old_block.id())?.is_none());
+        assert!(inner.get_header_by_hash(old_block.id())?.is_some());
+        assert!(inner.main_block_by_number(0)?.is_some());
+        let err = inner.main_block_by_number(500).unwrap_err();
+        assert!(err.is::<BlockPrunedError>());
+        let err = inner.get_block_by_hash(old_block.id()).unwrap_err();
+        assert!(err.is::<BlockPrunedError>());
+        let err = inner.get_block_txn_infos(old_block.id()).unwrap_err();
+        assert!(err.is::<BlockPrunedError>());
+
+        // the head queries and the proofs of the recent txns still work.
+        assert_eq!(inner.main_head_block().id(), head.id());
+        assert_eq!(inner.main_blocks_by_number(None, 100)?.len(), 100);
+        let recent = inner
+            .main_block_header_by_number(990)?
+            .expect("header should exist");
+        let proof = inner
+            .get_transaction_proof(recent.id(), 0, None)?
+            .expect("proof should exist");
+        proof.verify(recent.txn_accumulator_root())?;
+        assert!(inner.get_epoch_uncles_by_number(None).is_ok());
+
+        // the new blocks from the peer can still be synced, even after a restart.
+        peer.produce_and_apply_times(10)?;
+        let mut local =
+            BlockChain::new(inner.get_main().time_service(), head.id(), storage.clone())?;
+        let blocks = peer.head().get_blocks_by_number(None, 10)?;
+        for block in blocks.into_iter().rev() {
+            local.apply(block)?;
+        }
+        assert_eq!(
+            local.current_header().id(),
+            peer.head().current_header().id()
+        );
+        Ok(())
+    }
+}
@@ -1,36 +1,54 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::block_pruner::{BlockPruner, MAX_PRUNE_BLOCKS_PER_ROUND};
+use crate::metrics::CHAIN_SERVICE_METRICS;
 use anyhow::{format_err, Error, Result};
 use starcoin_chain::BlockChain;
 use starcoin_chain_api::message::{ChainRequest, ChainResponse};
 use starcoin_chain_api::{ChainReader, ChainWriter, ReadableChainService};
-use starcoin_config::NodeConfig;
+use starcoin_config::{NodeConfig, PruneHorizon};
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_service_registry::{
     ActorService, EventHandler, ServiceContext, ServiceFactory, ServiceHandler,
 };
 use starcoin_storage::{BlockStore, Storage, Store};
-use starcoin_types::block::{BlockSummary, EpochUncleSummary, ExecutedBlock, UncleSummary};
+use starcoin_types::block::{
+    BlockReward, BlockSummary, BranchInfo, EpochUncleSummary, ExecutedBlock, UncleSummary,
+};
 use starcoin_types::contract_event::ContractEventInfo;
 use starcoin_types::filter::Filter;
-use starcoin_types::system_events::NewHeadBlock;
-use starcoin_types::transaction::BlockTransactionInfo;
+use starcoin_types::proof::TransactionInfoWithProof;
+use starcoin_types::sync_status::SyncStatus;
+use starcoin_types::system_events::{NewBranch, NewHeadBlock, SyncStatusChangeEvent};
+use starcoin_types::transaction::{BlockTransactionInfo, TransactionPosition};
 use starcoin_types::{
     block::{Block, BlockHeader, BlockInfo, BlockNumber},
     contract_event::ContractEvent,
     startup_info::StartupInfo,
     transaction::Transaction,
 };
+use starcoin_vm_types::account_config::BlockRewardEvent;
 use starcoin_vm_types::on_chain_resource::{EpochInfo, GlobalTimeOnChain};
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// A Chain reader service to provider Reader API.
 pub struct ChainReaderService {
     inner: ChainReaderServiceInner,
+    sync_status: Option<SyncStatus>,
 }
 
+/// Prune the data of the main chain blocks before the configured horizon.
+#[derive(Clone, Debug)]
+struct PruneBlocksTick;
+
+/// The branches whose head is more than this count of blocks behind the main head are not tracked.
+const MAX_BRANCH_AGE: u64 = 1000;
+
 impl ChainReaderService {
     pub fn new(
         config: Arc<NodeConfig>,
@@ -39,6 +57,7 @@ impl ChainReaderService {
     ) -> Result<Self> {
         Ok(Self {
             inner: ChainReaderServiceInner::new(config, startup_info, storage)?,
+            sync_status: None,
         })
     }
 }
@@ -57,28 +76,70 @@ impl ServiceFactory<Self> for ChainReaderService {
 impl ActorService for ChainReaderService {
     fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.subscribe::<NewHeadBlock>();
+        ctx.subscribe::<NewBranch>();
+        ctx.subscribe::<SyncStatusChangeEvent>();
+        let storage_config = &self.inner.config.storage;
+        if let Some(horizon) = storage_config.prune_blocks_before() {
+            info!("Prune the data of the blocks before {}.", horizon);
+            ctx.run_interval(
+                Duration::from_secs(storage_config.prune_interval()),
+                |ctx| ctx.notify(PruneBlocksTick),
+            );
+        }
         Ok(())
     }
 
     fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.unsubscribe::<NewHeadBlock>();
+        ctx.unsubscribe::<NewBranch>();
+        ctx.unsubscribe::<SyncStatusChangeEvent>();
         Ok(())
     }
 }
 
+impl EventHandler<Self, SyncStatusChangeEvent> for ChainReaderService {
+    fn handle_event(&mut self, msg: SyncStatusChangeEvent, _ctx: &mut ServiceContext<Self>) {
+        self.sync_status = Some(msg.0);
+    }
+}
+
+impl EventHandler<Self, PruneBlocksTick> for ChainReaderService {
+    fn handle_event(&mut self, _msg: PruneBlocksTick, _ctx: &mut ServiceContext<Self>) {
+        let horizon = match self.inner.config.storage.prune_blocks_before() {
+            Some(horizon) => horizon,
+            None => return,
+        };
+        // the sync writes the blocks and switches the main chain, so never prune before it is done.
+        if !self
+            .sync_status
+            .as_ref()
+            .map(|sync_status| sync_status.is_synced())
+            .unwrap_or(false)
+        {
+            debug!("Skip the block pruning because the node is not synced.");
+            return;
+        }
+        if let Err(e) = self.inner.prune_blocks(horizon, MAX_PRUNE_BLOCKS_PER_ROUND) {
+            warn!("ChainReaderService prune blocks err: {:?}", e);
+        }
+    }
+}
+
 impl EventHandler<Self, NewHeadBlock> for ChainReaderService {
     fn handle_event(&mut self, event: NewHeadBlock, _ctx: &mut ServiceContext<ChainReaderService>) {
-        let new_head = event.0.block().header();
-        if let Err(e) = if self.inner.get_main().can_connect(event.0.as_ref()) {
-            self.inner.update_chain_head(event.0.as_ref().clone())
-        } else {
-            self.inner.switch_main(new_head.id())
-        } {
+        if let Err(e) = self.inner.update_head(event.0.as_ref().clone()) {
             warn!("ChainReaderService handle NewHeadBlock err: {:?}", e);
         }
     }
 }
 
+impl EventHandler<Self, NewBranch> for ChainReaderService {
+    fn handle_event(&mut self, event: NewBranch, _ctx: &mut ServiceContext<ChainReaderService>) {
+        self.inner
+            .add_branch_block(event.0.block().header().clone());
+    }
+}
+
 impl ServiceHandler<Self, ChainRequest> for ChainReaderService {
     fn handle(
         &mut self,
@@ -132,6 +193,16 @@ impl ServiceHandler<Self, ChainRequest> for ChainReaderService {
             ChainRequest::GetTransaction(hash) => Ok(ChainResponse::TransactionOption(
                 self.inner.get_transaction(hash)?.map(Box::new),
             )),
+            ChainRequest::GetMainTransactions(txn_hashes) => {
+                Ok(ChainResponse::TransactionWithInfoOptionVec(
+                    self.inner.get_main_transactions(txn_hashes)?,
+                ))
+            }
+            ChainRequest::GetTransactionPositions(txn_hashes) => {
+                Ok(ChainResponse::TransactionPositionOptionVec(
+                    self.inner.get_transaction_positions(txn_hashes)?,
+                ))
+            }
             ChainRequest::GetTransactionBlock(txn_id) => {
                 let block_id = self
                     .inner
@@ -190,11 +261,13 @@ impl ServiceHandler<Self, ChainRequest> for ChainReaderService {
 
                     events
                         .into_iter()
-                        .map(|evt| ContractEventInfo {
+                        .enumerate()
+                        .map(|(event_index, evt)| ContractEventInfo {
                             block_hash,
                             block_number: block.header().number(),
                             transaction_hash: txn_hash,
                             transaction_index: index as u32,
+                            event_index: event_index as u32,
                             event: evt,
                         })
                         .collect()
@@ -220,12 +293,20 @@ impl ServiceHandler<Self, ChainRequest> for ChainReaderService {
                 reverse,
                 max_size,
             )?)),
+            ChainRequest::GetTransactionInfos {
+                start_index,
+                reverse,
+                max_size,
+            } => Ok(ChainResponse::TransactionInfos(
+                self.inner
+                    .get_transaction_infos(start_index, reverse, max_size)?,
+            )),
             ChainRequest::GetBlocks(ids) => {
                 Ok(ChainResponse::BlockOptionVec(self.inner.get_blocks(ids)?))
             }
-            ChainRequest::GetHeaders(ids) => {
-                Ok(ChainResponse::BlockHeaderVec(self.inner.get_headers(ids)?))
-            }
+            ChainRequest::GetHeaders(ids) => Ok(ChainResponse::BlockHeaderOptionVec(
+                self.inner.get_headers(ids)?,
+            )),
             ChainRequest::GetEpochUnclesByNumber(number) => Ok(ChainResponse::BlockSummaries(
                 self.inner.get_epoch_uncles_by_number(number)?,
             )),
@@ -235,6 +316,30 @@ impl ServiceHandler<Self, ChainRequest> for ChainReaderService {
             ChainRequest::EpochUncleSummaryByNumber(number) => Ok(ChainResponse::UncleSummary(
                 self.inner.epoch_uncle_summary_by_number(number)?,
             )),
+            ChainRequest::GetBlockByUncleHash(uncle_id) => Ok(ChainResponse::BlockOption(
+                self.inner.get_block_by_uncle_hash(uncle_id)?.map(Box::new),
+            )),
+            ChainRequest::GetUnclesByNumber(number) => Ok(ChainResponse::BlockHeaderVec(
+                self.inner.main_uncles_by_number(number)?,
+            )),
+            ChainRequest::GetBlockRewards {
+                start_number,
+                count,
+            } => Ok(ChainResponse::BlockRewards(
+                self.inner.main_block_rewards(start_number, count)?,
+            )),
+            ChainRequest::GetTransactionProof {
+                block_id,
+                transaction_index,
+                event_index,
+            } => Ok(ChainResponse::TransactionProof(Box::new(
+                self.inner
+                    .get_transaction_proof(block_id, transaction_index, event_index)?,
+            ))),
+            ChainRequest::GetBranches() => Ok(ChainResponse::Branches(self.inner.get_branches()?)),
+            ChainRequest::GetCommonAncestor(hash1, hash2) => Ok(ChainResponse::HashValueOption(
+                self.inner.get_common_ancestor(hash1, hash2)?,
+            )),
         }
     }
 }
@@ -244,6 +349,9 @@ pub struct ChainReaderServiceInner {
     startup_info: StartupInfo,
     main: BlockChain,
     storage: Arc<dyn Store>,
+    pruner: BlockPruner,
+    /// The heads of the branches besides the main chain, by the head block id.
+    branches: HashMap<HashValue, BlockHeader>,
 }
 
 impl ChainReaderServiceInner {
@@ -254,11 +362,14 @@ impl ChainReaderServiceInner {
     ) -> Result<Self> {
         let net = config.net();
         let main = BlockChain::new(net.time_service(), startup_info.main, storage.clone())?;
+        let pruner = BlockPruner::new(storage.clone())?;
         Ok(Self {
             config,
             startup_info,
             main,
             storage,
+            pruner,
+            branches: HashMap::new(),
         })
     }
 
@@ -277,6 +388,97 @@ impl ChainReaderServiceInner {
         Ok(())
     }
 
+    /// Update the main chain to the new head, the old head becomes a branch head if the main chain is reorganized.
+    pub fn update_head(&mut self, block: ExecutedBlock) -> Result<()> {
+        if self.main.can_connect(&block) {
+            self.update_chain_head(block)?;
+        } else {
+            let old_head = self.main.current_header();
+            let new_head_id = block.header().id();
+            self.switch_main(new_head_id)?;
+            let ancestor = self
+                .get_common_ancestor(old_head.id(), new_head_id)?
+                .and_then(|ancestor| self.storage.get_block_header_by_hash(ancestor).transpose())
+                .transpose()?
+                .ok_or_else(|| {
+                    format_err!(
+                        "Can not find the common ancestor of {} and {}",
+                        old_head.id(),
+                        new_head_id
+                    )
+                })?;
+            let depth = old_head.number().saturating_sub(ancestor.number());
+            if depth > 0 {
+                info!(
+                    "Main chain reorg from {}({}) to {}({}), common ancestor: {}({}), depth: {}",
+                    old_head.id(),
+                    old_head.number(),
+                    new_head_id,
+                    block.header().number(),
+                    ancestor.id(),
+                    ancestor.number(),
+                    depth
+                );
+                CHAIN_SERVICE_METRICS.last_reorg_depth.set(depth as i64);
+                self.branches.insert(old_head.id(), old_head);
+            }
+        }
+        self.prune_branches()
+    }
+
+    /// Track the block applied to a branch, the block replaces its parent as the branch head.
+    pub fn add_branch_block(&mut self, header: BlockHeader) {
+        self.branches.remove(&header.parent_hash());
+        self.branches.insert(header.id(), header);
+        CHAIN_SERVICE_METRICS
+            .branch_count
+            .set(self.branches.len() as i64);
+    }
+
+    /// Drop the branches merged into the main chain, or too far behind the main head.
+    fn prune_branches(&mut self) -> Result<()> {
+        let head_number = self.main.current_header().number();
+        let mut pruned = vec![];
+        for (id, header) in self.branches.iter() {
+            if header.number().saturating_add(MAX_BRANCH_AGE) < head_number
+                || self.is_main_block(header)?
+            {
+                pruned.push(*id);
+            }
+        }
+        for id in pruned {
+            self.branches.remove(&id);
+        }
+        CHAIN_SERVICE_METRICS
+            .branch_count
+            .set(self.branches.len() as i64);
+        Ok(())
+    }
+
+    fn is_main_block(&self, header: &BlockHeader) -> Result<bool> {
+        Ok(self.main.get_hash_by_number(header.number())? == Some(header.id()))
+    }
+
+    /// Prune at most `max_blocks` main chain blocks before the horizon, return the count of the pruned blocks.
+    pub fn prune_blocks(&mut self, horizon: PruneHorizon, max_blocks: u64) -> Result<u64> {
+        self.pruner.prune(&self.main, horizon, max_blocks)
+    }
+
+    /// Return the `BlockPrunedError` if the data of the block is missing because it is pruned.
+    fn ensure_block_not_pruned(&self, block_id: HashValue) -> Result<()> {
+        if self.pruner.prune_info().pruned_before == 0 {
+            return Ok(());
+        }
+        if let Some(header) = self.storage.get_block_header_by_hash(block_id)? {
+            // the fork blocks at the pruned numbers are not pruned.
+            if self.pruner.is_pruned(header.number()) && self.storage.get_body(block_id)?.is_none()
+            {
+                self.pruner.ensure_not_pruned(header.number())?;
+            }
+        }
+        Ok(())
+    }
+
     fn uncle_summary(
         &self,
         start_number: BlockNumber,
@@ -284,6 +486,7 @@ impl ChainReaderServiceInner {
     ) -> Result<(u64, u64)> {
         let mut sum: u64 = 0;
         let mut time_sum: u64 = 0;
+        self.pruner.ensure_not_pruned(start_number)?;
         for num in start_number..(end_number + 1) {
             if let Some(block) = self.main.get_block_by_number(num)? {
                 if let Some(block_uncles) = block.uncles() {
@@ -305,21 +508,21 @@ impl ReadableChainService for ChainReaderServiceInner {
     }
 
     fn get_block_by_hash(&self, hash: HashValue) -> Result<Option<Block>> {
-        self.storage.get_block_by_hash(hash)
+        let block = self.storage.get_block_by_hash(hash)?;
+        if block.is_none() {
+            self.ensure_block_not_pruned(hash)?;
+        }
+        Ok(block)
     }
 
     fn get_blocks(&self, ids: Vec<HashValue>) -> Result<Vec<Option<Block>>> {
         self.storage.get_blocks(ids)
     }
 
-    fn get_headers(&self, ids: Vec<HashValue>) -> Result<Vec<BlockHeader>> {
-        let mut headers = Vec::new();
-        self.get_blocks(ids)?.into_iter().for_each(|block| {
-            if let Some(b) = block {
-                headers.push(b.header)
-            }
-        });
-        Ok(headers)
+    fn get_headers(&self, ids: Vec<HashValue>) -> Result<Vec<Option<BlockHeader>>> {
+        ids.into_iter()
+            .map(|id| self.storage.get_block_header_by_hash(id))
+            .collect()
     }
 
     fn get_block_info_by_hash(&self, hash: HashValue) -> Result<Option<BlockInfo>> {
@@ -337,7 +540,30 @@ impl ReadableChainService for ChainReaderServiceInner {
         self.main.get_transaction_info(txn_hash)
     }
 
+    fn get_main_transactions(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> Result<Vec<Option<(Transaction, BlockTransactionInfo)>>, Error> {
+        let txn_infos = self
+            .main
+            .get_transaction_infos_by_hashes(txn_hashes.clone())?;
+        let txns = self.storage.get_transactions(txn_hashes)?;
+        Ok(txns
+            .into_iter()
+            .zip(txn_infos)
+            .map(|(txn, txn_info)| txn.zip(txn_info))
+            .collect())
+    }
+
+    fn get_transaction_positions(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> Result<Vec<Option<TransactionPosition>>, Error> {
+        self.main.get_transaction_positions(txn_hashes)
+    }
+
     fn get_block_txn_infos(&self, block_id: HashValue) -> Result<Vec<BlockTransactionInfo>, Error> {
+        self.ensure_block_not_pruned(block_id)?;
         self.storage.get_block_transaction_infos(block_id)
     }
 
@@ -346,6 +572,7 @@ impl ReadableChainService for ChainReaderServiceInner {
         block_id: HashValue,
         idx: u64,
     ) -> Result<Option<BlockTransactionInfo>, Error> {
+        self.ensure_block_not_pruned(block_id)?;
         self.storage
             .get_transaction_info_by_block_and_index(block_id, idx)
     }
@@ -365,6 +592,7 @@ impl ReadableChainService for ChainReaderServiceInner {
     }
 
     fn main_block_by_number(&self, number: BlockNumber) -> Result<Option<Block>> {
+        self.pruner.ensure_not_pruned(number)?;
         self.main.get_block_by_number(number)
     }
 
@@ -380,6 +608,9 @@ impl ReadableChainService for ChainReaderServiceInner {
         self.startup_info.clone()
     }
     fn main_blocks_by_number(&self, number: Option<BlockNumber>, count: u64) -> Result<Vec<Block>> {
+        let end_number = number.unwrap_or_else(|| self.main.current_header().number());
+        self.pruner
+            .ensure_not_pruned(end_number.saturating_add(1).saturating_sub(count))?;
         self.main.get_blocks_by_number(number, count)
     }
 
@@ -396,7 +627,12 @@ impl ReadableChainService for ChainReaderServiceInner {
     }
 
     fn get_main_events(&self, filter: Filter) -> Result<Vec<ContractEventInfo>> {
-        self.main.filter_events(filter)
+        let from_block = filter.from_block;
+        // the filter may stop before reaching the pruned blocks, such as the reverse one with a limit.
+        self.main.filter_events(filter).or_else(|e| {
+            self.pruner.ensure_not_pruned(from_block)?;
+            Err(e)
+        })
     }
 
     fn get_block_ids(
@@ -408,6 +644,16 @@ impl ReadableChainService for ChainReaderServiceInner {
         self.main.get_block_ids(start_number, reverse, max_size)
     }
 
+    fn get_transaction_infos(
+        &self,
+        start_index: u64,
+        reverse: bool,
+        max_size: u64,
+    ) -> Result<Vec<BlockTransactionInfo>> {
+        self.main
+            .get_transaction_infos(start_index, reverse, max_size)
+    }
+
     fn get_epoch_uncles_by_number(&self, number: Option<BlockNumber>) -> Result<Vec<BlockSummary>> {
         let epoch_info = self.main.get_epoch_info_by_number(number)?;
         let start_number = epoch_info.start_block_number();
@@ -416,6 +662,7 @@ impl ReadableChainService for ChainReaderServiceInner {
             end_number = self.main.current_header().number();
         }
 
+        self.pruner.ensure_not_pruned(start_number)?;
         let mut block_summaries: Vec<BlockSummary> = Vec::new();
         for number in start_number..(end_number + 1) {
             if let Some(block) = self.main.get_block_by_number(number)? {
@@ -492,12 +739,184 @@ impl ReadableChainService for ChainReaderServiceInner {
             epoch_uncle_summary,
         ))
     }
+
+    fn get_block_by_uncle_hash(&self, uncle_id: HashValue) -> Result<Option<Block>> {
+        for block_id in self.storage.get_block_ids_by_uncle(uncle_id)? {
+            if self.main.exist_block(block_id)? {
+                return self.storage.get_block_by_hash(block_id);
+            }
+        }
+        Ok(None)
+    }
+
+    fn main_uncles_by_number(&self, number: BlockNumber) -> Result<Vec<BlockHeader>> {
+        self.pruner.ensure_not_pruned(number)?;
+        Ok(self
+            .main
+            .get_block_by_number(number)?
+            .and_then(|block| block.uncles().map(|uncles| uncles.to_vec()))
+            .unwrap_or_default())
+    }
+
+    fn main_block_rewards(
+        &self,
+        start_number: BlockNumber,
+        count: u64,
+    ) -> Result<Vec<BlockReward>> {
+        let head_number = self.main.current_header().number();
+        // the genesis block has no reward.
+        let start_number = start_number.max(1);
+        if count == 0 || start_number > head_number {
+            return Ok(vec![]);
+        }
+        let end_number = start_number.saturating_add(count - 1).min(head_number);
+        self.pruner.ensure_not_pruned(start_number + 1)?;
+        // the reward of a block is distributed by the block metadata txn of a later block,
+        // the events are emitted in the order of block number.
+        let mut reward_events = BTreeMap::new();
+        for number in (start_number + 1)..=head_number {
+            let block_id = self
+                .main
+                .get_hash_by_number(number)?
+                .ok_or_else(|| format_err!("Can not find block hash by number {}", number))?;
+            let metadata_txn_info = self
+                .storage
+                .get_transaction_info_by_block_and_index(block_id, 0)?
+                .ok_or_else(|| format_err!("Can not find block metadata txn of {}", block_id))?;
+            let events = self
+                .storage
+                .get_contract_events(metadata_txn_info.id())?
+                .unwrap_or_default();
+            let mut last_found = false;
+            for event in events.iter() {
+                if let Ok(reward_event) = BlockRewardEvent::try_from(event) {
+                    last_found = last_found || reward_event.block_number >= end_number;
+                    if reward_event.block_number >= start_number
+                        && reward_event.block_number <= end_number
+                    {
+                        reward_events.insert(reward_event.block_number, reward_event);
+                    }
+                }
+            }
+            if last_found {
+                break;
+            }
+        }
+        reward_events
+            .into_iter()
+            .map(|(number, reward_event)| {
+                let header = self
+                    .main
+                    .get_header_by_number(number)?
+                    .ok_or_else(|| format_err!("Can not find block header by number {}", number))?;
+                // the epoch at the block state is the one rewarding the block,
+                // even if the block starts a new epoch.
+                let base_reward = self
+                    .main
+                    .get_epoch_info_by_number(Some(number))?
+                    .reward_per_block();
+                Ok(BlockReward {
+                    block_number: number,
+                    block_hash: header.id(),
+                    miner: reward_event.miner,
+                    block_reward: reward_event.block_reward,
+                    base_reward,
+                    txn_fees: reward_event.gas_fees,
+                    gas_used: header.gas_used(),
+                })
+            })
+            .collect()
+    }
+
+    fn get_transaction_proof(
+        &self,
+        block_id: HashValue,
+        transaction_index: u64,
+        event_index: Option<u64>,
+    ) -> Result<Option<TransactionInfoWithProof>> {
+        self.ensure_block_not_pruned(block_id)?;
+        self.main
+            .get_transaction_proof(block_id, transaction_index, event_index)
+    }
+
+    fn get_branches(&self) -> Result<Vec<BranchInfo>> {
+        let main_head = self.main.current_header();
+        let mut branches = vec![BranchInfo {
+            total_difficulty: self.main.get_total_difficulty()?,
+            head: main_head.clone(),
+            length_from_common_ancestor: 0,
+        }];
+        for head in self.branches.values() {
+            let block_info = self
+                .storage
+                .get_block_info(head.id())?
+                .ok_or_else(|| format_err!("Can not find block info of {}", head.id()))?;
+            let ancestor = self
+                .get_common_ancestor(head.id(), main_head.id())?
+                .and_then(|ancestor| self.storage.get_block_header_by_hash(ancestor).transpose())
+                .transpose()?
+                .ok_or_else(|| format_err!("Can not find the common ancestor of {}", head.id()))?;
+            branches.push(BranchInfo {
+                head: head.clone(),
+                total_difficulty: block_info.total_difficulty,
+                length_from_common_ancestor: head.number().saturating_sub(ancestor.number()),
+            });
+        }
+        // the heaviest branch first, the main chain is always the first.
+        branches[1..].sort_by(|a, b| b.total_difficulty.cmp(&a.total_difficulty));
+        Ok(branches)
+    }
+
+    fn get_common_ancestor(&self, hash1: HashValue, hash2: HashValue) -> Result<Option<HashValue>> {
+        let (mut header1, mut header2) = match (
+            self.storage.get_block_header_by_hash(hash1)?,
+            self.storage.get_block_header_by_hash(hash2)?,
+        ) {
+            (Some(header1), Some(header2)) => (header1, header2),
+            _ => return Ok(None),
+        };
+        loop {
+            if header1.id() == header2.id() {
+                return Ok(Some(header1.id()));
+            }
+            let on_main1 = self.is_main_block(&header1)?;
+            let on_main2 = self.is_main_block(&header2)?;
+            // the common ancestor of two main chain blocks is the lower one,
+            // so only walk back the branch blocks until they reach the main chain.
+            let step_first = match (on_main1, on_main2) {
+                (true, true) => {
+                    return Ok(Some(if header1.number() <= header2.number() {
+                        header1.id()
+                    } else {
+                        header2.id()
+                    }))
+                }
+                (true, false) => false,
+                (false, true) => true,
+                (false, false) => header1.number() >= header2.number(),
+            };
+            let header = if step_first { &header1 } else { &header2 };
+            let parent = match self
+                .storage
+                .get_block_header_by_hash(header.parent_hash())?
+            {
+                Some(parent) => parent,
+                None => return Ok(None),
+            };
+            if step_first {
+                header1 = parent;
+            } else {
+                header2 = parent;
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use starcoin_chain_api::ChainAsyncService;
+    use starcoin_chain_mock::MockChain;
     use starcoin_config::NodeConfig;
     use starcoin_service_registry::{RegistryAsyncService, RegistryService};
 
@@ -513,4 +932,161 @@ mod tests {
         assert_eq!(&chain_status, chain_info.status());
         Ok(())
     }
+
+    fn executed_block(storage: &dyn Store, id: HashValue) -> Result<ExecutedBlock> {
+        let block = storage
+            .get_block_by_hash(id)?
+            .ok_or_else(|| format_err!("block {} should exist", id))?;
+        let block_info = storage
+            .get_block_info(id)?
+            .ok_or_else(|| format_err!("block info {} should exist", id))?;
+        Ok(ExecutedBlock::new(block, block_info))
+    }
+
+    #[stest::test]
+    fn test_branches_and_common_ancestor() -> Result<()> {
+        let config = Arc::new(NodeConfig::random_for_test());
+        let mut main = MockChain::new(config.net().clone())?;
+        main.produce_and_apply_times(10)?;
+        let storage = main.head().get_storage();
+        let fork_point = main.head().get_header_by_number(5)?.unwrap();
+        let mut branch = main.fork(Some(fork_point.id()))?;
+        let mut inner = ChainReaderServiceInner::new(
+            config,
+            StartupInfo::new(main.head().current_header().id()),
+            storage.clone(),
+        )?;
+        for _ in 0..3 {
+            let header = branch.produce_and_apply()?;
+            inner.add_branch_block(header);
+        }
+        let branch_head = branch.head().current_header();
+        let main_head = main.head().current_header();
+
+        let branches = inner.get_branches()?;
+        assert_eq!(branches.len(), 2);
+        assert_eq!(branches[0].head.id(), main_head.id());
+        assert_eq!(branches[0].length_from_common_ancestor, 0);
+        assert_eq!(branches[1].head.id(), branch_head.id());
+        assert_eq!(branches[1].length_from_common_ancestor, 3);
+        assert_eq!(
+            branches[1].total_difficulty,
+            branch.head().get_total_difficulty()?
+        );
+
+        assert_eq!(
+            inner.get_common_ancestor(branch_head.id(), main_head.id())?,
+            Some(fork_point.id())
+        );
+        let main_block3 = main.head().get_header_by_number(3)?.unwrap();
+        assert_eq!(
+            inner.get_common_ancestor(main_head.id(), main_block3.id())?,
+            Some(main_block3.id())
+        );
+        assert_eq!(
+            inner.get_common_ancestor(main_block3.id(), branch_head.id())?,
+            Some(main_block3.id())
+        );
+        assert_eq!(
+            inner.get_common_ancestor(main_head.id(), HashValue::random())?,
+            None
+        );
+
+        // the branch outgrows the main chain, and the old main head becomes a branch.
+        for _ in 0..5 {
+            let header = branch.produce_and_apply()?;
+            inner.add_branch_block(header);
+        }
+        let new_head = branch.head().current_header();
+        inner.update_head(executed_block(storage.as_ref(), new_head.id())?)?;
+        assert_eq!(inner.main_head_header().id(), new_head.id());
+        assert_eq!(CHAIN_SERVICE_METRICS.last_reorg_depth.get(), 5);
+        let branches = inner.get_branches()?;
+        assert_eq!(branches.len(), 2);
+        assert_eq!(branches[0].head.id(), new_head.id());
+        assert_eq!(branches[1].head.id(), main_head.id());
+        assert_eq!(branches[1].length_from_common_ancestor, 5);
+        Ok(())
+    }
+
+    fn reader_of(config: Arc<NodeConfig>, chain: &MockChain) -> Result<ChainReaderServiceInner> {
+        ChainReaderServiceInner::new(
+            config,
+            StartupInfo::new(chain.head().current_header().id()),
+            chain.head().get_storage(),
+        )
+    }
+
+    #[stest::test]
+    fn test_block_rewards_of_zero_txn_blocks() -> Result<()> {
+        let config = Arc::new(NodeConfig::random_for_test());
+        let reward_delay = config.net().genesis_config().reward_delay;
+        let mut chain = MockChain::new(config.net().clone())?;
+        chain.produce_and_apply_times(5)?;
+        let inner = reader_of(config, &chain)?;
+        let head_number = chain.head().current_header().number();
+
+        let rewards = inner.main_block_rewards(0, 100)?;
+        // the genesis has no reward, and the rewards of the latest blocks are not distributed yet.
+        assert_eq!(rewards.len() as u64, head_number - reward_delay);
+        for (reward, number) in rewards.iter().zip(1..) {
+            let header = chain.head().get_header_by_number(number)?.unwrap();
+            assert_eq!(reward.block_number, number);
+            assert_eq!(reward.block_hash, header.id());
+            assert_eq!(reward.miner, *chain.miner().address());
+            // the blocks only have the block metadata txn, which charges no gas.
+            assert_eq!(reward.gas_used, 0);
+            assert_eq!(reward.txn_fees, 0);
+            assert_eq!(reward.uncle_inclusion_reward(), 0);
+            assert_eq!(reward.total_reward(), reward.block_reward);
+            assert_eq!(reward.block_reward, reward.base_reward);
+        }
+        let page = inner.main_block_rewards(2, 2)?;
+        assert_eq!(
+            page.iter()
+                .map(|reward| reward.block_number)
+                .collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert!(inner.main_block_rewards(head_number, 1)?.is_empty());
+        assert!(inner.main_block_rewards(1, 0)?.is_empty());
+        Ok(())
+    }
+
+    #[stest::test]
+    fn test_block_rewards_across_epoch_boundary() -> Result<()> {
+        let config = Arc::new(NodeConfig::random_for_test());
+        let reward_delay = config.net().genesis_config().reward_delay;
+        let mut chain = MockChain::new(config.net().clone())?;
+        chain.produce_and_apply()?;
+        let first_epoch = chain.head().get_epoch_info_by_number(Some(1))?;
+        let boundary = first_epoch.end_block_number();
+        // produce until the rewards of the blocks after the boundary are distributed.
+        while chain.head().current_header().number() < boundary + reward_delay + 2 {
+            chain.produce_and_apply()?;
+        }
+        let second_epoch = chain.head().epoch_info()?;
+        assert_eq!(second_epoch.start_block_number(), boundary);
+        let inner = reader_of(config, &chain)?;
+
+        let rewards = inner.main_block_rewards(boundary - 2, 4)?;
+        assert_eq!(
+            rewards
+                .iter()
+                .map(|reward| reward.block_number)
+                .collect::<Vec<_>>(),
+            vec![boundary - 2, boundary - 1, boundary, boundary + 1]
+        );
+        for reward in rewards {
+            // the block starting the new epoch is rewarded by the new epoch.
+            let epoch = if reward.block_number < boundary {
+                &first_epoch
+            } else {
+                &second_epoch
+            };
+            assert_eq!(reward.base_reward, epoch.reward_per_block());
+            assert_eq!(reward.block_reward, epoch.reward_per_block());
+        }
+        Ok(())
+    }
 }
@@ -1,7 +1,10 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+mod block_pruner;
 mod chain_service;
+mod metrics;
 
+pub use block_pruner::BlockPruner;
 pub use chain_service::ChainReaderService;
 pub use starcoin_chain_api::{ChainAsyncService, ReadableChainService, WriteableChainService};
@@ -0,0 +1,38 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use once_cell::sync::Lazy;
+use starcoin_metrics::{register_int_gauge, IntGauge, Opts, PrometheusError};
+
+const SC_NS: &str = "starcoin";
+const PREFIX: &str = "starcoin_chain_service_";
+
+pub static CHAIN_SERVICE_METRICS: Lazy<ChainServiceMetrics> =
+    Lazy::new(|| ChainServiceMetrics::register().unwrap());
+
+#[derive(Clone)]
+pub struct ChainServiceMetrics {
+    pub last_reorg_depth: IntGauge,
+    pub branch_count: IntGauge,
+}
+
+impl ChainServiceMetrics {
+    pub fn register() -> Result<Self, PrometheusError> {
+        let last_reorg_depth = register_int_gauge!(Opts::new(
+            format!("{}{}", PREFIX, "last_reorg_depth"),
+            "the count of the main chain blocks retracted by the last reorg".to_string()
+        )
+        .namespace(SC_NS))?;
+
+        let branch_count = register_int_gauge!(Opts::new(
+            format!("{}{}", PREFIX, "branch_count"),
+            "the count of the branches tracked besides the main chain".to_string()
+        )
+        .namespace(SC_NS))?;
+
+        Ok(Self {
+            last_reorg_depth,
+            branch_count,
+        })
+    }
+}
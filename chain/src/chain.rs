@@ -3,12 +3,15 @@
 
 use crate::verifier::{BlockVerifier, FullVerifier};
 use anyhow::{ensure, format_err, Result};
+use bcs_ext::BCSCodec;
 use consensus::Consensus;
+use crypto::hash::PlainCryptoHash;
 use crypto::HashValue;
 use logger::prelude::*;
 use sp_utils::stop_watch::{watch, CHAIN_WATCH_NAME};
 use starcoin_accumulator::{
-    accumulator_info::AccumulatorInfo, node::AccumulatorStoreType, Accumulator, MerkleAccumulator,
+    accumulator_info::AccumulatorInfo, node::AccumulatorStoreType,
+    tree_store::mock::MockAccumulatorStore, Accumulator, MerkleAccumulator,
 };
 use starcoin_chain_api::{
     verify_block, ChainReader, ChainWriter, ConnectBlockError, ExcludedTxns, ExecutedBlock,
@@ -19,9 +22,10 @@ use starcoin_state_api::{AccountStateReader, ChainState, ChainStateReader, Chain
 use starcoin_statedb::ChainStateDB;
 use starcoin_types::block::BlockIdAndNumber;
 use starcoin_types::contract_event::ContractEventInfo;
-use starcoin_types::filter::Filter;
+use starcoin_types::filter::{EventPosition, Filter};
+use starcoin_types::proof::{EventWithProof, TransactionInfoWithProof};
 use starcoin_types::startup_info::{ChainInfo, ChainStatus};
-use starcoin_types::transaction::BlockTransactionInfo;
+use starcoin_types::transaction::{BlockTransactionInfo, TransactionPosition};
 use starcoin_types::{
     account_address::AccountAddress,
     block::{Block, BlockHeader, BlockInfo, BlockNumber, BlockTemplate},
@@ -30,15 +34,22 @@ use starcoin_types::{
     transaction::{SignedUserTransaction, Transaction, TransactionInfo},
     U256,
 };
-use starcoin_vm_types::account_config::genesis_address;
+use starcoin_vm_types::access_path::DataPath;
+use starcoin_vm_types::account_config::{genesis_address, BalanceResource};
 use starcoin_vm_types::genesis_config::ConsensusStrategy;
+use starcoin_vm_types::language_storage::StructTag;
 use starcoin_vm_types::on_chain_resource::{Epoch, EpochData, EpochInfo, GlobalTimeOnChain};
 use starcoin_vm_types::time::TimeService;
 use starcoin_vm_types::transaction::authenticator::AuthenticationKey;
+use starcoin_vm_types::write_set::WriteSet;
 use std::cmp::min;
 use std::iter::Extend;
 use std::option::Option::{None, Some};
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Arc,
+};
+use storage::token_holder::TokenHolderKey;
 use storage::Store;
 
 pub struct ChainStatusWithBlock {
@@ -451,6 +462,12 @@ impl BlockChain {
         );
 
         watch(CHAIN_WATCH_NAME, "n25");
+        if storage.is_token_holder_index_enabled() {
+            storage.save_block_token_holder_changes(
+                block_id,
+                token_holder_changes(&executed_data.txn_write_sets),
+            )?;
+        }
         // save block's transaction relationship and save transaction
         Self::save(
             storage,
@@ -463,7 +480,8 @@ impl BlockChain {
         Ok(ExecutedBlock { block, block_info })
     }
 
-    fn save(
+    /// Save the block and its transactions, transaction infos and events to the storage.
+    pub fn save(
         storage: &dyn Store,
         block: Block,
         block_info: BlockInfo,
@@ -496,6 +514,15 @@ impl BlockChain {
             .iter()
             .map(|user_txn| user_txn.id())
             .collect::<Vec<HashValue>>();
+        let txn_positions = TransactionPosition::block_positions(
+            block_id,
+            block.header().number(),
+            block.transactions().len(),
+            txn_id_vec.len(),
+            block_info.txn_accumulator_info.num_leaves,
+        )?;
+        storage
+            .save_transaction_positions(txn_id_vec.iter().copied().zip(txn_positions).collect())?;
         // save block's transactions
         storage.save_block_transaction_ids(block_id, txn_id_vec)?;
         // save transactions
@@ -589,6 +616,47 @@ impl ChainReader for BlockChain {
         Ok(None)
     }
 
+    fn get_transaction_infos_by_hashes(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> Result<Vec<Option<BlockTransactionInfo>>> {
+        let main_info_ids = self
+            .storage
+            .get_transaction_info_ids_by_hashes(txn_hashes)?
+            .into_iter()
+            .map(|ids| {
+                ids.into_iter()
+                    .find(|id| self.check_exist_transaction_info(*id))
+            })
+            .collect::<Vec<_>>();
+        let mut txn_infos = self
+            .storage
+            .get_transaction_infos(main_info_ids.iter().flatten().copied().collect())?
+            .into_iter();
+        Ok(main_info_ids
+            .into_iter()
+            .map(|id| id.and_then(|_| txn_infos.next().flatten()))
+            .collect())
+    }
+
+    fn get_transaction_positions(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> Result<Vec<Option<TransactionPosition>>> {
+        self.storage
+            .get_transaction_positions_by_hashes(txn_hashes)?
+            .into_iter()
+            .map(|positions| {
+                for position in positions {
+                    if self.check_exist_block(position.block_id, position.block_number)? {
+                        return Ok(Some(position));
+                    }
+                }
+                Ok(None)
+            })
+            .collect()
+    }
+
     fn get_transaction_info_by_version(
         &self,
         version: u64,
@@ -599,6 +667,108 @@ impl ChainReader for BlockChain {
         }
     }
 
+    fn get_transaction_infos(
+        &self,
+        start_index: u64,
+        reverse: bool,
+        max_size: u64,
+    ) -> Result<Vec<BlockTransactionInfo>> {
+        let txn_info_ids = self
+            .txn_accumulator
+            .get_leaves(start_index, reverse, max_size)?;
+        let mut txn_infos = Vec::with_capacity(txn_info_ids.len());
+        for txn_info_id in txn_info_ids {
+            txn_infos.push(
+                self.storage
+                    .get_transaction_info(txn_info_id)?
+                    .ok_or_else(|| format_err!("Can not find txn info by id: {}", txn_info_id))?,
+            );
+        }
+        Ok(txn_infos)
+    }
+
+    fn get_transaction_proof(
+        &self,
+        block_id: HashValue,
+        transaction_index: u64,
+        event_index: Option<u64>,
+    ) -> Result<Option<TransactionInfoWithProof>> {
+        let block_info = match self.storage.get_block_info(block_id)? {
+            Some(block_info) => block_info,
+            None => return Ok(None),
+        };
+        let txn_info_ids = self.storage.get_block_txn_info_ids(block_id)?;
+        let txn_info_id = match txn_info_ids.get(transaction_index as usize) {
+            Some(txn_info_id) => *txn_info_id,
+            None => return Ok(None),
+        };
+        let (_, txn_info) = self
+            .storage
+            .get_transaction_info(txn_info_id)?
+            .ok_or_else(|| format_err!("Can not find txn info by id {}", txn_info_id))?
+            .into();
+
+        // the txn infos of the block are the last leaves of the block's txn accumulator.
+        let txn_accumulator_info = block_info.get_txn_accumulator_info();
+        let leaf_index = txn_accumulator_info
+            .get_num_leaves()
+            .saturating_sub(txn_info_ids.len() as u64)
+            .saturating_add(transaction_index);
+        let txn_accumulator = info_2_accumulator(
+            txn_accumulator_info.clone(),
+            AccumulatorStoreType::Transaction,
+            self.storage.as_ref(),
+        );
+        let proof = txn_accumulator.get_proof(leaf_index)?.ok_or_else(|| {
+            format_err!(
+                "Can not get proof of txn info {} at leaf {}",
+                txn_info_id,
+                leaf_index
+            )
+        })?;
+
+        let event_proof = match event_index {
+            Some(event_index) => {
+                let events = self
+                    .storage
+                    .get_contract_events(txn_info_id)?
+                    .unwrap_or_default();
+                let event = events.get(event_index as usize).cloned().ok_or_else(|| {
+                    format_err!(
+                        "Can not find event {} of txn info {}, events len: {}",
+                        event_index,
+                        txn_info_id,
+                        events.len()
+                    )
+                })?;
+                let event_hashes: Vec<_> = events.iter().map(|e| e.crypto_hash()).collect();
+                let event_accumulator =
+                    MerkleAccumulator::new_empty(Arc::new(MockAccumulatorStore::new()));
+                event_accumulator.append(&event_hashes)?;
+                event_accumulator.flush()?;
+                let proof = event_accumulator.get_proof(event_index)?.ok_or_else(|| {
+                    format_err!(
+                        "Can not get proof of event {} of txn info {}",
+                        event_index,
+                        txn_info_id
+                    )
+                })?;
+                Some(EventWithProof {
+                    event,
+                    event_index,
+                    proof,
+                })
+            }
+            None => None,
+        };
+        Ok(Some(TransactionInfoWithProof {
+            transaction_info: txn_info,
+            leaf_index,
+            proof,
+            event_proof,
+        }))
+    }
+
     fn chain_state_reader(&self) -> &dyn ChainStateReader {
         &self.statedb
     }
@@ -767,17 +937,26 @@ impl BlockChain {
     pub fn filter_events(&self, filter: Filter) -> Result<Vec<ContractEventInfo>> {
         let reverse = filter.reverse;
         let chain_header = self.current_header();
-        let max_block_number = chain_header.number().min(filter.to_block);
+        let mut max_block_number = chain_header.number().min(filter.to_block);
+        let mut min_block_number = filter.from_block;
+        // skip the blocks before or after the cursor.
+        if let Some(cursor) = filter.cursor.as_ref() {
+            if reverse {
+                max_block_number = max_block_number.min(cursor.block_number);
+            } else {
+                min_block_number = min_block_number.max(cursor.block_number);
+            }
+        }
 
         // quick return.
-        if filter.from_block > max_block_number {
+        if min_block_number > max_block_number {
             return Ok(vec![]);
         }
 
         let (mut cur_block_number, tail) = if reverse {
-            (max_block_number, filter.from_block)
+            (max_block_number, min_block_number)
         } else {
-            (filter.from_block, max_block_number)
+            (min_block_number, max_block_number)
         };
         let mut event_with_infos = vec![];
         'outer: loop {
@@ -809,7 +988,15 @@ impl BlockChain {
                 })?;
                 let mut filtered_events = events
                     .into_iter()
-                    .filter(|evt| filter.matching(block_number, evt))
+                    .enumerate()
+                    .filter(|(event_index, evt)| {
+                        filter.matching(block_number, evt)
+                            && filter.after_cursor(&EventPosition {
+                                block_number,
+                                transaction_index: *idx as u32,
+                                event_index: *event_index as u32,
+                            })
+                    })
                     .peekable();
                 if filtered_events.peek().is_none() {
                     continue;
@@ -823,13 +1010,15 @@ impl BlockChain {
                     ))
                 })?;
 
-                let filtered_event_with_info = filtered_events.map(|evt| ContractEventInfo {
-                    block_hash: block_id,
-                    block_number: block.header().number(),
-                    transaction_hash: txn_info.transaction_hash(),
-                    transaction_index: *idx as u32,
-                    event: evt,
-                });
+                let filtered_event_with_info =
+                    filtered_events.map(|(event_index, evt)| ContractEventInfo {
+                        block_hash: block_id,
+                        block_number: block.header().number(),
+                        transaction_hash: txn_info.transaction_hash(),
+                        transaction_index: *idx as u32,
+                        event_index: event_index as u32,
+                        event: evt,
+                    });
                 if reverse {
                     event_with_infos.extend(filtered_event_with_info.rev())
                 } else {
@@ -867,6 +1056,105 @@ impl BlockChain {
     }
 }
 
+impl BlockChain {
+    /// Move the token holder index to the head of the chain, by the token holder changes of the
+    /// blocks between the index head and the chain head. Do nothing if the index is not built.
+    pub fn update_token_holder_index(&self) -> Result<()> {
+        if !self.storage.is_token_holder_index_enabled() {
+            return Ok(());
+        }
+        let index_head = match self.storage.get_token_holder_index_head()? {
+            Some(index_head) => index_head,
+            None => return Ok(()),
+        };
+        let head_id = self.status.head.id();
+        if index_head == head_id {
+            return Ok(());
+        }
+        let get_header = |block_id: HashValue| {
+            self.storage
+                .get_block_header_by_hash(block_id)?
+                .ok_or_else(|| format_err!("Can not find block header by hash {:?}", block_id))
+        };
+        // walk back to the common ancestor, the blocks on both sides are changed.
+        let mut retracted = get_header(index_head)?;
+        let mut enacted = self.status.head.header().clone();
+        let mut changed_blocks = vec![];
+        while retracted.id() != enacted.id() {
+            if retracted.number() >= enacted.number() {
+                changed_blocks.push(retracted.id());
+                retracted = get_header(retracted.parent_hash())?;
+            } else {
+                changed_blocks.push(enacted.id());
+                enacted = get_header(enacted.parent_hash())?;
+            }
+        }
+        let mut keys = BTreeSet::new();
+        for block_id in changed_blocks {
+            match self.storage.get_block_token_holder_changes(block_id)? {
+                Some(changes) => keys.extend(changes),
+                None => {
+                    warn!(
+                        "The token holder changes of block {:?} are missing, the token holder index need to be rebuilt.",
+                        block_id
+                    );
+                    return self.storage.clear_token_holder_index_head();
+                }
+            }
+        }
+        let reader = AccountStateReader::new(&self.statedb);
+        let balances = keys
+            .into_iter()
+            .map(|key| {
+                let balance =
+                    reader.get_balance_by_token_code(&key.address, key.token_code.clone())?;
+                Ok((key, balance))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.storage
+            .update_token_holders(index_head, head_id, balances)
+    }
+
+    /// Rebuild the token holder index by the balances in the state of the chain head,
+    /// return the count of the holders. The state is scanned account by account, and the
+    /// holders are written in batches, so the whole state is never loaded into memory.
+    pub fn rebuild_token_holder_index(&self) -> Result<u64> {
+        let mut rebuilder = self.storage.rebuild_token_holders()?;
+        self.statedb
+            .for_each_account_state_set(|address, account_state_set| {
+                let resource_set = match account_state_set.resource_set() {
+                    Some(resource_set) => resource_set,
+                    None => return Ok(()),
+                };
+                for (key, value) in resource_set.iter() {
+                    let struct_tag = StructTag::decode(key.as_slice())?;
+                    if let Some(token_code) = BalanceResource::token_code_of(&struct_tag) {
+                        let balance =
+                            bcs_ext::from_bytes::<BalanceResource>(value.as_slice())?.token();
+                        rebuilder.add_holder(TokenHolderKey::new(token_code, address), balance)?;
+                    }
+                }
+                Ok(())
+            })?;
+        rebuilder.finish(self.status.head.id())
+    }
+}
+
+/// The holders whose balance resource is written or deleted by the txns of the block.
+pub fn token_holder_changes(write_sets: &[WriteSet]) -> Vec<TokenHolderKey> {
+    write_sets
+        .iter()
+        .flat_map(|write_set| write_set.iter())
+        .filter_map(|(access_path, _)| match &access_path.path {
+            DataPath::Resource(struct_tag) => BalanceResource::token_code_of(struct_tag)
+                .map(|token_code| TokenHolderKey::new(token_code, access_path.address)),
+            DataPath::Code(_) => None,
+        })
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
 impl ChainWriter for BlockChain {
     fn can_connect(&self, executed_block: &ExecutedBlock) -> bool {
         executed_block.block.header().parent_hash() == self.status.status.head().id()
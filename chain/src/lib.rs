@@ -3,5 +3,5 @@
 #![deny(clippy::integer_arithmetic)]
 mod chain;
 pub mod verifier;
-pub use chain::BlockChain;
+pub use chain::{token_holder_changes, BlockChain};
 pub use starcoin_chain_api::{ChainReader, ChainWriter};
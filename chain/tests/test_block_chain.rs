@@ -18,6 +18,7 @@ use starcoin_vm_types::account_config::genesis_address;
 use starcoin_vm_types::event::EventKey;
 use starcoin_vm_types::transaction::authenticator::AuthenticationKey;
 use std::sync::Arc;
+use storage::BlockStore;
 
 #[stest::test(timeout = 120)]
 fn test_chain_filter_events() {
@@ -32,6 +33,7 @@ fn test_chain_filter_events() {
             event_keys: vec![evt_key],
             limit: None,
             reverse: false,
+            ..Default::default()
         };
         let evts = mock_chain.head().filter_events(event_filter).unwrap();
         assert_eq!(evts.len(), 5);
@@ -48,6 +50,7 @@ fn test_chain_filter_events() {
             event_keys: vec![EventKey::new_from_address(&genesis_address(), 4)],
             limit: Some(5),
             reverse: false,
+            ..Default::default()
         };
         let evts = mock_chain.head().filter_events(event_filter).unwrap();
         assert_eq!(evts.len(), 5);
@@ -62,6 +65,7 @@ fn test_chain_filter_events() {
             event_keys: vec![EventKey::new_from_address(&genesis_address(), 4)],
             limit: Some(5),
             reverse: true,
+            ..Default::default()
         };
         let evts = mock_chain.head().filter_events(event_filter).unwrap();
         assert_eq!(evts.len(), 5);
@@ -70,6 +74,58 @@ fn test_chain_filter_events() {
         assert_eq!(evt.transaction_index, 0);
     }
 
+    // test on type tag and address filters, and paginate by cursor.
+    {
+        let evt_key = EventKey::new_from_address(&genesis_address(), 4);
+        let all = mock_chain
+            .head()
+            .filter_events(Filter {
+                from_block: 1,
+                to_block: 10,
+                event_keys: vec![evt_key],
+                reverse: false,
+                ..Default::default()
+            })
+            .unwrap();
+        let type_tag = all.first().unwrap().event.type_tag().clone();
+        let event_filter = Filter {
+            from_block: 1,
+            to_block: 10,
+            type_tags: vec![type_tag.clone()],
+            addrs: vec![genesis_address()],
+            limit: Some(4),
+            reverse: false,
+            ..Default::default()
+        };
+        let mut evts = vec![];
+        let mut cursor = None;
+        loop {
+            let page = mock_chain
+                .head()
+                .filter_events(Filter {
+                    cursor,
+                    ..event_filter.clone()
+                })
+                .unwrap();
+            if page.is_empty() {
+                break;
+            }
+            cursor = page.last().map(|evt| evt.position());
+            evts.extend(page);
+        }
+        assert_eq!(evts, all);
+        assert!(evts.iter().all(|evt| evt.event.type_tag() == &type_tag));
+
+        let evts = mock_chain
+            .head()
+            .filter_events(Filter {
+                addrs: vec![account_address::AccountAddress::random()],
+                ..event_filter
+            })
+            .unwrap();
+        assert!(evts.is_empty());
+    }
+
     // test on from_block is 0
     {
         let event_filter = Filter {
@@ -78,6 +134,7 @@ fn test_chain_filter_events() {
             event_keys: vec![EventKey::new_from_address(&genesis_address(), 4)],
             limit: Some(20),
             reverse: true,
+            ..Default::default()
         };
         let evts = mock_chain.head().filter_events(event_filter).unwrap();
         assert_eq!(evts.len(), 10);
@@ -94,6 +151,7 @@ fn test_chain_filter_events() {
             event_keys: vec![EventKey::new_from_address(&genesis_address(), 4)],
             limit: Some(20),
             reverse: true,
+            ..Default::default()
         };
         let evts = mock_chain.head().filter_events(event_filter).unwrap();
         assert_eq!(evts.len(), 10);
@@ -282,6 +340,36 @@ fn test_random_uncle() {
     assert_eq!(mock_chain.head().current_epoch_uncles_size(), 0);
 }
 
+#[stest::test(timeout = 120)]
+fn test_uncle_index_after_reorg() -> Result<()> {
+    let (mut mock_chain, mut fork_block_chain, uncle_block_header) = gen_uncle();
+    let miner = mock_chain.miner().clone();
+    let nephew = product_a_block(mock_chain.head(), &miner, vec![uncle_block_header.clone()]);
+    let nephew_id = nephew.id();
+    mock_chain.apply(nephew)?;
+    let storage = mock_chain.head().get_storage();
+    assert_eq!(
+        storage.get_block_ids_by_uncle(uncle_block_header.id())?,
+        vec![nephew_id]
+    );
+
+    // the uncle's branch become the main chain.
+    let mut fork_head = None;
+    for _ in 0..4 {
+        let block = product_a_block(&fork_block_chain, &miner, Vec::new());
+        fork_block_chain.apply(block.clone())?;
+        fork_head = Some(block);
+    }
+    mock_chain.select_head(fork_head.unwrap())?;
+    assert!(mock_chain.head().exist_block(uncle_block_header.id())?);
+    assert!(!mock_chain.head().exist_block(nephew_id)?);
+    assert_eq!(
+        storage.get_block_ids_by_uncle(uncle_block_header.id())?,
+        vec![nephew_id]
+    );
+    Ok(())
+}
+
 #[stest::test(timeout = 480)]
 #[allow(clippy::vec_init_then_push)]
 fn test_switch_epoch() {
@@ -449,3 +537,69 @@ fn test_get_blocks_by_number() -> Result<()> {
     assert_eq!(blocks.len(), 11);
     Ok(())
 }
+
+#[stest::test]
+fn test_get_transaction_proof() -> Result<()> {
+    let mut mock_chain = MockChain::new(ChainNetwork::new_test()).unwrap();
+    let times = 5;
+    mock_chain.produce_and_apply_times(times).unwrap();
+    for number in 1..=times {
+        let block = mock_chain
+            .head()
+            .get_block_by_number(number)?
+            .expect("block should exist");
+        // the block metadata txn of every block emits a NewBlockEvent.
+        let proof = mock_chain
+            .head()
+            .get_transaction_proof(block.id(), 0, Some(0))?
+            .expect("txn proof should exist");
+        assert!(proof.event_proof.is_some());
+        proof.verify(block.header().txn_accumulator_root())?;
+        assert!(proof.verify(block.header().state_root()).is_err());
+    }
+    let head = mock_chain.head().current_header();
+    assert!(mock_chain
+        .head()
+        .get_transaction_proof(head.id(), 100, None)?
+        .is_none());
+    Ok(())
+}
+
+#[stest::test]
+fn test_get_transaction_infos() -> Result<()> {
+    let mut mock_chain = MockChain::new(ChainNetwork::new_test()).unwrap();
+    let times = 5;
+    mock_chain.produce_and_apply_times(times).unwrap();
+    // genesis and every mocked block only contain one txn.
+    let txn_infos = mock_chain
+        .head()
+        .get_transaction_infos(0, false, u64::max_value())?;
+    assert_eq!(txn_infos.len(), times as usize + 1);
+    for (number, txn_info) in txn_infos.iter().enumerate() {
+        let block = mock_chain
+            .head()
+            .get_block_by_number(number as u64)?
+            .expect("block should exist");
+        assert_eq!(txn_info.block_id(), block.id());
+    }
+
+    let txn_infos = mock_chain.head().get_transaction_infos(100, true, 3)?;
+    let numbers = txn_infos
+        .iter()
+        .map(|txn_info| {
+            mock_chain
+                .head()
+                .get_header(txn_info.block_id())
+                .unwrap()
+                .unwrap()
+                .number()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(numbers, vec![5, 4, 3]);
+
+    assert!(mock_chain
+        .head()
+        .get_transaction_infos(100, false, 3)?
+        .is_empty());
+    Ok(())
+}
@@ -0,0 +1,180 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use consensus::Consensus;
+use starcoin_chain::{token_holder_changes, BlockChain, ChainReader, ChainWriter};
+use starcoin_config::{temp_path, ChainNetwork, RocksdbConfig};
+use starcoin_executor::{
+    build_transfer_from_association, build_transfer_txn, Account, DEFAULT_EXPIRATION_TIME,
+    DEFAULT_MAX_GAS_AMOUNT,
+};
+use starcoin_genesis::Genesis;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::association_address;
+use starcoin_types::block::Block;
+use starcoin_types::transaction::SignedUserTransaction;
+use starcoin_vm_types::token::stc::{STC_TOKEN_CODE, STC_TOKEN_CODE_STR};
+use starcoin_vm_types::write_set::{WriteOp, WriteSetMut};
+use std::sync::Arc;
+use storage::cache_storage::CacheStorage;
+use storage::db_storage::DBStorage;
+use storage::storage::StorageInstance;
+use storage::token_holder::TokenHolderKey;
+use storage::{Storage, TokenHolderStore};
+
+fn create_block(chain: &BlockChain, txns: Vec<SignedUserTransaction>) -> Result<Block> {
+    let (template, excluded) =
+        chain.create_block_template(association_address(), None, None, txns, vec![], None)?;
+    assert!(excluded.discarded_txns.is_empty());
+    assert!(excluded.untouched_txns.is_empty());
+    chain
+        .consensus()
+        .create_block(template, chain.time_service())
+}
+
+fn stc_holders(storage: &Storage) -> Result<Vec<(AccountAddress, u128)>> {
+    storage.list_token_holders(&STC_TOKEN_CODE, None, usize::MAX)
+}
+
+fn stc_balance(holders: &[(AccountAddress, u128)], address: &AccountAddress) -> Option<u128> {
+    holders
+        .iter()
+        .find(|(holder, _)| holder == address)
+        .map(|(_, balance)| *balance)
+}
+
+#[stest::test(timeout = 120)]
+fn test_token_holder_index_update() -> Result<()> {
+    let net = ChainNetwork::new_test();
+    let tmpdir = temp_path();
+    let storage = Arc::new(
+        Storage::new(StorageInstance::new_cache_and_db_instance(
+            CacheStorage::new(),
+            DBStorage::new(tmpdir.path(), RocksdbConfig::default())?,
+        ))?
+        .with_token_holder_index(true),
+    );
+    let chain_info = Genesis::load(&net)?.execute_genesis_block(&net, storage.clone())?;
+    let mut chain = BlockChain::new(net.time_service(), chain_info.head().id(), storage.clone())?;
+    chain.rebuild_token_holder_index()?;
+    assert_eq!(
+        storage.get_token_holder_index_head()?,
+        Some(chain.current_header().id())
+    );
+
+    let account_a = Account::new();
+    let account_b = Account::new();
+    let account_c = Account::new();
+    let expiration = net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME;
+
+    // b1: association -> a
+    let txn = build_transfer_from_association(
+        *account_a.address(),
+        Some(account_a.auth_key()),
+        0,
+        10000,
+        expiration,
+        &net,
+    )
+    .as_signed_user_txn()?
+    .clone();
+    let b1 = create_block(&chain, vec![txn])?;
+    let b1_id = b1.id();
+    chain.apply(b1)?;
+    chain.update_token_holder_index()?;
+    let holders = stc_holders(storage.as_ref())?;
+    assert_eq!(stc_balance(&holders, account_a.address()), Some(10000));
+    let holder_count = storage.get_token_holder_count(&STC_TOKEN_CODE)?;
+    assert_eq!(holder_count, holders.len() as u64);
+
+    // b2: a transfers all of its balance to b, the gas price is zero, so the balance of a drops to zero.
+    let txn = account_a.sign_txn(build_transfer_txn(
+        *account_a.address(),
+        *account_b.address(),
+        Some(account_b.auth_key()),
+        0,
+        10000,
+        0,
+        DEFAULT_MAX_GAS_AMOUNT,
+        expiration,
+        net.chain_id(),
+    ));
+    let b2 = create_block(&chain, vec![txn])?;
+    let b2_id = b2.id();
+    chain.apply(b2)?;
+    let changes = storage
+        .get_block_token_holder_changes(b2_id)?
+        .expect("token holder changes of the block should exist");
+    assert!(changes.contains(&TokenHolderKey::new(
+        STC_TOKEN_CODE.clone(),
+        *account_a.address()
+    )));
+    assert!(changes.contains(&TokenHolderKey::new(
+        STC_TOKEN_CODE.clone(),
+        *account_b.address()
+    )));
+    chain.update_token_holder_index()?;
+    let holders = stc_holders(storage.as_ref())?;
+    assert_eq!(stc_balance(&holders, account_a.address()), None);
+    assert_eq!(stc_balance(&holders, account_b.address()), Some(10000));
+    assert_eq!(
+        storage.get_token_holder_count(&STC_TOKEN_CODE)?,
+        holder_count
+    );
+
+    // switch to a longer fork from b1, b does not exist on the fork.
+    let mut fork = chain.fork(b1_id)?;
+    let txn = build_transfer_from_association(
+        *account_c.address(),
+        Some(account_c.auth_key()),
+        1,
+        20000,
+        expiration,
+        &net,
+    )
+    .as_signed_user_txn()?
+    .clone();
+    let fork_b2 = create_block(&fork, vec![txn])?;
+    fork.apply(fork_b2)?;
+    let fork_b3 = create_block(&fork, vec![])?;
+    fork.apply(fork_b3)?;
+    fork.update_token_holder_index()?;
+    assert_eq!(
+        storage.get_token_holder_index_head()?,
+        Some(fork.current_header().id())
+    );
+    let holders = stc_holders(storage.as_ref())?;
+    assert_eq!(stc_balance(&holders, account_a.address()), Some(10000));
+    assert_eq!(stc_balance(&holders, account_b.address()), None);
+    assert_eq!(stc_balance(&holders, account_c.address()), Some(20000));
+    let holder_count = storage.get_token_holder_count(&STC_TOKEN_CODE)?;
+    assert_eq!(holder_count, holders.len() as u64);
+
+    // the incrementally updated index is same as the rebuilt one.
+    fork.rebuild_token_holder_index()?;
+    assert_eq!(stc_holders(storage.as_ref())?, holders);
+    assert_eq!(
+        storage.get_token_holder_count(&STC_TOKEN_CODE)?,
+        holder_count
+    );
+    Ok(())
+}
+
+#[stest::test]
+fn test_token_holder_changes_of_deletion() -> Result<()> {
+    let account = Account::new();
+    let write_set = WriteSetMut::new(vec![(
+        account.make_balance_access_path(STC_TOKEN_CODE_STR),
+        WriteOp::Deletion,
+    )])
+    .freeze()?;
+    assert_eq!(
+        token_holder_changes(&[write_set]),
+        vec![TokenHolderKey::new(
+            STC_TOKEN_CODE.clone(),
+            *account.address()
+        )]
+    );
+    Ok(())
+}
@@ -3,8 +3,8 @@ use anyhow::Result;
 use jsonrpc_core_client::RpcError;
 use starcoin_rpc_api::chain::ChainClient;
 use starcoin_rpc_api::types::{
-    BlockHeaderView, BlockTransactionsView, BlockView, ChainInfoView, TransactionEventView,
-    TransactionInfoView, TransactionView,
+    BlockHeaderView, BlockTransactionsView, BlockView, ChainStatusView, EncodedView,
+    TransactionEventView, TransactionInfoView, TransactionView,
 };
 
 pub struct BlockClient {
@@ -18,7 +18,7 @@ impl BlockClient {
         }
     }
     pub async fn get_block_whole_by_height(&self, height: u64) -> Result<BlockData, RpcError> {
-        let block: Option<BlockView> = self.node_client.get_block_by_number(height).await?;
+        let block: Option<BlockView> = self.node_client.get_block_by_number(height, None).await?;
         let block = block
             .ok_or_else(|| RpcError::Client(format!("cannot find block of height {}", height)))?;
         let mut txn_infos: Vec<TransactionInfoView> = self
@@ -29,16 +29,19 @@ impl BlockClient {
 
         {
             let txn_info = txn_infos.remove(0);
-            let txn: Option<TransactionView> = self
+            let txn: Option<EncodedView<TransactionView>> = self
                 .node_client
-                .get_transaction(txn_info.transaction_hash)
+                .get_transaction(txn_info.transaction_hash, None)
                 .await?;
-            let txn = txn.ok_or_else(|| {
-                RpcError::Client(format!(
-                    "cannot find txn with id {}",
-                    txn_info.transaction_hash
-                ))
-            })?;
+            let txn = txn
+                .ok_or_else(|| {
+                    RpcError::Client(format!(
+                        "cannot find txn with id {}",
+                        txn_info.transaction_hash
+                    ))
+                })?
+                .into_json()
+                .map_err(|e| RpcError::Client(e.to_string()))?;
 
             let events: Vec<TransactionEventView> = self
                 .node_client
@@ -77,7 +80,7 @@ impl BlockClient {
         Ok(BlockData { block, txns_data })
     }
     pub async fn get_chain_head(&self) -> Result<BlockHeaderView, RpcError> {
-        let chain_info: ChainInfoView = self.node_client.info().await?;
+        let chain_info: ChainStatusView = self.node_client.info().await?;
         Ok(chain_info.head)
     }
 }
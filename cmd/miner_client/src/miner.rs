@@ -10,7 +10,7 @@ use futures::stream::StreamExt;
 use futures::SinkExt;
 use logger::prelude::*;
 use parking_lot::Mutex;
-use starcoin_config::MinerClientConfig;
+use starcoin_config::{MinerClientConfig, NodeConfigChangedEvent};
 use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
 use starcoin_types::block::BlockHeaderExtra;
 use starcoin_types::genesis_config::ConsensusStrategy;
@@ -39,6 +39,11 @@ impl<C: JobClient> MinerClient<C> {
             current_task: None,
         })
     }
+    /// Replace the solver, it takes effect from the next mint work.
+    pub fn update_solver(&mut self, solver: Box<dyn Solver>) {
+        self.solver = solver;
+    }
+
     fn submit_seal(&self, minting_blob: Vec<u8>, nonce: u32, extra: BlockHeaderExtra) {
         if let Err(err) = self.job_client.submit_seal(minting_blob, nonce, extra) {
             error!("Submit seal to failed: {}", err);
@@ -80,6 +85,7 @@ pub struct MinerClientService<C: JobClient> {
 
 impl<C: JobClient> ActorService for MinerClientService<C> {
     fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.subscribe::<NodeConfigChangedEvent>();
         let jobs = self.inner.job_client.subscribe()?;
         ctx.add_stream(jobs);
         let seals = self
@@ -95,6 +101,11 @@ impl<C: JobClient> ActorService for MinerClientService<C> {
         ctx.add_stream(seals);
         Ok(())
     }
+
+    fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.unsubscribe::<NodeConfigChangedEvent>();
+        Ok(())
+    }
 }
 
 impl<C: JobClient> ServiceFactory<Self> for MinerClientService<C> {
@@ -124,3 +135,25 @@ impl<C: JobClient> EventHandler<Self, SealEvent> for MinerClientService<C> {
             .submit_seal(event.minting_blob, event.nonce, event.extra)
     }
 }
+
+impl<C: JobClient> EventHandler<Self, NodeConfigChangedEvent> for MinerClientService<C> {
+    fn handle_event(
+        &mut self,
+        event: NodeConfigChangedEvent,
+        _ctx: &mut ServiceContext<MinerClientService<C>>,
+    ) {
+        if !event.is_changed("miner.miner_thread") {
+            return;
+        }
+        if let Some(config) = event.config.miner.miner_client_config() {
+            info!(
+                "Miner thread changed to {}, update the solver.",
+                config.miner_thread()
+            );
+            match create_solver(config, Some(self.inner.job_client.time_service())) {
+                Ok(solver) => self.inner.update_solver(solver),
+                Err(e) => error!("Failed to create solver: {}", e),
+            }
+        }
+    }
+}
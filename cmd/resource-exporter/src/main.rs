@@ -126,7 +126,9 @@ impl serde::Serialize for MoveValue {
             AnnotatedMoveValue::U8(v) => serializer.serialize_u8(*v),
             AnnotatedMoveValue::U64(v) => serializer.serialize_u64(*v),
             AnnotatedMoveValue::U128(v) => serializer.serialize_u128(*v),
-            AnnotatedMoveValue::Address(v) => v.serialize(serializer),
+            AnnotatedMoveValue::Address(v) | AnnotatedMoveValue::Signer(v) => {
+                v.serialize(serializer)
+            }
             AnnotatedMoveValue::Vector(v) => {
                 let vs: Vec<_> = v.clone().into_iter().map(MoveValue).collect();
                 vs.serialize(serializer)
@@ -14,7 +14,7 @@ use starcoin_vm_types::token::token_code::TokenCode;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
-#[structopt(name = "accept_token")]
+#[structopt(name = "accept-token", alias = "accept_token")]
 pub struct AcceptTokenOpt {
     #[structopt(short = "s")]
     /// if `sender` is absent, use default account.
@@ -84,10 +84,10 @@ impl CommandAction for AcceptTokenCommand {
             ctx.state().net().chain_id(),
         );
 
-        let signed_txn = client.account_sign_txn(accept_token_txn)?;
+        let signed_txn = ctx.state().sign_txn(accept_token_txn)?;
         let txn_hash = signed_txn.id();
         client.submit_transaction(signed_txn)?;
-        println!("txn {:#x} submitted.", txn_hash);
+        scmd::side_println!("txn {:#x} submitted.", txn_hash);
 
         if opt.blocking {
             ctx.state().watch_txn(txn_hash)?;
@@ -0,0 +1,185 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, ensure, format_err, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::token::token_code::TokenCode;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// A receiver of the batch transfer file.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchTransferRow {
+    pub address: AccountAddress,
+    pub amount: u128,
+    /// The public key is required if the receiver does not exist on chain.
+    #[serde(default)]
+    pub public_key: Option<String>,
+}
+
+/// Read the receivers from a json file, which is an array of `{"address", "amount", "public_key"}`,
+/// or from a csv file, whose lines are `address,amount[,public_key]`,
+/// the header line, empty lines and lines start with `#` in the csv file are ignored.
+pub fn read_batch_file(path: &Path) -> Result<Vec<BatchTransferRow>> {
+    let content = std::fs::read_to_string(path)?;
+    let is_json = path.extension().map(|ext| ext == "json").unwrap_or(false)
+        || content.trim_start().starts_with('[');
+    let rows = if is_json {
+        serde_json::from_str::<Vec<BatchTransferRow>>(content.as_str())?
+    } else {
+        parse_csv(content.as_str())?
+    };
+    ensure!(!rows.is_empty(), "No receiver found in {}", path.display());
+    Ok(rows)
+}
+
+fn parse_csv(content: &str) -> Result<Vec<BatchTransferRow>> {
+    let mut rows = vec![];
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(|field| field.trim()).collect();
+        let address = match AccountAddress::from_str(fields[0]) {
+            Ok(address) => address,
+            // the header line.
+            Err(_) if rows.is_empty() && fields[0].eq_ignore_ascii_case("address") => continue,
+            Err(e) => bail!("Invalid address at line {}: {}", i + 1, e),
+        };
+        ensure!(
+            fields.len() == 2 || fields.len() == 3,
+            "Invalid line {}, expect `address,amount[,public_key]`",
+            i + 1
+        );
+        let amount = fields[1]
+            .parse::<u128>()
+            .map_err(|e| format_err!("Invalid amount at line {}: {}", i + 1, e))?;
+        let public_key = fields
+            .get(2)
+            .filter(|public_key| !public_key.is_empty())
+            .map(|public_key| public_key.to_string());
+        rows.push(BatchTransferRow {
+            address,
+            amount,
+            public_key,
+        });
+    }
+    Ok(rows)
+}
+
+/// The submitted txn and the indexes of the rows it carries.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SubmittedTxn {
+    pub txn_hash: HashValue,
+    pub rows: Vec<usize>,
+}
+
+/// The progress of a batch transfer, it is saved after every submission and confirmation,
+/// so the batch transfer can be resumed after a failure.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BatchTransferState {
+    pub sender: AccountAddress,
+    pub token_code: TokenCode,
+    /// The row count of the batch file, to detect the file is changed.
+    pub total_rows: usize,
+    /// The indexes of the rows which are confirmed on chain.
+    pub confirmed: BTreeSet<usize>,
+    /// The txns which are submitted but not confirmed yet.
+    pub submitted: Vec<SubmittedTxn>,
+}
+
+impl BatchTransferState {
+    pub fn new(sender: AccountAddress, token_code: TokenCode, total_rows: usize) -> Self {
+        Self {
+            sender,
+            token_code,
+            total_rows,
+            confirmed: BTreeSet::new(),
+            submitted: vec![],
+        }
+    }
+
+    /// The default state file is `<batch file>.state.json`.
+    pub fn default_path(batch_file: &Path) -> PathBuf {
+        let mut file_name = batch_file
+            .file_name()
+            .map(|name| name.to_os_string())
+            .unwrap_or_default();
+        file_name.push(".state.json");
+        batch_file.with_file_name(file_name)
+    }
+
+    /// Load the state from `path`, return a new state if the file does not exist.
+    pub fn load_or_new(
+        path: &Path,
+        sender: AccountAddress,
+        token_code: TokenCode,
+        total_rows: usize,
+    ) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new(sender, token_code, total_rows));
+        }
+        let state: Self = serde_json::from_slice(std::fs::read(path)?.as_slice())?;
+        ensure!(
+            state.sender == sender && state.token_code == token_code,
+            "The state file {} belongs to the batch transfer of sender {} and token {}",
+            path.display(),
+            state.sender,
+            state.token_code
+        );
+        ensure!(
+            state.total_rows == total_rows,
+            "The batch file has {} rows, but the state file {} has {} rows, the batch file may be changed.",
+            total_rows,
+            path.display(),
+            state.total_rows
+        );
+        Ok(state)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn unconfirmed_rows(&self) -> Vec<usize> {
+        (0..self.total_rows)
+            .filter(|i| !self.confirmed.contains(i))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv() {
+        let content = "address,amount,public_key\n\
+                       # comment line\n\
+                       0x1, 100\n\
+                       \n\
+                       0x2,200,\n";
+        let rows = parse_csv(content).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].address, AccountAddress::from_str("0x1").unwrap());
+        assert_eq!(rows[0].amount, 100);
+        assert_eq!(rows[1].amount, 200);
+        assert!(rows[1].public_key.is_none());
+        assert!(parse_csv("0x1,abc").is_err());
+        assert!(parse_csv("0x1,1\naddress,amount").is_err());
+    }
+
+    #[test]
+    fn test_default_state_path() {
+        assert_eq!(
+            BatchTransferState::default_path(Path::new("/tmp/airdrop.csv")),
+            PathBuf::from("/tmp/airdrop.csv.state.json")
+        );
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::view::StringView;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_vm_types::account_address::AccountAddress;
+use structopt::StructOpt;
+
+/// Sign the txns of the account by an external signer, such as a HSM.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "bind-external-signer")]
+pub struct BindExternalSignerOpt {
+    #[structopt(name = "account_address")]
+    /// the account may be not in the wallet.
+    account_address: AccountAddress,
+
+    #[structopt(short = "e", long = "endpoint")]
+    /// `http(s)://...` or `unix://<socket path>` of the signer, which serves the json rpc method `sign_raw_txn`.
+    endpoint: String,
+}
+
+pub struct BindExternalSignerCommand;
+
+impl CommandAction for BindExternalSignerCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = BindExternalSignerOpt;
+    type ReturnItem = StringView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let opt = ctx.opt();
+        client.account_bind_external_signer(opt.account_address, opt.endpoint.clone())?;
+        Ok(StringView {
+            result: opt.account_address.to_string(),
+        })
+    }
+}
@@ -19,6 +19,12 @@ pub struct ChangePasswordOpt {
 
     #[structopt(short, name = "password")]
     password: String,
+
+    #[structopt(
+        long = "old-password",
+        help = "The current password of the account, required if the account is locked."
+    )]
+    old_password: Option<String>,
 }
 
 pub struct ChangePasswordCmd;
@@ -36,7 +42,11 @@ impl CommandAction for ChangePasswordCmd {
         let client = ctx.state().client();
         let opt: &ChangePasswordOpt = ctx.opt();
         let account = ctx.state().get_account_or_default(opt.account_address)?;
-        client.account_change_password(account.address, opt.password.clone())?;
+        client.account_change_password(
+            account.address,
+            opt.old_password.clone(),
+            opt.password.clone(),
+        )?;
         Ok(())
     }
 }
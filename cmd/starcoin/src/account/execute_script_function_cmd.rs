@@ -1,20 +1,17 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::cli_state::CliState;
+use crate::cli_state::{CliState, GasPriceArg};
 use crate::StarcoinOpt;
 use anyhow::{format_err, Result};
 use scmd::{CommandAction, ExecContext};
 use starcoin_crypto::hash::HashValue;
-use starcoin_rpc_api::types::FunctionIdView;
+use starcoin_rpc_api::types::{ArgumentsView, FunctionIdView};
 use starcoin_rpc_client::RemoteStateReader;
 use starcoin_state_api::AccountStateReader;
-use starcoin_types::transaction::{
-    parse_transaction_argument, RawUserTransaction, TransactionArgument,
-};
+use starcoin_types::transaction::RawUserTransaction;
 use starcoin_vm_types::account_address::AccountAddress;
 use starcoin_vm_types::transaction::ScriptFunction;
-use starcoin_vm_types::transaction_argument::convert_txn_args;
 use starcoin_vm_types::{language_storage::TypeTag, parser::parse_type_tag};
 use structopt::StructOpt;
 
@@ -38,9 +35,17 @@ pub struct ExecuteScriptFunctionOpt {
     /// type tags for the script
     type_tags: Option<Vec<TypeTag>>,
 
-    #[structopt(long = "arg", name = "transaction-args", parse(try_from_str = parse_transaction_argument))]
-    /// args for the script.
-    args: Option<Vec<TransactionArgument>>,
+    #[structopt(long = "arg", name = "transaction-args")]
+    /// args for the script, vector args are json arrays such as `["0x1","0x2"]`.
+    args: Option<Vec<String>>,
+
+    #[structopt(
+    long = "arg-type",
+    name = "arg-type",
+    parse(try_from_str = parse_type_tag)
+    )]
+    /// parameter types to encode the json array args, resolved from the on-chain function ABI if absent.
+    arg_types: Option<Vec<TypeTag>>,
 
     #[structopt(
         name = "expiration_time",
@@ -62,9 +67,9 @@ pub struct ExecuteScriptFunctionOpt {
         long = "gas-price",
         name = "price of gas",
         default_value = "1",
-        help = "gas price used to deploy the module"
+        help = "gas price used to deploy the module, or `auto` to use the gas price suggested by the node"
     )]
-    gas_price: u64,
+    gas_price: GasPriceArg,
 
     #[structopt(
         short = "b",
@@ -73,6 +78,10 @@ pub struct ExecuteScriptFunctionOpt {
         help = "blocking wait txn mined"
     )]
     blocking: bool,
+
+    #[structopt(long = "replace")]
+    /// replace the pending txn with the same sequence number in txpool, the gas price is bumped automatically.
+    replace: bool,
 }
 
 pub struct ExecuteScriptFunctionCmd;
@@ -101,8 +110,31 @@ impl CommandAction for ExecuteScriptFunctionCmd {
         let expiration_time = opt.expiration_time + node_info.now_seconds;
 
         let type_tags = opt.type_tags.clone().unwrap_or_default();
-        let args = opt.args.clone().unwrap_or_default();
         let script_function = opt.script_function.clone().0;
+        let args = ArgumentsView::from_literals(&opt.args.clone().unwrap_or_default())?;
+        let args = match (&opt.arg_types, args.needs_arg_types()) {
+            (Some(arg_types), _) => args.to_bcs_bytes_by_types(arg_types)?,
+            (None, true) => {
+                let abi = client
+                    .contract_resolve_function(script_function.clone())?
+                    .ok_or_else(|| {
+                        format_err!("cannot resolve script function {}", script_function)
+                    })?;
+                let arg_types: Vec<_> = abi.args.into_iter().map(|arg| arg.type_tag.0).collect();
+                args.to_bcs_bytes_by_types(&arg_types)?
+            }
+            (None, false) => args.to_bcs_bytes()?,
+        };
+        let gas_price = ctx.state().gas_price(opt.gas_price)?;
+        let gas_price = if opt.replace {
+            ctx.state().replace_gas_price(
+                sender.address,
+                account_resource.sequence_number(),
+                gas_price,
+            )?
+        } else {
+            gas_price
+        };
         let script_txn = RawUserTransaction::new_script_function(
             sender.address,
             account_resource.sequence_number(),
@@ -110,18 +142,24 @@ impl CommandAction for ExecuteScriptFunctionCmd {
                 script_function.module,
                 script_function.function,
                 type_tags,
-                convert_txn_args(&args),
+                args,
             ),
             opt.max_gas_amount,
-            opt.gas_price,
+            gas_price,
             expiration_time,
             ctx.state().net().chain_id(),
         );
 
-        let signed_txn = client.account_sign_txn(script_txn)?;
+        let signed_txn = ctx.state().sign_txn(script_txn)?;
         let txn_hash = signed_txn.id();
-        client.submit_transaction(signed_txn)?;
-        println!("txn {:#x} submitted.", txn_hash);
+        if opt.replace {
+            if let Some(replaced) = client.replace_transaction(signed_txn)? {
+                scmd::side_println!("txn {:#x} replaced.", replaced);
+            }
+        } else {
+            client.submit_transaction(signed_txn)?;
+        }
+        scmd::side_println!("txn {:#x} submitted.", txn_hash);
 
         if opt.blocking {
             ctx.state().watch_txn(txn_hash)?;
@@ -5,6 +5,7 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::{bail, Result};
 use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
 use starcoin_crypto::{ed25519, ValidCryptoMaterialStringExt};
 use starcoin_vm_types::account_address::AccountAddress;
 use std::convert::TryFrom;
@@ -20,6 +21,15 @@ pub struct ExportOpt {
     password: String,
     #[structopt(short = "o", parse(from_os_str))]
     output_file: Option<PathBuf>,
+    /// export the account as an encrypted keystore file, the keystore is encrypted by the account password.
+    #[structopt(long = "keystore", parse(from_os_str), conflicts_with("output-file"))]
+    keystore: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportAccountView {
+    pub account: AccountAddress,
+    pub private_key: String,
 }
 
 pub struct ExportCommand;
@@ -28,11 +38,32 @@ impl CommandAction for ExportCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = ExportOpt;
-    type ReturnItem = ();
+    /// None if the account is exported as a keystore file.
+    type ReturnItem = Option<ExportAccountView>;
 
-    fn run(&self, ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>) -> Result<()> {
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
         let client = ctx.state().client();
         let opt: &ExportOpt = ctx.opt();
+        if let Some(keystore_file) = &opt.keystore {
+            if keystore_file.exists() {
+                bail!(
+                    "the keystore file {} is already exists, please change a name",
+                    keystore_file.as_path().display()
+                );
+            }
+            let keystore =
+                client.account_export_keystore(opt.account_address, opt.password.clone())?;
+            std::fs::write(keystore_file, keystore)?;
+            scmd::side_println!(
+                "account {} keystore saved to {}",
+                &opt.account_address,
+                keystore_file.as_path().display()
+            );
+            return Ok(None);
+        }
         let data = client.account_export(opt.account_address, opt.password.clone())?;
         let private_key = ed25519::Ed25519PrivateKey::try_from(data.as_slice())?;
         let encoded = private_key.to_encoded_string()?;
@@ -41,12 +72,11 @@ impl CommandAction for ExportCommand {
                 bail!("the output_file {} is already exists, please change a name");
             }
             std::fs::write(output_file, encoded.clone())?;
-            println!("private key saved to {}", output_file.as_path().display());
+            scmd::side_println!("private key saved to {}", output_file.as_path().display());
         }
-        println!(
-            "account {}, private key: {}",
-            &opt.account_address, &encoded
-        );
-        Ok(())
+        Ok(Some(ExportAccountView {
+            account: opt.account_address,
+            private_key: encoded,
+        }))
     }
 }
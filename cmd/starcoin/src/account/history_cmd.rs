@@ -0,0 +1,44 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_rpc_api::types::TxnHistoryView;
+use starcoin_vm_types::account_address::AccountAddress;
+use structopt::StructOpt;
+
+/// Show the txns signed by the wallet or sent from the account, from the newest to the oldest.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "history")]
+pub struct HistoryOpt {
+    #[structopt(name = "account_address")]
+    account_address: AccountAddress,
+
+    #[structopt(long = "start")]
+    /// skip the newest `start` txns, default is 0.
+    start: Option<u64>,
+
+    #[structopt(short = "l", long = "limit")]
+    /// max txns to show, default is 20.
+    limit: Option<u64>,
+}
+
+pub struct HistoryCommand;
+
+impl CommandAction for HistoryCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = HistoryOpt;
+    type ReturnItem = Vec<TxnHistoryView>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let opt = ctx.opt();
+        client.account_txn_history(opt.account_address, opt.start, opt.limit)
+    }
+}
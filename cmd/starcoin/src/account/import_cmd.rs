@@ -28,6 +28,25 @@ pub struct ImportOpt {
     )]
     from_file: Option<PathBuf>,
 
+    /// import account from an encrypted keystore file, the password should be the keystore password.
+    #[structopt(
+        long = "keystore",
+        parse(from_os_str),
+        conflicts_with_all(&["input", "from-file", "account_address"])
+    )]
+    keystore: Option<PathBuf>,
+
+    /// import the account derived from the BIP-39 mnemonic at the derivation `--index`.
+    #[structopt(
+        long = "mnemonic",
+        conflicts_with_all(&["input", "from-file", "keystore", "account_address"])
+    )]
+    mnemonic: Option<String>,
+
+    /// the derivation index of the account in the path `m/44'/101010'/0'/0'/<index>'`.
+    #[structopt(long = "index", default_value = "0", requires("mnemonic"))]
+    index: u32,
+
     /// if account_address is absent, generate address by public_key.
     #[structopt(name = "account_address")]
     account_address: Option<AccountAddress>,
@@ -48,6 +67,19 @@ impl CommandAction for ImportCommand {
         let client = ctx.state().client();
         let opt: &ImportOpt = ctx.opt();
 
+        if let Some(keystore_file) = &opt.keystore {
+            let keystore = std::fs::read_to_string(keystore_file)?;
+            return client.account_import_keystore(keystore, opt.password.clone());
+        }
+
+        if let Some(mnemonic) = &opt.mnemonic {
+            return client.account_import_from_mnemonic(
+                mnemonic.clone(),
+                opt.index,
+                opt.password.clone(),
+            );
+        }
+
         let private_key = match (opt.from_input.as_ref(), opt.from_file.as_ref()) {
             (Some(p), _) => AccountPrivateKey::from_encoded_string(p)?,
             (None, Some(p)) => {
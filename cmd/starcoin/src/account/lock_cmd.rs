@@ -17,6 +17,12 @@ pub struct LockOpt {
         help = "The wallet account address witch to lock, if absent, lock the default wallet."
     )]
     account_address: Option<AccountAddress>,
+    #[structopt(
+        long = "all",
+        conflicts_with = "account_address",
+        help = "Lock all the unlocked accounts."
+    )]
+    all: bool,
 }
 
 pub struct LockCommand;
@@ -33,6 +39,16 @@ impl CommandAction for LockCommand {
     ) -> Result<Self::ReturnItem> {
         let client = ctx.state().client();
         let opt: &LockOpt = ctx.opt();
+        if opt.all {
+            let addresses = client.account_lock_all()?;
+            return Ok(StringView {
+                result: addresses
+                    .iter()
+                    .map(|address| address.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            });
+        }
         let account = ctx.state().get_account_or_default(opt.account_address)?;
 
         client.account_lock(account.address)?;
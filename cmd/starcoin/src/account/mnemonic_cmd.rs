@@ -0,0 +1,75 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::view::StringView;
+use crate::StarcoinOpt;
+use anyhow::{ensure, Result};
+use scmd::{CommandAction, ExecContext};
+use starcoin_account_api::DerivedAccount;
+use structopt::StructOpt;
+
+/// Generate a BIP-39 english mnemonic, keep it safe, the node does not save it.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "generate-mnemonic")]
+pub struct GenerateMnemonicOpt {
+    /// the word count of the mnemonic, one of 12, 15, 18, 21 and 24.
+    #[structopt(long = "words", default_value = "12")]
+    word_count: u32,
+}
+
+pub struct GenerateMnemonicCommand;
+
+impl CommandAction for GenerateMnemonicCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = GenerateMnemonicOpt;
+    type ReturnItem = StringView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        Ok(StringView {
+            result: client.account_generate_mnemonic(Some(ctx.opt().word_count))?,
+        })
+    }
+}
+
+/// Derive the addresses from the mnemonic without importing them.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "derive-address")]
+pub struct DeriveAddressOpt {
+    #[structopt(short = "m", long = "mnemonic")]
+    mnemonic: String,
+
+    /// the derivation index of the first address.
+    #[structopt(short = "i", long = "index", default_value = "0")]
+    index: u32,
+
+    /// how many addresses to derive, from `index`.
+    #[structopt(short = "c", long = "count", default_value = "1")]
+    count: u32,
+}
+
+pub struct DeriveAddressCommand;
+
+impl CommandAction for DeriveAddressCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = DeriveAddressOpt;
+    type ReturnItem = Vec<DerivedAccount>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let opt = ctx.opt();
+        ensure!(opt.count > 0, "count should be greater than 0");
+        (opt.index..opt.index.saturating_add(opt.count))
+            .map(|index| client.account_derive_address(opt.mnemonic.clone(), index))
+            .collect()
+    }
+}
@@ -2,33 +2,48 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod accept_token_cmd;
+mod batch_transfer;
+mod bind_signer_cmd;
 mod change_password_cmd;
 mod create_cmd;
 mod default_cmd;
 mod execute_script_function_cmd;
 mod export_cmd;
+mod history_cmd;
 mod import_cmd;
+mod import_readonly_cmd;
 mod list_cmd;
 mod lock_cmd;
+mod mnemonic_cmd;
 mod partial_sign_txn_cmd;
+mod remove_cmd;
+mod rotate_key_cmd;
 mod show_cmd;
 mod sign_cmd;
+mod sign_txn_request_cmd;
 mod transfer_cmd;
 mod unlock_cmd;
 mod verify_sign_cmd;
 
 pub use accept_token_cmd::*;
+pub use bind_signer_cmd::*;
 pub use change_password_cmd::*;
 pub use create_cmd::*;
 pub use default_cmd::*;
 pub use execute_script_function_cmd::*;
 pub use export_cmd::*;
+pub use history_cmd::*;
 pub use import_cmd::*;
+pub use import_readonly_cmd::*;
 pub use list_cmd::*;
 pub use lock_cmd::*;
+pub use mnemonic_cmd::*;
 pub use partial_sign_txn_cmd::*;
+pub use remove_cmd::*;
+pub use rotate_key_cmd::*;
 pub use show_cmd::*;
 pub use sign_cmd::*;
+pub use sign_txn_request_cmd::*;
 pub use transfer_cmd::*;
 pub use unlock_cmd::*;
 pub use verify_sign_cmd::*;
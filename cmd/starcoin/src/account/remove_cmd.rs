@@ -0,0 +1,45 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_account_api::AccountInfo;
+use starcoin_vm_types::account_address::AccountAddress;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "remove")]
+pub struct RemoveOpt {
+    #[structopt(
+        name = "account_address",
+        help = "The wallet account address which to remove, if absent, use the default wallet."
+    )]
+    account_address: Option<AccountAddress>,
+
+    #[structopt(short, name = "password")]
+    password: String,
+
+    #[structopt(long = "force", help = "Force to remove the default account.")]
+    force: bool,
+}
+
+pub struct RemoveCommand;
+
+impl CommandAction for RemoveCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = RemoveOpt;
+    type ReturnItem = AccountInfo;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let opt: &RemoveOpt = ctx.opt();
+        let account = ctx.state().get_account_or_default(opt.account_address)?;
+        client.account_remove(account.address, opt.password.clone(), opt.force)
+    }
+}
@@ -0,0 +1,79 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_account_api::AccountPrivateKey;
+use starcoin_crypto::{ValidCryptoMaterial, ValidCryptoMaterialStringExt};
+use starcoin_rpc_api::types::RotateAuthKeyView;
+use starcoin_vm_types::account_address::AccountAddress;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Rotate the authentication key of the account, the account should be unlocked first.
+/// The wallet switches to the new key only after the rotation txn is executed on chain.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "rotate-key")]
+pub struct RotateKeyOpt {
+    #[structopt(
+        name = "account_address",
+        help = "The wallet account address which to rotate key, if absent, use the default wallet."
+    )]
+    account_address: Option<AccountAddress>,
+
+    /// the new private key, if absent, generate a new key by the wallet.
+    #[structopt(name = "input", short = "i")]
+    from_input: Option<String>,
+
+    /// file path of the new private key.
+    #[structopt(short = "f", parse(from_os_str), conflicts_with("input"))]
+    from_file: Option<PathBuf>,
+
+    /// only print the raw rotation txn, do not submit it or save the new key.
+    #[structopt(long = "dry-run")]
+    dry_run: bool,
+
+    /// how long to wait the rotation txn to be included.
+    #[structopt(long = "timeout-secs", default_value = "120")]
+    timeout_secs: u64,
+
+    /// replace the new key of the previous rotation whose txn is not included yet,
+    /// the account can not sign any more if that txn is included later.
+    #[structopt(long = "replace")]
+    replace: bool,
+}
+
+pub struct RotateKeyCommand;
+
+impl CommandAction for RotateKeyCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = RotateKeyOpt;
+    type ReturnItem = RotateAuthKeyView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let opt: &RotateKeyOpt = ctx.opt();
+        let account = ctx.state().get_account_or_default(opt.account_address)?;
+        let private_key = match (opt.from_input.as_ref(), opt.from_file.as_ref()) {
+            (Some(p), _) => Some(AccountPrivateKey::from_encoded_string(p)?),
+            (None, Some(p)) => {
+                let data = std::fs::read_to_string(p)?;
+                Some(AccountPrivateKey::from_encoded_string(data.trim())?)
+            }
+            (None, None) => None,
+        };
+        client.account_rotate_authentication_key(
+            account.address,
+            private_key.map(|k| k.to_bytes().to_vec()),
+            opt.dry_run,
+            Some(opt.timeout_secs),
+            opt.replace,
+        )
+    }
+}
@@ -7,6 +7,7 @@ use crate::StarcoinOpt;
 use anyhow::{format_err, Result};
 use scmd::{CommandAction, ExecContext};
 use starcoin_crypto::{HashValue, ValidCryptoMaterialStringExt};
+use starcoin_rpc_api::types::TokenInfoView;
 use starcoin_rpc_client::RemoteStateReader;
 use starcoin_state_api::AccountStateReader;
 use starcoin_vm_types::account_address::AccountAddress;
@@ -51,9 +52,9 @@ impl CommandAction for ShowCommand {
 
         let chain_state_reader = if let Some(block_id) = opt.block_id {
             let block = client
-                .chain_get_block_by_hash(block_id)?
+                .chain_get_block_by_hash(block_id, None)?
                 .ok_or_else(|| format_err!("block {} not found", block_id))?;
-            RemoteStateReader::new_with_root(client, block.header.state_root)
+            RemoteStateReader::new_at_root(client, block.header.state_root)
         } else {
             RemoteStateReader::new(client)?
         };
@@ -64,12 +65,19 @@ impl CommandAction for ShowCommand {
 
         let accepted_tokens = client.account_accepted_tokens(account_address)?;
         let mut balances = HashMap::with_capacity(accepted_tokens.len());
-        for token in accepted_tokens {
+        for token in accepted_tokens.into_iter().map(|token| token.0) {
             let token_name = token.name.clone();
             let balance =
-                account_state_reader.get_balance_by_token_code(account.address(), token)?;
+                account_state_reader.get_balance_by_token_code(account.address(), token.clone())?;
             if let Some(b) = balance {
-                balances.insert(token_name, b);
+                let token_info = account_state_reader
+                    .get_token_info(token.clone())?
+                    .ok_or_else(|| format_err!("Can not find token info of {}", token))?;
+                let token_info = TokenInfoView::new(token, &token_info);
+                balances.insert(
+                    token_name,
+                    format!("{} ({})", token_info.format_amount(b), b),
+                );
             }
         }
         let auth_key = account.public_key.authentication_key();
@@ -0,0 +1,81 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use short_hex_str::AsShortHexStr;
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::TransactionRequest;
+use std::env::current_dir;
+use std::fs::File;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Sign a transaction request by the wallet, output the hex of the signed txn to file,
+/// which can be submitted by `txpool submit-hex`.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "sign-txn-request")]
+pub struct SignTxnRequestOpt {
+    #[structopt(short = "i", long, parse(from_os_str))]
+    /// the json file of the transaction request
+    input: PathBuf,
+
+    #[structopt(short = "o", long, parse(from_os_str))]
+    /// the output file, default is `<txn hash>.signed-txn` in current dir
+    output: Option<PathBuf>,
+
+    #[structopt(long = "offline")]
+    /// do not read any state from the node, all of sender, sequence_number, expiration_timestamp_secs and chain_id should be set in the request,
+    /// the chain_id can be a number or a builtin network name, and defaults to the global `--chain` option.
+    offline: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignTxnRequestView {
+    pub txn_hash: HashValue,
+    pub output: PathBuf,
+}
+
+pub struct SignTxnRequestCommand;
+
+impl CommandAction for SignTxnRequestCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SignTxnRequestOpt;
+    type ReturnItem = SignTxnRequestView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let client = ctx.state().client();
+        let mut request: TransactionRequest = serde_json::from_reader(File::open(&opt.input)?)?;
+        if request.chain_id.is_none() {
+            request.chain_id = ctx.global_opt().chain.map(|chain_id| chain_id.id());
+        }
+        let signed_txn = if opt.offline {
+            ctx.state().sign_txn(request.to_raw_txn_offline()?)?
+        } else {
+            client.account_sign_txn_request(request)?
+        };
+        let txn_hash = signed_txn.id();
+        let output = match opt.output.clone() {
+            Some(output) => output,
+            None => {
+                let mut output = current_dir()?;
+                output.push(txn_hash.short_str().as_str());
+                output.set_extension("signed-txn");
+                output
+            }
+        };
+        std::fs::write(
+            &output,
+            format!("0x{}", hex::encode(bcs_ext::to_bytes(&signed_txn)?)),
+        )?;
+        Ok(SignTxnRequestView { txn_hash, output })
+    }
+}
@@ -1,19 +1,29 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::cli_state::CliState;
+use super::batch_transfer::{read_batch_file, BatchTransferState, SubmittedTxn};
+use crate::cli_state::{CliState, GasPriceArg};
 use crate::view::{ExecuteResultView, ExecutionOutputView};
 use crate::StarcoinOpt;
-use anyhow::{format_err, Result};
+use anyhow::{bail, ensure, format_err, Result};
 use scmd::{CommandAction, ExecContext};
 use starcoin_crypto::{ed25519::Ed25519PublicKey, ValidCryptoMaterialStringExt};
 use starcoin_executor::DEFAULT_EXPIRATION_TIME;
+use starcoin_rpc_api::types::{
+    DryRunTransactionRequest, ReceiptIdentifierView, StrView, TransactionVMStatus,
+};
 use starcoin_rpc_client::RemoteStateReader;
 use starcoin_state_api::AccountStateReader;
+use starcoin_transaction_builder::{
+    encode_batch_transfer_script_by_token_code, encode_transfer_script_by_token_code,
+    is_batch_transfer_supported,
+};
 use starcoin_vm_types::account_address::AccountAddress;
 use starcoin_vm_types::token::stc::STC_TOKEN_CODE;
 use starcoin_vm_types::token::token_code::TokenCode;
 use starcoin_vm_types::transaction::authenticator::AuthenticationKey;
+use starcoin_vm_types::transaction::{RawUserTransaction, ScriptFunction, TransactionPayload};
+use std::path::{Path, PathBuf};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -22,13 +32,17 @@ pub struct TransferOpt {
     #[structopt(short = "s")]
     /// if `sender` is absent, use default account.
     sender: Option<AccountAddress>,
-    #[structopt(short = "r")]
-    receiver: AccountAddress,
+    #[structopt(short = "r", required_unless_one = &["batch", "receipt"])]
+    receiver: Option<AccountAddress>,
+    #[structopt(long = "receipt", conflicts_with = "receiver")]
+    /// the receipt identifier of the receiver, such as `stc1p...`, instead of the receiver address.
+    /// The auth key in it is used to create the receiver account if the account not exist on chain.
+    receipt: Option<ReceiptIdentifierView>,
     #[structopt(short = "k")]
-    /// if `to` account not exist on chain, must provide public_key of the account.
+    /// if `to` account not exist on chain, must provide public_key of the account, or a receipt identifier with auth key.
     public_key: Option<String>,
-    #[structopt(short = "v")]
-    amount: u128,
+    #[structopt(short = "v", required_unless = "batch")]
+    amount: Option<u128>,
     #[structopt(
         short = "g",
         long = "max-gas",
@@ -42,9 +56,9 @@ pub struct TransferOpt {
         long = "gas-price",
         name = "price of gas",
         default_value = "1",
-        help = "gas price used"
+        help = "gas price used, or `auto` to use the gas price suggested by the node"
     )]
-    gas_price: u64,
+    gas_price: GasPriceArg,
 
     #[structopt(
         short = "t",
@@ -61,6 +75,31 @@ pub struct TransferOpt {
         help = "blocking wait txn mined"
     )]
     blocking: bool,
+
+    #[structopt(long = "replace")]
+    /// replace the pending txn with the same sequence number in txpool, the gas price is bumped automatically.
+    replace: bool,
+
+    #[structopt(long = "force")]
+    /// submit the txn even if the receiver does not accept the token, the txn will abort on chain.
+    force: bool,
+
+    #[structopt(
+        long = "batch",
+        parse(from_os_str),
+        conflicts_with_all = &["receiver", "receipt", "public_key", "amount", "replace"]
+    )]
+    /// transfer to the receivers in the json file, which is an array of `{"address", "amount", "public_key"}`,
+    /// or in the csv file, whose lines are `address,amount[,public_key]`.
+    batch: Option<PathBuf>,
+
+    #[structopt(long = "batch-size", default_value = "50")]
+    /// max receivers in one txn of the batch transfer.
+    batch_size: usize,
+
+    #[structopt(long = "batch-state", parse(from_os_str))]
+    /// the file to save the progress of the batch transfer, for resuming after a failure, default is `<batch file>.state.json`.
+    batch_state: Option<PathBuf>,
 }
 
 pub struct TransferCommand;
@@ -86,27 +125,57 @@ impl CommandAction for TransferCommand {
                 format_err!("Can not find default account, Please input from account.")
             })?,
         };
-        let receiver = opt.receiver;
+        let token_code = opt
+            .token_code
+            .clone()
+            .unwrap_or_else(|| STC_TOKEN_CODE.clone());
+        if let Some(batch_file) = opt.batch.as_ref() {
+            return self.batch_transfer(ctx, sender.address, token_code, batch_file.as_path());
+        }
+        let receiver = opt
+            .receiver
+            .or_else(|| opt.receipt.map(|receipt| receipt.address()))
+            .ok_or_else(|| format_err!("receiver is required"))?;
+        let amount = opt
+            .amount
+            .ok_or_else(|| format_err!("amount is required"))?;
 
         let chain_state_reader = RemoteStateReader::new(client)?;
         let account_state_reader = AccountStateReader::new(&chain_state_reader);
         let receiver_exist_on_chain = account_state_reader
             .get_account_resource(&receiver)?
             .is_some();
+        // a new account accepts the token when it is created by the transfer.
+        if receiver_exist_on_chain && !opt.force {
+            let accepted = client
+                .account_accepted_tokens(receiver)?
+                .into_iter()
+                .any(|token| token.0 == token_code);
+            if !accepted {
+                bail!(
+                    "Receiver {} does not accept token {}, the receiver should `account accept-token {}` first, or use --force to submit anyway.",
+                    receiver,
+                    token_code,
+                    token_code
+                );
+            }
+        }
         let receiver_auth_key = if receiver_exist_on_chain {
             None
+        } else if let Some(auth_key) = opt.receipt.and_then(|receipt| receipt.auth_key()) {
+            Some(auth_key)
         } else {
             let k = opt
                 .public_key
                 .as_ref()
                 .ok_or_else(|| {
                     format_err!(
-                        "To account {} not exist on chain, please provide public_key",
+                        "To account {} not exist on chain, please provide public_key, or a receipt identifier with auth key",
                         receiver
                     )
                 })
                 .and_then(|pubkey_str| Ok(Ed25519PublicKey::from_encoded_string(pubkey_str)?))?;
-            Some(k)
+            Some(AuthenticationKey::ed25519(&k))
         };
         let account_resource = account_state_reader
             .get_account_resource(sender.address())?
@@ -116,35 +185,306 @@ impl CommandAction for TransferCommand {
                     sender.address()
                 )
             })?;
-        let token_code = opt
-            .token_code
-            .clone()
-            .unwrap_or_else(|| STC_TOKEN_CODE.clone());
+        let gas_price = ctx.state().gas_price(opt.gas_price)?;
+        let gas_price = if opt.replace {
+            ctx.state().replace_gas_price(
+                sender.address,
+                account_resource.sequence_number(),
+                gas_price,
+            )?
+        } else {
+            gas_price
+        };
         let raw_txn = starcoin_executor::build_transfer_txn_by_token_type(
             sender.address,
             receiver,
-            receiver_auth_key
-                .as_ref()
-                .map(|k| AuthenticationKey::ed25519(k)),
+            receiver_auth_key,
             account_resource.sequence_number(),
-            opt.amount,
-            opt.gas_price,
+            amount,
+            gas_price,
             opt.max_gas_amount,
             token_code,
             node_info.now_seconds + DEFAULT_EXPIRATION_TIME,
             ctx.state().net().chain_id(),
         );
-        let txn = client.account_sign_txn(raw_txn)?;
+        let txn = ctx.state().sign_txn(raw_txn)?;
         let txn_hash = txn.id();
-        client.submit_transaction(txn)?;
+        if opt.replace {
+            if let Some(replaced) = client.replace_transaction(txn)? {
+                scmd::side_println!("txn {:#x} replaced.", replaced);
+            }
+        } else {
+            client.submit_transaction(txn)?;
+        }
 
         let mut output_view = ExecutionOutputView::new(txn_hash);
 
         if opt.blocking {
-            let block = ctx.state().watch_txn(txn_hash)?.0;
-            output_view.block_number = Some(block.header.number.0);
-            output_view.block_id = Some(block.header.block_hash);
+            let (block, txn_info) = ctx.state().watch_txn(txn_hash)?;
+            output_view.set_mined(&block, txn_info);
         }
         Ok(ExecuteResultView::Run(output_view))
     }
 }
+
+impl TransferCommand {
+    fn batch_transfer(
+        &self,
+        ctx: &ExecContext<CliState, StarcoinOpt, TransferOpt>,
+        sender: AccountAddress,
+        token_code: TokenCode,
+        batch_file: &Path,
+    ) -> Result<ExecuteResultView> {
+        let client = ctx.state().client();
+        let opt = ctx.opt();
+        ensure!(opt.batch_size > 0, "batch size should be greater than 0");
+        let rows = read_batch_file(batch_file)?;
+        let state_path = opt
+            .batch_state
+            .clone()
+            .unwrap_or_else(|| BatchTransferState::default_path(batch_file));
+        let mut state = BatchTransferState::load_or_new(
+            state_path.as_path(),
+            sender,
+            token_code.clone(),
+            rows.len(),
+        )?;
+
+        // check the txns submitted by the last run.
+        for submitted in &state.submitted {
+            if client
+                .chain_get_transaction_info(submitted.txn_hash)?
+                .is_none()
+                && client
+                    .get_pending_txn_by_hash(submitted.txn_hash)?
+                    .is_some()
+            {
+                bail!(
+                    "Txn {:#x} submitted by the last run is still pending in txpool, please retry after it is mined or dropped.",
+                    submitted.txn_hash
+                );
+            }
+        }
+        for submitted in std::mem::take(&mut state.submitted) {
+            match client.chain_get_transaction_info(submitted.txn_hash)? {
+                Some(txn_info) if txn_info.status == TransactionVMStatus::Executed => {
+                    state.confirmed.extend(submitted.rows);
+                }
+                Some(txn_info) => scmd::side_println!(
+                    "Txn {:#x} failed, status: {:?}, its rows will be transferred again.",
+                    submitted.txn_hash,
+                    txn_info.status
+                ),
+                None => scmd::side_println!(
+                    "Txn {:#x} is dropped, its rows will be transferred again.",
+                    submitted.txn_hash
+                ),
+            }
+        }
+        state.save(state_path.as_path())?;
+        let unconfirmed = state.unconfirmed_rows();
+        if unconfirmed.is_empty() {
+            scmd::side_println!("All {} rows are confirmed on chain.", rows.len());
+            return Ok(ExecuteResultView::Batch(vec![]));
+        }
+
+        let chain_state_reader = RemoteStateReader::new(client)?;
+        let account_state_reader = AccountStateReader::new(&chain_state_reader);
+        let mut auth_keys = vec![None; rows.len()];
+        for i in &unconfirmed {
+            let row = &rows[*i];
+            if account_state_reader
+                .get_account_resource(&row.address)?
+                .is_some()
+            {
+                if !opt.force
+                    && !client
+                        .account_accepted_tokens(row.address)?
+                        .into_iter()
+                        .any(|token| token.0 == token_code)
+                {
+                    bail!(
+                        "Receiver {} at row {} does not accept token {}, or use --force to submit anyway.",
+                        row.address,
+                        i,
+                        token_code
+                    );
+                }
+            } else {
+                let public_key = row.public_key.as_ref().ok_or_else(|| {
+                    format_err!(
+                        "Receiver {} at row {} not exist on chain, please provide public_key",
+                        row.address,
+                        i
+                    )
+                })?;
+                let public_key = Ed25519PublicKey::from_encoded_string(public_key)?;
+                auth_keys[*i] = Some(AuthenticationKey::ed25519(&public_key));
+            }
+        }
+
+        let stdlib_version = account_state_reader.get_stdlib_version()?;
+        let batch_supported = is_batch_transfer_supported(stdlib_version);
+        let chunk_size = if batch_supported {
+            opt.batch_size
+        } else {
+            scmd::side_println!(
+                "The stdlib version {} of the chain does not support batch transfer, transfer one by one.",
+                stdlib_version.as_string()
+            );
+            1
+        };
+        let chunks: Vec<Vec<usize>> = unconfirmed
+            .chunks(chunk_size)
+            .map(|chunk| chunk.to_vec())
+            .collect();
+        let build_payload = |chunk: &[usize]| {
+            if batch_supported {
+                encode_batch_transfer_script_by_token_code(
+                    chunk.iter().map(|i| rows[*i].address).collect(),
+                    chunk.iter().map(|i| auth_keys[*i].clone()).collect(),
+                    chunk.iter().map(|i| rows[*i].amount).collect(),
+                    token_code.clone(),
+                )
+            } else {
+                let i = chunk[0];
+                encode_transfer_script_by_token_code(
+                    stdlib_version,
+                    rows[i].address,
+                    auth_keys[i].clone(),
+                    rows[i].amount,
+                    token_code.clone(),
+                )
+            }
+        };
+
+        let account_resource = account_state_reader
+            .get_account_resource(&sender)?
+            .ok_or_else(|| format_err!("Can not find account on chain by address:{}", sender))?;
+        // the txns submitted before may be still in the txpool.
+        let sequence_number = client
+            .next_sequence_number_in_txpool(sender)?
+            .unwrap_or_else(|| account_resource.sequence_number())
+            .max(account_resource.sequence_number());
+        let expiration_timestamp_secs = client.node_info()?.now_seconds + DEFAULT_EXPIRATION_TIME;
+        let chain_id = ctx.state().net().chain_id();
+        let gas_price = ctx.state().gas_price(opt.gas_price)?;
+        let build_txn = |payload: ScriptFunction, sequence_number: u64, max_gas_amount: u64| {
+            RawUserTransaction::new_with_default_gas_token(
+                sender,
+                sequence_number,
+                TransactionPayload::ScriptFunction(payload),
+                max_gas_amount,
+                gas_price,
+                expiration_timestamp_secs,
+                chain_id,
+            )
+        };
+
+        let first_txn = ctx.state().sign_txn(build_txn(
+            build_payload(chunks[0].as_slice()),
+            sequence_number,
+            opt.max_gas_amount,
+        ))?;
+        let output = client.dry_run(DryRunTransactionRequest {
+            sender_public_key: Some(StrView(first_txn.authenticator().public_key())),
+            transaction: first_txn.raw_txn().clone().into(),
+            state_overrides: vec![],
+            with_gas_breakdown: false,
+        })?;
+        if output.status != TransactionVMStatus::Executed {
+            bail!("Dry run the first txn failed, status: {:?}", output.status);
+        }
+        // the txns may create different count of accounts, reserve double gas of the first txn.
+        let max_gas_amount = output.gas_used.0.saturating_mul(2).min(opt.max_gas_amount);
+        scmd::side_println!(
+            "Transfer {} rows in {} txns, gas used of the first txn: {}, max gas: {}",
+            unconfirmed.len(),
+            chunks.len(),
+            output.gas_used.0,
+            max_gas_amount
+        );
+
+        let total = chunks.len();
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            let txn = ctx.state().sign_txn(build_txn(
+                build_payload(chunk.as_slice()),
+                sequence_number + i as u64,
+                max_gas_amount,
+            ))?;
+            let txn_hash = txn.id();
+            if let Err(e) = client.submit_transaction(txn) {
+                bail!(
+                    "Submit txn {}/{} failed: {}, the progress is saved in {}, run the command again to resume.",
+                    i + 1,
+                    total,
+                    e,
+                    state_path.display()
+                );
+            }
+            scmd::side_println!(
+                "[{}/{}] txn {:#x} submitted, receivers: {}",
+                i + 1,
+                total,
+                txn_hash,
+                chunk.len()
+            );
+            state.submitted.push(SubmittedTxn {
+                txn_hash,
+                rows: chunk,
+            });
+            state.save(state_path.as_path())?;
+        }
+
+        let mut outputs = vec![];
+        for (i, submitted) in state.submitted.clone().into_iter().enumerate() {
+            let (block, txn_info) = match ctx.state().watch_txn(submitted.txn_hash) {
+                Ok(mined) => mined,
+                Err(e) => bail!(
+                    "Watch txn {:#x} failed: {}, the progress is saved in {}, run the command again to resume.",
+                    submitted.txn_hash,
+                    e,
+                    state_path.display()
+                ),
+            };
+            let mut output_view = ExecutionOutputView::new(submitted.txn_hash);
+            output_view.set_mined(&block, txn_info.clone());
+            outputs.push(output_view);
+            // keep the txn without txn info in the state, it is checked again when resuming.
+            if let Some(txn_info) = txn_info {
+                if txn_info.status == TransactionVMStatus::Executed {
+                    state.confirmed.extend(submitted.rows.iter());
+                    scmd::side_println!(
+                        "[{}/{}] txn {:#x} confirmed.",
+                        i + 1,
+                        total,
+                        submitted.txn_hash
+                    );
+                } else {
+                    scmd::side_println!(
+                        "[{}/{}] txn {:#x} failed, status: {:?}",
+                        i + 1,
+                        total,
+                        submitted.txn_hash,
+                        txn_info.status
+                    );
+                }
+                state
+                    .submitted
+                    .retain(|txn| txn.txn_hash != submitted.txn_hash);
+                state.save(state_path.as_path())?;
+            }
+        }
+        let unconfirmed = state.unconfirmed_rows().len();
+        if unconfirmed == 0 {
+            scmd::side_println!("All {} rows are confirmed on chain.", rows.len());
+        } else {
+            scmd::side_println!(
+                "{} rows are not confirmed, the progress is saved in {}, run the command again to resume.",
+                unconfirmed,
+                state_path.display()
+            );
+        }
+        Ok(ExecuteResultView::Batch(outputs))
+    }
+}
@@ -5,12 +5,12 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
-use starcoin_vm_types::on_chain_resource::EpochInfo;
+use starcoin_rpc_api::types::EpochInfoView;
 use structopt::StructOpt;
 
 /// Epoch info of main.
 #[derive(Debug, StructOpt)]
-#[structopt(name = "epoch_info")]
+#[structopt(name = "epoch-info", alias = "epoch_info")]
 pub struct EpochInfoOpt {}
 
 pub struct EpochInfoCommand;
@@ -19,7 +19,7 @@ impl CommandAction for EpochInfoCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = EpochInfoOpt;
-    type ReturnItem = EpochInfo;
+    type ReturnItem = EpochInfoView;
 
     fn run(
         &self,
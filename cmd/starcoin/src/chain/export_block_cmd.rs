@@ -0,0 +1,69 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_types::block::BlockNumber;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Export the block as a self-contained BCS bundle, which can be replayed offline by `dev replay-block`.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "export-block")]
+pub struct ExportBlockOpt {
+    #[structopt(long = "block")]
+    /// the hash of the block.
+    block_hash: HashValue,
+    #[structopt(long = "with-state")]
+    /// also record the state entries the block reads and the outputs of the txns, which are required to replay the block,
+    /// the node should be started with `--enable-block-bundle-state`.
+    with_state: bool,
+    #[structopt(long = "output", short = "o", parse(from_os_str))]
+    output: PathBuf,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExportBlockView {
+    pub block_hash: HashValue,
+    pub block_number: BlockNumber,
+    pub transactions: usize,
+    /// The count of the recorded state entries, None if exported without the state.
+    pub read_set: Option<usize>,
+    pub output: PathBuf,
+    pub bytes: usize,
+}
+
+pub struct ExportBlockCommand;
+
+impl CommandAction for ExportBlockCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ExportBlockOpt;
+    type ReturnItem = ExportBlockView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let bundle = ctx
+            .state()
+            .client()
+            .chain_export_block_bundle(opt.block_hash, opt.with_state)?
+            .ok_or_else(|| format_err!("block {} not found", opt.block_hash))?;
+        let bytes = bundle.encode()?;
+        std::fs::write(opt.output.as_path(), bytes.as_slice())?;
+        Ok(ExportBlockView {
+            block_hash: bundle.block.id(),
+            block_number: bundle.block.header().number(),
+            transactions: bundle.transactions.len(),
+            read_set: bundle.state.map(|state| state.read_set.len()),
+            output: opt.output.clone(),
+            bytes: bytes.len(),
+        })
+    }
+}
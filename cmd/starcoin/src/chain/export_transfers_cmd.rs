@@ -0,0 +1,239 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{bail, ensure, format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_rpc_api::transfer_export::{
+    export_transfers, TransferExportCheckpoint, TransferRecord,
+};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block::BlockNumber;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use structopt::StructOpt;
+
+/// The events fetched by one rpc call.
+const EXPORT_PAGE_SIZE: usize = 100;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    Csv,
+    /// One json object per line.
+    Json,
+}
+
+impl FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" | "CSV" => Ok(ExportFormat::Csv),
+            "json" | "JSON" => Ok(ExportFormat::Json),
+            _ => bail!("unknown format {}, should be csv or json", s),
+        }
+    }
+}
+
+/// Export the transfers in and out of the addresses in the block range from the Deposit/Withdraw events.
+/// The progress is saved to the checkpoint file after every page, rerun the same command to resume,
+/// the checkpoint file is removed when the export finishes.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "export-transfers")]
+pub struct ExportTransfersOpt {
+    #[structopt(long = "addresses", use_delimiter = true, required = true)]
+    addresses: Vec<AccountAddress>,
+    #[structopt(long = "from-block", default_value = "0")]
+    from_block: BlockNumber,
+    /// Default is the current head block.
+    #[structopt(long = "to-block")]
+    to_block: Option<BlockNumber>,
+    #[structopt(long = "output", short = "o", parse(from_os_str))]
+    output: PathBuf,
+    /// csv or json.
+    #[structopt(long = "format", default_value = "csv")]
+    format: ExportFormat,
+    /// Default is the output file with the `.checkpoint` extension.
+    #[structopt(long = "checkpoint", parse(from_os_str))]
+    checkpoint: Option<PathBuf>,
+    /// The block range of one event query, should not be greater than the `block_query_max_range` of the node.
+    #[structopt(long = "block-range", default_value = "32")]
+    block_range: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+struct ExportCheckpointFile {
+    addresses: Vec<AccountAddress>,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    format: ExportFormat,
+    records: u64,
+    checkpoint: TransferExportCheckpoint,
+}
+
+impl ExportCheckpointFile {
+    fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_slice(&std::fs::read(path)?)?))
+    }
+
+    /// Write to a temp file then rename it, so a crash does not leave a broken checkpoint.
+    fn save(&self, path: &Path) -> Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, serde_json::to_vec(self)?)?;
+        std::fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+}
+
+enum RecordWriter {
+    Csv(csv::Writer<File>),
+    Json(File),
+}
+
+impl RecordWriter {
+    fn open(path: &Path, format: ExportFormat, resume: bool) -> Result<Self> {
+        let file = if resume {
+            OpenOptions::new().append(true).open(path)?
+        } else {
+            File::create(path)?
+        };
+        Ok(match format {
+            ExportFormat::Csv => RecordWriter::Csv(
+                csv::WriterBuilder::new()
+                    .has_headers(!resume)
+                    .from_writer(file),
+            ),
+            ExportFormat::Json => RecordWriter::Json(file),
+        })
+    }
+
+    fn write(&mut self, record: &TransferRecord) -> Result<()> {
+        match self {
+            RecordWriter::Csv(writer) => writer.serialize(record)?,
+            RecordWriter::Json(file) => {
+                serde_json::to_writer(&mut *file, record)?;
+                file.write_all(b"\n")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        match self {
+            RecordWriter::Csv(writer) => writer.flush()?,
+            RecordWriter::Json(file) => file.flush()?,
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportTransfersView {
+    pub output: PathBuf,
+    pub records: u64,
+    pub from_block: BlockNumber,
+    pub to_block: BlockNumber,
+}
+
+pub struct ExportTransfersCommand;
+
+impl CommandAction for ExportTransfersCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ExportTransfersOpt;
+    type ReturnItem = ExportTransfersView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let opt = ctx.opt();
+        let checkpoint_path = opt
+            .checkpoint
+            .clone()
+            .unwrap_or_else(|| opt.output.with_extension("checkpoint"));
+        let loaded = ExportCheckpointFile::load(&checkpoint_path)?;
+        let resume = loaded.is_some();
+        let mut checkpoint_file = match loaded {
+            Some(checkpoint_file) => {
+                ensure!(
+                    checkpoint_file.addresses == opt.addresses
+                        && checkpoint_file.from_block == opt.from_block
+                        && opt
+                            .to_block
+                            .map(|to_block| to_block == checkpoint_file.to_block)
+                            .unwrap_or(true)
+                        && checkpoint_file.format == opt.format,
+                    "the checkpoint {} is of another export, remove it to restart",
+                    checkpoint_path.display()
+                );
+                ensure!(
+                    opt.output.exists(),
+                    "the output {} of the checkpoint {} is missing, remove the checkpoint to restart",
+                    opt.output.display(),
+                    checkpoint_path.display()
+                );
+                checkpoint_file
+            }
+            None => {
+                let to_block = match opt.to_block {
+                    Some(to_block) => to_block,
+                    None => client.chain_info()?.head.number.0,
+                };
+                ensure!(
+                    opt.from_block <= to_block,
+                    "from-block should not be greater than to-block"
+                );
+                ExportCheckpointFile {
+                    addresses: opt.addresses.clone(),
+                    from_block: opt.from_block,
+                    to_block,
+                    format: opt.format,
+                    records: 0,
+                    checkpoint: TransferExportCheckpoint::default(),
+                }
+            }
+        };
+        let mut writer = RecordWriter::open(&opt.output, opt.format, resume)?;
+        export_transfers(
+            client,
+            &checkpoint_file.addresses.clone(),
+            checkpoint_file.from_block,
+            checkpoint_file.to_block,
+            checkpoint_file.checkpoint.clone(),
+            opt.block_range,
+            EXPORT_PAGE_SIZE,
+            |records, checkpoint| {
+                for record in records.iter() {
+                    writer.write(record)?;
+                }
+                // the records of a page may be written twice if crashed before saving the checkpoint.
+                writer.flush()?;
+                checkpoint_file.records += records.len() as u64;
+                checkpoint_file.checkpoint = checkpoint.clone();
+                checkpoint_file.save(&checkpoint_path)
+            },
+        )?;
+        std::fs::remove_file(&checkpoint_path).map_err(|e| {
+            format_err!(
+                "remove the checkpoint {} failed: {:?}",
+                checkpoint_path.display(),
+                e
+            )
+        })?;
+        Ok(ExportTransfersView {
+            output: opt.output.clone(),
+            records: checkpoint_file.records,
+            from_block: checkpoint_file.from_block,
+            to_block: checkpoint_file.to_block,
+        })
+    }
+}
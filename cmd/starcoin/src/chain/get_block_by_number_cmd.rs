@@ -30,7 +30,7 @@ impl CommandAction for GetBlockByNumberCommand {
         let client = ctx.state().client();
         let opt = ctx.opt();
         let block = client
-            .chain_get_block_by_number(opt.number)?
+            .chain_get_block_by_number(opt.number, None)?
             .ok_or_else(|| anyhow::format_err!("block of height {} not found", opt.number))?;
         Ok(block.header)
     }
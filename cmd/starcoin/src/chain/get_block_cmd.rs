@@ -31,7 +31,7 @@ impl CommandAction for GetBlockCommand {
         let client = ctx.state().client();
         let opt = ctx.opt();
         let block = client
-            .chain_get_block_by_hash(opt.hash)?
+            .chain_get_block_by_hash(opt.hash, None)?
             .ok_or_else(|| anyhow::format_err!("block {} not found", opt.hash))?;
 
         Ok(block.header)
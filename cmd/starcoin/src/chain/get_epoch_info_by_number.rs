@@ -5,7 +5,7 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
-use starcoin_vm_types::on_chain_resource::EpochInfo;
+use starcoin_rpc_api::types::EpochInfoView;
 use structopt::StructOpt;
 
 /// Get epoch info of main.
@@ -22,7 +22,7 @@ impl CommandAction for GetEpochInfoByNumberCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = GetEpochInfoByNumberOpt;
-    type ReturnItem = EpochInfo;
+    type ReturnItem = EpochInfoView;
 
     fn run(
         &self,
@@ -5,12 +5,13 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
+use serde::Serialize;
 use starcoin_crypto::HashValue;
-use starcoin_rpc_api::types::TransactionView;
+use starcoin_rpc_api::types::{TransactionOutputView, TransactionView};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
-#[structopt(name = "get_txn")]
+#[structopt(name = "get-txn", alias = "get_txn")]
 pub struct GetOpt {
     #[structopt(name = "txn-hash")]
     /// txn hash
@@ -22,6 +23,18 @@ pub struct GetOpt {
     #[structopt(name = "idx", long, required_unless = "txn-hash")]
     /// the index(start from 0) of the txn in the block
     idx: Option<u64>,
+
+    #[structopt(long = "with-output")]
+    /// also show the output(write set, events and gas) of the txn, by re-executing it on the node.
+    with_output: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransactionWithOutputView {
+    #[serde(flatten)]
+    pub transaction: TransactionView,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<TransactionOutputView>,
 }
 
 pub struct GetTransactionCommand;
@@ -30,7 +43,7 @@ impl CommandAction for GetTransactionCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = GetOpt;
-    type ReturnItem = Option<TransactionView>;
+    type ReturnItem = Option<TransactionWithOutputView>;
 
     fn run(
         &self,
@@ -38,17 +51,30 @@ impl CommandAction for GetTransactionCommand {
     ) -> Result<Self::ReturnItem> {
         let client = ctx.state().client();
         let opt = ctx.opt();
-        match &opt.txn_hash {
-            Some(txn_hash) => Ok(client.chain_get_transaction(*txn_hash)?),
+        let txn_hash = match &opt.txn_hash {
+            Some(txn_hash) => *txn_hash,
             None => {
                 let block_hash = opt.block_hash.expect("block-hash exists");
                 let idx = opt.idx.expect("idx exists");
                 let txn_info = client.chain_get_txn_info_by_block_and_index(block_hash, idx)?;
                 match txn_info {
-                    Some(info) => Ok(client.chain_get_transaction(info.transaction_hash)?),
-                    None => Ok(None),
+                    Some(info) => info.transaction_hash,
+                    None => return Ok(None),
                 }
             }
-        }
+        };
+        let transaction = match client.chain_get_transaction(txn_hash)? {
+            Some(transaction) => transaction,
+            None => return Ok(None),
+        };
+        let output = if opt.with_output {
+            client.chain_get_transaction_output(txn_hash)?
+        } else {
+            None
+        };
+        Ok(Some(TransactionWithOutputView {
+            transaction,
+            output,
+        }))
     }
 }
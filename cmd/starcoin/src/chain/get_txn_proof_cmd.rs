@@ -0,0 +1,55 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::TransactionProofView;
+use structopt::StructOpt;
+
+/// Get the accumulator proof of a txn, and verify it against the block header.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "get_txn_proof")]
+pub struct GetTxnProofOpt {
+    #[structopt(name = "txn-hash")]
+    /// txn hash
+    txn_hash: HashValue,
+
+    #[structopt(name = "event-index", long)]
+    /// the index(start from 0) of the event in the txn, also return the proof of the event if present.
+    event_index: Option<u64>,
+}
+
+pub struct GetTxnProofCommand;
+
+impl CommandAction for GetTxnProofCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = GetTxnProofOpt;
+    type ReturnItem = TransactionProofView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let opt = ctx.opt();
+        let txn_info = client
+            .chain_get_transaction_info(opt.txn_hash)?
+            .ok_or_else(|| format_err!("txn {} not found", opt.txn_hash))?;
+        let block = client
+            .chain_get_block_by_hash(txn_info.block_hash, None)?
+            .ok_or_else(|| format_err!("block {} not found", txn_info.block_hash))?;
+        let proof = client
+            .chain_get_transaction_proof(
+                txn_info.block_hash,
+                txn_info.transaction_index as u64,
+                opt.event_index,
+            )?
+            .ok_or_else(|| format_err!("proof of txn {} not found", opt.txn_hash))?;
+        proof.verify(block.header.txn_accumulator_root)?;
+        Ok(proof)
+    }
+}
@@ -5,7 +5,7 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
-use starcoin_rpc_api::types::ChainInfoView;
+use starcoin_rpc_api::types::ChainStatusView;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -18,7 +18,7 @@ impl CommandAction for InfoCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = InfoOpt;
-    type ReturnItem = ChainInfoView;
+    type ReturnItem = ChainStatusView;
 
     fn run(
         &self,
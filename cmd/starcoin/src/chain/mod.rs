@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod epoch_info;
+mod export_block_cmd;
+mod export_transfers_cmd;
 mod get_block_by_number_cmd;
 mod get_block_cmd;
 mod get_epoch_info_by_number;
@@ -10,14 +12,18 @@ mod get_global_time_by_number;
 mod get_txn_by_block_cmd;
 mod get_txn_cmd;
 mod get_txn_info_cmd;
+mod get_txn_proof_cmd;
 mod info_cmd;
 mod list_block_cmd;
+mod rewards_cmd;
 mod stat;
 mod tps;
 pub mod uncle;
 mod verify;
 
 pub use epoch_info::*;
+pub use export_block_cmd::*;
+pub use export_transfers_cmd::*;
 pub use get_block_by_number_cmd::*;
 pub use get_block_cmd::*;
 pub use get_epoch_info_by_number::*;
@@ -26,8 +32,10 @@ pub use get_global_time_by_number::*;
 pub use get_txn_by_block_cmd::*;
 pub use get_txn_cmd::*;
 pub use get_txn_info_cmd::*;
+pub use get_txn_proof_cmd::*;
 pub use info_cmd::*;
 pub use list_block_cmd::*;
+pub use rewards_cmd::*;
 pub use stat::{StatBlockCommand, StatEpochCommand, StatTPSCommand};
 pub use tps::*;
 pub use verify::*;
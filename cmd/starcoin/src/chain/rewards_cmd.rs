@@ -0,0 +1,58 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{ensure, Result};
+use scmd::{CommandAction, ExecContext};
+use starcoin_rpc_api::types::BlockRewardView;
+use starcoin_types::block::BlockNumber;
+use structopt::StructOpt;
+
+/// The max block rewards returned by one rpc call.
+const REWARDS_PAGE_SIZE: u64 = 100;
+
+/// List the reward breakdowns of `count` main chain blocks from `start`,
+/// the blocks whose reward is not distributed yet are excluded.
+/// Use `--output-format table` to print a table.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "rewards")]
+pub struct RewardsOpt {
+    #[structopt(name = "start", long, short = "s", default_value = "1")]
+    start: BlockNumber,
+    #[structopt(name = "count", long, short = "c", default_value = "10")]
+    count: u64,
+}
+
+pub struct RewardsCommand;
+
+impl CommandAction for RewardsCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = RewardsOpt;
+    type ReturnItem = Vec<BlockRewardView>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let opt = ctx.opt();
+        ensure!(opt.count > 0, "count should be greater than 0");
+        let end = opt.start.saturating_add(opt.count);
+        let mut rewards = vec![];
+        let mut start = opt.start;
+        while start < end {
+            let count = (end - start).min(REWARDS_PAGE_SIZE);
+            let page = client.chain_get_block_rewards(start, count)?;
+            // the rewards of the later blocks are not distributed yet.
+            if (page.len() as u64) < count {
+                rewards.extend(page);
+                break;
+            }
+            rewards.extend(page);
+            start += count;
+        }
+        Ok(rewards)
+    }
+}
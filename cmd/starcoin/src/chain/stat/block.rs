@@ -64,12 +64,12 @@ impl CommandAction for StatBlockCommand {
         let mut vec_stat_block = vec![];
         while block_number < end_number {
             let block = client
-                .chain_get_block_by_number(block_number)?
+                .chain_get_block_by_number(block_number, None)?
                 .ok_or_else(|| anyhow::format_err!("block of height {} not found", block_number))?;
             let stat_view = BlockStatView::new(
                 block.header.number.0,
                 block.header.gas_used.0,
-                block.header.difficulty,
+                block.header.difficulty.0,
                 block.body.txn_hashes().len() as u64,
                 block.header.timestamp.0,
             );
@@ -5,10 +5,10 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::{format_err, Result};
 use scmd::{CommandAction, ExecContext};
+use starcoin_rpc_api::types::EpochInfoView;
 use starcoin_rpc_client::RemoteStateReader;
 use starcoin_state_api::AccountStateReader;
 use starcoin_vm_types::on_chain_config::ConsensusConfig;
-use starcoin_vm_types::on_chain_resource::EpochInfo;
 use structopt::StructOpt;
 
 /// Get stat of epoch_info from chain.
@@ -22,7 +22,7 @@ impl CommandAction for StatEpochCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = StatEpochOpt;
-    type ReturnItem = Vec<EpochInfo>;
+    type ReturnItem = Vec<EpochInfoView>;
 
     fn run(
         &self,
@@ -49,16 +49,16 @@ impl CommandAction for StatEpochCommand {
                 block_number = end_number;
             }
             let epoch = client.get_epoch_info_by_number(block_number)?;
-            println!(
+            scmd::side_println!(
                 "epoch: {:?} {:?} {:?} {:?} {:?} {:?} {:?} {:?}",
-                epoch.number(),
-                epoch.block_time_target(),
-                epoch.total_reward(),
-                epoch.reward_per_block(),
-                epoch.reward_per_uncle_percent(),
-                epoch.epoch_data().uncles(),
-                epoch.epoch_data().total_gas(),
-                epoch.start_time(),
+                epoch.number.0,
+                epoch.block_time_target.0,
+                epoch.total_reward.0,
+                epoch.reward_per_block.0,
+                epoch.reward_per_uncle_percent.0,
+                epoch.uncles.0,
+                epoch.total_gas.0,
+                epoch.start_time.0,
             );
             // vec_epoch.push(epoch);
             epoch_number += 1;
@@ -5,7 +5,7 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::{format_err, Result};
 use scmd::{CommandAction, ExecContext};
-use starcoin_rpc_api::types::ChainInfoView;
+use starcoin_rpc_api::types::ChainStatusView;
 use starcoin_rpc_client::RpcClient;
 use starcoin_types::block::BlockNumber;
 use starcoin_types::stress_test::TPS;
@@ -52,14 +52,14 @@ impl CommandAction for TPSCommand {
 impl TPSCommand {
     pub fn epoch_tps(
         client: &RpcClient,
-        chain_info: ChainInfoView,
+        chain_info: ChainStatusView,
         current_number: u64,
         block_number: u64,
     ) -> Result<TPS> {
         let epoch_info = client.get_epoch_info_by_number(block_number)?;
-        let start_time = epoch_info.start_time();
-        let start_block_number = epoch_info.start_block_number();
-        let end_block_number = epoch_info.end_block_number();
+        let start_time = epoch_info.start_time.0;
+        let start_block_number = epoch_info.start_block_number.0;
+        let end_block_number = epoch_info.end_block_number.0;
 
         let start_block_info = client
             .chain_get_block_info_by_number(start_block_number)?
@@ -70,12 +70,12 @@ impl TPSCommand {
             let end_time = chain_info.head.timestamp.0;
             let duration = (end_time - start_time) / 1000;
 
-            let end_leaves = chain_info.block_info.txn_accumulator_info.num_leaves;
+            let end_leaves = chain_info.block_info.txn_accumulator_info.num_leaves.0;
             let total_count = end_leaves - start_leaves;
             TPS::new(total_count, duration, total_count / duration)
         } else {
             let next_epoch = client.get_epoch_info_by_number(end_block_number)?;
-            let end_time = next_epoch.start_time();
+            let end_time = next_epoch.start_time.0;
             let duration = (end_time - start_time) / 1000;
 
             // count txn
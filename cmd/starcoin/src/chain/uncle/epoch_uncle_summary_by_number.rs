@@ -2,7 +2,8 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
-use starcoin_rpc_api::types::EpochUncleSummaryView;
+use serde::Serialize;
+use starcoin_rpc_api::types::{EpochInfoView, EpochUncleSummaryView};
 use structopt::StructOpt;
 
 /// Show uncle summary in a epoch.
@@ -15,18 +16,30 @@ pub struct EpochUncleSummaryByNumberOpt {
 
 pub struct EpochUncleSummaryByNumberCommand;
 
+#[derive(Debug, Clone, Serialize)]
+pub struct EpochUncleSummaryWithInfo {
+    #[serde(flatten)]
+    pub summary: EpochUncleSummaryView,
+    /// the epoch of the summary.
+    pub epoch_info: EpochInfoView,
+}
+
 impl CommandAction for EpochUncleSummaryByNumberCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = EpochUncleSummaryByNumberOpt;
-    type ReturnItem = EpochUncleSummaryView;
+    type ReturnItem = EpochUncleSummaryWithInfo;
 
     fn run(
         &self,
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<Self::ReturnItem> {
-        ctx.state()
-            .client()
-            .epoch_uncle_summary_by_number(ctx.opt().number)
+        let client = ctx.state().client();
+        let summary = client.epoch_uncle_summary_by_number(ctx.opt().number)?;
+        let epoch_info = client.get_epoch_info_by_number(ctx.opt().number)?;
+        Ok(EpochUncleSummaryWithInfo {
+            summary,
+            epoch_info,
+        })
     }
 }
@@ -37,10 +37,13 @@ impl CommandAction for ListEpochUnclesByNumberCommand {
         });
 
         let mut header_map = HashMap::new();
-        let parent_headers = client.get_headers(ids)?;
-        parent_headers.into_iter().for_each(|header| {
-            header_map.insert(header.block_hash, header);
-        });
+        // the node returns at most 256 headers in one call.
+        for chunk in ids.chunks(256) {
+            let parent_headers = client.get_headers(chunk.to_vec())?;
+            parent_headers.into_iter().for_each(|header| {
+                header_map.insert(header.block_hash, header);
+            });
+        }
 
         let mut views = Vec::new();
         block_summaries.into_iter().for_each(|block_summary| {
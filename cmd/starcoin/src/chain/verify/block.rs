@@ -40,20 +40,21 @@ impl CommandAction for VerifyBlockCommand {
         // time_target
         let epoch_info = client.get_epoch_info_by_number(block_number)?;
         let head = client.chain_info()?;
-        let start = epoch_info.start_block_number();
-        let last_epoch_time_target = if epoch_info.number() > 0 {
+        let start = epoch_info.start_block_number.0;
+        let last_epoch_time_target = if epoch_info.number.0 > 0 {
             client
                 .get_epoch_info_by_number(start - 1)?
-                .block_time_target()
+                .block_time_target
+                .0
         } else {
-            epoch_info.block_time_target()
+            epoch_info.block_time_target.0
         };
 
-        let end = min(epoch_info.end_block_number(), head.head.number.0);
-        let block_time_target = epoch_info.block_time_target() * 6;
+        let end = min(epoch_info.end_block_number.0, head.head.number.0);
+        let block_time_target = epoch_info.block_time_target.0 * 6;
 
         let mut block_map = HashMap::new();
-        let difficulty_window = epoch_info.block_difficulty_window();
+        let difficulty_window = epoch_info.block_difficulty_window.0;
         let load_start = if start > difficulty_window {
             start - difficulty_window
         } else {
@@ -99,19 +100,19 @@ impl CommandAction for VerifyBlockCommand {
             let block = block_map.get(&i).unwrap();
             block_diff_vec.push_front(BlockDiffInfo::new(
                 block.header.timestamp.0,
-                difficult_to_target(block.header.difficulty),
+                difficult_to_target(block.header.difficulty.0),
             ));
         }
         if !block_diff_vec.is_empty() {
             let time_plan = if index == start {
                 last_epoch_time_target
             } else {
-                epoch_info.block_time_target()
+                epoch_info.block_time_target.0
             };
             let target = get_next_target_helper(Vec::from(block_diff_vec), time_plan).unwrap();
             let block = block_map.get(&index).unwrap();
             let difficulty = target_to_difficulty(target);
-            assert_eq!(block.header.difficulty, difficulty);
+            assert_eq!(block.header.difficulty.0, difficulty);
             info!("difficulty verify ok: {:?}", index);
         } else {
             warn!("index err: {:?}, start: {}, end {:}", index, start, end);
@@ -38,20 +38,20 @@ impl CommandAction for VerifyEpochCommand {
         let block_number = opt.block_number;
 
         let epoch_info = client.get_epoch_info_by_number(block_number)?;
-        let start = epoch_info.start_block_number();
+        let start = epoch_info.start_block_number.0;
         let block_number1 = if block_number >= 240 {
             block_number + 1
         } else {
             block_number
         };
-        let end = min(epoch_info.end_block_number(), block_number1);
+        let end = min(epoch_info.end_block_number.0, block_number1);
         let mut block_map = HashMap::new();
         //check uncles
-        let uncles = epoch_info.uncles();
+        let uncles = epoch_info.uncles.0;
         let mut total_uncle = 0u64;
         for number in start..end {
             let block = client
-                .chain_get_block_by_number(number)?
+                .chain_get_block_by_number(number, None)?
                 .ok_or_else(|| format_err!("block: {} not found", number))?;
             block_map.insert(number, block.clone());
             total_uncle += block.uncles.len() as u64;
@@ -59,10 +59,10 @@ impl CommandAction for VerifyEpochCommand {
         assert_eq!(uncles, total_uncle);
         info!("verify uncle ok!");
         //block reward
-        let total_reward = epoch_info.total_reward();
+        let total_reward = epoch_info.total_reward.0;
         let mut block_total_reward = 0u128;
-        let reward_per_block = epoch_info.reward_per_block();
-        let reward_per_uncle_percent = epoch_info.reward_per_uncle_percent();
+        let reward_per_block = epoch_info.reward_per_block.0;
+        let reward_per_uncle_percent = epoch_info.reward_per_uncle_percent.0;
 
         for number in start..end {
             let block = block_map.get(&number).unwrap();
@@ -80,18 +80,18 @@ impl CommandAction for VerifyEpochCommand {
         if block_number >= 240 {
             let last_number = block_number - 240;
             let last_epoch_info = client.get_epoch_info_by_number(last_number)?;
-            let last_time_target = last_epoch_info.block_time_target();
-            let blocks = last_epoch_info.end_block_number() - last_epoch_info.start_block_number();
-            let uncles_rate = last_epoch_info.uncles() * 1000 / blocks;
+            let last_time_target = last_epoch_info.block_time_target.0;
+            let blocks = last_epoch_info.end_block_number.0 - last_epoch_info.start_block_number.0;
+            let uncles_rate = last_epoch_info.uncles.0 * 1000 / blocks;
             let total_time = client
-                .chain_get_block_by_number(last_epoch_info.end_block_number())?
+                .chain_get_block_by_number(last_epoch_info.end_block_number.0, None)?
                 .ok_or_else(|| {
-                    format_err!("block: {} not found", last_epoch_info.end_block_number())
+                    format_err!("block: {} not found", last_epoch_info.end_block_number.0)
                 })?
                 .header
                 .timestamp
                 .0
-                - last_epoch_info.start_time();
+                - last_epoch_info.start_time.0;
             let avg_block_time = total_time / blocks;
             let chain_state_reader = RemoteStateReader::new(client)?;
             let account_state_reader = AccountStateReader::new(&chain_state_reader);
@@ -116,7 +116,7 @@ impl CommandAction for VerifyEpochCommand {
             if time_target > max_block_time_target {
                 time_target = max_block_time_target;
             };
-            assert_eq!(time_target, epoch_info.block_time_target());
+            assert_eq!(time_target, epoch_info.block_time_target.0);
             info!("verify time_target increase ok!");
         } else {
             warn!("current epoch not exist last epoch!");
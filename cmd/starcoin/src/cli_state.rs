@@ -5,17 +5,42 @@ use starcoin_account_api::AccountInfo;
 use starcoin_config::{ChainNetworkID, DataDirPath};
 use starcoin_crypto::HashValue;
 use starcoin_node::NodeHandle;
+use starcoin_rpc_api::errors::{AccountErrorData, RpcErrorCode};
 use starcoin_rpc_api::types::TransactionInfoView;
-use starcoin_rpc_client::chain_watcher::ThinHeadBlock;
+use starcoin_rpc_client::chain_watcher::{ThinHeadBlock, WatchTxnTimeout};
 use starcoin_rpc_client::RpcClient;
 use starcoin_types::account_address::AccountAddress;
+use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
 use starcoin_vm_types::account_config::association_address;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 
 static HISTORY_FILE_NAME: &str = "history";
 
+/// The `--gas-price` of the txn commands, a number or `auto`,
+/// `auto` uses the p50 suggested by the gas price oracle of the node.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GasPriceArg {
+    Auto,
+    Fixed(u64),
+}
+
+impl FromStr for GasPriceArg {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.eq_ignore_ascii_case("auto") {
+            Ok(Self::Auto)
+        } else {
+            s.parse()
+                .map(Self::Fixed)
+                .map_err(|e| format_err!("Invalid gas price {}, expect a number or auto: {}", s, e))
+        }
+    }
+}
+
 pub struct CliState {
     net: ChainNetworkID,
     client: Arc<RpcClient>,
@@ -28,6 +53,8 @@ pub struct CliState {
 
 impl CliState {
     pub const DEFAULT_WATCH_TIMEOUT: Duration = Duration::from_secs(300);
+    /// How long the account is unlocked by the password prompted when signing.
+    pub const PROMPT_UNLOCK_DURATION: Duration = Duration::from_secs(300);
     pub fn new(
         net: ChainNetworkID,
         client: Arc<RpcClient>,
@@ -67,6 +94,10 @@ impl CliState {
         &self.client
     }
 
+    pub fn shared_client(&self) -> Arc<RpcClient> {
+        self.client.clone()
+    }
+
     pub fn temp_dir(&self) -> &Path {
         self.temp_dir.path()
     }
@@ -103,6 +134,83 @@ impl CliState {
         }
     }
 
+    /// Sign the txn by the wallet of the node, if the signer account is locked or its unlock is expired,
+    /// prompt for the password interactively, unlock the account and sign again.
+    pub fn sign_txn(&self, raw_txn: RawUserTransaction) -> Result<SignedUserTransaction> {
+        match self.client.account_sign_txn(raw_txn.clone()) {
+            Ok(signed_txn) => Ok(signed_txn),
+            Err(e) => {
+                let locked_address = e
+                    .downcast_ref::<jsonrpc_core::Error>()
+                    .filter(|rpc_err| {
+                        RpcErrorCode::of(rpc_err) == Some(RpcErrorCode::AccountLocked)
+                    })
+                    .and_then(|rpc_err| rpc_err.data.clone())
+                    .and_then(|data| serde_json::from_value::<AccountErrorData>(data).ok())
+                    .map(|data| data.address);
+                let address = match locked_address {
+                    Some(address) => address,
+                    None => return Err(e),
+                };
+                let password = rpassword::read_password_from_tty(Some(
+                    format!("Account {} is locked, please input the password: ", address).as_str(),
+                ))
+                .map_err(|prompt_err| {
+                    format_err!("{}, and fail to prompt for the password: {}", e, prompt_err)
+                })?;
+                self.client
+                    .account_unlock(address, password, Self::PROMPT_UNLOCK_DURATION)?;
+                self.client.account_sign_txn(raw_txn)
+            }
+        }
+    }
+
+    /// Resolve the gas price of the txn, query the gas price oracle of the node if it is `auto`.
+    pub fn gas_price(&self, gas_price: GasPriceArg) -> Result<u64> {
+        match gas_price {
+            GasPriceArg::Fixed(gas_price) => Ok(gas_price),
+            GasPriceArg::Auto => {
+                let oracle = self.client.txpool_gas_price_oracle()?;
+                scmd::side_println!(
+                    "Use the suggested gas price {} of the last {} blocks.",
+                    oracle.percentiles.p50.0,
+                    oracle.last_n_blocks
+                );
+                Ok(oracle.percentiles.p50.0)
+            }
+        }
+    }
+
+    /// Get the gas price to replace the pending txn of `sender` with same `sequence_number`.
+    /// The gas price of the pending txn is bumped by the bump percent of the node's txpool,
+    /// and `gas_price` is used if it is higher or there is no such pending txn.
+    pub fn replace_gas_price(
+        &self,
+        sender: AccountAddress,
+        sequence_number: u64,
+        gas_price: u64,
+    ) -> Result<u64> {
+        let pending_gas_price = self
+            .client
+            .get_pending_txns_of_sender(sender, None)?
+            .into_iter()
+            .find(|txn| txn.raw_txn.sequence_number.0 == sequence_number)
+            .map(|txn| txn.raw_txn.gas_unit_price.0);
+        Ok(match pending_gas_price {
+            Some(pending_gas_price) => {
+                let bump_percent = self
+                    .client
+                    .txpool_detailed_status()?
+                    .gas_price_bump_percent
+                    .0;
+                let bump = (pending_gas_price as u128 * bump_percent as u128 + 99) / 100;
+                let min_gas_price = pending_gas_price.saturating_add(bump as u64);
+                min_gas_price.max(gas_price)
+            }
+            None => gas_price,
+        })
+    }
+
     pub fn association_account(&self) -> Result<Option<AccountInfo>> {
         self.client.account_get(association_address())
     }
@@ -112,15 +220,37 @@ impl CliState {
         txn_hash: HashValue,
     ) -> Result<(ThinHeadBlock, Option<TransactionInfoView>)> {
         let block = self.client.watch_txn(txn_hash, Some(self.watch_timeout))?;
+        self.get_mined_txn_info(txn_hash, block)
+    }
+
+    /// Watch the txn until it is mined, return None if it is not mined within `timeout`.
+    pub fn watch_txn_with_timeout(
+        &self,
+        txn_hash: HashValue,
+        timeout: Duration,
+    ) -> Result<Option<(ThinHeadBlock, Option<TransactionInfoView>)>> {
+        match self.client.watch_txn(txn_hash, Some(timeout)) {
+            Ok(block) => self.get_mined_txn_info(txn_hash, block).map(Some),
+            Err(e) if e.is::<WatchTxnTimeout>() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
 
+    fn get_mined_txn_info(
+        &self,
+        txn_hash: HashValue,
+        block: ThinHeadBlock,
+    ) -> Result<(ThinHeadBlock, Option<TransactionInfoView>)> {
         let mut txn_info = self.client.chain_get_transaction_info(txn_hash)?;
         std::thread::sleep(Duration::from_secs(1));
         if txn_info.is_none() {
             txn_info = self.client.chain_get_transaction_info(txn_hash)?;
         }
-        println!(
+        scmd::side_println!(
             "txn mined in block height: {}, hash: {:#x}, txn info: {:?}",
-            block.header.number, block.header.block_hash, txn_info
+            block.header.number,
+            block.header.block_hash,
+            txn_info
         );
 
         Ok((block, txn_info))
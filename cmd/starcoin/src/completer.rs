@@ -0,0 +1,162 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use scmd::ConsoleCompleter;
+use starcoin_logger::prelude::*;
+use starcoin_rpc_client::RpcClient;
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::identifier::Identifier;
+use starcoin_vm_types::language_storage::ModuleId;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::mpsc::channel;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How long the completion waits for the rpc. The completion is skipped if the rpc is slower,
+/// and the result is cached once it is ready, so the next completion can use it.
+pub const COMPLETION_TIMEOUT: Duration = Duration::from_millis(200);
+const LIST_CODE_PAGE_SIZE: u32 = 100;
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+enum CompletionKey {
+    Addresses,
+    Modules(AccountAddress),
+    Functions(ModuleId),
+}
+
+/// Complete the addresses of the local accounts and the script function ids in console,
+/// the candidates are queried by rpc lazily and cached in the console session.
+pub struct StarcoinCompleter {
+    client: Arc<RpcClient>,
+    /// The candidates of each key, None if they are being loaded.
+    cache: Arc<Mutex<HashMap<CompletionKey, Option<Vec<String>>>>>,
+}
+
+impl StarcoinCompleter {
+    pub fn new(client: Arc<RpcClient>) -> Self {
+        Self {
+            client,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn candidates(&self, key: CompletionKey) -> Option<Vec<String>> {
+        {
+            let mut cache = self.cache.lock().expect("lock completion cache");
+            match cache.get(&key) {
+                Some(candidates) => return candidates.clone(),
+                None => cache.insert(key.clone(), None),
+            };
+        }
+        let (sender, receiver) = channel();
+        let client = self.client.clone();
+        let cache = self.cache.clone();
+        std::thread::spawn(move || {
+            let result = load_candidates(client.as_ref(), &key);
+            let mut cache = cache.lock().expect("lock completion cache");
+            match result {
+                Ok(candidates) => {
+                    cache.insert(key, Some(candidates.clone()));
+                    let _ = sender.send(candidates);
+                }
+                Err(e) => {
+                    debug!("Load completion candidates of {:?} error: {:?}", key, e);
+                    // retry on the next completion.
+                    cache.remove(&key);
+                }
+            }
+        });
+        receiver.recv_timeout(COMPLETION_TIMEOUT).ok()
+    }
+
+    fn complete_word(&self, word: &str) -> Option<Vec<String>> {
+        let parts: Vec<&str> = word.split("::").collect();
+        match parts.as_slice() {
+            [address] if address.starts_with("0x") => {
+                let prefix = address.to_lowercase();
+                Some(
+                    self.candidates(CompletionKey::Addresses)?
+                        .into_iter()
+                        .filter(|address| address.starts_with(prefix.as_str()))
+                        .collect(),
+                )
+            }
+            [address, module] => {
+                let address = AccountAddress::from_str(address).ok()?;
+                Some(
+                    self.candidates(CompletionKey::Modules(address))?
+                        .into_iter()
+                        .filter(|name| name.starts_with(module))
+                        .map(|name| format!("{}::{}::", parts[0], name))
+                        .collect(),
+                )
+            }
+            [address, module, function] => {
+                let module_id = ModuleId::new(
+                    AccountAddress::from_str(address).ok()?,
+                    Identifier::new(*module).ok()?,
+                );
+                Some(
+                    self.candidates(CompletionKey::Functions(module_id))?
+                        .into_iter()
+                        .filter(|name| name.starts_with(function))
+                        .map(|name| format!("{}::{}::{}", parts[0], parts[1], name))
+                        .collect(),
+                )
+            }
+            _ => None,
+        }
+    }
+}
+
+impl ConsoleCompleter for StarcoinCompleter {
+    fn complete(&self, _words: &[&str], word: &str) -> Vec<String> {
+        self.complete_word(word).unwrap_or_default()
+    }
+}
+
+fn load_candidates(client: &RpcClient, key: &CompletionKey) -> Result<Vec<String>> {
+    Ok(match key {
+        CompletionKey::Addresses => client
+            .account_list()?
+            .into_iter()
+            .map(|account| format!("{:#x}", account.address))
+            .collect(),
+        CompletionKey::Modules(address) => {
+            let mut modules = vec![];
+            let mut cursor = None;
+            let mut state_root = None;
+            loop {
+                let page = match client.state_list_code(
+                    *address,
+                    cursor,
+                    LIST_CODE_PAGE_SIZE,
+                    state_root,
+                )? {
+                    Some(page) => page,
+                    None => break,
+                };
+                modules.extend(page.codes.keys().map(|name| name.to_string()));
+                match page.next_cursor {
+                    Some(next_cursor) => {
+                        cursor = Some(next_cursor);
+                        state_root = Some(page.state_root);
+                    }
+                    None => break,
+                }
+            }
+            modules
+        }
+        CompletionKey::Functions(module_id) => client
+            .contract_get_module_abi(module_id.clone())?
+            .map(|abi| {
+                abi.script_functions
+                    .into_iter()
+                    .map(|function| function.name)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    })
+}
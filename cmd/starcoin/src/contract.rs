@@ -6,7 +6,7 @@ use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
 use serde::{Serialize, Serializer};
-use starcoin_rpc_api::types::{AnnotatedMoveStructView, StrView};
+use starcoin_rpc_api::types::{AnnotatedMoveStructView, CodeView, StrView};
 use starcoin_vm_types::account_address::AccountAddress;
 use starcoin_vm_types::language_storage::{ModuleId, StructTag};
 use structopt::StructOpt;
@@ -15,6 +15,7 @@ use structopt::StructOpt;
 ///  Some examples:
 ///  ``` shell
 ///  contract get code 0x1::Account
+///  contract get code 0x1::Account --disassemble
 ///  contract get resource 0x1 0x1::Account::Account
 ///  ```
 #[derive(Debug, StructOpt)]
@@ -23,6 +24,11 @@ pub enum GetContractDataOpt {
     Code {
         #[structopt(help = "module id like: 0x1::Account")]
         module_id: StrView<ModuleId>,
+        #[structopt(
+            long = "disassemble",
+            help = "also output the disassembly of the module"
+        )]
+        disassemble: bool,
     },
     Resource {
         #[structopt(help = "account address")]
@@ -38,7 +44,7 @@ pub enum GetContractDataOpt {
 pub struct GetContractDataCommand;
 
 pub enum GetContractDataResult {
-    Code(Option<String>),
+    Code(Option<CodeView>),
     Resource(Option<AnnotatedMoveStructView>),
 }
 impl Serialize for GetContractDataResult {
@@ -65,9 +71,14 @@ impl CommandAction for GetContractDataCommand {
     ) -> Result<Self::ReturnItem> {
         let opt = ctx.opt();
         let result = match opt {
-            GetContractDataOpt::Code { module_id } => {
-                GetContractDataResult::Code(ctx.state().client().get_code(module_id.0.clone())?)
-            }
+            GetContractDataOpt::Code {
+                module_id,
+                disassemble,
+            } => GetContractDataResult::Code(
+                ctx.state()
+                    .client()
+                    .get_code(module_id.0.clone(), *disassemble)?,
+            ),
             GetContractDataOpt::Resource {
                 address,
                 resource_type,
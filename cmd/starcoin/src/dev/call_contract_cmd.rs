@@ -6,7 +6,7 @@ use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
 use starcoin_rpc_api::types::{
-    AnnotatedMoveValueView, ContractCall, FunctionIdView, TransactionArgumentView, TypeTagView,
+    parse_argument_literal, AnnotatedMoveValueView, ContractCall, FunctionIdView, TypeTagView,
 };
 use structopt::StructOpt;
 
@@ -17,6 +17,8 @@ use structopt::StructOpt;
 ///  dev call --function 0x1::Block::current_block_number
 ///  # 0x1::Account::balance<0x1::STC::STC>(0x726098b70ba8aa2cc172af19af8804)
 ///  dev call --function 0x1::Account::balance -t 0x1::STC::STC --arg 0x726098b70ba8aa2cc172af19af8804
+///  # vector args are passed as json arrays, and encoded by the on-chain function signature.
+///  dev call --function 0x1::Foo::bar --arg '["0x1","0x2"]' --arg '[[1,2],[]]'
 ///  ```
 #[derive(Debug, StructOpt)]
 #[structopt(name = "call")]
@@ -35,9 +37,10 @@ pub struct CallContractOpt {
     #[structopt(
         long = "arg",
         name = "transaction-args",
-        help = "can specify multi arg"
+        help = "can specify multi arg",
+        parse(try_from_str = parse_argument_literal)
     )]
-    args: Option<Vec<TransactionArgumentView>>,
+    args: Option<Vec<serde_json::Value>>,
 }
 
 pub struct CallContractCommand;
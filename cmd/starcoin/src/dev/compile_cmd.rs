@@ -7,14 +7,18 @@ use crate::StarcoinOpt;
 use anyhow::{bail, Result};
 use scmd::{CommandAction, ExecContext};
 use starcoin_config::temp_path;
-use starcoin_move_compiler::{compile_source_string_no_report, errors};
+use starcoin_move_compiler::{compile_source_string_no_report, errors, CompiledUnit};
+use starcoin_rpc_api::types::ModuleAbiView;
 use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::file_format::FunctionDefinitionIndex;
 use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use stdlib::restore_stdlib_in_dir;
 use structopt::StructOpt;
 
+const ABI_EXTENSION: &str = "abi.json";
+
 #[derive(Debug, StructOpt)]
 #[structopt(name = "compile")]
 pub struct CompileOpt {
@@ -37,6 +41,10 @@ pub struct CompileOpt {
     #[structopt(short = "o", name = "out_dir", help = "out dir", parse(from_os_str))]
     out_dir: Option<PathBuf>,
 
+    /// Write the json ABI of the script functions of every compiled module to the dir.
+    #[structopt(long = "abi-out-dir", parse(from_os_str))]
+    abi_out_dir: Option<PathBuf>,
+
     #[structopt(name = "source", help = "source file path")]
     source_file: String,
 
@@ -105,11 +113,16 @@ impl CommandAction for CompileCommand {
         };
 
         let compile_unit = match compile_result {
-            Ok(mut c) => c
-                .pop()
-                .ok_or_else(|| anyhow::anyhow!("file should at least contain one compile unit"))?,
+            Ok(mut c) => {
+                if let Some(abi_out_dir) = ctx.opt().abi_out_dir.as_ref() {
+                    write_abi_files(&c, abi_out_dir)?;
+                }
+                c.pop().ok_or_else(|| {
+                    anyhow::anyhow!("file should at least contain one compile unit")
+                })?
+            }
             Err(e) => {
-                eprintln!(
+                scmd::side_println!(
                     "{}",
                     String::from_utf8_lossy(
                         errors::report_errors_to_color_buffer(sources, e).as_slice()
@@ -135,3 +148,44 @@ impl CommandAction for CompileCommand {
         })
     }
 }
+
+/// Write the ABI of every compiled module as `<module_name>.abi.json`, scripts are skipped.
+fn write_abi_files(units: &[CompiledUnit], abi_out_dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(abi_out_dir)?;
+    for unit in units {
+        if let Some(abi) = module_abi(unit)? {
+            let mut abi_path = abi_out_dir.to_path_buf();
+            abi_path.push(format!("{}.{}", abi.module_id.0.name(), ABI_EXTENSION));
+            std::fs::write(abi_path, serde_json::to_string_pretty(&abi)?)?;
+        }
+    }
+    Ok(())
+}
+
+/// Build the module ABI with the parameter names in the source map, return None for script unit.
+pub(crate) fn module_abi(unit: &CompiledUnit) -> Result<Option<ModuleAbiView>> {
+    match unit {
+        CompiledUnit::Module {
+            module, source_map, ..
+        } => Ok(Some(ModuleAbiView::new(module, |idx| {
+            source_map
+                .get_function_source_map(FunctionDefinitionIndex(idx as u16))
+                .ok()
+                .map(|function_map| {
+                    (
+                        function_map
+                            .type_parameters
+                            .iter()
+                            .map(|(name, _)| name.clone())
+                            .collect(),
+                        function_map
+                            .parameters
+                            .iter()
+                            .map(|(name, _)| name.clone())
+                            .collect(),
+                    )
+                })
+        })?)),
+        CompiledUnit::Script { .. } => Ok(None),
+    }
+}
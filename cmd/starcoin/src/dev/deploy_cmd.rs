@@ -3,12 +3,17 @@
 
 use crate::cli_state::CliState;
 use crate::StarcoinOpt;
-use anyhow::{bail, Result};
+use anyhow::{bail, format_err, Result};
 use scmd::{CommandAction, ExecContext};
 use starcoin_crypto::hash::HashValue;
+use starcoin_dev::compatibility::check_compatibility;
+use starcoin_rpc_api::types::ModuleAbiView;
 use starcoin_rpc_client::RemoteStateReader;
 use starcoin_state_api::AccountStateReader;
-use starcoin_types::transaction::{Module, RawUserTransaction};
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::language_storage::ModuleId;
+use starcoin_types::transaction::{Module, Package, RawUserTransaction, TransactionPayload};
 use starcoin_vm_types::{access::ModuleAccess, file_format::CompiledModule};
 use std::fs::OpenOptions;
 use std::io::Read;
@@ -48,8 +53,62 @@ pub struct DeployOpt {
     )]
     blocking: bool,
 
-    #[structopt(name = "bytecode_file", help = "module bytecode file path")]
-    bytecode_file: String,
+    #[structopt(long = "force")]
+    /// deploy the module even if it is incompatible with the on-chain module.
+    force: bool,
+
+    #[structopt(long = "print-abi")]
+    /// print the json ABI of the script functions in the module before deploying it.
+    print_abi: bool,
+
+    #[structopt(
+        name = "bytecode_file",
+        required = true,
+        help = "module bytecode file paths, the modules are deployed as one package"
+    )]
+    bytecode_file: Vec<String>,
+}
+
+/// The state of a local module compared with the on-chain module at the same id.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ModulePublishState {
+    /// The module is not published yet.
+    Absent,
+    /// The module is published with the same bytecode.
+    Identical,
+    /// The module is published with the different bytecode, it is an upgrade.
+    Changed,
+}
+
+/// Compare the hash of the local module bytecode with the on-chain code hash,
+/// the module must be published under the sender address.
+pub fn module_publish_state(
+    module_id: &ModuleId,
+    sender: AccountAddress,
+    local_hash: HashValue,
+    onchain_hash: Option<HashValue>,
+) -> Result<ModulePublishState> {
+    if *module_id.address() != sender {
+        match onchain_hash {
+            Some(_) => bail!(
+                "module {} is already published under address {}, not the sender address {}, \
+                 is the module name occupied at the sender address? compile the module with the sender address.",
+                module_id,
+                module_id.address(),
+                sender
+            ),
+            None => bail!(
+                "the address of module {} is not the sender address {}, all modules of a package must have the same address.",
+                module_id,
+                sender
+            ),
+        }
+    }
+    Ok(match onchain_hash {
+        None => ModulePublishState::Absent,
+        Some(hash) if hash == local_hash => ModulePublishState::Identical,
+        Some(_) => ModulePublishState::Changed,
+    })
 }
 
 pub struct DeployCommand;
@@ -58,28 +117,75 @@ impl CommandAction for DeployCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = DeployOpt;
-    type ReturnItem = HashValue;
+    type ReturnItem = Option<HashValue>;
 
     fn run(
         &self,
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<Self::ReturnItem> {
         let opt = ctx.opt();
-        let bytecode_path = ctx.opt().bytecode_file.clone();
-        let mut file = OpenOptions::new()
-            .read(true)
-            .write(false)
-            .open(bytecode_path)?;
-        let mut bytecode = vec![];
-        file.read_to_end(&mut bytecode)?;
-        let compiled_module = match CompiledModule::deserialize(bytecode.as_slice()) {
-            Err(e) => {
-                bail!("invalid bytecode file, cannot deserialize as module, {}", e);
+        let mut modules = vec![];
+        for bytecode_path in &opt.bytecode_file {
+            let mut file = OpenOptions::new()
+                .read(true)
+                .write(false)
+                .open(bytecode_path)?;
+            let mut bytecode = vec![];
+            file.read_to_end(&mut bytecode)?;
+            let compiled_module = match CompiledModule::deserialize(bytecode.as_slice()) {
+                Err(e) => {
+                    bail!(
+                        "invalid bytecode file {}, cannot deserialize as module, {}",
+                        bytecode_path,
+                        e
+                    );
+                }
+                Ok(compiled_module) => compiled_module,
+            };
+            modules.push((bytecode, compiled_module));
+        }
+        if opt.print_abi {
+            for (_, compiled_module) in &modules {
+                // the parameter names are not kept in bytecode, so they are named by position.
+                let abi = ModuleAbiView::new(compiled_module, |_| None)?;
+                scmd::side_println!("{}", serde_json::to_string_pretty(&abi)?);
             }
-            Ok(compiled_module) => compiled_module,
-        };
-        let module_address = *compiled_module.address();
+        }
+        // the modules are published by the account at the address of the first module.
+        let module_address = *modules[0].1.address();
         let client = ctx.state().client();
+        let mut all_identical = true;
+        for (bytecode, compiled_module) in &modules {
+            let module_id = compiled_module.self_id();
+            let local_hash = HashValue::sha3_256_of(bytecode.as_slice());
+            let onchain_hash = client.contract_module_exists(module_id.clone())?;
+            let state = module_publish_state(&module_id, module_address, local_hash, onchain_hash)?;
+            if state != ModulePublishState::Identical {
+                all_identical = false;
+            }
+            if state != ModulePublishState::Changed {
+                continue;
+            }
+            let old_bytecode = client
+                .state_get(AccessPath::from(&module_id))?
+                .ok_or_else(|| format_err!("on-chain module {:?} not found", module_id))?;
+            let old_module = CompiledModule::deserialize(old_bytecode.as_slice())
+                .map_err(|e| format_err!("invalid on-chain module {:?}, {}", module_id, e))?;
+            let report = check_compatibility(&old_module, compiled_module);
+            if !report.is_compatible() {
+                if opt.force {
+                    scmd::side_println!("{}", report);
+                } else {
+                    bail!("{}use --force to deploy it anyway.", report);
+                }
+            }
+        }
+        if all_identical {
+            scmd::side_println!(
+                "all modules are already published with the identical bytecode, skip deploying."
+            );
+            return Ok(None);
+        }
         let node_info = client.node_info()?;
         let chain_state_reader = RemoteStateReader::new(client)?;
         let account_state_reader = AccountStateReader::new(&chain_state_reader);
@@ -95,26 +201,32 @@ impl CommandAction for DeployCommand {
         let account_resource = account_resource.unwrap();
 
         let expiration_time = opt.expiration_time + node_info.now_seconds;
-        let deploy_txn = RawUserTransaction::new_module(
+        let package = Package::new_with_modules(
+            modules
+                .into_iter()
+                .map(|(bytecode, _)| Module::new(bytecode))
+                .collect(),
+        )?;
+        let deploy_txn = RawUserTransaction::new_with_default_gas_token(
             module_address,
             account_resource.sequence_number(),
-            Module::new(bytecode),
+            TransactionPayload::Package(package),
             opt.max_gas_amount,
             opt.gas_price,
             expiration_time,
             ctx.state().net().chain_id(),
         );
 
-        let signed_txn = client.account_sign_txn(deploy_txn)?;
+        let signed_txn = ctx.state().sign_txn(deploy_txn)?;
         let txn_hash = signed_txn.id();
         client.submit_transaction(signed_txn)?;
 
-        println!("txn {:#x} submitted.", txn_hash);
+        scmd::side_println!("txn {:#x} submitted.", txn_hash);
 
         if opt.blocking {
             ctx.state().watch_txn(txn_hash)?;
         }
 
-        Ok(txn_hash)
+        Ok(Some(txn_hash))
     }
 }
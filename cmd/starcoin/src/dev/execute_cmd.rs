@@ -1,18 +1,18 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::cli_state::CliState;
-use crate::view::{ExecuteResultView, ExecutionOutputView};
+use crate::cli_state::{CliState, GasPriceArg};
+use crate::view::{gas_breakdown_table, ExecuteResultView, ExecutionOutputView};
 use crate::StarcoinOpt;
 use anyhow::{bail, Result};
 use scmd::{CommandAction, ExecContext};
 use starcoin_config::temp_path;
-use starcoin_dev::playground;
+use starcoin_dev::playground::{self, OverlayStateView};
 use starcoin_move_compiler::{
-    compile_source_string_no_report, errors, load_bytecode_file, CompiledUnit, MOVE_EXTENSION,
+    compile_source_files_no_report, errors, load_bytecode_file, CompiledUnit, MOVE_EXTENSION,
 };
 use starcoin_rpc_api::types::{
-    DryRunTransactionRequest, FunctionIdView, StrView, TransactionVMStatus,
+    DryRunTransactionRequest, FunctionIdView, StrView, TransactionOutputView, TransactionVMStatus,
 };
 use starcoin_rpc_client::RemoteStateReader;
 use starcoin_state_api::AccountStateReader;
@@ -21,9 +21,13 @@ use starcoin_types::transaction::{
     ScriptFunction, TransactionArgument, TransactionPayload,
 };
 use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::language_storage::FunctionId;
 use starcoin_vm_types::transaction_argument::convert_txn_args;
+use starcoin_vm_types::write_set::WriteOp;
 use starcoin_vm_types::{language_storage::TypeTag, parser::parse_type_tag};
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Duration;
 use stdlib::restore_stdlib_in_dir;
 use structopt::StructOpt;
 
@@ -67,9 +71,9 @@ pub struct ExecuteOpt {
         long = "gas-price",
         name = "price of gas",
         default_value = "1",
-        help = "gas price used to execute the script"
+        help = "gas price used to execute the script, or `auto` to use the gas price suggested by the node"
     )]
-    gas_price: u64,
+    gas_price: GasPriceArg,
     #[structopt(
         short = "b",
         name = "blocking-mode",
@@ -77,8 +81,12 @@ pub struct ExecuteOpt {
         help = "blocking wait txn mined"
     )]
     blocking: bool,
+    #[structopt(long = "timeout-secs", default_value = "120")]
+    /// how long(in seconds) to wait the txn mined in blocking mode, return the txn hash when timeout.
+    timeout_secs: u64,
     #[structopt(long = "dry-run")]
-    /// dry-run script, only get transaction output, no state change to chain
+    /// dry-run script, only get transaction output, no state change to chain,
+    /// and print the gas used by every instruction and native as a table.
     dry_run: bool,
 
     #[structopt(long = "local")]
@@ -94,12 +102,94 @@ pub struct ExecuteOpt {
         parse(from_os_str),
         required_unless = "script-function"
     )]
-    /// bytecode file or move script source file
-    move_file: Option<PathBuf>,
+    /// bytecode file or move source files, multi move source files are compiled together,
+    /// the modules are deployed as a package with the script function as the init function,
+    /// or the script(at most one) is executed after the modules compiled with it are deployed.
+    move_files: Vec<PathBuf>,
 
     #[structopt(name = "dependency_path", long = "dep")]
     /// path of dependency used to build, only used when using move source file
     deps: Option<Vec<String>>,
+
+    #[structopt(
+        name = "address-mapping",
+        long = "address-mapping",
+        parse(try_from_str = parse_address_mapping)
+    )]
+    /// named address used in move source files, format: Name=0x1, replace the `{{Name}}` placeholder in source.
+    address_mapping: Vec<(String, AccountAddress)>,
+}
+
+fn parse_address_mapping(s: &str) -> Result<(String, AccountAddress)> {
+    let mut parts = s.splitn(2, '=');
+    match (parts.next(), parts.next()) {
+        (Some(name), Some(address)) if !name.is_empty() => {
+            Ok((name.to_string(), AccountAddress::from_hex_literal(address)?))
+        }
+        _ => bail!("invalid address mapping: {}, should be Name=0x1", s),
+    }
+}
+
+/// Build the txn payloads from the compiled `(name, bytecode)` of modules and the script,
+/// or the script function to call, the payloads should be executed in order.
+/// The modules are deployed as a package with the script function as the init function,
+/// a script can not be included in a package, so the modules compiled with it are deployed
+/// by a package txn first, then the script is executed by the next txn.
+pub(crate) fn build_txn_payloads(
+    modules: Vec<(String, Vec<u8>)>,
+    script: Option<Vec<u8>>,
+    script_function_id: Option<FunctionId>,
+    type_tags: Vec<TypeTag>,
+    args: Vec<TransactionArgument>,
+) -> Result<Vec<TransactionPayload>> {
+    Ok(match (script, script_function_id) {
+        (Some(_script), Some(_)) => {
+            bail!("should only provide script function or script file, not both");
+        }
+        (Some(bytecode), None) => {
+            let script = TransactionPayload::Script(Script::new(
+                bytecode,
+                type_tags,
+                convert_txn_args(&args),
+            ));
+            if modules.is_empty() {
+                vec![script]
+            } else {
+                vec![build_package_payload(modules, None)?, script]
+            }
+        }
+        // package deploy
+        (None, function_id) if !modules.is_empty() => {
+            let module_init_script_function = function_id.map(|id| {
+                ScriptFunction::new(id.module, id.function, type_tags, convert_txn_args(&args))
+            });
+            vec![build_package_payload(modules, module_init_script_function)?]
+        }
+        // script function
+        (None, Some(function_id)) => vec![TransactionPayload::ScriptFunction(ScriptFunction::new(
+            function_id.module,
+            function_id.function,
+            type_tags,
+            convert_txn_args(&args),
+        ))],
+        (None, None) => {
+            bail!("no script or module found in move files");
+        }
+    })
+}
+
+fn build_package_payload(
+    modules: Vec<(String, Vec<u8>)>,
+    init_script: Option<ScriptFunction>,
+) -> Result<TransactionPayload> {
+    let package = Package::new(
+        modules
+            .into_iter()
+            .map(|(_, bytecode)| Module::new(bytecode))
+            .collect(),
+        init_script,
+    )?;
+    Ok(TransactionPayload::Package(package))
 }
 
 pub struct ExecuteCommand;
@@ -125,149 +215,186 @@ impl CommandAction for ExecuteCommand {
         let args = opt.args.clone().unwrap_or_default();
 
         let script_function_id = opt.script_function.clone().map(|id| id.0);
-        let bytedata = if let Some(move_file_path) = ctx.opt().move_file.as_ref() {
-            let ext = move_file_path
+        let (modules, script) = if opt.move_files.is_empty() {
+            (vec![], None)
+        } else if opt.move_files.iter().all(|move_file_path| {
+            move_file_path
                 .as_path()
                 .extension()
-                .map(|os_str| os_str.to_str().expect("file extension should is utf8 str"))
-                .unwrap_or_else(|| "");
-            if ext == MOVE_EXTENSION {
-                let temp_path = temp_path();
-                let mut deps = restore_stdlib_in_dir(temp_path.path())?;
-                // add extra deps
-                deps.append(&mut ctx.opt().deps.clone().unwrap_or_default());
-                let (sources, compile_result) = compile_source_string_no_report(
-                    std::fs::read_to_string(move_file_path.as_path())?.as_str(),
-                    &deps,
-                    sender,
-                )?;
-                let mut compile_units = match compile_result {
-                    Ok(c) => c,
-                    Err(e) => {
-                        eprintln!(
-                            "{}",
-                            String::from_utf8_lossy(
-                                errors::report_errors_to_color_buffer(sources, e).as_slice()
-                            )
-                        );
-                        bail!("compile error")
-                    }
-                };
-                let compile_unit = compile_units.pop().ok_or_else(|| {
-                    anyhow::anyhow!("file should at least contain one compile unit")
-                })?;
-                let is_script = match compile_unit {
-                    CompiledUnit::Module { .. } => false,
-                    CompiledUnit::Script { .. } => true,
-                };
-                Some((compile_unit.serialize(), is_script))
-            } else {
-                Some(load_bytecode_file(move_file_path.as_path())?)
+                .and_then(|os_str| os_str.to_str())
+                == Some(MOVE_EXTENSION)
+        }) {
+            let temp_path = temp_path();
+            let mut deps = restore_stdlib_in_dir(temp_path.path())?;
+            // add extra deps
+            deps.append(&mut ctx.opt().deps.clone().unwrap_or_default());
+            let address_mapping: HashMap<String, AccountAddress> =
+                opt.address_mapping.iter().cloned().collect();
+            let (sources, compile_result) = compile_source_files_no_report(
+                opt.move_files.as_slice(),
+                &deps,
+                sender,
+                &address_mapping,
+            )?;
+            let compile_units = match compile_result {
+                Ok(c) => c,
+                Err(e) => {
+                    scmd::side_println!(
+                        "{}",
+                        String::from_utf8_lossy(
+                            errors::report_errors_to_color_buffer(sources, e).as_slice()
+                        )
+                    );
+                    bail!("compile error")
+                }
+            };
+            let (scripts, modules): (Vec<CompiledUnit>, Vec<CompiledUnit>) = compile_units
+                .into_iter()
+                .partition(|unit| matches!(unit, CompiledUnit::Script { .. }));
+            if scripts.len() > 1 {
+                bail!(
+                    "only one script is allowed, but found scripts: {}",
+                    scripts
+                        .iter()
+                        .map(|unit| unit.name())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
             }
+            (
+                modules
+                    .into_iter()
+                    .map(|unit| (unit.name(), unit.serialize()))
+                    .collect(),
+                scripts.into_iter().next().map(|unit| unit.serialize()),
+            )
         } else {
-            None
-        };
-        let txn_payload = match (bytedata, script_function_id) {
-            // package deploy
-            (Some((bytecode, false)), function_id) => {
-                let module_init_script_function = function_id.map(|id| {
-                    ScriptFunction::new(id.module, id.function, type_tags, convert_txn_args(&args))
-                });
-                let package =
-                    Package::new(vec![Module::new(bytecode)], module_init_script_function)?;
-                TransactionPayload::Package(package)
-            }
-            // script
-            (Some((bytecode, true)), None) => {
-                let script = Script::new(bytecode, type_tags, convert_txn_args(&args));
-                TransactionPayload::Script(script)
+            if opt.move_files.len() > 1 {
+                bail!("only one bytecode file is allowed, and can not mix with move source files");
             }
-            (Some((_bytecode, true)), Some(_)) => {
-                bail!("should only provide script function or script file, not both");
-            }
-            // script function
-            (None, Some(function_id)) => {
-                let script_function = ScriptFunction::new(
-                    function_id.module,
-                    function_id.function,
-                    type_tags,
-                    convert_txn_args(&args),
-                );
-                TransactionPayload::ScriptFunction(script_function)
-            }
-            (None, None) => {
-                bail!("this should not happen, bug here!");
+            let bytecode_file = opt.move_files[0].as_path();
+            match load_bytecode_file(bytecode_file)? {
+                (bytecode, true) => (vec![], Some(bytecode)),
+                (bytecode, false) => (vec![(bytecode_file.display().to_string(), bytecode)], None),
             }
         };
-
-        let raw_txn = {
-            let account_resource = {
-                let chain_state_reader = RemoteStateReader::new(client)?;
-                let account_state_reader = AccountStateReader::new(&chain_state_reader);
-                account_state_reader.get_account_resource(&sender)?
-            };
-
-            if account_resource.is_none() {
-                bail!("address {} not exists on chain", &sender);
-            }
-            let account_resource = account_resource.unwrap();
-
-            let expiration_time = {
-                let node_info = client.node_info()?;
-                opt.expiration_time + node_info.now_seconds
-            };
-            RawUserTransaction::new_with_default_gas_token(
-                sender,
-                account_resource.sequence_number(),
-                txn_payload,
-                opt.max_gas_amount,
-                opt.gas_price,
-                expiration_time,
-                ctx.state().net().chain_id(),
-            )
+        let txn_payloads =
+            build_txn_payloads(modules, script, script_function_id, type_tags, args)?;
+        // pin the state root, so the local dry-run reads the same snapshot as the sequence number.
+        let state_root = client.state_get_state_root()?;
+        let account_resource = {
+            let chain_state_reader = RemoteStateReader::new_at_root(client, state_root);
+            let account_state_reader = AccountStateReader::new(&chain_state_reader);
+            account_state_reader.get_account_resource(&sender)?
+        };
+        if account_resource.is_none() {
+            bail!("address {} not exists on chain", &sender);
+        }
+        let account_resource = account_resource.unwrap();
+        let expiration_time = {
+            let node_info = client.node_info()?;
+            opt.expiration_time + node_info.now_seconds
         };
+        let gas_price = ctx.state().gas_price(opt.gas_price)?;
+        let signed_txns = txn_payloads
+            .into_iter()
+            .enumerate()
+            .map(|(i, txn_payload)| {
+                let raw_txn = RawUserTransaction::new_with_default_gas_token(
+                    sender,
+                    account_resource.sequence_number() + i as u64,
+                    txn_payload,
+                    opt.max_gas_amount,
+                    gas_price,
+                    expiration_time,
+                    ctx.state().net().chain_id(),
+                );
+                ctx.state().sign_txn(raw_txn)
+            })
+            .collect::<Result<Vec<_>>>()?;
 
-        let signed_txn = client.account_sign_txn(raw_txn)?;
-        let txn_hash = signed_txn.id();
-        let output = if opt.local_mode {
-            let state_view = RemoteStateReader::new(client)?;
-            playground::dry_run(
-                &state_view,
-                DryRunTransaction {
+        // the txns are pre-run one by one, the later txn sees the state changed by the former,
+        // the remote dry-run can not override the modules, so the txns are pre-run locally.
+        let local_mode = opt.local_mode || signed_txns.len() > 1;
+        let state_view = RemoteStateReader::new_at_root(client, state_root);
+        let mut overrides = vec![];
+        let mut output = None;
+        for signed_txn in &signed_txns {
+            // the gas breakdown is only shown by the dry run.
+            let txn_output: TransactionOutputView = if local_mode {
+                let state_view = OverlayStateView::new(&state_view, overrides.clone());
+                let txn = DryRunTransaction {
                     public_key: signed_txn.authenticator().public_key(),
                     raw_txn: signed_txn.raw_txn().clone(),
-                },
-            )
-            .map(|(_, b)| b.into())?
-        } else {
-            client.dry_run(DryRunTransactionRequest {
-                sender_public_key: Some(StrView(signed_txn.authenticator().public_key())),
-                transaction: signed_txn.raw_txn().clone().into(),
-            })?
-        };
-        match output.status {
-            TransactionVMStatus::Discard { status_code } => {
-                bail!("TransactionStatus is discard: {:?}", status_code)
-            }
-            TransactionVMStatus::Executed => {}
-            s => {
-                bail!("pre-run failed, status: {:?}", s);
+                };
+                let (txn_output, gas_breakdown) = if opt.dry_run {
+                    let (_, txn_output, gas_breakdown) =
+                        playground::dry_run_with_gas_breakdown(&state_view, txn)?;
+                    (txn_output, Some(gas_breakdown))
+                } else {
+                    (playground::dry_run(&state_view, txn)?.1, None)
+                };
+                overrides.extend(txn_output.write_set().iter().map(|(access_path, op)| {
+                    let value = match op {
+                        WriteOp::Value(value) => Some(value.clone()),
+                        WriteOp::Deletion => None,
+                    };
+                    (access_path.clone(), value)
+                }));
+                let mut txn_output: TransactionOutputView = txn_output.into();
+                txn_output.gas_breakdown = gas_breakdown.map(Into::into);
+                txn_output
+            } else {
+                client.dry_run(DryRunTransactionRequest {
+                    sender_public_key: Some(StrView(signed_txn.authenticator().public_key())),
+                    transaction: signed_txn.raw_txn().clone().into(),
+                    state_overrides: vec![],
+                    with_gas_breakdown: opt.dry_run,
+                })?
+            };
+            match &txn_output.status {
+                TransactionVMStatus::Discard { status_code } => {
+                    bail!("TransactionStatus is discard: {:?}", status_code)
+                }
+                TransactionVMStatus::Executed => {}
+                s => {
+                    bail!("pre-run failed, status: {:?}", s);
+                }
             }
+            output = Some(txn_output);
         }
+        let output = output.expect("at least one txn is pre-run");
         if !opt.dry_run {
-            client.submit_transaction(signed_txn)?;
-
-            println!("txn {:#x} submitted.", txn_hash);
+            let mut txn_hash = None;
+            for signed_txn in signed_txns {
+                let hash = signed_txn.id();
+                client.submit_transaction(signed_txn)?;
+                scmd::side_println!("txn {:#x} submitted.", hash);
+                txn_hash = Some(hash);
+            }
+            // watch the last txn, it is mined after the former txns.
+            let txn_hash = txn_hash.expect("at least one txn is submitted");
 
             let mut output_view = ExecutionOutputView::new(txn_hash);
 
             if opt.blocking {
-                let block = ctx.state().watch_txn(txn_hash)?.0;
-                output_view.block_number = Some(block.header.number.0);
-                output_view.block_id = Some(block.header.block_hash);
+                match ctx
+                    .state()
+                    .watch_txn_with_timeout(txn_hash, Duration::from_secs(opt.timeout_secs))?
+                {
+                    Some((block, txn_info)) => output_view.set_mined(&block, txn_info),
+                    None => scmd::side_println!(
+                        "warning: txn {:#x} is not mined within timeout {}s.",
+                        txn_hash,
+                        opt.timeout_secs
+                    ),
+                }
             }
             Ok(ExecuteResultView::Run(output_view))
         } else {
+            if let Some(gas_breakdown) = output.gas_breakdown.as_ref() {
+                scmd::side_println!("{}", gas_breakdown_table(gas_breakdown));
+            }
             Ok(ExecuteResultView::DryRun(output.into()))
         }
     }
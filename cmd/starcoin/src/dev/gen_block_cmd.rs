@@ -0,0 +1,35 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_crypto::HashValue;
+use structopt::StructOpt;
+
+/// Generate blocks with the txns in the txpool, only for the dev chain.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "gen-block")]
+pub struct GenBlockOpt {
+    #[structopt(short = "c", long = "count", default_value = "1")]
+    /// the number of blocks to generate, at most 100.
+    count: u64,
+}
+
+pub struct GenBlockCommand;
+
+impl CommandAction for GenBlockCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = GenBlockOpt;
+    type ReturnItem = Vec<HashValue>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.dev_generate_block(ctx.opt().count)
+    }
+}
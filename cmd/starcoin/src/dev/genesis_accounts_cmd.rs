@@ -0,0 +1,107 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{ensure, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::ed25519::Ed25519PublicKey;
+use starcoin_crypto::keygen::KeyGen;
+use starcoin_crypto::ValidCryptoMaterialStringExt;
+use starcoin_rpc_api::types::ReceiptIdentifierView;
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::transaction::authenticator::AuthenticationKey;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Generate the accounts to be funded in the genesis block of a custom network,
+/// reference the output file by `genesis_accounts_file` of the genesis config.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "genesis-accounts")]
+pub struct GenesisAccountsOpt {
+    #[structopt(short = "n", long = "count")]
+    /// the count of the accounts to generate.
+    count: usize,
+
+    #[structopt(long = "balance")]
+    /// the STC balance in nanoSTC of each account.
+    balance: u128,
+
+    #[structopt(short = "o", long = "output", parse(from_os_str))]
+    /// the json file to write the accounts, it should not exist.
+    output: PathBuf,
+}
+
+pub struct GenesisAccountsCommand;
+
+impl CommandAction for GenesisAccountsCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = GenesisAccountsOpt;
+    type ReturnItem = GenesisAccountsView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        ensure!(
+            !opt.output.exists(),
+            "The output file {:?} already exists",
+            opt.output
+        );
+        let accounts = generate_genesis_accounts(opt.count, opt.balance)?;
+        let mut file = File::create(&opt.output)?;
+        file.write_all(serde_json::to_string_pretty(&accounts)?.as_bytes())?;
+        Ok(GenesisAccountsView {
+            output: opt.output.clone(),
+            addresses: accounts.iter().map(|account| account.address).collect(),
+            total_balance: opt.balance * opt.count as u128,
+        })
+    }
+}
+
+/// Generate `count` accounts with new key pairs, each account has the `balance`.
+pub fn generate_genesis_accounts(count: usize, balance: u128) -> Result<Vec<GeneratedAccount>> {
+    ensure!(count > 0, "The count of accounts should be greater than 0");
+    ensure!(
+        balance.checked_mul(count as u128).is_some(),
+        "The total balance of the accounts overflow"
+    );
+    let mut key_gen = KeyGen::from_os_rng();
+    (0..count)
+        .map(|_| {
+            let (private_key, public_key) = key_gen.generate_keypair();
+            let auth_key = AuthenticationKey::ed25519(&public_key);
+            let address = auth_key.derived_address();
+            Ok(GeneratedAccount {
+                address,
+                public_key,
+                private_key: private_key.to_encoded_string()?,
+                receipt_identifier: ReceiptIdentifierView::new(address, Some(auth_key))?,
+                balance,
+            })
+        })
+        .collect()
+}
+
+/// The generated account, it can be loaded as the genesis account, the private key is ignored by the genesis.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedAccount {
+    pub address: AccountAddress,
+    pub public_key: Ed25519PublicKey,
+    /// hex encoded private key, it can be imported by `account import`.
+    pub private_key: String,
+    pub receipt_identifier: ReceiptIdentifierView,
+    pub balance: u128,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenesisAccountsView {
+    pub output: PathBuf,
+    pub addresses: Vec<AccountAddress>,
+    pub total_balance: u128,
+}
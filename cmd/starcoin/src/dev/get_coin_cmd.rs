@@ -5,23 +5,64 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::{bail, format_err, Result};
 use scmd::{CommandAction, ExecContext};
+use starcoin_config::temp_path;
 use starcoin_executor::{DEFAULT_EXPIRATION_TIME, DEFAULT_MAX_GAS_AMOUNT};
+use starcoin_move_compiler::{compile_source_string_no_report, errors, CompiledUnit};
 use starcoin_rpc_api::types::SignedUserTransactionView;
-use starcoin_rpc_client::RemoteStateReader;
+use starcoin_rpc_client::{RemoteStateReader, RpcClient};
 use starcoin_state_api::AccountStateReader;
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config;
+use starcoin_types::transaction::authenticator::AuthenticationKey;
+use starcoin_types::transaction::{RawUserTransaction, Script, TransactionArgument};
+use starcoin_vm_types::identifier::Identifier;
+use starcoin_vm_types::language_storage::{StructTag, TypeTag, CORE_CODE_ADDRESS};
+use starcoin_vm_types::token::stc::STC_TOKEN_CODE;
+use starcoin_vm_types::token::token_code::TokenCode;
 use std::convert::TryInto;
+use std::path::PathBuf;
+use std::str::FromStr;
+use stdlib::restore_stdlib_in_dir;
 use structopt::StructOpt;
 use tokio::time::Duration;
 
-/// Get coin to default account.
+const MINT_SCRIPT: &str = r#"
+script {
+    use 0x1::Account;
+    use 0x1::Token;
+
+    fun main<TokenType: store>(account: signer, amount: u128) {
+        let tokens = Token::mint<TokenType>(&account, amount);
+        Account::deposit_to_self<TokenType>(&account, tokens);
+    }
+}
+"#;
+
+/// Get coin to default account, or to the given receivers.
+/// If the association account holds the token's mint capability, the coins are minted first,
+/// otherwise they are transferred from the association account's balance.
 /// This command only available in dev network.
 #[derive(Debug, StructOpt, Default)]
-#[structopt(name = "get_coin")]
+#[structopt(name = "get-coin", alias = "get_coin")]
 pub struct GetCoinOpt {
-    #[structopt(short = "v")]
-    /// if amount absent, transfer 20% of association_address's balance.
+    #[structopt(short = "v", long = "amount")]
+    /// amount of token for each receiver, in token units, scaled by the token's scaling factor.
+    /// if amount absent, transfer 20% of association_address's balance, without minting.
     amount: Option<u128>,
+    #[structopt(
+        short = "t",
+        long = "token",
+        name = "token_code",
+        help = "token code of the coin, default is 0x1::STC::STC"
+    )]
+    token_code: Option<TokenCode>,
+    #[structopt(long = "to", name = "receiver", conflicts_with = "to-file")]
+    /// receiver address, default is the default account.
+    to: Option<AccountAddress>,
+    #[structopt(long = "to-file", name = "to-file", parse(from_os_str))]
+    /// file of receivers, one receiver per line, in format `<address> [auth_key]`.
+    to_file: Option<PathBuf>,
     #[structopt(
         name = "no-blocking-mode",
         long = "no-blocking",
@@ -36,7 +77,7 @@ impl CommandAction for GetCoinCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = GetCoinOpt;
-    type ReturnItem = SignedUserTransactionView;
+    type ReturnItem = Vec<SignedUserTransactionView>;
 
     fn run(
         &self,
@@ -52,11 +93,22 @@ impl CommandAction for GetCoinCommand {
         }
         let client = ctx.state().client();
         let node_info = client.node_info()?;
-        let to = client.account_default()?.ok_or_else(|| {
-            format_err!("Can not find default account, Please create account first.")
-        })?;
-
         let association_address = account_config::association_address();
+        if client.account_get(association_address)?.is_none() {
+            bail!(
+                "association_address address {} is not available in local wallet",
+                association_address
+            );
+        }
+        let receivers = resolve_receivers(client, opt)?;
+        if receivers.is_empty() {
+            bail!("No receiver to get coin.");
+        }
+        let token_code = opt
+            .token_code
+            .clone()
+            .unwrap_or_else(|| STC_TOKEN_CODE.clone());
+
         let chain_state_reader = RemoteStateReader::new(client)?;
         let account_state_reader = AccountStateReader::new(&chain_state_reader);
         let account_resource = account_state_reader
@@ -67,37 +119,173 @@ impl CommandAction for GetCoinCommand {
                     association_address
                 )
             })?;
+        let token_info = account_state_reader
+            .get_token_info(token_code.clone())?
+            .ok_or_else(|| format_err!("Token {} is not registered on chain", token_code))?;
         let balance = account_state_reader
-            .get_balance(&association_address)?
-            .ok_or_else(|| {
-                format_err!(
-                    "association_address address {} balance must exist",
-                    association_address
-                )
-            })?;
-        let amount = opt.amount.unwrap_or(balance * 20 / 100);
-        let raw_txn = starcoin_executor::build_transfer_txn(
-            association_address,
-            to.address,
-            Some(to.public_key.authentication_key()),
-            account_resource.sequence_number(),
-            amount,
-            1,
-            DEFAULT_MAX_GAS_AMOUNT,
-            node_info.now_seconds + DEFAULT_EXPIRATION_TIME,
-            ctx.state().net().chain_id(),
-        );
+            .get_balance_by_token_code(&association_address, token_code.clone())?
+            .unwrap_or_default();
+        let token_type: TypeTag = token_code.clone().into();
+        let mint_capability = StructTag {
+            address: CORE_CODE_ADDRESS,
+            module: Identifier::new("Token")?,
+            name: Identifier::new("MintCapability")?,
+            type_params: vec![token_type.clone()],
+        };
+        // keep the default behavior of transferring 20% of the balance when amount is absent.
+        let can_mint = opt.amount.is_some()
+            && client
+                .state_get(AccessPath::resource_access_path(
+                    association_address,
+                    mint_capability,
+                ))?
+                .is_some();
+
+        let amount = match opt.amount {
+            Some(amount) => amount
+                .checked_mul(token_info.scaling_factor())
+                .ok_or_else(|| format_err!("amount {} overflow", amount))?,
+            None => balance * 20 / 100 / receivers.len() as u128,
+        };
+        let total_amount = amount
+            .checked_mul(receivers.len() as u128)
+            .ok_or_else(|| format_err!("total amount overflow"))?;
+        if !can_mint && total_amount > balance {
+            bail!(
+                "association_address address {} balance {} of token {} is not enough, require {}",
+                association_address,
+                balance,
+                token_code,
+                total_amount
+            );
+        }
+
         client.account_unlock(
             association_address,
             "".to_string(),
             Duration::from_secs(300),
         )?;
-        let txn = client.account_sign_txn(raw_txn)?;
-        let id = txn.id();
-        client.submit_transaction(txn.clone())?;
+        let expiration_timestamp_secs = node_info.now_seconds + DEFAULT_EXPIRATION_TIME;
+        let chain_id = net.chain_id();
+        let mut sequence_number = account_resource.sequence_number();
+        let mut raw_txns = vec![];
+        if can_mint {
+            let script = Script::new(
+                compile_mint_script()?,
+                vec![token_type],
+                vec![TransactionArgument::U128(total_amount)],
+            );
+            raw_txns.push(RawUserTransaction::new_script(
+                association_address,
+                sequence_number,
+                script,
+                DEFAULT_MAX_GAS_AMOUNT,
+                1,
+                expiration_timestamp_secs,
+                chain_id,
+            ));
+            sequence_number += 1;
+        }
+        for (receiver, auth_key) in receivers {
+            raw_txns.push(starcoin_executor::build_transfer_txn_by_token_type(
+                association_address,
+                receiver,
+                auth_key,
+                sequence_number,
+                amount,
+                1,
+                DEFAULT_MAX_GAS_AMOUNT,
+                token_code.clone(),
+                expiration_timestamp_secs,
+                chain_id,
+            ));
+            sequence_number += 1;
+        }
+
+        let mut txns = vec![];
+        for raw_txn in raw_txns {
+            let txn = ctx.state().sign_txn(raw_txn)?;
+            client.submit_transaction(txn.clone())?;
+            txns.push(txn);
+        }
         if !opt.no_blocking {
-            ctx.state().watch_txn(id)?;
+            if let Some(last_txn) = txns.last() {
+                ctx.state().watch_txn(last_txn.id())?;
+            }
+        }
+        txns.into_iter().map(TryInto::try_into).collect()
+    }
+}
+
+/// Resolve receivers from opt, a receiver's auth key is taken from the local wallet if absent.
+fn resolve_receivers(
+    client: &RpcClient,
+    opt: &GetCoinOpt,
+) -> Result<Vec<(AccountAddress, Option<AuthenticationKey>)>> {
+    let receivers = if let Some(to_file) = opt.to_file.as_ref() {
+        let mut receivers = vec![];
+        for (idx, line) in std::fs::read_to_string(to_file)?.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let parse_err = || format_err!("invalid receiver at line {}: {}", idx + 1, line);
+            let address = parts
+                .next()
+                .map(AccountAddress::from_str)
+                .transpose()
+                .map_err(|_| parse_err())?
+                .ok_or_else(parse_err)?;
+            let auth_key = parts
+                .next()
+                .map(AuthenticationKey::from_str)
+                .transpose()
+                .map_err(|_| parse_err())?;
+            receivers.push((address, auth_key));
+        }
+        receivers
+    } else if let Some(to) = opt.to {
+        vec![(to, None)]
+    } else {
+        let default_account = client.account_default()?.ok_or_else(|| {
+            format_err!("Can not find default account, Please create account first.")
+        })?;
+        vec![(default_account.address, None)]
+    };
+    receivers
+        .into_iter()
+        .map(|(address, auth_key)| {
+            let auth_key = match auth_key {
+                Some(auth_key) => Some(auth_key),
+                None => client
+                    .account_get(address)?
+                    .map(|account| account.public_key.authentication_key()),
+            };
+            Ok((address, auth_key))
+        })
+        .collect()
+}
+
+fn compile_mint_script() -> Result<Vec<u8>> {
+    let temp_path = temp_path();
+    let deps = restore_stdlib_in_dir(temp_path.path())?;
+    let (sources, compile_result) =
+        compile_source_string_no_report(MINT_SCRIPT, &deps, account_config::association_address())?;
+    let mut compile_units = match compile_result {
+        Ok(c) => c,
+        Err(e) => {
+            scmd::side_println!(
+                "{}",
+                String::from_utf8_lossy(
+                    errors::report_errors_to_color_buffer(sources, e).as_slice()
+                )
+            );
+            bail!("compile mint script error")
         }
-        txn.try_into()
+    };
+    match compile_units.pop() {
+        Some(unit @ CompiledUnit::Script { .. }) => Ok(unit.serialize()),
+        _ => bail!("compile mint script error: script not found"),
     }
 }
@@ -6,13 +6,18 @@ mod compile_cmd;
 mod deploy_cmd;
 mod derive_account_address_cmd;
 mod execute_cmd;
+mod gen_block_cmd;
 mod generate_multisig_txn_cmd;
+mod genesis_accounts_cmd;
 mod get_coin_cmd;
+mod replay_block_cmd;
 pub(crate) mod sign_txn_helper;
+mod sleep_cmd;
 mod submit_multisig_txn_cmd;
 mod subscribe_cmd;
 #[cfg(test)]
 mod tests;
+mod upgrade_cmd;
 mod upgrade_module_exe_cmd;
 mod upgrade_module_plan_cmd;
 mod upgrade_module_proposal_cmd;
@@ -26,11 +31,16 @@ pub use compile_cmd::*;
 pub use deploy_cmd::*;
 pub use derive_account_address_cmd::*;
 pub use execute_cmd::*;
+pub use gen_block_cmd::*;
 pub use generate_multisig_txn_cmd::*;
+pub use genesis_accounts_cmd::*;
 pub use get_coin_cmd::*;
+pub use replay_block_cmd::*;
 pub use sign_txn_helper::sign_txn_with_account_by_rpc_client;
+pub use sleep_cmd::*;
 pub use submit_multisig_txn_cmd::*;
 pub use subscribe_cmd::*;
+pub use upgrade_cmd::*;
 pub use upgrade_module_exe_cmd::*;
 pub use upgrade_module_plan_cmd::*;
 pub use upgrade_module_proposal_cmd::*;
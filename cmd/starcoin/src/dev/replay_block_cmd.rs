@@ -0,0 +1,60 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_dev::block_replay::{first_divergence, replay_block};
+use starcoin_types::block::BlockNumber;
+use starcoin_types::block_bundle::BlockBundle;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Replay the block bundle exported by `chain export-block --with-state` offline,
+/// and compare the write sets and events of the txns with the recorded ones.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "replay-block")]
+pub struct ReplayBlockOpt {
+    #[structopt(name = "bundle", parse(from_os_str))]
+    /// the block bundle file.
+    bundle: PathBuf,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayBlockView {
+    pub block_hash: HashValue,
+    pub block_number: BlockNumber,
+    pub transactions: usize,
+    /// The first divergence of the replayed outputs from the recorded ones, None if they are the same.
+    pub divergence: Option<String>,
+}
+
+pub struct ReplayBlockCommand;
+
+impl CommandAction for ReplayBlockCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ReplayBlockOpt;
+    type ReturnItem = ReplayBlockView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let bundle = BlockBundle::decode(std::fs::read(opt.bundle.as_path())?.as_slice())?;
+        let state = bundle.state.ok_or_else(|| {
+            format_err!("The bundle has no state, export it by `chain export-block --with-state`")
+        })?;
+        let replayed = replay_block(state.read_set, bundle.transactions.clone())?;
+        Ok(ReplayBlockView {
+            block_hash: bundle.block.id(),
+            block_number: bundle.block.header().number(),
+            transactions: bundle.transactions.len(),
+            divergence: first_divergence(&bundle.transactions, &state.outputs, &replayed),
+        })
+    }
+}
@@ -62,7 +62,7 @@ pub fn sign_txn_by_rpc_client(
         net.chain_id(),
     );
 
-    client.account_sign_txn(raw_txn)
+    cli_state.sign_txn(raw_txn)
 }
 
 pub fn get_dao_config(cli_state: &CliState) -> Result<DaoConfig> {
@@ -0,0 +1,34 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use structopt::StructOpt;
+
+/// Advance the mock time of the dev node, only for the dev chain.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "sleep")]
+pub struct SleepOpt {
+    #[structopt(name = "millis")]
+    /// milliseconds to advance.
+    millis: u64,
+}
+
+pub struct SleepCommand;
+
+impl CommandAction for SleepCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SleepOpt;
+    type ReturnItem = ();
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.sleep(ctx.opt().millis)
+    }
+}
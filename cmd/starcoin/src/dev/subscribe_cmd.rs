@@ -48,14 +48,15 @@ impl CommandAction for SubscribeEventCommand {
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<Self::ReturnItem> {
         let filter = EventFilter {
-            from_block: ctx.opt().from_block,
-            to_block: ctx.opt().to_block,
+            from_block: ctx.opt().from_block.map(Into::into),
+            to_block: ctx.opt().to_block.map(Into::into),
             event_keys: ctx.opt().event_key.clone().unwrap_or_default(),
             limit: ctx.opt().limit,
+            ..Default::default()
         };
 
         let event_stream = ctx.state().client().subscribe_events(filter)?;
-        println!("Subscribe successful, Press `q` and Enter to quit");
+        scmd::side_println!("Subscribe successful, Press `q` and Enter to quit");
         blocking_display_notification(event_stream, |evt| {
             serde_json::to_string(&evt).expect("should never fail")
         });
@@ -78,7 +79,7 @@ impl CommandAction for SubscribeBlockCommand {
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<Self::ReturnItem> {
         let event_stream = ctx.state().client().subscribe_new_blocks()?;
-        println!("Subscribe successful, Press `q` and Enter to quit");
+        scmd::side_println!("Subscribe successful, Press `q` and Enter to quit");
         blocking_display_notification(event_stream, |evt| {
             serde_json::to_string(&evt).expect("should never fail")
         });
@@ -99,7 +100,7 @@ impl CommandAction for SubscribeNewTxnCommand {
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<Self::ReturnItem> {
         let event_stream = ctx.state().client().subscribe_new_transactions()?;
-        println!("Subscribe successful, Press `q` and Enter to quit");
+        scmd::side_println!("Subscribe successful, Press `q` and Enter to quit");
         blocking_display_notification(event_stream, |evt| {
             serde_json::to_string(&evt).expect("should never fail")
         });
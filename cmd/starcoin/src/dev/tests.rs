@@ -1,8 +1,13 @@
+use crate::dev::execute_cmd::build_txn_payloads;
+use crate::dev::generate_genesis_accounts;
 use crate::dev::sign_txn_helper::{sign_txn_by_rpc_client, sign_txn_with_account_by_rpc_client};
 use crate::CliState;
 use anyhow::{format_err, Result};
-use starcoin_config::NodeConfig;
+use starcoin_config::{BuiltinNetworkID, ChainNetworkID, GenesisConfig, NodeConfig, StarcoinOpt};
+use starcoin_crypto::HashValue;
+use starcoin_dev::block_replay::{first_divergence, replay_block};
 use starcoin_logger::prelude::*;
+use starcoin_move_compiler::{compile_source_files_no_report, CompiledUnit};
 use starcoin_node::NodeHandle;
 use starcoin_rpc_api::types::{
     AnnotatedMoveValueView, ContractCall, FunctionIdView, TransactionVMStatus,
@@ -18,7 +23,7 @@ use starcoin_types::transaction::{
 use starcoin_vm_types::account_address::AccountAddress;
 use starcoin_vm_types::account_config::core_code_address;
 use starcoin_vm_types::identifier::Identifier;
-use starcoin_vm_types::language_storage::ModuleId;
+use starcoin_vm_types::language_storage::{FunctionId, ModuleId};
 use starcoin_vm_types::transaction::{
     RawUserTransaction, SignedUserTransaction, TransactionPayload,
 };
@@ -28,6 +33,7 @@ use starcoin_vm_types::{
     transaction::Package,
 };
 use starcoin_vm_types::{language_storage::TypeTag, parser::parse_type_tag};
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::{thread::sleep, time::Duration};
@@ -512,3 +518,348 @@ fn test_only_new_module() {
 
     node_handle.stop().unwrap();
 }
+
+#[stest::test(timeout = 120)]
+fn test_dev_sleep_expire_txn() {
+    let node_config = NodeConfig::random_for_test();
+    let config = Arc::new(node_config);
+    let node_handle = run_node_by_config(config.clone()).unwrap();
+    let rpc_service = node_handle.rpc_service().unwrap();
+    let rpc_client = RpcClient::connect_local(rpc_service).unwrap();
+    let node_info = rpc_client.node_info().unwrap();
+    let cli_state = CliState::new(node_info.net, Arc::new(rpc_client), None, None);
+    cli_state
+        .client()
+        .account_unlock(
+            association_address(),
+            "".to_string(),
+            Duration::from_secs(100),
+        )
+        .unwrap();
+
+    let (association_account_resource, _) =
+        get_account_resource(&cli_state, association_address()).unwrap();
+    let transfer_raw_txn = starcoin_executor::build_transfer_txn(
+        association_address(),
+        AccountAddress::random(),
+        None,
+        association_account_resource.sequence_number(),
+        1000,
+        1,
+        1_000_000,
+        10 + config.net().time_service().now_secs(),
+        cli_state.net().chain_id(),
+    );
+    let transfer_txn = cli_state
+        .client()
+        .account_sign_txn(transfer_raw_txn)
+        .unwrap();
+
+    // advance the time over the expiration, and generate a block to update the on-chain time.
+    cli_state.client().sleep(20_000).unwrap();
+    let block_hashes = cli_state.client().dev_generate_block(1).unwrap();
+    assert_eq!(block_hashes.len(), 1);
+    let head = cli_state.client().chain_info().unwrap().head;
+    assert_eq!(head.block_hash, block_hashes[0]);
+
+    assert!(cli_state.client().submit_transaction(transfer_txn).is_err());
+
+    node_handle.stop().unwrap();
+}
+
+#[stest::test]
+fn test_module_abi() -> Result<()> {
+    let source = r#"
+        module M {
+            public(script) fun transfer<TokenType: store>(account: signer, payee: address, amount: u128, metadata: vector<u8>) {
+                let _ = account;
+                let _ = payee;
+                let _ = amount;
+                let _ = metadata;
+            }
+            public fun value(x: u64): u64 {
+                x
+            }
+        }
+    "#;
+    let (_, units) =
+        starcoin_move_compiler::compile_sorce_string(source, &[], AccountAddress::random())?;
+    let abi = super::compile_cmd::module_abi(&units[0])?.expect("module should have abi");
+    assert_eq!(abi.module_id.0.name().as_str(), "M");
+    assert_eq!(abi.script_functions.len(), 1);
+    let function = &abi.script_functions[0];
+    assert_eq!(function.name, "transfer");
+    assert_eq!(function.type_params.len(), 1);
+    assert_eq!(function.type_params[0].name, "TokenType");
+    assert_eq!(function.type_params[0].abilities, vec!["store".to_string()]);
+    let args: Vec<_> = function
+        .args
+        .iter()
+        .map(|arg| (arg.name.as_str(), arg.type_tag.0.clone()))
+        .collect();
+    assert_eq!(
+        args,
+        vec![
+            ("payee", TypeTag::Address),
+            ("amount", TypeTag::U128),
+            ("metadata", TypeTag::Vector(Box::new(TypeTag::U8))),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_check_upgrade_plan() {
+    use crate::dev::upgrade_cmd::check_upgrade_plan;
+    use starcoin_crypto::HashValue;
+    use starcoin_rpc_api::types::UpgradePlanView;
+
+    let package_hash = HashValue::random();
+    let plan = UpgradePlanView {
+        package_hash,
+        active_after_time: 10_000.into(),
+        version: 1.into(),
+        enforced: false,
+    };
+    assert!(check_upgrade_plan(&plan, package_hash, 10_000).is_ok());
+
+    let err = check_upgrade_plan(&plan, package_hash, 4_000).unwrap_err();
+    assert!(err.to_string().contains("6 seconds"));
+
+    let local_hash = HashValue::random();
+    let err = check_upgrade_plan(&plan, local_hash, 10_000)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains(format!("{:#x}", local_hash).as_str()));
+    assert!(err.contains(format!("{:#x}", package_hash).as_str()));
+}
+
+#[test]
+fn test_module_publish_state() {
+    use crate::dev::deploy_cmd::{module_publish_state, ModulePublishState};
+    use starcoin_crypto::HashValue;
+
+    let sender = AccountAddress::random();
+    let module_id = ModuleId::new(sender, Identifier::new("M").unwrap());
+    let local_hash = HashValue::random();
+    assert_eq!(
+        module_publish_state(&module_id, sender, local_hash, None).unwrap(),
+        ModulePublishState::Absent
+    );
+    assert_eq!(
+        module_publish_state(&module_id, sender, local_hash, Some(local_hash)).unwrap(),
+        ModulePublishState::Identical
+    );
+    assert_eq!(
+        module_publish_state(&module_id, sender, local_hash, Some(HashValue::random())).unwrap(),
+        ModulePublishState::Changed
+    );
+
+    let other = AccountAddress::random();
+    let err = module_publish_state(&module_id, other, local_hash, Some(local_hash))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("occupied"));
+    assert!(err.contains(other.to_string().as_str()));
+    assert!(module_publish_state(&module_id, other, local_hash, None).is_err());
+}
+
+#[stest::test(timeout = 120)]
+fn test_export_and_replay_block() -> Result<()> {
+    let mut config = NodeConfig::random_for_test();
+    config.rpc.enable_block_bundle_state = true;
+    let config = Arc::new(config);
+    let node_handle = run_node_by_config(config.clone())?;
+    let rpc_service = node_handle.rpc_service()?;
+    let rpc_client = RpcClient::connect_local(rpc_service)?;
+    let node_info = rpc_client.node_info()?;
+    let cli_state = CliState::new(node_info.net, Arc::new(rpc_client), None, None);
+    let client = cli_state.client();
+    client.account_unlock(
+        association_address(),
+        "".to_string(),
+        Duration::from_secs(100),
+    )?;
+
+    let (association_account_resource, _) =
+        get_account_resource(&cli_state, association_address())?;
+    let transfer_raw_txn = starcoin_executor::build_transfer_txn(
+        association_address(),
+        AccountAddress::random(),
+        None,
+        association_account_resource.sequence_number(),
+        1000,
+        1,
+        1_000_000,
+        3600 + config.net().time_service().now_secs(),
+        cli_state.net().chain_id(),
+    );
+    let transfer_txn = client.account_sign_txn(transfer_raw_txn)?;
+    client.submit_transaction(transfer_txn.clone())?;
+    let block_hash = client.dev_generate_block(1)?[0];
+
+    let bundle = client
+        .chain_export_block_bundle(block_hash, true)?
+        .ok_or_else(|| format_err!("block {} not found", block_hash))?;
+    assert_eq!(bundle.block.id(), block_hash);
+    // the block metadata txn and the transfer txn.
+    assert_eq!(bundle.transactions.len(), 2);
+    assert_eq!(bundle.transactions[1].id(), transfer_txn.id());
+    let state = bundle
+        .state
+        .clone()
+        .ok_or_else(|| format_err!("bundle should have state"))?;
+    let replayed = replay_block(state.read_set.clone(), bundle.transactions.clone())?;
+    assert_eq!(
+        first_divergence(&bundle.transactions, &state.outputs, &replayed),
+        None
+    );
+
+    // the replay fails if a state entry the block reads is missing.
+    let mut read_set = state.read_set;
+    read_set.pop();
+    assert!(replay_block(read_set, bundle.transactions.clone()).is_err());
+
+    let bundle = client
+        .chain_export_block_bundle(block_hash, false)?
+        .ok_or_else(|| format_err!("block {} not found", block_hash))?;
+    assert!(bundle.state.is_none());
+    assert!(client
+        .chain_export_block_bundle(HashValue::random(), false)?
+        .is_none());
+
+    node_handle.stop()?;
+    Ok(())
+}
+
+#[stest::test(timeout = 120)]
+fn test_custom_chain_with_genesis_accounts() -> Result<()> {
+    let temp_dir = starcoin_config::temp_path();
+    let accounts = generate_genesis_accounts(5, 1_000_000)?;
+    let accounts_file = temp_dir.path().join("accounts.json");
+    std::fs::write(&accounts_file, serde_json::to_vec(&accounts)?)?;
+
+    let mut genesis_config = BuiltinNetworkID::Test.genesis_config().clone();
+    // a relative path is relative to the genesis config file.
+    genesis_config.genesis_accounts_file = Some("accounts.json".into());
+    let genesis_config_file = temp_dir.path().join("genesis_config.json");
+    genesis_config.save(&genesis_config_file)?;
+
+    let opt = StarcoinOpt {
+        net: Some(ChainNetworkID::from_str("genesis_accounts:123")?),
+        base_data_dir: Some(temp_dir.path().join("data")),
+        genesis_config: Some(genesis_config_file.to_string_lossy().to_string()),
+        ..StarcoinOpt::default()
+    };
+    let config = Arc::new(NodeConfig::load_with_opt(&opt)?);
+    assert_eq!(config.net().genesis_config().genesis_accounts.len(), 5);
+    let node_handle = run_node_by_config(config)?;
+    let rpc_service = node_handle.rpc_service()?;
+    let client = RpcClient::connect_local(rpc_service)?;
+    for account in &accounts {
+        assert_eq!(
+            client.account_balance_at(account.address, None, 0)?,
+            Some(account.balance)
+        );
+    }
+
+    // the duplicate accounts are rejected.
+    let duplicate_file = temp_dir.path().join("duplicate_accounts.json");
+    std::fs::write(
+        &duplicate_file,
+        serde_json::to_vec(&vec![accounts[0].clone(), accounts[0].clone()])?,
+    )?;
+    genesis_config.genesis_accounts_file = Some(duplicate_file);
+    genesis_config.save(&genesis_config_file)?;
+    assert!(GenesisConfig::load(&genesis_config_file).is_err());
+
+    node_handle.stop()?;
+    Ok(())
+}
+
+#[stest::test]
+fn test_build_execute_txn_payload() -> Result<()> {
+    let temp_dir = starcoin_config::temp_path();
+    let module_file = temp_dir.path().join("MyModule.move");
+    std::fs::write(
+        module_file.as_path(),
+        r#"
+        address {{sender}} {
+        module MyModule {
+            public(script) fun init() {}
+            public fun hello(): u64 { 1 }
+        }
+        }
+        "#,
+    )?;
+    let script_file = temp_dir.path().join("my_script.move");
+    std::fs::write(
+        script_file.as_path(),
+        r#"
+        script {
+            use {{sender}}::MyModule;
+            fun main() {
+                assert(MyModule::hello() == 1, 1000);
+            }
+        }
+        "#,
+    )?;
+    let deps = stdlib::restore_stdlib_in_dir(temp_dir.path())?;
+    let sender = association_address();
+    let units = compile_source_files_no_report(
+        &[module_file, script_file],
+        &deps,
+        sender,
+        &HashMap::new(),
+    )?
+    .1
+    .map_err(|e| format_err!("compile error: {:?}", e))?;
+    let (scripts, modules): (Vec<CompiledUnit>, Vec<CompiledUnit>) = units
+        .into_iter()
+        .partition(|unit| matches!(unit, CompiledUnit::Script { .. }));
+    let modules: Vec<(String, Vec<u8>)> = modules
+        .into_iter()
+        .map(|unit| (unit.name(), unit.serialize()))
+        .collect();
+    let script = scripts.into_iter().next().map(|unit| unit.serialize());
+
+    // the sibling module of the script is deployed by a package txn before the script txn.
+    match build_txn_payloads(modules.clone(), script.clone(), None, vec![], vec![])?.as_slice() {
+        [TransactionPayload::Package(package), TransactionPayload::Script(_)] => {
+            assert_eq!(package.modules().len(), 1);
+            assert!(package.init_script().is_none());
+        }
+        payloads => panic!("Unexpected payloads: {:?}", payloads),
+    }
+    assert!(matches!(
+        build_txn_payloads(vec![], script, None, vec![], vec![])?.as_slice(),
+        [TransactionPayload::Script(_)]
+    ));
+
+    // the modules are deployed as a package, with the script function as the init function.
+    let init_function = FunctionId {
+        module: ModuleId::new(sender, Identifier::new("MyModule")?),
+        function: Identifier::new("init")?,
+    };
+    match build_txn_payloads(
+        modules.clone(),
+        None,
+        Some(init_function.clone()),
+        vec![],
+        vec![],
+    )?
+    .as_slice()
+    {
+        [TransactionPayload::Package(package)] => {
+            assert_eq!(package.modules().len(), 1);
+            assert!(package.init_script().is_some());
+        }
+        payloads => panic!("Unexpected payloads: {:?}", payloads),
+    }
+    assert!(matches!(
+        build_txn_payloads(vec![], None, Some(init_function), vec![], vec![])?.as_slice(),
+        [TransactionPayload::ScriptFunction(_)]
+    ));
+    assert!(build_txn_payloads(vec![], None, None, vec![], vec![]).is_err());
+    Ok(())
+}
@@ -0,0 +1,430 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::dev::sign_txn_helper::{get_dao_config, sign_txn_with_account_by_rpc_client};
+use crate::StarcoinOpt;
+use anyhow::{bail, ensure, format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_config::temp_path;
+use starcoin_crypto::hash::{HashValue, PlainCryptoHash};
+use starcoin_move_compiler::{
+    compile_source_files_no_report, errors, verify_units, CompiledUnit, MOVE_EXTENSION,
+};
+use starcoin_rpc_api::types::{StrView, TwoPhaseUpgradeView, UpgradePlanView};
+use starcoin_rpc_client::RemoteStateReader;
+use starcoin_state_api::AccountStateReader;
+use starcoin_transaction_builder::build_module_upgrade_proposal_v2;
+use starcoin_types::transaction::{Module, Package};
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::account_config::TwoPhaseUpgradeV2Resource;
+use starcoin_vm_types::transaction::TransactionPayload;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
+use stdlib::restore_stdlib_in_dir;
+use structopt::StructOpt;
+
+/// Propose a two-phase upgrade of the package compiled from the package dir to the DAO.
+/// When the proposal is agreed and queued, submit the plan by `dev module_plan`,
+/// then deploy the package by `dev upgrade execute` after the plan is active.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "plan")]
+pub struct UpgradePlanOpt {
+    #[structopt(short = "s", long)]
+    /// hex encoded string, like 0x1, 0x12
+    sender: Option<AccountAddress>,
+
+    #[structopt(
+        short = "g",
+        name = "max-gas-amount",
+        default_value = "10000000",
+        help = "max gas used to submit the proposal"
+    )]
+    max_gas_amount: u64,
+    #[structopt(
+        short = "p",
+        long = "gas-price",
+        name = "price of gas",
+        default_value = "1",
+        help = "gas price used to submit the proposal"
+    )]
+    gas_price: u64,
+
+    #[structopt(
+        name = "expiration_time",
+        long = "timeout",
+        default_value = "3000",
+        help = "how long(in seconds) the txn stay alive"
+    )]
+    expiration_time: u64,
+
+    #[structopt(
+        short = "b",
+        name = "blocking-mode",
+        long = "blocking",
+        help = "blocking wait txn mined"
+    )]
+    blocking: bool,
+
+    #[structopt(
+        short = "e",
+        name = "enforced",
+        long = "enforced",
+        help = "enforced upgrade regardless of compatible or not"
+    )]
+    enforced: bool,
+
+    #[structopt(
+        short = "v",
+        name = "module-version",
+        long = "module-version",
+        default_value = "1",
+        help = "module version"
+    )]
+    version: u64,
+
+    #[structopt(
+        short = "d",
+        name = "dependency_path",
+        long = "dep",
+        help = "path of dependency used to build, support multi deps"
+    )]
+    deps: Option<Vec<String>>,
+
+    #[structopt(name = "package-dir", parse(from_os_str))]
+    /// the dir of the move source files of the package
+    package_dir: PathBuf,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpgradePlanSubmitView {
+    pub package_address: AccountAddress,
+    pub package_hash: HashValue,
+    pub txn_hash: HashValue,
+}
+
+pub struct UpgradePlanCommand;
+
+impl CommandAction for UpgradePlanCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = UpgradePlanOpt;
+    type ReturnItem = UpgradePlanSubmitView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let cli_state = ctx.state();
+        let sender = if let Some(sender) = opt.sender {
+            sender
+        } else {
+            cli_state.default_account()?.address
+        };
+        let package = build_package(
+            opt.package_dir.as_path(),
+            opt.deps.clone().unwrap_or_default(),
+            sender,
+        )?;
+        let min_action_delay = get_dao_config(cli_state)?.min_action_delay;
+        let (module_upgrade_proposal, package_hash) =
+            build_module_upgrade_proposal_v2(&package, opt.version, min_action_delay, opt.enforced);
+        let signed_txn = sign_txn_with_account_by_rpc_client(
+            cli_state,
+            sender,
+            opt.max_gas_amount,
+            opt.gas_price,
+            opt.expiration_time,
+            TransactionPayload::ScriptFunction(module_upgrade_proposal),
+        )?;
+        let txn_hash = signed_txn.crypto_hash();
+        cli_state.client().submit_transaction(signed_txn)?;
+
+        scmd::side_println!("txn {:#x} submitted.", txn_hash);
+
+        if opt.blocking {
+            cli_state.watch_txn(txn_hash)?;
+        }
+        Ok(UpgradePlanSubmitView {
+            package_address: package.package_address(),
+            package_hash,
+            txn_hash,
+        })
+    }
+}
+
+/// Show the two-phase upgrade plan of a package address.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "status")]
+pub struct UpgradeStatusOpt {
+    #[structopt(name = "package-address", default_value = "0x1")]
+    /// the address of the package, default is the stdlib address 0x1
+    package_address: AccountAddress,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpgradeStatusView {
+    pub package_address: AccountAddress,
+    /// milli seconds, the on-chain time.
+    pub now: StrView<u64>,
+    pub upgrade: TwoPhaseUpgradeView,
+    /// `none`, `pending` or `active`, the package can be deployed only when the plan is active.
+    pub status: String,
+    /// seconds until the pending plan is active.
+    pub time_remaining: Option<u64>,
+}
+
+pub struct UpgradeStatusCommand;
+
+impl CommandAction for UpgradeStatusCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = UpgradeStatusOpt;
+    type ReturnItem = UpgradeStatusView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let package_address = ctx.opt().package_address;
+        let (upgrade, now) = get_two_phase_upgrade(ctx.state(), package_address)?;
+        let (status, time_remaining) = match upgrade.plan.as_ref() {
+            None => ("none", None),
+            Some(plan) if plan.is_active(now) => ("active", None),
+            Some(plan) => ("pending", Some(plan.remaining_millis(now) / 1000)),
+        };
+        Ok(UpgradeStatusView {
+            package_address,
+            now: now.into(),
+            upgrade,
+            status: status.to_string(),
+            time_remaining,
+        })
+    }
+}
+
+/// Deploy the package compiled from the package dir, after checking it is the one of the active upgrade plan.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "execute")]
+pub struct UpgradeExecuteOpt {
+    #[structopt(short = "s", long)]
+    /// hex encoded string, like 0x1, 0x12
+    sender: Option<AccountAddress>,
+
+    #[structopt(
+        short = "g",
+        name = "max-gas-amount",
+        default_value = "10000000",
+        help = "max gas used to deploy the package"
+    )]
+    max_gas_amount: u64,
+    #[structopt(
+        short = "p",
+        long = "gas-price",
+        name = "price of gas",
+        default_value = "1",
+        help = "gas price used to deploy the package"
+    )]
+    gas_price: u64,
+
+    #[structopt(
+        name = "expiration_time",
+        long = "timeout",
+        default_value = "3000",
+        help = "how long(in seconds) the txn stay alive"
+    )]
+    expiration_time: u64,
+
+    #[structopt(
+        short = "b",
+        name = "blocking-mode",
+        long = "blocking",
+        help = "blocking wait txn mined"
+    )]
+    blocking: bool,
+
+    #[structopt(
+        short = "d",
+        name = "dependency_path",
+        long = "dep",
+        help = "path of dependency used to build, support multi deps"
+    )]
+    deps: Option<Vec<String>>,
+
+    #[structopt(name = "package-dir", parse(from_os_str))]
+    /// the dir of the move source files of the package
+    package_dir: PathBuf,
+}
+
+pub struct UpgradeExecuteCommand;
+
+impl CommandAction for UpgradeExecuteCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = UpgradeExecuteOpt;
+    type ReturnItem = HashValue;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let cli_state = ctx.state();
+        let sender = if let Some(sender) = opt.sender {
+            sender
+        } else {
+            cli_state.default_account()?.address
+        };
+        let package = build_package(
+            opt.package_dir.as_path(),
+            opt.deps.clone().unwrap_or_default(),
+            sender,
+        )?;
+        let package_address = package.package_address();
+        let package_hash = package.crypto_hash();
+        let (upgrade, now) = get_two_phase_upgrade(cli_state, package_address)?;
+        let plan = upgrade
+            .plan
+            .ok_or_else(|| format_err!("There is no upgrade plan of {}", package_address))?;
+        check_upgrade_plan(&plan, package_hash, now)?;
+
+        let signed_txn = sign_txn_with_account_by_rpc_client(
+            cli_state,
+            sender,
+            opt.max_gas_amount,
+            opt.gas_price,
+            opt.expiration_time,
+            TransactionPayload::Package(package),
+        )?;
+        let txn_hash = signed_txn.id();
+        cli_state.client().submit_transaction(signed_txn)?;
+
+        scmd::side_println!("txn {:#x} submitted.", txn_hash);
+
+        if opt.blocking {
+            cli_state.watch_txn(txn_hash)?;
+        }
+        Ok(txn_hash)
+    }
+}
+
+/// Check the package is the one of the plan, and the plan is active at `now_millis`.
+pub(crate) fn check_upgrade_plan(
+    plan: &UpgradePlanView,
+    package_hash: HashValue,
+    now_millis: u64,
+) -> Result<()> {
+    if plan.package_hash != package_hash {
+        bail!(
+            "Package hash mismatch, the locally built package hash is {:#x}, but the planned package hash is {:#x}",
+            package_hash,
+            plan.package_hash
+        );
+    }
+    if !plan.is_active(now_millis) {
+        bail!(
+            "The upgrade plan is not active yet, please retry after {} seconds",
+            plan.remaining_millis(now_millis) / 1000
+        );
+    }
+    Ok(())
+}
+
+/// Read the two-phase upgrade resource of the package address and the on-chain time in milli seconds at the same state.
+fn get_two_phase_upgrade(
+    cli_state: &CliState,
+    package_address: AccountAddress,
+) -> Result<(TwoPhaseUpgradeView, u64)> {
+    let chain_state_reader = RemoteStateReader::new(cli_state.client())?;
+    let account_state_reader = AccountStateReader::new(&chain_state_reader);
+    let upgrade = account_state_reader
+        .get_resource::<TwoPhaseUpgradeV2Resource>(package_address)?
+        .ok_or_else(|| {
+            format_err!(
+                "The package of {} is not upgraded by two phase upgrade strategy",
+                package_address
+            )
+        })?;
+    let now = account_state_reader.get_timestamp()?.milliseconds;
+    Ok((TwoPhaseUpgradeView::try_from(upgrade)?, now))
+}
+
+/// Compile all the move source files in the dir as a package, the modules must be at the same address.
+fn build_package(
+    package_dir: &Path,
+    extra_deps: Vec<String>,
+    sender: AccountAddress,
+) -> Result<Package> {
+    ensure!(package_dir.is_dir(), "{:?} is not a dir.", package_dir);
+    let mut source_files = vec![];
+    collect_source_files(package_dir, &mut source_files)?;
+    ensure!(
+        !source_files.is_empty(),
+        "There is no move source file in {:?}",
+        package_dir
+    );
+    // keep the module order stable, so the package hash is the same at plan and execute.
+    source_files.sort();
+
+    let temp_path = temp_path();
+    let source_names = source_files
+        .iter()
+        .filter_map(|file| file.file_name())
+        .collect::<Vec<_>>();
+    // skip the builtin stdlib files which are replaced by the package, such as upgrading the stdlib itself.
+    let mut deps = restore_stdlib_in_dir(temp_path.path())?
+        .into_iter()
+        .filter(|dep| {
+            Path::new(dep)
+                .file_name()
+                .map(|name| !source_names.contains(&name))
+                .unwrap_or(true)
+        })
+        .collect::<Vec<_>>();
+    deps.extend(extra_deps);
+
+    let (sources, compile_result) =
+        compile_source_files_no_report(source_files.as_slice(), &deps, sender, &HashMap::new())?;
+    let units = match compile_result.and_then(|units| {
+        let (units, errors) = verify_units(units);
+        if errors.is_empty() {
+            Ok(units)
+        } else {
+            Err(errors)
+        }
+    }) {
+        Ok(units) => units,
+        Err(e) => {
+            scmd::side_println!(
+                "{}",
+                String::from_utf8_lossy(
+                    errors::report_errors_to_color_buffer(sources, e).as_slice()
+                )
+            );
+            bail!("compile error")
+        }
+    };
+    let modules = units
+        .into_iter()
+        .map(|unit| match unit {
+            CompiledUnit::Module { .. } => Ok(Module::new(unit.serialize())),
+            CompiledUnit::Script { .. } => bail!("The package dir should only contain modules."),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Package::new(modules, None)
+}
+
+fn collect_source_files(dir: &Path, source_files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_source_files(path.as_path(), source_files)?;
+        } else if path.extension().and_then(|ext| ext.to_str()) == Some(MOVE_EXTENSION) {
+            source_files.push(path);
+        }
+    }
+    Ok(())
+}
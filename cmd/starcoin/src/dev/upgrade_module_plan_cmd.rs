@@ -99,7 +99,7 @@ impl CommandAction for UpgradeModulePlanCommand {
         let txn_hash = signed_txn.id();
         cli_state.client().submit_transaction(signed_txn)?;
 
-        println!("txn {:#x} submitted.", txn_hash);
+        scmd::side_println!("txn {:#x} submitted.", txn_hash);
 
         if opt.blocking {
             ctx.state().watch_txn(txn_hash)?;
@@ -129,7 +129,7 @@ impl CommandAction for UpgradeModuleProposalV2Command {
             let txn_hash = signed_txn.crypto_hash();
             cli_state.client().submit_transaction(signed_txn)?;
 
-            println!("txn {:#x} submitted.", txn_hash);
+            scmd::side_println!("txn {:#x} submitted.", txn_hash);
 
             if opt.blocking {
                 ctx.state().watch_txn(txn_hash)?;
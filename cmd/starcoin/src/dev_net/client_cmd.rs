@@ -0,0 +1,53 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dev_net::{default_work_dir, DevNetManifest, DevNetState};
+use crate::StarcoinOpt;
+use anyhow::{ensure, Result};
+use scmd::{CommandAction, ExecContext};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Open a console connected to a dev-net node by its ipc file.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "client")]
+pub struct ClientOpt {
+    #[structopt(long = "work-dir", parse(from_os_str))]
+    /// The dir of the dev-net, default is ~/.starcoin/dev-net
+    work_dir: Option<PathBuf>,
+
+    #[structopt(long = "node", default_value = "1")]
+    /// The node to connect, start from 1.
+    node: usize,
+}
+
+pub struct ClientCommand;
+
+impl CommandAction for ClientCommand {
+    type State = DevNetState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ClientOpt;
+    type ReturnItem = ();
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let work_dir = opt.work_dir.clone().unwrap_or_else(default_work_dir);
+        let manifest = DevNetManifest::load(work_dir.as_path())?;
+        let node = manifest.node(opt.node)?;
+        ensure!(
+            node.running_pid()?.is_some(),
+            "Node {} is not running, please start the dev-net first",
+            node.index
+        );
+        let status = std::process::Command::new(std::env::current_exe()?)
+            .arg("-c")
+            .arg(node.ipc_file.as_path())
+            .arg("console")
+            .status()?;
+        ensure!(status.success(), "The console exits with {}", status);
+        Ok(())
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Run a local network of several nodes as child processes, for developing and testing the
+//! p2p features, such as the block sync and the txn propagation, on a single machine.
+
+use anyhow::{bail, format_err, Result};
+use scmd::{CmdContext, Command};
+use serde::{Deserialize, Serialize};
+use starcoin_config::{ChainNetworkID, StarcoinOpt, APP_VERSION, CRATE_VERSION};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+mod client_cmd;
+mod start_cmd;
+mod status_cmd;
+mod stop_cmd;
+
+pub use client_cmd::*;
+pub use start_cmd::*;
+pub use status_cmd::*;
+pub use stop_cmd::*;
+
+pub const DEV_NET_COMMAND: &str = "dev-net";
+/// The chain name of the custom network shared by the nodes.
+pub const DEV_NET_CHAIN_NAME: &str = "devnet";
+pub const DEV_NET_MANIFEST_FILE: &str = "dev-net.json";
+pub const DEV_NET_GENESIS_CONFIG_FILE: &str = "genesis_config.json";
+pub const NODE_PID_FILE: &str = "starcoin.pid";
+/// The node output in detach mode.
+pub const NODE_LOG_FILE: &str = "node.log";
+
+/// The dev-net commands do not connect to a node, so they have no state.
+pub struct DevNetState;
+
+/// The nodes of a dev-net, saved in the work dir, so the other commands can find the nodes.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DevNetManifest {
+    pub net: ChainNetworkID,
+    pub nodes: Vec<DevNetNode>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DevNetNode {
+    /// Start from 1.
+    pub index: usize,
+    /// The base data dir of the node.
+    pub node_dir: PathBuf,
+    pub ipc_file: PathBuf,
+    /// The p2p address with peer id, the other nodes use it as the seed.
+    pub address: String,
+    pub http_port: u16,
+    /// The options to start the node, without the seeds.
+    pub args: Vec<String>,
+}
+
+impl DevNetManifest {
+    pub fn path(work_dir: &Path) -> PathBuf {
+        work_dir.join(DEV_NET_MANIFEST_FILE)
+    }
+
+    pub fn load(work_dir: &Path) -> Result<Self> {
+        let path = Self::path(work_dir);
+        if !path.exists() {
+            bail!(
+                "Can not find dev-net in {:?}, please start it by `starcoin dev-net start`",
+                work_dir
+            );
+        }
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    pub fn save(&self, work_dir: &Path) -> Result<()> {
+        std::fs::write(Self::path(work_dir), serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn node(&self, index: usize) -> Result<&DevNetNode> {
+        self.nodes
+            .iter()
+            .find(|node| node.index == index)
+            .ok_or_else(|| {
+                format_err!(
+                    "Can not find node {}, the nodes are 1..={}",
+                    index,
+                    self.nodes.len()
+                )
+            })
+    }
+}
+
+impl DevNetNode {
+    pub fn pid_file(&self) -> PathBuf {
+        self.node_dir.join(NODE_PID_FILE)
+    }
+
+    pub fn log_file(&self) -> PathBuf {
+        self.node_dir.join(NODE_LOG_FILE)
+    }
+
+    pub fn pid(&self) -> Result<Option<u32>> {
+        let pid_file = self.pid_file();
+        if !pid_file.exists() {
+            return Ok(None);
+        }
+        let pid = std::fs::read_to_string(pid_file.as_path())?;
+        Ok(Some(pid.trim().parse().map_err(|e| {
+            format_err!("Invalid pid file {:?}: {:?}", pid_file, e)
+        })?))
+    }
+
+    /// The pid of the node process if it is running.
+    pub fn running_pid(&self) -> Result<Option<u32>> {
+        Ok(self.pid()?.filter(|pid| is_process_running(*pid)))
+    }
+}
+
+/// The default work dir of dev-net, under the default base data dir.
+pub fn default_work_dir() -> PathBuf {
+    starcoin_config::DEFAULT_BASE_DATA_DIR.join(DEV_NET_COMMAND)
+}
+
+fn send_signal(pid: u32, signal: &str) -> Result<bool> {
+    if cfg!(not(unix)) {
+        bail!("dev-net only supports unix now");
+    }
+    Ok(std::process::Command::new("kill")
+        .arg(signal)
+        .arg(pid.to_string())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?
+        .success())
+}
+
+pub fn is_process_running(pid: u32) -> bool {
+    send_signal(pid, "-0").unwrap_or(false)
+}
+
+/// Terminate the process, and kill it if it is still running after the `timeout`.
+/// Return false if the process is killed.
+pub fn stop_process(pid: u32, timeout: Duration) -> Result<bool> {
+    send_signal(pid, "-TERM")?;
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        if !is_process_running(pid) {
+            return Ok(true);
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    }
+    send_signal(pid, "-KILL")?;
+    Ok(false)
+}
+
+/// Whether the args are of a dev-net command, the global output options may be before the command.
+pub fn is_dev_net_command(args: &[String]) -> bool {
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output-format" => {
+                args.next();
+            }
+            "-q" | "--quiet" => {}
+            arg => return arg == DEV_NET_COMMAND,
+        }
+    }
+    false
+}
+
+pub fn exec_dev_net() -> Result<()> {
+    let context = CmdContext::<DevNetState, StarcoinOpt>::with_state(
+        CRATE_VERSION,
+        Some(APP_VERSION.as_str()),
+        DevNetState,
+    );
+    context
+        .command(
+            Command::with_name(DEV_NET_COMMAND)
+                .subcommand(StartCommand)
+                .subcommand(StopCommand)
+                .subcommand(StatusCommand)
+                .subcommand(ClientCommand),
+        )
+        .exec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_dev_net_command() {
+        let args = |args: &[&str]| -> Vec<String> {
+            std::iter::once("starcoin")
+                .chain(args.iter().copied())
+                .map(|arg| arg.to_string())
+                .collect()
+        };
+        assert!(is_dev_net_command(&args(&["dev-net", "start"])));
+        assert!(is_dev_net_command(&args(&["-o", "json", "dev-net"])));
+        assert!(is_dev_net_command(&args(&["-q", "dev-net", "status"])));
+        assert!(!is_dev_net_command(&args(&[])));
+        assert!(!is_dev_net_command(&args(&["chain", "info"])));
+        assert!(!is_dev_net_command(&args(&[
+            "account", "create", "-p", "dev-net"
+        ])));
+    }
+}
@@ -0,0 +1,258 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dev_net::{
+    default_work_dir, DevNetManifest, DevNetNode, DevNetState, DEV_NET_CHAIN_NAME,
+    DEV_NET_GENESIS_CONFIG_FILE,
+};
+use crate::StarcoinOpt;
+use anyhow::{bail, ensure, format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_config::{get_available_port_from, ChainNetworkID, NodeConfig, DEV_CONFIG};
+use starcoin_vm_types::genesis_config::ConsensusStrategy;
+use starcoin_vm_types::time::TimeServiceType;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Stdio};
+use std::str::FromStr;
+use structopt::StructOpt;
+
+/// Start a local network of several nodes, the nodes share a genesis and use each other as seeds.
+/// The configs are generated in the work dir at the first start, and reused by the later starts.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "start")]
+pub struct StartOpt {
+    #[structopt(long = "nodes", default_value = "3")]
+    /// The number of the nodes.
+    nodes: usize,
+
+    #[structopt(long = "work-dir", parse(from_os_str))]
+    /// The dir of the node configs and data, default is ~/.starcoin/dev-net
+    work_dir: Option<PathBuf>,
+
+    #[structopt(long = "consensus", default_value = "dummy")]
+    /// The consensus of the genesis, dummy or cryptonight.
+    consensus: ConsensusStrategy,
+
+    #[structopt(long = "block-time", default_value = "1000")]
+    /// The block time target in milliseconds.
+    block_time: u64,
+
+    #[structopt(long = "chain-id", default_value = "100")]
+    chain_id: u8,
+
+    #[structopt(long = "base-port", default_value = "39840")]
+    /// The ports of the nodes are allocated from the first available one of it.
+    base_port: u16,
+
+    #[structopt(long = "detach")]
+    /// Return after the nodes are started, the node logs are written to the node.log in the node dirs.
+    detach: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartedNodeView {
+    pub node: usize,
+    pub pid: u32,
+    pub address: String,
+    pub ipc_file: PathBuf,
+    pub http_port: u16,
+}
+
+pub struct StartCommand;
+
+impl CommandAction for StartCommand {
+    type State = DevNetState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = StartOpt;
+    type ReturnItem = Vec<StartedNodeView>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        ensure!(opt.nodes > 0, "nodes should be greater than 0");
+        ensure!(opt.block_time > 0, "block-time should be greater than 0");
+        ensure!(
+            matches!(
+                opt.consensus,
+                ConsensusStrategy::Dummy | ConsensusStrategy::CryptoNight
+            ),
+            "dev-net only supports dummy or cryptonight consensus"
+        );
+        let work_dir = opt.work_dir.clone().unwrap_or_else(default_work_dir);
+        std::fs::create_dir_all(work_dir.as_path())?;
+        let manifest = if DevNetManifest::path(work_dir.as_path()).exists() {
+            let manifest = DevNetManifest::load(work_dir.as_path())?;
+            for node in &manifest.nodes {
+                if let Some(pid) = node.running_pid()? {
+                    bail!(
+                        "Node {} of the dev-net in {:?} is running with pid {}, please stop it first",
+                        node.index,
+                        work_dir,
+                        pid
+                    );
+                }
+            }
+            ensure!(
+                manifest.nodes.len() == opt.nodes,
+                "The dev-net in {:?} has {} nodes, please remove the work dir to start a new one",
+                work_dir,
+                manifest.nodes.len()
+            );
+            manifest
+        } else {
+            let manifest = generate_dev_net(opt, work_dir.as_path())?;
+            manifest.save(work_dir.as_path())?;
+            manifest
+        };
+
+        let exe = std::env::current_exe()?;
+        let mut children = vec![];
+        let mut views = vec![];
+        for node in &manifest.nodes {
+            let seeds: Vec<&str> = manifest
+                .nodes
+                .iter()
+                .filter(|other| other.index != node.index)
+                .map(|other| other.address.as_str())
+                .collect();
+            let mut cmd = std::process::Command::new(exe.as_path());
+            cmd.args(node.args.as_slice())
+                .arg("--seed")
+                .arg(seeds.join(","))
+                .stdin(Stdio::null());
+            if opt.detach {
+                let log = File::create(node.log_file())?;
+                cmd.stdout(log.try_clone()?).stderr(log);
+            } else {
+                cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+            }
+            let child = cmd
+                .spawn()
+                .map_err(|e| format_err!("Start node {} failed: {:?}", node.index, e))?;
+            std::fs::write(node.pid_file(), child.id().to_string())?;
+            views.push(StartedNodeView {
+                node: node.index,
+                pid: child.id(),
+                address: node.address.clone(),
+                ipc_file: node.ipc_file.clone(),
+                http_port: node.http_port,
+            });
+            children.push((node.index, child));
+        }
+        if !opt.detach {
+            for view in &views {
+                eprintln!(
+                    "[node{}] started with pid {}, attach a console by: starcoin dev-net client --node {}",
+                    view.node, view.pid, view.node
+                );
+            }
+            stream_logs(children)?;
+        }
+        Ok(views)
+    }
+}
+
+/// Generate the genesis config and the node configs of a new dev-net in the work dir.
+fn generate_dev_net(opt: &StartOpt, work_dir: &Path) -> Result<DevNetManifest> {
+    // the dev genesis with the real time, so the blocks of the nodes can be verified by each other.
+    let mut genesis_config = DEV_CONFIG.clone();
+    genesis_config.time_service_type = TimeServiceType::RealTimeService;
+    genesis_config.consensus_config.strategy = opt.consensus.value();
+    genesis_config.consensus_config.base_block_time_target = opt.block_time;
+    genesis_config.consensus_config.min_block_time_target = opt.block_time;
+    let genesis_config_path = work_dir.join(DEV_NET_GENESIS_CONFIG_FILE);
+    genesis_config.save(genesis_config_path.as_path())?;
+
+    let net =
+        ChainNetworkID::from_str(format!("{}:{}", DEV_NET_CHAIN_NAME, opt.chain_id).as_str())?;
+    let mut nodes = vec![];
+    let mut next_port = opt.base_port;
+    let mut allocate_port = || {
+        let port = get_available_port_from(next_port);
+        next_port = port + 1;
+        port
+    };
+    for index in 1..=opt.nodes {
+        let node_dir = work_dir.join(format!("node{}", index));
+        let http_port = allocate_port();
+        let args: Vec<String> = vec![
+            "-n".to_string(),
+            net.to_string(),
+            "-d".to_string(),
+            path_to_string(node_dir.as_path())?,
+            "--genesis-config".to_string(),
+            path_to_string(genesis_config_path.as_path())?,
+            "--listen".to_string(),
+            format!("/ip4/127.0.0.1/tcp/{}", allocate_port()),
+            "--http-port".to_string(),
+            http_port.to_string(),
+            "--tcp-port".to_string(),
+            allocate_port().to_string(),
+            "--websocket-port".to_string(),
+            allocate_port().to_string(),
+            "--metrics-port".to_string(),
+            allocate_port().to_string(),
+            "--stratum-port".to_string(),
+            allocate_port().to_string(),
+        ];
+        // generate the node config and the network key, so the peer id of the node is known.
+        let node_opt = StarcoinOpt::from_iter_safe(
+            std::iter::once("starcoin".to_string()).chain(args.iter().cloned()),
+        )?;
+        let config = NodeConfig::load_with_opt(&node_opt)?;
+        nodes.push(DevNetNode {
+            index,
+            node_dir,
+            ipc_file: config.rpc.get_ipc_file(),
+            address: config.network.self_address().to_string(),
+            http_port,
+            args,
+        });
+    }
+    Ok(DevNetManifest { net, nodes })
+}
+
+fn path_to_string(path: &Path) -> Result<String> {
+    path.to_str()
+        .map(|path| path.to_string())
+        .ok_or_else(|| format_err!("Invalid path {:?}", path))
+}
+
+fn prefix_lines<R: Read + Send + 'static>(index: usize, reader: R) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || {
+        for line in BufReader::new(reader).lines() {
+            match line {
+                Ok(line) => eprintln!("[node{}] {}", index, line),
+                Err(_) => break,
+            }
+        }
+    })
+}
+
+/// Print the output of the nodes with the node prefix until all the nodes exit.
+fn stream_logs(children: Vec<(usize, Child)>) -> Result<()> {
+    let mut handles = vec![];
+    let mut waits = vec![];
+    for (index, mut child) in children {
+        if let Some(stdout) = child.stdout.take() {
+            handles.push(prefix_lines(index, stdout));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            handles.push(prefix_lines(index, stderr));
+        }
+        waits.push((index, child));
+    }
+    for (index, mut child) in waits {
+        let status = child.wait()?;
+        eprintln!("[node{}] exited with {}", index, status);
+    }
+    for handle in handles {
+        let _ = handle.join();
+    }
+    Ok(())
+}
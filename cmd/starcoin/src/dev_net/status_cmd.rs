@@ -0,0 +1,88 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dev_net::{default_work_dir, DevNetManifest, DevNetState};
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_rpc_client::RpcClient;
+use starcoin_types::block::BlockNumber;
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+
+/// Show the processes and the heads of the dev-net nodes.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "status")]
+pub struct StatusOpt {
+    #[structopt(long = "work-dir", parse(from_os_str))]
+    /// The dir of the dev-net, default is ~/.starcoin/dev-net
+    work_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeStatusView {
+    pub node: usize,
+    pub pid: Option<u32>,
+    pub running: bool,
+    pub address: String,
+    pub ipc_file: PathBuf,
+    /// None if the node is not running or its rpc is not ready.
+    pub head_number: Option<BlockNumber>,
+    pub head_hash: Option<HashValue>,
+    pub peers: Option<usize>,
+}
+
+pub struct StatusCommand;
+
+impl CommandAction for StatusCommand {
+    type State = DevNetState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = StatusOpt;
+    type ReturnItem = Vec<NodeStatusView>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let work_dir = ctx.opt().work_dir.clone().unwrap_or_else(default_work_dir);
+        let manifest = DevNetManifest::load(work_dir.as_path())?;
+        let mut views = vec![];
+        for node in &manifest.nodes {
+            let pid = node.pid()?;
+            let running = node.running_pid()?.is_some();
+            let (head, peers) = if running {
+                match query_node(node.ipc_file.as_path()) {
+                    Ok((head_number, head_hash, peers)) => {
+                        (Some((head_number, head_hash)), Some(peers))
+                    }
+                    Err(_) => (None, None),
+                }
+            } else {
+                (None, None)
+            };
+            views.push(NodeStatusView {
+                node: node.index,
+                pid,
+                running,
+                address: node.address.clone(),
+                ipc_file: node.ipc_file.clone(),
+                head_number: head.map(|(number, _)| number),
+                head_hash: head.map(|(_, hash)| hash),
+                peers,
+            });
+        }
+        Ok(views)
+    }
+}
+
+fn query_node(ipc_file: &Path) -> Result<(BlockNumber, HashValue, usize)> {
+    let client = RpcClient::connect_ipc(ipc_file)?;
+    let result = client.chain_info().and_then(|chain_info| {
+        let peers = client.node_peers()?.len();
+        Ok((chain_info.head.number.0, chain_info.head.block_hash, peers))
+    });
+    client.close();
+    result
+}
@@ -0,0 +1,66 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dev_net::{default_work_dir, stop_process, DevNetManifest, DevNetState};
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+use structopt::StructOpt;
+
+/// Stop the dev-net nodes by their pid files.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "stop")]
+pub struct StopOpt {
+    #[structopt(long = "work-dir", parse(from_os_str))]
+    /// The dir of the dev-net, default is ~/.starcoin/dev-net
+    work_dir: Option<PathBuf>,
+
+    #[structopt(long = "timeout", default_value = "10")]
+    /// Seconds to wait for a node to exit, the node is killed after it.
+    timeout: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoppedNodeView {
+    pub node: usize,
+    pub pid: u32,
+    /// False if the node does not exit in time and is killed.
+    pub graceful: bool,
+}
+
+pub struct StopCommand;
+
+impl CommandAction for StopCommand {
+    type State = DevNetState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = StopOpt;
+    type ReturnItem = Vec<StoppedNodeView>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let work_dir = opt.work_dir.clone().unwrap_or_else(default_work_dir);
+        let manifest = DevNetManifest::load(work_dir.as_path())?;
+        let mut views = vec![];
+        for node in &manifest.nodes {
+            if let Some(pid) = node.running_pid()? {
+                let graceful = stop_process(pid, Duration::from_secs(opt.timeout))?;
+                views.push(StoppedNodeView {
+                    node: node.index,
+                    pid,
+                    graceful,
+                });
+            }
+            let pid_file = node.pid_file();
+            if pid_file.exists() {
+                std::fs::remove_file(pid_file)?;
+            }
+        }
+        Ok(views)
+    }
+}
@@ -7,9 +7,11 @@ use scmd::{CmdContext, Command};
 pub mod account;
 pub mod chain;
 pub mod cli_state;
+pub mod completer;
 pub mod contract;
 pub mod debug;
 pub mod dev;
+pub mod dev_net;
 pub mod helper;
 pub mod mutlisig_transaction;
 pub mod node;
@@ -32,14 +34,22 @@ pub fn add_command(
                 .subcommand(account::TransferCommand)
                 .subcommand(account::AcceptTokenCommand)
                 .subcommand(account::ListCommand)
+                .subcommand(account::HistoryCommand)
                 .subcommand(account::PartialSignTxnCommand)
                 .subcommand(account::UnlockCommand)
                 .subcommand(account::ExportCommand)
                 .subcommand(account::ImportCommand)
+                .subcommand(account::ImportReadonlyCommand)
+                .subcommand(account::GenerateMnemonicCommand)
+                .subcommand(account::DeriveAddressCommand)
                 .subcommand(account::ExecuteScriptFunctionCmd)
                 .subcommand(account::LockCommand)
+                .subcommand(account::BindExternalSignerCommand)
                 .subcommand(account::ChangePasswordCmd)
+                .subcommand(account::RemoveCommand)
+                .subcommand(account::RotateKeyCommand)
                 .subcommand(account::SignMessageCmd)
+                .subcommand(account::SignTxnRequestCommand)
                 .subcommand(account::VerifySignMessageCmd)
                 .subcommand(account::DefaultCommand),
         )
@@ -53,6 +63,7 @@ pub fn add_command(
         .command(
             Command::with_name("node")
                 .subcommand(node::InfoCommand)
+                .subcommand(node::HealthCommand)
                 .subcommand(node::PeersCommand)
                 .subcommand(node::MetricsCommand)
                 .subcommand(
@@ -60,6 +71,7 @@ pub fn add_command(
                         .subcommand(node::service::ListCommand)
                         .subcommand(node::service::StartCommand)
                         .subcommand(node::service::CheckCommand)
+                        .subcommand(node::service::HealthCommand)
                         .subcommand(node::service::StopCommand)
                     //TODO support shutdown by command    
                     //.subcommand(node::service::ShutdownSystemCommand),
@@ -72,12 +84,34 @@ pub fn add_command(
                         .subcommand(node::sync::CancelCommand)
                         .subcommand(node::sync::PeerScoreCommand)
                 )
+                .subcommand(
+                    Command::with_name("checkpoints")
+                        .subcommand(node::checkpoints::ExportCommand)
+                        .subcommand(node::checkpoints::VerifyCommand)
+                )
+                .subcommand(
+                    Command::with_name("config")
+                        .subcommand(node::config::ShowCommand)
+                        .subcommand(node::config::ReloadCommand)
+                )
+                .subcommand(
+                    Command::with_name("index")
+                        .subcommand(node::index::RebuildTokenHoldersCommand)
+                )
+                .subcommand(
+                    Command::with_name("webhook")
+                        .subcommand(node::webhook::RegisterCommand)
+                        .subcommand(node::webhook::ListCommand)
+                        .subcommand(node::webhook::RemoveCommand)
+                )
                 .subcommand(
                 Command::with_name("network")
                     .subcommand(node::network::StateCommand)
                     .subcommand(node::network::KnownPeersCommand)
                     .subcommand(node::network::GetAddressCommand)
                     .subcommand(node::network::AddPeerCommand)
+                    .subcommand(node::network::RemovePeerCommand)
+                    .subcommand(node::network::BanPeerCommand)
                     .subcommand(node::network::CallPeerCommand)
             ),
         )
@@ -89,12 +123,16 @@ pub fn add_command(
                 .subcommand(chain::GetTransactionCommand)
                 .subcommand(chain::GetTxnByBlockCommand)
                 .subcommand(chain::GetTransactionInfoCommand)
+                .subcommand(chain::GetTxnProofCommand)
                 .subcommand(chain::GetEventsCommand)
                 .subcommand(chain::GetBlockCommand)
                 .subcommand(chain::EpochInfoCommand)
                 .subcommand(chain::GetEpochInfoByNumberCommand)
                 .subcommand(chain::GetGlobalTimeByNumberCommand)
                 .subcommand(chain::TPSCommand)
+                .subcommand(chain::RewardsCommand)
+                .subcommand(chain::ExportTransfersCommand)
+                .subcommand(chain::ExportBlockCommand)
                 .subcommand(
                     Command::with_name("uncle")
                         .subcommand(chain::uncle::UnclePathCommand)
@@ -118,7 +156,10 @@ pub fn add_command(
             Command::with_name("txpool")
                 .subcommand(txpool::PendingTxnCommand)
                 .subcommand(txpool::PendingTxnsCommand)
-                .subcommand(txpool::TxPoolStatusCommand),
+                .subcommand(txpool::TxPoolStatusCommand)
+                .subcommand(txpool::SubmitHexCommand)
+                .subcommand(txpool::DiagnoseCommand)
+                .subcommand(txpool::RebroadcastCommand),
         )
         .command(
             Command::with_name("dev")
@@ -127,6 +168,7 @@ pub fn add_command(
                 .subcommand(dev::DeployCommand)
                 .subcommand(dev::ExecuteCommand)
                 .subcommand(dev::DeriveAddressCommand)
+                .subcommand(dev::GenesisAccountsCommand)
                 .subcommand(dev::GenerateMultisigTxnCommand)
                 .subcommand(dev::ExecuteMultiSignedTxnCommand)
                 .subcommand(dev::UpgradeModuleProposalCommand)
@@ -136,7 +178,16 @@ pub fn add_command(
                 .subcommand(dev::UpgradeModuleQueueV2Command)
                 .subcommand(dev::UpgradeModuleExeCommand)
                 .subcommand(dev::UpgradeVMConfigProposalCommand)
+                .subcommand(
+                    Command::with_name("upgrade")
+                        .subcommand(dev::UpgradePlanCommand)
+                        .subcommand(dev::UpgradeStatusCommand)
+                        .subcommand(dev::UpgradeExecuteCommand),
+                )
                 .subcommand(dev::CallContractCommand)
+                .subcommand(dev::SleepCommand)
+                .subcommand(dev::GenBlockCommand)
+                .subcommand(dev::ReplayBlockCommand)
                 .subcommand(
                     Command::with_name("subscribe")
                         .subcommand(dev::SubscribeBlockCommand)
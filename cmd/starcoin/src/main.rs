@@ -3,6 +3,7 @@
 use anyhow::Result;
 use scmd::error::CmdError;
 use scmd::CmdContext;
+use starcoin_cmd::completer::StarcoinCompleter;
 use starcoin_cmd::*;
 use starcoin_cmd::{CliState, StarcoinOpt};
 use starcoin_config::{Connect, APP_VERSION, CRATE_VERSION};
@@ -19,10 +20,23 @@ static EXIT_CODE_NEED_HELP: i32 = 120;
 
 fn run() -> Result<()> {
     let logger_handle = starcoin_logger::init();
+    // the dev-net commands manage the node processes, and do not start or connect a node.
+    let args: Vec<_> = std::env::args().collect();
+    if dev_net::is_dev_net_command(&args) {
+        return dev_net::exec_dev_net();
+    }
+    // the snapshot commands open the storage of the data dir, so the node should not be started.
+    if node::snapshot::is_snapshot_command(&args) {
+        return node::snapshot::exec_snapshot();
+    }
+    let initializer_logger_handle = logger_handle.clone();
     let context = CmdContext::<CliState, StarcoinOpt>::with_default_action(
         CRATE_VERSION,
         Some(APP_VERSION.as_str()),
-        |opt| -> Result<CliState> {
+        move |opt| -> Result<CliState> {
+            if scmd::is_quiet() {
+                initializer_logger_handle.update_level(LevelFilter::Error);
+            }
             info!("Starcoin opts: {}", opt);
             let connect = opt.connect.as_ref().unwrap_or(&Connect::IPC(None));
             let (client, node_handle) = match connect {
@@ -71,6 +85,17 @@ fn run() -> Result<()> {
             };
 
             let node_info = client.node_info()?;
+            if let Some(expected_chain_id) = opt.chain {
+                let node_chain_id = node_info.net.chain_id();
+                if expected_chain_id != node_chain_id {
+                    let msg = format!(
+                        "The connected node is on chain {}({}), but the cli expects chain {}, please check the node you connect to!",
+                        node_info.net, node_chain_id, expected_chain_id
+                    );
+                    warn!("{}", msg);
+                    eprintln!("WARNING: {}", msg);
+                }
+            }
             let state = CliState::new(
                 node_info.net,
                 Arc::new(client),
@@ -96,21 +121,23 @@ fn run() -> Result<()> {
             }
         },
     );
-    let context = context.with_console_support(
-        move |_app, _opt, state| {
-            info!("Start console, disable stderr output.");
-            logger_handle.disable_stderr();
-            (*scmd::DEFAULT_CONSOLE_CONFIG, Some(state.history_file()))
-        },
-        |_, _, state| {
-            let (_, _, handle) = state.into_inner();
-            if let Some(handle) = handle {
-                if let Err(e) = handle.stop() {
-                    error!("{:?}", e);
+    let context = context
+        .with_console_completer(|state| Box::new(StarcoinCompleter::new(state.shared_client())))
+        .with_console_support(
+            move |_app, _opt, state| {
+                info!("Start console, disable stderr output.");
+                logger_handle.disable_stderr();
+                (*scmd::DEFAULT_CONSOLE_CONFIG, Some(state.history_file()))
+            },
+            |_, _, state| {
+                let (_, _, handle) = state.into_inner();
+                if let Some(handle) = handle {
+                    if let Err(e) = handle.stop() {
+                        error!("{:?}", e);
+                    }
                 }
-            }
-        },
-    );
+            },
+        );
     add_command(context).exec()
 }
 
@@ -0,0 +1,93 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{ensure, format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
+use starcoin_crypto::ValidCryptoMaterialStringExt;
+use starcoin_types::block::BlockNumber;
+use starcoin_types::checkpoint::{Checkpoint, CheckpointSet};
+use starcoin_types::genesis_config::ChainId;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Export the main chain blocks every `--interval` blocks as the signed checkpoints file,
+/// which is the `config/checkpoints/<network>.json` shipped for the builtin network.
+/// The blocks within `--confirmations` of the head are not exported, as they may be rolled back.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "export")]
+pub struct ExportOpt {
+    #[structopt(long = "interval", default_value = "10000")]
+    /// the block number interval of the checkpoints.
+    interval: BlockNumber,
+    #[structopt(long = "confirmations", default_value = "1000")]
+    /// the latest checkpoint is at least `confirmations` blocks below the head.
+    confirmations: BlockNumber,
+    #[structopt(long = "private-key-file", parse(from_os_str))]
+    /// the file of the hex encoded ed25519 private key of the checkpoints signer.
+    private_key_file: PathBuf,
+    #[structopt(long = "output", short = "o", parse(from_os_str))]
+    output: PathBuf,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExportCheckpointsView {
+    pub chain_id: ChainId,
+    pub checkpoints: usize,
+    pub latest_checkpoint: Option<BlockNumber>,
+    /// The hex encoded public key of the signer, which is the `sync.checkpoints-signer` to verify the file.
+    pub signer: String,
+    pub output: PathBuf,
+}
+
+pub struct ExportCommand;
+
+impl CommandAction for ExportCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ExportOpt;
+    type ReturnItem = ExportCheckpointsView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        ensure!(opt.interval > 0, "The interval should be greater than 0.");
+        let private_key = Ed25519PrivateKey::from_encoded_string(
+            std::fs::read_to_string(opt.private_key_file.as_path())?.trim(),
+        )?;
+        let client = ctx.state().client();
+        let chain_info = client.chain_info()?;
+        let chain_id: ChainId = chain_info.chain_id.into();
+        let end = chain_info.head.number.0.saturating_sub(opt.confirmations);
+        let mut checkpoints = vec![];
+        let mut number = opt.interval;
+        while number <= end {
+            let header = client
+                .chain_get_block_by_number(number, None)?
+                .ok_or_else(|| format_err!("Can not find block by number {}", number))?
+                .header;
+            checkpoints.push(Checkpoint::new(
+                number,
+                header.block_hash,
+                header.state_root,
+            ));
+            number += opt.interval;
+        }
+        let checkpoint_set = CheckpointSet::new(chain_id, checkpoints)?;
+        let view = ExportCheckpointsView {
+            chain_id,
+            checkpoints: checkpoint_set.checkpoints().len(),
+            latest_checkpoint: checkpoint_set.latest().map(|checkpoint| checkpoint.number),
+            signer: hex::encode(Ed25519PublicKey::from(&private_key).to_bytes()),
+            output: opt.output.clone(),
+        };
+        let signed = checkpoint_set.sign(&private_key);
+        std::fs::write(opt.output.as_path(), serde_json::to_string_pretty(&signed)?)?;
+        Ok(view)
+    }
+}
@@ -0,0 +1,8 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+mod export_cmd;
+mod verify_cmd;
+
+pub use export_cmd::*;
+pub use verify_cmd::*;
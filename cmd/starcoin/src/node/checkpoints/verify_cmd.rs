@@ -0,0 +1,31 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_rpc_api::node::CheckpointsReport;
+use structopt::StructOpt;
+
+/// Re-verify the local main chain blocks at or below the head against the checkpoints of the node,
+/// and report the mismatched checkpoints. The command requires the ipc connection.
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "verify")]
+pub struct VerifyOpt {}
+
+pub struct VerifyCommand;
+
+impl CommandAction for VerifyCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = VerifyOpt;
+    type ReturnItem = CheckpointsReport;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        ctx.state().client().node_verify_checkpoints()
+    }
+}
@@ -0,0 +1,8 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+mod reload_cmd;
+mod show_cmd;
+
+pub use reload_cmd::*;
+pub use show_cmd::*;
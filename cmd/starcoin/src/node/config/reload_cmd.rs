@@ -0,0 +1,32 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_config::ConfigReloadResult;
+use structopt::StructOpt;
+
+/// Reload the node config file, and apply the hot reloadable fields.
+/// The other changed fields are ignored, they take effect after restart. The command requires the ipc connection.
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "reload")]
+pub struct ReloadOpt {}
+
+pub struct ReloadCommand;
+
+impl CommandAction for ReloadCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ReloadOpt;
+    type ReturnItem = ConfigReloadResult;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.node_reload_config()
+    }
+}
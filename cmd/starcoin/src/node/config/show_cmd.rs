@@ -0,0 +1,31 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use serde_json::Value;
+use structopt::StructOpt;
+
+/// Show the effective runtime config of the node, the secrets are redacted, the command requires the ipc connection.
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "show")]
+pub struct ShowOpt {}
+
+pub struct ShowCommand;
+
+impl CommandAction for ShowCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ShowOpt;
+    type ReturnItem = Value;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.node_config()
+    }
+}
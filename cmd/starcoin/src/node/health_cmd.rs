@@ -0,0 +1,30 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_rpc_api::node::HealthView;
+use structopt::StructOpt;
+
+/// Show the readiness of the node, which is also served by the http `GET /health`.
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "health")]
+pub struct HealthOpt {}
+
+pub struct HealthCommand;
+
+impl CommandAction for HealthCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = HealthOpt;
+    type ReturnItem = HealthView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        ctx.state().client().node_health()
+    }
+}
@@ -0,0 +1,6 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+mod rebuild_token_holders_cmd;
+
+pub use rebuild_token_holders_cmd::*;
@@ -0,0 +1,31 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use structopt::StructOpt;
+
+/// Rebuild the token holder index from the state of the main chain head, and return the count of the indexed holders.
+/// The index must be enabled by the `--token-holder-index true` option of the node, and the command requires the ipc connection.
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "rebuild-token-holders")]
+pub struct RebuildTokenHoldersOpt {}
+
+pub struct RebuildTokenHoldersCommand;
+
+impl CommandAction for RebuildTokenHoldersCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = RebuildTokenHoldersOpt;
+    type ReturnItem = u64;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.node_rebuild_token_holder_index()
+    }
+}
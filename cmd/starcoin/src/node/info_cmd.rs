@@ -5,12 +5,66 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
-use starcoin_rpc_api::node::NodeInfo;
+use serde::Serialize;
+use starcoin_config::ChainNetworkID;
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::node::{check_api_versions, ApiVersionMismatch, SystemInfo};
+use starcoin_rpc_api::types::{ChainStatusView, TxPoolSummaryView};
+use starcoin_types::peer_info::PeerId;
+use starcoin_vm_types::genesis_config::ConsensusStrategy;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt, Default)]
 #[structopt(name = "info")]
-pub struct InfoOpt {}
+pub struct InfoOpt {
+    /// also show the storage size, disk usage and process resource usage of the node.
+    #[structopt(long = "system")]
+    system: bool,
+}
+
+/// The summary of the chain status, the fields of the unavailable services are None.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainSummaryView {
+    pub head_number: u64,
+    pub head_hash: HashValue,
+    /// Seconds since the head block is generated.
+    pub head_age_secs: u64,
+    pub txpool: Option<TxPoolSummaryView>,
+    /// The target block number if the node is syncing.
+    pub sync_target: Option<u64>,
+    pub sync_eta_seconds: Option<u64>,
+    pub peers: Option<u32>,
+}
+
+impl From<ChainStatusView> for ChainSummaryView {
+    fn from(status: ChainStatusView) -> Self {
+        Self {
+            head_number: status.head.number.0,
+            head_hash: status.head.block_hash,
+            head_age_secs: status
+                .now_seconds
+                .saturating_sub(status.head.timestamp.0 / 1000),
+            txpool: status.txpool,
+            sync_target: status.sync.as_ref().map(|sync| sync.target_number.0),
+            sync_eta_seconds: status.sync.as_ref().and_then(|sync| sync.eta_seconds),
+            peers: status.peers,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeInfoView {
+    pub peer_id: PeerId,
+    pub self_address: String,
+    pub net: ChainNetworkID,
+    pub consensus: ConsensusStrategy,
+    pub version: String,
+    pub chain: ChainSummaryView,
+    /// The rpc namespaces whose versions differ between the node and this cli.
+    pub incompatible_apis: Vec<ApiVersionMismatch>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<SystemInfo>,
+}
 
 pub struct InfoCommand;
 
@@ -18,7 +72,7 @@ impl CommandAction for InfoCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = InfoOpt;
-    type ReturnItem = NodeInfo;
+    type ReturnItem = NodeInfoView;
 
     fn run(
         &self,
@@ -26,6 +80,22 @@ impl CommandAction for InfoCommand {
     ) -> Result<Self::ReturnItem> {
         let client = ctx.state().client();
         let node_info = client.node_info()?;
-        Ok(node_info)
+        let chain_status = client.chain_info()?;
+        let incompatible_apis = check_api_versions(&node_info.api_versions);
+        let system = if ctx.opt().system {
+            Some(client.node_system_info()?)
+        } else {
+            None
+        };
+        Ok(NodeInfoView {
+            peer_id: node_info.peer_info.peer_id,
+            self_address: node_info.self_address,
+            net: node_info.net,
+            consensus: node_info.consensus,
+            version: node_info.build.version,
+            chain: chain_status.into(),
+            incompatible_apis,
+            system,
+        })
     }
 }
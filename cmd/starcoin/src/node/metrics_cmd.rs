@@ -5,12 +5,64 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use starcoin_rpc_api::types::MetricView;
 use structopt::StructOpt;
 
+/// Show the node metrics.
 #[derive(Debug, StructOpt)]
 #[structopt(name = "metrics")]
-pub struct MetricsOpt {}
+pub struct MetricsOpt {
+    /// Only show the metrics whose name starts with the filter.
+    #[structopt(name = "filter")]
+    filter: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MetricItem {
+    pub name: String,
+    pub metric_type: String,
+    pub value: Option<f64>,
+    pub count: Option<u64>,
+    pub sum: Option<f64>,
+    pub p50: Option<f64>,
+    pub p90: Option<f64>,
+    pub p99: Option<f64>,
+}
+
+impl MetricItem {
+    fn new(name: String, metric: MetricView) -> Self {
+        let mut item = Self {
+            name,
+            metric_type: String::new(),
+            value: None,
+            count: None,
+            sum: None,
+            p50: None,
+            p90: None,
+            p99: None,
+        };
+        match metric {
+            MetricView::Counter { value } => {
+                item.metric_type = "counter".to_string();
+                item.value = Some(value);
+            }
+            MetricView::Gauge { value } => {
+                item.metric_type = "gauge".to_string();
+                item.value = Some(value);
+            }
+            MetricView::Histogram(histogram) => {
+                item.metric_type = "histogram".to_string();
+                item.count = Some(histogram.count);
+                item.sum = Some(histogram.sum);
+                item.p50 = Some(histogram.p50);
+                item.p90 = Some(histogram.p90);
+                item.p99 = Some(histogram.p99);
+            }
+        }
+        item
+    }
+}
 
 pub struct MetricsCommand;
 
@@ -18,14 +70,17 @@ impl CommandAction for MetricsCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = MetricsOpt;
-    type ReturnItem = HashMap<String, String>;
+    type ReturnItem = Vec<MetricItem>;
 
     fn run(
         &self,
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<Self::ReturnItem> {
         let client = ctx.state().client();
-        let node_info = client.node_metrics()?;
-        Ok(node_info)
+        let metrics = client.node_metrics(ctx.opt().filter.clone())?;
+        Ok(metrics
+            .into_iter()
+            .map(|(name, metric)| MetricItem::new(name, metric))
+            .collect())
     }
 }
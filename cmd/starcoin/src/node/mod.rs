@@ -1,14 +1,21 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+mod health_cmd;
 mod info_cmd;
 mod metrics_cmd;
 mod peers_cmd;
 
+pub mod checkpoints;
+pub mod config;
+pub mod index;
 pub mod network;
 pub mod service;
+pub mod snapshot;
 pub mod sync;
+pub mod webhook;
 
+pub use health_cmd::*;
 pub use info_cmd::*;
 pub use metrics_cmd::*;
 pub use peers_cmd::*;
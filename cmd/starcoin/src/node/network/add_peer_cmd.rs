@@ -8,7 +8,7 @@ use scmd::{CommandAction, ExecContext};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt, Default)]
-#[structopt(name = "add_peer")]
+#[structopt(name = "add_peer", alias = "add-peer")]
 ///Add a known peer
 pub struct AddPeerOpt {
     #[structopt(name = "peer")]
@@ -29,6 +29,6 @@ impl CommandAction for AddPeerCommand {
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<Self::ReturnItem> {
         let client = ctx.state().client();
-        client.network_add_peer(ctx.opt().peer.clone())
+        client.node_add_peer(ctx.opt().peer.clone())
     }
 }
@@ -0,0 +1,38 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_types::peer_info::PeerId;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "ban_peer", alias = "ban-peer")]
+///Disconnect the peer and reject its connection until the ban expires
+pub struct BanPeerOpt {
+    #[structopt(name = "peer-id")]
+    peer_id: PeerId,
+    #[structopt(short = "d", long = "duration")]
+    /// ban duration in seconds, default is one hour
+    duration_secs: Option<u64>,
+}
+
+pub struct BanPeerCommand;
+
+impl CommandAction for BanPeerCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = BanPeerOpt;
+    type ReturnItem = ();
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        let opt = ctx.opt();
+        client.node_ban_peer(opt.peer_id.clone(), opt.duration_secs)
+    }
+}
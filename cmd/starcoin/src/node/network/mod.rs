@@ -2,13 +2,17 @@
 // Copyright (c) The Starcoin Core Contributors
 
 mod add_peer_cmd;
+mod ban_peer_cmd;
 mod call_peer_cmd;
 mod get_address_cmd;
 mod known_peers_cmd;
+mod remove_peer_cmd;
 mod state_cmd;
 
 pub use add_peer_cmd::*;
+pub use ban_peer_cmd::*;
 pub use call_peer_cmd::*;
 pub use get_address_cmd::*;
 pub use known_peers_cmd::*;
+pub use remove_peer_cmd::*;
 pub use state_cmd::*;
@@ -5,12 +5,16 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
-use starcoin_rpc_api::types::PeerInfoView;
+use serde_json::Value;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 #[structopt(name = "peers")]
-pub struct PeersOpt {}
+pub struct PeersOpt {
+    /// Show the seeds resolved from the dns seeds instead of the connected peers.
+    #[structopt(long = "discovered")]
+    discovered: bool,
+}
 
 pub struct PeersCommand;
 
@@ -18,14 +22,17 @@ impl CommandAction for PeersCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = PeersOpt;
-    type ReturnItem = Vec<PeerInfoView>;
+    type ReturnItem = Value;
 
     fn run(
         &self,
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<Self::ReturnItem> {
         let client = ctx.state().client();
-        let peers = client.node_peers()?;
-        Ok(peers)
+        if ctx.opt().discovered {
+            Ok(serde_json::to_value(client.node_discovered_seeds()?)?)
+        } else {
+            Ok(serde_json::to_value(client.node_peers()?)?)
+        }
     }
 }
@@ -0,0 +1,45 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{format_err, Result};
+use scmd::{CommandAction, ExecContext};
+use starcoin_rpc_api::node::ServiceHealthView;
+use structopt::StructOpt;
+
+/// Show the health of every service by the last watchdog check.
+#[derive(Debug, StructOpt, Default)]
+#[structopt(name = "health")]
+pub struct HealthOpt {
+    /// Only show the unhealthy services.
+    #[structopt(long = "unhealthy")]
+    unhealthy: bool,
+}
+
+pub struct HealthCommand;
+
+impl CommandAction for HealthCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = HealthOpt;
+    type ReturnItem = Vec<ServiceHealthView>;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let report = ctx
+            .state()
+            .client()
+            .node_watchdog_status()?
+            .ok_or_else(|| {
+                format_err!("The watchdog is disabled or has not checked the services yet.")
+            })?;
+        Ok(report
+            .services
+            .into_iter()
+            .filter(|service| !ctx.opt().unhealthy || !service.healthy)
+            .collect())
+    }
+}
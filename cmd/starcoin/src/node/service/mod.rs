@@ -2,12 +2,14 @@
 // Copyright (c) The Starcoin Core Contributors
 
 mod check_cmd;
+mod health_cmd;
 mod list_cmd;
 mod shutdown_cmd;
 mod start_cmd;
 mod stop_cmd;
 
 pub use check_cmd::*;
+pub use health_cmd::*;
 pub use list_cmd::*;
 pub use shutdown_cmd::*;
 pub use start_cmd::*;
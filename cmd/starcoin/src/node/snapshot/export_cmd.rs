@@ -0,0 +1,45 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::node::snapshot::{export_snapshot, open_storage, SnapshotManifest, SnapshotState};
+use crate::StarcoinOpt;
+use anyhow::{bail, Result};
+use scmd::{CommandAction, ExecContext};
+use starcoin_config::NodeConfig;
+use starcoin_types::block::BlockNumber;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Export the blocks and the chain state of data_dir to a snapshot, the node should be stopped.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "export")]
+pub struct ExportOpt {
+    /// The snapshot output dir.
+    #[structopt(long, short = "o", parse(from_os_str))]
+    output: PathBuf,
+    /// Export the snapshot at the block number, default is the head block.
+    #[structopt(long)]
+    height: Option<BlockNumber>,
+}
+
+pub struct ExportCommand;
+
+impl CommandAction for ExportCommand {
+    type State = SnapshotState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ExportOpt;
+    type ReturnItem = SnapshotManifest;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let config = NodeConfig::load_with_opt(ctx.global_opt())?;
+        if config.base().base_data_dir().is_temp() {
+            bail!("Please set data_dir option.")
+        }
+        let storage = open_storage(&config)?;
+        export_snapshot(config.net(), storage, opt.height, opt.output.as_path())
+    }
+}
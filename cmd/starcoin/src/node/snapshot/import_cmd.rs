@@ -0,0 +1,52 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::node::snapshot::{
+    import_snapshot, open_storage, verify_snapshot, SnapshotManifest, SnapshotState,
+};
+use crate::StarcoinOpt;
+use anyhow::{bail, Result};
+use scmd::{CommandAction, ExecContext};
+use starcoin_config::NodeConfig;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Import a snapshot to an empty data_dir, the node can start from the snapshot block after import.
+/// The snapshot is rejected if it belongs to another network.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "import")]
+pub struct ImportOpt {
+    /// The snapshot dir.
+    #[structopt(long, short = "i", parse(from_os_str))]
+    input: PathBuf,
+    /// Only verify the snapshot files, they are imported to a temporary db instead of data_dir.
+    #[structopt(long)]
+    verify_only: bool,
+}
+
+pub struct ImportCommand;
+
+impl CommandAction for ImportCommand {
+    type State = SnapshotState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ImportOpt;
+    type ReturnItem = SnapshotManifest;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let config = NodeConfig::load_with_opt(ctx.global_opt())?;
+        if opt.verify_only {
+            return verify_snapshot(&config, opt.input.as_path());
+        }
+        if config.base().base_data_dir().is_temp() {
+            bail!("Please set data_dir option.")
+        }
+        let storage = open_storage(&config)?;
+        let (manifest, genesis) = import_snapshot(config.net(), storage, opt.input.as_path())?;
+        genesis.save(config.data_dir())?;
+        Ok(manifest)
+    }
+}
@@ -0,0 +1,617 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A snapshot is a directory with three files:
+//! `manifest.json`, the `SnapshotManifest`;
+//! `blocks.bcs`, the `BlockData` of every block from genesis to the snapshot block;
+//! `state.bcs`, the `(AccountAddress, AccountStateSet)` of every account at the snapshot block.
+//! Every record in the bcs files is prefixed by its length as a little endian u32.
+//!
+//! The snapshot commands open the storage of the data dir directly, so they run without a node,
+//! and the node of the data dir should be stopped.
+
+use crate::StarcoinOpt;
+use anyhow::{bail, ensure, format_err, Result};
+use scmd::{CmdContext, Command};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::{Accumulator, MerkleAccumulator};
+use starcoin_chain::{BlockChain, ChainReader};
+use starcoin_config::{ChainNetwork, NodeConfig, APP_VERSION, CRATE_VERSION};
+use starcoin_crypto::HashValue;
+use starcoin_genesis::Genesis;
+use starcoin_state_api::{ChainStateReader, ChainStateWriter};
+use starcoin_statedb::ChainStateDB;
+use starcoin_storage::cache_storage::CacheStorage;
+use starcoin_storage::db_storage::DBStorage;
+use starcoin_storage::storage::StorageInstance;
+use starcoin_storage::{BlockStore, Storage, Store};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block::{Block, BlockInfo, BlockNumber};
+use starcoin_types::contract_event::ContractEvent;
+use starcoin_types::genesis_config::{ChainId, StdlibVersion};
+use starcoin_types::startup_info::StartupInfo;
+use starcoin_types::state_set::{AccountStateSet, ChainStateSet};
+use starcoin_types::transaction::{Transaction, TransactionInfo};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+mod export_cmd;
+mod import_cmd;
+
+pub use export_cmd::*;
+pub use import_cmd::*;
+
+pub const SNAPSHOT_COMMAND: &str = "snapshot";
+pub const SNAPSHOT_VERSION: u64 = 1;
+pub const MANIFEST_FILE: &str = "manifest.json";
+pub const BLOCKS_FILE: &str = "blocks.bcs";
+pub const STATE_FILE: &str = "state.bcs";
+
+/// Print the progress every `PROGRESS_INTERVAL` records.
+const PROGRESS_INTERVAL: u64 = 1000;
+/// How many accounts are applied to the state db at once.
+const STATE_APPLY_BATCH_SIZE: usize = 1000;
+/// The max size of a record, a corrupted length prefix is rejected before allocating the buffer.
+pub const MAX_RECORD_SIZE: usize = 256 * 1024 * 1024;
+
+/// The snapshot commands do not connect to a node, so they have no state.
+pub struct SnapshotState;
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct SnapshotManifest {
+    pub version: u64,
+    pub chain_id: ChainId,
+    pub stdlib_version: StdlibVersion,
+    pub genesis_hash: HashValue,
+    pub block_number: BlockNumber,
+    pub block_hash: HashValue,
+    pub state_root: HashValue,
+    pub block_count: u64,
+    pub account_count: u64,
+}
+
+impl SnapshotManifest {
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(MANIFEST_FILE);
+        let file = File::open(&path)
+            .map_err(|e| format_err!("Open snapshot manifest {} error: {}", path.display(), e))?;
+        Ok(serde_json::from_reader(BufReader::new(file))?)
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        std::fs::write(dir.join(MANIFEST_FILE), serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Check the snapshot can be imported to the `net`.
+    pub fn check(&self, net: &ChainNetwork, genesis_hash: HashValue) -> Result<()> {
+        ensure!(
+            self.version == SNAPSHOT_VERSION,
+            "Unsupported snapshot version {}, expect {}",
+            self.version,
+            SNAPSHOT_VERSION
+        );
+        ensure!(
+            self.chain_id == net.chain_id(),
+            "The snapshot belongs to chain {}, but the network {} is chain {}",
+            self.chain_id,
+            net.id(),
+            net.chain_id()
+        );
+        ensure!(
+            self.stdlib_version == net.stdlib_version(),
+            "The snapshot stdlib version {:?} mismatch with the network stdlib version {:?}",
+            self.stdlib_version,
+            net.stdlib_version()
+        );
+        ensure!(
+            self.genesis_hash == genesis_hash,
+            "The snapshot genesis {} mismatch with the network genesis {}",
+            self.genesis_hash,
+            genesis_hash
+        );
+        Ok(())
+    }
+}
+
+/// A block and everything saved with it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BlockData {
+    pub block: Block,
+    pub block_info: BlockInfo,
+    pub transactions: Vec<Transaction>,
+    pub txn_infos: Vec<TransactionInfo>,
+    pub events: Vec<Vec<ContractEvent>>,
+}
+
+pub struct RecordWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> RecordWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    pub fn write<T: Serialize>(&mut self, record: &T) -> Result<()> {
+        let bytes = bcs_ext::to_bytes(record)?;
+        self.writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+        self.writer.write_all(bytes.as_slice())?;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+pub struct RecordReader<R: Read> {
+    reader: R,
+}
+
+impl<R: Read> RecordReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// Read the next record, return None at the end of the file.
+    pub fn read<T: DeserializeOwned>(&mut self) -> Result<Option<T>> {
+        let mut len = [0u8; 4];
+        match self.reader.read_exact(&mut len) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+        let len = u32::from_le_bytes(len) as usize;
+        ensure!(
+            len <= MAX_RECORD_SIZE,
+            "The snapshot record size {} exceeds the max size {}",
+            len,
+            MAX_RECORD_SIZE
+        );
+        let mut bytes = vec![0u8; len];
+        self.reader
+            .read_exact(bytes.as_mut_slice())
+            .map_err(|e| format_err!("Read snapshot record error: {}", e))?;
+        bcs_ext::from_bytes(bytes.as_slice())
+    }
+}
+
+/// Export the blocks from genesis to the block at `height` (default is head) and the state at it.
+pub fn export_snapshot(
+    net: &ChainNetwork,
+    storage: Arc<Storage>,
+    height: Option<BlockNumber>,
+    output: &Path,
+) -> Result<SnapshotManifest> {
+    let startup_info = storage
+        .get_startup_info()?
+        .ok_or_else(|| format_err!("Startup info is none, the storage is not initialized."))?;
+    let chain = BlockChain::new(
+        net.time_service(),
+        startup_info.main,
+        storage.clone() as Arc<dyn Store>,
+    )?;
+    let head = chain.current_header();
+    let height = height.unwrap_or_else(|| head.number());
+    ensure!(
+        height <= head.number(),
+        "The height {} is greater than the head block number {}",
+        height,
+        head.number()
+    );
+    std::fs::create_dir_all(output)?;
+
+    let mut writer = RecordWriter::new(BufWriter::new(File::create(output.join(BLOCKS_FILE))?));
+    let mut snapshot_block = None;
+    for number in 0..=height {
+        let block = chain
+            .get_block_by_number(number)?
+            .ok_or_else(|| format_err!("Can not find block by number {}", number))?;
+        let block_data = read_block_data(storage.as_ref(), block)?;
+        writer.write(&block_data)?;
+        if number % PROGRESS_INTERVAL == 0 || number == height {
+            scmd::side_println!("Exported block {}/{}", number, height);
+        }
+        snapshot_block = Some(block_data.block);
+    }
+    writer.flush()?;
+    let snapshot_block =
+        snapshot_block.ok_or_else(|| format_err!("Snapshot block should exist."))?;
+
+    let state_root = snapshot_block.header().state_root();
+    let statedb = ChainStateDB::new(storage.clone(), Some(state_root));
+    let mut writer = RecordWriter::new(BufWriter::new(File::create(output.join(STATE_FILE))?));
+    let mut account_count = 0u64;
+    // visit the accounts one by one, the whole state does not fit in memory on a large chain.
+    statedb.for_each_account_state_set(|address, state_set| {
+        writer.write(&(address, state_set))?;
+        account_count += 1;
+        if account_count % PROGRESS_INTERVAL == 0 {
+            scmd::side_println!("Exported account {}", account_count);
+        }
+        Ok(())
+    })?;
+    writer.flush()?;
+    scmd::side_println!("Exported account {}", account_count);
+
+    let manifest = SnapshotManifest {
+        version: SNAPSHOT_VERSION,
+        chain_id: net.chain_id(),
+        stdlib_version: net.stdlib_version(),
+        genesis_hash: chain.info().genesis_hash(),
+        block_number: snapshot_block.header().number(),
+        block_hash: snapshot_block.id(),
+        state_root,
+        block_count: height + 1,
+        account_count,
+    };
+    manifest.save(output)?;
+    Ok(manifest)
+}
+
+fn read_block_data(storage: &Storage, block: Block) -> Result<BlockData> {
+    let block_id = block.id();
+    let block_info = storage
+        .get_block_info(block_id)?
+        .ok_or_else(|| format_err!("Can not find block info of block {}", block_id))?;
+    let mut transactions = vec![];
+    let mut txn_infos = vec![];
+    let mut events = vec![];
+    for txn_info in storage.get_block_transaction_infos(block_id)? {
+        let info_id = txn_info.id();
+        let (_, txn_info): (HashValue, TransactionInfo) = txn_info.into();
+        let txn_hash = txn_info.transaction_hash();
+        transactions.push(
+            storage
+                .get_transaction(txn_hash)?
+                .ok_or_else(|| format_err!("Can not find transaction {}", txn_hash))?,
+        );
+        events.push(storage.get_contract_events(info_id)?.unwrap_or_default());
+        txn_infos.push(txn_info);
+    }
+    Ok(BlockData {
+        block,
+        block_info,
+        transactions,
+        txn_infos,
+        events,
+    })
+}
+
+/// Check the events are the events accumulated in the `txn_info`.
+fn verify_event_root(txn_info: &TransactionInfo, events: &[ContractEvent]) -> Result<()> {
+    let expect = TransactionInfo::new(
+        txn_info.transaction_hash(),
+        txn_info.state_root_hash(),
+        events,
+        txn_info.gas_used(),
+        txn_info.status().clone(),
+    );
+    ensure!(
+        expect.event_root_hash() == txn_info.event_root_hash(),
+        "The events of transaction {} mismatch with the event root {}",
+        txn_info.transaction_hash(),
+        txn_info.event_root_hash()
+    );
+    Ok(())
+}
+
+/// Open the storage of the data dir, the node of the data dir should be stopped.
+pub fn open_storage(config: &NodeConfig) -> Result<Arc<Storage>> {
+    Ok(Arc::new(Storage::new(
+        StorageInstance::new_cache_and_db_instance(
+            CacheStorage::new_with_capacity(config.storage.cache_size()),
+            DBStorage::new(config.storage.dir(), config.storage.rocksdb_config())?,
+        ),
+    )?))
+}
+
+/// Verify the snapshot files in `input` by importing them to a temporary db, which is removed after
+/// the verification, so the data dir is not touched and the state of a large snapshot is not
+/// kept in memory.
+pub fn verify_snapshot(config: &NodeConfig, input: &Path) -> Result<SnapshotManifest> {
+    let temp_dir = starcoin_config::temp_path();
+    let storage = Arc::new(Storage::new(StorageInstance::new_db_instance(
+        DBStorage::new(temp_dir.path(), config.storage.rocksdb_config())?,
+    ))?);
+    let (manifest, _genesis) = import_snapshot(config.net(), storage, input)?;
+    Ok(manifest)
+}
+
+/// Import the snapshot in `input` to the empty `storage`, the imported blocks and state are verified
+/// against the block headers, block infos and the manifest.
+pub fn import_snapshot(
+    net: &ChainNetwork,
+    storage: Arc<Storage>,
+    input: &Path,
+) -> Result<(SnapshotManifest, Genesis)> {
+    let manifest = SnapshotManifest::load(input)?;
+    let genesis = Genesis::load(net)?;
+    manifest.check(net, genesis.block().id())?;
+    if storage.get_chain_info()?.is_some() {
+        bail!(
+            "The storage is already initialized, please import the snapshot to an empty data dir."
+        )
+    }
+
+    let statedb = ChainStateDB::new(storage.clone(), None);
+    let mut reader = RecordReader::new(BufReader::new(File::open(input.join(STATE_FILE))?));
+    let mut batch = vec![];
+    let mut account_count = 0u64;
+    while let Some(state) = reader.read::<(AccountAddress, AccountStateSet)>()? {
+        batch.push(state);
+        account_count += 1;
+        if batch.len() >= STATE_APPLY_BATCH_SIZE {
+            statedb.apply(ChainStateSet::new(std::mem::take(&mut batch)))?;
+            scmd::side_println!(
+                "Imported account {}/{}",
+                account_count,
+                manifest.account_count
+            );
+        }
+    }
+    if !batch.is_empty() {
+        statedb.apply(ChainStateSet::new(batch))?;
+        scmd::side_println!(
+            "Imported account {}/{}",
+            account_count,
+            manifest.account_count
+        );
+    }
+    ensure!(
+        account_count == manifest.account_count,
+        "The snapshot has {} accounts, but the manifest expect {}",
+        account_count,
+        manifest.account_count
+    );
+    ensure!(
+        statedb.state_root() == manifest.state_root,
+        "The imported state root {} mismatch with the manifest state root {}",
+        statedb.state_root(),
+        manifest.state_root
+    );
+
+    let txn_accumulator = MerkleAccumulator::new_empty(
+        storage.get_accumulator_store(AccumulatorStoreType::Transaction),
+    );
+    let block_accumulator =
+        MerkleAccumulator::new_empty(storage.get_accumulator_store(AccumulatorStoreType::Block));
+    let mut reader = RecordReader::new(BufReader::new(File::open(input.join(BLOCKS_FILE))?));
+    let mut parent: Option<Block> = None;
+    while let Some(block_data) = reader.read::<BlockData>()? {
+        let header = block_data.block.header().clone();
+        let expect_number = parent
+            .as_ref()
+            .map(|p| p.header().number() + 1)
+            .unwrap_or(0);
+        ensure!(
+            header.number() == expect_number,
+            "Expect block {}, but got block {}",
+            expect_number,
+            header.number()
+        );
+        match &parent {
+            Some(parent) => ensure!(
+                header.parent_hash() == parent.id(),
+                "The parent of block {} is not {}",
+                header.number(),
+                parent.id()
+            ),
+            None => ensure!(
+                block_data.block == *genesis.block(),
+                "The first block of the snapshot is not the genesis block"
+            ),
+        }
+        ensure!(
+            block_data.block_info.block_id == header.id(),
+            "The block info mismatch with block {}",
+            header.id()
+        );
+        ensure!(
+            block_data.transactions.len() == block_data.txn_infos.len()
+                && block_data.events.len() == block_data.txn_infos.len(),
+            "The transactions, transaction infos and events of block {} mismatch",
+            header.id()
+        );
+        for ((txn, txn_info), events) in block_data
+            .transactions
+            .iter()
+            .zip(&block_data.txn_infos)
+            .zip(&block_data.events)
+        {
+            ensure!(
+                txn.id() == txn_info.transaction_hash(),
+                "The transaction {} mismatch with its transaction info in block {}",
+                txn.id(),
+                header.id()
+            );
+            verify_event_root(txn_info, events)?;
+        }
+
+        ensure!(
+            block_accumulator.root_hash() == header.block_accumulator_root(),
+            "The block accumulator root of block {} mismatch",
+            header.id()
+        );
+        let txn_info_hashes: Vec<_> = block_data.txn_infos.iter().map(|info| info.id()).collect();
+        txn_accumulator.append(&txn_info_hashes)?;
+        txn_accumulator.flush()?;
+        ensure!(
+            txn_accumulator.root_hash() == header.txn_accumulator_root()
+                && txn_accumulator.get_info() == block_data.block_info.txn_accumulator_info,
+            "The transaction accumulator of block {} mismatch",
+            header.id()
+        );
+        block_accumulator.append(&[header.id()])?;
+        block_accumulator.flush()?;
+        ensure!(
+            block_accumulator.get_info() == block_data.block_info.block_accumulator_info,
+            "The block accumulator of block {} mismatch",
+            header.id()
+        );
+
+        BlockChain::save(
+            storage.as_ref(),
+            block_data.block.clone(),
+            block_data.block_info,
+            block_data.transactions,
+            (block_data.txn_infos, block_data.events),
+        )?;
+        if header.number() % PROGRESS_INTERVAL == 0 || header.number() == manifest.block_number {
+            scmd::side_println!(
+                "Imported block {}/{}",
+                header.number(),
+                manifest.block_number
+            );
+        }
+        parent = Some(block_data.block);
+    }
+    let head = parent.ok_or_else(|| format_err!("The snapshot has no block."))?;
+    ensure!(
+        head.id() == manifest.block_hash && head.header().state_root() == manifest.state_root,
+        "The last block {} of the snapshot mismatch with the manifest block {}",
+        head.id(),
+        manifest.block_hash
+    );
+
+    storage.save_genesis(genesis.block().id())?;
+    // save the startup info at last, the storage is initialized only if the import is success.
+    storage.save_startup_info(StartupInfo::new(head.id()))?;
+    Ok((manifest, genesis))
+}
+
+/// Whether the args are of a snapshot command, `starcoin [global options] node snapshot ...`.
+pub fn is_snapshot_command(args: &[String]) -> bool {
+    args.windows(2)
+        .any(|pair| pair[0] == "node" && pair[1] == SNAPSHOT_COMMAND)
+}
+
+pub fn exec_snapshot() -> Result<()> {
+    let context = CmdContext::<SnapshotState, StarcoinOpt>::with_state(
+        CRATE_VERSION,
+        Some(APP_VERSION.as_str()),
+        SnapshotState,
+    );
+    context
+        .command(
+            Command::with_name("node").subcommand(
+                Command::with_name(SNAPSHOT_COMMAND)
+                    .subcommand(ExportCommand)
+                    .subcommand(ImportCommand),
+            ),
+        )
+        .exec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_storage::storage::StorageInstance;
+
+    #[test]
+    fn test_record_reader_writer() {
+        let mut writer = RecordWriter::new(vec![]);
+        writer.write(&1u64).unwrap();
+        writer.write(&"snapshot".to_string()).unwrap();
+        let bytes = writer.writer;
+        let mut reader = RecordReader::new(bytes.as_slice());
+        assert_eq!(reader.read::<u64>().unwrap(), Some(1));
+        assert_eq!(
+            reader.read::<String>().unwrap(),
+            Some("snapshot".to_string())
+        );
+        assert_eq!(reader.read::<u64>().unwrap(), None);
+
+        let mut reader = RecordReader::new(&bytes[..bytes.len() - 1]);
+        assert!(reader.read::<u64>().is_ok());
+        assert!(reader.read::<String>().is_err());
+
+        // an oversize length prefix is rejected without reading the record.
+        let bytes = ((MAX_RECORD_SIZE + 1) as u32).to_le_bytes();
+        let mut reader = RecordReader::new(&bytes[..]);
+        assert!(reader.read::<Vec<u8>>().is_err());
+    }
+
+    #[test]
+    fn test_verify_event_root() {
+        let net = ChainNetwork::new_test();
+        let (storage, chain_info, _genesis) = Genesis::init_storage_for_test(&net).unwrap();
+        let block = storage
+            .get_block_by_hash(chain_info.head().id())
+            .unwrap()
+            .unwrap();
+        let block_data = read_block_data(storage.as_ref(), block).unwrap();
+        let (txn_info, events) = block_data
+            .txn_infos
+            .iter()
+            .zip(&block_data.events)
+            .find(|(_, events)| !events.is_empty())
+            .expect("The genesis txn should emit events.");
+        verify_event_root(txn_info, events).unwrap();
+        assert!(verify_event_root(txn_info, &events[1..]).is_err());
+    }
+
+    #[test]
+    fn test_is_snapshot_command() {
+        let args = |args: &[&str]| -> Vec<String> {
+            std::iter::once("starcoin")
+                .chain(args.iter().copied())
+                .map(|arg| arg.to_string())
+                .collect()
+        };
+        assert!(is_snapshot_command(&args(&["node", "snapshot", "export"])));
+        assert!(is_snapshot_command(&args(&[
+            "-d", "/data", "-n", "dev", "node", "snapshot", "import"
+        ])));
+        assert!(!is_snapshot_command(&args(&["node", "info"])));
+        assert!(!is_snapshot_command(&args(&["snapshot", "node"])));
+    }
+
+    #[test]
+    fn test_export_and_import_snapshot() {
+        let net = ChainNetwork::new_test();
+        let (storage, chain_info, _genesis) = Genesis::init_storage_for_test(&net).unwrap();
+        let output = starcoin_config::temp_path();
+        let manifest = export_snapshot(&net, storage, None, output.path()).unwrap();
+        assert_eq!(manifest.block_hash, chain_info.head().id());
+        assert_eq!(manifest.block_count, 1);
+
+        let storage = Arc::new(Storage::new(StorageInstance::new_cache_instance()).unwrap());
+        let (imported, _) = import_snapshot(&net, storage.clone(), output.path()).unwrap();
+        assert_eq!(imported, manifest);
+        assert_eq!(
+            storage.get_chain_info().unwrap().unwrap().head().id(),
+            chain_info.head().id()
+        );
+        // the storage is initialized, import again should fail.
+        assert!(import_snapshot(&net, storage, output.path()).is_err());
+
+        let other_net = ChainNetwork::new_builtin(starcoin_config::BuiltinNetworkID::Dev);
+        let storage = Arc::new(Storage::new(StorageInstance::new_cache_instance()).unwrap());
+        assert!(import_snapshot(&other_net, storage, output.path()).is_err());
+    }
+
+    #[test]
+    fn test_verify_snapshot() {
+        let config = NodeConfig::random_for_test();
+        let (storage, _chain_info, _genesis) =
+            Genesis::init_storage_for_test(config.net()).unwrap();
+        let output = starcoin_config::temp_path();
+        let manifest = export_snapshot(config.net(), storage, None, output.path()).unwrap();
+        assert_eq!(verify_snapshot(&config, output.path()).unwrap(), manifest);
+
+        // the verification reads the files on disk, a corrupted state file is rejected.
+        let state_file = output.path().join(STATE_FILE);
+        let mut state = std::fs::read(state_file.as_path()).unwrap();
+        let last = state.len() - 1;
+        state[last] ^= 0xff;
+        std::fs::write(state_file.as_path(), state).unwrap();
+        assert!(verify_snapshot(&config, output.path()).is_err());
+    }
+}
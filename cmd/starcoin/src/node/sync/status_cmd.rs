@@ -5,12 +5,29 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
+use serde::{Deserialize, Serialize};
+use starcoin_rpc_api::types::SyncProgressView;
 use starcoin_types::sync_status::SyncStatus;
+use std::time::Duration;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt, Default)]
 #[structopt(name = "status")]
-pub struct StatusOpt {}
+pub struct StatusOpt {
+    #[structopt(short = "w", long = "watch")]
+    /// poll and display the sync progress until the sync task is done.
+    watch: bool,
+
+    #[structopt(short = "i", long = "interval", default_value = "3")]
+    /// poll interval in seconds when watch.
+    interval: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncStatusWithProgress {
+    pub status: SyncStatus,
+    pub progress: Option<SyncProgressView>,
+}
 
 pub struct StatusCommand;
 
@@ -18,13 +35,36 @@ impl CommandAction for StatusCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = StatusOpt;
-    type ReturnItem = SyncStatus;
+    type ReturnItem = SyncStatusWithProgress;
 
     fn run(
         &self,
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<Self::ReturnItem> {
         let client = ctx.state().client();
-        client.sync_status()
+        let opt = ctx.opt();
+        if opt.watch {
+            while let Some(progress) = client.sync_progress()? {
+                scmd::side_println!(
+                    "target: {}({}), current: {}, applied: {}, speed: {:.2} blocks/s, peers: {}, in-flight: {}, eta: {}",
+                    progress.target_number,
+                    progress.target_id,
+                    progress.current_number,
+                    progress.applied_blocks,
+                    progress.blocks_per_second,
+                    progress.peers,
+                    progress.inflight_fetches,
+                    progress
+                        .eta_seconds
+                        .map(|eta| format!("{}s", eta))
+                        .unwrap_or_else(|| "unknown".to_string()),
+                );
+                std::thread::sleep(Duration::from_secs(opt.interval));
+            }
+        }
+        Ok(SyncStatusWithProgress {
+            status: client.sync_status()?,
+            progress: client.sync_progress()?,
+        })
     }
 }
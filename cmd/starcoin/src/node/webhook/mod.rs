@@ -0,0 +1,10 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+mod list_cmd;
+mod register_cmd;
+mod remove_cmd;
+
+pub use list_cmd::*;
+pub use register_cmd::*;
+pub use remove_cmd::*;
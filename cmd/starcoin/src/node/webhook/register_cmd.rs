@@ -0,0 +1,52 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::{ensure, Result};
+use scmd::{CommandAction, ExecContext};
+use starcoin_crypto::HashValue;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::webhook::{WebhookFilter, WebhookRegistration};
+use structopt::StructOpt;
+
+/// Register a webhook, the node POSTs the lifecycle status changes of the matched transactions to the url.
+/// Keep the returned secret to verify the `X-Starcoin-Signature` header of the requests.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "register")]
+pub struct RegisterOpt {
+    #[structopt(name = "url")]
+    url: String,
+    #[structopt(long = "sender", short = "s")]
+    /// match the transactions sent by the sender, can be specified multiple times.
+    senders: Vec<AccountAddress>,
+    #[structopt(long = "txn-hash", short = "t")]
+    /// match the transaction with the hash, can be specified multiple times.
+    txn_hashes: Vec<HashValue>,
+}
+
+pub struct RegisterCommand;
+
+impl CommandAction for RegisterCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = RegisterOpt;
+    type ReturnItem = WebhookRegistration;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let filter = WebhookFilter {
+            senders: opt.senders.clone(),
+            txn_hashes: opt.txn_hashes.clone(),
+        };
+        ensure!(
+            !filter.is_empty(),
+            "Please specify at least one --sender or --txn-hash."
+        );
+        let client = ctx.state().client();
+        client.node_register_webhook(opt.url.clone(), filter)
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use scmd::{CommandAction, ExecContext};
+use starcoin_crypto::HashValue;
+use structopt::StructOpt;
+
+/// Remove the webhook registered by rpc.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "remove")]
+pub struct RemoveOpt {
+    #[structopt(name = "id")]
+    id: HashValue,
+}
+
+pub struct RemoveCommand;
+
+impl CommandAction for RemoveCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = RemoveOpt;
+    type ReturnItem = ();
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.node_remove_webhook(ctx.opt().id)
+    }
+}
@@ -5,10 +5,14 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::Result;
 use scmd::{CommandAction, ExecContext};
+use serde::Serialize;
 use starcoin_crypto::HashValue;
-use starcoin_rpc_api::types::SignedUserTransactionView;
-use starcoin_txpool_api::TxPoolStatus;
+use starcoin_rpc_api::types::{
+    SignedUserTransactionView, TxnDiagnoseTarget, TxnDiagnosisView, TxnVerdict,
+};
+use starcoin_txpool_api::{TxPoolStatus, TxnRebroadcastResult};
 use starcoin_vm_types::account_address::AccountAddress;
+use std::path::Path;
 use structopt::StructOpt;
 
 /// Get txn data by its hash
@@ -88,3 +92,155 @@ impl CommandAction for TxPoolStatusCommand {
         client.txpool_status()
     }
 }
+
+/// Submit the hex of a signed txn, such as the output of `account sign-txn-request --offline`.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "submit-hex")]
+pub struct SubmitHexOpt {
+    #[structopt(
+        name = "file-or-hex",
+        help = "the file which contains the hex of the signed txn, or the hex itself"
+    )]
+    input: String,
+}
+
+pub struct SubmitHexCommand;
+
+impl CommandAction for SubmitHexCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SubmitHexOpt;
+    type ReturnItem = HashValue;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let input = ctx.opt().input.as_str();
+        let txn_hex = if Path::new(input).is_file() {
+            std::fs::read_to_string(input)?.trim().to_string()
+        } else {
+            input.to_string()
+        };
+        ctx.state().client().submit_hex_transaction(txn_hex)
+    }
+}
+
+/// Diagnose why a txn is not mined, by the txn hash or the sender address.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "diagnose")]
+pub struct DiagnoseOpt {
+    #[structopt(
+        name = "hash-or-sender",
+        help = "hash of the txn, or the sender whose pending txn with the lowest sequence number is diagnosed"
+    )]
+    target: TxnDiagnoseTarget,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DiagnoseView {
+    #[serde(flatten)]
+    pub diagnosis: TxnDiagnosisView,
+    pub suggestion: String,
+}
+
+pub struct DiagnoseCommand;
+
+impl CommandAction for DiagnoseCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = DiagnoseOpt;
+    type ReturnItem = DiagnoseView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let diagnosis = ctx
+            .state()
+            .client()
+            .txpool_diagnose(ctx.opt().target.clone())?;
+        let suggestion = suggestion(&diagnosis.verdict);
+        Ok(DiagnoseView {
+            diagnosis,
+            suggestion,
+        })
+    }
+}
+
+fn suggestion(verdict: &TxnVerdict) -> String {
+    match verdict {
+        TxnVerdict::NotInPool => {
+            "The txn is not in the txpool, check whether it is mined by `chain get_txn_info`, otherwise resubmit it.".to_string()
+        }
+        TxnVerdict::Expired {
+            expiration_timestamp_secs,
+            now_seconds,
+        } => format!(
+            "The txn expired at {}, the node time is {}, sign a new txn with a later expiration.",
+            expiration_timestamp_secs, now_seconds
+        ),
+        TxnVerdict::SequenceGap {
+            onchain_sequence_number,
+            missing_count,
+            missing_sequence_numbers,
+        } => format!(
+            "The on chain sequence number is {}, {} txns are missing, submit the txns with sequence numbers {:?} first.",
+            onchain_sequence_number,
+            missing_count,
+            missing_sequence_numbers
+                .iter()
+                .map(|seq| seq.0)
+                .collect::<Vec<_>>()
+        ),
+        TxnVerdict::InsufficientBalance {
+            balance,
+            max_gas_cost,
+        } => format!(
+            "The balance {} can not pay the max gas {}, transfer some gas token to the sender or lower the max gas amount.",
+            balance.0, max_gas_cost.0
+        ),
+        TxnVerdict::GasPriceTooLow {
+            gas_unit_price,
+            recent_min_gas_price,
+        } => format!(
+            "The gas price {} is lower than the recent min gas price {}, resubmit it with a higher gas price and `--replace`.",
+            gas_unit_price, recent_min_gas_price
+        ),
+        TxnVerdict::Pending => "No problem found, the txn is waiting to be mined.".to_string(),
+    }
+}
+
+/// Announce the pending txns to the peers now, such as the txns submitted when the node had no peer.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "rebroadcast")]
+pub struct RebroadcastOpt {
+    #[structopt(
+        name = "hash",
+        help = "hashes of the pending txns, default to all the pending txns submitted to this node"
+    )]
+    txn_hashes: Vec<HashValue>,
+}
+
+pub struct RebroadcastCommand;
+
+impl CommandAction for RebroadcastCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = RebroadcastOpt;
+    type ReturnItem = TxnRebroadcastResult;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let txn_hashes = ctx.opt().txn_hashes.clone();
+        ctx.state()
+            .client()
+            .txpool_rebroadcast(if txn_hashes.is_empty() {
+                None
+            } else {
+                Some(txn_hashes)
+            })
+    }
+}
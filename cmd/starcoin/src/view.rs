@@ -6,9 +6,10 @@ use serde::{Deserialize, Serialize};
 use starcoin_account_api::AccountInfo;
 use starcoin_crypto::HashValue;
 use starcoin_rpc_api::types::{
-    StrView, TransactionEventView, TransactionOutputAction, TransactionOutputView,
-    TransactionVMStatus,
+    GasBreakdownView, StrView, TransactionEventView, TransactionInfoView, TransactionOutputAction,
+    TransactionOutputView, TransactionVMStatus,
 };
+use starcoin_rpc_client::chain_watcher::ThinHeadBlock;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::{DepositEvent, MintEvent, WithdrawEvent};
 use starcoin_types::contract_event::ContractEvent;
@@ -31,7 +32,8 @@ pub struct AccountWithStateView {
     pub account: AccountInfo,
     pub auth_key: String,
     pub sequence_number: Option<u64>,
-    pub balances: HashMap<String, u128>,
+    /// Balances formatted with the token's scaling factor, followed by the raw amount in parentheses.
+    pub balances: HashMap<String, String>,
 }
 
 #[derive(Clone, Debug, Serialize, PartialEq)]
@@ -215,6 +217,10 @@ pub struct TranscationOutputView {
 
     /// The execution status.
     pub status: TransactionVMStatus,
+
+    /// Where the gas goes, only present for the dry run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_breakdown: Option<GasBreakdownView>,
 }
 
 impl From<TransactionOutputView> for TranscationOutputView {
@@ -224,22 +230,105 @@ impl From<TransactionOutputView> for TranscationOutputView {
             events: output.events.into_iter().map(|e| e.into()).collect(),
             gas_used: output.gas_used.0,
             status: output.status,
+            gas_breakdown: output.gas_breakdown,
         }
     }
 }
 
+/// Render the gas breakdown as a text table, the gas of the instructions and natives is sorted
+/// from the most expensive, followed by the size of every write op.
+pub fn gas_breakdown_table(breakdown: &GasBreakdownView) -> String {
+    let mut charges: Vec<(&str, &str, u64)> = vec![("intrinsic", "", breakdown.intrinsic_gas.0)];
+    let mut entries: Vec<(&str, &str, u64)> = breakdown
+        .instructions
+        .iter()
+        .map(|charge| ("instruction", charge.name.as_str(), charge.gas.0))
+        .chain(
+            breakdown
+                .natives
+                .iter()
+                .map(|charge| ("native", charge.name.as_str(), charge.gas.0)),
+        )
+        .collect();
+    if entries.is_empty() {
+        entries.push(("instruction", "", breakdown.instruction_gas.0));
+    }
+    entries.sort_by(|a, b| b.2.cmp(&a.2));
+    charges.extend(entries);
+    charges.push(("storage write", "", breakdown.storage_write_gas.0));
+    let total: u64 = charges.iter().map(|(_, _, gas)| *gas).sum();
+    let name_width = charges
+        .iter()
+        .map(|(_, name, _)| name.len())
+        .max()
+        .unwrap_or_default()
+        .max("Name".len());
+    let mut lines = vec![format!(
+        "{:<13}  {:<name_width$}  {:>12}  {:>6}",
+        "Gas",
+        "Name",
+        "Gas Used",
+        "Share",
+        name_width = name_width
+    )];
+    for (kind, name, gas) in charges {
+        lines.push(format!(
+            "{:<13}  {:<name_width$}  {:>12}  {:>5.1}%",
+            kind,
+            name,
+            gas,
+            gas as f64 * 100.0 / total.max(1) as f64,
+            name_width = name_width
+        ));
+    }
+    lines.push(format!(
+        "{:<13}  {:<name_width$}  {:>12}",
+        "total",
+        "",
+        total,
+        name_width = name_width
+    ));
+    lines.push(String::new());
+    lines.push(format!(
+        "storage reads: {} ({} bytes), storage writes: {} ({} bytes)",
+        breakdown.storage_reads,
+        breakdown.storage_read_bytes,
+        breakdown.storage_writes,
+        breakdown.storage_write_bytes
+    ));
+    for write_op in &breakdown.write_ops {
+        lines.push(format!(
+            "  {} {}",
+            write_op
+                .size
+                .map(|size| format!("{:>8} bytes", size))
+                .unwrap_or_else(|| format!("{:>14}", "deleted")),
+            format!(
+                "{:#x}/{}",
+                write_op.access_path.address, write_op.access_path.path
+            )
+        ));
+    }
+    lines.join("\n")
+}
+
 #[derive(Debug, Serialize)]
 #[serde(tag = "type")]
 pub enum ExecuteResultView {
     DryRun(TranscationOutputView),
     Run(ExecutionOutputView),
+    /// The outputs of the txns submitted by a batch transfer.
+    Batch(Vec<ExecutionOutputView>),
 }
 
-#[derive(Serialize, Debug, Clone, Copy)]
+#[derive(Serialize, Debug, Clone)]
 pub struct ExecutionOutputView {
     pub txn_hash: HashValue,
     pub block_number: Option<u64>,
     pub block_id: Option<HashValue>,
+    /// The on chain vm status of the mined txn.
+    pub status: Option<TransactionVMStatus>,
+    pub gas_used: Option<u64>,
 }
 
 impl ExecutionOutputView {
@@ -248,6 +337,17 @@ impl ExecutionOutputView {
             txn_hash,
             block_number: None,
             block_id: None,
+            status: None,
+            gas_used: None,
+        }
+    }
+
+    pub fn set_mined(&mut self, block: &ThinHeadBlock, txn_info: Option<TransactionInfoView>) {
+        self.block_number = Some(block.header.number.0);
+        self.block_id = Some(block.header.block_hash);
+        if let Some(txn_info) = txn_info {
+            self.status = Some(txn_info.status);
+            self.gas_used = Some(txn_info.gas_used.0);
         }
     }
 }
@@ -0,0 +1,98 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{format_err, Result};
+use serde_json::Value;
+use starcoin_config::NodeConfig;
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::TransactionVMStatus;
+use starcoin_rpc_client::RpcClient;
+use starcoin_vm_types::account_config::association_address;
+use starcoin_vm_types::identifier::Identifier;
+use starcoin_vm_types::language_storage::ModuleId;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::sync::Arc;
+use std::thread::sleep;
+use std::time::Duration;
+
+fn run_cli(ipc_file: &Path, args: &[&str]) -> Result<Output> {
+    Ok(Command::new(env!("CARGO_BIN_EXE_starcoin"))
+        .arg("-c")
+        .arg(ipc_file)
+        .args(args)
+        .output()?)
+}
+
+#[stest::test(timeout = 120)]
+fn test_execute_script_with_sibling_module() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let ipc_file = config.rpc.get_ipc_file();
+
+    let temp_dir = starcoin_config::temp_path();
+    let module_file = temp_dir.path().join("MyModule.move");
+    std::fs::write(
+        module_file.as_path(),
+        r#"
+        address {{sender}} {
+        module MyModule {
+            public fun hello(): u64 { 1 }
+        }
+        }
+        "#,
+    )?;
+    let script_file = temp_dir.path().join("my_script.move");
+    std::fs::write(
+        script_file.as_path(),
+        r#"
+        script {
+            use {{sender}}::MyModule;
+            fun main() {
+                assert(MyModule::hello() == 1, 1000);
+            }
+        }
+        "#,
+    )?;
+
+    let sender = association_address().to_string();
+    let output = run_cli(
+        ipc_file.as_path(),
+        &["account", "unlock", "-p", "", sender.as_str()],
+    )?;
+    assert!(output.status.success(), "{:?}", output);
+    let output = run_cli(
+        ipc_file.as_path(),
+        &[
+            "-o",
+            "json",
+            "dev",
+            "execute",
+            "-s",
+            sender.as_str(),
+            module_file.to_string_lossy().as_ref(),
+            script_file.to_string_lossy().as_ref(),
+        ],
+    )?;
+    assert!(output.status.success(), "{:?}", output);
+    let result: Value = serde_json::from_slice(output.stdout.as_slice())?;
+    let script_txn_hash: HashValue = serde_json::from_value(result["txn_hash"].clone())?;
+
+    sleep(Duration::from_millis(500));
+    node_handle.generate_block()?;
+    let client = RpcClient::connect_ipc(ipc_file)?;
+    // the module is deployed by the package txn before the script txn.
+    assert!(client
+        .get_code(
+            ModuleId::new(association_address(), Identifier::new("MyModule")?),
+            false
+        )?
+        .is_some());
+    let txn_info = client
+        .chain_get_transaction_info(script_txn_hash)?
+        .ok_or_else(|| format_err!("script txn {} should be mined", script_txn_hash))?;
+    assert_eq!(txn_info.status, TransactionVMStatus::Executed);
+
+    let _e = node_handle.stop();
+    Ok(())
+}
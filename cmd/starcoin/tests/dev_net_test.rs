@@ -0,0 +1,100 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_cmd::dev_net::{DevNetManifest, NodeStatusView};
+use starcoin_rpc_client::RpcClient;
+use starcoin_types::block::BlockNumber;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::time::{Duration, Instant};
+
+fn run_dev_net(work_dir: &Path, args: &[&str]) -> Result<Output> {
+    Ok(Command::new(env!("CARGO_BIN_EXE_starcoin"))
+        .args(&["-o", "json", "dev-net"])
+        .args(args)
+        .arg("--work-dir")
+        .arg(work_dir)
+        .output()?)
+}
+
+fn dev_net_status(work_dir: &Path) -> Result<Vec<NodeStatusView>> {
+    let output = run_dev_net(work_dir, &["status"])?;
+    anyhow::ensure!(output.status.success(), "status failed: {:?}", output);
+    Ok(serde_json::from_slice(output.stdout.as_slice())?)
+}
+
+/// Wait until all the heads reach `min_height`, and the block below it is the same on all the nodes.
+fn wait_heads_converge(work_dir: &Path, min_height: BlockNumber) -> Result<()> {
+    let start = Instant::now();
+    loop {
+        let status = dev_net_status(work_dir)?;
+        anyhow::ensure!(status.len() == 3, "unexpected nodes: {:?}", status);
+        anyhow::ensure!(
+            status.iter().all(|node| node.running),
+            "nodes should be running: {:?}",
+            status
+        );
+        if status
+            .iter()
+            .all(|node| node.head_number.unwrap_or_default() >= min_height)
+        {
+            break;
+        }
+        anyhow::ensure!(
+            start.elapsed() <= Duration::from_secs(180),
+            "heads do not reach {} in time: {:?}",
+            min_height,
+            status
+        );
+        std::thread::sleep(Duration::from_secs(2));
+    }
+
+    // the block of a height below all the heads should be the same on all the nodes after sync.
+    let manifest = DevNetManifest::load(work_dir)?;
+    let start = Instant::now();
+    loop {
+        let mut hashes = vec![];
+        for node in &manifest.nodes {
+            let client = RpcClient::connect_ipc(node.ipc_file.as_path())?;
+            let block = client.chain_get_block_by_number(min_height - 1, None);
+            client.close();
+            hashes.push(block?.map(|block| block.header.block_hash));
+        }
+        if hashes[0].is_some() && hashes.iter().all(|hash| *hash == hashes[0]) {
+            return Ok(());
+        }
+        anyhow::ensure!(
+            start.elapsed() <= Duration::from_secs(60),
+            "heads do not converge: {:?}",
+            hashes
+        );
+        std::thread::sleep(Duration::from_secs(2));
+    }
+}
+
+fn stop_dev_net(work_dir: &Path) -> Result<()> {
+    let output = run_dev_net(work_dir, &["stop"])?;
+    anyhow::ensure!(output.status.success(), "stop failed: {:?}", output);
+    let status = dev_net_status(work_dir)?;
+    anyhow::ensure!(
+        status.iter().all(|node| !node.running),
+        "nodes should be stopped: {:?}",
+        status
+    );
+    Ok(())
+}
+
+#[stest::test(timeout = 300)]
+fn test_dev_net_heads_converge() -> Result<()> {
+    let work_dir = starcoin_config::temp_path();
+    let work_dir = work_dir.path();
+    let result = run_dev_net(work_dir, &["start", "--nodes", "3", "--detach"]).and_then(|output| {
+        anyhow::ensure!(output.status.success(), "start failed: {:?}", output);
+        wait_heads_converge(work_dir, 3)
+    });
+    // always stop the nodes before checking the result, so a failed test does not leak them.
+    let stopped = stop_dev_net(work_dir);
+    result?;
+    stopped
+}
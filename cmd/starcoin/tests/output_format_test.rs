@@ -0,0 +1,94 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use serde_json::Value;
+use starcoin_config::NodeConfig;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::sync::Arc;
+
+fn run_cli(ipc_file: &Path, args: &[&str]) -> Result<Output> {
+    Ok(Command::new(env!("CARGO_BIN_EXE_starcoin"))
+        .arg("-c")
+        .arg(ipc_file)
+        .args(args)
+        .output()?)
+}
+
+#[stest::test]
+fn test_json_output_format() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let ipc_file = config.rpc.get_ipc_file();
+
+    for cmd in &[
+        vec!["node", "info"],
+        vec!["chain", "info"],
+        vec!["account", "list"],
+        vec!["txpool", "status"],
+    ] {
+        let mut args = vec!["-o", "json"];
+        args.extend(cmd.iter());
+        let output = run_cli(ipc_file.as_path(), args.as_slice())?;
+        assert!(output.status.success(), "{:?} failed: {:?}", cmd, output);
+        let value: Value = serde_json::from_slice(output.stdout.as_slice())
+            .unwrap_or_else(|e| panic!("stdout of {:?} should be a json document: {}", cmd, e));
+        assert!(!value.is_null());
+    }
+
+    let output = run_cli(
+        ipc_file.as_path(),
+        &["--output-format", "json", "--quiet", "chain", "info"],
+    )?;
+    assert!(output.status.success(), "{:?}", output);
+    let chain_info: Value = serde_json::from_slice(output.stdout.as_slice())?;
+    assert!(chain_info["head"]["number"].is_string());
+    assert!(
+        output.stderr.is_empty(),
+        "stderr should be empty in quiet mode: {}",
+        String::from_utf8_lossy(output.stderr.as_slice())
+    );
+
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_account_export_json_output() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let ipc_file = config.rpc.get_ipc_file();
+
+    let output = run_cli(
+        ipc_file.as_path(),
+        &["-o", "json", "account", "create", "-p", "test"],
+    )?;
+    assert!(output.status.success(), "{:?}", output);
+    let account: Value = serde_json::from_slice(output.stdout.as_slice())?;
+    let address = account["address"]
+        .as_str()
+        .expect("address should be a string")
+        .to_string();
+
+    let output = run_cli(
+        ipc_file.as_path(),
+        &[
+            "-o",
+            "json",
+            "account",
+            "export",
+            "-p",
+            "test",
+            address.as_str(),
+        ],
+    )?;
+    assert!(output.status.success(), "{:?}", output);
+    // the private key is in the only json document of stdout.
+    let exported: Value = serde_json::from_slice(output.stdout.as_slice())?;
+    assert_eq!(exported["account"].as_str(), Some(address.as_str()));
+    assert!(exported["private_key"].is_string());
+
+    let _e = node_handle.stop();
+    Ok(())
+}
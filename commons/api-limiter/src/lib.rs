@@ -5,13 +5,40 @@ use governor::state::keyed::DefaultKeyedStateStore;
 use governor::state::{InMemoryState, NotKeyed};
 use governor::{NotUntil, RateLimiter};
 use std::collections::HashMap;
+use std::fmt;
 use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 pub use governor::Quota;
 
 type DirectRateLimiter = RateLimiter<NotKeyed, InMemoryState, DefaultClock>;
 type KeyedRateLimiter<K> = RateLimiter<K, DefaultKeyedStateStore<K>, DefaultClock>;
 
+/// The default max count of the users tracked by the user limiter of an api.
+pub const DEFAULT_MAX_TRACKED_USERS: usize = 10_000;
+/// The min interval to forget the idle users when the tracked users are full.
+const RETAIN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The call is rejected by the quota.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateLimitError {
+    /// The earliest time to wait before the call can be accepted.
+    pub retry_after: Duration,
+}
+
+impl fmt::Display for RateLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "api quota exceeded, retry after {}ms",
+            self.retry_after.as_millis()
+        )
+    }
+}
+
+impl std::error::Error for RateLimitError {}
+
 #[derive(Debug)]
 pub struct ApiLimiter<User>
 where
@@ -21,6 +48,11 @@ where
     user_quota: Quota,
     global_limiter: DirectRateLimiter,
     user_limiter: KeyedRateLimiter<User>,
+    /// The calls share this limiter when the tracked users are full, so the memory of the
+    /// user limiter is bounded even if the users are endless.
+    overflow_limiter: DirectRateLimiter,
+    max_users: usize,
+    last_retain: Mutex<Option<Instant>>,
 }
 
 impl<User> ApiLimiter<User>
@@ -31,17 +63,43 @@ where
         Self {
             global_limiter: DirectRateLimiter::direct(global_quota),
             user_limiter: KeyedRateLimiter::keyed(user_quota),
+            overflow_limiter: DirectRateLimiter::direct(user_quota),
+            max_users: DEFAULT_MAX_TRACKED_USERS,
+            last_retain: Mutex::new(None),
             global_quota,
             user_quota,
         }
     }
 
+    pub fn with_max_users(mut self, max_users: usize) -> Self {
+        self.max_users = max_users;
+        self
+    }
+
+    /// Forget the users whose quota is fully replenished, at most once per `RETAIN_INTERVAL`.
+    fn retain_recent_users(&self) {
+        let mut last_retain = self.last_retain.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        if last_retain.map_or(true, |last| now.duration_since(last) >= RETAIN_INTERVAL) {
+            self.user_limiter.retain_recent();
+            self.user_limiter.shrink_to_fit();
+            *last_retain = Some(now);
+        }
+    }
+
     pub fn check(
         &self,
         user: Option<&User>,
     ) -> Result<(), NotUntil<<DefaultClock as Clock>::Instant>> {
         if let Some(u) = user {
-            self.user_limiter.check_key(u)?;
+            if self.user_limiter.len() >= self.max_users {
+                self.retain_recent_users();
+            }
+            if self.user_limiter.len() >= self.max_users {
+                self.overflow_limiter.check()?;
+            } else {
+                self.user_limiter.check_key(u)?;
+            }
         }
         self.global_limiter.check()?;
         Ok(())
@@ -86,7 +144,7 @@ where
         }
     }
 
-    pub fn check(&self, api: &ApiName, user: Option<&User>) -> Result<(), anyhow::Error> {
+    pub fn check(&self, api: &ApiName, user: Option<&User>) -> Result<(), RateLimitError> {
         let elem = match self.limiters.entry(api.clone()) {
             Entry::Occupied(o) => o.into_ref(),
             Entry::Vacant(v) => {
@@ -95,7 +153,9 @@ where
             }
         };
 
-        elem.check(user).map_err(|e| anyhow::anyhow!("{}", &e))
+        elem.check(user).map_err(|e| RateLimitError {
+            retry_after: e.wait_time_from(DefaultClock::default().now()),
+        })
     }
 
     fn new_limiter(&self, api: &ApiName) -> ApiLimiter<User> {
@@ -115,7 +175,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::{ApiLimiter, Quota};
+    use crate::{ApiLimiter, ApiLimiters, Quota};
     use std::num::NonZeroU32;
     use std::thread::sleep;
     use std::time::Duration;
@@ -139,4 +199,40 @@ mod tests {
         let result = limiter.check(Some(&"abc".to_string()));
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_retry_after() {
+        let quota = Quota::per_second(unsafe { NonZeroU32::new_unchecked(2) });
+        let limiters = ApiLimiters::<String, String>::new(
+            quota,
+            Default::default(),
+            quota,
+            Default::default(),
+        );
+        let api = "node.info".to_string();
+        assert!(limiters.check(&api, None).is_ok());
+        assert!(limiters.check(&api, None).is_ok());
+        let err = limiters.check(&api, None).unwrap_err();
+        assert!(err.retry_after > Duration::from_millis(0));
+        assert!(err.retry_after <= Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_max_users() {
+        let global_quota = Quota::per_second(unsafe { NonZeroU32::new_unchecked(100) });
+        let user_quota = Quota::per_second(unsafe { NonZeroU32::new_unchecked(1) });
+        let limiter = ApiLimiter::<String>::new(global_quota, user_quota).with_max_users(2);
+        assert!(limiter.check(Some(&"a".to_string())).is_ok());
+        assert!(limiter.check(Some(&"b".to_string())).is_ok());
+        // the users beyond the max share the overflow quota.
+        assert!(limiter.check(Some(&"c".to_string())).is_ok());
+        assert!(limiter.check(Some(&"d".to_string())).is_err());
+        assert_eq!(limiter.user_limiter.len(), 2);
+
+        // the idle users are forgotten after their quota is replenished.
+        sleep(Duration::from_millis(1100));
+        assert!(limiter.check(Some(&"d".to_string())).is_ok());
+        assert!(limiter.check(Some(&"d".to_string())).is_err());
+        assert_eq!(limiter.user_limiter.len(), 1);
+    }
 }
@@ -49,6 +49,16 @@ fn test_insert_to_empty_tree() {
     assert_eq!(tree.get(_new_root_hash, key).unwrap().unwrap(), value);
 }
 
+#[test]
+fn test_missing_node() {
+    let db = MockTreeStore::default();
+    let tree = JellyfishMerkleTree::new(&db);
+    let err = tree
+        .get(HashValue::random(), HashValue::random())
+        .unwrap_err();
+    assert!(err.is::<MissingNodeError>());
+}
+
 #[test]
 fn test_delete_from_tree() {
     let db = MockTreeStore::default();
@@ -103,6 +103,11 @@ use tree_cache::TreeCache;
 /// The hardcoded maximum height of a [`JellyfishMerkleTree`] in nibbles.
 pub const ROOT_NIBBLE_HEIGHT: usize = HashValue::LENGTH * 2;
 
+/// The node is not in the store, the tree referencing it is pruned or corrupted.
+#[derive(Debug, thiserror::Error)]
+#[error("Missing node at {0:?}.")]
+pub struct MissingNodeError(pub NodeKey);
+
 /// `TreeReader` defines the interface between
 /// [`JellyfishMerkleTree`](struct.JellyfishMerkleTree.html)
 /// and underlying storage holding nodes.
@@ -110,7 +115,7 @@ pub trait TreeReader<K: RawKey> {
     /// Gets node given a node key. Returns error if the node does not exist.
     fn get_node(&self, node_key: &NodeKey) -> Result<Node<K>> {
         self.get_node_option(node_key)?
-            .ok_or_else(|| format_err!("Missing node at {:?}.", node_key))
+            .ok_or_else(|| MissingNodeError(*node_key).into())
     }
 
     /// Gets node given a node key. Returns `None` if the node does not exist.
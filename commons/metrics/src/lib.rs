@@ -46,6 +46,8 @@ pub mod prometheus_export {
     pub use prometheus::register;
 }
 
+pub use prometheus::proto;
+
 pub type UIntGaugeVec = GenericGaugeVec<AtomicU64>;
 pub type UIntGauge = GenericGauge<AtomicU64>;
 
@@ -147,7 +149,7 @@ Result: errors
 
 This is useful when exporting metric data to flat time series.
 */
-fn flatten_metric_with_labels(name: &str, metric: &prometheus::proto::Metric) -> String {
+pub fn flatten_metric_with_labels(name: &str, metric: &prometheus::proto::Metric) -> String {
     let res = String::from(name);
 
     if metric.get_label().is_empty() {
@@ -0,0 +1,177 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::App;
+use rustyline::completion::{Completer, Pair};
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Helper};
+use std::collections::BTreeMap;
+
+/// Complete the words which depend on the console state, such as the addresses of the accounts.
+pub trait ConsoleCompleter {
+    /// Return the candidates to replace the `word` under the cursor, `words` are the words before it.
+    /// It is called on every completion, so it should return quickly.
+    fn complete(&self, words: &[&str], word: &str) -> Vec<String>;
+}
+
+/// The subcommands and flags of a command.
+#[derive(Clone, Debug, Default)]
+struct CommandNode {
+    flags: Vec<String>,
+    subcommands: BTreeMap<String, CommandNode>,
+}
+
+impl CommandNode {
+    fn from_app(app: &App) -> Self {
+        // clap 2 does not expose the args and subcommands of an app except the hidden parser.
+        let mut flags = vec!["--help".to_string()];
+        let switches = app
+            .p
+            .flags
+            .iter()
+            .map(|flag| &flag.s)
+            .chain(app.p.opts.iter().map(|opt| &opt.s));
+        for switch in switches {
+            if let Some(long) = switch.long {
+                flags.push(format!("--{}", long));
+            }
+            if let Some(short) = switch.short {
+                flags.push(format!("-{}", short));
+            }
+        }
+        let subcommands = app
+            .p
+            .subcommands
+            .iter()
+            .map(|subcommand| {
+                (
+                    subcommand.get_name().to_string(),
+                    Self::from_app(subcommand),
+                )
+            })
+            .collect();
+        Self { flags, subcommands }
+    }
+
+    fn complete(&self, words: &[&str], word: &str) -> Option<Vec<String>> {
+        let mut node = self;
+        for w in words {
+            match node.subcommands.get(*w) {
+                Some(subcommand) => node = subcommand,
+                None => break,
+            }
+        }
+        let candidates: Vec<String> = if word.starts_with('-') {
+            node.flags
+                .iter()
+                .filter(|flag| flag.starts_with(word))
+                .cloned()
+                .collect()
+        } else if !node.subcommands.is_empty() {
+            node.subcommands
+                .keys()
+                .filter(|name| name.starts_with(word))
+                .cloned()
+                .collect()
+        } else {
+            return None;
+        };
+        Some(candidates)
+    }
+}
+
+/// Complete the command names and flags of the console app,
+/// and the arguments of the commands by the `ConsoleCompleter`.
+pub(crate) struct ConsoleHelper {
+    root: CommandNode,
+    completer: Option<Box<dyn ConsoleCompleter>>,
+}
+
+impl ConsoleHelper {
+    pub fn new(app: &App, completer: Option<Box<dyn ConsoleCompleter>>) -> Self {
+        Self {
+            root: CommandNode::from_app(app),
+            completer,
+        }
+    }
+
+    fn candidates(&self, words: &[&str], word: &str) -> Vec<String> {
+        match self.root.complete(words, word) {
+            Some(candidates) => candidates,
+            None => self
+                .completer
+                .as_ref()
+                .map(|completer| completer.complete(words, word))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Completer for ConsoleHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let line = &line[..pos];
+        let start = line.rfind(char::is_whitespace).map(|i| i + 1).unwrap_or(0);
+        let words: Vec<&str> = line[..start].split_whitespace().collect();
+        let candidates = self
+            .candidates(words.as_slice(), &line[start..])
+            .into_iter()
+            .map(|candidate| Pair {
+                display: candidate.clone(),
+                replacement: candidate,
+            })
+            .collect();
+        Ok((start, candidates))
+    }
+}
+
+impl Hinter for ConsoleHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ConsoleHelper {}
+
+impl Validator for ConsoleHelper {}
+
+impl Helper for ConsoleHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{Arg, SubCommand};
+
+    struct MockCompleter;
+
+    impl ConsoleCompleter for MockCompleter {
+        fn complete(&self, _words: &[&str], word: &str) -> Vec<String> {
+            vec![format!("{}1", word)]
+        }
+    }
+
+    #[test]
+    fn test_complete() {
+        let app = App::new("test").subcommand(
+            SubCommand::with_name("account")
+                .subcommand(
+                    SubCommand::with_name("show").arg(Arg::from_usage("-b, --block [block]")),
+                )
+                .subcommand(SubCommand::with_name("sign")),
+        );
+        let helper = ConsoleHelper::new(&app, Some(Box::new(MockCompleter)));
+        assert_eq!(helper.candidates(&[], "acc"), vec!["account"]);
+        assert_eq!(helper.candidates(&["account"], "s"), vec!["show", "sign"]);
+        assert_eq!(
+            helper.candidates(&["account", "show"], "--b"),
+            vec!["--block"]
+        );
+        assert_eq!(helper.candidates(&["account", "show"], "0x"), vec!["0x1"]);
+    }
+}
@@ -1,8 +1,12 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::completer::ConsoleHelper;
 use crate::error::CmdError;
-use crate::{print_action_result, Command, CommandAction, CommandExec, OutputFormat};
+use crate::{
+    print_action_result, set_output_format, set_quiet, Command, CommandAction, CommandExec,
+    ConsoleCompleter, OutputFormat,
+};
 use anyhow::Result;
 use clap::{crate_authors, App, Arg, SubCommand};
 use once_cell::sync::Lazy;
@@ -32,6 +36,9 @@ pub static DEFAULT_CONSOLE_CONFIG: Lazy<ConsoleConfig> = Lazy::new(|| {
 });
 
 static OUTPUT_FORMAT_ARG: &str = "output-format";
+static QUIET_ARG: &str = "quiet";
+
+type ConsoleCompleterFactory<State> = Box<dyn FnOnce(Arc<State>) -> Box<dyn ConsoleCompleter>>;
 
 pub struct CmdContext<State, GlobalOpt>
 where
@@ -46,6 +53,7 @@ where
         Box<dyn FnOnce(&App, Arc<GlobalOpt>, Arc<State>) -> (ConsoleConfig, Option<PathBuf>)>,
         Box<dyn FnOnce(App, GlobalOpt, State)>,
     )>,
+    console_completer: Option<ConsoleCompleterFactory<State>>,
 }
 
 impl<State, GlobalOpt> CmdContext<State, GlobalOpt>
@@ -101,9 +109,17 @@ where
             .arg(
                 Arg::with_name(OUTPUT_FORMAT_ARG)
                     .short("o")
-                    .help("set output-format, support [json|table]")
+                    .long(OUTPUT_FORMAT_ARG)
+                    .help("set output-format, support [json|table], in json format, only the json of the result is printed to stdout")
                     .takes_value(true)
+                    .possible_values(&["json", "JSON", "table", "TABLE"])
                     .default_value("table"),
+            )
+            .arg(
+                Arg::with_name(QUIET_ARG)
+                    .short("q")
+                    .long(QUIET_ARG)
+                    .help("suppress the progress messages and the logs except errors"),
             );
         app = Self::set_app_author(app);
         Self {
@@ -112,6 +128,7 @@ where
             default_action: Box::new(default_action),
             state_initializer: Box::new(state_initializer),
             console_support: None,
+            console_completer: None,
         }
     }
 
@@ -134,6 +151,16 @@ where
         self
     }
 
+    /// Complete the command arguments in console by the completer created from the state,
+    /// the command names and flags are always completed.
+    pub fn with_console_completer<F>(mut self, completer_factory: F) -> Self
+    where
+        F: FnOnce(Arc<State>) -> Box<dyn ConsoleCompleter> + 'static,
+    {
+        self.console_completer = Some(Box::new(completer_factory));
+        self
+    }
+
     //remove this after clap upgrade
     //use of deprecated item 'std::sync::ONCE_INIT': the `new` function is now preferred
     #[allow(deprecated)]
@@ -210,6 +237,8 @@ where
             .expect("output-format arg must exist")
             .parse()
             .expect("parse output-format must success.");
+        set_output_format(output_format);
+        set_quiet(matches.is_present(QUIET_ARG));
 
         let global_opt = GlobalOpt::from_clap(&matches);
         let state = (self.state_initializer)(&global_opt)?;
@@ -228,6 +257,7 @@ where
                         commands,
                         init_action,
                         quit_action,
+                        self.console_completer,
                         output_format,
                     );
                     Ok(Value::Null)
@@ -253,6 +283,7 @@ where
         Ok((output_format, result))
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn console_inner(
         app: App,
         global_opt: GlobalOpt,
@@ -262,6 +293,7 @@ where
             dyn FnOnce(&App, Arc<GlobalOpt>, Arc<State>) -> (ConsoleConfig, Option<PathBuf>),
         >,
         quit_action: Box<dyn FnOnce(App, GlobalOpt, State)>,
+        console_completer: Option<ConsoleCompleterFactory<State>>,
         mut output_format: OutputFormat,
     ) {
         //insert version, quit, history command
@@ -294,7 +326,9 @@ where
         let global_opt = Arc::new(global_opt);
         let state = Arc::new(state);
         let (config, history_file) = init_action(&app, global_opt.clone(), state.clone());
-        let mut rl = Editor::<()>::with_config(config);
+        let completer = console_completer.map(|factory| factory(state.clone()));
+        let mut rl = Editor::<ConsoleHelper>::with_config(config);
+        rl.set_helper(Some(ConsoleHelper::new(&app, completer)));
         if let Some(history_file) = history_file.as_ref() {
             if !history_file.exists() {
                 if let Err(e) = File::create(history_file.as_path()) {
@@ -325,14 +359,7 @@ where
                             let state = Arc::try_unwrap(state)
                                 .ok()
                                 .expect("unwrap state must success when quit.");
-                            if let Some(history_file) = history_file.as_ref() {
-                                if let Err(e) = rl.save_history(history_file.as_path()) {
-                                    println!(
-                                        "Save history to file {:?} error: {:?}",
-                                        history_file, e
-                                    );
-                                }
-                            }
+                            save_history(&mut rl, history_file.as_ref());
                             quit_action(app.clone(), global_opt, state);
                             break;
                         }
@@ -348,14 +375,7 @@ where
                                 let history = rl.history_mut();
                                 let len = history.len();
                                 history.clear();
-                                if let Some(history_file) = history_file.as_ref() {
-                                    if let Err(e) = rl.save_history(history_file.as_path()) {
-                                        println!(
-                                            "Save history to file {:?} error: {:?}",
-                                            history_file, e
-                                        );
-                                    }
-                                }
+                                save_history(&mut rl, history_file.as_ref());
                                 println!("Clear {} history command", len);
                             } else {
                                 println!("Unexpect params: {:?} for history command.", params);
@@ -374,6 +394,7 @@ where
                             } else if params.len() == 2 {
                                 output_format = OutputFormat::from_str(params[1])
                                     .unwrap_or(OutputFormat::TABLE);
+                                set_output_format(output_format);
                                 println!("Set output format to: {}", output_format);
                             } else {
                                 println!("Usage: output [format] 'Output format: JSON|TABLE'");
@@ -414,10 +435,12 @@ where
                 }
                 Err(ReadlineError::Interrupted) => {
                     println!("CTRL-C");
+                    save_history(&mut rl, history_file.as_ref());
                     break;
                 }
                 Err(ReadlineError::Eof) => {
                     println!("CTRL-D");
+                    save_history(&mut rl, history_file.as_ref());
                     break;
                 }
                 Err(err) => {
@@ -428,3 +451,11 @@ where
         }
     }
 }
+
+fn save_history(rl: &mut Editor<ConsoleHelper>, history_file: Option<&PathBuf>) {
+    if let Some(history_file) = history_file {
+        if let Err(e) = rl.save_history(history_file.as_path()) {
+            println!("Save history to file {:?} error: {:?}", history_file, e);
+        }
+    }
+}
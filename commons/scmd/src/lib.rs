@@ -3,11 +3,13 @@
 
 mod action;
 mod command;
+mod completer;
 mod context;
 pub mod error;
 mod result;
 
 pub use action::*;
 pub use command::*;
+pub use completer::ConsoleCompleter;
 pub use context::*;
 pub use result::*;
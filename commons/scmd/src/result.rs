@@ -5,10 +5,17 @@ use anyhow::{bail, Result};
 use cli_table::format::CellFormat;
 use cli_table::{Cell, Row, Table};
 use flatten_json::flatten;
+use once_cell::sync::Lazy;
 use serde_json::{json, Value};
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 
-#[derive(Copy, Clone, Debug)]
+/// The output format of the current command, the console can change it at runtime.
+static OUTPUT_FORMAT: Lazy<Mutex<OutputFormat>> = Lazy::new(|| Mutex::new(OutputFormat::TABLE));
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[allow(clippy::upper_case_acronyms)]
 pub enum OutputFormat {
     JSON,
@@ -36,6 +43,54 @@ impl std::fmt::Display for OutputFormat {
     }
 }
 
+pub fn output_format() -> OutputFormat {
+    *OUTPUT_FORMAT
+        .lock()
+        .expect("output format lock should not be poisoned.")
+}
+
+pub fn set_output_format(format: OutputFormat) {
+    *OUTPUT_FORMAT
+        .lock()
+        .expect("output format lock should not be poisoned.") = format;
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Print the side message of a command, such as the progress, which is not a part of the return item.
+/// It is printed to stderr in json format to keep stdout a single json document, and suppressed if quiet.
+pub fn print_side_message(message: std::fmt::Arguments) {
+    if is_quiet() {
+        return;
+    }
+    match output_format() {
+        OutputFormat::JSON => eprintln!("{}", message),
+        OutputFormat::TABLE => println!("{}", message),
+    }
+}
+
+/// `println!` for the side message of a command, see `print_side_message`.
+#[macro_export]
+macro_rules! side_println {
+    ($($arg:tt)*) => {
+        $crate::print_side_message(format_args!($($arg)*))
+    };
+}
+
+/// The json document of the action result, the return item itself if ok, `{"err": message}` if failed.
+pub fn action_result_to_json(result: Result<Value>) -> Value {
+    match result {
+        Ok(value) => value,
+        Err(err) => json!({ "err": err.to_string() }),
+    }
+}
+
 pub fn print_action_result(
     format: OutputFormat,
     result: Result<Value>,
@@ -48,12 +103,7 @@ pub fn print_action_result(
                 println!("{}", result.unwrap_err().to_string());
                 return Ok(());
             }
-
-            let value = match result {
-                Ok(value) => json!({ "ok": value }),
-                Err(err) => json!({"err": err.to_string()}),
-            };
-            print_json(value)
+            print_json(action_result_to_json(result))
         }
         OutputFormat::TABLE => {
             match result {
@@ -5,6 +5,8 @@ use crate::{ActorService, ServiceContext};
 use log::info;
 use std::any::Any;
 
+pub mod stall_service;
+
 pub trait MockHandler<S>: Send
 where
     S: ActorService,
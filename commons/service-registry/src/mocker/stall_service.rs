@@ -0,0 +1,40 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A service which can be blocked by event, for testing stalled service detection and recovery.
+
+use crate::{ActorService, EventHandler, ServiceContext, ServiceHandler, ServiceRequest};
+use std::time::Duration;
+
+#[derive(Default, Clone)]
+pub struct StallService {
+    pub stall_count: u64,
+}
+
+impl ActorService for StallService {}
+
+#[derive(Debug, Clone)]
+pub struct StallEvent {
+    pub millis: u64,
+}
+
+impl EventHandler<Self, StallEvent> for StallService {
+    fn handle_event(&mut self, msg: StallEvent, _ctx: &mut ServiceContext<StallService>) {
+        self.stall_count += 1;
+        // block the service thread to simulate a stuck service.
+        std::thread::sleep(Duration::from_millis(msg.millis));
+    }
+}
+
+#[derive(Debug)]
+pub struct GetStallCount;
+
+impl ServiceRequest for GetStallCount {
+    type Response = u64;
+}
+
+impl ServiceHandler<Self, GetStallCount> for StallService {
+    fn handle(&mut self, _msg: GetStallCount, _ctx: &mut ServiceContext<StallService>) -> u64 {
+        self.stall_count
+    }
+}
@@ -15,6 +15,8 @@ use anyhow::{format_err, Result};
 use futures::{Stream, StreamExt};
 use log::{debug, error, info};
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 const DEFAULT_MAIL_BOX_CAP: usize = 128;
 
@@ -24,6 +26,9 @@ where
 {
     proxy: Box<dyn HandlerProxy<S> + Send>,
     pub(crate) cache: ServiceCache,
+    /// Set by registry when the service is respawned in a new actor,
+    /// an abandoned actor drops the remaining messages in its mailbox.
+    abandoned: Arc<AtomicBool>,
 }
 
 impl<S> ServiceActor<S>
@@ -37,6 +42,7 @@ where
         Self {
             proxy: Box::new(ServiceHandlerProxy::new::<F>()),
             cache: ServiceCache::new(registry),
+            abandoned: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -47,9 +53,19 @@ where
         Self {
             proxy: Box::new(MockHandlerProxy::new(mocker)),
             cache: ServiceCache::new(registry),
+            abandoned: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    pub(crate) fn with_abandoned(mut self, abandoned: Arc<AtomicBool>) -> Self {
+        self.abandoned = abandoned;
+        self
+    }
+
+    fn is_abandoned(&self) -> bool {
+        self.abandoned.load(Ordering::SeqCst)
+    }
+
     fn notify_status(&self) {
         if self.cache.registry_ref().connected() {
             if let Err(e) = self
@@ -84,6 +100,12 @@ where
     }
 
     fn stopped(&mut self, ctx: &mut Self::Context) {
+        // the service has been respawned, do not stop the service, its bus subscriptions and
+        // status are owned by the new actor now.
+        if self.is_abandoned() {
+            info!("{} abandoned service actor stopped", S::service_name());
+            return;
+        }
         let mut service_ctx = ServiceContext::new(&mut self.cache, ctx);
         if let Err(e) = self.proxy.stop(&mut service_ctx) {
             error!("{} service stop fail: {:?}.", S::service_name(), e);
@@ -144,6 +166,12 @@ where
 
     fn handle(&mut self, msg: ServiceMessage<R>, ctx: &mut Self::Context) -> Self::Result {
         debug!("{} handle request: {:?}", S::service_name(), &msg.request);
+        if self.is_abandoned() {
+            return MessageResult(Err(format_err!(
+                "Service {} is respawned",
+                S::service_name()
+            )));
+        }
         if self.proxy.status().is_stopped() {
             return MessageResult(Err(format_err!("Service {} is stopped", S::service_name())));
         }
@@ -168,6 +196,9 @@ where
 
     fn handle(&mut self, msg: ServiceCmd, ctx: &mut Self::Context) -> Self::Result {
         debug!("{} Actor handle ServiceCmd: {:?}", S::service_name(), msg);
+        if self.is_abandoned() {
+            return Err(format_err!("Service {} is respawned", S::service_name()));
+        }
         let mut service_ctx = ServiceContext::new(&mut self.cache, ctx);
         let result = match msg {
             ServiceCmd::Stop => self.proxy.stop(&mut service_ctx),
@@ -213,6 +244,9 @@ where
     type Result = ();
 
     fn handle(&mut self, msg: ServiceEventStream<Fut>, ctx: &mut Self::Context) {
+        if self.is_abandoned() {
+            return;
+        }
         ctx.add_message_stream(msg.stream.map(EventMessage::new));
     }
 }
@@ -254,6 +288,10 @@ where
 
     fn handle(&mut self, msg: EventMessage<M>, ctx: &mut Self::Context) -> Self::Result {
         debug!("{} handle event: {:?}", S::service_name(), &msg.msg);
+        if self.is_abandoned() {
+            debug!("Service {} is respawned, drop event.", S::service_name());
+            return;
+        }
         if self.proxy.status().is_stopped() {
             info!("Service {} is already stopped", S::service_name());
             return;
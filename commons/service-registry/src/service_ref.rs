@@ -17,6 +17,7 @@ use log::warn;
 use std::any::type_name;
 use std::fmt::Debug;
 use std::sync::mpsc::TrySendError;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 #[derive(Clone)]
@@ -59,7 +60,7 @@ where
     fn from(service_ref: ServiceRef<S>) -> Self {
         Self {
             target_service: S::service_name(),
-            recipient: service_ref.addr.recipient::<EventMessage<M>>(),
+            recipient: service_ref.addr().recipient::<EventMessage<M>>(),
         }
     }
 }
@@ -82,7 +83,8 @@ pub struct ServiceRef<S>
 where
     S: ActorService + 'static,
 {
-    pub(crate) addr: Addr<ServiceActor<S>>,
+    // shared by all clones, so the clones follow the new actor after the service is respawned.
+    addr: Arc<RwLock<Addr<ServiceActor<S>>>>,
 }
 
 impl<S> Clone for ServiceRef<S>
@@ -101,7 +103,7 @@ where
     S: ActorService + 'static,
 {
     fn from(addr: Addr<ServiceActor<S>>) -> Self {
-        Self { addr }
+        Self::new(addr)
     }
 }
 
@@ -112,11 +114,22 @@ where
     S: ActorService,
 {
     pub fn new(addr: Addr<ServiceActor<S>>) -> Self {
-        Self { addr }
+        Self {
+            addr: Arc::new(RwLock::new(addr)),
+        }
+    }
+
+    pub(crate) fn addr(&self) -> Addr<ServiceActor<S>> {
+        self.addr.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Point this ref and all its clones to a new actor.
+    pub(crate) fn replace_addr(&self, addr: Addr<ServiceActor<S>>) {
+        *self.addr.write().unwrap_or_else(|e| e.into_inner()) = addr;
     }
 
     pub(crate) fn exec_service_cmd(&self, cmd: ServiceCmd) -> Result<()> {
-        self.addr.try_send(cmd).map_err(anyhow::Error::new)
+        self.addr().try_send(cmd).map_err(anyhow::Error::new)
     }
 
     pub fn start_self(&self) -> Result<()> {
@@ -133,7 +146,7 @@ where
 
     /// Returns whether the actor is still alive.
     pub fn connected(&self) -> bool {
-        self.addr.connected()
+        self.addr().connected()
     }
 
     /// Send a request to target service and wait response by default timeout.
@@ -142,9 +155,9 @@ where
         R: ServiceRequest + 'static,
         S: ServiceHandler<S, R>,
     {
+        let addr = self.addr();
         async move {
-            self.addr
-                .send(ServiceMessage::new(request))
+            addr.send(ServiceMessage::new(request))
                 .await
                 .map_err(anyhow::Error::new)?
         }
@@ -157,7 +170,7 @@ where
         R: ServiceRequest + 'static,
         S: ServiceHandler<S, R>,
     {
-        self.addr.do_send(ServiceMessage::new(request))
+        self.addr().do_send(ServiceMessage::new(request))
     }
 
     pub fn try_send<R>(&self, request: R) -> Result<(), TrySendError<R>>
@@ -165,7 +178,7 @@ where
         R: ServiceRequest + 'static,
         S: ServiceHandler<S, R>,
     {
-        self.addr
+        self.addr()
             .try_send(ServiceMessage::new(request))
             .map_err(|e| match e {
                 SendError::Full(m) => TrySendError::Full(m.into_inner()),
@@ -179,7 +192,7 @@ where
         S: EventHandler<S, M>,
         M: Clone + Debug + Send + 'static,
     {
-        self.addr
+        self.addr()
             .try_send(EventMessage::new(msg))
             .map_err(|e| match e {
                 SendError::Full(m) => TrySendError::Full(m.into_inner()),
@@ -202,7 +215,7 @@ where
         Fut: Stream<Item = M> + Send + 'static,
         M: Debug + Send + 'static,
     {
-        self.addr
+        self.addr()
             .try_send(ServiceEventStream { stream })
             .map_err(|e| match e {
                 SendError::Full(m) => TrySendError::Full(m.stream),
@@ -225,7 +238,7 @@ where
     /// Get self service status
     pub async fn self_status(&self) -> ServiceStatus {
         match self
-            .addr
+            .addr()
             .send(ServiceQuery::Status)
             .timeout(Duration::from_millis(50))
             .await
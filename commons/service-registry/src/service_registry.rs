@@ -6,28 +6,38 @@ use crate::mocker::MockHandler;
 use crate::service::{ActorService, ServiceFactory};
 use crate::service_actor::ServiceActor;
 use crate::{
-    EventHandler, ServiceCmd, ServiceContext, ServiceHandler, ServiceInfo, ServicePing, ServiceRef,
-    ServiceRequest, ServiceStatus,
+    EventHandler, ServiceCmd, ServiceContext, ServiceHandler, ServiceInfo, ServiceLatency,
+    ServicePing, ServiceRef, ServiceRequest, ServiceStatus,
 };
 use actix::prelude::SendError;
-use actix::{Actor, AsyncContext};
+use actix::{Actor, Addr, AsyncContext};
 use actix_rt::Arbiter;
 use anyhow::{bail, format_err, Result};
 use futures::executor::block_on;
+use futures::future::{BoxFuture, Either};
+use futures::FutureExt;
+use futures_timer::Delay;
 use log::info;
 use std::any::{type_name, Any, TypeId};
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+type ActorFactory<S> = Arc<dyn Fn(ServiceRef<RegistryService>) -> ServiceActor<S> + Send + Sync>;
 
 trait ServiceRefProxy: Send + Sync {
     fn service_name(&self) -> &'static str;
     fn service_info(&self) -> ServiceInfo;
     fn status(&self) -> ServiceStatus;
     fn check_status(&self) -> ServiceStatus;
+    fn ping(&self, timeout: Duration) -> BoxFuture<'static, ServiceLatency>;
     fn update_status(&mut self, status: ServiceStatus);
     fn exec_service_cmd(&self, service_cmd: ServiceCmd) -> Result<()>;
+    fn respawn(&mut self, registry_ref: ServiceRef<RegistryService>) -> Result<()>;
     fn shutdown(&self) -> Result<()>;
     fn as_any(&self) -> &dyn Any;
 }
@@ -39,21 +49,48 @@ where
     arbiter: Arbiter,
     status: ServiceStatus,
     service_ref: ServiceRef<S>,
+    abandoned: Arc<AtomicBool>,
+    // None if the service is a mocker, which can not be respawned.
+    factory: Option<ActorFactory<S>>,
 }
 
 impl<S> ServiceHolder<S>
 where
     S: ActorService,
 {
-    pub fn new(arbiter: Arbiter, service_ref: ServiceRef<S>) -> Self {
+    pub fn new(
+        arbiter: Arbiter,
+        service_ref: ServiceRef<S>,
+        abandoned: Arc<AtomicBool>,
+        factory: Option<ActorFactory<S>>,
+    ) -> Self {
         Self {
             arbiter,
             status: ServiceStatus::Started,
             service_ref,
+            abandoned,
+            factory,
         }
     }
 }
 
+fn start_actor<S, F>(
+    registry_ref: ServiceRef<RegistryService>,
+    f: F,
+) -> (Arbiter, Addr<ServiceActor<S>>, Arc<AtomicBool>)
+where
+    S: ActorService + 'static,
+    F: FnOnce(ServiceRef<RegistryService>) -> ServiceActor<S> + Send + 'static,
+{
+    let arbiter = Arbiter::new();
+    let abandoned = Arc::new(AtomicBool::new(false));
+    let actor_abandoned = abandoned.clone();
+    let addr = ServiceActor::start_in_arbiter(&arbiter, move |_ctx| {
+        f(registry_ref).with_abandoned(actor_abandoned)
+    });
+    (arbiter, addr, abandoned)
+}
+
 impl<S> ServiceRefProxy for ServiceHolder<S>
 where
     S: ActorService,
@@ -70,7 +107,7 @@ where
     }
 
     fn status(&self) -> ServiceStatus {
-        if self.service_ref.addr.connected() {
+        if self.service_ref.connected() {
             self.status
         } else {
             ServiceStatus::Shutdown
@@ -79,7 +116,7 @@ where
 
     fn check_status(&self) -> ServiceStatus {
         if self.status.is_started() {
-            if let Err(e) = self.service_ref.addr.try_send(ServicePing) {
+            if let Err(e) = self.service_ref.addr().try_send(ServicePing) {
                 match e {
                     SendError::Full(_) => ServiceStatus::Unavailable,
                     SendError::Closed(_) => ServiceStatus::Shutdown,
@@ -92,6 +129,29 @@ where
         }
     }
 
+    fn ping(&self, timeout: Duration) -> BoxFuture<'static, ServiceLatency> {
+        let name = self.service_name().to_string();
+        let status = self.status();
+        let addr = self.service_ref.addr();
+        async move {
+            let latency_millis = if status.is_started() {
+                let now = Instant::now();
+                match futures::future::select(addr.send(ServicePing), Delay::new(timeout)).await {
+                    Either::Left((Ok(_), _)) => Some(now.elapsed().as_millis() as u64),
+                    _ => None,
+                }
+            } else {
+                None
+            };
+            ServiceLatency {
+                name,
+                status,
+                latency_millis,
+            }
+        }
+        .boxed()
+    }
+
     fn update_status(&mut self, status: ServiceStatus) {
         self.status = status;
     }
@@ -100,6 +160,27 @@ where
         self.service_ref.exec_service_cmd(service_cmd)
     }
 
+    fn respawn(&mut self, registry_ref: ServiceRef<RegistryService>) -> Result<()> {
+        let factory = self.factory.clone().ok_or_else(|| {
+            format_err!(
+                "Service {} is not registered by factory, can not respawn.",
+                self.service_name()
+            )
+        })?;
+        info!("Start to respawn {}.", self.service_name());
+        // The old actor may be blocked in a handler, so the command can not go through its
+        // mailbox. Abandon it, its arbiter exits after the running handler returns.
+        self.abandoned.store(true, Ordering::SeqCst);
+        self.arbiter.stop();
+        let (arbiter, addr, abandoned) =
+            start_actor(registry_ref, move |registry_ref| factory(registry_ref));
+        self.service_ref.replace_addr(addr);
+        self.arbiter = arbiter;
+        self.abandoned = abandoned;
+        self.status = ServiceStatus::Started;
+        Ok(())
+    }
+
     fn shutdown(&self) -> Result<()> {
         info!("Start to shutdown {}.", self.service_name());
         self.arbiter.stop();
@@ -184,7 +265,15 @@ impl Registry {
             .map(|handle| handle.check_status())
     }
 
-    fn do_register<S, F>(&mut self, f: F) -> Result<ServiceRef<S>>
+    /// Ping all services, the returned futures should be awaited out of the registry service.
+    pub fn ping(&self, timeout: Duration) -> Vec<BoxFuture<'static, ServiceLatency>> {
+        self.services
+            .iter()
+            .map(|handle| handle.ping(timeout))
+            .collect()
+    }
+
+    fn do_register<S, F>(&mut self, f: F, factory: Option<ActorFactory<S>>) -> Result<ServiceRef<S>>
     where
         S: ActorService + 'static,
         F: FnOnce(ServiceRef<RegistryService>) -> ServiceActor<S> + Send + 'static,
@@ -195,11 +284,9 @@ impl Registry {
         }
         info!("Registry service: {}", service_name);
 
-        let arbiter = Arbiter::new();
-        let registry_ref = self.service_ref.clone();
-        let addr = ServiceActor::start_in_arbiter(&arbiter, move |_ctx| f(registry_ref));
+        let (arbiter, addr, abandoned) = start_actor(self.service_ref.clone(), f);
         let service_ref: ServiceRef<S> = addr.into();
-        let holder = ServiceHolder::new(arbiter, service_ref.clone());
+        let holder = ServiceHolder::new(arbiter, service_ref.clone(), abandoned, factory);
         self.services.push(Box::new(holder));
         Ok(service_ref)
     }
@@ -209,14 +296,41 @@ impl Registry {
         S: ActorService + 'static,
         F: ServiceFactory<S> + 'static,
     {
-        self.do_register(ServiceActor::new::<F>)
+        let factory: ActorFactory<S> = Arc::new(ServiceActor::new::<F>);
+        let actor_factory = factory.clone();
+        self.do_register(
+            move |registry_ref| actor_factory(registry_ref),
+            Some(factory),
+        )
     }
 
     pub fn register_mocker<S>(&mut self, mocker: Box<dyn MockHandler<S>>) -> Result<ServiceRef<S>>
     where
         S: ActorService + 'static,
     {
-        self.do_register(|registry_ref| ServiceActor::new_mocker(registry_ref, mocker))
+        self.do_register(
+            |registry_ref| ServiceActor::new_mocker(registry_ref, mocker),
+            None,
+        )
+    }
+
+    /// Start the service in a new actor without going through the old actor's mailbox,
+    /// so a service blocked in a handler or with a full mailbox can be recovered.
+    /// The registered `ServiceRef`s are redirected to the new actor, but the refs created by
+    /// the old actor itself (`ServiceContext::self_ref`) become disconnected.
+    pub fn respawn_service(&mut self, service_name: &str) -> Result<()> {
+        if service_name == BusService::service_name() {
+            bail!("Service {} can not respawn.", service_name)
+        }
+        let registry_ref = self.service_ref.clone();
+        match self
+            .services
+            .iter_mut()
+            .find(|proxy| proxy.service_name() == service_name)
+        {
+            Some(proxy) => proxy.respawn(registry_ref),
+            None => bail!("Can not find service {}", service_name),
+        }
     }
 
     /// Stop service thread and remove from registry.
@@ -652,6 +766,31 @@ impl ServiceHandler<Self, CheckServiceStatusRequest> for RegistryService {
     }
 }
 
+#[derive(Debug)]
+pub struct PingServicesRequest {
+    timeout: Duration,
+}
+
+impl PingServicesRequest {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+impl ServiceRequest for PingServicesRequest {
+    type Response = Vec<BoxFuture<'static, ServiceLatency>>;
+}
+
+impl ServiceHandler<Self, PingServicesRequest> for RegistryService {
+    fn handle(
+        &mut self,
+        msg: PingServicesRequest,
+        _ctx: &mut ServiceContext<RegistryService>,
+    ) -> Vec<BoxFuture<'static, ServiceLatency>> {
+        self.registry.ping(msg.timeout)
+    }
+}
+
 #[derive(Debug)]
 pub struct ServiceCmdRequest {
     service_name: String,
@@ -682,6 +821,31 @@ impl ServiceHandler<Self, ServiceCmdRequest> for RegistryService {
     }
 }
 
+#[derive(Debug)]
+pub struct RespawnServiceRequest {
+    service_name: String,
+}
+
+impl RespawnServiceRequest {
+    pub fn new(service_name: String) -> Self {
+        Self { service_name }
+    }
+}
+
+impl ServiceRequest for RespawnServiceRequest {
+    type Response = Result<()>;
+}
+
+impl ServiceHandler<Self, RespawnServiceRequest> for RegistryService {
+    fn handle(
+        &mut self,
+        msg: RespawnServiceRequest,
+        _ctx: &mut ServiceContext<RegistryService>,
+    ) -> Result<()> {
+        self.registry.respawn_service(msg.service_name.as_str())
+    }
+}
+
 #[derive(Debug)]
 pub enum SystemCmd {
     ///Shutdown system
@@ -794,12 +958,20 @@ pub trait RegistryAsyncService {
     fn restart_service_sync(&self, service_name: &str) -> Result<()> {
         block_on(async move { self.restart_service(service_name).await })
     }
+
+    /// Abandon the service's actor and start the service in a new actor, unlike
+    /// `restart_service`, this works even if the service's mailbox is stuck.
+    async fn respawn_service(&self, service_name: &str) -> Result<()>;
     async fn check_service_status(&self, service_name: &str) -> Result<ServiceStatus>;
 
     fn check_service_status_sync(&self, service_name: &str) -> Result<ServiceStatus> {
         block_on(async move { self.check_service_status(service_name).await })
     }
 
+    /// Ping every registered service's mailbox, a started service which can not respond in
+    /// `timeout` is reported with `latency_millis: None`.
+    async fn ping_services(&self, timeout: Duration) -> Result<Vec<ServiceLatency>>;
+
     async fn put_shared<T>(&self, t: T) -> Result<()>
     where
         T: Send + Sync + Clone + 'static;
@@ -925,11 +1097,21 @@ impl RegistryAsyncService for ServiceRef<RegistryService> {
         .await?
     }
 
+    async fn respawn_service(&self, service_name: &str) -> Result<()> {
+        self.send(RespawnServiceRequest::new(service_name.to_string()))
+            .await?
+    }
+
     async fn check_service_status(&self, service_name: &str) -> Result<ServiceStatus> {
         self.send(CheckServiceStatusRequest::new(service_name.to_string()))
             .await?
     }
 
+    async fn ping_services(&self, timeout: Duration) -> Result<Vec<ServiceLatency>> {
+        let pings = self.send(PingServicesRequest::new(timeout)).await?;
+        Ok(futures::future::join_all(pings).await)
+    }
+
     async fn put_shared<T>(&self, value: T) -> Result<()>
     where
         T: Send + Sync + Clone + 'static,
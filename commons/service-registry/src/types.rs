@@ -35,6 +35,22 @@ pub struct ServiceInfo {
     pub status: ServiceStatus,
 }
 
+/// The result of ping a service by its mailbox.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ServiceLatency {
+    pub name: String,
+    pub status: ServiceStatus,
+    /// The round trip time of the ping message, None if the service is not started or the ping timeout.
+    pub latency_millis: Option<u64>,
+}
+
+impl ServiceLatency {
+    /// The service is started but can not process the ping message in time.
+    pub fn is_stalled(&self) -> bool {
+        self.status.is_started() && self.latency_millis.is_none()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum ServiceCmd {
     Start,
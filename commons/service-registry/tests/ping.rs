@@ -0,0 +1,64 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::cal_service::CalService;
+use futures_timer::Delay;
+use starcoin_service_registry::mocker::stall_service::{StallEvent, StallService};
+use starcoin_service_registry::{ActorService, RegistryAsyncService, RegistryService};
+use std::time::Duration;
+
+pub mod common;
+
+#[stest::test]
+async fn test_ping_stalled_service() {
+    let registry = RegistryService::launch();
+    registry.register::<CalService>().await.unwrap();
+    let stall_service = registry.register::<StallService>().await.unwrap();
+
+    let latencies = registry
+        .ping_services(Duration::from_millis(500))
+        .await
+        .unwrap();
+    // BusService + CalService + StallService = 3
+    assert_eq!(3, latencies.len());
+    assert!(latencies.iter().all(|latency| !latency.is_stalled()));
+
+    stall_service.notify(StallEvent { millis: 1000 }).unwrap();
+    let latencies = registry
+        .ping_services(Duration::from_millis(200))
+        .await
+        .unwrap();
+    let stalled = latencies
+        .iter()
+        .filter(|latency| latency.is_stalled())
+        .map(|latency| latency.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(stalled, vec![StallService::service_name()]);
+
+    // the service recover after the stall.
+    Delay::new(Duration::from_millis(1000)).await;
+    let latencies = registry
+        .ping_services(Duration::from_millis(500))
+        .await
+        .unwrap();
+    assert!(latencies.iter().all(|latency| !latency.is_stalled()));
+
+    // stopped service is not pinged and not treated as stalled.
+    registry
+        .stop_service(StallService::service_name())
+        .await
+        .unwrap();
+    // wait the service status change event to be handled by registry.
+    Delay::new(Duration::from_millis(200)).await;
+    let latencies = registry
+        .ping_services(Duration::from_millis(500))
+        .await
+        .unwrap();
+    let stall_latency = latencies
+        .iter()
+        .find(|latency| latency.name == StallService::service_name())
+        .unwrap();
+    assert!(stall_latency.latency_millis.is_none());
+    assert!(!stall_latency.is_stalled());
+    registry.shutdown_system().await.unwrap();
+}
@@ -0,0 +1,89 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::cal_service::{CalAsyncService, CalService};
+use futures::future::Either;
+use futures_timer::Delay;
+use starcoin_service_registry::bus::BusService;
+use starcoin_service_registry::mocker::mock;
+use starcoin_service_registry::mocker::stall_service::{GetStallCount, StallEvent, StallService};
+use starcoin_service_registry::{
+    ActorService, RegistryAsyncService, RegistryService, ServiceStatus,
+};
+use std::time::Duration;
+
+pub mod common;
+
+#[stest::test]
+async fn test_respawn_stalled_service() {
+    let registry = RegistryService::launch();
+    let stall_service = registry.register::<StallService>().await.unwrap();
+    stall_service.notify(StallEvent { millis: 1000 }).unwrap();
+    stall_service.notify(StallEvent { millis: 3000 }).unwrap();
+    Delay::new(Duration::from_millis(100)).await;
+
+    // a restart command is queued behind the stall events.
+    registry
+        .restart_service(StallService::service_name())
+        .await
+        .unwrap();
+    registry
+        .respawn_service(StallService::service_name())
+        .await
+        .unwrap();
+
+    // the old ref is redirected to the new service, which respond immediately.
+    let stall_count = match futures::future::select(
+        stall_service.send(GetStallCount),
+        Delay::new(Duration::from_millis(500)),
+    )
+    .await
+    {
+        Either::Left((result, _)) => result.unwrap(),
+        Either::Right(_) => panic!("Respawned service should not be blocked by the old actor."),
+    };
+    assert_eq!(stall_count, 0);
+    assert_eq!(
+        registry
+            .check_service_status(StallService::service_name())
+            .await
+            .unwrap(),
+        ServiceStatus::Started
+    );
+
+    // the abandoned actor exits after the running handler returns,
+    // and does not change the status of the new service.
+    Delay::new(Duration::from_millis(1500)).await;
+    assert_eq!(stall_service.send(GetStallCount).await.unwrap(), 0);
+    let latencies = registry
+        .ping_services(Duration::from_millis(500))
+        .await
+        .unwrap();
+    assert!(latencies.iter().all(|latency| !latency.is_stalled()));
+    assert_eq!(
+        registry
+            .check_service_status(StallService::service_name())
+            .await
+            .unwrap(),
+        ServiceStatus::Started
+    );
+    registry.shutdown_system().await.unwrap();
+}
+
+#[stest::test]
+async fn test_respawn_not_supported_service() {
+    let registry = RegistryService::launch();
+    let mocker = mock(|_request, _ctx| Box::new(0u64));
+    let cal_service = registry.register_mocker(mocker).await.unwrap();
+    assert!(registry
+        .respawn_service(CalService::service_name())
+        .await
+        .is_err());
+    assert_eq!(cal_service.add(1).await.unwrap(), 0);
+    assert!(registry
+        .respawn_service(BusService::service_name())
+        .await
+        .is_err());
+    assert!(registry.respawn_service("not_exist").await.is_err());
+    registry.shutdown_system().await.unwrap();
+}
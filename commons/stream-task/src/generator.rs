@@ -99,6 +99,7 @@ where
 {
     init_state: S,
     buffer_size: usize,
+    sink_buffer_size: Option<usize>,
     max_retry_times: u64,
     delay_milliseconds: u64,
     collector: C,
@@ -123,6 +124,7 @@ where
         Self {
             init_state,
             buffer_size,
+            sink_buffer_size: None,
             max_retry_times,
             delay_milliseconds: delay_milliseconds_on_error,
             collector,
@@ -130,6 +132,12 @@ where
             custom_error_handle,
         }
     }
+
+    /// Set the buffer size of the items waiting for the collector, default is the `buffer_size` of the sub tasks.
+    pub fn with_sink_buffer_size(mut self, sink_buffer_size: usize) -> Self {
+        self.sink_buffer_size = Some(sink_buffer_size);
+        self
+    }
 }
 
 impl<S, C> Generator for TaskGenerator<S, C>
@@ -164,8 +172,9 @@ where
                 })
                 .flatten()
                 .map_err(SinkError::StreamTaskError);
+            let sink_buffer_size = self.sink_buffer_size.unwrap_or(self.buffer_size);
             let mut sink =
-                FutureTaskSink::new(self.collector, self.buffer_size, event_handle.clone());
+                FutureTaskSink::new(self.collector, sink_buffer_size, event_handle.clone());
             SinkError::map_result(sink.send_all(&mut buffered_stream).await)?;
             SinkError::map_result(sink.close().await)?;
             let output = sink.wait_output().await?;
@@ -1,7 +1,9 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use anyhow::{format_err, Result};
+use std::path::Path;
+use std::time::Duration;
 use systemstat::{Platform, System};
 
 pub fn get_free_mem_size() -> Result<u64> {
@@ -12,3 +14,71 @@ pub fn get_free_mem_size() -> Result<u64> {
     };
     Ok(free)
 }
+
+/// The disk usage of a file system volume, in bytes.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DiskUsage {
+    pub total: u64,
+    pub free: u64,
+    /// The free space available to unprivileged users.
+    pub available: u64,
+}
+
+impl DiskUsage {
+    pub fn used(&self) -> u64 {
+        self.total.saturating_sub(self.free)
+    }
+}
+
+/// Get the disk usage of the volume which `path` is on, it is the mount with the longest mount point containing `path`.
+pub fn get_disk_usage(path: &Path) -> Result<DiskUsage> {
+    let path = path.canonicalize()?;
+    let mounts = System::new().mounts()?;
+    let mount = mounts
+        .iter()
+        .filter(|mount| path.starts_with(mount.fs_mounted_on.as_str()))
+        .max_by_key(|mount| mount.fs_mounted_on.len())
+        .ok_or_else(|| format_err!("Can not find the mount of path {:?}", path))?;
+    Ok(DiskUsage {
+        total: mount.total.as_u64(),
+        free: mount.free.as_u64(),
+        available: mount.avail.as_u64(),
+    })
+}
+
+/// Get the count of file descriptors opened by current process, only supported on linux.
+pub fn get_open_fds() -> Result<u64> {
+    if cfg!(target_os = "linux") {
+        Ok(std::fs::read_dir("/proc/self/fd")?.count() as u64)
+    } else {
+        Err(format_err!("open fds is not supported on this platform"))
+    }
+}
+
+/// Get the resident memory size of current process in bytes.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+pub fn get_process_rss() -> Result<u64> {
+    let process = psutil::process::Process::current().map_err(|e| format_err!("{:?}", e))?;
+    let memory_info = process.memory_info().map_err(|e| format_err!("{:?}", e))?;
+    Ok(memory_info.rss())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+pub fn get_process_rss() -> Result<u64> {
+    Err(format_err!("process rss is not supported on this platform"))
+}
+
+/// Get how long current process has been running.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+pub fn get_process_uptime() -> Result<Duration> {
+    let process = psutil::process::Process::current().map_err(|e| format_err!("{:?}", e))?;
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?;
+    Ok(now.checked_sub(process.create_time()).unwrap_or_default())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+pub fn get_process_uptime() -> Result<Duration> {
+    Err(format_err!(
+        "process uptime is not supported on this platform"
+    ))
+}
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::any::Any;
-use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, TryRecvError};
 use std::thread;
 use std::time::Duration;
 use thiserror::Error;
@@ -66,6 +66,11 @@ impl<T> std::fmt::Debug for TimeoutJoinHandle<T> {
 }
 
 impl<T> TimeoutJoinHandle<T> {
+    /// Check whether the thread has finished, without blocking.
+    pub fn is_finished(&self) -> bool {
+        !matches!(self.signal.try_recv(), Err(TryRecvError::Empty))
+    }
+
     /// if thread join wait timeout, return handle self, otherwise return thread join result.
     pub fn join(self, timeout: Duration) -> Result<T, ThreadJoinError<T>> {
         if let Err(RecvTimeoutError::Timeout) = self.signal.recv_timeout(timeout) {
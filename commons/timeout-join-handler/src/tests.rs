@@ -49,3 +49,14 @@ fn test_panic() {
     assert!(error.is_panic());
     assert_eq!(error.panic_message().unwrap(), "test thread panic");
 }
+
+#[test]
+fn test_is_finished() {
+    let handle = spawn(|| {
+        thread::sleep(Duration::from_millis(100));
+    });
+    assert!(!handle.is_finished());
+    thread::sleep(Duration::from_millis(300));
+    assert!(handle.is_finished());
+    assert!(handle.join(Duration::from_millis(1000)).is_ok());
+}
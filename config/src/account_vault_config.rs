@@ -7,9 +7,12 @@ use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use structopt::StructOpt;
 
 static DEFAULT_DIR: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("account_vaults"));
+const DEFAULT_MAX_TXN_HISTORY: u64 = 1000;
+const DEFAULT_MAX_UNLOCK_DURATION: u64 = 86400;
 
 #[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, StructOpt)]
 #[serde(deny_unknown_fields)]
@@ -20,6 +23,18 @@ pub struct AccountVaultConfig {
     /// Default: account_vaults in data_dir
     dir: Option<PathBuf>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "vault-max-txn-history")]
+    /// Max txn history entries kept for each account, the oldest entries are pruned.
+    /// Default: 1000
+    max_txn_history: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "vault-max-unlock-duration")]
+    /// Max seconds an account can be unlocked, the longer requested unlock duration is capped to it.
+    /// Default: 86400
+    max_unlock_duration: Option<u64>,
+
     #[serde(skip)]
     #[structopt(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -38,6 +53,17 @@ impl AccountVaultConfig {
             self.base().data_dir().join(path)
         }
     }
+
+    pub fn max_txn_history(&self) -> u64 {
+        self.max_txn_history.unwrap_or(DEFAULT_MAX_TXN_HISTORY)
+    }
+
+    pub fn max_unlock_duration(&self) -> Duration {
+        Duration::from_secs(
+            self.max_unlock_duration
+                .unwrap_or(DEFAULT_MAX_UNLOCK_DURATION),
+        )
+    }
 }
 
 impl ConfigModule for AccountVaultConfig {
@@ -46,6 +72,12 @@ impl ConfigModule for AccountVaultConfig {
         if opt.vault.dir.is_some() {
             self.dir = opt.vault.dir.clone();
         }
+        if opt.vault.max_txn_history.is_some() {
+            self.max_txn_history = opt.vault.max_txn_history;
+        }
+        if opt.vault.max_unlock_duration.is_some() {
+            self.max_unlock_duration = opt.vault.max_unlock_duration;
+        }
         Ok(())
     }
 }
@@ -14,6 +14,7 @@ use starcoin_crypto::{
     HashValue, ValidCryptoMaterialStringExt,
 };
 use starcoin_uint::U256;
+use starcoin_vm_types::account_address::AccountAddress;
 use starcoin_vm_types::account_config::genesis_address;
 use starcoin_vm_types::event::EventHandle;
 use starcoin_vm_types::gas_schedule::{
@@ -29,13 +30,15 @@ use starcoin_vm_types::on_chain_resource::Epoch;
 use starcoin_vm_types::time::{TimeService, TimeServiceType};
 use starcoin_vm_types::token::stc::STCUnit;
 use starcoin_vm_types::token::token_value::TokenValue;
+use starcoin_vm_types::transaction::authenticator::AuthenticationKey;
 use starcoin_vm_types::transaction::{RawUserTransaction, SignedUserTransaction};
+use std::collections::HashSet;
 use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::fmt::{self, Display, Formatter};
 use std::fs::File;
 use std::io::{Read, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -181,6 +184,21 @@ impl BuiltinNetworkID {
     }
 }
 
+/// Parse a chain id from a number or a builtin network name, such as `251` or `barnard`.
+pub fn parse_chain_id(s: &str) -> Result<ChainId> {
+    match s.parse::<u8>() {
+        Ok(id) => Ok(ChainId::new(id)),
+        Err(_) => BuiltinNetworkID::from_str(s)
+            .map(|network| network.chain_id())
+            .map_err(|_| {
+                format_err!(
+                    "Invalid chain id: {}, expect a number or a builtin network name",
+                    s
+                )
+            }),
+    }
+}
+
 impl Default for BuiltinNetworkID {
     fn default() -> Self {
         BuiltinNetworkID::Dev
@@ -323,6 +341,13 @@ impl ChainNetworkID {
         }
     }
 
+    pub fn chain_name(&self) -> String {
+        match self {
+            Self::Builtin(b) => b.chain_name(),
+            Self::Custom(c) => c.chain_name().to_string(),
+        }
+    }
+
     pub fn assert_test_or_dev(&self) -> Result<()> {
         if !self.is_test_or_dev() {
             bail!("Only support test or dev network.")
@@ -603,6 +628,45 @@ pub struct GenesisConfig {
     pub time_service_type: TimeServiceType,
     /// transaction timeout
     pub transaction_timeout: u64,
+    /// The genesis accounts file, such as the output of `starcoin dev genesis-accounts`,
+    /// a relative path is relative to the genesis config file.
+    /// The accounts in it are appended to `genesis_accounts` when the config is loaded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genesis_accounts_file: Option<PathBuf>,
+    /// The accounts funded from the pre mine amount of the association account in the genesis block.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub genesis_accounts: Vec<GenesisAccount>,
+}
+
+/// An account which is created and funded in the genesis block.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct GenesisAccount {
+    pub address: AccountAddress,
+    pub public_key: Ed25519PublicKey,
+    pub balance: u128,
+}
+
+impl GenesisAccount {
+    pub fn auth_key(&self) -> AuthenticationKey {
+        AuthenticationKey::ed25519(&self.public_key)
+    }
+
+    /// Load the accounts from a json array file, the unknown fields such as the private key are ignored.
+    pub fn load_accounts<P>(path: P) -> Result<Vec<GenesisAccount>>
+    where
+        P: AsRef<Path>,
+    {
+        let mut file = File::open(&path).map_err(|e| {
+            format_err!(
+                "Open genesis accounts file {:?} error: {}",
+                path.as_ref(),
+                e
+            )
+        })?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
 }
 
 impl GenesisConfig {
@@ -651,7 +715,49 @@ impl GenesisConfig {
         let mut file = File::open(&path)?;
         let mut contents = String::new();
         file.read_to_string(&mut contents)?;
-        Ok(serde_json::from_str(&contents)?)
+        let mut config: GenesisConfig = serde_json::from_str(&contents)?;
+        if let Some(accounts_file) = config.genesis_accounts_file.take() {
+            let accounts_file = match path.as_ref().parent() {
+                Some(dir) => dir.join(accounts_file),
+                None => accounts_file,
+            };
+            config
+                .genesis_accounts
+                .extend(GenesisAccount::load_accounts(accounts_file)?);
+        }
+        config.validate_genesis_accounts()?;
+        Ok(config)
+    }
+
+    /// Check the genesis accounts, the address should be derived from the public key,
+    /// and the addresses should not be duplicated. The total balance is minted by the pre mine,
+    /// so it should not exceed the `pre_mine_amount`.
+    pub fn validate_genesis_accounts(&self) -> Result<()> {
+        let mut addresses = HashSet::new();
+        let mut total_balance = 0u128;
+        for account in &self.genesis_accounts {
+            ensure!(
+                addresses.insert(account.address),
+                "Duplicate genesis account {}",
+                account.address
+            );
+            ensure!(
+                account.auth_key().derived_address() == account.address,
+                "Genesis account {} is not derived from the public key {}",
+                account.address,
+                account.public_key
+            );
+            total_balance = total_balance
+                .checked_add(account.balance)
+                .ok_or_else(|| format_err!("The total balance of genesis accounts overflow"))?;
+        }
+        ensure!(
+            total_balance <= self.pre_mine_amount,
+            "The total balance {} of genesis accounts exceeds the pre mine amount {}",
+            total_balance,
+            self.pre_mine_amount
+        );
+        Ok(())
     }
 
     pub fn save<P>(&self, path: P) -> Result<()>
@@ -816,6 +922,8 @@ pub static TEST_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 1000, // 1h
         },
         transaction_timeout: ONE_DAY,
+        genesis_accounts_file: None,
+        genesis_accounts: vec![],
     }
 });
 
@@ -865,6 +973,8 @@ pub static DEV_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 1000, // 1h
         },
         transaction_timeout: ONE_DAY,
+        genesis_accounts_file: None,
+        genesis_accounts: vec![],
     }
 });
 
@@ -920,6 +1030,8 @@ pub static HALLEY_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 1000, // 1h
         },
         transaction_timeout: ONE_DAY,
+        genesis_accounts_file: None,
+        genesis_accounts: vec![],
     }
 });
 
@@ -976,6 +1088,8 @@ pub static PROXIMA_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 1000, // 1 minute
         },
         transaction_timeout: ONE_DAY,
+        genesis_accounts_file: None,
+        genesis_accounts: vec![],
     }
 });
 
@@ -1032,6 +1146,8 @@ pub static BARNARD_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 24 * 1000, // 1d
         },
         transaction_timeout: ONE_DAY,
+        genesis_accounts_file: None,
+        genesis_accounts: vec![],
     }
 });
 
@@ -1083,5 +1199,7 @@ pub static MAIN_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 24 * 1000, // 1d
         },
         transaction_timeout: ONE_DAY,
+        genesis_accounts_file: None,
+        genesis_accounts: vec![],
     }
 });
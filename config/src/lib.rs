@@ -3,7 +3,6 @@
 
 use crate::account_vault_config::AccountVaultConfig;
 use crate::helper::{load_config, save_config};
-use crate::sync_config::SyncConfig;
 use anyhow::{ensure, format_err, Result};
 use git_version::git_version;
 use once_cell::sync::Lazy;
@@ -11,6 +10,7 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use starcoin_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
 use starcoin_crypto::keygen::KeyGen;
 use starcoin_logger::prelude::*;
+use starcoin_vm_types::genesis_config::ChainId;
 use std::convert::TryFrom;
 use std::fs;
 use std::fs::create_dir_all;
@@ -34,6 +34,7 @@ mod logger_config;
 mod metrics_config;
 mod miner_config;
 mod network_config;
+mod reload;
 mod rpc_config;
 mod storage_config;
 mod stratum_config;
@@ -41,6 +42,8 @@ mod sync_config;
 #[cfg(test)]
 mod tests;
 mod txpool_config;
+mod watchdog_config;
+mod webhook_config;
 
 use crate::stratum_config::StratumConfig;
 pub use api_config::{Api, ApiSet};
@@ -50,23 +53,31 @@ pub use available_port::{
 };
 pub use diem_temppath::TempPath;
 pub use genesis_config::{
-    BuiltinNetworkID, ChainNetwork, ChainNetworkID, FutureBlockParameter,
-    FutureBlockParameterResolver, GenesisBlockParameter, GenesisBlockParameterConfig,
-    GenesisConfig, DEFAULT_GAS_CONSTANTS, DEV_CONFIG, HALLEY_CONFIG, INITIAL_GAS_SCHEDULE,
-    MAIN_CONFIG, PROXIMA_CONFIG, TEST_CONFIG, TEST_GAS_SCHEDULE,
+    parse_chain_id, BuiltinNetworkID, ChainNetwork, ChainNetworkID, FutureBlockParameter,
+    FutureBlockParameterResolver, GenesisAccount, GenesisBlockParameter,
+    GenesisBlockParameterConfig, GenesisConfig, DEFAULT_GAS_CONSTANTS, DEV_CONFIG, HALLEY_CONFIG,
+    INITIAL_GAS_SCHEDULE, MAIN_CONFIG, PROXIMA_CONFIG, TEST_CONFIG, TEST_GAS_SCHEDULE,
 };
 pub use logger_config::LoggerConfig;
 pub use metrics_config::MetricsConfig;
 pub use miner_config::{MinerClientConfig, MinerConfig};
 pub use network_config::{NetworkConfig, NetworkRpcQuotaConfiguration};
+pub use reload::{
+    ConfigFieldChange, ConfigReloadResult, NodeConfigChangedEvent, HOT_RELOADABLE_FIELDS,
+};
 pub use rpc_config::{
-    ApiQuotaConfiguration, HttpConfiguration, IpcConfiguration, RpcConfig, TcpConfiguration,
-    WsConfiguration,
+    ApiFilterConfiguration, ApiQuotaConfiguration, HealthConfiguration, HttpConfiguration,
+    IpcConfiguration, RpcConfig, TcpConfiguration, WsConfiguration,
 };
 pub use starcoin_crypto::ed25519::genesis_key_pair;
 pub use starcoin_vm_types::time::{MockTimeService, RealTimeService, TimeService};
-pub use storage_config::{RocksdbConfig, StorageConfig, DEFAULT_CACHE_SIZE};
+pub use storage_config::{
+    PruneHorizon, RocksdbConfig, StorageConfig, DEFAULT_CACHE_SIZE, DEFAULT_PRUNE_INTERVAL,
+};
+pub use sync_config::SyncConfig;
 pub use txpool_config::TxPoolConfig;
+pub use watchdog_config::WatchdogConfig;
+pub use webhook_config::{WebhookConfig, WebhookHookConfig};
 
 pub static CRATE_VERSION: &str = crate_version!();
 pub static GIT_VERSION: &str = git_version!(
@@ -74,6 +85,10 @@ pub static GIT_VERSION: &str = git_version!(
     fallback = "unknown"
 );
 
+/// The build time in unix seconds.
+pub static BUILD_TIME: &str = env!("STARCOIN_BUILD_TIME");
+pub static RUST_VERSION: &str = env!("STARCOIN_RUST_VERSION");
+
 pub static APP_NAME: &str = "starcoin";
 pub static APP_VERSION: Lazy<String> = Lazy::new(|| {
     if GIT_VERSION != "unknown" {
@@ -128,7 +143,7 @@ where
     ))
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(clippy::upper_case_acronyms)]
 pub enum Connect {
     /// Connect by ipc file path, if Path is absent, use default ipc file.
@@ -166,7 +181,7 @@ static OPT_NET_HELP: &str = r#"Chain Network
     Custom network first start should also set the `genesis-config` option.
     Use starcoin_generator command to generate a genesis config."#;
 
-#[derive(Clone, Debug, StructOpt, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, StructOpt, Default, Serialize, Deserialize)]
 #[structopt(name = "starcoin", about = "Starcoin")]
 pub struct StarcoinOpt {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -188,6 +203,12 @@ pub struct StarcoinOpt {
     /// Watch timeout in seconds
     pub watch_timeout: Option<u64>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "chain", parse(try_from_str = parse_chain_id))]
+    /// The chain which the cli expects to connect, a chain id or a builtin network name, such as `barnard` or `251`.
+    /// Warn if the connected node is on another chain, and fill the chain id of offline transaction requests.
+    pub chain: Option<ChainId>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     #[structopt(long = "genesis-config")]
     /// Init chain by a custom genesis config. if want to reuse builtin network config, just pass a builtin network name.
@@ -215,6 +236,12 @@ pub struct StarcoinOpt {
     #[serde(default)]
     #[structopt(flatten)]
     pub stratum: StratumConfig,
+    #[serde(default)]
+    #[structopt(flatten)]
+    pub webhook: WebhookConfig,
+    #[serde(default)]
+    #[structopt(flatten)]
+    pub watchdog: WatchdogConfig,
 }
 
 impl std::fmt::Display for StarcoinOpt {
@@ -410,6 +437,9 @@ pub trait ConfigModule: Sized {
 pub struct NodeConfig {
     #[serde(skip)]
     base: Option<Arc<BaseConfig>>,
+    /// The command line options merged into this config, keep it for reloading the config.
+    #[serde(skip)]
+    opt: Option<Arc<StarcoinOpt>>,
     #[serde(default)]
     pub network: NetworkConfig,
     #[serde(default)]
@@ -430,6 +460,10 @@ pub struct NodeConfig {
     pub logger: LoggerConfig,
     #[serde(default)]
     pub stratum: StratumConfig,
+    #[serde(default)]
+    pub webhook: WebhookConfig,
+    #[serde(default)]
+    pub watchdog: WatchdogConfig,
 }
 
 impl std::fmt::Display for NodeConfig {
@@ -480,6 +514,7 @@ impl NodeConfig {
 impl NodeConfig {
     pub fn merge_with_opt(&mut self, opt: &StarcoinOpt, base: Arc<BaseConfig>) -> Result<()> {
         self.base = Some(base.clone());
+        self.opt = Some(Arc::new(opt.clone()));
         self.network.merge_with_opt(opt, base.clone())?;
         self.rpc.merge_with_opt(opt, base.clone())?;
         self.miner.merge_with_opt(opt, base.clone())?;
@@ -489,7 +524,9 @@ impl NodeConfig {
         self.vault.merge_with_opt(opt, base.clone())?;
         self.metrics.merge_with_opt(opt, base.clone())?;
         self.logger.merge_with_opt(opt, base.clone())?;
-        self.stratum.merge_with_opt(opt, base)?;
+        self.stratum.merge_with_opt(opt, base.clone())?;
+        self.webhook.merge_with_opt(opt, base.clone())?;
+        self.watchdog.merge_with_opt(opt, base)?;
         Ok(())
     }
 }
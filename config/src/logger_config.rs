@@ -2,9 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{BaseConfig, ConfigModule, StarcoinOpt};
-use anyhow::Result;
+use anyhow::{format_err, Result};
 use serde::{Deserialize, Serialize};
+use starcoin_logger::prelude::LevelFilter;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 use structopt::StructOpt;
 
@@ -33,6 +35,14 @@ pub struct LoggerConfig {
     #[structopt(name = "logger-max-backup", long)]
     pub max_backup: Option<u32>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "logger-level",
+        long,
+        help = "global log level, such as error, warn, info, debug, trace, it can be reloaded without restart"
+    )]
+    pub level: Option<String>,
+
     #[structopt(skip)]
     #[serde(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -74,6 +84,13 @@ impl LoggerConfig {
         })
     }
 
+    /// The global log level, None if not set, then the level from `RUST_LOG` env is used.
+    pub fn level(&self) -> Option<LevelFilter> {
+        self.level
+            .as_deref()
+            .and_then(|level| LevelFilter::from_str(level).ok())
+    }
+
     pub fn max_backup(&self) -> u32 {
         self.max_backup.unwrap_or_else(|| {
             let base = self.base();
@@ -103,6 +120,13 @@ impl ConfigModule for LoggerConfig {
         if opt.logger.max_backup.is_some() {
             self.max_backup = opt.logger.max_backup;
         }
+        if opt.logger.level.is_some() {
+            self.level = opt.logger.level.clone();
+        }
+        if let Some(level) = self.level.as_deref() {
+            LevelFilter::from_str(level)
+                .map_err(|_| format_err!("Invalid logger level: {}", level))?;
+        }
         Ok(())
     }
 }
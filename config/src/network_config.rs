@@ -27,6 +27,7 @@ use std::num::NonZeroU32;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 use structopt::StructOpt;
 
 pub static DEFAULT_NETWORK_PORT: u16 = 9840;
@@ -186,6 +187,18 @@ pub struct NetworkConfig {
     /// P2P network seed, multi seed should use ',' as delimiter.
     pub seeds: Seeds,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "dns-seeds", long, use_delimiter = true)]
+    /// Dns seeds resolved into seeds at startup and periodically, multi dns seeds should use ',' as delimiter.
+    /// A domain is resolved by its TXT records, each record is a multiaddr with peer id,
+    /// a `/dns4/<host>/tcp/<port>/p2p/<peer_id>` address is resolved by the A records of the host.
+    pub dns_seeds: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "dns-seeds-interval")]
+    /// Seconds between resolving the dns seeds again. Default 1800.
+    dns_seeds_interval: Option<u64>,
+
     /// Enable peer discovery on local networks.
     /// By default this option is `false`. only support cli option.
     #[serde(skip)]
@@ -279,6 +292,18 @@ impl NetworkConfig {
         seeds
     }
 
+    /// The dns seeds, empty if the seed is disabled.
+    pub fn dns_seeds(&self) -> Vec<String> {
+        if self.disable_seed {
+            return vec![];
+        }
+        self.dns_seeds.clone().unwrap_or_default()
+    }
+
+    pub fn dns_seeds_interval(&self) -> Duration {
+        Duration::from_secs(self.dns_seeds_interval.unwrap_or(1800))
+    }
+
     pub fn network_keypair(&self) -> &(Ed25519PrivateKey, Ed25519PublicKey) {
         self.network_keypair.as_ref().expect("Config should init.")
     }
@@ -403,6 +428,19 @@ impl ConfigModule for NetworkConfig {
 
         self.seeds.merge(&opt.network.seeds);
 
+        if let Some(dns_seeds) = opt.network.dns_seeds.as_ref() {
+            let mut merged = self.dns_seeds.clone().unwrap_or_default();
+            for dns_seed in dns_seeds {
+                if !merged.contains(dns_seed) {
+                    merged.push(dns_seed.clone());
+                }
+            }
+            self.dns_seeds = Some(merged);
+        }
+        if opt.network.dns_seeds_interval.is_some() {
+            self.dns_seeds_interval = opt.network.dns_seeds_interval;
+        }
+
         if opt.network.disable_seed {
             self.disable_seed = opt.network.disable_seed;
         }
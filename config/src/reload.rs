@@ -0,0 +1,216 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::helper::load_config;
+use crate::NodeConfig;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// The config fields which can be applied without restarting the node,
+/// a changed field is hot reloadable if its path starts with one of them.
+pub const HOT_RELOADABLE_FIELDS: [&str; 8] = [
+    "logger.level",
+    "miner.miner_thread",
+    "tx_pool.max_count",
+    "tx_pool.max_per_sender",
+    "tx_pool.max_mem_usage",
+    "tx_pool.min_gas_price_under_pressure",
+    "rpc.api_quotas",
+    "rpc.api_filter",
+];
+
+/// The config fields which contain secrets, they are redacted when the config is exposed,
+/// `*` matches every element of an array.
+pub const REDACTED_FIELDS: [&str; 2] = ["network.node_key", "webhook.hooks.*.secret"];
+
+const REDACTED_VALUE: &str = "******";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ConfigFieldChange {
+    /// The dot separated path of the field, such as `tx_pool.max_count`.
+    pub field: String,
+    /// Null if the field is absent.
+    pub old_value: Value,
+    /// Null if the field is absent.
+    pub new_value: Value,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConfigReloadResult {
+    /// The changed fields which have been applied.
+    pub applied: Vec<ConfigFieldChange>,
+    /// The changed fields which are ignored, because they require a restart.
+    pub ignored: Vec<ConfigFieldChange>,
+}
+
+impl ConfigReloadResult {
+    pub fn is_applied(&self) -> bool {
+        !self.applied.is_empty()
+    }
+}
+
+/// Fire this event after the node config is reloaded and some hot reloadable fields changed,
+/// the service should pick up the changes it cares about from the new config.
+#[derive(Clone, Debug)]
+pub struct NodeConfigChangedEvent {
+    pub config: Arc<NodeConfig>,
+    pub changes: Vec<ConfigFieldChange>,
+}
+
+impl NodeConfigChangedEvent {
+    pub fn is_changed(&self, field_prefix: &str) -> bool {
+        self.changes
+            .iter()
+            .any(|change| field_match(change.field.as_str(), field_prefix))
+    }
+}
+
+pub(crate) fn is_hot_reloadable(field: &str) -> bool {
+    HOT_RELOADABLE_FIELDS
+        .iter()
+        .any(|prefix| field_match(field, prefix))
+}
+
+fn field_match(field: &str, prefix: &str) -> bool {
+    field == prefix || field.starts_with(format!("{}.", prefix).as_str())
+}
+
+/// Replace the secret fields of the config json value with a placeholder.
+pub(crate) fn redact(mut value: Value) -> Value {
+    for field in REDACTED_FIELDS.iter() {
+        redact_path(&mut value, field.split('.').collect::<Vec<_>>().as_slice());
+    }
+    value
+}
+
+fn redact_path(value: &mut Value, path: &[&str]) {
+    match path.split_first() {
+        None => {
+            if !value.is_null() {
+                *value = Value::String(REDACTED_VALUE.to_string());
+            }
+        }
+        Some((&"*", rest)) => {
+            if let Value::Array(values) = value {
+                for v in values {
+                    redact_path(v, rest);
+                }
+            }
+        }
+        Some((key, rest)) => {
+            if let Some(v) = value.get_mut(*key) {
+                redact_path(v, rest);
+            }
+        }
+    }
+}
+
+fn redact_change(mut change: ConfigFieldChange) -> ConfigFieldChange {
+    for field in REDACTED_FIELDS.iter() {
+        let path = field.split('.').collect::<Vec<_>>();
+        let change_path = change.field.split('.').collect::<Vec<_>>();
+        // the changed field is in the redacted field, or contains it, such as an array leaf.
+        if change_path
+            .iter()
+            .zip(path.iter())
+            .all(|(c, p)| *p == "*" || c == p)
+        {
+            let rest = &path[change_path.len().min(path.len())..];
+            redact_path(&mut change.old_value, rest);
+            redact_path(&mut change.new_value, rest);
+        }
+    }
+    change
+}
+
+/// Compare two config json values, and return the changed leaf fields, the secret values are redacted.
+pub(crate) fn diff(old: &Value, new: &Value) -> Vec<ConfigFieldChange> {
+    let mut old_fields = BTreeMap::new();
+    flatten(None, old, &mut old_fields);
+    let mut new_fields = BTreeMap::new();
+    flatten(None, new, &mut new_fields);
+    let mut fields = old_fields.keys().cloned().collect::<Vec<_>>();
+    fields.extend(
+        new_fields
+            .keys()
+            .filter(|field| !old_fields.contains_key(*field))
+            .cloned(),
+    );
+    fields.sort();
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let old_value = old_fields.get(&field).cloned().unwrap_or(Value::Null);
+            let new_value = new_fields.get(&field).cloned().unwrap_or(Value::Null);
+            if old_value == new_value {
+                None
+            } else {
+                Some(redact_change(ConfigFieldChange {
+                    field,
+                    old_value,
+                    new_value,
+                }))
+            }
+        })
+        .collect()
+}
+
+fn flatten(prefix: Option<&str>, value: &Value, fields: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let field = match prefix {
+                    Some(prefix) => format!("{}.{}", prefix, key),
+                    None => key.clone(),
+                };
+                flatten(Some(field.as_str()), value, fields);
+            }
+        }
+        value => {
+            if let Some(prefix) = prefix {
+                fields.insert(prefix.to_string(), value.clone());
+            }
+        }
+    }
+}
+
+impl NodeConfig {
+    /// The effective config as json, the secret fields are redacted.
+    pub fn to_redacted_json(&self) -> Result<Value> {
+        Ok(redact(serde_json::to_value(self)?))
+    }
+
+    /// Reload the config file, and merge it with the command line options the node started with.
+    /// Return the new config with only the hot reloadable changes applied, and the changes.
+    pub fn reload(&self) -> Result<(NodeConfig, ConfigReloadResult)> {
+        let opt = self.opt.as_deref().cloned().unwrap_or_default();
+        let mut file_config: NodeConfig = load_config(self.config_path())?;
+        file_config.merge_with_opt(
+            &opt,
+            self.base.clone().expect("Base must exist after init."),
+        )?;
+
+        let mut result = ConfigReloadResult::default();
+        for change in diff(
+            &serde_json::to_value(self)?,
+            &serde_json::to_value(&file_config)?,
+        ) {
+            if is_hot_reloadable(change.field.as_str()) {
+                result.applied.push(change);
+            } else {
+                result.ignored.push(change);
+            }
+        }
+
+        let mut new_config = self.clone();
+        new_config.logger.level = file_config.logger.level.clone();
+        new_config.miner.miner_thread = file_config.miner.miner_thread;
+        new_config.tx_pool.reload_limits(&file_config.tx_pool);
+        new_config.rpc.api_quotas = file_config.rpc.api_quotas.clone();
+        new_config.rpc.api_filter = file_config.rpc.api_filter.clone();
+        Ok((new_config, result))
+    }
+}
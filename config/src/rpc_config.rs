@@ -25,6 +25,17 @@ const DEFAULT_WEB_SOCKET_PORT: u16 = 9870;
 // UNSPECIFIED is 0.0.0.0
 const DEFAULT_RPC_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
 const DEFAULT_BLOCK_QUERY_MAX_RANGE: u64 = 32;
+const DEFAULT_TXN_INFO_QUERY_MAX_SIZE: u64 = 1000;
+const DEFAULT_EVENT_CURSOR_IDLE_TIMEOUT: u64 = 7 * 24 * 60 * 60;
+const DEFAULT_EVENT_CURSOR_MAX_LIFETIME: u64 = 30 * 24 * 60 * 60;
+const DEFAULT_MAX_EVENT_CURSORS: usize = 1000;
+const DEFAULT_MAX_SUBSCRIPTIONS: u32 = 1024;
+const DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION: u32 = 64;
+const DEFAULT_EXPENSIVE_USER_API_QUOTA: u32 = 10;
+const DEFAULT_HEALTH_MAX_HEAD_AGE: u64 = 600;
+const DEFAULT_HEALTH_MIN_PEERS: u64 = 1;
+/// The apis which execute move code.
+const EXPENSIVE_APIS: [&str; 3] = ["contract.call", "contract.call_v2", "contract.dry_run"];
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, StructOpt)]
 pub struct HttpConfiguration {
@@ -62,6 +73,17 @@ pub struct HttpConfiguration {
     /// list of http header which identify a ip, Default: X-Real-IP,X-Forwarded-For
     pub ip_headers: Option<Vec<String>>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "http-api-key-header", long)]
+    /// The http header of api key, the user quota is counted by the api key instead of ip if the
+    /// header is present and the key is one of `api_keys`.
+    pub api_key_header: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "http-api-keys", long, use_delimiter = true)]
+    /// The api keys which are issued to the users, a request with an unknown api key is counted by ip.
+    pub api_keys: Option<Vec<String>>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     #[structopt(name = "unsupported-rpc-protocols", long, use_delimiter = true)]
     unsupported_rpc_protocols: Option<Vec<String>>,
@@ -78,6 +100,16 @@ impl HttpConfiguration {
     pub fn apis(&self) -> &ApiSet {
         self.apis.as_ref().unwrap_or(&ApiSet::UnsafeContext)
     }
+    pub fn api_key_header(&self) -> Option<String> {
+        self.api_key_header.clone()
+    }
+    pub fn api_keys(&self) -> HashSet<String> {
+        self.api_keys
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .collect()
+    }
     pub fn ip_headers(&self) -> Vec<String> {
         self.ip_headers
             .clone()
@@ -110,6 +142,12 @@ impl HttpConfiguration {
             ip_headers.extend(o.ip_headers.clone().unwrap_or_default());
             self.ip_headers = Some(ip_headers.into_iter().collect());
         }
+        if o.api_key_header.is_some() {
+            self.api_key_header = o.api_key_header.clone();
+        }
+        if o.api_keys.is_some() {
+            self.api_keys = o.api_keys.clone();
+        }
         if o.unsupported_rpc_protocols.is_some() {
             let mut protocols: HashSet<String> = self
                 .unsupported_rpc_protocols
@@ -267,7 +305,7 @@ pub struct ApiQuotaConfiguration {
     #[structopt(
     name = "jsonrpc-custom-global-api-quota",
     long,
-    help = "customize api quota, eg: node.info=100/s",
+    help = "customize api quota, eg: node.info=100/s, or chain.*=100/s to share the quota by all apis of chain",
     number_of_values = 1,
     parse(try_from_str = parse_key_val)
     )]
@@ -287,14 +325,55 @@ pub struct ApiQuotaConfiguration {
     #[structopt(
     name = "jsonrpc-custom-user-api-quota",
     long,
-    help = "customize api quota of user, eg: node.info=100/s",
+    help = "customize api quota of user, eg: node.info=100/s, or chain.*=100/s to share the quota by all apis of chain",
     number_of_values = 1,
     parse(try_from_str = parse_key_val)
     )]
     pub custom_user_api_quota: Option<Vec<(String, ApiQuotaConfig)>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "jsonrpc-max-subscriptions", long)]
+    /// Max active pubsub subscriptions of the node, Default is 1024.
+    pub max_subscriptions: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "jsonrpc-max-subscriptions-per-connection", long)]
+    /// Max active pubsub subscriptions of one connection, Default is 64.
+    pub max_subscriptions_per_connection: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "jsonrpc-disable-api-quota", long)]
+    /// Disable all api quotas and the subscription limit, for local nodes.
+    /// This flag support both cli and config file.
+    pub disable_api_quota: Option<bool>,
 }
 
 impl ApiQuotaConfiguration {
+    pub fn disable_api_quota(&self) -> bool {
+        self.disable_api_quota.unwrap_or(false)
+    }
+
+    /// None if the api quota is disabled.
+    pub fn max_subscriptions(&self) -> Option<u32> {
+        if self.disable_api_quota() {
+            None
+        } else {
+            Some(self.max_subscriptions.unwrap_or(DEFAULT_MAX_SUBSCRIPTIONS))
+        }
+    }
+
+    /// None if the api quota is disabled.
+    pub fn max_subscriptions_per_connection(&self) -> Option<u32> {
+        if self.disable_api_quota() {
+            None
+        } else {
+            Some(
+                self.max_subscriptions_per_connection
+                    .unwrap_or(DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION),
+            )
+        }
+    }
+
     pub fn default_global_api_quota(&self) -> ApiQuotaConfig {
         self.default_global_api_quota
             .clone()
@@ -317,8 +396,22 @@ impl ApiQuotaConfiguration {
             })
     }
 
+    /// The customized user quotas, the apis which execute move code have a lower default quota.
     pub fn custom_user_api_quota(&self) -> Vec<(String, ApiQuotaConfig)> {
-        self.custom_user_api_quota.clone().unwrap_or_default()
+        let mut quotas = self.custom_user_api_quota.clone().unwrap_or_default();
+        for api in EXPENSIVE_APIS.iter() {
+            if !quotas.iter().any(|(name, _)| name == api) {
+                quotas.push((
+                    api.to_string(),
+                    ApiQuotaConfig {
+                        max_burst: NonZeroU32::new(DEFAULT_EXPENSIVE_USER_API_QUOTA)
+                            .expect("New NonZeroU32 should success."),
+                        duration: QuotaDuration::Second,
+                    },
+                ));
+            }
+        }
+        quotas
     }
 
     pub fn merge(&mut self, o: &Self) -> Result<()> {
@@ -335,10 +428,121 @@ impl ApiQuotaConfiguration {
         if o.custom_user_api_quota.is_some() {
             self.custom_user_api_quota = o.custom_user_api_quota.clone();
         }
+        if o.max_subscriptions.is_some() {
+            self.max_subscriptions = o.max_subscriptions;
+        }
+        if o.max_subscriptions_per_connection.is_some() {
+            self.max_subscriptions_per_connection = o.max_subscriptions_per_connection;
+        }
+        if o.disable_api_quota.is_some() {
+            self.disable_api_quota = o.disable_api_quota;
+        }
+        Ok(())
+    }
+}
+
+/// Disable the rpc methods on the public transports, the http, tcp and websocket.
+/// The ipc socket is local, so it always exposes all the methods.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, StructOpt)]
+pub struct ApiFilterConfiguration {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "rpc-api-allow", long, use_delimiter = true)]
+    /// Only the methods matching the glob patterns are enabled, such as `chain.*`.
+    /// Default: all methods are enabled.
+    pub allow: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "rpc-api-deny", long, use_delimiter = true)]
+    /// The methods matching the glob patterns are disabled, such as `account.*`,
+    /// the deny patterns take precedence over the allow patterns.
+    pub deny: Option<Vec<String>>,
+}
+
+impl ApiFilterConfiguration {
+    /// Return true if the method is enabled on the public transports.
+    pub fn is_enabled(&self, method: &str) -> bool {
+        let allowed = match &self.allow {
+            Some(allow) => allow.iter().any(|pattern| glob_match(pattern, method)),
+            None => true,
+        };
+        allowed
+            && !self
+                .deny
+                .as_ref()
+                .map(|deny| deny.iter().any(|pattern| glob_match(pattern, method)))
+                .unwrap_or(false)
+    }
+
+    pub fn merge(&mut self, o: &Self) -> Result<()> {
+        if o.allow.is_some() {
+            self.allow = o.allow.clone();
+        }
+        if o.deny.is_some() {
+            self.deny = o.deny.clone();
+        }
+        Ok(())
+    }
+}
+
+/// The readiness thresholds of `node.health` and the http `GET /health`.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, StructOpt)]
+pub struct HealthConfiguration {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "health-max-head-age", long)]
+    /// The node is not ready if its head block is older than the seconds, default is 600.
+    pub max_head_age: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "health-min-peers", long)]
+    /// The node is not ready if it connects to fewer peers, default is 1.
+    pub min_peers: Option<u64>,
+}
+
+impl HealthConfiguration {
+    pub fn max_head_age(&self) -> u64 {
+        self.max_head_age.unwrap_or(DEFAULT_HEALTH_MAX_HEAD_AGE)
+    }
+
+    pub fn min_peers(&self) -> u64 {
+        self.min_peers.unwrap_or(DEFAULT_HEALTH_MIN_PEERS)
+    }
+
+    pub fn merge(&mut self, o: &Self) -> Result<()> {
+        if o.max_head_age.is_some() {
+            self.max_head_age = o.max_head_age;
+        }
+        if o.min_peers.is_some() {
+            self.min_peers = o.min_peers;
+        }
         Ok(())
     }
 }
 
+/// Match the text with the pattern, `*` in the pattern matches any chars.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let mut rest = match text.strip_prefix(first) {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let parts: Vec<&str> = parts.collect();
+    let last = match parts.split_last() {
+        Some((last, middle)) => {
+            for part in middle {
+                match rest.find(part) {
+                    Some(idx) => rest = &rest[idx + part.len()..],
+                    None => return false,
+                }
+            }
+            last
+        }
+        // no `*` in the pattern.
+        None => return rest.is_empty(),
+    };
+    rest.ends_with(last)
+}
+
 #[derive(Clone, Default, Debug, PartialEq, Deserialize, Serialize, StructOpt)]
 #[serde(deny_unknown_fields)]
 pub struct RpcConfig {
@@ -362,6 +566,14 @@ pub struct RpcConfig {
     #[structopt(flatten)]
     pub api_quotas: ApiQuotaConfiguration,
 
+    #[serde(default)]
+    #[structopt(flatten)]
+    pub api_filter: ApiFilterConfiguration,
+
+    #[serde(default)]
+    #[structopt(flatten)]
+    pub health: HealthConfiguration,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     #[structopt(long = "rpc-address")]
     /// Rpc address, default is 0.0.0.0
@@ -371,6 +583,36 @@ pub struct RpcConfig {
     #[structopt(long = "event-query-max-block-range")]
     pub block_query_max_range: Option<u64>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "txn-info-query-max-size")]
+    /// Max size of txn infos returned by a range query, default is 1000.
+    pub txn_info_query_max_size: Option<u64>,
+
+    #[serde(default)]
+    #[structopt(long = "disable-txn-output-query")]
+    /// Disable `chain.get_transaction_output`, which re-executes the txn, recommended for public nodes.
+    pub disable_txn_output_query: bool,
+
+    #[serde(default)]
+    #[structopt(long = "enable-block-bundle-state")]
+    /// Enable `chain.export_block_bundle` with state, which re-executes the whole block, disabled by default.
+    pub enable_block_bundle_state: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "event-cursor-idle-timeout")]
+    /// Seconds an event cursor can be idle before it is removed, default is 7 days.
+    pub event_cursor_idle_timeout: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "event-cursor-max-lifetime")]
+    /// Seconds an event cursor lives after it is created, even if it is active, default is 30 days.
+    pub event_cursor_max_lifetime: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(long = "max-event-cursors")]
+    /// Max count of the event cursors, default is 1000.
+    pub max_event_cursors: Option<usize>,
+
     #[serde(skip)]
     #[structopt(skip)]
     http_address: Option<ListenAddress>,
@@ -451,6 +693,25 @@ impl RpcConfig {
             .unwrap_or(DEFAULT_BLOCK_QUERY_MAX_RANGE)
     }
 
+    pub fn txn_info_query_max_size(&self) -> u64 {
+        self.txn_info_query_max_size
+            .unwrap_or(DEFAULT_TXN_INFO_QUERY_MAX_SIZE)
+    }
+
+    pub fn event_cursor_idle_timeout(&self) -> u64 {
+        self.event_cursor_idle_timeout
+            .unwrap_or(DEFAULT_EVENT_CURSOR_IDLE_TIMEOUT)
+    }
+
+    pub fn event_cursor_max_lifetime(&self) -> u64 {
+        self.event_cursor_max_lifetime
+            .unwrap_or(DEFAULT_EVENT_CURSOR_MAX_LIFETIME)
+    }
+
+    pub fn max_event_cursors(&self) -> usize {
+        self.max_event_cursors.unwrap_or(DEFAULT_MAX_EVENT_CURSORS)
+    }
+
     fn base(&self) -> &BaseConfig {
         self.base.as_ref().expect("Config should init.")
     }
@@ -523,11 +784,31 @@ impl ConfigModule for RpcConfig {
         if opt.rpc.block_query_max_range.is_some() {
             self.block_query_max_range = opt.rpc.block_query_max_range;
         }
+        if opt.rpc.txn_info_query_max_size.is_some() {
+            self.txn_info_query_max_size = opt.rpc.txn_info_query_max_size;
+        }
+        if opt.rpc.disable_txn_output_query {
+            self.disable_txn_output_query = true;
+        }
+        if opt.rpc.enable_block_bundle_state {
+            self.enable_block_bundle_state = true;
+        }
+        if opt.rpc.event_cursor_idle_timeout.is_some() {
+            self.event_cursor_idle_timeout = opt.rpc.event_cursor_idle_timeout;
+        }
+        if opt.rpc.event_cursor_max_lifetime.is_some() {
+            self.event_cursor_max_lifetime = opt.rpc.event_cursor_max_lifetime;
+        }
+        if opt.rpc.max_event_cursors.is_some() {
+            self.max_event_cursors = opt.rpc.max_event_cursors;
+        }
         self.http.merge(&opt.rpc.http)?;
         self.tcp.merge(&opt.rpc.tcp)?;
         self.ws.merge(&opt.rpc.ws)?;
         self.ipc.merge(&opt.rpc.ipc)?;
         self.api_quotas.merge(&opt.rpc.api_quotas)?;
+        self.api_filter.merge(&opt.rpc.api_filter)?;
+        self.health.merge(&opt.rpc.health)?;
 
         self.generate_address();
 
@@ -2,10 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{BaseConfig, ConfigModule, StarcoinOpt};
-use anyhow::Result;
+use anyhow::{format_err, Result};
 use once_cell::sync::Lazy;
-use serde::{Deserialize, Serialize};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use starcoin_types::block::BlockNumber;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 use structopt::StructOpt;
 
@@ -57,6 +60,67 @@ impl Default for RocksdbConfig {
 
 static DEFAULT_DB_DIR: Lazy<PathBuf> = Lazy::new(|| PathBuf::from("starcoindb/db"));
 pub const DEFAULT_CACHE_SIZE: usize = 20000;
+pub const DEFAULT_PRUNE_INTERVAL: u64 = 60;
+
+/// The block data of the main chain blocks before the horizon is pruned,
+/// in the form of a block number, such as `100000`, or `recent:<n>` to keep the recent n blocks.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PruneHorizon {
+    BlockNumber(BlockNumber),
+    KeepRecent(u64),
+}
+
+impl PruneHorizon {
+    const KEEP_RECENT_PREFIX: &'static str = "recent:";
+
+    /// The blocks before the returned number are prunable when the head is `head_number`.
+    pub fn prune_before(&self, head_number: BlockNumber) -> BlockNumber {
+        match self {
+            PruneHorizon::BlockNumber(number) => *number,
+            PruneHorizon::KeepRecent(n) => head_number.saturating_add(1).saturating_sub(*n),
+        }
+    }
+}
+
+impl std::fmt::Display for PruneHorizon {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PruneHorizon::BlockNumber(number) => write!(f, "{}", number),
+            PruneHorizon::KeepRecent(n) => write!(f, "{}{}", Self::KEEP_RECENT_PREFIX, n),
+        }
+    }
+}
+
+impl FromStr for PruneHorizon {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |_| format_err!("invalid prune horizon {}, expect <number> or recent:<n>", s);
+        match s.strip_prefix(Self::KEEP_RECENT_PREFIX) {
+            Some(n) => Ok(PruneHorizon::KeepRecent(n.parse().map_err(invalid)?)),
+            None => Ok(PruneHorizon::BlockNumber(s.parse().map_err(invalid)?)),
+        }
+    }
+}
+
+impl Serialize for PruneHorizon {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.to_string().as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for PruneHorizon {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = <String>::deserialize(deserializer)?;
+        s.parse::<PruneHorizon>().map_err(D::Error::custom)
+    }
+}
 
 #[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, StructOpt)]
 #[serde(deny_unknown_fields)]
@@ -77,6 +141,30 @@ pub struct StorageConfig {
     #[structopt(name = "cache-sizes", long, help = "cache sizes")]
     pub cache_size: Option<usize>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "prune-blocks-before",
+        long,
+        help = "prune the block bodies, txn infos and events of the main chain blocks before the horizon, <number> or recent:<n>, the headers and the state are kept"
+    )]
+    pub prune_blocks_before: Option<PruneHorizon>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "prune-interval",
+        long,
+        help = "the interval in seconds to prune the block data, default is 60"
+    )]
+    pub prune_interval: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "token-holder-index",
+        long,
+        help = "maintain the index of the token holders, it is disabled by default, run `node index rebuild-token-holders` to build the index of an exist db"
+    )]
+    pub token_holder_index: Option<bool>,
+
     #[serde(skip)]
     #[structopt(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -103,6 +191,19 @@ impl StorageConfig {
     pub fn cache_size(&self) -> usize {
         self.cache_size.unwrap_or(DEFAULT_CACHE_SIZE)
     }
+
+    /// None if the pruning is disabled.
+    pub fn prune_blocks_before(&self) -> Option<PruneHorizon> {
+        self.prune_blocks_before
+    }
+
+    pub fn prune_interval(&self) -> u64 {
+        self.prune_interval.unwrap_or(DEFAULT_PRUNE_INTERVAL)
+    }
+
+    pub fn token_holder_index(&self) -> bool {
+        self.token_holder_index.unwrap_or(false)
+    }
 }
 
 impl ConfigModule for StorageConfig {
@@ -117,6 +218,15 @@ impl ConfigModule for StorageConfig {
         if opt.storage.cache_size.is_some() {
             self.cache_size = opt.storage.cache_size;
         }
+        if opt.storage.prune_blocks_before.is_some() {
+            self.prune_blocks_before = opt.storage.prune_blocks_before;
+        }
+        if opt.storage.prune_interval.is_some() {
+            self.prune_interval = opt.storage.prune_interval;
+        }
+        if opt.storage.token_holder_index.is_some() {
+            self.token_holder_index = opt.storage.token_holder_index;
+        }
         Ok(())
     }
 }
@@ -1,13 +1,21 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{BaseConfig, ConfigModule, StarcoinOpt};
-use anyhow::Result;
+use crate::{BaseConfig, ChainNetworkID, ConfigModule, StarcoinOpt};
+use anyhow::{format_err, Result};
+use include_dir::{include_dir, Dir};
 use network_api::PeerStrategy;
 use serde::{Deserialize, Serialize};
+use starcoin_crypto::ed25519::Ed25519PublicKey;
+use starcoin_crypto::ValidCryptoMaterialStringExt;
+use starcoin_types::checkpoint::{CheckpointSet, SignedCheckpointSet};
+use std::path::PathBuf;
 use std::sync::Arc;
 use structopt::StructOpt;
 
+/// The signed checkpoints shipped for the builtin networks, the file is named `<network>.json`.
+const BUILTIN_CHECKPOINTS_DIR: Dir = include_dir!("checkpoints");
+
 #[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, StructOpt)]
 #[serde(deny_unknown_fields)]
 pub struct SyncConfig {
@@ -28,6 +36,56 @@ pub struct SyncConfig {
         help = "max retry times once sync block failed, default 15."
     )]
     max_retry_times: Option<u64>,
+
+    /// max concurrent block fetches of the sync task
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "block-fetch-concurrency",
+        long,
+        help = "max concurrent block fetches across the sync peers, default 16."
+    )]
+    block_fetch_concurrency: Option<u64>,
+
+    /// max in-flight block fetches per peer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "max-inflight-per-peer",
+        long,
+        help = "max in-flight block fetches per sync peer, default 2."
+    )]
+    max_inflight_per_peer: Option<u64>,
+
+    /// buffer size of the fetched blocks waiting to be executed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "block-buffer-size",
+        long,
+        help = "max fetched blocks waiting to be executed in order, default 64."
+    )]
+    block_buffer_size: Option<u64>,
+
+    /// the signed checkpoints file, overrides the checkpoints shipped for the builtin network
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "checkpoints-file",
+        long,
+        parse(from_os_str),
+        help = "signed checkpoints file, the headers at or below the latest checkpoint skip the PoW verification when sync."
+    )]
+    checkpoints_file: Option<PathBuf>,
+
+    /// the trusted signer of the checkpoints file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "checkpoints-signer",
+        long,
+        help = "hex encoded ed25519 public key of the trusted checkpoints file signer."
+    )]
+    checkpoints_signer: Option<String>,
+
+    #[serde(skip)]
+    #[structopt(skip)]
+    base: Option<Arc<BaseConfig>>,
 }
 
 impl SyncConfig {
@@ -38,10 +96,85 @@ impl SyncConfig {
     pub fn max_retry_times(&self) -> u64 {
         self.max_retry_times.unwrap_or(15)
     }
+
+    pub fn block_fetch_concurrency(&self) -> u64 {
+        self.block_fetch_concurrency.unwrap_or(16)
+    }
+
+    pub fn max_inflight_per_peer(&self) -> u64 {
+        self.max_inflight_per_peer.unwrap_or(2)
+    }
+
+    pub fn block_buffer_size(&self) -> u64 {
+        self.block_buffer_size.unwrap_or(64)
+    }
+
+    fn base(&self) -> &BaseConfig {
+        self.base.as_ref().expect("Config should init.")
+    }
+
+    pub fn checkpoints_file(&self) -> Option<PathBuf> {
+        self.checkpoints_file.as_ref().map(|path| {
+            if path.is_absolute() {
+                path.clone()
+            } else {
+                self.base().data_dir().join(path)
+            }
+        })
+    }
+
+    pub fn checkpoints_signer(&self) -> Result<Option<Ed25519PublicKey>> {
+        self.checkpoints_signer
+            .as_ref()
+            .map(|signer| {
+                Ed25519PublicKey::from_encoded_string(signer)
+                    .map_err(|e| format_err!("Invalid checkpoints signer {}: {}", signer, e))
+            })
+            .transpose()
+    }
+
+    /// Load and verify the checkpoints file, or the checkpoints shipped for the builtin network.
+    /// Return None if there are no checkpoints.
+    pub fn checkpoints(&self) -> Result<Option<CheckpointSet>> {
+        let net = &self.base().net;
+        let chain_id = net.chain_id();
+        if let Some(path) = self.checkpoints_file() {
+            let signer = self.checkpoints_signer()?.ok_or_else(|| {
+                format_err!(
+                    "The checkpoints-signer is required to verify the checkpoints file {:?}",
+                    path
+                )
+            })?;
+            let signed: SignedCheckpointSet = serde_json::from_slice(
+                std::fs::read(&path)
+                    .map_err(|e| format_err!("Read checkpoints file {:?} error: {}", path, e))?
+                    .as_slice(),
+            )?;
+            return signed.verify(&signer, chain_id).map(Some);
+        }
+        let network = match net.id() {
+            ChainNetworkID::Builtin(network) => *network,
+            ChainNetworkID::Custom(_) => return Ok(None),
+        };
+        match BUILTIN_CHECKPOINTS_DIR.get_file(format!("{}.json", network)) {
+            Some(file) => {
+                let signed: SignedCheckpointSet = serde_json::from_slice(file.contents())?;
+                // the shipped checkpoints are trusted as the binary, the configured signer is
+                // only required to match if present.
+                let signer = match self.checkpoints_signer()? {
+                    Some(signer) => signer,
+                    None => signed.public_key().clone(),
+                };
+                signed.verify(&signer, chain_id).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
 }
 
 impl ConfigModule for SyncConfig {
-    fn merge_with_opt(&mut self, opt: &StarcoinOpt, _base: Arc<BaseConfig>) -> Result<()> {
+    fn merge_with_opt(&mut self, opt: &StarcoinOpt, base: Arc<BaseConfig>) -> Result<()> {
+        self.base = Some(base);
         if opt.sync.peer_select_strategy.is_some() {
             self.peer_select_strategy = opt.sync.peer_select_strategy;
         }
@@ -50,6 +183,26 @@ impl ConfigModule for SyncConfig {
             self.max_retry_times = opt.sync.max_retry_times;
         }
 
+        if opt.sync.block_fetch_concurrency.is_some() {
+            self.block_fetch_concurrency = opt.sync.block_fetch_concurrency;
+        }
+
+        if opt.sync.max_inflight_per_peer.is_some() {
+            self.max_inflight_per_peer = opt.sync.max_inflight_per_peer;
+        }
+
+        if opt.sync.block_buffer_size.is_some() {
+            self.block_buffer_size = opt.sync.block_buffer_size;
+        }
+
+        if opt.sync.checkpoints_file.is_some() {
+            self.checkpoints_file = opt.sync.checkpoints_file.clone();
+        }
+
+        if opt.sync.checkpoints_signer.is_some() {
+            self.checkpoints_signer = opt.sync.checkpoints_signer.clone();
+        }
+
         Ok(())
     }
 }
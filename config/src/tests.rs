@@ -3,6 +3,8 @@
 
 use super::*;
 use crate::helper::to_toml;
+use crate::reload::{diff, is_hot_reloadable, redact};
+use serde_json::{json, Value};
 use starcoin_vm_types::gas_schedule::GasAlgebra;
 
 #[test]
@@ -64,6 +66,31 @@ fn test_api_quota_config() {
     assert_eq!("1000/s", config.to_string().as_str());
 }
 
+#[test]
+fn test_api_filter_config() {
+    let filter = ApiFilterConfiguration::default();
+    assert!(filter.is_enabled("account.list"));
+
+    let filter = ApiFilterConfiguration {
+        allow: Some(vec!["chain.*".to_string(), "node.*".to_string()]),
+        deny: Some(vec!["node.*peer*".to_string()]),
+    };
+    assert!(filter.is_enabled("chain.info"));
+    assert!(filter.is_enabled("node.info"));
+    assert!(!filter.is_enabled("node.peers"));
+    assert!(!filter.is_enabled("account.list"));
+    assert!(!filter.is_enabled("chain"));
+
+    let filter = ApiFilterConfiguration {
+        allow: None,
+        deny: Some(vec!["account.*".to_string(), "debug.panic".to_string()]),
+    };
+    assert!(!filter.is_enabled("account.sign_txn"));
+    assert!(!filter.is_enabled("debug.panic"));
+    assert!(filter.is_enabled("debug.panic_hook"));
+    assert!(filter.is_enabled("accounts.list"));
+}
+
 #[test]
 fn test_example_config_compact() -> Result<()> {
     let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -177,3 +204,128 @@ fn test_genesis_config_security() {
         );
     }
 }
+
+#[test]
+fn test_config_diff() {
+    let old = json!({
+        "logger": {"level": "info"},
+        "miner": {"miner_thread": 1},
+        "network": {"node_key": "0x01", "seeds": ["a"]},
+    });
+    let new = json!({
+        "logger": {"level": "debug"},
+        "miner": {},
+        "network": {"node_key": "0x02", "seeds": ["a", "b"]},
+        "tx_pool": {"max_count": 100},
+    });
+    let changes = diff(&old, &new);
+    let fields = changes
+        .iter()
+        .map(|change| change.field.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        fields,
+        vec![
+            "logger.level",
+            "miner.miner_thread",
+            "network.node_key",
+            "network.seeds",
+            "tx_pool.max_count"
+        ]
+    );
+    assert_eq!(changes[1].new_value, Value::Null);
+    assert_eq!(changes[2].old_value, json!("******"));
+    assert_eq!(
+        redact(json!({"network": {"node_key": "0x01"}})),
+        json!({"network": {"node_key": "******"}})
+    );
+    assert_eq!(
+        redact(json!({"webhook": {"hooks": [{"url": "u", "secret": "s"}]}})),
+        json!({"webhook": {"hooks": [{"url": "u", "secret": "******"}]}})
+    );
+    let hook_changes = diff(
+        &json!({"webhook": {"hooks": []}}),
+        &json!({"webhook": {"hooks": [{"url": "u", "secret": "s"}]}}),
+    );
+    assert_eq!(
+        hook_changes[0].new_value,
+        json!([{"url": "u", "secret": "******"}])
+    );
+    let hot_reloadable = fields
+        .into_iter()
+        .filter(|field| is_hot_reloadable(field))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        hot_reloadable,
+        vec!["logger.level", "miner.miner_thread", "tx_pool.max_count"]
+    );
+}
+
+#[test]
+fn test_prune_horizon() -> Result<()> {
+    let horizon = "recent:100".parse::<PruneHorizon>()?;
+    assert_eq!(horizon, PruneHorizon::KeepRecent(100));
+    assert_eq!(horizon.prune_before(1000), 901);
+    assert_eq!(horizon.prune_before(10), 0);
+    let horizon = "500".parse::<PruneHorizon>()?;
+    assert_eq!(horizon, PruneHorizon::BlockNumber(500));
+    assert_eq!(horizon.prune_before(1000), 500);
+    assert!("recent:".parse::<PruneHorizon>().is_err());
+    assert!("latest".parse::<PruneHorizon>().is_err());
+
+    let mut config = StorageConfig::default();
+    config.prune_blocks_before = Some(PruneHorizon::KeepRecent(100));
+    let toml = to_toml(&config)?;
+    assert!(toml.contains("prune_blocks_before = \"recent:100\""));
+    assert_eq!(toml::from_str::<StorageConfig>(toml.as_str())?, config);
+    Ok(())
+}
+
+#[test]
+fn test_sync_checkpoints() -> Result<()> {
+    use starcoin_crypto::{HashValue, ValidCryptoMaterialStringExt};
+    use starcoin_types::checkpoint::{Checkpoint, CheckpointSet};
+
+    // no checkpoints are shipped for the test network.
+    let config = NodeConfig::random_for_test();
+    assert!(config.sync.checkpoints()?.is_none());
+
+    let temp_path = temp_path();
+    let checkpoints = CheckpointSet::new(
+        BuiltinNetworkID::Test.chain_id(),
+        vec![Checkpoint::new(
+            100,
+            HashValue::random(),
+            HashValue::random(),
+        )],
+    )?;
+    let (private_key, public_key) = genesis_key_pair();
+    let file = temp_path.path().join("checkpoints.json");
+    std::fs::write(
+        file.as_path(),
+        serde_json::to_vec(&checkpoints.clone().sign(&private_key))?,
+    )?;
+    let signer = public_key.to_encoded_string()?;
+    let data_dir = temp_path.path().to_str().unwrap();
+    let file = file.to_str().unwrap();
+
+    let args = vec![
+        "starcoin",
+        "-n",
+        "test",
+        "-d",
+        data_dir,
+        "--checkpoints-file",
+        file,
+        "--checkpoints-signer",
+        signer.as_str(),
+    ];
+    let config = NodeConfig::load_with_opt(&StarcoinOpt::from_iter_safe(args)?)?;
+    assert_eq!(config.sync.checkpoints()?, Some(checkpoints));
+
+    // the signer is required for the checkpoints file.
+    let args = vec!["starcoin", "-n", "test", "--checkpoints-file", file];
+    let config = NodeConfig::load_with_opt(&StarcoinOpt::from_iter_safe(args)?)?;
+    assert!(config.sync.checkpoints().is_err());
+    Ok(())
+}
@@ -9,6 +9,11 @@ use std::sync::Arc;
 use structopt::StructOpt;
 
 pub const DEFAULT_MEM_SIZE: u64 = 128 * 1024 * 1024; // 128M
+pub const DEFAULT_GAS_PRICE_BUMP_PERCENT: u64 = 10;
+pub const DEFAULT_TX_REBROADCAST_AFTER: u64 = 30;
+pub const DEFAULT_TX_REBROADCAST_INTERVAL: u64 = 10;
+pub const DEFAULT_MAX_TX_REBROADCAST_PER_INTERVAL: u64 = 100;
+pub const DEFAULT_GAS_PRICE_ORACLE_BLOCKS: u64 = 60;
 
 #[derive(Default, Clone, Debug, Eq, PartialEq, Deserialize, Serialize, StructOpt)]
 #[serde(deny_unknown_fields)]
@@ -37,6 +42,40 @@ pub struct TxPoolConfig {
     #[structopt(name = "txpool-min-gas-price", long)]
     /// reject transaction whose gas_price is less than the min_gas_price. default to 1.
     min_gas_price: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-min-gas-price-under-pressure", long)]
+    /// when the pool is full, reject transaction whose gas_price is less than it,
+    /// so the low gas price transactions can not push out each other. default to the min_gas_price.
+    min_gas_price_under_pressure: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-gas-price-bump-percent", long)]
+    /// a transaction can replace the pending one with same sender and sequence number,
+    /// only if its gas_price is at least the percent higher. default to 10.
+    gas_price_bump_percent: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-tx-rebroadcast-after", long)]
+    /// the local pending transaction is re-announced to peers after it stays in the pool for the seconds,
+    /// then the wait is doubled after every announcement. default to 30.
+    tx_rebroadcast_after: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-tx-rebroadcast-interval", long)]
+    /// interval(s) of tx rebroadcast timer. default to 10.
+    tx_rebroadcast_interval: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-max-tx-rebroadcast-per-interval", long)]
+    /// max count of the transactions re-announced by the rebroadcast timer every interval,
+    /// and by the `txpool.rebroadcast` requests every interval. default to 100.
+    max_tx_rebroadcast_per_interval: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "txpool-gas-price-oracle-blocks", long)]
+    /// the gas price oracle suggests the gas prices by the transactions in so many recent blocks. default to 60.
+    gas_price_oracle_blocks: Option<u64>,
 }
 
 impl TxPoolConfig {
@@ -46,6 +85,9 @@ impl TxPoolConfig {
     pub fn max_count(&self) -> u64 {
         self.max_count.clone().unwrap_or(4096)
     }
+    pub fn set_max_per_sender(&mut self, max_per_sender: u64) {
+        self.max_per_sender = Some(max_per_sender);
+    }
     pub fn max_per_sender(&self) -> u64 {
         self.max_per_sender.clone().unwrap_or(128)
     }
@@ -69,6 +111,40 @@ impl TxPoolConfig {
     pub fn min_gas_price(&self) -> u64 {
         self.min_gas_price.unwrap_or(1)
     }
+    pub fn min_gas_price_under_pressure(&self) -> u64 {
+        self.min_gas_price_under_pressure
+            .unwrap_or_else(|| self.min_gas_price())
+    }
+    pub fn gas_price_bump_percent(&self) -> u64 {
+        self.gas_price_bump_percent
+            .unwrap_or(DEFAULT_GAS_PRICE_BUMP_PERCENT)
+    }
+    pub fn tx_rebroadcast_after(&self) -> u64 {
+        self.tx_rebroadcast_after
+            .unwrap_or(DEFAULT_TX_REBROADCAST_AFTER)
+    }
+    pub fn tx_rebroadcast_interval(&self) -> u64 {
+        self.tx_rebroadcast_interval
+            .unwrap_or(DEFAULT_TX_REBROADCAST_INTERVAL)
+    }
+    pub fn set_max_tx_rebroadcast_per_interval(&mut self, max_tx_rebroadcast_per_interval: u64) {
+        self.max_tx_rebroadcast_per_interval = Some(max_tx_rebroadcast_per_interval);
+    }
+    pub fn max_tx_rebroadcast_per_interval(&self) -> u64 {
+        self.max_tx_rebroadcast_per_interval
+            .unwrap_or(DEFAULT_MAX_TX_REBROADCAST_PER_INTERVAL)
+    }
+    pub fn gas_price_oracle_blocks(&self) -> u64 {
+        self.gas_price_oracle_blocks
+            .unwrap_or(DEFAULT_GAS_PRICE_ORACLE_BLOCKS)
+    }
+    /// Take the pool size limits from other config, the limits can be applied without restart.
+    pub(crate) fn reload_limits(&mut self, other: &TxPoolConfig) {
+        self.max_count = other.max_count;
+        self.max_per_sender = other.max_per_sender;
+        self.max_mem_usage = other.max_mem_usage;
+        self.min_gas_price_under_pressure = other.min_gas_price_under_pressure;
+    }
 }
 
 impl ConfigModule for TxPoolConfig {
@@ -80,8 +156,8 @@ impl ConfigModule for TxPoolConfig {
         if let Some(m) = txpool_opt.max_count.as_ref() {
             self.max_count = Some(*m);
         }
-        if let Some(m) = txpool_opt.max_mem_usage.as_ref() {
-            self.max_mem_usage = Some(*m);
+        if let Some(m) = txpool_opt.max_per_sender.as_ref() {
+            self.max_per_sender = Some(*m);
         }
         if let Some(m) = txpool_opt.tx_propagate_interval.as_ref() {
             self.tx_propagate_interval = Some(*m);
@@ -89,6 +165,24 @@ impl ConfigModule for TxPoolConfig {
         if let Some(m) = txpool_opt.min_gas_price.as_ref() {
             self.min_gas_price = Some(*m);
         }
+        if let Some(m) = txpool_opt.min_gas_price_under_pressure.as_ref() {
+            self.min_gas_price_under_pressure = Some(*m);
+        }
+        if let Some(m) = txpool_opt.gas_price_bump_percent.as_ref() {
+            self.gas_price_bump_percent = Some(*m);
+        }
+        if let Some(m) = txpool_opt.tx_rebroadcast_after.as_ref() {
+            self.tx_rebroadcast_after = Some(*m);
+        }
+        if let Some(m) = txpool_opt.tx_rebroadcast_interval.as_ref() {
+            self.tx_rebroadcast_interval = Some(*m);
+        }
+        if let Some(m) = txpool_opt.max_tx_rebroadcast_per_interval.as_ref() {
+            self.max_tx_rebroadcast_per_interval = Some(*m);
+        }
+        if let Some(m) = txpool_opt.gas_price_oracle_blocks.as_ref() {
+            self.gas_price_oracle_blocks = Some(*m);
+        }
         Ok(())
     }
 }
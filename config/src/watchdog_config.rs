@@ -0,0 +1,148 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BaseConfig, ConfigModule, StarcoinOpt};
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use structopt::StructOpt;
+
+const DEFAULT_CHECK_INTERVAL_SECS: u64 = 10;
+const DEFAULT_BLOCK_STALL_FACTOR: u64 = 10;
+const DEFAULT_TXPOOL_MAX_LATENCY_MS: u64 = 1000;
+const DEFAULT_SERVICE_MAX_LATENCY_MS: u64 = 3000;
+const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+const DEFAULT_RESTART_COOLDOWN_SECS: u64 = 300;
+
+#[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, StructOpt)]
+#[serde(deny_unknown_fields)]
+pub struct WatchdogConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "disable-watchdog",
+        long,
+        help = "disable the watchdog which detects stalled block production and stuck services"
+    )]
+    pub disable: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "watchdog-auto-restart",
+        long,
+        help = "let the watchdog restart the stuck services"
+    )]
+    pub auto_restart: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "watchdog-check-interval", long)]
+    /// The watchdog check interval in seconds, default is 10.
+    pub check_interval: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "watchdog-block-stall-factor", long)]
+    /// Block production is treated as stalled when the head block is older than block_stall_factor * the epoch's block time target, default is 10.
+    pub block_stall_factor: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "watchdog-txpool-max-latency", long)]
+    /// Max latency in milliseconds of a txpool status query, default is 1000.
+    pub txpool_max_latency: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "watchdog-service-max-latency", long)]
+    /// Max latency in milliseconds of a service processing the ping message in its mailbox, default is 3000.
+    pub service_max_latency: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "watchdog-failure-threshold", long)]
+    /// How many consecutive failed checks before a stuck service is restarted, default is 3.
+    pub failure_threshold: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "watchdog-restart-cooldown", long)]
+    /// Min interval in seconds between two restarts of the same service, default is 300.
+    pub restart_cooldown: Option<u64>,
+}
+
+impl WatchdogConfig {
+    pub fn enable(&self) -> bool {
+        !self.disable.unwrap_or(false)
+    }
+
+    pub fn auto_restart(&self) -> bool {
+        self.auto_restart.unwrap_or(false)
+    }
+
+    pub fn check_interval(&self) -> Duration {
+        Duration::from_secs(self.check_interval.unwrap_or(DEFAULT_CHECK_INTERVAL_SECS))
+    }
+
+    pub fn block_stall_factor(&self) -> u64 {
+        self.block_stall_factor
+            .unwrap_or(DEFAULT_BLOCK_STALL_FACTOR)
+    }
+
+    pub fn txpool_max_latency(&self) -> Duration {
+        Duration::from_millis(
+            self.txpool_max_latency
+                .unwrap_or(DEFAULT_TXPOOL_MAX_LATENCY_MS),
+        )
+    }
+
+    pub fn service_max_latency(&self) -> Duration {
+        Duration::from_millis(
+            self.service_max_latency
+                .unwrap_or(DEFAULT_SERVICE_MAX_LATENCY_MS),
+        )
+    }
+
+    pub fn failure_threshold(&self) -> u32 {
+        self.failure_threshold.unwrap_or(DEFAULT_FAILURE_THRESHOLD)
+    }
+
+    pub fn restart_cooldown(&self) -> Duration {
+        Duration::from_secs(
+            self.restart_cooldown
+                .unwrap_or(DEFAULT_RESTART_COOLDOWN_SECS),
+        )
+    }
+}
+
+impl ConfigModule for WatchdogConfig {
+    fn merge_with_opt(&mut self, opt: &StarcoinOpt, _base: Arc<BaseConfig>) -> Result<()> {
+        if opt.watchdog.disable.is_some() {
+            self.disable = opt.watchdog.disable;
+        }
+        if opt.watchdog.auto_restart.is_some() {
+            self.auto_restart = opt.watchdog.auto_restart;
+        }
+        if opt.watchdog.check_interval.is_some() {
+            self.check_interval = opt.watchdog.check_interval;
+        }
+        if opt.watchdog.block_stall_factor.is_some() {
+            self.block_stall_factor = opt.watchdog.block_stall_factor;
+        }
+        if opt.watchdog.txpool_max_latency.is_some() {
+            self.txpool_max_latency = opt.watchdog.txpool_max_latency;
+        }
+        if opt.watchdog.service_max_latency.is_some() {
+            self.service_max_latency = opt.watchdog.service_max_latency;
+        }
+        if opt.watchdog.failure_threshold.is_some() {
+            self.failure_threshold = opt.watchdog.failure_threshold;
+        }
+        if opt.watchdog.restart_cooldown.is_some() {
+            self.restart_cooldown = opt.watchdog.restart_cooldown;
+        }
+        ensure!(
+            self.check_interval().as_secs() > 0,
+            "The watchdog check interval should be greater than 0."
+        );
+        ensure!(
+            self.failure_threshold() > 0,
+            "The watchdog failure threshold should be greater than 0."
+        );
+        Ok(())
+    }
+}
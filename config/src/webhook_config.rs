@@ -0,0 +1,125 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BaseConfig, ConfigModule, StarcoinOpt};
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_types::webhook::WebhookFilter;
+use std::sync::Arc;
+use std::time::Duration;
+use structopt::StructOpt;
+
+const DEFAULT_MAX_RETRY: u32 = 5;
+const DEFAULT_RETRY_INTERVAL_MS: u64 = 1000;
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_MAX_HOOKS: usize = 100;
+
+/// A webhook registered by config, it can not be removed by rpc.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookHookConfig {
+    pub url: String,
+    /// The secret to sign the payload with HMAC-SHA256.
+    pub secret: String,
+    #[serde(default)]
+    pub filter: WebhookFilter,
+}
+
+#[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, StructOpt)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(
+        name = "enable-webhook",
+        long,
+        help = "enable the transaction lifecycle webhook dispatcher"
+    )]
+    pub enable: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "webhook-max-retry", long)]
+    /// Max retry times of a failed webhook delivery, default is 5.
+    pub max_retry: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "webhook-retry-interval", long)]
+    /// The first retry interval in milliseconds of a failed webhook delivery, it doubles on every retry, default is 1000.
+    pub retry_interval: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "webhook-timeout", long)]
+    /// Timeout in seconds of a webhook request, default is 10.
+    pub timeout: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "webhook-max-hooks", long)]
+    /// Max count of the webhooks registered by rpc, default is 100.
+    pub max_hooks: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[structopt(name = "webhook-allow-private-targets", long)]
+    /// Allow the webhooks registered by rpc to target the loopback, private or link local addresses, default is false.
+    pub allow_private_targets: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[structopt(skip)]
+    /// The webhooks registered by config.
+    pub hooks: Vec<WebhookHookConfig>,
+}
+
+impl WebhookConfig {
+    pub fn enable(&self) -> bool {
+        self.enable.unwrap_or(false)
+    }
+
+    pub fn max_retry(&self) -> u32 {
+        self.max_retry.unwrap_or(DEFAULT_MAX_RETRY)
+    }
+
+    pub fn retry_interval(&self) -> Duration {
+        Duration::from_millis(self.retry_interval.unwrap_or(DEFAULT_RETRY_INTERVAL_MS))
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout.unwrap_or(DEFAULT_TIMEOUT_SECS))
+    }
+
+    pub fn max_hooks(&self) -> usize {
+        self.max_hooks.unwrap_or(DEFAULT_MAX_HOOKS)
+    }
+
+    pub fn allow_private_targets(&self) -> bool {
+        self.allow_private_targets.unwrap_or(false)
+    }
+}
+
+impl ConfigModule for WebhookConfig {
+    fn merge_with_opt(&mut self, opt: &StarcoinOpt, _base: Arc<BaseConfig>) -> Result<()> {
+        if opt.webhook.enable.is_some() {
+            self.enable = opt.webhook.enable;
+        }
+        if opt.webhook.max_retry.is_some() {
+            self.max_retry = opt.webhook.max_retry;
+        }
+        if opt.webhook.retry_interval.is_some() {
+            self.retry_interval = opt.webhook.retry_interval;
+        }
+        if opt.webhook.timeout.is_some() {
+            self.timeout = opt.webhook.timeout;
+        }
+        if opt.webhook.max_hooks.is_some() {
+            self.max_hooks = opt.webhook.max_hooks;
+        }
+        if opt.webhook.allow_private_targets.is_some() {
+            self.allow_private_targets = opt.webhook.allow_private_targets;
+        }
+        for hook in &self.hooks {
+            ensure!(
+                !hook.filter.is_empty(),
+                "The filter of webhook {} should not be empty.",
+                hook.url
+            );
+        }
+        Ok(())
+    }
+}
@@ -8,6 +8,7 @@ use starcoin_types::error::ExecutorResult;
 use starcoin_types::transaction::TransactionStatus;
 use starcoin_types::transaction::{Transaction, TransactionInfo};
 use starcoin_vm_types::contract_event::ContractEvent;
+use starcoin_vm_types::write_set::WriteSet;
 use vm_runtime::metrics::TXN_STATUS_COUNTERS;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -15,6 +16,7 @@ pub struct BlockExecutedData {
     pub state_root: HashValue,
     pub txn_infos: Vec<TransactionInfo>,
     pub txn_events: Vec<Vec<ContractEvent>>,
+    pub txn_write_sets: Vec<WriteSet>,
 }
 
 impl Default for BlockExecutedData {
@@ -23,6 +25,7 @@ impl Default for BlockExecutedData {
             state_root: HashValue::zero(),
             txn_events: vec![],
             txn_infos: vec![],
+            txn_write_sets: vec![],
         }
     }
 }
@@ -54,7 +57,7 @@ pub fn block_execute(
             TransactionStatus::Keep(status) => {
                 TXN_STATUS_COUNTERS.with_label_values(&["KEEP"]).inc();
                 chain_state
-                    .apply_write_set(write_set)
+                    .apply_write_set(write_set.clone())
                     .map_err(BlockExecutorError::BlockChainStateErr)?;
 
                 let txn_state_root = chain_state
@@ -69,6 +72,7 @@ pub fn block_execute(
                     status,
                 ));
                 executed_data.txn_events.push(events);
+                executed_data.txn_write_sets.push(write_set);
             }
         };
     }
@@ -0,0 +1,131 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::account::{create_account_txn_sent_as_association, Account};
+use anyhow::Result;
+use starcoin_dev::playground::dry_run_with_gas_breakdown;
+use starcoin_types::transaction::Transaction;
+use starcoin_vm_types::identifier::Identifier;
+use starcoin_vm_types::language_storage::ModuleId;
+use starcoin_vm_types::state_view::StateView;
+use starcoin_vm_types::transaction::{
+    DryRunTransaction, Package, ScriptFunction, TransactionPayload,
+};
+use starcoin_vm_types::value::{serialize_values, MoveValue};
+use starcoin_vm_types::vm_status::KeptVMStatus;
+use test_helper::executor::{compile_modules_with_address, execute_and_apply, prepare_genesis};
+
+#[stest::test]
+fn test_dry_run_gas_breakdown() -> Result<()> {
+    let (chain_state, net) = prepare_genesis();
+    let account1 = Account::new();
+    let txn1 = Transaction::UserTransaction(create_account_txn_sent_as_association(
+        &account1, 0, 50_000_000, 1, &net,
+    ));
+    let output1 = execute_and_apply(&chain_state, txn1);
+    assert_eq!(KeptVMStatus::Executed, output1.status().status().unwrap());
+
+    let module_source = r#"
+        module Blob {
+            use 0x1::Vector;
+
+            struct Blob has key, store {
+                data: vector<u8>,
+            }
+
+            public(script) fun store(account: &signer, len: u64) {
+                let data = Vector::empty<u8>();
+                let i = 0;
+                while (i < len) {
+                    Vector::push_back(&mut data, 1u8);
+                    i = i + 1;
+                };
+                move_to(account, Blob { data });
+            }
+        }
+        "#;
+    let module = compile_modules_with_address(*account1.address(), module_source)
+        .pop()
+        .unwrap();
+    let txn = Transaction::UserTransaction(account1.create_signed_txn_impl(
+        *account1.address(),
+        TransactionPayload::Package(Package::new_with_module(module).unwrap()),
+        0,
+        100_000,
+        1,
+        1,
+        net.chain_id(),
+    ));
+    let output = execute_and_apply(&chain_state, txn);
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+
+    let blob_len = 4096u64;
+    let payload = TransactionPayload::ScriptFunction(ScriptFunction::new(
+        ModuleId::new(*account1.address(), Identifier::new("Blob").unwrap()),
+        Identifier::new("store").unwrap(),
+        vec![],
+        serialize_values(&vec![MoveValue::U64(blob_len)]),
+    ));
+    let raw_txn = Account::create_raw_txn_impl(
+        *account1.address(),
+        payload,
+        1,
+        10_000_000,
+        1,
+        1,
+        net.chain_id(),
+    );
+    let (_status, output, breakdown) = dry_run_with_gas_breakdown(
+        &chain_state,
+        DryRunTransaction {
+            raw_txn,
+            public_key: account1.public_key(),
+        },
+    )?;
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    assert!(breakdown.intrinsic_gas > 0);
+    assert_eq!(
+        breakdown.intrinsic_gas + breakdown.instruction_gas + breakdown.storage_write_gas,
+        output.gas_used()
+    );
+    // the account of the sender is mutated.
+    assert!(breakdown.storage_write_gas > 0);
+    // the instruction gas is measured by every instruction and native.
+    let charged_gas: u64 = breakdown
+        .instructions
+        .iter()
+        .chain(breakdown.natives.iter())
+        .map(|(_, gas)| *gas)
+        .sum();
+    assert_eq!(charged_gas, breakdown.instruction_gas);
+    let gas_of = |charges: &[(String, u64)], name: &str| {
+        charges
+            .iter()
+            .find(|(charge_name, _)| charge_name == name)
+            .map(|(_, gas)| *gas)
+    };
+    assert!(gas_of(&breakdown.instructions, "MoveTo").unwrap() > 0);
+    // the loop pushes every byte of the blob.
+    let push_back_gas = gas_of(&breakdown.natives, "PUSH_BACK").unwrap();
+    assert!(push_back_gas >= blob_len);
+    assert!(breakdown
+        .instructions
+        .iter()
+        .chain(breakdown.natives.iter())
+        .all(|(_, gas)| *gas > 0));
+    assert!(breakdown.storage_reads > 0);
+    assert!(breakdown.storage_read_bytes > 0);
+    assert_eq!(breakdown.storage_writes, breakdown.write_ops.len() as u64);
+    // the blob is written with its length prefix, the other writes are the account resources.
+    let (blob_path, blob_size) = breakdown
+        .write_ops
+        .iter()
+        .filter_map(|(access_path, size)| size.map(|size| (access_path, size)))
+        .max_by_key(|(_, size)| *size)
+        .unwrap();
+    assert!(blob_size >= blob_len && blob_size < blob_len + 16);
+    assert!(breakdown.storage_write_bytes >= blob_size);
+    // the dry run does not change the state.
+    assert!(chain_state.get(blob_path)?.is_none());
+    Ok(())
+}
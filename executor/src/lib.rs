@@ -17,6 +17,8 @@ pub use starcoin_transaction_builder::{
 pub mod account;
 mod block_executor;
 #[cfg(test)]
+pub mod dry_run_test;
+#[cfg(test)]
 pub mod error_code_test;
 
 mod executor;
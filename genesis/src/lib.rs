@@ -12,16 +12,21 @@ use starcoin_chain::{BlockChain, ChainReader};
 use starcoin_config::{
     genesis_key_pair, BuiltinNetworkID, ChainNetwork, ChainNetworkID, GenesisBlockParameter,
 };
+use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_state_api::ChainState;
 use starcoin_statedb::ChainStateDB;
 use starcoin_storage::storage::StorageInstance;
 use starcoin_storage::{BlockStore, Storage, Store};
-use starcoin_transaction_builder::{build_stdlib_package, StdLibOptions};
+use starcoin_transaction_builder::{
+    build_stdlib_package, encode_batch_transfer_script_by_token_code, is_batch_transfer_supported,
+    StdLibOptions,
+};
 use starcoin_types::startup_info::{ChainInfo, StartupInfo};
 use starcoin_types::transaction::TransactionInfo;
 use starcoin_types::{block::Block, transaction::Transaction};
-use starcoin_vm_types::account_config::CORE_CODE_ADDRESS;
+use starcoin_vm_types::account_config::{association_address, CORE_CODE_ADDRESS};
+use starcoin_vm_types::token::stc::STC_TOKEN_CODE;
 use starcoin_vm_types::transaction::{
     RawUserTransaction, SignedUserTransaction, TransactionPayload,
 };
@@ -36,6 +41,9 @@ mod errors;
 pub use errors::GenesisError;
 
 pub static GENESIS_GENERATED_DIR: &str = "generated";
+/// The max count of the genesis accounts funded by one txn.
+const GENESIS_ACCOUNTS_PER_TXN: usize = 100;
+const GENESIS_ACCOUNTS_TXN_MAX_GAS_AMOUNT: u64 = 40_000_000;
 pub const GENESIS_DIR: Dir = include_dir!("generated");
 
 pub enum GenesisOpt {
@@ -111,27 +119,44 @@ impl Genesis {
         }) = genesis_config.genesis_block_parameter()
         {
             let txn = Self::build_genesis_transaction(net)?;
+            let accounts_txns = Self::build_genesis_accounts_transactions(net)?;
 
             let storage = Arc::new(Storage::new(StorageInstance::new_cache_instance())?);
             let chain_state_db = ChainStateDB::new(storage.clone(), None);
 
-            let transaction_info = Self::execute_genesis_txn(&chain_state_db, txn.clone())?;
+            let (transaction_infos, state_root) = if accounts_txns.is_empty() {
+                let transaction_info = Self::execute_genesis_txn(&chain_state_db, txn.clone())?;
+                let state_root = transaction_info.state_root_hash();
+                (vec![transaction_info], state_root)
+            } else {
+                Self::execute_genesis_txns(
+                    net,
+                    &chain_state_db,
+                    txn.clone(),
+                    accounts_txns.clone(),
+                )?
+            };
+            let gas_used = transaction_infos
+                .iter()
+                .fold(0u64, |acc, info| acc.saturating_add(info.gas_used()));
 
             let accumulator = MerkleAccumulator::new_with_info(
                 AccumulatorInfo::default(),
                 storage.get_accumulator_store(AccumulatorStoreType::Transaction),
             );
-            let txn_info_hash = transaction_info.id();
+            let txn_info_hashes: Vec<_> = transaction_infos.iter().map(|info| info.id()).collect();
 
-            let accumulator_root = accumulator.append(vec![txn_info_hash].as_slice())?;
+            let accumulator_root = accumulator.append(txn_info_hashes.as_slice())?;
             accumulator.flush()?;
             Ok(Block::genesis_block(
                 *parent_hash,
                 *timestamp,
                 accumulator_root,
-                transaction_info.state_root_hash(),
+                state_root,
+                gas_used,
                 *difficulty,
                 txn,
+                accounts_txns,
             ))
         } else {
             bail!("{}'s genesis config not ready to build genesis block", net);
@@ -162,6 +187,96 @@ impl Genesis {
         Ok(sign_txn.into_inner())
     }
 
+    /// Build the txns funding the genesis accounts from the pre mine of the association account,
+    /// they are executed after the genesis txn in the genesis block.
+    pub fn build_genesis_accounts_transactions(
+        net: &ChainNetwork,
+    ) -> Result<Vec<SignedUserTransaction>> {
+        let genesis_config = net.genesis_config();
+        if genesis_config.genesis_accounts.is_empty() {
+            return Ok(vec![]);
+        }
+        genesis_config.validate_genesis_accounts()?;
+        ensure!(
+            is_batch_transfer_supported(net.stdlib_version()),
+            "The stdlib {:?} of {} does not support batch transfer, can not fund the genesis accounts",
+            net.stdlib_version(),
+            net
+        );
+        let total_balance = genesis_config
+            .genesis_accounts
+            .iter()
+            .fold(0u128, |acc, account| acc.saturating_add(account.balance));
+        let txn_count = (genesis_config.genesis_accounts.len() + GENESIS_ACCOUNTS_PER_TXN - 1)
+            / GENESIS_ACCOUNTS_PER_TXN;
+        let max_gas_fee = u128::from(GENESIS_ACCOUNTS_TXN_MAX_GAS_AMOUNT) * txn_count as u128;
+        ensure!(
+            total_balance.saturating_add(max_gas_fee) <= genesis_config.pre_mine_amount,
+            "The pre mine amount {} is not enough to fund the genesis accounts {} and pay the gas fee {}",
+            genesis_config.pre_mine_amount,
+            total_balance,
+            max_gas_fee
+        );
+        let expiration_timestamp_secs =
+            net.genesis_block_parameter().timestamp / 1000 + genesis_config.transaction_timeout;
+        genesis_config
+            .genesis_accounts
+            .chunks(GENESIS_ACCOUNTS_PER_TXN)
+            .enumerate()
+            .map(|(seq_number, accounts)| {
+                let payload = encode_batch_transfer_script_by_token_code(
+                    accounts.iter().map(|account| account.address).collect(),
+                    accounts
+                        .iter()
+                        .map(|account| Some(account.auth_key()))
+                        .collect(),
+                    accounts.iter().map(|account| account.balance).collect(),
+                    STC_TOKEN_CODE.clone(),
+                );
+                let txn = RawUserTransaction::new_with_default_gas_token(
+                    association_address(),
+                    seq_number as u64,
+                    TransactionPayload::ScriptFunction(payload),
+                    GENESIS_ACCOUNTS_TXN_MAX_GAS_AMOUNT,
+                    1,
+                    expiration_timestamp_secs,
+                    net.chain_id(),
+                );
+                genesis_config
+                    .sign_with_association(txn)
+                    .map_err(|e| format_err!("Sign the genesis accounts txn error: {}", e))
+            })
+            .collect()
+    }
+
+    /// Execute the genesis txn and the genesis accounts txns in one block, as the chain executes the genesis block.
+    fn execute_genesis_txns(
+        net: &ChainNetwork,
+        chain_state: &dyn ChainState,
+        genesis_txn: SignedUserTransaction,
+        accounts_txns: Vec<SignedUserTransaction>,
+    ) -> Result<(Vec<TransactionInfo>, HashValue)> {
+        let txns = std::iter::once(genesis_txn)
+            .chain(accounts_txns)
+            .map(Transaction::UserTransaction)
+            .collect();
+        let executed_data = starcoin_executor::block_execute(
+            chain_state,
+            txns,
+            net.genesis_epoch().block_gas_limit(),
+        )?;
+        for txn_info in &executed_data.txn_infos {
+            ensure!(
+                txn_info.status() == &KeptVMStatus::Executed,
+                "Genesis txn {} execute fail for: {:?}",
+                txn_info.transaction_hash(),
+                txn_info.status()
+            );
+        }
+        chain_state.flush()?;
+        Ok((executed_data.txn_infos, executed_data.state_root))
+    }
+
     pub fn execute_genesis_txn(
         chain_state: &dyn ChainState,
         txn: SignedUserTransaction,
@@ -342,7 +457,8 @@ impl Genesis {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use starcoin_crypto::HashValue;
+    use starcoin_config::GenesisAccount;
+    use starcoin_crypto::keygen::KeyGen;
     use starcoin_state_api::AccountStateReader;
     use starcoin_storage::block_info::BlockInfoStore;
     use starcoin_storage::storage::StorageInstance;
@@ -353,6 +469,7 @@ mod tests {
     use starcoin_vm_types::on_chain_config::{ConsensusConfig, VMConfig, Version};
     use starcoin_vm_types::on_chain_config::{DaoConfig, TransactionPublishOption};
     use starcoin_vm_types::on_chain_resource::Epoch;
+    use starcoin_vm_types::transaction::authenticator::AuthenticationKey;
 
     #[stest::test]
     pub fn test_genesis_load() -> Result<()> {
@@ -391,6 +508,76 @@ mod tests {
         do_test_genesis(&net, temp_dir.path())
     }
 
+    #[stest::test]
+    pub fn test_custom_genesis_with_accounts() -> Result<()> {
+        let mut genesis_config = BuiltinNetworkID::Test.genesis_config().clone();
+        let mut key_gen = KeyGen::from_os_rng();
+        genesis_config.genesis_accounts = (0..3u128)
+            .map(|i| {
+                let (_, public_key) = key_gen.generate_keypair();
+                GenesisAccount {
+                    address: AuthenticationKey::ed25519(&public_key).derived_address(),
+                    public_key,
+                    balance: 1_000_000 * (i + 1),
+                }
+            })
+            .collect();
+        let accounts = genesis_config.genesis_accounts.clone();
+        let net = ChainNetwork::new_custom("testx".to_string(), ChainId::new(123), genesis_config)?;
+        let temp_dir = starcoin_config::temp_path();
+        do_test_genesis(&net, temp_dir.path())?;
+
+        let storage = Arc::new(Storage::new(StorageInstance::new_cache_instance())?);
+        let (chain_info, genesis) =
+            Genesis::init_and_check_storage(&net, storage.clone(), temp_dir.path())?;
+        // the genesis txn and the genesis accounts txn.
+        assert_eq!(genesis.block().transactions().len(), 2);
+        assert!(genesis.block().header().gas_used() > 0);
+        let state_db = ChainStateDB::new(
+            storage.into_super_arc(),
+            Some(chain_info.status().head().state_root()),
+        );
+        let account_state_reader = AccountStateReader::new(&state_db);
+        for account in accounts {
+            assert_eq!(
+                account_state_reader.get_balance(&account.address)?,
+                Some(account.balance)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_validate_genesis_accounts() {
+        let mut genesis_config = BuiltinNetworkID::Test.genesis_config().clone();
+        let (_, public_key) = KeyGen::from_os_rng().generate_keypair();
+        let account = GenesisAccount {
+            address: AuthenticationKey::ed25519(&public_key).derived_address(),
+            public_key,
+            balance: 1,
+        };
+        genesis_config.genesis_accounts = vec![account.clone()];
+        assert!(genesis_config.validate_genesis_accounts().is_ok());
+
+        // duplicate address.
+        genesis_config.genesis_accounts = vec![account.clone(), account.clone()];
+        assert!(genesis_config.validate_genesis_accounts().is_err());
+
+        // the address is not derived from the public key.
+        genesis_config.genesis_accounts = vec![GenesisAccount {
+            address: association_address(),
+            ..account.clone()
+        }];
+        assert!(genesis_config.validate_genesis_accounts().is_err());
+
+        // exceeds the pre mine amount.
+        genesis_config.genesis_accounts = vec![GenesisAccount {
+            balance: genesis_config.pre_mine_amount + 1,
+            ..account
+        }];
+        assert!(genesis_config.validate_genesis_accounts().is_err());
+    }
+
     pub fn do_test_genesis(net: &ChainNetwork, data_dir: &Path) -> Result<()> {
         let storage1 = Arc::new(Storage::new(StorageInstance::new_cache_instance())?);
         let (chain_info1, genesis1) = Genesis::init_and_check_storage(net, storage1, data_dir)?;
@@ -5,6 +5,7 @@ use crate::metrics::MINER_METRICS;
 use crate::task::MintTask;
 use anyhow::Result;
 use consensus::Consensus;
+use crypto::HashValue;
 use futures::executor::block_on;
 use logger::prelude::*;
 use starcoin_config::NodeConfig;
@@ -14,6 +15,10 @@ use starcoin_service_registry::{
 };
 use std::sync::Arc;
 use std::time::Duration;
+use thiserror::Error;
+use types::block::BlockNumber;
+use types::genesis_config::ConsensusStrategy;
+use types::U256;
 
 mod create_block_template;
 pub mod generate_block_event_pacemaker;
@@ -36,6 +41,45 @@ impl ServiceRequest for MinerClientSubscribeRequest {
     type Response = Result<Option<MintBlockEvent>>;
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Error)]
+pub enum MinerError {
+    /// The seal is not for the current mint job, the job is superseded or the block is mint by others.
+    #[error("stale work of minting blob {0}, the mint job is superseded")]
+    StaleWork(String),
+    #[error("invalid seal of minting blob {0}: {1}")]
+    InvalidSeal(String, String),
+}
+
+/// The current mint job, for the external miners to solve.
+#[derive(Clone, Debug)]
+pub struct MintWork {
+    pub parent_hash: HashValue,
+    pub minting_blob: Vec<u8>,
+    pub difficulty: U256,
+    pub block_number: BlockNumber,
+    pub strategy: ConsensusStrategy,
+}
+
+/// Get the current mint job, dispatch a new one if there is no job.
+#[derive(Debug)]
+pub struct GetWorkRequest;
+
+impl ServiceRequest for GetWorkRequest {
+    type Response = Result<Option<MintWork>>;
+}
+
+/// Submit the seal of the current mint job, return the id of the mint block.
+#[derive(Debug)]
+pub struct SubmitWorkRequest {
+    pub minting_blob: Vec<u8>,
+    pub nonce: u32,
+    pub extra: BlockHeaderExtra,
+}
+
+impl ServiceRequest for SubmitWorkRequest {
+    type Response = Result<HashValue>;
+}
+
 pub struct MinerService {
     config: Arc<NodeConfig>,
     current_task: Option<MintTask>,
@@ -67,6 +111,35 @@ impl ServiceHandler<Self, MinerClientSubscribeRequest> for MinerService {
     }
 }
 
+impl ServiceHandler<Self, GetWorkRequest> for MinerService {
+    fn handle(
+        &mut self,
+        _msg: GetWorkRequest,
+        ctx: &mut ServiceContext<MinerService>,
+    ) -> Result<Option<MintWork>> {
+        if self.current_task.is_none() {
+            self.dispatch_task(ctx)?;
+        }
+        Ok(self.current_task.as_ref().map(|task| MintWork {
+            parent_hash: task.block_template.parent_hash,
+            minting_blob: task.minting_blob.clone(),
+            difficulty: task.block_template.difficulty,
+            block_number: task.block_template.number,
+            strategy: task.block_template.strategy,
+        }))
+    }
+}
+
+impl ServiceHandler<Self, SubmitWorkRequest> for MinerService {
+    fn handle(
+        &mut self,
+        msg: SubmitWorkRequest,
+        ctx: &mut ServiceContext<MinerService>,
+    ) -> Result<HashValue> {
+        Ok(self.seal_task(msg.nonce, msg.extra, msg.minting_blob, ctx)?)
+    }
+}
+
 impl ServiceFactory<MinerService> for MinerService {
     fn create(ctx: &mut ServiceContext<MinerService>) -> Result<MinerService> {
         let config = ctx.get_shared::<Arc<NodeConfig>>()?;
@@ -148,47 +221,54 @@ impl MinerService {
         minting_blob: Vec<u8>,
         ctx: &mut ServiceContext<MinerService>,
     ) -> Result<()> {
-        match self.current_task.as_ref() {
-            None => {
-                debug!(
-                    "MintTask is none, but got nonce: {}, extra:{:?} for minting_blob: {:?}, may be mint by other client.",
-                    nonce, extra, minting_blob,
+        match self.seal_task(nonce, extra, minting_blob, ctx) {
+            Ok(_) => {}
+            Err(MinerError::StaleWork(minting_blob)) => {
+                info!(
+                    "[miner] Mint job of minting blob {} is superseded, probably received old job result or mint by other client, nonce: {}, extra: {:?}.",
+                    minting_blob, nonce, extra
                 );
-                return Ok(());
             }
-            Some(task) => {
-                if task.minting_blob != minting_blob {
-                    info!(
-                        "[miner] Jobs hash mismatch expect: {}, got: {}, probably received old job result.",
-                        hex::encode(task.minting_blob.as_slice()),
-                        hex::encode(minting_blob.as_slice())
-                    );
-                    return Ok(());
-                }
-                if let Err(e) = task.block_template.strategy.verify_blob(
-                    task.minting_blob.clone(),
-                    nonce,
-                    extra,
-                    task.block_template.difficulty,
-                ) {
-                    warn!(
-                        "Failed to verify blob: {}, nonce: {}, err: {}",
-                        hex::encode(task.minting_blob.as_slice()),
-                        nonce,
-                        e
-                    );
-                    return Ok(());
-                }
+            Err(e) => {
+                warn!("[miner] Failed to verify seal, err: {}", e);
             }
         }
+        Ok(())
+    }
 
-        if let Some(task) = self.current_task.take() {
-            let block = task.finish(nonce, extra);
-            info!("Mint new block: {}", block);
-            ctx.broadcast(MinedBlock(Arc::new(block)));
-            MINER_METRICS.block_mint_count.inc();
+    /// Verify the seal against the current mint job, then finish the job and broadcast the mint block.
+    fn seal_task(
+        &mut self,
+        nonce: u32,
+        extra: BlockHeaderExtra,
+        minting_blob: Vec<u8>,
+        ctx: &mut ServiceContext<MinerService>,
+    ) -> Result<HashValue, MinerError> {
+        let task = match self.current_task.take() {
+            Some(task) if task.minting_blob == minting_blob => task,
+            task => {
+                self.current_task = task;
+                return Err(MinerError::StaleWork(hex::encode(minting_blob)));
+            }
+        };
+        if let Err(e) = task.block_template.strategy.verify_blob(
+            task.minting_blob.clone(),
+            nonce,
+            extra,
+            task.block_template.difficulty,
+        ) {
+            self.current_task = Some(task);
+            return Err(MinerError::InvalidSeal(
+                hex::encode(minting_blob),
+                e.to_string(),
+            ));
         }
-        Ok(())
+        let block = task.finish(nonce, extra);
+        let block_id = block.id();
+        info!("Mint new block: {}", block);
+        ctx.broadcast(MinedBlock(Arc::new(block)));
+        MINER_METRICS.block_mint_count.inc();
+        Ok(block_id)
     }
 
     pub fn is_minting(&self) -> bool {
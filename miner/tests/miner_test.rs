@@ -7,7 +7,8 @@ use starcoin_account_service::AccountService;
 use starcoin_config::NodeConfig;
 use starcoin_genesis::Genesis;
 use starcoin_miner::{
-    BlockHeaderExtra, CreateBlockTemplateRequest, CreateBlockTemplateService, MinerService,
+    BlockHeaderExtra, CreateBlockTemplateRequest, CreateBlockTemplateService, GetWorkRequest,
+    MinerError, MinerService, SubmitWorkRequest,
 };
 use starcoin_service_registry::bus::Bus;
 use starcoin_service_registry::{RegistryAsyncService, RegistryService};
@@ -123,3 +124,84 @@ async fn test_miner_service() {
 
     registry.shutdown_system().await.unwrap();
 }
+
+#[stest::test]
+async fn test_miner_get_and_submit_work() {
+    let mut config = NodeConfig::random_for_test();
+    config.miner.disable_mint_empty_block = Some(false);
+    let registry = RegistryService::launch();
+    let node_config = Arc::new(config.clone());
+    registry.put_shared(node_config.clone()).await.unwrap();
+    let (storage, _chain_info, genesis) = Genesis::init_storage_for_test(config.net()).unwrap();
+    registry.put_shared(storage.clone()).await.unwrap();
+    let chain_header = storage
+        .get_block_header_by_hash(genesis.block().id())
+        .unwrap()
+        .unwrap();
+    let txpool = TxPoolService::new(node_config.clone(), storage.clone(), chain_header);
+    registry.put_shared(txpool).await.unwrap();
+    registry
+        .register_mocker(AccountService::mock().unwrap())
+        .await
+        .unwrap();
+    registry
+        .register::<CreateBlockTemplateService>()
+        .await
+        .unwrap();
+    let miner = registry.register::<MinerService>().await.unwrap();
+
+    let work = miner.send(GetWorkRequest).await.unwrap().unwrap().unwrap();
+    assert_eq!(work.block_number, 1);
+    assert_eq!(work.parent_hash, genesis.block().id());
+    // get work again return the same job.
+    let same_work = miner.send(GetWorkRequest).await.unwrap().unwrap().unwrap();
+    assert_eq!(work.minting_blob, same_work.minting_blob);
+
+    let extra = BlockHeaderExtra::new([0u8; 4]);
+    let stale_err = miner
+        .send(SubmitWorkRequest {
+            minting_blob: vec![0u8; 76],
+            nonce: 0,
+            extra,
+        })
+        .await
+        .unwrap()
+        .unwrap_err();
+    assert!(matches!(
+        stale_err.downcast::<MinerError>().unwrap(),
+        MinerError::StaleWork(_)
+    ));
+
+    let nonce = work.strategy.solve_consensus_nonce(
+        &work.minting_blob,
+        work.difficulty,
+        config.net().time_service().as_ref(),
+    );
+    let block_id = miner
+        .send(SubmitWorkRequest {
+            minting_blob: work.minting_blob.clone(),
+            nonce,
+            extra,
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_ne!(block_id, work.parent_hash);
+
+    // the job is finished, submit it again is stale.
+    let stale_err = miner
+        .send(SubmitWorkRequest {
+            minting_blob: work.minting_blob,
+            nonce,
+            extra,
+        })
+        .await
+        .unwrap()
+        .unwrap_err();
+    assert!(matches!(
+        stale_err.downcast::<MinerError>().unwrap(),
+        MinerError::StaleWork(_)
+    ));
+
+    registry.shutdown_system().await.unwrap();
+}
@@ -822,6 +822,13 @@ impl NetworkService {
         Ok(())
     }
 
+    /// Adds an address of the peer to the discovery, the peer may be connected after it is discovered.
+    pub fn add_known_address(&self, peer_id: PeerId, addr: Multiaddr) {
+        let _ = self
+            .to_worker
+            .unbounded_send(ServiceToWorkerMsg::AddKnownAddress(peer_id, addr));
+    }
+
     /// Returns the number of peers we're connected to.
     pub fn num_connected(&self) -> usize {
         self.num_connected.load(Ordering::Relaxed)
@@ -5,6 +5,7 @@ use crate::ReputationChange;
 use anyhow::*;
 use bcs_ext::{BCSCodec, Sample};
 use futures::channel::oneshot::Receiver;
+use network_p2p_types::MultiaddrWithPeerId;
 use serde::{Deserialize, Serialize};
 use starcoin_crypto::HashValue;
 use starcoin_service_registry::ServiceRequest;
@@ -351,3 +352,70 @@ pub struct GetSelfPeer;
 impl ServiceRequest for GetSelfPeer {
     type Response = PeerInfo;
 }
+
+/// Default duration of `BanPeer` if the duration is not present.
+pub const DEFAULT_BAN_DURATION_SECS: u64 = 60 * 60;
+
+/// Add a reserved peer, the `peer` should be a multiaddr with peer id.
+#[derive(Clone, Debug)]
+pub struct AddPeer {
+    pub peer: String,
+}
+
+impl ServiceRequest for AddPeer {
+    type Response = Result<()>;
+}
+
+/// Remove the peer from the reserved peers and disconnect it.
+#[derive(Clone, Debug)]
+pub struct RemovePeer {
+    pub peer_id: PeerId,
+}
+
+impl ServiceRequest for RemovePeer {
+    type Response = ();
+}
+
+/// Disconnect the peer and reject its connection until the ban expires.
+#[derive(Clone, Debug)]
+pub struct BanPeer {
+    pub peer_id: PeerId,
+    pub duration_secs: Option<u64>,
+}
+
+impl ServiceRequest for BanPeer {
+    type Response = ();
+}
+
+#[derive(Clone, Debug)]
+pub struct BannedPeer {
+    pub peer_id: PeerId,
+    /// The last known peer info before banned.
+    pub peer_info: Option<PeerInfo>,
+    /// Unix timestamp in seconds.
+    pub banned_until: u64,
+}
+
+/// Get the currently banned peers.
+#[derive(Clone, Debug)]
+pub struct GetBannedPeers;
+
+impl ServiceRequest for GetBannedPeers {
+    type Response = Vec<BannedPeer>;
+}
+
+/// A seed address resolved from a dns seed.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct DiscoveredSeed {
+    /// The dns seed entry in the config.
+    pub dns_seed: String,
+    pub seed: MultiaddrWithPeerId,
+}
+
+/// Get the seeds resolved from the dns seeds, excluding the configured seeds.
+#[derive(Clone, Debug)]
+pub struct GetDiscoveredSeeds;
+
+impl ServiceRequest for GetDiscoveredSeeds {
+    type Response = Vec<DiscoveredSeed>;
+}
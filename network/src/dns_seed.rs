@@ -0,0 +1,225 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Resolve the dns seeds into seed addresses, so the node can still join the network
+//! after the configured seeds change their ips.
+
+use anyhow::{format_err, Result};
+use log::{debug, warn};
+use network_api::messages::DiscoveredSeed;
+use network_p2p_types::multiaddr::Protocol;
+use network_p2p_types::MultiaddrWithPeerId;
+use std::collections::HashSet;
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+use trust_dns_resolver::Resolver;
+
+/// The prefix of the TXT records in the libp2p dnsaddr format, it is optional.
+const DNSADDR_PREFIX: &str = "dnsaddr=";
+
+pub trait SeedResolver: Send + Sync {
+    /// Get the TXT records of the domain, the character strings of a record are joined.
+    fn txt_lookup(&self, domain: &str) -> Result<Vec<String>>;
+
+    fn ipv4_lookup(&self, host: &str) -> Result<Vec<Ipv4Addr>>;
+}
+
+/// The resolver by the system dns config, its lookups are blocking.
+pub struct DnsSeedResolver {
+    resolver: Resolver,
+}
+
+impl DnsSeedResolver {
+    pub fn from_system_conf() -> Result<Self> {
+        Ok(Self {
+            resolver: Resolver::from_system_conf()?,
+        })
+    }
+}
+
+impl SeedResolver for DnsSeedResolver {
+    fn txt_lookup(&self, domain: &str) -> Result<Vec<String>> {
+        Ok(self
+            .resolver
+            .txt_lookup(domain)?
+            .iter()
+            .map(|txt| {
+                txt.txt_data()
+                    .iter()
+                    .map(|data| String::from_utf8_lossy(data).into_owned())
+                    .collect::<String>()
+            })
+            .collect())
+    }
+
+    fn ipv4_lookup(&self, host: &str) -> Result<Vec<Ipv4Addr>> {
+        Ok(self.resolver.ipv4_lookup(host)?.iter().cloned().collect())
+    }
+}
+
+/// Resolve one dns seed entry, a domain is resolved by its TXT records,
+/// and a dns multiaddr with peer id is resolved by the A records of its host.
+fn resolve_dns_seed(
+    resolver: &dyn SeedResolver,
+    dns_seed: &str,
+) -> Result<Vec<MultiaddrWithPeerId>> {
+    if !dns_seed.starts_with('/') {
+        return Ok(resolver
+            .txt_lookup(dns_seed)?
+            .into_iter()
+            .filter_map(|record| {
+                let addr = record.trim();
+                let addr = addr.strip_prefix(DNSADDR_PREFIX).unwrap_or(addr);
+                match MultiaddrWithPeerId::from_str(addr) {
+                    Ok(seed) => Some(seed),
+                    Err(e) => {
+                        debug!(
+                            "Ignore the TXT record {} of dns seed {}: {:?}",
+                            record, dns_seed, e
+                        );
+                        None
+                    }
+                }
+            })
+            .collect());
+    }
+    let seed = MultiaddrWithPeerId::from_str(dns_seed)
+        .map_err(|e| format_err!("invalid dns seed {}: {:?}", dns_seed, e))?;
+    let host = match seed.multiaddr.iter().next() {
+        Some(Protocol::Dns(host)) | Some(Protocol::Dns4(host)) => host.to_string(),
+        _ => return Err(format_err!("dns seed {} should start with /dns4", dns_seed)),
+    };
+    Ok(resolver
+        .ipv4_lookup(host.as_str())?
+        .into_iter()
+        .filter_map(|ip| {
+            seed.multiaddr
+                .replace(0, |_| Some(Protocol::Ip4(ip)))
+                .map(|multiaddr| MultiaddrWithPeerId::new(multiaddr, seed.peer_id))
+        })
+        .collect())
+}
+
+/// Resolve the dns seeds, the failed ones are skipped,
+/// and the seeds which are in `configured_seeds` or resolved before are removed.
+pub fn resolve_dns_seeds(
+    resolver: &dyn SeedResolver,
+    dns_seeds: &[String],
+    configured_seeds: &[MultiaddrWithPeerId],
+) -> Vec<DiscoveredSeed> {
+    let mut known: HashSet<MultiaddrWithPeerId> = configured_seeds.iter().cloned().collect();
+    let mut discovered = vec![];
+    for dns_seed in dns_seeds {
+        match resolve_dns_seed(resolver, dns_seed) {
+            Ok(seeds) => {
+                for seed in seeds {
+                    if known.insert(seed.clone()) {
+                        discovered.push(DiscoveredSeed {
+                            dns_seed: dns_seed.clone(),
+                            seed,
+                        });
+                    }
+                }
+            }
+            Err(e) => warn!("Resolve dns seed {} failed: {:?}", dns_seed, e),
+        }
+    }
+    discovered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use network_p2p_types::PeerId;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct MockResolver {
+        txt_records: HashMap<String, Vec<String>>,
+        a_records: HashMap<String, Vec<Ipv4Addr>>,
+    }
+
+    impl SeedResolver for MockResolver {
+        fn txt_lookup(&self, domain: &str) -> Result<Vec<String>> {
+            self.txt_records
+                .get(domain)
+                .cloned()
+                .ok_or_else(|| format_err!("no TXT record of {}", domain))
+        }
+
+        fn ipv4_lookup(&self, host: &str) -> Result<Vec<Ipv4Addr>> {
+            self.a_records
+                .get(host)
+                .cloned()
+                .ok_or_else(|| format_err!("no A record of {}", host))
+        }
+    }
+
+    fn seed(ip: &str, peer_id: &PeerId) -> MultiaddrWithPeerId {
+        format!("/ip4/{}/tcp/9840/p2p/{}", ip, peer_id)
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_resolve_dns_seeds() {
+        let peer1 = PeerId::random();
+        let peer2 = PeerId::random();
+        let peer3 = PeerId::random();
+        let mut resolver = MockResolver::default();
+        resolver.txt_records.insert(
+            "seeds.example.com".to_string(),
+            vec![
+                seed("10.0.0.1", &peer1).to_string(),
+                format!("{}{}", DNSADDR_PREFIX, seed("10.0.0.2", &peer2)),
+                "not a multiaddr".to_string(),
+            ],
+        );
+        resolver.a_records.insert(
+            "seed3.example.com".to_string(),
+            vec![
+                "10.0.0.3".parse().unwrap(),
+                // the configured seed is removed.
+                "10.0.0.4".parse().unwrap(),
+            ],
+        );
+        let dns_seeds = vec![
+            "seeds.example.com".to_string(),
+            // the failed dns seed is skipped.
+            "unknown.example.com".to_string(),
+            format!("/dns4/seed3.example.com/tcp/9840/p2p/{}", peer3),
+            // the seeds resolved before are removed.
+            "seeds.example.com".to_string(),
+        ];
+        let discovered = resolve_dns_seeds(&resolver, &dns_seeds, &[seed("10.0.0.4", &peer3)]);
+        assert_eq!(
+            discovered,
+            vec![
+                DiscoveredSeed {
+                    dns_seed: dns_seeds[0].clone(),
+                    seed: seed("10.0.0.1", &peer1),
+                },
+                DiscoveredSeed {
+                    dns_seed: dns_seeds[0].clone(),
+                    seed: seed("10.0.0.2", &peer2),
+                },
+                DiscoveredSeed {
+                    dns_seed: dns_seeds[2].clone(),
+                    seed: seed("10.0.0.3", &peer3),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_invalid_dns_seed() {
+        let resolver = MockResolver::default();
+        assert!(resolve_dns_seed(&resolver, "/ip4/10.0.0.1/tcp/9840").is_err());
+        assert!(resolve_dns_seed(
+            &resolver,
+            format!("/ip4/10.0.0.1/tcp/9840/p2p/{}", PeerId::random()).as_str()
+        )
+        .is_err());
+        assert!(resolve_dns_seeds(&resolver, &["unknown.example.com".to_string()], &[]).is_empty());
+    }
+}
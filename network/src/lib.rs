@@ -3,6 +3,7 @@
 
 #![deny(clippy::integer_arithmetic)]
 mod broadcast_score_metrics;
+pub mod dns_seed;
 pub mod helper;
 mod network_metrics;
 mod service;
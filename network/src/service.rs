@@ -2,23 +2,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::broadcast_score_metrics::BROADCAST_SCORE_METRICS;
+use crate::dns_seed::{resolve_dns_seeds, DnsSeedResolver};
 use crate::network_metrics::NetworkMetrics;
 use crate::{build_network_worker, Announcement};
 use anyhow::{format_err, Result};
 use bytes::Bytes;
 use futures::future::{abortable, AbortHandle};
 use futures::FutureExt;
-use log::{debug, error, info, trace};
+use log::{debug, error, info, trace, warn};
 use lru::LruCache;
 use network_api::messages::{
-    AnnouncementType, GetPeerById, GetPeerSet, GetSelfPeer, NotificationMessage, PeerEvent,
-    PeerMessage, PeerReputations, ReportReputation, TransactionsMessage,
+    AddPeer, AnnouncementType, BanPeer, BannedPeer, DiscoveredSeed, GetBannedPeers,
+    GetDiscoveredSeeds, GetPeerById, GetPeerSet, GetSelfPeer, NotificationMessage, PeerEvent,
+    PeerMessage, PeerReputations, RemovePeer, ReportReputation, TransactionsMessage,
+    DEFAULT_BAN_DURATION_SECS,
 };
 use network_api::peer_score::{BlockBroadcastEntry, HandleState, LinearScore, Score};
 use network_api::{BroadcastProtocolFilter, NetworkActor, PeerMessageHandler};
 use network_p2p::{Event, NetworkWorker};
 use rand::prelude::SliceRandom;
-use starcoin_config::NodeConfig;
+use starcoin_config::{NodeConfig, RealTimeService, TimeService};
 use starcoin_crypto::HashValue;
 use starcoin_network_rpc::NetworkRpcService;
 use starcoin_service_registry::{
@@ -39,8 +42,17 @@ pub struct NetworkActorService {
     inner: Inner,
 
     network_worker_handle: Option<AbortHandle>,
+    /// The seeds resolved from the dns seeds by the last resolution.
+    discovered_seeds: Vec<DiscoveredSeed>,
 }
 
+/// Resolve the dns seeds in a thread, as the dns lookups are blocking.
+#[derive(Clone, Debug)]
+struct ResolveDnsSeeds;
+
+#[derive(Clone, Debug)]
+struct DnsSeedsResolved(Vec<DiscoveredSeed>);
+
 impl NetworkActor for NetworkActorService {}
 
 impl NetworkActorService {
@@ -66,6 +78,7 @@ impl NetworkActorService {
             worker: Some(worker),
             inner,
             network_worker_handle: None,
+            discovered_seeds: vec![],
         })
     }
 
@@ -93,6 +106,12 @@ impl ActorService for NetworkActorService {
                 Ok(Ok(_)) => {}
             }
         }));
+        if !self.inner.config.network.dns_seeds().is_empty() {
+            ctx.notify(ResolveDnsSeeds);
+            ctx.run_interval(self.inner.config.network.dns_seeds_interval(), |ctx| {
+                ctx.notify(ResolveDnsSeeds)
+            });
+        }
         Ok(())
     }
 
@@ -112,6 +131,54 @@ impl EventHandler<Self, SyncStatusChangeEvent> for NetworkActorService {
     }
 }
 
+impl EventHandler<Self, ResolveDnsSeeds> for NetworkActorService {
+    fn handle_event(&mut self, _msg: ResolveDnsSeeds, ctx: &mut ServiceContext<Self>) {
+        let dns_seeds = self.inner.config.network.dns_seeds();
+        let configured_seeds = self.inner.config.network.seeds();
+        let self_ref = ctx.self_ref();
+        // the failures are only logged, they should not block the startup.
+        std::thread::spawn(move || {
+            let discovered = match DnsSeedResolver::from_system_conf() {
+                Ok(resolver) => resolve_dns_seeds(&resolver, &dns_seeds, &configured_seeds),
+                Err(e) => {
+                    warn!("Create dns resolver failed: {:?}", e);
+                    return;
+                }
+            };
+            if let Err(e) = self_ref.notify(DnsSeedsResolved(discovered)) {
+                debug!("Notify resolved dns seeds failed: {:?}", e);
+            }
+        });
+    }
+}
+
+impl EventHandler<Self, DnsSeedsResolved> for NetworkActorService {
+    fn handle_event(&mut self, msg: DnsSeedsResolved, _ctx: &mut ServiceContext<Self>) {
+        let self_peer_id = self.inner.config.network.self_peer_id();
+        let discovered = msg
+            .0
+            .into_iter()
+            .filter(|discovered| discovered.seed.peer_id != *self_peer_id.origin())
+            .collect::<Vec<_>>();
+        for discovered in discovered.iter() {
+            if !self
+                .discovered_seeds
+                .iter()
+                .any(|exist| exist.seed == discovered.seed)
+            {
+                info!(
+                    "Discovered seed {} from dns seed {}",
+                    discovered.seed, discovered.dns_seed
+                );
+                self.inner
+                    .network_service
+                    .add_known_address(discovered.seed.peer_id, discovered.seed.multiaddr.clone());
+            }
+        }
+        self.discovered_seeds = discovered;
+    }
+}
+
 impl EventHandler<Self, Event> for NetworkActorService {
     fn handle_event(&mut self, event: Event, ctx: &mut ServiceContext<NetworkActorService>) {
         match event {
@@ -125,6 +192,11 @@ impl EventHandler<Self, Event> for NetworkActorService {
                 notif_protocols,
                 rpc_protocols,
             } => {
+                if self.inner.is_banned(&remote.clone().into()) {
+                    info!("Reject banned peer {:?}, protocol: {}", remote, protocol);
+                    self.inner.network_service.disconnect_peer(remote, protocol);
+                    return;
+                }
                 //TODO Refactor PeerEvent for handle protocol and substream.
                 // Currently, every notification stream open will trigger a PeerEvent, so it will trigger repeat event.
                 debug!(
@@ -267,6 +339,62 @@ impl ServiceHandler<Self, GetSelfPeer> for NetworkActorService {
     }
 }
 
+impl ServiceHandler<Self, AddPeer> for NetworkActorService {
+    fn handle(
+        &mut self,
+        msg: AddPeer,
+        _ctx: &mut ServiceContext<NetworkActorService>,
+    ) -> <AddPeer as ServiceRequest>::Response {
+        self.inner
+            .network_service
+            .add_reserved_peer(msg.peer)
+            .map_err(|e| format_err!("{:?}", e))
+    }
+}
+
+impl ServiceHandler<Self, RemovePeer> for NetworkActorService {
+    fn handle(
+        &mut self,
+        msg: RemovePeer,
+        _ctx: &mut ServiceContext<NetworkActorService>,
+    ) -> <RemovePeer as ServiceRequest>::Response {
+        self.inner.remove_peer(msg.peer_id)
+    }
+}
+
+impl ServiceHandler<Self, BanPeer> for NetworkActorService {
+    fn handle(
+        &mut self,
+        msg: BanPeer,
+        _ctx: &mut ServiceContext<NetworkActorService>,
+    ) -> <BanPeer as ServiceRequest>::Response {
+        self.inner.ban_peer(
+            msg.peer_id,
+            msg.duration_secs.unwrap_or(DEFAULT_BAN_DURATION_SECS),
+        )
+    }
+}
+
+impl ServiceHandler<Self, GetDiscoveredSeeds> for NetworkActorService {
+    fn handle(
+        &mut self,
+        _msg: GetDiscoveredSeeds,
+        _ctx: &mut ServiceContext<NetworkActorService>,
+    ) -> <GetDiscoveredSeeds as ServiceRequest>::Response {
+        self.discovered_seeds.clone()
+    }
+}
+
+impl ServiceHandler<Self, GetBannedPeers> for NetworkActorService {
+    fn handle(
+        &mut self,
+        _msg: GetBannedPeers,
+        _ctx: &mut ServiceContext<NetworkActorService>,
+    ) -> <GetBannedPeers as ServiceRequest>::Response {
+        self.inner.banned_peers()
+    }
+}
+
 // max peers is 100(in: 25 + out:75), so blocks lru + txn lru max memory usage about is:
 // (100 +1 ) * ( LRU_CACHE_SIZE * 32) *2 = 64M
 const LRU_CACHE_SIZE: usize = 10240;
@@ -293,11 +421,17 @@ impl Peer {
     }
 }
 
+/// The ban expiry always uses the system clock, the time service of the test or dev net is a mock clock.
+fn ban_clock() -> RealTimeService {
+    RealTimeService::new()
+}
+
 pub(crate) struct Inner {
     config: Arc<NodeConfig>,
     network_service: Arc<network_p2p::NetworkService>,
     self_peer: Peer,
     peers: HashMap<PeerId, Peer>,
+    banned_peers: HashMap<PeerId, BannedPeer>,
     peer_message_handler: Arc<dyn PeerMessageHandler>,
     metrics: Option<NetworkMetrics>,
     score_handler: Arc<dyn Score<BlockBroadcastEntry> + 'static>,
@@ -335,6 +469,7 @@ impl Inner {
             network_service,
             self_peer: Peer::new(self_info),
             peers: HashMap::new(),
+            banned_peers: HashMap::new(),
             peer_message_handler: Arc::new(peer_message_handler),
             metrics,
             score_handler: Arc::new(LinearScore::new(10)),
@@ -503,6 +638,53 @@ impl Inner {
         self.peers.remove(&peer_id);
     }
 
+    pub(crate) fn remove_peer(&mut self, peer_id: PeerId) {
+        self.network_service
+            .remove_reserved_peer(peer_id.clone().into());
+        self.disconnect_peer(&peer_id);
+    }
+
+    pub(crate) fn ban_peer(&mut self, peer_id: PeerId, duration_secs: u64) {
+        let banned_until = ban_clock().now_secs().saturating_add(duration_secs);
+        info!("Ban peer {:?} until {}", peer_id, banned_until);
+        let peer_info = self.peers.get(&peer_id).map(|peer| peer.peer_info.clone());
+        self.remove_peer(peer_id.clone());
+        self.banned_peers.insert(
+            peer_id.clone(),
+            BannedPeer {
+                peer_id,
+                peer_info,
+                banned_until,
+            },
+        );
+    }
+
+    pub(crate) fn is_banned(&mut self, peer_id: &PeerId) -> bool {
+        self.remove_expired_bans();
+        self.banned_peers.contains_key(peer_id)
+    }
+
+    pub(crate) fn banned_peers(&mut self) -> Vec<BannedPeer> {
+        self.remove_expired_bans();
+        self.banned_peers.values().cloned().collect()
+    }
+
+    fn remove_expired_bans(&mut self) {
+        let now = ban_clock().now_secs();
+        self.banned_peers
+            .retain(|_, banned_peer| banned_peer.banned_until > now);
+    }
+
+    /// Close all notification substreams of the peer, the peer will be removed after the streams closed.
+    fn disconnect_peer(&self, peer_id: &PeerId) {
+        if let Some(peer) = self.peers.get(peer_id) {
+            for protocol in &peer.peer_info.notif_protocols {
+                self.network_service
+                    .disconnect_peer(peer_id.clone().into(), protocol.clone());
+            }
+        }
+    }
+
     pub(crate) fn send_peer_message(&mut self, peer_id: PeerId, notification: NotificationMessage) {
         let (protocol_name, data) = notification
             .encode_notification()
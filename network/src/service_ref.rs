@@ -9,7 +9,10 @@ use futures::channel::oneshot::Receiver;
 use futures::future::BoxFuture;
 use futures::FutureExt;
 use log::warn;
-use network_api::messages::NotificationMessage;
+use network_api::messages::{
+    AddPeer, BanPeer, BannedPeer, DiscoveredSeed, GetBannedPeers, GetDiscoveredSeeds,
+    NotificationMessage, RemovePeer,
+};
 use network_api::{NetworkService, PeerProvider, ReputationChange, SupportedRpcProtocol};
 use network_p2p_types::network_state::NetworkState;
 use network_p2p_types::{IfDisconnected, Multiaddr, RequestFailure};
@@ -122,10 +125,29 @@ impl NetworkServiceRef {
             service_ref,
         }
     }
-    pub fn add_peer(&self, peer: String) -> Result<()> {
-        self.network_service
-            .add_reserved_peer(peer)
-            .map_err(|e| format_err!("{:?}", e))
+    pub async fn add_peer(&self, peer: String) -> Result<()> {
+        self.service_ref.send(AddPeer { peer }).await?
+    }
+
+    pub async fn remove_peer(&self, peer_id: PeerId) -> Result<()> {
+        self.service_ref.send(RemovePeer { peer_id }).await
+    }
+
+    pub async fn ban_peer(&self, peer_id: PeerId, duration_secs: Option<u64>) -> Result<()> {
+        self.service_ref
+            .send(BanPeer {
+                peer_id,
+                duration_secs,
+            })
+            .await
+    }
+
+    pub async fn banned_peers(&self) -> Result<Vec<BannedPeer>> {
+        self.service_ref.send(GetBannedPeers).await
+    }
+
+    pub async fn discovered_seeds(&self) -> Result<Vec<DiscoveredSeed>> {
+        self.service_ref.send(GetDiscoveredSeeds).await
     }
 
     pub async fn network_state(&self) -> Result<NetworkState> {
@@ -8,9 +8,9 @@ use network_api::messages::{
     Announcement, AnnouncementType, CompactBlockMessage, NotificationMessage, PeerMessage,
     TransactionsMessage, ANNOUNCEMENT_PROTOCOL_NAME, TXN_PROTOCOL_NAME,
 };
-use network_api::{Multiaddr, NetworkService};
+use network_api::{Multiaddr, NetworkService, PeerProvider};
 use network_p2p_types::MultiaddrWithPeerId;
-use starcoin_config::{BuiltinNetworkID, NetworkConfig, NodeConfig};
+use starcoin_config::{BuiltinNetworkID, NetworkConfig, NodeConfig, RealTimeService, TimeService};
 use starcoin_crypto::hash::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_network::build_network_worker;
@@ -370,3 +370,34 @@ async fn test_filter_protocol() {
         msg_3.notification.protocol_name()
     );
 }
+
+#[stest::test]
+async fn test_ban_peer() {
+    let (network1, network2) = test_helper::build_network_pair().await.unwrap();
+    let peer_id2 = network2.peer_id();
+    assert!(network1
+        .service_ref
+        .get_peer(peer_id2.clone())
+        .await
+        .unwrap()
+        .is_some());
+
+    network1
+        .service_ref
+        .ban_peer(peer_id2.clone(), Some(60))
+        .await
+        .unwrap();
+    Delay::new(Duration::from_secs(3)).await;
+
+    assert!(network1
+        .service_ref
+        .get_peer(peer_id2.clone())
+        .await
+        .unwrap()
+        .is_none());
+    let banned_peers = network1.service_ref.banned_peers().await.unwrap();
+    assert_eq!(banned_peers.len(), 1);
+    assert_eq!(banned_peers[0].peer_id, peer_id2);
+    assert!(banned_peers[0].peer_info.is_some());
+    assert!(banned_peers[0].banned_until > RealTimeService::new().now_secs());
+}
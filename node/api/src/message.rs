@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
+use starcoin_config::{ConfigReloadResult, NodeConfig};
 use starcoin_service_registry::{ServiceInfo, ServiceRequest, ServiceStatus};
+use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub enum NodeRequest {
@@ -13,6 +16,10 @@ pub enum NodeRequest {
     StopPacemaker,
     StartPacemaker,
     ShutdownSystem,
+    /// Stop accepting new txns, and shutdown the system after the grace period.
+    Shutdown(Duration),
+    GetConfig,
+    ReloadConfig,
 }
 
 #[derive(Debug)]
@@ -20,6 +27,8 @@ pub enum NodeResponse {
     Services(Vec<ServiceInfo>),
     Result(Result<()>),
     ServiceStatus(ServiceStatus),
+    Config(Arc<NodeConfig>),
+    ConfigReloadResult(ConfigReloadResult),
 }
 
 impl ServiceRequest for NodeRequest {
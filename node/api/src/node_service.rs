@@ -3,9 +3,12 @@
 
 use crate::message::{NodeRequest, NodeResponse};
 use anyhow::Result;
+use starcoin_config::{ConfigReloadResult, NodeConfig};
 use starcoin_service_registry::{
     ActorService, ServiceHandler, ServiceInfo, ServiceRef, ServiceStatus,
 };
+use std::sync::Arc;
+use std::time::Duration;
 
 #[async_trait::async_trait]
 pub trait NodeAsyncService:
@@ -24,6 +27,14 @@ pub trait NodeAsyncService:
     async fn stop_pacemaker(&self) -> Result<()>;
 
     async fn shutdown_system(&self) -> Result<()>;
+
+    /// Stop accepting new txns, persist the txpool and shutdown the system after the `grace_period`.
+    async fn shutdown(&self, grace_period: Duration) -> Result<()>;
+
+    /// Get the effective node config, it may be changed by `reload_config`.
+    async fn get_config(&self) -> Result<Arc<NodeConfig>>;
+
+    async fn reload_config(&self) -> Result<ConfigReloadResult>;
 }
 
 #[async_trait::async_trait]
@@ -91,4 +102,31 @@ where
         self.try_send(NodeRequest::ShutdownSystem)?;
         Ok(())
     }
+
+    async fn shutdown(&self, grace_period: Duration) -> Result<()> {
+        let response = self.send(NodeRequest::Shutdown(grace_period)).await??;
+        if let NodeResponse::Result(result) = response {
+            result
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
+    async fn get_config(&self) -> Result<Arc<NodeConfig>> {
+        let response = self.send(NodeRequest::GetConfig).await??;
+        if let NodeResponse::Config(config) = response {
+            Ok(config)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
+    async fn reload_config(&self) -> Result<ConfigReloadResult> {
+        let response = self.send(NodeRequest::ReloadConfig).await??;
+        if let NodeResponse::ConfigReloadResult(result) = response {
+            Ok(result)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
 }
@@ -99,11 +99,11 @@ impl RpcFutureBlockParameterResolver {
                     difficulty,
                 })
             }
-            _ => match client.chain_get_block_by_number(block_number)? {
+            _ => match client.chain_get_block_by_number(block_number, None)? {
                 Some(block) => Ok(GenesisBlockParameter {
                     parent_hash: block.header.block_hash,
                     timestamp: block.header.timestamp.0,
-                    difficulty: block.header.difficulty,
+                    difficulty: block.header.difficulty.0,
                 }),
                 None => {
                     bail!("Can not get block by number:{}, retry.", block_number)
@@ -5,6 +5,7 @@ use crate::genesis_parameter_resolve::RpcFutureBlockParameterResolver;
 use crate::node::NodeService;
 use anyhow::{bail, format_err, Result};
 use futures::executor::block_on;
+use futures::{future::FutureExt, pin_mut, select};
 use futures_timer::Delay;
 use starcoin_chain_service::{ChainAsyncService, ChainReaderService};
 use starcoin_config::{BaseConfig, NodeConfig, StarcoinOpt};
@@ -33,6 +34,7 @@ pub mod network_service_factory;
 pub mod node;
 pub mod peer_message_handler;
 pub mod rpc_service_factory;
+pub mod watchdog;
 
 pub struct NodeHandle {
     runtime: Runtime,
@@ -87,18 +89,38 @@ impl NodeHandle {
         }
     }
 
+    /// Wait for the SIGINT or SIGTERM signal then stop the node, or the node is stopped by command, such as `node_manager.shutdown` rpc.
     pub fn join(mut self) -> Result<()> {
-        self.runtime.block_on(async {
-            //TODO also wait actor system stop signal, support stop system by command.
-            platform::wait_signal().await;
+        let join_handle = &self.join_handle;
+        let stopped_by_command = self.runtime.block_on(async {
+            let signal_fut = platform::wait_signal().fuse();
+            let exit_fut = async {
+                while !join_handle.is_finished() {
+                    tokio::time::delay_for(Duration::from_millis(500)).await;
+                }
+            }
+            .fuse();
+            pin_mut!(signal_fut, exit_fut);
+            select! {
+                _ = signal_fut => false,
+                _ = exit_fut => true,
+            }
         });
-        self.stop()
+        if stopped_by_command {
+            self.wait_exit()
+        } else {
+            self.stop()
+        }
     }
 
     pub fn stop(self) -> Result<()> {
         self.node_service
             .try_send(NodeRequest::ShutdownSystem)
             .map_err(|_| format_err!("Stop message send fail."))?;
+        self.wait_exit()
+    }
+
+    fn wait_exit(self) -> Result<()> {
         self.join_handle
             .join(Duration::from_millis(5000))
             .map_err(|_| format_err!("Waiting thread exist timeout."))??;
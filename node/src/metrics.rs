@@ -2,12 +2,106 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
+use once_cell::sync::Lazy;
 use starcoin_config::NodeConfig;
+use starcoin_logger::prelude::*;
+use starcoin_metrics::{
+    register_int_counter_vec, register_uint_gauge, register_uint_gauge_vec, IntCounterVec,
+    UIntGauge, UIntGaugeVec,
+};
 use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
+use starcoin_storage::Storage;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
+/// How often the system metrics are refreshed.
+pub const SYSTEM_METRICS_INTERVAL: Duration = Duration::from_secs(10);
+
+static STORAGE_CF_SIZE: Lazy<UIntGaugeVec> = Lazy::new(|| {
+    register_uint_gauge_vec!(
+        "starcoin_storage_cf_size_bytes",
+        "The sst files and mem tables size of the storage column family",
+        &["cf_name"]
+    )
+    .unwrap()
+});
+
+static DISK_TOTAL: Lazy<UIntGauge> = Lazy::new(|| {
+    register_uint_gauge!(
+        "starcoin_disk_total_bytes",
+        "The total size of the volume which the data dir is on"
+    )
+    .unwrap()
+});
+
+static DISK_FREE: Lazy<UIntGauge> = Lazy::new(|| {
+    register_uint_gauge!(
+        "starcoin_disk_free_bytes",
+        "The available size of the volume which the data dir is on"
+    )
+    .unwrap()
+});
+
+static OPEN_FDS: Lazy<UIntGauge> = Lazy::new(|| {
+    register_uint_gauge!(
+        "starcoin_process_open_fds",
+        "The count of file descriptors opened by the node"
+    )
+    .unwrap()
+});
+
+static UPTIME: Lazy<UIntGauge> = Lazy::new(|| {
+    register_uint_gauge!(
+        "starcoin_process_uptime_seconds",
+        "How long the node has been running"
+    )
+    .unwrap()
+});
+
+/// Refresh the storage, disk and process gauges, the process resident memory is exported by the process collector.
+pub(crate) static WATCHDOG_ALERTS_COUNTER: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "starcoin_watchdog_alerts",
+        "Counters of how many watchdog alerts, by the alert kind",
+        &["kind"]
+    )
+    .unwrap()
+});
+
+pub(crate) static WATCHDOG_RESTARTS_COUNTER: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "starcoin_watchdog_restarts",
+        "Counters of how many times a service is restarted by the watchdog",
+        &["service"]
+    )
+    .unwrap()
+});
+
+pub fn update_system_metrics(config: &NodeConfig, storage: &Storage) {
+    match storage.db_size_report() {
+        Ok(Some(report)) => {
+            for (cf_name, size) in report.column_families {
+                STORAGE_CF_SIZE
+                    .with_label_values(&[cf_name.as_str()])
+                    .set(size.sst_files_size + size.mem_tables_size);
+            }
+        }
+        Ok(None) => {}
+        Err(e) => warn!("Get db size report error: {:?}", e),
+    }
+    if let Ok(disk_usage) = starcoin_system::get_disk_usage(config.data_dir()) {
+        DISK_TOTAL.set(disk_usage.total);
+        DISK_FREE.set(disk_usage.available);
+    }
+    if let Ok(open_fds) = starcoin_system::get_open_fds() {
+        OPEN_FDS.set(open_fds);
+    }
+    if let Ok(uptime) = starcoin_system::get_process_uptime() {
+        UPTIME.set(uptime.as_secs());
+    }
+}
+
 #[derive(Clone)]
 pub struct MetricsActorService {
     push_url: String,
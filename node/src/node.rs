@@ -1,10 +1,11 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::metrics::MetricsActorService;
+use crate::metrics::{update_system_metrics, MetricsActorService, SYSTEM_METRICS_INTERVAL};
 use crate::network_service_factory::NetworkServiceFactory;
 use crate::peer_message_handler::NodePeerMessageHandler;
 use crate::rpc_service_factory::RpcServiceFactory;
+use crate::watchdog::{WatchdogService, WatchdogServiceFactory};
 use crate::NodeHandle;
 use actix::prelude::*;
 use anyhow::Result;
@@ -13,9 +14,10 @@ use futures::executor::block_on;
 use futures_timer::Delay;
 use starcoin_account_service::{AccountEventService, AccountService, AccountStorage};
 use starcoin_block_relayer::BlockRelayer;
+use starcoin_chain::BlockChain;
 use starcoin_chain_notify::ChainNotifyHandlerService;
 use starcoin_chain_service::ChainReaderService;
-use starcoin_config::NodeConfig;
+use starcoin_config::{ConfigReloadResult, NodeConfig, NodeConfigChangedEvent};
 use starcoin_genesis::{Genesis, GenesisError};
 use starcoin_logger::prelude::*;
 use starcoin_logger::LoggerHandle;
@@ -26,6 +28,7 @@ use starcoin_network::NetworkActorService;
 use starcoin_network_rpc::NetworkRpcService;
 use starcoin_node_api::errors::NodeStartError;
 use starcoin_node_api::message::{NodeRequest, NodeResponse};
+use starcoin_rpc_server::health::{HealthService, HealthServiceFactory, WatchdogMonitor};
 use starcoin_rpc_server::module::{PubSubService, PubSubServiceFactory};
 use starcoin_rpc_server::service::RpcService;
 use starcoin_service_registry::bus::{Bus, BusService};
@@ -38,37 +41,56 @@ use starcoin_storage::cache_storage::CacheStorage;
 use starcoin_storage::db_storage::DBStorage;
 use starcoin_storage::errors::StorageInitError;
 use starcoin_storage::storage::StorageInstance;
-use starcoin_storage::Storage;
+use starcoin_storage::{BlockStore, Storage, TokenHolderStore};
 use starcoin_stratum::service::{StratumService, StratumServiceFactory};
 use starcoin_stratum::stratum::{Stratum, StratumFactory};
 use starcoin_sync::announcement::AnnouncementService;
 use starcoin_sync::block_connector::BlockConnectorService;
 use starcoin_sync::sync::SyncService;
 use starcoin_sync::txn_sync::TxnSyncService;
-use starcoin_txpool::TxPoolActorService;
+use starcoin_txpool::{TxPoolActorService, TxPoolService};
 use starcoin_types::system_events::SystemStarted;
+use starcoin_webhook::WebhookService;
 use std::sync::Arc;
 use std::time::Duration;
 
 pub struct NodeService {
     registry: ServiceRef<RegistryService>,
+    config: Arc<NodeConfig>,
+    logger_handle: Arc<LoggerHandle>,
+    storage: Arc<Storage>,
 }
 
 impl ServiceFactory<Self> for NodeService {
     fn create(ctx: &mut ServiceContext<NodeService>) -> Result<NodeService> {
         Ok(Self {
             registry: ctx.registry_ref().clone(),
+            config: ctx.get_shared::<Arc<NodeConfig>>()?,
+            logger_handle: ctx.get_shared::<Arc<LoggerHandle>>()?,
+            storage: ctx.get_shared::<Arc<Storage>>()?,
         })
     }
 }
 
-impl ActorService for NodeService {}
+impl ActorService for NodeService {
+    fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        if !self.config.metrics.disable_metrics() {
+            let config = self.config.clone();
+            let storage = self.storage.clone();
+            update_system_metrics(config.as_ref(), storage.as_ref());
+            ctx.run_interval(SYSTEM_METRICS_INTERVAL, move |_ctx| {
+                update_system_metrics(config.as_ref(), storage.as_ref())
+            });
+        }
+        Ok(())
+    }
+}
 
 impl ServiceHandler<Self, NodeRequest> for NodeService {
     fn handle(
         &mut self,
         msg: NodeRequest,
-        _ctx: &mut ServiceContext<NodeService>,
+        ctx: &mut ServiceContext<NodeService>,
     ) -> Result<NodeResponse> {
         Ok(match msg {
             NodeRequest::ListService => NodeResponse::Services(self.registry.list_service_sync()?),
@@ -98,14 +120,37 @@ impl ServiceHandler<Self, NodeRequest> for NodeService {
             }
             NodeRequest::ShutdownSystem => {
                 info!("Receive StopSystem request, try to stop system.");
+                self.persist_txpool(ctx);
                 if let Err(e) = self.registry.shutdown_system_sync() {
                     error!("Shutdown registry error: {}", e);
                 };
+                if let Err(e) = self.storage.flush() {
+                    error!("Flush storage error: {:?}", e);
+                }
                 //wait a seconds for registry shutdown, then stop System.
                 std::thread::sleep(Duration::from_millis(2000));
                 System::current().stop();
                 NodeResponse::Result(Ok(()))
             }
+            NodeRequest::Shutdown(grace_period) => {
+                info!(
+                    "Receive Shutdown request, try to stop system after {:?}.",
+                    grace_period
+                );
+                if let Ok(txpool) = ctx.get_shared::<TxPoolService>() {
+                    txpool.close();
+                }
+                ctx.run_later(grace_period, |ctx| {
+                    if ctx
+                        .self_ref()
+                        .try_send(NodeRequest::ShutdownSystem)
+                        .is_err()
+                    {
+                        error!("Send ShutdownSystem request error.");
+                    }
+                });
+                NodeResponse::Result(Ok(()))
+            }
             NodeRequest::StopPacemaker => NodeResponse::Result(
                 self.registry
                     .stop_service_sync(GenerateBlockEventPacemaker::service_name()),
@@ -114,11 +159,61 @@ impl ServiceHandler<Self, NodeRequest> for NodeService {
                 self.registry
                     .start_service_sync(GenerateBlockEventPacemaker::service_name()),
             ),
+            NodeRequest::GetConfig => NodeResponse::Config(self.config.clone()),
+            NodeRequest::ReloadConfig => {
+                info!("Receive ReloadConfig request, try to reload config.");
+                NodeResponse::ConfigReloadResult(self.reload_config(ctx)?)
+            }
         })
     }
 }
 
 impl NodeService {
+    /// Save the txpool txns, so they are imported again on the next startup.
+    fn persist_txpool(&self, ctx: &mut ServiceContext<NodeService>) {
+        match ctx.get_shared::<TxPoolService>() {
+            Ok(txpool) => {
+                txpool.close();
+                match txpool.persist_txns(&txpool.persisted_txns_path()) {
+                    Ok(count) => info!("Persist {} txns of txpool.", count),
+                    Err(e) => error!("Persist txpool txns error: {:?}", e),
+                }
+            }
+            Err(e) => warn!("Get txpool service error: {:?}", e),
+        }
+    }
+
+    fn reload_config(
+        &mut self,
+        ctx: &mut ServiceContext<NodeService>,
+    ) -> Result<ConfigReloadResult> {
+        let (new_config, result) = self.config.reload()?;
+        for change in &result.ignored {
+            warn!(
+                "Config {} changed, but it requires restart to take effect.",
+                change.field
+            );
+        }
+        if !result.is_applied() {
+            return Ok(result);
+        }
+        let new_config = Arc::new(new_config);
+        if let Some(level) = new_config.logger.level() {
+            self.logger_handle.update_level(level);
+        }
+        self.config = new_config.clone();
+        if let Some(miner_client_config) = new_config.miner.miner_client_config() {
+            ctx.put_shared(miner_client_config)?;
+        }
+        // services created later, such as a restarted service, will use the new config.
+        ctx.put_shared(new_config.clone())?;
+        ctx.broadcast(NodeConfigChangedEvent {
+            config: new_config,
+            changes: result.applied.clone(),
+        });
+        Ok(result)
+    }
+
     pub fn launch(
         config: Arc<NodeConfig>,
         logger_handle: Arc<LoggerHandle>,
@@ -132,6 +227,9 @@ impl NodeService {
                 config.logger.max_backup(),
             );
         }
+        if let Some(level) = config.logger.level() {
+            logger_handle.update_level(level);
+        }
         if config.logger.disable_stderr() {
             logger_handle.disable_stderr();
         } else {
@@ -184,13 +282,37 @@ impl NodeService {
         registry.put_shared(logger_handle).await?;
 
         let bus = registry.service_ref::<BusService>().await?;
-        let storage = Arc::new(Storage::new(StorageInstance::new_cache_and_db_instance(
-            CacheStorage::new_with_capacity(config.storage.cache_size()),
-            DBStorage::new(config.storage.dir(), config.storage.rocksdb_config())?,
-        ))?);
+        let storage = Arc::new(
+            Storage::new(StorageInstance::new_cache_and_db_instance(
+                CacheStorage::new_with_capacity(config.storage.cache_size()),
+                DBStorage::new(config.storage.dir(), config.storage.rocksdb_config())?,
+            ))?
+            .with_token_holder_index(config.storage.token_holder_index()),
+        );
         registry.put_shared(storage.clone()).await?;
         let (chain_info, genesis) =
             Genesis::init_and_check_storage(config.net(), storage.clone(), config.data_dir())?;
+        storage.upgrade_uncle_index()?;
+        storage.upgrade_transaction_position_index()?;
+        if storage.is_token_holder_index_enabled()
+            && storage.get_token_holder_index_head()?.is_none()
+        {
+            // a new chain is indexed from the genesis, an exist chain needs the manual rebuild.
+            if chain_info.head().number() == 0 {
+                let count = BlockChain::new(
+                    config.net().time_service(),
+                    chain_info.head().id(),
+                    storage.clone(),
+                )?
+                .rebuild_token_holder_index()?;
+                info!(
+                    "Build token holder index at genesis, {} holders indexed.",
+                    count
+                );
+            } else {
+                warn!("The token holder index is not built, run `node index rebuild-token-holders` to build it.");
+            }
+        }
 
         info!("Start node with chain info: {}", chain_info);
 
@@ -267,6 +389,12 @@ impl NodeService {
         if config.metrics.push_config.is_config() {
             registry.register::<MetricsActorService>().await?;
         }
+
+        if config.webhook.enable() {
+            registry.register::<WebhookService>().await?;
+        } else {
+            info!("Config.webhook.enable is false, No webhook service.");
+        }
         // wait for service init.
         Delay::new(Duration::from_millis(1000)).await;
 
@@ -275,6 +403,17 @@ impl NodeService {
         registry
             .register_by_factory::<PubSubService, PubSubServiceFactory>()
             .await?;
+        registry
+            .register_by_factory::<HealthService, HealthServiceFactory>()
+            .await?;
+        registry.put_shared(WatchdogMonitor::default()).await?;
+        if config.watchdog.enable() {
+            registry
+                .register_by_factory::<WatchdogService, WatchdogServiceFactory>()
+                .await?;
+        } else {
+            info!("Config.watchdog.disable is true, No watchdog service.");
+        }
         registry
             .register_by_factory::<RpcService, RpcServiceFactory>()
             .await?;
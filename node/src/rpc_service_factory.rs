@@ -11,6 +11,7 @@ use starcoin_genesis::Genesis;
 use starcoin_logger::LoggerHandle;
 use starcoin_miner::MinerService;
 use starcoin_network::NetworkServiceRef;
+use starcoin_rpc_server::health::{HealthMonitor, WatchdogMonitor};
 use starcoin_rpc_server::module::{
     AccountRpcImpl, ChainRpcImpl, ContractRpcImpl, DebugRpcImpl, MinerRpcImpl,
     NetworkManagerRpcImpl, NodeManagerRpcImpl, NodeRpcImpl, PubSubImpl, PubSubService,
@@ -21,7 +22,8 @@ use starcoin_service_registry::{ServiceContext, ServiceFactory};
 use starcoin_state_service::ChainStateService;
 use starcoin_storage::Storage;
 use starcoin_sync::sync::SyncService;
-use starcoin_txpool::TxPoolService;
+use starcoin_txpool::{TxPoolActorService, TxPoolService};
+use starcoin_webhook::WebhookService;
 use std::sync::Arc;
 
 pub struct RpcServiceFactory;
@@ -34,25 +36,57 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
         let storage = ctx.get_shared::<Arc<Storage>>()?;
         let log_handler = ctx.get_shared::<Arc<LoggerHandle>>()?;
         let network_service = ctx.get_shared::<NetworkServiceRef>()?;
-        let node_api = NodeRpcImpl::new(config.clone(), Some(network_service.clone()));
-        let node_manager_api = ctx
-            .service_ref_opt::<NodeService>()?
-            .map(|service_ref| NodeManagerRpcImpl::new(service_ref.clone()));
+        let health_monitor = ctx.get_shared::<HealthMonitor>()?;
+        let watchdog_monitor = ctx.get_shared::<WatchdogMonitor>()?;
+        let node_api = NodeRpcImpl::new(
+            config.clone(),
+            Some(network_service.clone()),
+            Some(health_monitor.clone()),
+            Some(watchdog_monitor),
+        );
+        let webhook_service = ctx.service_ref_opt::<WebhookService>()?.cloned();
+        let node_manager_api = ctx.service_ref_opt::<NodeService>()?.map(|service_ref| {
+            NodeManagerRpcImpl::new(
+                config.clone(),
+                service_ref.clone(),
+                network_service.clone(),
+                storage.clone(),
+                webhook_service,
+            )
+        });
         let sync_manager_api = ctx
             .service_ref_opt::<SyncService>()?
             .map(|service_ref| SyncManagerRpcImpl::new(service_ref.clone()));
-        let network_manager_api = NetworkManagerRpcImpl::new(network_service);
+        let network_manager_api = NetworkManagerRpcImpl::new(network_service.clone());
+        let txpool_service = ctx.get_shared::<TxPoolService>()?;
+        let sync_service = ctx.service_ref_opt::<SyncService>()?.cloned();
         let chain_api = ctx
             .service_ref_opt::<ChainReaderService>()?
             .map(|service_ref| {
-                ChainRpcImpl::new(config.clone(), genesis.block().id(), service_ref.clone())
+                ChainRpcImpl::new(
+                    config.clone(),
+                    genesis.block().id(),
+                    service_ref.clone(),
+                    PlaygroudService::new(storage.clone()),
+                    storage.clone(),
+                    Some(txpool_service.clone()),
+                    sync_service,
+                    Some(network_service),
+                )
             });
-        let txpool_service = ctx.get_shared::<TxPoolService>()?;
-        let txpool_api = Some(TxPoolRpcImpl::new(txpool_service.clone()));
+        let txpool_api = Some(TxPoolRpcImpl::new(
+            config.clone(),
+            txpool_service.clone(),
+            ctx.service_ref_opt::<ChainStateService>()?.cloned(),
+            ctx.service_ref_opt::<ChainReaderService>()?.cloned(),
+            ctx.service_ref_opt::<TxPoolActorService>()?.cloned(),
+        ));
 
         let state_api = ctx
             .service_ref_opt::<ChainStateService>()?
-            .map(|service_ref| StateRpcImpl::new(service_ref.clone(), storage.clone()));
+            .map(|service_ref| {
+                StateRpcImpl::new(service_ref.clone(), storage.clone(), storage.clone())
+            });
         let chain_state_service = ctx.service_ref::<ChainStateService>()?.clone();
         let chain_service = ctx.service_ref::<ChainReaderService>()?.clone();
         let account_service = ctx.service_ref_opt::<AccountService>()?.cloned();
@@ -67,7 +101,12 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
         });
         let pubsub_service = ctx.service_ref::<PubSubService>()?.clone();
         let pubsub_api = Some(PubSubImpl::new(pubsub_service));
-        let debug_api = Some(DebugRpcImpl::new(config.clone(), log_handler));
+        let debug_api = Some(DebugRpcImpl::new(
+            config.clone(),
+            log_handler,
+            ctx.bus_ref().clone(),
+            ctx.service_ref_opt::<ChainReaderService>()?.cloned(),
+        ));
         let miner_api = ctx
             .service_ref_opt::<MinerService>()?
             .map(|service_ref| MinerRpcImpl::new(service_ref.clone()));
@@ -99,6 +138,7 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
             debug_api,
             miner_api,
             Some(contract_api),
-        ))
+        )
+        .with_health_monitor(health_monitor))
     }
 }
@@ -0,0 +1,538 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metrics::{WATCHDOG_ALERTS_COUNTER, WATCHDOG_RESTARTS_COUNTER};
+use anyhow::{format_err, Result};
+use futures::future::Either;
+use futures_timer::Delay;
+use starcoin_chain_service::{ChainAsyncService, ChainReaderService};
+use starcoin_config::{NodeConfig, WatchdogConfig};
+use starcoin_logger::prelude::*;
+use starcoin_rpc_api::node::{ServiceHealthView, WatchdogReport};
+use starcoin_rpc_server::health::WatchdogMonitor;
+use starcoin_service_registry::bus::BusService;
+use starcoin_service_registry::{
+    ActorService, EventHandler, RegistryAsyncService, RegistryService, ServiceContext,
+    ServiceFactory, ServiceLatency, ServiceRef, ServiceStatus,
+};
+use starcoin_storage::{BlockStore, Storage};
+use starcoin_txpool::TxPoolService;
+use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_types::block::BlockNumber;
+use starcoin_types::system_events::{NewHeadBlock, SyncStatusChangeEvent};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// The probe results of one watchdog check.
+#[derive(Clone, Debug)]
+pub struct WatchdogProbe {
+    pub now_millis: u64,
+    pub head_block_number: BlockNumber,
+    /// The head block timestamp in milliseconds.
+    pub head_block_timestamp: u64,
+    /// The block time target of current epoch in milliseconds.
+    pub block_time_target: u64,
+    /// False if the node is syncing, or no empty block is minted and the txpool is empty.
+    pub expect_new_block: bool,
+    /// None if the txpool status query timeout.
+    pub txpool_latency: Option<Duration>,
+    pub services: Vec<ServiceLatency>,
+}
+
+#[derive(Default)]
+struct ServiceState {
+    unhealthy_checks: u32,
+    restarts: u32,
+    last_restart: Option<Instant>,
+}
+
+/// Evaluate the probes by the thresholds in the config, keep the consecutive failures of every
+/// service and decide which stuck service should be restarted.
+pub struct WatchdogEvaluator {
+    config: WatchdogConfig,
+    /// The services which can not be restarted, such as the bus, restart it will lose all subscriptions.
+    non_restartable: Vec<String>,
+    states: HashMap<String, ServiceState>,
+}
+
+impl WatchdogEvaluator {
+    pub fn new(config: WatchdogConfig) -> Self {
+        Self {
+            config,
+            non_restartable: vec![
+                BusService::service_name().to_string(),
+                WatchdogService::service_name().to_string(),
+            ],
+            states: HashMap::new(),
+        }
+    }
+
+    pub fn evaluate(&mut self, probe: WatchdogProbe, now: Instant) -> WatchdogReport {
+        let mut alerts = vec![];
+        let head_block_age = probe.now_millis.saturating_sub(probe.head_block_timestamp);
+        let block_stalled = probe.expect_new_block
+            && probe.block_time_target > 0
+            && head_block_age
+                > probe
+                    .block_time_target
+                    .saturating_mul(self.config.block_stall_factor());
+        if block_stalled {
+            alerts.push(format!(
+                "Block production stalled, the head block {} is {}s old, the block time target is {}ms.",
+                probe.head_block_number,
+                head_block_age / 1000,
+                probe.block_time_target
+            ));
+        }
+        let txpool_max_latency = self.config.txpool_max_latency();
+        let txpool_slow = probe
+            .txpool_latency
+            .map(|latency| latency > txpool_max_latency)
+            .unwrap_or(true);
+        if txpool_slow {
+            alerts.push(format!(
+                "Txpool is slow, the status query latency {:?} exceeds {:?}.",
+                probe.txpool_latency, txpool_max_latency
+            ));
+        }
+
+        let restart_cooldown = self.config.restart_cooldown();
+        let mut restarted = vec![];
+        let mut services = vec![];
+        for latency in probe.services {
+            let healthy = !latency.is_stalled()
+                && latency.status != ServiceStatus::Shutdown
+                && latency.status != ServiceStatus::Unavailable;
+            let state = self.states.entry(latency.name.clone()).or_default();
+            if healthy {
+                state.unhealthy_checks = 0;
+            } else {
+                state.unhealthy_checks += 1;
+                alerts.push(format!(
+                    "Service {} is unhealthy for {} checks, status: {:?}.",
+                    latency.name, state.unhealthy_checks, latency.status
+                ));
+                let cooled_down = state
+                    .last_restart
+                    .map(|last_restart| {
+                        now.saturating_duration_since(last_restart) >= restart_cooldown
+                    })
+                    .unwrap_or(true);
+                // only the stalled service can be restarted, the shutdown service is removed from the registry.
+                if self.config.auto_restart()
+                    && latency.is_stalled()
+                    && state.unhealthy_checks >= self.config.failure_threshold()
+                    && cooled_down
+                    && !self.non_restartable.contains(&latency.name)
+                {
+                    state.unhealthy_checks = 0;
+                    state.restarts += 1;
+                    state.last_restart = Some(now);
+                    restarted.push(latency.name.clone());
+                }
+            }
+            services.push(ServiceHealthView {
+                name: latency.name,
+                status: latency.status,
+                latency_millis: latency.latency_millis,
+                healthy,
+                unhealthy_checks: state.unhealthy_checks,
+                restarts: state.restarts,
+            });
+        }
+
+        WatchdogReport {
+            timestamp: probe.now_millis,
+            head_block_number: probe.head_block_number.into(),
+            head_block_age_secs: head_block_age / 1000,
+            block_time_target: probe.block_time_target,
+            block_stalled,
+            txpool_latency_millis: probe
+                .txpool_latency
+                .map(|latency| latency.as_millis() as u64),
+            txpool_slow,
+            services,
+            restarted,
+            alerts,
+        }
+    }
+}
+
+/// Respawn the services in new actors, the stuck actors are abandoned, so the restart does not wait
+/// for the messages which are already in their mailboxes.
+pub async fn restart_services(registry: &ServiceRef<RegistryService>, services: &[String]) {
+    for service in services {
+        warn!("[watchdog] Restart the stuck service {}", service);
+        WATCHDOG_RESTARTS_COUNTER
+            .with_label_values(&[service.as_str()])
+            .inc();
+        if let Err(e) = registry.respawn_service(service.as_str()).await {
+            error!("[watchdog] Respawn service {} error: {:?}", service, e);
+        }
+    }
+}
+
+/// Await the future with a timeout, return None if timeout.
+async fn timeout<F>(dur: Duration, fut: F) -> Option<F::Output>
+where
+    F: Future + Unpin,
+{
+    match futures::future::select(fut, Delay::new(dur)).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right(_) => None,
+    }
+}
+
+/// Query the txpool status in a separate thread, for the txpool lock may be held by a stuck thread.
+/// Return the latency and the txn count, or None if timeout or the last probe is still pending.
+async fn probe_txpool(
+    txpool: TxPoolService,
+    pending: Arc<AtomicBool>,
+    dur: Duration,
+) -> Option<(Duration, usize)> {
+    if pending.swap(true, Ordering::SeqCst) {
+        return None;
+    }
+    let (sender, receiver) = futures::channel::oneshot::channel();
+    std::thread::spawn(move || {
+        let now = Instant::now();
+        let txn_count = txpool.status().txn_count;
+        pending.store(false, Ordering::SeqCst);
+        let _ = sender.send((now.elapsed(), txn_count));
+    });
+    timeout(dur, receiver).await.and_then(|result| result.ok())
+}
+
+#[derive(Clone, Debug)]
+struct WatchdogTick;
+
+#[derive(Clone, Debug)]
+struct WatchdogProbeResult {
+    services: Vec<ServiceLatency>,
+    txpool: Option<(Duration, usize)>,
+    block_time_target: Option<u64>,
+}
+
+/// Periodically check the block production, the txpool latency and the mailbox latency of every
+/// service, report the alerts, and restart the stuck services if `watchdog.auto_restart` is enabled.
+pub struct WatchdogService {
+    config: Arc<NodeConfig>,
+    monitor: WatchdogMonitor,
+    evaluator: WatchdogEvaluator,
+    txpool: TxPoolService,
+    chain: ServiceRef<ChainReaderService>,
+    txpool_probe_pending: Arc<AtomicBool>,
+    head_block_number: BlockNumber,
+    head_block_timestamp: u64,
+    block_time_target: u64,
+    syncing: bool,
+    checking: bool,
+}
+
+impl ActorService for WatchdogService {
+    fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.subscribe::<NewHeadBlock>();
+        ctx.subscribe::<SyncStatusChangeEvent>();
+        ctx.run_interval(self.config.watchdog.check_interval(), |ctx| {
+            ctx.notify(WatchdogTick)
+        });
+        Ok(())
+    }
+
+    fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.unsubscribe::<NewHeadBlock>();
+        ctx.unsubscribe::<SyncStatusChangeEvent>();
+        Ok(())
+    }
+}
+
+impl EventHandler<Self, NewHeadBlock> for WatchdogService {
+    fn handle_event(&mut self, msg: NewHeadBlock, _ctx: &mut ServiceContext<Self>) {
+        let header = msg.0.block().header();
+        self.head_block_number = header.number();
+        self.head_block_timestamp = header.timestamp();
+    }
+}
+
+impl EventHandler<Self, SyncStatusChangeEvent> for WatchdogService {
+    fn handle_event(&mut self, msg: SyncStatusChangeEvent, _ctx: &mut ServiceContext<Self>) {
+        self.syncing = msg.0.is_syncing();
+    }
+}
+
+impl EventHandler<Self, WatchdogTick> for WatchdogService {
+    fn handle_event(&mut self, _msg: WatchdogTick, ctx: &mut ServiceContext<Self>) {
+        if self.checking {
+            debug!("[watchdog] The last check is not finished, skip this check.");
+            return;
+        }
+        self.checking = true;
+        let config = self.config.watchdog.clone();
+        let registry = ctx.registry_ref().clone();
+        let txpool = self.txpool.clone();
+        let txpool_probe_pending = self.txpool_probe_pending.clone();
+        let chain = self.chain.clone();
+        let self_ref = ctx.self_ref();
+        ctx.spawn(async move {
+            let services = registry
+                .ping_services(config.service_max_latency())
+                .await
+                .unwrap_or_else(|e| {
+                    error!("[watchdog] Ping services error: {:?}", e);
+                    vec![]
+                });
+            let txpool =
+                probe_txpool(txpool, txpool_probe_pending, config.service_max_latency()).await;
+            let block_time_target = timeout(config.service_max_latency(), chain.epoch_info())
+                .await
+                .and_then(|result| result.ok())
+                .map(|epoch_info| epoch_info.block_time_target());
+            if let Err(e) = self_ref.notify(WatchdogProbeResult {
+                services,
+                txpool,
+                block_time_target,
+            }) {
+                error!("[watchdog] Notify probe result error: {:?}", e);
+            }
+        });
+    }
+}
+
+impl EventHandler<Self, WatchdogProbeResult> for WatchdogService {
+    fn handle_event(&mut self, msg: WatchdogProbeResult, ctx: &mut ServiceContext<Self>) {
+        self.checking = false;
+        if let Some(block_time_target) = msg.block_time_target {
+            self.block_time_target = block_time_target;
+        }
+        let txn_count = msg.txpool.map(|(_, txn_count)| txn_count).unwrap_or(0);
+        let expect_new_block =
+            !self.syncing && (!self.config.miner.is_disable_mint_empty_block() || txn_count > 0);
+        let probe = WatchdogProbe {
+            now_millis: self.config.net().time_service().now_millis(),
+            head_block_number: self.head_block_number,
+            head_block_timestamp: self.head_block_timestamp,
+            block_time_target: self.block_time_target,
+            expect_new_block,
+            txpool_latency: msg.txpool.map(|(latency, _)| latency),
+            services: msg.services,
+        };
+        let report = self.evaluator.evaluate(probe, Instant::now());
+        if report.block_stalled {
+            WATCHDOG_ALERTS_COUNTER
+                .with_label_values(&["block_stalled"])
+                .inc();
+        }
+        if report.txpool_slow {
+            WATCHDOG_ALERTS_COUNTER
+                .with_label_values(&["txpool_slow"])
+                .inc();
+        }
+        let unhealthy_services = report
+            .services
+            .iter()
+            .filter(|service| !service.healthy)
+            .count();
+        if unhealthy_services > 0 {
+            WATCHDOG_ALERTS_COUNTER
+                .with_label_values(&["service_unhealthy"])
+                .inc_by(unhealthy_services as u64);
+        }
+        let report_json = serde_json::to_string(&report).unwrap_or_default();
+        if report.is_healthy() {
+            debug!("[watchdog] report: {}", report_json);
+        } else {
+            warn!("[watchdog] report: {}", report_json);
+        }
+        if !report.restarted.is_empty() {
+            let registry = ctx.registry_ref().clone();
+            let services = report.restarted.clone();
+            ctx.spawn(async move {
+                restart_services(&registry, services.as_slice()).await;
+            });
+        }
+        self.monitor.update(report);
+    }
+}
+
+pub struct WatchdogServiceFactory;
+
+impl ServiceFactory<WatchdogService> for WatchdogServiceFactory {
+    fn create(ctx: &mut ServiceContext<WatchdogService>) -> Result<WatchdogService> {
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
+        let storage = ctx.get_shared::<Arc<Storage>>()?;
+        let txpool = ctx.get_shared::<TxPoolService>()?;
+        let monitor = ctx.get_shared::<WatchdogMonitor>()?;
+        let chain = ctx.service_ref::<ChainReaderService>()?.clone();
+        let startup_info = storage
+            .get_startup_info()?
+            .ok_or_else(|| format_err!("Startup info should exist."))?;
+        let head = storage
+            .get_block_header_by_hash(startup_info.main)?
+            .ok_or_else(|| format_err!("Can not find head block header {}", startup_info.main))?;
+        Ok(WatchdogService {
+            evaluator: WatchdogEvaluator::new(config.watchdog.clone()),
+            config,
+            monitor,
+            txpool,
+            chain,
+            txpool_probe_pending: Arc::new(AtomicBool::new(false)),
+            head_block_number: head.number(),
+            head_block_timestamp: head.timestamp(),
+            block_time_target: 0,
+            syncing: false,
+            checking: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_service_registry::mocker::stall_service::{
+        GetStallCount, StallEvent, StallService,
+    };
+
+    fn probe(now_millis: u64, services: Vec<ServiceLatency>) -> WatchdogProbe {
+        WatchdogProbe {
+            now_millis,
+            head_block_number: 10,
+            head_block_timestamp: 100_000,
+            block_time_target: 1000,
+            expect_new_block: true,
+            txpool_latency: Some(Duration::from_millis(1)),
+            services,
+        }
+    }
+
+    fn latency(name: &str, latency_millis: Option<u64>) -> ServiceLatency {
+        ServiceLatency {
+            name: name.to_string(),
+            status: ServiceStatus::Started,
+            latency_millis,
+        }
+    }
+
+    #[test]
+    fn test_watchdog_evaluate() {
+        let config = WatchdogConfig {
+            auto_restart: Some(true),
+            block_stall_factor: Some(10),
+            txpool_max_latency: Some(100),
+            failure_threshold: Some(2),
+            restart_cooldown: Some(60),
+            ..WatchdogConfig::default()
+        };
+        let mut evaluator = WatchdogEvaluator::new(config);
+        let now = Instant::now();
+
+        let report = evaluator.evaluate(probe(105_000, vec![latency("a", Some(1))]), now);
+        assert!(report.is_healthy());
+        assert_eq!(report.head_block_age_secs, 5);
+
+        // the head block is older than 10 * block time target.
+        let report = evaluator.evaluate(probe(111_000, vec![]), now);
+        assert!(report.block_stalled);
+        assert!(!report.is_healthy());
+
+        let mut no_new_block = probe(111_000, vec![]);
+        no_new_block.expect_new_block = false;
+        assert!(!evaluator.evaluate(no_new_block, now).block_stalled);
+
+        let mut slow_txpool = probe(105_000, vec![]);
+        slow_txpool.txpool_latency = Some(Duration::from_millis(200));
+        assert!(evaluator.evaluate(slow_txpool.clone(), now).txpool_slow);
+        slow_txpool.txpool_latency = None;
+        assert!(evaluator.evaluate(slow_txpool, now).txpool_slow);
+
+        // restart after the failure threshold.
+        let report = evaluator.evaluate(probe(105_000, vec![latency("a", None)]), now);
+        assert!(!report.services[0].healthy);
+        assert!(report.restarted.is_empty());
+        let report = evaluator.evaluate(probe(105_000, vec![latency("a", None)]), now);
+        assert_eq!(report.restarted, vec!["a".to_string()]);
+        assert_eq!(report.services[0].restarts, 1);
+
+        // not restart again in the cooldown.
+        for _ in 0..3 {
+            let report = evaluator.evaluate(probe(105_000, vec![latency("a", None)]), now);
+            assert!(report.restarted.is_empty());
+        }
+        let report = evaluator.evaluate(
+            probe(105_000, vec![latency("a", None)]),
+            now + Duration::from_secs(60),
+        );
+        assert_eq!(report.restarted, vec!["a".to_string()]);
+        assert_eq!(report.services[0].restarts, 2);
+
+        // recover reset the unhealthy checks.
+        let report = evaluator.evaluate(probe(105_000, vec![latency("a", None)]), now);
+        assert_eq!(report.services[0].unhealthy_checks, 1);
+        let report = evaluator.evaluate(probe(105_000, vec![latency("a", Some(1))]), now);
+        assert!(report.services[0].healthy);
+        assert_eq!(report.services[0].unhealthy_checks, 0);
+
+        // the bus service is never restarted.
+        for _ in 0..2 {
+            let report = evaluator.evaluate(
+                probe(105_000, vec![latency(BusService::service_name(), None)]),
+                now,
+            );
+            assert!(report.restarted.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_watchdog_not_restart_by_default() {
+        let mut evaluator = WatchdogEvaluator::new(WatchdogConfig::default());
+        let now = Instant::now();
+        for _ in 0..10 {
+            let report = evaluator.evaluate(probe(105_000, vec![latency("a", None)]), now);
+            assert!(report.restarted.is_empty());
+        }
+    }
+
+    #[stest::test]
+    async fn test_restart_stalled_service() {
+        let registry = RegistryService::launch();
+        let stall_service = registry.register::<StallService>().await.unwrap();
+
+        let config = WatchdogConfig {
+            auto_restart: Some(true),
+            failure_threshold: Some(2),
+            ..WatchdogConfig::default()
+        };
+        let mut evaluator = WatchdogEvaluator::new(config);
+        stall_service.notify(StallEvent { millis: 3000 }).unwrap();
+        let mut restarted = vec![];
+        for _ in 0..2 {
+            let services = registry
+                .ping_services(Duration::from_millis(200))
+                .await
+                .unwrap();
+            let mut probe = probe(105_000, services);
+            probe.expect_new_block = false;
+            restarted = evaluator.evaluate(probe, Instant::now()).restarted;
+        }
+        assert_eq!(restarted, vec![StallService::service_name().to_string()]);
+
+        restart_services(&registry, restarted.as_slice()).await;
+        // the restarted service responds before the stall of the old one ends.
+        let stall_count = timeout(
+            Duration::from_millis(500),
+            stall_service.send(GetStallCount),
+        )
+        .await
+        .expect("Restarted service should not wait the stuck mailbox.")
+        .unwrap();
+        assert_eq!(stall_count, 0);
+        let services = registry
+            .ping_services(Duration::from_millis(200))
+            .await
+            .unwrap();
+        assert!(services.iter().all(|service| !service.is_stalled()));
+        registry.shutdown_system().await.unwrap();
+    }
+}
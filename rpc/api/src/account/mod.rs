@@ -4,13 +4,18 @@
 use jsonrpc_derive::rpc;
 
 pub use self::gen_client::Client as AccountClient;
-use crate::types::{StrView, TransactionRequest};
+use crate::types::{
+    AccountAddressView, BalanceView, ReceiptIdentifierView, RotateAuthKeyView, StrView,
+    TokenCodeView, TransactionRequest, TxnHistoryView,
+};
 use crate::FutureResult;
-use starcoin_account_api::AccountInfo;
+use starcoin_account_api::{AccountInfo, DerivedAccount};
+use starcoin_crypto::HashValue;
 use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block::BlockNumber;
 use starcoin_types::sign_message::SigningMessage;
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
-use starcoin_vm_types::token::token_code::TokenCode;
+use std::collections::BTreeMap;
 
 #[rpc]
 pub trait AccountApi {
@@ -19,62 +24,200 @@ pub trait AccountApi {
     fn default(&self) -> FutureResult<Option<AccountInfo>>;
 
     #[rpc(name = "account.set_default_account")]
-    fn set_default_account(&self, addr: AccountAddress) -> FutureResult<Option<AccountInfo>>;
+    fn set_default_account(&self, addr: AccountAddressView) -> FutureResult<Option<AccountInfo>>;
 
     #[rpc(name = "account.create")]
     fn create(&self, password: String) -> FutureResult<AccountInfo>;
     #[rpc(name = "account.list")]
     fn list(&self) -> FutureResult<Vec<AccountInfo>>;
     #[rpc(name = "account.get")]
-    fn get(&self, address: AccountAddress) -> FutureResult<Option<AccountInfo>>;
+    fn get(&self, address: AccountAddressView) -> FutureResult<Option<AccountInfo>>;
 
     #[rpc(name = "account.sign")]
-    fn sign(&self, address: AccountAddress, data: SigningMessage)
-        -> FutureResult<StrView<Vec<u8>>>;
+    fn sign(
+        &self,
+        address: AccountAddressView,
+        data: SigningMessage,
+    ) -> FutureResult<StrView<Vec<u8>>>;
 
     /// sign a txn request, return hex encoded bcs_ext bytes of signed user txn.
+    /// If the sender is a read-only account without external signer,
+    /// return hex encoded bcs_ext bytes of raw user txn for external signing.
     #[rpc(name = "account.sign_txn_request")]
     fn sign_txn_request(&self, txn_request: TransactionRequest) -> FutureResult<String>;
 
+    /// Sign the txn by the external signer bound to `signer`, or by the wallet.
     #[rpc(name = "account.sign_txn")]
     fn sign_txn(
         &self,
         raw_txn: RawUserTransaction,
-        signer: AccountAddress,
+        signer: AccountAddressView,
     ) -> FutureResult<SignedUserTransaction>;
 
-    /// unlock account for duration in seconds, default to u32::max.
+    /// unlock account for duration in seconds, default to u32::max,
+    /// the duration is capped by the node config `vault.max_unlock_duration`.
     #[rpc(name = "account.unlock")]
     fn unlock(
         &self,
-        address: AccountAddress,
+        address: AccountAddressView,
         password: String,
         duration: Option<u32>,
     ) -> FutureResult<()>;
     #[rpc(name = "account.lock")]
-    fn lock(&self, address: AccountAddress) -> FutureResult<()>;
+    fn lock(&self, address: AccountAddressView) -> FutureResult<()>;
+    /// Lock all the unlocked accounts, return the locked accounts.
+    #[rpc(name = "account.lock_all")]
+    fn lock_all(&self) -> FutureResult<Vec<AccountAddress>>;
 
     /// Import private key with address.
     #[rpc(name = "account.import")]
     fn import(
         &self,
-        address: AccountAddress,
+        address: AccountAddressView,
         private_key: Vec<u8>,
         password: String,
     ) -> FutureResult<AccountInfo>;
 
+    /// Import a watch-only account with public key, the wallet holds no private key of it.
+    #[rpc(name = "account.import_readonly")]
+    fn import_readonly(
+        &self,
+        address: AccountAddressView,
+        public_key: StrView<Vec<u8>>,
+    ) -> FutureResult<AccountInfo>;
+
+    /// Assemble the raw txn signed externally with the signature and public key,
+    /// and submit it to txpool, return the txn hash.
+    #[rpc(name = "account.submit_signed")]
+    fn submit_signed(
+        &self,
+        raw_txn: StrView<Vec<u8>>,
+        signature: StrView<Vec<u8>>,
+        public_key: StrView<Vec<u8>>,
+    ) -> FutureResult<HashValue>;
+
     /// Return the private key as bytes for `address`
     #[rpc(name = "account.export")]
-    fn export(&self, address: AccountAddress, password: String) -> FutureResult<Vec<u8>>;
+    fn export(&self, address: AccountAddressView, password: String) -> FutureResult<Vec<u8>>;
+
+    /// Import account from the keystore json encrypted by `password`.
+    #[rpc(name = "account.import_keystore")]
+    fn import_keystore(&self, keystore: String, password: String) -> FutureResult<AccountInfo>;
 
+    /// Return the keystore json of `address`, encrypted by the account password.
+    #[rpc(name = "account.export_keystore")]
+    fn export_keystore(
+        &self,
+        address: AccountAddressView,
+        password: String,
+    ) -> FutureResult<String>;
+
+    /// Change the account password, the key is re-encrypted by the `new_password`.
+    /// The `old_password` is required if the account is locked, the `InvalidPassword` error if it is wrong.
     #[rpc(name = "account.change_password")]
-    // change account password, user need to unlock account first.
     fn change_account_password(
         &self,
-        address: AccountAddress,
+        address: AccountAddressView,
         new_password: String,
+        old_password: Option<String>,
     ) -> FutureResult<()>;
 
+    /// Remove the account from the wallet by its password, return the removed account.
+    /// The default account is refused unless `force` is true.
+    #[rpc(name = "account.remove")]
+    fn remove(
+        &self,
+        address: AccountAddressView,
+        password: String,
+        force: Option<bool>,
+    ) -> FutureResult<AccountInfo>;
+
+    /// Get the tokens accepted by `address`, which has the `Balance<T>` resource on chain.
+    /// The `address` of the on chain queries can also be a receipt identifier.
     #[rpc(name = "account.accepted_tokens")]
-    fn accepted_tokens(&self, address: AccountAddress) -> FutureResult<Vec<TokenCode>>;
+    fn accepted_tokens(&self, address: ReceiptIdentifierView) -> FutureResult<Vec<TokenCodeView>>;
+
+    /// Get all token balances of `address` at the latest state, keyed by token code.
+    #[rpc(name = "account.get_balances")]
+    fn get_balances(
+        &self,
+        address: ReceiptIdentifierView,
+    ) -> FutureResult<BTreeMap<String, BalanceView>>;
+
+    /// Get the balance of `token_code` of `address` at the state after block `block_number` of the main chain,
+    /// `token_code` is STC if absent. Return None if the account has no balance of the token at that block,
+    /// and the `StateNotFound` error if the state of that block is pruned.
+    #[rpc(name = "account.balance_at")]
+    fn balance_at(
+        &self,
+        address: ReceiptIdentifierView,
+        token_code: Option<String>,
+        block_number: BlockNumber,
+    ) -> FutureResult<Option<StrView<u128>>>;
+
+    /// Same as `account.balance_at`, return the balances at each of `block_numbers` in the same order,
+    /// at most 100 block numbers in one call.
+    #[rpc(name = "account.balances_at")]
+    fn balances_at(
+        &self,
+        address: ReceiptIdentifierView,
+        token_code: Option<String>,
+        block_numbers: Vec<BlockNumber>,
+    ) -> FutureResult<Vec<Option<StrView<u128>>>>;
+
+    /// Rotate the authentication key of the unlocked `address` to `private_key`, or a new key generated by the wallet.
+    /// The wallet keeps signing with the old key until the rotation txn is executed on chain in `timeout_secs`,
+    /// and drops the new key if the txn fails. If `dry_run`, only return the raw txn.
+    /// The new key of a rotation whose txn is not confirmed yet is replaced only if `replace`,
+    /// otherwise the call fails, since that txn may still be executed.
+    #[rpc(name = "account.rotate_authentication_key")]
+    fn rotate_authentication_key(
+        &self,
+        address: AccountAddressView,
+        private_key: Option<StrView<Vec<u8>>>,
+        dry_run: Option<bool>,
+        timeout_secs: Option<u64>,
+        replace: Option<bool>,
+    ) -> FutureResult<RotateAuthKeyView>;
+
+    /// Sign the txns of `address` by the external signer at `endpoint`, such as a HSM.
+    /// The `endpoint` is `http(s)://...` or `unix://<socket path>`, the signer serves the json rpc method
+    /// `sign_raw_txn(raw_txn_bcs_hex, address)` and returns the hex encoded bcs bytes of the authenticator.
+    #[rpc(name = "account.bind_external_signer")]
+    fn bind_external_signer(
+        &self,
+        address: AccountAddressView,
+        endpoint: String,
+    ) -> FutureResult<()>;
+
+    /// Generate a BIP-39 english mnemonic of `word_count` words, which is 12 if absent.
+    /// The mnemonic is not saved by the node.
+    #[rpc(name = "account.generate_mnemonic")]
+    fn generate_mnemonic(&self, word_count: Option<u32>) -> FutureResult<String>;
+
+    /// Import the account derived from `mnemonic` at the SLIP-0010 path `m/44'/101010'/0'/0'/<derivation_index>'`.
+    #[rpc(name = "account.import_from_mnemonic")]
+    fn import_from_mnemonic(
+        &self,
+        mnemonic: String,
+        derivation_index: u32,
+        password: String,
+    ) -> FutureResult<AccountInfo>;
+
+    /// Derive the address at `index` from `mnemonic` without importing it.
+    /// SLIP-0010 only supports hardened derivation for ed25519, so the mnemonic is required,
+    /// addresses can not be derived from a public root key.
+    #[rpc(name = "account.derive_address")]
+    fn derive_address(&self, mnemonic: String, index: u32) -> FutureResult<DerivedAccount>;
+
+    /// Get the txns signed by the wallet or sent from `address`, from the newest to the oldest,
+    /// with the status last observed from the chain. Skip `start` entries, default is 0,
+    /// and return at most `limit` entries, default is 20.
+    #[rpc(name = "account.txn_history")]
+    fn txn_history(
+        &self,
+        address: AccountAddressView,
+        start: Option<u64>,
+        limit: Option<u64>,
+    ) -> FutureResult<Vec<TxnHistoryView>>;
 }
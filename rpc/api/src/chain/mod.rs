@@ -4,30 +4,44 @@
 pub use self::gen_client::Client as ChainClient;
 use crate::types::pubsub::EventFilter;
 use crate::types::{
-    BlockHeaderView, BlockSummaryView, BlockView, ChainId, ChainInfoView, EpochUncleSummaryView,
-    TransactionEventView, TransactionInfoView, TransactionView,
+    AccountAddressView, BlockHeaderView, BlockHeadersView, BlockRewardView, BlockSummaryView,
+    BlockView, BranchView, ChainId, ChainStatusView, EncodedView, EpochInfoView,
+    EpochUncleSummaryView, EventCursorPageView, EventHandleView, GetBlockOption, OutputEncoding,
+    StrView, StructTagView, TransactionEventView, TransactionInfoView, TransactionOutputView,
+    TransactionProofView, TransactionView, TxnPositionView,
 };
 use crate::FutureResult;
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
 use starcoin_crypto::HashValue;
 use starcoin_types::block::{BlockInfo, BlockNumber};
-use starcoin_vm_types::on_chain_resource::{EpochInfo, GlobalTimeOnChain};
+use starcoin_types::event_cursor::EventCursorId;
+use starcoin_vm_types::on_chain_resource::GlobalTimeOnChain;
 
 #[rpc]
 pub trait ChainApi {
     #[rpc(name = "chain.id")]
     fn id(&self) -> Result<ChainId>;
 
-    /// Get main chain info
+    /// Get main chain info, with the txpool, sync and connected peers status of the node.
     #[rpc(name = "chain.info")]
-    fn info(&self) -> FutureResult<ChainInfoView>;
-    /// Get chain block info
+    fn info(&self) -> FutureResult<ChainStatusView>;
+    /// Get chain block info, return the bcs encoded `Block` if `output_encoding` is bcs.
     #[rpc(name = "chain.get_block_by_hash")]
-    fn get_block_by_hash(&self, block_hash: HashValue) -> FutureResult<Option<BlockView>>;
-    /// Get chain blocks by number
+    fn get_block_by_hash(
+        &self,
+        block_hash: HashValue,
+        option: Option<GetBlockOption>,
+        output_encoding: Option<OutputEncoding>,
+    ) -> FutureResult<Option<EncodedView<BlockView>>>;
+    /// Get chain blocks by number,
+    /// return the `BlockPruned` error if the block data is pruned by the `prune-blocks-before` config.
     #[rpc(name = "chain.get_block_by_number")]
-    fn get_block_by_number(&self, number: BlockNumber) -> FutureResult<Option<BlockView>>;
+    fn get_block_by_number(
+        &self,
+        number: BlockNumber,
+        option: Option<GetBlockOption>,
+    ) -> FutureResult<Option<BlockView>>;
     /// Get latest `count` blocks before `number`. if `number` is absent, use head block number.
     #[rpc(name = "chain.get_blocks_by_number")]
     fn get_blocks_by_number(
@@ -37,10 +51,30 @@ pub trait ChainApi {
     ) -> FutureResult<Vec<BlockView>>;
     #[rpc(name = "chain.get_block_info_by_number")]
     fn get_block_info_by_number(&self, number: BlockNumber) -> FutureResult<Option<BlockInfo>>;
-    /// Get chain transactions
+    /// Get chain transactions, return the bcs encoded `Transaction` if `output_encoding` is bcs.
     #[rpc(name = "chain.get_transaction")]
-    fn get_transaction(&self, transaction_hash: HashValue)
-        -> FutureResult<Option<TransactionView>>;
+    fn get_transaction(
+        &self,
+        transaction_hash: HashValue,
+        output_encoding: Option<OutputEncoding>,
+    ) -> FutureResult<Option<EncodedView<TransactionView>>>;
+
+    /// Get the main chain txns by hashes in one call, at most 100 hashes,
+    /// keep the order of `txn_hashes`, None if the txn is unknown or only in the txpool.
+    #[rpc(name = "chain.get_transactions")]
+    fn get_transactions(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> FutureResult<Vec<Option<TransactionView>>>;
+
+    /// Get the hash of the main chain block which includes the txn, None if the txn is not mined.
+    #[rpc(name = "chain.get_block_hash_by_txn")]
+    fn get_block_hash_by_txn(&self, txn_hash: HashValue) -> FutureResult<Option<HashValue>>;
+
+    /// Get the position of the txn on the main chain by the txn position index, None if the txn is not mined.
+    #[rpc(name = "chain.get_txn_position")]
+    fn get_txn_position(&self, txn_hash: HashValue) -> FutureResult<Option<TxnPositionView>>;
+
     /// Get chain transactions
     #[rpc(name = "chain.get_transaction_info")]
     fn get_transaction_info(
@@ -48,10 +82,32 @@ pub trait ChainApi {
         transaction_hash: HashValue,
     ) -> FutureResult<Option<TransactionInfoView>>;
 
-    /// Get chain transactions infos by block id
+    /// Get the output of a mined txn by re-executing it on the state before it,
+    /// the write set, events and gas used of the output are verified against the stored txn info.
+    /// Can be disabled by the rpc config `disable-txn-output-query`.
+    #[rpc(name = "chain.get_transaction_output")]
+    fn get_transaction_output(
+        &self,
+        transaction_hash: HashValue,
+    ) -> FutureResult<Option<TransactionOutputView>>;
+
+    /// Get the txn infos of the block in execution order, read from the txn info storage.
+    /// The block metadata txn is the first with `transaction_index` 0, the user txns start at 1,
+    /// same as the `transaction_index` of `chain.get_transaction`.
+    /// Return the `BlockPruned` error if the block data is pruned.
     #[rpc(name = "chain.get_block_txn_infos")]
     fn get_block_txn_infos(&self, block_hash: HashValue) -> FutureResult<Vec<TransactionInfoView>>;
 
+    /// Get txn infos of main chain by the index of txn accumulator, `start_global_index` is include,
+    /// the `max_size` is limited by the rpc config `txn-info-query-max-size`.
+    #[rpc(name = "chain.get_transaction_infos")]
+    fn get_transaction_infos(
+        &self,
+        start_global_index: u64,
+        reverse: bool,
+        max_size: u64,
+    ) -> FutureResult<Vec<TransactionInfoView>>;
+
     /// Get txn info of a txn at `idx` of block `block_id`
     #[rpc(name = "chain.get_txn_info_by_block_and_index")]
     fn get_txn_info_by_block_and_index(
@@ -60,22 +116,62 @@ pub trait ChainApi {
         idx: u64,
     ) -> FutureResult<Option<TransactionInfoView>>;
 
+    /// Get txn info of the txn at `transaction_index` of block `block_hash` with its accumulator proof,
+    /// and the proof of the event at `event_index` if present.
+    #[rpc(name = "chain.get_transaction_proof")]
+    fn get_transaction_proof(
+        &self,
+        block_hash: HashValue,
+        transaction_index: u64,
+        event_index: Option<u64>,
+    ) -> FutureResult<Option<TransactionProofView>>;
+
     #[rpc(name = "chain.get_events_by_txn_hash")]
     fn get_events_by_txn_hash(
         &self,
         txn_hash: HashValue,
     ) -> FutureResult<Vec<TransactionEventView>>;
 
+    /// Export the block as the bcs encoded `BlockBundle` to replay it offline, None if the block does not exist.
+    /// If `with_state` is true, the block is re-executed on the state before it to record the state entries
+    /// it reads and the outputs of the txns, the outputs are verified against the committed txn infos.
+    /// Exporting with state is disabled unless the rpc config `enable-block-bundle-state` is set.
+    #[rpc(name = "chain.export_block_bundle")]
+    fn export_block_bundle(
+        &self,
+        block_hash: HashValue,
+        with_state: Option<bool>,
+    ) -> FutureResult<Option<StrView<Vec<u8>>>>;
+
+    /// Get the events of the main chain matching the filter, in the order of (block number, txn index, event index).
+    /// The block range is capped by the node, paginate by passing the position of the last returned event as `cursor`.
     #[rpc(name = "chain.get_events")]
     fn get_events(&self, filter: EventFilter) -> FutureResult<Vec<TransactionEventView>>;
 
+    /// Poll at most `max_items` events after the last acked event of the cursor created by `node_manager.create_event_cursor`,
+    /// the unacked events are returned again by the next poll. Return the `EventCursorExpired` error if the cursor is expired.
+    #[rpc(name = "chain.poll_events")]
+    fn poll_events(
+        &self,
+        cursor_id: EventCursorId,
+        max_items: u64,
+    ) -> FutureResult<EventCursorPageView>;
+
+    /// Ack the events of the cursor up to the `global_index` of an event, acking an older event is ignored.
+    #[rpc(name = "chain.ack_events")]
+    fn ack_events(
+        &self,
+        cursor_id: EventCursorId,
+        up_to_global_index: StrView<u128>,
+    ) -> FutureResult<()>;
+
     /// Get current epoch info.
-    #[rpc(name = "chain.epoch")]
-    fn current_epoch(&self) -> FutureResult<EpochInfo>;
+    #[rpc(name = "chain.get_epoch_info", alias("chain.epoch"))]
+    fn current_epoch(&self) -> FutureResult<EpochInfoView>;
 
-    /// Get epoch info by number.
+    /// Get the epoch info at the block number, read from the state of the block.
     #[rpc(name = "chain.get_epoch_info_by_number")]
-    fn get_epoch_info_by_number(&self, number: BlockNumber) -> FutureResult<EpochInfo>;
+    fn get_epoch_info_by_number(&self, number: BlockNumber) -> FutureResult<EpochInfoView>;
 
     /// Get global time by number.
     #[rpc(name = "chain.get_global_time_by_number")]
@@ -88,14 +184,77 @@ pub trait ChainApi {
         number: BlockNumber,
     ) -> FutureResult<Vec<BlockSummaryView>>;
 
-    /// Get headers by ids.
+    /// Get the main chain block which includes the uncle, for looking up an uncle by its hash.
+    #[rpc(name = "chain.get_block_by_uncle_hash")]
+    fn get_block_by_uncle_hash(
+        &self,
+        uncle_hash: HashValue,
+    ) -> FutureResult<Option<BlockSummaryView>>;
+
+    /// Get the uncles included in the main chain block of the number.
+    #[rpc(name = "chain.get_uncles_by_number")]
+    fn get_uncles_by_number(&self, number: BlockNumber) -> FutureResult<Vec<BlockHeaderView>>;
+
+    /// Get headers by ids without the block bodies, the missing blocks are skipped, at most 256 ids in one call.
     #[rpc(name = "chain.get_headers")]
     fn get_headers(&self, ids: Vec<HashValue>) -> FutureResult<Vec<BlockHeaderView>>;
 
+    /// Same as `chain.get_headers`, but keep the position of the ids, None if the block does not exist.
+    #[rpc(name = "chain.get_headers_v2")]
+    fn get_headers_v2(&self, ids: Vec<HashValue>) -> FutureResult<Vec<Option<BlockHeaderView>>>;
+
+    /// Get at most `count` main chain headers from `start` in ascending order without the block bodies,
+    /// `count` is at most 256.
+    #[rpc(name = "chain.get_headers_by_number")]
+    fn get_headers_by_number(
+        &self,
+        start: BlockNumber,
+        count: u64,
+    ) -> FutureResult<BlockHeadersView>;
+
+    /// Get the reward breakdown of the main chain block of the number,
+    /// None if the block does not exist or its reward is not distributed yet.
+    #[rpc(name = "chain.get_block_reward")]
+    fn get_block_reward(&self, number: BlockNumber) -> FutureResult<Option<BlockRewardView>>;
+
+    /// Get the reward breakdowns of at most `count` main chain blocks from `start`, `count` is at most 100,
+    /// the blocks whose reward is not distributed yet are excluded.
+    #[rpc(name = "chain.get_block_rewards")]
+    fn get_block_rewards(
+        &self,
+        start: BlockNumber,
+        count: u64,
+    ) -> FutureResult<Vec<BlockRewardView>>;
+
     /// Epoch uncle summary by number.
     #[rpc(name = "chain.epoch_uncle_summary_by_number")]
     fn epoch_uncle_summary_by_number(
         &self,
         number: BlockNumber,
     ) -> FutureResult<EpochUncleSummaryView>;
+
+    /// Get the key and the counter of the `EventHandle` at the dotted `field_path` of the resource
+    /// `struct_tag` of `address` in the head state, such as `withdraw_events` of `0x1::Account::Account`,
+    /// or `market.orders.events` for a nested handle. None if the resource does not exist,
+    /// and the `FieldNotFound` error if a field of the path does not exist.
+    #[rpc(name = "chain.get_event_handle")]
+    fn get_event_handle(
+        &self,
+        address: AccountAddressView,
+        struct_tag: StructTagView,
+        field_path: String,
+    ) -> FutureResult<Option<EventHandleView>>;
+
+    /// Get the main chain head and the branch heads the node tracks, the main chain is the first,
+    /// followed by the branches in the descending order of the total difficulty.
+    #[rpc(name = "chain.get_branches")]
+    fn get_branches(&self) -> FutureResult<Vec<BranchView>>;
+
+    /// Get the latest common ancestor of the two blocks, None if any block does not exist.
+    #[rpc(name = "chain.get_common_ancestor")]
+    fn get_common_ancestor(
+        &self,
+        hash1: HashValue,
+        hash2: HashValue,
+    ) -> FutureResult<Option<HashValue>>;
 }
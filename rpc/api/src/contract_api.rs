@@ -2,24 +2,39 @@ use jsonrpc_derive::rpc;
 
 pub use self::gen_client::Client as ContractClient;
 use crate::types::{
-    AnnotatedMoveStructView, AnnotatedMoveValueView, ContractCall, DryRunTransactionRequest,
-    StrView, TransactionOutputView,
+    AccountAddressView, AnnotatedMoveStructView, AnnotatedMoveValueView, CodeView, ContractCall,
+    DisassemblyView, DryRunTransactionRequest, FunctionAbiView, FunctionIdView, GetCodeOption,
+    ModuleAbiView, ModuleIdView, StrView, TransactionOutputView,
 };
 use crate::FutureResult;
-use starcoin_vm_types::account_address::AccountAddress;
-use starcoin_vm_types::language_storage::{ModuleId, StructTag};
+use starcoin_crypto::HashValue;
+use starcoin_vm_types::language_storage::StructTag;
 
 #[rpc]
 pub trait ContractApi {
-    /// get code of module
+    /// Get the code of the on-chain module and its dependencies, with the disassembly if `option.disassemble` is true.
+    /// Return None if the module does not exist.
     #[rpc(name = "contract.get_code")]
-    fn get_code(&self, module_id: StrView<ModuleId>) -> FutureResult<Option<StrView<Vec<u8>>>>;
+    fn get_code(
+        &self,
+        module_id: ModuleIdView,
+        option: Option<GetCodeOption>,
+    ) -> FutureResult<Option<CodeView>>;
+
+    /// Get the sha3-256 hash of the on-chain module bytes, return None if the module does not exist.
+    #[rpc(name = "contract.module_exists")]
+    fn module_exists(&self, module_id: ModuleIdView) -> FutureResult<Option<HashValue>>;
+
+    /// Disassemble the hex encoded bytecode of a module or script.
+    /// Malformed bytecode is reported as an error with code `-50004`.
+    #[rpc(name = "contract.disassemble")]
+    fn disassemble(&self, code: StrView<Vec<u8>>) -> FutureResult<DisassemblyView>;
 
     /// get resource data of `addr`
     #[rpc(name = "contract.get_resource")]
     fn get_resource(
         &self,
-        addr: AccountAddress,
+        addr: AccountAddressView,
         resource_type: StrView<StructTag>,
     ) -> FutureResult<Option<AnnotatedMoveStructView>>;
 
@@ -27,6 +42,23 @@ pub trait ContractApi {
     #[rpc(name = "contract.call")]
     fn call(&self, call: ContractCall) -> FutureResult<Vec<AnnotatedMoveValueView>>;
 
+    /// Call a move contract, return the decoded move values.
+    /// If the execution fails, such as the function aborts, return an error with code `-50002`,
+    /// and the `TransactionVMStatus` as the error data, so that caller can check the abort code.
+    #[rpc(name = "contract.call_v2")]
+    fn call_v2(&self, call: ContractCall) -> FutureResult<Vec<AnnotatedMoveValueView>>;
+
     #[rpc(name = "contract.dry_run")]
     fn dry_run(&self, txn: DryRunTransactionRequest) -> FutureResult<TransactionOutputView>;
+
+    /// Get the ABI of the script functions in the on-chain module, return None if the module does not exist.
+    #[rpc(name = "contract.get_module_abi")]
+    fn get_module_abi(&self, module_id: ModuleIdView) -> FutureResult<Option<ModuleAbiView>>;
+
+    /// Get the ABI of the on-chain script function, return None if the module or function does not exist.
+    #[rpc(name = "contract.resolve_function")]
+    fn resolve_function(
+        &self,
+        function_id: FunctionIdView,
+    ) -> FutureResult<Option<FunctionAbiView>>;
 }
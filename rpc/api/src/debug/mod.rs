@@ -7,6 +7,8 @@ use starcoin_logger::LogPattern;
 
 pub use self::gen_client::Client as DebugClient;
 use crate::types::FactoryAction;
+use crate::FutureResult;
+use starcoin_crypto::HashValue;
 
 #[rpc]
 pub trait DebugApi {
@@ -22,10 +24,15 @@ pub trait DebugApi {
     #[rpc(name = "debug.panic")]
     fn panic(&self) -> Result<()>;
 
-    ///Only can used under dev net.
-    #[rpc(name = "debug.sleep")]
+    ///Advance the mock time of the node by `time` millis, only can used under dev or test net.
+    #[rpc(name = "debug.sleep", alias("dev.sleep"))]
     fn sleep(&self, time: u64) -> Result<()>;
 
+    /// Generate `count` blocks one by one with the txns in the txpool, and return the block hashes.
+    /// Only available on the dev or test chain with the dummy consensus, otherwise return an error.
+    #[rpc(name = "dev.generate_block")]
+    fn dev_generate_block(&self, count: u64) -> FutureResult<Vec<HashValue>>;
+
     /// Get and set txn factory status.
     #[rpc(name = "txfactory.status")]
     fn txfactory_status(&self, action: FactoryAction) -> Result<bool>;
@@ -1,7 +1,10 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::types::StrView;
 use jsonrpc_core::{Error, ErrorCode, Value};
+use serde::{Deserialize, Serialize};
+use starcoin_types::account_address::AccountAddress;
 use std::fmt;
 
 pub fn invalid_params<T: fmt::Debug>(param: &str, details: T) -> Error {
@@ -11,3 +14,296 @@ pub fn invalid_params<T: fmt::Debug>(param: &str, details: T) -> Error {
         data: Some(Value::String(format!("{:?}", details))),
     }
 }
+
+/// The server error codes of starcoin json rpc.
+/// Every code has a fixed type of `error.data`, so clients can match on the code
+/// instead of parsing the message.
+/// Invalid request params still use the standard `-32602` code.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RpcErrorCode {
+    /// The api quota is exceeded, data is the `RateLimitedData`.
+    RateLimited,
+    /// The active subscriptions reach the limit of the node or the connection,
+    /// data is the `SubscriptionLimitData`.
+    TooManySubscriptions,
+    /// The node is shutting down and rejects the writes, without data.
+    NodeShuttingDown,
+    /// The method is disabled by the node operator with the `rpc.api_filter` config, without data.
+    MethodDisabled,
+    /// The txpool reaches its limit, without data.
+    TxnPoolFull,
+    /// The requested state is pruned or corrupted, without data.
+    StateNotFound,
+    /// The txn or contract call fails when executing, data is the `TransactionVMStatus`.
+    ExecutionFailed,
+    /// The txn is discarded by validation, such as the sequence number is too old,
+    /// data is the `TransactionVMStatus::Discard` which carries the `status_code`.
+    /// The txn rejected by the txpool checks, such as already imported or the gas price is too low, is without data.
+    TxnValidationFailed,
+    /// The bytecode can not be deserialized or disassembled, data is the `InvalidBytecodeData`.
+    InvalidBytecode,
+    /// The submitted seal is not for the current mint job, without data.
+    StaleWork,
+    /// The submitted txn fails the gas, expiration or balance checks of the node,
+    /// data is the `InvalidTransactionData` which tells the failed check and the limit.
+    InvalidTransaction,
+    /// The event cursor is expired after idle for too long or reaching its max lifetime, without data.
+    /// The caller should create a new cursor and backfill from its last processed event.
+    EventCursorExpired,
+    /// A field of the requested field path is not found in the resource, data is the `FieldNotFoundData`.
+    FieldNotFound,
+    /// The requested block data, such as the body, txn infos and events, is deleted by the block
+    /// data pruning, data is the `BlockPrunedData`. The header and the state of the block are still available.
+    BlockPruned,
+    /// The account store of the wallet fails, without data.
+    AccountStoreError,
+    /// The account does not exist, data is the `AccountErrorData`.
+    AccountNotFound,
+    /// The account is locked, data is the `AccountErrorData`.
+    AccountLocked,
+    /// The external signer bound to the account does not respond in time, data is the `AccountErrorData`.
+    ExternalSignerTimeout,
+    /// The external signer bound to the account returns an error or an invalid signature,
+    /// data is the `AccountErrorData`.
+    ExternalSignerError,
+    /// The password of the account is wrong, data is the `AccountErrorData`.
+    InvalidPassword,
+}
+
+impl RpcErrorCode {
+    pub fn code(self) -> i64 {
+        match self {
+            RpcErrorCode::RateLimited => -10000,
+            RpcErrorCode::TooManySubscriptions => -10001,
+            RpcErrorCode::NodeShuttingDown => -10002,
+            RpcErrorCode::MethodDisabled => -10003,
+            RpcErrorCode::TxnPoolFull => -50000,
+            RpcErrorCode::StateNotFound => -50001,
+            RpcErrorCode::ExecutionFailed => -50002,
+            RpcErrorCode::TxnValidationFailed => -50003,
+            RpcErrorCode::InvalidBytecode => -50004,
+            RpcErrorCode::StaleWork => -50005,
+            RpcErrorCode::InvalidTransaction => -50006,
+            RpcErrorCode::EventCursorExpired => -50007,
+            RpcErrorCode::FieldNotFound => -50008,
+            RpcErrorCode::BlockPruned => -50009,
+            RpcErrorCode::AccountStoreError => -60000,
+            RpcErrorCode::AccountNotFound => -60001,
+            RpcErrorCode::AccountLocked => -60002,
+            RpcErrorCode::ExternalSignerTimeout => -60003,
+            RpcErrorCode::ExternalSignerError => -60004,
+            RpcErrorCode::InvalidPassword => -60005,
+        }
+    }
+
+    pub fn from_code(code: i64) -> Option<Self> {
+        Some(match code {
+            -10000 => RpcErrorCode::RateLimited,
+            -10001 => RpcErrorCode::TooManySubscriptions,
+            -10002 => RpcErrorCode::NodeShuttingDown,
+            -10003 => RpcErrorCode::MethodDisabled,
+            -50000 => RpcErrorCode::TxnPoolFull,
+            -50001 => RpcErrorCode::StateNotFound,
+            -50002 => RpcErrorCode::ExecutionFailed,
+            -50003 => RpcErrorCode::TxnValidationFailed,
+            -50004 => RpcErrorCode::InvalidBytecode,
+            -50005 => RpcErrorCode::StaleWork,
+            -50006 => RpcErrorCode::InvalidTransaction,
+            -50007 => RpcErrorCode::EventCursorExpired,
+            -50008 => RpcErrorCode::FieldNotFound,
+            -50009 => RpcErrorCode::BlockPruned,
+            -60000 => RpcErrorCode::AccountStoreError,
+            -60001 => RpcErrorCode::AccountNotFound,
+            -60002 => RpcErrorCode::AccountLocked,
+            -60003 => RpcErrorCode::ExternalSignerTimeout,
+            -60004 => RpcErrorCode::ExternalSignerError,
+            -60005 => RpcErrorCode::InvalidPassword,
+            _ => return None,
+        })
+    }
+
+    /// Get the registered code of the `error`, None if it is not a starcoin server error.
+    pub fn of(error: &Error) -> Option<Self> {
+        match error.code {
+            ErrorCode::ServerError(code) => Self::from_code(code),
+            _ => None,
+        }
+    }
+
+    pub fn error<M: Into<String>>(self, message: M) -> Error {
+        Error {
+            code: ErrorCode::ServerError(self.code()),
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    pub fn error_with_data<M: Into<String>, T: Serialize>(self, message: M, data: &T) -> Error {
+        Error {
+            code: ErrorCode::ServerError(self.code()),
+            message: message.into(),
+            data: Some(serde_json::to_value(data).expect("rpc error data to json should be ok")),
+        }
+    }
+}
+
+/// The error data of rate limited calls.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct RateLimitedData {
+    /// Milliseconds to wait before retrying the call.
+    pub retry_after_ms: u64,
+}
+
+/// Which limit of the active subscriptions is reached.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriptionLimitScope {
+    Node,
+    Connection,
+}
+
+/// The error data of the rejected subscriptions.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct SubscriptionLimitData {
+    pub scope: SubscriptionLimitScope,
+    pub limit: u32,
+}
+
+/// The error data of account errors.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct AccountErrorData {
+    pub address: AccountAddress,
+}
+
+/// The error data of invalid bytecode.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct InvalidBytecodeData {
+    /// The vm status code of the deserialization, None if the bytecode is deserialized but can not be disassembled.
+    pub status_code: Option<u64>,
+}
+
+/// The error data of a field path which can not be resolved in a resource.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct FieldNotFoundData {
+    pub resource_type: String,
+    /// The path up to the first field which is not found, such as `market.orders` of `market.orders.events`.
+    pub field_path: String,
+    /// The fields of the struct which the missing field is looked up in.
+    pub available_fields: Vec<String>,
+}
+
+/// The error data of the pruned block data.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BlockPrunedData {
+    pub number: StrView<u64>,
+    /// The data of the main chain blocks before it are pruned.
+    pub pruned_before: StrView<u64>,
+}
+
+/// The error data of the txn which fails the submit checks, `check` is the failed check.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "check", rename_all = "snake_case")]
+pub enum InvalidTransactionData {
+    /// The max gas amount is larger than the block gas limit of the current epoch.
+    MaxGasAmountExceeded {
+        max_gas_amount: StrView<u64>,
+        block_gas_limit: StrView<u64>,
+    },
+    /// The gas unit price is lower than the min gas price of the txpool.
+    GasPriceTooLow {
+        gas_unit_price: StrView<u64>,
+        min_gas_price: StrView<u64>,
+    },
+    /// The txn is expired by the node time.
+    Expired {
+        expiration_timestamp_secs: StrView<u64>,
+        now_seconds: u64,
+    },
+    /// The balance of the gas token can not pay `max_gas_amount * gas_unit_price`.
+    InsufficientBalance {
+        gas_token_code: String,
+        balance: StrView<u128>,
+        max_gas_cost: StrView<u128>,
+    },
+}
+
+impl InvalidTransactionData {
+    pub fn into_error(self) -> Error {
+        RpcErrorCode::InvalidTransaction.error_with_data(self.to_string(), &self)
+    }
+}
+
+impl fmt::Display for InvalidTransactionData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidTransactionData::MaxGasAmountExceeded {
+                max_gas_amount,
+                block_gas_limit,
+            } => write!(
+                f,
+                "max_gas_amount {} exceeds the block gas limit {}",
+                max_gas_amount, block_gas_limit
+            ),
+            InvalidTransactionData::GasPriceTooLow {
+                gas_unit_price,
+                min_gas_price,
+            } => write!(
+                f,
+                "gas_unit_price {} is lower than the min gas price {}",
+                gas_unit_price, min_gas_price
+            ),
+            InvalidTransactionData::Expired {
+                expiration_timestamp_secs,
+                now_seconds,
+            } => write!(
+                f,
+                "txn is expired at {}, the node time is {}",
+                expiration_timestamp_secs, now_seconds
+            ),
+            InvalidTransactionData::InsufficientBalance {
+                gas_token_code,
+                balance,
+                max_gas_cost,
+            } => write!(
+                f,
+                "balance {} of {} can not pay the max gas cost {}",
+                balance, gas_token_code, max_gas_cost
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_code_roundtrip() {
+        for code in vec![
+            RpcErrorCode::RateLimited,
+            RpcErrorCode::TooManySubscriptions,
+            RpcErrorCode::NodeShuttingDown,
+            RpcErrorCode::MethodDisabled,
+            RpcErrorCode::TxnPoolFull,
+            RpcErrorCode::StateNotFound,
+            RpcErrorCode::ExecutionFailed,
+            RpcErrorCode::TxnValidationFailed,
+            RpcErrorCode::InvalidBytecode,
+            RpcErrorCode::StaleWork,
+            RpcErrorCode::InvalidTransaction,
+            RpcErrorCode::EventCursorExpired,
+            RpcErrorCode::FieldNotFound,
+            RpcErrorCode::BlockPruned,
+            RpcErrorCode::AccountStoreError,
+            RpcErrorCode::AccountNotFound,
+            RpcErrorCode::AccountLocked,
+            RpcErrorCode::ExternalSignerTimeout,
+            RpcErrorCode::ExternalSignerError,
+            RpcErrorCode::InvalidPassword,
+        ] {
+            assert_eq!(RpcErrorCode::from_code(code.code()), Some(code));
+            assert_eq!(RpcErrorCode::of(&code.error("test")), Some(code));
+        }
+        assert_eq!(RpcErrorCode::of(&Error::invalid_params("test")), None);
+    }
+}
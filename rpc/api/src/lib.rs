@@ -1,9 +1,13 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
-use jsonrpc_core::{BoxFuture, Error};
+use futures::future::BoxFuture;
+use jsonrpc_core::Error;
 
-pub type FutureResult<T> = BoxFuture<Result<T, Error>>;
+/// The result of the async rpc methods. jsonrpc-core 17 is built on the std futures, so this is the
+/// same type as `jsonrpc_core::BoxFuture`, the implementations return a boxed `async` block directly,
+/// and the clients await it or block on it without any 0.1 futures compat layer.
+pub type FutureResult<T> = BoxFuture<'static, Result<T, Error>>;
 
 pub mod account;
 pub mod chain;
@@ -21,5 +25,6 @@ pub mod state;
 pub mod sync_manager;
 #[cfg(test)]
 mod tests;
+pub mod transfer_export;
 pub mod txpool;
 pub mod types;
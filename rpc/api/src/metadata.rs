@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use jsonrpc_pubsub::{PubSubMetadata, Session};
+use starcoin_rpc_middleware::TraceMetadata;
 use std::sync::Arc;
 
 /// RPC methods metadata.
@@ -12,6 +13,8 @@ pub struct Metadata {
     /// Request PubSub Session
     pub session: Option<Arc<Session>>,
     pub user: Option<String>,
+    /// The id shared by the calls of a batch request, set by the rpc middleware.
+    pub batch_id: Option<String>,
 }
 
 impl Metadata {
@@ -19,6 +22,7 @@ impl Metadata {
         Self {
             session: Some(session),
             user: None,
+            batch_id: None,
         }
     }
 }
@@ -29,3 +33,13 @@ impl PubSubMetadata for Metadata {
         self.session.clone()
     }
 }
+
+impl TraceMetadata for Metadata {
+    fn batch_id(&self) -> Option<&str> {
+        self.batch_id.as_deref()
+    }
+
+    fn set_batch_id(&mut self, batch_id: String) {
+        self.batch_id = Some(batch_id);
+    }
+}
@@ -2,12 +2,32 @@
 // SPDX-License-Identifier: Apache-2
 
 pub use self::gen_client::Client as MinerClient;
+use crate::types::{MintBlockTemplateView, StrView};
+use crate::FutureResult;
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
+use starcoin_crypto::HashValue;
+use starcoin_types::block::BlockHeaderExtra;
 
 #[rpc]
 pub trait MinerApi {
     /// submit mining seal
     #[rpc(name = "mining.submit")]
     fn submit(&self, minting_blob: String, nonce: u32, extra: String) -> Result<()>;
+
+    /// Get the current mint job, dispatch a new one if there is no job.
+    /// None if no job is dispatched, such as minting empty blocks is disabled and the txpool is empty.
+    /// Subscribe `newMintBlock` to be notified of the new jobs instead of polling.
+    #[rpc(name = "miner.get_work")]
+    fn get_work(&self) -> FutureResult<Option<MintBlockTemplateView>>;
+
+    /// Submit the seal of the current mint job, the seal is verified by the consensus strategy of the job,
+    /// return the hash of the mint block, or the `StaleWork` error if the job is superseded.
+    #[rpc(name = "miner.submit_work")]
+    fn submit_work(
+        &self,
+        minting_blob: StrView<Vec<u8>>,
+        nonce: u32,
+        extra: BlockHeaderExtra,
+    ) -> FutureResult<HashValue>;
 }
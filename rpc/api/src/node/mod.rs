@@ -2,14 +2,103 @@
 // SPDX-License-Identifier: Apache-2
 
 pub use self::gen_client::Client as NodeClient;
-use crate::types::PeerInfoView;
+use crate::types::{ChainIdView, MetricView, PeerInfoView, StrView};
 use crate::FutureResult;
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
+use network_api::messages::DiscoveredSeed;
 use serde::{Deserialize, Serialize};
-use starcoin_config::ChainNetworkID;
+use starcoin_config::{ChainNetworkID, HealthConfiguration};
+use starcoin_crypto::HashValue;
+use starcoin_service_registry::ServiceStatus;
+use starcoin_types::block::BlockNumber;
+use starcoin_types::checkpoint::{Checkpoint, CheckpointMismatch};
 use starcoin_vm_types::genesis_config::ConsensusStrategy;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The schema versions of rpc namespaces, bump the version of a namespace when its views change incompatibly,
+/// and note the change here.
+///
+/// - chain 2: `chain.get_events` returns the events from the oldest to the latest.
+/// - chain 3: the `chain_id` of the block header and chain info views is a `ChainIdView`.
+/// - chain 4: `BranchView.length_from_common_ancestor` and the `BlockPruned` error data are strings.
+/// - chain 5: `chain.epoch` and `chain.get_epoch_info_by_number` return `EpochInfoView` instead of the raw
+///   `EpochInfo` resource, `difficulty` and `total_difficulty` are hex strings, and a pruned block is reported
+///   by the `BlockPruned` error code -50009 instead of `StateNotFound`.
+/// - node 1: `node.info` reports the chain id by `ChainIdView`.
+/// - node 2: the block numbers of `SystemInfo`, `CheckpointsReport`, `HealthView` and `WatchdogReport` are strings.
+/// - pubsub 2: the `difficulty` and `chain_id` of the new block header are a hex string and a `ChainIdView`.
+/// - pubsub 3: `MintBlock.block_number` and `MintBlock.difficulty` are strings.
+/// - txpool 2: the chain values of `TxnVerdict`, `TxnDiagnosisView` and the invalid transaction error data are strings.
+pub const API_VERSIONS: [(&str, u32); 6] = [
+    ("account", 1),
+    ("chain", 5),
+    ("node", 2),
+    ("pubsub", 3),
+    ("state", 1),
+    ("txpool", 2),
+];
+
+pub fn api_versions() -> BTreeMap<String, u32> {
+    API_VERSIONS
+        .iter()
+        .map(|(namespace, version)| (namespace.to_string(), *version))
+        .collect()
+}
+
+/// Compare the api versions of the node with the versions this binary is built with.
+pub fn check_api_versions(node_api_versions: &BTreeMap<String, u32>) -> Vec<ApiVersionMismatch> {
+    let local = api_versions();
+    local
+        .keys()
+        .chain(node_api_versions.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter_map(|namespace| {
+            let local_version = local.get(namespace).cloned();
+            let node_version = node_api_versions.get(namespace).cloned();
+            if local_version == node_version {
+                None
+            } else {
+                Some(ApiVersionMismatch {
+                    namespace: namespace.clone(),
+                    local_version,
+                    node_version,
+                })
+            }
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ApiVersionMismatch {
+    pub namespace: String,
+    /// None if the namespace is unknown to this binary.
+    pub local_version: Option<u32>,
+    /// None if the node does not provide the namespace.
+    pub node_version: Option<u32>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BuildInfo {
+    pub version: String,
+    pub git_hash: String,
+    /// The build time in unix seconds.
+    pub build_time: u64,
+    pub rust_version: String,
+}
+
+impl BuildInfo {
+    /// The build info of current binary.
+    pub fn current() -> Self {
+        Self {
+            version: starcoin_config::CRATE_VERSION.to_string(),
+            git_hash: starcoin_config::GIT_VERSION.to_string(),
+            build_time: starcoin_config::BUILD_TIME.parse().unwrap_or_default(),
+            rust_version: starcoin_config::RUST_VERSION.to_string(),
+        }
+    }
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NodeInfo {
@@ -19,6 +108,15 @@ pub struct NodeInfo {
     pub net: ChainNetworkID,
     pub consensus: ConsensusStrategy,
     pub now_seconds: u64,
+    /// Default for the node which does not report build info.
+    #[serde(default)]
+    pub build: BuildInfo,
+    /// The schema version of each rpc namespace, empty for the node which does not report it.
+    #[serde(default)]
+    pub api_versions: BTreeMap<String, u32>,
+    /// The chain id of the node network, None for the node which does not report it.
+    #[serde(default)]
+    pub chain_id: Option<ChainIdView>,
 }
 
 impl NodeInfo {
@@ -29,16 +127,197 @@ impl NodeInfo {
         consensus: ConsensusStrategy,
         now_seconds: u64,
     ) -> Self {
+        let chain_id = ChainIdView {
+            id: net.chain_id().id(),
+            name: Some(net.chain_name()),
+        };
         Self {
             peer_info,
             self_address,
+            chain_id: Some(chain_id),
             net,
             consensus,
             now_seconds,
+            build: BuildInfo::current(),
+            api_versions: api_versions(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ColumnFamilySizeView {
+    pub sst_files_size: u64,
+    pub live_data_size: u64,
+    pub mem_tables_size: u64,
+    pub estimate_num_keys: u64,
+}
+
+/// The resource usage of the node, the sizes are in bytes.
+/// The fields which the platform does not support are None.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SystemInfo {
+    pub data_dir: String,
+    /// The size of each storage column family, empty if the storage is not backed by db.
+    pub storage: BTreeMap<String, ColumnFamilySizeView>,
+    /// The sum of the sst files and mem tables size of all column families.
+    pub storage_size: u64,
+    /// The disk usage of the volume which the data dir is on.
+    pub disk_total: Option<u64>,
+    pub disk_free: Option<u64>,
+    pub disk_used: Option<u64>,
+    pub open_fds: Option<u64>,
+    /// The resident memory size of the node process.
+    pub rss: Option<u64>,
+    pub uptime_secs: Option<u64>,
+    /// The configured block data pruning horizon, `<number>` or `recent:<n>`, None if the pruning is disabled.
+    #[serde(default)]
+    pub prune_blocks_before: Option<String>,
+    /// The data of the main chain blocks in `[1, pruned_before)` are pruned.
+    #[serde(default)]
+    pub pruned_before: StrView<BlockNumber>,
+    /// The size of the pruned block data, the disk space is reclaimed after the db compaction.
+    #[serde(default)]
+    pub pruned_bytes: u64,
+}
+
+/// The result of verifying the local main chain against the checkpoints.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CheckpointsReport {
+    /// The number of the latest checkpoint, None if the node has no checkpoints.
+    pub latest_checkpoint: Option<StrView<BlockNumber>>,
+    pub head_number: StrView<BlockNumber>,
+    /// The count of the checkpoints at or below the head which match the local main chain.
+    pub verified: u64,
+    pub mismatches: Vec<CheckpointMismatchView>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CheckpointView {
+    pub number: StrView<BlockNumber>,
+    pub block_hash: HashValue,
+    pub state_root: HashValue,
+}
+
+impl From<Checkpoint> for CheckpointView {
+    fn from(checkpoint: Checkpoint) -> Self {
+        Self {
+            number: checkpoint.number.into(),
+            block_hash: checkpoint.block_hash,
+            state_root: checkpoint.state_root,
+        }
+    }
+}
+
+/// A checkpoint which does not match the local main chain block.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CheckpointMismatchView {
+    pub checkpoint: CheckpointView,
+    /// The hash of the local main chain block at the checkpoint number.
+    pub block_hash: HashValue,
+    /// The state root of the local main chain block at the checkpoint number.
+    pub state_root: HashValue,
+}
+
+impl From<CheckpointMismatch> for CheckpointMismatchView {
+    fn from(mismatch: CheckpointMismatch) -> Self {
+        Self {
+            checkpoint: mismatch.checkpoint.into(),
+            block_hash: mismatch.block_hash,
+            state_root: mismatch.state_root,
         }
     }
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    /// The node head is fresh and it connects to enough peers.
+    Ready,
+    /// The node is syncing blocks from the peers.
+    Syncing,
+    /// The node head is stale or it connects to too few peers.
+    Degraded,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct HealthView {
+    pub status: HealthStatus,
+    pub head_block_number: StrView<BlockNumber>,
+    pub head_block_age_secs: u64,
+    pub peers: u64,
+    /// The count of the rpc calls which are in processing.
+    pub pending_rpc_queue: u64,
+}
+
+impl HealthView {
+    /// Evaluate the health status by the thresholds in the config.
+    pub fn evaluate(
+        config: &HealthConfiguration,
+        syncing: bool,
+        head_block_number: BlockNumber,
+        head_block_age_secs: u64,
+        peers: u64,
+        pending_rpc_queue: u64,
+    ) -> Self {
+        let status = if syncing {
+            HealthStatus::Syncing
+        } else if head_block_age_secs >= config.max_head_age() || peers < config.min_peers() {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Ready
+        };
+        Self {
+            status,
+            head_block_number: head_block_number.into(),
+            head_block_age_secs,
+            peers,
+            pending_rpc_queue,
+        }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.status == HealthStatus::Ready
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ServiceHealthView {
+    pub name: String,
+    pub status: ServiceStatus,
+    /// The latency of the service processing a ping message in its mailbox, None if the service is not started or the ping timeout.
+    pub latency_millis: Option<u64>,
+    pub healthy: bool,
+    /// How many consecutive checks the service is unhealthy.
+    pub unhealthy_checks: u32,
+    /// How many times the service is restarted by the watchdog.
+    pub restarts: u32,
+}
+
+/// The report of the last watchdog check.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct WatchdogReport {
+    /// The check time in unix milliseconds.
+    pub timestamp: u64,
+    pub head_block_number: StrView<BlockNumber>,
+    pub head_block_age_secs: u64,
+    /// The block time target of current epoch in milliseconds.
+    pub block_time_target: u64,
+    pub block_stalled: bool,
+    /// The latency of the txpool status query, None if the query timeout.
+    pub txpool_latency_millis: Option<u64>,
+    pub txpool_slow: bool,
+    pub services: Vec<ServiceHealthView>,
+    /// The services restarted by this check.
+    pub restarted: Vec<String>,
+    pub alerts: Vec<String>,
+}
+
+impl WatchdogReport {
+    pub fn is_healthy(&self) -> bool {
+        self.alerts.is_empty()
+    }
+}
+
 #[rpc]
 pub trait NodeApi {
     /// Get node run status, just for api available check.
@@ -49,10 +328,89 @@ pub trait NodeApi {
     #[rpc(name = "node.info")]
     fn info(&self) -> FutureResult<NodeInfo>;
 
-    /// Get current node connect peers.
+    /// Get the readiness of the node, it reads the cached state, so it never waits for the other rpc calls.
+    /// The same check is served by the http `GET /health`, which responds 200 if ready, otherwise 503.
+    #[rpc(name = "node.health")]
+    fn health(&self) -> Result<HealthView>;
+
+    /// Get the report of the last watchdog check, include the block production, txpool latency and the
+    /// mailbox latency of every service. Return None if the watchdog is disabled or has not checked yet.
+    #[rpc(name = "node.watchdog_status")]
+    fn watchdog_status(&self) -> Result<Option<WatchdogReport>>;
+
+    /// Get current node connect peers, and the currently banned peers with `banned_until`.
     #[rpc(name = "node.peers")]
     fn peers(&self) -> FutureResult<Vec<PeerInfoView>>;
 
+    /// Get the seeds resolved from the `network.dns_seeds` by the last resolution,
+    /// the configured seeds are not included.
+    #[rpc(name = "node.discovered_seeds")]
+    fn discovered_seeds(&self) -> FutureResult<Vec<DiscoveredSeed>>;
+
+    /// Get a snapshot of the node metrics, only return the metrics whose name starts with `filter` if present.
     #[rpc(name = "node.metrics")]
-    fn metrics(&self) -> Result<HashMap<String, String>>;
+    fn metrics(&self, filter: Option<String>) -> Result<BTreeMap<String, MetricView>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_health_evaluate() {
+        let config = HealthConfiguration {
+            max_head_age: Some(60),
+            min_peers: Some(1),
+        };
+        let health = HealthView::evaluate(&config, false, 10, 5, 2, 0);
+        assert_eq!(health.status, HealthStatus::Ready);
+        assert!(health.is_ready());
+
+        // stale head
+        let health = HealthView::evaluate(&config, false, 10, 60, 2, 0);
+        assert_eq!(health.status, HealthStatus::Degraded);
+        assert!(!health.is_ready());
+
+        // zero peer
+        let health = HealthView::evaluate(&config, false, 10, 5, 0, 0);
+        assert_eq!(health.status, HealthStatus::Degraded);
+
+        let health = HealthView::evaluate(&config, true, 10, 600, 2, 3);
+        assert_eq!(health.status, HealthStatus::Syncing);
+        assert_eq!(health.pending_rpc_queue, 3);
+        assert_eq!(
+            serde_json::to_value(&health).unwrap()["status"],
+            serde_json::json!("syncing")
+        );
+    }
+
+    #[test]
+    fn test_check_api_versions() {
+        assert!(check_api_versions(&api_versions()).is_empty());
+
+        let mut node_api_versions = api_versions();
+        node_api_versions.insert("chain".to_string(), 6);
+        node_api_versions.remove("pubsub");
+        node_api_versions.insert("contract".to_string(), 1);
+        assert_eq!(
+            check_api_versions(&node_api_versions),
+            vec![
+                ApiVersionMismatch {
+                    namespace: "chain".to_string(),
+                    local_version: Some(5),
+                    node_version: Some(6),
+                },
+                ApiVersionMismatch {
+                    namespace: "contract".to_string(),
+                    local_version: None,
+                    node_version: Some(1),
+                },
+                ApiVersionMismatch {
+                    namespace: "pubsub".to_string(),
+                    local_version: Some(3),
+                    node_version: None,
+                },
+            ]
+        );
+    }
 }
@@ -2,9 +2,20 @@
 // SPDX-License-Identifier: Apache-2
 
 pub use self::gen_client::Client as NodeManagerClient;
+use crate::node::{CheckpointsReport, SystemInfo};
+use crate::types::pubsub::EventFilter;
+use crate::types::WebhookView;
 use crate::FutureResult;
+use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
+use starcoin_config::ConfigReloadResult;
+use starcoin_crypto::HashValue;
 use starcoin_service_registry::{ServiceInfo, ServiceStatus};
+use starcoin_types::event_cursor::EventCursorId;
+use starcoin_types::peer_info::PeerId;
+use starcoin_types::webhook::{WebhookFilter, WebhookRegistration};
+
+pub const DEFAULT_SHUTDOWN_GRACE_SECS: u64 = 5;
 
 #[rpc]
 pub trait NodeManagerApi {
@@ -22,4 +33,76 @@ pub trait NodeManagerApi {
 
     #[rpc(name = "node_manager.shutdown_system")]
     fn shutdown_system(&self) -> FutureResult<()>;
+
+    /// Stop accepting new txns, wait `grace_secs` for the in-flight requests, default is 5 seconds,
+    /// then persist the txpool, flush the storage and stop the node.
+    /// The persisted txns are imported again on the next startup.
+    #[rpc(name = "node_manager.shutdown")]
+    fn shutdown(&self, grace_secs: Option<u64>) -> FutureResult<()>;
+
+    /// Register a webhook, the node POSTs the lifecycle status changes of the transactions matching the filter to the url.
+    /// Return the registration with the generated secret, which is used to sign the payload with HMAC-SHA256.
+    /// The url should not target a private address unless `webhook.allow_private_targets` is enabled,
+    /// and at most `webhook.max_hooks` webhooks can be registered.
+    #[rpc(name = "node_manager.register_webhook")]
+    fn register_webhook(
+        &self,
+        url: String,
+        filter: WebhookFilter,
+    ) -> FutureResult<WebhookRegistration>;
+
+    /// List the registered webhooks, the secrets are not returned.
+    #[rpc(name = "node_manager.list_webhooks")]
+    fn list_webhooks(&self) -> FutureResult<Vec<WebhookView>>;
+
+    /// Remove the webhook registered by rpc.
+    #[rpc(name = "node_manager.remove_webhook")]
+    fn remove_webhook(&self, id: HashValue) -> FutureResult<()>;
+
+    /// Rebuild the token holder index from the state of the main chain head, return the count of the indexed holders.
+    /// It scans the whole state, so it may take a long time on an exist chain, the index is unavailable until it is finished.
+    #[rpc(name = "node_manager.rebuild_token_holder_index")]
+    fn rebuild_token_holder_index(&self) -> FutureResult<u64>;
+
+    /// Create a cursor persisted by the node to consume the events matching the filter by `chain.poll_events`,
+    /// the events after the `cursor` of the filter are delivered. At most `rpc.max_event_cursors` cursors can be created,
+    /// a cursor expires after idle for `rpc.event_cursor_idle_timeout` or `rpc.event_cursor_max_lifetime` after created.
+    #[rpc(name = "node_manager.create_event_cursor")]
+    fn create_event_cursor(&self, filter: EventFilter) -> FutureResult<EventCursorId>;
+
+    /// Add a reserved peer and connect to it, the `multiaddr` should contains the peer id.
+    #[rpc(name = "node_manager.add_peer")]
+    fn add_peer(&self, multiaddr: String) -> FutureResult<()>;
+
+    /// Remove the peer from reserved peers and disconnect it.
+    #[rpc(name = "node_manager.remove_peer")]
+    fn remove_peer(&self, peer_id: PeerId) -> FutureResult<()>;
+
+    /// Disconnect the peer and reject its connection for `duration_secs`, default is one hour.
+    #[rpc(name = "node_manager.ban_peer")]
+    fn ban_peer(&self, peer_id: PeerId, duration_secs: Option<u64>) -> FutureResult<()>;
+
+    /// Get the effective runtime config of the node, the secrets are redacted.
+    #[rpc(name = "node_manager.config")]
+    fn config(&self) -> FutureResult<serde_json::Value>;
+
+    /// Reload the config file and apply the hot reloadable fields, such as log level, miner thread,
+    /// txpool size limits and rpc api quotas, the other changed fields are ignored until restart.
+    #[rpc(name = "node_manager.reload_config")]
+    fn reload_config(&self) -> FutureResult<ConfigReloadResult>;
+
+    /// Enable or disable the rpc call trace at runtime, the calls of the methods which start with
+    /// `method_filter`, or all methods if absent, are logged at info level instead of debug.
+    /// The trace is not persisted, it is disabled after restart.
+    #[rpc(name = "node_manager.trace_rpc")]
+    fn trace_rpc(&self, enable: bool, method_filter: Option<String>) -> Result<()>;
+
+    /// Get the data dir, storage size of each column family, disk usage, and process resource usage of the node.
+    #[rpc(name = "node_manager.system_info")]
+    fn system_info(&self) -> FutureResult<SystemInfo>;
+
+    /// Re-verify the local main chain blocks at or below the head against the checkpoints,
+    /// and report the mismatched checkpoints. It reads the header of every checkpoint from the storage.
+    #[rpc(name = "node_manager.verify_checkpoints")]
+    fn verify_checkpoints(&self) -> FutureResult<CheckpointsReport>;
 }
@@ -8,6 +8,8 @@ use jsonrpc_derive::rpc;
 use jsonrpc_pubsub::{typed, SubscriptionId};
 
 use crate::types::pubsub;
+use crate::types::pubsub::SubscriptionView;
+use crate::FutureResult;
 
 /// Starcoin PUB-SUB rpc interface.
 /// Example:
@@ -42,4 +44,8 @@ pub trait StarcoinPubSub {
         name = "starcoin_unsubscribe"
     )]
     fn unsubscribe(&self, meta: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool>;
+
+    /// List the active subscriptions of the current connection.
+    #[rpc(meta, name = "pubsub.list_subscriptions")]
+    fn list_subscriptions(&self, meta: Self::Metadata) -> FutureResult<Vec<SubscriptionView>>;
 }
@@ -7,34 +7,95 @@ use starcoin_crypto::HashValue;
 use starcoin_types::{
     access_path::AccessPath, account_address::AccountAddress, account_state::AccountState,
 };
+use starcoin_vm_types::identifier::Identifier;
 
 pub use self::gen_client::Client as StateClient;
-use crate::types::{AccountStateSetView, StateWithProofView};
+use crate::types::{
+    AccountAddressView, AccountStateSetView, EncodedView, ListCodeView, ListResourceView,
+    OutputEncoding, StateWithProofView, StrView, StructTagView, TokenCodeView,
+};
 
 #[rpc]
 pub trait StateApi {
     #[rpc(name = "state.get")]
     fn get(&self, access_path: AccessPath) -> FutureResult<Option<Vec<u8>>>;
 
+    /// Return the bcs encoded `(StateWithProof, HashValue)` tuple of the proof and the state root
+    /// it verifies against if `output_encoding` is bcs.
     #[rpc(name = "state.get_with_proof")]
-    fn get_with_proof(&self, access_path: AccessPath) -> FutureResult<StateWithProofView>;
+    fn get_with_proof(
+        &self,
+        access_path: AccessPath,
+        output_encoding: Option<OutputEncoding>,
+    ) -> FutureResult<EncodedView<StateWithProofView>>;
 
     #[rpc(name = "state.get_account_state")]
-    fn get_account_state(&self, address: AccountAddress) -> FutureResult<Option<AccountState>>;
+    fn get_account_state(&self, address: AccountAddressView) -> FutureResult<Option<AccountState>>;
 
     #[rpc(name = "state.get_account_state_set")]
     fn get_account_state_set(
         &self,
-        address: AccountAddress,
+        address: AccountAddressView,
     ) -> FutureResult<Option<AccountStateSetView>>;
 
+    /// Get the state root of the current chain head, pin it to read multiple states of one snapshot
+    /// by the `*_by_root` calls or the `state_root` param.
     #[rpc(name = "state.get_state_root")]
     fn get_state_root(&self) -> FutureResult<HashValue>;
 
+    /// Get the state with proof at the `state_root`,
+    /// return the `StateNotFound` error if the state of the root is pruned.
     #[rpc(name = "state.get_with_proof_by_root")]
     fn get_with_proof_by_root(
         &self,
         access_path: AccessPath,
         state_root: HashValue,
     ) -> FutureResult<StateWithProofView>;
+
+    /// Get the account state at the `state_root`,
+    /// return the `StateNotFound` error if the state of the root is pruned.
+    #[rpc(name = "state.get_account_state_by_root")]
+    fn get_account_state_by_root(
+        &self,
+        address: AccountAddressView,
+        state_root: HashValue,
+    ) -> FutureResult<Option<AccountState>>;
+
+    /// List at most `limit` resources of `address` start from `start`(inclusive) in the key hash order of the state tree,
+    /// `limit` should be greater than 0 and is capped by the server. If `state_root` is None, use the current state root.
+    #[rpc(name = "state.list_resources")]
+    fn list_resources(
+        &self,
+        address: AccountAddressView,
+        start: Option<StructTagView>,
+        limit: u32,
+        decode: bool,
+        state_root: Option<HashValue>,
+    ) -> FutureResult<Option<ListResourceView>>;
+
+    /// List at most `limit` module codes of `address` start from `start`(inclusive) in the key hash order of the state tree,
+    /// `limit` should be greater than 0 and is capped by the server. If `state_root` is None, use the current state root.
+    #[rpc(name = "state.list_code")]
+    fn list_code(
+        &self,
+        address: AccountAddressView,
+        start: Option<Identifier>,
+        limit: u32,
+        state_root: Option<HashValue>,
+    ) -> FutureResult<Option<ListCodeView>>;
+
+    /// List at most `limit` holders with nonzero balance of the token start from address `start`(inclusive),
+    /// sorted by address, `limit` should be greater than 0 and is capped by the server. Require the token holder index is enabled and built.
+    #[rpc(name = "state.list_token_holders")]
+    fn list_token_holders(
+        &self,
+        token_code: TokenCodeView,
+        start: Option<AccountAddressView>,
+        limit: u32,
+    ) -> FutureResult<Vec<(AccountAddress, StrView<u128>)>>;
+
+    /// The count of the holders with nonzero balance of the token.
+    /// Require the token holder index is enabled and built.
+    #[rpc(name = "state.token_holder_count")]
+    fn token_holder_count(&self, token_code: TokenCodeView) -> FutureResult<u64>;
 }
@@ -2,10 +2,11 @@
 // SPDX-License-Identifier: Apache-2
 
 pub use self::gen_client::Client as SyncManagerClient;
+use crate::types::SyncProgressView;
 use crate::FutureResult;
 use jsonrpc_derive::rpc;
 use network_api::PeerStrategy;
-use starcoin_sync_api::{PeerScoreResponse, SyncProgressReport};
+use starcoin_sync_api::PeerScoreResponse;
 use starcoin_types::peer_info::PeerId;
 use starcoin_types::sync_status::SyncStatus;
 
@@ -19,17 +20,18 @@ pub trait SyncManagerApi {
 
     #[rpc(name = "sync.start")]
     /// if `force` is true, will cancel current task and start a new task.
-    /// if peers is not empty, will try sync with the special peers.
+    /// if peers is present and not empty, will try sync with the special peers.
     fn start(
         &self,
         force: bool,
-        peers: Vec<PeerId>,
-        skip_pow_verify: bool,
+        peers: Option<Vec<PeerId>>,
+        skip_pow_verify: Option<bool>,
         strategy: Option<PeerStrategy>,
     ) -> FutureResult<()>;
 
+    /// Get the progress of the running sync task, return None if there is no running task.
     #[rpc(name = "sync.progress")]
-    fn progress(&self) -> FutureResult<Option<SyncProgressReport>>;
+    fn progress(&self) -> FutureResult<Option<SyncProgressView>>;
 
     #[rpc(name = "sync.score")]
     fn peer_score(&self) -> FutureResult<PeerScoreResponse>;
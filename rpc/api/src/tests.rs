@@ -1,6 +1,75 @@
-use crate::types::{ContractCall, TransactionArgumentView, TypeTagView};
-use starcoin_vm_types::token::stc::stc_type_tag;
+use crate::errors::{
+    BlockPrunedData, FieldNotFoundData, InvalidBytecodeData, InvalidTransactionData,
+    RateLimitedData, RpcErrorCode,
+};
+use crate::node::{
+    CheckpointMismatchView, CheckpointsReport, ColumnFamilySizeView, HealthStatus, HealthView,
+    NodeInfo, ServiceHealthView, SystemInfo, WatchdogReport,
+};
+use crate::transfer_export::{
+    export_transfers, TransferDataSource, TransferDirection, TransferExportCheckpoint,
+    TransferRecord,
+};
+use crate::types::pubsub::{EventFilter, MintBlock};
+use crate::types::{
+    AnnotatedMoveValueView, BalanceView, BlockHeaderView, BlockInfoView, BlockRewardView,
+    BlockView, BranchView, ChainStatusView, ContractCall, EncodedView, EventHandleView,
+    EventPositionView, EventWithProofView, GasBreakdownView, GasChargeView, GasPriceOracleView,
+    GasPricePercentilesView, HistogramSummaryView, MetricView, MintBlockTemplateView, PeerInfoView,
+    ReceiptIdentifierView, SignedUserTransactionView, StateWithProofView, StrView,
+    SyncProgressView, TokenInfoView, TransactionArgumentView, TransactionEventView,
+    TransactionInfoView, TransactionOutputView, TransactionProofView, TransactionRequest,
+    TransactionVMStatus, TransactionView, TxPoolStatusView, TxPoolSummaryView, TxnDiagnosisView,
+    TxnHistoryView, TxnLifecycleStatus, TxnPositionView, TxnVerdict, TypeTagView, WebhookPayload,
+    WriteOpSizeView,
+};
+use anyhow::Result;
+use bcs_ext::Sample;
+use starcoin_account_api::TxnHistoryStatus;
+use starcoin_config::ChainNetworkID;
+use starcoin_crypto::ed25519::genesis_key_pair;
+use starcoin_crypto::HashValue;
+use starcoin_metrics::{Histogram, HistogramOpts, IntCounter, IntGauge, Registry};
+use starcoin_resource_viewer::{AnnotatedMoveStruct, AnnotatedMoveValue};
+use starcoin_service_registry::ServiceStatus;
+use starcoin_state_api::{ChainStateReader, ChainStateWriter};
+use starcoin_state_tree::mock::MockStateNodeStore;
+use starcoin_statedb::ChainStateDB;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::{DepositEvent, WithdrawEvent};
+use starcoin_types::block::{
+    AccumulatorInfo, Block, BlockBody, BlockHeader, BlockHeaderExtra, BlockInfo, BlockNumber,
+};
+use starcoin_types::checkpoint::Checkpoint;
+use starcoin_types::contract_event::ContractEvent;
+use starcoin_types::event::EventKey;
+use starcoin_types::filter::EventPosition;
+use starcoin_types::genesis_config::{ChainId, ConsensusStrategy};
+use starcoin_types::language_storage::TypeTag;
+use starcoin_types::peer_info::{PeerId, PeerInfo};
+use starcoin_types::proof::AccumulatorProof;
+use starcoin_types::startup_info::{ChainInfo, ChainStatus};
+use starcoin_types::transaction::authenticator::AuthenticationKey;
+use starcoin_types::transaction::{
+    BlockTransactionInfo, RawUserTransaction, Script, SignedUserTransaction, TransactionInfo,
+    TransactionPayload, TransactionPosition,
+};
+use starcoin_types::vm_error::AbortLocation;
+use starcoin_types::U256;
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::account_config::BalanceResource;
+use starcoin_vm_types::file_format::AbilitySet;
+use starcoin_vm_types::identifier::Identifier;
+use starcoin_vm_types::language_storage::{StructTag, CORE_CODE_ADDRESS};
+use starcoin_vm_types::move_resource::MoveResource;
+use starcoin_vm_types::token::stc::{stc_type_tag, STC_TOKEN_CODE};
+use starcoin_vm_types::token::token_code::TokenCode;
 use starcoin_vm_types::transaction_argument::TransactionArgument;
+use starcoin_vm_types::write_set::{WriteOp, WriteSetMut};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str::FromStr;
+use std::sync::Arc;
 
 #[test]
 fn test_view_of_type_tag() {
@@ -32,3 +101,1199 @@ fn test_deserialize() {
     let v = serde_json::from_str::<ContractCall>(s).unwrap();
     println!("{:?}", v);
 }
+
+#[test]
+fn test_state_with_proof_view() {
+    let chain_state_db = ChainStateDB::new(Arc::new(MockStateNodeStore::new()), None);
+    let access_path = AccessPath::random_resource();
+    let write_set = WriteSetMut::new(vec![(
+        access_path.clone(),
+        WriteOp::Value(HashValue::random().to_vec()),
+    )])
+    .freeze()
+    .unwrap();
+    chain_state_db.apply_write_set(write_set).unwrap();
+    let state_root = chain_state_db.commit().unwrap();
+
+    // membership
+    let view = StateWithProofView::new(
+        chain_state_db.get_with_proof(&access_path).unwrap(),
+        state_root,
+    );
+    assert!(view.state.is_some());
+    let s = serde_json::to_string(&view).unwrap();
+    let view: StateWithProofView = serde_json::from_str(s.as_str()).unwrap();
+    assert_eq!(view.state_root, state_root);
+    view.verify(&access_path).unwrap();
+    assert!(view.verify(&AccessPath::random_resource()).is_err());
+
+    // non-membership
+    let not_exist_path = AccessPath::random_resource();
+    let view = StateWithProofView::new(
+        chain_state_db.get_with_proof(&not_exist_path).unwrap(),
+        state_root,
+    );
+    assert!(view.state.is_none());
+    let s = serde_json::to_string(&view).unwrap();
+    let view: StateWithProofView = serde_json::from_str(s.as_str()).unwrap();
+    assert!(view.state.is_none());
+    view.verify(&not_exist_path).unwrap();
+}
+
+#[test]
+fn test_block_view_with_block_info() {
+    let block_info = BlockInfo::new(
+        HashValue::random(),
+        U256::from(u64::max_value()) * U256::from(1024u64),
+        AccumulatorInfo::new(HashValue::random(), vec![HashValue::random()], 1, 1),
+        AccumulatorInfo::new(
+            HashValue::random(),
+            vec![HashValue::random(), HashValue::random()],
+            3,
+            4,
+        ),
+    );
+    let view: BlockInfoView = block_info.clone().into();
+    let s = serde_json::to_string(&view).unwrap();
+    let view2: BlockInfoView = serde_json::from_str(s.as_str()).unwrap();
+    assert_eq!(view, view2);
+    assert_eq!(BlockInfo::from(view2), block_info);
+
+    let block_view = BlockView::try_from_block(Block::sample(), false).unwrap();
+    let value = serde_json::to_value(&block_view).unwrap();
+    assert!(value.get("block_info").is_none());
+    let block_view2: BlockView = serde_json::from_value(value).unwrap();
+    assert_eq!(block_view, block_view2);
+
+    let block_view = block_view.with_block_info(Some(block_info));
+    let s = serde_json::to_string(&block_view).unwrap();
+    let block_view2: BlockView = serde_json::from_str(s.as_str()).unwrap();
+    assert_eq!(block_view, block_view2);
+    assert_eq!(block_view2.block_info, Some(view));
+}
+
+/// The max integer which a json number can hold without losing precision in javascript.
+const MAX_SAFE_JSON_INTEGER: u64 = (1 << 53) - 1;
+
+/// Collect the json numbers which may lose precision in javascript.
+fn unsafe_json_numbers(value: &serde_json::Value, path: String, unsafe_numbers: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Number(n) => {
+            let safe = match (n.as_u64(), n.as_i64()) {
+                (Some(v), _) => v <= MAX_SAFE_JSON_INTEGER,
+                (_, Some(v)) => v.unsigned_abs() <= MAX_SAFE_JSON_INTEGER,
+                // the float fields are floats in javascript too.
+                _ => n.is_f64(),
+            };
+            if !safe {
+                unsafe_numbers.push(format!("{}: {}", path, n));
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for (i, value) in values.iter().enumerate() {
+                unsafe_json_numbers(value, format!("{}[{}]", path, i), unsafe_numbers);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                unsafe_json_numbers(value, format!("{}.{}", path, key), unsafe_numbers);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn assert_no_unsafe_json_number<T: serde::Serialize>(name: &str, view: &T) {
+    assert_no_unsafe_json_number_except(name, view, &[]);
+}
+
+/// Like `assert_no_unsafe_json_number`, but the `bounded_fields` may be json numbers, their values
+/// are far below 2^53 in practice: the counts and sizes measured by the node process,
+/// the durations, and the times of the node clock.
+/// The values from the chain or the user, such as amounts, gas, sequence numbers, block numbers
+/// and the txn expiration, should always be serialized by StrView.
+fn assert_no_unsafe_json_number_except<T: serde::Serialize>(
+    name: &str,
+    view: &T,
+    bounded_fields: &[&str],
+) {
+    let value = serde_json::to_value(view).unwrap();
+    let mut unsafe_numbers = vec![];
+    unsafe_json_numbers(&value, name.to_string(), &mut unsafe_numbers);
+    unsafe_numbers.retain(|number| {
+        let path = number.split(':').next().unwrap();
+        let field = path.rsplit('.').next().unwrap().split('[').next().unwrap();
+        !bounded_fields.contains(&field)
+    });
+    assert!(
+        unsafe_numbers.is_empty(),
+        "the integers should be serialized by StrView: {:?}",
+        unsafe_numbers
+    );
+}
+
+/// A signed txn with the max sequence number, gas and expiration.
+fn max_signed_txn_view() -> SignedUserTransactionView {
+    let (private_key, public_key) = genesis_key_pair();
+    let raw_txn = RawUserTransaction::new_with_default_gas_token(
+        AccountAddress::random(),
+        u64::MAX,
+        TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+        u64::MAX,
+        u64::MAX,
+        u64::MAX,
+        ChainId::test(),
+    );
+    SignedUserTransactionView::try_from(
+        raw_txn.sign(&private_key, public_key).unwrap().into_inner(),
+    )
+    .unwrap()
+}
+
+fn max_event_view() -> TransactionEventView {
+    let mut event = TransactionEventView::new(
+        Some(HashValue::random()),
+        Some(u64::MAX),
+        Some(HashValue::random()),
+        Some(u32::MAX),
+        &ContractEvent::new(
+            EventKey::new_from_address(&AccountAddress::random(), u64::MAX),
+            u64::MAX,
+            stc_type_tag(),
+            vec![],
+        ),
+    );
+    event.event_index = Some(u32::MAX);
+    event.global_index = Some(StrView(u128::MAX));
+    event
+}
+
+#[test]
+fn test_views_no_unsafe_json_number() {
+    let token_info = TokenInfoView {
+        token_code: STC_TOKEN_CODE.to_string(),
+        symbol: STC_TOKEN_CODE.name.clone(),
+        scaling_factor: StrView(u128::MAX),
+        total_supply: StrView(u128::MAX),
+    };
+    assert_no_unsafe_json_number("TokenInfoView", &token_info);
+    assert_no_unsafe_json_number(
+        "BalanceView",
+        &BalanceView {
+            amount: StrView(u128::MAX),
+            token_info,
+        },
+    );
+    assert_no_unsafe_json_number(
+        "BlockRewardView",
+        &BlockRewardView {
+            block_number: StrView(u64::MAX),
+            block_hash: HashValue::random(),
+            miner: AccountAddress::random(),
+            total_reward: StrView(u128::MAX),
+            base_reward: StrView(u128::MAX),
+            uncle_inclusion_reward: StrView(u128::MAX),
+            txn_fees: StrView(u128::MAX),
+            gas_used: StrView(u64::MAX),
+        },
+    );
+    assert_no_unsafe_json_number(
+        "MintBlock",
+        &MintBlock {
+            strategy: ConsensusStrategy::CryptoNight,
+            minting_blob: hex::encode(vec![0u8; 76]),
+            difficulty: StrView(U256::max_value()),
+            block_number: StrView(u64::MAX),
+        },
+    );
+    assert_no_unsafe_json_number(
+        "MintBlockTemplateView",
+        &MintBlockTemplateView {
+            parent_hash: HashValue::random(),
+            minting_blob: StrView(vec![0u8; 76]),
+            difficulty: StrView(U256::max_value()),
+            block_number: StrView(u64::MAX),
+            strategy: ConsensusStrategy::CryptoNight,
+        },
+    );
+
+    let header = BlockHeader::new(
+        HashValue::random(),
+        u64::MAX,
+        u64::MAX,
+        AccountAddress::random(),
+        None,
+        HashValue::random(),
+        HashValue::random(),
+        HashValue::random(),
+        u64::MAX,
+        U256::max_value(),
+        HashValue::random(),
+        ChainId::test(),
+        u32::MAX,
+        BlockHeaderExtra::new([u8::MAX; 4]),
+    );
+    let block_info = BlockInfo::new(
+        header.id(),
+        U256::max_value(),
+        AccumulatorInfo::new(HashValue::random(), vec![], u64::MAX, u64::MAX),
+        AccumulatorInfo::new(HashValue::random(), vec![], u64::MAX, u64::MAX),
+    );
+    assert_no_unsafe_json_number("BlockInfoView", &BlockInfoView::from(block_info.clone()));
+    let block_view = BlockView::try_from_block(
+        Block::new(
+            header.clone(),
+            BlockBody::new(vec![], Some(vec![header.clone()])),
+        ),
+        false,
+    )
+    .unwrap()
+    .with_block_info(Some(block_info.clone()));
+    assert_no_unsafe_json_number("BlockView", &block_view);
+    assert_no_unsafe_json_number(
+        "BranchView",
+        &BranchView {
+            head: header.clone().into(),
+            total_difficulty: StrView(U256::max_value()),
+            length_from_common_ancestor: StrView(u64::MAX),
+        },
+    );
+
+    for value in vec![
+        AnnotatedMoveValueView::U64(StrView(u64::MAX)),
+        AnnotatedMoveValueView::U128(StrView(u128::MAX)),
+        AnnotatedMoveValueView::U256(StrView(U256::max_value())),
+    ] {
+        assert_no_unsafe_json_number("AnnotatedMoveValueView", &value);
+    }
+
+    // the txn and event views.
+    let txn = max_signed_txn_view();
+    assert_no_unsafe_json_number("SignedUserTransactionView", &txn);
+    let request = TransactionRequest {
+        sender: Some(AccountAddress::random().into()),
+        sequence_number: Some(StrView(u64::MAX)),
+        max_gas_amount: Some(StrView(u64::MAX)),
+        gas_unit_price: Some(StrView(u64::MAX)),
+        expiration_timestamp_secs: Some(StrView(u64::MAX)),
+        chain_id: Some(u8::MAX),
+        ..Default::default()
+    };
+    assert_no_unsafe_json_number("TransactionRequest", &request);
+    assert_no_unsafe_json_number(
+        "TxnHistoryView",
+        &TxnHistoryView {
+            txn: txn.clone(),
+            status: TxnHistoryStatus::Mined,
+            recorded_at: StrView(u64::MAX),
+            block_hash: Some(HashValue::random()),
+            block_number: Some(StrView(u64::MAX)),
+        },
+    );
+    let position = TransactionPosition {
+        block_id: HashValue::random(),
+        block_number: u64::MAX,
+        transaction_index: u32::MAX,
+        global_index: u64::MAX,
+    };
+    assert_no_unsafe_json_number("TxnPositionView", &TxnPositionView::from(position));
+    let txn_info = TransactionInfoView::new(
+        BlockTransactionInfo::new(position.block_id, TransactionInfo::sample()),
+        &position,
+    )
+    .unwrap();
+    assert_no_unsafe_json_number("TransactionInfoView", &txn_info);
+    let event = max_event_view();
+    assert_no_unsafe_json_number("TransactionEventView", &event);
+    assert_no_unsafe_json_number(
+        "EventPositionView",
+        &EventPositionView::from(event.position().unwrap()),
+    );
+    assert_no_unsafe_json_number_except(
+        "TransactionProofView",
+        &TransactionProofView {
+            transaction_info: txn_info,
+            leaf_index: StrView(u64::MAX),
+            proof: AccumulatorProof::new(vec![]),
+            event_proof: Some(EventWithProofView {
+                event: event.clone(),
+                event_index: u64::MAX,
+                proof: AccumulatorProof::new(vec![]),
+            }),
+        },
+        &["event_index"],
+    );
+    assert_no_unsafe_json_number(
+        "EventFilter",
+        &EventFilter {
+            from_block: Some(StrView(u64::MAX)),
+            to_block: Some(StrView(u64::MAX)),
+            cursor: event.position().map(Into::into),
+            ..Default::default()
+        },
+    );
+    for status in vec![
+        TransactionVMStatus::MoveAbort {
+            location: AbortLocation::Script,
+            abort_code: StrView(u64::MAX),
+        },
+        TransactionVMStatus::Discard {
+            status_code: StrView(u64::MAX),
+        },
+    ] {
+        assert_no_unsafe_json_number("TransactionVMStatus", &status);
+    }
+    assert_no_unsafe_json_number_except(
+        "TransactionOutputView",
+        &TransactionOutputView {
+            events: vec![event],
+            gas_used: StrView(u64::MAX),
+            status: TransactionVMStatus::Executed,
+            write_set: vec![],
+            verified: Some(true),
+            gas_breakdown: Some(GasBreakdownView {
+                intrinsic_gas: StrView(u64::MAX),
+                instruction_gas: StrView(u64::MAX),
+                storage_write_gas: StrView(u64::MAX),
+                instructions: vec![GasChargeView::from(("MoveTo".to_string(), u64::MAX))],
+                natives: vec![GasChargeView::from(("PUSH_BACK".to_string(), u64::MAX))],
+                storage_reads: u64::MAX,
+                storage_read_bytes: u64::MAX,
+                storage_writes: u64::MAX,
+                storage_write_bytes: u64::MAX,
+                write_ops: vec![WriteOpSizeView {
+                    access_path: AccessPath::resource_access_path(
+                        AccountAddress::random(),
+                        BalanceResource::struct_tag_for_token(stc_type_tag()),
+                    )
+                    .into(),
+                    size: Some(u64::MAX),
+                }],
+            }),
+        },
+        &[
+            "storage_reads",
+            "storage_read_bytes",
+            "storage_writes",
+            "storage_write_bytes",
+            "size",
+        ],
+    );
+
+    // the txpool views.
+    let txpool_counts = [
+        "pending",
+        "queued",
+        "senders",
+        "mem_usage_bytes",
+        "now_seconds",
+    ];
+    for verdict in vec![
+        TxnVerdict::Expired {
+            expiration_timestamp_secs: StrView(u64::MAX),
+            now_seconds: u64::MAX,
+        },
+        TxnVerdict::SequenceGap {
+            onchain_sequence_number: StrView(u64::MAX),
+            missing_count: StrView(u64::MAX),
+            missing_sequence_numbers: vec![StrView(u64::MAX)],
+        },
+        TxnVerdict::InsufficientBalance {
+            balance: StrView(u128::MAX),
+            max_gas_cost: StrView(u128::MAX),
+        },
+        TxnVerdict::GasPriceTooLow {
+            gas_unit_price: StrView(u64::MAX),
+            recent_min_gas_price: StrView(u64::MAX),
+        },
+    ] {
+        assert_no_unsafe_json_number_except(
+            "TxnDiagnosisView",
+            &TxnDiagnosisView {
+                txn_hash: Some(HashValue::random()),
+                sender: Some(AccountAddress::random().into()),
+                sequence_number: Some(StrView(u64::MAX)),
+                verdict,
+            },
+            &txpool_counts,
+        );
+    }
+    assert_no_unsafe_json_number_except(
+        "TxPoolStatusView",
+        &TxPoolStatusView {
+            pending: u64::MAX,
+            queued: u64::MAX,
+            senders: u64::MAX,
+            mem_usage_bytes: u64::MAX,
+            min_gas_price_to_enter: StrView(u64::MAX),
+            gas_price_bump_percent: StrView(u64::MAX),
+        },
+        &txpool_counts,
+    );
+    assert_no_unsafe_json_number(
+        "GasPriceOracleView",
+        &GasPriceOracleView {
+            last_n_blocks: u32::MAX,
+            percentiles: GasPricePercentilesView {
+                p25: StrView(u64::MAX),
+                p50: StrView(u64::MAX),
+                p90: StrView(u64::MAX),
+            },
+            pool_min_to_enter: StrView(u64::MAX),
+        },
+    );
+    assert_no_unsafe_json_number(
+        "WebhookPayload",
+        &WebhookPayload {
+            webhook_id: HashValue::random(),
+            transaction_hash: HashValue::random(),
+            status: TxnLifecycleStatus::Included {
+                block_number: StrView(u64::MAX),
+                block_hash: HashValue::random(),
+            },
+            transaction: Some(txn),
+            timestamp: StrView(u64::MAX),
+        },
+    );
+
+    // the chain status views.
+    let sync_counts = ["peers", "eta_seconds", "inflight_fetches", "inflight_peers"];
+    let mut chain_status = ChainStatusView::new(
+        ChainInfo::new(
+            ChainId::test(),
+            HashValue::random(),
+            ChainStatus::new(header, block_info),
+        ),
+        u64::MAX,
+    );
+    chain_status.txpool = Some(TxPoolSummaryView {
+        pending: u64::MAX,
+        queued: u64::MAX,
+    });
+    chain_status.sync = Some(SyncProgressView {
+        target_id: HashValue::random(),
+        target_number: StrView(u64::MAX),
+        begin_number: Some(StrView(u64::MAX)),
+        current_number: StrView(u64::MAX),
+        applied_blocks: StrView(u64::MAX),
+        blocks_per_second: f64::MAX,
+        peers: u64::MAX,
+        eta_seconds: Some(u64::MAX),
+        percent: Some(100.0),
+        inflight_fetches: u64::MAX,
+        inflight_peers: vec![(PeerId::random(), u64::MAX)],
+        bad_peers: vec![],
+    });
+    chain_status.peers = Some(u32::MAX);
+    let mut chain_status_bounded_fields = txpool_counts.to_vec();
+    chain_status_bounded_fields.extend_from_slice(&sync_counts);
+    assert_no_unsafe_json_number_except(
+        "ChainStatusView",
+        &chain_status,
+        &chain_status_bounded_fields,
+    );
+    assert_no_unsafe_json_number_except(
+        "PeerInfoView",
+        &PeerInfoView::from(PeerInfo::random()).with_banned_until(Some(u64::MAX)),
+        &["banned_until"],
+    );
+
+    // the node views.
+    assert_no_unsafe_json_number_except(
+        "SystemInfo",
+        &SystemInfo {
+            storage: vec![(
+                "block".to_string(),
+                ColumnFamilySizeView {
+                    sst_files_size: u64::MAX,
+                    live_data_size: u64::MAX,
+                    mem_tables_size: u64::MAX,
+                    estimate_num_keys: u64::MAX,
+                },
+            )]
+            .into_iter()
+            .collect(),
+            storage_size: u64::MAX,
+            disk_total: Some(u64::MAX),
+            disk_free: Some(u64::MAX),
+            disk_used: Some(u64::MAX),
+            open_fds: Some(u64::MAX),
+            rss: Some(u64::MAX),
+            uptime_secs: Some(u64::MAX),
+            pruned_before: StrView(u64::MAX),
+            pruned_bytes: u64::MAX,
+            ..Default::default()
+        },
+        &[
+            "sst_files_size",
+            "live_data_size",
+            "mem_tables_size",
+            "estimate_num_keys",
+            "storage_size",
+            "disk_total",
+            "disk_free",
+            "disk_used",
+            "open_fds",
+            "rss",
+            "uptime_secs",
+            "pruned_bytes",
+        ],
+    );
+    let checkpoint = Checkpoint::new(u64::MAX, HashValue::random(), HashValue::random());
+    assert_no_unsafe_json_number_except(
+        "CheckpointsReport",
+        &CheckpointsReport {
+            latest_checkpoint: Some(StrView(u64::MAX)),
+            head_number: StrView(u64::MAX),
+            verified: u64::MAX,
+            mismatches: vec![CheckpointMismatchView {
+                checkpoint: checkpoint.into(),
+                block_hash: HashValue::random(),
+                state_root: HashValue::random(),
+            }],
+        },
+        &["verified"],
+    );
+    let health_counts = ["head_block_age_secs", "peers", "pending_rpc_queue"];
+    assert_no_unsafe_json_number_except(
+        "HealthView",
+        &HealthView {
+            status: HealthStatus::Ready,
+            head_block_number: StrView(u64::MAX),
+            head_block_age_secs: u64::MAX,
+            peers: u64::MAX,
+            pending_rpc_queue: u64::MAX,
+        },
+        &health_counts,
+    );
+    assert_no_unsafe_json_number_except(
+        "WatchdogReport",
+        &WatchdogReport {
+            timestamp: u64::MAX,
+            head_block_number: StrView(u64::MAX),
+            head_block_age_secs: u64::MAX,
+            block_time_target: u64::MAX,
+            block_stalled: false,
+            txpool_latency_millis: Some(u64::MAX),
+            txpool_slow: false,
+            services: vec![ServiceHealthView {
+                name: "test".to_string(),
+                status: ServiceStatus::Started,
+                latency_millis: Some(u64::MAX),
+                healthy: true,
+                unhealthy_checks: u32::MAX,
+                restarts: u32::MAX,
+            }],
+            restarted: vec![],
+            alerts: vec![],
+        },
+        &[
+            "timestamp",
+            "head_block_age_secs",
+            "block_time_target",
+            "txpool_latency_millis",
+            "latency_millis",
+        ],
+    );
+    let mut node_info = NodeInfo::new(
+        PeerInfoView::from(PeerInfo::random()),
+        "/ip4/127.0.0.1/tcp/9840".to_string(),
+        ChainNetworkID::TEST,
+        ConsensusStrategy::Dummy,
+        u64::MAX,
+    );
+    node_info.build.build_time = u64::MAX;
+    assert_no_unsafe_json_number_except("NodeInfo", &node_info, &["now_seconds", "build_time"]);
+    assert_no_unsafe_json_number_except(
+        "HistogramSummaryView",
+        &HistogramSummaryView {
+            count: u64::MAX,
+            sum: f64::MAX,
+            p50: f64::MAX,
+            p90: f64::MAX,
+            p99: f64::MAX,
+        },
+        &["count"],
+    );
+
+    // the error data.
+    assert_no_unsafe_json_number(
+        "BlockPrunedData",
+        &BlockPrunedData {
+            number: StrView(u64::MAX),
+            pruned_before: StrView(u64::MAX),
+        },
+    );
+    for data in vec![
+        InvalidTransactionData::MaxGasAmountExceeded {
+            max_gas_amount: StrView(u64::MAX),
+            block_gas_limit: StrView(u64::MAX),
+        },
+        InvalidTransactionData::GasPriceTooLow {
+            gas_unit_price: StrView(u64::MAX),
+            min_gas_price: StrView(u64::MAX),
+        },
+        InvalidTransactionData::Expired {
+            expiration_timestamp_secs: StrView(u64::MAX),
+            now_seconds: u64::MAX,
+        },
+        InvalidTransactionData::InsufficientBalance {
+            gas_token_code: STC_TOKEN_CODE.to_string(),
+            balance: StrView(u128::MAX),
+            max_gas_cost: StrView(u128::MAX),
+        },
+    ] {
+        assert_no_unsafe_json_number_except("InvalidTransactionData", &data, &["now_seconds"]);
+    }
+    assert_no_unsafe_json_number_except(
+        "RateLimitedData",
+        &RateLimitedData {
+            retry_after_ms: u64::MAX,
+        },
+        &["retry_after_ms"],
+    );
+    assert_no_unsafe_json_number_except(
+        "InvalidBytecodeData",
+        &InvalidBytecodeData {
+            status_code: Some(u64::MAX),
+        },
+        &["status_code"],
+    );
+}
+
+#[test]
+fn test_str_view_accepts_legacy_number() {
+    let view: StrView<u128> = serde_json::from_str(u128::MAX.to_string().as_str()).unwrap();
+    assert_eq!(view.0, u128::MAX);
+    let view: StrView<u64> = serde_json::from_str("1024").unwrap();
+    assert_eq!(view.0, 1024);
+    let view: StrView<i128> = serde_json::from_str("-1024").unwrap();
+    assert_eq!(view.0, -1024);
+    let view: StrView<u64> = serde_json::from_str("\"1024\"").unwrap();
+    assert_eq!(view.0, 1024);
+    assert!(serde_json::from_str::<StrView<u64>>("-1").is_err());
+    assert!(serde_json::from_str::<StrView<u64>>("1.5").is_err());
+
+    let token_info: TokenInfoView = serde_json::from_value(serde_json::json!({
+        "token_code": "0x1::STC::STC",
+        "symbol": "STC",
+        "scaling_factor": 1_000_000_000u64,
+        "total_supply": 3_185_136_000_000_000_000u64,
+    }))
+    .unwrap();
+    assert_eq!(token_info.scaling_factor.0, 1_000_000_000);
+    assert_eq!(token_info.total_supply.0, 3_185_136_000_000_000_000);
+    // the bcs encoding still uses the string.
+    let bytes = bcs_ext::to_bytes(&StrView(u128::MAX)).unwrap();
+    let view: StrView<u128> = bcs_ext::from_bytes(bytes.as_slice()).unwrap();
+    assert_eq!(view.0, u128::MAX);
+}
+
+#[test]
+fn test_metric_view() {
+    let registry = Registry::new();
+    let counter = starcoin_metrics::register(
+        IntCounter::new("test_metric_counter", "test counter").unwrap(),
+        &registry,
+    )
+    .unwrap();
+    let gauge = starcoin_metrics::register(
+        IntGauge::new("test_metric_gauge", "test gauge").unwrap(),
+        &registry,
+    )
+    .unwrap();
+    let histogram = starcoin_metrics::register(
+        Histogram::with_opts(
+            HistogramOpts::new("other_histogram", "test histogram")
+                .buckets(vec![1.0, 2.0, 4.0, 8.0]),
+        )
+        .unwrap(),
+        &registry,
+    )
+    .unwrap();
+    counter.inc_by(3);
+    gauge.set(-2);
+    for v in &[0.5, 1.5, 1.5, 3.0] {
+        histogram.observe(*v);
+    }
+    let metric_families = registry.gather();
+
+    let metrics = MetricView::from_metric_families(&metric_families, None);
+    assert_eq!(metrics.len(), 3);
+    assert_eq!(
+        metrics.get("test_metric_counter"),
+        Some(&MetricView::Counter { value: 3.0 })
+    );
+    assert_eq!(
+        metrics.get("test_metric_gauge"),
+        Some(&MetricView::Gauge { value: -2.0 })
+    );
+    match metrics.get("other_histogram") {
+        Some(MetricView::Histogram(summary)) => {
+            assert_eq!(summary.count, 4);
+            assert!((summary.sum - 6.5).abs() < f64::EPSILON);
+            assert!((summary.p50 - 1.5).abs() < 1e-9);
+            assert!((summary.p90 - 3.2).abs() < 1e-9);
+            assert!((summary.p99 - 3.92).abs() < 1e-9);
+        }
+        other => panic!("Unexpected metric {:?}", other),
+    }
+    let value = serde_json::to_value(&metrics).unwrap();
+    assert_eq!(value["test_metric_counter"]["type"], "counter");
+    let metrics2 = serde_json::from_value(value).unwrap();
+    assert_eq!(metrics, metrics2);
+
+    let metrics = MetricView::from_metric_families(&metric_families, Some("test_metric"));
+    assert_eq!(metrics.len(), 2);
+    assert!(metrics.get("other_histogram").is_none());
+}
+
+#[test]
+fn test_encoded_view() {
+    let block = Block::sample();
+    let block_view = BlockView::try_from_block(block.clone(), false).unwrap();
+    let json_view = EncodedView::Json(block_view.clone());
+    // the json output is same as the view itself.
+    assert_eq!(
+        serde_json::to_value(&json_view).unwrap(),
+        serde_json::to_value(&block_view).unwrap()
+    );
+    let s = serde_json::to_string(&json_view).unwrap();
+    let json_view2: EncodedView<BlockView> = serde_json::from_str(s.as_str()).unwrap();
+    assert_eq!(json_view2.into_json().unwrap(), block_view);
+
+    let bcs_view = EncodedView::<BlockView>::bcs(&block).unwrap();
+    let s = serde_json::to_string(&bcs_view).unwrap();
+    let bcs_view2: EncodedView<BlockView> = serde_json::from_str(s.as_str()).unwrap();
+    assert_eq!(bcs_view2.decode_bcs::<Block>().unwrap(), block);
+    assert!(bcs_view2.into_json().is_err());
+}
+
+#[test]
+fn test_annotated_move_value_view() {
+    let address = AccountAddress::random();
+    let cases = vec![
+        (
+            AnnotatedMoveValueView::U16(u16::MAX),
+            serde_json::json!({ "U16": 65535 }),
+        ),
+        (
+            AnnotatedMoveValueView::U32(u32::MAX),
+            serde_json::json!({ "U32": 4294967295u32 }),
+        ),
+        (
+            AnnotatedMoveValueView::U256(StrView(U256::max_value())),
+            serde_json::json!({ "U256": format!("0x{:x}", U256::max_value()) }),
+        ),
+        (
+            AnnotatedMoveValueView::Signer(address),
+            serde_json::json!({ "Signer": serde_json::to_value(address).unwrap() }),
+        ),
+        // the exist variants keep the layout.
+        (
+            AnnotatedMoveValueView::U128(StrView(u128::MAX)),
+            serde_json::json!({ "U128": u128::MAX.to_string() }),
+        ),
+    ];
+    for (view, json) in cases {
+        assert_eq!(serde_json::to_value(&view).unwrap(), json);
+        let s = serde_json::to_string(&view).unwrap();
+        assert_eq!(
+            serde_json::from_str::<AnnotatedMoveValueView>(s.as_str()).unwrap(),
+            view
+        );
+    }
+    // the decimal u256 is accepted too.
+    assert_eq!(
+        serde_json::from_value::<AnnotatedMoveValueView>(serde_json::json!({ "U256": "1024" }))
+            .unwrap(),
+        AnnotatedMoveValueView::U256(StrView(U256::from(1024u64)))
+    );
+    assert_eq!(
+        AnnotatedMoveValueView::from(AnnotatedMoveValue::Signer(address)),
+        AnnotatedMoveValueView::Signer(address)
+    );
+}
+
+struct MockTransferDataSource {
+    events: Vec<TransactionEventView>,
+    senders: HashMap<HashValue, AccountAddress>,
+}
+
+impl MockTransferDataSource {
+    fn add_txn(
+        &mut self,
+        block_number: BlockNumber,
+        transaction_index: u32,
+        sender: AccountAddress,
+        transfers: Vec<(AccountAddress, bool, u128)>,
+    ) -> HashValue {
+        let txn_hash = HashValue::random();
+        self.senders.insert(txn_hash, sender);
+        for (event_index, (address, deposit, amount)) in transfers.into_iter().enumerate() {
+            let (type_tag, data) = if deposit {
+                let event = DepositEvent::new(amount, STC_TOKEN_CODE.clone(), vec![]);
+                (
+                    DepositEvent::struct_tag(),
+                    bcs_ext::to_bytes(&event).unwrap(),
+                )
+            } else {
+                let event = WithdrawEvent::new(amount, STC_TOKEN_CODE.clone(), vec![]);
+                (
+                    WithdrawEvent::struct_tag(),
+                    bcs_ext::to_bytes(&event).unwrap(),
+                )
+            };
+            let event = ContractEvent::new(
+                EventKey::new_from_address(&address, if deposit { 0 } else { 1 }),
+                0,
+                TypeTag::Struct(type_tag),
+                data,
+            );
+            let mut view = TransactionEventView::from(event);
+            view.block_number = Some(block_number.into());
+            view.transaction_hash = Some(txn_hash);
+            view.transaction_index = Some(transaction_index);
+            view.event_index = Some(event_index as u32);
+            self.events.push(view);
+        }
+        txn_hash
+    }
+}
+
+impl TransferDataSource for MockTransferDataSource {
+    fn get_events(&self, filter: EventFilter) -> Result<Vec<TransactionEventView>> {
+        Ok(self
+            .events
+            .iter()
+            .filter(|event| {
+                let position = EventPosition {
+                    block_number: event.block_number.unwrap().0,
+                    transaction_index: event.transaction_index.unwrap(),
+                    event_index: event.event_index.unwrap(),
+                };
+                position.block_number >= filter.from_block.unwrap().0
+                    && position.block_number <= filter.to_block.unwrap().0
+                    && filter
+                        .cursor
+                        .map(|cursor| position > cursor.into())
+                        .unwrap_or(true)
+                    && filter
+                        .addrs
+                        .contains(&event.event_key.get_creator_address())
+            })
+            .take(filter.limit.unwrap())
+            .cloned()
+            .collect())
+    }
+
+    fn get_events_by_txn_hash(&self, txn_hash: HashValue) -> Result<Vec<TransactionEventView>> {
+        Ok(self
+            .events
+            .iter()
+            .filter(|event| event.transaction_hash == Some(txn_hash))
+            .cloned()
+            .collect())
+    }
+
+    fn get_transaction(&self, txn_hash: HashValue) -> Result<Option<TransactionView>> {
+        let mut user_transaction =
+            SignedUserTransactionView::try_from(SignedUserTransaction::mock())?;
+        user_transaction.raw_txn.sender = self.senders[&txn_hash];
+        Ok(Some(TransactionView {
+            block_hash: HashValue::random(),
+            block_number: 0.into(),
+            transaction_hash: txn_hash,
+            transaction_index: 0,
+            block_metadata: None,
+            user_transaction: Some(user_transaction),
+        }))
+    }
+
+    fn get_block_header(&self, number: BlockNumber) -> Result<Option<BlockHeaderView>> {
+        let mut header = BlockHeaderView::from(BlockHeader::random());
+        header.number = number.into();
+        // 2021-06-01T00:00:00Z plus one minute per block.
+        header.timestamp = (1_622_505_600_000 + number * 60_000).into();
+        Ok(Some(header))
+    }
+
+    fn get_token_info(&self, token_code: TokenCode) -> Result<Option<TokenInfoView>> {
+        assert_eq!(token_code, *STC_TOKEN_CODE);
+        Ok(Some(TokenInfoView {
+            token_code: token_code.to_string(),
+            symbol: token_code.name.clone(),
+            scaling_factor: StrView(1_000_000_000),
+            total_supply: StrView(0),
+        }))
+    }
+}
+
+#[test]
+fn test_export_transfers() {
+    let alice = AccountAddress::random();
+    let bob = AccountAddress::random();
+    let carol = AccountAddress::random();
+    let mut source = MockTransferDataSource {
+        events: vec![],
+        senders: HashMap::new(),
+    };
+    let txn1 = source.add_txn(
+        1,
+        1,
+        alice,
+        vec![(alice, false, 1_500_000_000), (bob, true, 1_500_000_000)],
+    );
+    let txn2 = source.add_txn(
+        3,
+        1,
+        bob,
+        vec![(bob, false, 2_000_000_000), (alice, true, 2_000_000_000)],
+    );
+    // the mint to alice without a withdraw is from the txn sender.
+    let txn3 = source.add_txn(6, 2, carol, vec![(alice, true, 1)]);
+    // out of the range.
+    source.add_txn(8, 1, alice, vec![(alice, false, 1), (bob, true, 1)]);
+
+    let record = |block_number: BlockNumber,
+                  block_time: &str,
+                  txn_hash: HashValue,
+                  direction: TransferDirection,
+                  counterparty: AccountAddress,
+                  amount: &str,
+                  raw_amount: u128| TransferRecord {
+        block_number,
+        block_time: block_time.to_string(),
+        txn_hash,
+        address: alice,
+        direction,
+        counterparty: Some(counterparty),
+        token_code: STC_TOKEN_CODE.to_string(),
+        amount: amount.to_string(),
+        raw_amount: StrView(raw_amount),
+    };
+    let expected = vec![
+        record(
+            1,
+            "2021-06-01T00:01:00Z",
+            txn1,
+            TransferDirection::Out,
+            bob,
+            "1.5",
+            1_500_000_000,
+        ),
+        record(
+            3,
+            "2021-06-01T00:03:00Z",
+            txn2,
+            TransferDirection::In,
+            bob,
+            "2",
+            2_000_000_000,
+        ),
+        record(
+            6,
+            "2021-06-01T00:06:00Z",
+            txn3,
+            TransferDirection::In,
+            carol,
+            "0.000000001",
+            1,
+        ),
+    ];
+
+    let mut records = vec![];
+    let mut checkpoints = vec![];
+    let checkpoint = export_transfers(
+        &source,
+        &[alice],
+        0,
+        7,
+        TransferExportCheckpoint::default(),
+        2,
+        1,
+        |page, checkpoint| {
+            records.extend(page);
+            checkpoints.push(checkpoint.clone());
+            Ok(())
+        },
+    )
+    .unwrap();
+    assert_eq!(records, expected);
+    assert_eq!(checkpoint.next_block, 8);
+    assert!(checkpoint.cursor.is_none());
+
+    // resume from every checkpoint.
+    for (i, checkpoint) in checkpoints.into_iter().enumerate() {
+        let mut resumed = vec![];
+        export_transfers(&source, &[alice], 0, 7, checkpoint, 2, 1, |page, _| {
+            resumed.extend(page);
+            Ok(())
+        })
+        .unwrap();
+        let exported = expected.len() - resumed.len();
+        assert_eq!(
+            resumed.as_slice(),
+            &expected[exported..],
+            "checkpoint {}",
+            i
+        );
+    }
+}
+
+fn annotated_struct(
+    address: AccountAddress,
+    module: &str,
+    name: &str,
+    fields: Vec<(&str, AnnotatedMoveValue)>,
+) -> AnnotatedMoveStruct {
+    AnnotatedMoveStruct {
+        abilities: AbilitySet::from_u8(0).unwrap(),
+        type_: StructTag {
+            address,
+            module: Identifier::new(module).unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_params: vec![],
+        },
+        value: fields
+            .into_iter()
+            .map(|(field, value)| (Identifier::new(field).unwrap(), value))
+            .collect(),
+    }
+}
+
+fn annotated_event_handle(key: &EventKey, counter: u64) -> AnnotatedMoveValue {
+    AnnotatedMoveValue::Struct(annotated_struct(
+        CORE_CODE_ADDRESS,
+        "Event",
+        "EventHandle",
+        vec![
+            ("counter", AnnotatedMoveValue::U64(counter)),
+            ("guid", AnnotatedMoveValue::Bytes(key.to_vec())),
+        ],
+    ))
+}
+
+#[test]
+fn test_find_event_handle() {
+    let address = AccountAddress::random();
+    let order_key = EventKey::new_from_address(&address, 1);
+    let deposit_key = EventKey::new_from_address(&address, 2);
+    let orders = annotated_struct(
+        address,
+        "Market",
+        "Orders",
+        vec![
+            ("size", AnnotatedMoveValue::U64(2)),
+            ("events", annotated_event_handle(&order_key, 3)),
+        ],
+    );
+    let market = annotated_struct(
+        address,
+        "Market",
+        "Inner",
+        vec![("orders", AnnotatedMoveValue::Struct(orders))],
+    );
+    let resource = annotated_struct(
+        address,
+        "Market",
+        "Market",
+        vec![
+            ("market", AnnotatedMoveValue::Struct(market)),
+            ("deposit_events", annotated_event_handle(&deposit_key, 0)),
+        ],
+    );
+
+    let handle = EventHandleView::find_in(&resource, "deposit_events").unwrap();
+    assert_eq!(handle.key, deposit_key);
+    assert_eq!(handle.count.0, 0);
+    let handle = EventHandleView::find_in(&resource, "market.orders.events").unwrap();
+    assert_eq!(handle.key, order_key);
+    assert_eq!(handle.count.0, 3);
+
+    let err = EventHandleView::find_in(&resource, "market.trades.events")
+        .unwrap_err()
+        .downcast::<jsonrpc_core::Error>()
+        .unwrap();
+    assert_eq!(RpcErrorCode::of(&err), Some(RpcErrorCode::FieldNotFound));
+    let data: FieldNotFoundData = serde_json::from_value(err.data.unwrap()).unwrap();
+    assert_eq!(data.field_path, "market.trades");
+    assert_eq!(data.available_fields, vec!["orders".to_string()]);
+
+    // the fields which are not event handles.
+    for field_path in &[
+        "market.orders.size",
+        "market.orders",
+        "market.orders.size.x",
+    ] {
+        let err = EventHandleView::find_in(&resource, field_path)
+            .unwrap_err()
+            .downcast::<jsonrpc_core::Error>()
+            .unwrap();
+        assert_eq!(err.code, jsonrpc_core::ErrorCode::InvalidParams);
+    }
+}
+
+#[test]
+fn test_receipt_identifier() {
+    use bech32::ToBase32;
+
+    let auth_key = AuthenticationKey::random();
+    let address = auth_key.derived_address();
+    let receipt = ReceiptIdentifierView::new(address, Some(auth_key)).unwrap();
+    let encoded = receipt.to_string();
+    assert!(encoded.starts_with("stc1p"));
+    let decoded = ReceiptIdentifierView::from_str(encoded.as_str()).unwrap();
+    assert_eq!(decoded, receipt);
+    assert_eq!(decoded.address(), address);
+    assert_eq!(decoded.auth_key(), Some(auth_key));
+    // bech32 is case insensitive.
+    assert_eq!(
+        ReceiptIdentifierView::from_str(encoded.to_uppercase().as_str()).unwrap(),
+        receipt
+    );
+
+    // the identifier without auth key, and the bare address.
+    let receipt_without_key = ReceiptIdentifierView::from(address);
+    let encoded_without_key = receipt_without_key.to_string();
+    assert!(encoded_without_key.len() < encoded.len());
+    assert_eq!(
+        ReceiptIdentifierView::from_str(encoded_without_key.as_str()).unwrap(),
+        receipt_without_key
+    );
+    assert_eq!(
+        ReceiptIdentifierView::from_str(address.to_string().as_str()).unwrap(),
+        receipt_without_key
+    );
+    let json = serde_json::to_string(&receipt).unwrap();
+    assert_eq!(
+        serde_json::from_str::<ReceiptIdentifierView>(json.as_str()).unwrap(),
+        receipt
+    );
+
+    // the checksum mismatch.
+    let mut corrupted = encoded.clone();
+    let last = corrupted.pop().unwrap();
+    corrupted.push(if last == 'q' { 'p' } else { 'q' });
+    let err = ReceiptIdentifierView::from_str(corrupted.as_str()).unwrap_err();
+    assert!(err.to_string().contains("checksum"), "{}", err);
+
+    // the auth key does not derive the address.
+    let other_address = AccountAddress::random();
+    assert!(ReceiptIdentifierView::new(other_address, Some(auth_key)).is_err());
+    let mut bytes = other_address.to_vec();
+    bytes.extend(auth_key.to_vec());
+    let mut data = vec![bech32::u5::try_from_u8(1).unwrap()];
+    data.extend(bytes.to_base32());
+    let mismatched = bech32::encode("stc", data, bech32::Variant::Bech32).unwrap();
+    let err = ReceiptIdentifierView::from_str(mismatched.as_str()).unwrap_err();
+    assert!(err.to_string().contains("auth key"), "{}", err);
+
+    // the address arg of txns can be a receipt identifier.
+    assert_eq!(
+        TransactionArgumentView::from_str(encoded.as_str())
+            .unwrap()
+            .0,
+        TransactionArgument::Address(address)
+    );
+}
@@ -0,0 +1,282 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2
+
+//! Export the token transfers of accounts from the Deposit/Withdraw events, for audits.
+
+use crate::types::pubsub::EventFilter;
+use crate::types::{
+    BlockHeaderView, StrView, TokenInfoView, TransactionEventView, TransactionView,
+};
+use anyhow::{format_err, Result};
+use chrono::{SecondsFormat, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::{DepositEvent, WithdrawEvent};
+use starcoin_types::block::BlockNumber;
+use starcoin_types::filter::EventPosition;
+use starcoin_types::language_storage::TypeTag;
+use starcoin_vm_types::move_resource::MoveResource;
+use starcoin_vm_types::token::token_code::TokenCode;
+use std::collections::HashMap;
+
+/// The chain data needed by `export_transfers`, the cli implements it by the rpc client,
+/// and a rpc can implement it by the chain services.
+pub trait TransferDataSource {
+    /// Get the events matching the filter in ascending order.
+    fn get_events(&self, filter: EventFilter) -> Result<Vec<TransactionEventView>>;
+
+    fn get_events_by_txn_hash(&self, txn_hash: HashValue) -> Result<Vec<TransactionEventView>>;
+
+    fn get_transaction(&self, txn_hash: HashValue) -> Result<Option<TransactionView>>;
+
+    fn get_block_header(&self, number: BlockNumber) -> Result<Option<BlockHeaderView>>;
+
+    fn get_token_info(&self, token_code: TokenCode) -> Result<Option<TokenInfoView>>;
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TransferDirection {
+    In,
+    Out,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TransferRecord {
+    pub block_number: BlockNumber,
+    /// The block time in ISO-8601.
+    pub block_time: String,
+    pub txn_hash: HashValue,
+    pub address: AccountAddress,
+    pub direction: TransferDirection,
+    /// None if it cannot be resolved, such as the block reward.
+    pub counterparty: Option<AccountAddress>,
+    pub token_code: String,
+    /// The amount scaled by the scaling factor of the token, e.g. "1.5" for 1.5 STC.
+    pub amount: String,
+    /// The raw amount in the smallest unit.
+    pub raw_amount: StrView<u128>,
+}
+
+/// The progress of the export, the export can resume from it.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TransferExportCheckpoint {
+    /// The blocks before it are all exported.
+    pub next_block: BlockNumber,
+    /// The position of the last exported event in the blocks from `next_block`.
+    pub cursor: Option<EventPosition>,
+}
+
+/// The transfer event decoded from a Deposit or Withdraw event.
+struct TransferEvent {
+    address: AccountAddress,
+    direction: TransferDirection,
+    token_code: TokenCode,
+    amount: u128,
+}
+
+impl TransferEvent {
+    fn decode(event: &TransactionEventView) -> Result<Option<Self>> {
+        let address = event.event_key.get_creator_address();
+        let (direction, token_code, amount) =
+            if event.type_tag == TypeTag::Struct(DepositEvent::struct_tag()) {
+                let event = DepositEvent::try_from_bytes(event.data.0.as_slice())?;
+                (
+                    TransferDirection::In,
+                    event.token_code().clone(),
+                    event.amount(),
+                )
+            } else if event.type_tag == TypeTag::Struct(WithdrawEvent::struct_tag()) {
+                let event = WithdrawEvent::try_from_bytes(event.data.0.as_slice())?;
+                (
+                    TransferDirection::Out,
+                    event.token_code().clone(),
+                    event.amount(),
+                )
+            } else {
+                return Ok(None);
+            };
+        Ok(Some(Self {
+            address,
+            direction,
+            token_code,
+            amount,
+        }))
+    }
+}
+
+/// Export the transfers of the `addresses` in the blocks `from_block..=to_block` from their
+/// Deposit/Withdraw events, starting from the `checkpoint`.
+/// The events are fetched by pages of `page_size` in windows of at most `max_block_range` blocks,
+/// `on_page` is called with the records and the new checkpoint after every page,
+/// so the caller can persist them and resume from the checkpoint later.
+/// Return the final checkpoint.
+#[allow(clippy::too_many_arguments)]
+pub fn export_transfers<F>(
+    source: &dyn TransferDataSource,
+    addresses: &[AccountAddress],
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    checkpoint: TransferExportCheckpoint,
+    max_block_range: u64,
+    page_size: usize,
+    mut on_page: F,
+) -> Result<TransferExportCheckpoint>
+where
+    F: FnMut(Vec<TransferRecord>, &TransferExportCheckpoint) -> Result<()>,
+{
+    let type_tags = vec![
+        TypeTag::Struct(DepositEvent::struct_tag()).into(),
+        TypeTag::Struct(WithdrawEvent::struct_tag()).into(),
+    ];
+    let mut resolver = TransferResolver::new(source);
+    let mut checkpoint = TransferExportCheckpoint {
+        next_block: checkpoint.next_block.max(from_block),
+        cursor: checkpoint.cursor,
+    };
+    while checkpoint.next_block <= to_block {
+        let window_end = to_block.min(checkpoint.next_block.saturating_add(max_block_range));
+        let events = source.get_events(EventFilter {
+            from_block: Some(checkpoint.next_block.into()),
+            to_block: Some(window_end.into()),
+            type_tags: type_tags.clone(),
+            addrs: addresses.to_vec(),
+            limit: Some(page_size),
+            cursor: checkpoint.cursor.map(Into::into),
+            ..Default::default()
+        })?;
+        let window_done = events.len() < page_size;
+        let mut records = vec![];
+        for event in events {
+            checkpoint.cursor = Some(event_position(&event)?);
+            if let Some(record) = resolver.resolve(&event, addresses)? {
+                records.push(record);
+            }
+        }
+        if window_done {
+            checkpoint.next_block = window_end.saturating_add(1);
+            checkpoint.cursor = None;
+        }
+        on_page(records, &checkpoint)?;
+        if window_done && window_end == BlockNumber::MAX {
+            break;
+        }
+    }
+    Ok(checkpoint)
+}
+
+fn event_position(event: &TransactionEventView) -> Result<EventPosition> {
+    match (
+        event.block_number,
+        event.transaction_index,
+        event.event_index,
+    ) {
+        (Some(block_number), Some(transaction_index), Some(event_index)) => Ok(EventPosition {
+            block_number: block_number.0,
+            transaction_index,
+            event_index,
+        }),
+        _ => Err(format_err!("the position of event {:?} is missing", event)),
+    }
+}
+
+/// Resolve the transfer records from the events, with the caches of the chain data.
+struct TransferResolver<'a> {
+    source: &'a dyn TransferDataSource,
+    block_times: HashMap<BlockNumber, String>,
+    token_infos: HashMap<TokenCode, TokenInfoView>,
+}
+
+impl<'a> TransferResolver<'a> {
+    fn new(source: &'a dyn TransferDataSource) -> Self {
+        Self {
+            source,
+            block_times: HashMap::new(),
+            token_infos: HashMap::new(),
+        }
+    }
+
+    fn resolve(
+        &mut self,
+        event: &TransactionEventView,
+        addresses: &[AccountAddress],
+    ) -> Result<Option<TransferRecord>> {
+        let transfer = match TransferEvent::decode(event)? {
+            Some(transfer) if addresses.contains(&transfer.address) => transfer,
+            _ => return Ok(None),
+        };
+        let (block_number, txn_hash) = match (event.block_number, event.transaction_hash) {
+            (Some(block_number), Some(txn_hash)) => (block_number.0, txn_hash),
+            _ => return Err(format_err!("the txn of event {:?} is missing", event)),
+        };
+        let counterparty = self.resolve_counterparty(txn_hash, &transfer)?;
+        let block_time = self.block_time(block_number)?;
+        let token_info = self.token_info(&transfer.token_code)?;
+        Ok(Some(TransferRecord {
+            block_number,
+            block_time,
+            txn_hash,
+            address: transfer.address,
+            direction: transfer.direction,
+            counterparty,
+            token_code: token_info.token_code.clone(),
+            amount: token_info.format_amount(transfer.amount),
+            raw_amount: StrView(transfer.amount),
+        }))
+    }
+
+    /// The counterparty is the account of the opposite event of the same token and amount
+    /// in the txn, a deposit without such event is from the txn sender, such as a mint.
+    fn resolve_counterparty(
+        &self,
+        txn_hash: HashValue,
+        transfer: &TransferEvent,
+    ) -> Result<Option<AccountAddress>> {
+        for event in self.source.get_events_by_txn_hash(txn_hash)? {
+            if let Some(other) = TransferEvent::decode(&event)? {
+                if other.direction != transfer.direction
+                    && other.address != transfer.address
+                    && other.token_code == transfer.token_code
+                    && other.amount == transfer.amount
+                {
+                    return Ok(Some(other.address));
+                }
+            }
+        }
+        if transfer.direction == TransferDirection::Out {
+            return Ok(None);
+        }
+        Ok(self
+            .source
+            .get_transaction(txn_hash)?
+            .and_then(|txn| txn.user_transaction)
+            .map(|txn| txn.raw_txn.sender)
+            .filter(|sender| *sender != transfer.address))
+    }
+
+    fn block_time(&mut self, number: BlockNumber) -> Result<String> {
+        if let Some(time) = self.block_times.get(&number) {
+            return Ok(time.clone());
+        }
+        let header = self
+            .source
+            .get_block_header(number)?
+            .ok_or_else(|| format_err!("cannot find block {}", number))?;
+        let time = Utc
+            .timestamp_millis(header.timestamp.0 as i64)
+            .to_rfc3339_opts(SecondsFormat::Secs, true);
+        self.block_times.insert(number, time.clone());
+        Ok(time)
+    }
+
+    fn token_info(&mut self, token_code: &TokenCode) -> Result<&TokenInfoView> {
+        if !self.token_infos.contains_key(token_code) {
+            let token_info = self
+                .source
+                .get_token_info(token_code.clone())?
+                .ok_or_else(|| format_err!("cannot find the token info of {}", token_code))?;
+            self.token_infos.insert(token_code.clone(), token_info);
+        }
+        Ok(&self.token_infos[token_code])
+    }
+}
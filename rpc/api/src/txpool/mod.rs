@@ -6,28 +6,55 @@ use jsonrpc_derive::rpc;
 use starcoin_types::transaction::SignedUserTransaction;
 
 pub use self::gen_client::Client as TxPoolClient;
-use crate::types::{SignedUserTransactionView, StrView};
+use crate::types::{
+    AccountAddressView, GasPriceOracleView, SignedUserTransactionView, StrView, TxPoolStatusView,
+    TxnDiagnoseTarget, TxnDiagnosisView,
+};
 use starcoin_crypto::HashValue;
-use starcoin_txpool_api::TxPoolStatus;
-use starcoin_types::account_address::AccountAddress;
+use starcoin_txpool_api::{TxPoolStatus, TxnRebroadcastResult};
 
 #[rpc]
 pub trait TxPoolApi {
+    /// Submit the txn to the txpool, return the txn hash.
+    /// The txn is checked against the block gas limit of the current epoch, the min gas price,
+    /// the node time and the sender's gas token balance first, and rejected by the `InvalidTransaction` error.
+    /// If `validate_only` is true, only run the checks without submitting the txn.
     #[rpc(name = "txpool.submit_transaction")]
-    fn submit_transaction(&self, tx: SignedUserTransaction) -> FutureResult<HashValue>;
+    fn submit_transaction(
+        &self,
+        tx: SignedUserTransaction,
+        validate_only: Option<bool>,
+    ) -> FutureResult<HashValue>;
 
+    /// Submit the hex of the bcs encoded txn, same as `txpool.submit_transaction`.
     #[rpc(name = "txpool.submit_hex_transaction")]
-    fn submit_hex_transaction(&self, tx: String) -> FutureResult<HashValue>;
+    fn submit_hex_transaction(
+        &self,
+        tx: String,
+        validate_only: Option<bool>,
+    ) -> FutureResult<HashValue>;
+
+    /// Replace the pending txn which has the same sender and sequence number with `tx`,
+    /// the gas price of `tx` should be bumped enough, return the hash of the replaced txn.
+    #[rpc(name = "txpool.replace_transaction")]
+    fn replace_transaction(&self, tx: SignedUserTransaction) -> FutureResult<Option<HashValue>>;
 
     /// return current gas price
     #[rpc(name = "txpool.gas_price")]
     fn gas_price(&self) -> FutureResult<StrView<u64>>;
+
+    /// Suggest the gas prices by the percentiles of the gas prices of the txns in the recent blocks,
+    /// combined with the min gas price to enter the txpool.
+    /// The percentiles are cached and refreshed on new head blocks.
+    #[rpc(name = "txpool.gas_price_oracle")]
+    fn gas_price_oracle(&self) -> FutureResult<GasPriceOracleView>;
+
     /// get all pending txns in txpool of given sender.
     /// no matter the state of txn is ready or in future.
     #[rpc(name = "txpool.pending_txns_of_sender")]
     fn pending_txns(
         &self,
-        addr: AccountAddress,
+        addr: AccountAddressView,
         max_len: Option<u32>,
     ) -> FutureResult<Vec<SignedUserTransactionView>>;
 
@@ -38,9 +65,29 @@ pub trait TxPoolApi {
     /// Returns next valid sequence number for given sender
     /// or `None` if there are no pending transactions from that sender in txpool.
     #[rpc(name = "txpool.next_sequence_number")]
-    fn next_sequence_number(&self, address: AccountAddress) -> FutureResult<Option<u64>>;
+    fn next_sequence_number(&self, address: AccountAddressView) -> FutureResult<Option<u64>>;
 
     /// or `None` if there are no pending transactions from that sender in txpool.
     #[rpc(name = "txpool.state")]
     fn state(&self) -> FutureResult<TxPoolStatus>;
+
+    /// Returns the pending and queued txn counts, and the min gas price to enter the txpool,
+    /// clients can read the gas price floor before submitting txns when the txpool is full.
+    /// The counts are refreshed at most once per second.
+    #[rpc(name = "txpool.status")]
+    fn status(&self) -> FutureResult<TxPoolStatusView>;
+
+    /// Diagnose why the txn is not mined, by txn hash or by sender,
+    /// check the txpool, the node time, the sender's account state and the recent blocks.
+    #[rpc(name = "txpool.diagnose")]
+    fn diagnose(&self, target: TxnDiagnoseTarget) -> FutureResult<TxnDiagnosisView>;
+
+    /// Announce the pending txns to the peers now, default to all the pending txns submitted to this node,
+    /// return how many txns are announced to how many peers.
+    /// The txns are also rebroadcast automatically with backoff until they leave the txpool.
+    /// At most `txpool.max_tx_rebroadcast_per_interval` txns are announced by the calls every
+    /// `txpool.tx_rebroadcast_interval` seconds, the call fails if the quota is used up.
+    #[rpc(name = "txpool.rebroadcast")]
+    fn rebroadcast(&self, txn_hashes: Option<Vec<HashValue>>)
+        -> FutureResult<TxnRebroadcastResult>;
 }
@@ -1,57 +1,82 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+mod metric_types;
 mod node_api_types;
 pub mod pubsub;
+mod webhook_types;
 
+pub use metric_types::*;
 pub use node_api_types::*;
+pub use webhook_types::*;
 
+use crate::errors::{FieldNotFoundData, RpcErrorCode};
 use bcs_ext::BCSCodec;
-use hex::FromHex;
+use bech32::{FromBase32, ToBase32};
 use jsonrpc_core_client::RpcChannel;
-use serde::de::Error;
+use serde::de::{DeserializeOwned, Error, MapAccess, Visitor};
 use serde::{Deserialize, Serializer};
 use serde::{Deserializer, Serialize};
+use starcoin_account_api::{TxnHistoryEntry, TxnHistoryStatus};
+use starcoin_config::{parse_chain_id, BuiltinNetworkID};
 use starcoin_crypto::{CryptoMaterialError, HashValue, ValidCryptoMaterialStringExt};
 use starcoin_resource_viewer::{AnnotatedMoveStruct, AnnotatedMoveValue};
 use starcoin_service_registry::ServiceRequest;
 use starcoin_state_api::{StateProof, StateWithProof};
+use starcoin_sync_api::SyncProgressReport;
+use starcoin_txpool_api::{GasPriceOracle, TxPoolDetailedStatus};
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::block::{
-    Block, BlockBody, BlockHeader, BlockHeaderExtra, BlockInfo, BlockNumber, BlockSummary,
-    EpochUncleSummary, UncleSummary,
+    AccumulatorInfo, Block, BlockBody, BlockHeader, BlockHeaderExtra, BlockInfo, BlockNumber,
+    BlockReward, BlockSummary, BranchInfo, EpochUncleSummary, UncleSummary,
 };
 use starcoin_types::contract_event::{ContractEvent, ContractEventInfo};
 use starcoin_types::event::EventKey;
+use starcoin_types::filter::EventPosition;
 use starcoin_types::genesis_config;
 use starcoin_types::language_storage::TypeTag;
 use starcoin_types::peer_info::{PeerId, PeerInfo};
-use starcoin_types::proof::SparseMerkleProof;
+use starcoin_types::proof::{
+    AccumulatorProof, EventWithProof, SparseMerkleProof, TransactionInfoWithProof,
+};
 use starcoin_types::startup_info::ChainInfo;
 use starcoin_types::transaction::authenticator::{AuthenticationKey, TransactionAuthenticator};
 use starcoin_types::transaction::{RawUserTransaction, ScriptFunction, TransactionArgument};
 use starcoin_types::vm_error::AbortLocation;
 use starcoin_types::U256;
+use starcoin_vm_types::access::ModuleAccess;
 use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::account_config::{
+    BalanceResource, TwoPhaseUpgradeV2Resource, UpgradePlanV2Resource,
+};
 use starcoin_vm_types::block_metadata::BlockMetadata;
+use starcoin_vm_types::file_format::{
+    Ability, CompiledModule, SignatureToken, StructHandleIndex, Visibility,
+};
 use starcoin_vm_types::identifier::Identifier;
-use starcoin_vm_types::language_storage::{FunctionId, ModuleId, StructTag};
+use starcoin_vm_types::language_storage::{FunctionId, ModuleId, StructTag, CORE_CODE_ADDRESS};
+use starcoin_vm_types::on_chain_resource::EpochInfo;
 use starcoin_vm_types::parser::{parse_transaction_argument, parse_type_tag};
+use starcoin_vm_types::token::token_code::TokenCode;
+use starcoin_vm_types::token::token_info::TokenInfoResource;
 use starcoin_vm_types::transaction::authenticator::AccountPublicKey;
 use starcoin_vm_types::transaction::{
-    Script, SignedUserTransaction, Transaction, TransactionInfo, TransactionOutput,
-    TransactionPayload, TransactionStatus,
+    BlockTransactionInfo, GasBreakdown, Module, Package, Script, SignedUserTransaction,
+    Transaction, TransactionInfo, TransactionOutput, TransactionPayload, TransactionPosition,
+    TransactionStatus,
 };
 use starcoin_vm_types::transaction_argument::convert_txn_args;
+use starcoin_vm_types::value::{MoveTypeLayout, MoveValue};
 use starcoin_vm_types::vm_status::{DiscardedVMStatus, KeptVMStatus};
 use starcoin_vm_types::write_set::WriteOp;
 use std::collections::BTreeMap;
 use std::convert::{TryFrom, TryInto};
+use std::marker::PhantomData;
 use std::str::FromStr;
 
 pub type ByteCode = Vec<u8>;
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct AnnotatedMoveStructView {
     pub abilities: u8,
     pub type_: StructTagView,
@@ -71,7 +96,7 @@ impl From<AnnotatedMoveStruct> for AnnotatedMoveStructView {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub enum AnnotatedMoveValueView {
     U8(u8),
     U64(StrView<u64>),
@@ -81,6 +106,13 @@ pub enum AnnotatedMoveValueView {
     Vector(Vec<AnnotatedMoveValueView>),
     Bytes(StrView<Vec<u8>>),
     Struct(AnnotatedMoveStructView),
+    Signer(AccountAddress),
+    /// u16 of newer Move versions, not produced by the vm of this node yet.
+    U16(u16),
+    /// u32 of newer Move versions, not produced by the vm of this node yet.
+    U32(u32),
+    /// u256 of newer Move versions, not produced by the vm of this node yet.
+    U256(StrView<U256>),
 }
 
 impl From<AnnotatedMoveValue> for AnnotatedMoveValueView {
@@ -91,6 +123,7 @@ impl From<AnnotatedMoveValue> for AnnotatedMoveValueView {
             AnnotatedMoveValue::U128(u) => AnnotatedMoveValueView::U128(StrView(u)),
             AnnotatedMoveValue::Bool(b) => AnnotatedMoveValueView::Bool(b),
             AnnotatedMoveValue::Address(data) => AnnotatedMoveValueView::Address(data),
+            AnnotatedMoveValue::Signer(data) => AnnotatedMoveValueView::Signer(data),
             AnnotatedMoveValue::Vector(data) => {
                 AnnotatedMoveValueView::Vector(data.into_iter().map(Into::into).collect())
             }
@@ -100,18 +133,209 @@ impl From<AnnotatedMoveValue> for AnnotatedMoveValueView {
     }
 }
 
+/// The `0x1::Event::EventHandle` in a resource, the events emitted by it have the `key`.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct EventHandleView {
+    pub key: EventKey,
+    /// The number of the events emitted by the handle, also the sequence number of the next event.
+    pub count: StrView<u64>,
+}
+
+impl EventHandleView {
+    /// Find the event handle at the dotted `field_path` of the resource, such as `market.orders.events`.
+    /// Fail with the `FieldNotFound` error if a field of the path does not exist,
+    /// or the invalid params error if the field is not an event handle.
+    pub fn find_in(resource: &AnnotatedMoveStruct, field_path: &str) -> anyhow::Result<Self> {
+        let fields: Vec<&str> = field_path.split('.').collect();
+        let mut current = resource;
+        for (i, name) in fields.iter().enumerate() {
+            let path = fields[..=i].join(".");
+            let value = current
+                .value
+                .iter()
+                .find(|(field, _)| field.as_str() == *name)
+                .map(|(_, value)| value)
+                .ok_or_else(|| {
+                    RpcErrorCode::FieldNotFound.error_with_data(
+                        format!("field {} is not found in {}", path, resource.type_),
+                        &FieldNotFoundData {
+                            resource_type: resource.type_.to_string(),
+                            field_path: path.clone(),
+                            available_fields: current
+                                .value
+                                .iter()
+                                .map(|(field, _)| field.to_string())
+                                .collect(),
+                        },
+                    )
+                })?;
+            current = match value {
+                AnnotatedMoveValue::Struct(value) => value,
+                _ => {
+                    return Err(jsonrpc_core::Error::invalid_params(format!(
+                        "field {} of {} is not a struct",
+                        path, resource.type_
+                    ))
+                    .into())
+                }
+            };
+        }
+        Self::try_from_struct(current).ok_or_else(|| {
+            jsonrpc_core::Error::invalid_params(format!(
+                "field {} of {} is a {}, not an EventHandle",
+                field_path, resource.type_, current.type_
+            ))
+            .into()
+        })
+    }
+
+    fn try_from_struct(value: &AnnotatedMoveStruct) -> Option<Self> {
+        if value.type_.address != CORE_CODE_ADDRESS
+            || value.type_.module.as_str() != "Event"
+            || value.type_.name.as_str() != "EventHandle"
+        {
+            return None;
+        }
+        let (mut count, mut key) = (None, None);
+        for (field, value) in &value.value {
+            match (field.as_str(), value) {
+                ("counter", AnnotatedMoveValue::U64(counter)) => count = Some(*counter),
+                ("guid", AnnotatedMoveValue::Bytes(guid)) => {
+                    key = EventKey::try_from(guid.as_slice()).ok()
+                }
+                _ => {}
+            }
+        }
+        Some(Self {
+            key: key?,
+            count: StrView(count?),
+        })
+    }
+}
+
 #[derive(Default, Clone, Debug, Deserialize, Serialize)]
 pub struct AccountStateSetView {
     pub codes: BTreeMap<Identifier, StrView<ByteCode>>,
     pub resources: BTreeMap<StructTagView, AnnotatedMoveStructView>,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ResourceView {
+    pub raw: StrView<Vec<u8>>,
+    /// The decoded resource, only present when `decode` is requested.
+    pub json: Option<AnnotatedMoveStructView>,
+}
+
+/// A page of resources under an account.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ListResourceView {
+    /// The state root which the page is read from,
+    /// pass it back together with `next_cursor` to fetch the next page.
+    pub state_root: HashValue,
+    pub resources: BTreeMap<StructTagView, ResourceView>,
+    /// The first resource of the next page, None if this is the last page.
+    pub next_cursor: Option<StructTagView>,
+}
+
+/// A page of module codes under an account.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ListCodeView {
+    /// The state root which the page is read from,
+    /// pass it back together with `next_cursor` to fetch the next page.
+    pub state_root: HashValue,
+    pub codes: BTreeMap<Identifier, StrView<ByteCode>>,
+    /// The first module of the next page, None if this is the last page.
+    pub next_cursor: Option<Identifier>,
+}
+
+/// The metadata of a token, read from the on chain `Token::TokenInfo`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TokenInfoView {
+    pub token_code: String,
+    pub symbol: String,
+    /// The amount to divide by to get the human-readable representation, e.g. 10^9 for STC.
+    pub scaling_factor: StrView<u128>,
+    /// The raw total supply in the smallest unit.
+    pub total_supply: StrView<u128>,
+}
+
+impl TokenInfoView {
+    pub fn new(token_code: TokenCode, token_info: &TokenInfoResource) -> Self {
+        Self {
+            symbol: token_code.name.clone(),
+            token_code: token_code.to_string(),
+            scaling_factor: StrView(token_info.scaling_factor()),
+            total_supply: StrView(token_info.total_value()),
+        }
+    }
+
+    /// Format the raw amount in the smallest unit to decimal, e.g. 1500000000 to "1.5" for STC.
+    pub fn format_amount(&self, amount: u128) -> String {
+        let scaling_factor = self.scaling_factor.0;
+        if scaling_factor <= 1 {
+            return amount.to_string();
+        }
+        let decimals = (scaling_factor - 1).to_string().len();
+        let fraction = format!("{:0width$}", amount % scaling_factor, width = decimals);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            (amount / scaling_factor).to_string()
+        } else {
+            format!("{}.{}", amount / scaling_factor, fraction)
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BalanceView {
+    /// The raw amount in the smallest unit.
+    pub amount: StrView<u128>,
+    pub token_info: TokenInfoView,
+}
+
+/// The result of rotating the authentication key of an account.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RotateAuthKeyView {
+    pub address: AccountAddress,
+    pub new_public_key: AccountPublicKey,
+    pub new_auth_key: AuthenticationKey,
+    pub raw_txn: RawUserTransactionView,
+    /// The info of the executed rotation txn, None for dry run.
+    pub txn_info: Option<TransactionInfoView>,
+}
+
+/// A txn in the wallet history, annotated with the status last observed.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TxnHistoryView {
+    #[serde(flatten)]
+    pub txn: SignedUserTransactionView,
+    pub status: TxnHistoryStatus,
+    /// When the txn is recorded, in unix seconds.
+    pub recorded_at: StrView<u64>,
+    pub block_hash: Option<HashValue>,
+    pub block_number: Option<StrView<u64>>,
+}
+
+impl TryFrom<TxnHistoryEntry> for TxnHistoryView {
+    type Error = anyhow::Error;
+
+    fn try_from(entry: TxnHistoryEntry) -> Result<Self, Self::Error> {
+        Ok(Self {
+            txn: entry.txn.try_into()?,
+            status: entry.status,
+            recorded_at: entry.recorded_at.into(),
+            block_hash: entry.block_hash,
+            block_number: entry.block_number.map(Into::into),
+        })
+    }
+}
+
 #[derive(Default, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct TransactionRequest {
     /// Sender's address.
-    pub sender: Option<AccountAddress>,
+    pub sender: Option<AccountAddressView>,
     // Sequence number of this transaction corresponding to sender's account.
-    pub sequence_number: Option<u64>,
+    pub sequence_number: Option<StrView<u64>>,
     /// The transaction script to execute.
     #[serde(default)]
     pub script: Option<ScriptData>,
@@ -119,11 +343,12 @@ pub struct TransactionRequest {
     #[serde(default)]
     pub modules: Vec<StrView<ByteCode>>,
     // Maximal total gas specified by wallet to spend for this transaction.
-    pub max_gas_amount: Option<u64>,
+    pub max_gas_amount: Option<StrView<u64>>,
     // Maximal price can be paid per gas.
-    pub gas_unit_price: Option<u64>,
+    pub gas_unit_price: Option<StrView<u64>>,
     // The token code for pay transaction gas, Default is STC token code.
-    pub gas_token_code: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_gas_token_code")]
+    pub gas_token_code: Option<TokenCodeView>,
     // Expiration timestamp for this transaction. timestamp is represented
     // as u64 in seconds from Unix Epoch. If storage is queried and
     // the time returned is greater than or equal to this time and this
@@ -131,21 +356,137 @@ pub struct TransactionRequest {
     // never be included.
     // A transaction that doesn't expire is represented by a very large value like
     // u64::max_value().
-    pub expiration_timestamp_secs: Option<u64>,
+    pub expiration_timestamp_secs: Option<StrView<u64>>,
+    // The chain id, a number or a builtin network name such as `barnard`.
+    #[serde(default, deserialize_with = "deserialize_chain_id")]
     pub chain_id: Option<u8>,
 }
 
+/// The default max gas amount of a transaction request.
+pub const DEFAULT_MAX_GAS_AMOUNT: u64 = 1000000;
+/// The default gas unit price of a transaction request.
+pub const DEFAULT_GAS_UNIT_PRICE: u64 = 1;
+
+impl TransactionRequest {
+    /// Build the txn payload from the modules and the script of the request.
+    pub fn to_payload(&self) -> anyhow::Result<TransactionPayload> {
+        Ok(if !self.modules.is_empty() {
+            let modules = self
+                .modules
+                .iter()
+                .map(|c| Module::new(c.0.clone()))
+                .collect();
+            let script_function = self
+                .script
+                .clone()
+                .map(|script_data| script_data.into_script_function())
+                .transpose()?;
+            TransactionPayload::Package(Package::new(modules, script_function)?)
+        } else {
+            let script = self.script.clone().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "invalid transaction request: script should not be empty if no modules"
+                )
+            })?;
+            script.try_into()?
+        })
+    }
+
+    /// Build the raw txn without reading any state from the node, for signing on an offline machine.
+    /// Fails with the list of the missing fields if any state field is absent,
+    /// the gas fields fall back to the same defaults as the node, so the txn is identical to the online one.
+    pub fn to_raw_txn_offline(&self) -> anyhow::Result<RawUserTransaction> {
+        match (
+            self.sender,
+            self.sequence_number,
+            self.expiration_timestamp_secs,
+            self.chain_id,
+        ) {
+            (
+                Some(sender),
+                Some(sequence_number),
+                Some(expiration_timestamp_secs),
+                Some(chain_id),
+            ) => Ok(RawUserTransaction::new_with_default_gas_token(
+                sender.0,
+                sequence_number.0,
+                self.to_payload()?,
+                self.max_gas_amount
+                    .map(|v| v.0)
+                    .unwrap_or(DEFAULT_MAX_GAS_AMOUNT),
+                self.gas_unit_price
+                    .map(|v| v.0)
+                    .unwrap_or(DEFAULT_GAS_UNIT_PRICE),
+                expiration_timestamp_secs.0,
+                genesis_config::ChainId::new(chain_id),
+            )),
+            _ => {
+                let missing_fields: Vec<&str> = vec![
+                    ("sender", self.sender.is_none()),
+                    ("sequence_number", self.sequence_number.is_none()),
+                    (
+                        "expiration_timestamp_secs",
+                        self.expiration_timestamp_secs.is_none(),
+                    ),
+                    ("chain_id", self.chain_id.is_none()),
+                ]
+                .into_iter()
+                .filter(|(_, missing)| *missing)
+                .map(|(field, _)| field)
+                .collect();
+                anyhow::bail!(
+                    "offline transaction request requires the fields: {}",
+                    missing_fields.join(", ")
+                )
+            }
+        }
+    }
+
+    /// Decode the positional BCS args of the script function call by its ABI, for display.
+    pub fn decode_args(&self, abi: &FunctionAbiView) -> anyhow::Result<Vec<DecodedArgView>> {
+        let script = self
+            .script
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("transaction request has no script"))?;
+        let arg_types: Vec<_> = abi.args.iter().map(|arg| arg.type_tag.0.clone()).collect();
+        let args = script.args.to_bcs_bytes_by_types(&arg_types)?;
+        anyhow::ensure!(
+            args.len() == abi.args.len(),
+            "function {} expects {} args, but got {}",
+            abi.name,
+            abi.args.len(),
+            args.len()
+        );
+        args.iter()
+            .zip(abi.args.iter())
+            .map(|(arg, arg_abi)| {
+                let layout = type_tag_to_layout(&arg_abi.type_tag.0)?;
+                let value = if arg_abi.type_tag.0 == TypeTag::Vector(Box::new(TypeTag::U8)) {
+                    AnnotatedMoveValueView::Bytes(StrView(bcs_ext::from_bytes::<Vec<u8>>(arg)?))
+                } else {
+                    move_value_to_view(MoveValue::simple_deserialize(arg.as_slice(), &layout)?)?
+                };
+                Ok(DecodedArgView {
+                    name: arg_abi.name.clone(),
+                    type_tag: arg_abi.type_tag.clone(),
+                    value,
+                })
+            })
+            .collect()
+    }
+}
+
 impl From<RawUserTransaction> for TransactionRequest {
     fn from(raw: RawUserTransaction) -> Self {
         let mut request = TransactionRequest {
-            sender: Some(raw.sender()),
-            sequence_number: Some(raw.sequence_number()),
+            sender: Some(raw.sender().into()),
+            sequence_number: Some(raw.sequence_number().into()),
             script: None,
             modules: vec![],
-            max_gas_amount: Some(raw.max_gas_amount()),
-            gas_unit_price: Some(raw.gas_unit_price()),
-            gas_token_code: Some(raw.gas_token_code()),
-            expiration_timestamp_secs: Some(raw.expiration_timestamp_secs()),
+            max_gas_amount: Some(raw.max_gas_amount().into()),
+            gas_unit_price: Some(raw.gas_unit_price().into()),
+            gas_token_code: TokenCodeView::from_str(raw.gas_token_code().as_str()).ok(),
+            expiration_timestamp_secs: Some(raw.expiration_timestamp_secs().into()),
             chain_id: Some(raw.chain_id().id()),
         };
         match raw.into_payload() {
@@ -171,6 +512,68 @@ pub struct DryRunTransactionRequest {
     pub transaction: TransactionRequest,
     /// Sender's public key
     pub sender_public_key: Option<StrView<AccountPublicKey>>,
+    /// Pretend the state is overridden during the dry run, the overrides are never persisted.
+    #[serde(default)]
+    pub state_overrides: Vec<StateOverride>,
+    /// Return the `gas_breakdown` of the output, it is not collected by default to save the overhead.
+    #[serde(default)]
+    pub with_gas_breakdown: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct BalanceOverride {
+    pub token: TokenCodeView,
+    pub amount: StrView<u128>,
+}
+
+/// A state override of the dry run.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum StateOverride {
+    /// Pretend the resource has the bcs bytes `value`, or is deleted if the `value` is null.
+    Resource {
+        address: AccountAddressView,
+        resource_type: StructTagView,
+        value: Option<StrView<Vec<u8>>>,
+    },
+    /// Pretend the account has the balance of the token.
+    AccountBalance {
+        address: AccountAddressView,
+        balance: BalanceOverride,
+    },
+}
+
+impl StateOverride {
+    pub fn account_balance(address: AccountAddress, token: TokenCode, amount: u128) -> Self {
+        Self::AccountBalance {
+            address: address.into(),
+            balance: BalanceOverride {
+                token: StrView(token),
+                amount: StrView(amount),
+            },
+        }
+    }
+
+    /// Convert to the overridden access path and value.
+    pub fn into_access_path_value(self) -> anyhow::Result<(AccessPath, Option<Vec<u8>>)> {
+        Ok(match self {
+            Self::Resource {
+                address,
+                resource_type,
+                value,
+            } => (
+                AccessPath::resource_access_path(address.0, resource_type.0),
+                value.map(|v| v.0),
+            ),
+            Self::AccountBalance { address, balance } => (
+                AccessPath::resource_access_path(
+                    address.0,
+                    BalanceResource::struct_tag_for_token_code(balance.token.0),
+                ),
+                Some(bcs_ext::to_bytes(&BalanceResource::new(balance.amount.0))?),
+            ),
+        })
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -178,33 +581,148 @@ pub struct DryRunTransactionRequest {
 pub enum ArgumentsView {
     HumanReadable(Vec<TransactionArgumentView>),
     BCS(Vec<StrView<Vec<u8>>>),
+    /// Json literals such as `["0x1","0x2"]`, which can only be encoded by the parameter types of the function.
+    Json(Vec<serde_json::Value>),
 }
 
 impl ArgumentsView {
-    pub fn to_bcs_bytes(&self) -> Vec<Vec<u8>> {
+    /// Args are human readable if all of them are strings, otherwise they are kept as json literals.
+    pub fn from_values(values: Vec<serde_json::Value>) -> anyhow::Result<Self> {
+        if values.iter().all(|v| v.is_string()) {
+            let args = values
+                .iter()
+                .map(|v| TransactionArgumentView::from_str(v.as_str().unwrap_or_default()))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            Ok(Self::HumanReadable(args))
+        } else {
+            Ok(Self::Json(values))
+        }
+    }
+
+    /// Parse the args from the command line literals, see `parse_argument_literal`.
+    pub fn from_literals<S: AsRef<str>>(literals: &[S]) -> anyhow::Result<Self> {
+        let values = literals
+            .iter()
+            .map(|l| parse_argument_literal(l.as_ref()))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Self::from_values(values)
+    }
+
+    /// Whether the args need the parameter types of the function to be encoded.
+    pub fn needs_arg_types(&self) -> bool {
+        matches!(self, Self::Json(_))
+    }
+
+    pub fn to_bcs_bytes(&self) -> anyhow::Result<Vec<Vec<u8>>> {
+        match self {
+            Self::HumanReadable(vs) => Ok(convert_txn_args(
+                &vs.iter().map(|v| v.0.clone()).collect::<Vec<_>>(),
+            )),
+            Self::BCS(vs) => Ok(vs.iter().map(|v| v.0.clone()).collect()),
+            Self::Json(_) => {
+                anyhow::bail!(
+                    "json args can only be encoded by the parameter types of the function"
+                )
+            }
+        }
+    }
+
+    /// Encode the args by the parameter types of the function, the leading signer is not included.
+    pub fn to_bcs_bytes_by_types(&self, arg_types: &[TypeTag]) -> anyhow::Result<Vec<Vec<u8>>> {
         match self {
-            Self::HumanReadable(vs) => {
-                convert_txn_args(&vs.iter().map(|v| v.0.clone()).collect::<Vec<_>>())
+            Self::Json(values) => {
+                anyhow::ensure!(
+                    values.len() == arg_types.len(),
+                    "expect {} args, but got {}",
+                    arg_types.len(),
+                    values.len()
+                );
+                values
+                    .iter()
+                    .zip(arg_types)
+                    .map(|(value, type_tag)| {
+                        json_to_move_value(type_tag, value)?
+                            .simple_serialize()
+                            .ok_or_else(|| anyhow::format_err!("fail to serialize arg {}", value))
+                    })
+                    .collect()
             }
-            Self::BCS(vs) => vs.iter().map(|v| v.0.clone()).collect(),
+            _ => self.to_bcs_bytes(),
         }
     }
 }
 
+/// Parse a command line arg literal, a literal starts with `[` is a json array, such as `["0x1","0x2"]`,
+/// others are kept as strings, such as `1u8`, `0x1`, `x"01"`.
+pub fn parse_argument_literal(s: &str) -> anyhow::Result<serde_json::Value> {
+    let s = s.trim();
+    if s.starts_with('[') {
+        Ok(serde_json::from_str(s)?)
+    } else {
+        Ok(serde_json::Value::String(s.to_string()))
+    }
+}
+
+fn json_to_move_value(type_tag: &TypeTag, value: &serde_json::Value) -> anyhow::Result<MoveValue> {
+    use serde_json::Value;
+    Ok(match (type_tag, value) {
+        (TypeTag::Bool, Value::Bool(b)) => MoveValue::Bool(*b),
+        (TypeTag::Bool, Value::String(s)) => MoveValue::Bool(s.parse()?),
+        (TypeTag::U8, v) => MoveValue::U8(json_to_integer(v, "u8")?),
+        (TypeTag::U64, v) => MoveValue::U64(json_to_integer(v, "u64")?),
+        (TypeTag::U128, v) => MoveValue::U128(json_to_integer(v, "u128")?),
+        (TypeTag::Address, Value::String(s)) => {
+            MoveValue::Address(ReceiptIdentifierView::from_str(s)?.address())
+        }
+        (TypeTag::Vector(inner), Value::Array(values)) => MoveValue::Vector(
+            values
+                .iter()
+                .map(|v| json_to_move_value(inner, v))
+                .collect::<anyhow::Result<Vec<_>>>()?,
+        ),
+        // bytes can be a hex string such as `0x0102`, or a bytes literal such as `x"0102"`, `b"abc"`.
+        (TypeTag::Vector(inner), Value::String(s)) if **inner == TypeTag::U8 => {
+            match s.strip_prefix("0x") {
+                Some(h) => MoveValue::vector_u8(hex::decode(h)?),
+                None => match parse_transaction_argument(s)? {
+                    TransactionArgument::U8Vector(bytes) => MoveValue::vector_u8(bytes),
+                    arg => anyhow::bail!("expect bytes, but got {}", arg),
+                },
+            }
+        }
+        (t, v) => anyhow::bail!("cannot encode {} as type {}", v, t),
+    })
+}
+
+fn json_to_integer<T>(value: &serde_json::Value, suffix: &str) -> anyhow::Result<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: std::fmt::Display,
+{
+    let s = match value {
+        serde_json::Value::Number(n) => n.to_string(),
+        serde_json::Value::String(s) => s.strip_suffix(suffix).unwrap_or(s).to_string(),
+        v => anyhow::bail!("expect {}, but got {}", suffix, v),
+    };
+    s.parse::<T>()
+        .map_err(|e| anyhow::format_err!("invalid {} {}: {}", suffix, s, e))
+}
+
 /// Be caution:
-/// We only allow passing args by TransactionArgumentView to our jsonrpc.
+/// We only allow passing args by TransactionArgumentView or json literals to our jsonrpc.
 /// Because we cannot distinguish whether `0x12341235` is an human readable address or just some bcs bytes in hex string.
 impl<'de> Deserialize<'de> for ArgumentsView {
     fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
     where
         D: Deserializer<'de>,
     {
-        let args = <Vec<TransactionArgumentView>>::deserialize(deserializer)?;
-        Ok(ArgumentsView::HumanReadable(args))
+        let values = <Vec<serde_json::Value>>::deserialize(deserializer)?;
+        ArgumentsView::from_values(values).map_err(D::Error::custom)
     }
 }
 
-/// Only return BCS hex string when returning arguments out of jsonrpc.
+/// Only return BCS hex string when returning arguments out of jsonrpc,
+/// json literals are returned as is, for they cannot be encoded without the function signature.
 impl Serialize for ArgumentsView {
     fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
     where
@@ -213,10 +731,16 @@ impl Serialize for ArgumentsView {
         match self {
             Self::HumanReadable(_vs) => {
                 // transform view to bcs first.
-                let data: Vec<_> = self.to_bcs_bytes().into_iter().map(StrView).collect();
+                let data: Vec<_> = self
+                    .to_bcs_bytes()
+                    .map_err(<S::Error as serde::ser::Error>::custom)?
+                    .into_iter()
+                    .map(StrView)
+                    .collect();
                 data.serialize(serializer)
             }
             Self::BCS(data) => data.serialize(serializer),
+            Self::Json(values) => values.serialize(serializer),
         }
     }
 }
@@ -231,7 +755,7 @@ pub struct ScriptData {
 
 impl ScriptData {
     pub fn into_script_function(self) -> anyhow::Result<ScriptFunction> {
-        match self.into_data() {
+        match self.into_data()? {
             Err(script_function) => Ok(script_function),
             _ => {
                 anyhow::bail!("not a script function");
@@ -239,32 +763,40 @@ impl ScriptData {
         }
     }
     pub fn into_script(self) -> anyhow::Result<Script> {
-        match self.into_data() {
+        match self.into_data()? {
             Ok(script) => Ok(script),
             _ => {
                 anyhow::bail!("not a script");
             }
         }
     }
-    fn into_data(self) -> Result<Script, ScriptFunction> {
+    /// Encode the json literal args by the parameter types of the function.
+    pub fn resolve_args(&mut self, arg_types: &[TypeTag]) -> anyhow::Result<()> {
+        let args = self.args.to_bcs_bytes_by_types(arg_types)?;
+        self.args = ArgumentsView::BCS(args.into_iter().map(StrView).collect());
+        Ok(())
+    }
+    fn into_data(self) -> anyhow::Result<Result<Script, ScriptFunction>> {
         let ty_args: Vec<_> = self.type_args.into_iter().map(|s| s.0).collect();
-        let args: Vec<_> = self.args.to_bcs_bytes();
+        let args: Vec<_> = self.args.to_bcs_bytes()?;
 
-        match self.code.0 {
+        Ok(match self.code.0 {
             ByteCodeOrScriptFunction::ByteCode(code) => Ok(Script::new(code, ty_args, args)),
             ByteCodeOrScriptFunction::ScriptFunction(FunctionId { module, function }) => {
                 Err(ScriptFunction::new(module, function, ty_args, args))
             }
-        }
+        })
     }
 }
-#[allow(clippy::from_over_into)]
-impl Into<TransactionPayload> for ScriptData {
-    fn into(self) -> TransactionPayload {
-        match self.into_data() {
+
+impl TryFrom<ScriptData> for TransactionPayload {
+    type Error = anyhow::Error;
+
+    fn try_from(data: ScriptData) -> Result<Self, Self::Error> {
+        Ok(match data.into_data()? {
             Ok(script) => TransactionPayload::Script(script),
             Err(func) => TransactionPayload::ScriptFunction(func),
-        }
+        })
     }
 }
 
@@ -301,7 +833,7 @@ pub enum ByteCodeOrScriptFunction {
 impl std::fmt::Display for ByteCodeOrScriptFunction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self {
-            ByteCodeOrScriptFunction::ByteCode(c) => write!(f, "0x{}", hex::encode(c)),
+            ByteCodeOrScriptFunction::ByteCode(c) => write!(f, "{}", encode_hex_bytes(c)),
             ByteCodeOrScriptFunction::ScriptFunction(FunctionId { module, function }) => {
                 write!(f, "{}::{}", module, function)
             }
@@ -321,13 +853,94 @@ impl FromStr for ByteCodeOrScriptFunction {
                 function,
             }))
         } else {
-            Ok(ByteCodeOrScriptFunction::ByteCode(hex::decode(
-                s.strip_prefix("0x").unwrap_or(s),
-            )?))
+            Ok(ByteCodeOrScriptFunction::ByteCode(decode_hex_bytes(s)?))
         }
     }
 }
 
+/// The chain id with the name of the builtin network, such as `{"id": 1, "name": "main"}`.
+/// It can be deserialized from a plain number or a builtin network name too.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize)]
+pub struct ChainIdView {
+    pub id: u8,
+    /// The builtin network name of the id, None for custom networks.
+    pub name: Option<String>,
+}
+
+impl ChainIdView {
+    pub fn new(id: u8) -> Self {
+        Self {
+            id,
+            name: BuiltinNetworkID::try_from(id)
+                .ok()
+                .map(|network| network.chain_name()),
+        }
+    }
+}
+
+impl From<u8> for ChainIdView {
+    fn from(id: u8) -> Self {
+        Self::new(id)
+    }
+}
+
+impl From<genesis_config::ChainId> for ChainIdView {
+    fn from(chain_id: genesis_config::ChainId) -> Self {
+        Self::new(chain_id.id())
+    }
+}
+
+impl From<ChainIdView> for genesis_config::ChainId {
+    fn from(view: ChainIdView) -> Self {
+        genesis_config::ChainId::new(view.id)
+    }
+}
+
+impl std::fmt::Display for ChainIdView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{}({})", name, self.id),
+            None => write!(f, "{}", self.id),
+        }
+    }
+}
+
+impl FromStr for ChainIdView {
+    type Err = anyhow::Error;
+
+    /// Parse from a chain id number or a builtin network name, such as `1` or `main`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_chain_id(s).map(Into::into)
+    }
+}
+
+impl<'de> Deserialize<'de> for ChainIdView {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Go through serde_json::Value, untagged enums do not work with the arbitrary_precision numbers.
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let id = match &value {
+            serde_json::Value::Number(_) => value.as_u64(),
+            serde_json::Value::String(s) => {
+                return ChainIdView::from_str(s.as_str()).map_err(D::Error::custom);
+            }
+            serde_json::Value::Object(obj) => obj.get("id").and_then(|id| id.as_u64()),
+            _ => None,
+        };
+        let mut view = id
+            .and_then(|id| u8::try_from(id).ok())
+            .map(ChainIdView::new)
+            .ok_or_else(|| D::Error::custom(format!("invalid chain id: {}", value)))?;
+        // Keep the name reported by the node, it may be a custom network.
+        if let Some(name) = value.get("name").and_then(|name| name.as_str()) {
+            view.name = Some(name.to_string());
+        }
+        Ok(view)
+    }
+}
+
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct BlockHeaderView {
     pub block_hash: HashValue,
@@ -350,11 +963,11 @@ pub struct BlockHeaderView {
     /// Gas used for contracts execution.
     pub gas_used: StrView<u64>,
     /// Block difficulty
-    pub difficulty: U256,
+    pub difficulty: StrView<U256>,
     /// hash for block body
     pub body_hash: HashValue,
     /// The chain id
-    pub chain_id: u8,
+    pub chain_id: ChainIdView,
     /// Consensus nonce field.
     pub nonce: u32,
     /// block header extra
@@ -373,9 +986,9 @@ impl From<BlockHeader> for BlockHeaderView {
             block_accumulator_root: origin.block_accumulator_root(),
             state_root: origin.state_root(),
             gas_used: origin.gas_used().into(),
-            difficulty: origin.difficulty(),
+            difficulty: origin.difficulty().into(),
             body_hash: origin.body_hash(),
-            chain_id: origin.chain_id().id(),
+            chain_id: origin.chain_id().into(),
             nonce: origin.nonce(),
             extra: *origin.extra(),
         }
@@ -397,7 +1010,7 @@ pub struct RawUserTransactionView {
     // Maximal price can be paid per gas.
     pub gas_unit_price: StrView<u64>,
     // The token code for pay transaction gas, Default is STC token code.
-    pub gas_token_code: String,
+    pub gas_token_code: TokenCodeView,
     // Expiration timestamp for this transaction. timestamp is represented
     // as u64 in seconds from Unix Epoch. If storage is queried and
     // the time returned is greater than or equal to this time and this
@@ -418,7 +1031,7 @@ impl TryFrom<RawUserTransaction> for RawUserTransactionView {
             sequence_number: origin.sequence_number().into(),
             max_gas_amount: origin.max_gas_amount().into(),
             gas_unit_price: origin.gas_unit_price().into(),
-            gas_token_code: origin.gas_token_code(),
+            gas_token_code: TokenCodeView::from_str(origin.gas_token_code().as_str())?,
             expiration_timestamp_secs: origin.expiration_timestamp_secs().into(),
             chain_id: origin.chain_id().id(),
             payload: StrView(origin.into_payload().encode()?),
@@ -524,42 +1137,56 @@ pub struct TransactionView {
 }
 
 impl TransactionView {
-    pub fn new(txn: Transaction, block: &Block) -> anyhow::Result<Self> {
+    /// `position` is the position of the txn in the block which includes it,
+    /// the block metadata txn is executed first with index 0, so the user txns start at 1.
+    pub fn new(txn: Transaction, position: &TransactionPosition) -> anyhow::Result<Self> {
         let transaction_hash = txn.id();
-        let block_hash = block.id();
-        let block_number = block.header.number();
-        let transaction_index = match &txn {
-            Transaction::BlockMetadata(_) => 0,
-            _ => {
-                1 + block
-                    .transactions()
-                    .iter()
-                    .position(|t| t.id() == transaction_hash)
-                    .ok_or_else(|| {
-                        anyhow::anyhow!(
-                            "cannot find txn {} in block {}",
-                            transaction_hash,
-                            block_hash
-                        )
-                    })? as u32
-            }
-        };
+        let is_block_metadata = matches!(txn, Transaction::BlockMetadata(_));
+        anyhow::ensure!(
+            is_block_metadata == position.is_block_metadata(),
+            "txn {} is not at index {} of block {}",
+            transaction_hash,
+            position.transaction_index,
+            position.block_id
+        );
 
         let (meta, txn) = match txn {
             Transaction::BlockMetadata(meta) => (Some(meta.into()), None),
             Transaction::UserTransaction(t) => (None, Some(t.try_into()?)),
         };
         Ok(Self {
-            block_hash,
-            block_number: block_number.into(),
+            block_hash: position.block_id,
+            block_number: position.block_number.into(),
             transaction_hash,
-            transaction_index,
+            transaction_index: position.transaction_index,
             block_metadata: meta,
             user_transaction: txn,
         })
     }
 }
 
+/// The position of a txn on the main chain.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct TxnPositionView {
+    pub block_hash: HashValue,
+    pub block_number: StrView<BlockNumber>,
+    /// The index of the txn in the block, the block metadata txn is 0, so the user txns start at 1.
+    pub transaction_index: u32,
+    /// The index of the txn info in the txn accumulator.
+    pub global_index: StrView<u64>,
+}
+
+impl From<TransactionPosition> for TxnPositionView {
+    fn from(position: TransactionPosition) -> Self {
+        Self {
+            block_hash: position.block_id,
+            block_number: position.block_number.into(),
+            transaction_index: position.transaction_index,
+            global_index: position.global_index.into(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum BlockTransactionsView {
     Hashes(Vec<HashValue>),
@@ -598,6 +1225,74 @@ pub struct BlockView {
     pub header: BlockHeaderView,
     pub body: BlockTransactionsView,
     pub uncles: Vec<BlockHeaderView>,
+    /// The block info, only present when `include_block_info` is requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_info: Option<BlockInfoView>,
+}
+
+/// Options of the block query apis.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GetBlockOption {
+    /// Decode the block body to full transactions, otherwise only return the transaction hashes.
+    pub decode: bool,
+    /// Attach the block info to the returned block.
+    pub include_block_info: bool,
+}
+
+impl Default for GetBlockOption {
+    fn default() -> Self {
+        Self {
+            decode: true,
+            include_block_info: false,
+        }
+    }
+}
+
+/// The encoding of the rpc output.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputEncoding {
+    Json,
+    /// The bcs bytes of the underlying type, hex encoded.
+    Bcs,
+}
+
+impl Default for OutputEncoding {
+    fn default() -> Self {
+        OutputEncoding::Json
+    }
+}
+
+/// The output of the rpcs which accept an `output_encoding`,
+/// the json view is returned as is, and the bcs output is wrapped in a `bcs` field.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EncodedView<T> {
+    Bcs { bcs: StrView<Vec<u8>> },
+    Json(T),
+}
+
+impl<T> EncodedView<T> {
+    pub fn bcs<V: Serialize>(value: &V) -> anyhow::Result<Self> {
+        Ok(EncodedView::Bcs {
+            bcs: StrView(bcs_ext::to_bytes(value)?),
+        })
+    }
+
+    pub fn into_json(self) -> anyhow::Result<T> {
+        match self {
+            EncodedView::Json(view) => Ok(view),
+            EncodedView::Bcs { .. } => anyhow::bail!("expect json output, but got bcs"),
+        }
+    }
+
+    pub fn decode_bcs<V: DeserializeOwned>(&self) -> anyhow::Result<V> {
+        match self {
+            EncodedView::Bcs { bcs } => bcs_ext::from_bytes(bcs.0.as_slice()),
+            EncodedView::Json(_) => anyhow::bail!("expect bcs output, but got json"),
+        }
+    }
 }
 
 impl BlockView {
@@ -620,8 +1315,14 @@ impl BlockView {
                 .map(|h| h.into())
                 .collect(),
             body: txns_view,
+            block_info: None,
         })
     }
+
+    pub fn with_block_info(mut self, block_info: Option<BlockInfo>) -> Self {
+        self.block_info = block_info.map(Into::into);
+        self
+    }
 }
 
 impl TryFrom<Block> for BlockView {
@@ -633,67 +1334,243 @@ impl TryFrom<Block> for BlockView {
 }
 
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
-pub struct BlockSummaryView {
-    pub header: BlockHeaderView,
-    pub uncles: Vec<BlockHeaderView>,
-}
-impl From<BlockSummary> for BlockSummaryView {
-    fn from(summary: BlockSummary) -> Self {
-        BlockSummaryView {
-            header: summary.block_header.into(),
-            uncles: summary
-                .uncles
-                .into_iter()
-                .map(|uncle| uncle.into())
-                .collect(),
+pub struct AccumulatorInfoView {
+    /// Accumulator root hash
+    pub accumulator_root: HashValue,
+    /// Frozen subtree roots of this accumulator.
+    pub frozen_subtree_roots: Vec<HashValue>,
+    /// The total number of leaves in this accumulator.
+    pub num_leaves: StrView<u64>,
+    /// The total number of nodes in this accumulator.
+    pub num_nodes: StrView<u64>,
+}
+
+impl From<AccumulatorInfo> for AccumulatorInfoView {
+    fn from(info: AccumulatorInfo) -> Self {
+        AccumulatorInfoView {
+            accumulator_root: info.accumulator_root,
+            frozen_subtree_roots: info.frozen_subtree_roots,
+            num_leaves: info.num_leaves.into(),
+            num_nodes: info.num_nodes.into(),
         }
     }
 }
 
+impl From<AccumulatorInfoView> for AccumulatorInfo {
+    fn from(view: AccumulatorInfoView) -> Self {
+        AccumulatorInfo::new(
+            view.accumulator_root,
+            view.frozen_subtree_roots,
+            view.num_leaves.0,
+            view.num_nodes.0,
+        )
+    }
+}
+
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
-pub struct TransactionInfoView {
+pub struct BlockInfoView {
+    /// Block hash
     pub block_hash: HashValue,
-    pub block_number: StrView<u64>,
-    /// The hash of this transaction.
-    pub transaction_hash: HashValue,
-    pub transaction_index: u32,
-    /// The root hash of Sparse Merkle Tree describing the world state at the end of this
-    /// transaction.
-    pub state_root_hash: HashValue,
-
-    /// The root hash of Merkle Accumulator storing all events emitted during this transaction.
-    pub event_root_hash: HashValue,
-
-    /// The amount of gas used.
-    pub gas_used: StrView<u64>,
+    /// The total difficulty.
+    pub total_difficulty: StrView<U256>,
+    /// The transaction accumulator info
+    pub txn_accumulator_info: AccumulatorInfoView,
+    /// The block accumulator info.
+    pub block_accumulator_info: AccumulatorInfoView,
+}
+
+impl From<BlockInfo> for BlockInfoView {
+    fn from(block_info: BlockInfo) -> Self {
+        BlockInfoView {
+            block_hash: block_info.block_id,
+            total_difficulty: block_info.total_difficulty.into(),
+            txn_accumulator_info: block_info.txn_accumulator_info.into(),
+            block_accumulator_info: block_info.block_accumulator_info.into(),
+        }
+    }
+}
 
-    /// The vm status. If it is not `Executed`, this will provide the general error class. Execution
-    /// failures and Move abort's receive more detailed information. But other errors are generally
-    /// categorized with no status code or other information
-    pub status: TransactionVMStatus,
+impl From<BlockInfoView> for BlockInfo {
+    fn from(view: BlockInfoView) -> Self {
+        BlockInfo::new(
+            view.block_hash,
+            view.total_difficulty.0,
+            view.txn_accumulator_info.into(),
+            view.block_accumulator_info.into(),
+        )
+    }
 }
 
-impl TransactionInfoView {
-    pub fn new(txn_info: TransactionInfo, txn_block: &Block) -> anyhow::Result<Self> {
-        let block_hash = txn_block.id();
-        let transaction_hash = txn_info.transaction_hash();
+/// The main chain headers read from one chain snapshot.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BlockHeadersView {
+    pub headers: Vec<BlockHeaderView>,
+    /// The head of the main chain after the read, the client compares it between calls to detect a reorg.
+    pub head_block_hash: HashValue,
+    /// Every header is the parent of the next one and the range is still on the main chain after the read,
+    /// false means the range crossed a reorg boundary during the read.
+    pub consistent: bool,
+}
 
-        // if not found in block, it means it's block meta txn.
-        let index = txn_block
-            .transactions()
-            .iter()
-            .position(|t| t.id() == transaction_hash);
+impl BlockHeadersView {
+    pub fn new(
+        headers: Vec<BlockHeaderView>,
+        head_block_hash: HashValue,
+        on_main_chain: bool,
+    ) -> Self {
+        let consistent = on_main_chain
+            && headers
+                .windows(2)
+                .all(|pair| pair[1].parent_hash == pair[0].block_hash);
+        Self {
+            headers,
+            head_block_hash,
+            consistent,
+        }
+    }
+}
 
-        Ok(TransactionInfoView {
-            block_hash,
-            block_number: txn_block.header().number().into(),
-            transaction_hash,
-            transaction_index: index.map(|i| i + 1).unwrap_or_default() as u32,
+/// The reward breakdown of a main chain block.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BlockRewardView {
+    pub block_number: StrView<BlockNumber>,
+    pub block_hash: HashValue,
+    pub miner: AccountAddress,
+    /// The block reward plus the txn fees.
+    pub total_reward: StrView<u128>,
+    /// The reward per block of the epoch.
+    pub base_reward: StrView<u128>,
+    pub uncle_inclusion_reward: StrView<u128>,
+    pub txn_fees: StrView<u128>,
+    pub gas_used: StrView<u64>,
+}
+
+impl From<BlockReward> for BlockRewardView {
+    fn from(reward: BlockReward) -> Self {
+        Self {
+            block_number: reward.block_number.into(),
+            block_hash: reward.block_hash,
+            miner: reward.miner,
+            total_reward: reward.total_reward().into(),
+            base_reward: reward.base_reward.into(),
+            uncle_inclusion_reward: reward.uncle_inclusion_reward().into(),
+            txn_fees: reward.txn_fees.into(),
+            gas_used: reward.gas_used.into(),
+        }
+    }
+}
+
+/// A branch head tracked by the node.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BranchView {
+    pub head: BlockHeaderView,
+    pub total_difficulty: StrView<U256>,
+    /// The count of the blocks from the common ancestor with the main chain to the head,
+    /// 0 for the main chain.
+    pub length_from_common_ancestor: StrView<u64>,
+}
+
+impl From<BranchInfo> for BranchView {
+    fn from(branch: BranchInfo) -> Self {
+        Self {
+            head: branch.head.into(),
+            total_difficulty: branch.total_difficulty.into(),
+            length_from_common_ancestor: branch.length_from_common_ancestor.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BlockSummaryView {
+    pub header: BlockHeaderView,
+    pub uncles: Vec<BlockHeaderView>,
+}
+impl From<BlockSummary> for BlockSummaryView {
+    fn from(summary: BlockSummary) -> Self {
+        BlockSummaryView {
+            header: summary.block_header.into(),
+            uncles: summary
+                .uncles
+                .into_iter()
+                .map(|uncle| uncle.into())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TransactionInfoView {
+    pub block_hash: HashValue,
+    pub block_number: StrView<u64>,
+    /// The hash of this transaction.
+    pub transaction_hash: HashValue,
+    pub transaction_index: u32,
+    /// Whether the txn is the block metadata txn, which is executed first with index 0.
+    #[serde(default)]
+    pub is_block_metadata: bool,
+    /// The index of this txn info in the txn accumulator.
+    pub global_index: StrView<u64>,
+    /// The root hash of Sparse Merkle Tree describing the world state at the end of this
+    /// transaction.
+    pub state_root_hash: HashValue,
+
+    /// The root hash of Merkle Accumulator storing all events emitted during this transaction.
+    pub event_root_hash: HashValue,
+
+    /// The amount of gas used.
+    pub gas_used: StrView<u64>,
+
+    /// The vm status. If it is not `Executed`, this will provide the general error class. Execution
+    /// failures and Move abort's receive more detailed information. But other errors are generally
+    /// categorized with no status code or other information
+    pub status: TransactionVMStatus,
+}
+
+impl TransactionInfoView {
+    /// `position` is the position of the txn in the block which includes it,
+    /// return error if the txn info is produced by another block.
+    pub fn new(
+        txn_info: BlockTransactionInfo,
+        position: &TransactionPosition,
+    ) -> anyhow::Result<Self> {
+        let (block_id, txn_info) = txn_info.into();
+        anyhow::ensure!(
+            block_id == position.block_id,
+            "txn {} is executed by block {}, not block {}",
+            txn_info.transaction_hash(),
+            block_id,
+            position.block_id
+        );
+        Ok(Self::new_at(txn_info, position))
+    }
+
+    fn new_at(txn_info: TransactionInfo, position: &TransactionPosition) -> Self {
+        TransactionInfoView {
+            block_hash: position.block_id,
+            block_number: position.block_number.into(),
+            transaction_hash: txn_info.transaction_hash(),
+            transaction_index: position.transaction_index,
+            is_block_metadata: position.is_block_metadata(),
+            global_index: position.global_index.into(),
             state_root_hash: txn_info.state_root_hash(),
             event_root_hash: txn_info.event_root_hash(),
             gas_used: txn_info.gas_used().into(),
             status: TransactionVMStatus::from(txn_info.status().clone()),
-        })
+        }
+    }
+}
+
+impl TryFrom<TransactionInfoView> for TransactionInfo {
+    type Error = anyhow::Error;
+
+    fn try_from(view: TransactionInfoView) -> Result<Self, Self::Error> {
+        Ok(TransactionInfo::new_with_event_root_hash(
+            view.transaction_hash,
+            view.state_root_hash,
+            view.event_root_hash,
+            view.gas_used.0,
+            view.status.try_into()?,
+        ))
     }
 }
 
@@ -755,6 +1632,34 @@ impl From<DiscardedVMStatus> for TransactionVMStatus {
     }
 }
 
+impl TryFrom<TransactionVMStatus> for KeptVMStatus {
+    type Error = anyhow::Error;
+
+    fn try_from(origin: TransactionVMStatus) -> Result<Self, Self::Error> {
+        Ok(match origin {
+            TransactionVMStatus::Executed => KeptVMStatus::Executed,
+            TransactionVMStatus::OutOfGas => KeptVMStatus::OutOfGas,
+            TransactionVMStatus::MoveAbort {
+                location,
+                abort_code,
+            } => KeptVMStatus::MoveAbort(location, abort_code.0),
+            TransactionVMStatus::ExecutionFailure {
+                location,
+                function,
+                code_offset,
+            } => KeptVMStatus::ExecutionFailure {
+                location,
+                function,
+                code_offset,
+            },
+            TransactionVMStatus::MiscellaneousError => KeptVMStatus::MiscellaneousError,
+            TransactionVMStatus::Discard { status_code } => {
+                anyhow::bail!("discarded vm status {} is not a kept status", status_code)
+            }
+        })
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
 pub struct TransactionEventView {
     pub block_hash: Option<HashValue>,
@@ -762,11 +1667,21 @@ pub struct TransactionEventView {
     pub transaction_hash: Option<HashValue>,
     // txn index in block
     pub transaction_index: Option<u32>,
+    /// event index in txn, only set by `chain.get_events`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_index: Option<u32>,
+    /// The position of the event encoded as a sortable u128, only set with `event_index`,
+    /// pass it to `chain.ack_events`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub global_index: Option<StrView<u128>>,
 
     pub data: StrView<Vec<u8>>,
     pub type_tag: TypeTag,
     pub event_key: EventKey,
     pub event_seq_number: StrView<u64>,
+    /// The decoded event data, only present when `decode` is requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<AnnotatedMoveValueView>,
 }
 
 impl From<ContractEventInfo> for TransactionEventView {
@@ -776,10 +1691,13 @@ impl From<ContractEventInfo> for TransactionEventView {
             block_number: Some(info.block_number.into()),
             transaction_hash: Some(info.transaction_hash),
             transaction_index: Some(info.transaction_index),
+            event_index: Some(info.event_index),
+            global_index: Some(info.position().global_index().into()),
             data: StrView(info.event.event_data().to_vec()),
             type_tag: info.event.type_tag().clone(),
             event_key: *info.event.key(),
             event_seq_number: info.event.sequence_number().into(),
+            decoded: None,
         }
     }
 }
@@ -790,10 +1708,13 @@ impl From<ContractEvent> for TransactionEventView {
             block_number: None,
             transaction_hash: None,
             transaction_index: None,
+            event_index: None,
+            global_index: None,
             data: StrView(event.event_data().to_vec()),
             type_tag: event.type_tag().clone(),
             event_key: *event.key(),
             event_seq_number: event.sequence_number().into(),
+            decoded: None,
         }
     }
 }
@@ -811,19 +1732,157 @@ impl TransactionEventView {
             block_number: block_number.map(Into::into),
             transaction_hash,
             transaction_index,
+            event_index: None,
+            global_index: None,
             data: StrView(contract_event.event_data().to_vec()),
             type_tag: contract_event.type_tag().clone(),
             event_key: *contract_event.key(),
             event_seq_number: contract_event.sequence_number().into(),
+            decoded: None,
+        }
+    }
+
+    /// The position to pass as the cursor of `chain.get_events` to fetch the events after this one,
+    /// None if the event is not returned by `chain.get_events`.
+    pub fn position(&self) -> Option<EventPosition> {
+        Some(EventPosition {
+            block_number: self.block_number?.0,
+            transaction_index: self.transaction_index?,
+            event_index: self.event_index?,
+        })
+    }
+}
+
+/// The position of an event in the main chain, see `EventPosition`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct EventPositionView {
+    pub block_number: StrView<BlockNumber>,
+    /// txn index in block
+    pub transaction_index: u32,
+    /// event index in txn
+    pub event_index: u32,
+}
+
+impl From<EventPosition> for EventPositionView {
+    fn from(position: EventPosition) -> Self {
+        Self {
+            block_number: position.block_number.into(),
+            transaction_index: position.transaction_index,
+            event_index: position.event_index,
+        }
+    }
+}
+
+impl From<EventPositionView> for EventPosition {
+    fn from(view: EventPositionView) -> Self {
+        Self {
+            block_number: view.block_number.0,
+            transaction_index: view.transaction_index,
+            event_index: view.event_index,
+        }
+    }
+}
+
+impl From<TransactionEventView> for ContractEvent {
+    fn from(view: TransactionEventView) -> Self {
+        ContractEvent::new(
+            view.event_key,
+            view.event_seq_number.0,
+            view.type_tag,
+            view.data.0,
+        )
+    }
+}
+
+/// The events polled from an event cursor.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventCursorPageView {
+    pub events: Vec<TransactionEventView>,
+    /// All the events matching the filter up to the current head are returned.
+    pub done: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EventWithProofView {
+    pub event: TransactionEventView,
+    /// index of the event in the txn's event accumulator.
+    pub event_index: u64,
+    pub proof: AccumulatorProof,
+}
+
+impl From<EventWithProof> for EventWithProofView {
+    fn from(origin: EventWithProof) -> Self {
+        Self {
+            event: origin.event.into(),
+            event_index: origin.event_index,
+            proof: origin.proof,
+        }
+    }
+}
+
+impl From<EventWithProofView> for EventWithProof {
+    fn from(view: EventWithProofView) -> Self {
+        Self {
+            event: view.event.into(),
+            event_index: view.event_index,
+            proof: view.proof,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransactionProofView {
+    pub transaction_info: TransactionInfoView,
+    /// index of the txn info in the txn accumulator.
+    pub leaf_index: StrView<u64>,
+    /// proof of the txn info to the `txn_accumulator_root` of the block header.
+    pub proof: AccumulatorProof,
+    pub event_proof: Option<EventWithProofView>,
+}
+
+impl TransactionProofView {
+    pub fn new(proof: TransactionInfoWithProof, position: &TransactionPosition) -> Self {
+        Self {
+            transaction_info: TransactionInfoView::new_at(proof.transaction_info, position),
+            leaf_index: proof.leaf_index.into(),
+            proof: proof.proof,
+            event_proof: proof.event_proof.map(Into::into),
         }
     }
+
+    /// Verify the proof against `expected_root`, which should be the `txn_accumulator_root`
+    /// of the block header including the txn.
+    pub fn verify(&self, expected_root: HashValue) -> anyhow::Result<()> {
+        TransactionInfoWithProof::try_from(self.clone())?.verify(expected_root)
+    }
 }
+
+impl TryFrom<TransactionProofView> for TransactionInfoWithProof {
+    type Error = anyhow::Error;
+
+    fn try_from(view: TransactionProofView) -> Result<Self, Self::Error> {
+        Ok(Self {
+            transaction_info: view.transaction_info.try_into()?,
+            leaf_index: view.leaf_index.0,
+            proof: view.proof,
+            event_proof: view.event_proof.map(Into::into),
+        })
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TransactionOutputView {
     pub events: Vec<TransactionEventView>,
     pub gas_used: StrView<u64>,
     pub status: TransactionVMStatus,
     pub write_set: Vec<TransactionOutputAction>,
+    /// Only present for the output replayed from a mined txn, false if the state root after
+    /// the write set, the events or the gas used mismatch the stored `TransactionInfo`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified: Option<bool>,
+    /// Only present for the dry run with `with_gas_breakdown`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_breakdown: Option<GasBreakdownView>,
 }
 
 impl From<TransactionOutput> for TransactionOutputView {
@@ -840,10 +1899,87 @@ impl From<TransactionOutput> for TransactionOutputView {
                     action: w.into(),
                 })
                 .collect(),
+            verified: None,
+            gas_breakdown: None,
+        }
+    }
+}
+
+/// Where the gas of a dry run txn goes, `intrinsic_gas + instruction_gas + storage_write_gas` is the `gas_used`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GasBreakdownView {
+    /// The gas charged by the size of the txn.
+    pub intrinsic_gas: StrView<u64>,
+    /// The gas charged by the instructions, include the natives and the global storage instructions.
+    pub instruction_gas: StrView<u64>,
+    /// The gas charged by the accounts mutated by the write set.
+    pub storage_write_gas: StrView<u64>,
+    /// The `instruction_gas` charged by every executed instruction, empty if the txn runs out of gas.
+    pub instructions: Vec<GasChargeView>,
+    /// The `instruction_gas` charged by every called native function, empty if the txn runs out of gas.
+    pub natives: Vec<GasChargeView>,
+    pub storage_reads: u64,
+    pub storage_read_bytes: u64,
+    pub storage_writes: u64,
+    pub storage_write_bytes: u64,
+    pub write_ops: Vec<WriteOpSizeView>,
+}
+
+impl From<GasBreakdown> for GasBreakdownView {
+    fn from(breakdown: GasBreakdown) -> Self {
+        Self {
+            intrinsic_gas: breakdown.intrinsic_gas.into(),
+            instruction_gas: breakdown.instruction_gas.into(),
+            storage_write_gas: breakdown.storage_write_gas.into(),
+            instructions: breakdown
+                .instructions
+                .into_iter()
+                .map(GasChargeView::from)
+                .collect(),
+            natives: breakdown
+                .natives
+                .into_iter()
+                .map(GasChargeView::from)
+                .collect(),
+            storage_reads: breakdown.storage_reads,
+            storage_read_bytes: breakdown.storage_read_bytes,
+            storage_writes: breakdown.storage_writes,
+            storage_write_bytes: breakdown.storage_write_bytes,
+            write_ops: breakdown
+                .write_ops
+                .into_iter()
+                .map(|(access_path, size)| WriteOpSizeView {
+                    access_path: access_path.into(),
+                    size,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GasChargeView {
+    pub name: String,
+    pub gas: StrView<u64>,
+}
+
+impl From<(String, u64)> for GasChargeView {
+    fn from((name, gas): (String, u64)) -> Self {
+        Self {
+            name,
+            gas: gas.into(),
         }
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WriteOpSizeView {
+    #[serde(flatten)]
+    pub access_path: AccessPathView,
+    /// The bytes of the written value, None for a deletion.
+    pub size: Option<u64>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TransactionOutputAction {
     #[serde(flatten)]
@@ -905,7 +2041,7 @@ impl From<UncleSummary> for UncleSummaryView {
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EpochUncleSummaryView {
-    /// epoch number
+    /// epoch number, the epoch parameters are in `EpochInfoView`.
     pub epoch: StrView<u64>,
     pub number_summary: UncleSummaryView,
     pub epoch_summary: UncleSummaryView,
@@ -921,13 +2057,111 @@ impl From<EpochUncleSummary> for EpochUncleSummaryView {
     }
 }
 
+/// The on-chain `Epoch` resource and the `EpochData` of the epoch.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct EpochInfoView {
+    pub number: StrView<u64>,
+    /// milli seconds
+    pub start_time: StrView<u64>,
+    pub start_block_number: StrView<u64>,
+    pub end_block_number: StrView<u64>,
+    /// milli seconds
+    pub block_time_target: StrView<u64>,
+    pub reward_per_block: StrView<u128>,
+    pub reward_per_uncle_percent: StrView<u64>,
+    pub block_difficulty_window: StrView<u64>,
+    pub max_uncles_per_block: StrView<u64>,
+    pub block_gas_limit: StrView<u64>,
+    pub strategy: u8,
+    /// total uncles of the epoch so far.
+    pub uncles: StrView<u64>,
+    pub total_reward: StrView<u128>,
+    pub total_gas: StrView<u128>,
+}
+
+impl From<EpochInfo> for EpochInfoView {
+    fn from(info: EpochInfo) -> Self {
+        let epoch = info.epoch();
+        let epoch_data = info.epoch_data();
+        Self {
+            number: epoch.number().into(),
+            start_time: epoch.start_time().into(),
+            start_block_number: epoch.start_block_number().into(),
+            end_block_number: epoch.end_block_number().into(),
+            block_time_target: epoch.block_time_target().into(),
+            reward_per_block: epoch.reward_per_block().into(),
+            reward_per_uncle_percent: epoch.reward_per_uncle_percent().into(),
+            block_difficulty_window: epoch.block_difficulty_window().into(),
+            max_uncles_per_block: epoch.max_uncles_per_block().into(),
+            block_gas_limit: epoch.block_gas_limit().into(),
+            strategy: epoch.strategy().into(),
+            uncles: epoch_data.uncles().into(),
+            total_reward: epoch_data.total_reward().into(),
+            total_gas: epoch_data.total_gas().into(),
+        }
+    }
+}
+
+/// The on-chain `PackageTxnManager::TwoPhaseUpgradeV2` resource of a package address.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TwoPhaseUpgradeView {
+    /// milli seconds, the min delay between the plan submitted and the package can be deployed.
+    pub min_time_limit: StrView<u64>,
+    /// The submitted upgrade plan, None if there is no plan or the planned package has been deployed.
+    pub plan: Option<UpgradePlanView>,
+}
+
+impl TryFrom<TwoPhaseUpgradeV2Resource> for TwoPhaseUpgradeView {
+    type Error = anyhow::Error;
+
+    fn try_from(resource: TwoPhaseUpgradeV2Resource) -> Result<Self, Self::Error> {
+        Ok(Self {
+            min_time_limit: resource.config().min_time_limit().into(),
+            plan: resource.plan().map(UpgradePlanView::try_from).transpose()?,
+        })
+    }
+}
+
+/// The on-chain `PackageTxnManager::UpgradePlanV2`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct UpgradePlanView {
+    pub package_hash: HashValue,
+    /// milli seconds, the package can be deployed when the on-chain time reaches it.
+    pub active_after_time: StrView<u64>,
+    pub version: StrView<u64>,
+    pub enforced: bool,
+}
+
+impl UpgradePlanView {
+    pub fn is_active(&self, now_millis: u64) -> bool {
+        self.active_after_time.0 <= now_millis
+    }
+
+    /// The milli seconds until the plan is active, 0 if it is already active.
+    pub fn remaining_millis(&self, now_millis: u64) -> u64 {
+        self.active_after_time.0.saturating_sub(now_millis)
+    }
+}
+
+impl TryFrom<&UpgradePlanV2Resource> for UpgradePlanView {
+    type Error = anyhow::Error;
+
+    fn try_from(plan: &UpgradePlanV2Resource) -> Result<Self, Self::Error> {
+        Ok(Self {
+            package_hash: HashValue::from_slice(plan.package_hash())?,
+            active_after_time: plan.active_after_time().into(),
+            version: plan.version().into(),
+            enforced: plan.enforced(),
+        })
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ChainInfoView {
-    pub chain_id: u8,
+    pub chain_id: ChainIdView,
     pub genesis_hash: HashValue,
     pub head: BlockHeaderView,
-    //TODO should define block info view?
-    pub block_info: BlockInfo,
+    pub block_info: BlockInfoView,
 }
 
 impl From<ChainInfo> for ChainInfoView {
@@ -938,59 +2172,318 @@ impl From<ChainInfo> for ChainInfoView {
             chain_id: chain_id.into(),
             genesis_hash,
             head: head.into(),
-            block_info,
+            block_info: block_info.into(),
         }
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct PeerInfoView {
-    pub peer_id: PeerId,
-    pub chain_info: ChainInfoView,
-    pub notif_protocols: String,
-    pub rpc_protocols: String,
+/// The pending and queued txn counts of the txpool.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TxPoolSummaryView {
+    pub pending: u64,
+    pub queued: u64,
 }
 
-impl From<PeerInfo> for PeerInfoView {
-    fn from(info: PeerInfo) -> Self {
+impl From<TxPoolDetailedStatus> for TxPoolSummaryView {
+    fn from(status: TxPoolDetailedStatus) -> Self {
         Self {
-            peer_id: info.peer_id,
-            chain_info: info.chain_info.into(),
-            notif_protocols: info.notif_protocols.join(","),
-            rpc_protocols: info.rpc_protocols.join(","),
+            pending: status.pending as u64,
+            queued: status.queued as u64,
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct StateWithProofView {
-    pub state: Option<StrView<Vec<u8>>>,
-    pub account_state: Option<StrView<Vec<u8>>>,
-    pub account_proof: SparseMerkleProof,
-    pub account_state_proof: SparseMerkleProof,
-}
-impl StateWithProofView {
-    pub fn state_proof(&self) -> StateProof {
-        StateProof::new(
-            self.account_state.clone().map(|v| v.0),
-            self.account_proof.clone(),
-            self.account_state_proof.clone(),
-        )
-    }
+/// The main chain info with the txpool, sync and network status of the node.
+/// The status of a service which is disabled or does not respond in time is None.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChainStatusView {
+    pub chain_id: ChainIdView,
+    pub genesis_hash: HashValue,
+    pub head: BlockHeaderView,
+    pub block_info: BlockInfoView,
+    /// Default for the node which does not report it.
+    #[serde(default)]
+    pub now_seconds: u64,
+    #[serde(default)]
+    pub txpool: Option<TxPoolSummaryView>,
+    /// None if the node is not syncing.
+    #[serde(default)]
+    pub sync: Option<SyncProgressView>,
+    /// The number of the connected peers.
+    #[serde(default)]
+    pub peers: Option<u32>,
 }
 
-impl From<StateWithProof> for StateWithProofView {
-    fn from(state_proof: StateWithProof) -> Self {
-        let state = state_proof.state.map(StrView);
+impl ChainStatusView {
+    pub fn new(info: ChainInfo, now_seconds: u64) -> Self {
+        let ChainInfoView {
+            chain_id,
+            genesis_hash,
+            head,
+            block_info,
+        } = info.into();
         Self {
-            state,
-            account_state: state_proof.proof.account_state.map(|b| StrView(b.into())),
-            account_proof: state_proof.proof.account_proof,
-            account_state_proof: state_proof.proof.account_state_proof,
+            chain_id,
+            genesis_hash,
+            head,
+            block_info,
+            now_seconds,
+            txpool: None,
+            sync: None,
+            peers: None,
         }
     }
 }
 
+/// The mint job for the external miners, solve the nonce of `minting_blob` by the `strategy`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MintBlockTemplateView {
+    pub parent_hash: HashValue,
+    pub minting_blob: StrView<Vec<u8>>,
+    pub difficulty: StrView<U256>,
+    pub block_number: StrView<BlockNumber>,
+    pub strategy: genesis_config::ConsensusStrategy,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SyncProgressView {
+    pub target_id: HashValue,
+    pub target_number: StrView<BlockNumber>,
+    pub begin_number: Option<StrView<BlockNumber>>,
+    pub current_number: StrView<BlockNumber>,
+    /// Number of blocks applied since the sync task begin.
+    pub applied_blocks: StrView<u64>,
+    /// Blocks applied per second over the last minute.
+    pub blocks_per_second: f64,
+    /// Number of peers serving the sync task.
+    pub peers: u64,
+    /// Estimated seconds to reach the target.
+    pub eta_seconds: Option<u64>,
+    /// Percent of the current sync sub task.
+    pub percent: Option<f64>,
+    /// Number of the in-flight block fetches.
+    pub inflight_fetches: u64,
+    /// The in-flight block fetches of every peer.
+    pub inflight_peers: Vec<(PeerId, u64)>,
+    /// The peers blacklisted for returning bad blocks.
+    pub bad_peers: Vec<PeerId>,
+}
+
+impl From<SyncProgressReport> for SyncProgressView {
+    fn from(report: SyncProgressReport) -> Self {
+        Self {
+            target_id: report.target_id,
+            target_number: report.target_number.into(),
+            begin_number: report.begin_number.map(Into::into),
+            current_number: report.current_number.into(),
+            applied_blocks: report.applied_blocks.into(),
+            blocks_per_second: report.blocks_per_second,
+            peers: report.peers,
+            eta_seconds: report.eta_seconds,
+            percent: report.current.percent,
+            inflight_fetches: report.inflight_fetches,
+            inflight_peers: report.inflight_peers,
+            bad_peers: report.bad_peers,
+        }
+    }
+}
+
+/// The txn to diagnose, by txn hash or by sender, for the sender, the pending txn with the lowest
+/// sequence number is diagnosed, as it blocks all the others.
+/// The sender goes first, as a txn hash is too long to be an address.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TxnDiagnoseTarget {
+    Sender(AccountAddress),
+    Txn(HashValue),
+}
+
+impl FromStr for TxnDiagnoseTarget {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_account_address(s) {
+            Ok(sender) => Ok(Self::Sender(sender)),
+            Err(_) => Ok(Self::Txn(HashValue::from_str(s).map_err(|_| {
+                anyhow::format_err!("{} is neither a txn hash nor an account address", s)
+            })?)),
+        }
+    }
+}
+
+/// Why a txn is not mined yet.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TxnVerdict {
+    /// The txn is not in the txpool, it may be mined, dropped, or never submitted.
+    NotInPool,
+    /// The txn is expired by the node time, it will be dropped.
+    Expired {
+        expiration_timestamp_secs: StrView<u64>,
+        now_seconds: u64,
+    },
+    /// The txns with the sequence numbers between the on chain sequence number and the txn's
+    /// are missing in the txpool.
+    SequenceGap {
+        onchain_sequence_number: StrView<u64>,
+        /// How many sequence numbers are missing.
+        missing_count: StrView<u64>,
+        /// The lowest missing sequence numbers, at most 100.
+        missing_sequence_numbers: Vec<StrView<u64>>,
+    },
+    /// The balance of the gas token cannot pay the max gas of the txn.
+    InsufficientBalance {
+        balance: StrView<u128>,
+        max_gas_cost: StrView<u128>,
+    },
+    /// The gas price is lower than all the txns mined in the recent blocks.
+    GasPriceTooLow {
+        gas_unit_price: StrView<u64>,
+        recent_min_gas_price: StrView<u64>,
+    },
+    /// No problem found, the txn is waiting to be mined.
+    Pending,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TxnDiagnosisView {
+    pub txn_hash: Option<HashValue>,
+    pub sender: Option<AccountAddress>,
+    pub sequence_number: Option<StrView<u64>>,
+    pub verdict: TxnVerdict,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TxPoolStatusView {
+    /// The txns which are ready to be packaged.
+    pub pending: u64,
+    /// The txns which are waiting for the previous sequence numbers.
+    pub queued: u64,
+    pub senders: u64,
+    pub mem_usage_bytes: u64,
+    /// Submit txn with a gas price lower than this will be rejected,
+    /// it is raised when the txpool is full.
+    pub min_gas_price_to_enter: StrView<u64>,
+    /// Replace a pending txn requires the gas price is bumped by at least this percent.
+    pub gas_price_bump_percent: StrView<u64>,
+}
+
+impl From<TxPoolDetailedStatus> for TxPoolStatusView {
+    fn from(status: TxPoolDetailedStatus) -> Self {
+        Self {
+            pending: status.pending as u64,
+            queued: status.queued as u64,
+            senders: status.senders as u64,
+            mem_usage_bytes: status.mem_usage_bytes as u64,
+            min_gas_price_to_enter: status.min_gas_price_to_enter.into(),
+            gas_price_bump_percent: status.gas_price_bump_percent.into(),
+        }
+    }
+}
+
+/// The gas price suggestions, such as p25 for slow, p50 for standard and p90 for fast.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GasPriceOracleView {
+    /// The gas prices are sampled from the txns of the last so many blocks, the empty blocks are skipped.
+    pub last_n_blocks: u32,
+    /// The percentiles of the sampled gas prices, they are at least `pool_min_to_enter`.
+    pub percentiles: GasPricePercentilesView,
+    /// Submit txn with a gas price lower than this will be rejected.
+    pub pool_min_to_enter: StrView<u64>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GasPricePercentilesView {
+    pub p25: StrView<u64>,
+    pub p50: StrView<u64>,
+    pub p90: StrView<u64>,
+}
+
+impl From<GasPriceOracle> for GasPriceOracleView {
+    fn from(oracle: GasPriceOracle) -> Self {
+        Self {
+            last_n_blocks: oracle.last_n_blocks,
+            percentiles: GasPricePercentilesView {
+                p25: oracle.p25.into(),
+                p50: oracle.p50.into(),
+                p90: oracle.p90.into(),
+            },
+            pool_min_to_enter: oracle.pool_min_to_enter.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PeerInfoView {
+    pub peer_id: PeerId,
+    pub chain_info: ChainInfoView,
+    pub notif_protocols: String,
+    pub rpc_protocols: String,
+    /// Unix timestamp in seconds until which the peer is banned, only present for banned peers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub banned_until: Option<u64>,
+}
+
+impl PeerInfoView {
+    pub fn with_banned_until(mut self, banned_until: Option<u64>) -> Self {
+        self.banned_until = banned_until;
+        self
+    }
+}
+
+impl From<PeerInfo> for PeerInfoView {
+    fn from(info: PeerInfo) -> Self {
+        Self {
+            peer_id: info.peer_id,
+            chain_info: info.chain_info.into(),
+            notif_protocols: info.notif_protocols.join(","),
+            rpc_protocols: info.rpc_protocols.join(","),
+            banned_until: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StateWithProofView {
+    pub state: Option<StrView<Vec<u8>>>,
+    pub account_state: Option<StrView<Vec<u8>>>,
+    pub account_proof: SparseMerkleProof,
+    pub account_state_proof: SparseMerkleProof,
+    /// The state root which the proof is generated against.
+    pub state_root: HashValue,
+}
+
+impl StateWithProofView {
+    pub fn new(state_proof: StateWithProof, state_root: HashValue) -> Self {
+        let state = state_proof.state.map(StrView);
+        Self {
+            state,
+            account_state: state_proof.proof.account_state.map(|b| StrView(b.into())),
+            account_proof: state_proof.proof.account_proof,
+            account_state_proof: state_proof.proof.account_state_proof,
+            state_root,
+        }
+    }
+
+    pub fn state_proof(&self) -> StateProof {
+        StateProof::new(
+            self.account_state.clone().map(|v| v.0),
+            self.account_proof.clone(),
+            self.account_state_proof.clone(),
+        )
+    }
+
+    /// Verify the membership or non-membership of the `state` at `access_path`
+    /// against the `state_root` of the view, return the error if the proof can not
+    /// be decoded or verified.
+    pub fn verify(&self, access_path: &AccessPath) -> anyhow::Result<()> {
+        self.state_proof().verify(
+            self.state_root,
+            access_path.clone(),
+            self.state.as_ref().map(|v| v.0.as_slice()),
+        )
+    }
+}
+
 impl From<StateWithProofView> for StateWithProof {
     fn from(view: StateWithProofView) -> Self {
         let state = view.state.map(|v| v.0);
@@ -1003,7 +2496,7 @@ impl From<StateWithProofView> for StateWithProof {
     }
 }
 
-#[derive(Debug, PartialEq, Hash, Eq, Clone, Copy, PartialOrd, Ord)]
+#[derive(Debug, Default, PartialEq, Hash, Eq, Clone, Copy, PartialOrd, Ord)]
 pub struct StrView<T>(pub T);
 
 impl<T> From<T> for StrView<T> {
@@ -1033,9 +2526,78 @@ where
     where
         D: Deserializer<'de>,
     {
-        let s = <String>::deserialize(deserializer)?;
+        if deserializer.is_human_readable() {
+            // the numbers were serialized as raw json numbers before, still accept them.
+            deserializer.deserialize_any(StrViewVisitor(PhantomData))
+        } else {
+            let s = <String>::deserialize(deserializer)?;
+            StrView::<T>::from_str(&s).map_err(D::Error::custom)
+        }
+    }
+}
+
+/// The key of the number in serde_json's `arbitrary_precision` mode.
+const JSON_NUMBER_TOKEN: &str = "$serde_json::private::Number";
+
+struct StrViewVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for StrViewVisitor<T>
+where
+    StrView<T>: FromStr,
+    <StrView<T> as FromStr>::Err: std::fmt::Display,
+{
+    type Value = StrView<T>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a string or a legacy integer")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        StrView::<T>::from_str(v).map_err(E::custom)
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visit_str(v.to_string().as_str())
+    }
 
-        StrView::<T>::from_str(&s).map_err(D::Error::custom)
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visit_str(v.to_string().as_str())
+    }
+
+    fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visit_str(v.to_string().as_str())
+    }
+
+    fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visit_str(v.to_string().as_str())
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        match map.next_key::<String>()? {
+            Some(key) if key == JSON_NUMBER_TOKEN => {
+                let number = map.next_value::<String>()?;
+                self.visit_str(number.as_str())
+            }
+            _ => Err(A::Error::custom("expect a string or a legacy integer")),
+        }
     }
 }
 
@@ -1044,6 +2606,53 @@ pub type TypeTagView = StrView<TypeTag>;
 pub type StructTagView = StrView<StructTag>;
 pub type TransactionArgumentView = StrView<TransactionArgument>;
 pub type FunctionIdView = StrView<FunctionId>;
+pub type TokenCodeView = StrView<TokenCode>;
+pub type AccountAddressView = StrView<AccountAddress>;
+
+/// Encode the bytes as hex with the `0x` prefix, all the byte views are serialized by it.
+pub fn encode_hex_bytes(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// Decode the hex bytes with or without the `0x` prefix, the legacy payloads have no prefix.
+pub fn decode_hex_bytes(s: &str) -> anyhow::Result<Vec<u8>> {
+    let s = s.trim();
+    hex::decode(strip_hex_prefix(s).unwrap_or(s))
+        .map_err(|e| anyhow::format_err!("invalid hex bytes `{}`: {}", s, e))
+}
+
+/// Parse the account address at the rpc boundary, it can be the short form with the `0x` prefix such as `0x1`,
+/// or the full 32 hex digits with or without the prefix.
+pub fn parse_account_address(s: &str) -> anyhow::Result<AccountAddress> {
+    let s = s.trim();
+    let max_len = AccountAddress::LENGTH * 2;
+    let hex = match strip_hex_prefix(s) {
+        Some(hex) => {
+            anyhow::ensure!(
+                !hex.is_empty() && hex.len() <= max_len,
+                "invalid account address `{}`, expect at most {} hex digits after the 0x prefix",
+                s,
+                max_len
+            );
+            format!("{:0>width$}", hex, width = max_len)
+        }
+        None => {
+            anyhow::ensure!(
+                s.len() == max_len,
+                "invalid account address `{}`, the short form requires the 0x prefix",
+                s
+            );
+            s.to_string()
+        }
+    };
+    let bytes = hex::decode(&hex)
+        .map_err(|e| anyhow::format_err!("invalid account address `{}`: {}", s, e))?;
+    Ok(AccountAddress::try_from(bytes.as_slice())?)
+}
+
+fn strip_hex_prefix(s: &str) -> Option<&str> {
+    s.strip_prefix("0x").or_else(|| s.strip_prefix("0X"))
+}
 
 impl std::fmt::Display for FunctionIdView {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -1066,6 +2675,58 @@ impl FromStr for FunctionIdView {
     }
 }
 
+impl std::fmt::Display for StrView<TokenCode> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", &self.0)
+    }
+}
+
+impl FromStr for TokenCodeView {
+    type Err = anyhow::Error;
+
+    /// Parse the token code in `address::module::name` form, the address can be the short form like `0x1`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split("::").collect();
+        if parts.len() != 3 {
+            anyhow::bail!(
+                "invalid token code `{}`, expected `address::module::name`",
+                s
+            );
+        }
+        let address = parse_account_address(parts[0])
+            .map_err(|e| anyhow::format_err!("invalid token code `{}`, bad address: {}", s, e))?;
+        let module = Identifier::new(parts[1])
+            .map_err(|e| anyhow::format_err!("invalid token code `{}`, bad module: {}", s, e))?;
+        let name = Identifier::new(parts[2])
+            .map_err(|e| anyhow::format_err!("invalid token code `{}`, bad name: {}", s, e))?;
+        Ok(Self(TokenCode {
+            address,
+            module: module.into_string(),
+            name: name.into_string(),
+        }))
+    }
+}
+
+/// Deserialize the optional token code field, the error names the field as well as the invalid value.
+fn deserialize_gas_token_code<'de, D>(deserializer: D) -> Result<Option<TokenCodeView>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|s| {
+            TokenCodeView::from_str(s.as_str())
+                .map_err(|e| D::Error::custom(format!("invalid gas_token_code: {}", e)))
+        })
+        .transpose()
+}
+
+fn deserialize_chain_id<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<ChainIdView>::deserialize(deserializer)?.map(|chain_id| chain_id.id))
+}
+
 impl std::fmt::Display for StrView<ModuleId> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", &self.0)
@@ -1080,7 +2741,7 @@ impl FromStr for StrView<ModuleId> {
         if parts.len() != 2 {
             anyhow::bail!("invalid module id");
         }
-        let module_addr = parts[0].parse::<AccountAddress>()?;
+        let module_addr = parse_account_address(parts[0])?;
         let module_name = Identifier::new(parts[1])?;
         Ok(Self(ModuleId::new(module_addr, module_name)))
     }
@@ -1126,6 +2787,11 @@ impl FromStr for TransactionArgumentView {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // the address arg can be a receipt identifier, only the address is kept.
+        if ReceiptIdentifierView::is_receipt_identifier(s.trim()) {
+            let receipt = ReceiptIdentifierView::from_str(s)?;
+            return Ok(Self(TransactionArgument::Address(receipt.address())));
+        }
         let arg = parse_transaction_argument(s)?;
         Ok(Self(arg))
     }
@@ -1133,14 +2799,28 @@ impl FromStr for TransactionArgumentView {
 
 impl std::fmt::Display for StrView<Vec<u8>> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "0x{}", hex::encode(&self.0))
+        write!(f, "{}", encode_hex_bytes(&self.0))
     }
 }
 
 impl FromStr for StrView<Vec<u8>> {
     type Err = anyhow::Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(hex::decode(s.strip_prefix("0x").unwrap_or(s))?))
+        decode_hex_bytes(s).map(Self)
+    }
+}
+
+/// Always displayed as the full 32 hex digits with the `0x` prefix, parsed by `parse_account_address`.
+impl std::fmt::Display for StrView<AccountAddress> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", encode_hex_bytes(self.0.as_ref()))
+    }
+}
+
+impl FromStr for StrView<AccountAddress> {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_account_address(s).map(Self)
     }
 }
 
@@ -1161,6 +2841,26 @@ impl FromStr for StrView<AccountPublicKey> {
     }
 }
 
+/// Always displayed as hex, parsed from both hex with `0x` prefix and decimal.
+impl std::fmt::Display for StrView<U256> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{:x}", self.0)
+    }
+}
+
+impl FromStr for StrView<U256> {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = match s.strip_prefix("0x") {
+            Some(hex) => U256::from_str_radix(hex, 16)
+                .map_err(|e| anyhow::format_err!("invalid hex U256 {}: {:?}", s, e))?,
+            None => U256::from_dec_str(s)
+                .map_err(|e| anyhow::format_err!("invalid decimal U256 {}: {:?}", s, e))?,
+        };
+        Ok(Self(value))
+    }
+}
+
 macro_rules! impl_str_view_for {
     ($($t:ty)*) => {$(
     impl std::fmt::Display for StrView<$t> {
@@ -1212,11 +2912,7 @@ impl std::convert::AsRef<[u8]> for BytesView {
 
 impl std::fmt::Display for BytesView {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        for byte in self.inner() {
-            write!(f, "{:02x}", byte)?;
-        }
-
-        Ok(())
+        write!(f, "{}", encode_hex_bytes(self.inner()))
     }
 }
 
@@ -1238,7 +2934,7 @@ impl<'de> Deserialize<'de> for BytesView {
         D: Deserializer<'de>,
     {
         let s = <String>::deserialize(deserializer)?;
-        <Vec<u8>>::from_hex(s)
+        decode_hex_bytes(&s)
             .map_err(D::Error::custom)
             .map(Into::into)
     }
@@ -1249,15 +2945,409 @@ impl Serialize for BytesView {
     where
         S: Serializer,
     {
-        hex::encode(self).serialize(serializer)
+        encode_hex_bytes(self.inner()).serialize(serializer)
     }
 }
 
+/// The human readable part of the receipt identifier.
+pub const RECEIPT_IDENTIFIER_HRP: &str = "stc";
+const RECEIPT_IDENTIFIER_VERSION: u8 = 1;
+
+/// The receipt identifier is the bech32 encoding of the address and the optional auth key of an account,
+/// such as `stc1p...`, the first data symbol is the version.
+/// The auth key is needed by the transfer to create the account if the account not exist on chain.
+/// A bare address such as `0x1` is also accepted when parsing.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ReceiptIdentifierView {
+    address: AccountAddress,
+    auth_key: Option<AuthenticationKey>,
+}
+
+impl ReceiptIdentifierView {
+    /// Fails if the address is not derived from the auth key.
+    pub fn new(
+        address: AccountAddress,
+        auth_key: Option<AuthenticationKey>,
+    ) -> anyhow::Result<Self> {
+        if let Some(auth_key) = auth_key {
+            anyhow::ensure!(
+                auth_key.derived_address() == address,
+                "invalid receipt identifier: the auth key {} derives address {}, but the address is {}",
+                auth_key,
+                auth_key.derived_address(),
+                address
+            );
+        }
+        Ok(Self { address, auth_key })
+    }
+
+    pub fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    pub fn auth_key(&self) -> Option<AuthenticationKey> {
+        self.auth_key
+    }
+
+    /// Whether the `s` looks like a receipt identifier rather than a bare address.
+    pub fn is_receipt_identifier(s: &str) -> bool {
+        s.get(..RECEIPT_IDENTIFIER_HRP.len() + 1)
+            .map(|prefix| prefix.eq_ignore_ascii_case(&format!("{}1", RECEIPT_IDENTIFIER_HRP)))
+            .unwrap_or(false)
+    }
+
+    fn decode(s: &str) -> anyhow::Result<Self> {
+        let (hrp, data, variant) = bech32::decode(s).map_err(|e| match e {
+            bech32::Error::InvalidChecksum => {
+                anyhow::format_err!("invalid receipt identifier {}: checksum mismatch", s)
+            }
+            e => anyhow::format_err!("invalid receipt identifier {}: {}", s, e),
+        })?;
+        anyhow::ensure!(
+            variant == bech32::Variant::Bech32,
+            "invalid receipt identifier {}: expect the bech32 variant, but got {:?}",
+            s,
+            variant
+        );
+        anyhow::ensure!(
+            hrp == RECEIPT_IDENTIFIER_HRP,
+            "invalid receipt identifier {}: expect the prefix {}, but got {}",
+            s,
+            RECEIPT_IDENTIFIER_HRP,
+            hrp
+        );
+        let (version, data) = data
+            .split_first()
+            .ok_or_else(|| anyhow::format_err!("invalid receipt identifier {}: empty data", s))?;
+        anyhow::ensure!(
+            version.to_u8() == RECEIPT_IDENTIFIER_VERSION,
+            "invalid receipt identifier {}: unsupported version {}",
+            s,
+            version.to_u8()
+        );
+        let bytes = Vec::<u8>::from_base32(data)
+            .map_err(|e| anyhow::format_err!("invalid receipt identifier {}: {}", s, e))?;
+        let (address, auth_key) = if bytes.len() == AccountAddress::LENGTH {
+            (AccountAddress::try_from(bytes.as_slice())?, None)
+        } else if bytes.len() == AccountAddress::LENGTH + AuthenticationKey::LENGTH {
+            let (address, auth_key) = bytes.split_at(AccountAddress::LENGTH);
+            (
+                AccountAddress::try_from(address)?,
+                Some(AuthenticationKey::try_from(auth_key)?),
+            )
+        } else {
+            anyhow::bail!(
+                "invalid receipt identifier {}: expect {} bytes of address or {} bytes of address and auth key, but got {} bytes",
+                s,
+                AccountAddress::LENGTH,
+                AccountAddress::LENGTH + AuthenticationKey::LENGTH,
+                bytes.len()
+            );
+        };
+        Self::new(address, auth_key)
+    }
+}
+
+impl From<AccountAddress> for ReceiptIdentifierView {
+    fn from(address: AccountAddress) -> Self {
+        Self {
+            address,
+            auth_key: None,
+        }
+    }
+}
+
+impl std::fmt::Display for ReceiptIdentifierView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut bytes = self.address.to_vec();
+        if let Some(auth_key) = self.auth_key {
+            bytes.extend(auth_key.to_vec());
+        }
+        let mut data = vec![bech32::u5::try_from_u8(RECEIPT_IDENTIFIER_VERSION)
+            .expect("the version should be a valid u5")];
+        data.extend(bytes.to_base32());
+        let encoded = bech32::encode(RECEIPT_IDENTIFIER_HRP, data, bech32::Variant::Bech32)
+            .map_err(|_| std::fmt::Error)?;
+        write!(f, "{}", encoded)
+    }
+}
+
+impl FromStr for ReceiptIdentifierView {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if Self::is_receipt_identifier(s) {
+            Self::decode(s)
+        } else {
+            Ok(parse_account_address(s)?.into())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ReceiptIdentifierView {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = <String>::deserialize(deserializer)?;
+        Self::from_str(s.as_str()).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for ReceiptIdentifierView {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.to_string().serialize(serializer)
+    }
+}
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ContractCall {
     pub function_id: FunctionIdView,
     pub type_args: Vec<TypeTagView>,
-    pub args: Vec<TransactionArgumentView>,
+    /// Human readable args such as `1u8`, or json literals such as `["0x1","0x2"]`, see `ArgumentsView::from_values`.
+    pub args: Vec<serde_json::Value>,
+}
+
+/// Options of `contract.get_code`.
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GetCodeOption {
+    /// Attach the textual disassembly of the module.
+    pub disassemble: bool,
+}
+
+/// The on-chain code of a module.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CodeView {
+    pub code: StrView<Vec<u8>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disassembly: Option<String>,
+    /// The modules used by the module.
+    pub dependencies: Vec<ModuleIdView>,
+}
+
+/// The disassembly of a module or script bytecode.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DisassemblyView {
+    /// None if the bytecode is a script.
+    pub module_id: Option<ModuleIdView>,
+    pub dependencies: Vec<ModuleIdView>,
+    pub disassembly: String,
+}
+
+/// ABI of the script functions in a module.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ModuleAbiView {
+    pub module_id: ModuleIdView,
+    pub script_functions: Vec<FunctionAbiView>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FunctionAbiView {
+    pub name: String,
+    pub type_params: Vec<TypeParamAbiView>,
+    /// The arguments passed by the transaction, the leading signer is not included.
+    pub args: Vec<ArgumentAbiView>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TypeParamAbiView {
+    pub name: String,
+    /// The ability constraints, such as `store`.
+    pub abilities: Vec<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ArgumentAbiView {
+    pub name: String,
+    pub type_tag: TypeTagView,
+}
+
+/// A transaction argument decoded by the function ABI.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DecodedArgView {
+    pub name: String,
+    pub type_tag: TypeTagView,
+    pub value: AnnotatedMoveValueView,
+}
+
+impl ModuleAbiView {
+    /// Build the ABI from the compiled module, `param_names` returns the type parameter names and
+    /// parameter names of the function definition at the index, they are named by position if absent.
+    pub fn new<F>(module: &CompiledModule, param_names: F) -> anyhow::Result<Self>
+    where
+        F: Fn(usize) -> Option<(Vec<String>, Vec<String>)>,
+    {
+        let mut script_functions = vec![];
+        for (idx, function_def) in module.function_defs().iter().enumerate() {
+            if function_def.visibility != Visibility::Script {
+                continue;
+            }
+            let handle = module.function_handle_at(function_def.function);
+            let (type_param_names, arg_names) = param_names(idx).unwrap_or_default();
+            let type_params = handle
+                .type_parameters
+                .iter()
+                .enumerate()
+                .map(|(i, abilities)| TypeParamAbiView {
+                    name: type_param_names
+                        .get(i)
+                        .cloned()
+                        .unwrap_or_else(|| format!("T{}", i)),
+                    abilities: [
+                        (Ability::Copy, "copy"),
+                        (Ability::Drop, "drop"),
+                        (Ability::Store, "store"),
+                        (Ability::Key, "key"),
+                    ]
+                    .iter()
+                    .filter(|(ability, _)| abilities.has_ability(*ability))
+                    .map(|(_, name)| name.to_string())
+                    .collect(),
+                })
+                .collect();
+            let mut args = vec![];
+            for (i, token) in module.signature_at(handle.parameters).0.iter().enumerate() {
+                let type_tag = match token {
+                    SignatureToken::Signer => continue,
+                    SignatureToken::Reference(inner) if **inner == SignatureToken::Signer => {
+                        continue
+                    }
+                    token => signature_token_to_type_tag(module, token)?,
+                };
+                args.push(ArgumentAbiView {
+                    name: arg_names
+                        .get(i)
+                        .cloned()
+                        .unwrap_or_else(|| format!("arg{}", i)),
+                    type_tag: StrView(type_tag),
+                });
+            }
+            script_functions.push(FunctionAbiView {
+                name: module.identifier_at(handle.name).to_string(),
+                type_params,
+                args,
+            });
+        }
+        Ok(Self {
+            module_id: StrView(module.self_id()),
+            script_functions,
+        })
+    }
+
+    pub fn function(&self, name: &str) -> Option<&FunctionAbiView> {
+        self.script_functions.iter().find(|f| f.name == name)
+    }
+}
+
+/// The parameter types of any function defined in the module, the signer parameters are not included.
+pub fn function_arg_types(
+    module: &CompiledModule,
+    name: &str,
+) -> anyhow::Result<Option<Vec<TypeTag>>> {
+    let function_def = module.function_defs().iter().find(|function_def| {
+        module
+            .identifier_at(module.function_handle_at(function_def.function).name)
+            .as_str()
+            == name
+    });
+    let function_def = match function_def {
+        Some(function_def) => function_def,
+        None => return Ok(None),
+    };
+    let handle = module.function_handle_at(function_def.function);
+    let mut arg_types = vec![];
+    for token in module.signature_at(handle.parameters).0.iter() {
+        match token {
+            SignatureToken::Signer => continue,
+            SignatureToken::Reference(inner) if **inner == SignatureToken::Signer => continue,
+            token => arg_types.push(signature_token_to_type_tag(module, token)?),
+        }
+    }
+    Ok(Some(arg_types))
+}
+
+fn type_tag_to_layout(type_tag: &TypeTag) -> anyhow::Result<MoveTypeLayout> {
+    Ok(match type_tag {
+        TypeTag::Bool => MoveTypeLayout::Bool,
+        TypeTag::U8 => MoveTypeLayout::U8,
+        TypeTag::U64 => MoveTypeLayout::U64,
+        TypeTag::U128 => MoveTypeLayout::U128,
+        TypeTag::Address => MoveTypeLayout::Address,
+        TypeTag::Signer => MoveTypeLayout::Signer,
+        TypeTag::Vector(inner) => MoveTypeLayout::Vector(Box::new(type_tag_to_layout(inner)?)),
+        TypeTag::Struct(struct_tag) => {
+            anyhow::bail!("unsupported struct argument type {}", struct_tag)
+        }
+    })
+}
+
+fn move_value_to_view(value: MoveValue) -> anyhow::Result<AnnotatedMoveValueView> {
+    Ok(match value {
+        MoveValue::U8(u) => AnnotatedMoveValueView::U8(u),
+        MoveValue::U64(u) => AnnotatedMoveValueView::U64(StrView(u)),
+        MoveValue::U128(u) => AnnotatedMoveValueView::U128(StrView(u)),
+        MoveValue::Bool(b) => AnnotatedMoveValueView::Bool(b),
+        MoveValue::Address(address) => AnnotatedMoveValueView::Address(address),
+        MoveValue::Signer(address) => AnnotatedMoveValueView::Signer(address),
+        MoveValue::Vector(values) => AnnotatedMoveValueView::Vector(
+            values
+                .into_iter()
+                .map(move_value_to_view)
+                .collect::<anyhow::Result<Vec<_>>>()?,
+        ),
+        MoveValue::Struct(_) => anyhow::bail!("unsupported struct value"),
+    })
+}
+
+fn signature_token_to_type_tag(
+    module: &CompiledModule,
+    token: &SignatureToken,
+) -> anyhow::Result<TypeTag> {
+    Ok(match token {
+        SignatureToken::Bool => TypeTag::Bool,
+        SignatureToken::U8 => TypeTag::U8,
+        SignatureToken::U64 => TypeTag::U64,
+        SignatureToken::U128 => TypeTag::U128,
+        SignatureToken::Address => TypeTag::Address,
+        SignatureToken::Signer => TypeTag::Signer,
+        SignatureToken::Vector(inner) => {
+            TypeTag::Vector(Box::new(signature_token_to_type_tag(module, inner)?))
+        }
+        SignatureToken::Struct(idx) => {
+            TypeTag::Struct(struct_handle_to_struct_tag(module, *idx, vec![]))
+        }
+        SignatureToken::StructInstantiation(idx, type_args) => {
+            let type_params = type_args
+                .iter()
+                .map(|token| signature_token_to_type_tag(module, token))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            TypeTag::Struct(struct_handle_to_struct_tag(module, *idx, type_params))
+        }
+        SignatureToken::TypeParameter(_)
+        | SignatureToken::Reference(_)
+        | SignatureToken::MutableReference(_) => {
+            anyhow::bail!("unsupported argument type {:?} of script function", token)
+        }
+    })
+}
+
+fn struct_handle_to_struct_tag(
+    module: &CompiledModule,
+    idx: StructHandleIndex,
+    type_params: Vec<TypeTag>,
+) -> StructTag {
+    let struct_handle = module.struct_handle_at(idx);
+    let module_handle = module.module_handle_at(struct_handle.module);
+    StructTag {
+        address: *module.address_identifier_at(module_handle.address),
+        module: module.identifier_at(module_handle.name).to_owned(),
+        name: module.identifier_at(struct_handle.name).to_owned(),
+        type_params,
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1269,7 +3359,10 @@ impl ServiceRequest for ConnectLocal {
 
 #[cfg(test)]
 mod tests {
-    use crate::types::{ByteCodeOrScriptFunction, FunctionId};
+    use crate::types::{
+        ByteCodeOrScriptFunction, FunctionId, StrView, TokenInfoView, TransactionInfoView,
+        TransactionView,
+    };
     use starcoin_types::account_address::AccountAddress;
 
     #[test]
@@ -1290,4 +3383,551 @@ mod tests {
         let bytecode: ByteCodeOrScriptFunction = "0x123432ab34".parse().unwrap();
         assert!(matches!(bytecode, ByteCodeOrScriptFunction::ByteCode(_)));
     }
+
+    #[test]
+    fn test_format_amount() {
+        let token_info = TokenInfoView {
+            token_code: "0x1::STC::STC".to_string(),
+            symbol: "STC".to_string(),
+            scaling_factor: StrView(1_000_000_000),
+            total_supply: StrView(0),
+        };
+        assert_eq!(token_info.format_amount(0), "0");
+        assert_eq!(token_info.format_amount(1), "0.000000001");
+        assert_eq!(token_info.format_amount(1_500_000_000), "1.5");
+        assert_eq!(token_info.format_amount(20_000_000_000), "20");
+
+        let token_info = TokenInfoView {
+            scaling_factor: StrView(1),
+            ..token_info
+        };
+        assert_eq!(token_info.format_amount(123), "123");
+    }
+
+    #[test]
+    fn test_token_code_view() {
+        use crate::types::{TokenCodeView, TransactionRequest};
+        use starcoin_types::account_config::STC_TOKEN_CODE;
+
+        for s in vec![
+            "0x1::STC::STC",
+            "0x00000000000000000000000000000001::STC::STC",
+        ] {
+            let token_code: TokenCodeView = s.parse().unwrap();
+            assert_eq!(token_code.0, *STC_TOKEN_CODE);
+        }
+        for s in vec![
+            "0x1::STC:STC",
+            "0x1::STC",
+            "0x1::STC::STC::STC",
+            "0xg::STC::STC",
+            "0x1::STC::STC<u8>",
+            "0x1::::STC",
+        ] {
+            assert!(
+                s.parse::<TokenCodeView>().is_err(),
+                "{} should be invalid",
+                s
+            );
+        }
+
+        // the plain string of the old version is still accepted.
+        let request: TransactionRequest =
+            serde_json::from_str(r#"{"gas_token_code": "0x1::STC::STC"}"#).unwrap();
+        assert_eq!(
+            request.gas_token_code.map(|token_code| token_code.0),
+            Some(STC_TOKEN_CODE.clone())
+        );
+        let request: TransactionRequest = serde_json::from_str("{}").unwrap();
+        assert!(request.gas_token_code.is_none());
+        let err =
+            serde_json::from_str::<TransactionRequest>(r#"{"gas_token_code": "0x1::STC:STC"}"#)
+                .unwrap_err();
+        assert!(err.to_string().contains("gas_token_code"));
+    }
+
+    #[test]
+    fn test_decode_args() {
+        use crate::types::{
+            AnnotatedMoveValueView, ArgumentAbiView, ArgumentsView, FunctionAbiView, ScriptData,
+            TransactionRequest,
+        };
+        use starcoin_types::language_storage::TypeTag;
+
+        let abi = FunctionAbiView {
+            name: "peer_to_peer".to_string(),
+            type_params: vec![],
+            args: vec![
+                ArgumentAbiView {
+                    name: "payee".to_string(),
+                    type_tag: StrView(TypeTag::Address),
+                },
+                ArgumentAbiView {
+                    name: "payee_auth_key".to_string(),
+                    type_tag: StrView(TypeTag::Vector(Box::new(TypeTag::U8))),
+                },
+                ArgumentAbiView {
+                    name: "amount".to_string(),
+                    type_tag: StrView(TypeTag::U128),
+                },
+            ],
+        };
+        let payee = AccountAddress::random();
+        let request = TransactionRequest {
+            script: Some(ScriptData {
+                code: StrView("0x1::TransferScripts::peer_to_peer".parse().unwrap()),
+                type_args: vec![],
+                args: ArgumentsView::BCS(vec![
+                    StrView(bcs_ext::to_bytes(&payee).unwrap()),
+                    StrView(bcs_ext::to_bytes(&vec![1u8, 2u8]).unwrap()),
+                    StrView(bcs_ext::to_bytes(&100u128).unwrap()),
+                ]),
+            }),
+            ..Default::default()
+        };
+        let args = request.decode_args(&abi).unwrap();
+        assert_eq!(args.len(), 3);
+        assert_eq!(args[0].name, "payee");
+        assert!(matches!(args[0].value, AnnotatedMoveValueView::Address(a) if a == payee));
+        assert!(
+            matches!(&args[1].value, AnnotatedMoveValueView::Bytes(bytes) if bytes.0 == vec![1u8, 2u8])
+        );
+        assert!(matches!(
+            args[2].value,
+            AnnotatedMoveValueView::U128(StrView(100))
+        ));
+
+        let abi = FunctionAbiView {
+            args: abi.args[..2].to_vec(),
+            ..abi
+        };
+        assert!(request.decode_args(&abi).is_err());
+    }
+
+    #[test]
+    fn test_json_args() {
+        use crate::types::ArgumentsView;
+        use starcoin_types::language_storage::TypeTag;
+        use std::str::FromStr;
+
+        let vec_of = |t: TypeTag| TypeTag::Vector(Box::new(t));
+
+        // all strings are human readable args.
+        let args: ArgumentsView = serde_json::from_str(r#"["1u8", "0x1"]"#).unwrap();
+        assert!(matches!(args, ArgumentsView::HumanReadable(_)));
+        assert!(!args.needs_arg_types());
+
+        let a1 = AccountAddress::from_str("0x1").unwrap();
+        let a2 = AccountAddress::from_str("0x2").unwrap();
+        let args: ArgumentsView =
+            serde_json::from_str(r#"[["0x1", "0x2"], [], [[[1, 2], []], []], "100", [true]]"#)
+                .unwrap();
+        assert!(args.needs_arg_types());
+        assert!(args.to_bcs_bytes().is_err());
+        let arg_types = vec![
+            vec_of(TypeTag::Address),
+            vec_of(TypeTag::U64),
+            vec_of(vec_of(vec_of(TypeTag::U8))),
+            TypeTag::U128,
+            vec_of(TypeTag::Bool),
+        ];
+        let bytes = args.to_bcs_bytes_by_types(&arg_types).unwrap();
+        assert_eq!(bytes[0], bcs_ext::to_bytes(&vec![a1, a2]).unwrap());
+        assert_eq!(bytes[1], bcs_ext::to_bytes(&Vec::<u64>::new()).unwrap());
+        assert_eq!(
+            bytes[2],
+            bcs_ext::to_bytes(&vec![vec![vec![1u8, 2u8], vec![]], vec![]]).unwrap()
+        );
+        assert_eq!(bytes[3], bcs_ext::to_bytes(&100u128).unwrap());
+        assert_eq!(bytes[4], bcs_ext::to_bytes(&vec![true]).unwrap());
+
+        // bytes can be a hex string or a bytes literal.
+        let args = ArgumentsView::from_literals(&[r#"["0x0102", "x\"03\""]"#]).unwrap();
+        let bytes = args
+            .to_bcs_bytes_by_types(&[vec_of(vec_of(TypeTag::U8))])
+            .unwrap();
+        assert_eq!(
+            bytes[0],
+            bcs_ext::to_bytes(&vec![vec![1u8, 2u8], vec![3u8]]).unwrap()
+        );
+
+        // args mismatch the parameter types.
+        assert!(args.to_bcs_bytes_by_types(&[]).is_err());
+        assert!(args
+            .to_bcs_bytes_by_types(&[vec_of(TypeTag::Address)])
+            .is_err());
+        let args = ArgumentsView::from_literals(&["[256]"]).unwrap();
+        assert!(args.to_bcs_bytes_by_types(&[vec_of(TypeTag::U8)]).is_err());
+
+        // json args are returned as is.
+        assert_eq!(serde_json::to_string(&args).unwrap(), "[[256]]");
+    }
+
+    #[test]
+    fn test_raw_txn_offline() {
+        use crate::types::{ArgumentsView, ScriptData, TransactionRequest};
+
+        let mut request = TransactionRequest {
+            script: Some(ScriptData {
+                code: StrView("0x1::EmptyScripts::empty_script".parse().unwrap()),
+                type_args: vec![],
+                args: ArgumentsView::BCS(vec![]),
+            }),
+            sequence_number: Some(StrView(3)),
+            ..Default::default()
+        };
+        let err = request.to_raw_txn_offline().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "offline transaction request requires the fields: sender, expiration_timestamp_secs, chain_id"
+        );
+
+        let sender = AccountAddress::random();
+        request.sender = Some(sender.into());
+        request.expiration_timestamp_secs = Some(StrView(1000));
+        request.chain_id = Some(254);
+        let raw_txn = request.to_raw_txn_offline().unwrap();
+        assert_eq!(raw_txn.sender(), sender);
+        assert_eq!(raw_txn.sequence_number(), 3);
+        assert_eq!(raw_txn.chain_id().id(), 254);
+        assert_eq!(
+            TransactionRequest::from(raw_txn.clone())
+                .to_raw_txn_offline()
+                .unwrap(),
+            raw_txn
+        );
+    }
+
+    #[test]
+    fn test_txn_diagnose_target() {
+        use crate::types::TxnDiagnoseTarget;
+        use starcoin_crypto::HashValue;
+
+        let txn_hash = HashValue::random();
+        let sender = AccountAddress::random();
+        let target: TxnDiagnoseTarget = format!("{:#x}", txn_hash).parse().unwrap();
+        assert_eq!(target, TxnDiagnoseTarget::Txn(txn_hash));
+        let target: TxnDiagnoseTarget = format!("{:#x}", sender).parse().unwrap();
+        assert_eq!(target, TxnDiagnoseTarget::Sender(sender));
+        assert!("not_a_hash".parse::<TxnDiagnoseTarget>().is_err());
+
+        for target in vec![
+            TxnDiagnoseTarget::Txn(txn_hash),
+            TxnDiagnoseTarget::Sender(sender),
+        ] {
+            let json = serde_json::to_string(&target).unwrap();
+            assert_eq!(
+                serde_json::from_str::<TxnDiagnoseTarget>(json.as_str()).unwrap(),
+                target
+            );
+        }
+    }
+
+    #[test]
+    fn test_u256_str_view() {
+        use starcoin_types::U256;
+
+        for value in vec![U256::zero(), U256::from(31u64), U256::max_value()] {
+            let json = serde_json::to_string(&StrView(value)).unwrap();
+            assert!(json.starts_with("\"0x"));
+            let view: StrView<U256> = serde_json::from_str(json.as_str()).unwrap();
+            assert_eq!(view.0, value);
+            let view: StrView<U256> =
+                serde_json::from_str(format!("\"{}\"", value).as_str()).unwrap();
+            assert_eq!(view.0, value);
+        }
+        assert_eq!(
+            serde_json::to_string(&StrView(U256::from(31u64))).unwrap(),
+            "\"0x1f\""
+        );
+        let view: StrView<U256> = serde_json::from_str("\"0x1f\"").unwrap();
+        assert_eq!(view.0, U256::from(31u64));
+        let view: StrView<U256> = serde_json::from_str("\"31\"").unwrap();
+        assert_eq!(view.0, U256::from(31u64));
+        // the old full length hex is still accepted.
+        let old_json = serde_json::to_string(&U256::max_value()).unwrap();
+        let view: StrView<U256> = serde_json::from_str(old_json.as_str()).unwrap();
+        assert_eq!(view.0, U256::max_value());
+        assert!(serde_json::from_str::<StrView<U256>>("\"0xzz\"").is_err());
+        assert!(serde_json::from_str::<StrView<U256>>("\"1f\"").is_err());
+    }
+
+    #[test]
+    fn test_hex_bytes_compat() {
+        use crate::types::BytesView;
+
+        let bytes = vec![0u8, 1, 0xab, 0xff];
+        // the old BytesView payload has no prefix, the others have.
+        for input in &["\"0001abff\"", "\"0x0001abff\"", "\"0X0001ABFF\""] {
+            let view: BytesView = serde_json::from_str(input).unwrap();
+            assert_eq!(view.inner(), bytes.as_slice(), "deserialize {} fail", input);
+            let view: StrView<Vec<u8>> = serde_json::from_str(input).unwrap();
+            assert_eq!(view.0, bytes, "deserialize {} fail", input);
+        }
+        assert_eq!(
+            serde_json::to_string(&BytesView::from(bytes.clone())).unwrap(),
+            "\"0x0001abff\""
+        );
+        assert_eq!(
+            serde_json::to_string(&StrView(bytes.clone())).unwrap(),
+            "\"0x0001abff\""
+        );
+        for input in &["\"\"", "\"0x\""] {
+            let view: BytesView = serde_json::from_str(input).unwrap();
+            assert!(view.is_empty());
+        }
+        assert!(serde_json::from_str::<BytesView>("\"0x0g\"").is_err());
+        assert!(serde_json::from_str::<StrView<Vec<u8>>>("\"0x123\"").is_err());
+
+        let code: StrView<ByteCodeOrScriptFunction> = serde_json::from_str("\"0001abff\"").unwrap();
+        assert_eq!(code.0, ByteCodeOrScriptFunction::ByteCode(bytes.clone()));
+        let code: StrView<ByteCodeOrScriptFunction> =
+            serde_json::from_str("\"0x0001abff\"").unwrap();
+        assert_eq!(code.0, ByteCodeOrScriptFunction::ByteCode(bytes));
+        assert_eq!(serde_json::to_string(&code).unwrap(), "\"0x0001abff\"");
+    }
+
+    #[test]
+    fn test_account_address_compat() {
+        use crate::types::{
+            parse_account_address, AccountAddressView, DryRunTransactionRequest,
+            ReceiptIdentifierView, StateOverride,
+        };
+        use std::str::FromStr;
+
+        let one = AccountAddress::from_hex_literal("0x1").unwrap();
+        let full = "00000000000000000000000000000001";
+        let prefixed_full = format!("0x{}", full);
+        for input in &["0x1", "0x01", "0X1", full, prefixed_full.as_str()] {
+            assert_eq!(
+                parse_account_address(input).unwrap(),
+                one,
+                "parse {} fail",
+                input
+            );
+            let view: AccountAddressView =
+                serde_json::from_str(format!("\"{}\"", input).as_str()).unwrap();
+            assert_eq!(view.0, one, "deserialize {} fail", input);
+            assert_eq!(
+                ReceiptIdentifierView::from_str(input).unwrap().address(),
+                one
+            );
+        }
+        assert_eq!(
+            serde_json::to_string(&AccountAddressView::from(one)).unwrap(),
+            format!("\"0x{}\"", full)
+        );
+        // the address serialized by the old clients is still accepted.
+        let address = AccountAddress::random();
+        let old_json = serde_json::to_string(&address).unwrap();
+        let view: AccountAddressView = serde_json::from_str(old_json.as_str()).unwrap();
+        assert_eq!(view.0, address);
+        let view: AccountAddressView =
+            serde_json::from_str(serde_json::to_string(&view).unwrap().as_str()).unwrap();
+        assert_eq!(view.0, address);
+
+        // the addresses of the txn requests are parsed by the same rule.
+        let request: DryRunTransactionRequest = serde_json::from_str(
+            format!(
+                r#"{{"sender": "{}", "state_overrides": [{{"address": "0x1", "balance": {{"token": "0x1::STC::STC", "amount": "1"}}}}]}}"#,
+                full
+            )
+            .as_str(),
+        )
+        .unwrap();
+        assert_eq!(
+            request.transaction.sender,
+            Some(AccountAddressView::from(one))
+        );
+        assert!(matches!(
+            request.state_overrides[0],
+            StateOverride::AccountBalance { address, .. } if address.0 == one
+        ));
+
+        // a bare short form is ambiguous with the decimal number, the prefix is required.
+        let too_long = format!("0x0{}", full);
+        for input in &["1", "0x", "", "0xzz", too_long.as_str(), &full[1..]] {
+            assert!(
+                parse_account_address(input).is_err(),
+                "parse {} should fail",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_block_headers_view_consistent() {
+        use crate::types::{BlockHeaderView, BlockHeadersView};
+        use starcoin_crypto::HashValue;
+        use starcoin_types::block::BlockHeaderBuilder;
+
+        let first = BlockHeaderBuilder::random().build();
+        let second = BlockHeaderBuilder::random()
+            .with_parent_hash(first.id())
+            .build();
+        let headers: Vec<BlockHeaderView> = vec![first.into(), second.into()];
+        let view = BlockHeadersView::new(headers.clone(), HashValue::random(), true);
+        assert!(view.consistent);
+
+        // the range is switched out of the main chain during the read.
+        let view = BlockHeadersView::new(headers.clone(), HashValue::random(), false);
+        assert!(!view.consistent);
+
+        let forked = BlockHeaderBuilder::random().build();
+        let view = BlockHeadersView::new(
+            vec![headers[0].clone(), forked.into()],
+            HashValue::random(),
+            true,
+        );
+        assert!(!view.consistent);
+    }
+
+    #[test]
+    fn test_state_override() {
+        use crate::types::StateOverride;
+        use starcoin_vm_types::account_config::{genesis_address, stc_type_tag, BalanceResource};
+        use starcoin_vm_types::token::stc::STC_TOKEN_CODE_STR;
+
+        let overrides: Vec<StateOverride> = serde_json::from_str(
+            format!(
+                r#"[
+                {{"address": "0x1", "resource_type": "0x1::Account::Balance<0x1::STC::STC>", "value": "0x01"}},
+                {{"address": "0x1", "resource_type": "0x1::Account::Balance<0x1::STC::STC>", "value": null}},
+                {{"address": "0x1", "balance": {{"token": "{}", "amount": "1000"}}}}
+            ]"#,
+                STC_TOKEN_CODE_STR
+            )
+            .as_str(),
+        )
+        .unwrap();
+        assert_eq!(overrides.len(), 3);
+        let balance_path = starcoin_vm_types::access_path::AccessPath::resource_access_path(
+            genesis_address(),
+            BalanceResource::struct_tag_for_token(stc_type_tag()),
+        );
+        let (path, value) = overrides[0].clone().into_access_path_value().unwrap();
+        assert_eq!(path, balance_path);
+        assert_eq!(value, Some(vec![1u8]));
+        let (_, value) = overrides[1].clone().into_access_path_value().unwrap();
+        assert!(value.is_none());
+        assert!(matches!(overrides[2], StateOverride::AccountBalance { .. }));
+        let (path, value) = overrides[2].clone().into_access_path_value().unwrap();
+        assert_eq!(path, balance_path);
+        assert_eq!(
+            value,
+            Some(bcs_ext::to_bytes(&BalanceResource::new(1000)).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_txn_index_of_info_and_txn_view() {
+        use starcoin_crypto::HashValue;
+        use starcoin_types::block::{Block, BlockBody, BlockHeader};
+        use starcoin_vm_types::transaction::{
+            BlockTransactionInfo, SignedUserTransaction, Transaction, TransactionInfo,
+            TransactionPosition,
+        };
+        use starcoin_vm_types::vm_status::KeptVMStatus;
+
+        let user_txns: Vec<_> = (0..3).map(|_| SignedUserTransaction::mock()).collect();
+        let uncles = vec![BlockHeader::random(), BlockHeader::random()];
+        let block = Block::new(
+            BlockHeader::random(),
+            BlockBody::new(user_txns.clone(), Some(uncles)),
+        );
+        // the txns in execution order, as the txn infos are saved.
+        let mut txns = vec![Transaction::BlockMetadata(block.to_metadata(0))];
+        txns.extend(user_txns.into_iter().map(Transaction::UserTransaction));
+        let positions = TransactionPosition::block_positions(
+            block.id(),
+            block.header().number(),
+            block.transactions().len(),
+            txns.len(),
+            10,
+        )
+        .unwrap();
+
+        let new_txn_info = |block_id, txn_hash| {
+            BlockTransactionInfo::new(
+                block_id,
+                TransactionInfo::new(
+                    txn_hash,
+                    HashValue::random(),
+                    &[],
+                    0,
+                    KeptVMStatus::Executed,
+                ),
+            )
+        };
+        for (i, (txn, position)) in txns.iter().zip(positions.iter()).enumerate() {
+            let info_view =
+                TransactionInfoView::new(new_txn_info(block.id(), txn.id()), position).unwrap();
+            let txn_view = TransactionView::new(txn.clone(), position).unwrap();
+            assert_eq!(info_view.transaction_index, i as u32);
+            assert_eq!(info_view.is_block_metadata, i == 0);
+            assert_eq!(info_view.global_index.0, 6 + i as u64);
+            assert_eq!(info_view.transaction_index, txn_view.transaction_index);
+            assert_eq!(info_view.transaction_hash, txn_view.transaction_hash);
+            assert_eq!(txn_view.block_hash, block.id());
+        }
+
+        // the txn of another block is not mislabeled as the txn at the position.
+        let other_txn = SignedUserTransaction::mock();
+        assert!(TransactionInfoView::new(
+            new_txn_info(HashValue::random(), other_txn.id()),
+            &positions[1]
+        )
+        .is_err());
+        // the block metadata txn of another block neither.
+        let other_block = Block::new(BlockHeader::random(), BlockBody::new(vec![], None));
+        let other_metadata = Transaction::BlockMetadata(other_block.to_metadata(0));
+        assert!(TransactionInfoView::new(
+            new_txn_info(other_block.id(), other_metadata.id()),
+            &positions[0]
+        )
+        .is_err());
+
+        // the block metadata txn must be at index 0, and the user txns must not.
+        assert!(TransactionView::new(txns[0].clone(), &positions[1]).is_err());
+        assert!(TransactionView::new(txns[1].clone(), &positions[0]).is_err());
+
+        // the genesis block has no block metadata txn, its user txn starts at 1 too.
+        let genesis_positions =
+            TransactionPosition::block_positions(block.id(), 0, 1, 1, 1).unwrap();
+        assert_eq!(genesis_positions[0].transaction_index, 1);
+        assert_eq!(genesis_positions[0].global_index, 0);
+        assert!(TransactionPosition::block_positions(block.id(), 0, 1, 3, 3).is_err());
+        assert!(TransactionPosition::block_positions(block.id(), 0, 3, 4, 3).is_err());
+    }
+
+    #[test]
+    fn test_chain_id_view() {
+        use crate::types::{ChainIdView, TransactionRequest};
+        use starcoin_config::BuiltinNetworkID;
+
+        let barnard = ChainIdView::from(BuiltinNetworkID::Barnard.chain_id());
+        assert_eq!(barnard.name.as_deref(), Some("barnard"));
+        assert_eq!(ChainIdView::from(123).name, None);
+
+        let json = serde_json::to_string(&barnard).unwrap();
+        assert_eq!(json, r#"{"id":251,"name":"barnard"}"#);
+        for input in &[json.as_str(), "251", "\"251\"", "\"barnard\""] {
+            assert_eq!(
+                serde_json::from_str::<ChainIdView>(input).unwrap(),
+                barnard,
+                "deserialize {} fail",
+                input
+            );
+        }
+        assert!(serde_json::from_str::<ChainIdView>("256").is_err());
+        assert!(serde_json::from_str::<ChainIdView>("\"unknown\"").is_err());
+
+        let request: TransactionRequest =
+            serde_json::from_str(r#"{"chain_id": "barnard"}"#).unwrap();
+        assert_eq!(request.chain_id, Some(251));
+        let request: TransactionRequest = serde_json::from_str(r#"{"chain_id": 1}"#).unwrap();
+        assert_eq!(request.chain_id, Some(1));
+        let request: TransactionRequest = serde_json::from_str("{}").unwrap();
+        assert_eq!(request.chain_id, None);
+    }
 }
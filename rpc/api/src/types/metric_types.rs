@@ -0,0 +1,103 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use starcoin_metrics::flatten_metric_with_labels;
+use starcoin_metrics::proto::{Histogram, MetricFamily, MetricType};
+use std::collections::BTreeMap;
+
+/// Summary of a prometheus histogram, the percentiles are estimated from the buckets.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HistogramSummaryView {
+    pub count: u64,
+    pub sum: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+}
+
+impl From<&Histogram> for HistogramSummaryView {
+    fn from(histogram: &Histogram) -> Self {
+        Self {
+            count: histogram.get_sample_count(),
+            sum: histogram.get_sample_sum(),
+            p50: histogram_quantile(histogram, 0.5),
+            p90: histogram_quantile(histogram, 0.9),
+            p99: histogram_quantile(histogram, 0.99),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MetricView {
+    Counter { value: f64 },
+    Gauge { value: f64 },
+    Histogram(HistogramSummaryView),
+}
+
+impl MetricView {
+    /// Convert the gathered metric families to views keyed by the flattened metric name,
+    /// only the metrics whose name starts with `prefix` are kept if `prefix` is present.
+    /// Summary and untyped metrics are ignored.
+    pub fn from_metric_families(
+        metric_families: &[MetricFamily],
+        prefix: Option<&str>,
+    ) -> BTreeMap<String, MetricView> {
+        let mut metrics = BTreeMap::new();
+        for metric_family in metric_families {
+            let name = metric_family.get_name();
+            if let Some(prefix) = prefix {
+                if !name.starts_with(prefix) {
+                    continue;
+                }
+            }
+            let metric_type = metric_family.get_field_type();
+            for m in metric_family.get_metric() {
+                let view = match metric_type {
+                    MetricType::COUNTER => MetricView::Counter {
+                        value: m.get_counter().get_value(),
+                    },
+                    MetricType::GAUGE => MetricView::Gauge {
+                        value: m.get_gauge().get_value(),
+                    },
+                    MetricType::HISTOGRAM => MetricView::Histogram(m.get_histogram().into()),
+                    MetricType::SUMMARY | MetricType::UNTYPED => continue,
+                };
+                metrics.insert(flatten_metric_with_labels(name, m), view);
+            }
+        }
+        metrics
+    }
+}
+
+/// Estimate the `quantile` of the histogram by linear interpolation inside the bucket,
+/// same as prometheus's `histogram_quantile`.
+fn histogram_quantile(histogram: &Histogram, quantile: f64) -> f64 {
+    let count = histogram.get_sample_count();
+    if count == 0 {
+        return 0.0;
+    }
+    let rank = quantile * count as f64;
+    let mut lower_bound = 0.0;
+    let mut lower_count = 0u64;
+    for bucket in histogram.get_bucket() {
+        let upper_bound = bucket.get_upper_bound();
+        let cumulative_count = bucket.get_cumulative_count();
+        if cumulative_count as f64 >= rank {
+            if upper_bound.is_infinite() {
+                return lower_bound;
+            }
+            let bucket_count = cumulative_count.saturating_sub(lower_count);
+            if bucket_count == 0 {
+                return upper_bound;
+            }
+            return lower_bound
+                + (upper_bound - lower_bound) * (rank - lower_count as f64) / bucket_count as f64;
+        }
+        lower_bound = upper_bound;
+        lower_count = cumulative_count;
+    }
+    // the rank falls into the implicit +Inf bucket.
+    lower_bound
+}
@@ -2,12 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::errors;
-use crate::types::{BlockView, TransactionEventView};
+use crate::types::{BlockView, EventPositionView, StrView, TransactionEventView, TypeTagView};
 use jsonrpc_core::error::Error as JsonRpcError;
+use jsonrpc_pubsub::SubscriptionId;
 use serde::de::Error;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::{from_value, Value};
 use starcoin_crypto::HashValue;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block::BlockNumber;
 use starcoin_types::event::EventKey;
 use starcoin_types::filter::Filter;
 use starcoin_types::U256;
@@ -28,13 +31,18 @@ pub enum Kind {
     NewPendingTransactions,
     /// New block for minting
     NewMintBlock,
+    /// Dropped Transactions subscription, such as the replaced transactions.
+    DroppedTransactions,
+    /// Evicted Transactions subscription, the transactions pushed out when the txpool is full,
+    /// they can be resubmitted with a higher gas price.
+    EvictedTransactions,
 }
 
 /// Subscription result.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Result {
-    /// New block.
-    Block(Box<BlockView>),
+    /// New block or retracted block.
+    Block(Box<BlockNotificationView>),
     /// Transaction hash
     TransactionHash(Vec<HashValue>),
     Event(Box<TransactionEventView>),
@@ -88,21 +96,34 @@ impl<'a> Deserialize<'a> for Params {
 }
 
 /// Filter
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq, Hash)]
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Eq, Hash)]
 #[serde(deny_unknown_fields)]
 pub struct EventFilter {
     /// From Block
     #[serde(default)]
-    pub from_block: Option<u64>,
+    pub from_block: Option<StrView<BlockNumber>>,
     /// To Block
     #[serde(default)]
-    pub to_block: Option<u64>,
+    pub to_block: Option<StrView<BlockNumber>>,
     /// Event keys
     #[serde(default)]
     pub event_keys: Vec<EventKey>,
-    /// Limit: from latest to oldest
+    /// Event types, match all types if empty.
+    #[serde(default)]
+    pub type_tags: Vec<TypeTagView>,
+    /// The addresses of the event keys, match all addresses if empty.
+    #[serde(default)]
+    pub addrs: Vec<AccountAddress>,
+    /// Limit: from latest to oldest for subscriptions, from oldest to latest for `chain.get_events`.
     #[serde(default)]
     pub limit: Option<usize>,
+    /// Only for `chain.get_events`, return the events after the cursor,
+    /// pass the position of the last returned event to fetch the next page.
+    #[serde(default)]
+    pub cursor: Option<EventPositionView>,
+    /// Only for `chain.get_events`, return the decoded event data.
+    #[serde(default)]
+    pub decode: bool,
 }
 
 impl TryInto<Filter> for EventFilter {
@@ -119,21 +140,63 @@ impl TryInto<Filter> for EventFilter {
             _ => {}
         }
         Ok(Filter {
-            from_block: self.from_block.unwrap_or(0),
-            to_block: self.to_block.unwrap_or(std::u64::MAX),
+            from_block: self.from_block.map(|n| n.0).unwrap_or(0),
+            to_block: self.to_block.map(|n| n.0).unwrap_or(std::u64::MAX),
             event_keys: self.event_keys,
+            type_tags: self.type_tags.into_iter().map(|tag| tag.0).collect(),
+            addrs: self.addrs,
             limit: self.limit,
-            reverse: true,
+            reverse: false,
+            cursor: self.cursor.map(Into::into),
         })
     }
 }
 
+/// The kind of a block notification.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockNotificationKind {
+    /// The block becomes the head of main chain.
+    NewHead,
+    /// The block is removed from main chain by a reorg.
+    Retracted,
+}
+
+/// Block notification of the `newHeads` subscription.
+/// When the main chain switches branches, `retracted` notifications of the removed blocks
+/// are sent from the old head down to the common ancestor, before the `new_head` notifications.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlockNotificationView {
+    pub kind: BlockNotificationKind,
+    pub block: BlockView,
+    /// Only set on the first `new_head` notification after a reorg.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub common_ancestor: Option<HashValue>,
+}
+
+impl BlockNotificationView {
+    pub fn is_new_head(&self) -> bool {
+        self.kind == BlockNotificationKind::NewHead
+    }
+}
+
 /// Block for minting
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct MintBlock {
     pub strategy: ConsensusStrategy,
     pub minting_blob: String,
-    pub difficulty: U256,
-    pub block_number: u64,
+    pub difficulty: StrView<U256>,
+    pub block_number: StrView<u64>,
+}
+
+/// An active subscription of the connection.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubscriptionView {
+    pub id: SubscriptionId,
+    pub kind: Kind,
+    /// The filter of the `events` subscription.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<EventFilter>,
 }
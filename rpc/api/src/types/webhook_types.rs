@@ -0,0 +1,58 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::types::{SignedUserTransactionView, StrView};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_types::block::BlockNumber;
+use starcoin_types::webhook::{WebhookFilter, WebhookRegistration};
+
+/// The webhook registration without the secret.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct WebhookView {
+    pub id: HashValue,
+    pub url: String,
+    pub filter: WebhookFilter,
+    /// The webhook registered by config can not be removed by rpc.
+    pub from_config: bool,
+}
+
+impl WebhookView {
+    pub fn new(registration: &WebhookRegistration, from_config: bool) -> Self {
+        Self {
+            id: registration.id,
+            url: registration.url.clone(),
+            filter: registration.filter.clone(),
+            from_config,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxnLifecycleStatus {
+    /// The transaction is accepted into the txpool.
+    Pending,
+    Included {
+        block_number: StrView<BlockNumber>,
+        block_hash: HashValue,
+    },
+    /// The transaction is removed from the txpool without being included.
+    Dropped {
+        reason: String,
+    },
+    Expired,
+}
+
+/// The json body the node POSTs to the webhook url, it is signed by HMAC-SHA256 with the registration's secret,
+/// the hex encoded signature is in the `X-Starcoin-Signature` header.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct WebhookPayload {
+    pub webhook_id: HashValue,
+    pub transaction_hash: HashValue,
+    pub status: TxnLifecycleStatus,
+    /// None if the node does not hold the transaction any more.
+    pub transaction: Option<SignedUserTransactionView>,
+    /// The time when the status changed, in milliseconds.
+    pub timestamp: StrView<u64>,
+}
@@ -9,6 +9,7 @@ use jsonrpc_core_client::RpcError;
 use serde::{Deserialize, Serialize};
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
+use starcoin_rpc_api::types::pubsub::BlockNotificationView;
 use starcoin_rpc_api::types::{BlockHeaderView, BlockView};
 use starcoin_types::block::BlockNumber;
 use std::collections::HashMap;
@@ -93,6 +94,14 @@ impl Handler<StartSubscribe> for ChainWatcher {
     }
 }
 
+/// The watched txn is not mined within the timeout.
+#[derive(Debug, thiserror::Error)]
+#[error("txn {txn_hash} is not mined within {timeout:?}")]
+pub struct WatchTxnTimeout {
+    pub txn_hash: HashValue,
+    pub timeout: Duration,
+}
+
 pub type WatchResult = Result<ThinHeadBlock, anyhow::Error>;
 type Responder = oneshot::Sender<WatchResult>;
 
@@ -137,12 +146,16 @@ impl Handler<WatchTxn> for ChainWatcher {
     }
 }
 
-type BlockEvent = Result<BlockView, RpcError>;
+type BlockEvent = Result<BlockNotificationView, RpcError>;
 impl actix::StreamHandler<BlockEvent> for ChainWatcher {
     fn handle(&mut self, item: BlockEvent, _ctx: &mut Self::Context) {
         match item {
-            Ok(b) => {
-                let b: ThinHeadBlock = b.into();
+            Ok(notification) => {
+                // only watch the blocks on main chain.
+                if !notification.is_new_head() {
+                    return;
+                }
+                let b: ThinHeadBlock = notification.block.into();
                 if let Some(responders) = self.watched_blocks.remove(&b.header.number.0) {
                     for r in responders {
                         let _ = r.send(Ok(b.clone()));
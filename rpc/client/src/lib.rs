@@ -1,31 +1,44 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
-use crate::chain_watcher::{ChainWatcher, StartSubscribe, WatchBlock, WatchTxn};
+use crate::chain_watcher::{ChainWatcher, StartSubscribe, WatchBlock, WatchTxn, WatchTxnTimeout};
 use crate::pubsub_client::PubSubClient;
+use crate::resilient_subscriber::{ResilientEventSubscriber, ResilientSubscribeOptions};
 use actix::{Addr, System};
 use anyhow::anyhow;
 use futures::channel::oneshot;
-use futures::{TryStream, TryStreamExt};
+use futures::{Stream, TryStream, TryStreamExt};
 use jsonrpc_client_transports::RawClient;
 use jsonrpc_core_client::{transports::ipc, transports::ws, RpcChannel};
+use network_api::messages::DiscoveredSeed;
 use network_api::PeerStrategy;
 use network_p2p_types::network_state::NetworkState;
 use parking_lot::Mutex;
 use serde_json::Value;
-use starcoin_account_api::AccountInfo;
+use starcoin_account_api::{AccountInfo, DerivedAccount};
+use starcoin_config::ConfigReloadResult;
 use starcoin_crypto::HashValue;
 use starcoin_logger::{prelude::*, LogPattern};
-use starcoin_rpc_api::node::NodeInfo;
+use starcoin_rpc_api::node::{
+    check_api_versions, ApiVersionMismatch, CheckpointsReport, HealthView, NodeInfo, SystemInfo,
+    WatchdogReport,
+};
 use starcoin_rpc_api::service::RpcAsyncService;
+use starcoin_rpc_api::transfer_export::TransferDataSource;
 use starcoin_rpc_api::types::pubsub::EventFilter;
-use starcoin_rpc_api::types::pubsub::MintBlock;
+use starcoin_rpc_api::types::pubsub::{BlockNotificationView, MintBlock, SubscriptionView};
 use starcoin_rpc_api::types::{
-    AccountStateSetView, AnnotatedMoveStructView, AnnotatedMoveValueView, BlockHeaderView,
-    BlockSummaryView, BlockView, ChainId, ChainInfoView, ContractCall, DryRunTransactionRequest,
-    EpochUncleSummaryView, FactoryAction, PeerInfoView, SignedUserTransactionView,
-    StateWithProofView, StrView, TransactionInfoView, TransactionOutputView, TransactionRequest,
-    TransactionView,
+    AccountStateSetView, AnnotatedMoveStructView, AnnotatedMoveValueView, BalanceView,
+    BlockHeaderView, BlockHeadersView, BlockRewardView, BlockSummaryView, BlockView, BranchView,
+    ChainId, ChainStatusView, CodeView, ContractCall, DisassemblyView, DryRunTransactionRequest,
+    EncodedView, EpochInfoView, EpochUncleSummaryView, EventCursorPageView, EventHandleView,
+    FactoryAction, FunctionAbiView, GasPriceOracleView, GetBlockOption, GetCodeOption,
+    ListCodeView, ListResourceView, MetricView, MintBlockTemplateView, ModuleAbiView,
+    OutputEncoding, PeerInfoView, RotateAuthKeyView, SignedUserTransactionView, StateWithProofView,
+    StrView, StructTagView, SyncProgressView, TokenCodeView, TokenInfoView, TransactionInfoView,
+    TransactionOutputView, TransactionProofView, TransactionRequest, TransactionView,
+    TxPoolStatusView, TxnDiagnoseTarget, TxnDiagnosisView, TxnHistoryView, TxnPositionView,
+    WebhookView,
 };
 use starcoin_rpc_api::{
     account::AccountClient, chain::ChainClient, contract_api::ContractClient, debug::DebugClient,
@@ -34,30 +47,37 @@ use starcoin_rpc_api::{
     txpool::TxPoolClient, types::TransactionEventView,
 };
 use starcoin_service_registry::{ServiceInfo, ServiceStatus};
-use starcoin_sync_api::{PeerScoreResponse, SyncProgressReport};
-use starcoin_txpool_api::TxPoolStatus;
+use starcoin_state_api::{AccountStateReader, StateWithProof};
+use starcoin_sync_api::PeerScoreResponse;
+use starcoin_txpool_api::{TxPoolStatus, TxnRebroadcastResult};
 use starcoin_types::access_path::AccessPath;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_state::AccountState;
-use starcoin_types::block::{BlockInfo, BlockNumber};
+use starcoin_types::block::{Block, BlockHeaderExtra, BlockInfo, BlockNumber};
+use starcoin_types::block_bundle::BlockBundle;
+use starcoin_types::event_cursor::EventCursorId;
 use starcoin_types::peer_info::{Multiaddr, PeerId};
 use starcoin_types::sync_status::SyncStatus;
-use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
-use starcoin_vm_types::on_chain_resource::{EpochInfo, GlobalTimeOnChain};
+use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction, Transaction};
+use starcoin_types::webhook::{WebhookFilter, WebhookRegistration};
+use starcoin_vm_types::identifier::Identifier;
+use starcoin_vm_types::on_chain_resource::GlobalTimeOnChain;
 use starcoin_vm_types::token::token_code::TokenCode;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::thread::JoinHandle;
 use std::time::Duration;
 
 pub mod chain_watcher;
 mod pubsub_client;
 mod remote_state_reader;
+pub mod resilient_subscriber;
 
-pub use crate::remote_state_reader::RemoteStateReader;
+pub use crate::remote_state_reader::{RemoteStateError, RemoteStateReader};
 pub use jsonrpc_core::Params;
 use starcoin_types::sign_message::SigningMessage;
-use starcoin_vm_types::language_storage::{ModuleId, StructTag};
+use starcoin_vm_types::language_storage::{FunctionId, ModuleId, StructTag};
 use tokio::runtime::Runtime;
 
 #[derive(Clone)]
@@ -172,7 +192,14 @@ impl RpcClient {
         let f = async move {
             let r = chain_watcher.send(WatchTxn { txn_hash }).await?;
             match timeout {
-                Some(t) => async_std::future::timeout(t, r).await??,
+                Some(t) => {
+                    async_std::future::timeout(t, r)
+                        .await
+                        .map_err(|_| WatchTxnTimeout {
+                            txn_hash,
+                            timeout: t,
+                        })??
+                }
                 None => r.await?,
             }
         };
@@ -201,14 +228,103 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn node_health(&self) -> anyhow::Result<HealthView> {
+        self.call_rpc_blocking(|inner| inner.node_client.health())
+            .map_err(map_err)
+    }
+
+    pub fn node_watchdog_status(&self) -> anyhow::Result<Option<WatchdogReport>> {
+        self.call_rpc_blocking(|inner| inner.node_client.watchdog_status())
+            .map_err(map_err)
+    }
+
+    pub fn node_system_info(&self) -> anyhow::Result<SystemInfo> {
+        self.call_rpc_blocking(|inner| inner.node_manager_client.system_info())
+            .map_err(map_err)
+    }
+
+    pub fn node_verify_checkpoints(&self) -> anyhow::Result<CheckpointsReport> {
+        self.call_rpc_blocking(|inner| inner.node_manager_client.verify_checkpoints())
+            .map_err(map_err)
+    }
+
+    pub fn node_rebuild_token_holder_index(&self) -> anyhow::Result<u64> {
+        self.call_rpc_blocking(|inner| inner.node_manager_client.rebuild_token_holder_index())
+            .map_err(map_err)
+    }
+
+    /// Compare the api versions of the node with the versions this client is built with,
+    /// warn and return the mismatched namespaces.
+    pub fn check_compat(&self) -> anyhow::Result<Vec<ApiVersionMismatch>> {
+        let node_info = self.node_info()?;
+        let mismatches = check_api_versions(&node_info.api_versions);
+        for mismatch in &mismatches {
+            warn!(
+                "Api {} of node (build: {}) is version {:?}, but the client is built with version {:?}",
+                mismatch.namespace,
+                node_info.build.git_hash,
+                mismatch.node_version,
+                mismatch.local_version
+            );
+        }
+        Ok(mismatches)
+    }
+
     pub async fn node_info_async(&self) -> anyhow::Result<NodeInfo> {
         self.call_rpc_async(|inner| inner.node_client.info())
             .await
             .map_err(map_err)
     }
 
-    pub fn node_metrics(&self) -> anyhow::Result<HashMap<String, String>> {
-        self.call_rpc_blocking(|inner| inner.node_client.metrics())
+    pub fn node_metrics(
+        &self,
+        filter: Option<String>,
+    ) -> anyhow::Result<BTreeMap<String, MetricView>> {
+        self.call_rpc_blocking(|inner| inner.node_client.metrics(filter))
+            .map_err(map_err)
+    }
+
+    pub fn node_trace_rpc(
+        &self,
+        enable: bool,
+        method_filter: Option<String>,
+    ) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| inner.node_manager_client.trace_rpc(enable, method_filter))
+            .map_err(map_err)
+    }
+
+    pub fn node_config(&self) -> anyhow::Result<Value> {
+        self.call_rpc_blocking(|inner| inner.node_manager_client.config())
+            .map_err(map_err)
+    }
+
+    pub fn node_reload_config(&self) -> anyhow::Result<ConfigReloadResult> {
+        self.call_rpc_blocking(|inner| inner.node_manager_client.reload_config())
+            .map_err(map_err)
+    }
+
+    /// The methods enabled on the connected transport, after the `rpc.api_filter` of the node.
+    pub fn node_list_enabled_apis(&self) -> anyhow::Result<Vec<String>> {
+        let value = self.call_raw_api("node.list_enabled_apis", Params::None)?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    pub fn node_register_webhook(
+        &self,
+        url: String,
+        filter: WebhookFilter,
+    ) -> anyhow::Result<WebhookRegistration> {
+        self.call_rpc_blocking(|inner| inner.node_manager_client.register_webhook(url, filter))
+            .map_err(map_err)
+    }
+
+    pub fn node_list_webhooks(&self) -> anyhow::Result<Vec<WebhookView>> {
+        self.call_rpc_blocking(|inner| inner.node_manager_client.list_webhooks())
+            .map_err(map_err)
+    }
+
+    pub fn node_remove_webhook(&self, id: HashValue) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| inner.node_manager_client.remove_webhook(id))
             .map_err(map_err)
     }
 
@@ -217,6 +333,26 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn node_discovered_seeds(&self) -> anyhow::Result<Vec<DiscoveredSeed>> {
+        self.call_rpc_blocking(|inner| inner.node_client.discovered_seeds())
+            .map_err(map_err)
+    }
+
+    pub fn node_add_peer(&self, multiaddr: String) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| inner.node_manager_client.add_peer(multiaddr))
+            .map_err(map_err)
+    }
+
+    pub fn node_remove_peer(&self, peer_id: PeerId) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| inner.node_manager_client.remove_peer(peer_id))
+            .map_err(map_err)
+    }
+
+    pub fn node_ban_peer(&self, peer_id: PeerId, duration_secs: Option<u64>) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| inner.node_manager_client.ban_peer(peer_id, duration_secs))
+            .map_err(map_err)
+    }
+
     pub fn node_list_service(&self) -> anyhow::Result<Vec<ServiceInfo>> {
         self.call_rpc_blocking(|inner| inner.node_manager_client.list_service())
             .map_err(map_err)
@@ -237,6 +373,11 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn node_shutdown(&self, grace_secs: Option<u64>) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| inner.node_manager_client.shutdown(grace_secs))
+            .map_err(map_err)
+    }
+
     pub fn node_shutdown_system(&self) -> anyhow::Result<()> {
         self.call_rpc_blocking(|inner| inner.node_manager_client.shutdown_system())
             .map_err(map_err)
@@ -246,12 +387,32 @@ impl RpcClient {
         &self,
         address: AccountAddress,
     ) -> anyhow::Result<Option<u64>> {
-        self.call_rpc_blocking(|inner| inner.txpool_client.next_sequence_number(address))
+        self.call_rpc_blocking(|inner| inner.txpool_client.next_sequence_number(StrView(address)))
             .map_err(map_err)
     }
 
     pub fn submit_transaction(&self, txn: SignedUserTransaction) -> anyhow::Result<HashValue> {
-        self.call_rpc_blocking(|inner| inner.txpool_client.submit_transaction(txn))
+        self.call_rpc_blocking(|inner| inner.txpool_client.submit_transaction(txn, None))
+            .map_err(map_err)
+    }
+
+    /// Run the submit checks of the node on the txn without submitting it.
+    pub fn validate_transaction(&self, txn: SignedUserTransaction) -> anyhow::Result<HashValue> {
+        self.call_rpc_blocking(|inner| inner.txpool_client.submit_transaction(txn, Some(true)))
+            .map_err(map_err)
+    }
+
+    /// Submit the hex of the bcs encoded `SignedUserTransaction`.
+    pub fn submit_hex_transaction(&self, txn_hex: String) -> anyhow::Result<HashValue> {
+        self.call_rpc_blocking(|inner| inner.txpool_client.submit_hex_transaction(txn_hex, None))
+            .map_err(map_err)
+    }
+
+    pub fn replace_transaction(
+        &self,
+        txn: SignedUserTransaction,
+    ) -> anyhow::Result<Option<HashValue>> {
+        self.call_rpc_blocking(|inner| inner.txpool_client.replace_transaction(txn))
             .map_err(map_err)
     }
 
@@ -268,7 +429,7 @@ impl RpcClient {
         sender: AccountAddress,
         max_len: Option<u32>,
     ) -> anyhow::Result<Vec<SignedUserTransactionView>> {
-        self.call_rpc_blocking(|inner| inner.txpool_client.pending_txns(sender, max_len))
+        self.call_rpc_blocking(|inner| inner.txpool_client.pending_txns(StrView(sender), max_len))
             .map_err(map_err)
     }
 
@@ -280,7 +441,7 @@ impl RpcClient {
     }
 
     pub fn set_default_account(&self, addr: AccountAddress) -> anyhow::Result<Option<AccountInfo>> {
-        self.call_rpc_blocking(|inner| inner.account_client.set_default_account(addr))
+        self.call_rpc_blocking(|inner| inner.account_client.set_default_account(StrView(addr)))
             .map_err(map_err)
     }
 
@@ -295,7 +456,7 @@ impl RpcClient {
     }
 
     pub fn account_get(&self, address: AccountAddress) -> anyhow::Result<Option<AccountInfo>> {
-        self.call_rpc_blocking(|inner| inner.account_client.get(address))
+        self.call_rpc_blocking(|inner| inner.account_client.get(StrView(address)))
             .map_err(map_err)
     }
 
@@ -305,8 +466,12 @@ impl RpcClient {
         raw_txn: RawUserTransaction,
         signer_address: AccountAddress,
     ) -> anyhow::Result<SignedUserTransaction> {
-        self.call_rpc_blocking(|inner| inner.account_client.sign_txn(raw_txn, signer_address))
-            .map_err(map_err)
+        self.call_rpc_blocking(|inner| {
+            inner
+                .account_client
+                .sign_txn(raw_txn, StrView(signer_address))
+        })
+        .map_err(map_err)
     }
 
     pub fn account_sign_txn_request(
@@ -327,7 +492,7 @@ impl RpcClient {
         raw_txn: RawUserTransaction,
     ) -> anyhow::Result<SignedUserTransaction> {
         let signer = raw_txn.sender();
-        self.call_rpc_blocking(|inner| inner.account_client.sign_txn(raw_txn, signer))
+        self.call_rpc_blocking(|inner| inner.account_client.sign_txn(raw_txn, StrView(signer)))
             .map_err(map_err)
     }
 
@@ -336,25 +501,46 @@ impl RpcClient {
         signer: AccountAddress,
         message: SigningMessage,
     ) -> anyhow::Result<StrView<Vec<u8>>> {
-        self.call_rpc_blocking(|inner| inner.account_client.sign(signer, message))
+        self.call_rpc_blocking(|inner| inner.account_client.sign(StrView(signer), message))
             .map_err(map_err)
     }
 
     pub fn account_change_password(
         &self,
         address: AccountAddress,
+        old_password: Option<String>,
         new_password: String,
     ) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| {
+            inner.account_client.change_account_password(
+                StrView(address),
+                new_password,
+                old_password,
+            )
+        })
+        .map_err(map_err)
+    }
+
+    pub fn account_remove(
+        &self,
+        address: AccountAddress,
+        password: String,
+        force: bool,
+    ) -> anyhow::Result<AccountInfo> {
         self.call_rpc_blocking(|inner| {
             inner
                 .account_client
-                .change_account_password(address, new_password)
+                .remove(StrView(address), password, Some(force))
         })
         .map_err(map_err)
     }
 
     pub fn account_lock(&self, address: AccountAddress) -> anyhow::Result<()> {
-        self.call_rpc_blocking(|inner| inner.account_client.lock(address))
+        self.call_rpc_blocking(|inner| inner.account_client.lock(StrView(address)))
+            .map_err(map_err)
+    }
+    pub fn account_lock_all(&self) -> anyhow::Result<Vec<AccountAddress>> {
+        self.call_rpc_blocking(|inner| inner.account_client.lock_all())
             .map_err(map_err)
     }
     pub fn account_unlock(
@@ -366,7 +552,7 @@ impl RpcClient {
         self.call_rpc_blocking(|inner| {
             inner
                 .account_client
-                .unlock(address, password, Some(duration.as_secs() as u32))
+                .unlock(StrView(address), password, Some(duration.as_secs() as u32))
         })
         .map_err(map_err)
     }
@@ -375,7 +561,54 @@ impl RpcClient {
         address: AccountAddress,
         password: String,
     ) -> anyhow::Result<Vec<u8>> {
-        self.call_rpc_blocking(|inner| inner.account_client.export(address, password))
+        self.call_rpc_blocking(|inner| inner.account_client.export(StrView(address), password))
+            .map_err(map_err)
+    }
+    pub fn account_import_readonly(
+        &self,
+        address: AccountAddress,
+        public_key: Vec<u8>,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .account_client
+                .import_readonly(StrView(address), StrView(public_key))
+        })
+        .map_err(map_err)
+    }
+    pub fn account_submit_signed(
+        &self,
+        raw_txn: Vec<u8>,
+        signature: Vec<u8>,
+        public_key: Vec<u8>,
+    ) -> anyhow::Result<HashValue> {
+        self.call_rpc_blocking(|inner| {
+            inner.account_client.submit_signed(
+                StrView(raw_txn),
+                StrView(signature),
+                StrView(public_key),
+            )
+        })
+        .map_err(map_err)
+    }
+    pub fn account_export_keystore(
+        &self,
+        address: AccountAddress,
+        password: String,
+    ) -> anyhow::Result<String> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .account_client
+                .export_keystore(StrView(address), password)
+        })
+        .map_err(map_err)
+    }
+    pub fn account_import_keystore(
+        &self,
+        keystore: String,
+        password: String,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc_blocking(|inner| inner.account_client.import_keystore(keystore, password))
             .map_err(map_err)
     }
     pub fn account_import(
@@ -384,23 +617,177 @@ impl RpcClient {
         private_key: Vec<u8>,
         password: String,
     ) -> anyhow::Result<AccountInfo> {
-        self.call_rpc_blocking(|inner| inner.account_client.import(address, private_key, password))
-            .map_err(map_err)
+        self.call_rpc_blocking(|inner| {
+            inner
+                .account_client
+                .import(StrView(address), private_key, password)
+        })
+        .map_err(map_err)
     }
 
     pub fn account_accepted_tokens(
         &self,
         address: AccountAddress,
-    ) -> anyhow::Result<Vec<TokenCode>> {
-        self.call_rpc_blocking(|inner| inner.account_client.accepted_tokens(address))
+    ) -> anyhow::Result<Vec<TokenCodeView>> {
+        self.call_rpc_blocking(|inner| inner.account_client.accepted_tokens(address.into()))
             .map_err(map_err)
     }
 
-    pub fn get_code(&self, module_id: ModuleId) -> anyhow::Result<Option<String>> {
-        let result: Option<StrView<Vec<u8>>> = self
-            .call_rpc_blocking(|inner| inner.contract_client.get_code(StrView(module_id)))
+    pub fn account_get_balances(
+        &self,
+        address: AccountAddress,
+    ) -> anyhow::Result<BTreeMap<String, BalanceView>> {
+        self.call_rpc_blocking(|inner| inner.account_client.get_balances(address.into()))
+            .map_err(map_err)
+    }
+
+    pub fn account_balance_at(
+        &self,
+        address: AccountAddress,
+        token_code: Option<String>,
+        block_number: BlockNumber,
+    ) -> anyhow::Result<Option<u128>> {
+        let balance = self
+            .call_rpc_blocking(|inner| {
+                inner
+                    .account_client
+                    .balance_at(address.into(), token_code, block_number)
+            })
+            .map_err(map_err)?;
+        Ok(balance.map(|balance| balance.0))
+    }
+
+    pub fn account_balances_at(
+        &self,
+        address: AccountAddress,
+        token_code: Option<String>,
+        block_numbers: Vec<BlockNumber>,
+    ) -> anyhow::Result<Vec<Option<u128>>> {
+        let balances = self
+            .call_rpc_blocking(|inner| {
+                inner
+                    .account_client
+                    .balances_at(address.into(), token_code, block_numbers)
+            })
             .map_err(map_err)?;
-        Ok(result.map(|s| s.to_string()))
+        Ok(balances
+            .into_iter()
+            .map(|balance| balance.map(|balance| balance.0))
+            .collect())
+    }
+
+    pub fn account_rotate_authentication_key(
+        &self,
+        address: AccountAddress,
+        private_key: Option<Vec<u8>>,
+        dry_run: bool,
+        timeout_secs: Option<u64>,
+        replace: bool,
+    ) -> anyhow::Result<RotateAuthKeyView> {
+        self.call_rpc_blocking(|inner| {
+            inner.account_client.rotate_authentication_key(
+                StrView(address),
+                private_key.map(StrView),
+                Some(dry_run),
+                timeout_secs,
+                Some(replace),
+            )
+        })
+        .map_err(map_err)
+    }
+
+    pub fn account_bind_external_signer(
+        &self,
+        address: AccountAddress,
+        endpoint: String,
+    ) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .account_client
+                .bind_external_signer(StrView(address), endpoint)
+        })
+        .map_err(map_err)
+    }
+
+    pub fn account_generate_mnemonic(&self, word_count: Option<u32>) -> anyhow::Result<String> {
+        self.call_rpc_blocking(|inner| inner.account_client.generate_mnemonic(word_count))
+            .map_err(map_err)
+    }
+
+    pub fn account_import_from_mnemonic(
+        &self,
+        mnemonic: String,
+        derivation_index: u32,
+        password: String,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .account_client
+                .import_from_mnemonic(mnemonic, derivation_index, password)
+        })
+        .map_err(map_err)
+    }
+
+    pub fn account_derive_address(
+        &self,
+        mnemonic: String,
+        index: u32,
+    ) -> anyhow::Result<DerivedAccount> {
+        self.call_rpc_blocking(|inner| inner.account_client.derive_address(mnemonic, index))
+            .map_err(map_err)
+    }
+
+    pub fn account_txn_history(
+        &self,
+        address: AccountAddress,
+        start: Option<u64>,
+        limit: Option<u64>,
+    ) -> anyhow::Result<Vec<TxnHistoryView>> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .account_client
+                .txn_history(StrView(address), start, limit)
+        })
+        .map_err(map_err)
+    }
+
+    pub fn get_code(
+        &self,
+        module_id: ModuleId,
+        disassemble: bool,
+    ) -> anyhow::Result<Option<CodeView>> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .contract_client
+                .get_code(StrView(module_id), Some(GetCodeOption { disassemble }))
+        })
+        .map_err(map_err)
+    }
+
+    pub fn contract_disassemble(&self, code: Vec<u8>) -> anyhow::Result<DisassemblyView> {
+        self.call_rpc_blocking(|inner| inner.contract_client.disassemble(StrView(code)))
+            .map_err(map_err)
+    }
+
+    pub fn contract_module_exists(&self, module_id: ModuleId) -> anyhow::Result<Option<HashValue>> {
+        self.call_rpc_blocking(|inner| inner.contract_client.module_exists(StrView(module_id)))
+            .map_err(map_err)
+    }
+
+    pub fn contract_get_module_abi(
+        &self,
+        module_id: ModuleId,
+    ) -> anyhow::Result<Option<ModuleAbiView>> {
+        self.call_rpc_blocking(|inner| inner.contract_client.get_module_abi(StrView(module_id)))
+            .map_err(map_err)
+    }
+
+    pub fn contract_resolve_function(
+        &self,
+        function_id: FunctionId,
+    ) -> anyhow::Result<Option<FunctionAbiView>> {
+        self.call_rpc_blocking(|inner| inner.contract_client.resolve_function(StrView(function_id)))
+            .map_err(map_err)
     }
 
     pub fn get_resource(
@@ -411,7 +798,7 @@ impl RpcClient {
         self.call_rpc_blocking(|inner| {
             inner
                 .contract_client
-                .get_resource(addr, StrView(resource_type))
+                .get_resource(StrView(addr), StrView(resource_type))
         })
         .map_err(map_err)
     }
@@ -425,8 +812,24 @@ impl RpcClient {
         &self,
         access_path: AccessPath,
     ) -> anyhow::Result<StateWithProofView> {
-        self.call_rpc_blocking(|inner| inner.state_client.get_with_proof(access_path))
-            .map_err(map_err)
+        self.call_rpc_blocking(|inner| inner.state_client.get_with_proof(access_path, None))
+            .map_err(map_err)?
+            .into_json()
+    }
+
+    /// Get the state with proof in bcs, which decodes to `StateWithProof` without loss,
+    /// and the state root the proof verifies against.
+    pub fn state_get_with_proof_bcs(
+        &self,
+        access_path: AccessPath,
+    ) -> anyhow::Result<(StateWithProof, HashValue)> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .state_client
+                .get_with_proof(access_path, Some(OutputEncoding::Bcs))
+        })
+        .map_err(map_err)?
+        .decode_bcs()
     }
 
     pub fn state_get_with_proof_by_root(
@@ -451,15 +854,78 @@ impl RpcClient {
         &self,
         address: AccountAddress,
     ) -> anyhow::Result<Option<AccountState>> {
-        self.call_rpc_blocking(|inner| inner.state_client.get_account_state(address))
+        self.call_rpc_blocking(|inner| inner.state_client.get_account_state(StrView(address)))
             .map_err(map_err)
     }
 
+    pub fn state_get_account_state_by_root(
+        &self,
+        address: AccountAddress,
+        state_root: HashValue,
+    ) -> anyhow::Result<Option<AccountState>> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .state_client
+                .get_account_state_by_root(StrView(address), state_root)
+        })
+        .map_err(map_err)
+    }
+
     pub fn get_account_state_set(
         &self,
         address: AccountAddress,
     ) -> anyhow::Result<Option<AccountStateSetView>> {
-        self.call_rpc_blocking(|inner| inner.state_client.get_account_state_set(address))
+        self.call_rpc_blocking(|inner| inner.state_client.get_account_state_set(StrView(address)))
+            .map_err(map_err)
+    }
+
+    pub fn state_list_resources(
+        &self,
+        address: AccountAddress,
+        start: Option<StructTagView>,
+        limit: u32,
+        decode: bool,
+        state_root: Option<HashValue>,
+    ) -> anyhow::Result<Option<ListResourceView>> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .state_client
+                .list_resources(StrView(address), start, limit, decode, state_root)
+        })
+        .map_err(map_err)
+    }
+
+    pub fn state_list_code(
+        &self,
+        address: AccountAddress,
+        start: Option<Identifier>,
+        limit: u32,
+        state_root: Option<HashValue>,
+    ) -> anyhow::Result<Option<ListCodeView>> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .state_client
+                .list_code(StrView(address), start, limit, state_root)
+        })
+        .map_err(map_err)
+    }
+
+    pub fn state_list_token_holders(
+        &self,
+        token_code: TokenCode,
+        start: Option<AccountAddress>,
+        limit: u32,
+    ) -> anyhow::Result<Vec<(AccountAddress, StrView<u128>)>> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .state_client
+                .list_token_holders(StrView(token_code), start.map(StrView), limit)
+        })
+        .map_err(map_err)
+    }
+
+    pub fn state_token_holder_count(&self, token_code: TokenCode) -> anyhow::Result<u64> {
+        self.call_rpc_blocking(|inner| inner.state_client.token_holder_count(StrView(token_code)))
             .map_err(map_err)
     }
 
@@ -468,6 +934,14 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn contract_call_v2(
+        &self,
+        call: ContractCall,
+    ) -> anyhow::Result<Vec<AnnotatedMoveValueView>> {
+        self.call_rpc_blocking(|inner| inner.contract_client.call_v2(call))
+            .map_err(map_err)
+    }
+
     pub fn debug_set_log_level(
         &self,
         logger_name: Option<String>,
@@ -501,22 +975,27 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn dev_generate_block(&self, count: u64) -> anyhow::Result<Vec<HashValue>> {
+        self.call_rpc_blocking(|inner| inner.debug_client.dev_generate_block(count))
+            .map_err(map_err)
+    }
+
     pub fn chain_id(&self) -> anyhow::Result<ChainId> {
         self.call_rpc_blocking(|inner| inner.chain_client.id())
             .map_err(map_err)
     }
 
-    pub fn chain_info(&self) -> anyhow::Result<ChainInfoView> {
+    pub fn chain_info(&self) -> anyhow::Result<ChainStatusView> {
         self.call_rpc_blocking(|inner| inner.chain_client.info())
             .map_err(map_err)
     }
 
-    pub fn epoch_info(&self) -> anyhow::Result<EpochInfo> {
+    pub fn epoch_info(&self) -> anyhow::Result<EpochInfoView> {
         self.call_rpc_blocking(|inner| inner.chain_client.current_epoch())
             .map_err(map_err)
     }
 
-    pub fn get_epoch_info_by_number(&self, number: BlockNumber) -> anyhow::Result<EpochInfo> {
+    pub fn get_epoch_info_by_number(&self, number: BlockNumber) -> anyhow::Result<EpochInfoView> {
         self.call_rpc_blocking(|inner| inner.chain_client.get_epoch_info_by_number(number))
             .map_err(map_err)
     }
@@ -529,6 +1008,22 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn get_block_by_uncle_hash(
+        &self,
+        uncle_hash: HashValue,
+    ) -> anyhow::Result<Option<BlockSummaryView>> {
+        self.call_rpc_blocking(|inner| inner.chain_client.get_block_by_uncle_hash(uncle_hash))
+            .map_err(map_err)
+    }
+
+    pub fn get_uncles_by_number(
+        &self,
+        number: BlockNumber,
+    ) -> anyhow::Result<Vec<BlockHeaderView>> {
+        self.call_rpc_blocking(|inner| inner.chain_client.get_uncles_by_number(number))
+            .map_err(map_err)
+    }
+
     pub fn epoch_uncle_summary_by_number(
         &self,
         number: BlockNumber,
@@ -545,6 +1040,84 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn get_headers_v2(
+        &self,
+        block_hashes: Vec<HashValue>,
+    ) -> anyhow::Result<Vec<Option<BlockHeaderView>>> {
+        self.call_rpc_blocking(|inner| inner.chain_client.get_headers_v2(block_hashes))
+            .map_err(map_err)
+    }
+
+    pub fn get_headers_by_number(
+        &self,
+        start: BlockNumber,
+        count: u64,
+    ) -> anyhow::Result<BlockHeadersView> {
+        self.call_rpc_blocking(|inner| inner.chain_client.get_headers_by_number(start, count))
+            .map_err(map_err)
+    }
+
+    pub fn chain_get_block_reward(
+        &self,
+        number: BlockNumber,
+    ) -> anyhow::Result<Option<BlockRewardView>> {
+        self.call_rpc_blocking(|inner| inner.chain_client.get_block_reward(number))
+            .map_err(map_err)
+    }
+
+    pub fn chain_get_event_handle(
+        &self,
+        address: AccountAddress,
+        struct_tag: StructTag,
+        field_path: String,
+    ) -> anyhow::Result<Option<EventHandleView>> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .chain_client
+                .get_event_handle(StrView(address), StrView(struct_tag), field_path)
+        })
+        .map_err(map_err)
+    }
+
+    pub fn chain_get_branches(&self) -> anyhow::Result<Vec<BranchView>> {
+        self.call_rpc_blocking(|inner| inner.chain_client.get_branches())
+            .map_err(map_err)
+    }
+
+    pub fn chain_get_common_ancestor(
+        &self,
+        hash1: HashValue,
+        hash2: HashValue,
+    ) -> anyhow::Result<Option<HashValue>> {
+        self.call_rpc_blocking(|inner| inner.chain_client.get_common_ancestor(hash1, hash2))
+            .map_err(map_err)
+    }
+
+    /// Export the block as a `BlockBundle`, None if the block does not exist.
+    pub fn chain_export_block_bundle(
+        &self,
+        block_hash: HashValue,
+        with_state: bool,
+    ) -> anyhow::Result<Option<BlockBundle>> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .chain_client
+                .export_block_bundle(block_hash, Some(with_state))
+        })
+        .map_err(map_err)?
+        .map(|bytes| BlockBundle::decode(bytes.0.as_slice()))
+        .transpose()
+    }
+
+    pub fn chain_get_block_rewards(
+        &self,
+        start: BlockNumber,
+        count: u64,
+    ) -> anyhow::Result<Vec<BlockRewardView>> {
+        self.call_rpc_blocking(|inner| inner.chain_client.get_block_rewards(start, count))
+            .map_err(map_err)
+    }
+
     pub fn get_global_time_by_number(
         &self,
         number: BlockNumber,
@@ -553,16 +1126,35 @@ impl RpcClient {
             .map_err(map_err)
     }
 
-    pub fn chain_get_block_by_hash(&self, hash: HashValue) -> anyhow::Result<Option<BlockView>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_block_by_hash(hash))
-            .map_err(map_err)
+    pub fn chain_get_block_by_hash(
+        &self,
+        hash: HashValue,
+        option: Option<GetBlockOption>,
+    ) -> anyhow::Result<Option<BlockView>> {
+        self.call_rpc_blocking(|inner| inner.chain_client.get_block_by_hash(hash, option, None))
+            .map_err(map_err)?
+            .map(EncodedView::into_json)
+            .transpose()
+    }
+
+    /// Get the block in bcs, which decodes to `Block` without loss.
+    pub fn chain_get_block_by_hash_bcs(&self, hash: HashValue) -> anyhow::Result<Option<Block>> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .chain_client
+                .get_block_by_hash(hash, None, Some(OutputEncoding::Bcs))
+        })
+        .map_err(map_err)?
+        .map(|view| view.decode_bcs())
+        .transpose()
     }
 
     pub fn chain_get_block_by_number(
         &self,
         number: BlockNumber,
+        option: Option<GetBlockOption>,
     ) -> anyhow::Result<Option<BlockView>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_block_by_number(number))
+        self.call_rpc_blocking(|inner| inner.chain_client.get_block_by_number(number, option))
             .map_err(map_err)
     }
 
@@ -587,7 +1179,49 @@ impl RpcClient {
         &self,
         txn_id: HashValue,
     ) -> anyhow::Result<Option<TransactionView>> {
-        self.call_rpc_blocking(|inner| inner.chain_client.get_transaction(txn_id))
+        self.call_rpc_blocking(|inner| inner.chain_client.get_transaction(txn_id, None))
+            .map_err(map_err)?
+            .map(EncodedView::into_json)
+            .transpose()
+    }
+
+    /// Get the transaction in bcs, which decodes to `Transaction` without loss.
+    pub fn chain_get_transaction_bcs(
+        &self,
+        txn_id: HashValue,
+    ) -> anyhow::Result<Option<Transaction>> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .chain_client
+                .get_transaction(txn_id, Some(OutputEncoding::Bcs))
+        })
+        .map_err(map_err)?
+        .map(|view| view.decode_bcs())
+        .transpose()
+    }
+
+    /// Get the main chain txns in one call, at most 100 hashes, None if the txn is not mined.
+    pub fn chain_get_transactions(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> anyhow::Result<Vec<Option<TransactionView>>> {
+        self.call_rpc_blocking(|inner| inner.chain_client.get_transactions(txn_hashes))
+            .map_err(map_err)
+    }
+
+    pub fn chain_get_block_hash_by_txn(
+        &self,
+        txn_hash: HashValue,
+    ) -> anyhow::Result<Option<HashValue>> {
+        self.call_rpc_blocking(|inner| inner.chain_client.get_block_hash_by_txn(txn_hash))
+            .map_err(map_err)
+    }
+
+    pub fn chain_get_txn_position(
+        &self,
+        txn_hash: HashValue,
+    ) -> anyhow::Result<Option<TxnPositionView>> {
+        self.call_rpc_blocking(|inner| inner.chain_client.get_txn_position(txn_hash))
             .map_err(map_err)
     }
 
@@ -599,6 +1233,14 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn chain_get_transaction_output(
+        &self,
+        txn_hash: HashValue,
+    ) -> anyhow::Result<Option<TransactionOutputView>> {
+        self.call_rpc_blocking(|inner| inner.chain_client.get_transaction_output(txn_hash))
+            .map_err(map_err)
+    }
+
     pub fn chain_get_events_by_txn_hash(
         &self,
         txn_hash: HashValue,
@@ -607,6 +1249,41 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn chain_get_events(
+        &self,
+        filter: EventFilter,
+    ) -> anyhow::Result<Vec<TransactionEventView>> {
+        self.call_rpc_blocking(|inner| inner.chain_client.get_events(filter))
+            .map_err(map_err)
+    }
+
+    pub fn node_create_event_cursor(&self, filter: EventFilter) -> anyhow::Result<EventCursorId> {
+        self.call_rpc_blocking(|inner| inner.node_manager_client.create_event_cursor(filter))
+            .map_err(map_err)
+    }
+
+    pub fn chain_poll_events(
+        &self,
+        cursor_id: EventCursorId,
+        max_items: u64,
+    ) -> anyhow::Result<EventCursorPageView> {
+        self.call_rpc_blocking(|inner| inner.chain_client.poll_events(cursor_id, max_items))
+            .map_err(map_err)
+    }
+
+    pub fn chain_ack_events(
+        &self,
+        cursor_id: EventCursorId,
+        up_to_global_index: u128,
+    ) -> anyhow::Result<()> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .chain_client
+                .ack_events(cursor_id, up_to_global_index.into())
+        })
+        .map_err(map_err)
+    }
+
     pub fn chain_get_block_txn_infos(
         &self,
         block_id: HashValue,
@@ -615,6 +1292,20 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn chain_get_transaction_infos(
+        &self,
+        start_global_index: u64,
+        reverse: bool,
+        max_size: u64,
+    ) -> anyhow::Result<Vec<TransactionInfoView>> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .chain_client
+                .get_transaction_infos(start_global_index, reverse, max_size)
+        })
+        .map_err(map_err)
+    }
+
     pub fn chain_get_txn_info_by_block_and_index(
         &self,
         block_id: HashValue,
@@ -628,6 +1319,20 @@ impl RpcClient {
         .map_err(map_err)
     }
 
+    pub fn chain_get_transaction_proof(
+        &self,
+        block_hash: HashValue,
+        transaction_index: u64,
+        event_index: Option<u64>,
+    ) -> anyhow::Result<Option<TransactionProofView>> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .chain_client
+                .get_transaction_proof(block_hash, transaction_index, event_index)
+        })
+        .map_err(map_err)
+    }
+
     pub fn dry_run(&self, txn: DryRunTransactionRequest) -> anyhow::Result<TransactionOutputView> {
         self.call_rpc_blocking(|inner| inner.contract_client.dry_run(txn))
             .map_err(map_err)
@@ -652,11 +1357,53 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn miner_get_work(&self) -> anyhow::Result<Option<MintBlockTemplateView>> {
+        self.call_rpc_blocking(|inner| inner.miner_client.get_work())
+            .map_err(map_err)
+    }
+
+    pub fn miner_submit_work(
+        &self,
+        minting_blob: Vec<u8>,
+        nonce: u32,
+        extra: BlockHeaderExtra,
+    ) -> anyhow::Result<HashValue> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .miner_client
+                .submit_work(StrView(minting_blob), nonce, extra)
+        })
+        .map_err(map_err)
+    }
+
     pub fn txpool_status(&self) -> anyhow::Result<TxPoolStatus> {
         self.call_rpc_blocking(|inner| inner.txpool_client.state())
             .map_err(map_err)
     }
 
+    pub fn txpool_detailed_status(&self) -> anyhow::Result<TxPoolStatusView> {
+        self.call_rpc_blocking(|inner| inner.txpool_client.status())
+            .map_err(map_err)
+    }
+
+    pub fn txpool_diagnose(&self, target: TxnDiagnoseTarget) -> anyhow::Result<TxnDiagnosisView> {
+        self.call_rpc_blocking(|inner| inner.txpool_client.diagnose(target))
+            .map_err(map_err)
+    }
+
+    pub fn txpool_gas_price_oracle(&self) -> anyhow::Result<GasPriceOracleView> {
+        self.call_rpc_blocking(|inner| inner.txpool_client.gas_price_oracle())
+            .map_err(map_err)
+    }
+
+    pub fn txpool_rebroadcast(
+        &self,
+        txn_hashes: Option<Vec<HashValue>>,
+    ) -> anyhow::Result<TxnRebroadcastResult> {
+        self.call_rpc_blocking(|inner| inner.txpool_client.rebroadcast(txn_hashes))
+            .map_err(map_err)
+    }
+
     pub fn subscribe_events(
         &self,
         filter: EventFilter,
@@ -667,9 +1414,32 @@ impl RpcClient {
         })
         .map_err(map_err)
     }
+
+    /// Subscribe events which survives the node restart.
+    /// When the connection is lost, the client reconnects with exponential backoff,
+    /// and backfills the missed events from the last seen block by `chain.get_events`
+    /// before resuming the live subscription. Duplicated events are suppressed.
+    pub fn subscribe_events_resilient(
+        self: Arc<Self>,
+        filter: EventFilter,
+    ) -> impl Stream<Item = TransactionEventView> {
+        self.subscribe_events_resilient_with_options(filter, ResilientSubscribeOptions::default())
+    }
+
+    pub fn subscribe_events_resilient_with_options(
+        self: Arc<Self>,
+        filter: EventFilter,
+        options: ResilientSubscribeOptions,
+    ) -> impl Stream<Item = TransactionEventView> {
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        let subscriber = ResilientEventSubscriber::new(self, filter, options, tx);
+        std::thread::spawn(move || subscriber.run());
+        rx
+    }
+
     pub fn subscribe_new_blocks(
         &self,
-    ) -> anyhow::Result<impl TryStream<Ok = BlockView, Error = anyhow::Error>> {
+    ) -> anyhow::Result<impl TryStream<Ok = BlockNotificationView, Error = anyhow::Error>> {
         self.call_rpc_blocking(|inner| async move {
             let res = inner.pubsub_client.subscribe_new_block().await;
             res.map(|s| s.map_err(map_err))
@@ -686,6 +1456,26 @@ impl RpcClient {
         .map_err(map_err)
     }
 
+    pub fn subscribe_dropped_transactions(
+        &self,
+    ) -> anyhow::Result<impl TryStream<Ok = Vec<HashValue>, Error = anyhow::Error>> {
+        self.call_rpc_blocking(|inner| async move {
+            let res = inner.pubsub_client.subscribe_dropped_transactions().await;
+            res.map(|s| s.map_err(map_err))
+        })
+        .map_err(map_err)
+    }
+
+    pub fn subscribe_evicted_transactions(
+        &self,
+    ) -> anyhow::Result<impl TryStream<Ok = Vec<HashValue>, Error = anyhow::Error>> {
+        self.call_rpc_blocking(|inner| async move {
+            let res = inner.pubsub_client.subscribe_evicted_transactions().await;
+            res.map(|s| s.map_err(map_err))
+        })
+        .map_err(map_err)
+    }
+
     pub fn subscribe_new_mint_blocks(
         &self,
     ) -> anyhow::Result<impl TryStream<Ok = MintBlock, Error = anyhow::Error>> {
@@ -696,6 +1486,14 @@ impl RpcClient {
         .map_err(map_err)
     }
 
+    /// List the active subscriptions of this client's connection.
+    pub fn list_subscriptions(&self) -> anyhow::Result<Vec<SubscriptionView>> {
+        self.call_rpc_blocking(
+            |inner| async move { inner.pubsub_client.list_subscriptions().await },
+        )
+        .map_err(map_err)
+    }
+
     pub async fn subscribe_new_mint_blocks_async(
         &self,
     ) -> anyhow::Result<impl TryStream<Ok = MintBlock, Error = anyhow::Error>> {
@@ -754,12 +1552,17 @@ impl RpcClient {
         result
     }
 
+    /// Drop the current connection, the next call will reconnect.
+    pub(crate) fn reset_connection(&self) {
+        *(self.inner.lock()) = None;
+    }
+
     pub fn sync_status(&self) -> anyhow::Result<SyncStatus> {
         self.call_rpc_blocking(|inner| inner.sync_client.status())
             .map_err(map_err)
     }
 
-    pub fn sync_progress(&self) -> anyhow::Result<Option<SyncProgressReport>> {
+    pub fn sync_progress(&self) -> anyhow::Result<Option<SyncProgressView>> {
         self.call_rpc_blocking(|inner| inner.sync_client.progress())
             .map_err(map_err)
     }
@@ -779,7 +1582,7 @@ impl RpcClient {
         self.call_rpc_blocking(|inner| {
             inner
                 .sync_client
-                .start(force, peers, skip_pow_verify, strategy)
+                .start(force, Some(peers), Some(skip_pow_verify), strategy)
         })
         .map_err(map_err)
     }
@@ -838,6 +1641,38 @@ impl RpcClient {
     }
 }
 
+impl TransferDataSource for RpcClient {
+    fn get_events(&self, filter: EventFilter) -> anyhow::Result<Vec<TransactionEventView>> {
+        self.chain_get_events(filter)
+    }
+
+    fn get_events_by_txn_hash(
+        &self,
+        txn_hash: HashValue,
+    ) -> anyhow::Result<Vec<TransactionEventView>> {
+        self.chain_get_events_by_txn_hash(txn_hash)
+    }
+
+    fn get_transaction(&self, txn_hash: HashValue) -> anyhow::Result<Option<TransactionView>> {
+        self.chain_get_transaction(txn_hash)
+    }
+
+    fn get_block_header(&self, number: BlockNumber) -> anyhow::Result<Option<BlockHeaderView>> {
+        Ok(self
+            .get_headers_by_number(number, 1)?
+            .headers
+            .into_iter()
+            .next())
+    }
+
+    fn get_token_info(&self, token_code: TokenCode) -> anyhow::Result<Option<TokenInfoView>> {
+        let state_reader = RemoteStateReader::new(self)?;
+        Ok(AccountStateReader::new(&state_reader)
+            .get_token_info(token_code.clone())?
+            .map(|token_info| TokenInfoView::new(token_code, &token_info)))
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct RpcClientInner {
     raw_client: RawClient,
@@ -875,8 +1710,16 @@ impl RpcClientInner {
     }
 }
 
+/// Keep the server error in the returned error, so the caller can downcast it to
+/// `jsonrpc_core::Error` and check the code by `RpcErrorCode::of`.
 fn map_err(rpc_err: jsonrpc_client_transports::RpcError) -> anyhow::Error {
-    anyhow!(format!("{}", rpc_err))
+    let message = format!("{}", rpc_err);
+    match rpc_err {
+        jsonrpc_client_transports::RpcError::JsonRpcError(e) => {
+            anyhow::Error::new(e).context(message)
+        }
+        _ => anyhow!(message),
+    }
 }
 
 impl From<RpcChannel> for RpcClientInner {
@@ -3,12 +3,13 @@
 
 use jsonrpc_core_client::*;
 use starcoin_crypto::HashValue;
-use starcoin_rpc_api::types::pubsub::MintBlock;
-use starcoin_rpc_api::types::{pubsub::EventFilter, pubsub::Kind, BlockView, TransactionEventView};
+use starcoin_rpc_api::types::pubsub::{BlockNotificationView, MintBlock, SubscriptionView};
+use starcoin_rpc_api::types::{pubsub::EventFilter, pubsub::Kind, TransactionEventView};
 
 const STARCOIN_SUBSCRIPTION: &str = "starcoin_subscription";
 const STARCOIN_SUBSCRIBE: &str = "starcoin_subscribe";
 const STARCOIN_UNSUBSCRIBE: &str = "starcoin_unsubscribe";
+const LIST_SUBSCRIPTIONS: &str = "pubsub.list_subscriptions";
 #[derive(Clone)]
 pub struct PubSubClient {
     client: TypedClient,
@@ -43,13 +44,13 @@ impl PubSubClient {
     }
     pub async fn subscribe_new_block(
         &self,
-    ) -> Result<TypedSubscriptionStream<BlockView>, RpcError> {
+    ) -> Result<TypedSubscriptionStream<BlockNotificationView>, RpcError> {
         self.client.subscribe(
             STARCOIN_SUBSCRIBE,
             vec![Kind::NewHeads],
             STARCOIN_SUBSCRIPTION,
             STARCOIN_UNSUBSCRIBE,
-            "ThinBlock",
+            "BlockNotification",
         )
     }
     pub async fn subscribe_new_transactions(
@@ -63,6 +64,28 @@ impl PubSubClient {
             "Vec<HashValue>",
         )
     }
+    pub async fn subscribe_dropped_transactions(
+        &self,
+    ) -> Result<TypedSubscriptionStream<Vec<HashValue>>, RpcError> {
+        self.client.subscribe(
+            STARCOIN_SUBSCRIBE,
+            vec![Kind::DroppedTransactions],
+            STARCOIN_SUBSCRIPTION,
+            STARCOIN_UNSUBSCRIBE,
+            "Vec<HashValue>",
+        )
+    }
+    pub async fn subscribe_evicted_transactions(
+        &self,
+    ) -> Result<TypedSubscriptionStream<Vec<HashValue>>, RpcError> {
+        self.client.subscribe(
+            STARCOIN_SUBSCRIBE,
+            vec![Kind::EvictedTransactions],
+            STARCOIN_SUBSCRIPTION,
+            STARCOIN_UNSUBSCRIBE,
+            "Vec<HashValue>",
+        )
+    }
     pub async fn subscribe_new_mint_block(
         &self,
     ) -> Result<TypedSubscriptionStream<MintBlock>, RpcError> {
@@ -74,4 +97,10 @@ impl PubSubClient {
             "MintBlock",
         )
     }
+
+    pub async fn list_subscriptions(&self) -> Result<Vec<SubscriptionView>, RpcError> {
+        self.client
+            .call_method(LIST_SUBSCRIPTIONS, "Vec<SubscriptionView>", ())
+            .await
+    }
 }
@@ -1,41 +1,160 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
-use crate::RpcClient;
+use crate::{RpcClient, RpcClientInner};
 use anyhow::Result;
+use jsonrpc_client_transports::RpcError;
 use starcoin_crypto::HashValue;
+use starcoin_logger::prelude::*;
+use starcoin_rpc_api::errors::RpcErrorCode;
+use starcoin_rpc_api::types::StrView;
 use starcoin_state_api::{ChainStateReader, StateView, StateWithProof};
 use starcoin_types::access_path::AccessPath;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_state::AccountState;
 use starcoin_types::state_set::{AccountStateSet, ChainStateSet};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
+/// The default retry times of a read which fails by the transport error.
+pub const DEFAULT_READ_RETRIES: usize = 3;
+/// The default wait before the first retry, it is doubled for every retry.
+pub const DEFAULT_READ_RETRY_BACKOFF: Duration = Duration::from_millis(200);
+
+/// The error of the reads of `RemoteStateReader`, it is wrapped in the `anyhow::Error` returned
+/// by the `ChainStateReader` and `StateView` methods, and can be got by `downcast_ref`.
+#[derive(Debug, thiserror::Error)]
+pub enum RemoteStateError {
+    /// The state root is never found on the node, such as a wrong root.
+    #[error("the state root {0} is not found on the node")]
+    NotFound(HashValue),
+    /// The state root was read before, but it is pruned by the node now.
+    #[error("the state root {0} is pruned by the node")]
+    Pruned(HashValue),
+    /// The connection to the node fails after the retries.
+    #[error("rpc transport error: {0}")]
+    Transport(String),
+    /// The other errors returned by the node.
+    #[error("rpc error: {0}")]
+    Rpc(jsonrpc_core::Error),
+}
+
+/// Read the chain state of a pinned state root by rpc, so all the reads are of the same snapshot.
+/// The reads are idempotent, so they are retried with backoff on the transport errors,
+/// and the connection is rebuilt before every retry.
 pub struct RemoteStateReader<'a> {
     //TODO add cache.
     client: &'a RpcClient,
     state_root: HashValue,
+    /// Whether the state root has been read from the node, a root which is not found then is pruned.
+    root_seen: AtomicBool,
+    max_retries: usize,
+    retry_backoff: Duration,
 }
 
 impl<'a> RemoteStateReader<'a> {
+    /// Pin the latest state root of the node.
     pub fn new(client: &'a RpcClient) -> Result<Self> {
-        let state_root = client.state_get_state_root()?;
-        Ok(Self::new_with_root(client, state_root))
+        let mut reader = Self::new_at_root(client, HashValue::zero());
+        reader.refresh()?;
+        Ok(reader)
     }
 
-    pub fn new_with_root(client: &'a RpcClient, state_root: HashValue) -> Self {
-        Self { client, state_root }
+    /// Read the state at `state_root`, the reads fail with `RemoteStateError::Pruned` if it is pruned.
+    pub fn new_at_root(client: &'a RpcClient, state_root: HashValue) -> Self {
+        Self {
+            client,
+            state_root,
+            root_seen: AtomicBool::new(false),
+            max_retries: DEFAULT_READ_RETRIES,
+            retry_backoff: DEFAULT_READ_RETRY_BACKOFF,
+        }
+    }
+
+    /// Retry the failed reads at most `max_retries` times, wait `retry_backoff` before the first retry,
+    /// and double the wait for every later retry.
+    pub fn with_retry(mut self, max_retries: usize, retry_backoff: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.retry_backoff = retry_backoff;
+        self
+    }
+
+    /// Pin the latest state root of the node, the later reads see the latest state.
+    pub fn refresh(&mut self) -> Result<()> {
+        self.state_root = self.read(|inner| inner.state_client.get_state_root())?;
+        self.root_seen.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn read<T, F, Fut>(&self, f: F) -> Result<T, RemoteStateError>
+    where
+        T: Send,
+        F: Fn(RpcClientInner) -> Fut + Send + Sync,
+        Fut: Future<Output = Result<T, RpcError>> + Send,
+    {
+        let mut backoff = self.retry_backoff;
+        let mut retries = 0;
+        loop {
+            let err = match self.client.call_rpc_blocking(&f) {
+                Ok(result) => {
+                    self.root_seen.store(true, Ordering::Relaxed);
+                    return Ok(result);
+                }
+                Err(RpcError::JsonRpcError(e)) => {
+                    return Err(
+                        if RpcErrorCode::of(&e) == Some(RpcErrorCode::StateNotFound) {
+                            if self.root_seen.load(Ordering::Relaxed) {
+                                RemoteStateError::Pruned(self.state_root)
+                            } else {
+                                RemoteStateError::NotFound(self.state_root)
+                            }
+                        } else {
+                            RemoteStateError::Rpc(e)
+                        },
+                    );
+                }
+                Err(RpcError::ParseError(method, e)) => {
+                    return Err(RemoteStateError::Rpc(jsonrpc_core::Error::invalid_params(
+                        format!("parse the response of {} failed: {:?}", method, e),
+                    )));
+                }
+                Err(e) => e,
+            };
+            if retries >= self.max_retries {
+                return Err(RemoteStateError::Transport(err.to_string()));
+            }
+            retries += 1;
+            warn!(
+                "Read remote state failed: {}, retry {}/{} after {:?}",
+                err, retries, self.max_retries, backoff
+            );
+            self.client.reset_connection();
+            std::thread::sleep(backoff);
+            backoff *= 2;
+        }
     }
 }
 
 impl<'a> ChainStateReader for RemoteStateReader<'a> {
     fn get_with_proof(&self, access_path: &AccessPath) -> Result<StateWithProof> {
-        self.client
-            .state_get_with_proof(access_path.clone())
-            .map(Into::into)
+        let state_root = self.state_root;
+        Ok(self
+            .read(|inner| {
+                inner
+                    .state_client
+                    .get_with_proof_by_root(access_path.clone(), state_root)
+            })?
+            .into())
     }
 
     fn get_account_state(&self, address: &AccountAddress) -> Result<Option<AccountState>> {
-        self.client.state_get_account_state(*address)
+        let (address, state_root) = (*address, self.state_root);
+        Ok(self.read(|inner| {
+            inner
+                .state_client
+                .get_account_state_by_root(StrView(address), state_root)
+        })?)
     }
 
     fn state_root(&self) -> HashValue {
@@ -53,11 +172,7 @@ impl<'a> ChainStateReader for RemoteStateReader<'a> {
 
 impl<'a> StateView for RemoteStateReader<'a> {
     fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
-        Ok(self
-            .client
-            .state_get_with_proof_by_root(access_path.clone(), self.state_root())?
-            .state
-            .map(|v| v.0))
+        Ok(self.get_with_proof(access_path)?.state)
     }
 
     fn multi_get(&self, _access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
@@ -0,0 +1,267 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2
+
+use crate::RpcClient;
+use anyhow::Result;
+use futures::channel::mpsc;
+use futures::{TryStream, TryStreamExt};
+use starcoin_crypto::HashValue;
+use starcoin_logger::prelude::*;
+use starcoin_rpc_api::types::pubsub::EventFilter;
+use starcoin_rpc_api::types::TransactionEventView;
+use starcoin_types::block::BlockNumber;
+use starcoin_types::event::EventKey;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How many blocks before the last seen block whose event ids are kept for duplicate suppression.
+const DEDUP_BLOCK_WINDOW: u64 = 16;
+/// How often the subscriber checks whether the returned stream is dropped when no event comes.
+const CLOSE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Connection state changes of a resilient event subscription.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConnectionState {
+    /// The live subscription is established.
+    Connected,
+    /// The connection or the live subscription is lost.
+    Disconnected { reason: String },
+    /// Wait `backoff` before the `attempt`th reconnection.
+    Reconnecting { attempt: u32, backoff: Duration },
+    /// The missed events between `from_block` and `to_block` are fetched by `chain.get_events`.
+    Backfilled {
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+        events: usize,
+    },
+}
+
+pub type ConnectionStateCallback = Arc<dyn Fn(&ConnectionState) + Send + Sync>;
+
+#[derive(Clone)]
+pub struct ResilientSubscribeOptions {
+    /// Backoff before the first reconnection, doubled on every failed attempt.
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Block range of every backfill query, should not be greater than the node's `block_query_max_range`.
+    pub backfill_block_range: u64,
+    on_state_change: Option<ConnectionStateCallback>,
+}
+
+impl Default for ResilientSubscribeOptions {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            backfill_block_range: 32,
+            on_state_change: None,
+        }
+    }
+}
+
+impl ResilientSubscribeOptions {
+    /// Set the callback invoked on every connection state change, for example, to log reconnects.
+    pub fn on_state_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&ConnectionState) + Send + Sync + 'static,
+    {
+        self.on_state_change = Some(Arc::new(callback));
+        self
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .checked_mul(factor)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+}
+
+/// Event id used for duplicate suppression, the sequence number is only unique under the event key.
+type EventId = (HashValue, u32, EventKey, u64);
+
+pub(crate) struct ResilientEventSubscriber {
+    client: Arc<RpcClient>,
+    filter: EventFilter,
+    options: ResilientSubscribeOptions,
+    sender: mpsc::UnboundedSender<TransactionEventView>,
+    /// The block number of the latest emitted event, or the chain head at the last backfill.
+    last_seen_block: Option<BlockNumber>,
+    seen_events: BTreeMap<BlockNumber, HashSet<EventId>>,
+}
+
+impl ResilientEventSubscriber {
+    pub(crate) fn new(
+        client: Arc<RpcClient>,
+        filter: EventFilter,
+        options: ResilientSubscribeOptions,
+        sender: mpsc::UnboundedSender<TransactionEventView>,
+    ) -> Self {
+        Self {
+            client,
+            filter,
+            options,
+            sender,
+            last_seen_block: None,
+            seen_events: BTreeMap::new(),
+        }
+    }
+
+    /// Keep subscribing until the receiver is dropped.
+    pub(crate) fn run(mut self) {
+        let mut attempt = 0u32;
+        loop {
+            let reason = match self.client.subscribe_events(self.filter.clone()) {
+                Ok(stream) => {
+                    self.notify(ConnectionState::Connected);
+                    match self.backfill() {
+                        Ok(()) => {
+                            attempt = 0;
+                            match self.consume(stream) {
+                                Ok(()) => "subscription stream is closed".to_string(),
+                                Err(e) => e.to_string(),
+                            }
+                        }
+                        Err(e) => format!("backfill events fail: {}", e),
+                    }
+                }
+                Err(e) => e.to_string(),
+            };
+            if self.sender.is_closed() {
+                break;
+            }
+            warn!("Event subscription is lost: {}", reason);
+            self.notify(ConnectionState::Disconnected { reason });
+            self.client.reset_connection();
+            attempt = attempt.saturating_add(1);
+            let backoff = self.options.backoff(attempt);
+            self.notify(ConnectionState::Reconnecting { attempt, backoff });
+            std::thread::sleep(backoff);
+            if self.sender.is_closed() {
+                break;
+            }
+        }
+        debug!("Resilient event subscriber exit.");
+    }
+
+    /// Fetch the events from the last seen block to the current head.
+    fn backfill(&mut self) -> Result<()> {
+        let head = self.client.chain_info()?.head.number.0;
+        let from_block = match self
+            .last_seen_block
+            .or_else(|| self.filter.from_block.map(|n| n.0))
+        {
+            Some(from_block) => from_block,
+            None => {
+                // nothing is missed before the first connection.
+                self.last_seen_block = Some(head);
+                return Ok(());
+            }
+        };
+        let to_block = self
+            .filter
+            .to_block
+            .map(|to_block| to_block.0.min(head))
+            .unwrap_or(head);
+        if from_block > to_block {
+            return Ok(());
+        }
+        let range = self.options.backfill_block_range.max(1);
+        let mut events = 0usize;
+        let mut begin = from_block;
+        loop {
+            let end = begin.saturating_add(range - 1).min(to_block);
+            let mut filter = self.filter.clone();
+            filter.from_block = Some(begin.into());
+            filter.to_block = Some(end.into());
+            filter.limit = None;
+            let batch = self.client.chain_get_events(filter)?;
+            for event in batch {
+                if !self.emit(event) {
+                    return Ok(());
+                }
+                events += 1;
+            }
+            if end >= to_block {
+                break;
+            }
+            begin = end + 1;
+        }
+        if self.last_seen_block.map(|n| n < head).unwrap_or(true) {
+            self.last_seen_block = Some(head);
+        }
+        self.notify(ConnectionState::Backfilled {
+            from_block,
+            to_block,
+            events,
+        });
+        Ok(())
+    }
+
+    /// Forward the live events until the stream ends or the receiver is dropped.
+    fn consume(
+        &mut self,
+        stream: impl TryStream<Ok = TransactionEventView, Error = anyhow::Error>,
+    ) -> Result<()> {
+        let mut stream = Box::pin(stream.into_stream());
+        while !self.sender.is_closed() {
+            let next = futures::executor::block_on(async_std::future::timeout(
+                CLOSE_CHECK_INTERVAL,
+                stream.try_next(),
+            ));
+            match next {
+                Ok(Ok(Some(event))) => {
+                    if !self.emit(event) {
+                        break;
+                    }
+                }
+                Ok(Ok(None)) => break,
+                Ok(Err(e)) => return Err(e),
+                // timeout, check the receiver again.
+                Err(_) => continue,
+            }
+        }
+        Ok(())
+    }
+
+    /// Send the event to the receiver if it is not seen before, return false if the receiver is dropped.
+    fn emit(&mut self, event: TransactionEventView) -> bool {
+        if let Some(block_number) = event.block_number.map(|n| n.0) {
+            if let (Some(block_hash), Some(txn_index)) = (event.block_hash, event.transaction_index)
+            {
+                let id = (
+                    block_hash,
+                    txn_index,
+                    event.event_key,
+                    event.event_seq_number.0,
+                );
+                if !self
+                    .seen_events
+                    .entry(block_number)
+                    .or_insert_with(HashSet::new)
+                    .insert(id)
+                {
+                    return true;
+                }
+            }
+            if self
+                .last_seen_block
+                .map(|n| block_number > n)
+                .unwrap_or(true)
+            {
+                self.last_seen_block = Some(block_number);
+                let retain_from = block_number.saturating_sub(DEDUP_BLOCK_WINDOW);
+                self.seen_events = self.seen_events.split_off(&retain_from);
+            }
+        }
+        self.sender.unbounded_send(event).is_ok()
+    }
+
+    fn notify(&self, state: ConnectionState) {
+        if let Some(callback) = self.options.on_state_change.as_ref() {
+            callback(&state);
+        }
+    }
+}
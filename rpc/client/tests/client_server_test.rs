@@ -3,10 +3,32 @@
 
 use anyhow::Result;
 use futures::{StreamExt, TryStreamExt};
-use starcoin_config::NodeConfig;
+use starcoin_config::{ApiQuotaConfig, NodeConfig};
+use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
-use starcoin_rpc_api::types::pubsub::MintBlock;
-use starcoin_rpc_client::RpcClient;
+use starcoin_rpc_api::errors::{
+    AccountErrorData, RateLimitedData, RpcErrorCode, SubscriptionLimitData, SubscriptionLimitScope,
+};
+use starcoin_rpc_api::types::pubsub::{EventFilter, Kind, MintBlock};
+use starcoin_rpc_api::types::{
+    ContractCall, FunctionIdView, ModuleIdView, StrView, TransactionRequest, TransactionVMStatus,
+    TxPoolSummaryView, TxnDiagnoseTarget, TxnVerdict, TypeTagView,
+};
+use starcoin_rpc_client::resilient_subscriber::{ConnectionState, ResilientSubscribeOptions};
+use starcoin_rpc_client::{RemoteStateError, RemoteStateReader, RpcClient};
+use starcoin_state_api::ChainStateReader;
+use starcoin_transaction_builder::{build_empty_script, peer_to_peer_txn_sent_as_association};
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::language_storage::TypeTag;
+use starcoin_types::vm_error::StatusCode;
+use starcoin_types::webhook::WebhookFilter;
+use starcoin_vm_types::account_config::{association_address, AccountResource};
+use starcoin_vm_types::move_resource::MoveResource;
+use starcoin_vm_types::token::stc::STC_TOKEN_CODE;
+use std::collections::HashSet;
+use std::io::{Read, Write};
+use std::net::TcpStream;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -139,3 +161,993 @@ fn test_client_reconnect_subscribe() -> Result<()> {
     assert_ne!(events2.len(), 0);
     Ok(())
 }
+
+#[stest::test(timeout = 120)]
+fn test_remote_state_reader_retry() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let url = config.rpc.get_ws_address().unwrap();
+
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    std::thread::sleep(Duration::from_millis(300));
+
+    let ws_client =
+        RpcClient::connect_websocket(url.to_string().as_str()).expect("connect websocket fail.");
+    let reader = RemoteStateReader::new(&ws_client)?.with_retry(3, Duration::from_millis(100));
+    let state_root = reader.state_root();
+    let account_state = reader.get_account_state(&association_address())?;
+    assert!(account_state.is_some());
+
+    // drop the connection in the middle of the reads.
+    let _e = node_handle.stop();
+    let node_handle = test_helper::run_node_by_config(config)?;
+    std::thread::sleep(Duration::from_millis(300));
+
+    // the read is retried with a new connection, and still at the pinned root.
+    assert_eq!(
+        reader.get_account_state(&association_address())?,
+        account_state
+    );
+    assert_eq!(reader.state_root(), state_root);
+
+    let unknown_reader = RemoteStateReader::new_at_root(&ws_client, HashValue::random());
+    let err = unknown_reader
+        .get_account_state(&association_address())
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<RemoteStateError>(),
+        Some(RemoteStateError::NotFound(_))
+    ));
+
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test(timeout = 120)]
+fn test_resilient_event_subscribe() -> Result<()> {
+    let node_config = NodeConfig::random_for_test();
+    let config = Arc::new(node_config);
+    let url = config.rpc.get_ws_address().unwrap();
+
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    std::thread::sleep(Duration::from_millis(300));
+
+    let ws_client = Arc::new(
+        RpcClient::connect_websocket(url.to_string().as_str()).expect("connect websocket fail."),
+    );
+    let states = Arc::new(parking_lot::Mutex::new(vec![]));
+    let states_clone = states.clone();
+    let options = ResilientSubscribeOptions {
+        initial_backoff: Duration::from_millis(100),
+        ..Default::default()
+    }
+    .on_state_change(move |state| states_clone.lock().push(state.clone()));
+    let filter = EventFilter::default();
+    let mut stream = ws_client
+        .clone()
+        .subscribe_events_resilient_with_options(filter, options);
+    std::thread::sleep(Duration::from_millis(500));
+
+    // every block metadata txn emits a NewBlockEvent.
+    let block1 = node_handle.generate_block()?;
+    let event1 = futures::executor::block_on(stream.next()).expect("should receive event");
+    assert_eq!(event1.block_hash, Some(block1.id()));
+    let _e = node_handle.stop();
+
+    let node_handle = test_helper::run_node_by_config(config)?;
+    // the event of the block generated before reconnection should be backfilled.
+    let block2 = node_handle.generate_block()?;
+    let event2 = futures::executor::block_on(stream.next()).expect("should receive event");
+    assert_eq!(event2.block_hash, Some(block2.id()));
+    let block3 = node_handle.generate_block()?;
+    let event3 = futures::executor::block_on(stream.next()).expect("should receive event");
+    assert_eq!(event3.block_hash, Some(block3.id()));
+
+    let block_hashes = vec![event1, event2, event3]
+        .into_iter()
+        .map(|event| event.block_hash)
+        .collect::<HashSet<_>>();
+    assert_eq!(block_hashes.len(), 3);
+    assert!(states
+        .lock()
+        .iter()
+        .any(|state| matches!(state, ConnectionState::Reconnecting { .. })));
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_resolve_function() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+
+    let function = client
+        .contract_resolve_function(
+            "0x1::TransferScripts::peer_to_peer"
+                .parse::<FunctionIdView>()?
+                .0,
+        )?
+        .expect("peer_to_peer should exist");
+    assert_eq!(function.type_params.len(), 1);
+    assert_eq!(function.type_params[0].abilities, vec!["store".to_string()]);
+    let arg_types: Vec<_> = function
+        .args
+        .iter()
+        .map(|arg| arg.type_tag.0.clone())
+        .collect();
+    assert_eq!(
+        arg_types,
+        vec![
+            TypeTag::Address,
+            TypeTag::Vector(Box::new(TypeTag::U8)),
+            TypeTag::U128
+        ]
+    );
+
+    assert!(client
+        .contract_resolve_function(
+            "0x1::TransferScripts::not_exist"
+                .parse::<FunctionIdView>()?
+                .0
+        )?
+        .is_none());
+    assert!(client
+        .contract_get_module_abi("0x1::NotExist".parse::<ModuleIdView>()?.0)?
+        .is_none());
+    let abi = client
+        .contract_get_module_abi("0x1::TransferScripts".parse::<ModuleIdView>()?.0)?
+        .expect("TransferScripts should exist");
+    assert!(abi.function("peer_to_peer_batch").is_some());
+    assert!(client
+        .contract_module_exists("0x1::NotExist".parse::<ModuleIdView>()?.0)?
+        .is_none());
+    let module_id = "0x1::TransferScripts".parse::<ModuleIdView>()?.0;
+    let code = client
+        .get_code(module_id.clone(), false)?
+        .expect("TransferScripts should exist");
+    assert_eq!(
+        client.contract_module_exists(module_id)?,
+        Some(HashValue::sha3_256_of(code.code.0.as_slice()))
+    );
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_contract_call_v2() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+
+    let balance_call = |address: AccountAddress| -> Result<ContractCall> {
+        Ok(ContractCall {
+            function_id: "0x1::Account::balance".parse::<FunctionIdView>()?,
+            type_args: vec!["0x1::STC::STC".parse::<TypeTagView>()?],
+            args: vec![serde_json::Value::String(address.to_string())],
+        })
+    };
+    // call_v2 returns the same values as call if the execution succeeds.
+    assert_eq!(
+        client.contract_call_v2(balance_call(association_address())?)?,
+        client.contract_call(balance_call(association_address())?)?
+    );
+
+    // the balance does not exist, so the execution fails.
+    let err = client
+        .contract_call_v2(balance_call(AccountAddress::random())?)
+        .unwrap_err();
+    let rpc_err = err
+        .downcast_ref::<jsonrpc_core::Error>()
+        .expect("should be a rpc error");
+    assert_eq!(
+        RpcErrorCode::of(rpc_err),
+        Some(RpcErrorCode::ExecutionFailed)
+    );
+    let status: TransactionVMStatus = serde_json::from_value(rpc_err.data.clone().unwrap())?;
+    assert!(
+        matches!(status, TransactionVMStatus::ExecutionFailure { .. }),
+        "unexpected status: {:?}",
+        status
+    );
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_error_code() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+
+    let missing_account = AccountAddress::random();
+    let err = client
+        .account_unlock(missing_account, "".to_string(), Duration::from_secs(60))
+        .unwrap_err();
+    let rpc_err = err
+        .downcast_ref::<jsonrpc_core::Error>()
+        .expect("should be a rpc error");
+    assert_eq!(
+        RpcErrorCode::of(rpc_err),
+        Some(RpcErrorCode::AccountNotFound)
+    );
+    let data: AccountErrorData = serde_json::from_value(rpc_err.data.clone().unwrap())?;
+    assert_eq!(data.address, missing_account);
+
+    // the new account is not on chain, so its txn is discarded by validation.
+    let account = client.account_create("".to_string())?;
+    client.account_unlock(account.address, "".to_string(), Duration::from_secs(60))?;
+    let txn = client.account_sign_txn_request(TransactionRequest {
+        sender: Some(account.address.into()),
+        sequence_number: Some(StrView(0)),
+        script: Some(build_empty_script().into()),
+        chain_id: Some(config.net().chain_id().id()),
+        ..Default::default()
+    })?;
+    let err = client.submit_transaction(txn).unwrap_err();
+    let rpc_err = err
+        .downcast_ref::<jsonrpc_core::Error>()
+        .expect("should be a rpc error");
+    assert_eq!(
+        RpcErrorCode::of(rpc_err),
+        Some(RpcErrorCode::TxnValidationFailed)
+    );
+    let status: TransactionVMStatus = serde_json::from_value(rpc_err.data.clone().unwrap())?;
+    assert_eq!(
+        status,
+        TransactionVMStatus::Discard {
+            status_code: StrView(StatusCode::SENDING_ACCOUNT_DOES_NOT_EXIST.into())
+        }
+    );
+
+    // the txn rejected by the txpool is a validation failure too.
+    let expiration_timestamp_secs = client.node_info()?.now_seconds + 3600;
+    let txn = peer_to_peer_txn_sent_as_association(
+        AccountAddress::random(),
+        None,
+        0,
+        1000,
+        expiration_timestamp_secs,
+        config.net(),
+    );
+    client.submit_transaction(txn.clone())?;
+    let err = client.submit_transaction(txn).unwrap_err();
+    let rpc_err = err
+        .downcast_ref::<jsonrpc_core::Error>()
+        .expect("should be a rpc error");
+    assert_eq!(
+        RpcErrorCode::of(rpc_err),
+        Some(RpcErrorCode::TxnValidationFailed)
+    );
+    assert!(rpc_err.data.is_none());
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+fn rate_limited_data(e: &anyhow::Error) -> Option<RateLimitedData> {
+    let rpc_err = e.downcast_ref::<jsonrpc_core::Error>()?;
+    if RpcErrorCode::of(rpc_err) != Some(RpcErrorCode::RateLimited) {
+        return None;
+    }
+    serde_json::from_value(rpc_err.data.clone()?).ok()
+}
+
+#[stest::test]
+fn test_api_quota() -> Result<()> {
+    let mut node_config = NodeConfig::random_for_test();
+    node_config.rpc.api_quotas.custom_global_api_quota = Some(vec![
+        ("node.info".to_string(), "5/m".parse::<ApiQuotaConfig>()?),
+        ("chain.*".to_string(), "5/m".parse::<ApiQuotaConfig>()?),
+    ]);
+    node_config.rpc.api_quotas.max_subscriptions = Some(2);
+    let config = Arc::new(node_config);
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+
+    let results: Vec<_> = (0..20).map(|_| client.node_info()).collect();
+    assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 5);
+    let data = rate_limited_data(results.last().unwrap().as_ref().unwrap_err())
+        .expect("node.info should be rate limited");
+    assert!(data.retry_after_ms > 0);
+
+    // all apis of chain share the quota.
+    for _i in 0..5 {
+        client.chain_info()?;
+    }
+    let err = client.chain_get_block_by_number(0, None).unwrap_err();
+    assert!(rate_limited_data(&err).is_some());
+    // other apis are not affected.
+    client.account_list()?;
+
+    let _s1 = client.subscribe_new_blocks()?;
+    let _s2 = client.subscribe_new_transactions()?;
+    let err = client.subscribe_new_blocks().err().unwrap();
+    let rpc_err = err
+        .downcast_ref::<jsonrpc_core::Error>()
+        .expect("should be a rpc error");
+    assert_eq!(
+        RpcErrorCode::of(rpc_err),
+        Some(RpcErrorCode::TooManySubscriptions)
+    );
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_ipc_pubsub_subscriptions() -> Result<()> {
+    let mut node_config = NodeConfig::random_for_test();
+    node_config.rpc.api_quotas.max_subscriptions_per_connection = Some(6);
+    let config = Arc::new(node_config);
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+
+    // all kinds can be subscribed by ipc.
+    let _s1 = client.subscribe_new_blocks()?;
+    let _s2 = client.subscribe_new_transactions()?;
+    let _s3 = client.subscribe_dropped_transactions()?;
+    let _s4 = client.subscribe_evicted_transactions()?;
+    let _s5 = client.subscribe_new_mint_blocks()?;
+    let _s6 = client.subscribe_events(EventFilter::default())?;
+    let kinds: Vec<_> = client
+        .list_subscriptions()?
+        .into_iter()
+        .map(|s| s.kind)
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            Kind::NewHeads,
+            Kind::NewPendingTransactions,
+            Kind::DroppedTransactions,
+            Kind::EvictedTransactions,
+            Kind::NewMintBlock,
+            Kind::Events,
+        ]
+    );
+
+    let err = client.subscribe_new_blocks().err().unwrap();
+    let rpc_err = err
+        .downcast_ref::<jsonrpc_core::Error>()
+        .expect("should be a rpc error");
+    assert_eq!(
+        RpcErrorCode::of(rpc_err),
+        Some(RpcErrorCode::TooManySubscriptions)
+    );
+    let data: SubscriptionLimitData = serde_json::from_value(rpc_err.data.clone().unwrap())?;
+    assert_eq!(data.scope, SubscriptionLimitScope::Connection);
+    assert_eq!(data.limit, 6);
+
+    // the limit is of the connection.
+    let other_client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+    let _s7 = other_client.subscribe_new_blocks()?;
+    assert_eq!(other_client.list_subscriptions()?.len(), 1);
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_disable_api_quota() -> Result<()> {
+    let mut node_config = NodeConfig::random_for_test();
+    node_config.rpc.api_quotas.custom_global_api_quota = Some(vec![(
+        "node.info".to_string(),
+        "5/m".parse::<ApiQuotaConfig>()?,
+    )]);
+    node_config.rpc.api_quotas.disable_api_quota = Some(true);
+    let config = Arc::new(node_config);
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+    for _i in 0..20 {
+        client.node_info()?;
+    }
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+/// Call the method by a raw http request, return the error code of the response.
+fn http_call_error_code(port: u16, method: &str) -> Result<Option<i64>> {
+    let body =
+        serde_json::json!({"jsonrpc": "2.0", "method": method, "params": [], "id": 1}).to_string();
+    let mut stream = TcpStream::connect(("127.0.0.1", port))?;
+    write!(
+        stream,
+        "POST / HTTP/1.1\r\nHost: 127.0.0.1:{}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        port,
+        body.len(),
+        body
+    )?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let response_body = response
+        .splitn(2, "\r\n\r\n")
+        .nth(1)
+        .ok_or_else(|| anyhow::anyhow!("invalid http response: {}", response))?;
+    let output: serde_json::Value = serde_json::from_str(response_body)?;
+    Ok(output["error"]["code"].as_i64())
+}
+
+#[stest::test]
+fn test_api_filter() -> Result<()> {
+    let mut node_config = NodeConfig::random_for_test();
+    node_config.rpc.api_filter.deny = Some(vec!["account.*".to_string(), "chain.info".to_string()]);
+    let config = Arc::new(node_config);
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+
+    let http_port = config.rpc.get_http_address().unwrap().port;
+    assert_eq!(
+        http_call_error_code(http_port, "account.list")?,
+        Some(RpcErrorCode::MethodDisabled.code())
+    );
+    assert_eq!(
+        http_call_error_code(http_port, "chain.info")?,
+        Some(RpcErrorCode::MethodDisabled.code())
+    );
+    assert_eq!(http_call_error_code(http_port, "node.info")?, None);
+
+    let ws_url = config.rpc.get_ws_address().unwrap();
+    let ws_client = RpcClient::connect_websocket(ws_url.to_string().as_str())?;
+    let err = ws_client.chain_info().unwrap_err();
+    let rpc_err = err
+        .downcast_ref::<jsonrpc_core::Error>()
+        .expect("should be a rpc error");
+    assert_eq!(
+        RpcErrorCode::of(rpc_err),
+        Some(RpcErrorCode::MethodDisabled)
+    );
+    let ws_apis = ws_client.node_list_enabled_apis()?;
+    assert!(ws_apis.contains(&"node.info".to_string()));
+    assert!(ws_apis.contains(&"node.list_enabled_apis".to_string()));
+    assert!(!ws_apis.contains(&"chain.info".to_string()));
+    assert!(!ws_apis.iter().any(|api| api.starts_with("account.")));
+
+    // the ipc exposes all the methods.
+    let ipc_client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+    ipc_client.account_list()?;
+    ipc_client.chain_info()?;
+    let ipc_apis = ipc_client.node_list_enabled_apis()?;
+    assert!(ipc_apis.contains(&"chain.info".to_string()));
+    assert!(ipc_apis.contains(&"account.list".to_string()));
+
+    ws_client.close();
+    ipc_client.close();
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_admin_methods_ipc_only() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+
+    let admin_methods = vec![
+        "node_manager.shutdown",
+        "node_manager.register_webhook",
+        "node_manager.list_webhooks",
+        "node_manager.remove_webhook",
+        "node_manager.rebuild_token_holder_index",
+        "node_manager.create_event_cursor",
+        "node_manager.add_peer",
+        "node_manager.remove_peer",
+        "node_manager.ban_peer",
+        "node_manager.config",
+        "node_manager.reload_config",
+        "node_manager.trace_rpc",
+        "node_manager.system_info",
+        "node_manager.verify_checkpoints",
+    ];
+    // the methods moved from the public apis.
+    let removed_methods = vec![
+        "node.shutdown",
+        "node.register_webhook",
+        "node.rebuild_token_holder_index",
+        "chain.create_event_cursor",
+        "node.add_peer",
+        "node.remove_peer",
+        "node.ban_peer",
+        "node.config",
+        "node.reload_config",
+        "node.trace_rpc",
+        "node.system_info",
+        "node.verify_checkpoints",
+    ];
+    let http_port = config.rpc.get_http_address().unwrap().port;
+    for method in admin_methods.iter().chain(removed_methods.iter()) {
+        assert_eq!(
+            http_call_error_code(http_port, method)?,
+            Some(jsonrpc_core::ErrorCode::MethodNotFound.code()),
+            "{} should be rejected over http",
+            method
+        );
+    }
+
+    let ipc_client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+    let ipc_apis = ipc_client.node_list_enabled_apis()?;
+    for method in &admin_methods {
+        assert!(ipc_apis.contains(&method.to_string()));
+    }
+    ipc_client.close();
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_register_webhook_reject_private_target() -> Result<()> {
+    let mut node_config = NodeConfig::random_for_test();
+    node_config.webhook.enable = Some(true);
+    node_config.webhook.allow_private_targets = Some(false);
+    let config = Arc::new(node_config);
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+
+    let ipc_client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+    for url in &["http://127.0.0.1:9850/hook", "http://10.0.0.1/hook"] {
+        let filter = WebhookFilter {
+            senders: vec![association_address()],
+            txn_hashes: vec![],
+        };
+        assert!(ipc_client
+            .node_register_webhook(url.to_string(), filter)
+            .is_err());
+    }
+    assert!(ipc_client.node_list_webhooks()?.is_empty());
+    ipc_client.close();
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_check_compat() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+    let node_info = client.node_info()?;
+    assert_eq!(node_info.build.version, starcoin_config::CRATE_VERSION);
+    assert!(node_info.build.build_time > 0);
+    assert!(node_info.api_versions.contains_key("chain"));
+    assert!(client.check_compat()?.is_empty());
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_sign_txn_request_offline() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+    let account = client.account_create("".to_string())?;
+    client.account_unlock(account.address, "".to_string(), Duration::from_secs(60))?;
+
+    let request = TransactionRequest {
+        sender: Some(account.address.into()),
+        sequence_number: Some(StrView(0)),
+        script: Some(build_empty_script().into()),
+        expiration_timestamp_secs: Some(StrView(client.node_info()?.now_seconds + 3600)),
+        chain_id: Some(config.net().chain_id().id()),
+        ..Default::default()
+    };
+    let online_txn = client.account_sign_txn_request(request.clone())?;
+    let offline_txn = client.account_sign_txn(request.to_raw_txn_offline()?)?;
+    assert_eq!(
+        bcs_ext::to_bytes(&online_txn)?,
+        bcs_ext::to_bytes(&offline_txn)?
+    );
+
+    // the offline request should not miss any state field.
+    let err = TransactionRequest {
+        chain_id: None,
+        ..request
+    }
+    .to_raw_txn_offline()
+    .unwrap_err();
+    assert!(err.to_string().contains("chain_id"));
+
+    // the hex is decoded and validated by the node, the sender is not on chain.
+    let err = client
+        .submit_hex_transaction(hex::encode(bcs_ext::to_bytes(&offline_txn)?))
+        .unwrap_err();
+    let rpc_err = err
+        .downcast_ref::<jsonrpc_core::Error>()
+        .expect("should be a rpc error");
+    assert_eq!(
+        RpcErrorCode::of(rpc_err),
+        Some(RpcErrorCode::TxnValidationFailed)
+    );
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_epoch_info() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+
+    let epoch_info = client.epoch_info()?;
+    assert_eq!(epoch_info.number.0, 0);
+    assert_eq!(epoch_info.start_block_number.0, 0);
+    assert!(epoch_info.end_block_number.0 > 0);
+    assert!(epoch_info.reward_per_block.0 > 0);
+
+    let genesis_epoch_info = client.get_epoch_info_by_number(0)?;
+    assert_eq!(genesis_epoch_info.number, epoch_info.number);
+    assert_eq!(
+        genesis_epoch_info.block_time_target,
+        epoch_info.block_time_target
+    );
+    assert!(client.get_epoch_info_by_number(u64::max_value()).is_err());
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_transaction_output() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+    let block = node_handle.generate_block()?;
+
+    let txn_infos = client.chain_get_block_txn_infos(block.id())?;
+    assert!(!txn_infos.is_empty());
+    for txn_info in txn_infos {
+        let output = client
+            .chain_get_transaction_output(txn_info.transaction_hash)?
+            .expect("output of the mined txn should exist");
+        assert_eq!(output.verified, Some(true));
+        assert_eq!(output.gas_used, txn_info.gas_used);
+        assert!(!output.write_set.is_empty());
+    }
+    assert!(client
+        .chain_get_transaction_output(HashValue::random())?
+        .is_none());
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_state_get_with_proof_bcs() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+
+    let access_path = AccessPath::new(association_address(), AccountResource::resource_path());
+    let (state_proof, state_root) = client.state_get_with_proof_bcs(access_path.clone())?;
+    assert_eq!(state_root, client.state_get_state_root()?);
+    assert!(state_proof.state.is_some());
+    state_proof
+        .proof
+        .verify(state_root, access_path, state_proof.state.as_deref())?;
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_block_bundle_state_disabled_by_default() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+    let block = node_handle.generate_block()?;
+
+    assert!(client.chain_export_block_bundle(block.id(), true).is_err());
+    let bundle = client
+        .chain_export_block_bundle(block.id(), false)?
+        .expect("bundle of the block should exist");
+    assert!(bundle.state.is_none());
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_get_transaction_infos() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+    let expiration_timestamp_secs = client.node_info()?.now_seconds + 3600;
+
+    let mut blocks = vec![node_handle.generate_block()?];
+    let txn = peer_to_peer_txn_sent_as_association(
+        AccountAddress::random(),
+        None,
+        0,
+        1000,
+        expiration_timestamp_secs,
+        config.net(),
+    );
+    client.submit_transaction(txn)?;
+    blocks.push(node_handle.generate_block()?);
+    blocks.push(node_handle.generate_block()?);
+
+    let num_leaves = client
+        .chain_info()?
+        .block_info
+        .txn_accumulator_info
+        .num_leaves
+        .0;
+    let txn_infos = client.chain_get_transaction_infos(0, false, num_leaves)?;
+    assert_eq!(txn_infos.len() as u64, num_leaves);
+    for (i, txn_info) in txn_infos.iter().enumerate() {
+        assert_eq!(txn_info.global_index.0, i as u64);
+    }
+    // the txn infos of the blocks are at their positions of the main chain.
+    for block in &blocks {
+        let block_txn_infos = client.chain_get_block_txn_infos(block.id())?;
+        // the block metadata txn is the first one of the block.
+        assert_eq!(block_txn_infos.len(), block.transactions().len() + 1);
+        for block_txn_info in block_txn_infos {
+            let txn_info = &txn_infos[block_txn_info.global_index.0 as usize];
+            assert_eq!(txn_info, &block_txn_info);
+            assert_eq!(txn_info.block_hash, block.id());
+            assert_eq!(txn_info.block_number.0, block.header().number());
+        }
+    }
+
+    // paging forward.
+    let page = client.chain_get_transaction_infos(1, false, 2)?;
+    assert_eq!(page.as_slice(), &txn_infos[1..3]);
+    let page = client.chain_get_transaction_infos(num_leaves, false, 2)?;
+    assert!(page.is_empty());
+
+    // paging backward, begin from the last one if the start is out of range.
+    let page = client.chain_get_transaction_infos(u64::MAX, true, 2)?;
+    let global_indexes: Vec<u64> = page.iter().map(|info| info.global_index.0).collect();
+    assert_eq!(global_indexes, vec![num_leaves - 1, num_leaves - 2]);
+    let page = client.chain_get_transaction_infos(1, true, 5)?;
+    let global_indexes: Vec<u64> = page.iter().map(|info| info.global_index.0).collect();
+    assert_eq!(global_indexes, vec![1, 0]);
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_get_transactions() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+    let expiration_timestamp_secs = client.node_info()?.now_seconds + 3600;
+
+    let mined_txn = peer_to_peer_txn_sent_as_association(
+        AccountAddress::random(),
+        None,
+        0,
+        1000,
+        expiration_timestamp_secs,
+        config.net(),
+    );
+    let mined_txn_hash = client.submit_transaction(mined_txn)?;
+    let block = node_handle.generate_block()?;
+    assert!(block
+        .transactions()
+        .iter()
+        .any(|txn| txn.id() == mined_txn_hash));
+
+    let pending_txn = peer_to_peer_txn_sent_as_association(
+        AccountAddress::random(),
+        None,
+        1,
+        1000,
+        expiration_timestamp_secs,
+        config.net(),
+    );
+    let pending_txn_hash = client.submit_transaction(pending_txn)?;
+    let unknown_txn_hash = HashValue::random();
+
+    let txns = client.chain_get_transactions(vec![
+        unknown_txn_hash,
+        mined_txn_hash,
+        pending_txn_hash,
+        mined_txn_hash,
+    ])?;
+    assert_eq!(txns.len(), 4);
+    assert!(txns[0].is_none());
+    let mined = txns[1].as_ref().expect("mined txn should exist");
+    assert_eq!(mined.transaction_hash, mined_txn_hash);
+    assert_eq!(mined.block_hash, block.id());
+    assert_eq!(
+        Some(mined),
+        client.chain_get_transaction(mined_txn_hash)?.as_ref()
+    );
+    assert!(txns[2].is_none());
+    assert_eq!(txns[3].as_ref(), Some(mined));
+
+    assert_eq!(
+        client.chain_get_block_hash_by_txn(mined_txn_hash)?,
+        Some(block.id())
+    );
+    assert_eq!(client.chain_get_block_hash_by_txn(pending_txn_hash)?, None);
+
+    let err = client
+        .chain_get_transactions((0..101).map(|_| HashValue::random()).collect())
+        .unwrap_err();
+    let rpc_err = err
+        .downcast_ref::<jsonrpc_core::Error>()
+        .expect("should be a rpc error");
+    assert_eq!(rpc_err.code, jsonrpc_core::ErrorCode::InvalidParams);
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_get_headers() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+    let block1 = node_handle.generate_block()?;
+    let block2 = node_handle.generate_block()?;
+
+    let unknown_block_hash = HashValue::random();
+    let headers = client.get_headers(vec![block1.id(), unknown_block_hash, block2.id()])?;
+    assert_eq!(
+        headers
+            .iter()
+            .map(|header| header.block_hash)
+            .collect::<Vec<_>>(),
+        vec![block1.id(), block2.id()]
+    );
+    let headers = client.get_headers_v2(vec![block1.id(), unknown_block_hash, block2.id()])?;
+    assert_eq!(headers.len(), 3);
+    assert_eq!(
+        headers[0].as_ref().map(|header| header.block_hash),
+        Some(block1.id())
+    );
+    assert!(headers[1].is_none());
+    assert_eq!(
+        headers[2].as_ref().map(|header| header.block_hash),
+        Some(block2.id())
+    );
+
+    let view = client.get_headers_by_number(1, 10)?;
+    assert!(view.consistent);
+    assert_eq!(
+        view.headers
+            .iter()
+            .map(|header| header.block_hash)
+            .collect::<Vec<_>>(),
+        vec![block1.id(), block2.id()]
+    );
+    assert!(client.get_headers_by_number(0, 257).is_err());
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_event_cursor() -> Result<()> {
+    let mut node_config = NodeConfig::random_for_test();
+    node_config.rpc.event_cursor_idle_timeout = Some(60);
+    node_config.rpc.max_event_cursors = Some(1);
+    let config = Arc::new(node_config);
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+    let block1 = node_handle.generate_block()?;
+
+    let cursor_id = client.node_create_event_cursor(EventFilter {
+        from_block: Some(StrView(1)),
+        ..Default::default()
+    })?;
+    // the count of the cursors is capped.
+    assert!(client
+        .node_create_event_cursor(EventFilter::default())
+        .is_err());
+    let page = client.chain_poll_events(cursor_id, 1000)?;
+    assert!(page.done);
+    assert!(!page.events.is_empty());
+    assert!(page
+        .events
+        .iter()
+        .all(|event| event.block_hash == Some(block1.id())));
+    // the unacked events are replayed.
+    assert_eq!(client.chain_poll_events(cursor_id, 1000)?, page);
+    let first = client.chain_poll_events(cursor_id, 1)?;
+    assert_eq!(first.events, page.events[..1].to_vec());
+    assert_eq!(first.done, page.events.len() == 1);
+
+    let last_index = page.events.last().unwrap().global_index.unwrap().0;
+    client.chain_ack_events(cursor_id, last_index)?;
+    assert!(client.chain_poll_events(cursor_id, 1000)?.events.is_empty());
+    // ack an older event is ignored.
+    client.chain_ack_events(cursor_id, page.events[0].global_index.unwrap().0)?;
+    let block2 = node_handle.generate_block()?;
+    let page = client.chain_poll_events(cursor_id, 1000)?;
+    assert!(page.done);
+    assert!(!page.events.is_empty());
+    assert!(page
+        .events
+        .iter()
+        .all(|event| event.block_hash == Some(block2.id())));
+
+    // the cursor is removed after idle for more than the timeout.
+    config.net().time_service().sleep(120 * 1000);
+    let err = client.chain_poll_events(cursor_id, 1000).unwrap_err();
+    let rpc_err = err
+        .downcast_ref::<jsonrpc_core::Error>()
+        .expect("should be a rpc error");
+    assert_eq!(
+        RpcErrorCode::of(rpc_err),
+        Some(RpcErrorCode::EventCursorExpired)
+    );
+    let err = client.chain_poll_events(cursor_id + 100, 1000).unwrap_err();
+    let rpc_err = err
+        .downcast_ref::<jsonrpc_core::Error>()
+        .expect("should be a rpc error");
+    assert_eq!(rpc_err.code, jsonrpc_core::ErrorCode::InvalidParams);
+    // the expired cursor does not count.
+    let new_cursor_id = client.node_create_event_cursor(EventFilter::default())?;
+    assert!(new_cursor_id > cursor_id);
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_txpool_diagnose() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+
+    let txn_hash = HashValue::random();
+    let diagnosis = client.txpool_diagnose(TxnDiagnoseTarget::Txn(txn_hash))?;
+    assert_eq!(diagnosis.txn_hash, Some(txn_hash));
+    assert_eq!(diagnosis.verdict, TxnVerdict::NotInPool);
+
+    let sender = AccountAddress::random();
+    let diagnosis = client.txpool_diagnose(TxnDiagnoseTarget::Sender(sender))?;
+    assert_eq!(diagnosis.sender, Some(sender));
+    assert_eq!(diagnosis.verdict, TxnVerdict::NotInPool);
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_txpool_detailed_status() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+
+    let status = client.txpool_detailed_status()?;
+    assert_eq!(status.pending, 0);
+    assert_eq!(status.queued, 0);
+    assert_eq!(
+        status.min_gas_price_to_enter.0,
+        config.tx_pool.min_gas_price()
+    );
+    assert_eq!(
+        status.gas_price_bump_percent.0,
+        config.tx_pool.gas_price_bump_percent()
+    );
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_chain_info() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+    let block1 = node_handle.generate_block()?;
+
+    let chain_info = client.chain_info()?;
+    assert_eq!(chain_info.head.block_hash, block1.id());
+    assert_eq!(chain_info.chain_id.id, config.net().chain_id().id());
+    assert_eq!(
+        chain_info.txpool,
+        Some(TxPoolSummaryView {
+            pending: 0,
+            queued: 0
+        })
+    );
+    assert!(chain_info.sync.is_none());
+    assert_eq!(chain_info.peers, Some(0));
+    let _e = node_handle.stop();
+    Ok(())
+}
+
+#[stest::test]
+fn test_accepted_tokens() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_handle = test_helper::run_node_by_config(config.clone())?;
+    let client = RpcClient::connect_ipc(config.rpc.get_ipc_file())?;
+
+    let accepted_tokens = client.account_accepted_tokens(association_address())?;
+    assert!(accepted_tokens
+        .iter()
+        .any(|token| token.0 == *STC_TOKEN_CODE));
+    assert!(client
+        .account_accepted_tokens(AccountAddress::random())?
+        .is_empty());
+    let _e = node_handle.stop();
+    Ok(())
+}
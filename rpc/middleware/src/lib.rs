@@ -2,15 +2,19 @@
 // SPDX-License-Identifier: Apache-2
 
 use futures::{future::Either, Future, FutureExt};
-use jsonrpc_core::{Call, FutureResponse, Id, Metadata, Middleware, Output, Request, Response};
+use jsonrpc_core::{
+    Call, FutureResponse, Id, Middleware, Output, Params, Request, Response, Value,
+};
 use starcoin_logger::prelude::*;
 use starcoin_metrics::HistogramTimer;
 use std::fmt;
 
 mod metrics;
+mod trace;
 
 use jsonrpc_core::middleware::NoopCallFuture;
 pub use metrics::*;
+pub use trace::*;
 
 #[derive(Clone, Debug)]
 enum CallType {
@@ -31,33 +35,88 @@ impl fmt::Display for CallType {
 }
 
 struct RpcCallRecord {
+    trace_id: String,
+    batch_id: Option<String>,
     id: String,
     method: String,
     call_type: CallType,
+    params_size: usize,
     timer: HistogramTimer,
+    _pending: PendingCallGuard,
+}
+
+/// Count the call as pending until it is dropped, even if the call future is dropped before completion.
+struct PendingCallGuard;
+
+impl PendingCallGuard {
+    fn new() -> Self {
+        RPC_PENDING_CALLS.inc();
+        Self
+    }
+}
+
+impl Drop for PendingCallGuard {
+    fn drop(&mut self) {
+        RPC_PENDING_CALLS.dec();
+    }
 }
 
 impl RpcCallRecord {
-    pub fn new(id: String, method: Option<String>, call_type: CallType) -> Self {
+    pub fn new(
+        id: String,
+        method: Option<String>,
+        call_type: CallType,
+        params: Option<&Params>,
+    ) -> Self {
         let method = method.unwrap_or_else(|| "".to_owned());
         let timer = RPC_HISTOGRAMS
             .with_label_values(&[method.as_str()])
             .start_timer();
         Self {
+            trace_id: next_trace_id(),
+            batch_id: None,
             id,
             method,
             call_type,
+            params_size: params.map(params_size).unwrap_or_default(),
             timer,
+            _pending: PendingCallGuard::new(),
         }
     }
 
+    pub fn with_batch_id(mut self, batch_id: Option<String>) -> Self {
+        self.batch_id = batch_id;
+        self
+    }
+
     pub fn end(self, code: i64) {
         let use_time = self.timer.stop_and_record();
-
-        info!(
-            "rpc_call\t{}\t{}\t{}\t{}\t{}",
-            self.id, self.call_type, self.method, code, use_time
-        );
+        let batch_id = self.batch_id.as_deref().unwrap_or("-");
+        if is_rpc_traced(self.method.as_str()) {
+            info!(
+                "rpc_call\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                self.trace_id,
+                batch_id,
+                self.id,
+                self.call_type,
+                self.method,
+                self.params_size,
+                code,
+                use_time
+            );
+        } else {
+            debug!(
+                "rpc_call\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                self.trace_id,
+                batch_id,
+                self.id,
+                self.call_type,
+                self.method,
+                self.params_size,
+                code,
+                use_time
+            );
+        }
 
         RPC_COUNTERS
             .with_label_values(&[
@@ -69,6 +128,15 @@ impl RpcCallRecord {
     }
 }
 
+fn params_size(params: &Params) -> usize {
+    match params {
+        Params::None => 0,
+        params => serde_json::to_vec(params)
+            .map(|bytes| bytes.len())
+            .unwrap_or_default(),
+    }
+}
+
 fn id_to_string(id: &Id) -> String {
     match id {
         Id::Null => "".to_owned(),
@@ -84,29 +152,39 @@ impl From<&Call> for RpcCallRecord {
                 id_to_string(&method_call.id),
                 Some(method_call.method.clone()),
                 CallType::MethodCall,
+                Some(&method_call.params),
             ),
             Call::Notification(notification) => RpcCallRecord::new(
                 "0".to_owned(),
                 Some(notification.method.clone()),
                 CallType::Notification,
+                Some(&notification.params),
             ),
-            Call::Invalid { id } => RpcCallRecord::new(id_to_string(id), None, CallType::Invalid),
+            Call::Invalid { id } => {
+                RpcCallRecord::new(id_to_string(id), None, CallType::Invalid, None)
+            }
         }
     }
 }
 
+/// Record the metrics of every rpc call, and log it with a short trace id at debug level,
+/// or at info level if the method is traced by `set_rpc_trace`.
+/// The calls of a batch request share a batch id, and the trace id is attached to the returned errors.
 #[derive(Clone)]
 pub struct MetricMiddleware;
 
-impl<M: Metadata> Middleware<M> for MetricMiddleware {
+impl<M: TraceMetadata> Middleware<M> for MetricMiddleware {
     type Future = FutureResponse;
     type CallFuture = NoopCallFuture;
 
-    fn on_request<F, X>(&self, request: Request, meta: M, next: F) -> Either<Self::Future, X>
+    fn on_request<F, X>(&self, request: Request, mut meta: M, next: F) -> Either<Self::Future, X>
     where
         F: Fn(Request, M) -> X + Send + Sync,
         X: Future<Output = Option<Response>> + Send + 'static,
     {
+        if let Request::Batch(_) = &request {
+            meta.set_batch_id(next_trace_id());
+        }
         Either::Right(next(request, meta))
     }
 
@@ -115,10 +193,11 @@ impl<M: Metadata> Middleware<M> for MetricMiddleware {
         F: Fn(Call, M) -> X + Send + Sync,
         X: Future<Output = Option<Output>> + Send + 'static,
     {
-        let record: RpcCallRecord = (&call).into();
+        let record = RpcCallRecord::from(&call).with_batch_id(meta.batch_id().map(str::to_owned));
+        let trace_id = record.trace_id.clone();
         let fut = next(call, meta).map(move |output| {
             record.end(output_to_code(output.as_ref()));
-            output
+            output.map(|output| attach_trace_id(output, trace_id.as_str()))
         });
         // must declare type to convert type then wrap with Either.
         let box_fut: Self::CallFuture = Box::pin(fut);
@@ -126,6 +205,23 @@ impl<M: Metadata> Middleware<M> for MetricMiddleware {
     }
 }
 
+/// Attach the trace id to the error, put it into `error.data` if the error has no data,
+/// otherwise append it to the message, to keep the typed error data decodable.
+fn attach_trace_id(output: Output, trace_id: &str) -> Output {
+    match output {
+        Output::Failure(mut failure) => {
+            if matches!(failure.error.data, None | Some(Value::Null)) {
+                failure.error.data = Some(serde_json::json!({ "trace_id": trace_id }));
+            } else {
+                failure.error.message =
+                    format!("{} (trace_id: {})", failure.error.message, trace_id);
+            }
+            Output::Failure(failure)
+        }
+        output => output,
+    }
+}
+
 fn output_to_code(output: Option<&Output>) -> i64 {
     output
         .map(|output| match output {
@@ -3,7 +3,8 @@
 
 use once_cell::sync::Lazy;
 use starcoin_metrics::{
-    register_histogram_vec, register_int_counter_vec, HistogramVec, IntCounterVec,
+    register_histogram_vec, register_int_counter_vec, register_uint_gauge, HistogramVec,
+    IntCounterVec, UIntGauge,
 };
 
 pub static RPC_COUNTERS: Lazy<IntCounterVec> = Lazy::new(|| {
@@ -18,3 +19,16 @@ pub static RPC_COUNTERS: Lazy<IntCounterVec> = Lazy::new(|| {
 pub static RPC_HISTOGRAMS: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!("starcoin_rpc_time", "Histogram of rpc request", &["method"]).unwrap()
 });
+
+pub static RPC_PENDING_CALLS: Lazy<UIntGauge> = Lazy::new(|| {
+    register_uint_gauge!(
+        "starcoin_rpc_pending_calls",
+        "The count of the rpc calls which are in processing"
+    )
+    .unwrap()
+});
+
+/// The count of the rpc calls which are in processing on all transports.
+pub fn pending_rpc_calls() -> u64 {
+    RPC_PENDING_CALLS.get()
+}
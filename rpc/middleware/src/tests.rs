@@ -1,6 +1,6 @@
 use super::*;
 use futures::executor::block_on;
-use jsonrpc_core::{MetaIoHandler, Params, Value};
+use jsonrpc_core::{MetaIoHandler, Metadata, Params, Value};
 use rand::Rng;
 use starcoin_metrics::get_all_metrics;
 use std::time::Duration;
@@ -29,3 +29,64 @@ fn test_middleware() {
     }
     info!("metrics: {:?}", get_all_metrics());
 }
+
+#[derive(Clone, Default)]
+struct TestMeta {
+    batch_id: Option<String>,
+}
+
+impl Metadata for TestMeta {}
+
+impl TraceMetadata for TestMeta {
+    fn batch_id(&self) -> Option<&str> {
+        self.batch_id.as_deref()
+    }
+
+    fn set_batch_id(&mut self, batch_id: String) {
+        self.batch_id = Some(batch_id);
+    }
+}
+
+#[stest::test]
+fn test_trace_id_in_error() {
+    let mut io_handler = MetaIoHandler::with_middleware(MetricMiddleware);
+    io_handler.add_method_with_meta("batch_id", |_params: Params, meta: TestMeta| async move {
+        Ok(meta.batch_id.map(Value::String).unwrap_or(Value::Null))
+    });
+    io_handler.add_method_with_meta("fail", |_params: Params, _meta: TestMeta| async {
+        Err(jsonrpc_core::Error::internal_error())
+    });
+
+    let response = block_on(io_handler.handle_request(
+        r#"{"jsonrpc":"2.0","method":"fail","params":[],"id":1}"#,
+        TestMeta::default(),
+    ))
+    .unwrap();
+    let response: Value = serde_json::from_str(response.as_str()).unwrap();
+    assert!(response["error"]["data"]["trace_id"].is_string());
+
+    let response = block_on(io_handler.handle_request(
+        r#"[{"jsonrpc":"2.0","method":"batch_id","params":[],"id":1},{"jsonrpc":"2.0","method":"batch_id","params":[],"id":2},{"jsonrpc":"2.0","method":"fail","params":[],"id":3}]"#,
+        TestMeta::default(),
+    ))
+    .unwrap();
+    let response: Vec<Value> = serde_json::from_str(response.as_str()).unwrap();
+    assert_eq!(response.len(), 3);
+    let batch_id = response[0]["result"]
+        .as_str()
+        .expect("batch id should exist");
+    assert_eq!(response[1]["result"].as_str(), Some(batch_id));
+    assert!(response[2]["error"]["data"]["trace_id"].is_string());
+}
+
+#[stest::test]
+fn test_rpc_trace_filter() {
+    set_rpc_trace(true, Some("chain.".to_string()));
+    assert!(is_rpc_traced("chain.info"));
+    assert!(!is_rpc_traced("node.info"));
+    set_rpc_trace(true, None);
+    assert!(is_rpc_traced("node.info"));
+    set_rpc_trace(false, None);
+    assert!(!is_rpc_traced("chain.info"));
+    assert_eq!(rpc_trace_filter(), None);
+}
@@ -0,0 +1,68 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2
+
+use jsonrpc_core::Metadata;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static TRACE_ID_SEQ: AtomicU64 = AtomicU64::new(1);
+
+/// The methods whose call logs are bumped from debug to info, None if the trace is disabled.
+static RPC_TRACE_FILTER: Lazy<RwLock<Option<RpcTraceFilter>>> = Lazy::new(|| RwLock::new(None));
+
+/// Generate a short id for a rpc call or a batch request, it is unique in the node process.
+pub fn next_trace_id() -> String {
+    format!("{:x}", TRACE_ID_SEQ.fetch_add(1, Ordering::Relaxed))
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RpcTraceFilter {
+    /// Only trace the methods which start with the filter, such as `chain.` or `chain.get_block_by_hash`,
+    /// trace all methods if None.
+    pub method_filter: Option<String>,
+}
+
+impl RpcTraceFilter {
+    pub fn matches(&self, method: &str) -> bool {
+        self.method_filter
+            .as_deref()
+            .map(|filter| method.starts_with(filter))
+            .unwrap_or(true)
+    }
+}
+
+/// Enable or disable the rpc trace at runtime, the matched calls are logged at info level.
+pub fn set_rpc_trace(enable: bool, method_filter: Option<String>) {
+    *RPC_TRACE_FILTER.write() = if enable {
+        Some(RpcTraceFilter { method_filter })
+    } else {
+        None
+    };
+}
+
+pub fn rpc_trace_filter() -> Option<RpcTraceFilter> {
+    RPC_TRACE_FILTER.read().clone()
+}
+
+pub fn is_rpc_traced(method: &str) -> bool {
+    RPC_TRACE_FILTER
+        .read()
+        .as_ref()
+        .map(|filter| filter.matches(method))
+        .unwrap_or(false)
+}
+
+/// The rpc metadata which carries the batch id, so the calls of a batch request share it in the logs.
+pub trait TraceMetadata: Metadata {
+    fn batch_id(&self) -> Option<&str>;
+    fn set_batch_id(&mut self, batch_id: String);
+}
+
+impl TraceMetadata for () {
+    fn batch_id(&self) -> Option<&str> {
+        None
+    }
+
+    fn set_batch_id(&mut self, _batch_id: String) {}
+}
@@ -0,0 +1,75 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2
+
+use jsonrpc_core::futures::future::Either;
+use jsonrpc_core::futures::Future;
+use jsonrpc_core::middleware::NoopCallFuture;
+use jsonrpc_core::{Call, Failure, FutureResponse, Id, Middleware, Output};
+use parking_lot::RwLock;
+use starcoin_config::ApiFilterConfiguration;
+use starcoin_rpc_api::errors::RpcErrorCode;
+use starcoin_rpc_api::metadata::Metadata;
+use std::sync::Arc;
+
+/// Reject the calls of the methods disabled by the `rpc.api_filter` config.
+/// The filtered clones share the same filter, so the filter can be updated at runtime.
+#[derive(Clone, Debug)]
+pub struct ApiFilterMiddleware {
+    /// None if the io handler is not filtered, such as the ipc one.
+    filter: Option<Arc<RwLock<ApiFilterConfiguration>>>,
+}
+
+impl ApiFilterMiddleware {
+    pub fn new(filter: ApiFilterConfiguration) -> Self {
+        Self {
+            filter: Some(Arc::new(RwLock::new(filter))),
+        }
+    }
+
+    /// The middleware which enables all the methods.
+    pub fn unfiltered() -> Self {
+        Self { filter: None }
+    }
+
+    pub fn update_filter(&self, filter: ApiFilterConfiguration) {
+        if let Some(inner) = &self.filter {
+            *inner.write() = filter;
+        }
+    }
+
+    pub fn is_enabled(&self, method: &str) -> bool {
+        self.filter
+            .as_ref()
+            .map(|filter| filter.read().is_enabled(method))
+            .unwrap_or(true)
+    }
+}
+
+impl Middleware<Metadata> for ApiFilterMiddleware {
+    type Future = FutureResponse;
+    type CallFuture = NoopCallFuture;
+
+    fn on_call<F, X>(&self, call: Call, meta: Metadata, next: F) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, Metadata) -> X + Send + Sync,
+        X: Future<Output = Option<Output>> + Send + 'static,
+    {
+        let method = match &call {
+            Call::MethodCall(m) => Some((m.method.clone(), m.jsonrpc, m.id.clone())),
+            Call::Notification(n) => Some((n.method.clone(), n.jsonrpc, Id::Null)),
+            Call::Invalid { .. } => None,
+        };
+        match method {
+            Some((m, json_version, id)) if !self.is_enabled(m.as_str()) => {
+                let output = Output::Failure(Failure {
+                    jsonrpc: json_version,
+                    error: RpcErrorCode::MethodDisabled
+                        .error(format!("method {} is disabled by node operator", m)),
+                    id,
+                });
+                Either::Left(Box::pin(futures::future::ready(Some(output))))
+            }
+            _ => Either::Right(next(call, meta)),
+        }
+    }
+}
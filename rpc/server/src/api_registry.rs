@@ -1,53 +1,85 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
+use crate::api_filter_middleware::ApiFilterMiddleware;
 use crate::rate_limit_middleware::JsonApiRateLimitMiddleware;
-use jsonrpc_core::{MetaIoHandler, RemoteProcedure};
-use starcoin_config::{Api, ApiQuotaConfiguration};
+use jsonrpc_core::{MetaIoHandler, Params, RemoteProcedure, Value};
+use starcoin_config::{Api, ApiFilterConfiguration, ApiQuotaConfiguration};
 use starcoin_rpc_api::metadata::Metadata;
 use starcoin_rpc_middleware::MetricMiddleware;
 use std::collections::HashMap;
 
-type Middlewares = (MetricMiddleware, JsonApiRateLimitMiddleware);
+type Middlewares = (
+    MetricMiddleware,
+    ApiFilterMiddleware,
+    JsonApiRateLimitMiddleware,
+);
+
+/// The method to list the enabled methods of the transport, it is always enabled.
+const LIST_ENABLED_APIS_METHOD: &str = "node.list_enabled_apis";
 
 pub struct ApiRegistry {
     apis: HashMap<Api, MetaIoHandler<Metadata, Middlewares>>,
-    quotas: ApiQuotaConfiguration,
+    /// Shared by all the io handlers, so the quotas can be updated at runtime.
+    rate_limit_middleware: JsonApiRateLimitMiddleware,
+    /// Shared by the filtered io handlers, so the filter can be updated at runtime.
+    api_filter_middleware: ApiFilterMiddleware,
 }
 
 impl ApiRegistry {
-    pub fn new(api_quotas: ApiQuotaConfiguration) -> ApiRegistry {
+    pub fn new(
+        api_quotas: ApiQuotaConfiguration,
+        api_filter: ApiFilterConfiguration,
+    ) -> ApiRegistry {
         Self {
             apis: Default::default(),
-            quotas: api_quotas,
+            rate_limit_middleware: JsonApiRateLimitMiddleware::from_config(api_quotas),
+            api_filter_middleware: ApiFilterMiddleware::new(api_filter),
         }
     }
 
+    pub fn update_api_quotas(&self, api_quotas: ApiQuotaConfiguration) {
+        self.rate_limit_middleware.update_quotas(api_quotas);
+    }
+
+    pub fn update_api_filter(&self, api_filter: ApiFilterConfiguration) {
+        self.api_filter_middleware.update_filter(api_filter);
+    }
+
     pub fn register<F>(&mut self, api_type: Api, apis: F)
     where
         F: IntoIterator<Item = (String, RemoteProcedure<Metadata>)>,
     {
-        let rate_limit_middleware = JsonApiRateLimitMiddleware::from_config(self.quotas.clone());
+        let rate_limit_middleware = self.rate_limit_middleware.clone();
         let io_handler = self.apis.entry(api_type).or_insert_with(|| {
             MetaIoHandler::<Metadata, Middlewares>::with_middleware((
                 MetricMiddleware,
+                ApiFilterMiddleware::unfiltered(),
                 rate_limit_middleware,
             ))
         });
         io_handler.extend_with(apis);
     }
 
+    /// Merge the apis into one io handler, the `rpc.api_filter` applies to it if `filtered` is true.
     pub fn get_apis(
         &self,
         api_types: impl IntoIterator<Item = Api>,
+        filtered: bool,
     ) -> MetaIoHandler<Metadata, Middlewares> {
-        let rate_limit_middleware = JsonApiRateLimitMiddleware::from_config(self.quotas.clone());
-        api_types
+        let rate_limit_middleware = self.rate_limit_middleware.clone();
+        let api_filter_middleware = if filtered {
+            self.api_filter_middleware.clone()
+        } else {
+            ApiFilterMiddleware::unfiltered()
+        };
+        let mut io_handler = api_types
             .into_iter()
             .map(|api_type| self.apis.get(&api_type))
             .fold(
                 MetaIoHandler::<Metadata, Middlewares>::with_middleware((
                     MetricMiddleware,
+                    api_filter_middleware.clone(),
                     rate_limit_middleware,
                 )),
                 |mut init, apis| {
@@ -56,6 +88,25 @@ impl ApiRegistry {
                     }
                     init
                 },
-            )
+            );
+        let mut methods = io_handler
+            .iter()
+            .map(|(method, _)| method.clone())
+            .collect::<Vec<_>>();
+        methods.push(LIST_ENABLED_APIS_METHOD.to_string());
+        methods.sort();
+        io_handler.add_method(LIST_ENABLED_APIS_METHOD, move |_params: Params| {
+            let enabled = methods
+                .iter()
+                .filter(|method| {
+                    method.as_str() == LIST_ENABLED_APIS_METHOD
+                        || api_filter_middleware.is_enabled(method.as_str())
+                })
+                .cloned()
+                .map(Value::String)
+                .collect::<Vec<_>>();
+            futures::future::ready(Ok(Value::Array(enabled)))
+        });
+        io_handler
     }
 }
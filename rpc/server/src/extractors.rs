@@ -4,6 +4,7 @@
 use jsonrpc_http_server::hyper;
 use jsonrpc_pubsub::Session;
 use starcoin_rpc_api::metadata::Metadata;
+use std::collections::HashSet;
 use std::net::IpAddr;
 use std::sync::Arc;
 
@@ -11,6 +12,12 @@ use std::sync::Arc;
 #[derive(Default)]
 pub struct RpcExtractor {
     pub http_ip_headers: Vec<String>,
+    /// The user is identified by the api key if the header is present and the key is issued,
+    /// otherwise by the client ip.
+    pub http_api_key_header: Option<String>,
+    /// The issued api keys, the key in the header is client supplied, so only the issued ones
+    /// are trusted, otherwise the client can get a new quota by changing the key.
+    pub http_api_keys: HashSet<String>,
 }
 
 impl jsonrpc_http_server::MetaExtractor<Metadata> for RpcExtractor {
@@ -27,9 +34,19 @@ impl jsonrpc_http_server::MetaExtractor<Metadata> for RpcExtractor {
             }
         }
 
+        let api_key = self.http_api_key_header.as_ref().and_then(|header| {
+            _req.headers()
+                .get(header)
+                .and_then(|v| v.to_str().ok())
+                .map(|key| key.trim())
+                .filter(|key| self.http_api_keys.contains(*key))
+                .map(|key| format!("key:{}", key))
+        });
+
         Metadata {
             session: None,
-            user: client_ip.map(|ip| ip.to_string()),
+            user: api_key.or_else(|| client_ip.map(|ip| ip.to_string())),
+            batch_id: None,
         }
     }
 }
@@ -38,6 +55,7 @@ impl jsonrpc_ipc_server::MetaExtractor<Metadata> for RpcExtractor {
         Metadata {
             session: Some(Arc::new(Session::new(req.sender.clone()))),
             user: None,
+            batch_id: None,
         }
     }
 }
@@ -46,6 +64,7 @@ impl jsonrpc_tcp_server::MetaExtractor<Metadata> for RpcExtractor {
         Metadata {
             session: Some(Arc::new(Session::new(context.sender.clone()))),
             user: Some(context.peer_addr.ip().to_string()),
+            batch_id: None,
         }
     }
 }
@@ -57,6 +76,45 @@ impl jsonrpc_ws_server::MetaExtractor<Metadata> for WsExtractor {
         Metadata {
             session,
             user: None,
+            batch_id: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpc_http_server::MetaExtractor;
+
+    fn request(headers: Vec<(&str, &str)>) -> hyper::Request<hyper::Body> {
+        let mut builder = hyper::Request::builder();
+        for (name, value) in headers {
+            builder = builder.header(name, value);
         }
+        builder.body(hyper::Body::empty()).unwrap()
+    }
+
+    #[test]
+    fn test_http_user() {
+        let extractor = RpcExtractor {
+            http_ip_headers: vec!["X-Real-IP".to_string()],
+            http_api_key_header: Some("X-Api-Key".to_string()),
+            http_api_keys: vec!["issued".to_string()].into_iter().collect(),
+        };
+        let user = |headers| extractor.read_metadata(&request(headers)).user;
+        assert_eq!(
+            user(vec![("X-Real-IP", "1.1.1.1"), ("X-Api-Key", "issued")]),
+            Some("key:issued".to_string())
+        );
+        // the unknown key is ignored, the quota is counted by ip.
+        assert_eq!(
+            user(vec![("X-Real-IP", "1.1.1.1"), ("X-Api-Key", "random")]),
+            Some("1.1.1.1".to_string())
+        );
+        assert_eq!(user(vec![("X-Api-Key", "random")]), None);
+        assert_eq!(
+            user(vec![("X-Real-IP", "1.1.1.1")]),
+            Some("1.1.1.1".to_string())
+        );
     }
 }
@@ -0,0 +1,251 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{format_err, Result};
+use jsonrpc_http_server::hyper::{header, Body, Method, Request, Response, StatusCode};
+use jsonrpc_http_server::{RequestMiddleware, RequestMiddlewareAction};
+use network_api::PeerProvider;
+use parking_lot::RwLock;
+use starcoin_config::{HealthConfiguration, NodeConfig, TimeService};
+use starcoin_logger::prelude::*;
+use starcoin_network::NetworkServiceRef;
+use starcoin_rpc_api::node::{HealthView, WatchdogReport};
+use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
+use starcoin_storage::{BlockStore, Storage};
+use starcoin_types::block::BlockNumber;
+use starcoin_types::system_events::{NewHeadBlock, SyncStatusChangeEvent};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The http path of the health check, for the load balancers which can not call the json rpc.
+pub const HEALTH_PATH: &str = "/health";
+
+/// How often the peer count of the health state is refreshed.
+const HEALTH_PEERS_INTERVAL: Duration = Duration::from_secs(5);
+
+struct HealthState {
+    head_block_number: BlockNumber,
+    /// The head block timestamp in milliseconds.
+    head_block_timestamp: u64,
+    peers: u64,
+    syncing: bool,
+}
+
+/// The cached health state of the node, it is updated by the `HealthService` in background,
+/// so reading it never waits for the services which are busy with the heavy requests.
+#[derive(Clone)]
+pub struct HealthMonitor {
+    config: HealthConfiguration,
+    time_service: Arc<dyn TimeService>,
+    state: Arc<RwLock<HealthState>>,
+}
+
+impl HealthMonitor {
+    pub fn new(
+        config: HealthConfiguration,
+        time_service: Arc<dyn TimeService>,
+        head_block_number: BlockNumber,
+        head_block_timestamp: u64,
+    ) -> Self {
+        Self {
+            config,
+            time_service,
+            state: Arc::new(RwLock::new(HealthState {
+                head_block_number,
+                head_block_timestamp,
+                peers: 0,
+                syncing: false,
+            })),
+        }
+    }
+
+    pub fn update_head(&self, head_block_number: BlockNumber, head_block_timestamp: u64) {
+        let mut state = self.state.write();
+        state.head_block_number = head_block_number;
+        state.head_block_timestamp = head_block_timestamp;
+    }
+
+    pub fn update_peers(&self, peers: u64) {
+        self.state.write().peers = peers;
+    }
+
+    pub fn update_syncing(&self, syncing: bool) {
+        self.state.write().syncing = syncing;
+    }
+
+    pub fn health(&self) -> HealthView {
+        let now = self.time_service.now_millis();
+        let state = self.state.read();
+        HealthView::evaluate(
+            &self.config,
+            state.syncing,
+            state.head_block_number,
+            now.saturating_sub(state.head_block_timestamp) / 1000,
+            state.peers,
+            starcoin_rpc_middleware::pending_rpc_calls(),
+        )
+    }
+}
+
+/// The last watchdog report, it is updated by the watchdog service of the node and read by the rpc.
+#[derive(Clone, Default)]
+pub struct WatchdogMonitor {
+    report: Arc<RwLock<Option<WatchdogReport>>>,
+}
+
+impl WatchdogMonitor {
+    pub fn update(&self, report: WatchdogReport) {
+        *self.report.write() = Some(report);
+    }
+
+    pub fn report(&self) -> Option<WatchdogReport> {
+        self.report.read().clone()
+    }
+}
+
+/// Respond the http `GET /health` with the `HealthView`, the status is 200 if the node is ready, otherwise 503.
+pub struct HealthRequestMiddleware {
+    monitor: HealthMonitor,
+}
+
+impl HealthRequestMiddleware {
+    pub fn new(monitor: HealthMonitor) -> Self {
+        Self { monitor }
+    }
+}
+
+impl RequestMiddleware for HealthRequestMiddleware {
+    fn on_request(&self, request: Request<Body>) -> RequestMiddlewareAction {
+        if request.method() != Method::GET || request.uri().path() != HEALTH_PATH {
+            return request.into();
+        }
+        let health = self.monitor.health();
+        let status = if health.is_ready() {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        };
+        let body = serde_json::to_string(&health).unwrap_or_default();
+        Response::builder()
+            .status(status)
+            .header(header::CONTENT_TYPE, "application/json; charset=utf-8")
+            .body(Body::from(body))
+            .expect("Build health response should success.")
+            .into()
+    }
+}
+
+#[derive(Clone, Debug)]
+struct RefreshPeersTick;
+
+/// Keep the `HealthMonitor` up to date by the chain and sync events, and refresh the peer count periodically.
+pub struct HealthService {
+    monitor: HealthMonitor,
+    network: NetworkServiceRef,
+}
+
+impl ActorService for HealthService {
+    fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.subscribe::<NewHeadBlock>();
+        ctx.subscribe::<SyncStatusChangeEvent>();
+        ctx.notify(RefreshPeersTick);
+        ctx.run_interval(HEALTH_PEERS_INTERVAL, |ctx| ctx.notify(RefreshPeersTick));
+        Ok(())
+    }
+
+    fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.unsubscribe::<NewHeadBlock>();
+        ctx.unsubscribe::<SyncStatusChangeEvent>();
+        Ok(())
+    }
+}
+
+impl EventHandler<Self, NewHeadBlock> for HealthService {
+    fn handle_event(&mut self, msg: NewHeadBlock, _ctx: &mut ServiceContext<Self>) {
+        let header = msg.0.block().header();
+        self.monitor
+            .update_head(header.number(), header.timestamp());
+    }
+}
+
+impl EventHandler<Self, SyncStatusChangeEvent> for HealthService {
+    fn handle_event(&mut self, msg: SyncStatusChangeEvent, _ctx: &mut ServiceContext<Self>) {
+        self.monitor.update_syncing(msg.0.is_syncing());
+    }
+}
+
+impl EventHandler<Self, RefreshPeersTick> for HealthService {
+    fn handle_event(&mut self, _msg: RefreshPeersTick, ctx: &mut ServiceContext<Self>) {
+        let network = self.network.clone();
+        let monitor = self.monitor.clone();
+        ctx.spawn(async move {
+            match network.peer_set().await {
+                Ok(peers) => monitor.update_peers(peers.len() as u64),
+                Err(e) => warn!("Get peers for health check error: {:?}", e),
+            }
+        });
+    }
+}
+
+pub struct HealthServiceFactory;
+
+impl ServiceFactory<HealthService> for HealthServiceFactory {
+    fn create(ctx: &mut ServiceContext<HealthService>) -> Result<HealthService> {
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
+        let storage = ctx.get_shared::<Arc<Storage>>()?;
+        let network = ctx.get_shared::<NetworkServiceRef>()?;
+        let startup_info = storage
+            .get_startup_info()?
+            .ok_or_else(|| format_err!("Startup info should exist."))?;
+        let head = storage
+            .get_block_header_by_hash(startup_info.main)?
+            .ok_or_else(|| format_err!("Can not find head block header {}", startup_info.main))?;
+        let monitor = HealthMonitor::new(
+            config.rpc.health.clone(),
+            config.net().time_service(),
+            head.number(),
+            head.timestamp(),
+        );
+        ctx.put_shared(monitor.clone())?;
+        Ok(HealthService { monitor, network })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_config::MockTimeService;
+    use starcoin_rpc_api::node::HealthStatus;
+
+    #[test]
+    fn test_health_monitor() {
+        let time_service = MockTimeService::new_with_value(100_000);
+        let config = HealthConfiguration {
+            max_head_age: Some(60),
+            min_peers: Some(1),
+        };
+        let monitor = HealthMonitor::new(config, Arc::new(time_service.clone()), 10, 90_000);
+        // no peer is connected yet.
+        assert_eq!(monitor.health().status, HealthStatus::Degraded);
+
+        monitor.update_peers(3);
+        let health = monitor.health();
+        assert_eq!(health.status, HealthStatus::Ready);
+        assert_eq!(health.head_block_number.0, 10);
+        assert_eq!(health.head_block_age_secs, 10);
+
+        // the head is stale.
+        time_service.increment_by(60_000);
+        assert_eq!(monitor.health().status, HealthStatus::Degraded);
+
+        monitor.update_syncing(true);
+        assert_eq!(monitor.health().status, HealthStatus::Syncing);
+
+        monitor.update_syncing(false);
+        monitor.update_head(11, 160_000);
+        assert_eq!(monitor.health().status, HealthStatus::Ready);
+
+        monitor.update_peers(0);
+        assert_eq!(monitor.health().status, HealthStatus::Degraded);
+    }
+}
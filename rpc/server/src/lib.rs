@@ -1,8 +1,11 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2
 
+mod api_filter_middleware;
 mod api_registry;
 mod extractors;
+pub mod health;
+pub mod metrics;
 pub mod module;
 mod rate_limit_middleware;
 pub mod service;
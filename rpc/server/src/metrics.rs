@@ -0,0 +1,14 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2
+
+use once_cell::sync::Lazy;
+use starcoin_metrics::{register_int_gauge, IntGauge, Opts};
+
+pub static PUBSUB_SUBSCRIPTIONS_GAUGE: Lazy<IntGauge> = Lazy::new(|| {
+    let opts = Opts::new(
+        "rpc_pubsub_subscriptions",
+        "Gauge of how many active pubsub subscriptions",
+    )
+    .namespace("starcoin");
+    register_int_gauge!(opts).unwrap()
+});
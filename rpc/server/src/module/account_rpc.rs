@@ -1,23 +1,60 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::module::chain_rpc::to_txn_info_views;
 use crate::module::helpers::TransactionRequestFiller;
-use crate::module::map_err;
+use crate::module::{account_not_found_onchain, map_err, state_not_found_at, to_invalid_param_err};
+use anyhow::format_err;
+use bcs_ext::BCSCodec;
 use futures::future::TryFutureExt;
 use futures::FutureExt;
-use starcoin_account_api::{AccountAsyncService, AccountInfo};
+use futures_timer::Delay;
+use parking_lot::RwLock;
+use starcoin_account_api::error::AccountError;
+use starcoin_account_api::{
+    AccountAsyncService, AccountInfo, AccountPublicKey, DerivedAccount, SignerEndpoint,
+    DEFAULT_MNEMONIC_WORD_COUNT,
+};
+use starcoin_account_service::signer_of;
 use starcoin_chain_service::ChainAsyncService;
 use starcoin_config::NodeConfig;
-use starcoin_rpc_api::types::{StrView, TransactionRequest};
+use starcoin_crypto::ed25519::Ed25519Signature;
+use starcoin_crypto::multi_ed25519::MultiEd25519Signature;
+use starcoin_crypto::HashValue;
+use starcoin_logger::prelude::*;
+use starcoin_rpc_api::types::{
+    AccountAddressView, BalanceView, RawUserTransactionView, ReceiptIdentifierView,
+    RotateAuthKeyView, StrView, TokenCodeView, TokenInfoView, TransactionInfoView,
+    TransactionRequest, TransactionVMStatus, TxnHistoryView,
+};
 use starcoin_rpc_api::{account::AccountApi, FutureResult};
 use starcoin_state_api::ChainStateAsyncService;
+use starcoin_transaction_builder::encode_rotate_authentication_key_script_function;
 use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_types::access_path::AccessPath;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::token_code::TokenCode;
+use starcoin_types::account_config::{
+    AccountResource, BalanceResource, CORE_CODE_ADDRESS, STC_TOKEN_CODE,
+};
+use starcoin_types::block::BlockNumber;
 use starcoin_types::sign_message::SigningMessage;
+use starcoin_types::transaction::authenticator::AuthenticationKey;
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
+use starcoin_vm_types::language_storage::StructTag;
+use starcoin_vm_types::move_resource::MoveResource;
+use starcoin_vm_types::token::token_info::TokenInfoResource;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::str::FromStr;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+const DEFAULT_ROTATE_KEY_TIMEOUT_SECS: u64 = 120;
+const WAIT_TXN_INTERVAL: Duration = Duration::from_secs(1);
+/// Max block numbers of one `account.balances_at` call.
+const MAX_BALANCE_POINTS: usize = 100;
+const DEFAULT_TXN_HISTORY_LIMIT: u64 = 20;
 
 #[derive(Clone)]
 pub struct AccountRpcImpl<Account, Pool, State, Chain>
@@ -32,6 +69,8 @@ where
     chain_state: State,
     chain: Chain,
     node_config: Arc<NodeConfig>,
+    /// The token info is immutable after the token is registered, so cache it by token code.
+    token_info_cache: Arc<RwLock<HashMap<TokenCode, TokenInfoView>>>,
 }
 
 impl<Account, Pool, State, Chain> AccountRpcImpl<Account, Pool, State, Chain>
@@ -54,8 +93,114 @@ where
             chain_state,
             chain,
             node_config,
+            token_info_cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Submit the signed rotation txn and wait until it is included, return None if timeout.
+    async fn submit_and_wait(
+        &self,
+        raw_txn: RawUserTransaction,
+        timeout: Duration,
+    ) -> anyhow::Result<Option<TransactionInfoView>> {
+        let signed_txn = self
+            .account
+            .sign_txn(raw_txn.clone(), raw_txn.sender())
+            .await?;
+        let txn_hash = signed_txn.id();
+        self.pool
+            .add_txns(vec![signed_txn])
+            .pop()
+            .expect("txpool should return result")?;
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(txn_info) = self.chain.get_transaction_info(txn_hash).await? {
+                let block = self
+                    .chain
+                    .get_block_by_hash(txn_info.block_id())
+                    .await?
+                    .ok_or_else(|| {
+                        format_err!(
+                            "cannot find the block {} which include txn {}",
+                            txn_info.block_id(),
+                            txn_hash
+                        )
+                    })?;
+                let txn_infos = self.chain.get_block_txn_infos(block.id()).await?;
+                return Ok(to_txn_info_views(&self.chain, &block, txn_infos)
+                    .await?
+                    .into_iter()
+                    .find(|view| view.transaction_hash == txn_hash));
+            }
+            if Instant::now() >= deadline {
+                return Ok(None);
+            }
+            Delay::new(WAIT_TXN_INTERVAL).await;
+        }
+    }
+
+    /// Finish the rotation left by a previous timeout if the on-chain auth key is already the pending key.
+    async fn ensure_auth_key_synced(&self, address: AccountAddress) -> anyhow::Result<()> {
+        let account = self
+            .account
+            .get_account(address)
+            .await?
+            .ok_or(AccountError::AccountNotExist(address))?;
+        let on_chain_auth_key = match self
+            .chain_state
+            .clone()
+            .get_resource::<AccountResource>(address)
+            .await?
+        {
+            Some(resource) => AuthenticationKey::try_from(resource.authentication_key())?,
+            None => return Err(account_not_found_onchain(address)),
+        };
+        if on_chain_auth_key != account.public_key.authentication_key() {
+            self.account
+                .commit_rotate_key(address, on_chain_auth_key)
+                .await?;
+            info!(
+                "Switch account {} to the pending key of auth key {}",
+                address, on_chain_auth_key
+            );
         }
+        Ok(())
     }
+
+    /// Get the balance at the state root of the main chain block `number`.
+    async fn balance_at(
+        &self,
+        address: AccountAddress,
+        token_code: TokenCode,
+        number: BlockNumber,
+    ) -> anyhow::Result<Option<u128>> {
+        let header = self
+            .chain
+            .main_block_header_by_number(number)
+            .await?
+            .ok_or_else(|| {
+                jsonrpc_core::Error::invalid_params(format!(
+                    "cannot find block {} on the main chain",
+                    number
+                ))
+            })?;
+        let access_path = AccessPath::resource_access_path(
+            address,
+            BalanceResource::struct_tag_for_token_code(token_code),
+        );
+        let state = self
+            .chain_state
+            .clone()
+            .get_with_proof_by_root(access_path, header.state_root())
+            .await
+            .map_err(|e| state_not_found_at(number, e))?;
+        state
+            .state
+            .map(|bytes| bcs_ext::from_bytes::<BalanceResource>(bytes.as_slice()))
+            .transpose()
+            .map(|balance| balance.map(|balance| balance.token()))
+    }
+
     fn txn_request_filler(&self) -> TransactionRequestFiller<Account, Pool, State, Chain> {
         TransactionRequestFiller {
             account: Some(self.account.clone()),
@@ -84,7 +229,8 @@ where
         Box::pin(fut.boxed())
     }
 
-    fn set_default_account(&self, addr: AccountAddress) -> FutureResult<Option<AccountInfo>> {
+    fn set_default_account(&self, addr: AccountAddressView) -> FutureResult<Option<AccountInfo>> {
+        let addr = addr.0;
         let service = self.account.clone();
         let fut = async move {
             let result = service.set_default_account(addr).await?;
@@ -114,7 +260,8 @@ where
         Box::pin(fut.boxed())
     }
 
-    fn get(&self, address: AccountAddress) -> FutureResult<Option<AccountInfo>> {
+    fn get(&self, address: AccountAddressView) -> FutureResult<Option<AccountInfo>> {
+        let address = address.0;
         let service = self.account.clone();
         let fut = async move {
             let result = service.get_account(address).await?;
@@ -125,9 +272,10 @@ where
     }
     fn sign(
         &self,
-        address: AccountAddress,
+        address: AccountAddressView,
         data: SigningMessage,
     ) -> FutureResult<StrView<Vec<u8>>> {
+        let address = address.0;
         let account_service = self.account.clone();
         let f = async move {
             let signature = account_service.sign_message(address, data).await?;
@@ -144,7 +292,20 @@ where
                 .fill_transaction(txn_request)
                 .await?;
             let sender = raw_txn.sender();
-            let signed_txn = me.account.sign_txn(raw_txn, sender).await?;
+            let has_external_signer = me.account.get_signer_endpoint(sender).await?.is_some();
+            let is_readonly = me
+                .account
+                .get_account(sender)
+                .await?
+                .map(|account| account.is_readonly)
+                .unwrap_or(false);
+            if is_readonly && !has_external_signer {
+                return Ok(format!("0x{}", hex::encode(bcs_ext::to_bytes(&raw_txn)?)));
+            }
+            let signed_txn = signer_of(me.account.clone(), sender)
+                .await?
+                .sign_txn(raw_txn, sender)
+                .await?;
             Ok(format!(
                 "0x{}",
                 hex::encode(bcs_ext::to_bytes(&signed_txn)?)
@@ -157,11 +318,15 @@ where
     fn sign_txn(
         &self,
         raw_txn: RawUserTransaction,
-        signer: AccountAddress,
+        signer: AccountAddressView,
     ) -> FutureResult<SignedUserTransaction> {
+        let signer = signer.0;
         let service = self.account.clone();
         let fut = async move {
-            let result = service.sign_txn(raw_txn, signer).await?;
+            let result = signer_of(service, signer)
+                .await?
+                .sign_txn(raw_txn, signer)
+                .await?;
             Ok(result)
         }
         .map_err(map_err);
@@ -170,10 +335,11 @@ where
 
     fn unlock(
         &self,
-        address: AccountAddress,
+        address: AccountAddressView,
         password: String,
         duration: Option<u32>,
     ) -> FutureResult<()> {
+        let address = address.0;
         let service = self.account.clone();
         let fut = async move {
             service
@@ -188,19 +354,27 @@ where
         Box::pin(fut.boxed())
     }
 
-    fn lock(&self, address: AccountAddress) -> FutureResult<()> {
+    fn lock(&self, address: AccountAddressView) -> FutureResult<()> {
+        let address = address.0;
         let service = self.account.clone();
         let fut = async move { service.lock_account(address).await }.map_err(map_err);
         Box::pin(fut.boxed())
     }
 
+    fn lock_all(&self) -> FutureResult<Vec<AccountAddress>> {
+        let service = self.account.clone();
+        let fut = async move { service.lock_all_accounts().await }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
     /// Import private key with address.
     fn import(
         &self,
-        address: AccountAddress,
+        address: AccountAddressView,
         private_key: Vec<u8>,
         password: String,
     ) -> FutureResult<AccountInfo> {
+        let address = address.0;
         let service = self.account.clone();
         let fut = async move {
             let result = service
@@ -212,8 +386,58 @@ where
         Box::pin(fut.boxed())
     }
 
+    fn import_readonly(
+        &self,
+        address: AccountAddressView,
+        public_key: StrView<Vec<u8>>,
+    ) -> FutureResult<AccountInfo> {
+        let address = address.0;
+        let public_key = public_key.0;
+        let service = self.account.clone();
+        let fut = async move {
+            let result = service.import_readonly_account(address, public_key).await?;
+            Ok(result)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn submit_signed(
+        &self,
+        raw_txn: StrView<Vec<u8>>,
+        signature: StrView<Vec<u8>>,
+        public_key: StrView<Vec<u8>>,
+    ) -> FutureResult<HashValue> {
+        let pool = self.pool.clone();
+        let fut = async move {
+            let raw_txn = RawUserTransaction::decode(raw_txn.0.as_slice())?;
+            let signed_txn = match AccountPublicKey::try_from(public_key.0.as_slice())? {
+                AccountPublicKey::Single(public_key) => SignedUserTransaction::ed25519(
+                    raw_txn,
+                    public_key,
+                    Ed25519Signature::try_from(signature.0.as_slice())?,
+                ),
+                AccountPublicKey::Multi(public_key) => SignedUserTransaction::multi_ed25519(
+                    raw_txn,
+                    public_key,
+                    MultiEd25519Signature::try_from(signature.0.as_slice())?,
+                ),
+            };
+            // reject the txn with invalid signature before submitting to txpool.
+            let _ = signed_txn.clone().check_signature()?;
+            let txn_hash = signed_txn.id();
+            pool.add_txns(vec![signed_txn])
+                .pop()
+                .expect("txpool should return result")?;
+            Ok(txn_hash)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
     /// Return the private key as bytes for `address`
-    fn export(&self, address: AccountAddress, password: String) -> FutureResult<Vec<u8>> {
+    fn export(&self, address: AccountAddressView, password: String) -> FutureResult<Vec<u8>> {
+        let address = address.0;
         let service = self.account.clone();
         let fut = async move {
             let result = service.export_account(address, password).await?;
@@ -223,27 +447,362 @@ where
         Box::pin(fut.boxed())
     }
 
+    fn import_keystore(&self, keystore: String, password: String) -> FutureResult<AccountInfo> {
+        let service = self.account.clone();
+        let fut = async move {
+            let result = service.import_keystore(keystore, password).await?;
+            Ok(result)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn export_keystore(
+        &self,
+        address: AccountAddressView,
+        password: String,
+    ) -> FutureResult<String> {
+        let address = address.0;
+        let service = self.account.clone();
+        let fut = async move {
+            let result = service.export_keystore(address, password).await?;
+            Ok(result)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
     fn change_account_password(
         &self,
-        address: AccountAddress,
+        address: AccountAddressView,
         new_password: String,
+        old_password: Option<String>,
     ) -> FutureResult<()> {
+        let address = address.0;
         let account_service = self.account.clone();
         let fut = async move {
             account_service
-                .change_account_password(address, new_password)
+                .change_account_password(address, old_password, new_password)
                 .await
         };
         Box::pin(fut.map_err(map_err).boxed())
     }
 
-    fn accepted_tokens(&self, address: AccountAddress) -> FutureResult<Vec<TokenCode>> {
+    fn remove(
+        &self,
+        address: AccountAddressView,
+        password: String,
+        force: Option<bool>,
+    ) -> FutureResult<AccountInfo> {
+        let address = address.0;
         let service = self.account.clone();
         let fut = async move {
-            let result = service.accepted_tokens(address).await?;
-            Ok(result)
+            service
+                .remove_account(address, password, force.unwrap_or(false))
+                .await
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn accepted_tokens(&self, address: ReceiptIdentifierView) -> FutureResult<Vec<TokenCodeView>> {
+        let address = address.address();
+        let chain_state = self.chain_state.clone();
+        let fut = async move {
+            let balances = get_balance_resources(chain_state, address).await?;
+            Ok(balances
+                .into_iter()
+                .map(|(token_code, _)| token_code.into())
+                .collect())
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn get_balances(
+        &self,
+        address: ReceiptIdentifierView,
+    ) -> FutureResult<BTreeMap<String, BalanceView>> {
+        let address = address.address();
+        let chain_state = self.chain_state.clone();
+        let token_info_cache = self.token_info_cache.clone();
+        let fut = async move {
+            let mut balances = BTreeMap::new();
+            for (token_code, amount) in get_balance_resources(chain_state.clone(), address).await? {
+                let cached = token_info_cache.read().get(&token_code).cloned();
+                let token_info = match cached {
+                    Some(token_info) => token_info,
+                    None => {
+                        let token_info_resource = chain_state
+                            .clone()
+                            .get(TokenInfoResource::resource_path_for(token_code.clone()))
+                            .await?
+                            .map(|bytes| TokenInfoResource::try_from_bytes(bytes.as_slice()))
+                            .transpose()?
+                            .ok_or_else(|| {
+                                format_err!("Can not find token info of {}", token_code)
+                            })?;
+                        let token_info =
+                            TokenInfoView::new(token_code.clone(), &token_info_resource);
+                        token_info_cache
+                            .write()
+                            .insert(token_code, token_info.clone());
+                        token_info
+                    }
+                };
+                balances.insert(
+                    token_info.token_code.clone(),
+                    BalanceView {
+                        amount: StrView(amount),
+                        token_info,
+                    },
+                );
+            }
+            Ok(balances)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn balance_at(
+        &self,
+        address: ReceiptIdentifierView,
+        token_code: Option<String>,
+        block_number: BlockNumber,
+    ) -> FutureResult<Option<StrView<u128>>> {
+        let address = address.address();
+        let me = self.clone();
+        let fut = async move {
+            let token_code = parse_token_code(token_code)?;
+            let balance = me.balance_at(address, token_code, block_number).await?;
+            Ok(balance.map(StrView))
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn balances_at(
+        &self,
+        address: ReceiptIdentifierView,
+        token_code: Option<String>,
+        block_numbers: Vec<BlockNumber>,
+    ) -> FutureResult<Vec<Option<StrView<u128>>>> {
+        let address = address.address();
+        let me = self.clone();
+        let fut = async move {
+            if block_numbers.len() > MAX_BALANCE_POINTS {
+                return Err(jsonrpc_core::Error::invalid_params(format!(
+                    "too many block numbers, max is {}",
+                    MAX_BALANCE_POINTS
+                ))
+                .into());
+            }
+            let token_code = parse_token_code(token_code)?;
+            let mut balances = Vec::with_capacity(block_numbers.len());
+            for number in block_numbers {
+                let balance = me.balance_at(address, token_code.clone(), number).await?;
+                balances.push(balance.map(StrView));
+            }
+            Ok(balances)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn rotate_authentication_key(
+        &self,
+        address: AccountAddressView,
+        private_key: Option<StrView<Vec<u8>>>,
+        dry_run: Option<bool>,
+        timeout_secs: Option<u64>,
+        replace: Option<bool>,
+    ) -> FutureResult<RotateAuthKeyView> {
+        let address = address.0;
+        let me = self.clone();
+        let fut = async move {
+            let dry_run = dry_run.unwrap_or(false);
+            me.ensure_auth_key_synced(address).await?;
+            let new_public_key = me
+                .account
+                .prepare_rotate_key(
+                    address,
+                    private_key.map(|k| k.0),
+                    dry_run,
+                    replace.unwrap_or(false),
+                )
+                .await?;
+            let new_auth_key = new_public_key.authentication_key();
+            let txn_request = TransactionRequest {
+                sender: Some(address.into()),
+                script: Some(encode_rotate_authentication_key_script_function(new_auth_key).into()),
+                ..Default::default()
+            };
+            let raw_txn = me.txn_request_filler().fill_transaction(txn_request).await;
+            let raw_txn = match raw_txn {
+                Ok(raw_txn) => raw_txn,
+                Err(e) => {
+                    if !dry_run {
+                        me.account.abort_rotate_key(address).await?;
+                    }
+                    return Err(e);
+                }
+            };
+            let mut view = RotateAuthKeyView {
+                address,
+                new_public_key,
+                new_auth_key,
+                raw_txn: RawUserTransactionView::try_from(raw_txn.clone())?,
+                txn_info: None,
+            };
+            if dry_run {
+                return Ok(view);
+            }
+            let timeout =
+                Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_ROTATE_KEY_TIMEOUT_SECS));
+            let txn_info = match me.submit_and_wait(raw_txn, timeout).await {
+                Ok(Some(txn_info)) => txn_info,
+                // the txn may be included later, so keep the new key pending,
+                // the next rotation switches to it if the on-chain auth key is changed.
+                Ok(None) => anyhow::bail!(
+                    "rotation txn of {} is not included in {:?}, the wallet still uses the old key",
+                    address,
+                    timeout
+                ),
+                Err(e) => {
+                    me.account.abort_rotate_key(address).await?;
+                    return Err(e);
+                }
+            };
+            if txn_info.status != TransactionVMStatus::Executed {
+                me.account.abort_rotate_key(address).await?;
+                anyhow::bail!(
+                    "rotation txn {} failed with status {:?}, the wallet keeps the old key",
+                    txn_info.transaction_hash,
+                    txn_info.status
+                );
+            }
+            me.account.commit_rotate_key(address, new_auth_key).await?;
+            view.txn_info = Some(txn_info);
+            Ok(view)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn bind_external_signer(
+        &self,
+        address: AccountAddressView,
+        endpoint: String,
+    ) -> FutureResult<()> {
+        let address = address.0;
+        let service = self.account.clone();
+        let fut = async move {
+            let endpoint = SignerEndpoint::from_str(endpoint.as_str())?;
+            service.bind_external_signer(address, endpoint).await
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn generate_mnemonic(&self, word_count: Option<u32>) -> FutureResult<String> {
+        let service = self.account.clone();
+        let fut = async move {
+            service
+                .generate_mnemonic(word_count.unwrap_or(DEFAULT_MNEMONIC_WORD_COUNT))
+                .await
         }
         .map_err(map_err);
         Box::pin(fut.boxed())
     }
+
+    fn import_from_mnemonic(
+        &self,
+        mnemonic: String,
+        derivation_index: u32,
+        password: String,
+    ) -> FutureResult<AccountInfo> {
+        let service = self.account.clone();
+        let fut = async move {
+            service
+                .import_from_mnemonic(mnemonic, derivation_index, password)
+                .await
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn derive_address(&self, mnemonic: String, index: u32) -> FutureResult<DerivedAccount> {
+        let service = self.account.clone();
+        let fut = async move { service.derive_account(mnemonic, index).await }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn txn_history(
+        &self,
+        address: AccountAddressView,
+        start: Option<u64>,
+        limit: Option<u64>,
+    ) -> FutureResult<Vec<TxnHistoryView>> {
+        let address = address.0;
+        let service = self.account.clone();
+        let fut = async move {
+            let history = service
+                .txn_history(
+                    address,
+                    start.unwrap_or_default(),
+                    limit.unwrap_or(DEFAULT_TXN_HISTORY_LIMIT),
+                )
+                .await?;
+            history
+                .into_iter()
+                .map(TxnHistoryView::try_from)
+                .collect::<anyhow::Result<Vec<_>>>()
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+}
+
+/// The token code is STC if absent.
+fn parse_token_code(token_code: Option<String>) -> anyhow::Result<TokenCode> {
+    match token_code {
+        Some(token_code) => {
+            TokenCode::from_str(token_code.as_str()).map_err(|e| to_invalid_param_err(e).into())
+        }
+        None => Ok(STC_TOKEN_CODE.clone()),
+    }
+}
+
+/// Get the token codes and amounts of all the `Balance<T>` resources of `address` at the latest state,
+/// an account can only receive the token `T` after it accepts the token and has `Balance<T>`.
+async fn get_balance_resources<State>(
+    chain_state: State,
+    address: AccountAddress,
+) -> anyhow::Result<Vec<(TokenCode, u128)>>
+where
+    State: ChainStateAsyncService,
+{
+    let state_root = chain_state.clone().state_root().await?;
+    let state = chain_state
+        .get_account_state_set(address, Some(state_root))
+        .await?;
+    let resource_set = match state.as_ref().and_then(|state| state.resource_set()) {
+        Some(resource_set) => resource_set,
+        None => return Ok(vec![]),
+    };
+    let mut balances = vec![];
+    for (k, v) in resource_set.iter() {
+        let struct_tag = StructTag::decode(k.as_slice())?;
+        if struct_tag.address != CORE_CODE_ADDRESS
+            || struct_tag.module.as_str() != BalanceResource::MODULE_NAME
+            || struct_tag.name.as_str() != BalanceResource::STRUCT_NAME
+            || struct_tag.type_params.len() != 1
+        {
+            continue;
+        }
+        let token_code = TokenCode::try_from(struct_tag.type_params[0].clone())?;
+        let amount = bcs_ext::from_bytes::<BalanceResource>(v.as_slice())?.token();
+        balances.push((token_code, amount));
+    }
+    Ok(balances)
 }
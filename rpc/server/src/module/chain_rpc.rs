@@ -2,85 +2,214 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::module::map_err;
-use futures::future::{FutureExt, TryFutureExt};
+use futures::future::{self, Either, FutureExt, TryFutureExt};
+use futures::Future;
+use futures_timer::Delay;
+use network_api::PeerProvider;
+use parking_lot::Mutex;
 use starcoin_chain_service::ChainAsyncService;
 use starcoin_config::NodeConfig;
 use starcoin_crypto::HashValue;
+use starcoin_dev::playground::PlaygroudService;
+use starcoin_logger::prelude::*;
+use starcoin_network::NetworkServiceRef;
 use starcoin_rpc_api::chain::ChainApi;
+use starcoin_rpc_api::errors::RpcErrorCode;
 use starcoin_rpc_api::types::pubsub::EventFilter;
 use starcoin_rpc_api::types::{
-    BlockHeaderView, BlockSummaryView, BlockView, ChainId, ChainInfoView, EpochUncleSummaryView,
-    TransactionEventView, TransactionInfoView, TransactionView,
+    AccountAddressView, BlockHeaderView, BlockHeadersView, BlockRewardView, BlockSummaryView,
+    BlockView, BranchView, ChainId, ChainStatusView, EncodedView, EpochInfoView,
+    EpochUncleSummaryView, EventCursorPageView, EventHandleView, GetBlockOption, OutputEncoding,
+    StrView, StructTagView, TransactionEventView, TransactionInfoView, TransactionOutputView,
+    TransactionProofView, TransactionView, TxnPositionView,
 };
 use starcoin_rpc_api::FutureResult;
-use starcoin_types::block::{BlockInfo, BlockNumber};
-use starcoin_types::filter::Filter;
+use starcoin_state_api::StateView;
+use starcoin_statedb::ChainStateDB;
+use starcoin_storage::{BlockStore, EventCursorStore, Storage};
+use starcoin_sync_api::SyncAsyncService;
+use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_types::access_path::AccessPath;
+use starcoin_types::block::{Block, BlockInfo, BlockNumber, BlockSummary};
+use starcoin_types::block_bundle::{BlockBundle, BlockBundleState};
+use starcoin_types::contract_event::ContractEventInfo;
+use starcoin_types::event_cursor::{EventCursor, EventCursorId};
+use starcoin_types::filter::{EventPosition, Filter};
 use starcoin_types::startup_info::ChainInfo;
-use starcoin_types::transaction::TransactionInfo;
-use starcoin_vm_types::on_chain_resource::{EpochInfo, GlobalTimeOnChain};
+use starcoin_types::transaction::{
+    BlockTransactionInfo, Transaction, TransactionInfo, TransactionPosition,
+};
+use starcoin_vm_types::on_chain_resource::GlobalTimeOnChain;
 use std::convert::TryInto;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// The max headers returned by one `chain.get_headers`, `chain.get_headers_v2` or `chain.get_headers_by_number` call.
+const MAX_HEADERS_BATCH_SIZE: u64 = 256;
+/// The max block rewards returned by one `chain.get_block_rewards` call.
+const MAX_BLOCK_REWARDS_PAGE_SIZE: u64 = 100;
+/// The max txns returned by one `chain.get_transactions` call.
+const MAX_TRANSACTIONS_BATCH_SIZE: u64 = 100;
+/// The max events returned by one `chain.poll_events` call.
+const MAX_POLL_EVENTS_SIZE: u64 = 1000;
+/// The max time to wait the txpool, sync and network status for `chain.info`.
+const CHAIN_STATUS_COMPONENT_TIMEOUT: Duration = Duration::from_secs(2);
 
-pub struct ChainRpcImpl<S>
+pub struct ChainRpcImpl<S, P, Y>
 where
     S: ChainAsyncService + 'static,
+    P: TxPoolSyncService + 'static,
+    Y: SyncAsyncService + 'static,
 {
     config: Arc<NodeConfig>,
     genesis_hash: HashValue,
     service: S,
+    playground: PlaygroudService,
+    storage: Arc<Storage>,
+    /// Serialize the poll and ack of the event cursors, so the acked position only moves forward.
+    event_cursor_lock: Arc<Mutex<()>>,
+    txpool: Option<P>,
+    sync_service: Option<Y>,
+    network: Option<NetworkServiceRef>,
 }
 
-impl<S> ChainRpcImpl<S>
+impl<S, P, Y> ChainRpcImpl<S, P, Y>
 where
     S: ChainAsyncService,
+    P: TxPoolSyncService,
+    Y: SyncAsyncService,
 {
-    pub fn new(config: Arc<NodeConfig>, genesis_hash: HashValue, service: S) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        config: Arc<NodeConfig>,
+        genesis_hash: HashValue,
+        service: S,
+        playground: PlaygroudService,
+        storage: Arc<Storage>,
+        txpool: Option<P>,
+        sync_service: Option<Y>,
+        network: Option<NetworkServiceRef>,
+    ) -> Self {
         Self {
             config,
             genesis_hash,
             service,
+            playground,
+            storage,
+            event_cursor_lock: Arc::new(Mutex::new(())),
+            txpool,
+            sync_service,
+            network,
+        }
+    }
+}
+
+/// Wait the status of a service at most `CHAIN_STATUS_COMPONENT_TIMEOUT`,
+/// return None if the service is disabled, fails or timeouts.
+async fn chain_status_component<T, F>(name: &str, fut: Option<F>) -> Option<T>
+where
+    F: Future<Output = anyhow::Result<T>>,
+{
+    let fut = fut?;
+    match future::select(Box::pin(fut), Delay::new(CHAIN_STATUS_COMPONENT_TIMEOUT)).await {
+        Either::Left((Ok(result), _)) => Some(result),
+        Either::Left((Err(e), _)) => {
+            warn!("Get {} status for chain info failed: {:?}", name, e);
+            None
+        }
+        Either::Right(_) => {
+            warn!("Get {} status for chain info timeout", name);
+            None
         }
     }
 }
 
-impl<S> ChainApi for ChainRpcImpl<S>
+impl<S, P, Y> ChainApi for ChainRpcImpl<S, P, Y>
 where
     S: ChainAsyncService,
+    P: TxPoolSyncService,
+    Y: SyncAsyncService,
 {
     fn id(&self) -> jsonrpc_core::Result<ChainId> {
         Ok(self.config.net().id().into())
     }
 
-    fn info(&self) -> FutureResult<ChainInfoView> {
+    fn info(&self) -> FutureResult<ChainStatusView> {
         let service = self.service.clone();
         let chain_id = self.config.net().chain_id();
         let genesis_hash = self.genesis_hash;
+        let now_seconds = self.config.net().time_service().now_secs();
+        let txpool = self.txpool.clone();
+        let sync_service = self.sync_service.clone();
+        let network = self.network.clone();
         let fut = async move {
-            let chain_status = service.main_status().await?;
+            // the status of the services are got concurrently, and the slow ones are skipped.
+            let (chain_status, txpool, sync, peers) = futures::join!(
+                service.main_status(),
+                chain_status_component(
+                    "txpool",
+                    txpool.map(|txpool| async move { Ok(txpool.cached_detailed_status()) })
+                ),
+                chain_status_component(
+                    "sync",
+                    sync_service.map(|sync_service| async move { sync_service.progress().await })
+                ),
+                chain_status_component(
+                    "network",
+                    network.map(|network| async move { Ok(network.peer_set().await?.len()) })
+                ),
+            );
             //TODO get chain info from chain service.
-            Ok(ChainInfo::new(chain_id, genesis_hash, chain_status).into())
+            let mut view = ChainStatusView::new(
+                ChainInfo::new(chain_id, genesis_hash, chain_status?),
+                now_seconds,
+            );
+            view.txpool = txpool.map(Into::into);
+            view.sync = sync.flatten().map(Into::into);
+            view.peers = peers.map(|peers| peers as u32);
+            Ok(view)
         };
         Box::pin(fut.boxed().map_err(map_err))
     }
 
-    fn get_block_by_hash(&self, hash: HashValue) -> FutureResult<Option<BlockView>> {
+    fn get_block_by_hash(
+        &self,
+        hash: HashValue,
+        option: Option<GetBlockOption>,
+        output_encoding: Option<OutputEncoding>,
+    ) -> FutureResult<Option<EncodedView<BlockView>>> {
         let service = self.service.clone();
 
         let fut = async move {
             let result = service.get_block_by_hash(hash).await?;
-            result.map(|b| b.try_into()).transpose()
+            match (result, output_encoding.unwrap_or_default()) {
+                (Some(block), OutputEncoding::Bcs) => Ok(Some(EncodedView::bcs(&block)?)),
+                (Some(block), OutputEncoding::Json) => Ok(Some(EncodedView::Json(
+                    to_block_view(&service, block, option.unwrap_or_default()).await?,
+                ))),
+                (None, _) => Ok(None),
+            }
         }
         .map_err(map_err);
 
         Box::pin(fut.boxed())
     }
 
-    fn get_block_by_number(&self, number: u64) -> FutureResult<Option<BlockView>> {
+    fn get_block_by_number(
+        &self,
+        number: u64,
+        option: Option<GetBlockOption>,
+    ) -> FutureResult<Option<BlockView>> {
         let service = self.service.clone();
 
         let fut = async move {
             let result = service.main_block_by_number(number).await?;
-            result.map(|b| b.try_into()).transpose()
+            match result {
+                Some(block) => Ok(Some(
+                    to_block_view(&service, block, option.unwrap_or_default()).await?,
+                )),
+                None => Ok(None),
+            }
         }
         .map_err(map_err);
 
@@ -132,23 +261,29 @@ where
     fn get_transaction(
         &self,
         transaction_hash: HashValue,
-    ) -> FutureResult<Option<TransactionView>> {
+        output_encoding: Option<OutputEncoding>,
+    ) -> FutureResult<Option<EncodedView<TransactionView>>> {
         let service = self.service.clone();
         let fut = async move {
             let transaction = service.get_transaction(transaction_hash).await?;
             match transaction {
                 None => Ok(None),
+                Some(t) if output_encoding == Some(OutputEncoding::Bcs) => {
+                    Ok(Some(EncodedView::bcs(&t)?))
+                }
                 Some(t) => {
-                    let block = service
-                        .get_transaction_block(transaction_hash)
+                    let position = service
+                        .get_transaction_positions(vec![transaction_hash])
                         .await?
+                        .pop()
+                        .flatten()
                         .ok_or_else(|| {
                             anyhow::anyhow!(
-                                "cannot find block which includes the txn {}",
+                                "cannot find the main chain position of the txn {}",
                                 transaction_hash
                             )
                         })?;
-                    TransactionView::new(t, &block).map(Some)
+                    TransactionView::new(t, &position).map(|view| Some(EncodedView::Json(view)))
                 }
             }
         }
@@ -157,6 +292,68 @@ where
         Box::pin(fut.boxed())
     }
 
+    fn get_transactions(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> FutureResult<Vec<Option<TransactionView>>> {
+        let service = self.service.clone();
+        let fut = async move {
+            if txn_hashes.len() as u64 > MAX_TRANSACTIONS_BATCH_SIZE {
+                return Err(jsonrpc_core::Error::invalid_params(format!(
+                    "too many txn hashes, max batch size is {}",
+                    MAX_TRANSACTIONS_BATCH_SIZE
+                ))
+                .into());
+            }
+            let txns = service.get_main_transactions(txn_hashes.clone()).await?;
+            let positions = service.get_transaction_positions(txn_hashes).await?;
+            txns.into_iter()
+                .zip(positions)
+                .map(|(txn, position)| match txn {
+                    None => Ok(None),
+                    Some((txn, txn_info)) => {
+                        let position = position.ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "cannot find the main chain position of the txn {}",
+                                txn_info.transaction_hash()
+                            )
+                        })?;
+                        TransactionView::new(txn, &position).map(Some)
+                    }
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
+    fn get_block_hash_by_txn(&self, txn_hash: HashValue) -> FutureResult<Option<HashValue>> {
+        let service = self.service.clone();
+        let fut = async move {
+            let txn_info = service.get_transaction_info(txn_hash).await?;
+            Ok(txn_info.map(|txn_info| txn_info.block_id()))
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
+    fn get_txn_position(&self, txn_hash: HashValue) -> FutureResult<Option<TxnPositionView>> {
+        let service = self.service.clone();
+        let fut = async move {
+            let position = service
+                .get_transaction_positions(vec![txn_hash])
+                .await?
+                .pop()
+                .flatten();
+            Ok(position.map(Into::into))
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
     fn get_transaction_info(
         &self,
         transaction_hash: HashValue,
@@ -171,19 +368,84 @@ where
                 info.unwrap()
             };
 
-            let block = service
-                .get_block_by_hash(txn_info.block_id())
+            let position = service
+                .get_transaction_positions(vec![transaction_hash])
                 .await?
+                .pop()
+                .flatten()
                 .ok_or_else(|| {
                     anyhow::anyhow!(
-                        "cannot find the block {}  which include txn {}",
-                        txn_info.block_id(),
+                        "cannot find the main chain position of the txn {}",
                         transaction_hash
                     )
                 })?;
+            // the position index and the txn info may be left by different forks,
+            // so the info must be executed by the block of the main chain position.
+            anyhow::ensure!(
+                position.block_id == txn_info.block_id(),
+                "the txn info of {} is executed by block {}, but the txn is at block {} on the main chain",
+                transaction_hash,
+                txn_info.block_id(),
+                position.block_id
+            );
+            TransactionInfoView::new(txn_info, &position).map(Some)
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
 
-            TransactionInfoView::new(Into::<(_, TransactionInfo)>::into(txn_info).1, &block)
-                .map(Some)
+    fn get_transaction_output(
+        &self,
+        transaction_hash: HashValue,
+    ) -> FutureResult<Option<TransactionOutputView>> {
+        let service = self.service.clone();
+        let playground = self.playground.clone();
+        let disabled = self.config.rpc.disable_txn_output_query;
+        let fut = async move {
+            if disabled {
+                anyhow::bail!("chain.get_transaction_output is disabled by the rpc config");
+            }
+            let (txn, txn_info) = match (
+                service.get_transaction(transaction_hash).await?,
+                service.get_transaction_info(transaction_hash).await?,
+            ) {
+                (Some(txn), Some(txn_info)) => (txn, txn_info),
+                _ => return Ok(None),
+            };
+            let block_id = txn_info.block_id();
+            let txn_infos = service.get_block_txn_infos(block_id).await?;
+            let idx = txn_infos
+                .iter()
+                .position(|info| info.transaction_hash() == transaction_hash)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "cannot find the txn info of txn {} in block {}",
+                        transaction_hash,
+                        block_id
+                    )
+                })?;
+            // the state before the txn is the state after the previous txn in the block,
+            // or the state of the parent block for the first txn.
+            let state_root = if idx > 0 {
+                txn_infos[idx - 1].state_root_hash()
+            } else {
+                let header = service
+                    .get_header_by_hash(&block_id)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("cannot find block header {}", block_id))?;
+                service
+                    .get_header_by_hash(&header.parent_hash())
+                    .await?
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("cannot find block header {}", header.parent_hash())
+                    })?
+                    .state_root()
+            };
+            let (output, verified) = playground.replay_transaction(state_root, txn, &txn_info)?;
+            let mut view = TransactionOutputView::from(output);
+            view.verified = Some(verified);
+            Ok(Some(view))
         }
         .map_err(map_err);
 
@@ -197,12 +459,7 @@ where
             let block = service.get_block_by_hash(block_hash).await?;
             match block {
                 None => Ok(vec![]),
-                Some(block) => txn_infos
-                    .into_iter()
-                    .map(|info| {
-                        TransactionInfoView::new(Into::<(_, TransactionInfo)>::into(info).1, &block)
-                    })
-                    .collect::<Result<Vec<_>, _>>(),
+                Some(block) => to_txn_info_views(&service, &block, txn_infos).await,
             }
         }
         .map_err(map_err);
@@ -221,17 +478,11 @@ where
             match block {
                 None => Ok(None),
                 Some(block) => {
-                    let txn_info = service
-                        .get_txn_info_by_block_and_index(block_hash, idx)
-                        .await?;
-                    txn_info
-                        .map(|info| {
-                            TransactionInfoView::new(
-                                Into::<(_, TransactionInfo)>::into(info).1,
-                                &block,
-                            )
-                        })
-                        .transpose()
+                    let txn_infos = service.get_block_txn_infos(block_hash).await?;
+                    Ok(to_txn_info_views(&service, &block, txn_infos)
+                        .await?
+                        .into_iter()
+                        .nth(idx as usize))
                 }
             }
         }
@@ -239,6 +490,103 @@ where
 
         Box::pin(fut.boxed())
     }
+
+    fn get_transaction_infos(
+        &self,
+        start_global_index: u64,
+        reverse: bool,
+        max_size: u64,
+    ) -> FutureResult<Vec<TransactionInfoView>> {
+        let service = self.service.clone();
+        let config = self.config.clone();
+        let fut = async move {
+            let max_size = max_size.min(config.rpc.txn_info_query_max_size());
+            let num_leaves = service
+                .main_status()
+                .await?
+                .info()
+                .txn_accumulator_info
+                .num_leaves;
+            // the accumulator begin from the last leaf if reverse and the start index is out of range.
+            let start_index = if reverse {
+                start_global_index.min(num_leaves.saturating_sub(1))
+            } else {
+                start_global_index
+            };
+            let txn_infos = service
+                .get_transaction_infos(start_index, reverse, max_size)
+                .await?;
+
+            let positions = service
+                .get_transaction_positions(
+                    txn_infos
+                        .iter()
+                        .map(|txn_info| txn_info.transaction_hash())
+                        .collect(),
+                )
+                .await?;
+            txn_infos
+                .into_iter()
+                .zip(positions)
+                .map(|(txn_info, position)| {
+                    let position = position.ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "cannot find the main chain position of the txn {}",
+                            txn_info.transaction_hash()
+                        )
+                    })?;
+                    TransactionInfoView::new(txn_info, &position)
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
+    fn get_transaction_proof(
+        &self,
+        block_hash: HashValue,
+        transaction_index: u64,
+        event_index: Option<u64>,
+    ) -> FutureResult<Option<TransactionProofView>> {
+        let service = self.service.clone();
+        let fut = async move {
+            let block = match service.get_block_by_hash(block_hash).await? {
+                Some(block) => block,
+                None => return Ok(None),
+            };
+            let proof = service
+                .get_transaction_proof(block_hash, transaction_index, event_index)
+                .await?;
+            let proof = match proof {
+                Some(proof) => proof,
+                None => return Ok(None),
+            };
+            // the genesis block has no block metadata txn.
+            let txn_count = if block.header().is_genesis() {
+                block.transactions().len()
+            } else {
+                block.transactions().len() + 1
+            };
+            let positions = block_txn_positions(&service, &block, txn_count).await?;
+            let position = positions
+                .into_iter()
+                .find(|position| position.global_index == proof.leaf_index)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "the txn at leaf {} is not executed by block {}",
+                        proof.leaf_index,
+                        block_hash
+                    )
+                })?;
+            Ok(Some(TransactionProofView::new(proof, &position)))
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
     fn get_events_by_txn_hash(
         &self,
         txn_hash: HashValue,
@@ -253,15 +601,81 @@ where
         Box::pin(fut.boxed())
     }
 
+    fn export_block_bundle(
+        &self,
+        block_hash: HashValue,
+        with_state: Option<bool>,
+    ) -> FutureResult<Option<StrView<Vec<u8>>>> {
+        let service = self.service.clone();
+        let playground = self.playground.clone();
+        let state_enabled = self.config.rpc.enable_block_bundle_state;
+        let fut = async move {
+            let with_state = with_state.unwrap_or(false);
+            if with_state && !state_enabled {
+                anyhow::bail!("chain.export_block_bundle with state is disabled, enable it by `--enable-block-bundle-state`");
+            }
+            let block = match service.get_block_by_hash(block_hash).await? {
+                Some(block) => block,
+                None => return Ok(None),
+            };
+            anyhow::ensure!(
+                !block.header().is_genesis(),
+                "The genesis block can not be exported"
+            );
+            let parent_hash = block.header().parent_hash();
+            let parent = service
+                .get_header_by_hash(&parent_hash)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("cannot find block header {}", parent_hash))?;
+            let mut transactions = vec![Transaction::BlockMetadata(
+                block.to_metadata(parent.gas_used()),
+            )];
+            transactions.extend(
+                block
+                    .transactions()
+                    .iter()
+                    .cloned()
+                    .map(Transaction::UserTransaction),
+            );
+            let state = if with_state {
+                let txn_infos = service
+                    .get_block_txn_infos(block_hash)
+                    .await?
+                    .into_iter()
+                    .map(|txn_info| Into::<(_, TransactionInfo)>::into(txn_info).1)
+                    .collect::<Vec<_>>();
+                let (outputs, read_set) = playground.execute_block_with_read_set(
+                    parent.state_root(),
+                    transactions.clone(),
+                    &txn_infos,
+                )?;
+                Some(BlockBundleState {
+                    pre_state_root: parent.state_root(),
+                    read_set,
+                    outputs,
+                })
+            } else {
+                None
+            };
+            let bundle = BlockBundle::new(block, transactions, state);
+            Ok(Some(StrView(bundle.encode()?)))
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
     fn get_events(&self, mut filter: EventFilter) -> FutureResult<Vec<TransactionEventView>> {
         let service = self.service.clone();
         let config = self.config.clone();
+        let playground = self.playground.clone();
         let fut = async move {
+            let head = service.main_head_header().await?;
             if filter.to_block.is_none() {
                 // if user hasn't specify the `to_block`, we use latest block as the to_block.
-                let header_block_number = service.main_head_header().await?.number();
-                filter.to_block = Some(header_block_number);
+                filter.to_block = Some(head.number().into());
             }
+            let decode = filter.decode;
 
             let filter: Filter = filter.try_into()?;
 
@@ -280,24 +694,106 @@ where
                 .into());
             }
 
-            service.main_events(filter).await
+            let events = service.main_events(filter).await?;
+            to_event_views(&playground, head.state_root(), events, decode)
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
+    fn poll_events(
+        &self,
+        cursor_id: EventCursorId,
+        max_items: u64,
+    ) -> FutureResult<EventCursorPageView> {
+        let service = self.service.clone();
+        let config = self.config.clone();
+        let playground = self.playground.clone();
+        let storage = self.storage.clone();
+        let lock = self.event_cursor_lock.clone();
+        let fut = async move {
+            if max_items == 0 || max_items > MAX_POLL_EVENTS_SIZE {
+                return Err(jsonrpc_core::Error::invalid_params(format!(
+                    "max_items should be in 1..={}",
+                    MAX_POLL_EVENTS_SIZE
+                ))
+                .into());
+            }
+            let cursor = update_event_cursor(&config, &storage, &lock, cursor_id, |_| {})?;
+            let head = service.main_head_header().await?;
+
+            let mut filter = cursor.filter;
+            let from_block = match cursor.acked {
+                Some(acked) => filter.from_block.max(acked.block_number),
+                None => filter.from_block,
+            };
+            let end_block = filter.to_block.min(head.number());
+            if from_block > end_block {
+                return Ok(EventCursorPageView {
+                    events: vec![],
+                    done: true,
+                });
+            }
+            // scan at most `block_query_max_range` blocks in one poll, like `chain.get_events`.
+            let to_block =
+                end_block.min(from_block.saturating_add(config.rpc.block_query_max_range()));
+            filter.from_block = from_block;
+            filter.to_block = to_block;
+            filter.cursor = cursor.acked;
+            filter.reverse = false;
+            filter.limit = Some(max_items as usize + 1);
+
+            let mut events = service.main_events(filter).await?;
+            let done = events.len() as u64 <= max_items && to_block == end_block;
+            events.truncate(max_items as usize);
+            if events.is_empty() {
+                // nothing to deliver in the scanned blocks, so move the cursor after them,
+                // otherwise the next poll scans the same blocks again.
+                update_event_cursor(&config, &storage, &lock, cursor_id, |cursor| {
+                    cursor.ack(EventPosition::end_of_block(to_block))
+                })?;
+            }
+            Ok(EventCursorPageView {
+                events: to_event_views(&playground, head.state_root(), events, cursor.decode)?,
+                done,
+            })
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
+    fn ack_events(
+        &self,
+        cursor_id: EventCursorId,
+        up_to_global_index: StrView<u128>,
+    ) -> FutureResult<()> {
+        let config = self.config.clone();
+        let storage = self.storage.clone();
+        let lock = self.event_cursor_lock.clone();
+        let fut = async move {
+            let position = EventPosition::from_global_index(up_to_global_index.0);
+            update_event_cursor(&config, &storage, &lock, cursor_id, |cursor| {
+                cursor.ack(position)
+            })?;
+            Ok(())
         }
-        .map_ok(|d| d.into_iter().map(|e| e.into()).collect())
         .map_err(map_err);
 
         Box::pin(fut.boxed())
     }
 
-    fn current_epoch(&self) -> FutureResult<EpochInfo> {
+    fn current_epoch(&self) -> FutureResult<EpochInfoView> {
         let service = self.service.clone();
-        let fut = async move { service.epoch_info().await };
+        let fut = async move { Ok(service.epoch_info().await?.into()) };
 
         Box::pin(fut.boxed().map_err(map_err))
     }
 
-    fn get_epoch_info_by_number(&self, number: BlockNumber) -> FutureResult<EpochInfo> {
+    fn get_epoch_info_by_number(&self, number: BlockNumber) -> FutureResult<EpochInfoView> {
         let service = self.service.clone();
-        let fut = async move { service.get_epoch_info_by_number(number).await };
+        let fut = async move { Ok(service.get_epoch_info_by_number(number).await?.into()) };
 
         Box::pin(fut.boxed().map_err(map_err))
     }
@@ -323,11 +819,139 @@ where
         Box::pin(fut.boxed())
     }
 
+    fn get_block_by_uncle_hash(
+        &self,
+        uncle_hash: HashValue,
+    ) -> FutureResult<Option<BlockSummaryView>> {
+        let service = self.service.clone();
+        let fut = async move {
+            let block = service.get_block_by_uncle_hash(uncle_hash).await?;
+            Ok(block.map(|block| BlockSummary::from(block).into()))
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
+    fn get_uncles_by_number(&self, number: BlockNumber) -> FutureResult<Vec<BlockHeaderView>> {
+        let service = self.service.clone();
+        let fut = async move {
+            let uncles = service.main_uncles_by_number(number).await?;
+            Ok(uncles.into_iter().map(Into::into).collect())
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
     fn get_headers(&self, block_hashes: Vec<HashValue>) -> FutureResult<Vec<BlockHeaderView>> {
+        let fut = self
+            .get_headers_v2(block_hashes)
+            .map_ok(|headers| headers.into_iter().flatten().collect());
+        Box::pin(fut)
+    }
+
+    fn get_headers_v2(
+        &self,
+        block_hashes: Vec<HashValue>,
+    ) -> FutureResult<Vec<Option<BlockHeaderView>>> {
         let service = self.service.clone();
         let fut = async move {
+            if block_hashes.len() as u64 > MAX_HEADERS_BATCH_SIZE {
+                return Err(jsonrpc_core::Error::invalid_params(format!(
+                    "too many block hashes, max batch size is {}",
+                    MAX_HEADERS_BATCH_SIZE
+                ))
+                .into());
+            }
             let headers = service.get_headers(block_hashes).await?;
-            Ok(headers.into_iter().map(Into::into).collect())
+            Ok(headers
+                .into_iter()
+                .map(|header| header.map(Into::into))
+                .collect())
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
+    fn get_headers_by_number(
+        &self,
+        start: BlockNumber,
+        count: u64,
+    ) -> FutureResult<BlockHeadersView> {
+        let service = self.service.clone();
+        let storage = self.storage.clone();
+        let fut = async move {
+            if count > MAX_HEADERS_BATCH_SIZE {
+                return Err(jsonrpc_core::Error::invalid_params(format!(
+                    "count is too big, max batch size is {}",
+                    MAX_HEADERS_BATCH_SIZE
+                ))
+                .into());
+            }
+            let ids = service.get_block_ids(start, false, count).await?;
+            // read the headers from the storage directly, do not block the chain service.
+            let headers = ids
+                .iter()
+                .map(|id| {
+                    storage
+                        .get_block_header_by_hash(*id)?
+                        .ok_or_else(|| anyhow::anyhow!("cannot find block header {}", id))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            let head_block_hash = service.main_head_header().await?.id();
+            // the chain may switch after the ids are read, check the range is still on the main chain.
+            let on_main_chain = match headers.last() {
+                Some(last) => {
+                    service
+                        .get_block_ids(last.number(), false, 1)
+                        .await?
+                        .first()
+                        == Some(&last.id())
+                }
+                None => true,
+            };
+            Ok(BlockHeadersView::new(
+                headers.into_iter().map(Into::into).collect(),
+                head_block_hash,
+                on_main_chain,
+            ))
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
+    fn get_block_reward(&self, number: BlockNumber) -> FutureResult<Option<BlockRewardView>> {
+        let service = self.service.clone();
+        let fut = async move {
+            let reward = service.main_block_rewards(number, 1).await?.pop();
+            Ok(reward
+                .filter(|reward| reward.block_number == number)
+                .map(Into::into))
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
+    fn get_block_rewards(
+        &self,
+        start: BlockNumber,
+        count: u64,
+    ) -> FutureResult<Vec<BlockRewardView>> {
+        let service = self.service.clone();
+        let fut = async move {
+            if count > MAX_BLOCK_REWARDS_PAGE_SIZE {
+                return Err(jsonrpc_core::Error::invalid_params(format!(
+                    "count is too big, max page size is {}",
+                    MAX_BLOCK_REWARDS_PAGE_SIZE
+                ))
+                .into());
+            }
+            let rewards = service.main_block_rewards(start, count).await?;
+            Ok(rewards.into_iter().map(Into::into).collect())
         }
         .map_err(map_err);
 
@@ -347,4 +971,190 @@ where
 
         Box::pin(fut.boxed())
     }
+
+    fn get_branches(&self) -> FutureResult<Vec<BranchView>> {
+        let service = self.service.clone();
+        let fut = async move {
+            let branches = service.get_branches().await?;
+            Ok(branches.into_iter().map(Into::into).collect())
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
+    fn get_common_ancestor(
+        &self,
+        hash1: HashValue,
+        hash2: HashValue,
+    ) -> FutureResult<Option<HashValue>> {
+        let service = self.service.clone();
+        let fut = async move { service.get_common_ancestor(hash1, hash2).await }.map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+
+    fn get_event_handle(
+        &self,
+        address: AccountAddressView,
+        struct_tag: StructTagView,
+        field_path: String,
+    ) -> FutureResult<Option<EventHandleView>> {
+        let address = address.0;
+        let service = self.service.clone();
+        let storage = self.storage.clone();
+        let playground = self.playground.clone();
+        let fut = async move {
+            let state_root = service.main_head_header().await?.state_root();
+            let statedb = ChainStateDB::new(storage, Some(state_root));
+            let data = statedb.get(&AccessPath::resource_access_path(
+                address,
+                struct_tag.0.clone(),
+            ))?;
+            match data {
+                None => Ok(None),
+                Some(data) => {
+                    let resource =
+                        playground.view_resource(state_root, &struct_tag.0, data.as_slice())?;
+                    Ok(Some(EventHandleView::find_in(
+                        &resource,
+                        field_path.as_str(),
+                    )?))
+                }
+            }
+        }
+        .map_err(map_err);
+
+        Box::pin(fut.boxed())
+    }
+}
+
+/// Convert the events to views, decode the event data by the state of `state_root` if `decode`.
+fn to_event_views(
+    playground: &PlaygroudService,
+    state_root: HashValue,
+    events: Vec<ContractEventInfo>,
+    decode: bool,
+) -> anyhow::Result<Vec<TransactionEventView>> {
+    let decoded = if decode {
+        let contract_events: Vec<_> = events.iter().map(|e| e.event.clone()).collect();
+        playground
+            .view_events(state_root, &contract_events)?
+            .into_iter()
+            .map(|value| Some(value.into()))
+            .collect()
+    } else {
+        vec![None; events.len()]
+    };
+    Ok(events
+        .into_iter()
+        .zip(decoded)
+        .map(|(event, decoded)| TransactionEventView {
+            decoded,
+            ..event.into()
+        })
+        .collect())
+}
+
+/// Remove the expired event cursors, then update the cursor by `f` and mark it active.
+/// Return the `EventCursorExpired` error if the cursor is removed, or invalid params if it is never created.
+fn update_event_cursor<F>(
+    config: &NodeConfig,
+    storage: &Storage,
+    lock: &Mutex<()>,
+    cursor_id: EventCursorId,
+    f: F,
+) -> anyhow::Result<EventCursor>
+where
+    F: FnOnce(&mut EventCursor),
+{
+    let now_secs = config.net().time_service().now_secs();
+    let _guard = lock.lock();
+    storage.remove_expired_event_cursors(
+        now_secs,
+        config.rpc.event_cursor_idle_timeout(),
+        config.rpc.event_cursor_max_lifetime(),
+    )?;
+    let expired_err = || {
+        RpcErrorCode::EventCursorExpired.error(format!(
+            "event cursor {} is expired, create a new one",
+            cursor_id
+        ))
+    };
+    let mut cursor = match storage.get_event_cursor(cursor_id)? {
+        Some(cursor) => cursor,
+        None if cursor_id < storage.next_event_cursor_id()? => {
+            return Err(expired_err().into());
+        }
+        None => {
+            return Err(jsonrpc_core::Error::invalid_params(format!(
+                "event cursor {} does not exist",
+                cursor_id
+            ))
+            .into());
+        }
+    };
+    f(&mut cursor);
+    cursor.last_active_secs = now_secs;
+    if !storage.update_event_cursor(cursor.clone())? {
+        return Err(expired_err().into());
+    }
+    Ok(cursor)
+}
+
+async fn to_block_view<S>(
+    service: &S,
+    block: Block,
+    option: GetBlockOption,
+) -> anyhow::Result<BlockView>
+where
+    S: ChainAsyncService,
+{
+    let block_info = if option.include_block_info {
+        service.get_block_info_by_hash(&block.id()).await?
+    } else {
+        None
+    };
+    Ok(BlockView::try_from_block(block, !option.decode)?.with_block_info(block_info))
+}
+
+/// The positions of the `txn_count` txns executed by the `block`, in execution order,
+/// calculated by the txn accumulator info of the block.
+pub(crate) async fn block_txn_positions<S>(
+    service: &S,
+    block: &Block,
+    txn_count: usize,
+) -> anyhow::Result<Vec<TransactionPosition>>
+where
+    S: ChainAsyncService,
+{
+    let block_info = service
+        .get_block_info_by_hash(&block.id())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("cannot find the block info {}", block.id()))?;
+    TransactionPosition::block_positions(
+        block.id(),
+        block.header().number(),
+        block.transactions().len(),
+        txn_count,
+        block_info.txn_accumulator_info.num_leaves,
+    )
+}
+
+/// Convert all the txn infos of the `block` to views,
+/// the txn infos are in execution order, so the block metadata txn is at index 0.
+pub(crate) async fn to_txn_info_views<S>(
+    service: &S,
+    block: &Block,
+    txn_infos: Vec<BlockTransactionInfo>,
+) -> anyhow::Result<Vec<TransactionInfoView>>
+where
+    S: ChainAsyncService,
+{
+    let positions = block_txn_positions(service, block, txn_infos.len()).await?;
+    txn_infos
+        .into_iter()
+        .zip(positions.iter())
+        .map(|(txn_info, position)| TransactionInfoView::new(txn_info, position))
+        .collect()
 }
@@ -1,26 +1,34 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::module::helpers::TransactionRequestFiller;
-use crate::module::map_err;
+use crate::module::helpers::{get_module, TransactionRequestFiller};
+use crate::module::{execution_failed, invalid_bytecode, map_err};
 use futures::future::TryFutureExt;
 use futures::FutureExt;
 use starcoin_account_api::AccountAsyncService;
 use starcoin_chain_service::ChainAsyncService;
 use starcoin_config::NodeConfig;
+use starcoin_crypto::HashValue;
+use starcoin_dev::disassemble::{
+    disassemble, disassemble_module, module_dependencies, Disassembly,
+};
 use starcoin_dev::playground::PlaygroudService;
 use starcoin_rpc_api::contract_api::ContractApi;
 use starcoin_rpc_api::types::{
-    AnnotatedMoveStructView, AnnotatedMoveValueView, ContractCall, DryRunTransactionRequest,
-    StrView, TransactionOutputView,
+    function_arg_types, AccountAddressView, AnnotatedMoveStructView, AnnotatedMoveValueView,
+    ArgumentsView, CodeView, ContractCall, DisassemblyView, DryRunTransactionRequest,
+    FunctionAbiView, FunctionIdView, GetCodeOption, ModuleAbiView, ModuleIdView, StrView,
+    TransactionOutputView,
 };
 use starcoin_rpc_api::FutureResult;
 use starcoin_state_api::ChainStateAsyncService;
 use starcoin_txpool_api::TxPoolSyncService;
-use starcoin_types::account_address::AccountAddress;
 use starcoin_types::language_storage::{ModuleId, StructTag};
 use starcoin_types::transaction::DryRunTransaction;
 use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::file_format::CompiledModule;
+use starcoin_vm_types::language_storage::FunctionId;
+use starcoin_vm_types::vm_status::VMStatus;
 use std::sync::Arc;
 
 pub struct ContractRpcImpl<Account, Pool, State, Chain> {
@@ -56,6 +64,57 @@ where
             playground,
         }
     }
+    async fn module_abi(
+        chain_state: State,
+        module_id: ModuleId,
+    ) -> anyhow::Result<Option<ModuleAbiView>> {
+        match get_module(chain_state, &module_id).await? {
+            None => Ok(None),
+            Some(module) => Ok(Some(ModuleAbiView::new(&module, |_| None)?)),
+        }
+    }
+
+    /// Encode the call args to bcs, json literal args are encoded by the on-chain function signature.
+    async fn call_args(
+        chain_state: State,
+        function_id: &FunctionId,
+        args: Vec<serde_json::Value>,
+    ) -> anyhow::Result<Vec<Vec<u8>>> {
+        let args = ArgumentsView::from_values(args)?;
+        if !args.needs_arg_types() {
+            return args.to_bcs_bytes();
+        }
+        let module = get_module(chain_state, &function_id.module)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("cannot find module {} on chain", function_id.module))?;
+        let arg_types = function_arg_types(&module, function_id.function.as_str())?
+            .ok_or_else(|| anyhow::anyhow!("cannot find function {}", function_id))?;
+        args.to_bcs_bytes_by_types(&arg_types)
+    }
+
+    /// Call the move function on the latest state, the vm errors are returned as `VMStatus`.
+    async fn call_contract(
+        chain_state: State,
+        playground: PlaygroudService,
+        call: ContractCall,
+    ) -> anyhow::Result<Vec<AnnotatedMoveValueView>> {
+        let ContractCall {
+            function_id,
+            type_args,
+            args,
+        } = call;
+        let args = Self::call_args(chain_state.clone(), &function_id.0, args).await?;
+        let state_root = chain_state.state_root().await?;
+        let output = playground.call_contract(
+            state_root,
+            function_id.0.module,
+            function_id.0.function,
+            type_args.into_iter().map(|v| v.0).collect(),
+            args,
+        )?;
+        Ok(output.into_iter().map(Into::into).collect())
+    }
+
     fn txn_request_filler(&self) -> TransactionRequestFiller<Account, Pool, State, Chain> {
         TransactionRequestFiller {
             account: self.account.clone(),
@@ -74,20 +133,88 @@ where
     State: ChainStateAsyncService + 'static,
     Chain: ChainAsyncService + 'static,
 {
-    fn get_code(&self, module_id: StrView<ModuleId>) -> FutureResult<Option<StrView<Vec<u8>>>> {
+    fn get_code(
+        &self,
+        module_id: ModuleIdView,
+        option: Option<GetCodeOption>,
+    ) -> FutureResult<Option<CodeView>> {
+        let service = self.chain_state.clone();
+        let option = option.unwrap_or_default();
+        let f = async move {
+            let code = match service.get(AccessPath::from(&module_id.0)).await? {
+                None => return Ok(None),
+                Some(code) => code,
+            };
+            let module = CompiledModule::deserialize(code.as_slice())
+                .map_err(|e| invalid_bytecode(e.into()))?;
+            let dependencies = module_dependencies(&module)
+                .into_iter()
+                .map(StrView)
+                .collect();
+            let disassembly = if option.disassemble {
+                Some(disassemble_module(module).map_err(invalid_bytecode)?.code)
+            } else {
+                None
+            };
+            Ok(Some(CodeView {
+                code: StrView(code),
+                disassembly,
+                dependencies,
+            }))
+        };
+        Box::pin(f.map_err(map_err).boxed())
+    }
+
+    fn module_exists(&self, module_id: ModuleIdView) -> FutureResult<Option<HashValue>> {
         let service = self.chain_state.clone();
         let f = async move {
             let code = service.get(AccessPath::from(&module_id.0)).await?;
-            Ok(code.map(StrView))
+            Ok(code.map(|code| HashValue::sha3_256_of(code.as_slice())))
+        };
+        Box::pin(f.map_err(map_err).boxed())
+    }
+
+    fn disassemble(&self, code: StrView<Vec<u8>>) -> FutureResult<DisassemblyView> {
+        let f = async move {
+            let Disassembly {
+                module_id,
+                dependencies,
+                code,
+            } = disassemble(code.0.as_slice()).map_err(invalid_bytecode)?;
+            Ok(DisassemblyView {
+                module_id: module_id.map(StrView),
+                dependencies: dependencies.into_iter().map(StrView).collect(),
+                disassembly: code,
+            })
+        };
+        Box::pin(f.map_err(map_err).boxed())
+    }
+
+    fn get_module_abi(&self, module_id: ModuleIdView) -> FutureResult<Option<ModuleAbiView>> {
+        let service = self.chain_state.clone();
+        let f = async move { Self::module_abi(service, module_id.0).await };
+        Box::pin(f.map_err(map_err).boxed())
+    }
+
+    fn resolve_function(
+        &self,
+        function_id: FunctionIdView,
+    ) -> FutureResult<Option<FunctionAbiView>> {
+        let service = self.chain_state.clone();
+        let f = async move {
+            let FunctionId { module, function } = function_id.0;
+            let abi = Self::module_abi(service, module).await?;
+            Ok(abi.and_then(|abi| abi.function(function.as_str()).cloned()))
         };
         Box::pin(f.map_err(map_err).boxed())
     }
 
     fn get_resource(
         &self,
-        addr: AccountAddress,
+        addr: AccountAddressView,
         resource_type: StrView<StructTag>,
     ) -> FutureResult<Option<AnnotatedMoveStructView>> {
+        let addr = addr.0;
         let service = self.chain_state.clone();
         let playground = self.playground.clone();
         let f = async move {
@@ -110,25 +237,17 @@ where
         Box::pin(f.map_err(map_err).boxed())
     }
     fn call(&self, call: ContractCall) -> FutureResult<Vec<AnnotatedMoveValueView>> {
-        let service = self.chain_state.clone();
-        let playground = self.playground.clone();
-        let ContractCall {
-            function_id,
-            type_args,
-            args,
-        } = call;
-        let f = async move {
-            let state_root = service.state_root().await?;
-            let output = playground.call_contract(
-                state_root,
-                function_id.0.module,
-                function_id.0.function,
-                type_args.into_iter().map(|v| v.0).collect(),
-                args.into_iter().map(|v| v.0).collect(),
-            )?;
-            Ok(output.into_iter().map(Into::into).collect())
-        }
-        .map_err(map_err);
+        let f = Self::call_contract(self.chain_state.clone(), self.playground.clone(), call)
+            .map_err(map_err);
+        Box::pin(f.boxed())
+    }
+    fn call_v2(&self, call: ContractCall) -> FutureResult<Vec<AnnotatedMoveValueView>> {
+        let f = Self::call_contract(self.chain_state.clone(), self.playground.clone(), call)
+            .map_err(|e| match e.downcast::<VMStatus>() {
+                // report vm status as execution error, so the abort code is carried.
+                Ok(vm_status) => execution_failed(vm_status),
+                Err(e) => map_err(e),
+            });
         Box::pin(f.boxed())
     }
     fn dry_run(&self, txn: DryRunTransactionRequest) -> FutureResult<TransactionOutputView> {
@@ -141,7 +260,16 @@ where
             let DryRunTransactionRequest {
                 transaction,
                 sender_public_key,
+                state_overrides,
+                with_gas_breakdown,
             } = txn;
+            let state_overrides = state_overrides
+                .into_iter()
+                .map(|state_override| state_override.into_access_path_value())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| {
+                    jsonrpc_core::Error::invalid_params(format!("invalid state override: {}", e))
+                })?;
 
             let txn = txn_builder.fill_transaction(transaction).await?;
             let sender_public_key = match sender_public_key {
@@ -158,14 +286,20 @@ where
                 Some(p) => p.0,
             };
 
-            let output = playground.dry_run(
-                state_root,
-                DryRunTransaction {
-                    raw_txn: txn,
-                    public_key: sender_public_key,
-                },
-            )?;
-            Ok(output.1.into())
+            let txn = DryRunTransaction {
+                raw_txn: txn,
+                public_key: sender_public_key,
+            };
+            if with_gas_breakdown {
+                let (_, output, gas_breakdown) =
+                    playground.dry_run_with_gas_breakdown(state_root, txn, state_overrides)?;
+                let mut view: TransactionOutputView = output.into();
+                view.gas_breakdown = Some(gas_breakdown.into());
+                Ok(view)
+            } else {
+                let output = playground.dry_run_with_overrides(state_root, txn, state_overrides)?;
+                Ok(output.1.into())
+            }
         }
         .map_err(map_err);
         Box::pin(f.boxed())
@@ -1,29 +1,88 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::module::to_invalid_param_err;
 use crate::module::txfactory_rpc::TxFactoryStatusHandle;
-use jsonrpc_core::Result;
+use crate::module::{map_err, to_invalid_param_err};
+use anyhow::bail;
+use futures::future::{self, Either, FutureExt, TryFutureExt};
+use futures_timer::Delay;
+use jsonrpc_core::{ErrorCode, Result};
+use starcoin_chain_service::ChainAsyncService;
 use starcoin_config::NodeConfig;
+use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::LevelFilter;
 use starcoin_logger::{LogPattern, LoggerHandle};
 use starcoin_rpc_api::debug::DebugApi;
 use starcoin_rpc_api::types::FactoryAction;
+use starcoin_rpc_api::FutureResult;
+use starcoin_service_registry::bus::{Bus, BusService};
+use starcoin_service_registry::ServiceRef;
+use starcoin_types::system_events::{GenerateBlockEvent, NewHeadBlock};
+use starcoin_vm_types::genesis_config::ConsensusStrategy;
+use starcoin_vm_types::time::TimeServiceType;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
-pub struct DebugRpcImpl {
+/// The max blocks generated by one `dev.generate_block` call.
+const MAX_GENERATE_BLOCK_COUNT: u64 = 100;
+const GENERATE_BLOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct DebugRpcImpl<C>
+where
+    C: ChainAsyncService + 'static,
+{
     config: Arc<NodeConfig>,
     log_handle: Arc<LoggerHandle>,
+    bus: ServiceRef<BusService>,
+    chain: Option<C>,
 }
 
-impl DebugRpcImpl {
-    pub fn new(config: Arc<NodeConfig>, log_handle: Arc<LoggerHandle>) -> Self {
-        Self { config, log_handle }
+impl<C> DebugRpcImpl<C>
+where
+    C: ChainAsyncService,
+{
+    pub fn new(
+        config: Arc<NodeConfig>,
+        log_handle: Arc<LoggerHandle>,
+        bus: ServiceRef<BusService>,
+        chain: Option<C>,
+    ) -> Self {
+        Self {
+            config,
+            log_handle,
+            bus,
+            chain,
+        }
+    }
+
+    /// The dev apis control the time and the miner of the node,
+    /// so they must never be enabled on the chains other than the dev and test chain.
+    fn check_dev_chain(&self) -> Result<()> {
+        let net = self.config.net();
+        let genesis_config = net.genesis_config();
+        if (net.is_dev() || net.is_test())
+            && genesis_config.consensus() == ConsensusStrategy::Dummy
+            && genesis_config.time_service_type == TimeServiceType::MockTimeService
+        {
+            Ok(())
+        } else {
+            Err(jsonrpc_core::Error {
+                code: ErrorCode::InvalidRequest,
+                message: format!(
+                    "dev apis are only available on the dev or test chain with the dummy consensus, current chain is {}",
+                    net.id()
+                ),
+                data: None,
+            })
+        }
     }
 }
 
-impl DebugApi for DebugRpcImpl {
+impl<C> DebugApi for DebugRpcImpl<C>
+where
+    C: ChainAsyncService,
+{
     fn set_log_level(&self, logger_name: Option<String>, level: String) -> Result<()> {
         let logger_name = logger_name.and_then(|s| {
             let s = s.trim();
@@ -56,7 +115,7 @@ impl DebugApi for DebugRpcImpl {
 
     fn sleep(&self, time: u64) -> Result<()> {
         if !self.config.net().is_test() && !self.config.net().is_dev() {
-            panic!("DebugApi.panic");
+            return Err(jsonrpc_core::Error::invalid_request());
         }
 
         self.config.net().time_service().sleep(time);
@@ -64,6 +123,49 @@ impl DebugApi for DebugRpcImpl {
         Ok(())
     }
 
+    fn dev_generate_block(&self, count: u64) -> FutureResult<Vec<HashValue>> {
+        if let Err(e) = self.check_dev_chain() {
+            return Box::pin(future::err(e));
+        }
+        if count > MAX_GENERATE_BLOCK_COUNT {
+            return Box::pin(future::err(jsonrpc_core::Error::invalid_params(format!(
+                "count is too big, max block count is {}",
+                MAX_GENERATE_BLOCK_COUNT
+            ))));
+        }
+        let bus = self.bus.clone();
+        let chain = self.chain.clone();
+        let fut = async move {
+            let chain = match chain {
+                Some(chain) => chain,
+                None => bail!("The chain service is not started."),
+            };
+            let mut block_hashes = vec![];
+            for _ in 0..count {
+                let head = chain.main_head_header().await?;
+                let receiver = bus.oneshot::<NewHeadBlock>().await?;
+                bus.broadcast(GenerateBlockEvent::new(false))?;
+                let block_hash =
+                    match future::select(receiver, Delay::new(GENERATE_BLOCK_TIMEOUT)).await {
+                        Either::Left((Ok(event), _)) => event.0.block().id(),
+                        _ => {
+                            // the new head event may be missed, check the head again.
+                            let latest_head = chain.main_head_header().await?;
+                            if latest_head.number() > head.number() {
+                                latest_head.id()
+                            } else {
+                                bail!("Wait timeout for generating block {}", head.number() + 1)
+                            }
+                        }
+                    };
+                block_hashes.push(block_hash);
+            }
+            Ok(block_hashes)
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
     fn txfactory_status(&self, action: FactoryAction) -> Result<bool> {
         Ok(TxFactoryStatusHandle::handle_action(action))
     }
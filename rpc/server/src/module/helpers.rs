@@ -1,13 +1,36 @@
+use crate::module::account_not_found_onchain;
 use starcoin_account_api::AccountAsyncService;
 use starcoin_chain_service::ChainAsyncService;
 use starcoin_config::NodeConfig;
-use starcoin_rpc_api::types::TransactionRequest;
+use starcoin_rpc_api::types::{
+    ByteCodeOrScriptFunction, ModuleAbiView, TransactionRequest, DEFAULT_GAS_UNIT_PRICE,
+    DEFAULT_MAX_GAS_AMOUNT,
+};
 use starcoin_state_api::ChainStateAsyncService;
 use starcoin_txpool_api::TxPoolSyncService;
-use starcoin_types::account_config::AccountResource;
-use starcoin_types::transaction::{Module, Package, RawUserTransaction, TransactionPayload};
+use starcoin_types::account_config::{AccountResource, STC_TOKEN_CODE};
+use starcoin_types::language_storage::ModuleId;
+use starcoin_types::transaction::RawUserTransaction;
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::file_format::CompiledModule;
 use std::sync::Arc;
 
+/// Load the on-chain module, `None` if the module is not published.
+pub(crate) async fn get_module<State>(
+    chain_state: State,
+    module_id: &ModuleId,
+) -> anyhow::Result<Option<CompiledModule>>
+where
+    State: ChainStateAsyncService + 'static,
+{
+    match chain_state.get(AccessPath::from(module_id)).await? {
+        None => Ok(None),
+        Some(code) => Ok(Some(CompiledModule::deserialize(code.as_slice()).map_err(
+            |e| anyhow::format_err!("invalid on-chain module {}, {:?}", module_id, e),
+        )?)),
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct TransactionRequestFiller<Account, Pool, State, Chain> {
     pub(crate) account: Option<Account>,
@@ -26,30 +49,38 @@ where
 {
     pub(crate) async fn fill_transaction(
         &self,
-        txn_request: TransactionRequest,
+        mut txn_request: TransactionRequest,
     ) -> anyhow::Result<RawUserTransaction> {
-        let payload = if !txn_request.modules.is_empty() {
-            let modules = txn_request
-                .modules
-                .into_iter()
-                .map(|c| Module::new(c.0))
-                .collect();
-            let script_function = txn_request
-                .script
-                .map(|script_data| script_data.into_script_function())
-                .transpose()?;
-            TransactionPayload::Package(Package::new(modules, script_function)?)
-        } else {
-            let script = txn_request.script.ok_or_else(|| {
-                anyhow::anyhow!(
-                    "invalid transaction request: script should not be empty if no modules"
-                )
-            })?;
-            script.into()
-        };
+        if let Some(script) = txn_request.script.as_mut() {
+            if script.args.needs_arg_types() {
+                let function_id = match &script.code.0 {
+                    ByteCodeOrScriptFunction::ScriptFunction(function_id) => function_id,
+                    ByteCodeOrScriptFunction::ByteCode(_) => {
+                        anyhow::bail!("json args are only supported by script function")
+                    }
+                };
+                let module = get_module(self.chain_state.clone(), &function_id.module)
+                    .await?
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("cannot find module {} on chain", function_id.module)
+                    })?;
+                let abi = ModuleAbiView::new(&module, |_| None)?;
+                let function_abi =
+                    abi.function(function_id.function.as_str()).ok_or_else(|| {
+                        anyhow::anyhow!("cannot find script function {}", function_id)
+                    })?;
+                let arg_types: Vec<_> = function_abi
+                    .args
+                    .iter()
+                    .map(|arg| arg.type_tag.0.clone())
+                    .collect();
+                script.resolve_args(&arg_types)?;
+            }
+        }
+        let payload = txn_request.to_payload()?;
 
         let sender = match txn_request.sender {
-            Some(s) => s,
+            Some(s) => s.0,
             None => match self.account.as_ref() {
                 None => anyhow::bail!("sender "),
                 Some(account_service) => {
@@ -63,6 +94,7 @@ where
         };
         let next_seq_number = match txn_request
             .sequence_number
+            .map(|n| n.0)
             .or_else(|| self.pool.next_sequence_number(sender))
         {
             Some(n) => n,
@@ -73,13 +105,20 @@ where
                 .await?
             {
                 Some(r) => r.sequence_number(),
-                None => anyhow::bail!("cannot find account {} onchain", sender),
+                None => return Err(account_not_found_onchain(sender)),
             },
         };
-        let max_gas_amount = txn_request.max_gas_amount.unwrap_or(1000000); // default 10_00000
-        let max_gas_price = txn_request.gas_unit_price.unwrap_or(1);
+        let max_gas_amount = txn_request
+            .max_gas_amount
+            .map(|v| v.0)
+            .unwrap_or(DEFAULT_MAX_GAS_AMOUNT);
+        let max_gas_price = txn_request
+            .gas_unit_price
+            .map(|v| v.0)
+            .unwrap_or(DEFAULT_GAS_UNIT_PRICE);
         let expire = txn_request
             .expiration_timestamp_secs
+            .map(|v| v.0)
             .unwrap_or_else(|| self.node_config.net().time_service().now_secs() + 60 * 60 * 12); // default to 0.5d
 
         let chain_id = self.chain.main_status().await?.head().chain_id();
@@ -93,15 +132,27 @@ where
             }
         }
 
-        let raw_txn = RawUserTransaction::new_with_default_gas_token(
-            sender,
-            next_seq_number,
-            payload,
-            max_gas_amount,
-            max_gas_price,
-            expire,
-            chain_id,
-        );
+        let raw_txn = match txn_request.gas_token_code {
+            Some(gas_token_code) if gas_token_code.0 != *STC_TOKEN_CODE => RawUserTransaction::new(
+                sender,
+                next_seq_number,
+                payload,
+                max_gas_amount,
+                max_gas_price,
+                expire,
+                chain_id,
+                gas_token_code.to_string(),
+            ),
+            _ => RawUserTransaction::new_with_default_gas_token(
+                sender,
+                next_seq_number,
+                payload,
+                max_gas_amount,
+                max_gas_price,
+                expire,
+                chain_id,
+            ),
+        };
         Ok(raw_txn)
     }
 }
@@ -2,9 +2,13 @@
 // SPDX-License-Identifier: Apache-2
 
 use crate::module::{map_err, RpcError};
+use futures::{FutureExt, TryFutureExt};
 use jsonrpc_core::{ErrorCode, Result};
-use starcoin_miner::MinerService;
+use starcoin_crypto::HashValue;
+use starcoin_miner::{GetWorkRequest, MinerService, SubmitWorkRequest};
 use starcoin_rpc_api::miner::MinerApi;
+use starcoin_rpc_api::types::{MintBlockTemplateView, StrView};
+use starcoin_rpc_api::FutureResult;
 use starcoin_service_registry::ServiceRef;
 use starcoin_types::block::BlockHeaderExtra;
 use starcoin_types::system_events::SubmitSealEvent;
@@ -41,4 +45,38 @@ impl MinerApi for MinerRpcImpl {
             })
             .map_err(|e| map_err(e.into()))
     }
+
+    fn get_work(&self) -> FutureResult<Option<MintBlockTemplateView>> {
+        let service = self.miner_service.clone();
+        let fut = async move {
+            let work = service.send(GetWorkRequest).await??;
+            Ok(work.map(|work| MintBlockTemplateView {
+                parent_hash: work.parent_hash,
+                minting_blob: StrView(work.minting_blob),
+                difficulty: StrView(work.difficulty),
+                block_number: StrView(work.block_number),
+                strategy: work.strategy,
+            }))
+        };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn submit_work(
+        &self,
+        minting_blob: StrView<Vec<u8>>,
+        nonce: u32,
+        extra: BlockHeaderExtra,
+    ) -> FutureResult<HashValue> {
+        let service = self.miner_service.clone();
+        let fut = async move {
+            service
+                .send(SubmitWorkRequest {
+                    minting_blob: minting_blob.0,
+                    nonce,
+                    extra,
+                })
+                .await?
+        };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
 }
@@ -35,7 +35,17 @@ use anyhow::Error;
 use hex::FromHexError;
 use jsonrpc_core::ErrorCode;
 use starcoin_account_api::error::AccountError;
+use starcoin_crypto::HashValue;
+use starcoin_dev::disassemble::DisassembleError;
+use starcoin_miner::MinerError;
+use starcoin_rpc_api::errors::{
+    AccountErrorData, BlockPrunedData, InvalidBytecodeData, RpcErrorCode,
+};
 use starcoin_rpc_api::types::TransactionVMStatus;
+use starcoin_state_tree::MissingNodeError;
+use starcoin_storage::errors::BlockPrunedError;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block::BlockNumber;
 use starcoin_vm_types::transaction::{CallError, TransactionError, TransactionStatus};
 use starcoin_vm_types::vm_status::VMStatus;
 
@@ -55,6 +65,10 @@ pub fn map_err(err: anyhow::Error) -> jsonrpc_core::Error {
         err.downcast::<MailboxError>().unwrap().into()
     } else if err.is::<VMStatus>() {
         err.downcast::<VMStatus>().unwrap().into()
+    } else if err.is::<MinerError>() {
+        err.downcast::<MinerError>().unwrap().into()
+    } else if err.is::<BlockPrunedError>() {
+        err.downcast::<BlockPrunedError>().unwrap().into()
     } else {
         err.into()
     };
@@ -89,20 +103,54 @@ impl From<anyhow::Error> for RpcError {
     }
 }
 
-const TXN_ERROR_BASE: i64 = -50000;
-const ACCOUNT_ERROR_BASE: i64 = -60000;
+impl From<MinerError> for RpcError {
+    fn from(err: MinerError) -> Self {
+        let message = err.to_string();
+        let rpc_error = match err {
+            MinerError::StaleWork(_) => RpcErrorCode::StaleWork.error(message),
+            MinerError::InvalidSeal(..) => jsonrpc_core::Error {
+                code: ErrorCode::InvalidParams,
+                message,
+                data: None,
+            },
+        };
+        RpcError(rpc_error)
+    }
+}
+
+impl From<BlockPrunedError> for RpcError {
+    fn from(err: BlockPrunedError) -> Self {
+        RpcError(RpcErrorCode::BlockPruned.error_with_data(
+            err.to_string(),
+            &BlockPrunedData {
+                number: err.number.into(),
+                pruned_before: err.pruned_before.into(),
+            },
+        ))
+    }
+}
 
 impl From<AccountError> for RpcError {
     fn from(err: AccountError) -> Self {
+        let message = err.to_string();
         let rpc_error = match err {
-            AccountError::StoreError(error) => jsonrpc_core::Error {
-                code: ErrorCode::ServerError(ACCOUNT_ERROR_BASE),
-                message: error.to_string(),
-                data: None,
-            },
-            e => jsonrpc_core::Error {
+            AccountError::StoreError(error) => {
+                RpcErrorCode::AccountStoreError.error(error.to_string())
+            }
+            AccountError::AccountNotExist(address) => RpcErrorCode::AccountNotFound
+                .error_with_data(message, &AccountErrorData { address }),
+            AccountError::AccountLocked(address) => {
+                RpcErrorCode::AccountLocked.error_with_data(message, &AccountErrorData { address })
+            }
+            AccountError::ExternalSignerTimeout(address) => RpcErrorCode::ExternalSignerTimeout
+                .error_with_data(message, &AccountErrorData { address }),
+            AccountError::ExternalSignerError(address, _) => RpcErrorCode::ExternalSignerError
+                .error_with_data(message, &AccountErrorData { address }),
+            AccountError::InvalidPassword(address) => RpcErrorCode::InvalidPassword
+                .error_with_data(message, &AccountErrorData { address }),
+            _ => jsonrpc_core::Error {
                 code: ErrorCode::InvalidParams,
-                message: e.to_string(),
+                message,
                 data: None,
             },
         };
@@ -113,7 +161,7 @@ impl From<AccountError> for RpcError {
 impl From<TransactionError> for RpcError {
     fn from(err: TransactionError) -> Self {
         let err_message = err.to_string();
-        let (err_code, err_data) = match err {
+        let rpc_error = match err {
             TransactionError::AlreadyImported
             | TransactionError::Old
             | TransactionError::InsufficientGasPrice { .. }
@@ -127,33 +175,86 @@ impl From<TransactionError> for RpcError {
             | TransactionError::InvalidChainId
             | TransactionError::InvalidSignature(..)
             | TransactionError::NotAllowed
-            | TransactionError::TooBig => (ErrorCode::InvalidParams, None),
-            TransactionError::LimitReached => (ErrorCode::ServerError(TXN_ERROR_BASE), None),
+            | TransactionError::TooBig => RpcErrorCode::TxnValidationFailed.error(err_message),
+            TransactionError::LimitReached => RpcErrorCode::TxnPoolFull.error(err_message),
+            TransactionError::ShuttingDown => RpcErrorCode::NodeShuttingDown.error(err_message),
             TransactionError::CallErr(call_err) => match call_err {
-                CallError::TransactionNotFound => (ErrorCode::InvalidParams, None),
+                CallError::TransactionNotFound => jsonrpc_core::Error {
+                    code: ErrorCode::InvalidParams,
+                    message: err_message,
+                    data: None,
+                },
                 CallError::StatePruned | CallError::StateCorrupt => {
-                    (ErrorCode::ServerError(TXN_ERROR_BASE + 1), None)
+                    RpcErrorCode::StateNotFound.error(err_message)
+                }
+                CallError::ExecutionError(vm_status) => {
+                    // translate to jsonrpc types
+                    let status = TransactionVMStatus::from(TransactionStatus::from(vm_status));
+                    let code = if matches!(status, TransactionVMStatus::Discard { .. }) {
+                        RpcErrorCode::TxnValidationFailed
+                    } else {
+                        RpcErrorCode::ExecutionFailed
+                    };
+                    code.error_with_data(err_message, &status)
                 }
-                CallError::ExecutionError(vm_status) => (
-                    ErrorCode::ServerError(TXN_ERROR_BASE + 2),
-                    Some(
-                        // translate to jsonrpc types
-                        serde_json::to_value(TransactionVMStatus::from(TransactionStatus::from(
-                            vm_status,
-                        )))
-                        .expect("vm status to json should be ok"),
-                    ),
-                ),
             },
         };
-        RpcError(jsonrpc_core::Error {
-            code: err_code,
-            message: err_message,
-            data: err_data,
-        })
+        RpcError(rpc_error)
     }
 }
 
+/// Report the vm status as an execution failure, whether it is discarded or not.
+pub(crate) fn execution_failed(vm_status: VMStatus) -> jsonrpc_core::Error {
+    let message = vm_status.to_string();
+    RpcErrorCode::ExecutionFailed.error_with_data(
+        message,
+        &TransactionVMStatus::from(TransactionStatus::from(vm_status)),
+    )
+}
+
+pub(crate) fn account_not_found_onchain(address: AccountAddress) -> anyhow::Error {
+    RpcErrorCode::AccountNotFound
+        .error_with_data(
+            format!("cannot find account {} onchain", address),
+            &AccountErrorData { address },
+        )
+        .into()
+}
+
+/// Map the error of reading the state of the historical block, only the missing state node,
+/// which means the state is pruned or corrupted, is reported as `StateNotFound`.
+pub(crate) fn state_not_found_at(number: BlockNumber, err: anyhow::Error) -> anyhow::Error {
+    if !err.chain().any(|cause| cause.is::<MissingNodeError>()) {
+        return err;
+    }
+    RpcErrorCode::StateNotFound
+        .error(format!(
+            "the state at block {} is pruned or corrupted, {}",
+            number, err
+        ))
+        .into()
+}
+
+/// The state of the root can not be read, usually because it is pruned.
+pub(crate) fn state_not_found_by_root(state_root: HashValue) -> anyhow::Error {
+    RpcErrorCode::StateNotFound
+        .error(format!(
+            "the state at root {} is pruned or not exist",
+            state_root
+        ))
+        .into()
+}
+
+pub(crate) fn invalid_bytecode(err: DisassembleError) -> anyhow::Error {
+    let status_code = match &err {
+        DisassembleError::InvalidBytecode { status_code, .. } => Some(*status_code as u64),
+        DisassembleError::DisassembleFailed(_) => None,
+    };
+    RpcErrorCode::InvalidBytecode
+        .error_with_data(err.to_string(), &InvalidBytecodeData { status_code })
+        .into()
+}
+
 impl From<hex::FromHexError> for RpcError {
     fn from(err: FromHexError) -> Self {
         RpcError(jsonrpc_core::Error {
@@ -207,3 +308,76 @@ where
     let message = format!("Invalid param error: {:?}", anyhow_err);
     jsonrpc_core::Error::invalid_params(message)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_pruned_error() {
+        let err = map_err(
+            BlockPrunedError {
+                number: 10,
+                pruned_before: 100,
+            }
+            .into(),
+        );
+        assert_eq!(RpcErrorCode::of(&err), Some(RpcErrorCode::BlockPruned));
+        let data: BlockPrunedData = serde_json::from_value(err.data.unwrap()).unwrap();
+        assert_eq!(
+            data,
+            BlockPrunedData {
+                number: 10.into(),
+                pruned_before: 100.into(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_txpool_validation_error() {
+        for txn_err in vec![
+            TransactionError::AlreadyImported,
+            TransactionError::Old,
+            TransactionError::InsufficientGasPrice { minimal: 2, got: 1 },
+            TransactionError::InvalidSignature("test".to_string()),
+        ] {
+            let err = map_err(txn_err.into());
+            assert_eq!(
+                RpcErrorCode::of(&err),
+                Some(RpcErrorCode::TxnValidationFailed)
+            );
+            assert!(err.data.is_none());
+        }
+        let err = map_err(TransactionError::LimitReached.into());
+        assert_eq!(RpcErrorCode::of(&err), Some(RpcErrorCode::TxnPoolFull));
+    }
+
+    #[test]
+    fn test_state_not_found_at() {
+        let err = map_err(state_not_found_at(
+            1,
+            MissingNodeError(HashValue::random()).into(),
+        ));
+        assert_eq!(RpcErrorCode::of(&err), Some(RpcErrorCode::StateNotFound));
+
+        let err = map_err(state_not_found_at(
+            1,
+            anyhow::Error::new(MissingNodeError(HashValue::random())).context("read state"),
+        ));
+        assert_eq!(RpcErrorCode::of(&err), Some(RpcErrorCode::StateNotFound));
+
+        // the other errors, such as the storage io error, are not reported as the pruned state.
+        let err = map_err(state_not_found_at(1, anyhow::format_err!("io error")));
+        assert_eq!(RpcErrorCode::of(&err), None);
+        assert_eq!(err.code, ErrorCode::InternalError);
+        let err = map_err(state_not_found_at(
+            1,
+            BlockPrunedError {
+                number: 1,
+                pruned_before: 10,
+            }
+            .into(),
+        ));
+        assert_eq!(RpcErrorCode::of(&err), Some(RpcErrorCode::BlockPruned));
+    }
+}
@@ -54,7 +54,7 @@ impl NetworkManagerApi for NetworkManagerRpcImpl {
 
     fn add_peer(&self, peer: String) -> FutureResult<()> {
         let service = self.service.clone();
-        let fut = async move { service.add_peer(peer) }.map_err(map_err);
+        let fut = async move { service.add_peer(peer).await }.map_err(map_err);
         Box::pin(fut.boxed())
     }
 
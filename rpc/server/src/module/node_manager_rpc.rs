@@ -2,27 +2,159 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::module::map_err;
+use anyhow::{bail, format_err};
+use futures::channel::oneshot;
 use futures::future::TryFutureExt;
 use futures::FutureExt;
+use starcoin_chain::{BlockChain, ChainReader};
+use starcoin_config::{ConfigReloadResult, NodeConfig};
+use starcoin_crypto::HashValue;
+use starcoin_logger::prelude::*;
+use starcoin_network::NetworkServiceRef;
 use starcoin_node_api::node_service::NodeAsyncService;
-use starcoin_rpc_api::node_manager::NodeManagerApi;
+use starcoin_rpc_api::node::{CheckpointsReport, ColumnFamilySizeView, SystemInfo};
+use starcoin_rpc_api::node_manager::{NodeManagerApi, DEFAULT_SHUTDOWN_GRACE_SECS};
+use starcoin_rpc_api::types::pubsub::EventFilter;
+use starcoin_rpc_api::types::WebhookView;
 use starcoin_rpc_api::FutureResult;
-use starcoin_service_registry::{ServiceInfo, ServiceStatus};
+use starcoin_service_registry::{ServiceInfo, ServiceRef, ServiceStatus};
+use starcoin_storage::{BlockPruneStore, BlockStore, EventCursorStore, Storage, TokenHolderStore};
+use starcoin_types::checkpoint::CheckpointMismatch;
+use starcoin_types::event_cursor::EventCursorId;
+use starcoin_types::filter::Filter;
+use starcoin_types::peer_info::PeerId;
+use starcoin_types::webhook::{WebhookFilter, WebhookRegistration};
+use starcoin_webhook::{ensure_public_target, WebhookAsyncService, WebhookService};
+use std::convert::TryInto;
+use std::sync::Arc;
+use std::time::Duration;
 
 pub struct NodeManagerRpcImpl<S>
 where
     S: NodeAsyncService + 'static,
 {
+    config: Arc<NodeConfig>,
     service: S,
+    network: NetworkServiceRef,
+    storage: Arc<Storage>,
+    webhook_service: Option<ServiceRef<WebhookService>>,
 }
 
 impl<S> NodeManagerRpcImpl<S>
 where
     S: NodeAsyncService,
 {
-    pub fn new(service: S) -> Self {
-        Self { service }
+    pub fn new(
+        config: Arc<NodeConfig>,
+        service: S,
+        network: NetworkServiceRef,
+        storage: Arc<Storage>,
+        webhook_service: Option<ServiceRef<WebhookService>>,
+    ) -> Self {
+        Self {
+            config,
+            service,
+            network,
+            storage,
+            webhook_service,
+        }
+    }
+}
+
+fn rebuild_token_holder_index(config: &NodeConfig, storage: Arc<Storage>) -> anyhow::Result<u64> {
+    if !storage.is_token_holder_index_enabled() {
+        bail!("The token holder index is disabled, enable it by the `--token-holder-index true` option.");
+    }
+    let head = storage
+        .get_startup_info()?
+        .ok_or_else(|| format_err!("Startup info should exist."))?
+        .main;
+    let count = BlockChain::new(config.net().time_service(), head, storage)?
+        .rebuild_token_holder_index()?;
+    info!(
+        "Rebuild token holder index at block {}, {} holders indexed.",
+        head, count
+    );
+    Ok(count)
+}
+
+fn verify_local_checkpoints(
+    config: &NodeConfig,
+    storage: Arc<Storage>,
+) -> anyhow::Result<CheckpointsReport> {
+    let startup_info = storage
+        .get_startup_info()?
+        .ok_or_else(|| format_err!("Startup info should exist."))?;
+    let chain = BlockChain::new(config.net().time_service(), startup_info.main, storage)?;
+    let head_number = chain.current_header().number();
+    let checkpoints = match config.sync.checkpoints()? {
+        Some(checkpoints) => checkpoints,
+        None => {
+            return Ok(CheckpointsReport {
+                head_number: head_number.into(),
+                ..Default::default()
+            })
+        }
+    };
+    let mut report = CheckpointsReport {
+        latest_checkpoint: checkpoints
+            .latest()
+            .map(|checkpoint| checkpoint.number.into()),
+        head_number: head_number.into(),
+        ..Default::default()
+    };
+    for checkpoint in checkpoints.range(0, head_number) {
+        let header = chain
+            .get_header_by_number(checkpoint.number)?
+            .ok_or_else(|| {
+                format_err!("Can not find block header by number {}", checkpoint.number)
+            })?;
+        match CheckpointMismatch::check(checkpoint, &header) {
+            Some(mismatch) => report.mismatches.push(mismatch.into()),
+            None => report.verified += 1,
+        }
     }
+    Ok(report)
+}
+
+fn collect_system_info(config: &NodeConfig, storage: &Storage) -> anyhow::Result<SystemInfo> {
+    let data_dir = config.data_dir();
+    let prune_info = storage.get_prune_info()?.unwrap_or_default();
+    let storage = storage.db_size_report()?.unwrap_or_default();
+    let disk_usage = starcoin_system::get_disk_usage(data_dir).ok();
+    Ok(SystemInfo {
+        data_dir: data_dir.to_string_lossy().to_string(),
+        storage_size: storage.total_size(),
+        storage: storage
+            .column_families
+            .into_iter()
+            .map(|(cf_name, size)| {
+                (
+                    cf_name,
+                    ColumnFamilySizeView {
+                        sst_files_size: size.sst_files_size,
+                        live_data_size: size.live_data_size,
+                        mem_tables_size: size.mem_tables_size,
+                        estimate_num_keys: size.estimate_num_keys,
+                    },
+                )
+            })
+            .collect(),
+        disk_total: disk_usage.map(|usage| usage.total),
+        disk_free: disk_usage.map(|usage| usage.available),
+        disk_used: disk_usage.map(|usage| usage.used()),
+        open_fds: starcoin_system::get_open_fds().ok(),
+        rss: starcoin_system::get_process_rss().ok(),
+        uptime_secs: starcoin_system::get_process_uptime()
+            .ok()
+            .map(|uptime| uptime.as_secs()),
+        prune_blocks_before: config
+            .storage
+            .prune_blocks_before()
+            .map(|horizon| horizon.to_string()),
+        pruned_before: prune_info.pruned_before.into(),
+        pruned_bytes: prune_info.pruned_bytes,
+    })
 }
 
 impl<S> NodeManagerApi for NodeManagerRpcImpl<S>
@@ -74,4 +206,142 @@ where
         .map_err(map_err);
         Box::pin(fut.boxed())
     }
+
+    fn shutdown(&self, grace_secs: Option<u64>) -> FutureResult<()> {
+        let service = self.service.clone();
+        let grace_period = Duration::from_secs(grace_secs.unwrap_or(DEFAULT_SHUTDOWN_GRACE_SECS));
+        let fut = async move { service.shutdown(grace_period).await }.map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn register_webhook(
+        &self,
+        url: String,
+        filter: WebhookFilter,
+    ) -> FutureResult<WebhookRegistration> {
+        let webhook_service = self.webhook_service.clone();
+        let allow_private_targets = self.config.webhook.allow_private_targets();
+        let fut = async move {
+            let webhook_service =
+                webhook_service.ok_or_else(|| format_err!("Webhook service is disabled."))?;
+            // resolve the host here, so a slow dns lookup does not block the webhook service.
+            if !allow_private_targets {
+                ensure_public_target(url.as_str()).await?;
+            }
+            webhook_service.register_webhook(url, filter).await
+        };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn list_webhooks(&self) -> FutureResult<Vec<WebhookView>> {
+        let webhook_service = self.webhook_service.clone();
+        let fut = async move {
+            match webhook_service {
+                Some(webhook_service) => webhook_service.list_webhooks().await,
+                None => Ok(vec![]),
+            }
+        };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn remove_webhook(&self, id: HashValue) -> FutureResult<()> {
+        let webhook_service = self.webhook_service.clone();
+        let fut = async move {
+            let webhook_service =
+                webhook_service.ok_or_else(|| format_err!("Webhook service is disabled."))?;
+            webhook_service.remove_webhook(id).await
+        };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn rebuild_token_holder_index(&self) -> FutureResult<u64> {
+        let config = self.config.clone();
+        let storage = self.storage.clone();
+        let (tx, rx) = oneshot::channel();
+        // the rebuild scans the whole state, run it in a dedicated thread to not block the rpc executor.
+        std::thread::spawn(move || {
+            let _ = tx.send(rebuild_token_holder_index(config.as_ref(), storage));
+        });
+        let fut = async move { rx.await? };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn create_event_cursor(&self, filter: EventFilter) -> FutureResult<EventCursorId> {
+        let config = self.config.clone();
+        let storage = self.storage.clone();
+        let fut = async move {
+            if filter.limit.is_some() {
+                return Err(jsonrpc_core::Error::invalid_params(
+                    "limit is not supported by event cursors, use max_items of chain.poll_events",
+                )
+                .into());
+            }
+            let decode = filter.decode;
+            let filter: Filter = filter.try_into()?;
+            let acked = filter.cursor;
+            let now_secs = config.net().time_service().now_secs();
+            storage.remove_expired_event_cursors(
+                now_secs,
+                config.rpc.event_cursor_idle_timeout(),
+                config.rpc.event_cursor_max_lifetime(),
+            )?;
+            storage.create_event_cursor(
+                filter,
+                decode,
+                acked,
+                now_secs,
+                config.rpc.max_event_cursors(),
+            )
+        };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn add_peer(&self, multiaddr: String) -> FutureResult<()> {
+        let network = self.network.clone();
+        let fut = async move { network.add_peer(multiaddr).await };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn remove_peer(&self, peer_id: PeerId) -> FutureResult<()> {
+        let network = self.network.clone();
+        let fut = async move { network.remove_peer(peer_id).await };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn ban_peer(&self, peer_id: PeerId, duration_secs: Option<u64>) -> FutureResult<()> {
+        let network = self.network.clone();
+        let fut = async move { network.ban_peer(peer_id, duration_secs).await };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn config(&self) -> FutureResult<serde_json::Value> {
+        let service = self.service.clone();
+        let fut = async move { service.get_config().await?.to_redacted_json() };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn reload_config(&self) -> FutureResult<ConfigReloadResult> {
+        let service = self.service.clone();
+        let fut = async move { service.reload_config().await };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn trace_rpc(&self, enable: bool, method_filter: Option<String>) -> jsonrpc_core::Result<()> {
+        starcoin_rpc_middleware::set_rpc_trace(enable, method_filter);
+        Ok(())
+    }
+
+    fn system_info(&self) -> FutureResult<SystemInfo> {
+        let config = self.config.clone();
+        let storage = self.storage.clone();
+        let fut = async move { collect_system_info(config.as_ref(), storage.as_ref()) };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn verify_checkpoints(&self) -> FutureResult<CheckpointsReport> {
+        let config = self.config.clone();
+        let storage = self.storage.clone();
+        let fut = async move { verify_local_checkpoints(config.as_ref(), storage) };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
 }
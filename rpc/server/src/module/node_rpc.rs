@@ -1,27 +1,42 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::health::{HealthMonitor, WatchdogMonitor};
 use crate::module::map_err;
+use anyhow::format_err;
 use futures::future::TryFutureExt;
 use futures::FutureExt;
 use jsonrpc_core::Result;
+use network_api::messages::DiscoveredSeed;
 use network_api::PeerProvider;
 use starcoin_config::NodeConfig;
 use starcoin_network::NetworkServiceRef;
-use starcoin_rpc_api::node::{NodeApi, NodeInfo};
-use starcoin_rpc_api::types::PeerInfoView;
+use starcoin_rpc_api::node::{HealthView, NodeApi, NodeInfo, WatchdogReport};
+use starcoin_rpc_api::types::{MetricView, PeerInfoView};
 use starcoin_rpc_api::FutureResult;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 pub struct NodeRpcImpl {
     config: Arc<NodeConfig>,
     service: Option<NetworkServiceRef>,
+    health_monitor: Option<HealthMonitor>,
+    watchdog_monitor: Option<WatchdogMonitor>,
 }
 
 impl NodeRpcImpl {
-    pub fn new(config: Arc<NodeConfig>, service: Option<NetworkServiceRef>) -> Self {
-        Self { config, service }
+    pub fn new(
+        config: Arc<NodeConfig>,
+        service: Option<NetworkServiceRef>,
+        health_monitor: Option<HealthMonitor>,
+        watchdog_monitor: Option<WatchdogMonitor>,
+    ) -> Self {
+        Self {
+            config,
+            service,
+            health_monitor,
+            watchdog_monitor,
+        }
     }
 }
 
@@ -51,19 +66,58 @@ impl NodeApi for NodeRpcImpl {
         Box::pin(fut.map_err(map_err).boxed())
     }
 
+    fn health(&self) -> Result<HealthView> {
+        self.health_monitor
+            .as_ref()
+            .map(HealthMonitor::health)
+            .ok_or_else(|| map_err(format_err!("Health monitor is disabled.")))
+    }
+
+    fn watchdog_status(&self) -> Result<Option<WatchdogReport>> {
+        Ok(self
+            .watchdog_monitor
+            .as_ref()
+            .and_then(WatchdogMonitor::report))
+    }
+
     fn peers(&self) -> FutureResult<Vec<PeerInfoView>> {
         let service = self.service.clone().unwrap();
         let fut = async move {
             let peers = service.peer_set().await?;
-            Ok(peers
+            let banned_peers = service.banned_peers().await?;
+            let mut peers = peers
                 .into_iter()
                 .map(PeerInfoView::from)
-                .collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            for banned_peer in banned_peers {
+                if let Some(peer) = peers
+                    .iter_mut()
+                    .find(|peer| peer.peer_id == banned_peer.peer_id)
+                {
+                    peer.banned_until = Some(banned_peer.banned_until);
+                } else if let Some(peer_info) = banned_peer.peer_info {
+                    peers.push(
+                        PeerInfoView::from(peer_info)
+                            .with_banned_until(Some(banned_peer.banned_until)),
+                    );
+                }
+            }
+            Ok(peers)
         };
         Box::pin(fut.map_err(map_err).boxed())
     }
 
-    fn metrics(&self) -> Result<HashMap<String, String>> {
-        Ok(starcoin_metrics::get_all_metrics())
+    fn discovered_seeds(&self) -> FutureResult<Vec<DiscoveredSeed>> {
+        let service = self.service.clone().unwrap();
+        let fut = async move { service.discovered_seeds().await };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn metrics(&self, filter: Option<String>) -> Result<BTreeMap<String, MetricView>> {
+        let metric_families = starcoin_metrics::default_registry().gather();
+        Ok(MetricView::from_metric_families(
+            &metric_families,
+            filter.as_deref(),
+        ))
     }
 }
@@ -1,30 +1,38 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::metrics::PUBSUB_SUBSCRIPTIONS_GAUGE;
+use crate::module::map_err;
 use anyhow::Result;
 use futures::channel::mpsc;
-use futures::future::AbortHandle;
-use futures::StreamExt;
+use futures::future::{AbortHandle, TryFutureExt};
+use futures::{FutureExt, StreamExt};
 use jsonrpc_pubsub::typed::Subscriber;
-use jsonrpc_pubsub::SubscriptionId;
+use jsonrpc_pubsub::{Session, SubscriptionId};
 use parking_lot::RwLock;
-use starcoin_chain_notify::message::{Event, Notification, ThinBlock};
+use starcoin_chain_notify::message::{
+    BlockNotification, BlockNotificationKind, Event, NewHeadEventNotification, Notification,
+};
+use starcoin_config::NodeConfig;
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_miner::{MinerClientSubscribeRequest, MinerService};
+use starcoin_rpc_api::errors::{RpcErrorCode, SubscriptionLimitData, SubscriptionLimitScope};
 use starcoin_rpc_api::metadata::Metadata;
-use starcoin_rpc_api::types::pubsub::MintBlock;
-use starcoin_rpc_api::types::{BlockView, TransactionEventView};
+use starcoin_rpc_api::types::pubsub::{BlockNotificationView, MintBlock, SubscriptionView};
+use starcoin_rpc_api::types::{BlockView, StrView, TransactionEventView};
+use starcoin_rpc_api::FutureResult;
 use starcoin_rpc_api::{errors, pubsub::StarcoinPubSub, types::pubsub};
 use starcoin_service_registry::{
     ActorService, EventHandler as ActorEventHandler, ServiceContext, ServiceFactory,
     ServiceHandler, ServiceRef, ServiceRequest,
 };
 use starcoin_txpool::TxPoolService;
-use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_txpool_api::{TxPoolSyncService, TxnStatusFullEvent};
 use starcoin_types::filter::Filter;
 use starcoin_types::system_events::MintBlockEvent;
-use std::collections::HashMap;
+use starcoin_types::transaction::TxStatus;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
 use std::fmt::Debug;
 use std::sync::mpsc::TrySendError;
@@ -61,15 +69,28 @@ fn map_send_err<T>(err: &TrySendError<T>) -> jsonrpc_core::Error {
 impl PubSubImpl {
     fn inner_subscribe(
         &self,
-        _meta: Metadata,
+        meta: Metadata,
         subscriber: Subscriber<pubsub::Result>,
         kind: pubsub::Kind,
         params: Option<pubsub::Params>,
     ) -> Result<(), (Subscriber<pubsub::Result>, jsonrpc_core::Error)> {
+        let session = match meta.session {
+            Some(session) => session,
+            None => return Err((subscriber, jsonrpc_core::Error::invalid_request())),
+        };
+        let filter = match &params {
+            Some(pubsub::Params::Events(filter)) => Some(filter.clone()),
+            _ => None,
+        };
+        let info = SubscriptionInfo {
+            session,
+            kind: kind.clone(),
+            filter,
+        };
         match (kind, params) {
             (pubsub::Kind::NewHeads, None) => self
                 .service
-                .try_send(SubscribeNewHeads(subscriber))
+                .try_send(SubscribeNewHeads(subscriber, info))
                 .map_err(|e| {
                     let msg = map_send_err(&e);
                     (
@@ -86,7 +107,7 @@ impl PubSubImpl {
             )),
             (pubsub::Kind::NewPendingTransactions, None) => self
                 .service
-                .try_send(SubscribeNewPendingTxns { subscriber })
+                .try_send(SubscribeNewPendingTxns { subscriber, info })
                 .map_err(|e| {
                     let msg = map_send_err(&e);
                     (
@@ -101,12 +122,55 @@ impl PubSubImpl {
                 subscriber,
                 errors::invalid_params("newPendingTransactions", "Expected no parameters."),
             )),
+            (pubsub::Kind::DroppedTransactions, None) => self
+                .service
+                .try_send(SubscribeDroppedTxns {
+                    subscriber,
+                    info,
+                    evicted_only: false,
+                })
+                .map_err(|e| {
+                    let msg = map_send_err(&e);
+                    (
+                        match e {
+                            TrySendError::Disconnected(t) => t.subscriber,
+                            TrySendError::Full(t) => t.subscriber,
+                        },
+                        msg,
+                    )
+                }),
+            (pubsub::Kind::DroppedTransactions, _) => Err((
+                subscriber,
+                errors::invalid_params("droppedTransactions", "Expected no parameters."),
+            )),
+            (pubsub::Kind::EvictedTransactions, None) => self
+                .service
+                .try_send(SubscribeDroppedTxns {
+                    subscriber,
+                    info,
+                    evicted_only: true,
+                })
+                .map_err(|e| {
+                    let msg = map_send_err(&e);
+                    (
+                        match e {
+                            TrySendError::Disconnected(t) => t.subscriber,
+                            TrySendError::Full(t) => t.subscriber,
+                        },
+                        msg,
+                    )
+                }),
+            (pubsub::Kind::EvictedTransactions, _) => Err((
+                subscriber,
+                errors::invalid_params("evictedTransactions", "Expected no parameters."),
+            )),
             (pubsub::Kind::Events, Some(pubsub::Params::Events(filter))) => {
                 match filter.try_into() {
                     Ok(f) => self
                         .service
                         .try_send(SubscribeEvents {
                             subscriber,
+                            info,
                             filter: f,
                         })
                         .map_err(|e| {
@@ -128,7 +192,7 @@ impl PubSubImpl {
             )),
             (pubsub::Kind::NewMintBlock, _) => self
                 .service
-                .try_send(SubscribeMintBlock(subscriber))
+                .try_send(SubscribeMintBlock(subscriber, info))
                 .map_err(|e| {
                     let msg = map_send_err(&e);
                     (
@@ -147,12 +211,12 @@ impl StarcoinPubSub for PubSubImpl {
     type Metadata = Metadata;
     fn subscribe(
         &self,
-        _meta: Metadata,
+        meta: Metadata,
         subscriber: Subscriber<pubsub::Result>,
         kind: pubsub::Kind,
         params: Option<pubsub::Params>,
     ) {
-        if let Err((subscriber, error)) = self.inner_subscribe(_meta, subscriber, kind, params) {
+        if let Err((subscriber, error)) = self.inner_subscribe(meta, subscriber, kind, params) {
             let _ = subscriber.reject(error);
         }
     }
@@ -176,6 +240,19 @@ impl StarcoinPubSub for PubSubImpl {
             }),
         }
     }
+
+    fn list_subscriptions(&self, meta: Metadata) -> FutureResult<Vec<SubscriptionView>> {
+        let service = self.service.clone();
+        // the connections without session, such as http, can not subscribe.
+        let connection = meta.session.as_ref().map(connection_id);
+        let fut = async move {
+            match connection {
+                Some(connection) => service.send(ListSubscriptions(connection)).await,
+                None => Ok(vec![]),
+            }
+        };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
 }
 
 pub struct PubSubServiceFactory;
@@ -183,13 +260,39 @@ pub struct PubSubServiceFactory;
 impl ServiceFactory<PubSubService> for PubSubServiceFactory {
     fn create(ctx: &mut ServiceContext<PubSubService>) -> Result<PubSubService> {
         let miner_service = ctx.service_ref::<MinerService>()?.clone();
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
         Ok(PubSubService::new(
             ctx.get_shared::<TxPoolService>()?,
             miner_service,
+            config.rpc.api_quotas.max_subscriptions(),
+            config.rpc.api_quotas.max_subscriptions_per_connection(),
         ))
     }
 }
 
+/// The connection is identified by the address of its pubsub session, which lives as long as the
+/// connection, the subscriptions of a connection are removed as soon as the session is dropped.
+type ConnectionId = usize;
+
+fn connection_id(session: &Arc<Session>) -> ConnectionId {
+    Arc::as_ptr(session) as ConnectionId
+}
+
+/// The subscription request, the session must not be held by the service,
+/// otherwise the session is never dropped.
+#[derive(Debug)]
+struct SubscriptionInfo {
+    session: Arc<Session>,
+    kind: pubsub::Kind,
+    filter: Option<pubsub::EventFilter>,
+}
+
+struct ActiveSubscription {
+    connection: ConnectionId,
+    kind: pubsub::Kind,
+    filter: Option<pubsub::EventFilter>,
+}
+
 pub struct PubSubService {
     subscriber_id: Arc<atomic::AtomicU64>,
     txpool: TxPoolService,
@@ -199,28 +302,125 @@ pub struct PubSubService {
     new_event_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<NewEventNotification>>,
     mint_block_subscribers: HashMap<SubscriptionId, mpsc::UnboundedSender<MintBlockEvent>>,
     new_pending_txn_tasks: Arc<RwLock<HashMap<SubscriptionId, AbortHandle>>>,
+    subscriptions: HashMap<SubscriptionId, ActiveSubscription>,
+    connections: HashMap<ConnectionId, HashSet<SubscriptionId>>,
+    /// None if there is no limit of active subscriptions.
+    max_subscriptions: Option<u32>,
+    /// None if there is no limit of active subscriptions of a connection.
+    max_subscriptions_per_connection: Option<u32>,
 }
 
 impl PubSubService {
-    fn new(txpool: TxPoolService, miner_service: ServiceRef<MinerService>) -> Self {
+    fn new(
+        txpool: TxPoolService,
+        miner_service: ServiceRef<MinerService>,
+        max_subscriptions: Option<u32>,
+        max_subscriptions_per_connection: Option<u32>,
+    ) -> Self {
         let subscriber_id = Arc::new(atomic::AtomicU64::new(0));
         Self {
             subscriber_id,
             txpool,
             miner_service,
+            max_subscriptions,
+            max_subscriptions_per_connection,
             new_event_subscribers: Default::default(),
             new_header_subscribers: Default::default(),
             mint_block_subscribers: Default::default(),
             new_pending_txn_tasks: Arc::new(RwLock::new(HashMap::default())),
+            subscriptions: Default::default(),
+            connections: Default::default(),
+        }
+    }
+
+    /// Reject the subscriber if the active subscriptions of the node or the connection reach the limit,
+    /// otherwise register the subscription and return its id.
+    fn accept(
+        &mut self,
+        subscriber: Subscriber<pubsub::Result>,
+        info: SubscriptionInfo,
+        ctx: &mut ServiceContext<Self>,
+    ) -> Option<(Subscriber<pubsub::Result>, SubscriptionId)> {
+        let connection = connection_id(&info.session);
+        let connection_subscriptions = self.connections.get(&connection).map(|s| s.len());
+        let rejected = if let Some(limit) = self
+            .max_subscriptions
+            .filter(|limit| self.subscriptions.len() >= *limit as usize)
+        {
+            Some(SubscriptionLimitData {
+                scope: SubscriptionLimitScope::Node,
+                limit,
+            })
+        } else if let Some(limit) = self
+            .max_subscriptions_per_connection
+            .filter(|limit| connection_subscriptions.unwrap_or(0) >= *limit as usize)
+        {
+            Some(SubscriptionLimitData {
+                scope: SubscriptionLimitScope::Connection,
+                limit,
+            })
+        } else {
+            None
+        };
+        if let Some(data) = rejected {
+            let _ = subscriber.reject(RpcErrorCode::TooManySubscriptions.error_with_data(
+                format!(
+                    "active subscriptions of the {:?} reach the limit {}",
+                    data.scope, data.limit
+                ),
+                &data,
+            ));
+            return None;
+        }
+        if connection_subscriptions.is_none() {
+            let service = ctx.self_ref();
+            info.session
+                .on_drop(move || service.do_send(ConnectionClosed(connection)));
         }
+        let subscriber_id = self.next_id();
+        self.connections
+            .entry(connection)
+            .or_default()
+            .insert(subscriber_id.clone());
+        self.subscriptions.insert(
+            subscriber_id.clone(),
+            ActiveSubscription {
+                connection,
+                kind: info.kind,
+                filter: info.filter,
+            },
+        );
+        PUBSUB_SUBSCRIPTIONS_GAUGE.inc();
+        Some((subscriber, subscriber_id))
     }
+
+    fn remove_subscription(&mut self, id: &SubscriptionId) {
+        self.new_header_subscribers.remove(id);
+        self.new_event_subscribers.remove(id);
+        if self.mint_block_subscribers.remove(id).is_some() {
+            self.miner_service
+                .do_send(MinerClientSubscribeRequest::Remove(
+                    self.mint_block_subscribers.len() as u32,
+                ));
+        }
+        if let Some(h) = self.new_pending_txn_tasks.write().remove(id) {
+            h.abort();
+        }
+        if let Some(subscription) = self.subscriptions.remove(id) {
+            PUBSUB_SUBSCRIPTIONS_GAUGE.dec();
+            if let Some(ids) = self.connections.get_mut(&subscription.connection) {
+                ids.remove(id);
+            }
+        }
+    }
+
     fn next_id(&self) -> SubscriptionId {
         let id = self.subscriber_id.fetch_add(1, atomic::Ordering::SeqCst);
         SubscriptionId::Number(id)
     }
 }
 
-type NewHeadNotification = Notification<ThinBlock>;
+type NewHeadNotification = NewHeadEventNotification;
 type NewEventNotification = Notification<Arc<[Event]>>;
 // type NewTxns = Arc<[HashValue]>;
 
@@ -233,6 +433,14 @@ impl ActorService for PubSubService {
 
         Ok(())
     }
+
+    fn stopped(&mut self, _ctx: &mut ServiceContext<Self>) -> Result<()> {
+        let ids: Vec<_> = self.subscriptions.keys().cloned().collect();
+        for id in ids {
+            self.remove_subscription(&id);
+        }
+        Ok(())
+    }
 }
 
 impl ActorEventHandler<Self, NewHeadNotification> for PubSubService {
@@ -258,7 +466,7 @@ impl ActorEventHandler<Self, MintBlockEvent> for PubSubService {
 }
 
 #[derive(Debug)]
-struct SubscribeNewHeads(Subscriber<pubsub::Result>);
+struct SubscribeNewHeads(Subscriber<pubsub::Result>, SubscriptionInfo);
 
 impl ServiceRequest for SubscribeNewHeads {
     type Response = ();
@@ -266,9 +474,12 @@ impl ServiceRequest for SubscribeNewHeads {
 
 impl ServiceHandler<Self, SubscribeNewHeads> for PubSubService {
     fn handle(&mut self, msg: SubscribeNewHeads, ctx: &mut ServiceContext<Self>) {
-        let SubscribeNewHeads(sink) = msg;
+        let SubscribeNewHeads(sink, info) = msg;
+        let (sink, subscriber_id) = match self.accept(sink, info, ctx) {
+            Some(accepted) => accepted,
+            None => return,
+        };
         let (sender, receiver) = mpsc::unbounded();
-        let subscriber_id = self.next_id();
         self.new_header_subscribers
             .insert(subscriber_id.clone(), sender);
         ctx.spawn(run_subscription(
@@ -281,7 +492,7 @@ impl ServiceHandler<Self, SubscribeNewHeads> for PubSubService {
 }
 
 #[derive(Debug)]
-struct SubscribeMintBlock(Subscriber<pubsub::Result>);
+struct SubscribeMintBlock(Subscriber<pubsub::Result>, SubscriptionInfo);
 
 impl ServiceRequest for SubscribeMintBlock {
     type Response = ();
@@ -289,9 +500,12 @@ impl ServiceRequest for SubscribeMintBlock {
 
 impl ServiceHandler<Self, SubscribeMintBlock> for PubSubService {
     fn handle(&mut self, msg: SubscribeMintBlock, ctx: &mut ServiceContext<Self>) {
-        let SubscribeMintBlock(subscriber) = msg;
+        let SubscribeMintBlock(subscriber, info) = msg;
+        let (subscriber, subscriber_id) = match self.accept(subscriber, info, ctx) {
+            Some(accepted) => accepted,
+            None => return,
+        };
         let (sender, receiver) = mpsc::unbounded();
-        let subscriber_id = self.next_id();
         self.mint_block_subscribers
             .insert(subscriber_id.clone(), sender.clone());
         let miner_service = self.miner_service.clone();
@@ -322,6 +536,7 @@ impl ServiceHandler<Self, SubscribeMintBlock> for PubSubService {
 #[derive(Debug)]
 struct SubscribeEvents {
     subscriber: Subscriber<pubsub::Result>,
+    info: SubscriptionInfo,
     filter: Filter,
 }
 
@@ -331,9 +546,16 @@ impl ServiceRequest for SubscribeEvents {
 
 impl ServiceHandler<Self, SubscribeEvents> for PubSubService {
     fn handle(&mut self, msg: SubscribeEvents, ctx: &mut ServiceContext<Self>) {
-        let SubscribeEvents { subscriber, filter } = msg;
+        let SubscribeEvents {
+            subscriber,
+            info,
+            filter,
+        } = msg;
+        let (subscriber, subscriber_id) = match self.accept(subscriber, info, ctx) {
+            Some(accepted) => accepted,
+            None => return,
+        };
         let (sender, receiver) = mpsc::unbounded();
-        let subscriber_id = self.next_id();
         self.new_event_subscribers
             .insert(subscriber_id.clone(), sender);
         ctx.spawn(run_subscription(
@@ -348,6 +570,7 @@ impl ServiceHandler<Self, SubscribeEvents> for PubSubService {
 #[derive(Debug)]
 struct SubscribeNewPendingTxns {
     subscriber: Subscriber<pubsub::Result>,
+    info: SubscriptionInfo,
 }
 
 impl ServiceRequest for SubscribeNewPendingTxns {
@@ -356,8 +579,11 @@ impl ServiceRequest for SubscribeNewPendingTxns {
 
 impl ServiceHandler<Self, SubscribeNewPendingTxns> for PubSubService {
     fn handle(&mut self, msg: SubscribeNewPendingTxns, ctx: &mut ServiceContext<Self>) {
-        let SubscribeNewPendingTxns { subscriber } = msg;
-        let subscriber_id = self.next_id();
+        let SubscribeNewPendingTxns { subscriber, info } = msg;
+        let (subscriber, subscriber_id) = match self.accept(subscriber, info, ctx) {
+            Some(accepted) => accepted,
+            None => return,
+        };
         let tasks = self.new_pending_txn_tasks.clone();
         let subscriber_id_clone = subscriber_id.clone();
         let receiver = self.txpool.subscribe_pending_txn();
@@ -383,6 +609,54 @@ impl ServiceHandler<Self, SubscribeNewPendingTxns> for PubSubService {
     }
 }
 
+#[derive(Debug)]
+struct SubscribeDroppedTxns {
+    subscriber: Subscriber<pubsub::Result>,
+    info: SubscriptionInfo,
+    /// Only notify the txns evicted by a full txpool.
+    evicted_only: bool,
+}
+
+impl ServiceRequest for SubscribeDroppedTxns {
+    type Response = ();
+}
+
+impl ServiceHandler<Self, SubscribeDroppedTxns> for PubSubService {
+    fn handle(&mut self, msg: SubscribeDroppedTxns, ctx: &mut ServiceContext<Self>) {
+        let SubscribeDroppedTxns {
+            subscriber,
+            info,
+            evicted_only,
+        } = msg;
+        let (subscriber, subscriber_id) = match self.accept(subscriber, info, ctx) {
+            Some(accepted) => accepted,
+            None => return,
+        };
+        let tasks = self.new_pending_txn_tasks.clone();
+        let subscriber_id_clone = subscriber_id.clone();
+        let receiver = self.txpool.subscribe_txns();
+        let (f, abort_handle) = futures::future::abortable(async move {
+            run_subscription(
+                receiver,
+                subscriber_id_clone.clone(),
+                subscriber,
+                DroppedTxnEventHandler { evicted_only },
+            )
+            .await;
+            // remove self from task list.
+            tasks.write().remove(&subscriber_id_clone);
+        });
+
+        ctx.spawn(async move {
+            let _ = f.await;
+        });
+
+        self.new_pending_txn_tasks
+            .write()
+            .insert(subscriber_id, abort_handle);
+    }
+}
+
 #[derive(Debug)]
 struct Unsubscribe(SubscriptionId);
 
@@ -392,19 +666,62 @@ impl ServiceRequest for Unsubscribe {
 
 impl ServiceHandler<Self, Unsubscribe> for PubSubService {
     fn handle(&mut self, msg: Unsubscribe, _ctx: &mut ServiceContext<Self>) {
-        self.new_header_subscribers.remove(&msg.0);
-        self.new_event_subscribers.remove(&msg.0);
-        self.mint_block_subscribers.remove(&msg.0);
-        self.miner_service
-            .do_send(MinerClientSubscribeRequest::Remove(
-                self.mint_block_subscribers.len() as u32,
-            ));
-        if let Some(h) = self.new_pending_txn_tasks.write().remove(&msg.0) {
-            h.abort();
+        self.remove_subscription(&msg.0);
+    }
+}
+
+/// Sent when the pubsub session of the connection is dropped.
+#[derive(Debug)]
+struct ConnectionClosed(ConnectionId);
+
+impl ServiceRequest for ConnectionClosed {
+    type Response = ();
+}
+
+impl ServiceHandler<Self, ConnectionClosed> for PubSubService {
+    fn handle(&mut self, msg: ConnectionClosed, _ctx: &mut ServiceContext<Self>) {
+        if let Some(ids) = self.connections.remove(&msg.0) {
+            for id in ids {
+                self.remove_subscription(&id);
+            }
         }
     }
 }
 
+#[derive(Debug)]
+struct ListSubscriptions(ConnectionId);
+
+impl ServiceRequest for ListSubscriptions {
+    type Response = Vec<SubscriptionView>;
+}
+
+impl ServiceHandler<Self, ListSubscriptions> for PubSubService {
+    fn handle(
+        &mut self,
+        msg: ListSubscriptions,
+        _ctx: &mut ServiceContext<Self>,
+    ) -> Vec<SubscriptionView> {
+        let mut subscriptions: Vec<_> = self
+            .connections
+            .get(&msg.0)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| {
+                self.subscriptions.get(id).map(|s| SubscriptionView {
+                    id: id.clone(),
+                    kind: s.kind.clone(),
+                    filter: s.filter.clone(),
+                })
+            })
+            .collect();
+        subscriptions.sort_by_key(|s| match &s.id {
+            SubscriptionId::Number(n) => *n,
+            SubscriptionId::String(_) => u64::MAX,
+        });
+        subscriptions
+    }
+}
+
 fn send_to_all<T: Clone>(
     subscriptions: &mut HashMap<SubscriptionId, mpsc::UnboundedSender<T>>,
     msg: T,
@@ -467,16 +784,62 @@ impl EventHandler<Arc<[HashValue]>> for TxnEventHandler {
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub struct DroppedTxnEventHandler {
+    evicted_only: bool,
+}
+
+impl DroppedTxnEventHandler {
+    fn is_matched(&self, status: TxStatus) -> bool {
+        match status {
+            TxStatus::Evicted => true,
+            TxStatus::Dropped => !self.evicted_only,
+            _ => false,
+        }
+    }
+}
+
+impl EventHandler<TxnStatusFullEvent> for DroppedTxnEventHandler {
+    fn handle(&self, msg: TxnStatusFullEvent) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+        let dropped: Vec<HashValue> = msg
+            .iter()
+            .filter(|(_, status)| self.is_matched(*status))
+            .map(|(hash, _)| *hash)
+            .collect();
+        if dropped.is_empty() {
+            vec![]
+        } else {
+            vec![Ok(pubsub::Result::TransactionHash(dropped))]
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct NewHeadHandler;
 
-impl EventHandler<Notification<ThinBlock>> for NewHeadHandler {
-    fn handle(&self, msg: Notification<ThinBlock>) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
-        let Notification(block) = msg;
-        vec![Ok(pubsub::Result::Block(Box::new(BlockView {
-            header: block.header.into(),
-            body: block.body.into(),
-            uncles: vec![],
+impl EventHandler<Notification<BlockNotification>> for NewHeadHandler {
+    fn handle(
+        &self,
+        msg: Notification<BlockNotification>,
+    ) -> Vec<jsonrpc_core::Result<pubsub::Result>> {
+        let Notification(BlockNotification {
+            kind,
+            block,
+            common_ancestor,
+        }) = msg;
+        let kind = match kind {
+            BlockNotificationKind::NewHead => pubsub::BlockNotificationKind::NewHead,
+            BlockNotificationKind::Retracted => pubsub::BlockNotificationKind::Retracted,
+        };
+        vec![Ok(pubsub::Result::Block(Box::new(BlockNotificationView {
+            kind,
+            block: BlockView {
+                header: block.header.into(),
+                body: block.body.into(),
+                uncles: vec![],
+                block_info: None,
+            },
+            common_ancestor,
         })))]
     }
 }
@@ -489,8 +852,8 @@ impl EventHandler<MintBlockEvent> for NewMintBlockHandler {
         vec![Ok(pubsub::Result::MintBlock(Box::new(MintBlock {
             strategy: msg.strategy,
             minting_blob: hex::encode(msg.minting_blob),
-            difficulty: msg.difficulty,
-            block_number: msg.block_number,
+            difficulty: StrView(msg.difficulty),
+            block_number: StrView(msg.block_number),
         })))]
     }
 }
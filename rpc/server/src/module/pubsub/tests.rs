@@ -1,6 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::metrics::PUBSUB_SUBSCRIPTIONS_GAUGE;
 use crate::module::{PubSubImpl, PubSubService, PubSubServiceFactory};
 use anyhow::Result;
 use futures::StreamExt;
@@ -11,18 +12,22 @@ use starcoin_account_api::AccountInfo;
 use starcoin_chain::BlockChain;
 use starcoin_chain::{ChainReader, ChainWriter};
 use starcoin_chain_notify::ChainNotifyHandlerService;
+use starcoin_config::ChainNetwork;
 use starcoin_consensus::Consensus;
 use starcoin_crypto::{ed25519::Ed25519PrivateKey, Genesis, PrivateKey};
 use starcoin_executor::DEFAULT_EXPIRATION_TIME;
 use starcoin_logger::prelude::*;
 use starcoin_rpc_api::metadata::Metadata;
 use starcoin_rpc_api::pubsub::StarcoinPubSub;
-use starcoin_rpc_api::types::pubsub::MintBlock;
+use starcoin_rpc_api::types::pubsub::{
+    BlockNotificationKind, BlockNotificationView, Kind, MintBlock, SubscriptionView,
+};
 use starcoin_service_registry::bus::{Bus, BusService};
 use starcoin_service_registry::RegistryAsyncService;
 use starcoin_state_api::StateReaderExt;
 use starcoin_storage::BlockStore;
 use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_types::block::ExecutedBlock;
 use starcoin_types::system_events::MintBlockEvent;
 use starcoin_types::system_events::NewHeadBlock;
 use starcoin_types::transaction::authenticator::AuthenticationKey;
@@ -228,7 +233,7 @@ pub async fn test_subscribe_to_mint_block() -> Result<()> {
     let r: Value = serde_json::from_str(&res).unwrap();
     let v = r["params"]["result"].clone();
     let mint_block: MintBlock = serde_json::from_value(v).unwrap();
-    assert_eq!(mint_block.difficulty, diff);
+    assert_eq!(mint_block.difficulty.0, diff);
     assert_eq!(&mint_block.minting_blob, &hex::encode(&header_hash));
     // Unsubscribe
     let request = r#"{"jsonrpc": "2.0", "method": "starcoin_unsubscribe", "params": [0], "id": 1}"#;
@@ -237,3 +242,159 @@ pub async fn test_subscribe_to_mint_block() -> Result<()> {
     assert_eq!(resp, Some(response.to_owned()));
     Ok(())
 }
+
+fn produce_block(
+    chain: &mut BlockChain,
+    miner: &AccountInfo,
+    net: &ChainNetwork,
+) -> Result<ExecutedBlock> {
+    let (template, _) = chain.create_block_template(
+        *miner.address(),
+        Some(miner.public_key.authentication_key()),
+        None,
+        vec![],
+        vec![],
+        None,
+    )?;
+    let block = chain
+        .consensus()
+        .create_block(template, net.time_service().as_ref())?;
+    chain.apply(block)
+}
+
+#[stest::test]
+pub async fn test_subscribe_to_new_heads_with_reorg() -> Result<()> {
+    let (_txpool_service, storage, config, _, registry) = test_helper::start_txpool().await;
+    let net = config.net();
+    let genesis_id = storage.get_startup_info()?.unwrap().main;
+    let bus = registry.service_ref::<BusService>().await?;
+    let _notify_service = registry.register::<ChainNotifyHandlerService>().await?;
+    let service = registry
+        .register_by_factory::<PubSubService, PubSubServiceFactory>()
+        .await?;
+    let pubsub = PubSubImpl::new(service);
+    let pubsub = pubsub.to_delegate();
+
+    let mut io = MetaIoHandler::default();
+    io.extend_with(pubsub);
+
+    let mut metadata = Metadata::default();
+    let (sender, mut receiver) = futures::channel::mpsc::unbounded();
+    metadata.session = Some(Arc::new(Session::new(sender)));
+
+    // Subscribe
+    let request = r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"newHeads"}], "id": 1}"#;
+    let response = r#"{"jsonrpc":"2.0","result":0,"id":1}"#;
+    let resp = io.handle_request(request, metadata.clone()).await;
+    assert_eq!(resp, Some(response.to_owned()));
+
+    // main chain: genesis -> a1 -> a2
+    let mut main = BlockChain::new(net.time_service(), genesis_id, storage.clone())?;
+    let miner = AccountInfo::random();
+    let mut main_blocks = vec![];
+    for _ in 0..2 {
+        main_blocks.push(produce_block(&mut main, &miner, net)?);
+    }
+    // new branch: genesis -> b1 -> b2 -> b3
+    let mut branch = BlockChain::new(net.time_service(), genesis_id, storage)?;
+    let branch_miner = AccountInfo::random();
+    let mut branch_blocks = vec![];
+    for _ in 0..3 {
+        branch_blocks.push(produce_block(&mut branch, &branch_miner, net)?);
+    }
+
+    for block in &main_blocks {
+        bus.broadcast(NewHeadBlock(Arc::new(block.clone())))?;
+    }
+    // switch to the new branch.
+    bus.broadcast(NewHeadBlock(Arc::new(branch_blocks[2].clone())))?;
+
+    let mut expected = vec![];
+    for block in &main_blocks {
+        expected.push((BlockNotificationKind::NewHead, block.block().id(), None));
+    }
+    for block in main_blocks.iter().rev() {
+        expected.push((BlockNotificationKind::Retracted, block.block().id(), None));
+    }
+    for (i, block) in branch_blocks.iter().enumerate() {
+        let common_ancestor = if i == 0 { Some(genesis_id) } else { None };
+        expected.push((
+            BlockNotificationKind::NewHead,
+            block.block().id(),
+            common_ancestor,
+        ));
+    }
+
+    let mut notifications = vec![];
+    for _ in 0..expected.len() {
+        let res = timeout(Duration::from_secs(5), receiver.next())
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Empty value"))?;
+        let r: Value = serde_json::from_str(&res)?;
+        let notification: BlockNotificationView =
+            serde_json::from_value(r["params"]["result"].clone())?;
+        notifications.push((
+            notification.kind,
+            notification.block.header.block_hash,
+            notification.common_ancestor,
+        ));
+    }
+    assert_eq!(notifications, expected);
+    Ok(())
+}
+
+#[stest::test(timeout = 120)]
+pub async fn test_subscriptions_removed_on_connection_drop() -> Result<()> {
+    let (_txpool_service, .., registry) = test_helper::start_txpool().await;
+    let service = registry
+        .register_by_factory::<PubSubService, PubSubServiceFactory>()
+        .await?;
+    let pubsub = PubSubImpl::new(service);
+    let pubsub = pubsub.to_delegate();
+
+    let mut io = MetaIoHandler::default();
+    io.extend_with(pubsub);
+
+    // other tests may subscribe at the same time, so only check the subscriptions are not leaked.
+    let active_before = PUBSUB_SUBSCRIPTIONS_GAUGE.get();
+    let subscribe = r#"{"jsonrpc": "2.0", "method": "starcoin_subscribe", "params": [{"type_name":"newHeads"}], "id": 1}"#;
+    let list =
+        r#"{"jsonrpc": "2.0", "method": "pubsub.list_subscriptions", "params": [], "id": 2}"#;
+    for i in 0..1000 {
+        let mut metadata = Metadata::default();
+        let (sender, _receiver) = futures::channel::mpsc::unbounded();
+        metadata.session = Some(Arc::new(Session::new(sender)));
+        let resp: Value = serde_json::from_str(
+            &io.handle_request(subscribe, metadata.clone())
+                .await
+                .unwrap(),
+        )?;
+        assert!(resp["result"].is_number(), "subscribe failed: {}", resp);
+        if i == 0 {
+            let resp: Value =
+                serde_json::from_str(&io.handle_request(list, metadata.clone()).await.unwrap())?;
+            let subscriptions: Vec<SubscriptionView> =
+                serde_json::from_value(resp["result"].clone())?;
+            assert_eq!(subscriptions.len(), 1);
+            assert_eq!(subscriptions[0].kind, Kind::NewHeads);
+        }
+        // drop the connection.
+        drop(metadata);
+    }
+
+    let mut active = PUBSUB_SUBSCRIPTIONS_GAUGE.get();
+    for _ in 0..100 {
+        if active <= active_before {
+            break;
+        }
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+        active = PUBSUB_SUBSCRIPTIONS_GAUGE.get();
+    }
+    assert!(
+        active <= active_before,
+        "subscriptions leaked, active before: {}, active after: {}",
+        active_before,
+        active
+    );
+    Ok(())
+}
@@ -1,20 +1,25 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::module::map_err;
+use crate::module::{map_err, state_not_found_by_root};
+use anyhow::bail;
 use bcs_ext::BCSCodec;
 use futures::future::TryFutureExt;
 use futures::FutureExt;
+use starcoin_crypto::hash::SPARSE_MERKLE_PLACEHOLDER_HASH;
 use starcoin_crypto::HashValue;
 use starcoin_resource_viewer::MoveValueAnnotator;
 use starcoin_rpc_api::state::StateApi;
 use starcoin_rpc_api::types::{
-    AccountStateSetView, AnnotatedMoveStructView, StateWithProofView, StrView, StructTagView,
+    AccountAddressView, AccountStateSetView, AnnotatedMoveStructView, EncodedView, ListCodeView,
+    ListResourceView, OutputEncoding, ResourceView, StateWithProofView, StrView, StructTagView,
+    TokenCodeView,
 };
 use starcoin_rpc_api::FutureResult;
-use starcoin_state_api::ChainStateAsyncService;
-use starcoin_state_tree::StateNodeStore;
+use starcoin_state_api::{ChainStateAsyncService, ChainStateReader};
+use starcoin_state_tree::{RawKey, StateNodeStore, StateTree};
 use starcoin_statedb::ChainStateDB;
+use starcoin_storage::TokenHolderStore;
 use starcoin_types::{
     access_path::AccessPath, account_address::AccountAddress, account_state::AccountState,
 };
@@ -23,26 +28,58 @@ use starcoin_vm_types::language_storage::StructTag;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
+/// The max number of items returned by one page of `state.list_resources`, `state.list_code`
+/// or `state.list_token_holders`.
+pub const MAX_LIST_LIMIT: u32 = 100;
+
 pub struct StateRpcImpl<S>
 where
     S: ChainStateAsyncService + 'static,
 {
     service: S,
     state_store: Arc<dyn StateNodeStore>,
+    token_holder_store: Arc<dyn TokenHolderStore>,
 }
 
 impl<S> StateRpcImpl<S>
 where
     S: ChainStateAsyncService,
 {
-    pub fn new(service: S, state_store: Arc<dyn StateNodeStore>) -> Self {
+    pub fn new(
+        service: S,
+        state_store: Arc<dyn StateNodeStore>,
+        token_holder_store: Arc<dyn TokenHolderStore>,
+    ) -> Self {
         Self {
             service,
             state_store,
+            token_holder_store,
         }
     }
 }
 
+/// Ensure the state of the root is readable, the root node is missing if the state is pruned.
+fn ensure_state_root(
+    state_store: &dyn StateNodeStore,
+    state_root: HashValue,
+) -> anyhow::Result<()> {
+    if state_root != *SPARSE_MERKLE_PLACEHOLDER_HASH && state_store.get(&state_root)?.is_none() {
+        return Err(state_not_found_by_root(state_root));
+    }
+    Ok(())
+}
+
+/// Ensure the token holder index is enabled and built.
+fn ensure_token_holder_index(store: &dyn TokenHolderStore) -> anyhow::Result<()> {
+    if !store.is_token_holder_index_enabled() {
+        bail!("The token holder index is disabled, enable it by the `--token-holder-index true` option.");
+    }
+    if store.get_token_holder_index_head()?.is_none() {
+        bail!("The token holder index is not built, run `node index rebuild-token-holders` to build it.");
+    }
+    Ok(())
+}
+
 impl<S> StateApi for StateRpcImpl<S>
 where
     S: ChainStateAsyncService,
@@ -52,17 +89,32 @@ where
         Box::pin(fut)
     }
 
-    fn get_with_proof(&self, access_path: AccessPath) -> FutureResult<StateWithProofView> {
-        let fut = self
-            .service
-            .clone()
-            .get_with_proof(access_path)
-            .map_ok(|p| p.into())
-            .map_err(map_err);
-        Box::pin(fut)
+    fn get_with_proof(
+        &self,
+        access_path: AccessPath,
+        output_encoding: Option<OutputEncoding>,
+    ) -> FutureResult<EncodedView<StateWithProofView>> {
+        let service = self.service.clone();
+        let fut = async move {
+            // pin the state root, so the returned proof can be verified against it.
+            let state_root = service.clone().state_root().await?;
+            let state_proof = service
+                .get_with_proof_by_root(access_path, state_root)
+                .await?;
+            match output_encoding.unwrap_or_default() {
+                OutputEncoding::Bcs => EncodedView::bcs(&(state_proof, state_root)),
+                OutputEncoding::Json => Ok(EncodedView::Json(StateWithProofView::new(
+                    state_proof,
+                    state_root,
+                ))),
+            }
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
     }
 
-    fn get_account_state(&self, address: AccountAddress) -> FutureResult<Option<AccountState>> {
+    fn get_account_state(&self, address: AccountAddressView) -> FutureResult<Option<AccountState>> {
+        let address = address.0;
         let fut = self
             .service
             .clone()
@@ -73,8 +125,9 @@ where
 
     fn get_account_state_set(
         &self,
-        address: AccountAddress,
+        address: AccountAddressView,
     ) -> FutureResult<Option<AccountStateSetView>> {
+        let address = address.0;
         let state_service = self.service.clone();
         let db = self.state_store.clone();
         let fut = async move {
@@ -133,12 +186,278 @@ where
         access_path: AccessPath,
         state_root: HashValue,
     ) -> FutureResult<StateWithProofView> {
-        let fut = self
-            .service
-            .clone()
-            .get_with_proof_by_root(access_path, state_root)
-            .map_ok(|p| p.into())
-            .map_err(map_err);
-        Box::pin(fut)
+        let service = self.service.clone();
+        let db = self.state_store.clone();
+        let fut = async move {
+            ensure_state_root(db.as_ref(), state_root)?;
+            let proof = service
+                .get_with_proof_by_root(access_path, state_root)
+                .await?;
+            Ok(StateWithProofView::new(proof, state_root))
+        };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn get_account_state_by_root(
+        &self,
+        address: AccountAddressView,
+        state_root: HashValue,
+    ) -> FutureResult<Option<AccountState>> {
+        let address = address.0;
+        let service = self.service.clone();
+        let db = self.state_store.clone();
+        let fut = async move {
+            ensure_state_root(db.as_ref(), state_root)?;
+            service.get_account_state_by_root(address, state_root).await
+        };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn list_resources(
+        &self,
+        address: AccountAddressView,
+        start: Option<StructTagView>,
+        limit: u32,
+        decode: bool,
+        state_root: Option<HashValue>,
+    ) -> FutureResult<Option<ListResourceView>> {
+        let address = address.0;
+        let state_service = self.service.clone();
+        let db = self.state_store.clone();
+        let fut = async move {
+            let state_root = match state_root {
+                Some(state_root) => {
+                    ensure_state_root(db.as_ref(), state_root)?;
+                    state_root
+                }
+                None => state_service.clone().state_root().await?,
+            };
+            let statedb = ChainStateDB::new(db.clone(), Some(state_root));
+            let account_state = match statedb.get_account_state(&address)? {
+                None => return Ok(None),
+                Some(account_state) => account_state,
+            };
+            let resource_tree =
+                StateTree::<StructTag>::new(db, Some(account_state.resource_root()));
+            let (page, next) = scan_page(
+                &resource_tree,
+                start.map(|tag| tag.0).as_ref(),
+                list_limit(limit)?,
+            )?;
+            let annotator = MoveValueAnnotator::new(&statedb);
+            let resources = page
+                .into_iter()
+                .map(|(struct_tag, v)| {
+                    let json = if decode {
+                        Some(
+                            annotator
+                                .view_struct(struct_tag.clone(), v.as_slice())?
+                                .into(),
+                        )
+                    } else {
+                        None
+                    };
+                    Ok((
+                        StrView(struct_tag),
+                        ResourceView {
+                            raw: StrView(v),
+                            json,
+                        },
+                    ))
+                })
+                .collect::<anyhow::Result<BTreeMap<_, _>>>()?;
+            Ok(Some(ListResourceView {
+                state_root,
+                resources,
+                next_cursor: next.map(StrView),
+            }))
+        };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn list_code(
+        &self,
+        address: AccountAddressView,
+        start: Option<Identifier>,
+        limit: u32,
+        state_root: Option<HashValue>,
+    ) -> FutureResult<Option<ListCodeView>> {
+        let address = address.0;
+        let state_service = self.service.clone();
+        let db = self.state_store.clone();
+        let fut = async move {
+            let state_root = match state_root {
+                Some(state_root) => {
+                    ensure_state_root(db.as_ref(), state_root)?;
+                    state_root
+                }
+                None => state_service.clone().state_root().await?,
+            };
+            let statedb = ChainStateDB::new(db.clone(), Some(state_root));
+            let account_state = match statedb.get_account_state(&address)? {
+                None => return Ok(None),
+                Some(account_state) => account_state,
+            };
+            let limit = list_limit(limit)?;
+            let (page, next) = match account_state.code_root() {
+                Some(code_root) => scan_page(
+                    &StateTree::<Identifier>::new(db, Some(code_root)),
+                    start.as_ref(),
+                    limit,
+                )?,
+                None => (vec![], None),
+            };
+            let codes = page
+                .into_iter()
+                .map(|(k, v)| (k, StrView(v)))
+                .collect::<BTreeMap<_, _>>();
+            Ok(Some(ListCodeView {
+                state_root,
+                codes,
+                next_cursor: next,
+            }))
+        };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn list_token_holders(
+        &self,
+        token_code: TokenCodeView,
+        start: Option<AccountAddressView>,
+        limit: u32,
+    ) -> FutureResult<Vec<(AccountAddress, StrView<u128>)>> {
+        let token_code = token_code.0;
+        let start = start.map(|start| start.0);
+        let store = self.token_holder_store.clone();
+        let fut = async move {
+            ensure_token_holder_index(store.as_ref())?;
+            let holders = store.list_token_holders(&token_code, start, list_limit(limit)?)?;
+            Ok(holders
+                .into_iter()
+                .map(|(address, balance)| (address, StrView(balance)))
+                .collect())
+        };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+
+    fn token_holder_count(&self, token_code: TokenCodeView) -> FutureResult<u64> {
+        let token_code = token_code.0;
+        let store = self.token_holder_store.clone();
+        let fut = async move {
+            ensure_token_holder_index(store.as_ref())?;
+            store.get_token_holder_count(&token_code)
+        };
+        Box::pin(fut.map_err(map_err).boxed())
+    }
+}
+
+/// The page size of the list methods, capped by `MAX_LIST_LIMIT`.
+/// A zero limit is rejected, its page is empty and the next cursor is the start itself,
+/// so the caller which follows the cursor never ends.
+fn list_limit(limit: u32) -> anyhow::Result<usize> {
+    if limit == 0 {
+        return Err(
+            jsonrpc_core::Error::invalid_params("the limit should be greater than 0").into(),
+        );
+    }
+    Ok(limit.min(MAX_LIST_LIMIT) as usize)
+}
+
+/// Take a page of at most `limit` items from `state_set`,
+/// ordered by key and start from `start`(inclusive).
+/// Return the page and the key of the first item of next page.
+/// Read a page of at most `limit` entries of the tree from `start`(inclusive) in the key hash order,
+/// and the key of the first entry of the next page, None if this is the last page.
+fn scan_page<K: RawKey>(
+    tree: &StateTree<K>,
+    start: Option<&K>,
+    limit: usize,
+) -> anyhow::Result<(Vec<(K, Vec<u8>)>, Option<K>)> {
+    let mut page = tree.scan(start, limit + 1)?;
+    let next = if page.len() > limit {
+        page.pop().map(|(k, _)| k)
+    } else {
+        None
+    };
+    Ok((page.into_iter().map(|(k, v)| (k, v.into())).collect(), next))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_rpc_api::errors::RpcErrorCode;
+    use starcoin_state_tree::mock::MockStateNodeStore;
+    use starcoin_storage::storage::StorageInstance;
+    use starcoin_storage::Storage;
+
+    #[test]
+    fn test_ensure_state_root() {
+        let store = MockStateNodeStore::new();
+        assert!(ensure_state_root(&store, *SPARSE_MERKLE_PLACEHOLDER_HASH).is_ok());
+        let err = ensure_state_root(&store, HashValue::random())
+            .unwrap_err()
+            .downcast::<jsonrpc_core::Error>()
+            .unwrap();
+        assert_eq!(RpcErrorCode::of(&err), Some(RpcErrorCode::StateNotFound));
+    }
+
+    #[test]
+    fn test_ensure_token_holder_index() -> anyhow::Result<()> {
+        let storage = Storage::new(StorageInstance::new_cache_instance())?;
+        assert!(ensure_token_holder_index(&storage).is_err());
+        let storage = storage.with_token_holder_index(true);
+        // enabled, but not built.
+        assert!(ensure_token_holder_index(&storage).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_limit() {
+        let err = list_limit(0)
+            .unwrap_err()
+            .downcast::<jsonrpc_core::Error>()
+            .unwrap();
+        assert_eq!(err.code, jsonrpc_core::ErrorCode::InvalidParams);
+        assert_eq!(list_limit(1).unwrap(), 1);
+        assert_eq!(
+            list_limit(MAX_LIST_LIMIT + 1).unwrap(),
+            MAX_LIST_LIMIT as usize
+        );
+    }
+
+    #[test]
+    fn test_scan_page() -> anyhow::Result<()> {
+        let tree = StateTree::<u64>::new(Arc::new(MockStateNodeStore::new()), None);
+        for i in 0u64..10 {
+            tree.put(i, vec![i as u8]);
+        }
+        tree.commit()?;
+        let mut keys = vec![];
+        let mut start = None;
+        loop {
+            let (page, next) = scan_page(&tree, start.as_ref(), 4)?;
+            assert!(page.len() <= 4);
+            keys.extend(page.into_iter().map(|(k, v)| {
+                assert_eq!(v, vec![k as u8]);
+                k
+            }));
+            match next {
+                Some(next) => start = Some(next),
+                None => break,
+            }
+        }
+        // every key is listed once.
+        keys.sort_unstable();
+        assert_eq!(keys, (0u64..10).collect::<Vec<_>>());
+
+        let (page, next) = scan_page(&tree, None, 10)?;
+        assert_eq!(page.len(), 10);
+        assert_eq!(next, None);
+
+        let empty = StateTree::<u64>::new(Arc::new(MockStateNodeStore::new()), None);
+        let (page, next) = scan_page(&empty, None, 4)?;
+        assert!(page.is_empty());
+        assert_eq!(next, None);
+        Ok(())
     }
 }
@@ -6,8 +6,9 @@ use futures::future::TryFutureExt;
 use futures::FutureExt;
 use network_api::PeerStrategy;
 use starcoin_rpc_api::sync_manager::SyncManagerApi;
+use starcoin_rpc_api::types::SyncProgressView;
 use starcoin_rpc_api::FutureResult;
-use starcoin_sync_api::{PeerScoreResponse, SyncAsyncService, SyncProgressReport};
+use starcoin_sync_api::{PeerScoreResponse, SyncAsyncService};
 use starcoin_types::peer_info::PeerId;
 use starcoin_types::sync_status::SyncStatus;
 
@@ -54,14 +55,19 @@ where
     fn start(
         &self,
         force: bool,
-        peers: Vec<PeerId>,
-        skip_pow_verify: bool,
+        peers: Option<Vec<PeerId>>,
+        skip_pow_verify: Option<bool>,
         strategy: Option<PeerStrategy>,
     ) -> FutureResult<()> {
         let service = self.service.clone();
         let fut = async move {
             service
-                .start(force, peers, skip_pow_verify, strategy)
+                .start(
+                    force,
+                    peers.unwrap_or_default(),
+                    skip_pow_verify.unwrap_or_default(),
+                    strategy,
+                )
                 .await?;
             Ok(())
         }
@@ -69,11 +75,11 @@ where
         Box::pin(fut.boxed())
     }
 
-    fn progress(&self) -> FutureResult<Option<SyncProgressReport>> {
+    fn progress(&self) -> FutureResult<Option<SyncProgressView>> {
         let service = self.service.clone();
         let fut = async move {
             let result = service.progress().await?;
-            Ok(result)
+            Ok(result.map(Into::into))
         }
         .map_err(map_err);
         Box::pin(fut.boxed())
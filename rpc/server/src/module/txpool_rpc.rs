@@ -3,65 +3,143 @@
 
 use crate::module::{convert_to_rpc_error, map_err};
 use bcs_ext::BCSCodec;
+use futures::future::TryFutureExt;
+use futures::{Future, FutureExt};
+use starcoin_chain_service::ChainAsyncService;
+use starcoin_config::NodeConfig;
 use starcoin_crypto::HashValue;
+use starcoin_rpc_api::errors::InvalidTransactionData;
 /// Re-export the API
 pub use starcoin_rpc_api::txpool::*;
-use starcoin_rpc_api::types::{SignedUserTransactionView, StrView};
+use starcoin_rpc_api::types::{
+    AccountAddressView, GasPriceOracleView, SignedUserTransactionView, StrView, TxPoolStatusView,
+    TxnDiagnoseTarget, TxnDiagnosisView, TxnVerdict,
+};
 use starcoin_rpc_api::{txpool::TxPoolApi, FutureResult};
-use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService};
-use starcoin_types::account_address::AccountAddress;
+use starcoin_service_registry::ServiceRef;
+use starcoin_state_api::ChainStateAsyncService;
+use starcoin_txpool::{RebroadcastTransactions, TxPoolActorService};
+use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService, TxnRebroadcastResult};
+use starcoin_types::account_config::token_code::TokenCode;
+use starcoin_types::account_config::{genesis_address, AccountResource, BalanceResource};
 use starcoin_types::transaction::SignedUserTransaction;
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::on_chain_resource::Epoch;
+use std::collections::HashSet;
 use std::convert::TryInto;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Count of the recent blocks to find the min gas price of the mined txns.
+const GAS_PRICE_SAMPLE_BLOCKS: u64 = 10;
+/// Max count of the missing sequence numbers returned by the diagnosis, the sequence number of a
+/// txn can be far ahead of the on chain one, so the gap is not collected entirely.
+const MAX_MISSING_SEQUENCE_NUMBERS: usize = 100;
 
 /// Re-export the API
 pub use starcoin_rpc_api::txpool::*;
 
-pub struct TxPoolRpcImpl<S>
+pub struct TxPoolRpcImpl<S, State, Chain>
 where
     S: TxPoolSyncService + 'static,
+    State: ChainStateAsyncService + 'static,
+    Chain: ChainAsyncService + 'static,
 {
+    config: Arc<NodeConfig>,
     service: S,
+    chain_state: Option<State>,
+    chain: Option<Chain>,
+    txpool_actor: Option<ServiceRef<TxPoolActorService>>,
 }
 
-impl<S> TxPoolRpcImpl<S>
+impl<S, State, Chain> TxPoolRpcImpl<S, State, Chain>
 where
     S: TxPoolSyncService,
+    State: ChainStateAsyncService,
+    Chain: ChainAsyncService,
 {
-    pub fn new(service: S) -> Self {
-        Self { service }
+    pub fn new(
+        config: Arc<NodeConfig>,
+        service: S,
+        chain_state: Option<State>,
+        chain: Option<Chain>,
+        txpool_actor: Option<ServiceRef<TxPoolActorService>>,
+    ) -> Self {
+        Self {
+            config,
+            service,
+            chain_state,
+            chain,
+            txpool_actor,
+        }
     }
 }
 
-impl<S> TxPoolApi for TxPoolRpcImpl<S>
+impl<S, State, Chain> TxPoolRpcImpl<S, State, Chain>
 where
     S: TxPoolSyncService,
+    State: ChainStateAsyncService,
+    Chain: ChainAsyncService,
 {
-    fn submit_transaction(&self, txn: SignedUserTransaction) -> FutureResult<HashValue> {
-        let txn_hash = txn.id();
-        let result: Result<(), jsonrpc_core::Error> = self
-            .service
-            .add_txns(vec![txn])
-            .pop()
-            .expect("txpool should return result")
-            .map_err(convert_to_rpc_error);
+    /// Check the txn by `check_txn_limits`, then add it to the txpool if not `validate_only`.
+    fn check_and_submit(
+        &self,
+        txn: SignedUserTransaction,
+        validate_only: bool,
+    ) -> impl Future<Output = anyhow::Result<HashValue>> {
+        let service = self.service.clone();
+        let chain_state = self.chain_state.clone();
+        let min_gas_price = self.config.tx_pool.min_gas_price();
+        let now_seconds = self.config.net().time_service().now_secs();
+        async move {
+            let limits = submit_limits(chain_state, &txn, min_gas_price, now_seconds).await?;
+            check_txn_limits(&txn, &limits).map_err(InvalidTransactionData::into_error)?;
+            let txn_hash = txn.id();
+            if !validate_only {
+                service
+                    .add_txns(vec![txn])
+                    .pop()
+                    .expect("txpool should return result")?;
+            }
+            Ok(txn_hash)
+        }
+    }
+}
 
-        Box::pin(futures::future::ready(result.map(|_| txn_hash)))
+impl<S, State, Chain> TxPoolApi for TxPoolRpcImpl<S, State, Chain>
+where
+    S: TxPoolSyncService,
+    State: ChainStateAsyncService,
+    Chain: ChainAsyncService,
+{
+    fn submit_transaction(
+        &self,
+        txn: SignedUserTransaction,
+        validate_only: Option<bool>,
+    ) -> FutureResult<HashValue> {
+        let fut = self
+            .check_and_submit(txn, validate_only.unwrap_or(false))
+            .map_err(map_err);
+        Box::pin(fut.boxed())
     }
 
-    fn submit_hex_transaction(&self, tx: String) -> FutureResult<HashValue> {
+    fn submit_hex_transaction(
+        &self,
+        tx: String,
+        validate_only: Option<bool>,
+    ) -> FutureResult<HashValue> {
         let tx = tx.strip_prefix("0x").unwrap_or_else(|| tx.as_str());
-        let result = hex::decode(tx)
+        let txn = hex::decode(tx)
             .map_err(convert_to_rpc_error)
-            .and_then(|txn_bytes| SignedUserTransaction::decode(&txn_bytes).map_err(map_err))
-            .and_then(|txn| {
-                let txn_hash = txn.id();
-                self.service
-                    .add_txns(vec![txn])
-                    .pop()
-                    .expect("txpool should return result")
-                    .map(|_| txn_hash)
-                    .map_err(convert_to_rpc_error)
-            });
+            .and_then(|txn_bytes| SignedUserTransaction::decode(&txn_bytes).map_err(map_err));
+        match txn {
+            Ok(txn) => self.submit_transaction(txn, validate_only),
+            Err(e) => Box::pin(futures::future::err(e)),
+        }
+    }
+
+    fn replace_transaction(&self, txn: SignedUserTransaction) -> FutureResult<Option<HashValue>> {
+        let result = self.service.replace_txn(txn).map_err(convert_to_rpc_error);
         Box::pin(futures::future::ready(result))
     }
 
@@ -72,9 +150,10 @@ where
 
     fn pending_txns(
         &self,
-        addr: AccountAddress,
+        addr: AccountAddressView,
         max_len: Option<u32>,
     ) -> FutureResult<Vec<SignedUserTransactionView>> {
+        let addr = addr.0;
         let txns: Result<Vec<SignedUserTransactionView>, _> = self
             .service
             .txns_of_sender(&addr, max_len.map(|v| v as usize))
@@ -94,7 +173,8 @@ where
         Box::pin(futures::future::ready(txn))
     }
 
-    fn next_sequence_number(&self, address: AccountAddress) -> FutureResult<Option<u64>> {
+    fn next_sequence_number(&self, address: AccountAddressView) -> FutureResult<Option<u64>> {
+        let address = address.0;
         let result = self.service.next_sequence_number(address);
         Box::pin(futures::future::ok(result))
     }
@@ -103,6 +183,286 @@ where
         let state = self.service.status();
         Box::pin(futures::future::ok(state))
     }
+
+    fn gas_price_oracle(&self) -> FutureResult<GasPriceOracleView> {
+        let oracle = self.service.gas_price_oracle();
+        Box::pin(futures::future::ok(oracle.into()))
+    }
+
+    fn status(&self) -> FutureResult<TxPoolStatusView> {
+        // the counts walk the whole pool, so they are cached, but the gas price floor is cheap and always current.
+        let mut status = self.service.cached_detailed_status();
+        status.min_gas_price_to_enter = self.service.gas_price_oracle().pool_min_to_enter;
+        Box::pin(futures::future::ok(status.into()))
+    }
+
+    fn diagnose(&self, target: TxnDiagnoseTarget) -> FutureResult<TxnDiagnosisView> {
+        let service = self.service.clone();
+        let chain_state = self.chain_state.clone();
+        let chain = self.chain.clone();
+        let now_seconds = self.config.net().time_service().now_secs();
+        let fut = async move {
+            let (chain_state, chain) = match (chain_state, chain) {
+                (Some(chain_state), Some(chain)) => (chain_state, chain),
+                _ => anyhow::bail!("txpool.diagnose is not supported without chain services"),
+            };
+            let txn = match &target {
+                TxnDiagnoseTarget::Txn(txn_hash) => service.find_txn(txn_hash),
+                TxnDiagnoseTarget::Sender(sender) => service
+                    .txns_of_sender(sender, None)
+                    .into_iter()
+                    .min_by_key(|txn| txn.sequence_number()),
+            };
+            let txn = match txn {
+                Some(txn) => txn,
+                None => {
+                    let (txn_hash, sender) = match target {
+                        TxnDiagnoseTarget::Txn(txn_hash) => (Some(txn_hash), None),
+                        TxnDiagnoseTarget::Sender(sender) => (None, Some(sender)),
+                    };
+                    return Ok(TxnDiagnosisView {
+                        txn_hash,
+                        sender,
+                        sequence_number: None,
+                        verdict: TxnVerdict::NotInPool,
+                    });
+                }
+            };
+            let verdict = diagnose_txn(&service, chain_state, &chain, now_seconds, &txn).await?;
+            Ok(TxnDiagnosisView {
+                txn_hash: Some(txn.id()),
+                sender: Some(txn.sender().into()),
+                sequence_number: Some(txn.sequence_number().into()),
+                verdict,
+            })
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+
+    fn rebroadcast(
+        &self,
+        txn_hashes: Option<Vec<HashValue>>,
+    ) -> FutureResult<TxnRebroadcastResult> {
+        let txpool_actor = self.txpool_actor.clone();
+        let fut = async move {
+            let txpool_actor = txpool_actor.ok_or_else(|| {
+                anyhow::format_err!("txpool.rebroadcast is not supported without txpool service")
+            })?;
+            txpool_actor
+                .send(RebroadcastTransactions { txn_hashes })
+                .await?
+        }
+        .map_err(map_err);
+        Box::pin(fut.boxed())
+    }
+}
+
+/// Check the pending `txn` in order: expiration, sequence number gap, gas balance, gas price,
+/// return the first problem found.
+async fn diagnose_txn<S, State, Chain>(
+    pool: &S,
+    chain_state: State,
+    chain: &Chain,
+    now_seconds: u64,
+    txn: &SignedUserTransaction,
+) -> anyhow::Result<TxnVerdict>
+where
+    S: TxPoolSyncService,
+    State: ChainStateAsyncService,
+    Chain: ChainAsyncService,
+{
+    if txn.expiration_timestamp_secs() <= now_seconds {
+        return Ok(TxnVerdict::Expired {
+            expiration_timestamp_secs: txn.expiration_timestamp_secs().into(),
+            now_seconds,
+        });
+    }
+
+    let onchain_sequence_number = chain_state
+        .clone()
+        .get_resource::<AccountResource>(txn.sender())
+        .await?
+        .map(|account| account.sequence_number())
+        .unwrap_or_default();
+    let pending_sequence_numbers: HashSet<u64> = pool
+        .txns_of_sender(&txn.sender(), None)
+        .iter()
+        .map(|txn| txn.sequence_number())
+        .collect();
+    let (missing_count, missing_sequence_numbers) = missing_sequence_numbers(
+        onchain_sequence_number,
+        txn.sequence_number(),
+        &pending_sequence_numbers,
+    );
+    if missing_count > 0 {
+        return Ok(TxnVerdict::SequenceGap {
+            onchain_sequence_number: onchain_sequence_number.into(),
+            missing_count: missing_count.into(),
+            missing_sequence_numbers: missing_sequence_numbers
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        });
+    }
+
+    let balance = gas_token_balance(chain_state, txn).await?;
+    let max_gas_cost = max_gas_cost(txn);
+    if balance < max_gas_cost {
+        return Ok(TxnVerdict::InsufficientBalance {
+            balance: balance.into(),
+            max_gas_cost: max_gas_cost.into(),
+        });
+    }
+
+    let recent_min_gas_price = chain
+        .main_blocks_by_number(None, GAS_PRICE_SAMPLE_BLOCKS)
+        .await?
+        .iter()
+        .flat_map(|block| block.transactions().iter().map(|txn| txn.gas_unit_price()))
+        .min();
+    if let Some(recent_min_gas_price) = recent_min_gas_price {
+        if txn.gas_unit_price() < recent_min_gas_price {
+            return Ok(TxnVerdict::GasPriceTooLow {
+                gas_unit_price: txn.gas_unit_price().into(),
+                recent_min_gas_price: recent_min_gas_price.into(),
+            });
+        }
+    }
+    Ok(TxnVerdict::Pending)
+}
+
+/// Return the count of the sequence numbers in `[onchain_sequence_number, sequence_number)` which
+/// are not pending in the txpool, and the first `MAX_MISSING_SEQUENCE_NUMBERS` of them.
+fn missing_sequence_numbers(
+    onchain_sequence_number: u64,
+    sequence_number: u64,
+    pending_sequence_numbers: &HashSet<u64>,
+) -> (u64, Vec<u64>) {
+    let range = onchain_sequence_number..sequence_number;
+    let pending_in_range = pending_sequence_numbers
+        .iter()
+        .filter(|seq| range.contains(*seq))
+        .count() as u64;
+    let missing_count = sequence_number.saturating_sub(onchain_sequence_number) - pending_in_range;
+    let missing_sequence_numbers = range
+        .filter(|seq| !pending_sequence_numbers.contains(seq))
+        .take(MAX_MISSING_SEQUENCE_NUMBERS)
+        .collect();
+    (missing_count, missing_sequence_numbers)
+}
+
+/// The limits which the submitted txn is checked against.
+struct SubmitLimits {
+    min_gas_price: u64,
+    now_seconds: u64,
+    /// The block gas limit of the current epoch, None if the chain state is not available.
+    block_gas_limit: Option<u64>,
+    /// The gas token balance of the sender,
+    /// None if the sender is not on chain or the chain state is not available.
+    balance: Option<u128>,
+}
+
+async fn submit_limits<State>(
+    chain_state: Option<State>,
+    txn: &SignedUserTransaction,
+    min_gas_price: u64,
+    now_seconds: u64,
+) -> anyhow::Result<SubmitLimits>
+where
+    State: ChainStateAsyncService,
+{
+    let (block_gas_limit, balance) = match chain_state {
+        Some(chain_state) => {
+            let block_gas_limit = chain_state
+                .clone()
+                .get_resource::<Epoch>(genesis_address())
+                .await?
+                .map(|epoch| epoch.block_gas_limit());
+            // the txn of a sender not on chain is left to the vm validation, which reports the missing account.
+            let balance = if chain_state
+                .clone()
+                .get_resource::<AccountResource>(txn.sender())
+                .await?
+                .is_some()
+            {
+                Some(gas_token_balance(chain_state, txn).await?)
+            } else {
+                None
+            };
+            (block_gas_limit, balance)
+        }
+        None => (None, None),
+    };
+    Ok(SubmitLimits {
+        min_gas_price,
+        now_seconds,
+        block_gas_limit,
+        balance,
+    })
+}
+
+/// Check the gas price, max gas amount, expiration and gas balance of the txn in order,
+/// return the first failed check.
+fn check_txn_limits(
+    txn: &SignedUserTransaction,
+    limits: &SubmitLimits,
+) -> Result<(), InvalidTransactionData> {
+    if txn.gas_unit_price() < limits.min_gas_price {
+        return Err(InvalidTransactionData::GasPriceTooLow {
+            gas_unit_price: txn.gas_unit_price().into(),
+            min_gas_price: limits.min_gas_price.into(),
+        });
+    }
+    if let Some(block_gas_limit) = limits.block_gas_limit {
+        if txn.max_gas_amount() > block_gas_limit {
+            return Err(InvalidTransactionData::MaxGasAmountExceeded {
+                max_gas_amount: txn.max_gas_amount().into(),
+                block_gas_limit: block_gas_limit.into(),
+            });
+        }
+    }
+    if txn.expiration_timestamp_secs() <= limits.now_seconds {
+        return Err(InvalidTransactionData::Expired {
+            expiration_timestamp_secs: txn.expiration_timestamp_secs().into(),
+            now_seconds: limits.now_seconds,
+        });
+    }
+    if let Some(balance) = limits.balance {
+        let max_gas_cost = max_gas_cost(txn);
+        if balance < max_gas_cost {
+            return Err(InvalidTransactionData::InsufficientBalance {
+                gas_token_code: txn.gas_token_code().to_string(),
+                balance: balance.into(),
+                max_gas_cost: max_gas_cost.into(),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn max_gas_cost(txn: &SignedUserTransaction) -> u128 {
+    txn.max_gas_amount() as u128 * txn.gas_unit_price() as u128
+}
+
+async fn gas_token_balance<State>(
+    chain_state: State,
+    txn: &SignedUserTransaction,
+) -> anyhow::Result<u128>
+where
+    State: ChainStateAsyncService,
+{
+    let gas_token_code = TokenCode::from_str(txn.gas_token_code())?;
+    Ok(chain_state
+        .get(AccessPath::resource_access_path(
+            txn.sender(),
+            BalanceResource::struct_tag_for_token_code(gas_token_code),
+        ))
+        .await?
+        .map(|bytes| bcs_ext::from_bytes::<BalanceResource>(bytes.as_slice()))
+        .transpose()?
+        .map(|balance| balance.token())
+        .unwrap_or_default())
 }
 
 #[cfg(test)]
@@ -110,7 +470,49 @@ mod tests {
     use super::*;
     use futures::executor::block_on;
     use jsonrpc_core::IoHandler;
+    use starcoin_chain_service::ChainReaderService;
+    use starcoin_crypto::ed25519::genesis_key_pair;
+    use starcoin_rpc_api::errors::RpcErrorCode;
+    use starcoin_service_registry::ServiceRef;
+    use starcoin_state_api::mock::MockChainStateService;
     use starcoin_txpool_mock_service::MockTxPoolService;
+    use starcoin_types::account_address::AccountAddress;
+    use starcoin_vm_types::genesis_config::ChainId;
+    use starcoin_vm_types::token::stc::STC_TOKEN_CODE_STR;
+    use starcoin_vm_types::transaction::{RawUserTransaction, Script, TransactionPayload};
+
+    fn mock_txn(
+        max_gas_amount: u64,
+        gas_unit_price: u64,
+        expiration_timestamp_secs: u64,
+    ) -> SignedUserTransaction {
+        let (private_key, public_key) = genesis_key_pair();
+        RawUserTransaction::new_with_default_gas_token(
+            AccountAddress::random(),
+            0,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            max_gas_amount,
+            gas_unit_price,
+            expiration_timestamp_secs,
+            ChainId::test(),
+        )
+        .sign(&private_key, public_key)
+        .unwrap()
+        .into_inner()
+    }
+
+    fn txpool_rpc(
+        txpool_service: MockTxPoolService,
+    ) -> TxPoolRpcImpl<MockTxPoolService, MockChainStateService, ServiceRef<ChainReaderService>>
+    {
+        TxPoolRpcImpl::new(
+            Arc::new(NodeConfig::random_for_test()),
+            txpool_service,
+            None,
+            None,
+            None,
+        )
+    }
 
     #[test]
     fn test_submit_transaction() {
@@ -120,9 +522,8 @@ mod tests {
         assert_eq!(txn, txn1);
 
         let mut io = IoHandler::new();
-        let txpool_service = MockTxPoolService::new();
-        io.extend_with(TxPoolRpcImpl::new(txpool_service).to_delegate());
-        let txn = SignedUserTransaction::mock();
+        io.extend_with(txpool_rpc(MockTxPoolService::new()).to_delegate());
+        let txn = mock_txn(10000, 1, u64::max_value());
         let txn_hash = txn.id();
         let prefix = r#"{"jsonrpc":"2.0","method":"txpool.submit_transaction","params":["#;
         let suffix = r#"],"id":0}"#;
@@ -140,4 +541,98 @@ mod tests {
             response
         );
     }
+
+    #[test]
+    fn test_submit_transaction_validate_only() {
+        let txpool_service = MockTxPoolService::new();
+        let rpc = txpool_rpc(txpool_service.clone());
+
+        let txn = mock_txn(10000, 1, u64::max_value());
+        let txn_hash = block_on(rpc.submit_transaction(txn.clone(), Some(true))).unwrap();
+        assert_eq!(txn_hash, txn.id());
+        assert!(txpool_service.get_pending_txns(None, None).is_empty());
+
+        block_on(rpc.submit_transaction(txn, None)).unwrap();
+        assert_eq!(txpool_service.get_pending_txns(None, None).len(), 1);
+
+        let err =
+            block_on(rpc.submit_transaction(mock_txn(10000, 0, u64::max_value()), Some(true)))
+                .unwrap_err();
+        assert_eq!(
+            RpcErrorCode::of(&err),
+            Some(RpcErrorCode::InvalidTransaction)
+        );
+        let data: InvalidTransactionData = serde_json::from_value(err.data.unwrap()).unwrap();
+        assert_eq!(
+            data,
+            InvalidTransactionData::GasPriceTooLow {
+                gas_unit_price: 0.into(),
+                min_gas_price: 1.into()
+            }
+        );
+    }
+
+    #[test]
+    fn test_check_txn_limits() {
+        let limits = SubmitLimits {
+            min_gas_price: 1,
+            now_seconds: 100,
+            block_gas_limit: Some(50_000),
+            balance: Some(100_000),
+        };
+        assert!(check_txn_limits(&mock_txn(50_000, 2, 101), &limits).is_ok());
+
+        assert_eq!(
+            check_txn_limits(&mock_txn(10_000, 0, 101), &limits),
+            Err(InvalidTransactionData::GasPriceTooLow {
+                gas_unit_price: 0.into(),
+                min_gas_price: 1.into(),
+            })
+        );
+        assert_eq!(
+            check_txn_limits(&mock_txn(50_001, 1, 101), &limits),
+            Err(InvalidTransactionData::MaxGasAmountExceeded {
+                max_gas_amount: 50_001.into(),
+                block_gas_limit: 50_000.into(),
+            })
+        );
+        assert_eq!(
+            check_txn_limits(&mock_txn(10_000, 1, 100), &limits),
+            Err(InvalidTransactionData::Expired {
+                expiration_timestamp_secs: 100.into(),
+                now_seconds: 100,
+            })
+        );
+        assert_eq!(
+            check_txn_limits(&mock_txn(50_000, 3, 101), &limits),
+            Err(InvalidTransactionData::InsufficientBalance {
+                gas_token_code: STC_TOKEN_CODE_STR.to_string(),
+                balance: 100_000.into(),
+                max_gas_cost: 150_000.into(),
+            })
+        );
+
+        // the checks which need the chain state are skipped without it.
+        let limits = SubmitLimits {
+            block_gas_limit: None,
+            balance: None,
+            ..limits
+        };
+        assert!(check_txn_limits(&mock_txn(u64::max_value(), 1, 101), &limits).is_ok());
+    }
+
+    #[test]
+    fn test_missing_sequence_numbers() {
+        let pending: HashSet<u64> = vec![5, 6, 8, 20].into_iter().collect();
+        assert_eq!(missing_sequence_numbers(5, 10, &pending), (2, vec![7, 9]));
+        assert_eq!(missing_sequence_numbers(5, 7, &pending), (0, vec![]));
+        // the on chain sequence number is ahead of the txn.
+        assert_eq!(missing_sequence_numbers(10, 5, &pending), (0, vec![]));
+
+        // the collected sequence numbers are capped for a far ahead txn.
+        let (missing_count, missing) = missing_sequence_numbers(5, u64::max_value(), &pending);
+        assert_eq!(missing_count, u64::max_value() - 5 - 4);
+        assert_eq!(missing.len(), MAX_MISSING_SEQUENCE_NUMBERS);
+        assert_eq!(&missing[..3], &[7, 9, 10]);
+    }
 }
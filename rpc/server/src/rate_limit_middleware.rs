@@ -1,14 +1,18 @@
 use jsonrpc_core::futures::future::Either;
 use jsonrpc_core::futures::Future;
-use jsonrpc_core::{Call, Error, ErrorCode, Failure, FutureResponse, Id, Middleware, Output};
+use jsonrpc_core::{Call, Failure, FutureResponse, Id, Middleware, Output};
 
 type MethodName = String;
 
 use api_limiter::ApiLimiters;
 pub use api_limiter::Quota;
 use jsonrpc_core::middleware::NoopCallFuture;
+use parking_lot::RwLock;
 use starcoin_config::{ApiQuotaConfig, ApiQuotaConfiguration, QuotaDuration};
+use starcoin_rpc_api::errors::{RateLimitedData, RpcErrorCode};
 use starcoin_rpc_api::metadata::Metadata;
+use std::collections::HashSet;
+use std::sync::Arc;
 
 struct QuotaWrapper(Quota);
 
@@ -24,27 +28,80 @@ impl From<ApiQuotaConfig> for QuotaWrapper {
 }
 
 #[derive(Debug)]
-pub struct JsonApiRateLimitMiddleware {
-    limiters: ApiLimiters<MethodName, String>,
+struct RateLimiters {
+    /// None if the api quota is disabled.
+    limiters: Option<ApiLimiters<MethodName, String>>,
+    /// The apis which have a customized quota.
+    custom_apis: HashSet<MethodName>,
+    /// The namespace quotas, such as `chain.*`, which are shared by all apis in the namespace.
+    namespace_quotas: HashSet<MethodName>,
 }
 
-impl JsonApiRateLimitMiddleware {
-    pub fn from_config(quotas: ApiQuotaConfiguration) -> Self {
+impl RateLimiters {
+    fn from_config(quotas: ApiQuotaConfiguration) -> Self {
+        if quotas.disable_api_quota() {
+            return Self {
+                limiters: None,
+                custom_apis: HashSet::new(),
+                namespace_quotas: HashSet::new(),
+            };
+        }
+        let custom_global_api_quota = quotas.custom_global_api_quota();
+        let custom_user_api_quota = quotas.custom_user_api_quota();
+        let (namespace_quotas, custom_apis): (HashSet<_>, HashSet<_>) = custom_global_api_quota
+            .iter()
+            .chain(custom_user_api_quota.iter())
+            .map(|(k, _)| k.clone())
+            .partition(|k| k.ends_with(".*"));
         let limiters = ApiLimiters::new(
             Into::<QuotaWrapper>::into(quotas.default_global_api_quota()).0,
-            quotas
-                .custom_global_api_quota()
+            custom_global_api_quota
                 .into_iter()
                 .map(|(k, v)| (k, Into::<QuotaWrapper>::into(v).0))
                 .collect(),
             Into::<QuotaWrapper>::into(quotas.default_user_api_quota()).0,
-            quotas
-                .custom_user_api_quota()
+            custom_user_api_quota
                 .into_iter()
                 .map(|(k, v)| (k, Into::<QuotaWrapper>::into(v).0))
                 .collect(),
         );
-        Self { limiters }
+        Self {
+            limiters: Some(limiters),
+            custom_apis,
+            namespace_quotas,
+        }
+    }
+
+    /// The quota key of the method, the api quota takes precedence over the namespace quota.
+    fn quota_key(&self, method: &str) -> MethodName {
+        if !self.custom_apis.contains(method) {
+            if let Some(namespace) = method.splitn(2, '.').next() {
+                let namespace_key = format!("{}.*", namespace);
+                if self.namespace_quotas.contains(&namespace_key) {
+                    return namespace_key;
+                }
+            }
+        }
+        method.to_string()
+    }
+}
+
+/// The rate limit middleware, the clones share the same limiters, so the quotas can be updated at runtime.
+#[derive(Clone, Debug)]
+pub struct JsonApiRateLimitMiddleware {
+    inner: Arc<RwLock<RateLimiters>>,
+}
+
+impl JsonApiRateLimitMiddleware {
+    pub fn from_config(quotas: ApiQuotaConfiguration) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(RateLimiters::from_config(quotas))),
+        }
+    }
+
+    /// Replace the quotas, the rate limit states are reset.
+    pub fn update_quotas(&self, quotas: ApiQuotaConfiguration) {
+        *self.inner.write() = RateLimiters::from_config(quotas);
     }
 }
 
@@ -63,17 +120,23 @@ impl Middleware<Metadata> for JsonApiRateLimitMiddleware {
             Call::Notification(n) => Some((n.method.clone(), n.jsonrpc, Id::Null)),
             Call::Invalid { .. } => None,
         };
+        let inner = self.inner.read();
+        let limiters = match &inner.limiters {
+            Some(limiters) => limiters,
+            None => return Either::Right(next(call, meta)),
+        };
         if let Some((m, json_version, id)) = method {
-            match self.limiters.check(&m, meta.user.as_ref()) {
+            match limiters.check(&inner.quota_key(&m), meta.user.as_ref()) {
                 Ok(_) => Either::Right(next(call, meta)),
                 Err(e) => {
                     let output = Output::Failure(Failure {
                         jsonrpc: json_version,
-                        error: Error {
-                            code: ErrorCode::ServerError(-10000),
-                            message: e.to_string(),
-                            data: None,
-                        },
+                        error: RpcErrorCode::RateLimited.error_with_data(
+                            format!("{}: {}", m, e),
+                            &RateLimitedData {
+                                retry_after_ms: e.retry_after.as_millis() as u64,
+                            },
+                        ),
                         id,
                     });
                     Either::Left(Box::pin(futures::future::ready(Some(output))))
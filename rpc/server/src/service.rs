@@ -3,6 +3,7 @@
 
 use crate::api_registry::ApiRegistry;
 use crate::extractors::{RpcExtractor, WsExtractor};
+use crate::health::{HealthMonitor, HealthRequestMiddleware};
 use anyhow::Result;
 use futures::stream::*;
 use futures::{FutureExt, StreamExt};
@@ -15,7 +16,7 @@ use jsonrpc_core_client::{
 use jsonrpc_pubsub::Session;
 use jsonrpc_server_utils::cors::AccessControlAllowOrigin;
 use jsonrpc_server_utils::hosts::DomainsValidation;
-use starcoin_config::{Api, ApiSet, NodeConfig};
+use starcoin_config::{Api, ApiSet, NodeConfig, NodeConfigChangedEvent};
 use starcoin_logger::prelude::*;
 use starcoin_rpc_api::contract_api::ContractApi;
 use starcoin_rpc_api::metadata::Metadata;
@@ -27,7 +28,7 @@ use starcoin_rpc_api::{
     account::AccountApi, chain::ChainApi, debug::DebugApi, miner::MinerApi, node::NodeApi,
     pubsub::StarcoinPubSub, state::StateApi, txpool::TxPoolApi,
 };
-use starcoin_service_registry::{ActorService, ServiceContext, ServiceHandler};
+use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceHandler};
 use std::collections::HashSet;
 use std::ops::Deref;
 use std::sync::Arc;
@@ -35,6 +36,7 @@ use std::sync::Arc;
 pub struct RpcService {
     config: Arc<NodeConfig>,
     api_registry: ApiRegistry,
+    health_monitor: Option<HealthMonitor>,
     ipc: Option<jsonrpc_ipc_server::Server>,
     http: Option<jsonrpc_http_server::Server>,
     tcp: Option<jsonrpc_tcp_server::Server>,
@@ -42,7 +44,8 @@ pub struct RpcService {
 }
 
 impl ActorService for RpcService {
-    fn started(&mut self, _ctx: &mut ServiceContext<Self>) -> Result<()> {
+    fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.subscribe::<NodeConfigChangedEvent>();
         self.ipc = self.start_ipc()?;
         self.http = self.start_http()?;
         self.tcp = self.start_tcp()?;
@@ -50,17 +53,34 @@ impl ActorService for RpcService {
         Ok(())
     }
 
-    fn stopped(&mut self, _ctx: &mut ServiceContext<Self>) -> Result<()> {
+    fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.unsubscribe::<NodeConfigChangedEvent>();
         self.close();
         Ok(())
     }
 }
 
+impl EventHandler<Self, NodeConfigChangedEvent> for RpcService {
+    fn handle_event(&mut self, msg: NodeConfigChangedEvent, _ctx: &mut ServiceContext<Self>) {
+        if msg.is_changed("rpc.api_quotas") {
+            info!("Rpc api quotas changed, update the rate limiters.");
+            self.api_registry
+                .update_api_quotas(msg.config.rpc.api_quotas.clone());
+        }
+        if msg.is_changed("rpc.api_filter") {
+            info!("Rpc api filter changed, update the enabled methods.");
+            self.api_registry
+                .update_api_filter(msg.config.rpc.api_filter.clone());
+        }
+    }
+}
+
 impl RpcService {
     pub fn new(config: Arc<NodeConfig>, api_registry: ApiRegistry) -> Self {
         Self {
             config,
             api_registry,
+            health_monitor: None,
             ipc: None,
             http: None,
             tcp: None,
@@ -68,6 +88,12 @@ impl RpcService {
         }
     }
 
+    /// Serve the http `GET /health` by the monitor.
+    pub fn with_health_monitor(mut self, health_monitor: HealthMonitor) -> Self {
+        self.health_monitor = Some(health_monitor);
+        self
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn new_with_api<C, N, NM, SM, NWM, T, A, S, D, P, M, Contract>(
         config: Arc<NodeConfig>,
@@ -98,7 +124,8 @@ impl RpcService {
         M: MinerApi,
         Contract: ContractApi,
     {
-        let mut api_registry = ApiRegistry::new(config.rpc.api_quotas.clone());
+        let mut api_registry =
+            ApiRegistry::new(config.rpc.api_quotas.clone(), config.rpc.api_filter.clone());
 
         api_registry.register(Api::Node, NodeApi::to_delegate(node_api));
         if let Some(node_manager_api) = node_manager_api {
@@ -152,7 +179,8 @@ impl RpcService {
         } else {
             let ipc_file = self.config.rpc.get_ipc_file();
             let apis: HashSet<Api> = self.config.rpc.ipc.apis().list_apis();
-            let io_handler = self.api_registry.get_apis(apis);
+            // the ipc socket is local, the api filter does not apply to it.
+            let io_handler = self.api_registry.get_apis(apis, false);
 
             info!("Ipc rpc server start at :{:?}", ipc_file);
             Some(
@@ -167,10 +195,16 @@ impl RpcService {
         Ok(if let Some(addr) = self.config.rpc.get_http_address() {
             let address = addr.into();
             let apis = self.config.rpc.http.apis().list_apis();
-            let io_handler = self.api_registry.get_apis(apis);
-            let http = jsonrpc_http_server::ServerBuilder::new(io_handler)
+            let io_handler = self.api_registry.get_apis(apis, true);
+            let mut builder = jsonrpc_http_server::ServerBuilder::new(io_handler);
+            if let Some(health_monitor) = self.health_monitor.clone() {
+                builder = builder.request_middleware(HealthRequestMiddleware::new(health_monitor));
+            }
+            let http = builder
                 .meta_extractor(RpcExtractor {
                     http_ip_headers: self.config.rpc.http.ip_headers(),
+                    http_api_key_header: self.config.rpc.http.api_key_header(),
+                    http_api_keys: self.config.rpc.http.api_keys(),
                 })
                 .cors(DomainsValidation::AllowOnly(vec![
                     AccessControlAllowOrigin::Null,
@@ -192,7 +226,7 @@ impl RpcService {
             let address = addr.into();
             let apis = self.config.rpc.tcp.apis().list_apis();
 
-            let io_handler = self.api_registry.get_apis(apis);
+            let io_handler = self.api_registry.get_apis(apis, true);
             let tcp_server = jsonrpc_tcp_server::ServerBuilder::new(io_handler)
                 .session_meta_extractor(RpcExtractor::default())
                 .start(&address)?;
@@ -207,7 +241,7 @@ impl RpcService {
         Ok(if let Some(addr) = self.config.rpc.get_ws_address() {
             let address = addr.into();
             let apis = self.config.rpc.ws.apis().list_apis();
-            let io_handler = self.api_registry.get_apis(apis);
+            let io_handler = self.api_registry.get_apis(apis, true);
             let ws_server = jsonrpc_ws_server::ServerBuilder::new(io_handler)
                 .session_meta_extractor(WsExtractor)
                 .max_payload(self.config.rpc.ws.max_request_body_size())
@@ -264,7 +298,7 @@ pub fn connect_local(
 impl ServiceHandler<Self, ConnectLocal> for RpcService {
     fn handle(&mut self, _msg: ConnectLocal, ctx: &mut ServiceContext<RpcService>) -> RpcChannel {
         let apis = ApiSet::All.list_apis();
-        let io_handler = self.api_registry.get_apis(apis);
+        let io_handler = self.api_registry.get_apis(apis, false);
         //remove middleware.
         let mut local_io_handler = MetaIoHandler::default();
         local_io_handler.extend_with(io_handler.iter().map(|(n, f)| (n.clone(), f.clone())));
@@ -8,8 +8,8 @@ function rand(){
     echo $(($num%$max+$min))
 }
 # get last header
-#current_number=`~/kubectl --kubeconfig ~/.kube/starcoin_config -n starcoin-centauri exec starcoin-stress-2 -c starcoin --stdin --tty -- /starcoin/starcoin -c /sc-data/centauri/starcoin.ipc -o json chain info |grep -v INFO |jq .ok.head.number | sed 's/"//g'`
-current_number=`target/debug/starcoin -c data/centauri/starcoin.ipc -ojson chain info |grep -v INFO |jq .ok.head.number | sed 's/"//g'`
+#current_number=`~/kubectl --kubeconfig ~/.kube/starcoin_config -n starcoin-centauri exec starcoin-stress-2 -c starcoin --stdin --tty -- /starcoin/starcoin -c /sc-data/centauri/starcoin.ipc -o json chain info |grep -v INFO |jq .head.number | sed 's/"//g'`
+current_number=`target/debug/starcoin -c data/centauri/starcoin.ipc -ojson chain info |grep -v INFO |jq .head.number | sed 's/"//g'`
 echo "current number: $current_number"
 rnd_block_num=$(rand 1 current_number)
 
@@ -17,13 +17,16 @@ use starcoin_types::{
     state_set::ChainStateSet,
 };
 use starcoin_vm_types::account_config::{genesis_address, STC_TOKEN_CODE};
-use starcoin_vm_types::genesis_config::ChainId;
+use starcoin_vm_types::genesis_config::{ChainId, StdlibVersion};
 use starcoin_vm_types::language_storage::ModuleId;
 use starcoin_vm_types::on_chain_resource::{Epoch, EpochData, EpochInfo, GlobalTimeOnChain};
 use starcoin_vm_types::sips::SIP;
 use starcoin_vm_types::token::token_code::TokenCode;
+use starcoin_vm_types::token::token_info::TokenInfoResource;
 use starcoin_vm_types::{
-    move_resource::MoveResource, on_chain_config::OnChainConfig, state_view::StateView,
+    move_resource::MoveResource,
+    on_chain_config::{OnChainConfig, Version},
+    state_view::StateView,
 };
 use std::convert::TryFrom;
 use std::sync::Arc;
@@ -276,6 +279,13 @@ pub trait StateReaderExt: ChainStateReader {
         self.get_balance_by_type(address, token_code.into())
     }
 
+    /// Get the on chain `Token::TokenInfo` of the token.
+    fn get_token_info(&self, token_code: TokenCode) -> Result<Option<TokenInfoResource>> {
+        self.get(&TokenInfoResource::resource_path_for(token_code))?
+            .map(|bytes| TokenInfoResource::try_from_bytes(bytes.as_slice()))
+            .transpose()
+    }
+
     fn get_epoch(&self) -> Result<Epoch> {
         self.get_resource::<Epoch>(genesis_address())?
             .ok_or_else(|| format_err!("Epoch is none."))
@@ -303,6 +313,18 @@ pub trait StateReaderExt: ChainStateReader {
             .ok_or_else(|| format_err!("ChainId resource should exist at genesis address. "))
     }
 
+    /// Get the stdlib version of the chain from the on chain `Version` config,
+    /// the chain which starts with the latest stdlib has version 0.
+    fn get_stdlib_version(&self) -> Result<StdlibVersion> {
+        let version = self
+            .get_on_chain_config::<Version>()?
+            .ok_or_else(|| format_err!("Version config should exist at genesis address."))?;
+        Ok(match version.major {
+            0 => StdlibVersion::Latest,
+            major => StdlibVersion::new(major),
+        })
+    }
+
     fn get_code(&self, module_id: ModuleId) -> Result<Option<Vec<u8>>> {
         self.get(&AccessPath::from(&module_id))
     }
@@ -375,6 +397,10 @@ where
         self.reader.get_balance_by_token_code(*address, token_code)
     }
 
+    pub fn get_token_info(&self, token_code: TokenCode) -> Result<Option<TokenInfoResource>> {
+        self.reader.get_token_info(token_code)
+    }
+
     pub fn get_epoch(&self) -> Result<Epoch> {
         self.reader.get_epoch()
     }
@@ -390,4 +416,8 @@ where
     pub fn get_chain_id(&self) -> Result<ChainId> {
         self.reader.get_chain_id()
     }
+
+    pub fn get_stdlib_version(&self) -> Result<StdlibVersion> {
+        self.reader.get_stdlib_version()
+    }
 }
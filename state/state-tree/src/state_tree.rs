@@ -221,6 +221,45 @@ where
         Ok(StateSet::new(states))
     }
 
+    /// Visit the key value pairs of the tree in the key hash order, the pairs are read one by one,
+    /// so it does not load the whole tree into memory like `dump`.
+    pub fn for_each<F>(&self, mut f: F) -> Result<()>
+    where
+        F: FnMut(K, Blob) -> Result<()>,
+    {
+        let cur_root_hash = self.root_hash();
+        let mut cache_guard = self.cache.lock();
+        let cache = cache_guard.deref_mut();
+        let reader = CachedTreeReader {
+            store: self.storage.as_ref(),
+            cache,
+        };
+        let iterator = JellyfishMerkleIterator::new(&reader, cur_root_hash, HashValue::zero())?;
+        for item in iterator {
+            let (key, blob) = item?;
+            f(key, blob)?;
+        }
+        Ok(())
+    }
+
+    /// Read at most `limit` key value pairs of the tree in the key hash order, start from `start`(inclusive),
+    /// the pairs before `start` are skipped by the tree path, so a page of a large tree is read cheaply.
+    pub fn scan(&self, start: Option<&K>, limit: usize) -> Result<Vec<(K, Blob)>> {
+        let cur_root_hash = self.root_hash();
+        let mut cache_guard = self.cache.lock();
+        let cache = cache_guard.deref_mut();
+        let reader = CachedTreeReader {
+            store: self.storage.as_ref(),
+            cache,
+        };
+        let starting_key = start
+            .map(|key| key.key_hash())
+            .unwrap_or_else(HashValue::zero);
+        JellyfishMerkleIterator::new(&reader, cur_root_hash, starting_key)?
+            .take(limit)
+            .collect()
+    }
+
     /// passing None value with a key means delete the key
     fn updates(&self, updates: Vec<(K, Option<Blob>)>) -> Result<HashValue> {
         let cur_root_hash = self.root_hash();
@@ -166,6 +166,31 @@ pub fn test_state_dump() -> Result<()> {
     Ok(())
 }
 
+#[test]
+pub fn test_state_scan() -> Result<()> {
+    let s = MockStateNodeStore::new();
+    let state = StateTree::new(Arc::new(s), None);
+    for i in 0u64..10 {
+        state.put(i, vec![i as u8]);
+    }
+    state.commit()?;
+    let all = state.scan(None, 100)?;
+    assert_eq!(all.len(), 10);
+    let keys: Vec<u64> = all.iter().map(|(k, _)| *k).collect();
+    let hashes: Vec<HashValue> = keys.iter().map(|k| k.key_hash()).collect();
+    let mut sorted = hashes.clone();
+    sorted.sort();
+    assert_eq!(hashes, sorted);
+
+    let page = state.scan(Some(&keys[3]), 4)?;
+    assert_eq!(
+        page.iter().map(|(k, _)| *k).collect::<Vec<_>>(),
+        keys[3..7].to_vec()
+    );
+    assert_eq!(state.scan(Some(&keys[9]), 4)?.len(), 1);
+    Ok(())
+}
+
 #[test]
 pub fn test_repeat_commit() -> Result<()> {
     let s = MockStateNodeStore::new();
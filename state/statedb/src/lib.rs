@@ -249,6 +249,41 @@ impl ChainStateDB {
         StateTree::new(self.store.clone(), Some(root_hash))
     }
 
+    fn account_state_set(&self, account_state: &AccountState) -> Result<AccountStateSet> {
+        let mut state_sets = vec![];
+        for (idx, storage_root) in account_state.storage_roots().iter().enumerate() {
+            let state_set = match storage_root {
+                Some(storage_root) => {
+                    let data_type = DataType::from_index(idx as u8)?;
+                    match data_type {
+                        DataType::CODE => {
+                            Some(self.new_state_tree::<ModuleName>(*storage_root).dump()?)
+                        }
+                        DataType::RESOURCE => {
+                            Some(self.new_state_tree::<StructTag>(*storage_root).dump()?)
+                        }
+                    }
+                }
+                None => None,
+            };
+            state_sets.push(state_set);
+        }
+        Ok(AccountStateSet::new(state_sets))
+    }
+
+    /// Visit the state set of every account, only one account is loaded into memory at a time,
+    /// so it is used to scan the whole state of a large chain instead of `dump`.
+    pub fn for_each_account_state_set<F>(&self, mut f: F) -> Result<()>
+    where
+        F: FnMut(AccountAddress, AccountStateSet) -> Result<()>,
+    {
+        self.state_tree.for_each(|address, account_state_bytes| {
+            let account_state_bytes: Vec<u8> = account_state_bytes.into();
+            let account_state: AccountState = account_state_bytes.as_slice().try_into()?;
+            f(address, self.account_state_set(&account_state)?)
+        })
+    }
+
     fn get_account_state_object(
         &self,
         account_address: &AccountAddress,
@@ -396,28 +431,7 @@ impl ChainStateReader for ChainStateDB {
         let mut account_states = vec![];
         for (address_bytes, account_state_bytes) in global_states.iter() {
             let account_state: AccountState = account_state_bytes.as_slice().try_into()?;
-
-            let mut state_sets = vec![];
-            for (idx, storage_root) in account_state.storage_roots().iter().enumerate() {
-                let state_set = match storage_root {
-                    Some(storage_root) => {
-                        let data_type = DataType::from_index(idx as u8)?;
-                        match data_type {
-                            DataType::CODE => {
-                                Some(self.new_state_tree::<ModuleName>(*storage_root).dump()?)
-                            }
-                            DataType::RESOURCE => {
-                                Some(self.new_state_tree::<StructTag>(*storage_root).dump()?)
-                            }
-                        }
-                    }
-                    None => None,
-                };
-
-                state_sets.push(state_set);
-            }
-            let account_state_set = AccountStateSet::new(state_sets);
-
+            let account_state_set = self.account_state_set(&account_state)?;
             account_states.push((
                 AccountAddress::decode_key(address_bytes.as_slice())?,
                 account_state_set,
@@ -1,10 +1,12 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
+use crate::db_storage::DBStorage;
 use crate::define_storage;
-use crate::storage::{CodecKVStore, StorageInstance, ValueCodec};
+use crate::storage::{CodecKVStore, CodecWriteBatch, KeyCodec, StorageInstance, ValueCodec};
 use crate::{
     BLOCK_BODY_PREFIX_NAME, BLOCK_HEADER_PREFIX_NAME, BLOCK_PREFIX_NAME,
     BLOCK_TRANSACTIONS_PREFIX_NAME, BLOCK_TRANSACTION_INFOS_PREFIX_NAME, FAILED_BLOCK_PREFIX_NAME,
+    UNCLE_INDEX_PREFIX_NAME,
 };
 use anyhow::{bail, Result};
 use bcs_ext::BCSCodec;
@@ -13,6 +15,7 @@ use logger::prelude::*;
 use serde::{Deserialize, Serialize};
 use starcoin_types::block::{Block, BlockBody, BlockHeader};
 use starcoin_types::peer_info::PeerId;
+use std::sync::Arc;
 
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct FailedBlock {
@@ -69,6 +72,12 @@ define_storage!(
     FailedBlock,
     FAILED_BLOCK_PREFIX_NAME
 );
+define_storage!(
+    UncleIndexStorage,
+    HashValue,
+    Vec<HashValue>,
+    UNCLE_INDEX_PREFIX_NAME
+);
 
 #[derive(Clone)]
 pub struct BlockStorage {
@@ -78,6 +87,8 @@ pub struct BlockStorage {
     block_txns_store: BlockTransactionsStorage,
     block_txn_infos_store: BlockTransactionInfosStorage,
     failed_block_storage: FailedBlockStorage,
+    uncle_index_storage: UncleIndexStorage,
+    db: Option<Arc<DBStorage>>,
 }
 
 impl ValueCodec for Block {
@@ -128,7 +139,9 @@ impl BlockStorage {
             body_store: BlockBodyStorage::new(instance.clone()),
             block_txns_store: BlockTransactionsStorage::new(instance.clone()),
             block_txn_infos_store: BlockTransactionInfosStorage::new(instance.clone()),
-            failed_block_storage: FailedBlockStorage::new(instance),
+            failed_block_storage: FailedBlockStorage::new(instance.clone()),
+            uncle_index_storage: UncleIndexStorage::new(instance.clone()),
+            db: instance.db(),
         }
     }
     pub fn save(&self, block: Block) -> Result<()> {
@@ -174,6 +187,10 @@ impl BlockStorage {
         //save header
         let block_id = header.id();
         self.save_header(header)?;
+        //index uncles
+        if let Some(uncles) = body.uncles.as_ref() {
+            self.index_uncles(block_id, uncles)?;
+        }
         //save body
         self.save_body(block_id, body)?;
         //save block cache
@@ -217,6 +234,30 @@ impl BlockStorage {
         self.block_txn_infos_store.put(block_id, txn_info_ids)
     }
 
+    /// Delete the block, body, txn ids and txn info ids of the block, the header is kept.
+    /// Return the size of the deleted values.
+    pub fn prune_block_data(&self, block_id: HashValue) -> Result<u64> {
+        let mut pruned_bytes = 0;
+        if let Some(block) = self.block_store.get(block_id)? {
+            pruned_bytes += block.encode_value()?.len();
+        }
+        if let Some(body) = self.body_store.get(block_id)? {
+            pruned_bytes += body.encode_value()?.len();
+        }
+        if let Some(txn_ids) = self.block_txns_store.get(block_id)? {
+            pruned_bytes += txn_ids.encode_value()?.len();
+        }
+        if let Some(txn_info_ids) = self.block_txn_infos_store.get(block_id)? {
+            pruned_bytes += txn_info_ids.encode_value()?.len();
+        }
+        self.block_store.remove(block_id)?;
+        self.body_store.remove(block_id)?;
+        self.block_txns_store.remove(block_id)?;
+        // the txn info ids are removed at last, so an interrupted pruning can be resumed by them.
+        self.block_txn_infos_store.remove(block_id)?;
+        Ok(pruned_bytes as u64)
+    }
+
     pub fn save_failed_block(
         &self,
         block_id: HashValue,
@@ -237,4 +278,41 @@ impl BlockStorage {
             None => Ok(None),
         }
     }
+
+    pub fn get_block_ids_by_uncle(&self, uncle_id: HashValue) -> Result<Vec<HashValue>> {
+        Ok(self.uncle_index_storage.get(uncle_id)?.unwrap_or_default())
+    }
+
+    fn index_uncles(&self, block_id: HashValue, uncles: &[BlockHeader]) -> Result<()> {
+        let mut batch = CodecWriteBatch::new();
+        for uncle in uncles {
+            let mut block_ids = self.get_block_ids_by_uncle(uncle.id())?;
+            if !block_ids.contains(&block_id) {
+                block_ids.push(block_id);
+                batch.put(uncle.id(), block_ids)?;
+            }
+        }
+        self.uncle_index_storage.write_batch(batch)
+    }
+
+    /// Index the uncles of all the block bodies in db, return the count of the blocks which have uncles.
+    /// The cache only storage is always indexed by `commit_block`, so nothing to do.
+    pub fn rebuild_uncle_index(&self) -> Result<usize> {
+        let db = match self.db.as_ref() {
+            Some(db) => db,
+            None => return Ok(0),
+        };
+        let mut iter = db.iter(BLOCK_BODY_PREFIX_NAME)?;
+        iter.seek_to_first();
+        let mut count = 0;
+        for item in iter {
+            let (key, value) = item?;
+            let block_id = HashValue::decode_key(key.as_slice())?;
+            if let Some(uncles) = BlockBody::decode_value(value.as_slice())?.uncles {
+                self.index_uncles(block_id, &uncles)?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
 }
@@ -4,10 +4,28 @@
 use crate::storage::{ColumnFamily, InnerStorage, KVStore};
 use crate::CHAIN_INFO_PREFIX_NAME;
 use anyhow::Result;
+use bcs_ext::BCSCodec;
 use crypto::HashValue;
+use serde::{Deserialize, Serialize};
+use starcoin_types::block::BlockNumber;
 use starcoin_types::startup_info::StartupInfo;
 use std::convert::TryInto;
 
+/// The progress of the block data pruning.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct PruneInfo {
+    /// The data of the main chain blocks in `[1, pruned_before)` are pruned, the genesis is always kept.
+    pub pruned_before: BlockNumber,
+    /// The size of the deleted values, the space is reclaimed after the db compaction.
+    pub pruned_bytes: u64,
+}
+
+impl PruneInfo {
+    pub fn is_pruned(&self, number: BlockNumber) -> bool {
+        number > 0 && number < self.pruned_before
+    }
+}
+
 #[derive(Clone)]
 pub struct ChainInfoColumnFamily;
 
@@ -25,6 +43,12 @@ pub type ChainInfoStorage = InnerStorage<ChainInfoColumnFamily>;
 impl ChainInfoStorage {
     const STARTUP_INFO_KEY: &'static str = "startup_info";
     const GENESIS_KEY: &'static str = "genesis";
+    const UNCLE_INDEX_KEY: &'static str = "uncle_index";
+    const PRUNE_INFO_KEY: &'static str = "prune_info";
+    const TXN_POSITION_INDEX_KEY: &'static str = "txn_position_index";
+    const TXN_POSITION_INDEX_PROGRESS_KEY: &'static str = "txn_position_index_progress";
+    const TOKEN_HOLDER_INDEX_HEAD_KEY: &'static str = "token_holder_index_head";
+    const NEXT_EVENT_CURSOR_ID_KEY: &'static str = "next_event_cursor_id";
 
     pub fn get_startup_info(&self) -> Result<Option<StartupInfo>> {
         self.get(Self::STARTUP_INFO_KEY.as_bytes())
@@ -55,4 +79,79 @@ impl ChainInfoStorage {
             genesis_block_hash.to_vec(),
         )
     }
+
+    pub fn is_uncle_index_built(&self) -> Result<bool> {
+        Ok(self.get(Self::UNCLE_INDEX_KEY.as_bytes())?.is_some())
+    }
+
+    pub fn save_uncle_index_built(&self) -> Result<()> {
+        self.put(Self::UNCLE_INDEX_KEY.as_bytes().to_vec(), vec![1u8])
+    }
+
+    pub fn is_txn_position_index_built(&self) -> Result<bool> {
+        Ok(self.get(Self::TXN_POSITION_INDEX_KEY.as_bytes())?.is_some())
+    }
+
+    pub fn save_txn_position_index_built(&self) -> Result<()> {
+        self.put(Self::TXN_POSITION_INDEX_KEY.as_bytes().to_vec(), vec![1u8])?;
+        self.remove(Self::TXN_POSITION_INDEX_PROGRESS_KEY.as_bytes().to_vec())
+    }
+
+    /// The last block indexed by the interrupted txn position index backfill.
+    pub fn get_txn_position_index_progress(&self) -> Result<Option<HashValue>> {
+        self.get(Self::TXN_POSITION_INDEX_PROGRESS_KEY.as_bytes())?
+            .map(|bytes| HashValue::from_slice(bytes.as_slice()))
+            .transpose()
+    }
+
+    pub fn save_txn_position_index_progress(&self, block_id: HashValue) -> Result<()> {
+        self.put(
+            Self::TXN_POSITION_INDEX_PROGRESS_KEY.as_bytes().to_vec(),
+            block_id.to_vec(),
+        )
+    }
+
+    /// The block which the token holder index is consistent with, None if the index is not built.
+    pub fn get_token_holder_index_head(&self) -> Result<Option<HashValue>> {
+        self.get(Self::TOKEN_HOLDER_INDEX_HEAD_KEY.as_bytes())?
+            .map(|bytes| HashValue::from_slice(bytes.as_slice()))
+            .transpose()
+    }
+
+    pub fn save_token_holder_index_head(&self, block_id: HashValue) -> Result<()> {
+        self.put(
+            Self::TOKEN_HOLDER_INDEX_HEAD_KEY.as_bytes().to_vec(),
+            block_id.to_vec(),
+        )
+    }
+
+    pub fn remove_token_holder_index_head(&self) -> Result<()> {
+        self.remove(Self::TOKEN_HOLDER_INDEX_HEAD_KEY.as_bytes().to_vec())
+    }
+
+    pub fn get_next_event_cursor_id(&self) -> Result<Option<u64>> {
+        self.get(Self::NEXT_EVENT_CURSOR_ID_KEY.as_bytes())?
+            .map(|bytes| u64::decode(bytes.as_slice()))
+            .transpose()
+    }
+
+    pub fn save_next_event_cursor_id(&self, id: u64) -> Result<()> {
+        self.put(
+            Self::NEXT_EVENT_CURSOR_ID_KEY.as_bytes().to_vec(),
+            id.encode()?,
+        )
+    }
+
+    pub fn get_prune_info(&self) -> Result<Option<PruneInfo>> {
+        self.get(Self::PRUNE_INFO_KEY.as_bytes())?
+            .map(|bytes| PruneInfo::decode(bytes.as_slice()))
+            .transpose()
+    }
+
+    pub fn save_prune_info(&self, prune_info: PruneInfo) -> Result<()> {
+        self.put(
+            Self::PRUNE_INFO_KEY.as_bytes().to_vec(),
+            prune_info.encode()?,
+        )
+    }
 }
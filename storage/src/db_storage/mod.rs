@@ -8,11 +8,36 @@ use crate::storage::{ColumnFamilyName, InnerStore, WriteOp};
 use crate::{DEFAULT_PREFIX_NAME, VEC_PREFIX_NAME};
 use anyhow::{ensure, format_err, Error, Result};
 use rocksdb::{Options, ReadOptions, WriteBatch as DBWriteBatch, WriteOptions, DB};
+use serde::{Deserialize, Serialize};
 use starcoin_config::RocksdbConfig;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::marker::PhantomData;
 use std::path::Path;
 
+/// The size of a column family, in bytes, read from the rocksdb properties.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ColumnFamilySize {
+    pub sst_files_size: u64,
+    pub live_data_size: u64,
+    pub mem_tables_size: u64,
+    pub estimate_num_keys: u64,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct DbSizeReport {
+    pub column_families: BTreeMap<String, ColumnFamilySize>,
+}
+
+impl DbSizeReport {
+    /// The total size of the sst files and mem tables of all column families.
+    pub fn total_size(&self) -> u64 {
+        self.column_families
+            .values()
+            .map(|size| size.sst_files_size + size.mem_tables_size)
+            .sum()
+    }
+}
+
 #[allow(clippy::upper_case_acronyms)]
 pub struct DBStorage {
     db: DB,
@@ -126,8 +151,7 @@ impl DBStorage {
         Ok(())
     }
 
-    /// Flushes all memtable data. This is only used for testing `get_approximate_sizes_cf` in unit
-    /// tests.
+    /// Flushes all memtable data, it is used in tests and when the node shuts down.
     pub fn flush_all(&self) -> Result<()> {
         for cf_name in &self.cfs {
             let cf_handle = self.get_cf_handle(cf_name)?;
@@ -136,6 +160,30 @@ impl DBStorage {
         Ok(())
     }
 
+    /// Report the size of every column family, it only reads the rocksdb metadata, so it is cheap.
+    pub fn db_size_report(&self) -> Result<DbSizeReport> {
+        let mut column_families = BTreeMap::new();
+        for cf_name in &self.cfs {
+            let cf_handle = self.get_cf_handle(cf_name)?;
+            let property = |name: &str| -> Result<u64> {
+                Ok(self
+                    .db
+                    .property_int_value_cf(cf_handle, name)?
+                    .unwrap_or_default())
+            };
+            column_families.insert(
+                cf_name.to_string(),
+                ColumnFamilySize {
+                    sst_files_size: property("rocksdb.total-sst-files-size")?,
+                    live_data_size: property("rocksdb.estimate-live-data-size")?,
+                    mem_tables_size: property("rocksdb.cur-size-all-mem-tables")?,
+                    estimate_num_keys: property("rocksdb.estimate-num-keys")?,
+                },
+            );
+        }
+        Ok(DbSizeReport { column_families })
+    }
+
     /// List cf
     pub fn list_cf(path: impl AsRef<Path>) -> Result<Vec<String>, Error> {
         Ok(rocksdb::DB::list_cf(&rocksdb::Options::default(), path)?)
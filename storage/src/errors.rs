@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Error;
+use starcoin_types::block::BlockNumber;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -9,3 +10,12 @@ pub enum StorageInitError {
     #[error("Storage check error {0:?}.")]
     StorageCheckError(Error),
 }
+
+/// The data of the main chain block is deleted by the block data pruning,
+/// the header and the state of the block are still available.
+#[derive(Debug, Error)]
+#[error("The data of block {number} is pruned, the blocks before {pruned_before} are pruned.")]
+pub struct BlockPrunedError {
+    pub number: BlockNumber,
+    pub pruned_before: BlockNumber,
+}
@@ -0,0 +1,134 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::chain_info::ChainInfoStorage;
+use crate::db_storage::DBStorage;
+use crate::define_storage;
+use crate::storage::{CodecKVStore, StorageInstance, ValueCodec};
+use crate::EVENT_CURSOR_PREFIX_NAME;
+use anyhow::{ensure, format_err, Result};
+use bcs_ext::BCSCodec;
+use parking_lot::Mutex;
+use starcoin_types::event_cursor::{EventCursor, EventCursorId};
+use starcoin_types::filter::{EventPosition, Filter};
+use std::sync::Arc;
+
+impl ValueCodec for EventCursor {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Self::decode(data)
+    }
+}
+
+define_storage!(
+    EventCursorStorage,
+    EventCursorId,
+    EventCursor,
+    EVENT_CURSOR_PREFIX_NAME
+);
+
+/// The event cursors, every cursor is saved under its id,
+/// and the next cursor id is saved alone to tell the removed cursors from the unknown ones.
+#[derive(Clone)]
+pub struct EventCursors {
+    cursor_storage: EventCursorStorage,
+    chain_info_storage: ChainInfoStorage,
+    db: Option<Arc<DBStorage>>,
+    /// Serialize the create, update and removal of the cursors,
+    /// so a removed cursor is never saved back by an update.
+    lock: Arc<Mutex<()>>,
+}
+
+impl EventCursors {
+    pub fn new(instance: StorageInstance) -> Self {
+        Self {
+            cursor_storage: EventCursorStorage::new(instance.clone()),
+            chain_info_storage: ChainInfoStorage::new(instance.clone()),
+            db: instance.db(),
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    fn db(&self) -> Result<&Arc<DBStorage>> {
+        self.db
+            .as_ref()
+            .ok_or_else(|| format_err!("The event cursors require the db storage."))
+    }
+
+    pub fn get_event_cursor(&self, id: EventCursorId) -> Result<Option<EventCursor>> {
+        self.cursor_storage.get(id)
+    }
+
+    /// List all the cursors, sorted by id.
+    pub fn list_event_cursors(&self) -> Result<Vec<EventCursor>> {
+        let mut iter = self.db()?.iter(EVENT_CURSOR_PREFIX_NAME)?;
+        iter.seek_to_first();
+        iter.map(|item| {
+            let (_, value) = item?;
+            EventCursor::decode_value(value.as_slice())
+        })
+        .collect()
+    }
+
+    pub fn next_event_cursor_id(&self) -> Result<EventCursorId> {
+        Ok(self
+            .chain_info_storage
+            .get_next_event_cursor_id()?
+            .unwrap_or_default())
+    }
+
+    pub fn create_event_cursor(
+        &self,
+        filter: Filter,
+        decode: bool,
+        acked: Option<EventPosition>,
+        now_secs: u64,
+        max_cursors: usize,
+    ) -> Result<EventCursorId> {
+        let _guard = self.lock.lock();
+        let count = self.list_event_cursors()?.len();
+        ensure!(
+            count < max_cursors,
+            "Too many event cursors, at most {} cursors can be created.",
+            max_cursors
+        );
+        let id = self.next_event_cursor_id()?;
+        // save the next id first, a crash between the two puts only skips an id.
+        self.chain_info_storage.save_next_event_cursor_id(id + 1)?;
+        self.cursor_storage
+            .put(id, EventCursor::new(id, filter, decode, acked, now_secs))?;
+        Ok(id)
+    }
+
+    /// Save the cursor if it exists, return false if it is removed.
+    pub fn update_event_cursor(&self, cursor: EventCursor) -> Result<bool> {
+        let _guard = self.lock.lock();
+        if !self.cursor_storage.contains_key(cursor.id)? {
+            return Ok(false);
+        }
+        self.cursor_storage.put(cursor.id, cursor)?;
+        Ok(true)
+    }
+
+    pub fn remove_expired_event_cursors(
+        &self,
+        now_secs: u64,
+        idle_timeout_secs: u64,
+        max_lifetime_secs: u64,
+    ) -> Result<Vec<EventCursorId>> {
+        let _guard = self.lock.lock();
+        let expired = self
+            .list_event_cursors()?
+            .into_iter()
+            .filter(|cursor| cursor.is_expired(now_secs, idle_timeout_secs, max_lifetime_secs))
+            .map(|cursor| cursor.id)
+            .collect::<Vec<_>>();
+        if !expired.is_empty() {
+            self.cursor_storage.delete_all(expired.clone())?;
+        }
+        Ok(expired)
+    }
+}
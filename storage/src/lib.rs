@@ -6,22 +6,36 @@ use crate::accumulator::{
 };
 use crate::block::BlockStorage;
 use crate::block_info::{BlockInfoStorage, BlockInfoStore};
-use crate::chain_info::ChainInfoStorage;
+use crate::chain_info::{ChainInfoStorage, PruneInfo};
 use crate::contract_event::ContractEventStorage;
+use crate::db_storage::{DBStorage, DbSizeReport};
+use crate::event_cursor::EventCursors;
 use crate::state_node::StateStorage;
-use crate::storage::{CodecKVStore, CodecWriteBatch, ColumnFamilyName, StorageInstance};
+use crate::storage::{
+    CodecKVStore, CodecWriteBatch, ColumnFamilyName, KeyCodec, StorageInstance, ValueCodec,
+};
+use crate::token_holder::{TokenHolderIndex, TokenHolderIndexRebuilder, TokenHolderKey};
 use crate::transaction::TransactionStorage;
-use crate::transaction_info::{TransactionInfoHashStorage, TransactionInfoStorage};
+use crate::transaction_info::{
+    TransactionInfoHashStorage, TransactionInfoStorage, TransactionPositionStorage,
+};
+use crate::webhook::WebhookStorage;
 use anyhow::{bail, format_err, Error, Result};
 use crypto::HashValue;
+use logger::prelude::*;
 use once_cell::sync::Lazy;
 use starcoin_accumulator::node::AccumulatorStoreType;
 use starcoin_accumulator::AccumulatorTreeStore;
 use starcoin_state_store_api::{StateNode, StateNodeStore};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::token_code::TokenCode;
 use starcoin_types::contract_event::ContractEvent;
+use starcoin_types::event_cursor::{EventCursor, EventCursorId};
+use starcoin_types::filter::{EventPosition, Filter};
 use starcoin_types::peer_info::PeerId;
 use starcoin_types::startup_info::{ChainInfo, ChainStatus};
-use starcoin_types::transaction::{BlockTransactionInfo, Transaction};
+use starcoin_types::transaction::{BlockTransactionInfo, Transaction, TransactionPosition};
+use starcoin_types::webhook::WebhookRegistration;
 use starcoin_types::{
     block::{Block, BlockBody, BlockHeader, BlockInfo},
     startup_info::StartupInfo,
@@ -39,13 +53,16 @@ pub mod chain_info;
 pub mod contract_event;
 pub mod db_storage;
 pub mod errors;
+pub mod event_cursor;
 mod metrics;
 pub mod state_node;
 pub mod storage;
 #[cfg(test)]
 mod tests;
+pub mod token_holder;
 pub mod transaction;
 pub mod transaction_info;
+pub mod webhook;
 
 #[macro_use]
 pub mod storage_macros;
@@ -65,6 +82,13 @@ pub const TRANSACTION_INFO_PREFIX_NAME: ColumnFamilyName = "transaction_info";
 pub const TRANSACTION_INFO_HASH_PREFIX_NAME: ColumnFamilyName = "transaction_info_hash";
 pub const CONTRACT_EVENT_PREFIX_NAME: ColumnFamilyName = "contract_event";
 pub const FAILED_BLOCK_PREFIX_NAME: ColumnFamilyName = "failed_block";
+pub const UNCLE_INDEX_PREFIX_NAME: ColumnFamilyName = "uncle_index";
+pub const WEBHOOK_PREFIX_NAME: ColumnFamilyName = "webhook";
+pub const EVENT_CURSOR_PREFIX_NAME: ColumnFamilyName = "event_cursor";
+pub const TRANSACTION_POSITION_PREFIX_NAME: ColumnFamilyName = "transaction_position";
+pub const TOKEN_HOLDER_PREFIX_NAME: ColumnFamilyName = "token_holder";
+pub const TOKEN_HOLDER_COUNT_PREFIX_NAME: ColumnFamilyName = "token_holder_count";
+pub const BLOCK_TOKEN_HOLDER_CHANGES_PREFIX_NAME: ColumnFamilyName = "block_token_holder_changes";
 
 ///db storage use prefix_name vec to init
 /// Please note that adding a prefix needs to be added in vec simultaneously, remember！！
@@ -85,6 +109,13 @@ pub static VEC_PREFIX_NAME: Lazy<Vec<ColumnFamilyName>> = Lazy::new(|| {
         TRANSACTION_INFO_HASH_PREFIX_NAME,
         CONTRACT_EVENT_PREFIX_NAME,
         FAILED_BLOCK_PREFIX_NAME,
+        UNCLE_INDEX_PREFIX_NAME,
+        WEBHOOK_PREFIX_NAME,
+        EVENT_CURSOR_PREFIX_NAME,
+        TRANSACTION_POSITION_PREFIX_NAME,
+        TOKEN_HOLDER_PREFIX_NAME,
+        TOKEN_HOLDER_COUNT_PREFIX_NAME,
+        BLOCK_TOKEN_HOLDER_CHANGES_PREFIX_NAME,
     ]
 });
 
@@ -138,6 +169,18 @@ pub trait BlockStore {
         &self,
         block_id: HashValue,
     ) -> Result<Option<(Block, Option<PeerId>, String)>>;
+
+    /// Get the ids of the blocks which include the uncle `uncle_id`, the blocks may be on different branches.
+    fn get_block_ids_by_uncle(&self, uncle_id: HashValue) -> Result<Vec<HashValue>>;
+
+    /// Backfill the uncle index from all the saved blocks, if the index is not built yet,
+    /// the index is maintained by `commit_block` after that.
+    fn upgrade_uncle_index(&self) -> Result<()>;
+
+    /// Backfill the txn position index from all the saved blocks, if the index is not built yet,
+    /// the index is maintained by `save_transaction_positions` when the block is saved after that.
+    /// The progress is saved periodically, so an interrupted backfill resumes from where it stopped.
+    fn upgrade_transaction_position_index(&self) -> Result<()>;
 }
 
 pub trait BlockTransactionInfoStore {
@@ -149,7 +192,28 @@ pub trait BlockTransactionInfoStore {
     /// Get transaction info ids by transaction hash, one transaction may be in different chain branch, so produce multiply transaction info.
     /// if not transaction info match with the `txn_hash`, return empty Vec.
     fn get_transaction_info_ids_by_hash(&self, txn_hash: HashValue) -> Result<Vec<HashValue>>;
+    /// Same as `get_transaction_info_ids_by_hash`, but read all the hashes in one multi get, keep the order of `txn_hashes`.
+    fn get_transaction_info_ids_by_hashes(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> Result<Vec<Vec<HashValue>>>;
+    /// Get the transaction infos by ids in one multi get, keep the order of `ids`.
+    fn get_transaction_infos(
+        &self,
+        ids: Vec<HashValue>,
+    ) -> Result<Vec<Option<BlockTransactionInfo>>>;
     fn save_transaction_infos(&self, vec_txn_info: Vec<BlockTransactionInfo>) -> Result<()>;
+    /// Get the positions of the txn in all the blocks which include it, empty if the txn is not found.
+    fn get_transaction_positions(&self, txn_hash: HashValue) -> Result<Vec<TransactionPosition>>;
+    /// Same as `get_transaction_positions`, but read all the hashes in one multi get, keep the order of `txn_hashes`.
+    fn get_transaction_positions_by_hashes(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> Result<Vec<Vec<TransactionPosition>>>;
+    fn save_transaction_positions(
+        &self,
+        txn_positions: Vec<(HashValue, TransactionPosition)>,
+    ) -> Result<()>;
 }
 pub trait ContractEventStore {
     /// Save events by key `txn_info_id`.
@@ -168,15 +232,110 @@ pub trait ContractEventStore {
 
 pub trait TransactionStore {
     fn get_transaction(&self, txn_hash: HashValue) -> Result<Option<Transaction>>;
+    /// Get the transactions in one multi get, keep the order of `txn_hashes`.
+    fn get_transactions(&self, txn_hashes: Vec<HashValue>) -> Result<Vec<Option<Transaction>>>;
     fn save_transaction(&self, txn_info: Transaction) -> Result<()>;
     fn save_transaction_batch(&self, txn_vec: Vec<Transaction>) -> Result<()>;
 }
 
+pub trait WebhookStore {
+    fn get_webhooks(&self) -> Result<Vec<WebhookRegistration>>;
+    /// Save the registration, replace the exist one with the same id.
+    fn save_webhook(&self, registration: WebhookRegistration) -> Result<()>;
+    /// Remove the registration by id, return None if it does not exist.
+    fn remove_webhook(&self, id: HashValue) -> Result<Option<WebhookRegistration>>;
+}
+
+pub trait EventCursorStore {
+    fn get_event_cursor(&self, id: EventCursorId) -> Result<Option<EventCursor>>;
+    /// List all the cursors, sorted by id.
+    fn list_event_cursors(&self) -> Result<Vec<EventCursor>>;
+    /// The id of the next created cursor, the cursors with a smaller id are created before.
+    fn next_event_cursor_id(&self) -> Result<EventCursorId>;
+    /// Create a cursor with a new id, fail if there are `max_cursors` cursors already.
+    fn create_event_cursor(
+        &self,
+        filter: Filter,
+        decode: bool,
+        acked: Option<EventPosition>,
+        now_secs: u64,
+        max_cursors: usize,
+    ) -> Result<EventCursorId>;
+    /// Save the cursor if it exists, return false if it is removed.
+    fn update_event_cursor(&self, cursor: EventCursor) -> Result<bool>;
+    /// Remove the cursors idle for more than `idle_timeout_secs` or created more than
+    /// `max_lifetime_secs` ago, return the removed ids.
+    fn remove_expired_event_cursors(
+        &self,
+        now_secs: u64,
+        idle_timeout_secs: u64,
+        max_lifetime_secs: u64,
+    ) -> Result<Vec<EventCursorId>>;
+}
+
+pub trait BlockPruneStore {
+    /// The progress of the block data pruning, None if the storage has never been pruned.
+    fn get_prune_info(&self) -> Result<Option<PruneInfo>>;
+
+    fn save_prune_info(&self, prune_info: PruneInfo) -> Result<()>;
+
+    /// Delete the body, txns, txn infos, events and token holder changes of the block, the header,
+    /// the block info, the accumulator nodes and the state nodes are kept. Return the size of the deleted values.
+    fn prune_block_data(&self, block_id: HashValue) -> Result<u64>;
+}
+
+pub trait TokenHolderStore {
+    /// Whether the token holder index is maintained, it is disabled by default.
+    fn is_token_holder_index_enabled(&self) -> bool;
+
+    /// The block which the index is consistent with, None if the index has not been built.
+    fn get_token_holder_index_head(&self) -> Result<Option<HashValue>>;
+
+    /// Invalidate the index, it must be rebuilt before use.
+    fn clear_token_holder_index_head(&self) -> Result<()>;
+
+    /// The holders whose balance resource is written by the block, None if not recorded.
+    fn get_block_token_holder_changes(
+        &self,
+        block_id: HashValue,
+    ) -> Result<Option<Vec<TokenHolderKey>>>;
+
+    fn save_block_token_holder_changes(
+        &self,
+        block_id: HashValue,
+        changes: Vec<TokenHolderKey>,
+    ) -> Result<()>;
+
+    /// Set the balances of the holders and move the index head from `from` to `head`,
+    /// a None or zero balance removes the holder.
+    fn update_token_holders(
+        &self,
+        from: HashValue,
+        head: HashValue,
+        balances: Vec<(TokenHolderKey, Option<u128>)>,
+    ) -> Result<()>;
+
+    /// Remove all the holders and start to rebuild the index, the holders are added by the
+    /// returned rebuilder, and the index is invalid until the rebuilder is finished.
+    fn rebuild_token_holders(&self) -> Result<TokenHolderIndexRebuilder>;
+
+    /// List at most `limit` holders of the token from address `start`(inclusive), sorted by address.
+    fn list_token_holders(
+        &self,
+        token_code: &TokenCode,
+        start: Option<AccountAddress>,
+        limit: usize,
+    ) -> Result<Vec<(AccountAddress, u128)>>;
+
+    fn get_token_holder_count(&self, token_code: &TokenCode) -> Result<u64>;
+}
+
 // TODO: remove Arc<dyn Store>, we can clone Storage directly.
 #[derive(Clone)]
 pub struct Storage {
     transaction_info_storage: TransactionInfoStorage,
     transaction_info_hash_storage: TransactionInfoHashStorage,
+    transaction_position_storage: TransactionPositionStorage,
     transaction_storage: TransactionStorage,
     block_storage: BlockStorage,
     state_node_storage: StateStorage,
@@ -185,6 +344,11 @@ pub struct Storage {
     block_info_storage: BlockInfoStorage,
     event_storage: ContractEventStorage,
     chain_info_storage: ChainInfoStorage,
+    webhook_storage: WebhookStorage,
+    event_cursors: EventCursors,
+    token_holder_index: TokenHolderIndex,
+    token_holder_index_enabled: bool,
+    db: Option<Arc<DBStorage>>,
 }
 
 impl Storage {
@@ -192,6 +356,7 @@ impl Storage {
         Ok(Self {
             transaction_info_storage: TransactionInfoStorage::new(instance.clone()),
             transaction_info_hash_storage: TransactionInfoHashStorage::new(instance.clone()),
+            transaction_position_storage: TransactionPositionStorage::new(instance.clone()),
             transaction_storage: TransactionStorage::new(instance.clone()),
             block_storage: BlockStorage::new(instance.clone()),
             state_node_storage: StateStorage::new(instance.clone()),
@@ -202,10 +367,21 @@ impl Storage {
                 AccumulatorStorage::new_transaction_accumulator_storage(instance.clone()),
             block_info_storage: BlockInfoStorage::new(instance.clone()),
             event_storage: ContractEventStorage::new(instance.clone()),
-            chain_info_storage: ChainInfoStorage::new(instance),
+            chain_info_storage: ChainInfoStorage::new(instance.clone()),
+            webhook_storage: WebhookStorage::new(instance.clone()),
+            event_cursors: EventCursors::new(instance.clone()),
+            token_holder_index: TokenHolderIndex::new(instance.clone()),
+            token_holder_index_enabled: false,
+            db: instance.db(),
         })
     }
 
+    /// Maintain the token holder index when the blocks are executed.
+    pub fn with_token_holder_index(mut self, enable: bool) -> Self {
+        self.token_holder_index_enabled = enable;
+        self
+    }
+
     pub fn get_block_accumulator_storage(&self) -> AccumulatorStorage<BlockAccumulatorStorage> {
         self.block_accumulator_storage.clone()
     }
@@ -215,6 +391,19 @@ impl Storage {
     ) -> AccumulatorStorage<TransactionAccumulatorStorage> {
         self.transaction_accumulator_storage.clone()
     }
+
+    /// Flush the memtables of the db to disk, do nothing if the storage is cache only.
+    pub fn flush(&self) -> Result<()> {
+        match &self.db {
+            Some(db) => db.flush_all(),
+            None => Ok(()),
+        }
+    }
+
+    /// Report the size of the db column families, None if the storage is cache only.
+    pub fn db_size_report(&self) -> Result<Option<DbSizeReport>> {
+        self.db.as_ref().map(|db| db.db_size_report()).transpose()
+    }
 }
 
 impl StateNodeStore for Storage {
@@ -349,6 +538,93 @@ impl BlockStore for Storage {
     ) -> Result<Option<(Block, Option<PeerId>, String)>> {
         self.block_storage.get_failed_block_by_id(block_id)
     }
+
+    fn get_block_ids_by_uncle(&self, uncle_id: HashValue) -> Result<Vec<HashValue>> {
+        self.block_storage.get_block_ids_by_uncle(uncle_id)
+    }
+
+    fn upgrade_uncle_index(&self) -> Result<()> {
+        if self.chain_info_storage.is_uncle_index_built()? {
+            return Ok(());
+        }
+        let indexed = self.block_storage.rebuild_uncle_index()?;
+        info!("Backfill uncle index of {} blocks.", indexed);
+        self.chain_info_storage.save_uncle_index_built()
+    }
+
+    fn upgrade_transaction_position_index(&self) -> Result<()> {
+        if self.chain_info_storage.is_txn_position_index_built()? {
+            return Ok(());
+        }
+        let indexed = self.rebuild_transaction_position_index()?;
+        info!("Backfill txn position index of {} blocks.", indexed);
+        self.chain_info_storage.save_txn_position_index_built()
+    }
+}
+
+impl Storage {
+    /// How many blocks are indexed between two progress saves and logs of the backfill.
+    const TXN_POSITION_BACKFILL_PROGRESS_INTERVAL: usize = 10000;
+
+    /// Index the txn positions of all the blocks whose txns are in db, return the count of the indexed blocks.
+    /// The pruned blocks are skipped, the cache only storage is always indexed when the block is saved.
+    /// The blocks are indexed in key order, starting after the block of the saved progress if any.
+    fn rebuild_transaction_position_index(&self) -> Result<usize> {
+        let db = match self.db.as_ref() {
+            Some(db) => db,
+            None => return Ok(0),
+        };
+        let mut iter = db.iter(BLOCK_TRANSACTIONS_PREFIX_NAME)?;
+        let progress = self.chain_info_storage.get_txn_position_index_progress()?;
+        match progress {
+            Some(block_id) => {
+                info!(
+                    "Resume txn position index backfill after block {}.",
+                    block_id
+                );
+                iter.seek(block_id.encode_key()?)?;
+            }
+            None => iter.seek_to_first(),
+        }
+        let mut count = 0;
+        let mut last_block_id = None;
+        for item in iter {
+            let (key, value) = item?;
+            let block_id = HashValue::decode_key(key.as_slice())?;
+            if progress == Some(block_id) {
+                continue;
+            }
+            let txn_hashes = Vec::<HashValue>::decode_value(value.as_slice())?;
+            let (header, body, block_info) = match (
+                self.block_storage.get_block_header_by_hash(block_id)?,
+                self.block_storage.get_body(block_id)?,
+                self.block_info_storage.get(block_id)?,
+            ) {
+                (Some(header), Some(body), Some(block_info)) => (header, body, block_info),
+                _ => continue,
+            };
+            let positions = TransactionPosition::block_positions(
+                block_id,
+                header.number(),
+                body.transactions.len(),
+                txn_hashes.len(),
+                block_info.txn_accumulator_info.num_leaves,
+            )?;
+            self.save_transaction_positions(txn_hashes.into_iter().zip(positions).collect())?;
+            last_block_id = Some(block_id);
+            count += 1;
+            if count % Self::TXN_POSITION_BACKFILL_PROGRESS_INTERVAL == 0 {
+                self.chain_info_storage
+                    .save_txn_position_index_progress(block_id)?;
+                info!("Backfill txn position index, {} blocks indexed.", count);
+            }
+        }
+        if let Some(block_id) = last_block_id {
+            self.chain_info_storage
+                .save_txn_position_index_progress(block_id)?;
+        }
+        Ok(count)
+    }
 }
 
 impl BlockInfoStore for Storage {
@@ -389,12 +665,58 @@ impl BlockTransactionInfoStore for Storage {
             .get_transaction_info_ids_by_hash(txn_hash)
     }
 
+    fn get_transaction_info_ids_by_hashes(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> Result<Vec<Vec<HashValue>>, Error> {
+        Ok(self
+            .transaction_info_hash_storage
+            .multiple_get(txn_hashes)?
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect())
+    }
+
+    fn get_transaction_infos(
+        &self,
+        ids: Vec<HashValue>,
+    ) -> Result<Vec<Option<BlockTransactionInfo>>, Error> {
+        self.transaction_info_storage.multiple_get(ids)
+    }
+
     fn save_transaction_infos(&self, vec_txn_info: Vec<BlockTransactionInfo>) -> Result<(), Error> {
         self.transaction_info_hash_storage
             .save_transaction_infos(vec_txn_info.clone())?;
         self.transaction_info_storage
             .save_transaction_infos(vec_txn_info)
     }
+
+    fn get_transaction_positions(&self, txn_hash: HashValue) -> Result<Vec<TransactionPosition>> {
+        Ok(self
+            .transaction_position_storage
+            .get(txn_hash)?
+            .unwrap_or_default())
+    }
+
+    fn get_transaction_positions_by_hashes(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> Result<Vec<Vec<TransactionPosition>>> {
+        Ok(self
+            .transaction_position_storage
+            .multiple_get(txn_hashes)?
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect())
+    }
+
+    fn save_transaction_positions(
+        &self,
+        txn_positions: Vec<(HashValue, TransactionPosition)>,
+    ) -> Result<()> {
+        self.transaction_position_storage
+            .save_transaction_positions(txn_positions)
+    }
 }
 
 impl ContractEventStore for Storage {
@@ -419,6 +741,13 @@ impl TransactionStore for Storage {
         self.transaction_storage.get(txn_hash)
     }
 
+    fn get_transactions(
+        &self,
+        txn_hashes: Vec<HashValue>,
+    ) -> Result<Vec<Option<Transaction>>, Error> {
+        self.transaction_storage.multiple_get(txn_hashes)
+    }
+
     fn save_transaction(&self, txn: Transaction) -> Result<(), Error> {
         self.transaction_storage.put(txn.id(), txn)
     }
@@ -428,6 +757,197 @@ impl TransactionStore for Storage {
     }
 }
 
+impl WebhookStore for Storage {
+    fn get_webhooks(&self) -> Result<Vec<WebhookRegistration>> {
+        self.webhook_storage.get_webhooks()
+    }
+
+    fn save_webhook(&self, registration: WebhookRegistration) -> Result<()> {
+        self.webhook_storage.save_webhook(registration)
+    }
+
+    fn remove_webhook(&self, id: HashValue) -> Result<Option<WebhookRegistration>> {
+        self.webhook_storage.remove_webhook(id)
+    }
+}
+
+impl EventCursorStore for Storage {
+    fn get_event_cursor(&self, id: EventCursorId) -> Result<Option<EventCursor>> {
+        self.event_cursors.get_event_cursor(id)
+    }
+
+    fn list_event_cursors(&self) -> Result<Vec<EventCursor>> {
+        self.event_cursors.list_event_cursors()
+    }
+
+    fn next_event_cursor_id(&self) -> Result<EventCursorId> {
+        self.event_cursors.next_event_cursor_id()
+    }
+
+    fn create_event_cursor(
+        &self,
+        filter: Filter,
+        decode: bool,
+        acked: Option<EventPosition>,
+        now_secs: u64,
+        max_cursors: usize,
+    ) -> Result<EventCursorId> {
+        self.event_cursors
+            .create_event_cursor(filter, decode, acked, now_secs, max_cursors)
+    }
+
+    fn update_event_cursor(&self, cursor: EventCursor) -> Result<bool> {
+        self.event_cursors.update_event_cursor(cursor)
+    }
+
+    fn remove_expired_event_cursors(
+        &self,
+        now_secs: u64,
+        idle_timeout_secs: u64,
+        max_lifetime_secs: u64,
+    ) -> Result<Vec<EventCursorId>> {
+        self.event_cursors.remove_expired_event_cursors(
+            now_secs,
+            idle_timeout_secs,
+            max_lifetime_secs,
+        )
+    }
+}
+
+impl BlockPruneStore for Storage {
+    fn get_prune_info(&self) -> Result<Option<PruneInfo>> {
+        self.chain_info_storage.get_prune_info()
+    }
+
+    fn save_prune_info(&self, prune_info: PruneInfo) -> Result<()> {
+        self.chain_info_storage.save_prune_info(prune_info)
+    }
+
+    fn prune_block_data(&self, block_id: HashValue) -> Result<u64> {
+        let txn_info_ids = self
+            .block_storage
+            .get_transaction_info_ids(block_id)?
+            .unwrap_or_default();
+        let mut pruned_bytes = 0;
+        let mut event_batch = CodecWriteBatch::new();
+        let mut txn_info_batch = CodecWriteBatch::new();
+        let mut txn_info_hash_batch = CodecWriteBatch::new();
+        let mut txn_position_batch = CodecWriteBatch::new();
+        let mut txn_batch = CodecWriteBatch::new();
+        for txn_info_id in txn_info_ids {
+            if let Some(events) = self.event_storage.get(txn_info_id)? {
+                pruned_bytes += events.encode_value()?.len();
+                event_batch.delete(txn_info_id)?;
+            }
+            let txn_info = match self.transaction_info_storage.get(txn_info_id)? {
+                Some(txn_info) => txn_info,
+                None => continue,
+            };
+            pruned_bytes += txn_info.encode_value()?.len();
+            txn_info_batch.delete(txn_info_id)?;
+            // the txn may be included by the fork blocks too, it is deleted with its last txn info.
+            let txn_hash = txn_info.transaction_hash();
+            let positions = self.get_transaction_positions(txn_hash)?;
+            if positions
+                .iter()
+                .any(|position| position.block_id == block_id)
+            {
+                let remaining = positions
+                    .iter()
+                    .filter(|position| position.block_id != block_id)
+                    .copied()
+                    .collect::<Vec<_>>();
+                pruned_bytes += positions.encode_value()?.len() - remaining.encode_value()?.len();
+                if remaining.is_empty() {
+                    txn_position_batch.delete(txn_hash)?;
+                } else {
+                    txn_position_batch.put(txn_hash, remaining)?;
+                }
+            }
+            let mut ids = self
+                .transaction_info_hash_storage
+                .get_transaction_info_ids_by_hash(txn_hash)?;
+            ids.retain(|id| *id != txn_info_id);
+            pruned_bytes += HashValue::LENGTH;
+            if ids.is_empty() {
+                txn_info_hash_batch.delete(txn_hash)?;
+                if let Some(txn) = self.transaction_storage.get(txn_hash)? {
+                    pruned_bytes += txn.encode_value()?.len();
+                    txn_batch.delete(txn_hash)?;
+                }
+            } else {
+                txn_info_hash_batch.put(txn_hash, ids)?;
+            }
+        }
+        self.event_storage.write_batch(event_batch)?;
+        self.transaction_storage.write_batch(txn_batch)?;
+        self.transaction_info_hash_storage
+            .write_batch(txn_info_hash_batch)?;
+        self.transaction_position_storage
+            .write_batch(txn_position_batch)?;
+        self.transaction_info_storage.write_batch(txn_info_batch)?;
+        pruned_bytes += self.token_holder_index.prune_block_changes(block_id)? as usize;
+        Ok(pruned_bytes as u64 + self.block_storage.prune_block_data(block_id)?)
+    }
+}
+
+impl TokenHolderStore for Storage {
+    fn is_token_holder_index_enabled(&self) -> bool {
+        self.token_holder_index_enabled
+    }
+
+    fn get_token_holder_index_head(&self) -> Result<Option<HashValue>> {
+        self.token_holder_index.get_head()
+    }
+
+    fn clear_token_holder_index_head(&self) -> Result<()> {
+        self.token_holder_index.clear_head()
+    }
+
+    fn get_block_token_holder_changes(
+        &self,
+        block_id: HashValue,
+    ) -> Result<Option<Vec<TokenHolderKey>>> {
+        self.token_holder_index.get_block_changes(block_id)
+    }
+
+    fn save_block_token_holder_changes(
+        &self,
+        block_id: HashValue,
+        changes: Vec<TokenHolderKey>,
+    ) -> Result<()> {
+        self.token_holder_index
+            .save_block_changes(block_id, changes)
+    }
+
+    fn update_token_holders(
+        &self,
+        from: HashValue,
+        head: HashValue,
+        balances: Vec<(TokenHolderKey, Option<u128>)>,
+    ) -> Result<()> {
+        self.token_holder_index.update_holders(from, head, balances)
+    }
+
+    fn rebuild_token_holders(&self) -> Result<TokenHolderIndexRebuilder> {
+        self.token_holder_index.rebuild()
+    }
+
+    fn list_token_holders(
+        &self,
+        token_code: &TokenCode,
+        start: Option<AccountAddress>,
+        limit: usize,
+    ) -> Result<Vec<(AccountAddress, u128)>> {
+        self.token_holder_index
+            .list_holders(token_code, start, limit)
+    }
+
+    fn get_token_holder_count(&self, token_code: &TokenCode) -> Result<u64> {
+        self.token_holder_index.get_holder_count(token_code)
+    }
+}
+
 /// Chain storage define
 pub trait Store:
     StateNodeStore
@@ -436,6 +956,8 @@ pub trait Store:
     + TransactionStore
     + BlockTransactionInfoStore
     + ContractEventStore
+    + BlockPruneStore
+    + TokenHolderStore
     + IntoSuper<dyn StateNodeStore>
 {
     fn get_transaction_info_by_block_and_index(
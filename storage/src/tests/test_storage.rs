@@ -6,15 +6,26 @@ extern crate chrono;
 use crate::cache_storage::CacheStorage;
 use crate::db_storage::DBStorage;
 use crate::storage::{CodecKVStore, InnerStore, StorageInstance, ValueCodec, CACHE_NONE_OBJECT};
+use crate::token_holder::TokenHolderKey;
 use crate::{
-    BlockTransactionInfoStore, Storage, DEFAULT_PREFIX_NAME, TRANSACTION_INFO_PREFIX_NAME,
+    BlockInfoStore, BlockPruneStore, BlockStore, BlockTransactionInfoStore, EventCursorStore,
+    Storage, TokenHolderStore, WebhookStore, DEFAULT_PREFIX_NAME, TRANSACTION_INFO_PREFIX_NAME,
     VEC_PREFIX_NAME,
 };
 use anyhow::Result;
 use crypto::HashValue;
+use starcoin_accumulator::accumulator_info::AccumulatorInfo;
 use starcoin_config::RocksdbConfig;
-use starcoin_types::transaction::{BlockTransactionInfo, TransactionInfo};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::token_code::TokenCode;
+use starcoin_types::block::{Block, BlockBody, BlockHeader, BlockInfo};
+use starcoin_types::event_cursor::EventCursor;
+use starcoin_types::filter::{EventPosition, Filter};
+use starcoin_types::transaction::{
+    BlockTransactionInfo, SignedUserTransaction, TransactionInfo, TransactionPosition,
+};
 use starcoin_types::vm_error::KeptVMStatus;
+use starcoin_types::webhook::{WebhookFilter, WebhookRegistration};
 
 #[test]
 fn test_reopen() {
@@ -218,3 +229,357 @@ fn test_missing_key_handle() -> Result<()> {
     assert_eq!(contains, false);
     Ok(())
 }
+
+#[test]
+fn test_webhook_storage() -> Result<()> {
+    let tmpdir = starcoin_config::temp_path();
+    let registration = WebhookRegistration::new(
+        HashValue::random(),
+        "http://127.0.0.1:8080/hook".to_string(),
+        WebhookFilter {
+            senders: vec![AccountAddress::random()],
+            txn_hashes: vec![],
+        },
+        "secret".to_string(),
+    );
+    {
+        let storage = Storage::new(StorageInstance::new_cache_and_db_instance(
+            CacheStorage::new(),
+            DBStorage::new(tmpdir.path(), RocksdbConfig::default())?,
+        ))?;
+        assert!(storage.get_webhooks()?.is_empty());
+        storage.save_webhook(registration.clone())?;
+        assert!(storage.remove_webhook(HashValue::random())?.is_none());
+    }
+    // reopen the db, the registration should be persisted.
+    let storage = Storage::new(StorageInstance::new_db_instance(DBStorage::new(
+        tmpdir.path(),
+        RocksdbConfig::default(),
+    )?))?;
+    assert_eq!(storage.get_webhooks()?, vec![registration.clone()]);
+    assert_eq!(storage.remove_webhook(registration.id)?, Some(registration));
+    assert!(storage.get_webhooks()?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_event_cursor_storage() -> Result<()> {
+    let tmpdir = starcoin_config::temp_path();
+    let position = EventPosition {
+        block_number: 10,
+        transaction_index: 1,
+        event_index: 0,
+    };
+    {
+        let storage = Storage::new(StorageInstance::new_cache_and_db_instance(
+            CacheStorage::new(),
+            DBStorage::new(tmpdir.path(), RocksdbConfig::default())?,
+        ))?;
+        assert_eq!(storage.next_event_cursor_id()?, 0);
+        assert_eq!(
+            storage.create_event_cursor(Filter::default(), false, None, 100, 2)?,
+            0
+        );
+        assert_eq!(
+            storage.create_event_cursor(Filter::default(), true, None, 100, 2)?,
+            1
+        );
+        // the count of the cursors is capped.
+        assert!(storage
+            .create_event_cursor(Filter::default(), true, None, 100, 2)
+            .is_err());
+        let mut cursor = storage.get_event_cursor(0)?.unwrap();
+        cursor.ack(position);
+        cursor.last_active_secs = 200;
+        assert!(storage.update_event_cursor(cursor)?);
+    }
+    // reopen the db, the cursors and the acked position should be persisted.
+    let storage = Storage::new(StorageInstance::new_db_instance(DBStorage::new(
+        tmpdir.path(),
+        RocksdbConfig::default(),
+    )?))?;
+    assert_eq!(storage.next_event_cursor_id()?, 2);
+    assert_eq!(storage.list_event_cursors()?.len(), 2);
+    assert_eq!(storage.get_event_cursor(0)?.unwrap().acked, Some(position));
+    assert_eq!(
+        storage.remove_expired_event_cursors(250, 100, 1000)?,
+        vec![1]
+    );
+    assert!(storage.get_event_cursor(1)?.is_none());
+    // a removed cursor is not saved back by an update.
+    assert!(!storage.update_event_cursor(EventCursor::new(
+        1,
+        Filter::default(),
+        true,
+        None,
+        250
+    ))?);
+    assert!(storage.get_event_cursor(1)?.is_none());
+    assert!(storage.get_event_cursor(0)?.is_some());
+    // the removed ids are not reused.
+    assert_eq!(storage.next_event_cursor_id()?, 2);
+    assert_eq!(
+        storage.create_event_cursor(Filter::default(), false, None, 250, 2)?,
+        2
+    );
+    // the cursor is expired after the max lifetime even if it is active.
+    assert_eq!(
+        storage.remove_expired_event_cursors(1150, 1000, 1000)?,
+        vec![0]
+    );
+    assert_eq!(
+        storage
+            .list_event_cursors()?
+            .into_iter()
+            .map(|cursor| cursor.id)
+            .collect::<Vec<_>>(),
+        vec![2]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_db_size_report() -> Result<()> {
+    let tmpdir = starcoin_config::temp_path();
+    let db = DBStorage::new(tmpdir.path(), RocksdbConfig::default())?;
+    for _ in 0..10 {
+        db.put(
+            DEFAULT_PREFIX_NAME,
+            HashValue::random().to_vec(),
+            HashValue::random().to_vec(),
+        )?;
+    }
+    db.flush_all()?;
+    let report = db.db_size_report()?;
+    assert_eq!(report.column_families.len(), VEC_PREFIX_NAME.len());
+    let default_cf = report
+        .column_families
+        .get(DEFAULT_PREFIX_NAME)
+        .expect("default cf should exist");
+    assert!(default_cf.sst_files_size > 0);
+    assert!(report.total_size() >= default_cf.sst_files_size);
+
+    let cache_storage = Storage::new(StorageInstance::new_cache_instance())?;
+    assert!(cache_storage.db_size_report()?.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_upgrade_transaction_position_index() -> Result<()> {
+    let tmpdir = starcoin_config::temp_path();
+    let storage = Storage::new(StorageInstance::new_db_instance(DBStorage::new(
+        tmpdir.path(),
+        RocksdbConfig::default(),
+    )?))?;
+    let user_txns: Vec<_> = (0..3).map(|_| SignedUserTransaction::mock()).collect();
+    // save the block as the node before the index is introduced.
+    let save_block = |block: Block, txn_hashes: Vec<HashValue>, num_leaves: u64| -> Result<()> {
+        let block_id = block.id();
+        storage.commit_block(block)?;
+        storage.save_block_transaction_ids(block_id, txn_hashes)?;
+        storage.save_block_info(BlockInfo::new(
+            block_id,
+            0.into(),
+            AccumulatorInfo::new(HashValue::random(), vec![], num_leaves, 0),
+            AccumulatorInfo::default(),
+        ))
+    };
+    let block = Block::new(
+        BlockHeader::random(),
+        BlockBody::new(user_txns.clone(), None),
+    );
+    let block_id = block.id();
+    let block_metadata_hash = HashValue::random();
+    let mut txn_hashes = vec![block_metadata_hash];
+    txn_hashes.extend(user_txns.iter().map(|txn| txn.id()));
+    save_block(block, txn_hashes, 10)?;
+    // the fork block includes the same user txn.
+    let fork_block = Block::new(
+        BlockHeader::random(),
+        BlockBody::new(vec![user_txns[1].clone()], None),
+    );
+    let fork_block_id = fork_block.id();
+    save_block(fork_block, vec![HashValue::random(), user_txns[1].id()], 8)?;
+    assert!(storage
+        .get_transaction_positions(block_metadata_hash)?
+        .is_empty());
+
+    storage.upgrade_transaction_position_index()?;
+    let positions = storage.get_transaction_positions(block_metadata_hash)?;
+    assert_eq!(positions.len(), 1);
+    assert!(positions[0].is_block_metadata());
+    assert_eq!(positions[0].block_id, block_id);
+    assert_eq!(positions[0].global_index, 6);
+    let positions = storage.get_transaction_positions_by_hashes(vec![
+        user_txns[0].id(),
+        user_txns[2].id(),
+        HashValue::random(),
+    ])?;
+    assert_eq!(positions[0][0].transaction_index, 1);
+    assert_eq!(positions[1][0].transaction_index, 3);
+    assert_eq!(positions[1][0].global_index, 9);
+    assert!(positions[2].is_empty());
+    let mut positions = storage.get_transaction_positions(user_txns[1].id())?;
+    positions.sort_by_key(|position| position.global_index);
+    assert_eq!(
+        positions
+            .iter()
+            .map(|position| (position.block_id, position.transaction_index))
+            .collect::<Vec<_>>(),
+        vec![(fork_block_id, 1), (block_id, 2)]
+    );
+
+    // the index is built only once, the later blocks are indexed when they are saved.
+    let txn_hash = HashValue::random();
+    let later_block = Block::new(BlockHeader::random(), BlockBody::new(vec![], None));
+    save_block(later_block, vec![txn_hash], 11)?;
+    storage.upgrade_transaction_position_index()?;
+    assert!(storage.get_transaction_positions(txn_hash)?.is_empty());
+    let position = TransactionPosition::block_positions(HashValue::random(), 5, 0, 1, 11)?[0];
+    storage.save_transaction_positions(vec![(txn_hash, position)])?;
+    assert_eq!(storage.get_transaction_positions(txn_hash)?, vec![position]);
+    Ok(())
+}
+
+#[test]
+fn test_resume_transaction_position_index() -> Result<()> {
+    let tmpdir = starcoin_config::temp_path();
+    let storage = Storage::new(StorageInstance::new_db_instance(DBStorage::new(
+        tmpdir.path(),
+        RocksdbConfig::default(),
+    )?))?;
+    let mut blocks = vec![];
+    for i in 0..3u64 {
+        let block = Block::new(BlockHeader::random(), BlockBody::new(vec![], None));
+        let block_id = block.id();
+        let txn_hash = HashValue::random();
+        storage.commit_block(block)?;
+        storage.save_block_transaction_ids(block_id, vec![txn_hash])?;
+        storage.save_block_info(BlockInfo::new(
+            block_id,
+            0.into(),
+            AccumulatorInfo::new(HashValue::random(), vec![], i + 1, 0),
+            AccumulatorInfo::default(),
+        ))?;
+        blocks.push((block_id, txn_hash));
+    }
+    // the backfill is indexed in key order, it was interrupted after the first block.
+    blocks.sort_by_key(|(block_id, _)| *block_id);
+    storage
+        .chain_info_storage
+        .save_txn_position_index_progress(blocks[0].0)?;
+
+    storage.upgrade_transaction_position_index()?;
+    assert!(storage.get_transaction_positions(blocks[0].1)?.is_empty());
+    for (block_id, txn_hash) in &blocks[1..] {
+        let positions = storage.get_transaction_positions(*txn_hash)?;
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].block_id, *block_id);
+    }
+    assert!(storage.chain_info_storage.is_txn_position_index_built()?);
+    assert_eq!(
+        storage
+            .chain_info_storage
+            .get_txn_position_index_progress()?,
+        None
+    );
+    Ok(())
+}
+
+#[test]
+fn test_token_holder_index() -> Result<()> {
+    let tmpdir = starcoin_config::temp_path();
+    let storage = Storage::new(StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new(),
+        DBStorage::new(tmpdir.path(), RocksdbConfig::default())?,
+    ))?
+    .with_token_holder_index(true);
+    assert!(storage.is_token_holder_index_enabled());
+    let stc: TokenCode = "0x1::STC::STC".parse()?;
+    let other: TokenCode = "0x2::ABC::ABC".parse()?;
+    let mut addresses: Vec<_> = (0..5).map(|_| AccountAddress::random()).collect();
+    addresses.sort();
+    let key = |token_code: &TokenCode, idx: usize| {
+        TokenHolderKey::new(token_code.clone(), addresses[idx])
+    };
+
+    let genesis_id = HashValue::random();
+    assert!(storage.get_token_holder_index_head()?.is_none());
+    let mut rebuilder = storage.rebuild_token_holders()?;
+    // only one rebuild runs at a time.
+    assert!(storage.rebuild_token_holders().is_err());
+    rebuilder.add_holder(key(&stc, 3), 30)?;
+    rebuilder.add_holder(key(&stc, 1), 10)?;
+    rebuilder.add_holder(key(&stc, 0), 0)?;
+    rebuilder.add_holder(key(&other, 2), 200)?;
+    assert!(storage.get_token_holder_index_head()?.is_none());
+    let count = rebuilder.finish(genesis_id)?;
+    assert_eq!(count, 3);
+    assert_eq!(storage.get_token_holder_index_head()?, Some(genesis_id));
+    assert_eq!(storage.get_token_holder_count(&stc)?, 2);
+    assert_eq!(storage.get_token_holder_count(&other)?, 1);
+    assert_eq!(
+        storage.list_token_holders(&stc, None, 10)?,
+        vec![(addresses[1], 10), (addresses[3], 30)]
+    );
+
+    // the balance of holder 1 is zero, holder 3 is deleted, holder 4 is new.
+    let block_id = HashValue::random();
+    assert!(storage
+        .update_token_holders(HashValue::random(), block_id, vec![])
+        .is_err());
+    storage.update_token_holders(
+        genesis_id,
+        block_id,
+        vec![
+            (key(&stc, 1), Some(0)),
+            (key(&stc, 3), None),
+            (key(&stc, 4), Some(40)),
+            (key(&stc, 2), Some(20)),
+            (key(&other, 2), Some(100)),
+        ],
+    )?;
+    assert_eq!(storage.get_token_holder_index_head()?, Some(block_id));
+    assert_eq!(storage.get_token_holder_count(&stc)?, 2);
+    assert_eq!(storage.get_token_holder_count(&other)?, 1);
+    assert_eq!(
+        storage.list_token_holders(&stc, None, 10)?,
+        vec![(addresses[2], 20), (addresses[4], 40)]
+    );
+    assert_eq!(
+        storage.list_token_holders(&stc, Some(addresses[3]), 10)?,
+        vec![(addresses[4], 40)]
+    );
+    assert_eq!(
+        storage.list_token_holders(&stc, None, 1)?,
+        vec![(addresses[2], 20)]
+    );
+    assert_eq!(
+        storage.list_token_holders(&other, None, 10)?,
+        vec![(addresses[2], 100)]
+    );
+
+    // the rebuild replaces all the holders.
+    let mut rebuilder = storage.rebuild_token_holders()?;
+    rebuilder.add_holder(key(&other, 0), 1)?;
+    let count = rebuilder.finish(block_id)?;
+    assert_eq!(count, 1);
+    assert_eq!(storage.get_token_holder_count(&stc)?, 0);
+    assert!(storage.list_token_holders(&stc, None, 10)?.is_empty());
+    assert_eq!(
+        storage.list_token_holders(&other, None, 10)?,
+        vec![(addresses[0], 1)]
+    );
+    storage.clear_token_holder_index_head()?;
+    assert!(storage.get_token_holder_index_head()?.is_none());
+
+    // the changes of the block are deleted when the block is pruned.
+    storage.save_block_token_holder_changes(block_id, vec![key(&stc, 1), key(&other, 2)])?;
+    assert_eq!(
+        storage.get_block_token_holder_changes(block_id)?,
+        Some(vec![key(&stc, 1), key(&other, 2)])
+    );
+    storage.prune_block_data(block_id)?;
+    assert!(storage.get_block_token_holder_changes(block_id)?.is_none());
+    Ok(())
+}
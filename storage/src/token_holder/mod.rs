@@ -0,0 +1,387 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::chain_info::ChainInfoStorage;
+use crate::db_storage::DBStorage;
+use crate::define_storage;
+use crate::storage::{CodecKVStore, CodecWriteBatch, KeyCodec, StorageInstance, ValueCodec};
+use crate::{
+    BLOCK_TOKEN_HOLDER_CHANGES_PREFIX_NAME, TOKEN_HOLDER_COUNT_PREFIX_NAME,
+    TOKEN_HOLDER_PREFIX_NAME,
+};
+use anyhow::{bail, format_err, Result};
+use bcs_ext::BCSCodec;
+use crypto::HashValue;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::token_code::TokenCode;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A holder of a token, the holders of a token are sorted by address in the index.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+pub struct TokenHolderKey {
+    pub token_code: TokenCode,
+    pub address: AccountAddress,
+}
+
+impl TokenHolderKey {
+    pub fn new(token_code: TokenCode, address: AccountAddress) -> Self {
+        Self {
+            token_code,
+            address,
+        }
+    }
+
+    /// The bcs encoded token code is self delimited, so it is the common prefix of the keys of a token.
+    fn prefix(token_code: &TokenCode) -> Result<Vec<u8>> {
+        token_code.encode()
+    }
+}
+
+impl KeyCodec for TokenHolderKey {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let mut key = Self::prefix(&self.token_code)?;
+        key.extend(self.address.to_vec());
+        Ok(key)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        let split = data
+            .len()
+            .checked_sub(AccountAddress::LENGTH)
+            .ok_or_else(|| format_err!("Invalid token holder key length: {}", data.len()))?;
+        Ok(Self {
+            token_code: TokenCode::decode(&data[..split])?,
+            address: AccountAddress::try_from(&data[split..])?,
+        })
+    }
+}
+
+impl KeyCodec for TokenCode {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        Self::decode(data)
+    }
+}
+
+impl ValueCodec for u128 {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Self::decode(data)
+    }
+}
+
+impl ValueCodec for u64 {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Self::decode(data)
+    }
+}
+
+impl ValueCodec for Vec<TokenHolderKey> {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Self::decode(data)
+    }
+}
+
+define_storage!(
+    TokenHolderStorage,
+    TokenHolderKey,
+    u128,
+    TOKEN_HOLDER_PREFIX_NAME
+);
+
+define_storage!(
+    TokenHolderCountStorage,
+    TokenCode,
+    u64,
+    TOKEN_HOLDER_COUNT_PREFIX_NAME
+);
+
+define_storage!(
+    BlockTokenHolderChangesStorage,
+    HashValue,
+    Vec<TokenHolderKey>,
+    BLOCK_TOKEN_HOLDER_CHANGES_PREFIX_NAME
+);
+
+/// The index of the holders with nonzero balance of every token, and the holders whose balance is
+/// written by every executed block, which are used to move the index between the blocks.
+#[derive(Clone)]
+pub struct TokenHolderIndex {
+    holder_storage: TokenHolderStorage,
+    count_storage: TokenHolderCountStorage,
+    changes_storage: BlockTokenHolderChangesStorage,
+    chain_info_storage: ChainInfoStorage,
+    db: Option<Arc<DBStorage>>,
+    /// Serialize the changes of the index head.
+    lock: Arc<Mutex<()>>,
+    /// Only one rebuild runs at a time, the rebuild does not hold the `lock` while writing the holders,
+    /// so it does not block the block connecting.
+    rebuilding: Arc<AtomicBool>,
+}
+
+impl TokenHolderIndex {
+    /// How many keys are written or deleted in one batch when rebuild the index.
+    const BATCH_SIZE: usize = 10000;
+
+    pub fn new(instance: StorageInstance) -> Self {
+        Self {
+            holder_storage: TokenHolderStorage::new(instance.clone()),
+            count_storage: TokenHolderCountStorage::new(instance.clone()),
+            changes_storage: BlockTokenHolderChangesStorage::new(instance.clone()),
+            chain_info_storage: ChainInfoStorage::new(instance.clone()),
+            db: instance.db(),
+            lock: Arc::new(Mutex::new(())),
+            rebuilding: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    fn db(&self) -> Result<&Arc<DBStorage>> {
+        self.db
+            .as_ref()
+            .ok_or_else(|| format_err!("The token holder index requires the db storage."))
+    }
+
+    pub fn get_head(&self) -> Result<Option<HashValue>> {
+        self.chain_info_storage.get_token_holder_index_head()
+    }
+
+    pub fn clear_head(&self) -> Result<()> {
+        let _guard = self.lock.lock();
+        self.chain_info_storage.remove_token_holder_index_head()
+    }
+
+    pub fn get_block_changes(&self, block_id: HashValue) -> Result<Option<Vec<TokenHolderKey>>> {
+        self.changes_storage.get(block_id)
+    }
+
+    pub fn save_block_changes(
+        &self,
+        block_id: HashValue,
+        changes: Vec<TokenHolderKey>,
+    ) -> Result<()> {
+        self.changes_storage.put(block_id, changes)
+    }
+
+    /// Remove the changes of the pruned block, return the size of the deleted value.
+    pub fn prune_block_changes(&self, block_id: HashValue) -> Result<u64> {
+        match self.changes_storage.get(block_id)? {
+            Some(changes) => {
+                self.changes_storage.remove(block_id)?;
+                Ok(changes.encode_value()?.len() as u64)
+            }
+            None => Ok(0),
+        }
+    }
+
+    pub fn get_holder_count(&self, token_code: &TokenCode) -> Result<u64> {
+        Ok(self
+            .count_storage
+            .get(token_code.clone())?
+            .unwrap_or_default())
+    }
+
+    /// List at most `limit` holders of the token from `start`(inclusive), sorted by address.
+    pub fn list_holders(
+        &self,
+        token_code: &TokenCode,
+        start: Option<AccountAddress>,
+        limit: usize,
+    ) -> Result<Vec<(AccountAddress, u128)>> {
+        let prefix = TokenHolderKey::prefix(token_code)?;
+        let seek_key = match start {
+            Some(start) => TokenHolderKey::new(token_code.clone(), start).encode_key()?,
+            None => prefix.clone(),
+        };
+        let mut iter = self.db()?.iter(TOKEN_HOLDER_PREFIX_NAME)?;
+        iter.seek(seek_key)?;
+        let mut holders = vec![];
+        for item in iter {
+            if holders.len() >= limit {
+                break;
+            }
+            let (key, value) = item?;
+            if !key.starts_with(prefix.as_slice()) {
+                break;
+            }
+            let key = TokenHolderKey::decode_key(key.as_slice())?;
+            holders.push((key.address, u128::decode_value(value.as_slice())?));
+        }
+        Ok(holders)
+    }
+
+    /// Set the balances of the holders and move the head of the index from `from` to `head`,
+    /// the holder is removed if its balance is None or zero.
+    /// The head is removed before writing the holders and the counts, and saved after both of them are written,
+    /// so the index is invalid instead of inconsistent if the node crashes in the middle.
+    pub fn update_holders(
+        &self,
+        from: HashValue,
+        head: HashValue,
+        balances: Vec<(TokenHolderKey, Option<u128>)>,
+    ) -> Result<()> {
+        let _guard = self.lock.lock();
+        // the index may be rebuilt or cleared after the balances are read.
+        let current = self.get_head()?;
+        if current != Some(from) {
+            bail!(
+                "The token holder index head {:?} is not the expected {}.",
+                current,
+                from
+            );
+        }
+        let mut deltas: BTreeMap<TokenCode, i64> = BTreeMap::new();
+        let mut batch = CodecWriteBatch::new();
+        for (key, balance) in balances {
+            let exist = self.holder_storage.get(key.clone())?.is_some();
+            match balance {
+                Some(balance) if balance > 0 => {
+                    if !exist {
+                        *deltas.entry(key.token_code.clone()).or_default() += 1;
+                    }
+                    batch.put(key, balance)?;
+                }
+                _ => {
+                    if exist {
+                        *deltas.entry(key.token_code.clone()).or_default() -= 1;
+                        batch.delete(key)?;
+                    }
+                }
+            }
+        }
+        let mut count_batch = CodecWriteBatch::new();
+        for (token_code, delta) in deltas {
+            let count = (self.get_holder_count(&token_code)? as i64 + delta).max(0) as u64;
+            if count == 0 {
+                count_batch.delete(token_code)?;
+            } else {
+                count_batch.put(token_code, count)?;
+            }
+        }
+        self.chain_info_storage.remove_token_holder_index_head()?;
+        self.holder_storage.write_batch(batch)?;
+        self.count_storage.write_batch(count_batch)?;
+        self.chain_info_storage.save_token_holder_index_head(head)
+    }
+
+    /// Remove all the holders and start to rebuild the index, the index is invalid until the
+    /// returned rebuilder is finished.
+    pub fn rebuild(&self) -> Result<TokenHolderIndexRebuilder> {
+        if self
+            .rebuilding
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            bail!("The token holder index is being rebuilt.");
+        }
+        // the rebuilder resets the `rebuilding` flag when dropped, even if the clear fails.
+        let rebuilder = TokenHolderIndexRebuilder {
+            index: self.clone(),
+            holders: vec![],
+            counts: BTreeMap::new(),
+            saved: 0,
+        };
+        self.clear_head()?;
+        self.clear(TOKEN_HOLDER_PREFIX_NAME, |keys| {
+            self.holder_storage.delete_all(
+                keys.iter()
+                    .map(|key| TokenHolderKey::decode_key(key.as_slice()))
+                    .collect::<Result<Vec<_>>>()?,
+            )
+        })?;
+        self.clear(TOKEN_HOLDER_COUNT_PREFIX_NAME, |keys| {
+            self.count_storage.delete_all(
+                keys.iter()
+                    .map(|key| TokenCode::decode_key(key.as_slice()))
+                    .collect::<Result<Vec<_>>>()?,
+            )
+        })?;
+        Ok(rebuilder)
+    }
+
+    fn clear<F>(&self, prefix_name: &str, delete: F) -> Result<()>
+    where
+        F: Fn(Vec<Vec<u8>>) -> Result<()>,
+    {
+        loop {
+            let mut iter = self.db()?.iter(prefix_name)?;
+            iter.seek_to_first();
+            let keys = iter
+                .take(Self::BATCH_SIZE)
+                .map(|item| item.map(|(key, _)| key))
+                .collect::<Result<Vec<_>>>()?;
+            if keys.is_empty() {
+                return Ok(());
+            }
+            delete(keys)?;
+        }
+    }
+}
+
+/// Write the holders of the rebuilding index in batches, so the holders are never all in memory.
+pub struct TokenHolderIndexRebuilder {
+    index: TokenHolderIndex,
+    holders: Vec<(TokenHolderKey, u128)>,
+    counts: BTreeMap<TokenCode, u64>,
+    saved: u64,
+}
+
+impl TokenHolderIndexRebuilder {
+    /// Add a holder to the index, the zero balance is ignored.
+    pub fn add_holder(&mut self, key: TokenHolderKey, balance: u128) -> Result<()> {
+        if balance == 0 {
+            return Ok(());
+        }
+        *self.counts.entry(key.token_code.clone()).or_default() += 1;
+        self.holders.push((key, balance));
+        self.saved += 1;
+        if self.holders.len() >= TokenHolderIndex::BATCH_SIZE {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let holders = std::mem::take(&mut self.holders);
+        self.index.holder_storage.put_all(holders)
+    }
+
+    /// Save the holder counts and set the index head to the block `head` whose state the holders are read from,
+    /// return the count of the saved holders.
+    pub fn finish(mut self, head: HashValue) -> Result<u64> {
+        self.flush()?;
+        let counts = std::mem::take(&mut self.counts);
+        self.index
+            .count_storage
+            .put_all(counts.into_iter().collect())?;
+        let _guard = self.index.lock.lock();
+        self.index
+            .chain_info_storage
+            .save_token_holder_index_head(head)?;
+        Ok(self.saved)
+    }
+}
+
+impl Drop for TokenHolderIndexRebuilder {
+    fn drop(&mut self) {
+        self.index.rebuilding.store(false, Ordering::SeqCst);
+    }
+}
@@ -31,6 +31,10 @@ impl TransactionStore for TransactionStorage {
         self.get(txn_hash)
     }
 
+    fn get_transactions(&self, txn_hashes: Vec<HashValue>) -> Result<Vec<Option<Transaction>>> {
+        self.multiple_get(txn_hashes)
+    }
+
     fn save_transaction(&self, txn_info: Transaction) -> Result<()> {
         self.put(txn_info.id(), txn_info)
     }
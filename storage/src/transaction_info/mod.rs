@@ -5,10 +5,11 @@ use crate::define_storage;
 use crate::storage::{CodecKVStore, CodecWriteBatch, ValueCodec};
 use crate::TRANSACTION_INFO_HASH_PREFIX_NAME;
 use crate::TRANSACTION_INFO_PREFIX_NAME;
+use crate::TRANSACTION_POSITION_PREFIX_NAME;
 use anyhow::{Error, Result};
 use bcs_ext::BCSCodec;
 use crypto::HashValue;
-use starcoin_types::transaction::BlockTransactionInfo;
+use starcoin_types::transaction::{BlockTransactionInfo, TransactionPosition};
 
 define_storage!(
     TransactionInfoStorage,
@@ -24,6 +25,23 @@ define_storage!(
     TRANSACTION_INFO_HASH_PREFIX_NAME
 );
 
+define_storage!(
+    TransactionPositionStorage,
+    HashValue,
+    Vec<TransactionPosition>,
+    TRANSACTION_POSITION_PREFIX_NAME
+);
+
+impl ValueCodec for Vec<TransactionPosition> {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Self::decode(data)
+    }
+}
+
 impl ValueCodec for BlockTransactionInfo {
     fn encode_value(&self) -> Result<Vec<u8>> {
         self.encode()
@@ -80,3 +98,20 @@ impl TransactionInfoStorage {
         self.write_batch(batch)
     }
 }
+
+impl TransactionPositionStorage {
+    /// Add the positions to the txns, the position of the same block is replaced.
+    pub(crate) fn save_transaction_positions(
+        &self,
+        txn_positions: Vec<(HashValue, TransactionPosition)>,
+    ) -> Result<(), Error> {
+        let mut batch = CodecWriteBatch::new();
+        for (txn_hash, position) in txn_positions {
+            let mut positions = self.get(txn_hash)?.unwrap_or_default();
+            positions.retain(|exist| exist.block_id != position.block_id);
+            positions.push(position);
+            batch.put(txn_hash, positions)?;
+        }
+        self.write_batch(batch)
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::storage::{ColumnFamily, InnerStorage, KVStore};
+use crate::WEBHOOK_PREFIX_NAME;
+use anyhow::Result;
+use bcs_ext::BCSCodec;
+use crypto::HashValue;
+use starcoin_types::webhook::WebhookRegistration;
+
+#[derive(Clone)]
+pub struct WebhookColumnFamily;
+
+impl ColumnFamily for WebhookColumnFamily {
+    type Key = String;
+    type Value = Vec<u8>;
+
+    fn name() -> &'static str {
+        WEBHOOK_PREFIX_NAME
+    }
+}
+
+/// The webhook registrations are few, so all of them are saved under one key,
+/// as the db storage does not support iterating keys.
+pub type WebhookStorage = InnerStorage<WebhookColumnFamily>;
+
+impl WebhookStorage {
+    const REGISTRATIONS_KEY: &'static str = "registrations";
+
+    pub fn get_webhooks(&self) -> Result<Vec<WebhookRegistration>> {
+        match self.get(Self::REGISTRATIONS_KEY.as_bytes())? {
+            Some(bytes) => Vec::<WebhookRegistration>::decode(bytes.as_slice()),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn save_webhooks(&self, registrations: Vec<WebhookRegistration>) -> Result<()> {
+        self.put(
+            Self::REGISTRATIONS_KEY.as_bytes().to_vec(),
+            registrations.encode()?,
+        )
+    }
+
+    pub fn save_webhook(&self, registration: WebhookRegistration) -> Result<()> {
+        let mut registrations = self.get_webhooks()?;
+        match registrations
+            .iter_mut()
+            .find(|exist| exist.id == registration.id)
+        {
+            Some(exist) => *exist = registration,
+            None => registrations.push(registration),
+        }
+        self.save_webhooks(registrations)
+    }
+
+    pub fn remove_webhook(&self, id: HashValue) -> Result<Option<WebhookRegistration>> {
+        let mut registrations = self.get_webhooks()?;
+        match registrations.iter().position(|exist| exist.id == id) {
+            Some(idx) => {
+                let removed = registrations.remove(idx);
+                self.save_webhooks(registrations)?;
+                Ok(Some(removed))
+            }
+            None => Ok(None),
+        }
+    }
+}
@@ -70,6 +70,22 @@ pub struct SyncProgressReport {
     pub target_difficulty: U256,
     pub target_peers: Vec<PeerId>,
     pub current: TaskProgressReport,
+    /// The head block number of the local chain.
+    pub current_number: BlockNumber,
+    /// Number of blocks applied since the sync task begin.
+    pub applied_blocks: u64,
+    /// Blocks applied per second over the last progress window.
+    pub blocks_per_second: f64,
+    /// Number of peers serving the sync task.
+    pub peers: u64,
+    /// Estimated seconds to reach the target, `None` if no block applied in the last window.
+    pub eta_seconds: Option<u64>,
+    /// Number of the in-flight block fetches.
+    pub inflight_fetches: u64,
+    /// The in-flight block fetches of every peer.
+    pub inflight_peers: Vec<(PeerId, u64)>,
+    /// The peers blacklisted for returning bad blocks.
+    pub bad_peers: Vec<PeerId>,
 }
 
 impl ServiceRequest for SyncProgressRequest {
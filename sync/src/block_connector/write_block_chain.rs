@@ -10,7 +10,7 @@ use starcoin_chain_api::{ChainReader, ChainWriter, ConnectBlockError, WriteableC
 use starcoin_crypto::HashValue;
 use starcoin_service_registry::bus::{Bus, BusService};
 use starcoin_service_registry::ServiceRef;
-use starcoin_storage::Store;
+use starcoin_storage::{Store, TokenHolderStore};
 use starcoin_txpool_api::TxPoolSyncService;
 use starcoin_types::{
     block::{Block, BlockHeader, ExecutedBlock},
@@ -151,6 +151,16 @@ where
         debug_assert!(!enacted_blocks.is_empty());
         debug_assert_eq!(enacted_blocks.last().unwrap(), executed_block.block());
         self.update_startup_info(executed_block.header())?;
+        if let Err(e) = self.main.update_token_holder_index() {
+            // invalidate the index rather than serve the stale holders, it need to be rebuilt.
+            error!(
+                "update token holder index err : {:?}, clear the index head.",
+                e
+            );
+            if let Err(e) = self.main.get_storage().clear_token_holder_index_head() {
+                error!("clear token holder index head err : {:?}", e);
+            }
+        }
         if retracted_count > 0 {
             WRITE_BLOCK_CHAIN_METRICS
                 .rollback_block_size
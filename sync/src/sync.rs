@@ -3,8 +3,8 @@
 
 use crate::block_connector::BlockConnectorService;
 use crate::sync_metrics::SYNC_METRICS;
-use crate::tasks::{full_sync_task, AncestorEvent, SyncFetcher};
-use crate::verified_rpc_client::{RpcVerifyError, VerifiedRpcClient};
+use crate::tasks::{full_sync_task, AncestorEvent, BlockFetchOption, SyncFetcher};
+use crate::verified_rpc_client::{BlockFetchTracker, RpcVerifyError, VerifiedRpcClient};
 use anyhow::{format_err, Result};
 use config::NodeConfig;
 use futures::FutureExt;
@@ -24,16 +24,64 @@ use starcoin_sync_api::{
     PeerScoreRequest, PeerScoreResponse, SyncCancelRequest, SyncProgressReport,
     SyncProgressRequest, SyncServiceHandler, SyncStartRequest, SyncStatusRequest, SyncTarget,
 };
-use starcoin_types::block::BlockIdAndNumber;
+use starcoin_types::block::{BlockIdAndNumber, BlockNumber};
+use starcoin_types::checkpoint::CheckpointSet;
 use starcoin_types::peer_info::PeerId;
 use starcoin_types::startup_info::ChainStatus;
 use starcoin_types::sync_status::SyncStatus;
 use starcoin_types::system_events::{NewHeadBlock, SyncStatusChangeEvent, SystemStarted};
+use std::collections::VecDeque;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use stream_task::{TaskError, TaskEventCounterHandle, TaskHandle};
 
 const REPUTATION_THRESHOLD: i32 = -1000;
+/// The window for calculating the sync speed.
+const PROGRESS_WINDOW: Duration = Duration::from_secs(60);
+
+/// Samples of applied block number during the last `PROGRESS_WINDOW`.
+#[derive(Default)]
+struct ProgressWindow {
+    samples: VecDeque<(Instant, BlockNumber)>,
+}
+
+impl ProgressWindow {
+    fn record(&mut self, number: BlockNumber) {
+        self.record_at(Instant::now(), number)
+    }
+
+    fn record_at(&mut self, now: Instant, number: BlockNumber) {
+        self.samples.push_back((now, number));
+        // keep the latest sample before the window begin, as the base of the speed.
+        while self.samples.len() > 2 && now.duration_since(self.samples[1].0) > PROGRESS_WINDOW {
+            self.samples.pop_front();
+        }
+    }
+
+    fn blocks_per_second(&self) -> f64 {
+        match (self.samples.front(), self.samples.back()) {
+            (Some((begin_time, begin_number)), Some((end_time, end_number))) => {
+                let seconds = end_time.duration_since(*begin_time).as_secs_f64();
+                if seconds > 0f64 {
+                    end_number.saturating_sub(*begin_number) as f64 / seconds
+                } else {
+                    0f64
+                }
+            }
+            _ => 0f64,
+        }
+    }
+
+    /// The estimated seconds to apply the `remaining` blocks, None if there is no progress in the window.
+    fn eta_seconds(&self, remaining: u64) -> Option<u64> {
+        let blocks_per_second = self.blocks_per_second();
+        if blocks_per_second > 0f64 {
+            Some((remaining as f64 / blocks_per_second).ceil() as u64)
+        } else {
+            None
+        }
+    }
+}
 
 //TODO combine task_handle and task_event_handle in stream_task
 pub struct SyncTaskHandle {
@@ -42,6 +90,8 @@ pub struct SyncTaskHandle {
     task_handle: TaskHandle,
     task_event_handle: Arc<TaskEventCounterHandle>,
     peer_selector: PeerSelector,
+    fetch_tracker: BlockFetchTracker,
+    progress_window: ProgressWindow,
 }
 
 pub enum SyncStage {
@@ -57,10 +107,21 @@ pub struct SyncService {
     stage: SyncStage,
     config: Arc<NodeConfig>,
     storage: Arc<Storage>,
+    checkpoints: Option<Arc<CheckpointSet>>,
 }
 
 impl SyncService {
     pub fn new(config: Arc<NodeConfig>, storage: Arc<Storage>) -> Result<Self> {
+        let checkpoints = config.sync.checkpoints()?.map(Arc::new);
+        if let Some(latest) = checkpoints
+            .as_ref()
+            .and_then(|checkpoints| checkpoints.latest())
+        {
+            info!(
+                "[sync] Load checkpoints, the headers at or below {} skip the PoW verification.",
+                latest.number
+            );
+        }
         let startup_info = storage
             .get_startup_info()?
             .ok_or_else(|| format_err!("can't get startup info"))?;
@@ -77,6 +138,7 @@ impl SyncService {
             stage: SyncStage::NotStart,
             config,
             storage,
+            checkpoints,
         })
     }
 
@@ -119,6 +181,7 @@ impl SyncService {
         let self_ref = ctx.self_ref();
         let connector_service = ctx.service_ref::<BlockConnectorService>()?.clone();
         let config = self.config.clone();
+        let checkpoints = self.checkpoints.clone();
         let fut = async move {
             let peer_select_strategy =
                 peer_strategy.unwrap_or_else(|| config.sync.peer_select_strategy());
@@ -172,10 +235,10 @@ impl SyncService {
                     format_err!("Can not find block info by id: {}", current_block_id)
                 })?;
 
-            let rpc_client = Arc::new(VerifiedRpcClient::new(
-                peer_selector.clone(),
-                network.clone(),
-            ));
+            let rpc_client = Arc::new(
+                VerifiedRpcClient::new(peer_selector.clone(), network.clone())
+                    .with_max_inflight_per_peer(config.sync.max_inflight_per_peer()),
+            );
             if let Some(target) =
                 rpc_client.get_best_target(current_block_info.get_total_difficulty())?
             {
@@ -192,6 +255,8 @@ impl SyncService {
                     self_ref.clone(),
                     network.clone(),
                     config.sync.max_retry_times(),
+                    BlockFetchOption::from(&config.sync),
+                    checkpoints,
                 )?;
 
                 self_ref.notify(SyncBeginEvent {
@@ -199,6 +264,7 @@ impl SyncService {
                     task_handle,
                     task_event_handle,
                     peer_selector,
+                    fetch_tracker: rpc_client.fetch_tracker().clone(),
                 })?;
                 SYNC_METRICS.sync_times.with_label_values(&["start"]).inc();
                 Ok(Some(fut.await?))
@@ -323,6 +389,7 @@ impl EventHandler<Self, AncestorEvent> for SyncService {
     fn handle_event(&mut self, msg: AncestorEvent, _ctx: &mut ServiceContext<SyncService>) {
         match &mut self.stage {
             SyncStage::Synchronizing(handle) => {
+                handle.progress_window.record(msg.ancestor.number);
                 handle.task_begin = Some(msg.ancestor);
             }
             _ => {
@@ -369,15 +436,17 @@ pub struct SyncBeginEvent {
     task_handle: TaskHandle,
     task_event_handle: Arc<TaskEventCounterHandle>,
     peer_selector: PeerSelector,
+    fetch_tracker: BlockFetchTracker,
 }
 
 impl EventHandler<Self, SyncBeginEvent> for SyncService {
     fn handle_event(&mut self, msg: SyncBeginEvent, ctx: &mut ServiceContext<Self>) {
-        let (target, task_handle, task_event_handle, peer_selector) = (
+        let (target, task_handle, task_event_handle, peer_selector, fetch_tracker) = (
             msg.target,
             msg.task_handle,
             msg.task_event_handle,
             msg.peer_selector,
+            msg.fetch_tracker,
         );
         let sync_task_handle = SyncTaskHandle {
             target: target.clone(),
@@ -385,6 +454,8 @@ impl EventHandler<Self, SyncBeginEvent> for SyncService {
             task_handle: task_handle.clone(),
             task_event_handle,
             peer_selector,
+            fetch_tracker,
+            progress_window: ProgressWindow::default(),
         };
         match std::mem::replace(
             &mut self.stage,
@@ -506,6 +577,9 @@ impl EventHandler<Self, SyncDoneEvent> for SyncService {
 impl EventHandler<Self, NewHeadBlock> for SyncService {
     fn handle_event(&mut self, msg: NewHeadBlock, ctx: &mut ServiceContext<Self>) {
         let NewHeadBlock(block) = msg;
+        if let SyncStage::Synchronizing(handle) = &mut self.stage {
+            handle.progress_window.record(block.header().number());
+        }
         if self.sync_status.update_chain_status(ChainStatus::new(
             block.header().clone(),
             block.block_info.clone(),
@@ -545,17 +619,22 @@ impl ServiceHandler<Self, SyncProgressRequest> for SyncService {
         _msg: SyncProgressRequest,
         _ctx: &mut ServiceContext<SyncService>,
     ) -> Option<SyncProgressReport> {
+        let current_number = self.sync_status.chain_status().head().number();
         self.task_handle().and_then(|handle| {
             handle.task_event_handle.total_report().map(|mut report| {
+                let target_number = handle.target.target_id.number();
                 if let Some(begin) = handle.task_begin.as_ref() {
-                    report.fix_percent(
-                        handle
-                            .target
-                            .target_id
-                            .number()
-                            .saturating_sub(begin.number),
-                    );
+                    report.fix_percent(target_number.saturating_sub(begin.number));
                 }
+                let applied_blocks = handle
+                    .task_begin
+                    .as_ref()
+                    .map(|begin| current_number.saturating_sub(begin.number))
+                    .unwrap_or_default();
+                let blocks_per_second = handle.progress_window.blocks_per_second();
+                let eta_seconds = handle
+                    .progress_window
+                    .eta_seconds(target_number.saturating_sub(current_number));
 
                 SyncProgressReport {
                     target_id: handle.target.target_id.id(),
@@ -563,10 +642,18 @@ impl ServiceHandler<Self, SyncProgressRequest> for SyncService {
                         .task_begin
                         .as_ref()
                         .map(|begin| -> u64 { begin.number }),
-                    target_number: handle.target.target_id.number(),
+                    target_number,
                     target_difficulty: handle.target.block_info.total_difficulty,
                     target_peers: handle.target.peers.clone(),
                     current: report,
+                    current_number,
+                    applied_blocks,
+                    blocks_per_second,
+                    peers: handle.peer_selector.len() as u64,
+                    eta_seconds,
+                    inflight_fetches: handle.fetch_tracker.inflight(),
+                    inflight_peers: handle.fetch_tracker.inflight_peers(),
+                    bad_peers: handle.fetch_tracker.bad_peers(),
                 }
             })
         })
@@ -599,3 +686,49 @@ impl ServiceHandler<Self, SyncStartRequest> for SyncService {
 }
 
 impl SyncServiceHandler for SyncService {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_speed(window: &ProgressWindow, expected: f64) {
+        assert!((window.blocks_per_second() - expected).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_progress_window() {
+        let mut window = ProgressWindow::default();
+        assert_speed(&window, 0f64);
+        assert_eq!(window.eta_seconds(100), None);
+
+        let begin = Instant::now();
+        window.record_at(begin, 100);
+        // one sample has no speed.
+        assert_speed(&window, 0f64);
+        assert_eq!(window.eta_seconds(100), None);
+
+        window.record_at(begin + Duration::from_secs(10), 150);
+        assert_speed(&window, 5f64);
+        assert_eq!(window.eta_seconds(100), Some(20));
+        // round up the seconds.
+        assert_eq!(window.eta_seconds(101), Some(21));
+        assert_eq!(window.eta_seconds(0), Some(0));
+
+        window.record_at(begin + Duration::from_secs(20), 250);
+        assert_speed(&window, 7.5f64);
+
+        // the samples out of the window are dropped, but the latest one before the window
+        // is kept as the base.
+        window.record_at(begin + PROGRESS_WINDOW + Duration::from_secs(30), 400);
+        assert_eq!(window.samples.len(), 2);
+        assert_eq!(window.samples.front().unwrap().1, 250);
+        assert_speed(&window, 150f64 / 70f64);
+
+        // no progress in the window.
+        let mut window = ProgressWindow::default();
+        window.record_at(begin, 100);
+        window.record_at(begin + Duration::from_secs(10), 100);
+        assert_speed(&window, 0f64);
+        assert_eq!(window.eta_seconds(100), None);
+    }
+}
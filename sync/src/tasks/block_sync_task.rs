@@ -11,9 +11,12 @@ use logger::prelude::*;
 use network_api::PeerProvider;
 use starcoin_accumulator::{Accumulator, MerkleAccumulator};
 use starcoin_chain::{verifier::BasicVerifier, BlockChain};
-use starcoin_chain_api::{ChainReader, ChainWriter, ConnectBlockError, ExecutedBlock};
+use starcoin_chain_api::{
+    ChainReader, ChainWriter, ConnectBlockError, ExecutedBlock, VerifyBlockField,
+};
 use starcoin_sync_api::SyncTarget;
 use starcoin_types::block::{Block, BlockIdAndNumber, BlockInfo, BlockNumber};
+use starcoin_types::checkpoint::{CheckpointMismatch, CheckpointSet};
 use starcoin_types::peer_info::PeerId;
 use starcoin_vm_types::on_chain_config::GlobalTimeOnChain;
 use std::collections::HashMap;
@@ -187,6 +190,9 @@ pub struct BlockCollector<N, H> {
     event_handle: H,
     peer_provider: N,
     skip_pow_verify: bool,
+    checkpoints: Option<Arc<CheckpointSet>>,
+    // the blocks at or below the verified checkpoint skip the PoW verification.
+    verified_checkpoint: Option<BlockNumber>,
 }
 
 impl<N, H> BlockCollector<N, H>
@@ -209,6 +215,39 @@ where
             event_handle,
             peer_provider,
             skip_pow_verify,
+            checkpoints: None,
+            verified_checkpoint: None,
+        }
+    }
+
+    /// Check the blocks at the checkpoint numbers against the checkpoints, the blocks at or below
+    /// `verified_checkpoint`, which is checked by the block accumulator, skip the PoW verification.
+    pub fn with_checkpoints(
+        mut self,
+        checkpoints: Arc<CheckpointSet>,
+        verified_checkpoint: Option<BlockNumber>,
+    ) -> Self {
+        self.checkpoints = Some(checkpoints);
+        self.verified_checkpoint = verified_checkpoint;
+        self
+    }
+
+    fn verify_checkpoint(&self, block: &Block) -> Result<()> {
+        let checkpoint = match self
+            .checkpoints
+            .as_ref()
+            .and_then(|checkpoints| checkpoints.get(block.header().number()))
+        {
+            Some(checkpoint) => checkpoint,
+            None => return Ok(()),
+        };
+        match CheckpointMismatch::check(checkpoint, block.header()) {
+            Some(mismatch) => Err(ConnectBlockError::VerifyBlockFailed(
+                VerifyBlockField::Checkpoint,
+                format_err!("{}", mismatch),
+            )
+            .into()),
+            None => Ok(()),
         }
     }
 
@@ -222,12 +261,19 @@ where
             .sync_apply_block_time
             .with_label_values(&["time"])
             .start_timer();
-        if let Err(err) = if self.skip_pow_verify {
-            self.chain
-                .apply_with_verifier::<BasicVerifier>(block.clone())
-        } else {
-            self.chain.apply(block.clone())
-        } {
+        let skip_pow_verify = self.skip_pow_verify
+            || self
+                .verified_checkpoint
+                .map(|number| block.header().number() <= number)
+                .unwrap_or(false);
+        if let Err(err) = self.verify_checkpoint(&block).and_then(|_| {
+            if skip_pow_verify {
+                self.chain
+                    .apply_with_verifier::<BasicVerifier>(block.clone())
+            } else {
+                self.chain.apply(block.clone())
+            }
+        }) {
             error!(
                 "[sync] collect block error: {:?}, peer_id:{:?} ",
                 err, peer_id
@@ -1,14 +1,17 @@
 use crate::tasks::{
     AccumulatorCollector, BlockAccumulatorSyncTask, BlockCollector, BlockConnectedEventHandle,
-    BlockFetcher, BlockIdFetcher, BlockSyncTask, PeerOperator,
+    BlockFetchOption, BlockFetcher, BlockIdFetcher, BlockSyncTask, PeerOperator,
 };
 use anyhow::format_err;
 use network_api::PeerProvider;
 use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::{Accumulator, MerkleAccumulator};
 use starcoin_chain::BlockChain;
+use starcoin_chain_api::{ConnectBlockError, VerifyBlockField};
 use starcoin_storage::Store;
 use starcoin_sync_api::SyncTarget;
-use starcoin_types::block::{BlockIdAndNumber, BlockInfo};
+use starcoin_types::block::{BlockIdAndNumber, BlockInfo, BlockNumber};
+use starcoin_types::checkpoint::CheckpointSet;
 use starcoin_types::time::TimeService;
 use std::sync::Arc;
 use stream_task::{
@@ -30,6 +33,8 @@ where
     time_service: Arc<dyn TimeService>,
     peer_provider: N,
     custom_error_handle: Arc<dyn CustomErrorHandle>,
+    fetch_option: BlockFetchOption,
+    checkpoints: Option<Arc<CheckpointSet>>,
 }
 
 impl<H, F, N> InnerSyncTask<H, F, N>
@@ -48,6 +53,8 @@ where
         time_service: Arc<dyn TimeService>,
         peer_provider: N,
         custom_error_handle: Arc<dyn CustomErrorHandle>,
+        fetch_option: BlockFetchOption,
+        checkpoints: Option<Arc<CheckpointSet>>,
     ) -> Self {
         Self {
             ancestor,
@@ -59,6 +66,8 @@ where
             time_service,
             peer_provider,
             custom_error_handle,
+            fetch_option,
+            checkpoints,
         }
     }
 
@@ -81,6 +90,9 @@ where
         skip_pow_verify_when_sync: bool,
     ) -> Result<(BlockChain, TaskHandle), TaskError> {
         let buffer_size = self.target.peers.len();
+        // fetch the blocks concurrently from the peers, the blocks are still executed in order.
+        let fetch_concurrency = self.fetch_option.concurrency_for_peers(buffer_size);
+        let block_buffer_size = std::cmp::max(self.fetch_option.buffer_size, fetch_concurrency);
 
         let ancestor_block_info = self.ancestor_block_info().map_err(TaskError::BreakError)?;
         let accumulator_sync_task = BlockAccumulatorSyncTask::new(
@@ -109,6 +121,13 @@ where
         .and_then(move |(ancestor, accumulator), event_handle| {
             let check_local_store =
                 ancestor_block_info.total_difficulty < current_block_info.total_difficulty;
+            // the mismatched checkpoint aborts the sync before fetching the blocks.
+            let verified_checkpoint = match self.checkpoints.as_ref() {
+                Some(checkpoints) => {
+                    verify_accumulator_checkpoints(checkpoints, &accumulator, ancestor.number)?
+                }
+                None => None,
+            };
 
             let block_sync_task = BlockSyncTask::new(
                 accumulator,
@@ -128,15 +147,22 @@ where
                 self.peer_provider.clone(),
                 skip_pow_verify_when_sync,
             );
+            let block_collector = match self.checkpoints.clone() {
+                Some(checkpoints) => {
+                    block_collector.with_checkpoints(checkpoints, verified_checkpoint)
+                }
+                None => block_collector,
+            };
             Ok(TaskGenerator::new(
                 block_sync_task,
-                buffer_size,
+                fetch_concurrency,
                 max_retry_times,
                 delay_milliseconds_on_error,
                 block_collector,
                 event_handle,
                 self.custom_error_handle.clone(),
-            ))
+            )
+            .with_sink_buffer_size(block_buffer_size))
         })
         .generate();
 
@@ -146,3 +172,39 @@ where
         Ok((block_chain, handle))
     }
 }
+
+/// Check the block ids in the block accumulator of the sync target against the checkpoints after
+/// the ancestor, return the number of the latest checkpoint in the accumulator.
+pub(crate) fn verify_accumulator_checkpoints(
+    checkpoints: &CheckpointSet,
+    accumulator: &MerkleAccumulator,
+    ancestor_number: BlockNumber,
+) -> anyhow::Result<Option<BlockNumber>> {
+    let target_number = match accumulator.num_leaves().checked_sub(1) {
+        Some(number) => number,
+        None => return Ok(None),
+    };
+    let mut verified_checkpoint = None;
+    for checkpoint in checkpoints.range(ancestor_number.saturating_add(1), target_number) {
+        let block_id = accumulator.get_leaf(checkpoint.number)?.ok_or_else(|| {
+            format_err!(
+                "[sync] Can not find block id by number {} in the block accumulator",
+                checkpoint.number
+            )
+        })?;
+        if block_id != checkpoint.block_hash {
+            return Err(ConnectBlockError::VerifyBlockFailed(
+                VerifyBlockField::Checkpoint,
+                format_err!(
+                    "The sync target block {:#x} at number {} mismatches the checkpoint block {:#x}, abort the sync.",
+                    block_id,
+                    checkpoint.number,
+                    checkpoint.block_hash
+                ),
+            )
+            .into());
+        }
+        verified_checkpoint = Some(checkpoint.number);
+    }
+    Ok(verified_checkpoint)
+}
@@ -5,6 +5,7 @@ use crate::tasks::block_sync_task::SyncBlockData;
 use crate::tasks::inner_sync_task::InnerSyncTask;
 use crate::verified_rpc_client::{RpcVerifyError, VerifiedRpcClient};
 use anyhow::{format_err, Error, Result};
+use config::SyncConfig;
 use futures::channel::mpsc::UnboundedSender;
 use futures::future::BoxFuture;
 use futures::{FutureExt, TryFutureExt};
@@ -19,6 +20,7 @@ use starcoin_service_registry::{ActorService, EventHandler, ServiceRef};
 use starcoin_storage::Store;
 use starcoin_sync_api::SyncTarget;
 use starcoin_types::block::{Block, BlockIdAndNumber, BlockInfo, BlockNumber};
+use starcoin_types::checkpoint::CheckpointSet;
 use starcoin_types::peer_info::PeerId;
 use starcoin_types::startup_info::ChainStatus;
 use starcoin_types::U256;
@@ -489,6 +491,8 @@ pub fn full_sync_task<H, A, F, N>(
     ancestor_event_handle: A,
     peer_provider: N,
     max_retry_times: u64,
+    fetch_option: BlockFetchOption,
+    checkpoints: Option<Arc<CheckpointSet>>,
 ) -> Result<(
     BoxFuture<'static, Result<BlockChain, TaskError>>,
     TaskHandle,
@@ -593,6 +597,8 @@ where
                 time_service.clone(),
                 peer_provider.clone(),
                 ext_error_handle.clone(),
+                fetch_option,
+                checkpoints.clone(),
             );
             let start_now = Instant::now();
             let (block_chain, _) = inner
@@ -643,6 +649,46 @@ where
     Ok((fut, handle, event_handle))
 }
 
+/// Options for fetching the blocks of the sync task concurrently from the peers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BlockFetchOption {
+    /// Max concurrent block fetches across all the peers.
+    pub concurrency: usize,
+    /// Max in-flight block fetches per peer.
+    pub max_inflight_per_peer: usize,
+    /// Max fetched blocks waiting to be executed in order.
+    pub buffer_size: usize,
+}
+
+impl BlockFetchOption {
+    /// The concurrency for the peers, limited by the in-flight limit of every peer.
+    pub fn concurrency_for_peers(&self, peers: usize) -> usize {
+        std::cmp::max(
+            std::cmp::min(
+                self.concurrency,
+                peers.saturating_mul(self.max_inflight_per_peer),
+            ),
+            1,
+        )
+    }
+}
+
+impl Default for BlockFetchOption {
+    fn default() -> Self {
+        Self::from(&SyncConfig::default())
+    }
+}
+
+impl From<&SyncConfig> for BlockFetchOption {
+    fn from(config: &SyncConfig) -> Self {
+        Self {
+            concurrency: config.block_fetch_concurrency() as usize,
+            max_inflight_per_peer: config.max_inflight_per_peer() as usize,
+            buffer_size: config.block_buffer_size() as usize,
+        }
+    }
+}
+
 const MAX_BETTER_PEER_SIZE: u64 = 20;
 
 fn max_better_peers(target_block_number: u64, latest_block_number: u64) -> u64 {
@@ -6,9 +6,10 @@ use crate::tasks::block_sync_task::SyncBlockData;
 use crate::tasks::mock::{ErrorStrategy, MockBlockIdFetcher, SyncNodeMocker};
 use crate::tasks::{
     full_sync_task, AccumulatorCollector, AncestorCollector, BlockAccumulatorSyncTask,
-    BlockCollector, BlockFetcher, BlockLocalStore, BlockSyncTask, FindAncestorTask, SyncFetcher,
+    BlockCollector, BlockFetchOption, BlockFetcher, BlockLocalStore, BlockSyncTask,
+    FindAncestorTask, SyncFetcher,
 };
-use crate::verified_rpc_client::RpcVerifyError;
+use crate::verified_rpc_client::{BlockFetchTracker, RpcVerifyError, VerifiedRpcClient};
 use anyhow::Context;
 use anyhow::{format_err, Result};
 use config::{BuiltinNetworkID, ChainNetwork};
@@ -27,13 +28,16 @@ use starcoin_chain_api::ChainReader;
 use starcoin_chain_mock::MockChain;
 use starcoin_crypto::HashValue;
 use starcoin_genesis::Genesis;
+use starcoin_network_rpc_api::RawRpcClient;
 use starcoin_storage::BlockStore;
 use starcoin_sync_api::SyncTarget;
+use starcoin_types::checkpoint::{Checkpoint, CheckpointSet};
 use starcoin_types::peer_info::PeerInfo;
 use starcoin_types::{
     block::{Block, BlockBody, BlockHeaderBuilder, BlockIdAndNumber, BlockInfo},
     U256,
 };
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use stream_task::{
@@ -71,6 +75,8 @@ pub async fn test_full_sync_new_node() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOption::default(),
+        None,
     )?;
     let join_handle = node2.process_block_connect_event(receiver_1).await;
     let branch = sync_task.await?;
@@ -100,6 +106,8 @@ pub async fn test_full_sync_new_node() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOption::default(),
+        None,
     )?;
     let join_handle = node2.process_block_connect_event(receiver_1).await;
     let branch = sync_task.await?;
@@ -116,6 +124,65 @@ pub async fn test_full_sync_new_node() -> Result<()> {
     Ok(())
 }
 
+#[stest::test]
+pub async fn test_full_sync_with_checkpoints() -> Result<()> {
+    let net1 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+    let mut node1 = SyncNodeMocker::new(net1, 1, 50)?;
+    node1.produce_block(20)?;
+    let checkpoints: Vec<Checkpoint> = [5u64, 10]
+        .iter()
+        .map(|number| -> Result<Checkpoint> {
+            let header = node1
+                .chain()
+                .get_header_by_number(*number)?
+                .expect("header should exist");
+            Ok(Checkpoint::new(*number, header.id(), header.state_root()))
+        })
+        .collect::<Result<_>>()?;
+    let arc_node1 = Arc::new(node1);
+    let target = arc_node1.sync_target();
+
+    let sync = |checkpoints: Vec<Checkpoint>| {
+        let arc_node1 = arc_node1.clone();
+        let target = target.clone();
+        async move {
+            let net2 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+            let node2 = SyncNodeMocker::new(net2.clone(), 1, 50)?;
+            let checkpoints = CheckpointSet::new(net2.chain_id(), checkpoints)?;
+            let (sender_1, receiver_1) = unbounded();
+            let (sender_2, _receiver_2) = unbounded();
+            let (sync_task, _task_handle, _task_event_counter) = full_sync_task(
+                node2.chain().current_header().id(),
+                target,
+                false,
+                net2.time_service(),
+                node2.chain().get_storage(),
+                sender_1,
+                arc_node1,
+                sender_2,
+                DummyNetworkService::default(),
+                15,
+                BlockFetchOption::default(),
+                Some(Arc::new(checkpoints)),
+            )?;
+            let join_handle = node2.process_block_connect_event(receiver_1).await;
+            let result = sync_task.await;
+            let _node2 = join_handle.await;
+            Ok::<_, anyhow::Error>(result)
+        }
+    };
+
+    let branch = sync(checkpoints.clone()).await??;
+    assert_eq!(branch.current_header().id(), target.target_id.id());
+
+    // the mismatched checkpoint aborts the sync.
+    let mut mismatched = checkpoints;
+    mismatched[1].block_hash = HashValue::random();
+    let err = sync(mismatched).await?.unwrap_err();
+    assert!(format!("{:?}", err).contains("checkpoint"));
+    Ok(())
+}
+
 #[stest::test]
 pub async fn test_sync_invalid_target() -> Result<()> {
     let net1 = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
@@ -148,6 +215,8 @@ pub async fn test_sync_invalid_target() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOption::default(),
+        None,
     )?;
     let _join_handle = node2.process_block_connect_event(receiver_1).await;
     let sync_result = sync_task.await;
@@ -229,6 +298,8 @@ pub async fn test_full_sync_fork() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOption::default(),
+        None,
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let branch = sync_task.await?;
@@ -260,6 +331,8 @@ pub async fn test_full_sync_fork() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOption::default(),
+        None,
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let branch = sync_task.await?;
@@ -307,6 +380,8 @@ pub async fn test_full_sync_fork_from_genesis() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOption::default(),
+        None,
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let branch = sync_task.await?;
@@ -359,6 +434,8 @@ pub async fn test_full_sync_continue() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOption::default(),
+        None,
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let branch = sync_task.await?;
@@ -392,6 +469,8 @@ pub async fn test_full_sync_continue() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOption::default(),
+        None,
     )?;
 
     let join_handle = node2.process_block_connect_event(receiver).await;
@@ -442,6 +521,8 @@ pub async fn test_full_sync_cancel() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOption::default(),
+        None,
     )?;
     let join_handle = node2.process_block_connect_event(receiver).await;
     let sync_join_handle = tokio::task::spawn(sync_task);
@@ -883,6 +964,8 @@ async fn test_net_rpc_err() -> Result<()> {
         sender_2,
         DummyNetworkService::default(),
         15,
+        BlockFetchOption::default(),
+        None,
     )?;
     let _join_handle = node2.process_block_connect_event(receiver).await;
     let sync_join_handle = tokio::task::spawn(sync_task);
@@ -958,3 +1041,205 @@ async fn test_sync_target() {
     assert_eq!(target.target_id.number(), low_chain_info.head().number());
     assert_eq!(target.target_id.id(), low_chain_info.head().id());
 }
+
+#[derive(Default)]
+struct BlockRpcState {
+    inflight: HashMap<PeerId, u64>,
+    max_inflight: HashMap<PeerId, u64>,
+}
+
+/// Serve the `get_blocks` requests of the `VerifiedRpcClient` for multiple mock peers,
+/// record the max in-flight requests of every peer, the `bad_peer` returns the blocks with tampered bodies.
+#[derive(Clone)]
+struct MockBlockRpcClient {
+    blocks: Arc<HashMap<HashValue, Block>>,
+    bad_peer: Option<PeerId>,
+    state: Arc<Mutex<BlockRpcState>>,
+}
+
+impl MockBlockRpcClient {
+    pub fn new(total_blocks: u64) -> (Self, MerkleAccumulator) {
+        let store = Arc::new(MockAccumulatorStore::new());
+        let accumulator = MerkleAccumulator::new_empty(store);
+        let mut blocks = HashMap::new();
+        for i in 0..total_blocks {
+            let header = BlockHeaderBuilder::random()
+                .with_number(i)
+                .with_body_hash(BlockBody::new_empty().hash())
+                .build();
+            let block = Block::new(header, BlockBody::new_empty());
+            accumulator.append(&[block.id()]).unwrap();
+            blocks.insert(block.id(), block);
+        }
+        accumulator.flush().unwrap();
+        let client = Self {
+            blocks: Arc::new(blocks),
+            bad_peer: None,
+            state: Arc::new(Mutex::new(BlockRpcState::default())),
+        };
+        (client, accumulator)
+    }
+
+    pub fn max_inflight(&self) -> HashMap<PeerId, u64> {
+        self.state.lock().unwrap().max_inflight.clone()
+    }
+
+    fn get_blocks(&self, peer_id: &PeerId, ids: Vec<HashValue>) -> Vec<Option<Block>> {
+        ids.iter()
+            .map(|id| {
+                self.blocks.get(id).map(|block| {
+                    if self.bad_peer.as_ref() == Some(peer_id) {
+                        let uncles = Some(vec![block.header().clone()]);
+                        Block::new(block.header().clone(), BlockBody::new(vec![], uncles))
+                    } else {
+                        block.clone()
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+impl RawRpcClient for MockBlockRpcClient {
+    fn send_raw_request(
+        &self,
+        peer_id: PeerId,
+        rpc_path: Cow<'static, str>,
+        message: Vec<u8>,
+    ) -> BoxFuture<Result<Vec<u8>>> {
+        let client = self.clone();
+        async move {
+            if rpc_path != "get_blocks" {
+                return Err(format_err!("Unsupported rpc path {}", rpc_path));
+            }
+            {
+                let mut state = client.state.lock().unwrap();
+                let inflight = state.inflight.entry(peer_id.clone()).or_default();
+                *inflight += 1;
+                let inflight = *inflight;
+                let max_inflight = state.max_inflight.entry(peer_id.clone()).or_default();
+                *max_inflight = std::cmp::max(*max_inflight, inflight);
+            }
+            Delay::new(Duration::from_millis(20)).await;
+            let ids: Vec<HashValue> = bcs_ext::from_bytes(message.as_slice())?;
+            let blocks = client.get_blocks(&peer_id, ids);
+            *client
+                .state
+                .lock()
+                .unwrap()
+                .inflight
+                .get_mut(&peer_id)
+                .expect("in-flight peer should exist") -= 1;
+            let response: network_rpc_core::Result<Vec<u8>> = Ok(bcs_ext::to_bytes(&blocks)?);
+            bcs_ext::to_bytes(&response)
+        }
+        .boxed()
+    }
+}
+
+fn new_verified_rpc_client(
+    client: MockBlockRpcClient,
+    peers: usize,
+    max_inflight_per_peer: u64,
+) -> VerifiedRpcClient {
+    let peer_infos = (0..peers).map(|_| PeerInfo::random()).collect();
+    VerifiedRpcClient::new(
+        PeerSelector::new(peer_infos, PeerStrategy::default()),
+        client,
+    )
+    .with_max_inflight_per_peer(max_inflight_per_peer)
+}
+
+async fn multi_peer_block_sync(
+    fetcher: Arc<VerifiedRpcClient>,
+    accumulator: MerkleAccumulator,
+    fetch_option: BlockFetchOption,
+) -> Result<()> {
+    let total_blocks = accumulator.num_leaves();
+    let ancestor = BlockIdAndNumber::new(accumulator.get_leaf(0)?.unwrap(), 0);
+    let concurrency = fetch_option.concurrency_for_peers(fetcher.selector().len());
+    let block_sync_state = BlockSyncTask::new(
+        accumulator,
+        ancestor,
+        fetcher,
+        false,
+        MockLocalBlockStore::new(),
+        1,
+    );
+    let result = TaskGenerator::new(
+        block_sync_state,
+        concurrency,
+        3,
+        1,
+        vec![],
+        Arc::new(TaskEventCounterHandle::new()),
+        Arc::new(DefaultCustomErrorHandle),
+    )
+    .with_sink_buffer_size(fetch_option.buffer_size)
+    .generate()
+    .await?;
+    let numbers: Vec<u64> = result
+        .iter()
+        .map(|block_data| block_data.block.header().number())
+        .collect();
+    assert_eq!(numbers, (1..total_blocks).collect::<Vec<_>>());
+    Ok(())
+}
+
+#[stest::test(timeout = 120)]
+async fn test_block_sync_from_multi_peers() -> Result<()> {
+    let (client, accumulator) = MockBlockRpcClient::new(25);
+    let fetcher = Arc::new(new_verified_rpc_client(client.clone(), 4, 2));
+    // the concurrency is more than the in-flight limit of all the peers.
+    let fetch_option = BlockFetchOption {
+        concurrency: 16,
+        max_inflight_per_peer: 2,
+        buffer_size: 8,
+    };
+    multi_peer_block_sync(fetcher.clone(), accumulator, fetch_option).await?;
+    let max_inflight = client.max_inflight();
+    // the blocks are fetched from more than one peer, and never exceed the limit of a peer.
+    assert!(max_inflight.len() > 1);
+    assert!(max_inflight.values().all(|count| *count <= 2));
+    assert_eq!(fetcher.fetch_tracker().inflight(), 0);
+    assert!(fetcher.fetch_tracker().bad_peers().is_empty());
+    Ok(())
+}
+
+#[stest::test]
+async fn test_block_fetch_tracker_limit() -> Result<()> {
+    let peer_selector = PeerSelector::new(vec![PeerInfo::random()], PeerStrategy::default());
+    let tracker = BlockFetchTracker::new(1);
+    let guard = tracker.acquire(&peer_selector).await?;
+    // the only peer reaches the limit, the fetch waits until the in-flight fetch finishes.
+    assert!(tracker.acquire(&peer_selector).now_or_never().is_none());
+    let mut waiting = tracker.acquire(&peer_selector).boxed();
+    assert!((&mut waiting).now_or_never().is_none());
+    // the waiting fetch is woken up when the in-flight fetch finishes.
+    drop(guard);
+    let guard = waiting.await?;
+    assert_eq!(tracker.inflight(), 1);
+    drop(guard);
+    assert_eq!(tracker.inflight(), 0);
+    Ok(())
+}
+
+#[stest::test(timeout = 120)]
+async fn test_block_sync_blacklist_bad_peer() -> Result<()> {
+    let (mut client, accumulator) = MockBlockRpcClient::new(20);
+    let peer_infos: Vec<PeerInfo> = (0..4).map(|_| PeerInfo::random()).collect();
+    let bad_peer = peer_infos.last().unwrap().peer_id();
+    client.bad_peer = Some(bad_peer.clone());
+    let fetcher = Arc::new(
+        VerifiedRpcClient::new(
+            PeerSelector::new(peer_infos, PeerStrategy::default()),
+            client,
+        )
+        .with_max_inflight_per_peer(2),
+    );
+    multi_peer_block_sync(fetcher.clone(), accumulator, BlockFetchOption::default()).await?;
+    assert_eq!(fetcher.fetch_tracker().bad_peers(), vec![bad_peer.clone()]);
+    assert!(!fetcher.selector().peer_exist(&bad_peer));
+    assert_eq!(fetcher.selector().len(), 3);
+    Ok(())
+}
@@ -4,9 +4,11 @@
 use crate::sync_metrics::SYNC_METRICS;
 use crate::tasks::sync_score_metrics::SYNC_SCORE_METRICS;
 use anyhow::{format_err, Result};
+use futures::channel::oneshot;
 use logger::prelude::*;
 use network_api::peer_score::{InverseScore, Score};
 use network_api::PeerSelector;
+use parking_lot::Mutex;
 use starcoin_accumulator::node::AccumulatorStoreType;
 use starcoin_accumulator::AccumulatorNode;
 use starcoin_crypto::hash::HashValue;
@@ -23,6 +25,7 @@ use starcoin_types::{
     peer_info::PeerId,
     transaction::TransactionInfo,
 };
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::Debug;
 use std::sync::Arc;
 use std::time::Instant;
@@ -102,12 +105,176 @@ static BLOCK_BODY_VERIFIER: fn(&HashValue, &BlockBody) -> bool =
 static BLOCK_INFO_VERIFIER: fn(&HashValue, &BlockInfo) -> bool =
     |block_id, block_info| -> bool { *block_id == block_info.block_id };
 
+/// Check the block body against the body hash of the block header.
+pub fn verify_block_body(peer_id: &PeerId, block: &Block) -> Result<()> {
+    let body_hash = block.body.hash();
+    let header_body_hash = block.header().body_hash();
+    if body_hash != header_body_hash {
+        return Err(format_err!(
+            "Peer {} return block {} with body hash {}, but the header body hash is {}",
+            peer_id,
+            block.id(),
+            body_hash,
+            header_body_hash
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+struct BlockFetchState {
+    inflight: HashMap<PeerId, u64>,
+    bad_peers: HashSet<PeerId>,
+    /// The fetches waiting for a peer below the in-flight limit.
+    waiters: VecDeque<oneshot::Sender<()>>,
+}
+
+impl BlockFetchState {
+    fn load(&self, peer_id: &PeerId) -> u64 {
+        self.inflight.get(peer_id).copied().unwrap_or_default()
+    }
+
+    /// Wake a waiting fetch, skip the fetches which are cancelled.
+    fn wake_one(&mut self) {
+        while let Some(waiter) = self.waiters.pop_front() {
+            if waiter.send(()).is_ok() {
+                break;
+            }
+        }
+    }
+}
+
+/// Track the in-flight block fetches per peer, and the peers blacklisted for returning bad blocks.
+#[derive(Clone)]
+pub struct BlockFetchTracker {
+    max_inflight_per_peer: u64,
+    state: Arc<Mutex<BlockFetchState>>,
+}
+
+impl BlockFetchTracker {
+    pub fn new(max_inflight_per_peer: u64) -> Self {
+        Self {
+            max_inflight_per_peer: std::cmp::max(max_inflight_per_peer, 1),
+            state: Arc::new(Mutex::new(BlockFetchState::default())),
+        }
+    }
+
+    pub fn max_inflight_per_peer(&self) -> u64 {
+        self.max_inflight_per_peer
+    }
+
+    /// Select a peer for fetching blocks, prefer the peer chosen by the selector strategy,
+    /// fallback to the least loaded peer if it reaches the in-flight limit,
+    /// and wait until a fetch finishes if every peer reaches the limit.
+    /// The fetch is in-flight until the returned guard is dropped.
+    pub async fn acquire(&self, peer_selector: &PeerSelector) -> Result<BlockFetchGuard> {
+        loop {
+            let selected = peer_selector
+                .select_peer()
+                .ok_or_else(|| format_err!("No peers for send request."))?;
+            let waiter = {
+                let mut state = self.state.lock();
+                let peer_id = if state.load(&selected) < self.max_inflight_per_peer {
+                    Some(selected)
+                } else {
+                    peer_selector
+                        .peers()
+                        .into_iter()
+                        .filter(|peer_id| state.load(peer_id) < self.max_inflight_per_peer)
+                        .min_by_key(|peer_id| state.load(peer_id))
+                };
+                if let Some(peer_id) = peer_id {
+                    *state.inflight.entry(peer_id.clone()).or_default() += 1;
+                    return Ok(BlockFetchGuard {
+                        peer_id,
+                        state: self.state.clone(),
+                    });
+                }
+                let (sender, receiver) = oneshot::channel();
+                state.waiters.push_back(sender);
+                receiver
+            };
+            // the sender is dropped without sending only if the tracker is dropped, select again anyway.
+            let _ = waiter.await;
+        }
+    }
+
+    /// Remove the peer from the selector, and never select it again in the sync task.
+    pub fn blacklist(&self, peer_selector: &PeerSelector, peer_id: &PeerId) {
+        self.state.lock().bad_peers.insert(peer_id.clone());
+        let peers = peer_selector.remove_peer(peer_id);
+        warn!(
+            "[sync] Blacklist peer {} for returning bad blocks, remaining peers: {}",
+            peer_id, peers
+        );
+    }
+
+    pub fn inflight(&self) -> u64 {
+        self.state.lock().inflight.values().sum()
+    }
+
+    pub fn inflight_peers(&self) -> Vec<(PeerId, u64)> {
+        self.state
+            .lock()
+            .inflight
+            .iter()
+            .filter(|(_, count)| **count > 0)
+            .map(|(peer_id, count)| (peer_id.clone(), *count))
+            .collect()
+    }
+
+    pub fn bad_peers(&self) -> Vec<PeerId> {
+        self.state.lock().bad_peers.iter().cloned().collect()
+    }
+}
+
+impl Debug for BlockFetchTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlockFetchTracker")
+            .field("max_inflight_per_peer", &self.max_inflight_per_peer)
+            .field("inflight", &self.inflight())
+            .finish()
+    }
+}
+
+impl Default for BlockFetchTracker {
+    fn default() -> Self {
+        Self::new(2)
+    }
+}
+
+/// A in-flight block fetch to the peer, release the slot of the peer when dropped.
+pub struct BlockFetchGuard {
+    peer_id: PeerId,
+    state: Arc<Mutex<BlockFetchState>>,
+}
+
+impl BlockFetchGuard {
+    pub fn peer_id(&self) -> &PeerId {
+        &self.peer_id
+    }
+}
+
+impl Drop for BlockFetchGuard {
+    fn drop(&mut self) {
+        let mut state = self.state.lock();
+        if let Some(count) = state.inflight.get_mut(&self.peer_id) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                state.inflight.remove(&self.peer_id);
+            }
+        }
+        state.wake_one();
+    }
+}
+
 /// Enhancement RpcClient, for verify rpc response by request and auto select peer.
 #[derive(Clone)]
 pub struct VerifiedRpcClient {
     peer_selector: PeerSelector,
     client: NetworkRpcClient,
     score_handler: Arc<dyn Score<u32> + 'static>,
+    fetch_tracker: BlockFetchTracker,
 }
 
 impl VerifiedRpcClient {
@@ -123,9 +290,19 @@ impl VerifiedRpcClient {
             peer_selector,
             client,
             score_handler: Arc::new(InverseScore::new(100, 60)),
+            fetch_tracker: BlockFetchTracker::default(),
         }
     }
 
+    pub fn with_max_inflight_per_peer(mut self, max_inflight_per_peer: u64) -> Self {
+        self.fetch_tracker = BlockFetchTracker::new(max_inflight_per_peer);
+        self
+    }
+
+    pub fn fetch_tracker(&self) -> &BlockFetchTracker {
+        &self.fetch_tracker
+    }
+
     pub fn selector(&self) -> &PeerSelector {
         &self.peer_selector
     }
@@ -388,7 +565,8 @@ impl VerifiedRpcClient {
         &self,
         ids: Vec<HashValue>,
     ) -> Result<Vec<Option<(Block, Option<PeerId>)>>> {
-        let peer_id = self.select_a_peer()?;
+        let guard = self.fetch_tracker.acquire(&self.peer_selector).await?;
+        let peer_id = guard.peer_id().clone();
         let timer = SYNC_SCORE_METRICS
             .peer_sync_per_time
             .with_label_values(&[&format!("peer-{:?}", peer_id)])
@@ -403,6 +581,15 @@ impl VerifiedRpcClient {
         let score = self.score(time);
         self.record(&peer_id, score);
         SYNC_SCORE_METRICS.update_metrics(peer_id.clone(), time, score);
+        drop(guard);
+        // a bad body can not be detected by the block id, do not use the peer any more,
+        // and fail the fetch with a retryable error to fetch the blocks from other peers.
+        for block in blocks.iter().flatten() {
+            if let Err(e) = verify_block_body(&peer_id, block) {
+                self.fetch_tracker.blacklist(&self.peer_selector, &peer_id);
+                return Err(e);
+            }
+        }
         Ok(ids
             .into_iter()
             .zip(blocks)
@@ -35,6 +35,8 @@ Feature: cmd integration test
     Then cmd: "node metrics"
     Then cmd: "node info"
     Then cmd: "node peers"
+    Then cmd: "node config show"
+    Then cmd: "node config reload"
     Then stop
 
     Examples:
@@ -17,14 +17,8 @@ pub fn steps() -> Steps<MyWorld> {
             .state_get_with_proof(access_path.clone())
             .unwrap();
         let state_root = client.clone().state_get_state_root().unwrap();
-        proof
-            .state_proof()
-            .verify(
-                state_root,
-                access_path,
-                proof.state.as_ref().map(|v| v.0.as_slice()),
-            )
-            .unwrap();
+        assert_eq!(proof.state_root, state_root);
+        proof.verify(&access_path).unwrap();
     });
     builder.build()
 }
@@ -23,12 +23,59 @@ pub struct TxPoolStatus {
     pub is_full: bool,
 }
 
+/// The txpool status with the readiness of the txns and the gas price floor to enter the pool.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TxPoolDetailedStatus {
+    /// The txns which are ready to be packaged.
+    pub pending: usize,
+    /// The txns which are waiting for the previous sequence numbers.
+    pub queued: usize,
+    pub senders: usize,
+    pub mem_usage_bytes: usize,
+    /// A regular txn whose gas price is lower than this is rejected,
+    /// it is raised when the pool is full.
+    pub min_gas_price_to_enter: u64,
+    /// The percent which gas price should be bumped at least to replace a pending txn.
+    pub gas_price_bump_percent: u64,
+}
+
+/// The gas prices suggested by the txns of the recent blocks, combined with the pool's entry floor.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GasPriceOracle {
+    /// The gas prices are sampled from the txns of the last so many blocks, the empty blocks are skipped.
+    pub last_n_blocks: u32,
+    /// The percentiles of the sampled gas prices, they are at least `pool_min_to_enter`.
+    pub p25: u64,
+    pub p50: u64,
+    pub p90: u64,
+    /// Same as `TxPoolDetailedStatus::min_gas_price_to_enter`.
+    pub pool_min_to_enter: u64,
+}
+
+/// The result of a rebroadcast of the pending txns.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TxnRebroadcastResult {
+    /// The count of the txns announced to the peers.
+    pub announced: usize,
+    /// The count of the connected peers when the txns are announced.
+    pub peers: usize,
+}
+
 pub trait TxPoolSyncService: Clone + Send + Sync + Unpin {
     fn add_txns(
         &self,
         txns: Vec<SignedUserTransaction>,
     ) -> Vec<Result<(), transaction::TransactionError>>;
 
+    /// Replace the pending txn which has the same sender and sequence number with `txn`,
+    /// the gas price of `txn` should be high enough to replace the old one.
+    /// Returns the hash of the replaced txn,
+    /// or `None` if there is no such pending txn and `txn` is just added.
+    fn replace_txn(
+        &self,
+        txn: SignedUserTransaction,
+    ) -> Result<Option<HashValue>, transaction::TransactionError>;
+
     /// Removes transaction from the pool.
     ///
     /// Attempts to "cancel" a transaction. If it was not propagated yet (or not accepted by other peers)
@@ -61,6 +108,17 @@ pub trait TxPoolSyncService: Clone + Send + Sync + Unpin {
     /// Tx Pool status
     fn status(&self) -> TxPoolStatus;
 
+    /// Tx Pool status with the txn readiness and the min gas price to enter,
+    /// it checks every txn in the pool, so it is more expensive than `status`.
+    fn detailed_status(&self) -> TxPoolDetailedStatus;
+
+    /// Same as `detailed_status`, but the result is reused for a second,
+    /// so the frequent callers such as the rpc do not walk the pool every time.
+    fn cached_detailed_status(&self) -> TxPoolDetailedStatus;
+
+    /// The gas price suggestions, the percentiles of the recent blocks are cached and refreshed on new head blocks.
+    fn gas_price_oracle(&self) -> GasPriceOracle;
+
     fn find_txn(&self, hash: &HashValue) -> Option<SignedUserTransaction>;
     fn txns_of_sender(
         &self,
@@ -4,7 +4,7 @@
 use anyhow::Result;
 use crypto::hash::HashValue;
 use futures_channel::mpsc;
-use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService};
+use starcoin_txpool_api::{GasPriceOracle, TxPoolDetailedStatus, TxPoolStatus, TxPoolSyncService};
 use std::{
     iter::Iterator,
     sync::{Arc, Mutex},
@@ -42,6 +42,26 @@ impl TxPoolSyncService for MockTxPoolService {
         results
     }
 
+    fn replace_txn(
+        &self,
+        txn: SignedUserTransaction,
+    ) -> Result<Option<HashValue>, transaction::TransactionError> {
+        let mut pool = self.pool.lock().unwrap();
+        let replaced = pool.iter().position(|t| {
+            t.sender() == txn.sender() && t.sequence_number() == txn.sequence_number()
+        });
+        match replaced {
+            Some(idx) => {
+                let old = std::mem::replace(&mut pool[idx], txn);
+                Ok(Some(old.id()))
+            }
+            None => {
+                pool.push(txn);
+                Ok(None)
+            }
+        }
+    }
+
     /// Removes transaction from the pool.
     ///
     /// Attempts to "cancel" a transaction. If it was not propagated yet (or not accepted by other peers)
@@ -90,6 +110,18 @@ impl TxPoolSyncService for MockTxPoolService {
         unimplemented!()
     }
 
+    fn detailed_status(&self) -> TxPoolDetailedStatus {
+        unimplemented!()
+    }
+
+    fn cached_detailed_status(&self) -> TxPoolDetailedStatus {
+        unimplemented!()
+    }
+
+    fn gas_price_oracle(&self) -> GasPriceOracle {
+        unimplemented!()
+    }
+
     fn find_txn(&self, _hash: &HashValue) -> Option<SignedUserTransaction> {
         unimplemented!()
     }
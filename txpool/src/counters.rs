@@ -1,5 +1,5 @@
 use once_cell::sync::Lazy;
-use prometheus::{HistogramOpts, HistogramVec, IntGauge, IntGaugeVec, Opts};
+use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts};
 
 pub static TXPOOL_TXNS_GAUGE: Lazy<IntGauge> = Lazy::new(|| {
     let opts =
@@ -17,3 +17,30 @@ pub static TXPOOL_SERVICE_HISTOGRAM: Lazy<HistogramVec> = Lazy::new(|| {
         HistogramOpts::new("txpool_service", "Histogram of txpool service").namespace("starcoin");
     register_histogram_vec!(opts, &["api"]).unwrap()
 });
+
+pub static TXPOOL_LOCAL_TXNS_GAUGE: Lazy<IntGauge> = Lazy::new(|| {
+    let opts = Opts::new(
+        "txpool_local_txns",
+        "Gauge of the txns submitted to this node and tracked for rebroadcast",
+    )
+    .namespace("starcoin");
+    register_int_gauge!(opts).unwrap()
+});
+
+pub static TXPOOL_REBROADCAST_COUNTER_VEC: Lazy<IntCounterVec> = Lazy::new(|| {
+    let opts = Opts::new(
+        "txpool_rebroadcast_txns",
+        "Counter of the txns rebroadcast to the peers",
+    )
+    .namespace("starcoin");
+    register_int_counter_vec!(opts, &["trigger"]).unwrap()
+});
+
+pub static TXPOOL_GAS_PRICE_P50_GAUGE: Lazy<IntGauge> = Lazy::new(|| {
+    let opts = Opts::new(
+        "txpool_gas_price_p50",
+        "Gauge of the median gas price of the txns in the recent blocks",
+    )
+    .namespace("starcoin");
+    register_int_gauge!(opts).unwrap()
+});
@@ -0,0 +1,220 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Suggest the gas prices by the txns of the recent blocks of the main chain.
+
+use crypto::hash::HashValue;
+use std::collections::VecDeque;
+use types::block::{Block, BlockNumber};
+
+/// The percentiles of the gas prices of the sampled txns.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct GasPricePercentiles {
+    pub p25: u64,
+    pub p50: u64,
+    pub p90: u64,
+}
+
+#[derive(Clone, Debug)]
+struct SampledBlock {
+    id: HashValue,
+    number: BlockNumber,
+    gas_prices: Vec<u64>,
+}
+
+/// Keep the gas prices of the non-empty blocks in the last `last_n_blocks` blocks,
+/// the percentiles are recomputed when the blocks change, so the queries are cheap.
+#[derive(Debug)]
+pub(crate) struct GasPriceSampler {
+    last_n_blocks: u64,
+    /// The blocks in the window, ordered by number.
+    blocks: VecDeque<SampledBlock>,
+    percentiles: Option<GasPricePercentiles>,
+}
+
+impl GasPriceSampler {
+    pub fn new(last_n_blocks: u64) -> Self {
+        Self {
+            last_n_blocks,
+            blocks: VecDeque::new(),
+            percentiles: None,
+        }
+    }
+
+    pub fn last_n_blocks(&self) -> u64 {
+        self.last_n_blocks
+    }
+
+    /// The cached percentiles, None if there is no txn in the window.
+    pub fn percentiles(&self) -> Option<GasPricePercentiles> {
+        self.percentiles
+    }
+
+    /// Apply the main chain change, the `enacted` blocks are ordered by number.
+    pub fn update(&mut self, enacted: &[Block], retracted: &[Block]) {
+        if !retracted.is_empty() {
+            self.blocks.retain(|sampled| {
+                !retracted
+                    .iter()
+                    .any(|block| block.header().id() == sampled.id)
+            });
+        }
+        let head_number = match enacted.last() {
+            Some(head) => head.header().number(),
+            None => return,
+        };
+        // the blocks not older than the window.
+        let from_number = head_number
+            .saturating_add(1)
+            .saturating_sub(self.last_n_blocks);
+        for block in enacted {
+            let number = block.header().number();
+            if number < from_number || block.transactions().is_empty() {
+                continue;
+            }
+            // the blocks at the same or higher height are replaced by the new chain.
+            while self
+                .blocks
+                .back()
+                .map(|last| last.number >= number)
+                .unwrap_or(false)
+            {
+                self.blocks.pop_back();
+            }
+            self.blocks.push_back(SampledBlock {
+                id: block.header().id(),
+                number,
+                gas_prices: block
+                    .transactions()
+                    .iter()
+                    .map(|txn| txn.gas_unit_price())
+                    .collect(),
+            });
+        }
+        while self
+            .blocks
+            .front()
+            .map(|first| first.number < from_number)
+            .unwrap_or(false)
+        {
+            self.blocks.pop_front();
+        }
+        self.percentiles = self.compute_percentiles();
+    }
+
+    fn compute_percentiles(&self) -> Option<GasPricePercentiles> {
+        let mut gas_prices: Vec<u64> = self
+            .blocks
+            .iter()
+            .flat_map(|sampled| sampled.gas_prices.iter().copied())
+            .collect();
+        if gas_prices.is_empty() {
+            return None;
+        }
+        gas_prices.sort_unstable();
+        Some(GasPricePercentiles {
+            p25: percentile(&gas_prices, 25),
+            p50: percentile(&gas_prices, 50),
+            p90: percentile(&gas_prices, 90),
+        })
+    }
+}
+
+/// The nearest-rank percentile of the sorted non-empty values.
+fn percentile(sorted: &[u64], percent: usize) -> u64 {
+    let rank = (sorted.len() * percent + 99) / 100;
+    sorted[rank.max(1) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto::keygen::KeyGen;
+    use types::account_address;
+    use types::block::{BlockBody, BlockHeaderBuilder};
+    use types::genesis_config::ChainId;
+    use types::transaction::{RawUserTransaction, Script, TransactionPayload};
+
+    fn new_block(number: BlockNumber, parent_hash: HashValue, gas_prices: &[u64]) -> Block {
+        let (private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
+        let sender = account_address::from_public_key(&public_key);
+        let txns = gas_prices
+            .iter()
+            .enumerate()
+            .map(|(seq, gas_price)| {
+                RawUserTransaction::new_with_default_gas_token(
+                    sender,
+                    seq as u64,
+                    TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+                    10_000,
+                    *gas_price,
+                    u64::max_value(),
+                    ChainId::test(),
+                )
+                .sign(&private_key, public_key.clone())
+                .unwrap()
+                .into_inner()
+            })
+            .collect();
+        let header = BlockHeaderBuilder::random()
+            .with_number(number)
+            .with_parent_hash(parent_hash)
+            .build();
+        Block::new(header, BlockBody::new(txns, None))
+    }
+
+    #[test]
+    fn test_gas_price_sampler() {
+        let mut sampler = GasPriceSampler::new(3);
+        assert!(sampler.percentiles().is_none());
+
+        let block1 = new_block(1, HashValue::zero(), &[1, 2, 3, 4]);
+        let block2 = new_block(2, block1.id(), &[]);
+        let block3 = new_block(3, block2.id(), &[5, 6, 7, 8, 9, 10]);
+        sampler.update(&[block1.clone(), block2.clone(), block3.clone()], &[]);
+        assert_eq!(
+            sampler.percentiles(),
+            Some(GasPricePercentiles {
+                p25: 3,
+                p50: 5,
+                p90: 9,
+            })
+        );
+
+        // the empty block is skipped, and the block1 leaves the window.
+        let block4 = new_block(4, block3.id(), &[]);
+        sampler.update(&[block4.clone()], &[]);
+        assert_eq!(
+            sampler.percentiles(),
+            Some(GasPricePercentiles {
+                p25: 6,
+                p50: 7,
+                p90: 10,
+            })
+        );
+
+        // the block3 and block4 are retracted by a fork.
+        let fork3 = new_block(3, block2.id(), &[100]);
+        let fork4 = new_block(4, fork3.id(), &[200]);
+        sampler.update(&[fork3, fork4], &[block3, block4]);
+        assert_eq!(
+            sampler.percentiles(),
+            Some(GasPricePercentiles {
+                p25: 100,
+                p50: 100,
+                p90: 200,
+            })
+        );
+
+        // all the blocks in the window are empty.
+        let mut parent = block2.id();
+        let mut empty_blocks = vec![];
+        for number in 5..8 {
+            let block = new_block(number, parent, &[]);
+            parent = block.id();
+            empty_blocks.push(block);
+        }
+        sampler.update(&empty_blocks, &[]);
+        assert!(sampler.percentiles().is_none());
+    }
+}
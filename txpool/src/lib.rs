@@ -10,28 +10,35 @@ extern crate trace_time;
 extern crate prometheus;
 extern crate transaction_pool as tx_pool;
 
-use anyhow::{format_err, Result};
-use counters::{TXPOOL_STATUS_GAUGE_VEC, TXPOOL_TXNS_GAUGE};
-use network_api::messages::PeerTransactionsMessage;
+use anyhow::{bail, format_err, Result};
+use counters::{TXPOOL_REBROADCAST_COUNTER_VEC, TXPOOL_STATUS_GAUGE_VEC, TXPOOL_TXNS_GAUGE};
+use crypto::hash::HashValue;
+use network_api::messages::{PeerEvent, PeerTransactionsMessage};
+use network_api::PeerId;
 pub use pool::TxStatus;
-use starcoin_config::NodeConfig;
-use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
+use starcoin_config::{NodeConfig, NodeConfigChangedEvent};
+use starcoin_service_registry::{
+    ActorService, EventHandler, ServiceContext, ServiceFactory, ServiceHandler, ServiceRequest,
+};
 use starcoin_state_api::AccountStateReader;
-use starcoin_txpool_api::{PropagateTransactions, TxnStatusFullEvent};
+use starcoin_txpool_api::{PropagateTransactions, TxnRebroadcastResult, TxnStatusFullEvent};
+use std::collections::HashSet;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use storage::{BlockStore, Storage};
 use tx_pool_service_impl::Inner;
-pub use tx_pool_service_impl::TxPoolService;
+pub use tx_pool_service_impl::{TxPoolService, PERSISTED_TXNS_FILE};
 use types::{
     sync_status::SyncStatus, system_events::SyncStatusChangeEvent,
     transaction::SignedUserTransaction,
 };
 
 mod counters;
+mod gas_price_oracle;
 mod pool;
 mod pool_client;
+mod rebroadcast;
 #[cfg(test)]
 mod test;
 mod tx_pool_service_impl;
@@ -41,6 +48,27 @@ pub struct TxPoolActorService {
     inner: Inner,
     new_txs_received: Arc<AtomicBool>,
     sync_status: Option<SyncStatus>,
+    peers: HashSet<PeerId>,
+    /// The start of the current rebroadcast interval, and the count of the txns announced by
+    /// the manual rebroadcasts in it, which is capped by `max_tx_rebroadcast_per_interval`.
+    manual_rebroadcast: (Instant, usize),
+}
+
+/// Rebroadcast the local pending txns whose backoff is due.
+#[derive(Clone, Debug)]
+struct RebroadcastTick;
+
+/// Announce the pending txns to the peers now,
+/// if `txn_hashes` is None, announce the pending txns submitted to this node.
+/// At most `max_tx_rebroadcast_per_interval` txns are announced by the requests every
+/// `tx_rebroadcast_interval`, the request fails if the quota of the interval is used up.
+#[derive(Clone, Debug)]
+pub struct RebroadcastTransactions {
+    pub txn_hashes: Option<Vec<HashValue>>,
+}
+
+impl ServiceRequest for RebroadcastTransactions {
+    type Response = Result<TxnRebroadcastResult>;
 }
 
 impl std::fmt::Debug for TxPoolActorService {
@@ -58,6 +86,8 @@ impl TxPoolActorService {
             inner,
             sync_status: None,
             new_txs_received: Arc::new(AtomicBool::new(false)),
+            peers: HashSet::new(),
+            manual_rebroadcast: (Instant::now(), 0),
         }
     }
 
@@ -110,7 +140,14 @@ impl ServiceFactory<Self> for TxPoolActorService {
                 })?;
 
             let best_block_header = best_block.into_inner().0;
-            Ok(TxPoolService::new(node_config, storage, best_block_header))
+            let txpool_service = TxPoolService::new(node_config, storage, best_block_header);
+            // re-import the txns persisted when the node shut down last time.
+            if let Err(e) =
+                txpool_service.load_persisted_txns(&txpool_service.persisted_txns_path())
+            {
+                warn!("Load persisted txns error: {:?}", e);
+            }
+            Ok(txpool_service)
         })?;
         Ok(Self::new(txpool_service.get_inner()))
     }
@@ -137,10 +174,42 @@ impl TxPoolActorService {
             }
         }
     }
+
+    /// Announce the txns in the pool to the peers, the txns not in the pool are skipped.
+    /// Nothing is announced if there is no peer, so the local txns keep their backoff.
+    fn rebroadcast_txns(
+        &self,
+        txn_hashes: Vec<HashValue>,
+        trigger: &str,
+        ctx: &mut ServiceContext<Self>,
+    ) -> TxnRebroadcastResult {
+        let peers = self.peers.len();
+        if peers == 0 || txn_hashes.is_empty() {
+            return TxnRebroadcastResult {
+                announced: 0,
+                peers,
+            };
+        }
+        let txns = self.inner.txns_to_rebroadcast(&txn_hashes);
+        let announced = txns.len();
+        if announced > 0 {
+            debug!(
+                "[txpool] Rebroadcast {} txns to {} peers, trigger: {}",
+                announced, peers, trigger
+            );
+            TXPOOL_REBROADCAST_COUNTER_VEC
+                .with_label_values(&[trigger])
+                .inc_by(announced as u64);
+            ctx.broadcast(PropagateTransactions::new(txns));
+        }
+        TxnRebroadcastResult { announced, peers }
+    }
 }
 impl ActorService for TxPoolActorService {
     fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.subscribe::<SyncStatusChangeEvent>();
+        ctx.subscribe::<NodeConfigChangedEvent>();
+        ctx.subscribe::<PeerEvent>();
         ctx.add_stream(self.inner.subscribe_txns());
 
         // every x seconds, we tick a txn propagation.
@@ -149,22 +218,98 @@ impl ActorService for TxPoolActorService {
         ctx.run_interval(Duration::from_secs(interval), move |ctx| {
             myself.try_propagate_txns(ctx)
         });
+        let rebroadcast_interval = self.inner.node_config.tx_pool.tx_rebroadcast_interval();
+        ctx.run_interval(Duration::from_secs(rebroadcast_interval), |ctx| {
+            ctx.notify(RebroadcastTick)
+        });
 
         Ok(())
     }
 
     fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.unsubscribe::<SyncStatusChangeEvent>();
+        ctx.unsubscribe::<NodeConfigChangedEvent>();
+        ctx.unsubscribe::<PeerEvent>();
         Ok(())
     }
 }
 
+impl EventHandler<Self, NodeConfigChangedEvent> for TxPoolActorService {
+    fn handle_event(&mut self, msg: NodeConfigChangedEvent, _ctx: &mut ServiceContext<Self>) {
+        if msg.is_changed("tx_pool") {
+            self.inner.update_pool_limits(&msg.config.tx_pool);
+        }
+    }
+}
+
 impl EventHandler<Self, SyncStatusChangeEvent> for TxPoolActorService {
     fn handle_event(&mut self, msg: SyncStatusChangeEvent, _ctx: &mut ServiceContext<Self>) {
         self.sync_status = Some(msg.0);
     }
 }
 
+impl EventHandler<Self, PeerEvent> for TxPoolActorService {
+    fn handle_event(&mut self, msg: PeerEvent, _ctx: &mut ServiceContext<Self>) {
+        match msg {
+            PeerEvent::Open(peer_id, _) => {
+                self.peers.insert(peer_id);
+            }
+            PeerEvent::Close(peer_id) => {
+                self.peers.remove(&peer_id);
+            }
+        }
+    }
+}
+
+impl EventHandler<Self, RebroadcastTick> for TxPoolActorService {
+    fn handle_event(&mut self, _msg: RebroadcastTick, ctx: &mut ServiceContext<Self>) {
+        if self.peers.is_empty() {
+            return;
+        }
+        let max_len = self
+            .inner
+            .node_config
+            .tx_pool
+            .max_tx_rebroadcast_per_interval() as usize;
+        let txn_hashes = self.inner.local_txns_to_rebroadcast(Some(max_len));
+        self.rebroadcast_txns(txn_hashes, "timer", ctx);
+    }
+}
+
+impl ServiceHandler<Self, RebroadcastTransactions> for TxPoolActorService {
+    fn handle(
+        &mut self,
+        msg: RebroadcastTransactions,
+        ctx: &mut ServiceContext<Self>,
+    ) -> Result<TxnRebroadcastResult> {
+        let pool_config = &self.inner.node_config.tx_pool;
+        let interval = Duration::from_secs(pool_config.tx_rebroadcast_interval());
+        let max_len = pool_config.max_tx_rebroadcast_per_interval() as usize;
+        let (interval_start, announced) = &mut self.manual_rebroadcast;
+        if interval_start.elapsed() >= interval {
+            *interval_start = Instant::now();
+            *announced = 0;
+        }
+        let remaining = max_len.saturating_sub(*announced);
+        if remaining == 0 {
+            bail!(
+                "At most {} txns can be rebroadcast every {} seconds, retry after {} seconds.",
+                max_len,
+                interval.as_secs(),
+                interval.saturating_sub(interval_start.elapsed()).as_secs() + 1
+            );
+        }
+        let mut txn_hashes = match msg.txn_hashes {
+            Some(txn_hashes) => txn_hashes,
+            None => self.inner.local_txns_to_rebroadcast(None),
+        };
+        txn_hashes.truncate(remaining);
+        let result = self.rebroadcast_txns(txn_hashes, "manual", ctx);
+        self.manual_rebroadcast.1 += result.announced;
+        Ok(result)
+    }
+}
+
 /// Listen to txn status, and propagate to remote peers if necessary.
 impl EventHandler<Self, TxnStatusFullEvent> for TxPoolActorService {
     fn handle_event(&mut self, item: TxnStatusFullEvent, _ctx: &mut ServiceContext<Self>) {
@@ -185,7 +330,7 @@ impl EventHandler<Self, TxnStatusFullEvent> for TxPoolActorService {
                 .set(txn_count as i64);
         }
         let mut has_new_txns = false;
-        for (_, s) in item.iter() {
+        for (txn_hash, s) in item.iter() {
             match *s {
                 TxStatus::Added => {
                     TXPOOL_TXNS_GAUGE.inc();
@@ -194,6 +339,7 @@ impl EventHandler<Self, TxnStatusFullEvent> for TxPoolActorService {
                 TxStatus::Rejected => {}
                 _ => {
                     TXPOOL_TXNS_GAUGE.dec();
+                    self.inner.untrack_local_txn(txn_hash);
                 }
             }
         }
@@ -3,7 +3,7 @@
 
 use std::{fmt, sync::Arc};
 
-use super::{TxStatus, VerifiedTransaction as Transaction};
+use super::{ScoredTransaction, TxStatus, VerifiedTransaction as Transaction};
 use crypto::hash::HashValue as H256;
 use futures_channel::mpsc;
 use transaction_pool as tx_pool;
@@ -27,7 +27,11 @@ impl tx_pool::Listener<Transaction> for Logger {
             data = tx.signed().payload(),
         );
         if let Some(old) = old {
-            debug!(target: "txqueue", "[{:?}] Dropped. Replaced by [{:?}]", old.hash(), tx.hash());
+            if is_replaced(old, tx) {
+                debug!(target: "txqueue", "[{:?}] Dropped. Replaced by [{:?}]", old.hash(), tx.hash());
+            } else {
+                debug!(target: "txqueue", "[{:?}] Evicted. Pushed out by [{:?}]", old.hash(), tx.hash());
+            }
         }
     }
 
@@ -61,6 +65,20 @@ impl tx_pool::Listener<Transaction> for Logger {
     }
 }
 
+/// The `old` txn is replaced by the `new` one with the same sequence number,
+/// otherwise it is pushed out because the sender reaches the `max_per_sender` limit.
+fn is_replaced(old: &Transaction, new: &Transaction) -> bool {
+    old.seq_number() == new.seq_number()
+}
+
+/// The txn dropped with a new txn is evicted by the new one because the pool is full.
+fn dropped_status(new: Option<&Transaction>) -> TxStatus {
+    match new {
+        Some(_) => TxStatus::Evicted,
+        None => TxStatus::Dropped,
+    }
+}
+
 /// Transaction status logger.
 #[derive(Default, Debug)]
 pub struct StatusLogger;
@@ -78,7 +96,12 @@ impl tx_pool::Listener<Transaction> for StatusLogger {
     fn added(&mut self, tx: &Arc<Transaction>, old: Option<&Arc<Transaction>>) {
         Self::log_status(tx, TxStatus::Added);
         if let Some(old) = old {
-            Self::log_status(old, TxStatus::Dropped);
+            let status = if is_replaced(old, tx) {
+                TxStatus::Dropped
+            } else {
+                TxStatus::Evicted
+            };
+            Self::log_status(old, status);
         }
     }
 
@@ -90,8 +113,8 @@ impl tx_pool::Listener<Transaction> for StatusLogger {
         Self::log_status(tx, TxStatus::Rejected);
     }
 
-    fn dropped(&mut self, tx: &Arc<Transaction>, _new: Option<&Transaction>) {
-        Self::log_status(tx, TxStatus::Dropped);
+    fn dropped(&mut self, tx: &Arc<Transaction>, new: Option<&Transaction>) {
+        Self::log_status(tx, dropped_status(new));
     }
 
     fn invalid(&mut self, tx: &Arc<Transaction>) {
@@ -132,15 +155,18 @@ impl TransactionsPoolNotifier {
             return;
         }
 
+        // only the added txns are new pending txns.
         let to_pending_send: Arc<[H256]> = self
             .tx_statuses
-            .clone()
-            .into_iter()
-            .map(|(hash, _)| hash)
+            .iter()
+            .filter(|(_, status)| *status == TxStatus::Added)
+            .map(|(hash, _)| *hash)
             .collect::<Vec<_>>()
             .into();
-        self.pending_listeners
-            .retain(|listener| listener.unbounded_send(to_pending_send.clone()).is_ok());
+        if !to_pending_send.is_empty() {
+            self.pending_listeners
+                .retain(|listener| listener.unbounded_send(to_pending_send.clone()).is_ok());
+        }
 
         let to_full_send: Arc<[(H256, TxStatus)]> =
             std::mem::replace(&mut self.tx_statuses, Vec::new()).into();
@@ -159,8 +185,17 @@ impl fmt::Debug for TransactionsPoolNotifier {
 }
 
 impl tx_pool::Listener<Transaction> for TransactionsPoolNotifier {
-    fn added(&mut self, tx: &Arc<Transaction>, _old: Option<&Arc<Transaction>>) {
+    fn added(&mut self, tx: &Arc<Transaction>, old: Option<&Arc<Transaction>>) {
         self.tx_statuses.push((tx.hash, TxStatus::Added));
+        // the old txn is replaced by the new one, or pushed out by the sender limit.
+        if let Some(old) = old {
+            let status = if is_replaced(old, tx) {
+                TxStatus::Dropped
+            } else {
+                TxStatus::Evicted
+            };
+            self.tx_statuses.push((old.hash, status));
+        }
     }
 
     fn rejected<H: fmt::Debug + fmt::LowerHex>(
@@ -171,8 +206,8 @@ impl tx_pool::Listener<Transaction> for TransactionsPoolNotifier {
         self.tx_statuses.push((tx.hash, TxStatus::Rejected));
     }
 
-    fn dropped(&mut self, tx: &Arc<Transaction>, _new: Option<&Transaction>) {
-        self.tx_statuses.push((tx.hash, TxStatus::Dropped));
+    fn dropped(&mut self, tx: &Arc<Transaction>, new: Option<&Transaction>) {
+        self.tx_statuses.push((tx.hash, dropped_status(new)));
     }
 
     fn invalid(&mut self, tx: &Arc<Transaction>) {
@@ -33,6 +33,47 @@ fn should_notify_listeners() {
     assert_eq!(pending_res, Some(vec![*tx.hash()].into()));
 }
 
+#[test]
+fn should_notify_replaced_txn_dropped() {
+    let (full_sender, mut full_receiver) = mpsc::unbounded();
+    let mut tx_listener = TransactionsPoolNotifier::default();
+    tx_listener.add_full_listener(full_sender);
+
+    let old_tx = new_tx();
+    let tx = new_tx();
+    tx_listener.added(&tx, Some(&old_tx));
+    tx_listener.notify();
+    let full_res = full_receiver.try_next().unwrap();
+    assert_eq!(
+        full_res,
+        Some(
+            vec![
+                (*tx.hash(), TxStatus::Added),
+                (*old_tx.hash(), TxStatus::Dropped)
+            ]
+            .into()
+        )
+    );
+}
+
+#[test]
+fn should_notify_evicted_txn() {
+    let (full_sender, mut full_receiver) = mpsc::unbounded();
+    let mut tx_listener = TransactionsPoolNotifier::default();
+    tx_listener.add_full_listener(full_sender);
+
+    // pushed out by a txn of another sender when the pool is full.
+    let old_tx = new_tx();
+    let tx = new_tx();
+    tx_listener.dropped(&old_tx, Some(&tx));
+    tx_listener.notify();
+    let full_res = full_receiver.try_next().unwrap();
+    assert_eq!(
+        full_res,
+        Some(vec![(*old_tx.hash(), TxStatus::Evicted)].into())
+    );
+}
+
 #[test]
 fn test_notify() {
     // given
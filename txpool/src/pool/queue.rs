@@ -5,7 +5,8 @@
 
 use super::{
     client, listener, local_transactions::LocalTransactionsList, ready, replace, scoring, verifier,
-    PendingOrdering, PendingSettings, PrioritizationStrategy, SeqNumber, TxStatus,
+    GasPrice, PendingOrdering, PendingSettings, PrioritizationStrategy, Priority,
+    ScoredTransaction, SeqNumber, TxStatus,
 };
 use crate::pool::ready::Expiration;
 use crate::{pool, pool::PoolTransaction};
@@ -22,7 +23,7 @@ use std::{
         Arc,
     },
 };
-use tx_pool::{self, Verifier};
+use tx_pool::{self, Listener as _, Verifier};
 use types::{account_address::AccountAddress as Address, transaction};
 
 type Listener = (
@@ -231,13 +232,14 @@ impl TransactionQueue {
         limits: tx_pool::Options,
         verification_options: verifier::Options,
         strategy: PrioritizationStrategy,
+        gas_price_bump_percent: u64,
     ) -> Self {
         let max_count = limits.max_count;
         TransactionQueue {
             insertion_id: Default::default(),
             pool: RwLock::new(tx_pool::Pool::new(
                 Default::default(),
-                scoring::SeqNumberAndGasPrice(strategy),
+                scoring::SeqNumberAndGasPrice::new(strategy, gas_price_bump_percent),
                 limits,
             )),
             options: RwLock::new(verification_options),
@@ -256,6 +258,51 @@ impl TransactionQueue {
         *self.options.write() = options;
     }
 
+    /// Update the pool limits without recreating the queue.
+    ///
+    /// The transactions are moved to a new pool with the limits,
+    /// the ones exceed the new limits are dropped.
+    pub fn set_limits<C>(&self, client: C, limits: tx_pool::Options)
+    where
+        C: client::AccountSeqNumberClient,
+    {
+        let mut pool = self.pool.write();
+        let old_limits = pool.options();
+        if old_limits.max_count == limits.max_count
+            && old_limits.max_per_sender == limits.max_per_sender
+            && old_limits.max_mem_usage == limits.max_mem_usage
+        {
+            return;
+        }
+        let scoring = pool.scoring().clone();
+        let replace = replace::ReplaceByScoreAndReadiness::new(scoring.clone(), client);
+        let mut new_pool = Pool::new(Default::default(), scoring, limits);
+        // always ready
+        let txns = pool
+            .unordered_pending(Expiration::new(0))
+            .collect::<Vec<_>>();
+        for txn in &txns {
+            // the rejected txns are checked below.
+            let _ = new_pool.import(txn.as_ref().clone(), &replace);
+        }
+        // the txns are rejected by the new pool, or pushed out by the others when moving.
+        let dropped = txns
+            .into_iter()
+            .filter(|txn| new_pool.find(&txn.hash).is_none())
+            .collect::<Vec<_>>();
+        // keep the listeners, they are not notified about the moved transactions,
+        // but about the dropped ones.
+        std::mem::swap(new_pool.listener_mut(), pool.listener_mut());
+        *pool = new_pool;
+        for txn in &dropped {
+            pool.listener_mut().dropped(txn, None);
+        }
+        (pool.listener_mut().1).0.notify();
+        drop(pool);
+        self.cached_pending.write().clear();
+        info!(target: "txqueue", "Pool limits updated, {} transactions dropped.", dropped.len());
+    }
+
     /// Sets the in-chain transaction checker for pool listener.
     pub fn set_in_chain_checker<F>(&self, f: F)
     where
@@ -279,19 +326,22 @@ impl TransactionQueue {
     {
         // Run verification
         trace_time!("pool::verify_and_import");
-        let options = self.options.read().clone();
+        let mut options = self.options.read().clone();
 
         let transaction_to_replace = {
-            if options.no_early_reject {
+            let pool = self.pool.read();
+            let is_full = pool.is_full();
+            if is_full {
+                // raise the gas price floor when the pool is full,
+                // so the low gas price txns can not push out each other.
+                options.min_gas_price =
+                    cmp::max(options.min_gas_price, options.min_gas_price_under_pressure);
+            }
+            if options.no_early_reject || !is_full {
                 None
             } else {
-                let pool = self.pool.write();
-                if pool.is_full() {
-                    pool.worst_transaction()
-                        .map(|worst| (pool.scoring().clone(), worst))
-                } else {
-                    None
-                }
+                pool.worst_transaction()
+                    .map(|worst| (pool.scoring().clone(), worst))
             }
         };
 
@@ -359,6 +409,13 @@ impl TransactionQueue {
             .collect()
     }
 
+    /// Returns all transactions in the pool, regardless of their readiness.
+    pub fn all_transactions(&self) -> Vec<Arc<pool::VerifiedTransaction>> {
+        // always ready
+        let ready = Expiration::new(0);
+        self.pool.read().unordered_pending(ready).collect()
+    }
+
     /// Returns current pending transactions ordered by priority.
     ///
     /// NOTE: This may return a cached version of pending transaction set.
@@ -514,11 +571,49 @@ impl TransactionQueue {
     where
         C: client::AccountSeqNumberClient,
     {
-        let ready = Self::ready(client, block_number, current_timestamp);
-        let status = self.pool.read().status(ready);
+        let status = self.readiness_status(client, block_number, current_timestamp);
         debug!("txpool queue inner status: {:?}", status);
     }
 
+    /// Returns the count of the txns which are ready to be packaged (pending),
+    /// the ones waiting for the previous sequence numbers (future),
+    /// and the stalled ones which are going to be culled.
+    ///
+    /// NOTE: It checks the readiness of every txn in the pool, which is expensive.
+    pub fn readiness_status<C>(
+        &self,
+        client: C,
+        block_number: u64,
+        current_timestamp: u64,
+    ) -> tx_pool::Status
+    where
+        C: client::AccountSeqNumberClient,
+    {
+        let ready = Self::ready(client, block_number, current_timestamp);
+        self.pool.read().status(ready)
+    }
+
+    /// Returns the min gas price a regular txn should pay to enter the pool now.
+    ///
+    /// When the pool is full, the txn must outbid the worst txn in the pool to evict it,
+    /// the worst txn is always the last one of its sender, so no sender's sequence is broken.
+    /// Returns `GasPrice::MAX` if the worst txn is a local or retracted one, which can not be evicted.
+    pub fn min_gas_price_to_enter(&self) -> GasPrice {
+        let options = self.options.read().clone();
+        let pool = self.pool.read();
+        if !pool.is_full() {
+            return options.min_gas_price;
+        }
+        let floor = cmp::max(options.min_gas_price, options.min_gas_price_under_pressure);
+        match pool.worst_transaction() {
+            Some(worst) if worst.priority() == Priority::Regular => {
+                cmp::max(floor, worst.gas_price().saturating_add(1))
+            }
+            Some(_) => GasPrice::MAX,
+            None => floor,
+        }
+    }
+
     /// Returns a status of the queue.
     pub fn status(&self) -> Status {
         let pool = &self.pool.read();
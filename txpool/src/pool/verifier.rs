@@ -22,6 +22,8 @@ pub struct Options {
     pub no_early_reject: bool,
     /// reject txn whose gas_price is lower than this.
     pub min_gas_price: u64,
+    /// reject txn whose gas_price is lower than this when the pool is full.
+    pub min_gas_price_under_pressure: u64,
 }
 
 #[cfg(test)]
@@ -30,6 +32,7 @@ impl Default for Options {
         Options {
             no_early_reject: false,
             min_gas_price: 0,
+            min_gas_price_under_pressure: 0,
         }
     }
 }
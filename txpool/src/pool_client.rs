@@ -9,7 +9,9 @@ use types::{
     account_address::AccountAddress,
     block::BlockHeader,
     transaction,
-    transaction::{CallError, SignedUserTransaction, TransactionError},
+    transaction::{
+        signature_verifier::SignatureVerifier, CallError, SignedUserTransaction, TransactionError,
+    },
 };
 
 /// Cache for state nonces.
@@ -147,9 +149,9 @@ impl crate::pool::Client for PoolClient {
         tx: UnverifiedUserTransaction,
     ) -> Result<transaction::SignatureCheckedTransaction, transaction::TransactionError> {
         let txn = SignedUserTransaction::from(tx);
-        let checked_txn = txn
-            .clone()
-            .check_signature()
+        // the verified txn is cached, so the signature is not verified again by the vm.
+        let checked_txn = SignatureVerifier::global()
+            .check_signature(txn.clone())
             .map_err(|e| TransactionError::InvalidSignature(e.to_string()))?;
         match starcoin_executor::validate_transaction(self.nonce_client.statedb.as_ref(), txn) {
             None => Ok(checked_txn),
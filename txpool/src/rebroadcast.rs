@@ -0,0 +1,136 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Track the local txns, so the pending ones can be re-announced to the peers,
+//! such as the txns submitted when the node has no peer.
+
+use crypto::hash::HashValue;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// The wait between two announcements of a txn is doubled at most so many times.
+const MAX_BACKOFF_SHIFT: u32 = 6;
+
+#[derive(Clone, Debug)]
+struct LocalTxn {
+    submitted_at: Instant,
+    next_announce_at: Instant,
+    announcements: u32,
+}
+
+/// The local txns are the ones submitted to this node, not the ones received from the peers.
+#[derive(Debug)]
+pub(crate) struct RebroadcastTracker {
+    rebroadcast_after: Duration,
+    txns: HashMap<HashValue, LocalTxn>,
+}
+
+impl RebroadcastTracker {
+    pub fn new(rebroadcast_after: Duration) -> Self {
+        Self {
+            rebroadcast_after,
+            txns: HashMap::new(),
+        }
+    }
+
+    pub fn add_local(&mut self, txn_hash: HashValue, now: Instant) {
+        let rebroadcast_after = self.rebroadcast_after;
+        self.txns.entry(txn_hash).or_insert_with(|| LocalTxn {
+            submitted_at: now,
+            next_announce_at: now + rebroadcast_after,
+            announcements: 0,
+        });
+    }
+
+    pub fn remove(&mut self, txn_hash: &HashValue) {
+        self.txns.remove(txn_hash);
+    }
+
+    pub fn len(&self) -> usize {
+        self.txns.len()
+    }
+
+    /// All the local txns, the oldest first.
+    pub fn local_txns(&self) -> Vec<HashValue> {
+        let mut txns: Vec<_> = self.txns.iter().collect();
+        txns.sort_by_key(|(_, txn)| txn.submitted_at);
+        txns.into_iter().map(|(txn_hash, _)| *txn_hash).collect()
+    }
+
+    /// The local txns whose next announcement is due at `now`, the oldest first, at most `max_len`.
+    pub fn due(&self, now: Instant, max_len: usize) -> Vec<HashValue> {
+        let mut txns: Vec<_> = self
+            .txns
+            .iter()
+            .filter(|(_, txn)| txn.next_announce_at <= now)
+            .collect();
+        txns.sort_by_key(|(_, txn)| txn.submitted_at);
+        txns.into_iter()
+            .take(max_len)
+            .map(|(txn_hash, _)| *txn_hash)
+            .collect()
+    }
+
+    /// Record an announcement of the txn, the wait for the next one is doubled.
+    pub fn mark_announced(&mut self, txn_hash: &HashValue, now: Instant) {
+        let rebroadcast_after = self.rebroadcast_after;
+        if let Some(txn) = self.txns.get_mut(txn_hash) {
+            txn.announcements = txn.announcements.saturating_add(1);
+            txn.next_announce_at =
+                now + rebroadcast_after * (1 << txn.announcements.min(MAX_BACKOFF_SHIFT));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rebroadcast_backoff() {
+        let after = Duration::from_secs(10);
+        let mut tracker = RebroadcastTracker::new(after);
+        let start = Instant::now();
+        let txn1 = HashValue::random();
+        let txn2 = HashValue::random();
+        tracker.add_local(txn1, start);
+        tracker.add_local(txn2, start + Duration::from_secs(1));
+        // add again does not reset the txn.
+        tracker.add_local(txn1, start + Duration::from_secs(5));
+
+        assert!(tracker.due(start + Duration::from_secs(9), 10).is_empty());
+        assert_eq!(tracker.due(start + after, 10), vec![txn1]);
+        assert_eq!(
+            tracker.due(start + Duration::from_secs(11), 10),
+            vec![txn1, txn2]
+        );
+        assert_eq!(tracker.due(start + Duration::from_secs(11), 1), vec![txn1]);
+
+        let now = start + Duration::from_secs(11);
+        tracker.mark_announced(&txn1, now);
+        assert_eq!(tracker.due(now, 10), vec![txn2]);
+        // the wait is doubled after every announcement.
+        assert!(!tracker
+            .due(now + after * 2 - Duration::from_secs(1), 10)
+            .contains(&txn1));
+        assert!(tracker.due(now + after * 2, 10).contains(&txn1));
+        let now = now + after * 2;
+        tracker.mark_announced(&txn1, now);
+        assert!(!tracker
+            .due(now + after * 4 - Duration::from_secs(1), 10)
+            .contains(&txn1));
+        assert!(tracker.due(now + after * 4, 10).contains(&txn1));
+
+        // the backoff is capped.
+        for _ in 0..100 {
+            tracker.mark_announced(&txn1, now);
+        }
+        assert!(tracker
+            .due(now + after * (1 << MAX_BACKOFF_SHIFT), 10)
+            .contains(&txn1));
+
+        tracker.remove(&txn1);
+        assert_eq!(tracker.local_txns(), vec![txn2]);
+        assert_eq!(tracker.len(), 1);
+    }
+}
@@ -2,10 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::pool::AccountSeqNumberClient;
-use crate::TxStatus;
+use crate::{RebroadcastTransactions, TxPoolService, TxStatus};
 use anyhow::Result;
+use crypto::hash::HashValue;
 use crypto::keygen::KeyGen;
-use network_api::messages::{PeerTransactionsMessage, TransactionsMessage};
+use futures::StreamExt;
+use network_api::messages::{PeerEvent, PeerTransactionsMessage, TransactionsMessage};
 use network_api::PeerId;
 use parking_lot::RwLock;
 use starcoin_config::NodeConfig;
@@ -14,9 +16,13 @@ use starcoin_executor::{
     DEFAULT_EXPIRATION_TIME, DEFAULT_MAX_GAS_AMOUNT,
 };
 use starcoin_open_block::OpenedBlock;
+use starcoin_service_registry::bus::{Bus, BusService};
+use starcoin_service_registry::RegistryAsyncService;
 use starcoin_state_api::ChainStateWriter;
 use starcoin_statedb::ChainStateDB;
-use starcoin_txpool_api::{TxPoolSyncService, TxnStatusFullEvent};
+use starcoin_txpool_api::{
+    PropagateTransactions, TxPoolSyncService, TxnRebroadcastResult, TxnStatusFullEvent,
+};
 use std::time::Duration;
 use std::{collections::HashMap, sync::Arc};
 use stest::actix_export::time::delay_for;
@@ -24,6 +30,7 @@ use storage::BlockStore;
 use types::{
     account_address::{self, AccountAddress},
     account_config,
+    startup_info::ChainInfo,
     transaction::authenticator::AuthenticationKey,
     transaction::{SignedUserTransaction, Transaction, TransactionPayload},
     U256,
@@ -95,6 +102,84 @@ async fn test_tx_pool() -> Result<()> {
     Ok(())
 }
 
+#[stest::test]
+async fn test_replace_txn() -> Result<()> {
+    let (txpool_service, _storage, config, _, _) = test_helper::start_txpool().await;
+    let txn = generate_txn_with_gas_price(config.clone(), 0, 10);
+    let txn_hash = txn.id();
+    assert_eq!(txpool_service.replace_txn(txn)?, None);
+
+    // the gas price is not bumped enough.
+    let txn = generate_txn_with_gas_price(config.clone(), 0, 10);
+    assert!(txpool_service.replace_txn(txn).is_err());
+
+    let mut receiver = txpool_service.subscribe_txns();
+    let txn = generate_txn_with_gas_price(config, 0, 11);
+    let new_txn_hash = txn.id();
+    assert_eq!(txpool_service.replace_txn(txn)?, Some(txn_hash));
+    let pending_txns = txpool_service.get_pending_txns(None, Some(0));
+    assert_eq!(pending_txns.len(), 1);
+    assert_eq!(pending_txns[0].id(), new_txn_hash);
+    let statuses = receiver.next().await.unwrap();
+    assert!(statuses.contains(&(txn_hash, TxStatus::Dropped)));
+    Ok(())
+}
+
+#[stest::test]
+async fn test_drop_txns_by_pool_limits() -> Result<()> {
+    let (txpool_service, _storage, config, _, _) = test_helper::start_txpool().await;
+    for seq in 0..3 {
+        let txn = generate_txn(config.clone(), seq);
+        txpool_service.add_txns(vec![txn]).pop().unwrap()?;
+    }
+    let mut receiver = txpool_service.subscribe_txns();
+    let mut pool_config = config.tx_pool.clone();
+    pool_config.set_max_count(1);
+    txpool_service.get_inner().update_pool_limits(&pool_config);
+    assert_eq!(txpool_service.get_pending_txns(None, Some(0)).len(), 1);
+    // the txns exceed the new limits are notified as dropped.
+    let statuses = receiver.next().await.unwrap();
+    assert_eq!(
+        statuses
+            .iter()
+            .filter(|(_, status)| *status == TxStatus::Dropped)
+            .count(),
+        2
+    );
+    Ok(())
+}
+
+#[stest::test]
+async fn test_min_gas_price_to_enter() -> Result<()> {
+    let (txpool_service, _storage, config, _, _) = test_helper::start_txpool_with_size(2).await;
+    let status = txpool_service.detailed_status();
+    assert_eq!(
+        status.min_gas_price_to_enter,
+        config.tx_pool.min_gas_price()
+    );
+    for (seq, gas_price) in vec![(0, 10), (1, 5)] {
+        let txn = generate_txn_with_gas_price(config.clone(), seq, gas_price);
+        txpool_service.add_txns(vec![txn]).pop().unwrap()?;
+    }
+    // the pool is full, a new txn should outbid the last txn of the sender.
+    let status = txpool_service.detailed_status();
+    assert_eq!(status.senders, 1);
+    assert_eq!(status.min_gas_price_to_enter, 6);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_cached_detailed_status() -> Result<()> {
+    let (txpool_service, _storage, config, _, _) = test_helper::start_txpool().await;
+    assert_eq!(txpool_service.cached_detailed_status().pending, 0);
+    let txn = generate_txn(config, 0);
+    txpool_service.add_txns(vec![txn]).pop().unwrap()?;
+    assert_eq!(txpool_service.detailed_status().pending, 1);
+    // the pool is not walked again until the cache expires.
+    assert_eq!(txpool_service.cached_detailed_status().pending, 0);
+    Ok(())
+}
+
 #[stest::test]
 async fn test_subscribe_txns() {
     let (pool, ..) = test_helper::start_txpool().await;
@@ -250,7 +335,189 @@ async fn test_txpool_actor_service() {
     delay_for(Duration::from_millis(300)).await;
 }
 
+#[stest::test(timeout = 480)]
+async fn test_persist_txns() -> Result<()> {
+    let mut config = NodeConfig::random_for_test();
+    config.tx_pool.set_max_per_sender(1000);
+    let (pool, storage, config, _, _) = test_helper::start_txpool_with_config(config).await;
+    // the txns from 900 expire before the next block.
+    let txns: Vec<_> = (0..1000u64)
+        .map(|seq| {
+            let expiration = if seq < 900 {
+                DEFAULT_EXPIRATION_TIME
+            } else {
+                2
+            };
+            generate_txn_with_expiration(config.clone(), seq, 1, expiration)
+        })
+        .collect();
+    for result in pool.add_txns(txns.clone()) {
+        result?;
+    }
+    let path = pool.persisted_txns_path();
+    assert_eq!(pool.persist_txns(&path)?, 1000);
+
+    // new txns are rejected after the pool is closed.
+    pool.close();
+    assert_eq!(
+        pool.add_txns(vec![generate_txn(config.clone(), 1000)]),
+        vec![Err(types::transaction::TransactionError::ShuttingDown)]
+    );
+
+    // the first 10 txns are packed into the new head block, so their sequence numbers are stale.
+    let main = storage.get_startup_info()?.unwrap().main;
+    let parent_header = storage.get_block_header_by_hash(main)?.unwrap();
+    let (_private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
+    let mut open_block = OpenedBlock::new(
+        storage.clone(),
+        parent_header.clone(),
+        u64::MAX,
+        account_address::from_public_key(&public_key),
+        Some(AuthenticationKey::ed25519(&public_key)),
+        60 * 10 * 1000,
+        vec![],
+        U256::from(1024u64),
+        config.net().genesis_config().consensus(),
+    )?;
+    let excluded_txns = open_block.push_txns(txns[0..10].to_vec())?;
+    assert!(excluded_txns.discarded_txns.is_empty());
+    let block = open_block
+        .finalize()?
+        .into_block(0, types::block::BlockHeaderExtra::new([0u8; 4]));
+    let chain_state = ChainStateDB::new(storage.clone(), Some(parent_header.state_root()));
+    let mut block_txns: Vec<_> = block
+        .transactions()
+        .iter()
+        .map(|t| Transaction::UserTransaction(t.clone()))
+        .collect();
+    block_txns.insert(
+        0,
+        Transaction::BlockMetadata(block.to_metadata(parent_header.gas_used())),
+    );
+    let root = starcoin_executor::block_execute(&chain_state, block_txns, u64::MAX)?.state_root;
+    assert_eq!(root, block.header().state_root());
+    chain_state.flush()?;
+
+    // restart the pool on the new head block.
+    let new_pool = TxPoolService::new(config, storage, block.header().clone());
+    assert_eq!(new_pool.load_persisted_txns(&path)?, 890);
+    assert!(!path.exists());
+    for (seq, txn) in txns.iter().enumerate() {
+        let found = new_pool.find_txn(&txn.id()).is_some();
+        assert_eq!(found, (10..900).contains(&seq), "txn {}", seq);
+    }
+    Ok(())
+}
+
+#[stest::test]
+async fn test_rebroadcast_local_txns() -> Result<()> {
+    let (txpool_service, _storage, config, tx_pool_actor, registry) =
+        test_helper::start_txpool().await;
+    let bus = registry.service_ref::<BusService>().await?;
+    let mut propagated = bus.channel::<PropagateTransactions>().await?;
+    let txn = generate_txn(config, 0);
+    txpool_service.add_txns(vec![txn.clone()]).pop().unwrap()?;
+
+    // nothing is announced without peers.
+    let result = tx_pool_actor
+        .send(RebroadcastTransactions { txn_hashes: None })
+        .await??;
+    assert_eq!(
+        result,
+        TxnRebroadcastResult {
+            announced: 0,
+            peers: 0
+        }
+    );
+
+    tx_pool_actor.notify(PeerEvent::Open(
+        PeerId::random(),
+        Box::new(ChainInfo::random()),
+    ))?;
+    let result = tx_pool_actor
+        .send(RebroadcastTransactions { txn_hashes: None })
+        .await??;
+    assert_eq!(
+        result,
+        TxnRebroadcastResult {
+            announced: 1,
+            peers: 1
+        }
+    );
+    let msg = propagated.next().await.unwrap();
+    assert!(msg
+        .transaction_to_propagate()
+        .iter()
+        .any(|t| t.id() == txn.id()));
+
+    // the txns not in the pool are skipped.
+    let result = tx_pool_actor
+        .send(RebroadcastTransactions {
+            txn_hashes: Some(vec![HashValue::random()]),
+        })
+        .await??;
+    assert_eq!(result.announced, 0);
+
+    // the txn left the pool is not rebroadcast any more.
+    txpool_service.remove_txn(txn.id(), false);
+    delay_for(Duration::from_millis(200)).await;
+    let result = tx_pool_actor
+        .send(RebroadcastTransactions { txn_hashes: None })
+        .await??;
+    assert_eq!(result.announced, 0);
+    Ok(())
+}
+
+#[stest::test]
+async fn test_rebroadcast_quota() -> Result<()> {
+    let mut config = NodeConfig::random_for_test();
+    config.tx_pool.set_max_tx_rebroadcast_per_interval(2);
+    let (txpool_service, _storage, config, tx_pool_actor, _registry) =
+        test_helper::start_txpool_with_config(config).await;
+    let txns = (0..3)
+        .map(|seq| generate_txn(config.clone(), seq))
+        .collect::<Vec<_>>();
+    for result in txpool_service.add_txns(txns.clone()) {
+        result?;
+    }
+    tx_pool_actor.notify(PeerEvent::Open(
+        PeerId::random(),
+        Box::new(ChainInfo::random()),
+    ))?;
+
+    // the request is capped by the quota of the interval.
+    let result = tx_pool_actor
+        .send(RebroadcastTransactions {
+            txn_hashes: Some(txns.iter().map(|txn| txn.id()).collect()),
+        })
+        .await??;
+    assert_eq!(result.announced, 2);
+    // the quota is used up until the next interval.
+    assert!(tx_pool_actor
+        .send(RebroadcastTransactions { txn_hashes: None })
+        .await?
+        .is_err());
+    Ok(())
+}
+
 fn generate_txn(config: Arc<NodeConfig>, seq: u64) -> SignedUserTransaction {
+    generate_txn_with_gas_price(config, seq, 1)
+}
+
+fn generate_txn_with_gas_price(
+    config: Arc<NodeConfig>,
+    seq: u64,
+    gas_price: u64,
+) -> SignedUserTransaction {
+    generate_txn_with_expiration(config, seq, gas_price, 2)
+}
+
+fn generate_txn_with_expiration(
+    config: Arc<NodeConfig>,
+    seq: u64,
+    gas_price: u64,
+    expiration_timestamp_secs: u64,
+) -> SignedUserTransaction {
     let (_private_key, public_key) = KeyGen::from_os_rng().generate_keypair();
     let account_address = account_address::from_public_key(&public_key);
     let txn = create_signed_txn_with_association_account(
@@ -262,8 +529,8 @@ fn generate_txn(config: Arc<NodeConfig>, seq: u64) -> SignedUserTransaction {
         )),
         seq,
         DEFAULT_MAX_GAS_AMOUNT,
-        1,
-        2,
+        gas_price,
+        expiration_timestamp_secs,
         config.net(),
     );
     txn
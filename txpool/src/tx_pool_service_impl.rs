@@ -2,25 +2,31 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    counters::TXPOOL_SERVICE_HISTOGRAM,
+    counters::{TXPOOL_GAS_PRICE_P50_GAUGE, TXPOOL_LOCAL_TXNS_GAUGE, TXPOOL_SERVICE_HISTOGRAM},
+    gas_price_oracle::GasPriceSampler,
     pool,
     pool::{
         PendingOrdering, PendingSettings, PoolTransaction, PrioritizationStrategy, Status,
         TxStatus, UnverifiedUserTransaction, VerifiedTransaction,
     },
     pool_client::{NonceCache, PoolClient},
+    rebroadcast::RebroadcastTracker,
 };
 
 use crate::pool::{Client, TransactionQueue};
 use anyhow::Result;
+use bcs_ext::BCSCodec;
 use crypto::hash::HashValue;
 use futures_channel::mpsc;
-use parking_lot::RwLock;
-use starcoin_config::NodeConfig;
+use parking_lot::{Mutex, RwLock};
+use starcoin_config::{NodeConfig, TxPoolConfig};
 use starcoin_statedb::ChainStateDB;
-use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService};
+use starcoin_txpool_api::{GasPriceOracle, TxPoolDetailedStatus, TxPoolStatus, TxPoolSyncService};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use storage::Store;
+use std::time::{Duration, Instant};
+use storage::{BlockStore, Store};
 use types::{
     account_address::AccountAddress,
     block::{Block, BlockHeader},
@@ -28,6 +34,11 @@ use types::{
     transaction::SignedUserTransaction,
 };
 
+/// The file under the data dir which keeps the pool txns across restarts.
+pub const PERSISTED_TXNS_FILE: &str = "txpool_txns.bcs";
+/// How long the detailed status is reused by `cached_detailed_status`.
+const DETAILED_STATUS_CACHE_TTL: Duration = Duration::from_secs(1);
+
 #[derive(Clone, Debug)]
 pub struct TxPoolService {
     inner: Inner,
@@ -42,6 +53,7 @@ impl TxPoolService {
         let verifier_options = pool::VerifierOptions {
             no_early_reject: false,
             min_gas_price: node_config.tx_pool.min_gas_price(),
+            min_gas_price_under_pressure: node_config.tx_pool.min_gas_price_under_pressure(),
         };
         let queue = TxnQueue::new(
             tx_pool::Options {
@@ -51,15 +63,26 @@ impl TxPoolService {
             },
             verifier_options,
             PrioritizationStrategy::GasPriceOnly,
+            pool_config.gas_price_bump_percent(),
         );
         let queue = Arc::new(queue);
+        let local_txns =
+            RebroadcastTracker::new(Duration::from_secs(pool_config.tx_rebroadcast_after()));
+        let gas_price_sampler = GasPriceSampler::new(pool_config.gas_price_oracle_blocks());
         let inner = Inner {
             node_config,
             queue,
             storage,
             chain_header: Arc::new(RwLock::new(chain_header)),
             sequence_number_cache: NonceCache::new(128),
+            closed: Arc::new(AtomicBool::new(false)),
+            local_txns: Arc::new(Mutex::new(local_txns)),
+            gas_price_sampler: Arc::new(RwLock::new(gas_price_sampler)),
+            detailed_status_cache: Arc::new(Mutex::new(None)),
         };
+        if let Err(e) = inner.load_recent_blocks() {
+            warn!("Load recent blocks for gas price oracle error: {:?}", e);
+        }
 
         Self { inner }
     }
@@ -83,6 +106,55 @@ impl TxPoolService {
             .get_pool_client()
             .verify_transaction(tx.into())
     }
+
+    /// Reject the new txns with `TransactionError::ShuttingDown`, it is called when the node is shutting down.
+    pub fn close(&self) {
+        self.inner.closed.store(true, Ordering::SeqCst);
+    }
+
+    pub fn persisted_txns_path(&self) -> PathBuf {
+        self.inner.node_config.data_dir().join(PERSISTED_TXNS_FILE)
+    }
+
+    /// Save all txns in the pool to `path` in bcs, return the count of the saved txns.
+    pub fn persist_txns(&self, path: &Path) -> Result<usize> {
+        let txns: Vec<SignedUserTransaction> = self
+            .inner
+            .queue
+            .all_transactions()
+            .into_iter()
+            .map(|t| t.signed().clone())
+            .collect();
+        // write to a temp file first, so a crash can not leave a partial file.
+        let temp_path = path.with_extension("tmp");
+        std::fs::write(&temp_path, txns.encode()?)?;
+        std::fs::rename(&temp_path, path)?;
+        Ok(txns.len())
+    }
+
+    /// Import the txns saved by `persist_txns` and remove the file, the txns which are expired or
+    /// whose sequence number is stale are dropped. Return the count of the imported txns.
+    pub fn load_persisted_txns(&self, path: &Path) -> Result<usize> {
+        if !path.exists() {
+            return Ok(0);
+        }
+        let txns = Vec::<SignedUserTransaction>::decode(std::fs::read(path)?.as_slice())?;
+        let total = txns.len();
+        let imported = self
+            .inner
+            .import_txns(txns)
+            .into_iter()
+            .filter(|result| result.is_ok())
+            .count();
+        std::fs::remove_file(path)?;
+        info!(
+            "Load persisted txns from {:?}, imported: {}, dropped: {}",
+            path,
+            imported,
+            total - imported
+        );
+        Ok(imported)
+    }
 }
 
 impl TxPoolSyncService for TxPoolService {
@@ -95,7 +167,29 @@ impl TxPoolSyncService for TxPoolService {
         let _timer = TXPOOL_SERVICE_HISTOGRAM
             .with_label_values(&["add_txns"])
             .start_timer();
-        self.inner.import_txns(txns)
+        let txn_hashes: Vec<HashValue> = txns.iter().map(|txn| txn.id()).collect();
+        let results = self.inner.import_txns(txns);
+        self.inner.track_local_txns(
+            txn_hashes
+                .into_iter()
+                .zip(results.iter())
+                .filter(|(_, result)| result.is_ok())
+                .map(|(txn_hash, _)| txn_hash),
+        );
+        results
+    }
+
+    fn replace_txn(
+        &self,
+        txn: SignedUserTransaction,
+    ) -> Result<Option<HashValue>, transaction::TransactionError> {
+        let _timer = TXPOOL_SERVICE_HISTOGRAM
+            .with_label_values(&["replace_txn"])
+            .start_timer();
+        let txn_hash = txn.id();
+        let replaced = self.inner.replace_txn(txn)?;
+        self.inner.track_local_txns(vec![txn_hash]);
+        Ok(replaced)
     }
 
     fn remove_txn(&self, txn_hash: HashValue, is_invalid: bool) -> Option<SignedUserTransaction> {
@@ -161,6 +255,21 @@ impl TxPoolSyncService for TxPoolService {
         self.inner.queue.status().into()
     }
 
+    fn detailed_status(&self) -> TxPoolDetailedStatus {
+        let _timer = TXPOOL_SERVICE_HISTOGRAM
+            .with_label_values(&["detailed_status"])
+            .start_timer();
+        self.inner.detailed_status()
+    }
+
+    fn cached_detailed_status(&self) -> TxPoolDetailedStatus {
+        self.inner.cached_detailed_status()
+    }
+
+    fn gas_price_oracle(&self) -> GasPriceOracle {
+        self.inner.gas_price_oracle()
+    }
+
     fn find_txn(&self, hash: &HashValue) -> Option<SignedUserTransaction> {
         self.inner
             .queue
@@ -189,6 +298,14 @@ pub(crate) struct Inner {
     chain_header: Arc<RwLock<BlockHeader>>,
     storage: Arc<dyn Store>,
     sequence_number_cache: NonceCache,
+    /// Reject the new txns if true.
+    closed: Arc<AtomicBool>,
+    /// The txns submitted to this node, they are rebroadcast until leave the pool.
+    local_txns: Arc<Mutex<RebroadcastTracker>>,
+    /// The gas prices of the recent blocks, for the gas price oracle.
+    gas_price_sampler: Arc<RwLock<GasPriceSampler>>,
+    /// The last detailed status and when it is computed.
+    detailed_status_cache: Arc<Mutex<Option<(Instant, TxPoolDetailedStatus)>>>,
 }
 impl std::fmt::Debug for Inner {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -208,6 +325,20 @@ impl Inner {
         self.queue.status()
     }
 
+    pub(crate) fn update_pool_limits(&self, pool_config: &TxPoolConfig) {
+        let mut options = self.queue.status().options;
+        options.min_gas_price_under_pressure = pool_config.min_gas_price_under_pressure();
+        self.queue.set_verifier_options(options);
+        self.queue.set_limits(
+            self.get_pool_client(),
+            tx_pool::Options {
+                max_count: pool_config.max_count() as usize,
+                max_mem_usage: pool_config.max_mem_usage() as usize,
+                max_per_sender: pool_config.max_per_sender() as usize,
+            },
+        )
+    }
+
     pub(crate) fn notify_new_chain_header(&self, header: BlockHeader) {
         *self.chain_header.write() = header;
         self.sequence_number_cache.clear();
@@ -236,11 +367,35 @@ impl Inner {
         &self,
         txns: Vec<transaction::SignedUserTransaction>,
     ) -> Vec<Result<(), transaction::TransactionError>> {
+        if self.closed.load(Ordering::SeqCst) {
+            return txns
+                .iter()
+                .map(|_| Err(transaction::TransactionError::ShuttingDown))
+                .collect();
+        }
         let txns = txns
             .into_iter()
             .map(|t| PoolTransaction::Unverified(UnverifiedUserTransaction::from(t)));
         self.queue.import(self.get_pool_client(), txns)
     }
+    pub(crate) fn replace_txn(
+        &self,
+        txn: SignedUserTransaction,
+    ) -> Result<Option<HashValue>, transaction::TransactionError> {
+        let replaced = self
+            .queue
+            .txns_of_sender(&txn.sender(), usize::max_value())
+            .into_iter()
+            .find(|t| t.signed().sequence_number() == txn.sequence_number())
+            .map(|t| t.signed().id());
+        if replaced == Some(txn.id()) {
+            return Err(transaction::TransactionError::AlreadyImported);
+        }
+        self.import_txns(vec![txn])
+            .pop()
+            .expect("txpool should return result")?;
+        Ok(replaced)
+    }
     pub(crate) fn remove_txn(
         &self,
         txn_hash: HashValue,
@@ -269,6 +424,131 @@ impl Inner {
         );
         self.queue.pending(self.get_pool_client(), pending_settings)
     }
+    /// Track the txns submitted to this node, so they can be rebroadcast.
+    pub(crate) fn track_local_txns(&self, txn_hashes: impl IntoIterator<Item = HashValue>) {
+        let now = Instant::now();
+        let mut local_txns = self.local_txns.lock();
+        for txn_hash in txn_hashes {
+            local_txns.add_local(txn_hash, now);
+        }
+        TXPOOL_LOCAL_TXNS_GAUGE.set(local_txns.len() as i64);
+    }
+    pub(crate) fn untrack_local_txn(&self, txn_hash: &HashValue) {
+        let mut local_txns = self.local_txns.lock();
+        local_txns.remove(txn_hash);
+        TXPOOL_LOCAL_TXNS_GAUGE.set(local_txns.len() as i64);
+    }
+    /// The local txns to rebroadcast, the oldest first.
+    /// If `max_len` is None, return all the local txns regardless of their backoff.
+    pub(crate) fn local_txns_to_rebroadcast(&self, max_len: Option<usize>) -> Vec<HashValue> {
+        let local_txns = self.local_txns.lock();
+        match max_len {
+            Some(max_len) => local_txns.due(Instant::now(), max_len),
+            None => local_txns.local_txns(),
+        }
+    }
+    /// Get the txns in the pool to rebroadcast, the local txns which are not in the pool are untracked,
+    /// and the returned local txns are marked as announced.
+    pub(crate) fn txns_to_rebroadcast(
+        &self,
+        txn_hashes: &[HashValue],
+    ) -> Vec<SignedUserTransaction> {
+        let now = Instant::now();
+        let mut local_txns = self.local_txns.lock();
+        let txns = txn_hashes
+            .iter()
+            .filter_map(|txn_hash| match self.queue.find(txn_hash) {
+                Some(txn) => {
+                    local_txns.mark_announced(txn_hash, now);
+                    Some(txn.signed().clone())
+                }
+                None => {
+                    local_txns.remove(txn_hash);
+                    None
+                }
+            })
+            .collect();
+        TXPOOL_LOCAL_TXNS_GAUGE.set(local_txns.len() as i64);
+        txns
+    }
+    pub(crate) fn detailed_status(&self) -> TxPoolDetailedStatus {
+        let now_seconds = self.node_config.net().time_service().now_secs();
+        let readiness =
+            self.queue
+                .readiness_status(self.get_pool_client(), u64::max_value(), now_seconds);
+        let status = self.queue.status().status;
+        TxPoolDetailedStatus {
+            pending: readiness.pending,
+            queued: readiness.future,
+            senders: status.senders,
+            mem_usage_bytes: status.mem_usage,
+            min_gas_price_to_enter: self.queue.min_gas_price_to_enter(),
+            gas_price_bump_percent: self.node_config.tx_pool.gas_price_bump_percent(),
+        }
+    }
+    /// Reuse the detailed status computed in `DETAILED_STATUS_CACHE_TTL`, the lock is held while computing,
+    /// so the concurrent callers walk the pool only once.
+    pub(crate) fn cached_detailed_status(&self) -> TxPoolDetailedStatus {
+        let mut cache = self.detailed_status_cache.lock();
+        if let Some((computed_at, status)) = cache.as_ref() {
+            if computed_at.elapsed() < DETAILED_STATUS_CACHE_TTL {
+                return status.clone();
+            }
+        }
+        let status = self.detailed_status();
+        *cache = Some((Instant::now(), status.clone()));
+        status
+    }
+    /// Suggest the gas prices by the cached percentiles of the recent blocks, they are at least the
+    /// min gas price to enter the pool, and are the min gas price if the recent blocks have no txn.
+    pub(crate) fn gas_price_oracle(&self) -> GasPriceOracle {
+        let pool_min_to_enter = self.queue.min_gas_price_to_enter();
+        let sampler = self.gas_price_sampler.read();
+        let percentiles = sampler.percentiles();
+        let suggest = |gas_price: Option<u64>| {
+            gas_price
+                .unwrap_or(pool_min_to_enter)
+                .max(pool_min_to_enter)
+        };
+        GasPriceOracle {
+            last_n_blocks: sampler.last_n_blocks() as u32,
+            p25: suggest(percentiles.map(|p| p.p25)),
+            p50: suggest(percentiles.map(|p| p.p50)),
+            p90: suggest(percentiles.map(|p| p.p90)),
+            pool_min_to_enter,
+        }
+    }
+    /// Sample the gas prices of the recent blocks of the main chain when the pool is created,
+    /// the later blocks are sampled by `chain_new_block`.
+    fn load_recent_blocks(&self) -> Result<()> {
+        let last_n_blocks = self.gas_price_sampler.read().last_n_blocks();
+        let mut blocks = vec![];
+        let mut block_id = self.get_chain_header().id();
+        while (blocks.len() as u64) < last_n_blocks {
+            let block = match self.storage.get_block_by_hash(block_id)? {
+                Some(block) => block,
+                None => break,
+            };
+            let number = block.header().number();
+            block_id = block.header().parent_hash();
+            blocks.push(block);
+            if number == 0 {
+                break;
+            }
+        }
+        blocks.reverse();
+        self.update_gas_price_sampler(&blocks, &[]);
+        Ok(())
+    }
+    fn update_gas_price_sampler(&self, enacted: &[Block], retracted: &[Block]) {
+        let mut sampler = self.gas_price_sampler.write();
+        sampler.update(enacted, retracted);
+        let p50 = sampler
+            .percentiles()
+            .map(|p| p.p50)
+            .unwrap_or_else(|| self.queue.min_gas_price_to_enter());
+        TXPOOL_GAS_PRICE_P50_GAUGE.set(p50 as i64);
+    }
     pub(crate) fn next_sequence_number(&self, address: AccountAddress) -> Option<u64> {
         self.queue
             .next_sequence_number(self.get_pool_client(), &address)
@@ -302,6 +582,7 @@ impl Inner {
         if let Some(block) = enacted.last() {
             self.notify_new_chain_header(block.header().clone());
         }
+        self.update_gas_price_sampler(&enacted, &retracted);
 
         // remove outdated txns.
         self.cull();
@@ -271,6 +271,7 @@ impl BlockHeader {
         timestamp: u64,
         txn_accumulator_root: HashValue,
         state_root: HashValue,
+        gas_used: u64,
         difficulty: U256,
         body_hash: HashValue,
         chain_id: ChainId,
@@ -284,7 +285,7 @@ impl BlockHeader {
             txn_accumulator_root,
             *ACCUMULATOR_PLACEHOLDER_HASH,
             state_root,
-            0,
+            gas_used,
             difficulty,
             body_hash,
             chain_id,
@@ -657,21 +658,28 @@ impl Block {
         (self.header, self.body)
     }
 
+    /// The genesis block executes the `genesis_txn` first, then the `txns`, such as the txns funding the genesis accounts.
+    /// The genesis txn consumes no gas, so `gas_used` is zero if `txns` is empty.
     pub fn genesis_block(
         parent_hash: HashValue,
         timestamp: u64,
         accumulator_root: HashValue,
         state_root: HashValue,
+        gas_used: u64,
         difficulty: U256,
         genesis_txn: SignedUserTransaction,
+        txns: Vec<SignedUserTransaction>,
     ) -> Self {
         let chain_id = genesis_txn.chain_id();
-        let block_body = BlockBody::new(vec![genesis_txn], None);
+        let mut block_txns = vec![genesis_txn];
+        block_txns.extend(txns);
+        let block_body = BlockBody::new(block_txns, None);
         let header = BlockHeader::genesis_block_header(
             parent_hash,
             timestamp,
             accumulator_root,
             state_root,
+            gas_used,
             difficulty,
             block_body.hash(),
             chain_id,
@@ -1058,3 +1066,39 @@ impl EpochUncleSummary {
         }
     }
 }
+
+/// The reward of a main chain block, read from the `BlockRewardEvent` which is emitted
+/// when the reward is distributed `reward_delay` blocks later.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BlockReward {
+    pub block_number: BlockNumber,
+    pub block_hash: HashValue,
+    pub miner: AccountAddress,
+    /// The minted reward, it is the base reward plus the uncle inclusion reward.
+    pub block_reward: u128,
+    /// The reward per block of the epoch the block belongs to.
+    pub base_reward: u128,
+    /// The gas fees of the txns in the block.
+    pub txn_fees: u128,
+    pub gas_used: u64,
+}
+
+impl BlockReward {
+    pub fn uncle_inclusion_reward(&self) -> u128 {
+        self.block_reward.saturating_sub(self.base_reward)
+    }
+
+    pub fn total_reward(&self) -> u128 {
+        self.block_reward.saturating_add(self.txn_fees)
+    }
+}
+
+/// A branch head tracked by the chain service.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BranchInfo {
+    pub head: BlockHeader,
+    pub total_difficulty: U256,
+    /// The count of the blocks from the common ancestor with the main chain to the head,
+    /// 0 for the main chain.
+    pub length_from_common_ancestor: u64,
+}
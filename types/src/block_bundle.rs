@@ -0,0 +1,97 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The block bundle is a self-contained BCS file of a block, with the state the block reads,
+//! so the block can be replayed offline, such as reproducing a VM bug of a remote node locally.
+
+use crate::access_path::AccessPath;
+use crate::block::Block;
+use crate::transaction::{Transaction, TransactionOutput};
+use anyhow::{ensure, format_err, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use std::convert::TryInto;
+
+/// The version of the bundle schema, bump it when the schema changes.
+pub const BLOCK_BUNDLE_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BlockBundle {
+    /// Keep it the first field, so the version can be checked before decoding the others.
+    version: u32,
+    pub block: Block,
+    /// The txns of the block in the execution order, the block metadata txn is the first.
+    pub transactions: Vec<Transaction>,
+    /// None if the bundle is exported without the state.
+    pub state: Option<BlockBundleState>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BlockBundleState {
+    /// The state root before the block, which is the state root of the parent block.
+    pub pre_state_root: HashValue,
+    /// The state entries before the block which are read by executing the block,
+    /// sorted by the access path, the value is None if the entry does not exist.
+    pub read_set: Vec<(AccessPath, Option<Vec<u8>>)>,
+    /// The outputs of the txns recorded when exporting, in the order of the txns.
+    pub outputs: Vec<TransactionOutput>,
+}
+
+impl BlockBundle {
+    pub fn new(
+        block: Block,
+        transactions: Vec<Transaction>,
+        state: Option<BlockBundleState>,
+    ) -> Self {
+        Self {
+            version: BLOCK_BUNDLE_VERSION,
+            block,
+            transactions,
+            state,
+        }
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        bcs_ext::to_bytes(self)
+    }
+
+    /// Decode the bundle, the bundle of another schema version is rejected.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        // the version is the first field, bcs encodes the u32 as 4 little endian bytes.
+        let version = bytes
+            .get(0..4)
+            .and_then(|version| version.try_into().ok())
+            .map(u32::from_le_bytes)
+            .ok_or_else(|| format_err!("Invalid block bundle, too short"))?;
+        ensure!(
+            version == BLOCK_BUNDLE_VERSION,
+            "Unsupported block bundle version {}, expect version {}",
+            version,
+            BLOCK_BUNDLE_VERSION
+        );
+        bcs_ext::from_bytes(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::{BlockBody, BlockHeader};
+
+    #[test]
+    fn test_block_bundle_version() {
+        let block = Block::new(BlockHeader::random(), BlockBody::new(vec![], None));
+        let transactions = vec![Transaction::BlockMetadata(block.to_metadata(0))];
+        let bundle = BlockBundle::new(block, transactions, None);
+        let mut bytes = bundle.encode().unwrap();
+        assert_eq!(BlockBundle::decode(bytes.as_slice()).unwrap(), bundle);
+
+        bytes[0] = bytes[0].wrapping_add(1);
+        assert!(BlockBundle::decode(bytes.as_slice()).is_err());
+        assert!(BlockBundle::decode(&[1, 0]).is_err());
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The checkpoints pin the hash and the state root of the main chain blocks at some numbers,
+//! the headers at or below a checkpoint are authenticated by the hash chain, so the initial sync
+//! can skip their PoW verification.
+
+use crate::block::{BlockHeader, BlockNumber};
+use crate::genesis_config::ChainId;
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature};
+use starcoin_crypto::hash::{CryptoHash, CryptoHasher};
+use starcoin_crypto::{HashValue, Signature, SigningKey};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub number: BlockNumber,
+    pub block_hash: HashValue,
+    pub state_root: HashValue,
+}
+
+impl Checkpoint {
+    pub fn new(number: BlockNumber, block_hash: HashValue, state_root: HashValue) -> Self {
+        Self {
+            number,
+            block_hash,
+            state_root,
+        }
+    }
+
+    /// Return true if the main chain block header at the checkpoint number matches the checkpoint.
+    pub fn matches(&self, header: &BlockHeader) -> bool {
+        header.number() == self.number
+            && header.id() == self.block_hash
+            && header.state_root() == self.state_root
+    }
+}
+
+/// The checkpoints of a chain, sorted by the block number.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, CryptoHasher, CryptoHash)]
+pub struct CheckpointSet {
+    chain_id: ChainId,
+    checkpoints: Vec<Checkpoint>,
+}
+
+impl CheckpointSet {
+    pub fn new(chain_id: ChainId, mut checkpoints: Vec<Checkpoint>) -> Result<Self> {
+        checkpoints.sort_by_key(|checkpoint| checkpoint.number);
+        for pair in checkpoints.windows(2) {
+            ensure!(
+                pair[0].number != pair[1].number,
+                "Duplicate checkpoint at block number {}",
+                pair[0].number
+            );
+        }
+        Ok(Self {
+            chain_id,
+            checkpoints,
+        })
+    }
+
+    pub fn chain_id(&self) -> ChainId {
+        self.chain_id
+    }
+
+    pub fn checkpoints(&self) -> &[Checkpoint] {
+        self.checkpoints.as_slice()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.checkpoints.is_empty()
+    }
+
+    pub fn latest(&self) -> Option<&Checkpoint> {
+        self.checkpoints.last()
+    }
+
+    pub fn get(&self, number: BlockNumber) -> Option<&Checkpoint> {
+        self.checkpoints
+            .binary_search_by_key(&number, |checkpoint| checkpoint.number)
+            .ok()
+            .map(|idx| &self.checkpoints[idx])
+    }
+
+    /// The checkpoints whose number is in `[start, end]`.
+    pub fn range(&self, start: BlockNumber, end: BlockNumber) -> &[Checkpoint] {
+        let from = match self
+            .checkpoints
+            .binary_search_by_key(&start, |checkpoint| checkpoint.number)
+        {
+            Ok(idx) | Err(idx) => idx,
+        };
+        let to = match self
+            .checkpoints
+            .binary_search_by_key(&end, |checkpoint| checkpoint.number)
+        {
+            Ok(idx) => idx.saturating_add(1),
+            Err(idx) => idx,
+        };
+        if from >= to {
+            &[]
+        } else {
+            &self.checkpoints[from..to]
+        }
+    }
+
+    pub fn sign(self, private_key: &Ed25519PrivateKey) -> SignedCheckpointSet {
+        let signature = private_key.sign(&self);
+        SignedCheckpointSet {
+            checkpoints: self,
+            public_key: Ed25519PublicKey::from(private_key),
+            signature,
+        }
+    }
+}
+
+/// The checkpoints file content, the checkpoints signed by the checkpoints signer.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SignedCheckpointSet {
+    checkpoints: CheckpointSet,
+    public_key: Ed25519PublicKey,
+    signature: Ed25519Signature,
+}
+
+impl SignedCheckpointSet {
+    pub fn public_key(&self) -> &Ed25519PublicKey {
+        &self.public_key
+    }
+
+    /// Verify the checkpoints are signed by the trusted `signer` for the chain `chain_id`.
+    pub fn verify(self, signer: &Ed25519PublicKey, chain_id: ChainId) -> Result<CheckpointSet> {
+        ensure!(
+            &self.public_key == signer,
+            "The checkpoints are signed by {}, not the trusted signer {}",
+            hex::encode(self.public_key.to_bytes()),
+            hex::encode(signer.to_bytes())
+        );
+        self.signature.verify(&self.checkpoints, &self.public_key)?;
+        ensure!(
+            self.checkpoints.chain_id == chain_id,
+            "The checkpoints are for chain {}, not the chain {}",
+            self.checkpoints.chain_id,
+            chain_id
+        );
+        // the deserialized checkpoints may be unsorted.
+        CheckpointSet::new(self.checkpoints.chain_id, self.checkpoints.checkpoints)
+    }
+}
+
+/// A checkpoint which does not match the local main chain block.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CheckpointMismatch {
+    pub checkpoint: Checkpoint,
+    /// The hash of the local main chain block at the checkpoint number.
+    pub block_hash: HashValue,
+    /// The state root of the local main chain block at the checkpoint number.
+    pub state_root: HashValue,
+}
+
+impl CheckpointMismatch {
+    /// Return the mismatch if the main chain block header does not match the checkpoint.
+    pub fn check(checkpoint: &Checkpoint, header: &BlockHeader) -> Option<Self> {
+        if checkpoint.matches(header) {
+            None
+        } else {
+            Some(Self {
+                checkpoint: *checkpoint,
+                block_hash: header.id(),
+                state_root: header.state_root(),
+            })
+        }
+    }
+}
+
+impl std::fmt::Display for CheckpointMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "block {:#x} with state root {:#x} mismatches the checkpoint at number {}, expect block {:#x} with state root {:#x}",
+            self.block_hash,
+            self.state_root,
+            self.checkpoint.number,
+            self.checkpoint.block_hash,
+            self.checkpoint.state_root
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_crypto::ed25519::genesis_key_pair;
+    use starcoin_crypto::keygen::KeyGen;
+
+    #[test]
+    fn test_signed_checkpoints() {
+        let chain_id = ChainId::test();
+        let checkpoints: Vec<Checkpoint> = [300, 100, 200]
+            .iter()
+            .map(|number| Checkpoint::new(*number, HashValue::random(), HashValue::random()))
+            .collect();
+        let checkpoint_set = CheckpointSet::new(chain_id, checkpoints.clone()).unwrap();
+        assert_eq!(checkpoint_set.latest().unwrap().number, 300);
+        assert_eq!(checkpoint_set.get(200), Some(&checkpoints[2]));
+        assert!(checkpoint_set.get(250).is_none());
+        let range: Vec<_> = checkpoint_set
+            .range(100, 250)
+            .iter()
+            .map(|checkpoint| checkpoint.number)
+            .collect();
+        assert_eq!(range, vec![100, 200]);
+        assert!(checkpoint_set.range(301, 400).is_empty());
+        let mut duplicated = checkpoints;
+        duplicated.push(duplicated[0]);
+        assert!(CheckpointSet::new(chain_id, duplicated).is_err());
+
+        let (private_key, public_key) = genesis_key_pair();
+        let signed = checkpoint_set.clone().sign(&private_key);
+        let json = serde_json::to_string(&signed).unwrap();
+        let signed: SignedCheckpointSet = serde_json::from_str(json.as_str()).unwrap();
+        assert_eq!(
+            signed.clone().verify(&public_key, chain_id).unwrap(),
+            checkpoint_set
+        );
+        assert!(signed
+            .clone()
+            .verify(&public_key, ChainId::new(254))
+            .is_err());
+        let (_, other_key) = KeyGen::from_os_rng().generate_keypair();
+        assert!(signed.verify(&other_key, chain_id).is_err());
+    }
+}
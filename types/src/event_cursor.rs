@@ -0,0 +1,61 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Event cursors persisted by the node for the indexers.
+
+use crate::filter::{EventPosition, Filter};
+use serde::{Deserialize, Serialize};
+
+pub type EventCursorId = u64;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EventCursor {
+    pub id: EventCursorId,
+    pub filter: Filter,
+    /// Decode the event data when polling.
+    pub decode: bool,
+    /// The position of the last acked event, the events are replayed after it.
+    pub acked: Option<EventPosition>,
+    /// The node time in seconds when the cursor is created.
+    pub created_secs: u64,
+    /// The node time in seconds of the last create, poll or ack.
+    pub last_active_secs: u64,
+}
+
+impl EventCursor {
+    pub fn new(
+        id: EventCursorId,
+        filter: Filter,
+        decode: bool,
+        acked: Option<EventPosition>,
+        now_secs: u64,
+    ) -> Self {
+        Self {
+            id,
+            filter,
+            decode,
+            acked,
+            created_secs: now_secs,
+            last_active_secs: now_secs,
+        }
+    }
+
+    /// Move the acked position forward to `position`, an older position is ignored.
+    pub fn ack(&mut self, position: EventPosition) {
+        if self.acked.map(|acked| acked < position).unwrap_or(true) {
+            self.acked = Some(position);
+        }
+    }
+
+    /// The cursor is expired if it is idle for more than `idle_timeout_secs`,
+    /// or it is created more than `max_lifetime_secs` ago.
+    pub fn is_expired(
+        &self,
+        now_secs: u64,
+        idle_timeout_secs: u64,
+        max_lifetime_secs: u64,
+    ) -> bool {
+        self.last_active_secs.saturating_add(idle_timeout_secs) < now_secs
+            || self.created_secs.saturating_add(max_lifetime_secs) < now_secs
+    }
+}
@@ -1,5 +1,6 @@
 use crate::block::BlockNumber;
 use crate::contract_event::ContractEvent;
+use crate::filter::EventPosition;
 use starcoin_crypto::HashValue;
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
@@ -9,5 +10,17 @@ pub struct ContractEventInfo {
     pub transaction_hash: HashValue,
     // txn index in block
     pub transaction_index: u32,
+    // event index in txn
+    pub event_index: u32,
     pub event: ContractEvent,
 }
+
+impl ContractEventInfo {
+    pub fn position(&self) -> EventPosition {
+        EventPosition {
+            block_number: self.block_number,
+            transaction_index: self.transaction_index,
+            event_index: self.event_index,
+        }
+    }
+}
@@ -1,10 +1,50 @@
 //! Blockchain filter
 
+use crate::account_address::AccountAddress;
 use crate::block::BlockNumber;
 use crate::contract_event::ContractEvent;
 use crate::event::EventKey;
+use crate::language_storage::TypeTag;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq)]
+/// The position of an event in the main chain, the events are ordered by it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub struct EventPosition {
+    pub block_number: BlockNumber,
+    /// txn index in block
+    pub transaction_index: u32,
+    /// event index in txn
+    pub event_index: u32,
+}
+
+impl EventPosition {
+    /// Encode the position into one u128 which keeps the order of positions,
+    /// the block number takes the high 64 bits, then the txn index and the event index take 32 bits each.
+    pub fn global_index(&self) -> u128 {
+        ((self.block_number as u128) << 64)
+            | ((self.transaction_index as u128) << 32)
+            | self.event_index as u128
+    }
+
+    pub fn from_global_index(index: u128) -> Self {
+        Self {
+            block_number: (index >> 64) as BlockNumber,
+            transaction_index: (index >> 32) as u32,
+            event_index: index as u32,
+        }
+    }
+
+    /// The position after all the events of the block.
+    pub fn end_of_block(block_number: BlockNumber) -> Self {
+        Self {
+            block_number,
+            transaction_index: u32::MAX,
+            event_index: u32::MAX,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Filter {
     /// Blockchain will be searched from this block.
     pub from_block: BlockNumber,
@@ -16,6 +56,12 @@ pub struct Filter {
     /// If empty, match all.
     /// If specified, event must produced from one of the event keys.
     pub event_keys: Vec<EventKey>,
+    /// If empty, match all.
+    /// If specified, event must be one of the types.
+    pub type_tags: Vec<TypeTag>,
+    /// If empty, match all.
+    /// If specified, the address of the event key must be one of the addresses.
+    pub addrs: Vec<AccountAddress>,
     /// Events limit
     ///
     /// If None, return all events
@@ -23,6 +69,9 @@ pub struct Filter {
     pub limit: Option<usize>,
     /// return events in reverse order.
     pub reverse: bool,
+    /// If specified, only return the events after the position,
+    /// or before the position if `reverse`.
+    pub cursor: Option<EventPosition>,
 }
 
 impl Default for Filter {
@@ -31,8 +80,11 @@ impl Default for Filter {
             from_block: 0,
             to_block: 0,
             event_keys: vec![],
+            type_tags: vec![],
+            addrs: vec![],
             limit: None,
             reverse: true,
+            cursor: None,
         }
     }
 }
@@ -42,9 +94,56 @@ impl Filter {
         if self.from_block <= block_number
             && block_number <= self.to_block
             && (self.event_keys.is_empty() || self.event_keys.contains(e.key()))
+            && (self.type_tags.is_empty() || self.type_tags.contains(e.type_tag()))
+            && (self.addrs.is_empty() || self.addrs.contains(&e.key().get_creator_address()))
         {
             return true;
         }
         false
     }
+
+    /// Whether the event at `position` is not skipped by the cursor.
+    pub fn after_cursor(&self, position: &EventPosition) -> bool {
+        match &self.cursor {
+            None => true,
+            Some(cursor) if self.reverse => position < cursor,
+            Some(cursor) => position > cursor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_position_global_index() {
+        let positions = vec![
+            EventPosition {
+                block_number: 1,
+                transaction_index: 0,
+                event_index: 2,
+            },
+            EventPosition {
+                block_number: 1,
+                transaction_index: 1,
+                event_index: 0,
+            },
+            EventPosition::end_of_block(1),
+            EventPosition {
+                block_number: 2,
+                transaction_index: 0,
+                event_index: 0,
+            },
+        ];
+        for (pre, next) in positions.iter().zip(positions.iter().skip(1)) {
+            assert!(pre.global_index() < next.global_index());
+        }
+        for position in positions {
+            assert_eq!(
+                EventPosition::from_global_index(position.global_index()),
+                position
+            );
+        }
+    }
 }
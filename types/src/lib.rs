@@ -21,6 +21,8 @@ pub mod account_state;
 
 #[allow(clippy::too_many_arguments)]
 pub mod block;
+pub mod block_bundle;
+pub mod checkpoint;
 pub mod cmpact_block;
 
 pub mod block_metadata {
@@ -42,6 +44,7 @@ pub mod event {
     pub use starcoin_vm_types::event::*;
 }
 
+pub mod event_cursor;
 pub mod filter;
 pub mod peer_info;
 
@@ -59,6 +62,8 @@ pub mod transaction {
     pub use starcoin_vm_types::transaction::*;
 }
 
+pub mod webhook;
+
 //TODO rename or remove this mode.
 pub mod vm_error {
     pub use starcoin_vm_types::vm_status::*;
@@ -85,6 +90,4 @@ pub mod genesis_config {
 pub mod stress_test;
 pub mod sync_status;
 
-pub mod proof {
-    pub use forkable_jellyfish_merkle::proof::SparseMerkleProof;
-}
+pub mod proof;
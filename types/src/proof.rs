@@ -0,0 +1,52 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::contract_event::ContractEvent;
+use crate::transaction::TransactionInfo;
+use anyhow::Result;
+pub use forkable_jellyfish_merkle::proof::SparseMerkleProof;
+use serde::{Deserialize, Serialize};
+pub use starcoin_accumulator::AccumulatorProof;
+use starcoin_crypto::hash::PlainCryptoHash;
+use starcoin_crypto::HashValue;
+
+/// A contract event together with the proof of its inclusion in the
+/// event accumulator of the transaction which emitted it.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct EventWithProof {
+    pub event: ContractEvent,
+    /// The index of the event in the txn's event accumulator.
+    pub event_index: u64,
+    pub proof: AccumulatorProof,
+}
+
+impl EventWithProof {
+    /// Verify the event against the `event_root_hash` of its transaction info.
+    pub fn verify(&self, event_root_hash: HashValue) -> Result<()> {
+        self.proof
+            .verify(event_root_hash, self.event.crypto_hash(), self.event_index)
+    }
+}
+
+/// A transaction info together with the proof of its inclusion in the transaction accumulator.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct TransactionInfoWithProof {
+    pub transaction_info: TransactionInfo,
+    /// The index of the txn info in the transaction accumulator.
+    pub leaf_index: u64,
+    pub proof: AccumulatorProof,
+    pub event_proof: Option<EventWithProof>,
+}
+
+impl TransactionInfoWithProof {
+    /// Verify the txn info against the `txn_accumulator_root` of the block header,
+    /// and the event proof against the txn info's `event_root_hash` if present.
+    pub fn verify(&self, expected_root: HashValue) -> Result<()> {
+        self.proof
+            .verify(expected_root, self.transaction_info.id(), self.leaf_index)?;
+        if let Some(event_proof) = &self.event_proof {
+            event_proof.verify(self.transaction_info.event_root_hash())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Transaction lifecycle webhook registrations
+
+use crate::account_address::AccountAddress;
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+
+/// Filter the transactions a webhook cares about.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct WebhookFilter {
+    /// Match the transactions sent by one of the senders.
+    #[serde(default)]
+    pub senders: Vec<AccountAddress>,
+    /// Match the transactions with one of the hashes.
+    #[serde(default)]
+    pub txn_hashes: Vec<HashValue>,
+}
+
+impl WebhookFilter {
+    pub fn is_empty(&self) -> bool {
+        self.senders.is_empty() && self.txn_hashes.is_empty()
+    }
+
+    pub fn matching(&self, sender: &AccountAddress, txn_hash: &HashValue) -> bool {
+        self.senders.contains(sender) || self.txn_hashes.contains(txn_hash)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct WebhookRegistration {
+    pub id: HashValue,
+    /// The url to POST the transaction status to.
+    pub url: String,
+    pub filter: WebhookFilter,
+    /// The secret to sign the payload with HMAC-SHA256.
+    pub secret: String,
+}
+
+impl WebhookRegistration {
+    pub fn new(id: HashValue, url: String, filter: WebhookFilter, secret: String) -> Self {
+        Self {
+            id,
+            url,
+            filter,
+            secret,
+        }
+    }
+}
@@ -136,6 +136,54 @@ pub fn compile_source_string_no_report(
     })
 }
 
+/// Compile multiple source files together, and return compile error.
+/// The `{{name}}` placeholders in sources are replaced by `address_mapping`, and `{{sender}}` by `sender`.
+pub fn compile_source_files_no_report<P: AsRef<Path>>(
+    source_files: &[P],
+    deps: &[String],
+    sender: AccountAddress,
+    address_mapping: &HashMap<String, AccountAddress>,
+) -> Result<(FilesSourceText, Result<Vec<CompiledUnit>, Errors>)> {
+    let temp_dir = tempfile::tempdir()?;
+    let sender = Address::new(sender.into());
+    let mut targets = vec![];
+    for (idx, source_file) in source_files.iter().enumerate() {
+        let source_file = source_file.as_ref();
+        ensure!(source_file.is_file(), "{:?} not a file.", source_file);
+        let source = std::fs::read_to_string(source_file)?;
+        let vars = address_mapping
+            .iter()
+            .map(|(name, address)| {
+                (
+                    name.as_str(),
+                    format!("{}", Address::new((*address).into())),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        let processed_source = process_source_tpl(source.as_str(), sender, vars);
+        // prefix the index, in case of files with same name in different dirs.
+        let temp_file = temp_dir
+            .path()
+            .join(format!(
+                "{}_{}",
+                idx,
+                source_file
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .expect("source_file must contains file_name.")
+            ))
+            .with_extension(MOVE_EXTENSION);
+        std::fs::write(temp_file.as_path(), processed_source.as_bytes())?;
+        targets.push(
+            temp_file
+                .to_str()
+                .expect("temp file path must is str.")
+                .to_string(),
+        );
+    }
+    move_compile(&targets, deps, Some(sender), None, true)
+}
+
 /// check module compatibility
 pub fn check_module_compat(pre_code: &[u8], new_code: &[u8]) -> VMResult<bool> {
     let pre_module =
@@ -287,6 +335,60 @@ mod tests {
         }
     }
 
+    #[stest::test]
+    fn test_compile_source_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let module_file = temp_dir.path().join("MyModule.move");
+        std::fs::write(
+            module_file.as_path(),
+            r#"
+            address {{alice}} {
+            module MyModule {
+                public fun hello(): u64 {
+                    1
+                }
+            }
+            }
+        "#,
+        )
+        .unwrap();
+        let script_file = temp_dir.path().join("my_script.move");
+        std::fs::write(
+            script_file.as_path(),
+            r#"
+            script {
+            use {{alice}}::MyModule;
+            fun main() {
+                assert(MyModule::hello() == 1, 1000);
+            }
+            }
+        "#,
+        )
+        .unwrap();
+        let mut address_mapping = HashMap::new();
+        address_mapping.insert(
+            "alice".to_string(),
+            AccountAddress::from_hex_literal("0x1dcd9f05cc902e4f342a404ade878efa").unwrap(),
+        );
+        let units = compile_source_files_no_report(
+            &[module_file, script_file],
+            &[],
+            CORE_CODE_ADDRESS,
+            &address_mapping,
+        )
+        .unwrap()
+        .1
+        .unwrap();
+        assert_eq!(units.len(), 2);
+        assert_eq!(
+            units
+                .iter()
+                .filter(|unit| matches!(unit, CompiledUnit::Script { .. }))
+                .count(),
+            1
+        );
+    }
+
     fn do_test_compat(pre_source_code: &str, new_source_code: &str, expect: bool) {
         let pre_code = compile_source_string_no_report(pre_source_code, &[], CORE_CODE_ADDRESS)
             .unwrap()
@@ -0,0 +1,351 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Execute a block recording the state entries it reads, and replay the block offline
+//! on the recorded entries, to reproduce the execution of a block without the full state.
+
+use anyhow::{bail, ensure, format_err, Result};
+use starcoin_state_api::ChainStateWriter;
+use starcoin_vm_runtime::starcoin_vm::StarcoinVM;
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::state_view::StateView;
+use starcoin_vm_types::transaction::{
+    Transaction, TransactionInfo, TransactionOutput, TransactionStatus,
+};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// A state view which records the value of every access path read through it.
+/// The vm caches the writes of the previous txns of a block, so the recorded values are the
+/// state before the block.
+pub struct ReadRecordingStateView<'a> {
+    state_view: &'a dyn StateView,
+    reads: Mutex<BTreeMap<AccessPath, Option<Vec<u8>>>>,
+}
+
+impl<'a> ReadRecordingStateView<'a> {
+    pub fn new(state_view: &'a dyn StateView) -> Self {
+        Self {
+            state_view,
+            reads: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// The recorded entries, sorted by the access path.
+    pub fn into_read_set(self) -> Vec<(AccessPath, Option<Vec<u8>>)> {
+        self.reads
+            .into_inner()
+            .expect("Read set lock should not be poisoned.")
+            .into_iter()
+            .collect()
+    }
+}
+
+impl<'a> StateView for ReadRecordingStateView<'a> {
+    fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        let value = self.state_view.get(access_path)?;
+        self.reads
+            .lock()
+            .map_err(|_| format_err!("Read set lock is poisoned"))?
+            .entry(access_path.clone())
+            .or_insert_with(|| value.clone());
+        Ok(value)
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        access_paths
+            .iter()
+            .map(|access_path| self.get(access_path))
+            .collect()
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.state_view.is_genesis()
+    }
+}
+
+/// A state view of the recorded entries, reading an entry which is not recorded is an error,
+/// because the execution has already diverged from the recorded one.
+/// The vm may turn the error into a txn status, so the first unrecorded read is kept too.
+pub struct RecordedStateView {
+    entries: BTreeMap<AccessPath, Option<Vec<u8>>>,
+    unrecorded: Mutex<Option<AccessPath>>,
+}
+
+impl RecordedStateView {
+    pub fn new(read_set: Vec<(AccessPath, Option<Vec<u8>>)>) -> Self {
+        Self {
+            entries: read_set.into_iter().collect(),
+            unrecorded: Mutex::new(None),
+        }
+    }
+
+    /// The first access path read but not in the read set.
+    pub fn unrecorded_read(&self) -> Option<AccessPath> {
+        self.unrecorded
+            .lock()
+            .expect("Unrecorded read lock should not be poisoned.")
+            .clone()
+    }
+}
+
+impl StateView for RecordedStateView {
+    fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        match self.entries.get(access_path) {
+            Some(value) => Ok(value.clone()),
+            None => {
+                self.unrecorded
+                    .lock()
+                    .map_err(|_| format_err!("Unrecorded read lock is poisoned"))?
+                    .get_or_insert_with(|| access_path.clone());
+                bail!("The access path {} is not in the read set", access_path)
+            }
+        }
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        access_paths
+            .iter()
+            .map(|access_path| self.get(access_path))
+            .collect()
+    }
+
+    fn is_genesis(&self) -> bool {
+        false
+    }
+}
+
+fn execute_block(
+    state_view: &dyn StateView,
+    transactions: Vec<Transaction>,
+) -> Result<Vec<TransactionOutput>> {
+    let mut vm = StarcoinVM::new();
+    Ok(vm
+        .execute_block_transactions(state_view, transactions, None)?
+        .into_iter()
+        .map(|(_, output)| output)
+        .collect())
+}
+
+/// Execute the txns of a block on the state before the block, the outputs are not committed.
+/// Return the outputs, and the state entries the block reads.
+pub fn execute_block_with_read_set(
+    state_view: &dyn StateView,
+    transactions: Vec<Transaction>,
+) -> Result<(Vec<TransactionOutput>, Vec<(AccessPath, Option<Vec<u8>>)>)> {
+    let recording_view = ReadRecordingStateView::new(state_view);
+    let outputs = execute_block(&recording_view, transactions)?;
+    Ok((outputs, recording_view.into_read_set()))
+}
+
+/// Verify the outputs of a block against the committed `txn_infos` of the block.
+/// `chain_state` is the state before the block, the write sets are applied to it in order
+/// to compute the state root after every txn, so it should not be flushed.
+pub fn verify_block_outputs(
+    chain_state: &dyn ChainStateWriter,
+    transactions: &[Transaction],
+    outputs: &[TransactionOutput],
+    txn_infos: &[TransactionInfo],
+) -> Result<()> {
+    ensure!(
+        transactions.len() == outputs.len() && outputs.len() == txn_infos.len(),
+        "{} txns, but {} outputs and {} committed txn infos",
+        transactions.len(),
+        outputs.len(),
+        txn_infos.len()
+    );
+    for (i, ((txn, output), txn_info)) in
+        transactions.iter().zip(outputs).zip(txn_infos).enumerate()
+    {
+        let status = match output.status() {
+            TransactionStatus::Keep(status) => status.clone(),
+            TransactionStatus::Discard(status) => {
+                bail!("txn {}({}) is discarded by {:?}", i, txn.id(), status)
+            }
+        };
+        chain_state.apply_write_set(output.write_set().clone())?;
+        let state_root = chain_state.commit()?;
+        let output_info = TransactionInfo::new(
+            txn.id(),
+            state_root,
+            output.events(),
+            output.gas_used(),
+            status,
+        );
+        ensure!(
+            output_info.id() == txn_info.id(),
+            "txn {}({}): the output mismatches the committed txn info, state root {} vs {}, event root {} vs {}",
+            i,
+            txn.id(),
+            output_info.state_root_hash(),
+            txn_info.state_root_hash(),
+            output_info.event_root_hash(),
+            txn_info.event_root_hash()
+        );
+    }
+    Ok(())
+}
+
+/// Replay the txns of a block on the state entries recorded by `execute_block_with_read_set`.
+/// Return error if the block reads an entry which is not recorded, the read set is incomplete then.
+pub fn replay_block(
+    read_set: Vec<(AccessPath, Option<Vec<u8>>)>,
+    transactions: Vec<Transaction>,
+) -> Result<Vec<TransactionOutput>> {
+    let state_view = RecordedStateView::new(read_set);
+    let outputs = execute_block(&state_view, transactions);
+    if let Some(access_path) = state_view.unrecorded_read() {
+        bail!(
+            "The block reads the access path {} which is not in the read set",
+            access_path
+        );
+    }
+    outputs
+}
+
+/// Describe the first divergence of the replayed outputs from the recorded ones,
+/// compared by the status, the gas used, the write set and the events of every txn in order.
+/// Return None if they are the same.
+pub fn first_divergence(
+    transactions: &[Transaction],
+    recorded: &[TransactionOutput],
+    replayed: &[TransactionOutput],
+) -> Option<String> {
+    for (i, txn) in transactions.iter().enumerate() {
+        let (expect, actual) = match (recorded.get(i), replayed.get(i)) {
+            (Some(expect), Some(actual)) => (expect, actual),
+            (None, None) => return None,
+            (expect, actual) => {
+                return Some(format!(
+                    "txn {}({}): recorded output exists: {}, replayed output exists: {}",
+                    i,
+                    txn.id(),
+                    expect.is_some(),
+                    actual.is_some()
+                ))
+            }
+        };
+        if expect.status() != actual.status() {
+            return Some(format!(
+                "txn {}({}): status {:?} != recorded {:?}",
+                i,
+                txn.id(),
+                actual.status(),
+                expect.status()
+            ));
+        }
+        if expect.gas_used() != actual.gas_used() {
+            return Some(format!(
+                "txn {}({}): gas used {} != recorded {}",
+                i,
+                txn.id(),
+                actual.gas_used(),
+                expect.gas_used()
+            ));
+        }
+        let expect_writes = expect.write_set().iter().collect::<Vec<_>>();
+        let actual_writes = actual.write_set().iter().collect::<Vec<_>>();
+        for j in 0..expect_writes.len().max(actual_writes.len()) {
+            let (expect_write, actual_write) = (expect_writes.get(j), actual_writes.get(j));
+            if expect_write != actual_write {
+                return Some(format!(
+                    "txn {}({}): write op {} is {:?}, recorded {:?}",
+                    i,
+                    txn.id(),
+                    j,
+                    actual_write,
+                    expect_write
+                ));
+            }
+        }
+        for j in 0..expect.events().len().max(actual.events().len()) {
+            let (expect_event, actual_event) = (expect.events().get(j), actual.events().get(j));
+            if expect_event != actual_event {
+                return Some(format!(
+                    "txn {}({}): event {} is {:?}, recorded {:?}",
+                    i,
+                    txn.id(),
+                    j,
+                    actual_event,
+                    expect_event
+                ));
+            }
+        }
+    }
+    if replayed.len() > transactions.len() || recorded.len() > transactions.len() {
+        return Some(format!(
+            "{} txns, but {} recorded outputs and {} replayed outputs",
+            transactions.len(),
+            recorded.len(),
+            replayed.len()
+        ));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_state_api::ChainStateWriter;
+    use starcoin_statedb::ChainStateDB;
+    use starcoin_vm_types::account_address::AccountAddress;
+    use starcoin_vm_types::account_config::{stc_type_tag, BalanceResource};
+    use starcoin_vm_types::transaction::{SignedUserTransaction, TransactionStatus};
+    use starcoin_vm_types::vm_status::KeptVMStatus;
+    use starcoin_vm_types::write_set::{WriteOp, WriteSetMut};
+
+    fn balance_path() -> AccessPath {
+        AccessPath::resource_access_path(
+            AccountAddress::random(),
+            BalanceResource::struct_tag_for_token(stc_type_tag()),
+        )
+    }
+
+    #[test]
+    fn test_record_and_replay_reads() {
+        let state = ChainStateDB::mock();
+        let exist_path = balance_path();
+        let missing_path = balance_path();
+        state.set(&exist_path, vec![1]).unwrap();
+
+        let recording_view = ReadRecordingStateView::new(&state);
+        assert_eq!(recording_view.get(&exist_path).unwrap(), Some(vec![1]));
+        assert_eq!(recording_view.get(&missing_path).unwrap(), None);
+        assert_eq!(recording_view.get(&exist_path).unwrap(), Some(vec![1]));
+        let read_set = recording_view.into_read_set();
+        assert_eq!(read_set.len(), 2);
+
+        let recorded_view = RecordedStateView::new(read_set);
+        assert_eq!(recorded_view.get(&exist_path).unwrap(), Some(vec![1]));
+        assert_eq!(recorded_view.get(&missing_path).unwrap(), None);
+        assert!(recorded_view.get(&balance_path()).is_err());
+    }
+
+    #[test]
+    fn test_first_divergence() {
+        let txns = vec![
+            Transaction::UserTransaction(SignedUserTransaction::mock()),
+            Transaction::UserTransaction(SignedUserTransaction::mock()),
+        ];
+        let output = |value: u8| {
+            TransactionOutput::new(
+                WriteSetMut::new(vec![(balance_path(), WriteOp::Value(vec![value]))])
+                    .freeze()
+                    .unwrap(),
+                vec![],
+                100,
+                TransactionStatus::Keep(KeptVMStatus::Executed),
+            )
+        };
+        let recorded = vec![output(1), output(2)];
+        assert_eq!(first_divergence(&txns, &recorded, &recorded), None);
+
+        let mut replayed = recorded.clone();
+        replayed[1] = output(3);
+        let divergence = first_divergence(&txns, &recorded, &replayed).unwrap();
+        assert!(divergence.starts_with(&format!("txn 1({}): write op 0", txns[1].id())));
+
+        let divergence = first_divergence(&txns, &recorded, &recorded[..1]).unwrap();
+        assert!(divergence.starts_with("txn 1"));
+    }
+}
@@ -0,0 +1,210 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use starcoin_vm_types::file_format::CompiledModule;
+use starcoin_vm_types::identifier::Identifier;
+use starcoin_vm_types::language_storage::ModuleId;
+use starcoin_vm_types::normalized::Module;
+use std::fmt;
+
+/// A breaking change of a module upgrade.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Incompatibility {
+    /// The struct is removed.
+    StructMissing(Identifier),
+    /// Some abilities of the struct are removed.
+    StructAbilitiesChanged(Identifier),
+    /// The type parameters of the struct are changed.
+    StructTypeParametersChanged(Identifier),
+    /// The fields of the struct are changed.
+    StructLayoutChanged(Identifier),
+    /// The public function is removed.
+    FunctionMissing(Identifier),
+    /// The signature of the public function is changed.
+    FunctionSignatureChanged(Identifier),
+}
+
+impl fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Incompatibility::StructMissing(name) => write!(f, "struct {} is removed", name),
+            Incompatibility::StructAbilitiesChanged(name) => {
+                write!(f, "abilities of struct {} are removed", name)
+            }
+            Incompatibility::StructTypeParametersChanged(name) => {
+                write!(f, "type parameters of struct {} are changed", name)
+            }
+            Incompatibility::StructLayoutChanged(name) => {
+                write!(f, "fields of struct {} are changed", name)
+            }
+            Incompatibility::FunctionMissing(name) => {
+                write!(f, "public function {} is removed", name)
+            }
+            Incompatibility::FunctionSignatureChanged(name) => {
+                write!(f, "signature of public function {} is changed", name)
+            }
+        }
+    }
+}
+
+/// The result of checking an upgrade of module against the old one.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompatibilityReport {
+    pub module_id: ModuleId,
+    pub incompatibilities: Vec<Incompatibility>,
+}
+
+impl CompatibilityReport {
+    pub fn is_compatible(&self) -> bool {
+        self.incompatibilities.is_empty()
+    }
+}
+
+impl fmt::Display for CompatibilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_compatible() {
+            return write!(
+                f,
+                "module {}::{} is compatible",
+                self.module_id.address(),
+                self.module_id.name()
+            );
+        }
+        writeln!(
+            f,
+            "module {}::{} is incompatible:",
+            self.module_id.address(),
+            self.module_id.name()
+        )?;
+        for incompatibility in &self.incompatibilities {
+            writeln!(f, "  {}", incompatibility)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check whether the `new` module can replace the `old` one,
+/// and report every incompatible struct and public function.
+pub fn check_compatibility(old: &CompiledModule, new: &CompiledModule) -> CompatibilityReport {
+    let old = Module::new(old);
+    let new = Module::new(new);
+    let mut incompatibilities = vec![];
+    for (name, old_struct) in &old.structs {
+        let new_struct = match new.structs.get(name) {
+            Some(new_struct) => new_struct,
+            None => {
+                incompatibilities.push(Incompatibility::StructMissing(name.clone()));
+                continue;
+            }
+        };
+        if !old_struct.abilities.is_subset(new_struct.abilities) {
+            incompatibilities.push(Incompatibility::StructAbilitiesChanged(name.clone()));
+        }
+        if old_struct.type_parameters != new_struct.type_parameters {
+            incompatibilities.push(Incompatibility::StructTypeParametersChanged(name.clone()));
+        }
+        if old_struct.fields != new_struct.fields {
+            incompatibilities.push(Incompatibility::StructLayoutChanged(name.clone()));
+        }
+    }
+    for (name, old_func) in &old.exported_functions {
+        match new.exported_functions.get(name) {
+            Some(new_func) => {
+                if old_func != new_func {
+                    incompatibilities.push(Incompatibility::FunctionSignatureChanged(name.clone()));
+                }
+            }
+            None => incompatibilities.push(Incompatibility::FunctionMissing(name.clone())),
+        }
+    }
+    CompatibilityReport {
+        module_id: ModuleId::new(old.address, old.name),
+        incompatibilities,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_move_compiler::compile_source_string_no_report;
+    use starcoin_vm_types::language_storage::CORE_CODE_ADDRESS;
+
+    fn compile(source: &str) -> CompiledModule {
+        let unit = compile_source_string_no_report(source, &[], CORE_CODE_ADDRESS)
+            .unwrap()
+            .1
+            .unwrap()
+            .pop()
+            .unwrap();
+        CompiledModule::deserialize(unit.serialize().as_slice()).unwrap()
+    }
+
+    #[stest::test]
+    fn test_check_compatibility() {
+        let old = compile(
+            r#"
+            module M {
+                struct S has copy, drop {
+                    value: u64,
+                }
+                struct S2 {
+                    value: u64,
+                }
+                public fun hello() {
+                }
+                public fun world(_v: u64) {
+                }
+            }
+        "#,
+        );
+        let compatible = compile(
+            r#"
+            module M {
+                struct S has copy, drop, store {
+                    value: u64,
+                }
+                struct S2 {
+                    value: u64,
+                }
+                struct S3 {
+                    value: u128,
+                }
+                public fun hello() {
+                }
+                public fun world(_v: u64) {
+                }
+                public fun hello2() {
+                }
+            }
+        "#,
+        );
+        let report = check_compatibility(&old, &compatible);
+        assert!(report.is_compatible(), "{}", report);
+
+        let incompatible = compile(
+            r#"
+            module M {
+                struct S has copy {
+                    value: u64,
+                    new_field: address,
+                }
+                public fun world(_v: u128) {
+                }
+            }
+        "#,
+        );
+        let report = check_compatibility(&old, &incompatible);
+        assert!(!report.is_compatible());
+        let name = |s: &str| Identifier::new(s).unwrap();
+        assert_eq!(
+            report.incompatibilities,
+            vec![
+                Incompatibility::StructAbilitiesChanged(name("S")),
+                Incompatibility::StructLayoutChanged(name("S")),
+                Incompatibility::StructMissing(name("S2")),
+                Incompatibility::FunctionMissing(name("hello")),
+                Incompatibility::FunctionSignatureChanged(name("world")),
+            ]
+        );
+    }
+}
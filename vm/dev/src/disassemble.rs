@@ -0,0 +1,186 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use bytecode_source_map::mapping::SourceMapping;
+use disassembler::disassembler::{Disassembler, DisassemblerOptions};
+use move_ir_types::location::Spanned;
+use starcoin_vm_types::access::{ModuleAccess, ScriptAccess};
+use starcoin_vm_types::errors::PartialVMError;
+use starcoin_vm_types::file_format::{CompiledModule, CompiledScript};
+use starcoin_vm_types::language_storage::ModuleId;
+use starcoin_vm_types::vm_status::StatusCode;
+
+/// The error of disassembling a bytecode blob.
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+pub enum DisassembleError {
+    /// The bytes are neither a module nor a script, such as the bytecode is truncated.
+    #[error("invalid bytecode, {status_code:?}: {message}")]
+    InvalidBytecode {
+        status_code: StatusCode,
+        message: String,
+    },
+    #[error("failed to disassemble the bytecode, {0}")]
+    DisassembleFailed(String),
+}
+
+impl From<PartialVMError> for DisassembleError {
+    fn from(err: PartialVMError) -> Self {
+        DisassembleError::InvalidBytecode {
+            status_code: err.major_status(),
+            message: format!("{:?}", err),
+        }
+    }
+}
+
+/// The textual disassembly of a module or script.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Disassembly {
+    /// The id of the module, None if the bytecode is a script.
+    pub module_id: Option<ModuleId>,
+    /// The modules used by the bytecode.
+    pub dependencies: Vec<ModuleId>,
+    pub code: String,
+}
+
+fn disassembler_options() -> DisassemblerOptions {
+    let mut options = DisassemblerOptions::new();
+    options.print_code = true;
+    options.print_basic_blocks = true;
+    options.print_locals = true;
+    options
+}
+
+/// Get the modules used by `module`, excluding itself.
+pub fn module_dependencies(module: &CompiledModule) -> Vec<ModuleId> {
+    let self_id = module.self_id();
+    module
+        .module_handles()
+        .iter()
+        .map(|handle| module.module_id_for_handle(handle))
+        .filter(|id| id != &self_id)
+        .collect()
+}
+
+fn script_dependencies(script: &CompiledScript) -> Vec<ModuleId> {
+    script
+        .module_handles()
+        .iter()
+        .map(|handle| {
+            ModuleId::new(
+                *script.address_identifier_at(handle.address),
+                script.identifier_at(handle.name).to_owned(),
+            )
+        })
+        .collect()
+}
+
+pub fn disassemble_module(module: CompiledModule) -> Result<Disassembly, DisassembleError> {
+    let module_id = module.self_id();
+    let dependencies = module_dependencies(&module);
+    let source_mapping = SourceMapping::new_from_module(module, Spanned::unsafe_no_loc(()).loc)
+        .map_err(|e| DisassembleError::DisassembleFailed(e.to_string()))?;
+    let code = Disassembler::new(source_mapping, disassembler_options())
+        .disassemble()
+        .map_err(|e| DisassembleError::DisassembleFailed(e.to_string()))?;
+    Ok(Disassembly {
+        module_id: Some(module_id),
+        dependencies,
+        code,
+    })
+}
+
+pub fn disassemble_script(script: CompiledScript) -> Result<Disassembly, DisassembleError> {
+    let dependencies = script_dependencies(&script);
+    let source_mapping = SourceMapping::new_from_script(script, Spanned::unsafe_no_loc(()).loc)
+        .map_err(|e| DisassembleError::DisassembleFailed(e.to_string()))?;
+    let code = Disassembler::new(source_mapping, disassembler_options())
+        .disassemble()
+        .map_err(|e| DisassembleError::DisassembleFailed(e.to_string()))?;
+    Ok(Disassembly {
+        module_id: None,
+        dependencies,
+        code,
+    })
+}
+
+/// Disassemble the bytecode of a module or script.
+/// The bytecode is deserialized and bounds checked first, so malformed bytecode is reported as an error.
+pub fn disassemble(code: &[u8]) -> Result<Disassembly, DisassembleError> {
+    match CompiledModule::deserialize(code) {
+        Ok(module) => disassemble_module(module),
+        Err(module_err) => match CompiledScript::deserialize(code) {
+            Ok(script) => disassemble_script(script),
+            // report the error of the module, which is the most common case.
+            Err(_) => Err(module_err.into()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_move_compiler::compile_source_string_no_report;
+    use starcoin_vm_types::language_storage::CORE_CODE_ADDRESS;
+
+    fn compile(source: &str) -> Vec<u8> {
+        compile_source_string_no_report(source, &[], CORE_CODE_ADDRESS)
+            .unwrap()
+            .1
+            .unwrap()
+            .pop()
+            .unwrap()
+            .serialize()
+    }
+
+    #[stest::test]
+    fn test_disassemble_module() {
+        let code = compile(
+            r#"
+            module M {
+                public fun add(a: u64, b: u64): u64 {
+                    a + b
+                }
+            }
+        "#,
+        );
+        let disassembly = disassemble(code.as_slice()).unwrap();
+        assert_eq!(
+            disassembly.module_id,
+            Some(ModuleId::new(
+                CORE_CODE_ADDRESS,
+                starcoin_vm_types::identifier::Identifier::new("M").unwrap()
+            ))
+        );
+        assert!(disassembly.dependencies.is_empty());
+        assert!(disassembly.code.contains("add"), "{}", disassembly.code);
+    }
+
+    #[stest::test]
+    fn test_disassemble_truncated_bytecode() {
+        let code = compile(
+            r#"
+            module M {
+                struct S has copy, drop {
+                    value: u64,
+                }
+                public fun new(value: u64): S {
+                    S { value }
+                }
+            }
+        "#,
+        );
+        for len in 0..code.len() {
+            assert!(
+                disassemble(&code[..len]).is_err(),
+                "the bytecode truncated to {} bytes should be invalid",
+                len
+            );
+        }
+        let mut corrupted = code;
+        corrupted[0] = !corrupted[0];
+        assert!(matches!(
+            disassemble(corrupted.as_slice()),
+            Err(DisassembleError::InvalidBytecode { .. })
+        ));
+    }
+}
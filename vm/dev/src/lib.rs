@@ -1,4 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod block_replay;
+pub mod compatibility;
+pub mod disassemble;
 pub mod playground;
@@ -1,19 +1,29 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::block_replay::{execute_block_with_read_set, verify_block_outputs};
 use anyhow::Result;
 use starcoin_crypto::HashValue;
 use starcoin_resource_viewer::{AnnotatedMoveStruct, AnnotatedMoveValue, MoveValueAnnotator};
-use starcoin_state_api::StateNodeStore;
+use starcoin_state_api::{ChainStateWriter, StateNodeStore};
 use starcoin_statedb::ChainStateDB;
 use starcoin_vm_runtime::starcoin_vm::StarcoinVM;
+use starcoin_vm_types::access_path::AccessPath;
+use starcoin_vm_types::contract_event::ContractEvent;
+use starcoin_vm_types::gas_schedule::{calculate_intrinsic_gas, AbstractMemorySize, GasAlgebra};
 use starcoin_vm_types::identifier::{IdentStr, Identifier};
 use starcoin_vm_types::language_storage::{ModuleId, StructTag, TypeTag};
+use starcoin_vm_types::on_chain_config::{instruction_names, native_names};
 use starcoin_vm_types::state_view::StateView;
-use starcoin_vm_types::transaction::{DryRunTransaction, TransactionOutput};
-use starcoin_vm_types::transaction_argument::convert_txn_args;
-use starcoin_vm_types::transaction_argument::TransactionArgument;
-use starcoin_vm_types::vm_status::VMStatus;
+use starcoin_vm_types::transaction::{
+    DryRunTransaction, GasBreakdown, Transaction, TransactionInfo, TransactionOutput,
+    TransactionStatus,
+};
+use starcoin_vm_types::vm_status::{KeptVMStatus, VMStatus};
+use starcoin_vm_types::write_set::WriteOp;
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 #[derive(Clone)]
@@ -32,9 +42,82 @@ impl PlaygroudService {
         &self,
         state_root: HashValue,
         txn: DryRunTransaction,
+    ) -> Result<(VMStatus, TransactionOutput)> {
+        self.dry_run_with_overrides(state_root, txn, vec![])
+    }
+
+    /// Dry run the `txn` as if the state at `state_root` is overridden by the `overrides`,
+    /// the overrides only affect this dry run.
+    pub fn dry_run_with_overrides(
+        &self,
+        state_root: HashValue,
+        txn: DryRunTransaction,
+        overrides: Vec<(AccessPath, Option<Vec<u8>>)>,
     ) -> Result<(VMStatus, TransactionOutput)> {
         let state_view = ChainStateDB::new(self.state.clone(), Some(state_root));
-        dry_run(&state_view, txn)
+        if overrides.is_empty() {
+            dry_run(&state_view, txn)
+        } else {
+            dry_run(&OverlayStateView::new(&state_view, overrides), txn)
+        }
+    }
+
+    /// Dry run the `txn` like `dry_run_with_overrides`, and collect where the gas goes.
+    pub fn dry_run_with_gas_breakdown(
+        &self,
+        state_root: HashValue,
+        txn: DryRunTransaction,
+        overrides: Vec<(AccessPath, Option<Vec<u8>>)>,
+    ) -> Result<(VMStatus, TransactionOutput, GasBreakdown)> {
+        let state_view = ChainStateDB::new(self.state.clone(), Some(state_root));
+        if overrides.is_empty() {
+            dry_run_with_gas_breakdown(&state_view, txn)
+        } else {
+            dry_run_with_gas_breakdown(&OverlayStateView::new(&state_view, overrides), txn)
+        }
+    }
+
+    /// Re-execute a mined `txn` on the state of `state_root` before it, the output is not committed.
+    /// Return the output, and whether it matches the committed `txn_info`. The write set is
+    /// verified by the state root after applying it, together with the events and the gas used.
+    pub fn replay_transaction(
+        &self,
+        state_root: HashValue,
+        txn: Transaction,
+        txn_info: &TransactionInfo,
+    ) -> Result<(TransactionOutput, bool)> {
+        let chain_state = ChainStateDB::new(self.state.clone(), Some(state_root));
+        let txn_hash = txn.id();
+        let output = replay_transaction(&chain_state, txn)?;
+        let status = match output.status() {
+            TransactionStatus::Keep(status) => status.clone(),
+            TransactionStatus::Discard(_) => return Ok((output, false)),
+        };
+        chain_state.apply_write_set(output.write_set().clone())?;
+        let replayed_info = TransactionInfo::new(
+            txn_hash,
+            chain_state.commit()?,
+            output.events(),
+            output.gas_used(),
+            status,
+        );
+        let verified = replayed_info.id() == txn_info.id();
+        Ok((output, verified))
+    }
+
+    /// Re-execute the txns of a block on the state of `state_root` before the block, the outputs
+    /// are not committed, but verified against the committed `txn_infos` of the block.
+    /// Return the outputs, and the state entries the block reads.
+    pub fn execute_block_with_read_set(
+        &self,
+        state_root: HashValue,
+        txns: Vec<Transaction>,
+        txn_infos: &[TransactionInfo],
+    ) -> Result<(Vec<TransactionOutput>, Vec<(AccessPath, Option<Vec<u8>>)>)> {
+        let state_view = ChainStateDB::new(self.state.clone(), Some(state_root));
+        let (outputs, read_set) = execute_block_with_read_set(&state_view, txns.clone())?;
+        verify_block_outputs(&state_view, &txns, &outputs, txn_infos)?;
+        Ok((outputs, read_set))
     }
 
     pub fn call_contract(
@@ -43,7 +126,7 @@ impl PlaygroudService {
         module_id: ModuleId,
         func: Identifier,
         type_args: Vec<TypeTag>,
-        args: Vec<TransactionArgument>,
+        args: Vec<Vec<u8>>,
     ) -> Result<Vec<AnnotatedMoveValue>> {
         let state_view = ChainStateDB::new(self.state.clone(), Some(state_root));
         let rets = call_contract(&state_view, module_id, func.as_str(), type_args, args)?;
@@ -58,6 +141,59 @@ impl PlaygroudService {
         let state_view = ChainStateDB::new(self.state.clone(), Some(state_root));
         view_resource(&state_view, struct_tag.clone(), data)
     }
+
+    /// Decode the data of the events by the modules at `state_root`.
+    pub fn view_events(
+        &self,
+        state_root: HashValue,
+        events: &[ContractEvent],
+    ) -> Result<Vec<AnnotatedMoveValue>> {
+        let state_view = ChainStateDB::new(self.state.clone(), Some(state_root));
+        let annotator = MoveValueAnnotator::new(&state_view);
+        events
+            .iter()
+            .map(|event| annotator.view_contract_event(event))
+            .collect()
+    }
+}
+
+/// A read only state view which serves the overridden access paths first,
+/// a `None` value means the access path is deleted.
+pub struct OverlayStateView<'a> {
+    state_view: &'a dyn StateView,
+    overrides: BTreeMap<AccessPath, Option<Vec<u8>>>,
+}
+
+impl<'a> OverlayStateView<'a> {
+    pub fn new(
+        state_view: &'a dyn StateView,
+        overrides: Vec<(AccessPath, Option<Vec<u8>>)>,
+    ) -> Self {
+        Self {
+            state_view,
+            overrides: overrides.into_iter().collect(),
+        }
+    }
+}
+
+impl<'a> StateView for OverlayStateView<'a> {
+    fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        match self.overrides.get(access_path) {
+            Some(value) => Ok(value.clone()),
+            None => self.state_view.get(access_path),
+        }
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        access_paths
+            .iter()
+            .map(|access_path| self.get(access_path))
+            .collect()
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.state_view.is_genesis()
+    }
 }
 
 pub fn view_resource(
@@ -78,12 +214,180 @@ pub fn dry_run(
     vm.dry_run_transaction(state_view, txn)
 }
 
+/// A state view which counts the reads, the vm caches the state of a txn,
+/// so the reads are the ones which go to the storage.
+struct ReadCountingStateView<'a> {
+    state_view: &'a dyn StateView,
+    reads: AtomicU64,
+    read_bytes: AtomicU64,
+}
+
+impl<'a> ReadCountingStateView<'a> {
+    fn new(state_view: &'a dyn StateView) -> Self {
+        Self {
+            state_view,
+            reads: AtomicU64::new(0),
+            read_bytes: AtomicU64::new(0),
+        }
+    }
+}
+
+impl<'a> StateView for ReadCountingStateView<'a> {
+    fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        let value = self.state_view.get(access_path)?;
+        self.reads.fetch_add(1, Ordering::Relaxed);
+        if let Some(value) = &value {
+            self.read_bytes
+                .fetch_add(value.len() as u64, Ordering::Relaxed);
+        }
+        Ok(value)
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        access_paths
+            .iter()
+            .map(|access_path| self.get(access_path))
+            .collect()
+    }
+
+    fn is_genesis(&self) -> bool {
+        self.state_view.is_genesis()
+    }
+}
+
+/// Dry run the `txn`, and break the gas used down into the intrinsic gas, the storage write gas and
+/// the gas of every instruction and native, with the storage reads and writes of the txn.
+///
+/// The gas of the instructions and natives is measured by dry running the `txn` again with the gas
+/// schedule in which only some of them are charged, the table is bisected until every charged entry
+/// is measured. The gas is not broken down by the entries if the txn runs out of gas, as the execution
+/// with a cheaper gas schedule goes further.
+pub fn dry_run_with_gas_breakdown(
+    state_view: &dyn StateView,
+    txn: DryRunTransaction,
+) -> Result<(VMStatus, TransactionOutput, GasBreakdown)> {
+    let counting_view = ReadCountingStateView::new(state_view);
+    let txn_size = txn.raw_txn.txn_size();
+    let mut vm = StarcoinVM::new();
+    let (status, output) = vm.dry_run_transaction(&counting_view, txn.clone())?;
+    let gas_used = output.gas_used();
+    // the txn discarded before charging the intrinsic gas uses no gas.
+    let (intrinsic_gas, instructions_len, natives_len) = match vm.get_gas_schedule() {
+        Ok(gas_schedule) => {
+            let gas_constants = &gas_schedule.gas_constants;
+            let intrinsic_gas = gas_constants
+                .to_external_units(calculate_intrinsic_gas(
+                    AbstractMemorySize::new(txn_size as u64),
+                    gas_constants,
+                ))
+                .get()
+                .min(gas_used);
+            (
+                intrinsic_gas,
+                gas_schedule.instruction_table.len(),
+                gas_schedule.native_table.len(),
+            )
+        }
+        Err(_) => (0, 0, 0),
+    };
+    let out_of_gas = matches!(
+        output.status(),
+        TransactionStatus::Keep(KeptVMStatus::OutOfGas)
+    );
+    let mut storage_write_gas = 0;
+    let mut charged_entries = vec![];
+    if gas_used > intrinsic_gas && !out_of_gas {
+        let dry_run_charged = |charged: Range<usize>| -> Result<u64> {
+            let mut vm = StarcoinVM::new();
+            vm.set_charged_gas_costs(Some(charged));
+            let (_, output) = vm.dry_run_transaction(state_view, txn.clone())?;
+            Ok(output.gas_used())
+        };
+        // with all the entries free, only the intrinsic gas and the storage write gas are charged.
+        let base_gas = dry_run_charged(0..0)?;
+        storage_write_gas = base_gas
+            .saturating_sub(intrinsic_gas)
+            .min(gas_used - intrinsic_gas);
+        // the gas of an entry is the same whatever the other entries cost, so the gas of the
+        // right half of a range is the gas of the range minus the left half.
+        let mut ranges = vec![(
+            0..instructions_len + natives_len,
+            gas_used.saturating_sub(base_gas),
+        )];
+        while let Some((range, gas)) = ranges.pop() {
+            if gas == 0 {
+                continue;
+            }
+            if range.len() == 1 {
+                charged_entries.push((range.start, gas));
+                continue;
+            }
+            let mid = range.start + range.len() / 2;
+            let left_gas = dry_run_charged(range.start..mid)?.saturating_sub(base_gas);
+            ranges.push((mid..range.end, gas.saturating_sub(left_gas)));
+            ranges.push((range.start..mid, left_gas));
+        }
+    }
+    let instruction_names = instruction_names();
+    let native_names = native_names();
+    let table_name = |names: &[String], index: usize| {
+        names
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| format!("#{}", index))
+    };
+    let (instructions, natives): (Vec<_>, Vec<_>) = charged_entries
+        .into_iter()
+        .partition(|(index, _)| *index < instructions_len);
+    let write_ops: Vec<_> = output
+        .write_set()
+        .iter()
+        .map(|(access_path, write_op)| {
+            let size = match write_op {
+                WriteOp::Value(value) => Some(value.len() as u64),
+                WriteOp::Deletion => None,
+            };
+            (access_path.clone(), size)
+        })
+        .collect();
+    let breakdown = GasBreakdown {
+        intrinsic_gas,
+        instruction_gas: gas_used - intrinsic_gas - storage_write_gas,
+        storage_write_gas,
+        instructions: instructions
+            .into_iter()
+            .map(|(index, gas)| (table_name(&instruction_names, index), gas))
+            .collect(),
+        natives: natives
+            .into_iter()
+            .map(|(index, gas)| (table_name(&native_names, index - instructions_len), gas))
+            .collect(),
+        storage_reads: counting_view.reads.load(Ordering::Relaxed),
+        storage_read_bytes: counting_view.read_bytes.load(Ordering::Relaxed),
+        storage_writes: write_ops.len() as u64,
+        storage_write_bytes: write_ops.iter().filter_map(|(_, size)| *size).sum(),
+        write_ops,
+    };
+    Ok((status, output, breakdown))
+}
+
+pub fn replay_transaction(
+    state_view: &dyn StateView,
+    txn: Transaction,
+) -> Result<TransactionOutput> {
+    let mut vm = StarcoinVM::new();
+    vm.execute_block_transactions(state_view, vec![txn], None)?
+        .pop()
+        .map(|(_, output)| output)
+        .ok_or_else(|| anyhow::format_err!("no output of the replayed transaction"))
+}
+
 pub fn call_contract(
     state_view: &dyn StateView,
     module_id: ModuleId,
     func: &str,
     type_args: Vec<TypeTag>,
-    args: Vec<TransactionArgument>,
+    args: Vec<Vec<u8>>,
 ) -> Result<Vec<AnnotatedMoveValue>> {
     let mut vm = StarcoinVM::new();
     let rets = vm.execute_readonly_function(
@@ -91,7 +395,7 @@ pub fn call_contract(
         &module_id,
         &IdentStr::new(func)?,
         type_args,
-        convert_txn_args(&args),
+        args,
     )?;
     let annotator = MoveValueAnnotator::new(state_view);
     let mut annotated_values = Vec::with_capacity(rets.len());
@@ -105,3 +409,45 @@ pub fn call_contract(
     }
     Ok(annotated_values)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_vm_types::account_address::AccountAddress;
+    use starcoin_vm_types::account_config::{stc_type_tag, BalanceResource};
+
+    #[test]
+    fn test_overlay_state_view() {
+        let state = ChainStateDB::mock();
+        let address = AccountAddress::random();
+        let balance_path = AccessPath::resource_access_path(
+            address,
+            BalanceResource::struct_tag_for_token(stc_type_tag()),
+        );
+        let other_path = AccessPath::resource_access_path(
+            AccountAddress::random(),
+            BalanceResource::struct_tag_for_token(stc_type_tag()),
+        );
+        state.set(&balance_path, vec![1]).unwrap();
+        state.set(&other_path, vec![2]).unwrap();
+
+        let overlay = OverlayStateView::new(
+            &state,
+            vec![
+                (balance_path.clone(), Some(vec![3])),
+                (other_path.clone(), None),
+            ],
+        );
+        assert_eq!(overlay.get(&balance_path).unwrap(), Some(vec![3]));
+        assert_eq!(overlay.get(&other_path).unwrap(), None);
+        assert_eq!(
+            overlay
+                .multi_get(&[balance_path.clone(), other_path.clone()])
+                .unwrap(),
+            vec![Some(vec![3]), None]
+        );
+        // the overrides never touch the underlying state.
+        assert_eq!(state.get(&balance_path).unwrap(), Some(vec![1]));
+        assert_eq!(state.get(&other_path).unwrap(), Some(vec![2]));
+    }
+}
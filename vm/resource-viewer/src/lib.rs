@@ -53,6 +53,7 @@ pub enum AnnotatedMoveValue {
     U128(u128),
     Bool(bool),
     Address(AccountAddress),
+    Signer(AccountAddress),
     Vector(Vec<AnnotatedMoveValue>),
     Bytes(Vec<u8>),
     Struct(AnnotatedMoveStruct),
@@ -176,6 +177,7 @@ impl<'a> MoveValueAnnotator<'a> {
             (MoveValue::U64(i), FatType::U64) => AnnotatedMoveValue::U64(*i),
             (MoveValue::U128(i), FatType::U128) => AnnotatedMoveValue::U128(*i),
             (MoveValue::Address(a), FatType::Address) => AnnotatedMoveValue::Address(*a),
+            (MoveValue::Signer(a), FatType::Signer) => AnnotatedMoveValue::Signer(*a),
             (MoveValue::Vector(a), FatType::Vector(ty)) => match ty.as_ref() {
                 FatType::U8 => AnnotatedMoveValue::Bytes(
                     a.iter()
@@ -223,6 +225,7 @@ fn pretty_print_value(
         AnnotatedMoveValue::U64(v) => write!(f, "{}", v),
         AnnotatedMoveValue::U128(v) => write!(f, "{}u128", v),
         AnnotatedMoveValue::Address(a) => write!(f, "0x{:#x}", a),
+        AnnotatedMoveValue::Signer(a) => write!(f, "signer(0x{:#x})", a),
         AnnotatedMoveValue::Vector(v) => {
             writeln!(f, "[")?;
             for value in v.iter() {
@@ -238,6 +238,17 @@ pub fn encode_create_account_script_function(
     )
 }
 
+pub fn encode_rotate_authentication_key_script_function(
+    new_auth_key: AuthenticationKey,
+) -> ScriptFunction {
+    ScriptFunction::new(
+        ModuleId::new(core_code_address(), Identifier::new("Account").unwrap()),
+        Identifier::new("rotate_authentication_key").unwrap(),
+        vec![],
+        vec![bcs_ext::to_bytes(&new_auth_key.to_vec()).unwrap()],
+    )
+}
+
 pub fn encode_transfer_script_function(
     version: StdlibVersion,
     recipient: AccountAddress,
@@ -275,6 +286,44 @@ pub fn encode_transfer_script_by_token_code(
     )
 }
 
+/// The first stdlib version which provides `TransferScripts::batch_peer_to_peer`.
+pub const BATCH_TRANSFER_STDLIB_VERSION: u64 = 3;
+
+/// Check whether the `TransferScripts::batch_peer_to_peer` script function is available in the stdlib `version`.
+pub fn is_batch_transfer_supported(version: StdlibVersion) -> bool {
+    match version {
+        StdlibVersion::Latest => true,
+        StdlibVersion::Version(version) => version >= BATCH_TRANSFER_STDLIB_VERSION,
+    }
+}
+
+/// Transfer different amount of token to each payee in one txn, the payee which does not exist on chain
+/// is created with the auth key.
+pub fn encode_batch_transfer_script_by_token_code(
+    payees: Vec<AccountAddress>,
+    payee_auth_keys: Vec<Option<AuthenticationKey>>,
+    amounts: Vec<u128>,
+    token_code: TokenCode,
+) -> ScriptFunction {
+    let payee_auth_keys: Vec<Vec<u8>> = payee_auth_keys
+        .into_iter()
+        .map(|k| k.map(|k| k.to_vec()).unwrap_or_default())
+        .collect();
+    ScriptFunction::new(
+        ModuleId::new(
+            core_code_address(),
+            Identifier::new("TransferScripts").unwrap(),
+        ),
+        Identifier::new("batch_peer_to_peer").unwrap(),
+        vec![token_code.into()],
+        vec![
+            bcs_ext::to_bytes(&payees).unwrap(),
+            bcs_ext::to_bytes(&payee_auth_keys).unwrap(),
+            bcs_ext::to_bytes(&amounts).unwrap(),
+        ],
+    )
+}
+
 pub fn peer_to_peer_txn_sent_as_association(
     recipient: AccountAddress,
     recipient_auth_key: Option<AuthenticationKey>,
@@ -12,6 +12,7 @@ use move_core_types::{
     move_resource::MoveResource,
 };
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 
 /// The balance resource held under an account.
 #[derive(Debug, Serialize, Deserialize)]
@@ -42,6 +43,18 @@ impl BalanceResource {
         Self::struct_tag_for_token(token_code.into())
     }
 
+    /// The token code of the balance, None if the struct tag is not a `Balance<Token>`.
+    pub fn token_code_of(struct_tag: &StructTag) -> Option<TokenCode> {
+        if struct_tag.address != CORE_CODE_ADDRESS
+            || struct_tag.module.as_str() != Self::MODULE_NAME
+            || struct_tag.name.as_str() != Self::STRUCT_NAME
+            || struct_tag.type_params.len() != 1
+        {
+            return None;
+        }
+        TokenCode::try_from(struct_tag.type_params[0].clone()).ok()
+    }
+
     // TODO: remove this once the MoveResource trait allows type arguments to `resource_path`.
     pub fn access_path_for(token_type_tag: TypeTag) -> DataPath {
         AccessPath::resource_data_path(BalanceResource::struct_tag_for_token(token_type_tag))
@@ -44,6 +44,14 @@ pub struct TwoPhaseUpgradeV2Resource {
     upgrade_event: EventHandle,
 }
 impl TwoPhaseUpgradeV2Resource {
+    pub fn config(&self) -> &TwoPhaseUpgradeConfigResource {
+        &self.config
+    }
+
+    pub fn plan(&self) -> Option<&UpgradePlanV2Resource> {
+        self.plan.as_ref()
+    }
+
     pub fn enforced(&self) -> bool {
         match &self.plan {
             Some(plan) => plan.enforced,
@@ -64,6 +72,12 @@ pub fn access_path_for_two_phase_upgrade_v2(address: AccountAddress) -> AccessPa
 pub struct TwoPhaseUpgradeConfigResource {
     min_time_limit: u64,
 }
+impl TwoPhaseUpgradeConfigResource {
+    /// The min delay in milliseconds between the plan submitted and the package can be deployed.
+    pub fn min_time_limit(&self) -> u64 {
+        self.min_time_limit
+    }
+}
 impl MoveResource for TwoPhaseUpgradeConfigResource {
     const MODULE_NAME: &'static str = "PackageTxnManager";
     const STRUCT_NAME: &'static str = "TwoPhaseUpgradeConfig";
@@ -76,6 +90,24 @@ pub struct UpgradePlanV2Resource {
     version: u64,
     enforced: bool,
 }
+impl UpgradePlanV2Resource {
+    pub fn package_hash(&self) -> &[u8] {
+        self.package_hash.as_slice()
+    }
+
+    /// The package can be deployed when the on-chain time in milliseconds reaches it.
+    pub fn active_after_time(&self) -> u64 {
+        self.active_after_time
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn enforced(&self) -> bool {
+        self.enforced
+    }
+}
 impl MoveResource for UpgradePlanV2Resource {
     const MODULE_NAME: &'static str = "PackageTxnManager";
     const STRUCT_NAME: &'static str = "UpgradePlanV2";
@@ -14,7 +14,8 @@ use vm::{
     file_format_common::instruction_key,
 };
 
-pub fn initial_instruction_table() -> Vec<GasCost> {
+/// The instructions sorted by the instruction order, with their initial gas costs.
+fn initial_instructions() -> Vec<(Bytecode, GasCost)> {
     use Bytecode::*;
     let mut instrs = vec![
         (MoveTo(StructDefinitionIndex::new(0)), GasCost::new(13, 1)),
@@ -135,10 +136,32 @@ pub fn initial_instruction_table() -> Vec<GasCost> {
             "all instructions must be in the cost table"
         );
     }
-    instrs.into_iter().map(|(_, cost)| cost).collect::<Vec<_>>()
+    instrs
 }
 
-pub fn initial_native_table() -> Vec<GasCost> {
+pub fn initial_instruction_table() -> Vec<GasCost> {
+    initial_instructions()
+        .into_iter()
+        .map(|(_, cost)| cost)
+        .collect::<Vec<_>>()
+}
+
+/// The names of the instructions, in the order of the instruction table.
+pub fn instruction_names() -> Vec<String> {
+    initial_instructions()
+        .into_iter()
+        .map(|(instr, _)| {
+            let name = format!("{:?}", instr);
+            match name.find('(') {
+                Some(end) => name[..end].to_string(),
+                None => name,
+            }
+        })
+        .collect()
+}
+
+/// The natives sorted by the native cost index, with their initial gas costs.
+fn initial_natives() -> Vec<(N, GasCost)> {
     let mut raw_native_table = vec![
         (N::SHA2_256, GasCost::new(21, 1)),
         (N::SHA3_256, GasCost::new(64, 1)),
@@ -163,7 +186,11 @@ pub fn initial_native_table() -> Vec<GasCost> {
         (N::KECCAK_256, GasCost::new(64, 1)),
     ];
     raw_native_table.sort_by_key(|cost| cost.0 as u64);
-    let native_table = raw_native_table
+    raw_native_table
+}
+
+pub fn initial_native_table() -> Vec<GasCost> {
+    let native_table = initial_natives()
         .into_iter()
         .map(|(_, cost)| cost)
         .collect::<Vec<_>>();
@@ -175,6 +202,14 @@ pub fn initial_native_table() -> Vec<GasCost> {
     native_table
 }
 
+/// The names of the natives, in the order of the native table.
+pub fn native_names() -> Vec<String> {
+    initial_natives()
+        .into_iter()
+        .map(|(native, _)| format!("{:?}", native))
+        .collect()
+}
+
 pub fn v1_native_table() -> Vec<GasCost> {
     let mut raw_native_table = vec![
         (N::SHA2_256, GasCost::new(21, 1)),
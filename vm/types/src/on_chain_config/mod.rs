@@ -26,7 +26,8 @@ pub use self::{
     consensus_config::{consensus_config_type_tag, ConsensusConfig, CONSENSUS_CONFIG_IDENTIFIER},
     dao_config::DaoConfig,
     genesis_gas_schedule::{
-        init_cost_table, initial_instruction_table, initial_native_table, v1_native_table,
+        init_cost_table, initial_instruction_table, initial_native_table, instruction_names,
+        native_names, v1_native_table,
     },
     version::{version_config_type_tag, Version, VERSION_CONFIG_IDENTIFIER},
     vm_config::{vm_config_type_tag, TransactionPublishOption, VMConfig, SCRIPT_HASH_LENGTH},
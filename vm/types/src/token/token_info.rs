@@ -14,7 +14,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TokenInfoResource {
     total_value: u128,
-    fractional_part: u64,
+    scaling_factor: u128,
     mint_events: EventHandle,
     burn_events: EventHandle,
 }
@@ -25,8 +25,12 @@ impl MoveResource for TokenInfoResource {
 }
 
 impl TokenInfoResource {
-    pub fn fractional_part(&self) -> u64 {
-        self.fractional_part
+    pub fn total_value(&self) -> u128 {
+        self.total_value
+    }
+
+    pub fn scaling_factor(&self) -> u128 {
+        self.scaling_factor
     }
 
     pub fn struct_tag_for(token_code: TokenCode) -> StructTag {
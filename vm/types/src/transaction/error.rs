@@ -65,6 +65,8 @@ pub enum Error {
     InvalidSignature(String),
     /// Transaction too big
     TooBig,
+    /// The node is shutting down and does not accept new transactions.
+    ShuttingDown,
     CallErr(CallError),
 }
 
@@ -102,6 +104,7 @@ impl fmt::Display for Error {
                 "Sender does not have permissions to execute this type of transaction".into()
             }
             TooBig => "Transaction too big".into(),
+            ShuttingDown => "Node is shutting down".into(),
             CallErr(call_err) => format!("Call txn err: {}.", call_err),
         };
 
@@ -9,6 +9,7 @@ use crate::block_metadata::BlockMetadata;
 use crate::genesis_config::ChainId;
 use crate::transaction::authenticator::{AccountPublicKey, TransactionAuthenticator};
 use crate::{
+    access_path::AccessPath,
     account_address::AccountAddress,
     contract_event::ContractEvent,
     vm_status::{DiscardedVMStatus, KeptVMStatus},
@@ -50,6 +51,7 @@ mod module;
 mod package;
 mod pending_transaction;
 mod script;
+pub mod signature_verifier;
 #[cfg(test)]
 mod tests;
 mod transaction_argument;
@@ -374,6 +376,28 @@ pub struct DryRunTransaction {
     pub public_key: AccountPublicKey,
 }
 
+/// Where the gas of a dry run txn goes.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GasBreakdown {
+    /// The gas charged by the size of the txn before the execution.
+    pub intrinsic_gas: u64,
+    /// The gas charged by the instructions, include the natives and the global storage instructions.
+    pub instruction_gas: u64,
+    /// The gas charged by the accounts mutated by the write set.
+    pub storage_write_gas: u64,
+    /// The gas charged by every executed instruction, such as `MoveTo`, the instructions charged no gas are omitted.
+    pub instructions: Vec<(String, u64)>,
+    /// The gas charged by every called native function, such as `PUSH_BACK`.
+    pub natives: Vec<(String, u64)>,
+    /// The state reads which miss the cache of the vm.
+    pub storage_reads: u64,
+    pub storage_read_bytes: u64,
+    pub storage_writes: u64,
+    pub storage_write_bytes: u64,
+    /// The size of every write op of the write set, None for a deletion.
+    pub write_ops: Vec<(AccessPath, Option<u64>)>,
+}
+
 /// A transaction for which the signature has been verified. Created by
 /// [`SignedUserTransaction::check_signature`] and [`RawUserTransaction::sign`].
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -671,6 +695,24 @@ impl TransactionInfo {
         }
     }
 
+    /// Constructs a `TransactionInfo` object with a precomputed event root hash,
+    /// used to rebuild a txn info from its fields.
+    pub fn new_with_event_root_hash(
+        transaction_hash: HashValue,
+        state_root_hash: HashValue,
+        event_root_hash: HashValue,
+        gas_used: u64,
+        status: KeptVMStatus,
+    ) -> TransactionInfo {
+        TransactionInfo {
+            transaction_hash,
+            state_root_hash,
+            event_root_hash,
+            gas_used,
+            status,
+        }
+    }
+
     pub fn id(&self) -> HashValue {
         self.crypto_hash()
     }
@@ -748,6 +790,59 @@ impl BlockTransactionInfo {
     }
 }
 
+/// The position of a txn in the block which includes it, and in the txn accumulator.
+/// One txn may be included by the blocks of different branches, so it may have multiple positions.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TransactionPosition {
+    pub block_id: HashValue,
+    pub block_number: u64,
+    /// The index in the execution order of the block, the block metadata txn is at 0, so the user txns start at 1.
+    pub transaction_index: u32,
+    /// The leaf index of the txn info in the txn accumulator.
+    pub global_index: u64,
+}
+
+impl TransactionPosition {
+    /// The positions of the txns executed by the block, in execution order.
+    /// `txn_count` is the count of the executed txns, it is one more than the `body_txn_count`
+    /// for the block metadata txn, except for the genesis block, which has no block metadata txn.
+    /// `txn_accumulator_leaves` is the leaves count of the txn accumulator after the block is executed.
+    pub fn block_positions(
+        block_id: HashValue,
+        block_number: u64,
+        body_txn_count: usize,
+        txn_count: usize,
+        txn_accumulator_leaves: u64,
+    ) -> Result<Vec<Self>> {
+        let index_offset = (body_txn_count + 1)
+            .checked_sub(txn_count)
+            .filter(|offset| *offset <= 1)
+            .ok_or_else(|| {
+                format_err!(
+                    "block {} executed {} txns, but its body has {} txns",
+                    block_id,
+                    txn_count,
+                    body_txn_count
+                )
+            })?;
+        let start_global_index = txn_accumulator_leaves
+            .checked_sub(txn_count as u64)
+            .ok_or_else(|| format_err!("invalid txn accumulator leaves of block {}", block_id))?;
+        Ok((0..txn_count)
+            .map(|i| Self {
+                block_id,
+                block_number,
+                transaction_index: (i + index_offset) as u32,
+                global_index: start_global_index + i as u64,
+            })
+            .collect())
+    }
+
+    pub fn is_block_metadata(&self) -> bool {
+        self.transaction_index == 0
+    }
+}
+
 /// `Transaction` will be the transaction type used internally in the diem node to represent the
 /// transaction to be processed and persisted.
 ///
@@ -806,6 +901,8 @@ pub enum TxStatus {
     Canceled,
     /// Culled transaction
     Culled,
+    /// Evicted transaction, it is pushed out by a higher gas price one when the pool is full.
+    Evicted,
 }
 
 impl std::fmt::Display for TxStatus {
@@ -817,6 +914,7 @@ impl std::fmt::Display for TxStatus {
             TxStatus::Invalid => "invalid",
             TxStatus::Canceled => "canceled",
             TxStatus::Culled => "culled",
+            TxStatus::Evicted => "evicted",
         };
         write!(f, "{}", s)
     }
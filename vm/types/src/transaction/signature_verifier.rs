@@ -0,0 +1,96 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::transaction::{SignatureCheckedTransaction, SignedUserTransaction};
+use anyhow::{format_err, Result};
+use lru::LruCache;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use rayon::prelude::*;
+use starcoin_crypto::HashValue;
+
+/// How many verified transaction hashes are cached by the global verifier.
+pub const DEFAULT_VERIFIED_CACHE_SIZE: usize = 65536;
+
+static GLOBAL_SIGNATURE_VERIFIER: Lazy<SignatureVerifier> =
+    Lazy::new(|| SignatureVerifier::new(DEFAULT_VERIFIED_CACHE_SIZE));
+
+/// Verify the signatures of user transactions, and cache the hashes of the verified transactions.
+/// The transaction hash covers the authenticator, so a cached hash means the same signature is valid.
+pub struct SignatureVerifier {
+    verified: Mutex<LruCache<HashValue, ()>>,
+}
+
+impl SignatureVerifier {
+    pub fn new(cache_size: usize) -> Self {
+        Self {
+            verified: Mutex::new(LruCache::new(cache_size)),
+        }
+    }
+
+    /// The verifier shared by the txpool and the block execution,
+    /// so a txn verified on pool entry is not verified again when its block is executed.
+    pub fn global() -> &'static SignatureVerifier {
+        &GLOBAL_SIGNATURE_VERIFIER
+    }
+
+    pub fn is_verified(&self, txn_hash: &HashValue) -> bool {
+        self.verified.lock().get(txn_hash).is_some()
+    }
+
+    /// Verify the signature of the `txn`, return false if the signature is invalid.
+    pub fn verify(&self, txn: &SignedUserTransaction) -> bool {
+        let txn_hash = txn.id();
+        if self.is_verified(&txn_hash) {
+            return true;
+        }
+        let valid = txn.authenticator.verify(&txn.raw_txn).is_ok();
+        if valid {
+            self.verified.lock().put(txn_hash, ());
+        }
+        valid
+    }
+
+    /// Verify the signatures of the `txns` in parallel, the result is in the same order as `txns`.
+    pub fn batch_verify(&self, txns: &[SignedUserTransaction]) -> Vec<bool> {
+        txns.par_iter().map(|txn| self.verify(txn)).collect()
+    }
+
+    /// Same as `SignedUserTransaction::check_signature`, but use the cache.
+    pub fn check_signature(
+        &self,
+        txn: SignedUserTransaction,
+    ) -> Result<SignatureCheckedTransaction> {
+        if self.verify(&txn) {
+            Ok(SignatureCheckedTransaction(txn))
+        } else {
+            Err(format_err!("Invalid signature of txn {}", txn.id()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_verify() {
+        let verifier = SignatureVerifier::new(16);
+        let txns: Vec<_> = (0..8).map(|_| SignedUserTransaction::mock()).collect();
+        // sign the raw txn of txns[0] with another txn's signature.
+        let invalid = SignedUserTransaction::new(
+            txns[0].raw_txn().clone(),
+            SignedUserTransaction::mock().authenticator(),
+        );
+        let mut all = txns.clone();
+        all.push(invalid.clone());
+        let result = verifier.batch_verify(all.as_slice());
+        assert_eq!(result.len(), 9);
+        assert!(result[..8].iter().all(|valid| *valid));
+        assert!(!result[8]);
+        assert!(txns.iter().all(|txn| verifier.is_verified(&txn.id())));
+        assert!(!verifier.is_verified(&invalid.id()));
+        assert!(verifier.check_signature(txns[0].clone()).is_ok());
+        assert!(verifier.check_signature(invalid).is_err());
+    }
+}
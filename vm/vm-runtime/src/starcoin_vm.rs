@@ -33,7 +33,7 @@ use starcoin_vm_types::account_config::{
 };
 use starcoin_vm_types::contract_event::ContractEvent;
 use starcoin_vm_types::file_format::CompiledModule;
-use starcoin_vm_types::gas_schedule::{zero_cost_schedule, CostStrategy};
+use starcoin_vm_types::gas_schedule::{zero_cost_schedule, CostStrategy, GasCost};
 use starcoin_vm_types::identifier::IdentStr;
 use starcoin_vm_types::language_storage::ModuleId;
 use starcoin_vm_types::transaction::{DryRunTransaction, Module, Package, TransactionPayloadType};
@@ -54,6 +54,7 @@ use starcoin_vm_types::{
     vm_status::{StatusCode, VMStatus},
 };
 use std::convert::TryFrom;
+use std::ops::Range;
 use std::sync::Arc;
 
 #[derive(Clone)]
@@ -63,6 +64,7 @@ pub struct StarcoinVM {
     move_vm: Arc<MoveVMAdapter>,
     vm_config: Option<VMConfig>,
     version: Option<Version>,
+    charged_gas_costs: Option<Range<usize>>,
 }
 
 impl Default for StarcoinVM {
@@ -78,19 +80,42 @@ impl StarcoinVM {
             move_vm: Arc::new(inner),
             vm_config: None,
             version: None,
+            charged_gas_costs: None,
         }
     }
 
+    /// Only charge the gas costs of the gas schedule whose index is in `charged`, the others are free,
+    /// the index of a native is offset by the length of the instruction table.
+    /// It is only used by the dry run to measure the gas charged by every instruction and native.
+    pub fn set_charged_gas_costs(&mut self, charged: Option<Range<usize>>) {
+        self.charged_gas_costs = charged;
+    }
+
     fn load_configs(&mut self, state: &dyn StateView) -> Result<(), Error> {
         if state.is_genesis() {
             self.vm_config = Some(VMConfig {
                 gas_schedule: INITIAL_GAS_SCHEDULE.clone(),
             });
             self.version = Some(Version { major: 0 });
-            Ok(())
         } else {
-            self.load_configs_impl(state)
+            self.load_configs_impl(state)?;
+        }
+        if let (Some(charged), Some(vm_config)) =
+            (self.charged_gas_costs.as_ref(), self.vm_config.as_mut())
+        {
+            let gas_schedule = &mut vm_config.gas_schedule;
+            for (index, cost) in gas_schedule
+                .instruction_table
+                .iter_mut()
+                .chain(gas_schedule.native_table.iter_mut())
+                .enumerate()
+            {
+                if !charged.contains(&index) {
+                    *cost = GasCost::new(0, 0);
+                }
+            }
         }
+        Ok(())
     }
 
     fn load_configs_impl(&mut self, state: &dyn StateView) -> Result<(), Error> {
@@ -240,7 +265,7 @@ impl StarcoinVM {
         txn: SignedUserTransaction,
     ) -> Option<VMStatus> {
         let data_cache = StateViewCache::new(state_view);
-        let signature_verified_txn = match txn.check_signature() {
+        let signature_verified_txn = match SignatureVerifier::global().check_signature(txn) {
             Ok(t) => t,
             Err(_) => return Some(VMStatus::Error(StatusCode::INVALID_SIGNATURE)),
         };
@@ -658,9 +683,11 @@ impl StarcoinVM {
         )
     }
 
+    /// Execute the user `txn`, its signature is verified in batch before, the result is `signature_verified`.
     fn execute_user_transaction(
         &mut self,
         txn: SignedUserTransaction,
+        signature_verified: bool,
         remote_cache: &mut StateViewCache<'_>,
     ) -> (VMStatus, TransactionOutput) {
         let gas_schedule = match self.get_gas_schedule() {
@@ -681,60 +708,48 @@ impl StarcoinVM {
             }
         };
         let mut cost_strategy = CostStrategy::system(gas_schedule, txn_data.max_gas_amount());
-        // check signature
-        let signature_checked_txn = match txn.check_signature() {
-            Ok(t) => Ok(t),
-            Err(_) => Err(VMStatus::Error(StatusCode::INVALID_SIGNATURE)),
+        if !signature_verified {
+            return discard_error_vm_status(VMStatus::Error(StatusCode::INVALID_SIGNATURE));
+        }
+        let result = match txn.payload() {
+            payload @ TransactionPayload::Script(_)
+            | payload @ TransactionPayload::ScriptFunction(_) => self
+                .execute_script_or_script_function(
+                    remote_cache,
+                    gas_schedule,
+                    &mut cost_strategy,
+                    &txn_data,
+                    payload,
+                ),
+            TransactionPayload::Package(p) => {
+                self.execute_package(remote_cache, gas_schedule, &mut cost_strategy, &txn_data, p)
+            }
         };
-
-        match signature_checked_txn {
-            Ok(txn) => {
-                let result = match txn.payload() {
-                    payload @ TransactionPayload::Script(_)
-                    | payload @ TransactionPayload::ScriptFunction(_) => self
-                        .execute_script_or_script_function(
-                            remote_cache,
-                            gas_schedule,
-                            &mut cost_strategy,
-                            &txn_data,
-                            payload,
-                        ),
-                    TransactionPayload::Package(p) => self.execute_package(
-                        remote_cache,
+        match result {
+            Ok(status_and_output) => {
+                log_vm_status(
+                    txn_id,
+                    &txn_data,
+                    &status_and_output.0,
+                    Some(&status_and_output.1),
+                );
+                status_and_output
+            }
+            Err(err) => {
+                let txn_status = TransactionStatus::from(err.clone());
+                log_vm_status(txn_id, &txn_data, &err, None);
+                if txn_status.is_discarded() {
+                    discard_error_vm_status(err)
+                } else {
+                    self.failed_transaction_cleanup(
+                        err,
                         gas_schedule,
-                        &mut cost_strategy,
+                        cost_strategy.remaining_gas(),
                         &txn_data,
-                        p,
-                    ),
-                };
-                match result {
-                    Ok(status_and_output) => {
-                        log_vm_status(
-                            txn_id,
-                            &txn_data,
-                            &status_and_output.0,
-                            Some(&status_and_output.1),
-                        );
-                        status_and_output
-                    }
-                    Err(err) => {
-                        let txn_status = TransactionStatus::from(err.clone());
-                        log_vm_status(txn_id, &txn_data, &err, None);
-                        if txn_status.is_discarded() {
-                            discard_error_vm_status(err)
-                        } else {
-                            self.failed_transaction_cleanup(
-                                err,
-                                gas_schedule,
-                                cost_strategy.remaining_gas(),
-                                &txn_data,
-                                remote_cache,
-                            )
-                        }
-                    }
+                        remote_cache,
+                    )
                 }
             }
-            Err(e) => discard_error_vm_status(e),
         }
     }
 
@@ -822,10 +837,17 @@ impl StarcoinVM {
         'outer: for block in blocks {
             match block {
                 TransactionBlock::UserTransaction(txns) => {
-                    for transaction in txns {
+                    // verify all the signatures in parallel before the sequential execution.
+                    let signature_verified = SignatureVerifier::global().batch_verify(&txns);
+                    for (transaction, signature_verified) in
+                        txns.into_iter().zip(signature_verified)
+                    {
                         let gas_unit_price = transaction.gas_unit_price();
-                        let (status, output) =
-                            self.execute_user_transaction(transaction, &mut data_cache);
+                        let (status, output) = self.execute_user_transaction(
+                            transaction,
+                            signature_verified,
+                            &mut data_cache,
+                        );
                         // only need to check for user transactions.
                         match gas_left.checked_sub(output.gas_used()) {
                             Some(l) => gas_left = l,
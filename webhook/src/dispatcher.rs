@@ -0,0 +1,198 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, format_err, Result};
+use futures_timer::Delay;
+use hmac::{Hmac, Mac, NewMac};
+use reqwest::header::CONTENT_TYPE;
+use reqwest::redirect::Policy;
+use reqwest::Url;
+use sha2::Sha256;
+use starcoin_config::WebhookConfig;
+use starcoin_logger::prelude::*;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+/// The header of the hex encoded HMAC-SHA256 signature of the request body.
+pub const SIGNATURE_HEADER: &str = "X-Starcoin-Signature";
+
+/// Sign the payload with the registration's secret, return the hex encoded signature.
+pub fn sign_payload(secret: &[u8], payload: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_varkey(secret).expect("HMAC can take key of any size");
+    mac.update(payload);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Whether the ip is reachable from the public network.
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let octets = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                // the shared address space 100.64.0.0/10
+                || (octets[0] == 100 && (octets[1] & 0xc0) == 64))
+        }
+        IpAddr::V6(ip) => {
+            if ip.is_loopback() || ip.is_unspecified() {
+                return false;
+            }
+            if let Some(ip) = ip.to_ipv4() {
+                return is_public_ip(IpAddr::V4(ip));
+            }
+            let first = ip.segments()[0];
+            // the unique local fc00::/7 and the link local fe80::/10
+            (first & 0xfe00) != 0xfc00 && (first & 0xffc0) != 0xfe80
+        }
+    }
+}
+
+fn ensure_public_addrs(url: &Url, addrs: &[SocketAddr]) -> Result<()> {
+    ensure!(!addrs.is_empty(), "Can not resolve the webhook url {}", url);
+    for addr in addrs {
+        ensure!(
+            is_public_ip(addr.ip()),
+            "The webhook url {} targets the non public address {}",
+            url,
+            addr.ip()
+        );
+    }
+    Ok(())
+}
+
+/// Reject the url which resolves to a loopback, private, link local or unspecified address,
+/// so the webhooks registered by rpc can not be used to probe the network of the node.
+/// The host is resolved without blocking, and resolved again before delivering,
+/// as the dns record may be changed after the registration.
+pub async fn ensure_public_target(url: &str) -> Result<()> {
+    let url = Url::parse(url)?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| format_err!("The webhook url {} has no host", url))?
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| format_err!("The webhook url {} has no port", url))?;
+    let addrs = tokio::net::lookup_host((host, port))
+        .await?
+        .collect::<Vec<_>>();
+    ensure_public_addrs(&url, addrs.as_slice())
+}
+
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    client: reqwest::Client,
+    max_retry: u32,
+    retry_interval: Duration,
+    allow_private_targets: bool,
+}
+
+impl WebhookDispatcher {
+    pub fn new(config: &WebhookConfig) -> Result<Self> {
+        Ok(Self {
+            // do not follow the redirects, the redirected url may target a private address.
+            client: reqwest::Client::builder()
+                .timeout(config.timeout())
+                .redirect(Policy::none())
+                .build()?,
+            max_retry: config.max_retry(),
+            retry_interval: config.retry_interval(),
+            allow_private_targets: config.allow_private_targets(),
+        })
+    }
+
+    /// POST the payload to the url, retry with exponential backoff until the url responds success,
+    /// or give up after `max_retry` retries.
+    /// The url of the `trusted` webhook, which is registered by config, is allowed to target a private address.
+    pub async fn deliver(self, url: String, secret: String, payload: Vec<u8>, trusted: bool) {
+        if !trusted && !self.allow_private_targets {
+            if let Err(e) = ensure_public_target(url.as_str()).await {
+                warn!("Refuse to deliver webhook to {}: {:?}", url, e);
+                return;
+            }
+        }
+        let signature = sign_payload(secret.as_bytes(), payload.as_slice());
+        let mut retry_interval = self.retry_interval;
+        for attempt in 0..=self.max_retry {
+            let result = self
+                .client
+                .post(url.as_str())
+                .header(CONTENT_TYPE, "application/json")
+                .header(SIGNATURE_HEADER, signature.as_str())
+                .body(payload.clone())
+                .send()
+                .await;
+            match result {
+                Ok(response) if response.status().is_success() => return,
+                Ok(response) => warn!(
+                    "Deliver webhook to {} failed, attempt: {}, status: {}",
+                    url,
+                    attempt,
+                    response.status()
+                ),
+                Err(e) => warn!(
+                    "Deliver webhook to {} failed, attempt: {}, error: {:?}",
+                    url, attempt, e
+                ),
+            }
+            if attempt < self.max_retry {
+                Delay::new(retry_interval).await;
+                retry_interval *= 2;
+            }
+        }
+        error!(
+            "Give up delivering webhook to {} after {} retries.",
+            url, self.max_retry
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_payload() {
+        // the test vector of RFC 4231 test case 2.
+        assert_eq!(
+            sign_payload(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[stest::test]
+    async fn test_ensure_public_target() {
+        for url in &[
+            "http://127.0.0.1:9850",
+            "http://localhost/hook",
+            "http://10.0.0.1",
+            "http://172.16.3.4",
+            "http://192.168.1.1",
+            "http://169.254.169.254/latest/meta-data",
+            "http://100.64.0.1",
+            "http://0.0.0.0",
+            "http://[::1]:8080",
+            "http://[fd00::1]",
+            "http://[fe80::1]",
+            "http://[::ffff:127.0.0.1]",
+        ] {
+            assert!(
+                ensure_public_target(url).await.is_err(),
+                "{} should be rejected",
+                url
+            );
+        }
+        for url in &["http://8.8.8.8/hook", "https://[2001:4860:4860::8888]"] {
+            assert!(
+                ensure_public_target(url).await.is_ok(),
+                "{} should be allowed",
+                url
+            );
+        }
+    }
+}
@@ -0,0 +1,13 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+mod dispatcher;
+mod service;
+#[cfg(test)]
+mod tests;
+
+pub use dispatcher::{ensure_public_target, sign_payload, WebhookDispatcher, SIGNATURE_HEADER};
+pub use service::{
+    ListWebhooksRequest, RegisterWebhookRequest, RemoveWebhookRequest, WebhookAsyncService,
+    WebhookService,
+};
@@ -0,0 +1,345 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dispatcher::WebhookDispatcher;
+use anyhow::{ensure, format_err, Result};
+use starcoin_config::NodeConfig;
+use starcoin_crypto::HashValue;
+use starcoin_logger::prelude::*;
+use starcoin_rpc_api::types::{TxnLifecycleStatus, WebhookPayload, WebhookView};
+use starcoin_service_registry::{
+    ActorService, EventHandler, ServiceContext, ServiceFactory, ServiceHandler, ServiceRef,
+    ServiceRequest,
+};
+use starcoin_storage::{BlockTransactionInfoStore, Storage, WebhookStore};
+use starcoin_txpool::TxPoolService;
+use starcoin_txpool_api::{TxPoolSyncService, TxnStatusFullEvent};
+use starcoin_types::system_events::NewHeadBlock;
+use starcoin_types::transaction::{SignedUserTransaction, TxStatus};
+use starcoin_types::webhook::{WebhookFilter, WebhookRegistration};
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::sync::Arc;
+
+/// Dispatch the lifecycle status changes of the matched transactions to the registered webhooks.
+pub struct WebhookService {
+    config: Arc<NodeConfig>,
+    storage: Arc<Storage>,
+    txpool: TxPoolService,
+    dispatcher: WebhookDispatcher,
+    /// The webhooks registered by config, they are not persisted.
+    config_hooks: Vec<WebhookRegistration>,
+    /// The webhooks registered by rpc, they are persisted in storage.
+    hooks: Vec<WebhookRegistration>,
+    /// The matched transactions in the txpool, keep them for notifying when they are dropped.
+    pending_txns: HashMap<HashValue, SignedUserTransaction>,
+}
+
+impl ServiceFactory<Self> for WebhookService {
+    fn create(ctx: &mut ServiceContext<WebhookService>) -> Result<WebhookService> {
+        let config = ctx.get_shared::<Arc<NodeConfig>>()?;
+        let storage = ctx.get_shared::<Arc<Storage>>()?;
+        let txpool = ctx.get_shared::<TxPoolService>()?;
+        let dispatcher = WebhookDispatcher::new(&config.webhook)?;
+        let config_hooks = config
+            .webhook
+            .hooks
+            .iter()
+            .map(|hook| {
+                // the id of the config webhook should be stable across restarts.
+                let id = HashValue::sha3_256_of(
+                    serde_json::to_vec(&(hook.url.as_str(), &hook.filter))?.as_slice(),
+                );
+                Ok(WebhookRegistration::new(
+                    id,
+                    hook.url.clone(),
+                    hook.filter.clone(),
+                    hook.secret.clone(),
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let hooks = storage.get_webhooks()?;
+        info!(
+            "Load {} webhooks from config, {} webhooks from storage.",
+            config_hooks.len(),
+            hooks.len()
+        );
+        Ok(Self {
+            config,
+            storage,
+            txpool,
+            dispatcher,
+            config_hooks,
+            hooks,
+            pending_txns: HashMap::new(),
+        })
+    }
+}
+
+impl ActorService for WebhookService {
+    fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.subscribe::<NewHeadBlock>();
+        ctx.add_stream(self.txpool.subscribe_txns());
+        Ok(())
+    }
+
+    fn stopped(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.unsubscribe::<NewHeadBlock>();
+        Ok(())
+    }
+}
+
+impl WebhookService {
+    fn matched_hooks(&self, txn: &SignedUserTransaction) -> Vec<WebhookRegistration> {
+        let sender = txn.sender();
+        let txn_hash = txn.id();
+        self.config_hooks
+            .iter()
+            .chain(self.hooks.iter())
+            .filter(|hook| hook.filter.matching(&sender, &txn_hash))
+            .cloned()
+            .collect()
+    }
+
+    fn notify(
+        &self,
+        hooks: Vec<WebhookRegistration>,
+        txn: SignedUserTransaction,
+        status: TxnLifecycleStatus,
+        ctx: &mut ServiceContext<Self>,
+    ) {
+        let txn_hash = txn.id();
+        let transaction = match txn.try_into() {
+            Ok(view) => Some(view),
+            Err(e) => {
+                warn!("Convert txn {} to view error: {:?}", txn_hash, e);
+                None
+            }
+        };
+        let timestamp = self.config.net().time_service().now_millis();
+        for hook in hooks {
+            let trusted = self.config_hooks.iter().any(|exist| exist.id == hook.id);
+            let payload = WebhookPayload {
+                webhook_id: hook.id,
+                transaction_hash: txn_hash,
+                status: status.clone(),
+                transaction: transaction.clone(),
+                timestamp: timestamp.into(),
+            };
+            match serde_json::to_vec(&payload) {
+                Ok(payload) => ctx.spawn(self.dispatcher.clone().deliver(
+                    hook.url,
+                    hook.secret,
+                    payload,
+                    trusted,
+                )),
+                Err(e) => error!("Serialize webhook payload error: {:?}", e),
+            }
+        }
+    }
+
+    /// A txn is culled from the txpool when it is included in a block, or when it is expired or stale.
+    fn culled_status(&self, txn: &SignedUserTransaction) -> Result<Option<TxnLifecycleStatus>> {
+        if !self
+            .storage
+            .get_transaction_info_ids_by_hash(txn.id())?
+            .is_empty()
+        {
+            // the included status is notified by the new head block.
+            return Ok(None);
+        }
+        let now = self.config.net().time_service().now_secs();
+        if txn.expiration_timestamp_secs() <= now {
+            Ok(Some(TxnLifecycleStatus::Expired))
+        } else {
+            Ok(Some(TxnLifecycleStatus::Dropped {
+                reason: "culled".to_string(),
+            }))
+        }
+    }
+}
+
+impl EventHandler<Self, TxnStatusFullEvent> for WebhookService {
+    fn handle_event(&mut self, item: TxnStatusFullEvent, ctx: &mut ServiceContext<Self>) {
+        if self.config_hooks.is_empty() && self.hooks.is_empty() {
+            return;
+        }
+        for (txn_hash, status) in item.iter() {
+            match status {
+                TxStatus::Added => {
+                    if let Some(txn) = self.txpool.find_txn(txn_hash) {
+                        let hooks = self.matched_hooks(&txn);
+                        if !hooks.is_empty() {
+                            self.pending_txns.insert(*txn_hash, txn.clone());
+                            self.notify(hooks, txn, TxnLifecycleStatus::Pending, ctx);
+                        }
+                    }
+                }
+                // the rejected txn never enters the txpool.
+                TxStatus::Rejected => {}
+                TxStatus::Culled => {
+                    if let Some(txn) = self.pending_txns.remove(txn_hash) {
+                        match self.culled_status(&txn) {
+                            Ok(Some(status)) => {
+                                let hooks = self.matched_hooks(&txn);
+                                self.notify(hooks, txn, status, ctx);
+                            }
+                            Ok(None) => {}
+                            Err(e) => error!("Check culled txn {} error: {:?}", txn_hash, e),
+                        }
+                    }
+                }
+                TxStatus::Dropped | TxStatus::Evicted | TxStatus::Invalid | TxStatus::Canceled => {
+                    if let Some(txn) = self.pending_txns.remove(txn_hash) {
+                        let hooks = self.matched_hooks(&txn);
+                        let reason = format!("{:?}", status).to_lowercase();
+                        self.notify(hooks, txn, TxnLifecycleStatus::Dropped { reason }, ctx);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl EventHandler<Self, NewHeadBlock> for WebhookService {
+    fn handle_event(&mut self, msg: NewHeadBlock, ctx: &mut ServiceContext<Self>) {
+        if self.config_hooks.is_empty() && self.hooks.is_empty() {
+            return;
+        }
+        let block = msg.0.block();
+        for txn in block.transactions() {
+            self.pending_txns.remove(&txn.id());
+            let hooks = self.matched_hooks(txn);
+            if !hooks.is_empty() {
+                let status = TxnLifecycleStatus::Included {
+                    block_number: block.header().number().into(),
+                    block_hash: block.id(),
+                };
+                self.notify(hooks, txn.clone(), status, ctx);
+            }
+        }
+    }
+}
+
+/// The url target is not resolved by the service, the caller should check it by
+/// `ensure_public_target` first if the private targets are not allowed, the dns lookup may be slow.
+#[derive(Clone, Debug)]
+pub struct RegisterWebhookRequest {
+    pub url: String,
+    pub filter: WebhookFilter,
+}
+
+impl ServiceRequest for RegisterWebhookRequest {
+    type Response = Result<WebhookRegistration>;
+}
+
+impl ServiceHandler<Self, RegisterWebhookRequest> for WebhookService {
+    fn handle(
+        &mut self,
+        msg: RegisterWebhookRequest,
+        _ctx: &mut ServiceContext<Self>,
+    ) -> Result<WebhookRegistration> {
+        let url = reqwest::Url::parse(msg.url.as_str())?;
+        ensure!(
+            url.scheme() == "http" || url.scheme() == "https",
+            "Unsupported webhook url scheme: {}",
+            url.scheme()
+        );
+        ensure!(!msg.filter.is_empty(), "The webhook filter is empty.");
+        let max_hooks = self.config.webhook.max_hooks();
+        ensure!(
+            self.hooks.len() < max_hooks,
+            "Too many webhooks registered, the limit is {}.",
+            max_hooks
+        );
+        let registration = WebhookRegistration::new(
+            HashValue::random(),
+            msg.url,
+            msg.filter,
+            hex::encode(rand::random::<[u8; 32]>()),
+        );
+        self.storage.save_webhook(registration.clone())?;
+        self.hooks.push(registration.clone());
+        Ok(registration)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ListWebhooksRequest;
+
+impl ServiceRequest for ListWebhooksRequest {
+    type Response = Vec<WebhookView>;
+}
+
+impl ServiceHandler<Self, ListWebhooksRequest> for WebhookService {
+    fn handle(
+        &mut self,
+        _msg: ListWebhooksRequest,
+        _ctx: &mut ServiceContext<Self>,
+    ) -> Vec<WebhookView> {
+        self.config_hooks
+            .iter()
+            .map(|hook| WebhookView::new(hook, true))
+            .chain(self.hooks.iter().map(|hook| WebhookView::new(hook, false)))
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RemoveWebhookRequest {
+    pub id: HashValue,
+}
+
+impl ServiceRequest for RemoveWebhookRequest {
+    type Response = Result<()>;
+}
+
+impl ServiceHandler<Self, RemoveWebhookRequest> for WebhookService {
+    fn handle(&mut self, msg: RemoveWebhookRequest, _ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ensure!(
+            !self.config_hooks.iter().any(|hook| hook.id == msg.id),
+            "The webhook {} is registered by config, can not be removed.",
+            msg.id
+        );
+        self.storage
+            .remove_webhook(msg.id)?
+            .ok_or_else(|| format_err!("Can not find webhook {}", msg.id))?;
+        self.hooks.retain(|hook| hook.id != msg.id);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+pub trait WebhookAsyncService:
+    Clone + std::marker::Unpin + std::marker::Sync + std::marker::Send
+{
+    /// Register a webhook, return the registration with the generated id and secret.
+    async fn register_webhook(
+        &self,
+        url: String,
+        filter: WebhookFilter,
+    ) -> Result<WebhookRegistration>;
+
+    async fn list_webhooks(&self) -> Result<Vec<WebhookView>>;
+
+    async fn remove_webhook(&self, id: HashValue) -> Result<()>;
+}
+
+#[async_trait::async_trait]
+impl WebhookAsyncService for ServiceRef<WebhookService> {
+    async fn register_webhook(
+        &self,
+        url: String,
+        filter: WebhookFilter,
+    ) -> Result<WebhookRegistration> {
+        self.send(RegisterWebhookRequest { url, filter }).await?
+    }
+
+    async fn list_webhooks(&self) -> Result<Vec<WebhookView>> {
+        self.send(ListWebhooksRequest).await
+    }
+
+    async fn remove_webhook(&self, id: HashValue) -> Result<()> {
+        self.send(RemoveWebhookRequest { id }).await?
+    }
+}
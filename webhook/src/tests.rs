@@ -0,0 +1,264 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    sign_payload, WebhookAsyncService, WebhookDispatcher, WebhookService, SIGNATURE_HEADER,
+};
+use anyhow::Result;
+use futures_timer::Delay;
+use starcoin_config::{NodeConfig, WebhookConfig};
+use starcoin_rpc_api::types::{TxnLifecycleStatus, WebhookPayload};
+use starcoin_service_registry::RegistryAsyncService;
+use starcoin_transaction_builder::{peer_to_peer_txn_sent_as_association, DEFAULT_EXPIRATION_TIME};
+use starcoin_txpool_api::TxPoolSyncService;
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::account_config::association_address;
+use starcoin_types::webhook::WebhookFilter;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Clone, Debug)]
+struct ReceivedRequest {
+    signature: Option<String>,
+    body: Vec<u8>,
+}
+
+/// A http server which responds the `statuses` in order, the last status is repeated.
+struct MockWebhookServer {
+    url: String,
+    requests: Arc<Mutex<Vec<ReceivedRequest>>>,
+}
+
+impl MockWebhookServer {
+    fn start(statuses: Vec<u16>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+        let received = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => return,
+                };
+                let request = match read_request(&mut stream) {
+                    Some(request) => request,
+                    None => continue,
+                };
+                let mut received = received.lock().unwrap();
+                received.push(request);
+                let status = statuses
+                    .get(received.len() - 1)
+                    .or_else(|| statuses.last())
+                    .cloned()
+                    .unwrap_or(200);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {} Mock\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+            }
+        });
+        Self { url, requests }
+    }
+
+    fn requests(&self) -> Vec<ReceivedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    async fn wait_requests(&self, count: usize) -> Vec<ReceivedRequest> {
+        for _ in 0..100 {
+            let requests = self.requests();
+            if requests.len() >= count {
+                return requests;
+            }
+            Delay::new(Duration::from_millis(50)).await;
+        }
+        self.requests()
+    }
+}
+
+fn read_request(stream: &mut std::net::TcpStream) -> Option<ReceivedRequest> {
+    let mut data = vec![];
+    let mut buf = [0u8; 4096];
+    let header_end = loop {
+        let n = stream.read(&mut buf).ok()?;
+        if n == 0 {
+            return None;
+        }
+        data.extend_from_slice(&buf[..n]);
+        if let Some(pos) = data.windows(4).position(|window| window == b"\r\n\r\n") {
+            break pos + 4;
+        }
+    };
+    let header = String::from_utf8_lossy(&data[..header_end]).to_string();
+    let header_value = |name: &str| {
+        header.lines().find_map(|line| {
+            let mut parts = line.splitn(2, ':');
+            let key = parts.next()?.trim();
+            if key.eq_ignore_ascii_case(name) {
+                parts.next().map(|value| value.trim().to_string())
+            } else {
+                None
+            }
+        })
+    };
+    let content_length = header_value("content-length")
+        .and_then(|value| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    while data.len() < header_end + content_length {
+        let n = stream.read(&mut buf).ok()?;
+        if n == 0 {
+            break;
+        }
+        data.extend_from_slice(&buf[..n]);
+    }
+    Some(ReceivedRequest {
+        signature: header_value(SIGNATURE_HEADER),
+        body: data[header_end..].to_vec(),
+    })
+}
+
+fn test_webhook_config(max_retry: u32) -> WebhookConfig {
+    WebhookConfig {
+        enable: Some(true),
+        max_retry: Some(max_retry),
+        retry_interval: Some(10),
+        timeout: Some(1),
+        max_hooks: Some(2),
+        allow_private_targets: Some(true),
+        hooks: vec![],
+    }
+}
+
+#[stest::test]
+async fn test_deliver_retry() {
+    let server = MockWebhookServer::start(vec![500, 503, 200]);
+    let dispatcher = WebhookDispatcher::new(&test_webhook_config(5)).unwrap();
+    let payload = b"{\"status\":\"pending\"}".to_vec();
+    dispatcher
+        .deliver(
+            server.url.clone(),
+            "secret".to_string(),
+            payload.clone(),
+            false,
+        )
+        .await;
+    let requests = server.requests();
+    // stop retrying after the first success.
+    assert_eq!(requests.len(), 3);
+    for request in requests {
+        assert_eq!(request.body, payload);
+        assert_eq!(
+            request.signature,
+            Some(sign_payload(b"secret", payload.as_slice()))
+        );
+    }
+}
+
+#[stest::test]
+async fn test_deliver_give_up() {
+    let server = MockWebhookServer::start(vec![500]);
+    let dispatcher = WebhookDispatcher::new(&test_webhook_config(2)).unwrap();
+    dispatcher
+        .deliver(
+            server.url.clone(),
+            "secret".to_string(),
+            b"{}".to_vec(),
+            false,
+        )
+        .await;
+    // the first attempt and 2 retries.
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[stest::test]
+async fn test_deliver_reject_private_target() {
+    let server = MockWebhookServer::start(vec![200]);
+    let mut config = test_webhook_config(0);
+    config.allow_private_targets = Some(false);
+    let dispatcher = WebhookDispatcher::new(&config).unwrap();
+    dispatcher
+        .clone()
+        .deliver(
+            server.url.clone(),
+            "secret".to_string(),
+            b"{}".to_vec(),
+            false,
+        )
+        .await;
+    assert!(server.requests().is_empty());
+    // the webhook registered by config is trusted.
+    dispatcher
+        .deliver(
+            server.url.clone(),
+            "secret".to_string(),
+            b"{}".to_vec(),
+            true,
+        )
+        .await;
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[stest::test]
+async fn test_webhook_service() -> Result<()> {
+    let mut config = NodeConfig::random_for_test();
+    config.webhook = test_webhook_config(3);
+    let (txpool, _storage, config, _, registry) =
+        test_helper::start_txpool_with_config(config).await;
+    let webhook_service = registry.register::<WebhookService>().await?;
+
+    // the first delivery fails and is retried.
+    let server = MockWebhookServer::start(vec![500, 200]);
+    let filter = WebhookFilter {
+        senders: vec![association_address()],
+        txn_hashes: vec![],
+    };
+    let registration = webhook_service
+        .register_webhook(server.url.clone(), filter.clone())
+        .await?;
+    assert_eq!(webhook_service.list_webhooks().await?.len(), 1);
+
+    let txn = peer_to_peer_txn_sent_as_association(
+        AccountAddress::random(),
+        None,
+        0,
+        10000,
+        config.net().time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+        config.net(),
+    );
+    let txn_hash = txn.id();
+    txpool.add_txns(vec![txn]).pop().unwrap()?;
+
+    let requests = server.wait_requests(2).await;
+    assert_eq!(requests.len(), 2);
+    for request in requests {
+        assert_eq!(
+            request.signature,
+            Some(sign_payload(
+                registration.secret.as_bytes(),
+                request.body.as_slice()
+            ))
+        );
+        let payload: WebhookPayload = serde_json::from_slice(request.body.as_slice())?;
+        assert_eq!(payload.webhook_id, registration.id);
+        assert_eq!(payload.transaction_hash, txn_hash);
+        assert_eq!(payload.status, TxnLifecycleStatus::Pending);
+    }
+
+    // the registrations are capped.
+    webhook_service
+        .register_webhook(server.url.clone(), filter.clone())
+        .await?;
+    assert!(webhook_service
+        .register_webhook(server.url.clone(), filter.clone())
+        .await
+        .is_err());
+    webhook_service.remove_webhook(registration.id).await?;
+    assert_eq!(webhook_service.list_webhooks().await?.len(), 1);
+
+    registry.shutdown_system().await?;
+    Ok(())
+}